@@ -4,6 +4,7 @@ use tracing::debug;
 
 use crate::error::AIError;
 use crate::provider::AIClient;
+use crate::stream::ChatStream;
 use crate::types::{
     ChatContext, ChatInput, ChatMessage, ChatResponse, MessageRole,
 };
@@ -22,6 +23,29 @@ impl ChatService {
 
     /// Process a chat message and return a response.
     pub async fn chat(&self, input: ChatInput) -> Result<ChatResponse, AIError> {
+        let messages = self.build_messages(input);
+
+        debug!("Sending chat with {} messages", messages.len());
+
+        let (response_message, usage) = self.client.chat(messages).await?;
+
+        Ok(ChatResponse {
+            message: response_message,
+            usage,
+        })
+    }
+
+    /// Process a chat message and stream the response as it is generated.
+    pub async fn chat_stream(&self, input: ChatInput) -> Result<ChatStream, AIError> {
+        let messages = self.build_messages(input);
+
+        debug!("Streaming chat with {} messages", messages.len());
+
+        self.client.chat_stream(messages).await
+    }
+
+    /// Build the conversation sent to the provider: context, history, then the new message.
+    fn build_messages(&self, input: ChatInput) -> Vec<ChatMessage> {
         let mut messages = Vec::new();
 
         // Add system message with context
@@ -44,14 +68,7 @@ impl ChatService {
             timestamp: chrono::Utc::now(),
         });
 
-        debug!("Sending chat with {} messages", messages.len());
-
-        let (response_message, usage) = self.client.chat(messages).await?;
-
-        Ok(ChatResponse {
-            message: response_message,
-            usage,
-        })
+        messages
     }
 
     /// Build the system message with context.
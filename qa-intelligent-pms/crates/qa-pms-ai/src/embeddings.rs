@@ -0,0 +1,71 @@
+//! Text embeddings for semantic search.
+
+use tracing::debug;
+
+use crate::error::AIError;
+use crate::provider::AIClient;
+
+/// Most texts sent to the provider in one embedding request.
+const BATCH_SIZE: usize = 64;
+
+/// Longest text embedded, in characters; longer texts are truncated.
+const MAX_INPUT_CHARS: usize = 8_000;
+
+/// Service that turns texts into embedding vectors with the configured
+/// provider.
+pub struct EmbeddingService {
+    client: AIClient,
+}
+
+impl EmbeddingService {
+    /// Create a new embedding service.
+    #[must_use]
+    pub const fn new(client: AIClient) -> Self {
+        Self { client }
+    }
+
+    /// Embedding model in use, or `None` when the provider has none.
+    #[must_use]
+    pub fn model(&self) -> Option<&str> {
+        self.client.embedding_model()
+    }
+
+    /// Embed documents in batches, in input order.
+    pub async fn embed_documents(&self, texts: &[String]) -> Result<Vec<Vec<f32>>, AIError> {
+        let mut vectors = Vec::with_capacity(texts.len());
+        for batch in texts.chunks(BATCH_SIZE) {
+            let inputs: Vec<String> = batch.iter().map(|t| truncate(t)).collect();
+            debug!(count = inputs.len(), "Embedding documents");
+            vectors.extend(self.client.embed(&inputs).await?);
+        }
+        Ok(vectors)
+    }
+
+    /// Embed a search query.
+    pub async fn embed_query(&self, query: &str) -> Result<Vec<f32>, AIError> {
+        self.client
+            .embed(&[truncate(query)])
+            .await?
+            .pop()
+            .ok_or_else(|| AIError::ParseError("No embedding returned".into()))
+    }
+}
+
+fn truncate(text: &str) -> String {
+    match text.char_indices().nth(MAX_INPUT_CHARS) {
+        Some((end, _)) => text[..end].to_string(),
+        None => text.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_truncate_keeps_char_boundaries() {
+        assert_eq!(truncate("login"), "login");
+        let long = "é".repeat(MAX_INPUT_CHARS + 10);
+        assert_eq!(truncate(&long).chars().count(), MAX_INPUT_CHARS);
+    }
+}
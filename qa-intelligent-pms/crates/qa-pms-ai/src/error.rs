@@ -25,6 +25,15 @@ pub enum AIError {
     #[error("Rate limit exceeded. Please wait and try again.")]
     RateLimited,
 
+    /// Monthly token budget used up
+    #[error("Monthly AI token budget exhausted ({used} of {limit} tokens used)")]
+    BudgetExceeded {
+        /// Tokens used this month
+        used: u64,
+        /// Hard monthly limit
+        limit: u64,
+    },
+
     /// Model not available
     #[error("Model not available: {0}")]
     ModelNotAvailable(String),
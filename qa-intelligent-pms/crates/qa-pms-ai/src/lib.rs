@@ -5,8 +5,10 @@
 //! This crate provides:
 //! - AI provider abstraction (Anthropic, `OpenAI`, Deepseek, z.ai, Custom)
 //! - Semantic search enhancement
+//! - Text embeddings for nearest-neighbor search
 //! - Gherkin test suggestions
 //! - Mini-chatbot functionality
+//! - Token usage tracking with monthly budgets
 //!
 //! ## Features
 //!
@@ -18,13 +20,19 @@
 pub mod types;
 pub mod error;
 pub mod provider;
+pub mod stream;
 pub mod chat;
 pub mod semantic;
+pub mod embeddings;
 pub mod gherkin;
+pub mod usage;
 
 pub use types::*;
 pub use error::AIError;
 pub use provider::{AIProvider, AIClient};
+pub use stream::ChatStream;
 pub use chat::ChatService;
 pub use semantic::SemanticSearchService;
+pub use embeddings::EmbeddingService;
 pub use gherkin::GherkinAnalyzer;
+pub use usage::{AIFeature, BudgetStatus, TokenBudget, UsageReport, UsageScope, UsageTracker};
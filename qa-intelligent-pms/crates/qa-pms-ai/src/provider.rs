@@ -5,16 +5,33 @@
 use std::time::{Duration, Instant};
 
 use async_trait::async_trait;
+use futures::StreamExt;
+use qa_pms_core::retry::{self, RetryPolicy};
 use reqwest::Client;
 use secrecy::{ExposeSecret, SecretString};
 use serde::{Deserialize, Serialize};
 use tracing::{debug, info, warn};
 
 use crate::error::AIError;
+use crate::stream::{ndjson_stream, sse_stream, ChatStream, SseEvent};
 use crate::types::{
-    ChatMessage, ConnectionTestResult, MessageRole, ModelInfo, ProviderModels, ProviderType,
-    TokenUsage,
+    ChatChunk, ChatMessage, ConnectionTestResult, MessageRole, ModelInfo, ProviderModels,
+    ProviderType, TokenUsage,
 };
+use crate::usage::{AIFeature, UsageScope, UsageTracker};
+
+/// Upper bound for a streamed response, which may run far longer than a
+/// buffered one before the last token arrives.
+const STREAM_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Map an unsuccessful provider response to an error.
+fn status_error(status: reqwest::StatusCode, body: &str) -> AIError {
+    match status.as_u16() {
+        401 => AIError::InvalidApiKey("Invalid API key".into()),
+        429 => AIError::RateLimited,
+        _ => AIError::RequestFailed(format!("{status}: {body}")),
+    }
+}
 
 /// Trait for AI providers.
 #[async_trait]
@@ -34,37 +51,121 @@ pub trait AIProvider: Send + Sync {
         messages: Vec<ChatMessage>,
         model: &str,
     ) -> Result<(ChatMessage, Option<TokenUsage>), AIError>;
+
+    /// Send a chat completion request and stream the response as it is generated.
+    ///
+    /// The default sends a regular request and yields the whole response as
+    /// one chunk, for providers without streaming.
+    async fn chat_completion_stream(
+        &self,
+        messages: Vec<ChatMessage>,
+        model: &str,
+    ) -> Result<ChatStream, AIError> {
+        let (message, usage) = self.chat_completion(messages, model).await?;
+        let chunk = ChatChunk {
+            delta: message.content,
+            usage,
+        };
+        Ok(Box::pin(futures::stream::once(async move { Ok(chunk) })))
+    }
+
+    /// Models the provider can serve right now.
+    ///
+    /// The default returns the built-in list; providers that can be asked
+    /// override it.
+    async fn list_models(&self) -> Result<Vec<ModelInfo>, AIError> {
+        Ok(self.available_models())
+    }
+
+    /// Embedding model used when none is configured, if the provider has one.
+    fn default_embedding_model(&self) -> Option<&'static str> {
+        None
+    }
+
+    /// Embed each input as a vector, in input order.
+    ///
+    /// The default reports embeddings as unsupported.
+    async fn embed(&self, inputs: &[String], model: &str) -> Result<Vec<Vec<f32>>, AIError> {
+        let _ = (inputs, model);
+        Err(AIError::UnsupportedProvider(format!(
+            "{:?} does not offer embeddings",
+            self.provider_type()
+        )))
+    }
 }
 
 /// AI client that wraps a provider.
 pub struct AIClient {
     provider: Box<dyn AIProvider>,
     model: String,
+    embedding_model: Option<String>,
+    usage: Option<UsageScope>,
 }
 
 impl AIClient {
     /// Create a new AI client.
     #[must_use] 
     pub fn new(provider: Box<dyn AIProvider>, model: String) -> Self {
-        Self { provider, model }
+        Self {
+            provider,
+            model,
+            embedding_model: None,
+            usage: None,
+        }
+    }
+
+    /// Use a specific embedding model instead of the provider default.
+    #[must_use]
+    pub fn with_embedding_model(mut self, model: String) -> Self {
+        self.embedding_model = Some(model);
+        self
+    }
+
+    /// Record token usage for `user_id` and enforce their budget.
+    #[must_use]
+    pub fn with_usage(mut self, tracker: UsageTracker, user_id: String, feature: AIFeature) -> Self {
+        self.usage = Some(UsageScope {
+            tracker,
+            user_id,
+            feature,
+        });
+        self
+    }
+
+    async fn check_budget(&self) -> Result<(), AIError> {
+        if let Some(scope) = &self.usage {
+            scope.tracker.check(&scope.user_id).await?;
+        }
+        Ok(())
     }
 
     /// Create a client from configuration.
+    ///
+    /// Provider requests that fail with a network error or a transient
+    /// status are retried with `retry`.
     pub fn from_config(
         provider_type: ProviderType,
         api_key: SecretString,
         model: String,
         custom_base_url: Option<String>,
+        retry: RetryPolicy,
     ) -> Result<Self, AIError> {
         let provider: Box<dyn AIProvider> = match provider_type {
-            ProviderType::OpenAi => Box::new(OpenAIProvider::new(api_key)),
-            ProviderType::Anthropic => Box::new(AnthropicProvider::new(api_key)),
-            ProviderType::Deepseek => Box::new(DeepseekProvider::new(api_key)),
-            ProviderType::Zai => Box::new(ZaiProvider::new(api_key)),
+            ProviderType::OpenAi => Box::new(OpenAIProvider::new(api_key).with_retry_policy(retry)),
+            ProviderType::Anthropic => Box::new(AnthropicProvider::new(api_key).with_retry_policy(retry)),
+            ProviderType::Deepseek => Box::new(DeepseekProvider::new(api_key).with_retry_policy(retry)),
+            ProviderType::Zai => Box::new(ZaiProvider::new(api_key).with_retry_policy(retry)),
             ProviderType::Custom => {
                 let base_url = custom_base_url
                     .ok_or_else(|| AIError::InvalidApiKey("Custom provider requires base URL".into()))?;
-                Box::new(CustomProvider::new(api_key, base_url))
+                Box::new(CustomProvider::new(api_key, base_url).with_retry_policy(retry))
+            }
+            ProviderType::Ollama => Box::new(OllamaProvider::new(custom_base_url).with_retry_policy(retry)),
+            ProviderType::AzureOpenAi => {
+                let endpoint = custom_base_url.ok_or_else(|| {
+                    AIError::InvalidApiKey("Azure OpenAI requires the resource endpoint URL".into())
+                })?;
+                Box::new(AzureOpenAIProvider::new(api_key, &endpoint, model.clone()).with_retry_policy(retry))
             }
         };
 
@@ -81,7 +182,33 @@ impl AIClient {
         &self,
         messages: Vec<ChatMessage>,
     ) -> Result<(ChatMessage, Option<TokenUsage>), AIError> {
-        self.provider.chat_completion(messages, &self.model).await
+        self.check_budget().await?;
+        let (message, usage) = self.provider.chat_completion(messages, &self.model).await?;
+        if let (Some(scope), Some(usage)) = (&self.usage, &usage) {
+            scope.record(&self.provider_type().to_string(), usage).await;
+        }
+        Ok((message, usage))
+    }
+
+    /// Send a chat completion and stream the response.
+    ///
+    /// Usage is recorded when the provider reports it in the stream.
+    pub async fn chat_stream(&self, messages: Vec<ChatMessage>) -> Result<ChatStream, AIError> {
+        self.check_budget().await?;
+        let stream = self
+            .provider
+            .chat_completion_stream(messages, &self.model)
+            .await?;
+        let Some(scope) = self.usage.clone() else {
+            return Ok(stream);
+        };
+        let provider = self.provider_type().to_string();
+        Ok(Box::pin(stream.inspect(move |chunk| {
+            if let Ok(ChatChunk { usage: Some(usage), .. }) = chunk {
+                let (scope, provider, usage) = (scope.clone(), provider.clone(), usage.clone());
+                tokio::spawn(async move { scope.record(&provider, &usage).await });
+            }
+        })))
     }
 
     /// Get the provider type.
@@ -90,11 +217,39 @@ impl AIClient {
         self.provider.provider_type()
     }
 
+    /// List the models the provider can serve.
+    pub async fn list_models(&self) -> Result<Vec<ModelInfo>, AIError> {
+        self.provider.list_models().await
+    }
+
     /// Get the model.
     #[must_use] 
     pub fn model(&self) -> &str {
         &self.model
     }
+
+    /// Embedding model in use: the configured one, else the provider default.
+    #[must_use]
+    pub fn embedding_model(&self) -> Option<&str> {
+        self.embedding_model
+            .as_deref()
+            .or_else(|| self.provider.default_embedding_model())
+    }
+
+    /// Embed texts with the embedding model.
+    ///
+    /// Refused once the budget is used up; providers do not report embedding
+    /// usage here, so it is not recorded.
+    pub async fn embed(&self, inputs: &[String]) -> Result<Vec<Vec<f32>>, AIError> {
+        let model = self.embedding_model().ok_or_else(|| {
+            AIError::UnsupportedProvider(format!(
+                "No embedding model configured for {:?}",
+                self.provider_type()
+            ))
+        })?;
+        self.check_budget().await?;
+        self.provider.embed(inputs, model).await
+    }
 }
 
 /// Get available models for all providers.
@@ -126,6 +281,14 @@ pub fn get_all_provider_models() -> Vec<ProviderModels> {
                 supports_streaming: true,
             }],
         },
+        ProviderModels {
+            provider: ProviderType::Ollama,
+            models: OllamaProvider::default_models(),
+        },
+        ProviderModels {
+            provider: ProviderType::AzureOpenAi,
+            models: AzureOpenAIProvider::default_models(),
+        },
     ]
 }
 
@@ -134,6 +297,7 @@ pub fn get_all_provider_models() -> Vec<ProviderModels> {
 /// `OpenAI` API provider.
 pub struct OpenAIProvider {
     client: Client,
+    retry: RetryPolicy,
     api_key: SecretString,
     base_url: String,
 }
@@ -144,11 +308,19 @@ impl OpenAIProvider {
     pub fn new(api_key: SecretString) -> Self {
         Self {
             client: Client::new(),
+            retry: RetryPolicy::default(),
             api_key,
             base_url: "https://api.openai.com/v1".to_string(),
         }
     }
 
+    /// Retry failed requests with the given policy.
+    #[must_use]
+    pub const fn with_retry_policy(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
     /// Get default models.
     #[must_use] 
     pub fn default_models() -> Vec<ModelInfo> {
@@ -186,6 +358,15 @@ struct OpenAIChatRequest {
     model: String,
     messages: Vec<OpenAIMessage>,
     max_tokens: u32,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    stream: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stream_options: Option<OpenAIStreamOptions>,
+}
+
+#[derive(Serialize)]
+struct OpenAIStreamOptions {
+    include_usage: bool,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -212,6 +393,99 @@ struct OpenAIUsage {
     total_tokens: u32,
 }
 
+impl From<OpenAIUsage> for TokenUsage {
+    fn from(u: OpenAIUsage) -> Self {
+        Self {
+            prompt_tokens: u.prompt_tokens,
+            completion_tokens: u.completion_tokens,
+            total_tokens: u.total_tokens,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct OpenAIStreamResponse {
+    #[serde(default)]
+    choices: Vec<OpenAIStreamChoice>,
+    usage: Option<OpenAIUsage>,
+}
+
+#[derive(Deserialize)]
+struct OpenAIStreamChoice {
+    delta: OpenAIDelta,
+}
+
+#[derive(Deserialize)]
+struct OpenAIDelta {
+    content: Option<String>,
+}
+
+#[derive(Serialize)]
+struct OpenAIEmbeddingRequest<'a> {
+    model: &'a str,
+    input: &'a [String],
+}
+
+#[derive(Deserialize)]
+struct OpenAIEmbeddingResponse {
+    data: Vec<OpenAIEmbedding>,
+}
+
+#[derive(Deserialize)]
+struct OpenAIEmbedding {
+    index: usize,
+    embedding: Vec<f32>,
+}
+
+/// Order embeddings by input index, checking one came back per input.
+fn openai_embeddings(
+    response: OpenAIEmbeddingResponse,
+    expected: usize,
+) -> Result<Vec<Vec<f32>>, AIError> {
+    let mut data = response.data;
+    data.sort_by_key(|e| e.index);
+    if data.len() != expected || data.iter().enumerate().any(|(i, e)| e.index != i) {
+        return Err(AIError::ParseError(format!(
+            "Expected {expected} embeddings, got {}",
+            data.len()
+        )));
+    }
+    Ok(data.into_iter().map(|e| e.embedding).collect())
+}
+
+fn openai_messages(messages: &[ChatMessage]) -> Vec<OpenAIMessage> {
+    messages
+        .iter()
+        .map(|m| OpenAIMessage {
+            role: match m.role {
+                MessageRole::System => "system".to_string(),
+                MessageRole::User => "user".to_string(),
+                MessageRole::Assistant => "assistant".to_string(),
+            },
+            content: m.content.clone(),
+        })
+        .collect()
+}
+
+/// Parse one event of an OpenAI-compatible completion stream.
+fn parse_openai_event(event: &SseEvent) -> Result<Option<ChatChunk>, AIError> {
+    if event.data == "[DONE]" {
+        return Ok(None);
+    }
+    let response: OpenAIStreamResponse = serde_json::from_str(&event.data)
+        .map_err(|e| AIError::ParseError(format!("Invalid stream event: {e}")))?;
+    let delta: String = response
+        .choices
+        .into_iter()
+        .filter_map(|c| c.delta.content)
+        .collect();
+    let usage = response.usage.map(TokenUsage::from);
+    if delta.is_empty() && usage.is_none() {
+        return Ok(None);
+    }
+    Ok(Some(ChatChunk { delta, usage }))
+}
+
 #[async_trait]
 impl AIProvider for OpenAIProvider {
     fn provider_type(&self) -> ProviderType {
@@ -232,17 +506,21 @@ impl AIProvider for OpenAIProvider {
                 content: "Say 'OK' if you can hear me.".to_string(),
             }],
             max_tokens: 10,
+            stream: false,
+            stream_options: None,
         };
 
-        let response = self
-            .client
-            .post(format!("{}/chat/completions", self.base_url))
-            .header("Authorization", format!("Bearer {}", self.api_key.expose_secret()))
-            .header("Content-Type", "application/json")
-            .json(&request)
-            .timeout(Duration::from_secs(30))
-            .send()
-            .await?;
+        let response = retry::send(
+            &self.retry,
+            &self.base_url,
+            self.client
+                .post(format!("{}/chat/completions", self.base_url))
+                .header("Authorization", format!("Bearer {}", self.api_key.expose_secret()))
+                .header("Content-Type", "application/json")
+                .json(&request)
+                .timeout(Duration::from_secs(30)),
+        )
+        .await?;
 
         let elapsed = start.elapsed().as_millis() as u64;
 
@@ -280,35 +558,27 @@ impl AIProvider for OpenAIProvider {
         messages: Vec<ChatMessage>,
         model: &str,
     ) -> Result<(ChatMessage, Option<TokenUsage>), AIError> {
-        let openai_messages: Vec<OpenAIMessage> = messages
-            .iter()
-            .map(|m| OpenAIMessage {
-                role: match m.role {
-                    MessageRole::System => "system".to_string(),
-                    MessageRole::User => "user".to_string(),
-                    MessageRole::Assistant => "assistant".to_string(),
-                },
-                content: m.content.clone(),
-            })
-            .collect();
-
         let request = OpenAIChatRequest {
             model: model.to_string(),
-            messages: openai_messages,
+            messages: openai_messages(&messages),
             max_tokens: 2048,
+            stream: false,
+            stream_options: None,
         };
 
         debug!("Sending chat completion request to OpenAI");
 
-        let response = self
-            .client
-            .post(format!("{}/chat/completions", self.base_url))
-            .header("Authorization", format!("Bearer {}", self.api_key.expose_secret()))
-            .header("Content-Type", "application/json")
-            .json(&request)
-            .timeout(Duration::from_secs(60))
-            .send()
-            .await?;
+        let response = retry::send(
+            &self.retry,
+            &self.base_url,
+            self.client
+                .post(format!("{}/chat/completions", self.base_url))
+                .header("Authorization", format!("Bearer {}", self.api_key.expose_secret()))
+                .header("Content-Type", "application/json")
+                .json(&request)
+                .timeout(Duration::from_secs(60)),
+        )
+        .await?;
 
         if !response.status().is_success() {
             let status = response.status();
@@ -338,14 +608,82 @@ impl AIProvider for OpenAIProvider {
             timestamp: chrono::Utc::now(),
         };
 
-        let usage = chat_response.usage.map(|u| TokenUsage {
-            prompt_tokens: u.prompt_tokens,
-            completion_tokens: u.completion_tokens,
-            total_tokens: u.total_tokens,
-        });
+        let usage = chat_response.usage.map(TokenUsage::from);
 
         Ok((message, usage))
     }
+
+    async fn chat_completion_stream(
+        &self,
+        messages: Vec<ChatMessage>,
+        model: &str,
+    ) -> Result<ChatStream, AIError> {
+        let request = OpenAIChatRequest {
+            model: model.to_string(),
+            messages: openai_messages(&messages),
+            max_tokens: 2048,
+            stream: true,
+            stream_options: Some(OpenAIStreamOptions {
+                include_usage: true,
+            }),
+        };
+
+        debug!("Sending streaming chat completion request to OpenAI");
+
+        let response = retry::send(
+            &self.retry,
+            &self.base_url,
+            self.client
+                .post(format!("{}/chat/completions", self.base_url))
+                .header("Authorization", format!("Bearer {}", self.api_key.expose_secret()))
+                .header("Content-Type", "application/json")
+                .json(&request)
+                .timeout(STREAM_TIMEOUT),
+        )
+        .await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(status_error(status, &error_text));
+        }
+
+        Ok(sse_stream(response, parse_openai_event))
+    }
+
+    fn default_embedding_model(&self) -> Option<&'static str> {
+        Some("text-embedding-3-small")
+    }
+
+    async fn embed(&self, inputs: &[String], model: &str) -> Result<Vec<Vec<f32>>, AIError> {
+        if inputs.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        debug!(count = inputs.len(), "Sending embedding request to OpenAI");
+
+        let response = retry::send(
+            &self.retry,
+            &self.base_url,
+            self.client
+                .post(format!("{}/embeddings", self.base_url))
+                .header("Authorization", format!("Bearer {}", self.api_key.expose_secret()))
+                .json(&OpenAIEmbeddingRequest {
+                    model,
+                    input: inputs,
+                })
+                .timeout(Duration::from_secs(60)),
+        )
+        .await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(status_error(status, &error_text));
+        }
+
+        openai_embeddings(response.json().await?, inputs.len())
+    }
 }
 
 // ==================== Anthropic Provider ====================
@@ -353,6 +691,7 @@ impl AIProvider for OpenAIProvider {
 /// Anthropic (Claude) API provider.
 pub struct AnthropicProvider {
     client: Client,
+    retry: RetryPolicy,
     api_key: SecretString,
     base_url: String,
 }
@@ -363,11 +702,19 @@ impl AnthropicProvider {
     pub fn new(api_key: SecretString) -> Self {
         Self {
             client: Client::new(),
+            retry: RetryPolicy::default(),
             api_key,
             base_url: "https://api.anthropic.com/v1".to_string(),
         }
     }
 
+    /// Retry failed requests with the given policy.
+    #[must_use]
+    pub const fn with_retry_policy(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
     /// Get default models.
     #[must_use] 
     pub fn default_models() -> Vec<ModelInfo> {
@@ -399,6 +746,8 @@ struct AnthropicChatRequest {
     model: String,
     messages: Vec<AnthropicMessage>,
     max_tokens: u32,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    stream: bool,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -424,6 +773,99 @@ struct AnthropicUsage {
     output_tokens: u32,
 }
 
+/// Events of an Anthropic message stream that carry text or usage.
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum AnthropicStreamEvent {
+    MessageStart { message: AnthropicStreamMessage },
+    ContentBlockDelta { delta: AnthropicDelta },
+    MessageDelta { usage: AnthropicOutputUsage },
+    Error { error: AnthropicStreamError },
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Deserialize)]
+struct AnthropicStreamMessage {
+    usage: AnthropicInputUsage,
+}
+
+#[derive(Deserialize)]
+struct AnthropicInputUsage {
+    input_tokens: u32,
+}
+
+#[derive(Deserialize)]
+struct AnthropicOutputUsage {
+    output_tokens: u32,
+}
+
+#[derive(Deserialize)]
+struct AnthropicDelta {
+    text: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct AnthropicStreamError {
+    #[serde(rename = "type")]
+    kind: String,
+    message: String,
+}
+
+/// Anthropic reports input tokens when the message starts and output tokens
+/// when it ends, so the usage is assembled across events.
+#[derive(Default)]
+struct AnthropicStreamParser {
+    input_tokens: u32,
+}
+
+impl AnthropicStreamParser {
+    fn parse(&mut self, event: &SseEvent) -> Result<Option<ChatChunk>, AIError> {
+        let parsed: AnthropicStreamEvent = serde_json::from_str(&event.data)
+            .map_err(|e| AIError::ParseError(format!("Invalid stream event: {e}")))?;
+        match parsed {
+            AnthropicStreamEvent::MessageStart { message } => {
+                self.input_tokens = message.usage.input_tokens;
+                Ok(None)
+            }
+            AnthropicStreamEvent::ContentBlockDelta { delta } => Ok(delta
+                .text
+                .filter(|text| !text.is_empty())
+                .map(|delta| ChatChunk { delta, usage: None })),
+            AnthropicStreamEvent::MessageDelta { usage } => Ok(Some(ChatChunk {
+                delta: String::new(),
+                usage: Some(TokenUsage {
+                    prompt_tokens: self.input_tokens,
+                    completion_tokens: usage.output_tokens,
+                    total_tokens: self.input_tokens + usage.output_tokens,
+                }),
+            })),
+            AnthropicStreamEvent::Error { error } => match error.kind.as_str() {
+                "rate_limit_error" => Err(AIError::RateLimited),
+                _ => Err(AIError::RequestFailed(error.message)),
+            },
+            AnthropicStreamEvent::Other => Ok(None),
+        }
+    }
+}
+
+fn anthropic_messages(messages: &[ChatMessage]) -> Vec<AnthropicMessage> {
+    // Anthropic doesn't support system messages in the messages array
+    // We need to handle them separately
+    messages
+        .iter()
+        .filter(|m| m.role != MessageRole::System)
+        .map(|m| AnthropicMessage {
+            role: match m.role {
+                MessageRole::User => "user".to_string(),
+                MessageRole::Assistant => "assistant".to_string(),
+                MessageRole::System => "user".to_string(), // Won't reach here
+            },
+            content: m.content.clone(),
+        })
+        .collect()
+}
+
 #[async_trait]
 impl AIProvider for AnthropicProvider {
     fn provider_type(&self) -> ProviderType {
@@ -444,18 +886,21 @@ impl AIProvider for AnthropicProvider {
                 content: "Say 'OK' if you can hear me.".to_string(),
             }],
             max_tokens: 10,
+            stream: false,
         };
 
-        let response = self
-            .client
-            .post(format!("{}/messages", self.base_url))
-            .header("x-api-key", self.api_key.expose_secret())
-            .header("anthropic-version", "2023-06-01")
-            .header("Content-Type", "application/json")
-            .json(&request)
-            .timeout(Duration::from_secs(30))
-            .send()
-            .await?;
+        let response = retry::send(
+            &self.retry,
+            &self.base_url,
+            self.client
+                .post(format!("{}/messages", self.base_url))
+                .header("x-api-key", self.api_key.expose_secret())
+                .header("anthropic-version", "2023-06-01")
+                .header("Content-Type", "application/json")
+                .json(&request)
+                .timeout(Duration::from_secs(30)),
+        )
+        .await?;
 
         let elapsed = start.elapsed().as_millis() as u64;
 
@@ -493,39 +938,27 @@ impl AIProvider for AnthropicProvider {
         messages: Vec<ChatMessage>,
         model: &str,
     ) -> Result<(ChatMessage, Option<TokenUsage>), AIError> {
-        // Anthropic doesn't support system messages in the messages array
-        // We need to handle them separately
-        let anthropic_messages: Vec<AnthropicMessage> = messages
-            .iter()
-            .filter(|m| m.role != MessageRole::System)
-            .map(|m| AnthropicMessage {
-                role: match m.role {
-                    MessageRole::User => "user".to_string(),
-                    MessageRole::Assistant => "assistant".to_string(),
-                    MessageRole::System => "user".to_string(), // Won't reach here
-                },
-                content: m.content.clone(),
-            })
-            .collect();
-
         let request = AnthropicChatRequest {
             model: model.to_string(),
-            messages: anthropic_messages,
+            messages: anthropic_messages(&messages),
             max_tokens: 2048,
+            stream: false,
         };
 
         debug!("Sending chat completion request to Anthropic");
 
-        let response = self
-            .client
-            .post(format!("{}/messages", self.base_url))
-            .header("x-api-key", self.api_key.expose_secret())
-            .header("anthropic-version", "2023-06-01")
-            .header("Content-Type", "application/json")
-            .json(&request)
-            .timeout(Duration::from_secs(60))
-            .send()
-            .await?;
+        let response = retry::send(
+            &self.retry,
+            &self.base_url,
+            self.client
+                .post(format!("{}/messages", self.base_url))
+                .header("x-api-key", self.api_key.expose_secret())
+                .header("anthropic-version", "2023-06-01")
+                .header("Content-Type", "application/json")
+                .json(&request)
+                .timeout(Duration::from_secs(60)),
+        )
+        .await?;
 
         if !response.status().is_success() {
             let status = response.status();
@@ -563,6 +996,43 @@ impl AIProvider for AnthropicProvider {
 
         Ok((message, usage))
     }
+
+    async fn chat_completion_stream(
+        &self,
+        messages: Vec<ChatMessage>,
+        model: &str,
+    ) -> Result<ChatStream, AIError> {
+        let request = AnthropicChatRequest {
+            model: model.to_string(),
+            messages: anthropic_messages(&messages),
+            max_tokens: 2048,
+            stream: true,
+        };
+
+        debug!("Sending streaming chat completion request to Anthropic");
+
+        let response = retry::send(
+            &self.retry,
+            &self.base_url,
+            self.client
+                .post(format!("{}/messages", self.base_url))
+                .header("x-api-key", self.api_key.expose_secret())
+                .header("anthropic-version", "2023-06-01")
+                .header("Content-Type", "application/json")
+                .json(&request)
+                .timeout(STREAM_TIMEOUT),
+        )
+        .await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(status_error(status, &error_text));
+        }
+
+        let mut parser = AnthropicStreamParser::default();
+        Ok(sse_stream(response, move |event| parser.parse(event)))
+    }
 }
 
 // ==================== Deepseek Provider ====================
@@ -581,6 +1051,13 @@ impl DeepseekProvider {
         Self { inner }
     }
 
+    /// Retry failed requests with the given policy.
+    #[must_use]
+    pub fn with_retry_policy(mut self, retry: RetryPolicy) -> Self {
+        self.inner = self.inner.with_retry_policy(retry);
+        self
+    }
+
     /// Get default models.
     #[must_use] 
     pub fn default_models() -> Vec<ModelInfo> {
@@ -622,6 +1099,14 @@ impl AIProvider for DeepseekProvider {
     ) -> Result<(ChatMessage, Option<TokenUsage>), AIError> {
         self.inner.chat_completion(messages, model).await
     }
+
+    async fn chat_completion_stream(
+        &self,
+        messages: Vec<ChatMessage>,
+        model: &str,
+    ) -> Result<ChatStream, AIError> {
+        self.inner.chat_completion_stream(messages, model).await
+    }
 }
 
 // ==================== z.ai Provider ====================
@@ -640,6 +1125,13 @@ impl ZaiProvider {
         Self { inner }
     }
 
+    /// Retry failed requests with the given policy.
+    #[must_use]
+    pub fn with_retry_policy(mut self, retry: RetryPolicy) -> Self {
+        self.inner = self.inner.with_retry_policy(retry);
+        self
+    }
+
     /// Get default models.
     #[must_use] 
     pub fn default_models() -> Vec<ModelInfo> {
@@ -673,6 +1165,14 @@ impl AIProvider for ZaiProvider {
     ) -> Result<(ChatMessage, Option<TokenUsage>), AIError> {
         self.inner.chat_completion(messages, model).await
     }
+
+    async fn chat_completion_stream(
+        &self,
+        messages: Vec<ChatMessage>,
+        model: &str,
+    ) -> Result<ChatStream, AIError> {
+        self.inner.chat_completion_stream(messages, model).await
+    }
 }
 
 // ==================== Custom Provider ====================
@@ -690,6 +1190,13 @@ impl CustomProvider {
         inner.base_url = base_url;
         Self { inner }
     }
+
+    /// Retry failed requests with the given policy.
+    #[must_use]
+    pub fn with_retry_policy(mut self, retry: RetryPolicy) -> Self {
+        self.inner = self.inner.with_retry_policy(retry);
+        self
+    }
 }
 
 #[async_trait]
@@ -718,4 +1225,712 @@ impl AIProvider for CustomProvider {
     ) -> Result<(ChatMessage, Option<TokenUsage>), AIError> {
         self.inner.chat_completion(messages, model).await
     }
+
+    async fn chat_completion_stream(
+        &self,
+        messages: Vec<ChatMessage>,
+        model: &str,
+    ) -> Result<ChatStream, AIError> {
+        self.inner.chat_completion_stream(messages, model).await
+    }
+
+    async fn embed(&self, inputs: &[String], model: &str) -> Result<Vec<Vec<f32>>, AIError> {
+        self.inner.embed(inputs, model).await
+    }
+}
+
+// ==================== Azure OpenAI Provider ====================
+
+/// API version used when the endpoint URL does not name one.
+pub const DEFAULT_AZURE_API_VERSION: &str = "2024-10-21";
+
+/// Azure `OpenAI` Service provider.
+///
+/// Azure serves each model from a named deployment, so the configured model
+/// ID is the deployment name and requests go to
+/// `{endpoint}/openai/deployments/{deployment}/...?api-version=...`. The
+/// endpoint is the resource URL (e.g. `https://acme.openai.azure.com`),
+/// optionally with an `api-version` query parameter.
+pub struct AzureOpenAIProvider {
+    client: Client,
+    retry: RetryPolicy,
+    api_key: SecretString,
+    endpoint: String,
+    api_version: String,
+    deployment: String,
+}
+
+impl AzureOpenAIProvider {
+    /// Create a provider for `deployment` on the resource at `endpoint`.
+    #[must_use]
+    pub fn new(api_key: SecretString, endpoint: &str, deployment: String) -> Self {
+        let (endpoint, api_version) = split_api_version(endpoint);
+        Self {
+            client: Client::new(),
+            retry: RetryPolicy::default(),
+            api_key,
+            endpoint,
+            api_version: api_version.unwrap_or_else(|| DEFAULT_AZURE_API_VERSION.to_string()),
+            deployment,
+        }
+    }
+
+    /// Retry failed requests with the given policy.
+    #[must_use]
+    pub const fn with_retry_policy(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Get default models.
+    ///
+    /// Azure deployments are named by their owner; these are the names
+    /// Azure suggests when deploying each model.
+    #[must_use]
+    pub fn default_models() -> Vec<ModelInfo> {
+        vec![
+            ModelInfo {
+                id: "gpt-4o".to_string(),
+                name: "GPT-4o deployment".to_string(),
+                context_window: 128000,
+                supports_streaming: true,
+            },
+            ModelInfo {
+                id: "gpt-4o-mini".to_string(),
+                name: "GPT-4o Mini deployment".to_string(),
+                context_window: 128000,
+                supports_streaming: true,
+            },
+        ]
+    }
+
+    fn url(&self, deployment: &str, operation: &str) -> String {
+        format!(
+            "{}/openai/deployments/{deployment}/{operation}?api-version={}",
+            self.endpoint, self.api_version
+        )
+    }
+
+    async fn post<T: Serialize + Sync>(
+        &self,
+        deployment: &str,
+        operation: &str,
+        body: &T,
+        timeout: Duration,
+    ) -> Result<reqwest::Response, AIError> {
+        let response = retry::send(
+            &self.retry,
+            &self.endpoint,
+            self.client
+                .post(self.url(deployment, operation))
+                .header("api-key", self.api_key.expose_secret())
+                .json(body)
+                .timeout(timeout),
+        )
+        .await?;
+        if response.status().is_success() {
+            return Ok(response);
+        }
+        let status = response.status();
+        let error_text = response.text().await.unwrap_or_default();
+        if status.as_u16() == 404 {
+            return Err(AIError::ModelNotAvailable(format!(
+                "No deployment named {deployment}"
+            )));
+        }
+        Err(status_error(status, &error_text))
+    }
+}
+
+/// Split an `api-version` query parameter off an endpoint URL.
+fn split_api_version(endpoint: &str) -> (String, Option<String>) {
+    let (base, query) = endpoint.split_once('?').unwrap_or((endpoint, ""));
+    let api_version = query
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(key, _)| *key == "api-version")
+        .map(|(_, value)| value.to_string())
+        .filter(|value| !value.is_empty());
+    let base = base.trim_end_matches('/');
+    let base = base.strip_suffix("/openai").unwrap_or(base);
+    (base.to_string(), api_version)
+}
+
+#[async_trait]
+impl AIProvider for AzureOpenAIProvider {
+    fn provider_type(&self) -> ProviderType {
+        ProviderType::AzureOpenAi
+    }
+
+    fn available_models(&self) -> Vec<ModelInfo> {
+        Self::default_models()
+    }
+
+    async fn test_connection(&self) -> Result<ConnectionTestResult, AIError> {
+        let start = Instant::now();
+        let request = OpenAIChatRequest {
+            model: self.deployment.clone(),
+            messages: vec![OpenAIMessage {
+                role: "user".to_string(),
+                content: "Say 'OK' if you can hear me.".to_string(),
+            }],
+            max_tokens: 10,
+            stream: false,
+            stream_options: None,
+        };
+
+        let result = self
+            .post(&self.deployment, "chat/completions", &request, Duration::from_secs(30))
+            .await;
+        let elapsed = start.elapsed().as_millis() as u64;
+
+        match result {
+            Ok(_) => {
+                info!("Azure OpenAI connection test successful");
+                Ok(ConnectionTestResult {
+                    success: true,
+                    message: "Connection successful".to_string(),
+                    response_time_ms: Some(elapsed),
+                    model: Some(self.deployment.clone()),
+                })
+            }
+            Err(e @ (AIError::InvalidApiKey(_) | AIError::RateLimited)) => Err(e),
+            Err(e) => {
+                warn!("Azure OpenAI connection test failed: {e}");
+                Ok(ConnectionTestResult {
+                    success: false,
+                    message: format!("Connection failed: {e}"),
+                    response_time_ms: Some(elapsed),
+                    model: None,
+                })
+            }
+        }
+    }
+
+    async fn chat_completion(
+        &self,
+        messages: Vec<ChatMessage>,
+        model: &str,
+    ) -> Result<(ChatMessage, Option<TokenUsage>), AIError> {
+        let request = OpenAIChatRequest {
+            model: model.to_string(),
+            messages: openai_messages(&messages),
+            max_tokens: 2048,
+            stream: false,
+            stream_options: None,
+        };
+
+        debug!(deployment = model, "Sending chat completion request to Azure OpenAI");
+
+        let response = self
+            .post(model, "chat/completions", &request, Duration::from_secs(60))
+            .await?;
+        let chat_response: OpenAIChatResponse = response.json().await?;
+
+        let assistant_message = chat_response
+            .choices
+            .first()
+            .ok_or_else(|| AIError::ParseError("No choices in response".into()))?;
+
+        let message = ChatMessage {
+            id: uuid::Uuid::new_v4(),
+            role: MessageRole::Assistant,
+            content: assistant_message.message.content.clone(),
+            timestamp: chrono::Utc::now(),
+        };
+
+        Ok((message, chat_response.usage.map(TokenUsage::from)))
+    }
+
+    async fn chat_completion_stream(
+        &self,
+        messages: Vec<ChatMessage>,
+        model: &str,
+    ) -> Result<ChatStream, AIError> {
+        let request = OpenAIChatRequest {
+            model: model.to_string(),
+            messages: openai_messages(&messages),
+            max_tokens: 2048,
+            stream: true,
+            stream_options: Some(OpenAIStreamOptions {
+                include_usage: true,
+            }),
+        };
+
+        debug!(deployment = model, "Sending streaming chat completion request to Azure OpenAI");
+
+        let response = self
+            .post(model, "chat/completions", &request, STREAM_TIMEOUT)
+            .await?;
+        Ok(sse_stream(response, parse_openai_event))
+    }
+
+    async fn embed(&self, inputs: &[String], model: &str) -> Result<Vec<Vec<f32>>, AIError> {
+        if inputs.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        debug!(count = inputs.len(), deployment = model, "Sending embedding request to Azure OpenAI");
+
+        let request = OpenAIEmbeddingRequest {
+            model,
+            input: inputs,
+        };
+        let response = self
+            .post(model, "embeddings", &request, Duration::from_secs(60))
+            .await?;
+        openai_embeddings(response.json().await?, inputs.len())
+    }
+}
+
+// ==================== Ollama Provider ====================
+
+/// Default address of a local Ollama server.
+pub const DEFAULT_OLLAMA_URL: &str = "http://localhost:11434";
+
+/// Ollama's default context length; `/api/tags` does not report it.
+const OLLAMA_CONTEXT_WINDOW: u32 = 4096;
+
+/// Local Ollama server provider.
+///
+/// Nothing leaves the machine running Ollama and no API key is needed.
+pub struct OllamaProvider {
+    client: Client,
+    retry: RetryPolicy,
+    base_url: String,
+}
+
+impl OllamaProvider {
+    /// Create a provider for the server at `base_url` (default
+    /// [`DEFAULT_OLLAMA_URL`]).
+    #[must_use]
+    pub fn new(base_url: Option<String>) -> Self {
+        let base_url = base_url
+            .filter(|url| !url.trim().is_empty())
+            .unwrap_or_else(|| DEFAULT_OLLAMA_URL.to_string());
+        Self {
+            client: Client::new(),
+            retry: RetryPolicy::default(),
+            base_url: base_url.trim_end_matches('/').to_string(),
+        }
+    }
+
+    /// Retry failed requests with the given policy.
+    #[must_use]
+    pub const fn with_retry_policy(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Get default models.
+    ///
+    /// Suggestions only; [`AIProvider::list_models`] reports what the server
+    /// has pulled.
+    #[must_use]
+    pub fn default_models() -> Vec<ModelInfo> {
+        [("llama3.1", "Llama 3.1"), ("mistral", "Mistral"), ("qwen2.5", "Qwen 2.5")]
+            .into_iter()
+            .map(|(id, name)| ollama_model(id, name))
+            .collect()
+    }
+
+    async fn tags(&self) -> Result<OllamaTagsResponse, AIError> {
+        let response = retry::send(
+            &self.retry,
+            &self.base_url,
+            self.client
+                .get(format!("{}/api/tags", self.base_url))
+                .timeout(Duration::from_secs(10)),
+        )
+        .await?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(status_error(status, &error_text));
+        }
+        Ok(response.json().await?)
+    }
+
+    async fn post_chat(
+        &self,
+        messages: &[ChatMessage],
+        model: &str,
+        stream: bool,
+        timeout: Duration,
+    ) -> Result<reqwest::Response, AIError> {
+        let request = OllamaChatRequest {
+            model,
+            messages: openai_messages(messages),
+            stream,
+        };
+        let response = retry::send(
+            &self.retry,
+            &self.base_url,
+            self.client
+                .post(format!("{}/api/chat", self.base_url))
+                .json(&request)
+                .timeout(timeout),
+        )
+        .await?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            if status.as_u16() == 404 {
+                return Err(AIError::ModelNotAvailable(format!(
+                    "{model} (pull it with `ollama pull {model}`)"
+                )));
+            }
+            return Err(status_error(status, &error_text));
+        }
+        Ok(response)
+    }
+}
+
+fn ollama_model(id: &str, name: &str) -> ModelInfo {
+    ModelInfo {
+        id: id.to_string(),
+        name: name.to_string(),
+        context_window: OLLAMA_CONTEXT_WINDOW,
+        supports_streaming: true,
+    }
+}
+
+#[derive(Serialize)]
+struct OllamaChatRequest<'a> {
+    model: &'a str,
+    messages: Vec<OpenAIMessage>,
+    stream: bool,
+}
+
+/// A whole response, or one line of a streamed one.
+#[derive(Deserialize)]
+struct OllamaChatResponse {
+    message: Option<OllamaMessage>,
+    #[serde(default)]
+    done: bool,
+    prompt_eval_count: Option<u32>,
+    eval_count: Option<u32>,
+    error: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct OllamaMessage {
+    content: String,
+}
+
+impl OllamaChatResponse {
+    /// Token counts, reported with the final message.
+    fn usage(&self) -> Option<TokenUsage> {
+        if !self.done {
+            return None;
+        }
+        let prompt_tokens = self.prompt_eval_count.unwrap_or(0);
+        let completion_tokens = self.eval_count.unwrap_or(0);
+        Some(TokenUsage {
+            prompt_tokens,
+            completion_tokens,
+            total_tokens: prompt_tokens + completion_tokens,
+        })
+    }
+}
+
+#[derive(Deserialize)]
+struct OllamaTagsResponse {
+    models: Vec<OllamaTag>,
+}
+
+#[derive(Deserialize)]
+struct OllamaTag {
+    name: String,
+}
+
+#[derive(Serialize)]
+struct OllamaEmbedRequest<'a> {
+    model: &'a str,
+    input: &'a [String],
+}
+
+#[derive(Deserialize)]
+struct OllamaEmbedResponse {
+    embeddings: Vec<Vec<f32>>,
+}
+
+fn parse_ollama_line(line: &str) -> Result<Option<ChatChunk>, AIError> {
+    let response: OllamaChatResponse = serde_json::from_str(line)
+        .map_err(|e| AIError::ParseError(format!("Invalid stream line: {e}")))?;
+    if let Some(error) = response.error {
+        return Err(AIError::RequestFailed(error));
+    }
+    let usage = response.usage();
+    let delta = response.message.map(|m| m.content).unwrap_or_default();
+    if delta.is_empty() && usage.is_none() {
+        return Ok(None);
+    }
+    Ok(Some(ChatChunk { delta, usage }))
+}
+
+#[async_trait]
+impl AIProvider for OllamaProvider {
+    fn provider_type(&self) -> ProviderType {
+        ProviderType::Ollama
+    }
+
+    fn available_models(&self) -> Vec<ModelInfo> {
+        Self::default_models()
+    }
+
+    async fn list_models(&self) -> Result<Vec<ModelInfo>, AIError> {
+        Ok(self
+            .tags()
+            .await?
+            .models
+            .into_iter()
+            .map(|tag| ollama_model(&tag.name, &tag.name))
+            .collect())
+    }
+
+    async fn test_connection(&self) -> Result<ConnectionTestResult, AIError> {
+        let start = Instant::now();
+        let tags = self.tags().await?;
+        let elapsed = start.elapsed().as_millis() as u64;
+
+        info!(models = tags.models.len(), "Ollama connection test successful");
+        Ok(ConnectionTestResult {
+            success: true,
+            message: if tags.models.is_empty() {
+                "Connected, but no models are pulled yet".to_string()
+            } else {
+                "Connection successful".to_string()
+            },
+            response_time_ms: Some(elapsed),
+            model: tags.models.into_iter().next().map(|tag| tag.name),
+        })
+    }
+
+    async fn chat_completion(
+        &self,
+        messages: Vec<ChatMessage>,
+        model: &str,
+    ) -> Result<(ChatMessage, Option<TokenUsage>), AIError> {
+        debug!("Sending chat request to Ollama");
+
+        // Local models can be slow, especially while loading
+        let response = self
+            .post_chat(&messages, model, false, Duration::from_secs(180))
+            .await?;
+        let chat_response: OllamaChatResponse = response.json().await?;
+        if let Some(error) = chat_response.error {
+            return Err(AIError::RequestFailed(error));
+        }
+
+        let usage = chat_response.usage();
+        let content = chat_response
+            .message
+            .ok_or_else(|| AIError::ParseError("No message in response".into()))?
+            .content;
+        let message = ChatMessage {
+            id: uuid::Uuid::new_v4(),
+            role: MessageRole::Assistant,
+            content,
+            timestamp: chrono::Utc::now(),
+        };
+
+        Ok((message, usage))
+    }
+
+    async fn chat_completion_stream(
+        &self,
+        messages: Vec<ChatMessage>,
+        model: &str,
+    ) -> Result<ChatStream, AIError> {
+        debug!("Sending streaming chat request to Ollama");
+
+        let response = self
+            .post_chat(&messages, model, true, STREAM_TIMEOUT)
+            .await?;
+        Ok(ndjson_stream(response, parse_ollama_line))
+    }
+
+    fn default_embedding_model(&self) -> Option<&'static str> {
+        Some("nomic-embed-text")
+    }
+
+    async fn embed(&self, inputs: &[String], model: &str) -> Result<Vec<Vec<f32>>, AIError> {
+        if inputs.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        debug!(count = inputs.len(), "Sending embedding request to Ollama");
+
+        let response = retry::send(
+            &self.retry,
+            &self.base_url,
+            self.client
+                .post(format!("{}/api/embed", self.base_url))
+                .json(&OllamaEmbedRequest { model, input: inputs })
+                .timeout(Duration::from_secs(120)),
+        )
+        .await?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(status_error(status, &error_text));
+        }
+
+        let embeddings = response.json::<OllamaEmbedResponse>().await?.embeddings;
+        if embeddings.len() != inputs.len() {
+            return Err(AIError::ParseError(format!(
+                "Expected {} embeddings, got {}",
+                inputs.len(),
+                embeddings.len()
+            )));
+        }
+        Ok(embeddings)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(data: &str) -> SseEvent {
+        SseEvent {
+            event: String::new(),
+            data: data.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_openai_stream_events() {
+        let delta = parse_openai_event(&event(
+            r#"{"choices":[{"index":0,"delta":{"content":"Hel"}}]}"#,
+        ))
+        .unwrap();
+        assert_eq!(delta.map(|c| c.delta).as_deref(), Some("Hel"));
+
+        // Role-only first chunk and the terminator carry nothing
+        let role = parse_openai_event(&event(r#"{"choices":[{"delta":{"role":"assistant"}}]}"#));
+        assert_eq!(role.unwrap(), None);
+        assert_eq!(parse_openai_event(&event("[DONE]")).unwrap(), None);
+
+        let usage = parse_openai_event(&event(
+            r#"{"choices":[],"usage":{"prompt_tokens":5,"completion_tokens":2,"total_tokens":7}}"#,
+        ))
+        .unwrap()
+        .and_then(|c| c.usage)
+        .unwrap();
+        assert_eq!(usage.total_tokens, 7);
+
+        assert!(parse_openai_event(&event("not json")).is_err());
+    }
+
+    #[test]
+    fn test_anthropic_stream_events() {
+        let mut parser = AnthropicStreamParser::default();
+        let events = [
+            r#"{"type":"message_start","message":{"id":"m","usage":{"input_tokens":12,"output_tokens":1}}}"#,
+            r#"{"type":"content_block_start","index":0,"content_block":{"type":"text","text":""}}"#,
+            r#"{"type":"ping"}"#,
+            r#"{"type":"content_block_delta","index":0,"delta":{"type":"text_delta","text":"Hi"}}"#,
+            r#"{"type":"message_delta","delta":{"stop_reason":"end_turn"},"usage":{"output_tokens":3}}"#,
+            r#"{"type":"message_stop"}"#,
+        ];
+        let chunks: Vec<ChatChunk> = events
+            .iter()
+            .filter_map(|data| parser.parse(&event(data)).unwrap())
+            .collect();
+
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[0].delta, "Hi");
+        assert_eq!(
+            chunks[1].usage,
+            Some(TokenUsage {
+                prompt_tokens: 12,
+                completion_tokens: 3,
+                total_tokens: 15,
+            })
+        );
+
+        let overloaded = parser.parse(&event(
+            r#"{"type":"error","error":{"type":"overloaded_error","message":"Overloaded"}}"#,
+        ));
+        assert!(matches!(overloaded, Err(AIError::RequestFailed(m)) if m == "Overloaded"));
+    }
+
+    #[test]
+    fn test_openai_embeddings_follow_input_order() {
+        let response: OpenAIEmbeddingResponse = serde_json::from_str(
+            r#"{"data":[{"index":1,"embedding":[0.5]},{"index":0,"embedding":[0.25,0.75]}]}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            openai_embeddings(response, 2).unwrap(),
+            vec![vec![0.25, 0.75], vec![0.5]]
+        );
+
+        let missing: OpenAIEmbeddingResponse =
+            serde_json::from_str(r#"{"data":[{"index":1,"embedding":[0.5]}]}"#).unwrap();
+        assert!(matches!(openai_embeddings(missing, 2), Err(AIError::ParseError(_))));
+    }
+
+    #[test]
+    fn test_ollama_stream_lines() {
+        let delta = parse_ollama_line(
+            r#"{"model":"llama3.1","message":{"role":"assistant","content":"Hi"},"done":false}"#,
+        )
+        .unwrap();
+        assert_eq!(delta.map(|c| c.delta).as_deref(), Some("Hi"));
+
+        let last = parse_ollama_line(
+            r#"{"model":"llama3.1","message":{"role":"assistant","content":""},"done":true,"prompt_eval_count":9,"eval_count":4}"#,
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(last.delta, "");
+        assert_eq!(
+            last.usage,
+            Some(TokenUsage {
+                prompt_tokens: 9,
+                completion_tokens: 4,
+                total_tokens: 13,
+            })
+        );
+
+        let error = parse_ollama_line(r#"{"error":"model 'x' not found"}"#);
+        assert!(matches!(error, Err(AIError::RequestFailed(m)) if m == "model 'x' not found"));
+    }
+
+    #[test]
+    fn test_ollama_base_url_defaults_to_localhost() {
+        assert_eq!(OllamaProvider::new(None).base_url, DEFAULT_OLLAMA_URL);
+        assert_eq!(
+            OllamaProvider::new(Some("http://gpu-box:11434/".into())).base_url,
+            "http://gpu-box:11434"
+        );
+        assert!(!ProviderType::Ollama.requires_api_key());
+    }
+
+    #[test]
+    fn test_azure_urls_use_deployment_and_api_version() {
+        let key = SecretString::new("k".repeat(32));
+        let provider = AzureOpenAIProvider::new(
+            key.clone(),
+            "https://acme.openai.azure.com/",
+            "chat-prod".to_string(),
+        );
+        assert_eq!(
+            provider.url("chat-prod", "chat/completions"),
+            "https://acme.openai.azure.com/openai/deployments/chat-prod/chat/completions?api-version=2024-10-21"
+        );
+
+        let pinned = AzureOpenAIProvider::new(
+            key,
+            "https://acme.openai.azure.com/openai?api-version=2024-06-01",
+            "chat-prod".to_string(),
+        );
+        assert_eq!(
+            pinned.url("embed", "embeddings"),
+            "https://acme.openai.azure.com/openai/deployments/embed/embeddings?api-version=2024-06-01"
+        );
+    }
 }
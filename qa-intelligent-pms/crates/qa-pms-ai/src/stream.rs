@@ -0,0 +1,198 @@
+//! Streaming chat responses.
+//!
+//! Most providers stream completions as server-sent events; Ollama streams
+//! newline-delimited JSON. `SseDecoder` and `LineDecoder` split the raw body
+//! into events or lines regardless of how the bytes are chunked on the wire;
+//! each provider turns those into `ChatChunk`s.
+
+use std::pin::Pin;
+
+use futures::{Stream, StreamExt};
+
+use crate::error::AIError;
+use crate::types::ChatChunk;
+
+/// Stream of response chunks, in order.
+pub type ChatStream = Pin<Box<dyn Stream<Item = Result<ChatChunk, AIError>> + Send>>;
+
+/// One server-sent event.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SseEvent {
+    /// Event name, empty when the server sent none
+    pub event: String,
+    /// Data lines joined with newlines
+    pub data: String,
+}
+
+/// Incremental server-sent event parser.
+#[derive(Debug, Default)]
+pub struct SseDecoder {
+    buffer: String,
+    pending: Vec<u8>,
+}
+
+/// Append `bytes` to `pending` and take the complete UTF-8 text from it.
+///
+/// An incomplete sequence at the end is kept until the rest of it arrives.
+fn take_text(pending: &mut Vec<u8>, bytes: &[u8]) -> String {
+    pending.extend_from_slice(bytes);
+    let valid = match std::str::from_utf8(pending) {
+        Ok(text) => text.len(),
+        Err(e) => e.valid_up_to(),
+    };
+    let text = String::from_utf8_lossy(&pending[..valid]).into_owned();
+    pending.drain(..valid);
+    text.replace("\r\n", "\n")
+}
+
+impl SseDecoder {
+    /// Add received bytes and return the events they complete.
+    pub fn feed(&mut self, bytes: &[u8]) -> Vec<SseEvent> {
+        let text = take_text(&mut self.pending, bytes);
+        self.buffer.push_str(&text);
+
+        let mut events = Vec::new();
+        while let Some(end) = self.buffer.find("\n\n") {
+            let block: String = self.buffer.drain(..end + 2).collect();
+            if let Some(event) = parse_block(&block) {
+                events.push(event);
+            }
+        }
+        events
+    }
+}
+
+fn parse_block(block: &str) -> Option<SseEvent> {
+    let mut event = SseEvent::default();
+    let mut data = Vec::new();
+    for line in block.lines() {
+        let (field, value) = line.split_once(':').unwrap_or((line, ""));
+        let value = value.strip_prefix(' ').unwrap_or(value);
+        match field {
+            "event" => event.event = value.to_string(),
+            "data" => data.push(value),
+            // Comments, ids and retry hints carry nothing for us
+            _ => {}
+        }
+    }
+    if data.is_empty() {
+        return None;
+    }
+    event.data = data.join("\n");
+    Some(event)
+}
+
+/// Incremental parser for newline-delimited bodies.
+#[derive(Debug, Default)]
+pub struct LineDecoder {
+    buffer: String,
+    pending: Vec<u8>,
+}
+
+impl LineDecoder {
+    /// Add received bytes and return the non-empty lines they complete.
+    pub fn feed(&mut self, bytes: &[u8]) -> Vec<String> {
+        let text = take_text(&mut self.pending, bytes);
+        self.buffer.push_str(&text);
+
+        let mut lines = Vec::new();
+        while let Some(end) = self.buffer.find('\n') {
+            let line: String = self.buffer.drain(..=end).collect();
+            let line = line.trim();
+            if !line.is_empty() {
+                lines.push(line.to_string());
+            }
+        }
+        lines
+    }
+}
+
+/// Turn a streaming HTTP response into chunks using a provider's event parser.
+///
+/// The parser returns `None` for events without text or usage.
+pub(crate) fn sse_stream<F>(response: reqwest::Response, mut parse: F) -> ChatStream
+where
+    F: FnMut(&SseEvent) -> Result<Option<ChatChunk>, AIError> + Send + 'static,
+{
+    let mut decoder = SseDecoder::default();
+    let chunks = response
+        .bytes_stream()
+        .map(move |bytes| match bytes {
+            Ok(bytes) => decoder
+                .feed(&bytes)
+                .iter()
+                .filter_map(|event| parse(event).transpose())
+                .collect::<Vec<_>>(),
+            Err(e) => vec![Err(AIError::from(e))],
+        })
+        .flat_map(futures::stream::iter);
+    Box::pin(chunks)
+}
+
+/// Turn a newline-delimited JSON response into chunks using a provider's
+/// line parser.
+///
+/// The parser returns `None` for lines without text or usage.
+pub(crate) fn ndjson_stream<F>(response: reqwest::Response, mut parse: F) -> ChatStream
+where
+    F: FnMut(&str) -> Result<Option<ChatChunk>, AIError> + Send + 'static,
+{
+    let mut decoder = LineDecoder::default();
+    let chunks = response
+        .bytes_stream()
+        .map(move |bytes| match bytes {
+            Ok(bytes) => decoder
+                .feed(&bytes)
+                .iter()
+                .filter_map(|line| parse(line).transpose())
+                .collect::<Vec<_>>(),
+            Err(e) => vec![Err(AIError::from(e))],
+        })
+        .flat_map(futures::stream::iter);
+    Box::pin(chunks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decoder_handles_split_events() {
+        let mut decoder = SseDecoder::default();
+        assert!(decoder.feed(b"event: ping\ndata: {\"a\"").is_empty());
+
+        let events = decoder.feed(b":1}\r\n\r\ndata: one\ndata: two\n\n: comment\n\n");
+        assert_eq!(
+            events,
+            vec![
+                SseEvent {
+                    event: "ping".to_string(),
+                    data: "{\"a\":1}".to_string(),
+                },
+                SseEvent {
+                    event: String::new(),
+                    data: "one\ntwo".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_decoder_keeps_split_utf8() {
+        let mut decoder = SseDecoder::default();
+        let bytes = "data: café\n\n".as_bytes();
+        let split = bytes.len() - 3;
+        assert!(decoder.feed(&bytes[..split]).is_empty());
+        let events = decoder.feed(&bytes[split..]);
+        assert_eq!(events[0].data, "café");
+    }
+
+    #[test]
+    fn test_line_decoder_handles_split_lines() {
+        let mut decoder = LineDecoder::default();
+        assert!(decoder.feed(b"{\"a\":").is_empty());
+        let lines = decoder.feed(b"1}\r\n\n{\"b\":2}\n{\"c\"");
+        assert_eq!(lines, vec!["{\"a\":1}", "{\"b\":2}"]);
+        assert_eq!(decoder.feed(b":3}\n"), vec!["{\"c\":3}"]);
+    }
+}
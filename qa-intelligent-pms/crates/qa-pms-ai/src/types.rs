@@ -19,6 +19,19 @@ pub enum ProviderType {
     Zai,
     /// Custom OpenAI-compatible endpoint
     Custom,
+    /// Local Ollama server
+    Ollama,
+    /// Azure `OpenAI` Service deployment
+    #[serde(rename = "azure_openai")]
+    AzureOpenAi,
+}
+
+impl ProviderType {
+    /// Whether the provider needs an API key; local servers do not.
+    #[must_use]
+    pub const fn requires_api_key(self) -> bool {
+        !matches!(self, Self::Ollama)
+    }
 }
 
 impl std::fmt::Display for ProviderType {
@@ -29,6 +42,8 @@ impl std::fmt::Display for ProviderType {
             Self::Deepseek => write!(f, "Deepseek"),
             Self::Zai => write!(f, "z.ai"),
             Self::Custom => write!(f, "Custom"),
+            Self::Ollama => write!(f, "Ollama"),
+            Self::AzureOpenAi => write!(f, "Azure OpenAI"),
         }
     }
 }
@@ -67,7 +82,7 @@ pub struct AIConfig {
     pub provider: ProviderType,
     /// Selected model ID
     pub model_id: String,
-    /// Custom base URL (for Custom provider)
+    /// Custom base URL (for Custom, Ollama and Azure `OpenAI` providers)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub custom_base_url: Option<String>,
     /// Last validated timestamp
@@ -221,7 +236,7 @@ pub struct ChatResponse {
 }
 
 /// Token usage information.
-#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct TokenUsage {
     /// Prompt tokens
@@ -232,6 +247,17 @@ pub struct TokenUsage {
     pub total_tokens: u32,
 }
 
+/// A piece of a streamed chat response.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ChatChunk {
+    /// Text to append to the response
+    pub delta: String,
+    /// Token usage, on the chunk where the provider reports it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub usage: Option<TokenUsage>,
+}
+
 /// Input for semantic search.
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
@@ -0,0 +1,439 @@
+//! Token usage tracking and monthly budgets.
+//!
+//! Token counts reported by the provider are added to `ai_token_usage`, one
+//! row per user, provider, feature and day. A user's month-to-date total is
+//! checked against the configured budget before each request: past the soft
+//! limit requests still run and a warning is logged, past the hard limit
+//! they fail with [`AIError::BudgetExceeded`].
+
+use std::collections::BTreeMap;
+
+use chrono::{Datelike, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool};
+use tracing::warn;
+use utoipa::ToSchema;
+
+use crate::error::AIError;
+use crate::types::TokenUsage;
+
+/// Feature an AI request was made for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AIFeature {
+    /// Mini-chatbot
+    Chat,
+    /// Generated test cases
+    TestGeneration,
+    /// Gherkin acceptance criteria analysis
+    Gherkin,
+    /// Semantic search (query expansion and embeddings)
+    SemanticSearch,
+    /// Knowledge base drafts from resolved errors
+    KnowledgeBase,
+}
+
+impl AIFeature {
+    /// Name stored in the usage table.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Chat => "chat",
+            Self::TestGeneration => "test_generation",
+            Self::Gherkin => "gherkin",
+            Self::SemanticSearch => "semantic_search",
+            Self::KnowledgeBase => "knowledge_base",
+        }
+    }
+}
+
+/// Monthly token limits per user; `None` means unlimited.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TokenBudget {
+    /// Tokens after which usage is flagged
+    pub soft_limit: Option<u64>,
+    /// Tokens after which requests are refused
+    pub hard_limit: Option<u64>,
+}
+
+/// Where a user's usage stands against the budget.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum BudgetStatus {
+    /// Below every limit
+    WithinBudget,
+    /// Soft limit reached; requests still run
+    SoftLimitReached,
+    /// Hard limit reached; requests are refused
+    HardLimitReached,
+}
+
+impl TokenBudget {
+    /// Status for `used` tokens this month.
+    #[must_use]
+    pub fn status(&self, used: u64) -> BudgetStatus {
+        if self.hard_limit.is_some_and(|limit| used >= limit) {
+            BudgetStatus::HardLimitReached
+        } else if self.soft_limit.is_some_and(|limit| used >= limit) {
+            BudgetStatus::SoftLimitReached
+        } else {
+            BudgetStatus::WithinBudget
+        }
+    }
+}
+
+/// Tokens used under one key of a breakdown.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageTotals {
+    /// Prompt tokens
+    pub prompt_tokens: u64,
+    /// Completion tokens
+    pub completion_tokens: u64,
+    /// Total tokens
+    pub total_tokens: u64,
+    /// Requests made
+    pub requests: u64,
+}
+
+impl UsageTotals {
+    fn add(&mut self, row: &UsageRow) {
+        self.prompt_tokens += to_u64(row.prompt_tokens);
+        self.completion_tokens += to_u64(row.completion_tokens);
+        self.total_tokens += to_u64(row.total_tokens);
+        self.requests += to_u64(row.requests);
+    }
+}
+
+/// Usage on one day.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DailyUsage {
+    /// Day (UTC)
+    pub date: NaiveDate,
+    /// Tokens used that day
+    #[serde(flatten)]
+    pub totals: UsageTotals,
+}
+
+/// Month-to-date usage against the budget.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BudgetReport {
+    /// Tokens used since the start of the month
+    pub used_this_month: u64,
+    /// Soft monthly limit
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub soft_limit: Option<u64>,
+    /// Hard monthly limit
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hard_limit: Option<u64>,
+    /// Where usage stands
+    pub status: BudgetStatus,
+}
+
+/// A user's usage over a date range.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageReport {
+    /// First day covered
+    pub from: NaiveDate,
+    /// Last day covered
+    pub to: NaiveDate,
+    /// Usage over the whole range
+    pub totals: UsageTotals,
+    /// Usage per feature (`chat`, `test_generation`, `gherkin`, `semantic_search`)
+    pub by_feature: BTreeMap<String, UsageTotals>,
+    /// Usage per provider
+    pub by_provider: BTreeMap<String, UsageTotals>,
+    /// Usage per day with any, oldest first
+    pub by_day: Vec<DailyUsage>,
+    /// Month-to-date budget status
+    pub budget: BudgetReport,
+}
+
+#[derive(Debug, Clone, FromRow)]
+struct UsageRow {
+    provider: String,
+    feature: String,
+    usage_date: NaiveDate,
+    prompt_tokens: i64,
+    completion_tokens: i64,
+    total_tokens: i64,
+    requests: i64,
+}
+
+fn to_u64(value: i64) -> u64 {
+    u64::try_from(value).unwrap_or(0)
+}
+
+/// First day of the month containing `day`.
+#[must_use]
+pub fn month_start(day: NaiveDate) -> NaiveDate {
+    day.with_day(1).unwrap_or(day)
+}
+
+/// Token usage store with budget enforcement.
+#[derive(Debug, Clone)]
+pub struct UsageTracker {
+    db: PgPool,
+    budget: TokenBudget,
+}
+
+impl UsageTracker {
+    /// Create a tracker over the given pool.
+    #[must_use]
+    pub const fn new(db: PgPool, budget: TokenBudget) -> Self {
+        Self { db, budget }
+    }
+
+    /// Configured budget.
+    #[must_use]
+    pub const fn budget(&self) -> TokenBudget {
+        self.budget
+    }
+
+    /// Add one request's usage to today's totals.
+    ///
+    /// # Errors
+    /// Returns an error if the database is unavailable.
+    pub async fn record(
+        &self,
+        user_id: &str,
+        provider: &str,
+        feature: AIFeature,
+        usage: &TokenUsage,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r"
+            INSERT INTO ai_token_usage
+                (user_id, provider, feature, usage_date,
+                 prompt_tokens, completion_tokens, total_tokens, requests)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, 1)
+            ON CONFLICT (user_id, provider, feature, usage_date) DO UPDATE SET
+                prompt_tokens = ai_token_usage.prompt_tokens + EXCLUDED.prompt_tokens,
+                completion_tokens = ai_token_usage.completion_tokens + EXCLUDED.completion_tokens,
+                total_tokens = ai_token_usage.total_tokens + EXCLUDED.total_tokens,
+                requests = ai_token_usage.requests + 1
+            ",
+        )
+        .bind(user_id)
+        .bind(provider)
+        .bind(feature.as_str())
+        .bind(Utc::now().date_naive())
+        .bind(i64::from(usage.prompt_tokens))
+        .bind(i64::from(usage.completion_tokens))
+        .bind(i64::from(usage.total_tokens))
+        .execute(&self.db)
+        .await?;
+        Ok(())
+    }
+
+    /// Tokens `user_id` used since the start of the current month.
+    ///
+    /// # Errors
+    /// Returns an error if the database is unavailable.
+    pub async fn used_this_month(&self, user_id: &str) -> Result<u64, sqlx::Error> {
+        let used: Option<i64> = sqlx::query_scalar(
+            "SELECT SUM(total_tokens)::BIGINT FROM ai_token_usage \
+             WHERE user_id = $1 AND usage_date >= $2",
+        )
+        .bind(user_id)
+        .bind(month_start(Utc::now().date_naive()))
+        .fetch_one(&self.db)
+        .await?;
+        Ok(used.map_or(0, to_u64))
+    }
+
+    /// Refuse a request once `user_id` has reached the hard limit.
+    ///
+    /// Usage that cannot be read does not block requests.
+    ///
+    /// # Errors
+    /// Returns [`AIError::BudgetExceeded`] at or past the hard limit.
+    pub async fn check(&self, user_id: &str) -> Result<BudgetStatus, AIError> {
+        if self.budget == TokenBudget::default() {
+            return Ok(BudgetStatus::WithinBudget);
+        }
+        let used = match self.used_this_month(user_id).await {
+            Ok(used) => used,
+            Err(e) => {
+                warn!(error = %e, "Could not read AI token usage; skipping budget check");
+                return Ok(BudgetStatus::WithinBudget);
+            }
+        };
+        match self.budget.status(used) {
+            BudgetStatus::HardLimitReached => Err(AIError::BudgetExceeded {
+                used,
+                limit: self.budget.hard_limit.unwrap_or(used),
+            }),
+            BudgetStatus::SoftLimitReached => {
+                warn!(user = user_id, used, "AI token soft limit reached");
+                Ok(BudgetStatus::SoftLimitReached)
+            }
+            BudgetStatus::WithinBudget => Ok(BudgetStatus::WithinBudget),
+        }
+    }
+
+    /// Usage of `user_id` from `from` to `to`, inclusive.
+    ///
+    /// # Errors
+    /// Returns an error if the database is unavailable.
+    pub async fn report(
+        &self,
+        user_id: &str,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<UsageReport, sqlx::Error> {
+        let rows: Vec<UsageRow> = sqlx::query_as(
+            r"
+            SELECT provider, feature, usage_date,
+                   prompt_tokens, completion_tokens, total_tokens, requests
+            FROM ai_token_usage
+            WHERE user_id = $1 AND usage_date BETWEEN $2 AND $3
+            ORDER BY usage_date
+            ",
+        )
+        .bind(user_id)
+        .bind(from)
+        .bind(to)
+        .fetch_all(&self.db)
+        .await?;
+        let used = self.used_this_month(user_id).await?;
+        Ok(build_report(&rows, from, to, used, self.budget))
+    }
+}
+
+fn build_report(
+    rows: &[UsageRow],
+    from: NaiveDate,
+    to: NaiveDate,
+    used_this_month: u64,
+    budget: TokenBudget,
+) -> UsageReport {
+    let mut totals = UsageTotals::default();
+    let mut by_feature: BTreeMap<String, UsageTotals> = BTreeMap::new();
+    let mut by_provider: BTreeMap<String, UsageTotals> = BTreeMap::new();
+    let mut by_day: BTreeMap<NaiveDate, UsageTotals> = BTreeMap::new();
+    for row in rows {
+        totals.add(row);
+        by_feature.entry(row.feature.clone()).or_default().add(row);
+        by_provider
+            .entry(row.provider.clone())
+            .or_default()
+            .add(row);
+        by_day.entry(row.usage_date).or_default().add(row);
+    }
+
+    UsageReport {
+        from,
+        to,
+        totals,
+        by_feature,
+        by_provider,
+        by_day: by_day
+            .into_iter()
+            .map(|(date, totals)| DailyUsage { date, totals })
+            .collect(),
+        budget: BudgetReport {
+            used_this_month,
+            soft_limit: budget.soft_limit,
+            hard_limit: budget.hard_limit,
+            status: budget.status(used_this_month),
+        },
+    }
+}
+
+/// Whose usage an [`crate::AIClient`] records, and for which feature.
+#[derive(Debug, Clone)]
+pub struct UsageScope {
+    /// Tracker to record into
+    pub tracker: UsageTracker,
+    /// User the requests are made for
+    pub user_id: String,
+    /// Feature the requests are made for
+    pub feature: AIFeature,
+}
+
+impl UsageScope {
+    /// Record usage, logging rather than failing when it cannot be stored.
+    pub async fn record(&self, provider: &str, usage: &TokenUsage) {
+        if let Err(e) = self
+            .tracker
+            .record(&self.user_id, provider, self.feature, usage)
+            .await
+        {
+            warn!(error = %e, "Failed to record AI token usage");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 3, d).unwrap()
+    }
+
+    fn row(provider: &str, feature: AIFeature, date: NaiveDate, total: i64) -> UsageRow {
+        UsageRow {
+            provider: provider.to_string(),
+            feature: feature.as_str().to_string(),
+            usage_date: date,
+            prompt_tokens: total / 2,
+            completion_tokens: total - total / 2,
+            total_tokens: total,
+            requests: 1,
+        }
+    }
+
+    #[test]
+    fn test_budget_status() {
+        let budget = TokenBudget {
+            soft_limit: Some(800),
+            hard_limit: Some(1000),
+        };
+        assert_eq!(budget.status(799), BudgetStatus::WithinBudget);
+        assert_eq!(budget.status(800), BudgetStatus::SoftLimitReached);
+        assert_eq!(budget.status(1000), BudgetStatus::HardLimitReached);
+        assert_eq!(
+            TokenBudget::default().status(u64::MAX),
+            BudgetStatus::WithinBudget
+        );
+    }
+
+    #[test]
+    fn test_report_breakdowns() {
+        let rows = vec![
+            row("openai", AIFeature::Chat, day(1), 100),
+            row("openai", AIFeature::Gherkin, day(1), 50),
+            row("anthropic", AIFeature::Chat, day(3), 30),
+        ];
+        let budget = TokenBudget {
+            soft_limit: Some(150),
+            hard_limit: None,
+        };
+
+        let report = build_report(&rows, day(1), day(5), 180, budget);
+
+        assert_eq!(report.totals.total_tokens, 180);
+        assert_eq!(report.totals.requests, 3);
+        assert_eq!(report.by_feature["chat"].total_tokens, 130);
+        assert_eq!(report.by_feature["gherkin"].total_tokens, 50);
+        assert_eq!(report.by_provider["openai"].total_tokens, 150);
+        assert_eq!(report.by_provider["anthropic"].requests, 1);
+        let days: Vec<_> = report
+            .by_day
+            .iter()
+            .map(|d| (d.date, d.totals.total_tokens))
+            .collect();
+        assert_eq!(days, vec![(day(1), 150), (day(3), 30)]);
+        assert_eq!(report.budget.status, BudgetStatus::SoftLimitReached);
+    }
+
+    #[test]
+    fn test_month_start() {
+        assert_eq!(month_start(day(17)), day(1));
+    }
+}
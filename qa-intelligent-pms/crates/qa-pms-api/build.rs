@@ -0,0 +1,79 @@
+//! Build script for the API server.
+//!
+//! Reruns when a migration is added, since `sqlx::migrate!` embeds the
+//! `migrations` directory at compile time.
+//!
+//! With the `grpc` feature enabled, generates tonic service stubs for the
+//! services described in `proto/qa_pms.proto`. Message types are written by
+//! hand with `prost` derives, so no `protoc` is required at build time.
+
+fn main() {
+    println!("cargo:rerun-if-changed=../../migrations");
+    #[cfg(feature = "grpc")]
+    grpc::generate();
+}
+
+#[cfg(feature = "grpc")]
+mod grpc {
+    use tonic_build::manual::{Builder, Method, Service};
+
+    const PACKAGE: &str = "qa_pms.v1";
+    const CODEC: &str = "tonic::codec::ProstCodec";
+
+    fn method(name: &str, route: &str, input: &str, output: &str) -> Method {
+        Method::builder()
+            .name(name)
+            .route_name(route)
+            .input_type(format!("crate::grpc::messages::{input}"))
+            .output_type(format!("crate::grpc::messages::{output}"))
+            .codec_path(CODEC)
+            .build()
+    }
+
+    pub fn generate() {
+        println!("cargo:rerun-if-changed=proto/qa_pms.proto");
+
+        let workflow_service = Service::builder()
+            .name("WorkflowService")
+            .package(PACKAGE)
+            .method(method("create_workflow", "CreateWorkflow", "CreateWorkflowRequest", "Workflow"))
+            .method(method("get_workflow", "GetWorkflow", "WorkflowIdRequest", "Workflow"))
+            .method(method(
+                "list_user_workflows",
+                "ListUserWorkflows",
+                "ListUserWorkflowsRequest",
+                "ListWorkflowsResponse",
+            ))
+            .method(method("complete_step", "CompleteStep", "StepActionRequest", "StepActionResponse"))
+            .method(method("skip_step", "SkipStep", "StepActionRequest", "StepActionResponse"))
+            .method(method("pause_workflow", "PauseWorkflow", "WorkflowIdRequest", "WorkflowStatusResponse"))
+            .method(method("resume_workflow", "ResumeWorkflow", "WorkflowIdRequest", "WorkflowStatusResponse"))
+            .method(method(
+                "complete_workflow",
+                "CompleteWorkflow",
+                "WorkflowIdRequest",
+                "WorkflowStatusResponse",
+            ))
+            .method(method("cancel_workflow", "CancelWorkflow", "WorkflowIdRequest", "WorkflowStatusResponse"))
+            .build();
+
+        let time_service = Service::builder()
+            .name("TimeTrackingService")
+            .package(PACKAGE)
+            .method(method("start_session", "StartSession", "StartSessionRequest", "TimeSession"))
+            .method(method("end_session", "EndSession", "SessionIdRequest", "TimeSession"))
+            .method(method("pause_session", "PauseSession", "SessionIdRequest", "TimeSession"))
+            .method(method("resume_session", "ResumeSession", "SessionIdRequest", "TimeSession"))
+            .method(method(
+                "get_workflow_time",
+                "GetWorkflowTime",
+                "WorkflowIdRequest",
+                "WorkflowTimeResponse",
+            ))
+            .build();
+
+        Builder::new()
+            .build_client(false)
+            .compile(&[workflow_service, time_service]);
+    }
+}
@@ -0,0 +1,452 @@
+//! API keys for machine clients.
+//!
+//! CI pipelines and scripts authenticate with an `X-Api-Key` header instead
+//! of signing in. A key belongs to the user who created it and acts as that
+//! user within its scopes, so deactivating the user stops the key too. Keys
+//! never reach admin routes or key management, so a leaked key cannot mint
+//! more.
+//!
+//! Keys look like `qpk_<prefix>_<secret>`. The prefix lets owners tell their
+//! keys apart; only a SHA-256 hash of the whole key is kept, and the key
+//! itself is shown once, when it is created or rotated. Each key has its own rate limit
+//! bucket, at the client rate unless the key sets its own.
+
+use argon2::password_hash::rand_core::{OsRng, RngCore};
+use axum::http::Method;
+use chrono::{DateTime, Utc};
+use qa_pms_core::{TenantId, UserId};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use sqlx::{FromRow, PgPool};
+use tracing::warn;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::auth::{is_under, required_role, CurrentUser, Role, GRPC_READS};
+
+/// Header carrying an API key.
+pub const API_KEY_HEADER: &str = "x-api-key";
+
+/// Marks a string as one of our keys.
+const KEY_MARKER: &str = "qpk";
+
+/// Random bytes in the lookup prefix and the secret.
+const PREFIX_BYTES: usize = 4;
+const SECRET_BYTES: usize = 24;
+
+/// Routes only signed-in users reach, whatever a key's scopes.
+const SIGNED_IN_ONLY: &[&str] = &["/api/v1/auth"];
+
+/// Routes a `workflows` key may change, over HTTP or gRPC.
+const WORKFLOW_PREFIXES: &[&str] = &[
+    "/api/v1/workflows",
+    "/qa_pms.v1.WorkflowService",
+    "/qa_pms.v1.TimeTrackingService",
+];
+
+/// Routes an `integrations` key may change.
+const INTEGRATION_PREFIXES: &[&str] = &[
+    "/api/v1/tickets",
+    "/api/v1/testmo",
+    "/api/v1/postman",
+    "/api/v1/splunk",
+];
+
+const COLUMNS: &str = "id, name, prefix, scopes, rate_limit_per_minute, created_at, \
+                       rotated_at, last_used_at, expires_at";
+
+/// What a key may do.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ApiKeyScope {
+    /// Read anything the owner can read
+    ReadOnly,
+    /// Also start, update and complete workflows
+    Workflows,
+    /// Also act on Jira, Testmo, Postman and Splunk
+    Integrations,
+}
+
+impl ApiKeyScope {
+    /// Stored name of the scope.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::ReadOnly => "read_only",
+            Self::Workflows => "workflows",
+            Self::Integrations => "integrations",
+        }
+    }
+
+    /// Parse a stored scope name.
+    #[must_use]
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "read_only" => Some(Self::ReadOnly),
+            "workflows" => Some(Self::Workflows),
+            "integrations" => Some(Self::Integrations),
+            _ => None,
+        }
+    }
+
+    /// Whether this scope covers `method` on `path`. Every scope reads.
+    #[must_use]
+    pub fn allows(self, method: &Method, path: &str) -> bool {
+        if matches!(*method, Method::GET | Method::HEAD | Method::OPTIONS) || GRPC_READS.contains(&path) {
+            return true;
+        }
+        let prefixes = match self {
+            Self::ReadOnly => return false,
+            Self::Workflows => WORKFLOW_PREFIXES,
+            Self::Integrations => INTEGRATION_PREFIXES,
+        };
+        prefixes.iter().any(|prefix| is_under(path, prefix))
+    }
+}
+
+/// A stored key, without its secret.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiKey {
+    /// Key ID
+    pub id: Uuid,
+    /// Name given by the owner, such as the pipeline using it
+    pub name: String,
+    /// Start of the key, to recognise it
+    pub prefix: String,
+    /// What the key may do
+    pub scopes: Vec<ApiKeyScope>,
+    /// Requests per minute, or the client rate when unset
+    pub rate_limit_per_minute: Option<u32>,
+    /// When the key was created
+    pub created_at: DateTime<Utc>,
+    /// When the secret was last replaced
+    pub rotated_at: Option<DateTime<Utc>>,
+    /// Last request made with the key, to the minute
+    pub last_used_at: Option<DateTime<Utc>>,
+    /// When the key stops being accepted
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, FromRow)]
+struct ApiKeyRow {
+    id: Uuid,
+    name: String,
+    prefix: String,
+    scopes: Vec<String>,
+    rate_limit_per_minute: Option<i32>,
+    created_at: DateTime<Utc>,
+    rotated_at: Option<DateTime<Utc>>,
+    last_used_at: Option<DateTime<Utc>>,
+    expires_at: Option<DateTime<Utc>>,
+}
+
+impl From<ApiKeyRow> for ApiKey {
+    fn from(row: ApiKeyRow) -> Self {
+        Self {
+            id: row.id,
+            name: row.name,
+            prefix: row.prefix,
+            // Unknown stored scopes grant nothing
+            scopes: row.scopes.iter().filter_map(|s| ApiKeyScope::parse(s)).collect(),
+            rate_limit_per_minute: row.rate_limit_per_minute.and_then(|n| u32::try_from(n).ok()),
+            created_at: row.created_at,
+            rotated_at: row.rotated_at,
+            last_used_at: row.last_used_at,
+            expires_at: row.expires_at,
+        }
+    }
+}
+
+/// A live key with its owner.
+#[derive(Debug, FromRow)]
+struct KeyOwnerRow {
+    id: Uuid,
+    scopes: Vec<String>,
+    rate_limit_per_minute: Option<i32>,
+    user_id: Uuid,
+    email: String,
+    role: String,
+    tenant_id: String,
+}
+
+/// New key settings.
+#[derive(Debug, Clone)]
+pub struct NewApiKey {
+    /// Name given by the owner
+    pub name: String,
+    /// What the key may do
+    pub scopes: Vec<ApiKeyScope>,
+    /// Requests per minute, or the client rate when unset
+    pub rate_limit_per_minute: Option<u32>,
+    /// When the key stops being accepted
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+/// The key a request authenticated with, in the request extensions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApiKeyIdentity {
+    /// Key ID
+    pub id: Uuid,
+    /// What the key may do
+    pub scopes: Vec<ApiKeyScope>,
+    /// Requests per minute, or the client rate when unset
+    pub rate_limit_per_minute: Option<u32>,
+}
+
+impl ApiKeyIdentity {
+    /// Whether the key may call `method` on `path`.
+    #[must_use]
+    pub fn allows(&self, method: &Method, path: &str) -> bool {
+        if SIGNED_IN_ONLY.iter().any(|prefix| is_under(path, prefix))
+            || required_role(path) == Some(Role::Admin)
+        {
+            return false;
+        }
+        self.scopes.iter().any(|scope| scope.allows(method, path))
+    }
+}
+
+/// A freshly generated key.
+struct GeneratedKey {
+    key: String,
+    prefix: String,
+    hash: String,
+}
+
+impl GeneratedKey {
+    fn new() -> Self {
+        let mut prefix = [0u8; PREFIX_BYTES];
+        let mut secret = [0u8; SECRET_BYTES];
+        OsRng.fill_bytes(&mut prefix);
+        OsRng.fill_bytes(&mut secret);
+        let prefix = hex::encode(prefix);
+        let key = format!("{KEY_MARKER}_{prefix}_{}", hex::encode(secret));
+        Self {
+            hash: hash_key(&key),
+            key,
+            prefix,
+        }
+    }
+}
+
+fn hash_key(key: &str) -> String {
+    hex::encode(Sha256::digest(key.as_bytes()))
+}
+
+/// Whether a presented key is shaped like one of ours, so others are
+/// refused without a lookup.
+fn is_key_shaped(key: &str) -> bool {
+    let is_hex = |s: &str, bytes: usize| s.len() == bytes * 2 && s.bytes().all(|b| b.is_ascii_hexdigit());
+    key.strip_prefix(KEY_MARKER)
+        .and_then(|rest| rest.strip_prefix('_'))
+        .and_then(|rest| rest.split_once('_'))
+        .is_some_and(|(prefix, secret)| is_hex(prefix, PREFIX_BYTES) && is_hex(secret, SECRET_BYTES))
+}
+
+/// Key repository backed by the `api_keys` table.
+#[derive(Debug, Clone)]
+pub struct ApiKeyStore {
+    db: PgPool,
+}
+
+impl ApiKeyStore {
+    /// Create a store on the given pool.
+    #[must_use]
+    pub const fn new(db: PgPool) -> Self {
+        Self { db }
+    }
+
+    /// A user's live keys, newest first.
+    pub async fn list(&self, owner: &CurrentUser) -> Result<Vec<ApiKey>, sqlx::Error> {
+        let rows: Vec<ApiKeyRow> = sqlx::query_as(&format!(
+            "SELECT {COLUMNS} FROM api_keys \
+             WHERE owner_id = $1 AND tenant_id = $2 AND revoked_at IS NULL \
+             ORDER BY created_at DESC"
+        ))
+        .bind(owner.id.0)
+        .bind(owner.tenant.as_str())
+        .fetch_all(&self.db)
+        .await?;
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    /// Create a key, returning it with the full key to hand to the client.
+    pub async fn create(&self, owner: &CurrentUser, new: NewApiKey) -> Result<(ApiKey, String), sqlx::Error> {
+        let generated = GeneratedKey::new();
+        let scopes: Vec<&str> = new.scopes.iter().map(|scope| scope.as_str()).collect();
+        let row: ApiKeyRow = sqlx::query_as(&format!(
+            r"
+            INSERT INTO api_keys
+                (id, owner_id, tenant_id, name, prefix, key_hash, scopes,
+                 rate_limit_per_minute, created_at, expires_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, NOW(), $9)
+            RETURNING {COLUMNS}
+            "
+        ))
+        .bind(Uuid::new_v4())
+        .bind(owner.id.0)
+        .bind(owner.tenant.as_str())
+        .bind(new.name.trim())
+        .bind(&generated.prefix)
+        .bind(&generated.hash)
+        .bind(&scopes)
+        .bind(new.rate_limit_per_minute.map(|n| i32::try_from(n).unwrap_or(i32::MAX)))
+        .bind(new.expires_at)
+        .fetch_one(&self.db)
+        .await?;
+        Ok((row.into(), generated.key))
+    }
+
+    /// Replace a key's secret, keeping its settings. The old key stops
+    /// working at once. Returns `None` if the owner has no such live key.
+    pub async fn rotate(&self, owner: &CurrentUser, id: Uuid) -> Result<Option<(ApiKey, String)>, sqlx::Error> {
+        let generated = GeneratedKey::new();
+        let row: Option<ApiKeyRow> = sqlx::query_as(&format!(
+            r"
+            UPDATE api_keys SET prefix = $4, key_hash = $5, rotated_at = NOW()
+            WHERE id = $1 AND owner_id = $2 AND tenant_id = $3 AND revoked_at IS NULL
+            RETURNING {COLUMNS}
+            "
+        ))
+        .bind(id)
+        .bind(owner.id.0)
+        .bind(owner.tenant.as_str())
+        .bind(&generated.prefix)
+        .bind(&generated.hash)
+        .fetch_optional(&self.db)
+        .await?;
+        Ok(row.map(|row| (row.into(), generated.key)))
+    }
+
+    /// Revoke a key. Returns whether the owner had such a live key.
+    pub async fn revoke(&self, owner: &CurrentUser, id: Uuid) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            "UPDATE api_keys SET revoked_at = NOW() \
+             WHERE id = $1 AND owner_id = $2 AND tenant_id = $3 AND revoked_at IS NULL",
+        )
+        .bind(id)
+        .bind(owner.id.0)
+        .bind(owner.tenant.as_str())
+        .execute(&self.db)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// The user and key behind a presented key, if it is live and its
+    /// owner active. Marks the key used, at most once a minute.
+    pub async fn authenticate(&self, key: &str) -> Result<Option<(CurrentUser, ApiKeyIdentity)>, sqlx::Error> {
+        if !is_key_shaped(key) {
+            return Ok(None);
+        }
+        let row: Option<KeyOwnerRow> = sqlx::query_as(
+            r"
+            SELECT k.id, k.scopes, k.rate_limit_per_minute,
+                   u.id AS user_id, u.email, u.role, u.tenant_id
+            FROM api_keys k
+            JOIN users u ON u.id = k.owner_id AND u.tenant_id = k.tenant_id
+            WHERE k.key_hash = $1 AND k.revoked_at IS NULL
+              AND (k.expires_at IS NULL OR k.expires_at > NOW())
+              AND u.is_active
+            ",
+        )
+        .bind(hash_key(key))
+        .fetch_optional(&self.db)
+        .await?;
+        let Some(row) = row else {
+            return Ok(None);
+        };
+        let (Some(role), Some(tenant)) = (Role::parse(&row.role), TenantId::parse(&row.tenant_id)) else {
+            return Ok(None);
+        };
+        let id = row.id;
+
+        let db = self.db.clone();
+        tokio::spawn(async move {
+            let touched = sqlx::query(
+                "UPDATE api_keys SET last_used_at = NOW() \
+                 WHERE id = $1 AND (last_used_at IS NULL OR last_used_at < NOW() - INTERVAL '1 minute')",
+            )
+            .bind(id)
+            .execute(&db)
+            .await;
+            if let Err(e) = touched {
+                warn!(error = %e, key_id = %id, "Failed to record API key use");
+            }
+        });
+
+        let user = CurrentUser {
+            id: UserId::from_uuid(row.user_id),
+            email: row.email,
+            role,
+            claimed_id: None,
+            tenant,
+        };
+        let identity = ApiKeyIdentity {
+            id,
+            scopes: row.scopes.iter().filter_map(|s| ApiKeyScope::parse(s)).collect(),
+            rate_limit_per_minute: row.rate_limit_per_minute.and_then(|n| u32::try_from(n).ok()),
+        };
+        Ok(Some((user, identity)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn identity(scopes: &[ApiKeyScope]) -> ApiKeyIdentity {
+        ApiKeyIdentity {
+            id: Uuid::new_v4(),
+            scopes: scopes.to_vec(),
+            rate_limit_per_minute: None,
+        }
+    }
+
+    #[test]
+    fn test_generated_keys_parse_and_hash() {
+        let generated = GeneratedKey::new();
+        assert!(generated.key.starts_with(&format!("qpk_{}_", generated.prefix)));
+        assert!(is_key_shaped(&generated.key));
+        assert_eq!(generated.hash, hash_key(&generated.key));
+        assert_ne!(GeneratedKey::new().key, generated.key);
+
+        assert!(!is_key_shaped("qpk_abc_def"));
+        assert!(!is_key_shaped("Bearer abc"));
+    }
+
+    #[test]
+    fn test_scopes_limit_writes_by_route() {
+        let read_only = identity(&[ApiKeyScope::ReadOnly]);
+        assert!(read_only.allows(&Method::GET, "/api/v1/testmo/test-runs"));
+        assert!(!read_only.allows(&Method::POST, "/api/v1/testmo/test-runs"));
+
+        let integrations = identity(&[ApiKeyScope::Integrations]);
+        assert!(integrations.allows(&Method::POST, "/api/v1/testmo/test-runs"));
+        assert!(integrations.allows(&Method::PUT, "/api/v1/tickets/PROJ-1/transition"));
+        assert!(!integrations.allows(&Method::POST, "/api/v1/workflows"));
+
+        let workflows = identity(&[ApiKeyScope::Workflows, ApiKeyScope::ReadOnly]);
+        assert!(workflows.allows(&Method::POST, "/api/v1/workflows/abc/complete"));
+        assert!(!workflows.allows(&Method::POST, "/api/v1/tickets"));
+        assert!(workflows.allows(&Method::POST, "/qa_pms.v1.WorkflowService/CompleteStep"));
+
+        // gRPC reads are POSTs too
+        assert!(read_only.allows(&Method::POST, "/qa_pms.v1.WorkflowService/GetWorkflow"));
+        assert!(!read_only.allows(&Method::POST, "/qa_pms.v1.WorkflowService/CancelWorkflow"));
+    }
+
+    #[test]
+    fn test_keys_never_reach_admin_or_key_management() {
+        let all = identity(&[
+            ApiKeyScope::ReadOnly,
+            ApiKeyScope::Workflows,
+            ApiKeyScope::Integrations,
+        ]);
+        assert!(!all.allows(&Method::GET, "/api/v1/auth/api-keys"));
+        assert!(!all.allows(&Method::GET, "/api/v1/auth/me"));
+        assert!(!all.allows(&Method::GET, "/api/v1/backup"));
+        assert!(!all.allows(&Method::GET, "/api/v1/setup/status"));
+        assert!(all.allows(&Method::GET, "/api/v1/pm-dashboard"));
+    }
+}
@@ -2,32 +2,71 @@
 //!
 //! Creates the Axum router with all routes and middleware.
 
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Duration;
 
 use anyhow::{Context, Result};
+use axum::extract::DefaultBodyLimit;
 use axum::Router;
+use qa_pms_ai::{TokenBudget, UsageTracker};
 use qa_pms_core::health::HealthCheck;
-use qa_pms_core::HealthStore;
-use qa_pms_jira::JiraHealthCheck;
-use qa_pms_postman::PostmanHealthCheck;
-use qa_pms_testmo::{TestmoClient, TestmoHealthCheck};
+use qa_pms_core::{CircuitBreaker, HealthStore, ProjectRepository, TestCaseRepository, TokenStore};
+use qa_pms_jira::{FileTokenStore, JiraHealthCheck, TicketSearchCache};
+use qa_pms_postman::{
+    PostmanCache, PostmanClient, PostmanHealthCheck, WorkspacePolicy, DEFAULT_REFRESH_SECS,
+};
+use qa_pms_splunk::SplunkApiClient;
+use qa_pms_support::SupportRepository;
+use qa_pms_testmo::{ClientOptions, FieldMapping, TestmoClient, TestmoHealthCheck};
+use qa_pms_time::IdleSweeper;
+use qa_pms_workflow::WorkflowArchiver;
 use secrecy::ExposeSecret;
 use sqlx::postgres::PgPoolOptions;
 use sqlx::PgPool;
 use tower_http::{
     compression::CompressionLayer,
-    cors::{Any, CorsLayer},
+    limit::RequestBodyLimitLayer,
     trace::TraceLayer,
 };
-use tracing::info;
-
-use qa_pms_config::Settings;
+use tracing::{info, warn};
 
+use qa_pms_config::settings::{
+    CircuitBreakerSettings, PostmanSettings, RetrySettings, SplunkSettings, TestmoSettings,
+};
+use qa_pms_config::{Settings, TenantCredentialStore, UserConfig};
+
+use crate::api_keys::ApiKeyStore;
+use crate::audit::{self, AuditLog};
+use crate::auth;
+use crate::cross_origin;
+use crate::digest::DigestScheduler;
+use crate::error_capture::{self, ErrorSampler};
+use crate::health_history::HealthHistory;
 use crate::health_scheduler::HealthScheduler;
+use crate::http_log::{self, HttpLogger};
+use crate::kpi_monitor::KpiTargetMonitor;
+use crate::mailer::Mailer;
+use crate::notifications::NotificationHub;
+use crate::pattern_scheduler::PatternScheduler;
+use crate::pm_export::PmExportScheduler;
+use crate::project_store::PgProjectRepository;
+use crate::migrations;
+use crate::rate_limit::{self, RateLimiter};
+use crate::report_scheduler::ReportScheduler;
 use crate::routes;
-use crate::routes::setup::{create_setup_store, SetupStore};
+use crate::routes::setup::{
+    create_setup_store, jira_oauth_client, JiraOAuthFlow, SetupStore,
+};
+use crate::saved_filters::SavedFilterStore;
+use crate::search_cache::SearchCache;
+use crate::search_index::SearchIndex;
+use crate::setup_state::FileSetupStateStore;
 use crate::startup::StartupValidator;
+use crate::startup_history::{StartupHistory, ValidationTrigger};
+use crate::test_case_store::PgTestCaseRepository;
+use crate::test_run_sync::{TestRunResultSync, DEFAULT_RESULT_SYNC_SECS};
+use crate::ticket_watch::{WatchPoller, WatchStore};
 
 /// Application state shared across all handlers.
 #[derive(Clone)]
@@ -38,30 +77,127 @@ pub struct AppState {
     pub settings: Arc<Settings>,
     /// Temporary setup wizard state
     pub setup_store: SetupStore,
+    /// Jira OAuth authorization state and tokens
+    pub jira_oauth: JiraOAuthFlow,
     /// Integration health store
     pub health_store: Arc<HealthStore>,
+    /// Stored health check results per integration
+    pub health_history: HealthHistory,
     /// Startup validator for credential checks
     pub startup_validator: Arc<StartupValidator>,
+    /// Stored startup validation runs
+    pub startup_history: StartupHistory,
+    /// Who changed what through the API
+    pub audit_log: AuditLog,
     /// Testmo client (optional, if configured)
     pub testmo_client: Option<Arc<TestmoClient>>,
     /// Testmo project ID for test runs
     pub testmo_project_id: Option<i64>,
+    /// Custom field mapping for the Testmo project
+    pub testmo_field_mapping: Arc<FieldMapping>,
+    /// Local test case repository (synced with Testmo)
+    pub test_cases: Arc<dyn TestCaseRepository>,
+    /// Projects scoping tickets, workflows, dashboards and alerts
+    pub projects: Arc<dyn ProjectRepository>,
+    /// Encrypted integration credentials per tenant (unavailable without a
+    /// usable config directory or encryption key)
+    pub tenant_credentials: Option<Arc<TenantCredentialStore>>,
+    /// Real-time notification hub
+    pub notifications: NotificationHub,
+    /// Local full-text index behind unified search
+    pub search_index: Arc<SearchIndex>,
+    /// Recent complete search results, for paging
+    pub search_cache: Arc<SearchCache>,
+    /// Recent Jira ticket searches
+    pub ticket_cache: Arc<TicketSearchCache>,
+    /// Saved ticket list filters per user
+    pub saved_filters: SavedFilterStore,
+    /// Tickets users watch for changes
+    pub ticket_watches: WatchStore,
+    /// Request rate limits per client and integration
+    pub rate_limiter: Arc<RateLimiter>,
+    /// Circuit breakers shared by the integration clients
+    pub circuit_breakers: IntegrationBreakers,
+    /// AI token usage and monthly budgets
+    pub ai_usage: UsageTracker,
+    /// Which failed requests are stored in the support error log
+    pub error_sampler: Arc<ErrorSampler>,
+    /// Structured request logging and its redaction
+    pub http_logger: Arc<HttpLogger>,
+    /// API keys of machine clients
+    pub api_keys: ApiKeyStore,
+}
+
+/// One circuit breaker per external integration.
+///
+/// Clients are often built per request, so the breakers live here and are
+/// handed to each new client.
+#[derive(Clone)]
+pub struct IntegrationBreakers {
+    /// Jira ticket API
+    pub jira: Arc<CircuitBreaker>,
+    /// Testmo API
+    pub testmo: Arc<CircuitBreaker>,
+    /// Postman API
+    pub postman: Arc<CircuitBreaker>,
+}
+
+impl IntegrationBreakers {
+    /// Create closed breakers with the configured thresholds.
+    #[must_use]
+    pub fn new(settings: CircuitBreakerSettings) -> Self {
+        let breaker = |name: &str| {
+            Arc::new(CircuitBreaker::new(
+                name,
+                settings.failure_threshold,
+                Duration::from_secs(settings.open_secs),
+            ))
+        };
+        Self {
+            jira: breaker("jira"),
+            testmo: breaker("testmo"),
+            postman: breaker("postman"),
+        }
+    }
+
+    /// Breaker for an integration by its health check name.
+    #[must_use]
+    pub fn get(&self, integration: &str) -> Option<&Arc<CircuitBreaker>> {
+        match integration {
+            "jira" => Some(&self.jira),
+            "testmo" => Some(&self.testmo),
+            "postman" => Some(&self.postman),
+            _ => None,
+        }
+    }
 }
 
-/// Create the Axum application with all routes and middleware.
+/// Create the Axum application with all routes and middleware on a
+/// connected database pool.
 ///
 /// Returns the router and an optional health scheduler to start as a background task.
-pub async fn create_app(settings: Settings) -> Result<(Router, Option<HealthScheduler>)> {
-    // Create database connection pool
-    let db = create_db_pool(&settings).await?;
-
-    // Run migrations
-    info!("Running database migrations...");
-    sqlx::migrate!("../../migrations")
-        .run(&db)
-        .await
-        .context("Failed to run database migrations")?;
-    info!("Migrations complete");
+pub async fn create_app(
+    settings: Settings,
+    db: PgPool,
+) -> Result<(Router, Option<HealthScheduler>)> {
+    if settings.database.auto_migrate {
+        info!("Running database migrations...");
+        migrations::MIGRATOR
+            .run(&db)
+            .await
+            .context("Failed to run database migrations")?;
+        info!("Migrations complete");
+    } else {
+        match migrations::status(&db).await {
+            Ok(report) if report.pending > 0 || report.drift => warn!(
+                pending = report.pending,
+                drift = report.drift,
+                "Database schema does not match this build; see /api/v1/admin/migrations"
+            ),
+            Ok(_) => {}
+            Err(e) => warn!(error = %e, "Failed to check database migrations"),
+        }
+    }
 
     // Seed default workflow templates
     info!("Seeding default workflow templates...");
@@ -78,6 +214,14 @@ pub async fn create_app(settings: Settings) -> Result<(Router, Option<HealthSche
         }
     }
 
+    if let Some(auth) = settings.auth.as_ref() {
+        if let Err(e) = auth::bootstrap_admin(&db, auth).await {
+            tracing::warn!(error = %e, "Failed to create bootstrap admin (non-fatal)");
+        }
+    } else {
+        warn!("AUTH_JWT_SECRET not set; the API is open and every request acts as admin");
+    }
+
     // Seed default Splunk query templates
     info!("Seeding default Splunk query templates...");
     let splunk_service = qa_pms_splunk::QueryTemplateService::new(db.clone());
@@ -93,65 +237,415 @@ pub async fn create_app(settings: Settings) -> Result<(Router, Option<HealthSche
     // Create health store for integration monitoring
     let health_store = Arc::new(HealthStore::new());
 
+    // Notification hub for WebSocket clients
+    let notifications = NotificationHub::default();
+
     // Create startup validator with configured integrations
     let startup_validator = Arc::new(create_startup_validator(&settings));
+    let startup_history = StartupHistory::new(db.clone());
+    let audit_log = AuditLog::new(db.clone());
+    start_boot_validation(&startup_validator, &startup_history);
 
     // Create health scheduler with the same checks for periodic monitoring
-    let health_scheduler = create_health_scheduler(&settings, Arc::clone(&health_store));
+    let health_history = HealthHistory::new(db.clone(), settings.health_history_retention_days);
+    let health_scheduler = create_health_scheduler(&settings, Arc::clone(&health_store)).map(
+        |scheduler| {
+            scheduler
+                .with_notifications(notifications.clone())
+                .with_history(health_history.clone())
+        },
+    );
+
+    let circuit_breakers = IntegrationBreakers::new(settings.circuit_breaker);
+    let rate_limiter = Arc::new(RateLimiter::new(settings.rate_limit));
 
     // Create Testmo client if configured
-    let (testmo_client, testmo_project_id) = create_testmo_client(&settings);
+    let (testmo_client, testmo_project_id) = create_testmo_client(&settings, &circuit_breakers);
+    let testmo_field_mapping = Arc::new(load_testmo_field_mapping(&settings));
+
+    start_postman_cache_refresh(&settings, &db, &circuit_breakers);
+    start_testmo_result_sync(&settings, &db, testmo_client.as_ref(), &rate_limiter);
+    start_idle_sweeper(&settings, &db);
+    start_workflow_archiver(&settings, &db);
+    start_report_scheduler(&settings, &db);
+    start_digest_scheduler(&settings, &db, &health_store);
+    start_pm_export_scheduler(&settings, &db);
+    if let Some(scheduler) =
+        PatternScheduler::new(db.clone(), notifications.clone(), &settings.pattern_sweep)
+    {
+        scheduler.start();
+    }
+    KpiTargetMonitor::new(db.clone(), notifications.clone()).start();
+
+    let testmo_base_url = testmo_client.as_ref().map(|c| c.base_url().to_string());
+    let search_index = Arc::new(SearchIndex::new(
+        testmo_base_url,
+        settings.search.fuzzy_distance,
+    )?);
+    let test_cases: Arc<dyn TestCaseRepository> = Arc::new(PgTestCaseRepository::new(db.clone()));
+    start_search_index_rebuild(&search_index, &db, &test_cases);
+    start_kb_reindex(&db);
+    let projects: Arc<dyn ProjectRepository> = Arc::new(PgProjectRepository::new(db.clone()));
+    let tenant_credentials = open_tenant_credentials(&settings);
+    let search_cache = Arc::new(SearchCache::new(Duration::from_secs(
+        settings.search.cache_ttl_secs,
+    )));
+    let ticket_cache = Arc::new(TicketSearchCache::new(Duration::from_secs(
+        settings.ticket_cache_ttl_secs,
+    )));
+
+    let saved_filters = SavedFilterStore::new(db.clone());
+    let ticket_watches = WatchStore::new(db.clone());
+    let api_keys = ApiKeyStore::new(db.clone());
+
+    let setup_store = open_setup_store(&settings).await;
+    let jira_oauth = open_jira_oauth(&settings, &setup_store).await;
+
+    #[cfg(feature = "grpc")]
+    let grpc_router = crate::grpc::router(db.clone(), notifications.clone());
+
+    let ai_usage = UsageTracker::new(
+        db.clone(),
+        TokenBudget {
+            soft_limit: settings.ai_budget.soft_limit_tokens,
+            hard_limit: settings.ai_budget.hard_limit_tokens,
+        },
+    );
+
+    let error_sampler = Arc::new(ErrorSampler::new(settings.error_capture));
+    let http_logger = Arc::new(HttpLogger::new(settings.http_log.clone()));
 
     // Create shared state
     let state = AppState {
         db,
         settings: Arc::new(settings),
-        setup_store: create_setup_store(),
+        setup_store,
+        jira_oauth,
         health_store,
+        health_history,
         startup_validator,
+        startup_history,
+        audit_log,
         testmo_client,
         testmo_project_id,
+        testmo_field_mapping,
+        test_cases,
+        projects,
+        tenant_credentials,
+        notifications,
+        search_index,
+        search_cache,
+        ticket_cache,
+        saved_filters,
+        ticket_watches,
+        rate_limiter,
+        circuit_breakers,
+        ai_usage,
+        error_sampler,
+        http_logger,
+        api_keys,
     };
 
+    if let Some(poller) = WatchPoller::new(state.clone()) {
+        poller.start();
+    }
+
     // Build the router
     let app = Router::new()
         .merge(routes::alerts::router())
+        .merge(routes::auth::router())
+        .merge(routes::api_keys::router())
         .merge(routes::dashboard::router())
+        .merge(routes::graphql::router())
         .merge(routes::pm_dashboard::router())
+        .merge(routes::postman::router())
         .merge(routes::health::router())
+        .merge(routes::notifications::router())
         .merge(routes::setup::router())
         .merge(routes::tickets::router())
         .merge(routes::startup::router())
         .merge(routes::search::router())
+        .merge(routes::search_history::router())
+        .merge(routes::projects::router())
+        .merge(routes::tenant::router())
+        .merge(routes::encryption::router())
+        .merge(routes::migrations::router())
+        .merge(routes::saved_filters::router())
+        .merge(routes::ticket_watch::router())
+        .merge(routes::test_cases::router())
+        .merge(routes::backup::router())
+        .merge(routes::webhooks::router())
         .nest("/api/v1/testmo", routes::testmo::router())
         .merge(routes::workflows::router())
         .merge(routes::time::router())
         .merge(routes::reports::router())
+        .merge(routes::report_templates::router())
         .merge(routes::splunk::router())
         .nest("/api/v1/support", routes::support::router())
         .nest("/api/v1/ai", routes::ai::router())
         .merge(routes::api_docs())
-        .with_state(state)
-        .layer(
-            tower::ServiceBuilder::new()
-                // Tracing for all requests
-                .layer(TraceLayer::new_for_http())
-                // Response compression
-                .layer(CompressionLayer::new())
-                // CORS configuration
-                .layer(
-                    CorsLayer::new()
-                        .allow_origin(Any)
-                        .allow_methods(Any)
-                        .allow_headers(Any),
-                ),
-        );
+        .merge(routes::audit::router());
+    let app = with_request_layers(app, &state).with_state(state.clone());
+
+    // Batch endpoint dispatches sub-requests against the routes above. Those
+    // carry no origin or cookies, so the batch request itself is checked
+    // for CSRF.
+    let batch = routes::batch::router(app.clone())
+        .layer(axum::middleware::from_fn_with_state(
+            state.clone(),
+            cross_origin::protect,
+        ));
+    let app = app.merge(batch);
+
+    // gRPC services share the HTTP port, routed by service path, and go
+    // through the same authentication, rate limits and audit as the API
+    #[cfg(feature = "grpc")]
+    let app = app.merge(with_request_layers(grpc_router, &state));
+
+    Ok((with_outer_layers(app, &state.settings), health_scheduler))
+}
+
+/// Wrap the whole application in tracing, compression, the body size limit
+/// and CORS. Also applied to the degraded-mode application.
+pub(crate) fn with_outer_layers(app: Router, settings: &Settings) -> Router {
+    app.layer(
+        tower::ServiceBuilder::new()
+            // Tracing for all requests
+            .layer(TraceLayer::new_for_http())
+            // Response compression (gzip/br, negotiated via Accept-Encoding)
+            .layer(CompressionLayer::new().gzip(true).br(true))
+            // Reject oversized request bodies with 413
+            .layer(DefaultBodyLimit::disable())
+            .layer(RequestBodyLimitLayer::new(settings.server.max_body_bytes))
+            // CORS configuration
+            .layer(cross_origin::cors_layer(&settings.cors)),
+    )
+}
+
+/// Wrap routes in the per-request layers: logging, CSRF protection,
+/// authentication, rate limiting, error capture and audit.
+fn with_request_layers<S>(router: Router<S>, state: &AppState) -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    router
+        // Innermost, so only requests that reached a handler are recorded
+        .layer(axum::middleware::from_fn_with_state(
+            state.clone(),
+            audit::record,
+        ))
+        // Inside authentication, so captured errors name the signed-in user
+        .layer(axum::middleware::from_fn_with_state(
+            state.clone(),
+            error_capture::capture,
+        ))
+        // Inside authentication, so limits apply per signed-in user
+        .layer(axum::middleware::from_fn_with_state(
+            state.clone(),
+            rate_limit::limit,
+        ))
+        .layer(axum::middleware::from_fn_with_state(
+            state.clone(),
+            auth::authenticate,
+        ))
+        // Outside authentication, so forged requests are refused before any
+        // account is looked up
+        .layer(axum::middleware::from_fn_with_state(
+            state.clone(),
+            cross_origin::protect,
+        ))
+        // Outside authentication, so rejected requests are logged too
+        .layer(axum::middleware::from_fn_with_state(
+            state.clone(),
+            http_log::log,
+        ))
+}
 
-    Ok((app, health_scheduler))
+/// Keep the Postman collection cache fresh in the background, if configured.
+fn start_postman_cache_refresh(settings: &Settings, db: &PgPool, breakers: &IntegrationBreakers) {
+    let Some(postman) = settings.postman.as_ref() else {
+        return;
+    };
+    let api_key = postman.api_key.expose_secret();
+    let interval_secs = postman.cache_refresh_secs.unwrap_or(DEFAULT_REFRESH_SECS);
+    if api_key.is_empty() || interval_secs == 0 {
+        return;
+    }
+    PostmanCache::new(db.clone())
+        .start_refresh(postman_client(postman, &settings.retry, breakers), Duration::from_secs(interval_secs));
+}
+
+/// Pull results of framework-created Testmo runs in the background, if configured.
+fn start_testmo_result_sync(
+    settings: &Settings,
+    db: &PgPool,
+    client: Option<&Arc<TestmoClient>>,
+    rate_limiter: &Arc<RateLimiter>,
+) {
+    let (Some(testmo), Some(client)) = (settings.testmo.as_ref(), client) else {
+        return;
+    };
+    let interval_secs = testmo.result_sync_secs.unwrap_or(DEFAULT_RESULT_SYNC_SECS);
+    if interval_secs == 0 {
+        return;
+    }
+    TestRunResultSync::new(
+        db.clone(),
+        Arc::clone(client),
+        Arc::clone(rate_limiter),
+        Duration::from_secs(interval_secs),
+    )
+    .start();
+}
+
+/// Auto-pause time sessions left running without heartbeats, unless disabled.
+fn start_idle_sweeper(settings: &Settings, db: &PgPool) {
+    if settings.time_idle_minutes == 0 {
+        return;
+    }
+    IdleSweeper::new(db.clone(), Duration::from_secs(settings.time_idle_minutes * 60)).start();
+}
+
+/// Archive long-finished workflows, unless disabled.
+fn start_workflow_archiver(settings: &Settings, db: &PgPool) {
+    if settings.workflow_archive_days == 0 {
+        return;
+    }
+    WorkflowArchiver::new(db.clone(), settings.workflow_archive_days).start();
+}
+
+/// Mail periodic reports on the configured schedule, if SMTP is set up.
+fn start_report_scheduler(settings: &Settings, db: &PgPool) {
+    let Some(schedule) = settings.report_schedule.as_ref() else {
+        return;
+    };
+    let Some(smtp) = settings.smtp.as_ref() else {
+        warn!("REPORT_SCHEDULE_CRON is set but SMTP is not configured; scheduled reports disabled");
+        return;
+    };
+    let scheduler = Mailer::from_settings(smtp)
+        .and_then(|mailer| ReportScheduler::new(db.clone(), mailer, schedule));
+    match scheduler {
+        Ok(scheduler) => scheduler.start(),
+        Err(e) => warn!(error = %e, "Scheduled reports disabled"),
+    }
+}
+
+/// Export the PM dashboard on its schedule, if configured.
+fn start_pm_export_scheduler(settings: &Settings, db: &PgPool) {
+    let Some(export) = settings.pm_export.as_ref() else {
+        return;
+    };
+    let mailer = match settings.smtp.as_ref() {
+        Some(smtp) => match Mailer::from_settings(smtp) {
+            Ok(mailer) => Some(mailer),
+            Err(e) => {
+                warn!(error = %e, "PM dashboard exports will not be mailed");
+                None
+            }
+        },
+        None => {
+            if !export.recipients.is_empty() {
+                warn!("PM_EXPORT_RECIPIENTS is set but SMTP is not configured; exports will not be mailed");
+            }
+            None
+        }
+    };
+    match PmExportScheduler::new(db.clone(), mailer, export) {
+        Ok(scheduler) => scheduler.start(),
+        Err(e) => warn!(error = %e, "Scheduled PM dashboard exports disabled"),
+    }
+}
+
+/// Mail the daily digest on its schedule, if SMTP is set up.
+fn start_digest_scheduler(settings: &Settings, db: &PgPool, health_store: &Arc<HealthStore>) {
+    let Some(digest) = settings.digest.as_ref() else {
+        return;
+    };
+    let Some(smtp) = settings.smtp.as_ref() else {
+        warn!("DIGEST_RECIPIENTS is set but SMTP is not configured; digest emails disabled");
+        return;
+    };
+    let scheduler = Mailer::from_settings(smtp).and_then(|mailer| {
+        DigestScheduler::new(db.clone(), Arc::clone(health_store), mailer, digest)
+    });
+    match scheduler {
+        Ok(scheduler) => scheduler.start(),
+        Err(e) => warn!(error = %e, "Digest emails disabled"),
+    }
+}
+
+/// Validate the integrations once in the background and store the run.
+fn start_boot_validation(validator: &Arc<StartupValidator>, history: &StartupHistory) {
+    let (validator, history) = (Arc::clone(validator), history.clone());
+    tokio::spawn(async move {
+        let report = validator.validate().await;
+        if let Err(e) = history.record(ValidationTrigger::Boot, &report).await {
+            warn!(error = %e, "Failed to store boot validation run");
+        }
+    });
+}
+
+/// Fill the search index from stored items in the background.
+pub(crate) fn start_search_index_rebuild(
+    index: &Arc<SearchIndex>,
+    db: &PgPool,
+    test_cases: &Arc<dyn TestCaseRepository>,
+) {
+    let (index, db, test_cases) = (Arc::clone(index), db.clone(), Arc::clone(test_cases));
+    tokio::spawn(async move {
+        let cases = test_cases.list().await.unwrap_or_else(|e| {
+            warn!(error = %e, "Failed to list test cases for search index");
+            Vec::new()
+        });
+        if let Err(e) = index.rebuild(&db, &cases).await {
+            warn!(error = %e, "Search index rebuild failed");
+        }
+    });
+}
+
+/// Index knowledge base entries missing a full-text search document in the
+/// background.
+fn start_kb_reindex(db: &PgPool) {
+    let repo = SupportRepository::new(db.clone());
+    tokio::spawn(async move {
+        match repo.reindex_kb_entries().await {
+            Ok(0) => {}
+            Ok(count) => info!(count, "Indexed knowledge base entries for full-text search"),
+            Err(e) => warn!(error = %e, "Knowledge base reindex failed"),
+        }
+    });
+}
+
+/// Postman client restricted by the configured workspace policy.
+pub(crate) fn postman_client(
+    settings: &PostmanSettings,
+    retry: &RetrySettings,
+    breakers: &IntegrationBreakers,
+) -> PostmanClient {
+    PostmanClient::new(settings.api_key.expose_secret().clone())
+        .with_policy(WorkspacePolicy::new(
+            settings.allowed_workspaces.clone(),
+            settings.read_only,
+        ))
+        .with_retry_policy(retry.policy())
+        .with_circuit_breaker(Arc::clone(&breakers.postman))
+}
+
+/// Splunk search API client, using the configured search timeout if set.
+pub(crate) fn splunk_client(settings: &SplunkSettings) -> SplunkApiClient {
+    let client = SplunkApiClient::new(&settings.api_url, settings.token.expose_secret().clone());
+    match settings.search_timeout_secs {
+        Some(secs) => client.with_search_timeout(Duration::from_secs(secs)),
+        None => client,
+    }
 }
 
 /// Create Testmo client from settings.
-fn create_testmo_client(settings: &Settings) -> (Option<Arc<TestmoClient>>, Option<i64>) {
+fn create_testmo_client(
+    settings: &Settings,
+    breakers: &IntegrationBreakers,
+) -> (Option<Arc<TestmoClient>>, Option<i64>) {
     let Some(testmo_settings) = settings.testmo.as_ref() else {
         return (None, None);
     };
@@ -164,10 +658,114 @@ fn create_testmo_client(settings: &Settings) -> (Option<Arc<TestmoClient>>, Opti
     }
 
     info!("Testmo client configured for {}", base_url);
-    let client = TestmoClient::new(base_url.clone(), api_key.clone());
+    let client = TestmoClient::with_options(
+        base_url.clone(),
+        api_key.clone(),
+        testmo_client_options(testmo_settings, &settings.retry),
+    )
+    .with_circuit_breaker(Arc::clone(&breakers.testmo));
     (Some(Arc::new(client)), testmo_settings.project_id)
 }
 
+/// Build Testmo client options, keeping client defaults for unset values.
+///
+/// `TESTMO_MAX_RETRIES` overrides the shared attempt count.
+pub(crate) fn testmo_client_options(settings: &TestmoSettings, retry: &RetrySettings) -> ClientOptions {
+    let defaults = ClientOptions::default();
+    let mut retry = retry.policy();
+    if let Some(max_retries) = settings.max_retries {
+        retry.max_attempts = max_retries;
+    }
+    ClientOptions {
+        timeout: settings
+            .timeout_secs
+            .map_or(defaults.timeout, Duration::from_secs),
+        retry,
+        page_size: settings.page_size.unwrap_or(defaults.page_size),
+    }
+}
+
+/// Parse the configured Testmo custom field mapping.
+///
+/// An invalid mapping is logged and replaced by the default (built-in fields).
+fn load_testmo_field_mapping(settings: &Settings) -> FieldMapping {
+    let Some(json) = settings.testmo.as_ref().and_then(|t| t.field_mapping.as_deref()) else {
+        return FieldMapping::default();
+    };
+    FieldMapping::from_json(json).unwrap_or_else(|e| {
+        warn!(error = %e, "Invalid TESTMO_FIELD_MAPPING, using built-in Testmo fields");
+        FieldMapping::default()
+    })
+}
+
+/// Open the setup wizard store, saved encrypted next to the user config.
+///
+/// Falls back to memory only, losing progress on restart, when the config
+/// directory or encryption key is unusable.
+pub(crate) async fn open_setup_store(settings: &Settings) -> SetupStore {
+    match (FileSetupStateStore::default_path(), settings.encryptor()) {
+        (Ok(path), Ok(encryptor)) => {
+            SetupStore::open(Arc::new(FileSetupStateStore::new(path, encryptor))).await
+        }
+        (Err(e), _) | (_, Err(e)) => {
+            warn!(error = %e, "Setup wizard progress will not survive a restart");
+            create_setup_store().await
+        }
+    }
+}
+
+/// Open the Jira OAuth token store and resume refreshing stored tokens.
+///
+/// Without a token file, OAuth tokens are kept only in the setup state.
+async fn open_jira_oauth(settings: &Settings, setup_store: &SetupStore) -> JiraOAuthFlow {
+    let tokens = match open_token_store(settings).await {
+        Ok(store) => Some(store),
+        Err(e) => {
+            warn!(error = %e, "Jira OAuth tokens will not be stored or refreshed");
+            None
+        }
+    };
+    let flow = JiraOAuthFlow::new(tokens);
+
+    let client = setup_store
+        .lock()
+        .await
+        .jira
+        .as_ref()
+        .filter(|jira| jira.has_oauth())
+        .and_then(|jira| jira_oauth_client(settings, jira));
+    if let Some(client) = client {
+        if flow.access_token().await.is_some() {
+            info!("Resuming Jira OAuth token refresh");
+            flow.start_refresh(client);
+        }
+    }
+    flow
+}
+
+/// Open the per-tenant credential files next to the user config.
+fn open_tenant_credentials(settings: &Settings) -> Option<Arc<TenantCredentialStore>> {
+    let store = TenantCredentialStore::default_dir()
+        .and_then(|dir| Ok(TenantCredentialStore::new(dir, settings.encryptor()?)));
+    match store {
+        Ok(store) => Some(Arc::new(store)),
+        Err(e) => {
+            warn!(error = %e, "Tenant credentials cannot be stored");
+            None
+        }
+    }
+}
+
+async fn open_token_store(settings: &Settings) -> Result<Arc<dyn TokenStore>> {
+    let path = token_store_path()?;
+    Ok(Arc::new(FileTokenStore::new(path, settings.encryptor()?).await?))
+}
+
+/// Location of the Jira OAuth token file, next to the user config.
+pub(crate) fn token_store_path() -> Result<PathBuf> {
+    Ok(UserConfig::default_path()?.with_file_name("tokens.json"))
+}
+
 /// Create startup validator with configured integrations.
 ///
 /// Adds health checks for all integrations with API keys/tokens configured.
@@ -282,7 +880,7 @@ fn create_health_scheduler(
 }
 
 /// Create the database connection pool.
-async fn create_db_pool(settings: &Settings) -> Result<PgPool> {
+pub(crate) async fn create_db_pool(settings: &Settings) -> Result<PgPool> {
     let pool = PgPoolOptions::new()
         .max_connections(settings.database.max_connections)
         .min_connections(settings.database.min_connections)
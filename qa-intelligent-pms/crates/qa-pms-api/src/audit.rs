@@ -0,0 +1,425 @@
+//! Audit log of mutating API calls.
+//!
+//! The [`record`] middleware stores an `audit_log` row for every POST, PUT,
+//! PATCH and DELETE request: who sent it, the route it hit, the entity the
+//! route acts on and the response status. A JSON request body is kept as the
+//! "after" snapshot with secrets masked. Handlers that can say more attach an
+//! [`AuditChange`] to their response, holding the entity as it was before and
+//! after the call, which is stored instead.
+//!
+//! Requests made without signing in, such as login and setup, are recorded
+//! in the tenant named by their `X-Tenant-Id` header, or the default tenant.
+//!
+//! POST endpoints that only read, such as searches, are not recorded.
+
+use axum::{
+    body::{Body, Bytes},
+    extract::{MatchedPath, Request, State},
+    http::{header, Method},
+    middleware::Next,
+    response::Response,
+};
+use chrono::{DateTime, Utc};
+use futures::StreamExt;
+use qa_pms_core::error::ApiError;
+use qa_pms_core::TenantId;
+use qa_pms_core::types::{CursorPage, PageRequest};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::{FromRow, PgPool};
+use tracing::warn;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::app::AppState;
+use crate::auth::{header_value, is_under, CurrentUser, GRPC_READS, TENANT_HEADER};
+use crate::redact::mask_secrets;
+
+/// Largest request body kept as a snapshot; larger bodies are not stored.
+const MAX_SNAPSHOT_BYTES: u64 = 64 * 1024;
+
+/// POST endpoints that change nothing.
+const READ_ONLY: &[&str] = &[
+    "/api/v1/graphql",
+    "/api/v1/search/contextual",
+    "/api/v1/search/postman",
+    "/api/v1/search/testmo",
+    "/api/v1/search/all",
+    "/api/v1/search/semantic",
+    "/api/v1/tickets/jql/validate",
+    "/api/v1/time/heartbeat",
+    "/api/v1/ai/chat",
+    "/api/v1/ai/models",
+    "/api/v1/ai/semantic-search",
+    "/api/v1/ai/gherkin",
+    "/api/v1/splunk/query",
+];
+
+/// Entity state around a change, attached to a handler's response with
+/// [`AuditChange::into_extension`].
+#[derive(Debug, Clone, Default)]
+pub struct AuditChange {
+    entity_id: Option<String>,
+    before: Option<Value>,
+    after: Option<Value>,
+}
+
+impl AuditChange {
+    /// A change to the entity with the given ID.
+    #[must_use]
+    pub fn new(entity_id: impl ToString) -> Self {
+        Self {
+            entity_id: Some(entity_id.to_string()),
+            ..Self::default()
+        }
+    }
+
+    /// Record the entity as it was before the call.
+    #[must_use]
+    pub fn before(mut self, entity: &impl Serialize) -> Self {
+        self.before = serde_json::to_value(entity).ok().map(mask_secrets);
+        self
+    }
+
+    /// Record the entity as it is after the call.
+    #[must_use]
+    pub fn after(mut self, entity: &impl Serialize) -> Self {
+        self.after = serde_json::to_value(entity).ok().map(mask_secrets);
+        self
+    }
+
+    /// Response part carrying the change to the middleware.
+    pub fn into_extension(self) -> axum::Extension<Self> {
+        axum::Extension(self)
+    }
+}
+
+/// A stored audit entry.
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditEntry {
+    /// Entry ID
+    pub id: Uuid,
+    /// When the request was handled
+    pub occurred_at: DateTime<Utc>,
+    /// User ID of the caller, if signed in
+    pub actor_id: Option<String>,
+    /// Email of the caller, if signed in
+    pub actor_email: Option<String>,
+    /// Tenant the request ran in
+    pub tenant_id: Option<String>,
+    /// Method and route, e.g. "PUT /api/v1/workflows/templates/:id"
+    pub action: String,
+    /// HTTP method
+    pub method: String,
+    /// Requested path
+    pub path: String,
+    /// Kind of entity changed, e.g. "workflows/templates"
+    pub entity_type: String,
+    /// ID of the entity changed, if the route names one
+    pub entity_id: Option<String>,
+    /// Response status code
+    pub status_code: i32,
+    /// Entity before the change
+    #[schema(value_type = Option<Object>)]
+    pub before: Option<Value>,
+    /// Entity after the change, or the request body
+    #[schema(value_type = Option<Object>)]
+    pub after: Option<Value>,
+}
+
+/// Filters for listing audit entries.
+#[derive(Debug, Clone, Default, Deserialize, IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditFilter {
+    /// Only calls by this user, by ID or email
+    pub actor: Option<String>,
+    /// Only this action, e.g. "POST /api/v1/tickets/:key/transition"
+    pub action: Option<String>,
+    /// Only changes to this kind of entity
+    pub entity_type: Option<String>,
+    /// Only changes to this entity
+    pub entity_id: Option<String>,
+    /// Only calls at or after this time
+    pub since: Option<DateTime<Utc>>,
+    /// Only calls before this time
+    pub until: Option<DateTime<Utc>>,
+}
+
+/// Audit log backed by the `audit_log` table.
+#[derive(Debug, Clone)]
+pub struct AuditLog {
+    db: PgPool,
+}
+
+impl AuditLog {
+    /// Create an audit log.
+    #[must_use]
+    pub const fn new(db: PgPool) -> Self {
+        Self { db }
+    }
+
+    /// Store an entry.
+    ///
+    /// # Errors
+    /// Returns an error if the database is unavailable.
+    pub async fn insert(&self, entry: &AuditEntry) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "INSERT INTO audit_log \
+             (id, occurred_at, actor_id, actor_email, tenant_id, action, method, path, \
+              entity_type, entity_id, status_code, before, after) \
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)",
+        )
+        .bind(entry.id)
+        .bind(entry.occurred_at)
+        .bind(&entry.actor_id)
+        .bind(&entry.actor_email)
+        .bind(&entry.tenant_id)
+        .bind(&entry.action)
+        .bind(&entry.method)
+        .bind(&entry.path)
+        .bind(&entry.entity_type)
+        .bind(&entry.entity_id)
+        .bind(entry.status_code)
+        .bind(&entry.before)
+        .bind(&entry.after)
+        .execute(&self.db)
+        .await?;
+        Ok(())
+    }
+
+    /// One page of a tenant's entries matching the filter.
+    ///
+    /// # Errors
+    /// Returns an error if the database is unavailable.
+    pub async fn list(
+        &self,
+        tenant_id: &str,
+        filter: &AuditFilter,
+        page: &PageRequest,
+    ) -> Result<CursorPage<AuditEntry>, sqlx::Error> {
+        let keyset = page
+            .keyset_sql("occurred_at", "id", 8)
+            .map(|clause| format!("AND {clause}"))
+            .unwrap_or_default();
+        let sql = format!(
+            "SELECT id, occurred_at, actor_id, actor_email, tenant_id, action, method, path, \
+                    entity_type, entity_id, status_code, before, after \
+             FROM audit_log \
+             WHERE tenant_id = $1 \
+               AND ($2::text IS NULL OR actor_id = $2 OR actor_email = $2) \
+               AND ($3::text IS NULL OR action = $3) \
+               AND ($4::text IS NULL OR entity_type = $4) \
+               AND ($5::text IS NULL OR entity_id = $5) \
+               AND ($6::timestamptz IS NULL OR occurred_at >= $6) \
+               AND ($7::timestamptz IS NULL OR occurred_at < $7) {keyset} \
+             ORDER BY {order} LIMIT {limit}",
+            order = page.order_sql("occurred_at", "id"),
+            limit = page.fetch_limit(),
+        );
+        let mut query = sqlx::query_as::<_, AuditEntry>(&sql)
+            .bind(tenant_id)
+            .bind(&filter.actor)
+            .bind(&filter.action)
+            .bind(&filter.entity_type)
+            .bind(&filter.entity_id)
+            .bind(filter.since)
+            .bind(filter.until);
+        if let Some((at, id)) = page.keyset() {
+            query = query.bind(at).bind(id);
+        }
+        let rows = query.fetch_all(&self.db).await?;
+        Ok(page.finish(rows, |entry| (entry.occurred_at, entry.id)))
+    }
+}
+
+/// Record mutating requests in the audit log.
+///
+/// Runs inside authentication so the caller is known. Failing to store an
+/// entry is logged and does not fail the request.
+pub async fn record(
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Result<Response, ApiError> {
+    let Some(route) = request.extensions().get::<MatchedPath>().cloned() else {
+        return Ok(next.run(request).await);
+    };
+    let method = request.method().clone();
+    let path = request.uri().path().to_string();
+    if !is_mutating(&method)
+        || READ_ONLY.iter().any(|prefix| is_under(&path, prefix))
+        || GRPC_READS.contains(&path.as_str())
+    {
+        return Ok(next.run(request).await);
+    }
+    let actor = request.extensions().get::<CurrentUser>().cloned();
+    let tenant = match &actor {
+        Some(user) => user.tenant.clone(),
+        None => header_value(&request, TENANT_HEADER)
+            .and_then(|value| TenantId::parse(&value))
+            .unwrap_or_default(),
+    };
+
+    let (request, body) = read_json_body(request, MAX_SNAPSHOT_BYTES).await?;
+    let body = body.map(mask_secrets);
+    let response = next.run(request).await;
+
+    let change = response.extensions().get::<AuditChange>().cloned();
+    let (entity_type, route_entity_id) = entity_of(route.as_str(), &path);
+    let (entity_id, before, after) = match change {
+        Some(change) => (
+            change.entity_id.or(route_entity_id),
+            change.before,
+            change.after,
+        ),
+        None => (route_entity_id, None, body),
+    };
+    let entry = AuditEntry {
+        id: Uuid::new_v4(),
+        occurred_at: Utc::now(),
+        actor_id: actor.as_ref().map(|user| user.owner(None)),
+        actor_email: actor.as_ref().map(|user| user.email.clone()),
+        tenant_id: Some(tenant.to_string()),
+        action: format!("{method} {}", route.as_str()),
+        method: method.to_string(),
+        path,
+        entity_type,
+        entity_id,
+        status_code: i32::from(response.status().as_u16()),
+        before,
+        after,
+    };
+    if let Err(e) = state.audit_log.insert(&entry).await {
+        warn!(error = %e, action = %entry.action, "Failed to store audit entry");
+    }
+    Ok(response)
+}
+
+fn is_mutating(method: &Method) -> bool {
+    matches!(
+        *method,
+        Method::POST | Method::PUT | Method::PATCH | Method::DELETE
+    )
+}
+
+/// Read a JSON body of at most `max_bytes`, returning the request with the
+/// body restored. Other bodies are left unread.
+///
+/// Bodies without a `Content-Length`, such as batch sub-requests, are
+/// buffered up to the limit; a longer one is passed on whole but not kept.
+pub(crate) async fn read_json_body(
+    request: Request,
+    max_bytes: u64,
+) -> Result<(Request, Option<Value>), ApiError> {
+    let is_json = request
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("application/json"));
+    let length = request
+        .headers()
+        .get(header::CONTENT_LENGTH)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse::<u64>().ok());
+    if !is_json || length.is_some_and(|length| length > max_bytes) {
+        return Ok((request, None));
+    }
+
+    let max_bytes = usize::try_from(max_bytes).unwrap_or(usize::MAX);
+    let (parts, body) = request.into_parts();
+    let mut stream = body.into_data_stream();
+    let mut buffered = Vec::new();
+    while let Some(chunk) = stream.next().await {
+        let chunk =
+            chunk.map_err(|e| ApiError::Validation(format!("Failed to read request body: {e}")))?;
+        buffered.extend_from_slice(&chunk);
+        if buffered.len() > max_bytes {
+            let read = futures::stream::once(async { Ok(Bytes::from(buffered)) });
+            let body = Body::from_stream(read.chain(stream));
+            return Ok((Request::from_parts(parts, body), None));
+        }
+    }
+    let snapshot = serde_json::from_slice(&buffered).ok();
+    Ok((Request::from_parts(parts, Body::from(buffered)), snapshot))
+}
+
+/// Entity type and ID of a route.
+///
+/// The type is the static segments after `/api/v1` up to the first path
+/// parameter, whose value is the ID: `/api/v1/workflows/templates/:id`
+/// acting on `/api/v1/workflows/templates/42` is `("workflows/templates",
+/// Some("42"))`.
+fn entity_of(route: &str, path: &str) -> (String, Option<String>) {
+    let route = route.strip_prefix("/api/v1/").unwrap_or(route);
+    let path = path.strip_prefix("/api/v1/").unwrap_or(path);
+    let mut entity_type = Vec::new();
+    for (segment, value) in route.split('/').zip(path.split('/')) {
+        if segment.starts_with(':') || segment.starts_with('*') {
+            return (entity_type.join("/"), Some(value.to_string()));
+        }
+        entity_type.push(segment);
+    }
+    (entity_type.join("/"), None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::to_bytes;
+
+    #[test]
+    fn test_entity_of_route() {
+        assert_eq!(
+            entity_of(
+                "/api/v1/workflows/templates/:id",
+                "/api/v1/workflows/templates/42"
+            ),
+            ("workflows/templates".to_string(), Some("42".to_string()))
+        );
+        assert_eq!(
+            entity_of(
+                "/api/v1/tickets/:key/transition",
+                "/api/v1/tickets/PROJ-7/transition"
+            ),
+            ("tickets".to_string(), Some("PROJ-7".to_string()))
+        );
+        assert_eq!(
+            entity_of("/api/v1/workflows", "/api/v1/workflows"),
+            ("workflows".to_string(), None)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_bodies_without_length_are_buffered_up_to_the_limit() {
+        let request = |body: &'static str| {
+            Request::builder()
+                .method(Method::POST)
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(Body::from_stream(futures::stream::once(async move {
+                    Ok::<_, std::io::Error>(body)
+                })))
+                .unwrap()
+        };
+
+        let (_, snapshot) = read_json_body(request(r#"{"name":"x"}"#), 64).await.unwrap();
+        assert_eq!(snapshot, Some(serde_json::json!({"name": "x"})));
+
+        let (restored, snapshot) = read_json_body(request(r#"{"name":"xyz"}"#), 8).await.unwrap();
+        assert_eq!(snapshot, None);
+        let body = to_bytes(restored.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(&body[..], br#"{"name":"xyz"}"#);
+    }
+
+    #[test]
+    fn test_read_only_and_safe_methods_are_skipped() {
+        assert!(!is_mutating(&Method::GET));
+        assert!(is_mutating(&Method::DELETE));
+        assert!(READ_ONLY
+            .iter()
+            .any(|prefix| is_under("/api/v1/search/all", prefix)));
+        assert!(!READ_ONLY
+            .iter()
+            .any(|prefix| is_under("/api/v1/search/saved", prefix)));
+    }
+}
@@ -0,0 +1,596 @@
+//! API authentication and role-based access.
+//!
+//! When `AUTH_JWT_SECRET` is set, every API route except a short public list
+//! requires a bearer token issued by `POST /api/v1/auth/login`. Each route
+//! prefix maps to the lowest role allowed to call it; roles are ordered
+//! QA < PM < Admin. Without a secret the API stays open and every request
+//! acts as a local admin, which keeps single-user installs working as before;
+//! such clients may still name themselves with an `X-User-Id` header so
+//! workflows are kept per person.
+//!
+//! Machine clients send an `X-Api-Key` header instead of a token; see
+//! [`crate::api_keys`] for what a key may reach.
+//!
+//! Every request also runs in a tenant. Signed-in users belong to the tenant
+//! of their account, carried in their token; an `X-Tenant-Id` header naming
+//! another tenant is refused. Without authentication the header selects the
+//! tenant, and requests without it use the default tenant.
+//!
+//! Handlers read the caller with the [`CurrentUser`] extractor.
+
+use std::collections::HashMap;
+
+use argon2::password_hash::rand_core::OsRng;
+use argon2::password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use argon2::Argon2;
+use axum::{
+    async_trait,
+    extract::{FromRequestParts, Query, Request, State},
+    http::{header, request::Parts},
+    middleware::Next,
+    response::Response,
+};
+use chrono::{DateTime, Utc};
+use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
+use qa_pms_config::settings::AuthSettings;
+use qa_pms_core::error::ApiError;
+use qa_pms_core::{TenantId, UserId};
+use secrecy::ExposeSecret;
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use tracing::info;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::api_keys::API_KEY_HEADER;
+use crate::app::AppState;
+use crate::routes::setup::JIRA_OAUTH_CALLBACK_PATH;
+
+/// Query parameter carrying the token where headers cannot be set (WebSocket).
+const TOKEN_QUERY_PARAM: &str = "access_token";
+
+/// Header naming the caller while authentication is disabled.
+pub const USER_ID_HEADER: &str = "x-user-id";
+
+/// Header selecting the tenant a request runs in.
+pub const TENANT_HEADER: &str = "x-tenant-id";
+
+/// User role, ordered from least to most privileged.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, ToSchema,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum Role {
+    /// Runs workflows and uses the integrations
+    Qa,
+    /// Also sees the PM dashboard
+    Pm,
+    /// Also manages setup, backups and users
+    Admin,
+}
+
+impl Role {
+    /// Whether this role may call routes requiring `required`.
+    #[must_use]
+    pub fn satisfies(self, required: Self) -> bool {
+        self >= required
+    }
+
+    /// Stored name of the role.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Qa => "qa",
+            Self::Pm => "pm",
+            Self::Admin => "admin",
+        }
+    }
+
+    /// Parse a stored role name.
+    #[must_use]
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "qa" => Some(Self::Qa),
+            "pm" => Some(Self::Pm),
+            "admin" => Some(Self::Admin),
+            _ => None,
+        }
+    }
+}
+
+/// Lowest role allowed to call `path`, or `None` for public routes.
+#[must_use]
+pub fn required_role(path: &str) -> Option<Role> {
+    const PUBLIC: &[&str] = &[
+        "/api/v1/health",
+        "/api/v1/auth/login",
+        "/api/v1/auth/csrf",
+        "/api/v1/openapi.json",
+        "/api/v1/webhooks",
+        JIRA_OAUTH_CALLBACK_PATH,
+    ];
+    const ADMIN: &[&str] = &[
+        "/api/v1/setup",
+        "/api/v1/backup",
+        "/api/v1/auth/users",
+        "/api/v1/tenant",
+        "/api/v1/encryption",
+        "/api/v1/admin",
+        "/api/v1/audit",
+        "/api/v1/support/bundle",
+    ];
+    const PM: &[&str] = &["/api/v1/pm-dashboard"];
+
+    let matches = |prefixes: &[&str]| prefixes.iter().any(|prefix| is_under(path, prefix));
+    if matches(PUBLIC) {
+        None
+    } else if matches(ADMIN) {
+        Some(Role::Admin)
+    } else if matches(PM) {
+        Some(Role::Pm)
+    } else {
+        Some(Role::Qa)
+    }
+}
+
+/// gRPC calls that change nothing. gRPC sends every call as a POST, so these
+/// are told apart by path.
+pub(crate) const GRPC_READS: &[&str] = &[
+    "/qa_pms.v1.WorkflowService/GetWorkflow",
+    "/qa_pms.v1.WorkflowService/ListUserWorkflows",
+    "/qa_pms.v1.TimeTrackingService/GetWorkflowTime",
+];
+
+/// `path` equals `prefix` or is nested below it.
+pub(crate) fn is_under(path: &str, prefix: &str) -> bool {
+    path.strip_prefix(prefix)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+}
+
+// ============================================================================
+// Current user
+// ============================================================================
+
+/// The user a request runs as.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CurrentUser {
+    /// User ID (nil for the local user)
+    pub id: UserId,
+    /// Login email
+    pub email: String,
+    /// Granted role
+    pub role: Role,
+    /// Name the local user gave in `X-User-Id`
+    pub claimed_id: Option<String>,
+    /// Tenant the request runs in
+    pub tenant: TenantId,
+}
+
+impl CurrentUser {
+    /// The implicit admin used while authentication is disabled.
+    #[must_use]
+    pub fn local() -> Self {
+        Self {
+            id: UserId::from_uuid(Uuid::nil()),
+            email: "local".to_string(),
+            role: Role::Admin,
+            claimed_id: None,
+            tenant: TenantId::default(),
+        }
+    }
+
+    /// The local user, optionally naming itself.
+    #[must_use]
+    pub fn local_as(claimed_id: Option<String>) -> Self {
+        Self {
+            claimed_id,
+            ..Self::local()
+        }
+    }
+
+    /// Whether this is the implicit local user.
+    #[must_use]
+    pub fn is_local(&self) -> bool {
+        self.id.0.is_nil()
+    }
+
+    /// Owner key for records this user creates or lists.
+    ///
+    /// Authenticated users always act as themselves. The local user may name
+    /// an owner explicitly, as clients did before authentication existed,
+    /// and otherwise acts as the `X-User-Id` it sent.
+    #[must_use]
+    pub fn owner(&self, requested: Option<String>) -> String {
+        if !self.is_local() {
+            return self.id.to_string();
+        }
+        requested
+            .or_else(|| self.claimed_id.clone())
+            .unwrap_or_else(|| self.id.to_string())
+    }
+
+    /// Whether records owned by `owner` belong to this user.
+    ///
+    /// An anonymous local user owns everything, since it has no identity
+    /// to compare against.
+    #[must_use]
+    pub fn owns(&self, owner: &str) -> bool {
+        if self.is_local() && self.claimed_id.is_none() {
+            return true;
+        }
+        self.owner(None) == owner
+    }
+
+    /// Whether this user may change records owned by `owner`.
+    ///
+    /// Admins may act on other users' records only when they ask to with
+    /// `admin_override`, so an accidental click does not touch someone
+    /// else's work.
+    #[must_use]
+    pub fn can_manage(&self, owner: &str, admin_override: bool) -> bool {
+        self.owns(owner) || (admin_override && self.role.satisfies(Role::Admin))
+    }
+}
+
+#[async_trait]
+impl<S: Send + Sync> FromRequestParts<S> for CurrentUser {
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        parts
+            .extensions
+            .get::<Self>()
+            .cloned()
+            .ok_or_else(|| ApiError::Unauthorized("Authentication required".into()))
+    }
+}
+
+// ============================================================================
+// Tokens and passwords
+// ============================================================================
+
+/// Access token claims.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Claims {
+    /// User ID
+    pub sub: Uuid,
+    /// Login email
+    pub email: String,
+    /// Role at issue time
+    pub role: Role,
+    /// Tenant of the account (default for tokens issued before tenants)
+    #[serde(default)]
+    pub tenant: TenantId,
+    /// Issued at (Unix seconds)
+    pub iat: i64,
+    /// Expiry (Unix seconds)
+    pub exp: i64,
+}
+
+/// Issue an access token, returning it with its expiry.
+///
+/// # Errors
+/// Returns error if the token cannot be signed.
+pub fn issue_token(
+    settings: &AuthSettings,
+    user_id: Uuid,
+    email: &str,
+    role: Role,
+    tenant: &TenantId,
+) -> Result<(String, DateTime<Utc>), ApiError> {
+    let now = Utc::now();
+    let ttl = i64::try_from(settings.token_ttl_secs).unwrap_or(i64::MAX);
+    let expires_at = now + chrono::Duration::seconds(ttl);
+    let claims = Claims {
+        sub: user_id,
+        email: email.to_string(),
+        role,
+        tenant: tenant.clone(),
+        iat: now.timestamp(),
+        exp: expires_at.timestamp(),
+    };
+    let key = EncodingKey::from_secret(settings.jwt_secret.expose_secret().as_bytes());
+    let token = encode(&Header::default(), &claims, &key)
+        .map_err(|e| ApiError::Internal(anyhow::anyhow!("Failed to sign token: {e}")))?;
+    Ok((token, expires_at))
+}
+
+/// Verify an access token and return its claims.
+///
+/// # Errors
+/// Returns `Unauthorized` if the token is malformed, tampered with or expired.
+pub fn verify_token(settings: &AuthSettings, token: &str) -> Result<Claims, ApiError> {
+    let key = DecodingKey::from_secret(settings.jwt_secret.expose_secret().as_bytes());
+    decode::<Claims>(token, &key, &Validation::default())
+        .map(|data| data.claims)
+        .map_err(|_| ApiError::Unauthorized("Invalid or expired token".into()))
+}
+
+/// Hash a password for storage.
+///
+/// # Errors
+/// Returns error if hashing fails.
+pub fn hash_password(password: &str) -> Result<String, ApiError> {
+    let salt = SaltString::generate(&mut OsRng);
+    Argon2::default()
+        .hash_password(password.as_bytes(), &salt)
+        .map(|hash| hash.to_string())
+        .map_err(|e| ApiError::Internal(anyhow::anyhow!("Failed to hash password: {e}")))
+}
+
+/// Check a password against a stored hash.
+#[must_use]
+pub fn verify_password(password: &str, hash: &str) -> bool {
+    PasswordHash::new(hash).is_ok_and(|parsed| {
+        Argon2::default()
+            .verify_password(password.as_bytes(), &parsed)
+            .is_ok()
+    })
+}
+
+// ============================================================================
+// Middleware
+// ============================================================================
+
+/// Resolve the caller and its tenant, and enforce the route's role.
+///
+/// Tokens come from the `Authorization: Bearer` header, or the
+/// `access_token` query parameter for WebSocket upgrades. A request with an
+/// `X-Api-Key` header runs as the key's owner instead. The user is re-read
+/// on every request so deactivation and role changes apply at once.
+///
+/// # Errors
+/// Returns 400 for a malformed `X-Tenant-Id`, 401 without a valid token or
+/// key for an active user, and 403 when the user's role is below the
+/// route's, the key's scopes do not cover the route, or the header names a
+/// tenant other than the user's.
+pub async fn authenticate(
+    State(state): State<AppState>,
+    mut request: Request,
+    next: Next,
+) -> Result<Response, ApiError> {
+    let requested_tenant = header_value(&request, TENANT_HEADER)
+        .map(|value| {
+            TenantId::parse(&value)
+                .ok_or_else(|| ApiError::Validation(format!("Invalid {TENANT_HEADER}: {value}")))
+        })
+        .transpose()?;
+    let Some(settings) = state.settings.auth.as_ref() else {
+        let claimed_id = header_value(&request, USER_ID_HEADER);
+        request.extensions_mut().insert(CurrentUser {
+            tenant: requested_tenant.unwrap_or_default(),
+            ..CurrentUser::local_as(claimed_id)
+        });
+        return Ok(next.run(request).await);
+    };
+    let Some(required) = required_role(request.uri().path()) else {
+        return Ok(next.run(request).await);
+    };
+
+    let user = if let Some(key) = header_value(&request, API_KEY_HEADER) {
+        let (user, key) = state
+            .api_keys
+            .authenticate(&key)
+            .await
+            .map_err(|e| ApiError::Internal(e.into()))?
+            .ok_or_else(|| ApiError::Unauthorized("Invalid, expired or revoked API key".into()))?;
+        if !key.allows(request.method(), request.uri().path()) {
+            return Err(ApiError::Forbidden(format!(
+                "This API key does not allow {} {}",
+                request.method(),
+                request.uri().path()
+            )));
+        }
+        request.extensions_mut().insert(key);
+        user
+    } else {
+        let token = bearer_token(&request)
+            .ok_or_else(|| ApiError::Unauthorized("Authentication required".into()))?;
+        let claims = verify_token(settings, &token)?;
+        load_active_user(&state.db, claims.sub)
+            .await?
+            .ok_or_else(|| ApiError::Unauthorized("Account is disabled or no longer exists".into()))?
+    };
+    if requested_tenant.is_some_and(|tenant| tenant != user.tenant) {
+        return Err(ApiError::Forbidden(format!(
+            "This account belongs to tenant {}",
+            user.tenant
+        )));
+    }
+
+    if !user.role.satisfies(required) {
+        return Err(ApiError::Forbidden(format!(
+            "This action requires the {} role",
+            required.as_str()
+        )));
+    }
+
+    request.extensions_mut().insert(user);
+    Ok(next.run(request).await)
+}
+
+pub(crate) fn header_value(request: &Request, name: &str) -> Option<String> {
+    request
+        .headers()
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+}
+
+pub(crate) fn bearer_token(request: &Request) -> Option<String> {
+    let from_header = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .map(|token| token.trim().to_string());
+    from_header.or_else(|| {
+        Query::<HashMap<String, String>>::try_from_uri(request.uri())
+            .ok()
+            .and_then(|Query(mut params)| params.remove(TOKEN_QUERY_PARAM))
+    })
+}
+
+async fn load_active_user(db: &PgPool, id: Uuid) -> Result<Option<CurrentUser>, ApiError> {
+    let row: Option<(String, String, String)> =
+        sqlx::query_as("SELECT email, role, tenant_id FROM users WHERE id = $1 AND is_active")
+            .bind(id)
+            .fetch_optional(db)
+            .await
+            .map_err(|e| ApiError::Internal(e.into()))?;
+    Ok(row.and_then(|(email, role, tenant)| {
+        Some(CurrentUser {
+            id: UserId::from_uuid(id),
+            email,
+            role: Role::parse(&role)?,
+            claimed_id: None,
+            tenant: TenantId::parse(&tenant)?,
+        })
+    }))
+}
+
+/// Create the configured admin account if no users exist yet.
+///
+/// # Errors
+/// Returns error if the users table cannot be read or written.
+pub async fn bootstrap_admin(db: &PgPool, settings: &AuthSettings) -> anyhow::Result<()> {
+    let (Some(email), Some(password)) = (&settings.admin_email, &settings.admin_password) else {
+        return Ok(());
+    };
+    let (existing,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM users")
+        .fetch_one(db)
+        .await?;
+    if existing > 0 {
+        return Ok(());
+    }
+
+    let hash = hash_password(password.expose_secret()).map_err(|e| anyhow::anyhow!("{e}"))?;
+    sqlx::query(
+        "INSERT INTO users \
+         (id, email, display_name, role, password_hash, tenant_id, is_active, created_at) \
+         VALUES ($1, $2, $2, $3, $4, $5, TRUE, NOW())",
+    )
+    .bind(Uuid::new_v4())
+    .bind(email.trim().to_lowercase())
+    .bind(Role::Admin.as_str())
+    .bind(hash)
+    .bind(TenantId::DEFAULT)
+    .execute(db)
+    .await?;
+    info!(email = %email, "Created bootstrap admin account");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use secrecy::SecretString;
+
+    fn settings() -> AuthSettings {
+        AuthSettings {
+            jwt_secret: SecretString::from("0123456789abcdef0123456789abcdef".to_string()),
+            token_ttl_secs: 3600,
+            admin_email: None,
+            admin_password: None,
+        }
+    }
+
+    #[test]
+    fn test_role_ordering() {
+        assert!(Role::Admin.satisfies(Role::Pm));
+        assert!(Role::Pm.satisfies(Role::Qa));
+        assert!(Role::Pm.satisfies(Role::Pm));
+        assert!(!Role::Qa.satisfies(Role::Pm));
+        assert!(!Role::Pm.satisfies(Role::Admin));
+        assert_eq!(Role::parse(Role::Pm.as_str()), Some(Role::Pm));
+        assert_eq!(Role::parse("root"), None);
+    }
+
+    #[test]
+    fn test_required_role_by_path() {
+        assert_eq!(required_role("/api/v1/health"), None);
+        assert_eq!(required_role("/api/v1/health/integrations"), None);
+        assert_eq!(required_role("/api/v1/auth/login"), None);
+        assert_eq!(required_role("/api/v1/auth/csrf"), None);
+        assert_eq!(required_role(JIRA_OAUTH_CALLBACK_PATH), None);
+        assert_eq!(required_role("/api/v1/setup/status"), Some(Role::Admin));
+        assert_eq!(required_role("/api/v1/auth/users/abc"), Some(Role::Admin));
+        assert_eq!(required_role("/api/v1/tenant/credentials"), Some(Role::Admin));
+        assert_eq!(required_role("/api/v1/encryption/rotate-keys"), Some(Role::Admin));
+        assert_eq!(required_role("/api/v1/admin/migrations"), Some(Role::Admin));
+        assert_eq!(required_role("/api/v1/audit"), Some(Role::Admin));
+        assert_eq!(required_role("/api/v1/pm-dashboard/export"), Some(Role::Pm));
+        assert_eq!(required_role("/api/v1/pm-dashboardx"), Some(Role::Qa));
+        assert_eq!(required_role("/api/v1/auth/me"), Some(Role::Qa));
+        assert_eq!(required_role("/api/v1/workflows"), Some(Role::Qa));
+    }
+
+    #[test]
+    fn test_token_round_trip() {
+        let settings = settings();
+        let id = Uuid::new_v4();
+        let tenant = TenantId::parse("payments").unwrap();
+        let (token, expires_at) =
+            issue_token(&settings, id, "qa@example.com", Role::Pm, &tenant).unwrap();
+        assert!(expires_at > Utc::now());
+
+        let claims = verify_token(&settings, &token).unwrap();
+        assert_eq!(claims.sub, id);
+        assert_eq!(claims.role, Role::Pm);
+        assert_eq!(claims.tenant, tenant);
+
+        let mut other = settings;
+        other.jwt_secret = SecretString::from("another-secret-another-secret-xx".to_string());
+        assert!(verify_token(&other, &token).is_err());
+    }
+
+    #[test]
+    fn test_password_hash_and_verify() {
+        let hash = hash_password("correct horse battery").unwrap();
+        assert!(verify_password("correct horse battery", &hash));
+        assert!(!verify_password("wrong", &hash));
+        assert!(!verify_password("anything", "not-a-hash"));
+    }
+
+    #[test]
+    fn test_owner_is_pinned_for_authenticated_users() {
+        let local = CurrentUser::local();
+        assert_eq!(local.owner(Some("cli-user".into())), "cli-user");
+        assert_eq!(local.owner(None), Uuid::nil().to_string());
+        assert_eq!(CurrentUser::local_as(Some("alice".into())).owner(None), "alice");
+
+        let user = user(Role::Qa);
+        assert_eq!(user.owner(Some("someone-else".into())), user.id.to_string());
+    }
+
+    fn user(role: Role) -> CurrentUser {
+        CurrentUser {
+            id: UserId::from_uuid(Uuid::new_v4()),
+            email: "qa@example.com".into(),
+            role,
+            claimed_id: None,
+            tenant: TenantId::default(),
+        }
+    }
+
+    #[test]
+    fn test_can_manage_requires_ownership_or_admin_override() {
+        let qa = user(Role::Qa);
+        let own = qa.id.to_string();
+        assert!(qa.can_manage(&own, false));
+        assert!(!qa.can_manage("someone-else", false));
+        assert!(!qa.can_manage("someone-else", true));
+
+        let admin = user(Role::Admin);
+        assert!(!admin.can_manage(&own, false));
+        assert!(admin.can_manage(&own, true));
+
+        assert!(CurrentUser::local().can_manage("anyone", false));
+        let alice = CurrentUser::local_as(Some("alice".into()));
+        assert!(alice.can_manage("alice", false));
+        assert!(!alice.can_manage("bob", false));
+        assert!(alice.can_manage("bob", true));
+    }
+}
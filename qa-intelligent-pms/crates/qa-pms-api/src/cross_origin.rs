@@ -0,0 +1,310 @@
+//! Cross-origin access and CSRF protection.
+//!
+//! [`cors_layer`] builds the CORS policy from [`CorsSettings`]. Without
+//! allowed origins any origin may call the API, without credentials; with
+//! them only those origins may, and cookies may be allowed for a frontend
+//! served from another origin.
+//!
+//! With CSRF protection on, [`protect`] guards state-changing requests sent
+//! by browsers. They must come from an allowed origin, or the API's own, and
+//! carry an `X-CSRF-Token` header matching the `qa_pms_csrf` cookie: a
+//! double-submit token issued by `GET /api/v1/auth/csrf`. Requests with a
+//! bearer token or API key are exempt, since browsers never attach those on
+//! their own and a forged request cannot carry them.
+
+use std::time::Duration;
+
+use argon2::password_hash::rand_core::{OsRng, RngCore};
+use axum::{
+    extract::{Request, State},
+    http::{header, HeaderMap, HeaderName, HeaderValue, Method},
+    middleware::Next,
+    response::Response,
+};
+use qa_pms_config::settings::CorsSettings;
+use qa_pms_core::error::ApiError;
+use tower_http::cors::{AllowOrigin, Any, CorsLayer};
+
+use crate::api_keys::API_KEY_HEADER;
+use crate::app::AppState;
+use crate::auth::{TENANT_HEADER, USER_ID_HEADER};
+use crate::error_capture::REQUEST_ID_HEADER;
+use crate::rate_limit::{
+    INTEGRATION_HEADER, INTEGRATION_LIMIT_HEADER, INTEGRATION_REMAINING_HEADER, LIMIT_HEADER,
+    REMAINING_HEADER,
+};
+
+/// Header carrying the CSRF token.
+pub const CSRF_HEADER: HeaderName = HeaderName::from_static("x-csrf-token");
+
+/// Cookie holding the CSRF token.
+pub const CSRF_COOKIE: &str = "qa_pms_csrf";
+
+/// Random bytes in a CSRF token.
+const CSRF_TOKEN_BYTES: usize = 32;
+
+/// Methods a listed origin may use.
+const ALLOWED_METHODS: [Method; 7] = [
+    Method::GET,
+    Method::HEAD,
+    Method::POST,
+    Method::PUT,
+    Method::PATCH,
+    Method::DELETE,
+    Method::OPTIONS,
+];
+
+/// Request headers the API reads.
+fn api_headers() -> Vec<HeaderName> {
+    vec![
+        header::ACCEPT,
+        header::AUTHORIZATION,
+        header::CONTENT_TYPE,
+        header::IF_NONE_MATCH,
+        CSRF_HEADER,
+        REQUEST_ID_HEADER,
+        HeaderName::from_static(API_KEY_HEADER),
+        HeaderName::from_static(TENANT_HEADER),
+        HeaderName::from_static(USER_ID_HEADER),
+    ]
+}
+
+/// Response headers cross-origin scripts may read.
+fn exposed_headers() -> Vec<HeaderName> {
+    vec![
+        header::CONTENT_DISPOSITION,
+        header::ETAG,
+        header::RETRY_AFTER,
+        REQUEST_ID_HEADER,
+        LIMIT_HEADER,
+        REMAINING_HEADER,
+        INTEGRATION_HEADER,
+        INTEGRATION_LIMIT_HEADER,
+        INTEGRATION_REMAINING_HEADER,
+    ]
+}
+
+/// CORS policy for the configured origins.
+pub fn cors_layer(settings: &CorsSettings) -> CorsLayer {
+    let layer = CorsLayer::new()
+        .max_age(Duration::from_secs(settings.max_age_secs))
+        .expose_headers(exposed_headers());
+    if settings.allowed_origins.is_empty() {
+        return layer.allow_origin(Any).allow_methods(Any).allow_headers(Any);
+    }
+
+    let origins = settings
+        .allowed_origins
+        .iter()
+        .filter_map(|origin| HeaderValue::from_str(origin).ok());
+    let extra_headers = settings
+        .allowed_headers
+        .iter()
+        .filter_map(|name| HeaderName::from_bytes(name.trim().to_ascii_lowercase().as_bytes()).ok());
+    layer
+        .allow_origin(AllowOrigin::list(origins))
+        .allow_methods(ALLOWED_METHODS)
+        .allow_headers(api_headers().into_iter().chain(extra_headers).collect::<Vec<_>>())
+        .allow_credentials(settings.allow_credentials)
+}
+
+/// A new CSRF token.
+#[must_use]
+pub fn new_csrf_token() -> String {
+    let mut bytes = [0u8; CSRF_TOKEN_BYTES];
+    OsRng.fill_bytes(&mut bytes);
+    hex::encode(bytes)
+}
+
+/// The request's CSRF cookie, if it holds a well-formed token.
+#[must_use]
+pub fn csrf_cookie(headers: &HeaderMap) -> Option<String> {
+    cookie_value(headers, CSRF_COOKIE)
+        .filter(|token| token.len() == CSRF_TOKEN_BYTES * 2 && token.bytes().all(|b| b.is_ascii_hexdigit()))
+}
+
+/// `Set-Cookie` value storing a CSRF token.
+///
+/// The cookie must reach the API from the frontend's origin when
+/// credentials are allowed, so it is then `SameSite=None` and `Secure`;
+/// otherwise it stays on same-site requests.
+#[must_use]
+pub fn csrf_set_cookie(settings: &CorsSettings, token: &str) -> String {
+    let same_site = if settings.allow_credentials {
+        "SameSite=None; Secure"
+    } else {
+        "SameSite=Strict"
+    };
+    format!("{CSRF_COOKIE}={token}; Path=/; HttpOnly; {same_site}")
+}
+
+/// Refuse state-changing browser requests without a valid CSRF token.
+///
+/// Runs outside authentication, so forged requests are turned away before
+/// any account is looked up.
+///
+/// # Errors
+/// Returns 403 for a request from an origin that is not allowed, or without
+/// a token matching its cookie.
+pub async fn protect(
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Result<Response, ApiError> {
+    check_csrf(&state.settings.cors, request.method(), request.headers())?;
+    Ok(next.run(request).await)
+}
+
+fn check_csrf(settings: &CorsSettings, method: &Method, headers: &HeaderMap) -> Result<(), ApiError> {
+    if !settings.csrf_enabled
+        || matches!(*method, Method::GET | Method::HEAD | Method::OPTIONS)
+        || !is_from_browser(headers)
+        || headers.contains_key(header::AUTHORIZATION)
+        || headers.contains_key(API_KEY_HEADER)
+    {
+        return Ok(());
+    }
+
+    if let Some(origin) = headers.get(header::ORIGIN).and_then(|value| value.to_str().ok()) {
+        if !origin_allowed(settings, origin, headers) {
+            return Err(ApiError::Forbidden(format!(
+                "Requests from origin {origin} are not allowed"
+            )));
+        }
+    }
+    let sent = headers
+        .get(&CSRF_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .map(str::trim);
+    match (csrf_cookie(headers), sent) {
+        (Some(cookie), Some(sent)) if cookie == sent => Ok(()),
+        _ => Err(ApiError::Forbidden(
+            "Missing or invalid CSRF token; get one from GET /api/v1/auth/csrf".into(),
+        )),
+    }
+}
+
+/// Browsers send an origin with state-changing requests, and cookies when
+/// they have them; other clients normally send neither.
+fn is_from_browser(headers: &HeaderMap) -> bool {
+    headers.contains_key(header::ORIGIN) || headers.contains_key(header::COOKIE)
+}
+
+/// Whether `origin` is listed, or is the API's own origin.
+fn origin_allowed(settings: &CorsSettings, origin: &str, headers: &HeaderMap) -> bool {
+    let origin = origin.trim_end_matches('/').to_ascii_lowercase();
+    if settings.allowed_origins.contains(&origin) {
+        return true;
+    }
+    let host = headers
+        .get(header::HOST)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_ascii_lowercase);
+    origin
+        .split_once("://")
+        .is_some_and(|(_, origin_host)| host.as_deref() == Some(origin_host))
+}
+
+fn cookie_value(headers: &HeaderMap, name: &str) -> Option<String> {
+    headers
+        .get_all(header::COOKIE)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(';'))
+        .filter_map(|pair| pair.trim().split_once('='))
+        .find(|(key, _)| *key == name)
+        .map(|(_, value)| value.trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tower::Layer;
+
+    fn settings(origins: &[&str], allow_credentials: bool) -> CorsSettings {
+        CorsSettings {
+            allowed_origins: origins.iter().map(ToString::to_string).collect(),
+            allow_credentials,
+            csrf_enabled: true,
+            ..CorsSettings::default()
+        }
+    }
+
+    fn headers(pairs: &[(HeaderName, &str)]) -> HeaderMap {
+        pairs
+            .iter()
+            .map(|(name, value)| (name.clone(), HeaderValue::from_str(value).unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn test_cors_layer_accepts_both_modes() {
+        let service = tower::service_fn(|_: Request| async { Ok::<_, std::convert::Infallible>(Response::new(axum::body::Body::empty())) });
+        let _ = cors_layer(&CorsSettings::default()).layer(service);
+        let mut listed = settings(&["https://qa.example.com"], true);
+        listed.allowed_headers = vec!["X-Trace".to_string()];
+        let _ = cors_layer(&listed).layer(service);
+    }
+
+    #[test]
+    fn test_csrf_token_must_match_cookie() {
+        let settings = settings(&["https://qa.example.com"], true);
+        let token = new_csrf_token();
+        let cookie = format!("theme=dark; {CSRF_COOKIE}={token}");
+        let origin = (header::ORIGIN, "https://qa.example.com");
+
+        let valid = headers(&[origin.clone(), (header::COOKIE, &cookie), (CSRF_HEADER, &token)]);
+        assert!(check_csrf(&settings, &Method::POST, &valid).is_ok());
+
+        let missing = headers(&[origin.clone(), (header::COOKIE, &cookie)]);
+        assert!(check_csrf(&settings, &Method::POST, &missing).is_err());
+        assert!(check_csrf(&settings, &Method::GET, &missing).is_ok());
+
+        let wrong = headers(&[origin, (header::COOKIE, &cookie), (CSRF_HEADER, &new_csrf_token())]);
+        assert!(check_csrf(&settings, &Method::DELETE, &wrong).is_err());
+    }
+
+    #[test]
+    fn test_csrf_refuses_other_origins_and_skips_non_browsers() {
+        let settings = settings(&["https://qa.example.com"], true);
+        let token = new_csrf_token();
+        let cookie = format!("{CSRF_COOKIE}={token}");
+
+        let evil = headers(&[
+            (header::ORIGIN, "https://evil.example.net"),
+            (header::COOKIE, &cookie),
+            (CSRF_HEADER, &token),
+        ]);
+        assert!(check_csrf(&settings, &Method::POST, &evil).is_err());
+
+        let same_origin = headers(&[
+            (header::HOST, "api.example.com"),
+            (header::ORIGIN, "https://api.example.com"),
+            (header::COOKIE, &cookie),
+            (CSRF_HEADER, &token),
+        ]);
+        assert!(check_csrf(&settings, &Method::POST, &same_origin).is_ok());
+
+        assert!(check_csrf(&settings, &Method::POST, &HeaderMap::new()).is_ok());
+        let bearer = headers(&[
+            (header::ORIGIN, "https://evil.example.net"),
+            (header::AUTHORIZATION, "Bearer abc"),
+        ]);
+        assert!(check_csrf(&settings, &Method::POST, &bearer).is_ok());
+
+        let disabled = CorsSettings::default();
+        assert!(check_csrf(&disabled, &Method::POST, &evil).is_ok());
+    }
+
+    #[test]
+    fn test_csrf_cookie_attributes_follow_credentials() {
+        let token = new_csrf_token();
+        assert!(csrf_set_cookie(&settings(&["https://qa.example.com"], true), &token)
+            .ends_with("SameSite=None; Secure"));
+        assert!(csrf_set_cookie(&CorsSettings::default(), &token).ends_with("SameSite=Strict"));
+        assert_eq!(
+            csrf_cookie(&headers(&[(header::COOKIE, &format!("{CSRF_COOKIE}=short"))])),
+            None
+        );
+    }
+}
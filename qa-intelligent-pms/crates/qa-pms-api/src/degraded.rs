@@ -0,0 +1,276 @@
+//! Degraded mode while the database is unreachable.
+//!
+//! If the database cannot be reached at startup, the server starts anyway
+//! and serves the endpoints that work without it: health, setup status and
+//! the `OpenAPI` document. Every other route answers 503. A background task
+//! reconnects with exponential backoff; once the full application has been
+//! built on the new pool it replaces the degraded one without a restart.
+//! Both are served behind the same CORS, compression and body size layers.
+
+use std::sync::{Arc, PoisonError, RwLock};
+use std::time::Duration;
+
+use axum::{
+    extract::{Request, State},
+    middleware::Next,
+    response::Response,
+    routing::get,
+    Json, Router,
+};
+use chrono::Utc;
+use qa_pms_config::settings::AuthSettings;
+use qa_pms_config::Settings;
+use qa_pms_core::error::ApiError;
+use tower::{service_fn, ServiceExt};
+use tracing::{info, warn};
+
+use crate::app;
+use crate::auth::{bearer_token, required_role, verify_token};
+use crate::routes::health::{DatabaseStatus, HealthResponse};
+use crate::routes::setup::{setup_status, SetupStatusResponse, SetupStore};
+
+/// First wait before reconnecting.
+const INITIAL_RETRY_DELAY: Duration = Duration::from_secs(1);
+
+/// The application currently being served, replaced on recovery.
+#[derive(Clone)]
+struct AppSwitch {
+    current: Arc<RwLock<Router>>,
+}
+
+impl AppSwitch {
+    fn new(router: Router) -> Self {
+        Self {
+            current: Arc::new(RwLock::new(router)),
+        }
+    }
+
+    fn replace(&self, router: Router) {
+        *self.current.write().unwrap_or_else(PoisonError::into_inner) = router;
+    }
+
+    /// A router handing every request to the current application.
+    fn into_router(self) -> Router {
+        Router::new().fallback_service(service_fn(move |request: Request| {
+            let app = self
+                .current
+                .read()
+                .unwrap_or_else(PoisonError::into_inner)
+                .clone();
+            app.oneshot(request)
+        }))
+    }
+}
+
+/// Why the database is unavailable.
+#[derive(Debug)]
+struct Outage {
+    error: String,
+    attempts: u32,
+}
+
+#[derive(Clone)]
+struct DegradedState {
+    auth: Option<AuthSettings>,
+    server_address: String,
+    setup_store: SetupStore,
+    outage: Arc<RwLock<Outage>>,
+}
+
+/// Serve the degraded application and keep reconnecting until the full one
+/// can replace it.
+pub async fn start(settings: Settings, error: &anyhow::Error) -> Router {
+    let error = format!("{error:#}");
+    warn!(error = %error, "Database unavailable; starting in degraded mode");
+    let outage = Arc::new(RwLock::new(Outage { error, attempts: 1 }));
+    let state = DegradedState {
+        auth: settings.auth.clone(),
+        server_address: settings.server_addr(),
+        setup_store: app::open_setup_store(&settings).await,
+        outage: Arc::clone(&outage),
+    };
+    // The full application brings its own outer layers, so only the
+    // degraded one is wrapped here rather than the switch
+    let switch = AppSwitch::new(app::with_outer_layers(router(state), &settings));
+    tokio::spawn(recover(settings, switch.clone(), outage));
+    switch.into_router()
+}
+
+/// Reconnect until the full application starts, then swap it in.
+async fn recover(settings: Settings, switch: AppSwitch, outage: Arc<RwLock<Outage>>) {
+    let max_delay = Duration::from_secs(settings.database.retry_max_secs.max(1));
+    let mut delay = INITIAL_RETRY_DELAY;
+    loop {
+        tokio::time::sleep(delay).await;
+        let started = match app::create_db_pool(&settings).await {
+            Ok(db) => app::create_app(settings.clone(), db).await,
+            Err(e) => Err(e),
+        };
+        match started {
+            Ok((full, health_scheduler)) => {
+                switch.replace(full);
+                if let Some(scheduler) = health_scheduler {
+                    scheduler.start();
+                }
+                info!("Database available; leaving degraded mode");
+                return;
+            }
+            Err(e) => {
+                let mut outage = outage.write().unwrap_or_else(PoisonError::into_inner);
+                outage.error = format!("{e:#}");
+                outage.attempts += 1;
+                warn!(
+                    error = %outage.error,
+                    attempts = outage.attempts,
+                    "Database still unavailable"
+                );
+            }
+        }
+        delay = next_delay(delay, max_delay);
+    }
+}
+
+/// Double the wait, up to `max`.
+fn next_delay(delay: Duration, max: Duration) -> Duration {
+    delay.saturating_mul(2).min(max)
+}
+
+fn router(state: DegradedState) -> Router {
+    Router::new()
+        .route("/api/v1/health", get(health_check))
+        .route("/api/v1/setup/status", get(get_status))
+        .route("/api/v1/openapi.json", get(crate::routes::openapi_json))
+        .route_layer(axum::middleware::from_fn_with_state(
+            state.clone(),
+            authorize,
+        ))
+        .fallback(unavailable)
+        .with_state(state)
+}
+
+/// Enforce route roles from the token alone, as users cannot be looked up.
+async fn authorize(
+    State(state): State<DegradedState>,
+    request: Request,
+    next: Next,
+) -> Result<Response, ApiError> {
+    if let (Some(auth), Some(required)) = (&state.auth, required_role(request.uri().path())) {
+        let token = bearer_token(&request)
+            .ok_or_else(|| ApiError::Unauthorized("Authentication required".into()))?;
+        if !verify_token(auth, &token)?.role.satisfies(required) {
+            return Err(ApiError::Forbidden(format!(
+                "This action requires the {} role",
+                required.as_str()
+            )));
+        }
+    }
+    Ok(next.run(request).await)
+}
+
+async fn health_check(State(state): State<DegradedState>) -> Json<HealthResponse> {
+    let outage = state.outage.read().unwrap_or_else(PoisonError::into_inner);
+    Json(HealthResponse {
+        status: "degraded".to_string(),
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        timestamp: Utc::now(),
+        database: DatabaseStatus {
+            connected: false,
+            response_time_ms: None,
+            error: Some(outage.error.clone()),
+            reconnect_attempts: Some(outage.attempts),
+        },
+    })
+}
+
+async fn get_status(State(state): State<DegradedState>) -> Json<SetupStatusResponse> {
+    Json(setup_status(&state.setup_store, state.server_address).await)
+}
+
+async fn unavailable() -> ApiError {
+    ApiError::ServiceUnavailable(
+        "The database is unavailable; only health, setup status and API docs are served".into(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::{to_bytes, Body};
+    use axum::http::StatusCode;
+    use secrecy::SecretString;
+
+    use crate::routes::setup::create_setup_store;
+
+    async fn degraded(auth: Option<AuthSettings>) -> Router {
+        router(DegradedState {
+            auth,
+            server_address: "127.0.0.1:3000".to_string(),
+            setup_store: create_setup_store().await,
+            outage: Arc::new(RwLock::new(Outage {
+                error: "Connection refused".to_string(),
+                attempts: 3,
+            })),
+        })
+    }
+
+    async fn fetch(app: Router, path: &str) -> (StatusCode, serde_json::Value) {
+        let request = Request::get(path).body(Body::empty()).unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        let status = response.status();
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, serde_json::from_slice(&body).unwrap_or_default())
+    }
+
+    #[tokio::test]
+    async fn test_degraded_routes() {
+        let app = degraded(None).await;
+
+        let (status, health) = fetch(app.clone(), "/api/v1/health").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(health["status"], "degraded");
+        assert_eq!(health["database"]["connected"], false);
+        assert_eq!(health["database"]["reconnectAttempts"], 3);
+
+        let (status, setup) = fetch(app.clone(), "/api/v1/setup/status").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(setup["complete"], false);
+
+        let (status, _) = fetch(app, "/api/v1/workflows").await;
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    }
+
+    #[tokio::test]
+    async fn test_setup_status_requires_admin_token_with_auth() {
+        let auth = AuthSettings {
+            jwt_secret: SecretString::from("0123456789abcdef0123456789abcdef".to_string()),
+            token_ttl_secs: 3600,
+            admin_email: None,
+            admin_password: None,
+        };
+        let app = degraded(Some(auth)).await;
+
+        let (status, _) = fetch(app.clone(), "/api/v1/setup/status").await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        let (status, _) = fetch(app, "/api/v1/health").await;
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_switch_serves_replacement() {
+        let switch = AppSwitch::new(degraded(None).await);
+        let served = switch.clone().into_router();
+        let (status, _) = fetch(served.clone(), "/api/v1/workflows").await;
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+
+        switch.replace(Router::new().route("/api/v1/workflows", get(|| async { "[]" })));
+        let (status, _) = fetch(served, "/api/v1/workflows").await;
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    #[test]
+    fn test_retry_delay_doubles_up_to_max() {
+        let max = Duration::from_secs(60);
+        assert_eq!(next_delay(INITIAL_RETRY_DELAY, max), Duration::from_secs(2));
+        assert_eq!(next_delay(Duration::from_secs(40), max), max);
+    }
+}
@@ -0,0 +1,407 @@
+//! Daily digest email.
+//!
+//! Summarizes what needs attention: unread alerts, patterns detected in the
+//! last day, integration health and the dashboard KPIs for the day. A cron
+//! schedule mails it to the configured recipients; the notifications API can
+//! render or send it on demand.
+
+use std::fmt::Write;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use cron::Schedule;
+use qa_pms_config::settings::DigestSettings;
+use qa_pms_core::health::{HealthStatus, IntegrationHealth};
+use qa_pms_core::HealthStore;
+use qa_pms_patterns::{Alert, DetectedPattern, PatternRepository};
+use sqlx::PgPool;
+use tracing::{info, warn};
+
+use crate::mailer::Mailer;
+use crate::period::rolling_boundaries;
+use crate::report_export::format_long_duration;
+use crate::routes::dashboard::{calculate_kpis, DashboardKPIs, KPIMetric};
+use crate::routes::tickets::html_escape;
+
+/// Days covered by the patterns and KPIs of a digest.
+const DIGEST_PERIOD_DAYS: i64 = 1;
+
+/// Most unread alerts listed; the rest are only counted.
+const MAX_DIGEST_ALERTS: usize = 20;
+
+/// Contents of one digest.
+pub struct Digest {
+    /// When the digest was built
+    pub generated_at: DateTime<Utc>,
+    /// Unread, undismissed alerts, most severe first
+    pub alerts: Vec<Alert>,
+    /// Patterns detected during the period
+    pub patterns: Vec<DetectedPattern>,
+    /// Latest status per integration
+    pub health: Vec<IntegrationHealth>,
+    /// KPIs for the period, compared with the one before
+    pub kpis: DashboardKPIs,
+}
+
+impl Digest {
+    /// Gather the digest contents.
+    ///
+    /// # Errors
+    /// Returns error if alerts, patterns or KPIs cannot be loaded.
+    pub async fn load(db: &PgPool, health_store: &HealthStore) -> Result<Self> {
+        let generated_at = Utc::now();
+        let repo = PatternRepository::new(db.clone());
+        let alerts = repo
+            .get_unread_alerts()
+            .await
+            .context("Failed to load unread alerts")?;
+        let patterns = repo
+            .get_patterns_since(generated_at - Duration::days(DIGEST_PERIOD_DAYS))
+            .await
+            .context("Failed to load detected patterns")?;
+        let kpis = calculate_kpis(db, &rolling_boundaries(generated_at, DIGEST_PERIOD_DAYS), None, None)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to calculate KPIs: {e}"))?;
+        let mut health = health_store.get_all().await;
+        health.sort_by(|a, b| a.integration.cmp(&b.integration));
+
+        Ok(Self {
+            generated_at,
+            alerts,
+            patterns,
+            health,
+            kpis,
+        })
+    }
+
+    /// Email subject.
+    pub fn subject(&self) -> String {
+        format!(
+            "QA digest for {}: {} unread alert(s)",
+            self.generated_at.format("%Y-%m-%d"),
+            self.alerts.len()
+        )
+    }
+
+    /// Plain-text body.
+    pub fn render_text(&self) -> String {
+        let mut out = String::new();
+        let _ = writeln!(out, "{}\n", self.subject());
+
+        out.push_str("Key metrics (last 24h)\n");
+        for (label, value, metric) in self.kpi_rows() {
+            let _ = writeln!(out, "- {label}: {value} ({})", change(metric));
+        }
+
+        let _ = writeln!(out, "\nUnread alerts ({})", self.alerts.len());
+        if self.alerts.is_empty() {
+            out.push_str("- None\n");
+        }
+        for alert in self.alerts.iter().take(MAX_DIGEST_ALERTS) {
+            let _ = writeln!(out, "- [{}] {}", alert.severity, alert.title);
+        }
+        if let Some(more) = self.more_alerts() {
+            let _ = writeln!(out, "- ...and {more} more");
+        }
+
+        let _ = writeln!(out, "\nPatterns detected ({})", self.patterns.len());
+        if self.patterns.is_empty() {
+            out.push_str("- None\n");
+        }
+        for pattern in &self.patterns {
+            let _ = writeln!(out, "- [{}] {}", pattern.severity, pattern.title);
+        }
+
+        out.push_str("\nIntegration health\n");
+        if self.health.is_empty() {
+            out.push_str("- No integrations checked yet\n");
+        }
+        for health in &self.health {
+            let _ = write!(
+                out,
+                "- {}: {}",
+                health.integration,
+                status_label(health.status)
+            );
+            if let Some(error) = &health.error_message {
+                let _ = write!(out, " ({error})");
+            }
+            out.push('\n');
+        }
+        out
+    }
+
+    /// HTML body.
+    pub fn render_html(&self) -> String {
+        let subject = html_escape(&self.subject());
+        let mut out = String::new();
+        let _ = write!(
+            out,
+            "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>{subject}</title>\n</head>\n\
+             <body style=\"font-family:sans-serif;max-width:720px\">\n<h1>{subject}</h1>\n"
+        );
+
+        out.push_str("<h2>Key metrics (last 24h)</h2>\n<ul>\n");
+        for (label, value, metric) in self.kpi_rows() {
+            let _ = writeln!(
+                out,
+                "<li><strong>{label}:</strong> {} ({})</li>",
+                html_escape(&value),
+                change(metric)
+            );
+        }
+        out.push_str("</ul>\n");
+
+        let _ = writeln!(out, "<h2>Unread alerts ({})</h2>", self.alerts.len());
+        if self.alerts.is_empty() {
+            out.push_str("<p>None</p>\n");
+        } else {
+            out.push_str("<ul>\n");
+            for alert in self.alerts.iter().take(MAX_DIGEST_ALERTS) {
+                let _ = writeln!(
+                    out,
+                    "<li><strong>{}</strong> {}</li>",
+                    alert.severity,
+                    html_escape(&alert.title)
+                );
+            }
+            if let Some(more) = self.more_alerts() {
+                let _ = writeln!(out, "<li>...and {more} more</li>");
+            }
+            out.push_str("</ul>\n");
+        }
+
+        let _ = writeln!(out, "<h2>Patterns detected ({})</h2>", self.patterns.len());
+        if self.patterns.is_empty() {
+            out.push_str("<p>None</p>\n");
+        } else {
+            out.push_str("<ul>\n");
+            for pattern in &self.patterns {
+                let _ = writeln!(
+                    out,
+                    "<li><strong>{}</strong> {}</li>",
+                    pattern.severity,
+                    html_escape(&pattern.title)
+                );
+            }
+            out.push_str("</ul>\n");
+        }
+
+        out.push_str("<h2>Integration health</h2>\n");
+        if self.health.is_empty() {
+            out.push_str("<p>No integrations checked yet</p>\n");
+        } else {
+            out.push_str("<ul>\n");
+            for health in &self.health {
+                let _ = write!(
+                    out,
+                    "<li><strong>{}:</strong> {}",
+                    html_escape(&health.integration),
+                    status_label(health.status)
+                );
+                if let Some(error) = &health.error_message {
+                    let _ = write!(out, " ({})", html_escape(error));
+                }
+                out.push_str("</li>\n");
+            }
+            out.push_str("</ul>\n");
+        }
+        out.push_str("</body>\n</html>\n");
+        out
+    }
+
+    fn kpi_rows(&self) -> [(&'static str, String, &KPIMetric); 4] {
+        let kpis = &self.kpis;
+        [
+            (
+                "Tickets completed",
+                format!("{:.0}", kpis.tickets_completed.value),
+                &kpis.tickets_completed,
+            ),
+            (
+                "Average time per ticket",
+                format_long_duration(kpis.avg_time_per_ticket.value.round() as i64),
+                &kpis.avg_time_per_ticket,
+            ),
+            (
+                "Efficiency",
+                format!("{:.0}%", kpis.efficiency.value),
+                &kpis.efficiency,
+            ),
+            (
+                "Hours logged",
+                format!("{:.1}", kpis.total_hours.value),
+                &kpis.total_hours,
+            ),
+        ]
+    }
+
+    fn more_alerts(&self) -> Option<usize> {
+        self.alerts
+            .len()
+            .checked_sub(MAX_DIGEST_ALERTS)
+            .filter(|more| *more > 0)
+    }
+}
+
+fn change(metric: &KPIMetric) -> String {
+    format!("{:+.0}% vs previous day", metric.change)
+}
+
+const fn status_label(status: HealthStatus) -> &'static str {
+    match status {
+        HealthStatus::Online => "online",
+        HealthStatus::Degraded => "degraded",
+        HealthStatus::Offline => "offline",
+    }
+}
+
+/// Cron-driven digest mailer.
+pub struct DigestScheduler {
+    db: PgPool,
+    health_store: Arc<HealthStore>,
+    mailer: Mailer,
+    schedule: Schedule,
+    recipients: Vec<String>,
+}
+
+impl DigestScheduler {
+    /// Create a scheduler from settings.
+    ///
+    /// # Errors
+    /// Returns error if the cron expression is invalid.
+    pub fn new(
+        db: PgPool,
+        health_store: Arc<HealthStore>,
+        mailer: Mailer,
+        settings: &DigestSettings,
+    ) -> Result<Self> {
+        let schedule = Schedule::from_str(&settings.cron)
+            .with_context(|| format!("Invalid DIGEST_CRON: {}", settings.cron))?;
+        Ok(Self {
+            db,
+            health_store,
+            mailer,
+            schedule,
+            recipients: settings.recipients.clone(),
+        })
+    }
+
+    /// Build the digest and mail it.
+    ///
+    /// # Errors
+    /// Returns error if the digest cannot be loaded or delivered.
+    pub async fn run_once(&self) -> Result<Digest> {
+        let digest = Digest::load(&self.db, &self.health_store).await?;
+        self.mailer
+            .send(
+                &self.recipients,
+                &digest.subject(),
+                digest.render_text(),
+                digest.render_html(),
+            )
+            .await?;
+        info!(
+            alerts = digest.alerts.len(),
+            patterns = digest.patterns.len(),
+            recipients = self.recipients.len(),
+            "Digest delivered"
+        );
+        Ok(digest)
+    }
+
+    /// Run on the schedule in the background.
+    pub fn start(self) {
+        tokio::spawn(async move {
+            info!(
+                recipients = self.recipients.len(),
+                "Digest scheduler started"
+            );
+            while let Some(next) = self.schedule.upcoming(Utc).next() {
+                let wait = (next - Utc::now()).to_std().unwrap_or_default();
+                tokio::time::sleep(wait).await;
+                if let Err(e) = self.run_once().await {
+                    warn!(error = %e, "Scheduled digest failed");
+                }
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use qa_pms_patterns::{PatternType, Severity};
+
+    fn metric(value: f64, change: f64) -> KPIMetric {
+        KPIMetric {
+            value,
+            change,
+            trend: "up".to_string(),
+            target: None,
+            target_met: None,
+        }
+    }
+
+    fn sample(alerts: usize) -> Digest {
+        let now = Utc::now();
+        Digest {
+            generated_at: now,
+            alerts: (0..alerts)
+                .map(|i| Alert {
+                    id: uuid::Uuid::new_v4(),
+                    pattern_id: None,
+                    alert_type: PatternType::Spike,
+                    severity: Severity::Warning,
+                    title: format!("Alert <{i}>"),
+                    message: None,
+                    affected_tickets: Vec::new(),
+                    suggested_actions: Vec::new(),
+                    is_read: false,
+                    is_dismissed: false,
+                    dismissed_at: None,
+                    dismissed_by: None,
+                    created_at: now,
+                    tenant_id: "default".to_string(),
+                })
+                .collect(),
+            patterns: Vec::new(),
+            health: vec![IntegrationHealth {
+                error_message: Some("timeout".to_string()),
+                ..IntegrationHealth::new("jira")
+            }],
+            kpis: DashboardKPIs {
+                tickets_completed: metric(4.0, 33.0),
+                avg_time_per_ticket: metric(5400.0, -10.0),
+                efficiency: metric(92.0, 0.0),
+                total_hours: metric(6.0, 20.0),
+            },
+        }
+    }
+
+    #[test]
+    fn test_digest_lists_alerts_health_and_kpis() {
+        let digest = sample(2);
+        let text = digest.render_text();
+        assert!(text.contains("Unread alerts (2)"));
+        assert!(text.contains("- [warning] Alert <1>"));
+        assert!(text.contains("- jira: offline (timeout)"));
+        assert!(text.contains("Average time per ticket: 1h 30m (-10% vs previous day)"));
+        assert!(text.contains("Patterns detected (0)\n- None"));
+
+        let html = digest.render_html();
+        assert!(html.contains("Alert &lt;1&gt;"));
+        assert!(!html.contains("Alert <1>"));
+    }
+
+    #[test]
+    fn test_digest_caps_listed_alerts() {
+        let digest = sample(MAX_DIGEST_ALERTS + 3);
+        let text = digest.render_text();
+        assert!(text.contains("...and 3 more"));
+        assert_eq!(text.matches("[warning]").count(), MAX_DIGEST_ALERTS);
+        assert!(sample(1).more_alerts().is_none());
+    }
+}
@@ -0,0 +1,287 @@
+//! Automatic error log capture.
+//!
+//! The [`capture`] middleware stores a support error log entry for every
+//! request that ends in a 5xx response: the route it hit, its request ID, the
+//! integration behind the route and the error class. Entries are written
+//! through [`SupportRepository::create_or_increment_error`], so repeats of
+//! one failure add to a single entry's count. To keep an outage from flooding
+//! the log, failures are sampled per route and error class as configured in
+//! [`ErrorCaptureSettings`].
+//!
+//! Every response carries `X-Request-Id`, echoing the client's own when it
+//! sent one, so a captured entry can be matched to the call that failed.
+//! Messages pass through the configured [`Redactor`](crate::redact::Redactor),
+//! and a request body sampled by the request log is kept in the entry's
+//! context.
+
+use std::collections::HashMap;
+use std::sync::{Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+use axum::{
+    extract::{MatchedPath, Request, State},
+    http::{HeaderName, HeaderValue, StatusCode},
+    middleware::Next,
+    response::Response,
+};
+use qa_pms_config::settings::ErrorCaptureSettings;
+use qa_pms_core::ErrorDetail;
+use qa_pms_support::{CreateErrorLogInput, ErrorSeverity, ErrorSource, SupportRepository};
+use serde_json::json;
+use tracing::warn;
+use uuid::Uuid;
+
+use crate::app::AppState;
+use crate::auth::{is_under, CurrentUser};
+use crate::http_log::LoggedBody;
+use crate::rate_limit::Integration;
+use crate::search_index::IndexDocument;
+
+/// Request ID, taken from the request or assigned, echoed on the response.
+pub const REQUEST_ID_HEADER: HeaderName = HeaderName::from_static("x-request-id");
+
+/// Longest client-sent request ID kept; longer ones are replaced.
+const MAX_REQUEST_ID_LEN: usize = 128;
+
+/// Period over which `max_per_minute` applies.
+const CAPTURE_WINDOW: Duration = Duration::from_secs(60);
+
+/// Groups tracked before those outside the current window are dropped.
+const MAX_TRACKED_GROUPS: usize = 1_000;
+
+/// Integrations without a rate limit of their own, by API prefix.
+const OTHER_INTEGRATIONS: &[(&str, &str)] = &[("/api/v1/splunk", "splunk"), ("/api/v1/ai", "ai")];
+
+/// Decides which failures are stored.
+#[derive(Debug)]
+pub struct ErrorSampler {
+    settings: ErrorCaptureSettings,
+    groups: Mutex<HashMap<String, CaptureGroup>>,
+}
+
+/// Failures of one route and error class.
+#[derive(Debug)]
+struct CaptureGroup {
+    window_start: Instant,
+    stored_in_window: u32,
+    seen: u64,
+}
+
+impl ErrorSampler {
+    /// Create a sampler with the given settings.
+    #[must_use]
+    pub fn new(settings: ErrorCaptureSettings) -> Self {
+        Self {
+            settings,
+            groups: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Whether to store a failure of the given group, counting it either way.
+    ///
+    /// With a sample rate of `r`, a failure is stored each time the running
+    /// total of `r` per failure passes a whole number, so one in every `1/r`
+    /// failures of a group is kept.
+    fn admit(&self, group: &str, now: Instant) -> bool {
+        if !self.settings.enabled {
+            return false;
+        }
+        let mut groups = self.groups.lock().unwrap_or_else(PoisonError::into_inner);
+        if groups.len() >= MAX_TRACKED_GROUPS && !groups.contains_key(group) {
+            groups.retain(|_, g| now.duration_since(g.window_start) < CAPTURE_WINDOW);
+        }
+        let entry = groups.entry(group.to_string()).or_insert(CaptureGroup {
+            window_start: now,
+            stored_in_window: 0,
+            seen: 0,
+        });
+        if now.duration_since(entry.window_start) >= CAPTURE_WINDOW {
+            entry.window_start = now;
+            entry.stored_in_window = 0;
+        }
+        entry.seen += 1;
+
+        let sampled = is_sampled(entry.seen, self.settings.sample_rate);
+        let capped = self.settings.max_per_minute > 0
+            && entry.stored_in_window >= self.settings.max_per_minute;
+        if !sampled || capped {
+            return false;
+        }
+        entry.stored_in_window += 1;
+        true
+    }
+}
+
+/// Whether the `seen`th event is kept at the given sample rate: it is when
+/// the running total of `rate` per event passes a whole number.
+pub(crate) fn is_sampled(seen: u64, rate: f64) -> bool {
+    (seen as f64 * rate).floor() > (seen.saturating_sub(1) as f64 * rate).floor()
+}
+
+/// Request ID of a request, from its header or newly assigned, set on the
+/// request so inner layers and handlers see the same one.
+pub(crate) fn ensure_request_id(request: &mut Request) -> String {
+    let request_id = request
+        .headers()
+        .get(&REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .filter(|id| !id.is_empty() && id.len() <= MAX_REQUEST_ID_LEN)
+        .map_or_else(|| Uuid::new_v4().to_string(), str::to_string);
+    if let Ok(value) = HeaderValue::from_str(&request_id) {
+        request.headers_mut().insert(REQUEST_ID_HEADER, value);
+    }
+    request_id
+}
+
+/// Echo the request ID on the response.
+pub(crate) fn echo_request_id(response: &mut Response, request_id: &str) {
+    if let Ok(value) = HeaderValue::from_str(request_id) {
+        response.headers_mut().insert(REQUEST_ID_HEADER, value);
+    }
+}
+
+/// Store failed requests in the support error log.
+///
+/// Runs inside authentication so entries name the signed-in user. Entries
+/// are written in the background; failing to store one is logged and does
+/// not affect the response.
+pub async fn capture(State(state): State<AppState>, mut request: Request, next: Next) -> Response {
+    let request_id = ensure_request_id(&mut request);
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string());
+    let method = request.method().clone();
+    let path = request.uri().path().to_string();
+    let user = request.extensions().get::<CurrentUser>().cloned();
+    let body = request.extensions().get::<LoggedBody>().map(|body| body.0.clone());
+
+    let mut response = next.run(request).await;
+    echo_request_id(&mut response, &request_id);
+    if !response.status().is_server_error() {
+        return response;
+    }
+
+    let route = route.unwrap_or_else(|| path.clone());
+    let detail = response.extensions().get::<ErrorDetail>().cloned();
+    let error_class = error_class(detail.as_ref(), response.status());
+    if !state.error_sampler.admit(&format!("{route} {error_class}"), Instant::now()) {
+        return response;
+    }
+
+    let integration = integration_for(&path);
+    let redactor = state.http_logger.redactor();
+    let input = CreateErrorLogInput {
+        message: detail.map_or_else(
+            || format!("{method} {route} failed with {}", response.status()),
+            |detail| redactor.scrub(&detail.message),
+        ),
+        stack_trace: None,
+        severity: severity_of(response.status()),
+        source: if integration.is_some() || error_class == "EXTERNAL_SERVICE_ERROR" {
+            ErrorSource::Integration
+        } else {
+            ErrorSource::Backend
+        },
+        user_id: user
+            .as_ref()
+            .filter(|user| !user.is_local())
+            .map(|user| user.id.0),
+        session_id: None,
+        page_url: None,
+        action: Some(format!("{method} {route}")),
+        browser_info: None,
+        device_info: None,
+        context: json!({
+            "route": route,
+            "path": path,
+            "requestId": request_id,
+            "status": response.status().as_u16(),
+            "errorClass": error_class,
+            "integration": integration,
+            "requestBody": body,
+        }),
+    };
+    tokio::spawn(async move {
+        let repo = SupportRepository::new(state.db.clone());
+        match repo.create_or_increment_error(input).await {
+            Ok(error) => state.search_index.upsert([IndexDocument::error_log(&error)]),
+            Err(e) => warn!(error = %e, request_id = %request_id, "Failed to capture error log"),
+        }
+    });
+    response
+}
+
+/// Error code of the response, or `HTTP_<status>` when it has none.
+fn error_class(detail: Option<&ErrorDetail>, status: StatusCode) -> String {
+    detail.map_or_else(|| format!("HTTP_{}", status.as_u16()), |detail| detail.code.to_string())
+}
+
+/// Integration an API path calls, if any.
+fn integration_for(path: &str) -> Option<&'static str> {
+    Integration::for_path(path).first().copied().map(Integration::as_str).or_else(|| {
+        OTHER_INTEGRATIONS
+            .iter()
+            .find(|(prefix, _)| is_under(path, prefix))
+            .map(|(_, name)| *name)
+    })
+}
+
+/// Internal failures are high; an unreachable or failing upstream is medium.
+fn severity_of(status: StatusCode) -> ErrorSeverity {
+    match status {
+        StatusCode::BAD_GATEWAY | StatusCode::SERVICE_UNAVAILABLE | StatusCode::GATEWAY_TIMEOUT => {
+            ErrorSeverity::Medium
+        }
+        _ => ErrorSeverity::High,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sampler(sample_rate: f64, max_per_minute: u32) -> ErrorSampler {
+        ErrorSampler::new(ErrorCaptureSettings {
+            enabled: true,
+            sample_rate,
+            max_per_minute,
+        })
+    }
+
+    #[test]
+    fn test_cap_applies_per_group_and_window() {
+        let sampler = sampler(1.0, 2);
+        let now = Instant::now();
+        assert!(sampler.admit("GET /a INTERNAL_ERROR", now));
+        assert!(sampler.admit("GET /a INTERNAL_ERROR", now));
+        assert!(!sampler.admit("GET /a INTERNAL_ERROR", now));
+        assert!(sampler.admit("GET /b INTERNAL_ERROR", now));
+        assert!(sampler.admit("GET /a INTERNAL_ERROR", now + CAPTURE_WINDOW));
+    }
+
+    #[test]
+    fn test_sample_rate_keeps_a_share_of_failures() {
+        let sampler = sampler(0.25, 0);
+        let now = Instant::now();
+        let stored = (0..100).filter(|_| sampler.admit("GET /a HTTP_500", now)).count();
+        assert_eq!(stored, 25);
+
+        assert!(!self::sampler(0.0, 0).admit("GET /a HTTP_500", now));
+        let disabled = ErrorSampler::new(ErrorCaptureSettings {
+            enabled: false,
+            ..ErrorCaptureSettings::default()
+        });
+        assert!(!disabled.admit("GET /a HTTP_500", now));
+    }
+
+    #[test]
+    fn test_integration_and_severity_of_failures() {
+        assert_eq!(integration_for("/api/v1/tickets/PROJ-1"), Some("jira"));
+        assert_eq!(integration_for("/api/v1/splunk/query"), Some("splunk"));
+        assert_eq!(integration_for("/api/v1/workflows"), None);
+        assert_eq!(severity_of(StatusCode::BAD_GATEWAY), ErrorSeverity::Medium);
+        assert_eq!(severity_of(StatusCode::INTERNAL_SERVER_ERROR), ErrorSeverity::High);
+        assert_eq!(error_class(None, StatusCode::INTERNAL_SERVER_ERROR), "HTTP_500");
+    }
+}
@@ -0,0 +1,320 @@
+//! GraphQL schema over the existing services.
+//!
+//! Exposes tickets, workflows, time summaries and dashboard metrics so the
+//! frontend can fetch nested data in a single round trip. Resolvers reuse the
+//! same repositories and helpers as the REST handlers.
+
+use async_graphql::{
+    ComplexObject, Context, EmptyMutation, EmptySubscription, ErrorExtensions, Object, Schema,
+    SimpleObject,
+};
+use chrono::NaiveDate;
+use uuid::Uuid;
+
+use qa_pms_core::error::ApiError;
+use qa_pms_dashboard::KpiTargetRepository;
+use qa_pms_jira::TicketFilters;
+use qa_pms_workflow::{
+    get_active_workflow, get_all_templates, get_all_user_active_workflows, get_instance,
+    get_instance_template, get_step_results, TemplateSummary, WorkflowInstance,
+};
+
+use crate::app::AppState;
+use crate::auth::CurrentUser;
+use crate::period::{period_boundaries, PeriodQuery};
+use crate::routes::dashboard::{
+    calculate_kpis, get_recent_activity, get_trend_data, DashboardResponse, TeamQuery,
+};
+use crate::routes::projects::ProjectQuery;
+use crate::routes::tickets::{get_jira_client, TicketSummary};
+use crate::routes::time::{TimeSessionResponse, TimeSessionsResponse};
+use crate::routes::workflows::{steps_with_status, TemplateResponse, WorkflowStepWithStatus};
+
+/// The application GraphQL schema (read-only).
+pub type AppSchema = Schema<QueryRoot, EmptyMutation, EmptySubscription>;
+
+/// Build the GraphQL schema.
+///
+/// Per-request `AppState` and `CurrentUser` are injected as request data by
+/// the handler.
+#[must_use]
+pub fn build_schema() -> AppSchema {
+    Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .limit_depth(10)
+        .finish()
+}
+
+/// Convert an `ApiError` into a GraphQL error carrying the same error code.
+fn gql_err(err: ApiError) -> async_graphql::Error {
+    let code = err.code();
+    async_graphql::Error::new(err.to_string()).extend_with(|_, e| e.set("code", code))
+}
+
+fn db_err(err: sqlx::Error) -> async_graphql::Error {
+    gql_err(ApiError::Internal(err.into()))
+}
+
+/// Tenant of the caller; workflows of other tenants are never returned.
+fn tenant<'a>(ctx: &Context<'a>) -> async_graphql::Result<&'a str> {
+    Ok(ctx.data::<CurrentUser>()?.tenant.as_str())
+}
+
+// ============================================================================
+// Object Types
+// ============================================================================
+
+/// A page of Jira tickets.
+#[derive(SimpleObject)]
+pub struct TicketPage {
+    pub tickets: Vec<TicketSummary>,
+    pub total: u32,
+    pub page: u32,
+    pub page_size: u32,
+    pub has_more: bool,
+}
+
+#[ComplexObject]
+impl TicketSummary {
+    /// The active or paused workflow for this ticket, if any.
+    async fn active_workflow(&self, ctx: &Context<'_>) -> async_graphql::Result<Option<WorkflowNode>> {
+        let state = ctx.data::<AppState>()?;
+        let instance = get_active_workflow(&state.db, &self.key, Some(tenant(ctx)?))
+            .await
+            .map_err(db_err)?;
+        Ok(instance.map(WorkflowNode))
+    }
+}
+
+/// A workflow instance with lazily resolved nested data.
+pub struct WorkflowNode(WorkflowInstance);
+
+#[Object(name = "Workflow")]
+impl WorkflowNode {
+    async fn id(&self) -> Uuid {
+        self.0.id
+    }
+
+    async fn ticket_id(&self) -> &str {
+        &self.0.ticket_id
+    }
+
+    async fn user_id(&self) -> &str {
+        &self.0.user_id
+    }
+
+    async fn status(&self) -> &str {
+        &self.0.status
+    }
+
+    async fn current_step(&self) -> i32 {
+        self.0.current_step
+    }
+
+    async fn started_at(&self) -> String {
+        self.0.started_at.to_rfc3339()
+    }
+
+    async fn completed_at(&self) -> Option<String> {
+        self.0.completed_at.map(|t| t.to_rfc3339())
+    }
+
+    /// The template this workflow was started from.
+    async fn template(&self, ctx: &Context<'_>) -> async_graphql::Result<Option<TemplateResponse>> {
+        let state = ctx.data::<AppState>()?;
+        let template = get_instance_template(&state.db, &self.0)
+            .await
+            .map_err(db_err)?;
+        Ok(template.map(|t| TemplateSummary::from(&t).into()))
+    }
+
+    /// Template steps merged with their recorded results.
+    async fn steps(&self, ctx: &Context<'_>) -> async_graphql::Result<Vec<WorkflowStepWithStatus>> {
+        let state = ctx.data::<AppState>()?;
+        let Some(template) = get_instance_template(&state.db, &self.0)
+            .await
+            .map_err(db_err)?
+        else {
+            return Ok(Vec::new());
+        };
+        let step_results = get_step_results(&state.db, self.0.id)
+            .await
+            .map_err(db_err)?;
+        Ok(steps_with_status(&template, &step_results))
+    }
+
+    /// Time sessions recorded for this workflow.
+    async fn time_summary(&self, ctx: &Context<'_>) -> async_graphql::Result<TimeSessionsResponse> {
+        let state = ctx.data::<AppState>()?;
+        let sessions = qa_pms_time::get_workflow_sessions(&state.db, self.0.id)
+            .await
+            .map_err(db_err)?;
+        let total_seconds = sessions.iter().map(|s| s.total_seconds).sum();
+        Ok(TimeSessionsResponse {
+            sessions: sessions.into_iter().map(TimeSessionResponse::from).collect(),
+            total_seconds,
+        })
+    }
+}
+
+// ============================================================================
+// Query Root
+// ============================================================================
+
+/// Root query type.
+pub struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    /// List Jira tickets with optional filters.
+    async fn tickets(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default)] statuses: Vec<String>,
+        assignee: Option<String>,
+        project: Option<String>,
+        #[graphql(default = 1)] page: u32,
+        #[graphql(default = 20)] page_size: u32,
+    ) -> async_graphql::Result<TicketPage> {
+        let state = ctx.data::<AppState>()?;
+        let user = ctx.data::<CurrentUser>()?;
+        let jira_client = get_jira_client(state, &user.tenant)
+            .await
+            .map_err(gql_err)?;
+
+        let page = page.max(1);
+        let page_size = page_size.min(100);
+        let start_at = (page - 1) * page_size;
+
+        let filters = TicketFilters {
+            statuses,
+            assignee,
+            project,
+            sprint: None,
+            jql: None,
+            raw_jql: None,
+        };
+
+        let search = state
+            .ticket_cache
+            .list_tickets(&jira_client, &filters, start_at, page_size)
+            .await
+            .map_err(|e| gql_err(ApiError::ServiceUnavailable(format!("Jira error: {e}"))))?;
+        let response = search.response;
+
+        Ok(TicketPage {
+            tickets: response.issues.iter().cloned().map(TicketSummary::from).collect(),
+            total: response.total,
+            page,
+            page_size,
+            has_more: start_at + page_size < response.total,
+        })
+    }
+
+    /// Get a workflow instance by ID.
+    async fn workflow(&self, ctx: &Context<'_>, id: Uuid) -> async_graphql::Result<Option<WorkflowNode>> {
+        let state = ctx.data::<AppState>()?;
+        let tenant = tenant(ctx)?;
+        let instance = get_instance(&state.db, id).await.map_err(db_err)?;
+        Ok(instance
+            .filter(|instance| instance.tenant_id == tenant)
+            .map(WorkflowNode))
+    }
+
+    /// Active and paused workflows for a user.
+    async fn user_workflows(
+        &self,
+        ctx: &Context<'_>,
+        user_id: String,
+    ) -> async_graphql::Result<Vec<WorkflowNode>> {
+        let state = ctx.data::<AppState>()?;
+        let tenant = tenant(ctx)?;
+        let instances = get_all_user_active_workflows(&state.db, &user_id)
+            .await
+            .map_err(db_err)?;
+        Ok(instances
+            .into_iter()
+            .filter(|instance| instance.tenant_id == tenant)
+            .map(WorkflowNode)
+            .collect())
+    }
+
+    /// All workflow templates.
+    async fn workflow_templates(&self, ctx: &Context<'_>) -> async_graphql::Result<Vec<TemplateResponse>> {
+        let state = ctx.data::<AppState>()?;
+        let templates = get_all_templates(&state.db).await.map_err(db_err)?;
+        Ok(templates
+            .iter()
+            .map(|t| TemplateSummary::from(t).into())
+            .collect())
+    }
+
+    /// Dashboard KPIs, trend and recent activity for a period (7d, 30d, 90d,
+    /// 1y) or the days `from`..`to`, counted in timezone `tz`, with the KPI
+    /// targets of `team`, optionally for one project's tickets.
+    #[allow(clippy::too_many_arguments)] // one per GraphQL argument
+    async fn dashboard(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default_with = "String::from(\"30d\")")] period: String,
+        from: Option<NaiveDate>,
+        to: Option<NaiveDate>,
+        tz: Option<String>,
+        team: Option<String>,
+        project_id: Option<Uuid>,
+    ) -> async_graphql::Result<DashboardResponse> {
+        let state = ctx.data::<AppState>()?;
+        let period = PeriodQuery { period, from, to, tz }
+            .resolve()
+            .map_err(async_graphql::Error::new)?;
+        let project = ProjectQuery { project_id }.resolve(state).await.map_err(gql_err)?;
+        let project = project.as_ref().map(|p| p.key.as_str());
+        let tenant = Some(tenant(ctx)?);
+
+        let mut kpis = calculate_kpis(&state.db, &period_boundaries(&period), tenant, project)
+            .await
+            .map_err(gql_err)?;
+        let targets = KpiTargetRepository::new(state.db.clone())
+            .list(Some(TeamQuery { team }.team()))
+            .await
+            .map_err(db_err)?;
+        kpis.apply_targets(&targets);
+        let trend = get_trend_data(&state.db, &period, tenant, project).await.map_err(gql_err)?;
+        let recent_activity = get_recent_activity(&state.db, 10, tenant, project)
+            .await
+            .map_err(gql_err)?;
+
+        Ok(DashboardResponse {
+            kpis,
+            trend,
+            recent_activity,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_schema_exposes_query_fields() {
+        let sdl = build_schema().sdl();
+        assert!(sdl.contains("tickets("));
+        assert!(sdl.contains("workflow(id: UUID!)"));
+        assert!(sdl.contains("timeSummary: TimeSessionsResponse!"));
+        assert!(sdl.contains("activeWorkflow: Workflow"));
+        assert!(sdl.contains(
+            "dashboard(period: String! = \"30d\", from: NaiveDate, to: NaiveDate, tz: String, team: String, projectId: UUID)"
+        ));
+    }
+
+    #[test]
+    fn test_gql_err_carries_code() {
+        let err = gql_err(ApiError::NotFound("Workflow not found".into()));
+        let code = err
+            .extensions
+            .as_ref()
+            .and_then(|e| e.get("code"))
+            .cloned();
+        assert_eq!(code, Some(async_graphql::Value::from("NOT_FOUND")));
+    }
+}
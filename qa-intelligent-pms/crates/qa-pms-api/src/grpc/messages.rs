@@ -0,0 +1,162 @@
+//! Protobuf message types for the gRPC services.
+//!
+//! Hand-written mirrors of `proto/qa_pms.proto`; tag numbers must match.
+
+// ============================================================================
+// Workflows
+// ============================================================================
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct CreateWorkflowRequest {
+    #[prost(string, tag = "1")]
+    pub template_id: String,
+    #[prost(string, tag = "2")]
+    pub ticket_id: String,
+    #[prost(string, tag = "3")]
+    pub user_id: String,
+    #[prost(bool, tag = "4")]
+    pub auto_track: bool,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct WorkflowIdRequest {
+    #[prost(string, tag = "1")]
+    pub id: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ListUserWorkflowsRequest {
+    #[prost(string, tag = "1")]
+    pub user_id: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Step {
+    #[prost(uint32, tag = "1")]
+    pub index: u32,
+    #[prost(string, tag = "2")]
+    pub name: String,
+    #[prost(string, tag = "3")]
+    pub status: String,
+    #[prost(string, optional, tag = "4")]
+    pub notes: Option<String>,
+    #[prost(int32, tag = "5")]
+    pub estimated_minutes: i32,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Workflow {
+    #[prost(string, tag = "1")]
+    pub id: String,
+    #[prost(string, tag = "2")]
+    pub template_id: String,
+    #[prost(string, tag = "3")]
+    pub template_name: String,
+    #[prost(string, tag = "4")]
+    pub ticket_id: String,
+    #[prost(string, tag = "5")]
+    pub user_id: String,
+    #[prost(string, tag = "6")]
+    pub status: String,
+    #[prost(int32, tag = "7")]
+    pub current_step: i32,
+    #[prost(message, repeated, tag = "8")]
+    pub steps: Vec<Step>,
+    #[prost(string, tag = "9")]
+    pub started_at: String,
+    #[prost(string, optional, tag = "10")]
+    pub completed_at: Option<String>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ListWorkflowsResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub workflows: Vec<Workflow>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct StepActionRequest {
+    #[prost(string, tag = "1")]
+    pub workflow_id: String,
+    #[prost(int32, tag = "2")]
+    pub step_index: i32,
+    #[prost(string, optional, tag = "3")]
+    pub notes: Option<String>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct StepActionResponse {
+    #[prost(bool, tag = "1")]
+    pub workflow_completed: bool,
+    #[prost(int32, tag = "2")]
+    pub current_step_index: i32,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct WorkflowStatusResponse {
+    #[prost(string, tag = "1")]
+    pub status: String,
+    #[prost(string, tag = "2")]
+    pub message: String,
+}
+
+// ============================================================================
+// Time Tracking
+// ============================================================================
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct StartSessionRequest {
+    #[prost(string, tag = "1")]
+    pub workflow_id: String,
+    #[prost(int32, tag = "2")]
+    pub step_index: i32,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct SessionIdRequest {
+    #[prost(string, tag = "1")]
+    pub session_id: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct TimeSession {
+    #[prost(string, tag = "1")]
+    pub id: String,
+    #[prost(string, tag = "2")]
+    pub workflow_id: String,
+    #[prost(int32, tag = "3")]
+    pub step_index: i32,
+    #[prost(string, tag = "4")]
+    pub started_at: String,
+    #[prost(string, optional, tag = "5")]
+    pub paused_at: Option<String>,
+    #[prost(string, optional, tag = "6")]
+    pub ended_at: Option<String>,
+    #[prost(int32, tag = "7")]
+    pub total_seconds: i32,
+    #[prost(bool, tag = "8")]
+    pub is_active: bool,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct WorkflowTimeResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub sessions: Vec<TimeSession>,
+    #[prost(int32, tag = "2")]
+    pub total_seconds: i32,
+}
+
+impl From<qa_pms_time::TimeSession> for TimeSession {
+    fn from(s: qa_pms_time::TimeSession) -> Self {
+        Self {
+            id: s.id.to_string(),
+            workflow_id: s.workflow_instance_id.to_string(),
+            step_index: s.step_index,
+            started_at: s.started_at.to_rfc3339(),
+            paused_at: s.paused_at.map(|t| t.to_rfc3339()),
+            ended_at: s.ended_at.map(|t| t.to_rfc3339()),
+            total_seconds: s.total_seconds,
+            is_active: s.is_active,
+        }
+    }
+}
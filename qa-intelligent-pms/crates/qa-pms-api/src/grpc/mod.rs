@@ -0,0 +1,189 @@
+//! gRPC services for machine integrations.
+//!
+//! Exposes workflow and time-tracking operations to CI pipelines and internal
+//! tools. Enabled with the `grpc` feature and served on the same port as the
+//! HTTP API (routed by the `/qa_pms.v1.*` paths). The wire contract lives in
+//! `proto/qa_pms.proto`.
+//!
+//! Calls pass through the same layers as the HTTP API, so they authenticate
+//! with an `authorization: Bearer` or `x-api-key` metadata entry, count
+//! against the caller's rate limit and are audited. A rejected call gets an
+//! HTTP error status, which gRPC clients report as `UNAUTHENTICATED`,
+//! `PERMISSION_DENIED` or `UNAVAILABLE`.
+//!
+//! As over HTTP, callers work in their own tenant and may only act on their
+//! own workflows. Admins may act
+//! on other users' workflows by sending `x-as-admin: true`.
+
+// `tonic::Status` is the error type the generated service traits require.
+#![allow(clippy::result_large_err)]
+
+pub mod messages;
+mod time;
+mod workflow;
+
+use qa_pms_workflow::{get_instance, WorkflowInstance};
+use sqlx::PgPool;
+use tonic::{Request, Status};
+use uuid::Uuid;
+
+use crate::auth::CurrentUser;
+use crate::notifications::NotificationHub;
+
+/// Metadata entry asking to act as admin on another user's workflow.
+const ADMIN_OVERRIDE_METADATA: &str = "x-as-admin";
+
+/// Generated server stubs (see `build.rs`).
+pub mod pb {
+    include!(concat!(env!("OUT_DIR"), "/qa_pms.v1.WorkflowService.rs"));
+    include!(concat!(env!("OUT_DIR"), "/qa_pms.v1.TimeTrackingService.rs"));
+}
+
+pub use time::TimeTrackingGrpc;
+pub use workflow::WorkflowGrpc;
+
+/// Build an axum router serving all gRPC services.
+pub fn router(pool: PgPool, notifications: NotificationHub) -> axum::Router {
+    tonic::service::Routes::new(pb::workflow_service_server::WorkflowServiceServer::new(
+        WorkflowGrpc::new(pool.clone(), notifications),
+    ))
+    .add_service(pb::time_tracking_service_server::TimeTrackingServiceServer::new(
+        TimeTrackingGrpc::new(pool),
+    ))
+    .into_axum_router()
+}
+
+/// Parse a UUID field, reporting the field name on failure.
+fn parse_uuid(value: &str, field: &str) -> Result<Uuid, Status> {
+    Uuid::parse_str(value).map_err(|_| Status::invalid_argument(format!("{field} must be a UUID")))
+}
+
+/// Caller identity set by the authentication layer.
+fn current_user<T>(request: &Request<T>) -> Result<CurrentUser, Status> {
+    request
+        .extensions()
+        .get::<CurrentUser>()
+        .cloned()
+        .ok_or_else(|| Status::unauthenticated("Authentication required"))
+}
+
+/// Whether the call asks to act as admin on another user's workflow.
+fn admin_override<T>(request: &Request<T>) -> bool {
+    request
+        .metadata()
+        .get(ADMIN_OVERRIDE_METADATA)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.eq_ignore_ascii_case("true"))
+}
+
+/// Fetch a workflow of the caller's tenant that the caller may act on.
+async fn fetch_managed_instance(
+    pool: &PgPool,
+    id: Uuid,
+    user: &CurrentUser,
+    admin_override: bool,
+) -> Result<WorkflowInstance, Status> {
+    let instance = get_instance(pool, id)
+        .await
+        .map_err(db_status)?
+        .ok_or_else(|| Status::not_found("Workflow not found"))?;
+    check_access(&instance, user, admin_override)?;
+    Ok(instance)
+}
+
+/// Fail unless the workflow is in the caller's tenant and the caller may act
+/// on it. Workflows of other tenants are reported as missing.
+fn check_access(instance: &WorkflowInstance, user: &CurrentUser, admin_override: bool) -> Result<(), Status> {
+    if instance.tenant_id != user.tenant.as_str() {
+        return Err(Status::not_found("Workflow not found"));
+    }
+    if !user.can_manage(&instance.user_id, admin_override) {
+        return Err(Status::permission_denied("Workflow belongs to another user"));
+    }
+    Ok(())
+}
+
+/// Map a database error to a gRPC status.
+fn db_status(err: sqlx::Error) -> Status {
+    match err {
+        sqlx::Error::RowNotFound => Status::not_found("Record not found"),
+        e => {
+            tracing::error!(error = %e, "gRPC database error");
+            Status::internal("Database error")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use qa_pms_core::TenantId;
+
+    #[test]
+    fn test_parse_uuid_rejects_garbage() {
+        let status = parse_uuid("not-a-uuid", "workflow_id").unwrap_err();
+        assert_eq!(status.code(), tonic::Code::InvalidArgument);
+        assert!(status.message().contains("workflow_id"));
+    }
+
+    #[test]
+    fn test_identity_comes_from_request_extensions() {
+        let mut request = Request::new(());
+        assert_eq!(current_user(&request).unwrap_err().code(), tonic::Code::Unauthenticated);
+        assert!(!admin_override(&request));
+
+        request.extensions_mut().insert(CurrentUser::local_as(Some("alice".into())));
+        request
+            .metadata_mut()
+            .insert(ADMIN_OVERRIDE_METADATA, "true".parse().unwrap());
+        assert_eq!(current_user(&request).unwrap().claimed_id.as_deref(), Some("alice"));
+        assert!(admin_override(&request));
+    }
+
+    #[test]
+    fn test_access_requires_same_tenant_and_owner() {
+        let now = chrono::Utc::now();
+        let instance = WorkflowInstance {
+            id: Uuid::new_v4(),
+            template_id: Uuid::new_v4(),
+            template_version: 1,
+            ticket_id: "PROJ-7".to_string(),
+            user_id: "dana".to_string(),
+            tenant_id: "acme".to_string(),
+            status: "active".to_string(),
+            current_step: 0,
+            started_at: now,
+            paused_at: None,
+            resumed_at: None,
+            completed_at: None,
+            auto_track: false,
+            archived_at: None,
+            deleted_at: None,
+            created_at: now,
+            updated_at: now,
+        };
+        let in_tenant = |tenant: &str, name: &str| CurrentUser {
+            tenant: TenantId::parse(tenant).unwrap(),
+            ..CurrentUser::local_as(Some(name.to_string()))
+        };
+
+        assert!(check_access(&instance, &in_tenant("acme", "dana"), false).is_ok());
+        assert_eq!(
+            check_access(&instance, &in_tenant("acme", "bob"), false).unwrap_err().code(),
+            tonic::Code::PermissionDenied
+        );
+        assert_eq!(
+            check_access(&instance, &in_tenant("globex", "dana"), true).unwrap_err().code(),
+            tonic::Code::NotFound
+        );
+    }
+
+    #[test]
+    fn test_db_status_maps_not_found() {
+        assert_eq!(db_status(sqlx::Error::RowNotFound).code(), tonic::Code::NotFound);
+        assert_eq!(
+            db_status(sqlx::Error::PoolTimedOut).code(),
+            tonic::Code::Internal
+        );
+    }
+}
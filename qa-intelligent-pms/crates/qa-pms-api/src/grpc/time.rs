@@ -0,0 +1,106 @@
+//! `TimeTrackingService` implementation.
+
+use sqlx::PgPool;
+use tonic::{Request, Response, Status};
+use uuid::Uuid;
+
+use qa_pms_time::{
+    end_session, get_session, get_workflow_sessions, pause_session,
+    resume_session, start_session,
+};
+
+use super::messages::{
+    SessionIdRequest, StartSessionRequest, TimeSession, WorkflowIdRequest, WorkflowTimeResponse,
+};
+use super::pb::time_tracking_service_server::TimeTrackingService;
+use super::{admin_override, current_user, db_status, fetch_managed_instance, parse_uuid};
+
+/// gRPC time-tracking service backed by the time repository.
+pub struct TimeTrackingGrpc {
+    pool: PgPool,
+}
+
+impl TimeTrackingGrpc {
+    /// Create a new service over the given pool.
+    pub const fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Fail unless the caller may act on the workflow.
+    async fn check_workflow<T>(&self, request: &Request<T>, workflow_id: Uuid) -> Result<(), Status> {
+        let user = current_user(request)?;
+        fetch_managed_instance(&self.pool, workflow_id, &user, admin_override(request)).await?;
+        Ok(())
+    }
+
+    /// Fail unless the caller may act on the session's workflow.
+    async fn check_session<T>(&self, request: &Request<T>, session_id: Uuid) -> Result<(), Status> {
+        let session = get_session(&self.pool, session_id).await.map_err(db_status)?;
+        self.check_workflow(request, session.workflow_instance_id).await
+    }
+}
+
+#[tonic::async_trait]
+impl TimeTrackingService for TimeTrackingGrpc {
+    async fn start_session(
+        &self,
+        request: Request<StartSessionRequest>,
+    ) -> Result<Response<TimeSession>, Status> {
+        let workflow_id = parse_uuid(&request.get_ref().workflow_id, "workflow_id")?;
+        self.check_workflow(&request, workflow_id).await?;
+        let req = request.into_inner();
+
+        let session = start_session(&self.pool, workflow_id, req.step_index)
+            .await
+            .map_err(db_status)?;
+        Ok(Response::new(session.into()))
+    }
+
+    async fn end_session(
+        &self,
+        request: Request<SessionIdRequest>,
+    ) -> Result<Response<TimeSession>, Status> {
+        let id = parse_uuid(&request.get_ref().session_id, "session_id")?;
+        self.check_session(&request, id).await?;
+        let session = end_session(&self.pool, id).await.map_err(db_status)?;
+        Ok(Response::new(session.into()))
+    }
+
+    async fn pause_session(
+        &self,
+        request: Request<SessionIdRequest>,
+    ) -> Result<Response<TimeSession>, Status> {
+        let id = parse_uuid(&request.get_ref().session_id, "session_id")?;
+        self.check_session(&request, id).await?;
+        pause_session(&self.pool, id).await.map_err(db_status)?;
+        let session = get_session(&self.pool, id).await.map_err(db_status)?;
+        Ok(Response::new(session.into()))
+    }
+
+    async fn resume_session(
+        &self,
+        request: Request<SessionIdRequest>,
+    ) -> Result<Response<TimeSession>, Status> {
+        let id = parse_uuid(&request.get_ref().session_id, "session_id")?;
+        self.check_session(&request, id).await?;
+        resume_session(&self.pool, id).await.map_err(db_status)?;
+        let session = get_session(&self.pool, id).await.map_err(db_status)?;
+        Ok(Response::new(session.into()))
+    }
+
+    async fn get_workflow_time(
+        &self,
+        request: Request<WorkflowIdRequest>,
+    ) -> Result<Response<WorkflowTimeResponse>, Status> {
+        let workflow_id = parse_uuid(&request.get_ref().id, "id")?;
+        self.check_workflow(&request, workflow_id).await?;
+        let sessions = get_workflow_sessions(&self.pool, workflow_id)
+            .await
+            .map_err(db_status)?;
+        let total_seconds = sessions.iter().map(|s| s.total_seconds).sum();
+        Ok(Response::new(WorkflowTimeResponse {
+            sessions: sessions.into_iter().map(TimeSession::from).collect(),
+            total_seconds,
+        }))
+    }
+}
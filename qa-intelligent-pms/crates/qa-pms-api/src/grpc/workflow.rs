@@ -0,0 +1,332 @@
+//! `WorkflowService` implementation.
+
+use sqlx::PgPool;
+use tonic::{Request, Response, Status};
+use tracing::info;
+use uuid::Uuid;
+
+use qa_pms_workflow::{
+    cancel_workflow, complete_step, complete_workflow, create_instance,
+    get_all_user_active_workflows, get_instance, get_instance_template, get_step_results, get_template, pause_workflow,
+    resolve_next_step, resume_workflow, skip_bypassed_steps, skip_step, start_step, update_instance_step,
+    StepMetadata, WorkflowInstance, WorkflowStep, WorkflowTemplate,
+};
+
+use qa_pms_time::TrackingService;
+
+use super::messages::{
+    CreateWorkflowRequest, ListUserWorkflowsRequest, ListWorkflowsResponse, Step,
+    StepActionRequest, StepActionResponse, Workflow, WorkflowIdRequest, WorkflowStatusResponse,
+};
+use super::pb::workflow_service_server::WorkflowService;
+use super::{admin_override, current_user, db_status, fetch_managed_instance, parse_uuid};
+use crate::notifications::NotificationHub;
+use crate::routes::workflows::{publish_workflow_event, spawn_pattern_detection, steps_with_status};
+
+/// gRPC workflow service backed by the workflow repository.
+pub struct WorkflowGrpc {
+    pool: PgPool,
+    notifications: NotificationHub,
+}
+
+impl WorkflowGrpc {
+    /// Create a new service over the given pool.
+    pub const fn new(pool: PgPool, notifications: NotificationHub) -> Self {
+        Self { pool, notifications }
+    }
+
+    /// Fetch the workflow a call names, if the caller may act on it.
+    async fn fetch_instance<T>(&self, request: &Request<T>, id: Uuid) -> Result<WorkflowInstance, Status> {
+        let user = current_user(request)?;
+        fetch_managed_instance(&self.pool, id, &user, admin_override(request)).await
+    }
+
+    async fn fetch_template(&self, id: Uuid) -> Result<WorkflowTemplate, Status> {
+        get_template(&self.pool, id)
+            .await
+            .map_err(db_status)?
+            .filter(|template| template.archived_at.is_none())
+            .ok_or_else(|| Status::not_found("Template not found"))
+    }
+
+    async fn fetch_instance_template(
+        &self,
+        instance: &WorkflowInstance,
+    ) -> Result<WorkflowTemplate, Status> {
+        get_instance_template(&self.pool, instance)
+            .await
+            .map_err(db_status)?
+            .ok_or_else(|| Status::not_found("Template not found"))
+    }
+
+    async fn to_message(&self, instance: WorkflowInstance) -> Result<Workflow, Status> {
+        let template = self.fetch_instance_template(&instance).await?;
+        let step_results = get_step_results(&self.pool, instance.id)
+            .await
+            .map_err(db_status)?;
+        let steps = steps_with_status(&template, &step_results)
+            .into_iter()
+            .map(|s| Step {
+                index: u32::try_from(s.index).unwrap_or(u32::MAX),
+                name: s.name,
+                status: s.status,
+                notes: s.notes,
+                estimated_minutes: s.estimated_minutes,
+            })
+            .collect();
+
+        Ok(Workflow {
+            id: instance.id.to_string(),
+            template_id: instance.template_id.to_string(),
+            template_name: template.name,
+            ticket_id: instance.ticket_id,
+            user_id: instance.user_id,
+            status: instance.status,
+            current_step: instance.current_step,
+            steps,
+            started_at: instance.started_at.to_rfc3339(),
+            completed_at: instance.completed_at.map(|t| t.to_rfc3339()),
+        })
+    }
+
+    /// Validate a step index against the workflow's template.
+    async fn template_steps(&self, instance: &WorkflowInstance, step_index: i32) -> Result<Vec<WorkflowStep>, Status> {
+        let template = self.fetch_instance_template(instance).await?;
+        let total_steps = i32::try_from(template.steps().len()).unwrap_or(i32::MAX);
+        if step_index < 0 || step_index >= total_steps {
+            return Err(Status::invalid_argument("Invalid step index"));
+        }
+        Ok(template.steps_json.0)
+    }
+
+    /// Move the instance past `step_index`. gRPC requests carry no step
+    /// metadata, so only default branches apply. An auto-tracked workflow's
+    /// time session moves along with it.
+    async fn advance(
+        &self,
+        workflow_id: Uuid,
+        steps: &[WorkflowStep],
+        step_index: i32,
+    ) -> Result<Option<usize>, Status> {
+        let next = resolve_next_step(steps, step_index as usize, &StepMetadata::new());
+        let target = i32::try_from(next.unwrap_or(steps.len())).unwrap_or(i32::MAX);
+        skip_bypassed_steps(&self.pool, workflow_id, step_index, target)
+            .await
+            .map_err(db_status)?;
+        if next.is_some() {
+            update_instance_step(&self.pool, workflow_id, target)
+                .await
+                .map_err(db_status)?;
+        }
+
+        let auto_track = get_instance(&self.pool, workflow_id)
+            .await
+            .map_err(db_status)?
+            .is_some_and(|instance| instance.auto_track);
+        if auto_track {
+            let next_step = next.map(|_| target);
+            if let Err(e) = TrackingService::new(self.pool.clone())
+                .advance(workflow_id, step_index, next_step)
+                .await
+            {
+                tracing::warn!(workflow_id = %workflow_id, step_index, error = %e, "Failed to auto-track step time");
+            }
+        }
+        Ok(next)
+    }
+}
+
+fn step_response(step_index: i32, next_step: Option<usize>) -> StepActionResponse {
+    StepActionResponse {
+        workflow_completed: next_step.is_none(),
+        current_step_index: next_step.map_or(step_index, |next| i32::try_from(next).unwrap_or(i32::MAX)),
+    }
+}
+
+fn status_response(status: &str, message: &str) -> Response<WorkflowStatusResponse> {
+    Response::new(WorkflowStatusResponse {
+        status: status.to_string(),
+        message: message.to_string(),
+    })
+}
+
+#[tonic::async_trait]
+impl WorkflowService for WorkflowGrpc {
+    async fn create_workflow(
+        &self,
+        request: Request<CreateWorkflowRequest>,
+    ) -> Result<Response<Workflow>, Status> {
+        let user = current_user(&request)?;
+        let req = request.into_inner();
+        let template_id = parse_uuid(&req.template_id, "template_id")?;
+        if req.ticket_id.is_empty() {
+            return Err(Status::invalid_argument("ticket_id is required"));
+        }
+        self.fetch_template(template_id).await?;
+        // Signed-in callers always own what they create
+        let owner = user.owner(Some(req.user_id).filter(|id| !id.is_empty()));
+
+        let instance = create_instance(
+            &self.pool,
+            template_id,
+            &req.ticket_id,
+            &owner,
+            user.tenant.as_str(),
+            req.auto_track,
+        )
+        .await
+        .map_err(db_status)?;
+
+        // Start the first step (non-critical if fails)
+        if let Err(e) = start_step(&self.pool, instance.id, 0).await {
+            tracing::warn!(error = %e, "Failed to start first step");
+        }
+        if instance.auto_track {
+            if let Err(e) = TrackingService::new(self.pool.clone()).step_started(instance.id, 0).await {
+                tracing::warn!(workflow_id = %instance.id, error = %e, "Failed to start time session for first step");
+            }
+        }
+
+        info!(workflow_id = %instance.id, ticket_id = %req.ticket_id, "Created workflow via gRPC");
+
+        Ok(Response::new(self.to_message(instance).await?))
+    }
+
+    async fn get_workflow(
+        &self,
+        request: Request<WorkflowIdRequest>,
+    ) -> Result<Response<Workflow>, Status> {
+        let id = parse_uuid(&request.get_ref().id, "id")?;
+        let instance = self.fetch_instance(&request, id).await?;
+        Ok(Response::new(self.to_message(instance).await?))
+    }
+
+    async fn list_user_workflows(
+        &self,
+        request: Request<ListUserWorkflowsRequest>,
+    ) -> Result<Response<ListWorkflowsResponse>, Status> {
+        let user = current_user(&request)?;
+        let owner = user.owner(Some(request.get_ref().user_id.clone()).filter(|id| !id.is_empty()));
+        let instances = get_all_user_active_workflows(&self.pool, &owner)
+            .await
+            .map_err(db_status)?;
+
+        let mut workflows = Vec::with_capacity(instances.len());
+        for instance in instances
+            .into_iter()
+            .filter(|instance| instance.tenant_id == user.tenant.as_str())
+        {
+            workflows.push(self.to_message(instance).await?);
+        }
+
+        Ok(Response::new(ListWorkflowsResponse { workflows }))
+    }
+
+    async fn complete_step(
+        &self,
+        request: Request<StepActionRequest>,
+    ) -> Result<Response<StepActionResponse>, Status> {
+        let id = parse_uuid(&request.get_ref().workflow_id, "workflow_id")?;
+        let instance = self.fetch_instance(&request, id).await?;
+        let req = request.into_inner();
+        let steps = self.template_steps(&instance, req.step_index).await?;
+
+        complete_step(&self.pool, id, req.step_index, req.notes.as_deref(), None)
+            .await
+            .map_err(db_status)?;
+        let next_step = self.advance(id, &steps, req.step_index).await?;
+
+        info!(workflow_id = %id, step_index = req.step_index, "Completed workflow step via gRPC");
+
+        Ok(Response::new(step_response(req.step_index, next_step)))
+    }
+
+    async fn skip_step(
+        &self,
+        request: Request<StepActionRequest>,
+    ) -> Result<Response<StepActionResponse>, Status> {
+        let id = parse_uuid(&request.get_ref().workflow_id, "workflow_id")?;
+        let instance = self.fetch_instance(&request, id).await?;
+        let req = request.into_inner();
+        let steps = self.template_steps(&instance, req.step_index).await?;
+
+        skip_step(&self.pool, id, req.step_index)
+            .await
+            .map_err(db_status)?;
+        let next_step = self.advance(id, &steps, req.step_index).await?;
+
+        info!(workflow_id = %id, step_index = req.step_index, "Skipped workflow step via gRPC");
+
+        Ok(Response::new(step_response(req.step_index, next_step)))
+    }
+
+    async fn pause_workflow(
+        &self,
+        request: Request<WorkflowIdRequest>,
+    ) -> Result<Response<WorkflowStatusResponse>, Status> {
+        let id = parse_uuid(&request.get_ref().id, "id")?;
+        let instance = self.fetch_instance(&request, id).await?;
+        if instance.status != "active" {
+            return Err(Status::failed_precondition("Workflow is not active"));
+        }
+        pause_workflow(&self.pool, id).await.map_err(db_status)?;
+        publish_workflow_event(&self.notifications, &instance, "paused", serde_json::json!({}));
+        Ok(status_response("paused", "Workflow paused successfully"))
+    }
+
+    async fn resume_workflow(
+        &self,
+        request: Request<WorkflowIdRequest>,
+    ) -> Result<Response<WorkflowStatusResponse>, Status> {
+        let id = parse_uuid(&request.get_ref().id, "id")?;
+        let instance = self.fetch_instance(&request, id).await?;
+        if instance.status != "paused" {
+            return Err(Status::failed_precondition("Workflow is not paused"));
+        }
+        resume_workflow(&self.pool, id).await.map_err(db_status)?;
+        publish_workflow_event(&self.notifications, &instance, "resumed", serde_json::json!({}));
+        Ok(status_response("active", "Workflow resumed successfully"))
+    }
+
+    async fn complete_workflow(
+        &self,
+        request: Request<WorkflowIdRequest>,
+    ) -> Result<Response<WorkflowStatusResponse>, Status> {
+        let id = parse_uuid(&request.get_ref().id, "id")?;
+        let instance = self.fetch_instance(&request, id).await?;
+        complete_workflow(&self.pool, id).await.map_err(db_status)?;
+
+        info!(workflow_id = %id, "Completed workflow via gRPC");
+        publish_workflow_event(&self.notifications, &instance, "completed", serde_json::json!({}));
+        spawn_pattern_detection(self.pool.clone(), self.notifications.clone(), id);
+
+        Ok(status_response("completed", "Workflow completed successfully"))
+    }
+
+    async fn cancel_workflow(
+        &self,
+        request: Request<WorkflowIdRequest>,
+    ) -> Result<Response<WorkflowStatusResponse>, Status> {
+        let id = parse_uuid(&request.get_ref().id, "id")?;
+        let instance = self.fetch_instance(&request, id).await?;
+        cancel_workflow(&self.pool, id).await.map_err(db_status)?;
+        publish_workflow_event(&self.notifications, &instance, "cancelled", serde_json::json!({}));
+        Ok(status_response("cancelled", "Workflow cancelled"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_step_response_advances_until_last_step() {
+        let mid = step_response(0, Some(1));
+        assert!(!mid.workflow_completed);
+        assert_eq!(mid.current_step_index, 1);
+
+        let last = step_response(2, None);
+        assert!(last.workflow_completed);
+        assert_eq!(last.current_step_index, 2);
+    }
+}
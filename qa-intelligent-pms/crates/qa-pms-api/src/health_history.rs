@@ -0,0 +1,334 @@
+//! Integration health check history.
+//!
+//! Every scheduled check result is appended to `integration_health_history`
+//! and rows older than the retention period are pruned as new ones arrive.
+//! Summaries are computed from the raw samples: uptime is the share of
+//! checks that were not offline, and downtime windows run from the first
+//! offline check to the next check that succeeded.
+
+use chrono::{DateTime, Duration, DurationRound, Utc};
+use qa_pms_core::health::{HealthCheckResult, HealthStatus};
+use serde::Serialize;
+use sqlx::{FromRow, PgPool};
+use utoipa::ToSchema;
+
+/// One stored health check result.
+#[derive(Debug, Clone, FromRow)]
+pub struct HealthSample {
+    /// "online", "degraded" or "offline"
+    pub status: String,
+    /// Response time in milliseconds
+    pub response_time_ms: Option<i64>,
+    /// When the check ran
+    pub checked_at: DateTime<Utc>,
+}
+
+impl HealthSample {
+    fn is_down(&self) -> bool {
+        self.status == status_str(HealthStatus::Offline)
+    }
+}
+
+const fn status_str(status: HealthStatus) -> &'static str {
+    match status {
+        HealthStatus::Online => "online",
+        HealthStatus::Degraded => "degraded",
+        HealthStatus::Offline => "offline",
+    }
+}
+
+/// Health check history backed by the `integration_health_history` table.
+#[derive(Debug, Clone)]
+pub struct HealthHistory {
+    db: PgPool,
+    retention: Duration,
+}
+
+impl HealthHistory {
+    /// Create a history store keeping results for `retention_days`.
+    #[must_use]
+    pub fn new(db: PgPool, retention_days: u32) -> Self {
+        Self {
+            db,
+            retention: Duration::days(i64::from(retention_days)),
+        }
+    }
+
+    /// Longest period a summary can cover.
+    #[must_use]
+    pub const fn retention(&self) -> Duration {
+        self.retention
+    }
+
+    /// Append check results and drop those past the retention period.
+    ///
+    /// # Errors
+    /// Returns an error if the database is unavailable.
+    pub async fn record(&self, results: &[HealthCheckResult]) -> Result<(), sqlx::Error> {
+        let mut tx = self.db.begin().await?;
+        for result in results {
+            sqlx::query(
+                "INSERT INTO integration_health_history \
+                 (integration, status, response_time_ms, error_message, checked_at) \
+                 VALUES ($1, $2, $3, $4, $5)",
+            )
+            .bind(&result.integration)
+            .bind(status_str(result.status))
+            .bind(
+                result
+                    .response_time_ms
+                    .and_then(|ms| i64::try_from(ms).ok()),
+            )
+            .bind(&result.error_message)
+            .bind(result.checked_at)
+            .execute(&mut *tx)
+            .await?;
+        }
+        sqlx::query("DELETE FROM integration_health_history WHERE checked_at < $1")
+            .bind(Utc::now() - self.retention)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await
+    }
+
+    /// Results for one integration since `since`, oldest first.
+    ///
+    /// # Errors
+    /// Returns an error if the database is unavailable.
+    pub async fn samples(
+        &self,
+        integration: &str,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<HealthSample>, sqlx::Error> {
+        sqlx::query_as(
+            "SELECT status, response_time_ms, checked_at FROM integration_health_history \
+             WHERE integration = $1 AND checked_at >= $2 ORDER BY checked_at",
+        )
+        .bind(integration)
+        .bind(since)
+        .fetch_all(&self.db)
+        .await
+    }
+}
+
+/// Mean response time over one bucket of the trend.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ResponseTimeBucket {
+    /// Bucket start
+    pub start: DateTime<Utc>,
+    /// Checks in the bucket
+    pub checks: u32,
+    /// Mean response time of checks that reported one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mean_response_time_ms: Option<f64>,
+}
+
+/// A run of consecutive offline checks.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DowntimeWindow {
+    /// First offline check
+    pub start: DateTime<Utc>,
+    /// First successful check afterwards (absent while still down)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end: Option<DateTime<Utc>>,
+    /// Length of the window, up to now when still down
+    pub duration_secs: i64,
+}
+
+/// Uptime, response time trend and downtime over a period.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct HealthSummary {
+    /// Checks in the period
+    pub checks: usize,
+    /// Share of checks that were online or degraded, 0–100
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub uptime_percent: Option<f64>,
+    /// Mean response time over the whole period
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mean_response_time_ms: Option<f64>,
+    /// Mean response time per bucket, oldest first (empty buckets omitted)
+    pub response_time_trend: Vec<ResponseTimeBucket>,
+    /// Offline windows, oldest first
+    pub downtime_windows: Vec<DowntimeWindow>,
+}
+
+fn mean(values: impl Iterator<Item = i64>) -> Option<f64> {
+    let (sum, count) = values.fold((0i64, 0u32), |(sum, count), v| (sum + v, count + 1));
+    (count > 0).then(|| sum as f64 / f64::from(count))
+}
+
+/// Summarize samples ordered by check time.
+///
+/// `bucket` sets the trend resolution; `now` closes a window that is still
+/// open.
+#[must_use]
+pub fn summarize(samples: &[HealthSample], bucket: Duration, now: DateTime<Utc>) -> HealthSummary {
+    let up = samples.iter().filter(|s| !s.is_down()).count();
+    let uptime_percent = (!samples.is_empty()).then(|| up as f64 * 100.0 / samples.len() as f64);
+    let mean_response_time_ms = mean(samples.iter().filter_map(|s| s.response_time_ms));
+
+    let mut response_time_trend: Vec<ResponseTimeBucket> = Vec::new();
+    for chunk in samples.chunk_by(|a, b| {
+        a.checked_at.duration_trunc(bucket).ok() == b.checked_at.duration_trunc(bucket).ok()
+    }) {
+        let start = chunk[0]
+            .checked_at
+            .duration_trunc(bucket)
+            .unwrap_or(chunk[0].checked_at);
+        response_time_trend.push(ResponseTimeBucket {
+            start,
+            checks: u32::try_from(chunk.len()).unwrap_or(u32::MAX),
+            mean_response_time_ms: mean(chunk.iter().filter_map(|s| s.response_time_ms)),
+        });
+    }
+
+    let mut downtime_windows = Vec::new();
+    let mut down_since: Option<DateTime<Utc>> = None;
+    for sample in samples {
+        match (sample.is_down(), down_since) {
+            (true, None) => down_since = Some(sample.checked_at),
+            (false, Some(start)) => {
+                downtime_windows.push(DowntimeWindow {
+                    start,
+                    end: Some(sample.checked_at),
+                    duration_secs: (sample.checked_at - start).num_seconds(),
+                });
+                down_since = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some(start) = down_since {
+        downtime_windows.push(DowntimeWindow {
+            start,
+            end: None,
+            duration_secs: (now - start).num_seconds(),
+        });
+    }
+
+    HealthSummary {
+        checks: samples.len(),
+        uptime_percent,
+        mean_response_time_ms,
+        response_time_trend,
+        downtime_windows,
+    }
+}
+
+/// Parse a period such as `24h` or `7d`.
+#[must_use]
+pub fn parse_period(period: &str) -> Option<Duration> {
+    let unit = period.chars().last()?;
+    let amount: i64 = period[..period.len() - unit.len_utf8()]
+        .parse()
+        .ok()
+        .filter(|n| (1..=3650).contains(n))?;
+    match unit {
+        'h' => Some(Duration::hours(amount)),
+        'd' => Some(Duration::days(amount)),
+        'w' => Some(Duration::weeks(amount)),
+        _ => None,
+    }
+}
+
+/// Trend resolution for a period: hourly up to two days, daily beyond.
+#[must_use]
+pub fn bucket_for(period: Duration) -> Duration {
+    if period <= Duration::days(2) {
+        Duration::hours(1)
+    } else {
+        Duration::days(1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn at(hour: u32, minute: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 3, 2, hour, minute, 0).unwrap()
+    }
+
+    fn sample(status: HealthStatus, ms: Option<i64>, checked_at: DateTime<Utc>) -> HealthSample {
+        HealthSample {
+            status: status_str(status).to_string(),
+            response_time_ms: ms,
+            checked_at,
+        }
+    }
+
+    #[test]
+    fn test_summarize_uptime_trend_and_downtime() {
+        use HealthStatus::{Degraded, Offline, Online};
+        let samples = vec![
+            sample(Online, Some(100), at(9, 0)),
+            sample(Degraded, Some(300), at(9, 30)),
+            sample(Offline, None, at(10, 0)),
+            sample(Offline, None, at(10, 30)),
+            sample(Online, Some(200), at(11, 0)),
+            sample(Offline, None, at(11, 30)),
+        ];
+
+        let summary = summarize(&samples, Duration::hours(1), at(12, 0));
+
+        assert_eq!(summary.checks, 6);
+        assert_eq!(summary.uptime_percent, Some(50.0));
+        assert_eq!(summary.mean_response_time_ms, Some(200.0));
+
+        let trend: Vec<_> = summary
+            .response_time_trend
+            .iter()
+            .map(|b| (b.start, b.checks, b.mean_response_time_ms))
+            .collect();
+        assert_eq!(
+            trend,
+            vec![
+                (at(9, 0), 2, Some(200.0)),
+                (at(10, 0), 2, None),
+                (at(11, 0), 2, Some(200.0)),
+            ]
+        );
+
+        assert_eq!(
+            summary.downtime_windows,
+            vec![
+                DowntimeWindow {
+                    start: at(10, 0),
+                    end: Some(at(11, 0)),
+                    duration_secs: 3600,
+                },
+                DowntimeWindow {
+                    start: at(11, 30),
+                    end: None,
+                    duration_secs: 1800,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_summarize_without_samples() {
+        let summary = summarize(&[], Duration::hours(1), at(12, 0));
+        assert_eq!(summary.checks, 0);
+        assert!(summary.uptime_percent.is_none());
+        assert!(summary.response_time_trend.is_empty());
+        assert!(summary.downtime_windows.is_empty());
+    }
+
+    #[test]
+    fn test_parse_period() {
+        assert_eq!(parse_period("7d"), Some(Duration::days(7)));
+        assert_eq!(parse_period("24h"), Some(Duration::hours(24)));
+        assert_eq!(parse_period("2w"), Some(Duration::weeks(2)));
+        assert_eq!(parse_period("0d"), None);
+        assert_eq!(parse_period("7x"), None);
+        assert_eq!(parse_period(""), None);
+        assert_eq!(parse_period("7é"), None);
+        assert_eq!(bucket_for(Duration::hours(24)), Duration::hours(1));
+        assert_eq!(bucket_for(Duration::days(7)), Duration::days(1));
+    }
+}
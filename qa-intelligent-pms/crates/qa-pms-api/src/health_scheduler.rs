@@ -4,11 +4,15 @@
 
 use futures::future::join_all;
 use qa_pms_core::health::HealthCheck;
+use serde_json::json;
 use qa_pms_core::HealthStore;
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::time::interval;
-use tracing::{debug, info};
+use tracing::{debug, info, warn};
+
+use crate::health_history::HealthHistory;
+use crate::notifications::{Notification, NotificationHub, NotificationKind};
 
 /// Default health check interval (60 seconds).
 pub const DEFAULT_INTERVAL_SECS: u64 = 60;
@@ -37,6 +41,8 @@ pub struct HealthScheduler {
     checks: Vec<Arc<dyn HealthCheck>>,
     store: Arc<HealthStore>,
     config: HealthSchedulerConfig,
+    notifications: Option<NotificationHub>,
+    history: Option<HealthHistory>,
 }
 
 impl HealthScheduler {
@@ -50,6 +56,8 @@ impl HealthScheduler {
             checks: Vec::new(),
             store,
             config,
+            notifications: None,
+            history: None,
         }
     }
 
@@ -58,6 +66,20 @@ impl HealthScheduler {
         Self::new(store, HealthSchedulerConfig::default())
     }
 
+    /// Publish integration status changes to the notification hub.
+    #[must_use]
+    pub fn with_notifications(mut self, hub: NotificationHub) -> Self {
+        self.notifications = Some(hub);
+        self
+    }
+
+    /// Keep every check result in the health history.
+    #[must_use]
+    pub fn with_history(mut self, history: HealthHistory) -> Self {
+        self.history = Some(history);
+        self
+    }
+
     /// Add a health check.
     ///
     /// Returns self for method chaining.
@@ -98,6 +120,12 @@ impl HealthScheduler {
         let futures: Vec<_> = self.checks.iter().map(|c| c.check()).collect();
         let results = join_all(futures).await;
 
+        if let Some(history) = &self.history {
+            if let Err(e) = history.record(&results).await {
+                warn!(error = %e, "Failed to record health check history");
+            }
+        }
+
         // Update store with results
         for result in results {
             debug!(
@@ -106,6 +134,21 @@ impl HealthScheduler {
                 response_time_ms = ?result.response_time_ms,
                 "Health check completed"
             );
+            let previous = self.store.get(&result.integration).await.map(|h| h.status);
+            if let (Some(hub), Some(previous)) = (&self.notifications, previous) {
+                if previous != result.status {
+                    hub.publish(Notification::install_wide(
+                        NotificationKind::Health,
+                        "status_changed",
+                        json!({
+                            "integration": result.integration,
+                            "previous": previous,
+                            "status": result.status,
+                            "errorMessage": result.error_message,
+                        }),
+                    ));
+                }
+            }
             self.store.update(result).await;
         }
     }
@@ -248,4 +291,28 @@ mod tests {
 
         assert_eq!(check.calls(), 3);
     }
+
+    #[tokio::test]
+    async fn test_scheduler_publishes_status_changes() {
+        let store = Arc::new(HealthStore::new());
+        store
+            .update(HealthCheckResult::online("jira", StdDuration::from_millis(10)))
+            .await;
+        let hub = NotificationHub::new(8);
+        let mut rx = hub.subscribe();
+
+        let scheduler = HealthScheduler::with_defaults(Arc::clone(&store))
+            .with_notifications(hub)
+            .add_check(Arc::new(MockHealthCheck::new("jira", HealthStatus::Offline)))
+            .add_check(Arc::new(MockHealthCheck::new("postman", HealthStatus::Offline)));
+
+        scheduler.run_checks().await;
+
+        // Only jira changed; postman had no previous status
+        let notification = rx.try_recv().unwrap();
+        assert_eq!(notification.kind, NotificationKind::Health);
+        assert_eq!(notification.payload["integration"], "jira");
+        assert_eq!(notification.payload["status"], "offline");
+        assert!(rx.try_recv().is_err());
+    }
 }
@@ -0,0 +1,178 @@
+//! Structured request logging.
+//!
+//! The [`log`] middleware writes one tracing event per request with its
+//! method, route, path, status, latency and request ID, at warn level for
+//! client errors, error level for server errors and info otherwise. A share
+//! of JSON request bodies, as configured in [`HttpLogSettings`], is logged
+//! too after passing through the configured [`Redactor`]. A logged body is
+//! left on the request as [`LoggedBody`], so error capture keeps it with the
+//! error log entry when the request fails.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Instant;
+
+use axum::{
+    extract::{MatchedPath, Request, State},
+    http::StatusCode,
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use qa_pms_config::settings::HttpLogSettings;
+use serde_json::Value;
+use tracing::Level;
+
+use crate::app::AppState;
+use crate::audit::read_json_body;
+use crate::error_capture::{echo_request_id, ensure_request_id, is_sampled};
+use crate::redact::Redactor;
+
+/// Tracing target of request events.
+const LOG_TARGET: &str = "qa_pms_api::http";
+
+/// Logs requests and decides which bodies are logged.
+#[derive(Debug)]
+pub struct HttpLogger {
+    settings: HttpLogSettings,
+    redactor: Redactor,
+    seen: AtomicU64,
+}
+
+/// A request body as logged, with secrets masked.
+#[derive(Debug, Clone)]
+pub struct LoggedBody(pub Value);
+
+impl HttpLogger {
+    /// Create a logger with the given settings.
+    #[must_use]
+    pub fn new(settings: HttpLogSettings) -> Self {
+        Self {
+            redactor: Redactor::new(&settings),
+            settings,
+            seen: AtomicU64::new(0),
+        }
+    }
+
+    /// Redaction applied to logged bodies and captured errors.
+    #[must_use]
+    pub const fn redactor(&self) -> &Redactor {
+        &self.redactor
+    }
+
+    /// Whether to log the body of the next request.
+    fn sample_body(&self) -> bool {
+        if self.settings.body_sample_rate <= 0.0 {
+            return false;
+        }
+        let seen = self.seen.fetch_add(1, Ordering::Relaxed) + 1;
+        is_sampled(seen, self.settings.body_sample_rate)
+    }
+}
+
+/// Log every request.
+///
+/// Runs outside authentication and rate limiting so rejected requests are
+/// logged too, and assigns the request ID the inner layers use.
+pub async fn log(State(state): State<AppState>, mut request: Request, next: Next) -> Response {
+    let logger = &state.http_logger;
+    if !logger.settings.enabled {
+        return next.run(request).await;
+    }
+    let started = Instant::now();
+    let request_id = ensure_request_id(&mut request);
+    let method = request.method().clone();
+    let path = request.uri().path().to_string();
+    let query = request.uri().query().map(|query| logger.redactor.scrub(query));
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|route| route.as_str().to_string());
+
+    let mut body = None;
+    if logger.sample_body() {
+        let max_bytes = u64::try_from(logger.settings.max_body_bytes).unwrap_or(u64::MAX);
+        match read_json_body(request, max_bytes).await {
+            Ok((restored, value)) => {
+                request = restored;
+                body = value.map(|value| logger.redactor.redact(value));
+            }
+            Err(e) => return e.into_response(),
+        }
+    }
+    if let Some(body) = &body {
+        request.extensions_mut().insert(LoggedBody(body.clone()));
+    }
+
+    let mut response = next.run(request).await;
+    echo_request_id(&mut response, &request_id);
+
+    let status = response.status();
+    let latency_ms = u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX);
+    let body = body.map(|body| body.to_string());
+    macro_rules! request_event {
+        ($level:expr) => {
+            tracing::event!(
+                target: LOG_TARGET,
+                $level,
+                method = %method,
+                route = route.as_deref(),
+                path = %path,
+                query = query.as_deref(),
+                status = status.as_u16(),
+                latency_ms,
+                request_id = %request_id,
+                body = body.as_deref(),
+                "{method} {path} {}",
+                status.as_u16()
+            )
+        };
+    }
+    match level_of(status) {
+        Level::ERROR => request_event!(Level::ERROR),
+        Level::WARN => request_event!(Level::WARN),
+        _ => request_event!(Level::INFO),
+    }
+    response
+}
+
+/// Error for server errors, warn for client errors, info otherwise.
+fn level_of(status: StatusCode) -> Level {
+    if status.is_server_error() {
+        Level::ERROR
+    } else if status.is_client_error() {
+        Level::WARN
+    } else {
+        Level::INFO
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn logger(body_sample_rate: f64) -> HttpLogger {
+        HttpLogger::new(HttpLogSettings {
+            body_sample_rate,
+            ..HttpLogSettings::default()
+        })
+    }
+
+    #[test]
+    fn test_body_sample_rate_logs_a_share_of_bodies() {
+        let sampled = logger(0.1);
+        assert_eq!((0..100).filter(|_| sampled.sample_body()).count(), 10);
+
+        let all = logger(1.0);
+        assert!((0..10).all(|_| all.sample_body()));
+
+        let none = logger(0.0);
+        assert!(!(0..10).any(|_| none.sample_body()));
+    }
+
+    #[test]
+    fn test_level_follows_status_class() {
+        assert_eq!(level_of(StatusCode::OK), Level::INFO);
+        assert_eq!(level_of(StatusCode::NOT_MODIFIED), Level::INFO);
+        assert_eq!(level_of(StatusCode::TOO_MANY_REQUESTS), Level::WARN);
+        assert_eq!(level_of(StatusCode::BAD_GATEWAY), Level::ERROR);
+    }
+}
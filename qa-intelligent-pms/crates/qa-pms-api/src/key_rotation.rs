@@ -0,0 +1,156 @@
+//! Encryption key rotation.
+//!
+//! To rotate the key, set `ENCRYPTION_KEY` to a new key, move the old one to
+//! `PREVIOUS_ENCRYPTION_KEYS` and restart. Secrets sealed with the old key
+//! keep working; a rotation then re-encrypts every stored secret with the new
+//! key, after which the old key can be removed.
+//!
+//! Every store is checked before any is written, so a secret that no
+//! configured key can open stops the rotation without changing anything.
+
+use anyhow::{Context, Result};
+use qa_pms_config::{Encryptor, RotationCount, TenantCredentialStore, UserConfig};
+use qa_pms_jira::FileTokenStore;
+use serde::Serialize;
+use sqlx::PgPool;
+use utoipa::ToSchema;
+
+use crate::app::{token_store_path, AppState};
+use crate::setup_state::FileSetupStateStore;
+
+/// Outcome of rotating one store.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct StoreRotation {
+    /// Store name
+    pub store: String,
+    /// Secrets re-encrypted with the current key
+    pub rotated: usize,
+    /// Secrets that already used the current key
+    pub unchanged: usize,
+}
+
+/// Outcome of a key rotation.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RotationReport {
+    /// ID of the key secrets are now sealed with
+    pub key_id: String,
+    /// Whether nothing was written
+    pub dry_run: bool,
+    /// Per-store counts
+    pub stores: Vec<StoreRotation>,
+}
+
+/// Re-encrypt every stored secret with the current key, or with `dry_run`
+/// only report what would be re-encrypted.
+///
+/// # Errors
+///
+/// Returns an error if a store cannot be read or written, or holds a secret
+/// that none of the configured keys can decrypt.
+pub async fn rotate_keys(state: &AppState, dry_run: bool) -> Result<RotationReport> {
+    let encryptor = state.settings.encryptor()?;
+    let check = rotate_stores(state, &encryptor, true).await?;
+    let stores = if dry_run {
+        check
+    } else {
+        rotate_stores(state, &encryptor, false).await?
+    };
+    Ok(RotationReport {
+        key_id: encryptor.key_id().to_string(),
+        dry_run,
+        stores: stores
+            .into_iter()
+            .map(|(store, count)| StoreRotation {
+                store: store.to_string(),
+                rotated: count.rotated,
+                unchanged: count.unchanged,
+            })
+            .collect(),
+    })
+}
+
+async fn rotate_stores(
+    state: &AppState,
+    encryptor: &Encryptor,
+    dry_run: bool,
+) -> Result<Vec<(&'static str, RotationCount)>> {
+    let setup_state =
+        FileSetupStateStore::new(FileSetupStateStore::default_path()?, encryptor.clone());
+    let tenant_credentials = match &state.tenant_credentials {
+        Some(store) => store.rotate(dry_run),
+        None => {
+            TenantCredentialStore::new(TenantCredentialStore::default_dir()?, encryptor.clone())
+                .rotate(dry_run)
+        }
+    };
+
+    Ok(vec![
+        (
+            "userConfig",
+            rotate_user_config(encryptor, dry_run).context("Failed to rotate the user config")?,
+        ),
+        ("setupState", setup_state.rotate(dry_run).await?),
+        (
+            "jiraTokens",
+            FileTokenStore::rotate_file(&token_store_path()?, encryptor, dry_run).await?,
+        ),
+        (
+            "tenantCredentials",
+            tenant_credentials.context("Failed to rotate tenant credentials")?,
+        ),
+        (
+            "aiConfigs",
+            rotate_ai_configs(&state.db, encryptor, dry_run).await?,
+        ),
+    ])
+}
+
+fn rotate_user_config(encryptor: &Encryptor, dry_run: bool) -> Result<RotationCount> {
+    let path = UserConfig::default_path()?;
+    if !path.exists() {
+        return Ok(RotationCount::default());
+    }
+    let mut config = UserConfig::from_file(&path)?;
+    let count = config.rotate_secrets(encryptor)?;
+    if count.rotated > 0 && !dry_run {
+        config.write_to_file(&path)?;
+    }
+    Ok(count)
+}
+
+async fn rotate_ai_configs(
+    db: &PgPool,
+    encryptor: &Encryptor,
+    dry_run: bool,
+) -> Result<RotationCount> {
+    let keys: Vec<(String,)> = sqlx::query_as(
+        "SELECT api_key_encrypted FROM ai_configs WHERE api_key_encrypted IS NOT NULL",
+    )
+    .fetch_all(db)
+    .await
+    .context("Failed to read AI configurations")?;
+
+    let mut count = RotationCount::default();
+    for (old,) in keys {
+        let mut new = old.clone();
+        let rotated = count.rotated;
+        encryptor
+            .rotate(&mut new, &mut count)
+            .context("Failed to rotate an AI API key")?;
+        if count.rotated > rotated && !dry_run {
+            // Matching the old value leaves keys replaced meanwhile alone
+            sqlx::query(
+                "UPDATE ai_configs SET api_key_encrypted = $2, updated_at = NOW() \
+                 WHERE api_key_encrypted = $1",
+            )
+            .bind(&old)
+            .bind(&new)
+            .execute(db)
+            .await
+            .context("Failed to store a rotated AI API key")?;
+        }
+    }
+    Ok(count)
+}
@@ -0,0 +1,194 @@
+//! KPI target monitoring.
+//!
+//! Every hour each team's KPI targets are checked against the KPI's value on
+//! each of the last completed UTC days. A target missed on `breach_days`
+//! consecutive days raises a warning alert, pushed to connected clients like
+//! pattern alerts; it is raised once per breach.
+
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use chrono::{NaiveDate, Utc};
+use chrono_tz::Tz;
+use qa_pms_dashboard::{Comparison, Kpi, KpiTarget, KpiTargetRepository, TargetStatus};
+use qa_pms_patterns::{AlertService, PatternRepository};
+use sqlx::PgPool;
+use tokio::time::{interval, MissedTickBehavior};
+use tracing::{info, warn};
+
+use crate::notifications::{Notification, NotificationHub};
+use crate::period::Window;
+use crate::routes::dashboard::{get_period_metrics, PeriodMetrics};
+
+/// Time between checks of the KPI targets.
+const CHECK_INTERVAL: Duration = Duration::from_secs(3600);
+
+/// Checks KPI targets and alerts on breaches.
+pub struct KpiTargetMonitor {
+    db: PgPool,
+    notifications: NotificationHub,
+}
+
+impl KpiTargetMonitor {
+    /// Create a monitor publishing alerts to `notifications`.
+    #[must_use]
+    pub const fn new(db: PgPool, notifications: NotificationHub) -> Self {
+        Self { db, notifications }
+    }
+
+    /// Check every target once. Returns the number of alerts raised.
+    ///
+    /// # Errors
+    /// Returns error if the targets or the daily KPIs cannot be loaded.
+    pub async fn run_once(&self) -> Result<usize> {
+        let repo = KpiTargetRepository::new(self.db.clone());
+        let targets = repo.list(None).await?;
+        let Some(days) = targets.iter().map(|t| t.breach_days).max() else {
+            return Ok(0);
+        };
+
+        let today = Utc::now().date_naive();
+        let mut daily = Vec::new();
+        for offset in (1..=i64::from(days)).rev() {
+            let day = today - chrono::Duration::days(offset);
+            let window = Window::new(Tz::UTC, day, day + chrono::Duration::days(1));
+            let metrics = get_period_metrics(&self.db, &window, None, None)
+                .await
+                .map_err(|e| anyhow!("Failed to compute KPIs for {day}: {e}"))?;
+            daily.push((day, metrics));
+        }
+
+        let alert_service = AlertService::new(PatternRepository::new(self.db.clone()));
+        let mut raised = 0;
+        for target in targets {
+            let values = daily_values(&daily, target.kpi);
+            let outcome = match target.evaluate(&values) {
+                TargetStatus::Breached { since } => {
+                    let latest = values.last().map_or(0.0, |(_, value)| *value);
+                    let (title, message) = breach_text(&target, since, latest);
+                    let actions = vec![format!(
+                        "Review the dashboard for team '{}' and adjust the target if it no longer applies",
+                        target.team
+                    )];
+                    match alert_service
+                        .generate_kpi_alert(title, message, actions)
+                        .await
+                    {
+                        Ok(Some(alert)) => {
+                            raised += 1;
+                            self.notifications.publish(Notification::alert_created(&alert));
+                            repo.set_last_alerted(target.id, Some(alert.created_at))
+                                .await
+                                .map_err(anyhow::Error::from)
+                        }
+                        // Muted: checked again next time, in case the rule expires
+                        Ok(None) => Ok(()),
+                        Err(e) => Err(e),
+                    }
+                }
+                TargetStatus::Recovered => repo
+                    .set_last_alerted(target.id, None)
+                    .await
+                    .map_err(anyhow::Error::from),
+                TargetStatus::Unchanged => Ok(()),
+            };
+            if let Err(e) = outcome {
+                warn!(error = %e, target_id = %target.id, "Failed to update KPI target alert");
+            }
+        }
+        Ok(raised)
+    }
+
+    /// Check the targets every hour, in the background.
+    pub fn start(self) {
+        tokio::spawn(async move {
+            info!("KPI target monitor started");
+            let mut ticker = interval(CHECK_INTERVAL);
+            ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            loop {
+                ticker.tick().await;
+                match self.run_once().await {
+                    Ok(0) => {}
+                    Ok(count) => info!(count, "Raised KPI target breach alerts"),
+                    Err(e) => warn!(error = %e, "KPI target check failed"),
+                }
+            }
+        });
+    }
+}
+
+fn daily_values(daily: &[(NaiveDate, PeriodMetrics)], kpi: Kpi) -> Vec<(NaiveDate, f64)> {
+    daily
+        .iter()
+        .map(|(day, metrics)| (*day, metrics.kpi_value(kpi)))
+        .collect()
+}
+
+/// Alert title and message for a breached target.
+fn breach_text(target: &KpiTarget, since: NaiveDate, latest: f64) -> (String, String) {
+    let name = kpi_name(target.kpi);
+    let side = match target.comparison {
+        Comparison::Below => "above",
+        Comparison::Above => "below",
+    };
+    (
+        format!("{name} off target for team '{}'", target.team),
+        format!(
+            "{name} has been {side} its target of {} every day since {since} (latest: {}).",
+            format_value(target.kpi, target.target_value),
+            format_value(target.kpi, latest),
+        ),
+    )
+}
+
+const fn kpi_name(kpi: Kpi) -> &'static str {
+    match kpi {
+        Kpi::TicketsCompleted => "Tickets completed",
+        Kpi::AvgTimePerTicket => "Average time per ticket",
+        Kpi::Efficiency => "Efficiency",
+        Kpi::TotalHours => "Hours tracked",
+    }
+}
+
+fn format_value(kpi: Kpi, value: f64) -> String {
+    match kpi {
+        Kpi::TicketsCompleted => format!("{value:.0}"),
+        Kpi::AvgTimePerTicket => format!("{:.1}h", value / 3600.0),
+        Kpi::Efficiency => format!("{:.0}%", value * 100.0),
+        Kpi::TotalHours => format!("{value:.1}h"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use qa_pms_dashboard::DEFAULT_TEAM;
+    use uuid::Uuid;
+
+    use super::*;
+
+    #[test]
+    fn test_breach_text_describes_the_miss() {
+        let target = KpiTarget {
+            id: Uuid::new_v4(),
+            team: DEFAULT_TEAM.to_string(),
+            kpi: Kpi::AvgTimePerTicket,
+            comparison: Comparison::Below,
+            target_value: 7200.0,
+            breach_days: 3,
+            last_alerted_at: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+        let since = NaiveDate::from_ymd_opt(2026, 3, 3).unwrap();
+        let (title, message) = breach_text(&target, since, 9000.0);
+
+        assert_eq!(
+            title,
+            "Average time per ticket off target for team 'default'"
+        );
+        assert_eq!(
+            message,
+            "Average time per ticket has been above its target of 2.0h every day since 2026-03-03 (latest: 2.5h)."
+        );
+    }
+}
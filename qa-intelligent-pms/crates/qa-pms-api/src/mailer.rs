@@ -0,0 +1,217 @@
+//! Outgoing email.
+//!
+//! Thin wrapper over an SMTP transport configured from `SMTP_*` settings.
+//! Messages are sent as multipart/alternative with a plain-text and an HTML
+//! body, wrapped in multipart/mixed when a file is attached.
+
+use anyhow::{anyhow, Context, Result};
+use lettre::message::header::ContentType;
+use lettre::message::{Mailbox, MultiPart};
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+use qa_pms_config::settings::{SmtpSettings, SmtpTls};
+use secrecy::ExposeSecret;
+use tracing::info;
+
+/// A file attached to a message.
+#[derive(Debug, Clone)]
+pub struct Attachment {
+    pub filename: String,
+    pub content_type: String,
+    pub body: Vec<u8>,
+}
+
+/// SMTP mailer.
+#[derive(Clone)]
+pub struct Mailer {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+    from: Mailbox,
+}
+
+impl Mailer {
+    /// Build a mailer from settings.
+    ///
+    /// # Errors
+    /// Returns error if the sender address or the relay host is invalid.
+    pub fn from_settings(settings: &SmtpSettings) -> Result<Self> {
+        let from: Mailbox = settings
+            .from
+            .parse()
+            .with_context(|| format!("Invalid SMTP_FROM address: {}", settings.from))?;
+
+        let mut builder = match settings.tls {
+            SmtpTls::StartTls => {
+                AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&settings.host)
+                    .context("Invalid SMTP relay")?
+            }
+            SmtpTls::Tls => AsyncSmtpTransport::<Tokio1Executor>::relay(&settings.host)
+                .context("Invalid SMTP relay")?,
+            SmtpTls::None => {
+                AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(&settings.host)
+            }
+        };
+        if let Some(port) = settings.port {
+            builder = builder.port(port);
+        }
+        if let (Some(username), Some(password)) = (&settings.username, &settings.password) {
+            builder = builder.credentials(Credentials::new(
+                username.clone(),
+                password.expose_secret().clone(),
+            ));
+        }
+
+        Ok(Self {
+            transport: builder.build(),
+            from,
+        })
+    }
+
+    /// Send a message to every recipient.
+    ///
+    /// # Errors
+    /// Returns error if a recipient address is invalid or the server rejects
+    /// the message.
+    pub async fn send(
+        &self,
+        recipients: &[String],
+        subject: &str,
+        text: String,
+        html: String,
+    ) -> Result<()> {
+        self.deliver(
+            build_message(&self.from, recipients, subject, text, html, None)?,
+            recipients,
+            subject,
+        )
+        .await
+    }
+
+    /// Send a message with a file attached to every recipient.
+    ///
+    /// # Errors
+    /// Returns error if a recipient address or the attachment's content type
+    /// is invalid, or the server rejects the message.
+    pub async fn send_with_attachment(
+        &self,
+        recipients: &[String],
+        subject: &str,
+        text: String,
+        html: String,
+        attachment: Attachment,
+    ) -> Result<()> {
+        let message = build_message(
+            &self.from,
+            recipients,
+            subject,
+            text,
+            html,
+            Some(attachment),
+        )?;
+        self.deliver(message, recipients, subject).await
+    }
+
+    async fn deliver(&self, message: Message, recipients: &[String], subject: &str) -> Result<()> {
+        self.transport
+            .send(message)
+            .await
+            .context("SMTP delivery failed")?;
+        info!(recipients = recipients.len(), subject, "Email sent");
+        Ok(())
+    }
+}
+
+fn build_message(
+    from: &Mailbox,
+    recipients: &[String],
+    subject: &str,
+    text: String,
+    html: String,
+    attachment: Option<Attachment>,
+) -> Result<Message> {
+    let mut builder = Message::builder().from(from.clone()).subject(subject);
+    for recipient in recipients {
+        let mailbox: Mailbox = recipient
+            .parse()
+            .with_context(|| format!("Invalid recipient address: {recipient}"))?;
+        builder = builder.to(mailbox);
+    }
+    let body = MultiPart::alternative_plain_html(text, html);
+    let body = match attachment {
+        Some(attachment) => {
+            let content_type = ContentType::parse(&attachment.content_type).map_err(|e| {
+                anyhow!(
+                    "Invalid attachment content type {}: {e}",
+                    attachment.content_type
+                )
+            })?;
+            MultiPart::mixed().multipart(body).singlepart(
+                lettre::message::Attachment::new(attachment.filename)
+                    .body(attachment.body, content_type),
+            )
+        }
+        None => body,
+    };
+    builder.multipart(body).context("Failed to build email")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_message_addresses_every_recipient() {
+        let from: Mailbox = "QA Reports <qa@example.com>".parse().unwrap();
+        let recipients = vec!["lead@example.com".to_string(), "pm@example.com".to_string()];
+        let message = build_message(
+            &from,
+            &recipients,
+            "Weekly QA report",
+            "text".into(),
+            "<p>html</p>".into(),
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(message.envelope().to().len(), 2);
+        let raw = String::from_utf8(message.formatted()).unwrap();
+        assert!(raw.contains("Subject: Weekly QA report"));
+        assert!(raw.contains("multipart/alternative"));
+    }
+
+    #[test]
+    fn test_build_message_rejects_invalid_recipient() {
+        let from: Mailbox = "qa@example.com".parse().unwrap();
+        let result = build_message(
+            &from,
+            &["not an address".to_string()],
+            "s",
+            String::new(),
+            String::new(),
+            None,
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_build_message_with_attachment() {
+        let from: Mailbox = "qa@example.com".parse().unwrap();
+        let attachment = Attachment {
+            filename: "pm-dashboard.csv".to_string(),
+            content_type: "text/csv; charset=utf-8".to_string(),
+            body: b"Metric,Value\n".to_vec(),
+        };
+        let message = build_message(
+            &from,
+            &["pm@example.com".to_string()],
+            "PM dashboard export",
+            "text".into(),
+            "<p>html</p>".into(),
+            Some(attachment),
+        )
+        .unwrap();
+
+        let raw = String::from_utf8(message.formatted()).unwrap();
+        assert!(raw.contains("multipart/mixed"));
+        assert!(raw.contains("filename=\"pm-dashboard.csv\""));
+    }
+}
@@ -2,14 +2,59 @@
 //!
 //! Main entry point for the Axum web server.
 
+use std::net::SocketAddr;
+
 use anyhow::Result;
 use tracing::info;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
+mod api_keys;
 mod app;
+mod audit;
+mod auth;
+mod backup;
+mod cross_origin;
+mod degraded;
+mod digest;
+mod error_capture;
+mod graphql;
+#[cfg(feature = "grpc")]
+mod grpc;
+mod health_history;
 mod health_scheduler;
+mod http_log;
+mod key_rotation;
+mod kpi_monitor;
+mod mailer;
+mod migrations;
+mod notifications;
+mod pattern_scheduler;
+mod period;
+mod project_store;
+mod pm_export;
+mod rate_limit;
+mod redact;
+mod report_aggregate;
+mod report_compare;
+mod report_export;
+mod report_integrity;
+mod report_pdf;
+mod report_scheduler;
 mod routes;
+mod saved_filters;
+mod search_cache;
+mod search_facets;
+mod search_index;
+mod search_ranking;
+mod semantic_index;
+mod setup_state;
 mod startup;
+mod startup_history;
+mod support_bundle;
+mod test_case_store;
+mod test_run_sync;
+mod ticket_watch;
+mod validation;
 
 #[tokio::main]
 async fn main() -> Result<()> {
@@ -31,8 +76,13 @@ async fn main() -> Result<()> {
     info!("Database: {}", settings.database.url_masked());
     info!("Listening on: http://{}", addr);
 
-    // Build the application (returns router and health scheduler)
-    let (app, health_scheduler) = app::create_app(settings).await?;
+    // Build the application (returns router and health scheduler), or serve
+    // the degraded one until the database can be reached
+    let (app, health_scheduler) = match app::create_db_pool(&settings).await {
+        Ok(db) => app::create_app(settings, db).await?,
+        Err(e) if settings.database.degraded_start => (degraded::start(settings, &e).await, None),
+        Err(e) => return Err(e),
+    };
 
     // Start the health scheduler as a background task
     if let Some(scheduler) = health_scheduler {
@@ -40,7 +90,8 @@ async fn main() -> Result<()> {
     }
 
     let listener = tokio::net::TcpListener::bind(&addr).await?;
-    axum::serve(listener, app).await?;
+    // Peer addresses key the rate limits of anonymous clients
+    axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>()).await?;
 
     Ok(())
 }
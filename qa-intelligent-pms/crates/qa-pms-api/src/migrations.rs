@@ -0,0 +1,218 @@
+//! Embedded database migrations.
+//!
+//! Migrations are compiled into the binary and, unless
+//! `DATABASE_AUTO_MIGRATE=false`, applied at startup. The status compares
+//! them with the rows sqlx records in `_sqlx_migrations`, so a database whose
+//! schema has drifted from this build can be spotted before it fails.
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::migrate::{Migration, Migrator};
+use sqlx::{FromRow, PgPool};
+use utoipa::ToSchema;
+
+/// Migrations embedded at build time.
+pub static MIGRATOR: Migrator = sqlx::migrate!("../../migrations");
+
+/// State of one migration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum MigrationState {
+    /// Applied and unchanged
+    Applied,
+    /// Embedded but not applied yet
+    Pending,
+    /// Applied, but the embedded script has changed since
+    Modified,
+    /// Applied, but its script is not embedded in this build
+    Missing,
+    /// Started but did not complete
+    Failed,
+}
+
+/// One migration and its state.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct MigrationStatus {
+    /// Version (timestamp prefix of the file name)
+    pub version: i64,
+    /// Description from the file name
+    pub description: String,
+    /// State in the database
+    pub state: MigrationState,
+    /// When it was applied
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub installed_on: Option<DateTime<Utc>>,
+}
+
+/// Applied and pending migrations.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct MigrationReport {
+    /// Migrations applied and unchanged
+    pub applied: usize,
+    /// Migrations still to apply
+    pub pending: usize,
+    /// Whether the applied migrations differ from this build's (modified,
+    /// missing or failed), which stops new ones from being applied
+    pub drift: bool,
+    /// Every migration by version
+    pub migrations: Vec<MigrationStatus>,
+}
+
+impl MigrationReport {
+    fn new(migrations: Vec<MigrationStatus>) -> Self {
+        let count = |state| migrations.iter().filter(|m| m.state == state).count();
+        Self {
+            applied: count(MigrationState::Applied),
+            pending: count(MigrationState::Pending),
+            drift: migrations
+                .iter()
+                .any(|m| !matches!(m.state, MigrationState::Applied | MigrationState::Pending)),
+            migrations,
+        }
+    }
+}
+
+/// A row of `_sqlx_migrations`.
+#[derive(Debug, FromRow)]
+struct AppliedRow {
+    version: i64,
+    description: String,
+    installed_on: DateTime<Utc>,
+    success: bool,
+    checksum: Vec<u8>,
+}
+
+/// Compare the embedded migrations with the database.
+///
+/// # Errors
+/// Returns an error if the database is unavailable.
+pub async fn status(db: &PgPool) -> Result<MigrationReport, sqlx::Error> {
+    let tracked: bool = sqlx::query_scalar("SELECT to_regclass('_sqlx_migrations') IS NOT NULL")
+        .fetch_one(db)
+        .await?;
+    let applied = if tracked {
+        sqlx::query_as(
+            "SELECT version, description, installed_on, success, checksum \
+             FROM _sqlx_migrations ORDER BY version",
+        )
+        .fetch_all(db)
+        .await?
+    } else {
+        Vec::new()
+    };
+    Ok(compare(MIGRATOR.iter(), applied))
+}
+
+/// Apply pending migrations and return the new status.
+///
+/// # Errors
+/// Returns an error if the database is unavailable or a migration fails,
+/// including when the applied migrations have drifted.
+pub async fn run(db: &PgPool) -> anyhow::Result<MigrationReport> {
+    MIGRATOR.run(db).await?;
+    Ok(status(db).await?)
+}
+
+fn compare<'a>(
+    embedded: impl Iterator<Item = &'a Migration>,
+    applied: Vec<AppliedRow>,
+) -> MigrationReport {
+    let mut applied: std::collections::BTreeMap<i64, AppliedRow> =
+        applied.into_iter().map(|row| (row.version, row)).collect();
+    let mut migrations: Vec<MigrationStatus> = embedded
+        .filter(|m| !m.migration_type.is_down_migration())
+        .map(|m| {
+            let row = applied.remove(&m.version);
+            let state = match &row {
+                None => MigrationState::Pending,
+                Some(row) if !row.success => MigrationState::Failed,
+                Some(row) if row.checksum[..] != m.checksum[..] => MigrationState::Modified,
+                Some(_) => MigrationState::Applied,
+            };
+            MigrationStatus {
+                version: m.version,
+                description: m.description.to_string(),
+                state,
+                installed_on: row.map(|row| row.installed_on),
+            }
+        })
+        .collect();
+    migrations.extend(applied.into_values().map(|row| MigrationStatus {
+        version: row.version,
+        description: row.description,
+        state: MigrationState::Missing,
+        installed_on: Some(row.installed_on),
+    }));
+    migrations.sort_by_key(|m| m.version);
+    MigrationReport::new(migrations)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::migrate::MigrationType;
+    use std::borrow::Cow;
+
+    fn migration(version: i64, sql: &'static str) -> Migration {
+        Migration::new(
+            version,
+            Cow::Owned(format!("step {version}")),
+            MigrationType::Simple,
+            Cow::Borrowed(sql),
+        )
+    }
+
+    fn row(migration: &Migration, success: bool) -> AppliedRow {
+        AppliedRow {
+            version: migration.version,
+            description: migration.description.to_string(),
+            installed_on: Utc::now(),
+            success,
+            checksum: migration.checksum.to_vec(),
+        }
+    }
+
+    #[test]
+    fn test_migrations_are_embedded() {
+        assert!(MIGRATOR.iter().count() > 0);
+    }
+
+    #[test]
+    fn test_pending_migrations_are_not_drift() {
+        let embedded = [
+            migration(1, "CREATE TABLE a ()"),
+            migration(2, "CREATE TABLE b ()"),
+        ];
+        let report = compare(embedded.iter(), vec![row(&embedded[0], true)]);
+
+        assert_eq!((report.applied, report.pending), (1, 1));
+        assert!(!report.drift);
+        assert_eq!(report.migrations[1].state, MigrationState::Pending);
+        assert!(report.migrations[1].installed_on.is_none());
+    }
+
+    #[test]
+    fn test_changed_and_unknown_migrations_are_drift() {
+        let embedded = [migration(1, "CREATE TABLE a ()")];
+        let applied_earlier = migration(1, "CREATE TABLE a (id INT)");
+        let newer_build = migration(3, "CREATE TABLE c ()");
+        let report = compare(
+            embedded.iter(),
+            vec![row(&applied_earlier, true), row(&newer_build, true)],
+        );
+
+        assert!(report.drift);
+        let states: Vec<_> = report.migrations.iter().map(|m| m.state).collect();
+        assert_eq!(states, [MigrationState::Modified, MigrationState::Missing]);
+    }
+
+    #[test]
+    fn test_failed_migration_is_drift() {
+        let embedded = [migration(1, "CREATE TABLE a ()")];
+        let report = compare(embedded.iter(), vec![row(&embedded[0], false)]);
+        assert!(report.drift);
+        assert_eq!(report.migrations[0].state, MigrationState::Failed);
+    }
+}
@@ -0,0 +1,249 @@
+//! Notification hub.
+//!
+//! Fans out alerts, workflow events, time warnings and integration health
+//! changes to connected WebSocket clients. Events belong to a tenant and are
+//! only delivered to connections in that tenant; integration health changes
+//! concern the whole install and go to every tenant. Events addressed to a
+//! user are only delivered to that user's connections.
+
+use chrono::{DateTime, Utc};
+use qa_pms_core::TenantId;
+use qa_pms_patterns::Alert;
+use qa_pms_workflow::WorkflowInstance;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tokio::sync::broadcast;
+use utoipa::ToSchema;
+
+/// Default number of buffered notifications per subscriber.
+pub const DEFAULT_CAPACITY: usize = 256;
+
+/// Category of a notification.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationKind {
+    /// Pattern alert generated
+    Alert,
+    /// Workflow lifecycle event
+    Workflow,
+    /// Time tracking warning (e.g. step over estimate)
+    TimeWarning,
+    /// Integration health status change
+    Health,
+}
+
+impl NotificationKind {
+    /// Parse a kind from its wire name.
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim() {
+            "alert" => Some(Self::Alert),
+            "workflow" => Some(Self::Workflow),
+            "time_warning" => Some(Self::TimeWarning),
+            "health" => Some(Self::Health),
+            _ => None,
+        }
+    }
+}
+
+/// A single notification pushed to clients.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Notification {
+    /// Notification category
+    pub kind: NotificationKind,
+    /// Event name within the category (e.g. "step_completed")
+    pub event: String,
+    /// Tenant the event belongs to, or `None` for install-wide events
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<String>)]
+    pub tenant: Option<TenantId>,
+    /// Recipient user, or `None` for a broadcast
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<String>,
+    /// Event-specific data
+    pub payload: Value,
+    /// When the event happened
+    pub timestamp: DateTime<Utc>,
+}
+
+impl Notification {
+    /// Create a notification for everyone in a tenant.
+    pub fn new(tenant: TenantId, kind: NotificationKind, event: &str, payload: Value) -> Self {
+        Self {
+            kind,
+            event: event.to_string(),
+            tenant: Some(tenant),
+            user_id: None,
+            payload,
+            timestamp: Utc::now(),
+        }
+    }
+
+    /// Create a notification for every tenant, such as an integration health
+    /// change.
+    pub fn install_wide(kind: NotificationKind, event: &str, payload: Value) -> Self {
+        Self {
+            tenant: None,
+            ..Self::new(TenantId::default(), kind, event, payload)
+        }
+    }
+
+    /// Announce a newly generated alert to its tenant.
+    pub fn alert_created(alert: &Alert) -> Self {
+        Self::new(
+            tenant_of(&alert.tenant_id),
+            NotificationKind::Alert,
+            "created",
+            serde_json::to_value(alert).unwrap_or_default(),
+        )
+    }
+
+    /// Create a notification for the owner of a workflow, in its tenant.
+    pub fn for_workflow_owner(
+        instance: &WorkflowInstance,
+        kind: NotificationKind,
+        event: &str,
+        payload: Value,
+    ) -> Self {
+        Self::new(tenant_of(&instance.tenant_id), kind, event, payload).for_user(&instance.user_id)
+    }
+
+    /// Address the notification to a single user.
+    #[must_use]
+    pub fn for_user(mut self, user_id: &str) -> Self {
+        self.user_id = Some(user_id.to_string());
+        self
+    }
+
+    /// Whether a subscriber in `tenant` should receive this notification.
+    ///
+    /// An empty `topics` list means all kinds.
+    pub fn is_visible_to(
+        &self,
+        tenant: &TenantId,
+        user_id: Option<&str>,
+        topics: &[NotificationKind],
+    ) -> bool {
+        let tenant_ok = self.tenant.as_ref().map_or(true, |target| target == tenant);
+        let user_ok = match (&self.user_id, user_id) {
+            (None, _) => true,
+            (Some(target), Some(user)) => target == user,
+            (Some(_), None) => false,
+        };
+        tenant_ok && user_ok && (topics.is_empty() || topics.contains(&self.kind))
+    }
+}
+
+/// Tenant stored on a record, falling back to the default for malformed IDs.
+fn tenant_of(tenant_id: &str) -> TenantId {
+    TenantId::parse(tenant_id).unwrap_or_default()
+}
+
+/// Broadcast hub shared through `AppState`.
+#[derive(Clone)]
+pub struct NotificationHub {
+    sender: broadcast::Sender<Notification>,
+}
+
+impl Default for NotificationHub {
+    fn default() -> Self {
+        Self::new(DEFAULT_CAPACITY)
+    }
+}
+
+impl NotificationHub {
+    /// Create a hub buffering up to `capacity` notifications per subscriber.
+    pub fn new(capacity: usize) -> Self {
+        let (sender, _) = broadcast::channel(capacity);
+        Self { sender }
+    }
+
+    /// Publish a notification to all current subscribers.
+    ///
+    /// Publishing with no connected clients is not an error.
+    pub fn publish(&self, notification: Notification) {
+        let _ = self.sender.send(notification);
+    }
+
+    /// Subscribe to all future notifications.
+    pub fn subscribe(&self) -> broadcast::Receiver<Notification> {
+        self.sender.subscribe()
+    }
+
+    /// Number of connected subscribers.
+    pub fn subscriber_count(&self) -> usize {
+        self.sender.receiver_count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_visibility_respects_user_and_topics() {
+        let tenant = TenantId::default();
+        let broadcast = Notification::new(tenant.clone(), NotificationKind::Alert, "created", json!({}));
+        let personal = Notification::new(tenant.clone(), NotificationKind::Workflow, "paused", json!({}))
+            .for_user("alice@example.com");
+
+        assert!(broadcast.is_visible_to(&tenant, None, &[]));
+        assert!(broadcast.is_visible_to(&tenant, Some("bob@example.com"), &[]));
+        assert!(!broadcast.is_visible_to(&tenant, None, &[NotificationKind::Health]));
+
+        assert!(personal.is_visible_to(&tenant, Some("alice@example.com"), &[NotificationKind::Workflow]));
+        assert!(!personal.is_visible_to(&tenant, Some("bob@example.com"), &[]));
+        assert!(!personal.is_visible_to(&tenant, None, &[]));
+    }
+
+    #[test]
+    fn test_visibility_respects_tenant() {
+        let acme = TenantId::parse("acme").unwrap();
+        let globex = TenantId::parse("globex").unwrap();
+        let alert = Notification::new(acme.clone(), NotificationKind::Alert, "created", json!({}));
+        let personal = Notification::new(acme.clone(), NotificationKind::Workflow, "paused", json!({}))
+            .for_user("alice@example.com");
+        let health = Notification::install_wide(NotificationKind::Health, "status_changed", json!({}));
+
+        assert!(alert.is_visible_to(&acme, None, &[]));
+        assert!(!alert.is_visible_to(&globex, None, &[]));
+        // Same user ID in another tenant is someone else
+        assert!(!personal.is_visible_to(&globex, Some("alice@example.com"), &[]));
+        assert!(health.is_visible_to(&acme, None, &[]));
+        assert!(health.is_visible_to(&globex, None, &[]));
+    }
+
+    #[test]
+    fn test_kind_parse_round_trips() {
+        for kind in [
+            NotificationKind::Alert,
+            NotificationKind::Workflow,
+            NotificationKind::TimeWarning,
+            NotificationKind::Health,
+        ] {
+            let wire = serde_json::to_value(kind).unwrap();
+            assert_eq!(NotificationKind::parse(wire.as_str().unwrap()), Some(kind));
+        }
+        assert_eq!(NotificationKind::parse("bogus"), None);
+    }
+
+    #[tokio::test]
+    async fn test_publish_reaches_subscribers() {
+        let hub = NotificationHub::new(8);
+        hub.publish(Notification::new(TenantId::default(), NotificationKind::Alert, "dropped", json!({})));
+
+        let mut rx = hub.subscribe();
+        assert_eq!(hub.subscriber_count(), 1);
+        hub.publish(Notification::new(
+            TenantId::default(),
+            NotificationKind::Alert,
+            "created",
+            json!({ "id": 1 }),
+        ));
+
+        let received = rx.recv().await.unwrap();
+        assert_eq!(received.event, "created");
+        assert_eq!(received.payload["id"], 1);
+    }
+}
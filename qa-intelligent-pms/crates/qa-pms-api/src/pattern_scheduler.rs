@@ -0,0 +1,103 @@
+//! Scheduled pattern detection.
+//!
+//! Workflow completion only triggers detection for that workflow. This
+//! background task also sweeps recent workflow activity at a fixed interval,
+//! so recurring problems, volume spikes and requirement gaps show up while
+//! tickets are still being worked on.
+
+use std::time::Duration;
+
+use anyhow::Result;
+use chrono::Utc;
+use qa_pms_config::settings::PatternSweepSettings;
+use qa_pms_patterns::{AlertService, DetectedPattern, PatternDetector, PatternRepository};
+use sqlx::PgPool;
+use tokio::time::{interval, MissedTickBehavior};
+use tracing::{info, warn};
+
+use crate::notifications::{Notification, NotificationHub};
+
+/// Interval-driven pattern sweep.
+pub struct PatternScheduler {
+    db: PgPool,
+    notifications: NotificationHub,
+    interval: Duration,
+    lookback: chrono::Duration,
+    requirement_gap_phrases: Vec<String>,
+}
+
+impl PatternScheduler {
+    /// Create a scheduler from settings, or `None` if the sweep is disabled.
+    #[must_use]
+    pub fn new(
+        db: PgPool,
+        notifications: NotificationHub,
+        settings: &PatternSweepSettings,
+    ) -> Option<Self> {
+        if settings.interval_secs == 0 {
+            return None;
+        }
+        Some(Self {
+            db,
+            notifications,
+            interval: Duration::from_secs(settings.interval_secs),
+            lookback: chrono::Duration::hours(i64::from(settings.lookback_hours)),
+            requirement_gap_phrases: settings.requirement_gap_phrases.clone(),
+        })
+    }
+
+    /// Sweep the lookback window once and raise alerts for new patterns.
+    ///
+    /// # Errors
+    /// Returns error if pattern detection fails.
+    pub async fn run_once(&self) -> Result<usize> {
+        let detector = PatternDetector::new(self.db.clone())
+            .with_requirement_gap_phrases(self.requirement_gap_phrases.clone());
+        let patterns = detector.sweep(Utc::now() - self.lookback).await?;
+        let count = patterns.len();
+        publish_pattern_alerts(self.db.clone(), &self.notifications, patterns).await;
+        Ok(count)
+    }
+
+    /// Run the sweep in the background.
+    pub fn start(self) {
+        tokio::spawn(async move {
+            info!(
+                interval_secs = self.interval.as_secs(),
+                lookback_hours = self.lookback.num_hours(),
+                "Pattern scheduler started"
+            );
+            let mut ticker = interval(self.interval);
+            ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            // The first tick fires immediately; skip it so startup stays quiet
+            ticker.tick().await;
+            loop {
+                ticker.tick().await;
+                if let Err(e) = self.run_once().await {
+                    warn!(error = %e, "Scheduled pattern sweep failed");
+                }
+            }
+        });
+    }
+}
+
+/// Turn detected patterns into alerts and push them to connected clients.
+///
+/// Failures are logged per pattern, so one bad alert does not drop the rest.
+pub(crate) async fn publish_pattern_alerts(
+    pool: PgPool,
+    hub: &NotificationHub,
+    patterns: Vec<DetectedPattern>,
+) {
+    if patterns.is_empty() {
+        return;
+    }
+    let alert_service = AlertService::new(PatternRepository::new(pool));
+    for pattern in patterns {
+        match alert_service.generate_alert(&pattern).await {
+            Ok(Some(alert)) => hub.publish(Notification::alert_created(&alert)),
+            Ok(None) => {}
+            Err(e) => warn!(error = %e, "Failed to generate alert for pattern"),
+        }
+    }
+}
@@ -0,0 +1,290 @@
+//! Reporting periods for the dashboards.
+//!
+//! A period is a run of whole days in the user's timezone: either a preset
+//! ending today (`7d`, `30d`, `90d`, `1y`) or explicit `from`/`to` dates,
+//! both inclusive. [`period_boundaries`] turns it into UTC instants for
+//! queries, together with the equally long period just before it for
+//! comparisons.
+
+use chrono::{DateTime, Duration, NaiveDate, TimeZone, Utc};
+use chrono_tz::Tz;
+use serde::Deserialize;
+
+/// Longest period that can be requested, in days.
+pub const MAX_PERIOD_DAYS: i64 = 366;
+
+/// Query parameters selecting a dashboard period.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PeriodQuery {
+    /// Preset ending today: 7d, 30d, 90d, 1y (default: 30d); ignored when `from` is set
+    #[serde(default = "default_period")]
+    pub period: String,
+    /// First day, inclusive
+    pub from: Option<NaiveDate>,
+    /// Last day, inclusive (default: today)
+    pub to: Option<NaiveDate>,
+    /// IANA timezone the days are counted in (default: UTC)
+    pub tz: Option<String>,
+}
+
+fn default_period() -> String {
+    "30d".to_string()
+}
+
+impl Default for PeriodQuery {
+    fn default() -> Self {
+        Self {
+            period: default_period(),
+            from: None,
+            to: None,
+            tz: None,
+        }
+    }
+}
+
+impl PeriodQuery {
+    /// Resolve the query into a period.
+    ///
+    /// # Errors
+    /// Returns a message when the timezone is unknown or the dates do not
+    /// form a valid range.
+    pub fn resolve(&self) -> Result<Period, String> {
+        self.resolve_at(Utc::now())
+    }
+
+    fn resolve_at(&self, now: DateTime<Utc>) -> Result<Period, String> {
+        let timezone = match self
+            .tz
+            .as_deref()
+            .map(str::trim)
+            .filter(|tz| !tz.is_empty())
+        {
+            Some(name) => name
+                .parse::<Tz>()
+                .map_err(|_| format!("Unknown timezone: {name}"))?,
+            None => Tz::UTC,
+        };
+        let today = now.with_timezone(&timezone).date_naive();
+
+        let (from, to, label) = match (self.from, self.to) {
+            (Some(from), to) => {
+                let to = to.unwrap_or(today);
+                (from, to, format!("{from}..{to}"))
+            }
+            (None, Some(_)) => return Err("from is required when to is given".to_string()),
+            (None, None) => {
+                let days = preset_days(&self.period);
+                (today - Duration::days(days - 1), today, self.period.clone())
+            }
+        };
+        if from > to {
+            return Err(format!("from ({from}) must not be after to ({to})"));
+        }
+        if (to - from).num_days() >= MAX_PERIOD_DAYS {
+            return Err(format!("Periods are limited to {MAX_PERIOD_DAYS} days"));
+        }
+
+        Ok(Period {
+            from,
+            to,
+            timezone,
+            label,
+        })
+    }
+}
+
+/// Days covered by a preset, falling back to 30 for unknown presets.
+fn preset_days(period: &str) -> i64 {
+    match period {
+        "7d" => 7,
+        "90d" => 90,
+        "1y" => 365,
+        _ => 30,
+    }
+}
+
+/// Whole days in a timezone, from `from` to `to` inclusive.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Period {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub timezone: Tz,
+    /// Preset name or `from..to`, for display
+    pub label: String,
+}
+
+impl Period {
+    /// Number of days covered.
+    #[must_use]
+    pub fn days(&self) -> i64 {
+        (self.to - self.from).num_days() + 1
+    }
+
+    /// Name of the timezone, as understood by `AT TIME ZONE` in SQL.
+    #[must_use]
+    pub fn timezone_name(&self) -> &'static str {
+        self.timezone.name()
+    }
+}
+
+/// A span of whole local days as UTC instants.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Window {
+    /// Start of the first day
+    pub start: DateTime<Utc>,
+    /// Start of the day after the last one
+    pub end: DateTime<Utc>,
+    /// First day
+    pub first_day: NaiveDate,
+    /// Day after the last one
+    pub end_day: NaiveDate,
+}
+
+impl Window {
+    /// Whole days in `timezone` from `first_day` up to `end_day`, exclusive.
+    #[must_use]
+    pub fn new(timezone: Tz, first_day: NaiveDate, end_day: NaiveDate) -> Self {
+        Self {
+            start: local_midnight(timezone, first_day),
+            end: local_midnight(timezone, end_day),
+            first_day,
+            end_day,
+        }
+    }
+}
+
+/// A period and the equally long period just before it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PeriodBoundaries {
+    pub current: Window,
+    pub previous: Window,
+}
+
+/// Compute the UTC boundaries of a period and of the period before it.
+#[must_use]
+pub fn period_boundaries(period: &Period) -> PeriodBoundaries {
+    let end_day = period.to + Duration::days(1);
+    let previous_first = period.from - Duration::days(period.days());
+    PeriodBoundaries {
+        current: Window::new(period.timezone, period.from, end_day),
+        previous: Window::new(period.timezone, previous_first, period.from),
+    }
+}
+
+/// Boundaries of the `days` days up to `end`, and of the days before them.
+///
+/// Unlike a [`Period`] these are not aligned to midnight, for summaries of
+/// e.g. the last 24 hours. The dates are the UTC days the instants fall on.
+#[must_use]
+pub fn rolling_boundaries(end: DateTime<Utc>, days: i64) -> PeriodBoundaries {
+    let window = |start: DateTime<Utc>, end: DateTime<Utc>| Window {
+        start,
+        end,
+        first_day: start.date_naive(),
+        end_day: end.date_naive(),
+    };
+    let start = end - Duration::days(days);
+    PeriodBoundaries {
+        current: window(start, end),
+        previous: window(start - Duration::days(days), start),
+    }
+}
+
+/// Start of a day in a timezone. Where midnight is skipped by a DST change
+/// the day starts at the first instant after the gap.
+fn local_midnight(timezone: Tz, day: NaiveDate) -> DateTime<Utc> {
+    let midnight = day.and_time(chrono::NaiveTime::MIN);
+    (0..=3)
+        .find_map(|hours| {
+            timezone
+                .from_local_datetime(&(midnight + Duration::hours(hours)))
+                .earliest()
+        })
+        .map_or_else(|| midnight.and_utc(), |local| local.with_timezone(&Utc))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> NaiveDate {
+        s.parse().unwrap()
+    }
+
+    fn query(from: Option<&str>, to: Option<&str>, tz: Option<&str>) -> PeriodQuery {
+        PeriodQuery {
+            from: from.map(date),
+            to: to.map(date),
+            tz: tz.map(str::to_string),
+            ..PeriodQuery::default()
+        }
+    }
+
+    fn now() -> DateTime<Utc> {
+        "2026-03-10T23:30:00Z".parse().unwrap()
+    }
+
+    #[test]
+    fn test_presets_end_today_in_the_timezone() {
+        let utc = PeriodQuery::default().resolve_at(now()).unwrap();
+        assert_eq!((utc.from, utc.to), (date("2026-02-09"), date("2026-03-10")));
+        assert_eq!(utc.days(), 30);
+        assert_eq!(utc.label, "30d");
+
+        // Already March 11th in Warsaw.
+        let warsaw = query(None, None, Some("Europe/Warsaw"))
+            .resolve_at(now())
+            .unwrap();
+        assert_eq!(warsaw.to, date("2026-03-11"));
+    }
+
+    #[test]
+    fn test_explicit_range_is_validated() {
+        let period = query(Some("2026-01-01"), Some("2026-01-31"), None)
+            .resolve_at(now())
+            .unwrap();
+        assert_eq!(period.days(), 31);
+        assert_eq!(period.label, "2026-01-01..2026-01-31");
+
+        assert!(query(Some("2026-02-01"), Some("2026-01-01"), None)
+            .resolve_at(now())
+            .is_err());
+        assert!(query(Some("2024-12-31"), Some("2026-01-01"), None)
+            .resolve_at(now())
+            .is_err());
+        assert!(query(None, Some("2026-01-01"), None)
+            .resolve_at(now())
+            .is_err());
+        assert!(query(None, None, Some("Mars/Olympus"))
+            .resolve_at(now())
+            .is_err());
+    }
+
+    #[test]
+    fn test_boundaries_follow_the_timezone() {
+        let period = query(
+            Some("2026-07-01"),
+            Some("2026-07-02"),
+            Some("America/Sao_Paulo"),
+        )
+        .resolve_at(now())
+        .unwrap();
+        let bounds = period_boundaries(&period);
+
+        assert_eq!(
+            bounds.current.start.to_rfc3339(),
+            "2026-07-01T03:00:00+00:00"
+        );
+        assert_eq!(bounds.current.end.to_rfc3339(), "2026-07-03T03:00:00+00:00");
+        assert_eq!(bounds.previous.first_day, date("2026-06-29"));
+        assert_eq!(bounds.previous.end, bounds.current.start);
+    }
+
+    #[test]
+    fn test_midnight_skipped_by_dst() {
+        // Clocks in Santiago jumped from 00:00 to 01:00 on 2024-09-08.
+        let start = local_midnight("America/Santiago".parse().unwrap(), date("2024-09-08"));
+        assert_eq!(start.to_rfc3339(), "2024-09-08T04:00:00+00:00");
+    }
+}
@@ -0,0 +1,234 @@
+//! Scheduled PM dashboard exports.
+//!
+//! On a cron schedule, the PM dashboard for the configured period is
+//! exported as CSV or JSON, written to the export directory and mailed as an
+//! attachment, whichever are configured. Every run is recorded in
+//! `pm_dashboard_exports`, including the destinations that failed.
+
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use anyhow::{anyhow, Context, Result};
+use chrono::{DateTime, NaiveDate, Utc};
+use cron::Schedule;
+use qa_pms_config::settings::PmExportSettings;
+use serde::Serialize;
+use sqlx::{FromRow, PgPool};
+use tracing::{info, warn};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::mailer::{Attachment, Mailer};
+use crate::period::PeriodQuery;
+use crate::routes::pm_dashboard::{export_filename, load_pm_dashboard, render_export};
+
+/// A scheduled export that has run.
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PmExportRecord {
+    /// Export ID
+    pub id: Uuid,
+    /// csv or json
+    pub format: String,
+    /// Dashboard period, e.g. 7d
+    pub period: String,
+    /// First day covered
+    pub period_from: NaiveDate,
+    /// Last day covered
+    pub period_to: NaiveDate,
+    /// File name of the export
+    pub filename: String,
+    /// Size of the export
+    pub size_bytes: i64,
+    /// File paths and email addresses the export was delivered to
+    pub delivered_to: Vec<String>,
+    /// Why delivery failed, for destinations that did not receive it
+    pub error: Option<String>,
+    /// When the export ran
+    pub created_at: DateTime<Utc>,
+}
+
+/// List past exports, newest first.
+///
+/// # Errors
+/// Returns error if the query fails.
+pub async fn list_exports(pool: &PgPool, limit: i64) -> Result<Vec<PmExportRecord>, sqlx::Error> {
+    sqlx::query_as::<_, PmExportRecord>(
+        "SELECT * FROM pm_dashboard_exports ORDER BY created_at DESC LIMIT $1",
+    )
+    .bind(limit)
+    .fetch_all(pool)
+    .await
+}
+
+/// Cron-driven PM dashboard exporter.
+pub struct PmExportScheduler {
+    db: PgPool,
+    mailer: Option<Mailer>,
+    schedule: Schedule,
+    settings: PmExportSettings,
+}
+
+impl PmExportScheduler {
+    /// Create a scheduler from settings. `mailer` is needed when the
+    /// settings name recipients.
+    ///
+    /// # Errors
+    /// Returns error if the cron expression is invalid.
+    pub fn new(db: PgPool, mailer: Option<Mailer>, settings: &PmExportSettings) -> Result<Self> {
+        let schedule = Schedule::from_str(&settings.cron)
+            .with_context(|| format!("Invalid PM_EXPORT_CRON: {}", settings.cron))?;
+        Ok(Self {
+            db,
+            mailer,
+            schedule,
+            settings: settings.clone(),
+        })
+    }
+
+    /// Export the dashboard, deliver it and record the run.
+    ///
+    /// A destination that fails does not stop delivery to the others; the
+    /// failure is recorded with the export.
+    ///
+    /// # Errors
+    /// Returns error if the dashboard cannot be loaded or the run cannot be
+    /// recorded.
+    pub async fn run_once(&self) -> Result<PmExportRecord> {
+        let format = self.settings.format;
+        let period = PeriodQuery {
+            period: self.settings.period.clone(),
+            ..PeriodQuery::default()
+        }
+        .resolve()
+        .map_err(|e| anyhow!(e))?;
+        let dashboard = load_pm_dashboard(&self.db, &period, None, None)
+            .await
+            .map_err(|e| anyhow!("Failed to load PM dashboard: {e}"))?;
+        let body = render_export(format, &period, &dashboard)
+            .map_err(|e| anyhow!("Failed to render PM dashboard export: {e}"))?;
+        let filename = export_filename(format, &period);
+
+        let mut delivered_to = Vec::new();
+        let mut errors = Vec::new();
+        if let Some(directory) = &self.settings.directory {
+            match write_export(directory, &filename, &body).await {
+                Ok(path) => delivered_to.push(path.display().to_string()),
+                Err(e) => errors.push(format!("{e:#}")),
+            }
+        }
+        if !self.settings.recipients.is_empty() {
+            let sent = match &self.mailer {
+                Some(mailer) => {
+                    let subject = format!("PM dashboard export ({})", period.label);
+                    let text = format!(
+                        "The PM dashboard for {} to {} is attached.",
+                        period.from, period.to
+                    );
+                    let html = format!("<p>{text}</p>");
+                    let attachment = Attachment {
+                        filename: filename.clone(),
+                        content_type: format.content_type().to_string(),
+                        body: body.clone(),
+                    };
+                    mailer
+                        .send_with_attachment(
+                            &self.settings.recipients,
+                            &subject,
+                            text,
+                            html,
+                            attachment,
+                        )
+                        .await
+                }
+                None => Err(anyhow!("SMTP is not configured")),
+            };
+            match sent {
+                Ok(()) => delivered_to.extend(self.settings.recipients.iter().cloned()),
+                Err(e) => errors.push(format!("Email: {e:#}")),
+            }
+        }
+
+        let error = (!errors.is_empty()).then(|| errors.join("; "));
+        if let Some(error) = &error {
+            warn!(error = %error, filename, "PM dashboard export was not fully delivered");
+        }
+        let record = sqlx::query_as::<_, PmExportRecord>(
+            r"
+            INSERT INTO pm_dashboard_exports (
+                id, format, period, period_from, period_to, filename, size_bytes,
+                delivered_to, error
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            RETURNING *
+            ",
+        )
+        .bind(Uuid::new_v4())
+        .bind(format.extension())
+        .bind(&period.label)
+        .bind(period.from)
+        .bind(period.to)
+        .bind(&filename)
+        .bind(i64::try_from(body.len()).unwrap_or(i64::MAX))
+        .bind(&delivered_to)
+        .bind(&error)
+        .fetch_one(&self.db)
+        .await
+        .context("Failed to record PM dashboard export")?;
+
+        info!(
+            filename,
+            destinations = record.delivered_to.len(),
+            "PM dashboard export finished"
+        );
+        Ok(record)
+    }
+
+    /// Run on the schedule in the background.
+    pub fn start(self) {
+        tokio::spawn(async move {
+            info!(
+                format = self.settings.format.extension(),
+                period = %self.settings.period,
+                "PM dashboard export scheduler started"
+            );
+            while let Some(next) = self.schedule.upcoming(Utc).next() {
+                let wait = (next - Utc::now()).to_std().unwrap_or_default();
+                tokio::time::sleep(wait).await;
+                if let Err(e) = self.run_once().await {
+                    warn!(error = %e, "Scheduled PM dashboard export failed");
+                }
+            }
+        });
+    }
+}
+
+/// Write an export into `directory`, creating it if needed.
+async fn write_export(directory: &Path, filename: &str, body: &[u8]) -> Result<PathBuf> {
+    tokio::fs::create_dir_all(directory)
+        .await
+        .with_context(|| format!("Failed to create {}", directory.display()))?;
+    let path = directory.join(filename);
+    tokio::fs::write(&path, body)
+        .await
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_write_export_creates_the_directory() {
+        let directory = std::env::temp_dir()
+            .join(format!("pm-export-{}", Uuid::new_v4()))
+            .join("nightly");
+        let path = write_export(&directory, "pm-dashboard.csv", b"Metric,Value\n")
+            .await
+            .unwrap();
+
+        assert_eq!(path, directory.join("pm-dashboard.csv"));
+        assert_eq!(std::fs::read(&path).unwrap(), b"Metric,Value\n");
+        std::fs::remove_dir_all(directory.parent().unwrap()).unwrap();
+    }
+}
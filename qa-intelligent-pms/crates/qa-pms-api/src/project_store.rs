@@ -0,0 +1,112 @@
+//! Postgres storage for projects.
+//!
+//! Default templates are kept as a JSON object from ticket type to template
+//! ID. Keys are unique.
+
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use qa_pms_core::{Project, ProjectRepository};
+use sqlx::{FromRow, PgPool};
+use uuid::Uuid;
+
+const COLUMNS: &str = "id, key, name, default_templates, created_at, updated_at";
+
+#[derive(Debug, FromRow)]
+struct ProjectRow {
+    id: Uuid,
+    key: String,
+    name: String,
+    default_templates: sqlx::types::Json<HashMap<String, Uuid>>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+impl From<ProjectRow> for Project {
+    fn from(row: ProjectRow) -> Self {
+        Self {
+            id: row.id,
+            key: row.key,
+            name: row.name,
+            default_templates: row.default_templates.0,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+        }
+    }
+}
+
+/// Project repository backed by the `projects` table.
+#[derive(Debug, Clone)]
+pub struct PgProjectRepository {
+    pool: PgPool,
+}
+
+impl PgProjectRepository {
+    /// Create a repository over a connection pool.
+    #[must_use]
+    pub const fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl ProjectRepository for PgProjectRepository {
+    async fn get(&self, id: Uuid) -> anyhow::Result<Option<Project>> {
+        let row: Option<ProjectRow> =
+            sqlx::query_as(&format!("SELECT {COLUMNS} FROM projects WHERE id = $1"))
+                .bind(id)
+                .fetch_optional(&self.pool)
+                .await?;
+        Ok(row.map(Into::into))
+    }
+
+    async fn find_by_key(&self, key: &str) -> anyhow::Result<Option<Project>> {
+        let row: Option<ProjectRow> =
+            sqlx::query_as(&format!("SELECT {COLUMNS} FROM projects WHERE key = $1"))
+                .bind(key)
+                .fetch_optional(&self.pool)
+                .await?;
+        Ok(row.map(Into::into))
+    }
+
+    async fn list(&self) -> anyhow::Result<Vec<Project>> {
+        let rows: Vec<ProjectRow> =
+            sqlx::query_as(&format!("SELECT {COLUMNS} FROM projects ORDER BY key"))
+                .fetch_all(&self.pool)
+                .await?;
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    async fn save(&self, project: Project) -> anyhow::Result<Project> {
+        let row: ProjectRow = sqlx::query_as(&format!(
+            r"
+            INSERT INTO projects ({COLUMNS})
+            VALUES ($1, $2, $3, $4, $5, $6)
+            ON CONFLICT (id) DO UPDATE SET
+                key = EXCLUDED.key,
+                name = EXCLUDED.name,
+                default_templates = EXCLUDED.default_templates,
+                updated_at = EXCLUDED.updated_at
+            RETURNING {COLUMNS}
+            "
+        ))
+        .bind(project.id)
+        .bind(&project.key)
+        .bind(&project.name)
+        .bind(sqlx::types::Json(&project.default_templates))
+        .bind(project.created_at)
+        .bind(project.updated_at)
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(row.into())
+    }
+
+    async fn delete(&self, id: Uuid) -> anyhow::Result<bool> {
+        let result = sqlx::query("DELETE FROM projects WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+}
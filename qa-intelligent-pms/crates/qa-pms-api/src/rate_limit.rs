@@ -0,0 +1,633 @@
+//! Token bucket rate limiting.
+//!
+//! Every request draws from its client's bucket: the API key, the signed-in
+//! user, or the peer IP address for anonymous requests. A key may set its
+//! own rate; other clients share the configured one. Requests to endpoints
+//! backed by Jira, Testmo or Postman also draw from that integration's
+//! bucket, shared by all clients and by background pollers so the upstream
+//! quota holds however many people are working. Rejected requests get 429
+//! with `Retry-After`; allowed responses report the limits that applied in
+//! `X-RateLimit-*` headers.
+
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::{Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+use axum::{
+    extract::{ConnectInfo, Request, State},
+    http::{header, HeaderMap, HeaderName, HeaderValue},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use qa_pms_config::settings::RateLimitSettings;
+use qa_pms_core::error::ApiError;
+use tracing::debug;
+
+use crate::api_keys::ApiKeyIdentity;
+use crate::app::AppState;
+use crate::auth::CurrentUser;
+
+/// Client's steady rate, per minute.
+pub const LIMIT_HEADER: HeaderName = HeaderName::from_static("x-ratelimit-limit");
+/// Requests the client can still send right away.
+pub const REMAINING_HEADER: HeaderName = HeaderName::from_static("x-ratelimit-remaining");
+/// Integration whose shared limit also applied.
+pub const INTEGRATION_HEADER: HeaderName = HeaderName::from_static("x-ratelimit-integration");
+/// Integration's steady rate, per minute.
+pub const INTEGRATION_LIMIT_HEADER: HeaderName =
+    HeaderName::from_static("x-ratelimit-integration-limit");
+/// Requests the integration can still take right away.
+pub const INTEGRATION_REMAINING_HEADER: HeaderName =
+    HeaderName::from_static("x-ratelimit-integration-remaining");
+
+/// Client buckets kept before full (idle) ones are dropped.
+const MAX_TRACKED_CLIENTS: usize = 10_000;
+
+/// Downstream service behind a group of endpoints.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Integration {
+    Jira,
+    Testmo,
+    Postman,
+}
+
+/// Endpoints calling out to integrations, by path prefix.
+const INTEGRATION_PREFIXES: &[(&str, &[Integration])] = &[
+    ("/api/v1/tickets", &[Integration::Jira]),
+    ("/api/v1/testmo", &[Integration::Testmo]),
+    ("/api/v1/postman", &[Integration::Postman]),
+    ("/api/v1/integrations/postman/collections", &[Integration::Postman]),
+    ("/api/v1/setup/integrations/jira", &[Integration::Jira]),
+    ("/api/v1/setup/integrations/testmo", &[Integration::Testmo]),
+    ("/api/v1/setup/integrations/postman", &[Integration::Postman]),
+    ("/api/v1/search/contextual", &[Integration::Postman, Integration::Testmo]),
+    ("/api/v1/search/postman", &[Integration::Postman]),
+    ("/api/v1/search/testmo", &[Integration::Testmo]),
+];
+
+/// Saved searches are run again, contextual ones against Postman and Testmo.
+const SAVED_SEARCH_PREFIX: &str = "/api/v1/search/saved/";
+
+impl Integration {
+    /// Integrations an API path calls, if any.
+    #[must_use]
+    pub fn for_path(path: &str) -> &'static [Self] {
+        if path
+            .strip_prefix(SAVED_SEARCH_PREFIX)
+            .is_some_and(|rest| rest.ends_with("/run"))
+        {
+            return &[Self::Postman, Self::Testmo];
+        }
+        INTEGRATION_PREFIXES
+            .iter()
+            .find(|(prefix, _)| {
+                path.strip_prefix(prefix)
+                    .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+            })
+            .map_or(&[], |(_, integrations)| integrations)
+    }
+
+    /// Lowercase name, as reported in headers.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Jira => "jira",
+            Self::Testmo => "testmo",
+            Self::Postman => "postman",
+        }
+    }
+
+    const fn per_minute(self, settings: &RateLimitSettings) -> u32 {
+        match self {
+            Self::Jira => settings.jira_per_minute,
+            Self::Testmo => settings.testmo_per_minute,
+            Self::Postman => settings.postman_per_minute,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct TokenBucket {
+    capacity: f64,
+    per_sec: f64,
+    tokens: f64,
+    updated: Instant,
+}
+
+impl TokenBucket {
+    fn new(per_minute: u32, capacity: u32, now: Instant) -> Self {
+        let capacity = f64::from(capacity.max(1));
+        Self {
+            capacity,
+            per_sec: f64::from(per_minute) / 60.0,
+            tokens: capacity,
+            updated: now,
+        }
+    }
+
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.updated).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.per_sec).min(self.capacity);
+        self.updated = now;
+    }
+
+    /// Take a token, returning what is left or how long until one is available.
+    fn try_take(&mut self, now: Instant) -> Result<u32, Duration> {
+        self.refill(now);
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            Ok(self.tokens as u32)
+        } else {
+            Err(Duration::from_secs_f64((1.0 - self.tokens) / self.per_sec))
+        }
+    }
+
+    fn give_back(&mut self) {
+        self.tokens = (self.tokens + 1.0).min(self.capacity);
+    }
+
+    fn is_full(&self) -> bool {
+        self.tokens >= self.capacity
+    }
+}
+
+/// Limit that applied to an allowed request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Quota {
+    /// Steady rate per minute
+    pub per_minute: u32,
+    /// Requests left right now
+    pub remaining: u32,
+}
+
+/// Outcome of a rate limit check.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RateDecision {
+    /// Go ahead; the quotas that applied are reported back to the client
+    Allowed {
+        client: Option<Quota>,
+        integration: Option<(Integration, Quota)>,
+    },
+    /// Rejected until `retry_after` has passed
+    Limited {
+        retry_after: Duration,
+        integration: Option<Integration>,
+    },
+}
+
+/// In-memory token buckets per client and per integration.
+pub struct RateLimiter {
+    settings: RateLimitSettings,
+    clients: Mutex<HashMap<String, TokenBucket>>,
+    integrations: Mutex<HashMap<Integration, TokenBucket>>,
+}
+
+impl RateLimiter {
+    /// Create a limiter with empty buckets.
+    #[must_use]
+    pub fn new(settings: RateLimitSettings) -> Self {
+        Self {
+            settings,
+            clients: Mutex::new(HashMap::new()),
+            integrations: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Take one request from `client`'s bucket and from the bucket of each
+    /// integration the path calls. A request rejected by an integration
+    /// does not count against the client or the other integrations.
+    ///
+    /// `client_per_minute` replaces the configured client rate when set,
+    /// with the burst capped at that rate.
+    pub fn check(
+        &self,
+        client: &str,
+        client_per_minute: Option<u32>,
+        integrations: &[Integration],
+        now: Instant,
+    ) -> RateDecision {
+        let settings = self.settings;
+        let mut clients = self.clients.lock().unwrap_or_else(PoisonError::into_inner);
+
+        let (per_minute, burst) = client_per_minute.map_or(
+            (settings.client_per_minute, settings.client_burst),
+            |per_minute| (per_minute, settings.client_burst.min(per_minute)),
+        );
+        let client_quota = if per_minute == 0 {
+            None
+        } else {
+            if clients.len() >= MAX_TRACKED_CLIENTS && !clients.contains_key(client) {
+                clients.retain(|_, bucket| {
+                    bucket.refill(now);
+                    !bucket.is_full()
+                });
+            }
+            let bucket = clients
+                .entry(client.to_string())
+                .or_insert_with(|| TokenBucket::new(per_minute, burst, now));
+            match bucket.try_take(now) {
+                Ok(remaining) => Some(Quota {
+                    per_minute,
+                    remaining,
+                }),
+                Err(retry_after) => {
+                    return RateDecision::Limited {
+                        retry_after,
+                        integration: None,
+                    }
+                }
+            }
+        };
+
+        let mut buckets = self
+            .integrations
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        let mut taken: Vec<(Integration, Quota)> = Vec::with_capacity(integrations.len());
+        for &integration in integrations {
+            match take_integration(&settings, &mut buckets, integration, now) {
+                Ok(Some(quota)) => taken.push((integration, quota)),
+                Ok(None) => {}
+                Err(retry_after) => {
+                    for (integration, _) in &taken {
+                        if let Some(bucket) = buckets.get_mut(integration) {
+                            bucket.give_back();
+                        }
+                    }
+                    if client_quota.is_some() {
+                        if let Some(bucket) = clients.get_mut(client) {
+                            bucket.give_back();
+                        }
+                    }
+                    return RateDecision::Limited {
+                        retry_after,
+                        integration: Some(integration),
+                    };
+                }
+            }
+        }
+
+        RateDecision::Allowed {
+            client: client_quota,
+            // The integration closest to its limit is the one to report
+            integration: taken.into_iter().min_by_key(|(_, quota)| quota.remaining),
+        }
+    }
+
+    /// Take one call from an integration's shared bucket, for work that
+    /// calls it outside a request, such as background pollers.
+    ///
+    /// # Errors
+    /// Returns how long until the integration can take another call.
+    pub fn take_integration(&self, integration: Integration, now: Instant) -> Result<(), Duration> {
+        let mut buckets = self
+            .integrations
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        take_integration(&self.settings, &mut buckets, integration, now).map(|_| ())
+    }
+}
+
+/// Take a token from `integration`'s bucket; `None` when it is unlimited.
+fn take_integration(
+    settings: &RateLimitSettings,
+    buckets: &mut HashMap<Integration, TokenBucket>,
+    integration: Integration,
+    now: Instant,
+) -> Result<Option<Quota>, Duration> {
+    let per_minute = integration.per_minute(settings);
+    if per_minute == 0 {
+        return Ok(None);
+    }
+    // Ten seconds' worth of requests may go out at once
+    let bucket = buckets
+        .entry(integration)
+        .or_insert_with(|| TokenBucket::new(per_minute, per_minute / 6, now));
+    bucket.try_take(now).map(|remaining| {
+        Some(Quota {
+            per_minute,
+            remaining,
+        })
+    })
+}
+
+/// Bucket key for a request: the API key, the authenticated user, else the
+/// peer address.
+fn client_key(request: &Request) -> String {
+    if let Some(key) = request.extensions().get::<ApiKeyIdentity>() {
+        return format!("key:{}", key.id);
+    }
+    if let Some(user) = request
+        .extensions()
+        .get::<CurrentUser>()
+        .filter(|user| !user.is_local())
+    {
+        return format!("user:{}", user.id.0);
+    }
+    request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map_or_else(
+            || "unknown".to_string(),
+            |ConnectInfo(addr)| format!("ip:{}", addr.ip()),
+        )
+}
+
+fn set_quota_headers(
+    headers: &mut HeaderMap,
+    client: Option<Quota>,
+    integration: Option<(Integration, Quota)>,
+) {
+    if let Some(quota) = client {
+        headers.insert(LIMIT_HEADER, HeaderValue::from(quota.per_minute));
+        headers.insert(REMAINING_HEADER, HeaderValue::from(quota.remaining));
+    }
+    if let Some((integration, quota)) = integration {
+        headers.insert(
+            INTEGRATION_HEADER,
+            HeaderValue::from_static(integration.as_str()),
+        );
+        headers.insert(
+            INTEGRATION_LIMIT_HEADER,
+            HeaderValue::from(quota.per_minute),
+        );
+        headers.insert(
+            INTEGRATION_REMAINING_HEADER,
+            HeaderValue::from(quota.remaining),
+        );
+    }
+}
+
+/// Middleware enforcing the configured rate limits.
+///
+/// Runs after [`crate::auth::authenticate`] so signed-in users are limited
+/// per account rather than per address.
+pub async fn limit(State(state): State<AppState>, request: Request, next: Next) -> Response {
+    let integrations = Integration::for_path(request.uri().path());
+    let client = client_key(&request);
+    let client_per_minute = request
+        .extensions()
+        .get::<ApiKeyIdentity>()
+        .and_then(|key| key.rate_limit_per_minute);
+
+    match state
+        .rate_limiter
+        .check(&client, client_per_minute, integrations, Instant::now())
+    {
+        RateDecision::Allowed {
+            client,
+            integration,
+        } => {
+            let mut response = next.run(request).await;
+            set_quota_headers(response.headers_mut(), client, integration);
+            response
+        }
+        RateDecision::Limited {
+            retry_after,
+            integration,
+        } => {
+            debug!(
+                client = %client,
+                integration = integration.map(Integration::as_str),
+                retry_after_ms = retry_after.as_millis() as u64,
+                "Rate limit exceeded"
+            );
+            let mut response = ApiError::RateLimited.into_response();
+            let retry_secs = retry_after.as_secs() + u64::from(retry_after.subsec_nanos() > 0);
+            response
+                .headers_mut()
+                .insert(header::RETRY_AFTER, HeaderValue::from(retry_secs.max(1)));
+            if let Some(integration) = integration {
+                response.headers_mut().insert(
+                    INTEGRATION_HEADER,
+                    HeaderValue::from_static(integration.as_str()),
+                );
+            }
+            response
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings(
+        client_per_minute: u32,
+        client_burst: u32,
+        jira_per_minute: u32,
+    ) -> RateLimitSettings {
+        RateLimitSettings {
+            client_per_minute,
+            client_burst,
+            jira_per_minute,
+            testmo_per_minute: 0,
+            postman_per_minute: 0,
+        }
+    }
+
+    #[test]
+    fn test_integration_for_path() {
+        assert_eq!(
+            Integration::for_path("/api/v1/tickets"),
+            &[Integration::Jira]
+        );
+        assert_eq!(
+            Integration::for_path("/api/v1/tickets/PROJ-1"),
+            &[Integration::Jira]
+        );
+        assert_eq!(
+            Integration::for_path("/api/v1/testmo/runs"),
+            &[Integration::Testmo]
+        );
+        assert_eq!(
+            Integration::for_path("/api/v1/postman/cache"),
+            &[Integration::Postman]
+        );
+        assert_eq!(
+            Integration::for_path("/api/v1/integrations/postman/collections/c1/run"),
+            &[Integration::Postman]
+        );
+        assert_eq!(
+            Integration::for_path("/api/v1/setup/integrations/jira/test"),
+            &[Integration::Jira]
+        );
+        assert_eq!(
+            Integration::for_path("/api/v1/search/contextual"),
+            &[Integration::Postman, Integration::Testmo]
+        );
+        assert_eq!(
+            Integration::for_path("/api/v1/search/saved/5d2c/run"),
+            &[Integration::Postman, Integration::Testmo]
+        );
+        assert!(Integration::for_path("/api/v1/search/saved").is_empty());
+        assert!(Integration::for_path("/api/v1/integrations/postman/health/history").is_empty());
+        assert!(Integration::for_path("/api/v1/ticketsearch").is_empty());
+        assert!(Integration::for_path("/api/v1/workflows").is_empty());
+    }
+
+    #[test]
+    fn test_client_bucket_bursts_then_refills() {
+        let limiter = RateLimiter::new(settings(60, 2, 0));
+        let start = Instant::now();
+
+        let first = limiter.check("ip:10.0.0.1", None, &[], start);
+        assert_eq!(
+            first,
+            RateDecision::Allowed {
+                client: Some(Quota {
+                    per_minute: 60,
+                    remaining: 1
+                }),
+                integration: None,
+            }
+        );
+        assert!(matches!(
+            limiter.check("ip:10.0.0.1", None, &[], start),
+            RateDecision::Allowed { .. }
+        ));
+        let RateDecision::Limited { retry_after, .. } = limiter.check("ip:10.0.0.1", None, &[], start)
+        else {
+            panic!("third request should be limited");
+        };
+        assert_eq!(retry_after, Duration::from_secs(1));
+
+        // Other clients have their own bucket
+        assert!(matches!(
+            limiter.check("ip:10.0.0.2", None, &[], start),
+            RateDecision::Allowed { .. }
+        ));
+        // One token per second at 60/min
+        let later = start + Duration::from_secs(1);
+        assert!(matches!(
+            limiter.check("ip:10.0.0.1", None, &[], later),
+            RateDecision::Allowed { .. }
+        ));
+    }
+
+    #[test]
+    fn test_integration_bucket_is_shared_and_refunds_client() {
+        let limiter = RateLimiter::new(settings(600, 10, 6));
+        let now = Instant::now();
+
+        let allowed = limiter.check("user:a", None, &[Integration::Jira], now);
+        let RateDecision::Allowed {
+            client,
+            integration,
+        } = allowed
+        else {
+            panic!("first Jira request should pass");
+        };
+        assert_eq!(client.map(|q| q.remaining), Some(9));
+        assert_eq!(
+            integration.map(|(i, q)| (i, q.remaining)),
+            Some((Integration::Jira, 0))
+        );
+
+        let limited = limiter.check("user:b", None, &[Integration::Jira], now);
+        assert!(matches!(
+            limited,
+            RateDecision::Limited {
+                integration: Some(Integration::Jira),
+                ..
+            }
+        ));
+        // The rejected request did not cost user b anything
+        let RateDecision::Allowed { client, .. } = limiter.check("user:b", None, &[], now) else {
+            panic!("non-Jira request should pass");
+        };
+        assert_eq!(client.map(|q| q.remaining), Some(9));
+    }
+
+    #[test]
+    fn test_fan_out_refunds_integrations_already_taken() {
+        let mut rates = settings(0, 0, 0);
+        rates.postman_per_minute = 60;
+        rates.testmo_per_minute = 6;
+        let limiter = RateLimiter::new(rates);
+        let now = Instant::now();
+        let both = [Integration::Postman, Integration::Testmo];
+
+        let RateDecision::Allowed { integration, .. } = limiter.check("user:a", None, &both, now)
+        else {
+            panic!("first search should pass");
+        };
+        // Testmo has the least left, so it is reported
+        assert_eq!(
+            integration.map(|(i, q)| (i, q.remaining)),
+            Some((Integration::Testmo, 0))
+        );
+        assert!(matches!(
+            limiter.check("user:a", None, &both, now),
+            RateDecision::Limited {
+                integration: Some(Integration::Testmo),
+                ..
+            }
+        ));
+        // The rejected search gave its Postman token back
+        let RateDecision::Allowed { integration, .. } =
+            limiter.check("user:a", None, &[Integration::Postman], now)
+        else {
+            panic!("Postman request should pass");
+        };
+        assert_eq!(integration.map(|(_, q)| q.remaining), Some(8));
+    }
+
+    #[test]
+    fn test_background_calls_share_integration_bucket() {
+        let limiter = RateLimiter::new(settings(0, 0, 6));
+        let now = Instant::now();
+        assert!(limiter.take_integration(Integration::Jira, now).is_ok());
+        assert!(matches!(
+            limiter.check("user:a", None, &[Integration::Jira], now),
+            RateDecision::Limited { .. }
+        ));
+        assert!(limiter.take_integration(Integration::Testmo, now).is_ok());
+    }
+
+    #[test]
+    fn test_key_rate_replaces_client_rate() {
+        let limiter = RateLimiter::new(settings(0, 10, 0));
+        let now = Instant::now();
+        let RateDecision::Allowed { client, .. } = limiter.check("key:a", Some(2), &[], now)
+        else {
+            panic!("first key request should pass");
+        };
+        assert_eq!(
+            client,
+            Some(Quota {
+                per_minute: 2,
+                remaining: 1
+            })
+        );
+        assert!(matches!(
+            limiter.check("key:a", Some(2), &[], now),
+            RateDecision::Allowed { .. }
+        ));
+        assert!(matches!(
+            limiter.check("key:a", Some(2), &[], now),
+            RateDecision::Limited { .. }
+        ));
+        // Without a rate of its own the client limit (off here) applies
+        assert!(matches!(
+            limiter.check("key:b", None, &[], now),
+            RateDecision::Allowed { client: None, .. }
+        ));
+    }
+
+    #[test]
+    fn test_zero_rates_disable_limits() {
+        let limiter = RateLimiter::new(settings(0, 0, 0));
+        let now = Instant::now();
+        for _ in 0..100 {
+            assert_eq!(
+                limiter.check("ip:10.0.0.1", None, &[Integration::Jira], now),
+                RateDecision::Allowed {
+                    client: None,
+                    integration: None,
+                }
+            );
+        }
+    }
+}
@@ -0,0 +1,404 @@
+//! Aggregate reports.
+//!
+//! Summarizes every workflow completed in a date range, optionally narrowed
+//! to the tickets of one sprint: totals, the slowest steps, bugs found and
+//! how close tracked time came to the template estimates.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::PgPool;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use qa_pms_workflow::get_template;
+
+/// Number of steps listed under "slowest steps".
+const SLOWEST_STEPS: usize = 5;
+
+/// Step notes that count as a bug found; same heuristic as the PM dashboard.
+const BUG_NOTES_PATTERN: &str = "bug|error|fail|issue";
+
+/// Which workflows an aggregate report covers.
+#[derive(Debug, Clone)]
+pub struct AggregateScope {
+    /// Completed at or after this instant
+    pub start: DateTime<Utc>,
+    /// Completed before this instant
+    pub end: DateTime<Utc>,
+    /// Sprint the tickets were taken from
+    pub sprint: Option<String>,
+    /// Restrict to these tickets (the sprint's issues)
+    pub ticket_ids: Option<Vec<String>>,
+    /// Only workflows of this tenant
+    pub tenant_id: Option<String>,
+}
+
+/// One completed workflow.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkflowSummary {
+    /// Workflow instance ID
+    pub workflow_instance_id: Uuid,
+    /// Jira ticket key
+    pub ticket_id: String,
+    /// Ticket title from the latest generated report
+    pub ticket_title: Option<String>,
+    /// Workflow template name
+    pub template_name: String,
+    /// Completion time
+    pub completed_at: DateTime<Utc>,
+    /// Tracked time, in seconds
+    pub time_seconds: i64,
+    /// Template estimate, in seconds
+    pub estimated_seconds: i64,
+    /// Step notes mentioning a bug
+    pub bugs_found: i64,
+}
+
+/// Template step ranked by tracked time.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SlowStep {
+    /// Workflow template name
+    pub template_name: String,
+    /// Step name
+    pub step_name: String,
+    /// Workflows in which the step was timed
+    pub runs: usize,
+    /// Average tracked time, in seconds
+    pub average_seconds: i64,
+    /// Longest tracked time, in seconds
+    pub max_seconds: i64,
+    /// Template estimate, in seconds
+    pub estimated_seconds: i64,
+}
+
+/// Totals across all workflows in the report.
+#[derive(Debug, Clone, Default, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AggregateTotals {
+    /// Completed workflows
+    pub workflows: usize,
+    /// Tracked time, in seconds
+    pub time_seconds: i64,
+    /// Template estimates, in seconds
+    pub estimated_seconds: i64,
+    /// Step notes mentioning a bug
+    pub bugs_found: i64,
+    /// Mean of `100 - |actual - estimate| / estimate * 100` (floored at 0)
+    /// over workflows with both tracked time and an estimate
+    pub estimate_accuracy_percent: Option<f64>,
+}
+
+/// Summary of the workflows completed in a period or sprint.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AggregateReport {
+    /// Period start (inclusive)
+    pub start: DateTime<Utc>,
+    /// Period end (exclusive)
+    pub end: DateTime<Utc>,
+    /// Sprint the report is limited to
+    pub sprint: Option<String>,
+    /// Totals
+    pub totals: AggregateTotals,
+    /// Steps with the highest average time
+    pub slowest_steps: Vec<SlowStep>,
+    /// Completed workflows, oldest first
+    pub workflows: Vec<WorkflowSummary>,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct InstanceRow {
+    id: Uuid,
+    template_id: Uuid,
+    ticket_id: String,
+    ticket_title: Option<String>,
+    completed_at: DateTime<Utc>,
+}
+
+/// Template name and per-step `(name, estimated seconds)`.
+struct TemplateSteps {
+    name: String,
+    steps: Vec<(String, i64)>,
+}
+
+fn estimate_accuracy(actual: i64, estimated: i64) -> Option<f64> {
+    if actual <= 0 || estimated <= 0 {
+        return None;
+    }
+    let error = (actual - estimated).abs() as f64 / estimated as f64;
+    Some((100.0 - error * 100.0).max(0.0))
+}
+
+fn build_report(
+    scope: &AggregateScope,
+    instances: Vec<InstanceRow>,
+    step_times: &HashMap<(Uuid, i32), i64>,
+    bugs: &HashMap<Uuid, i64>,
+    templates: &HashMap<Uuid, TemplateSteps>,
+) -> AggregateReport {
+    let mut totals = AggregateTotals::default();
+    let mut accuracies = Vec::new();
+    // (template, step index) -> tracked times
+    let mut per_step: HashMap<(Uuid, usize), Vec<i64>> = HashMap::new();
+    let mut workflows = Vec::with_capacity(instances.len());
+
+    for instance in instances {
+        let template = templates.get(&instance.template_id);
+        let mut time_seconds = 0;
+        let mut estimated_seconds = 0;
+        for (index, (_, estimate)) in template.map_or(&[][..], |t| &t.steps).iter().enumerate() {
+            estimated_seconds += estimate;
+            let key = (instance.id, i32::try_from(index).unwrap_or(i32::MAX));
+            if let Some(&seconds) = step_times.get(&key) {
+                time_seconds += seconds;
+                per_step
+                    .entry((instance.template_id, index))
+                    .or_default()
+                    .push(seconds);
+            }
+        }
+        let bugs_found = bugs.get(&instance.id).copied().unwrap_or(0);
+
+        totals.workflows += 1;
+        totals.time_seconds += time_seconds;
+        totals.estimated_seconds += estimated_seconds;
+        totals.bugs_found += bugs_found;
+        accuracies.extend(estimate_accuracy(time_seconds, estimated_seconds));
+
+        workflows.push(WorkflowSummary {
+            workflow_instance_id: instance.id,
+            ticket_id: instance.ticket_id,
+            ticket_title: instance.ticket_title,
+            template_name: template.map_or_else(String::new, |t| t.name.clone()),
+            completed_at: instance.completed_at,
+            time_seconds,
+            estimated_seconds,
+            bugs_found,
+        });
+    }
+
+    if !accuracies.is_empty() {
+        totals.estimate_accuracy_percent =
+            Some(accuracies.iter().sum::<f64>() / accuracies.len() as f64);
+    }
+
+    let mut slowest_steps: Vec<SlowStep> = per_step
+        .into_iter()
+        .filter_map(|((template_id, index), times)| {
+            let template = templates.get(&template_id)?;
+            let (step_name, estimated_seconds) = template.steps.get(index)?;
+            let runs = times.len();
+            Some(SlowStep {
+                template_name: template.name.clone(),
+                step_name: step_name.clone(),
+                runs,
+                average_seconds: times.iter().sum::<i64>() / i64::try_from(runs).unwrap_or(1),
+                max_seconds: times.iter().copied().max().unwrap_or(0),
+                estimated_seconds: *estimated_seconds,
+            })
+        })
+        .collect();
+    slowest_steps.sort_by(|a, b| {
+        b.average_seconds
+            .cmp(&a.average_seconds)
+            .then_with(|| a.step_name.cmp(&b.step_name))
+    });
+    slowest_steps.truncate(SLOWEST_STEPS);
+
+    AggregateReport {
+        start: scope.start,
+        end: scope.end,
+        sprint: scope.sprint.clone(),
+        totals,
+        slowest_steps,
+        workflows,
+    }
+}
+
+impl AggregateReport {
+    /// Load the workflows in scope and summarize them.
+    ///
+    /// # Errors
+    /// Returns error if a database query fails.
+    pub async fn load(db: &PgPool, scope: &AggregateScope) -> Result<Self, sqlx::Error> {
+        let instances: Vec<InstanceRow> = sqlx::query_as(
+            r"
+            SELECT wi.id, wi.template_id, wi.ticket_id, wr.ticket_title, wi.completed_at
+            FROM workflow_instances wi
+            LEFT JOIN LATERAL (
+                SELECT ticket_title FROM workflow_reports r
+                WHERE r.workflow_instance_id = wi.id
+                ORDER BY r.generated_at DESC
+                LIMIT 1
+            ) wr ON true
+            WHERE wi.status = 'completed'
+              AND wi.completed_at >= $1 AND wi.completed_at < $2
+              AND ($3::TEXT[] IS NULL OR wi.ticket_id = ANY($3))
+              AND ($4::TEXT IS NULL OR wi.tenant_id = $4)
+              AND wi.deleted_at IS NULL
+            ORDER BY wi.completed_at
+            ",
+        )
+        .bind(scope.start)
+        .bind(scope.end)
+        .bind(&scope.ticket_ids)
+        .bind(&scope.tenant_id)
+        .fetch_all(db)
+        .await?;
+
+        let ids: Vec<Uuid> = instances.iter().map(|i| i.id).collect();
+
+        let step_times: HashMap<(Uuid, i32), i64> = sqlx::query_as::<_, (Uuid, i32, i64)>(
+            r"
+            SELECT workflow_instance_id, step_index, SUM(total_seconds)::BIGINT
+            FROM time_sessions
+            WHERE workflow_instance_id = ANY($1)
+            GROUP BY workflow_instance_id, step_index
+            ",
+        )
+        .bind(&ids)
+        .fetch_all(db)
+        .await?
+        .into_iter()
+        .map(|(id, index, seconds)| ((id, index), seconds))
+        .collect();
+
+        let bugs: HashMap<Uuid, i64> = sqlx::query_as(
+            r"
+            SELECT instance_id, COUNT(*) FILTER (WHERE notes ~* $2)
+            FROM workflow_step_results
+            WHERE instance_id = ANY($1)
+            GROUP BY instance_id
+            ",
+        )
+        .bind(&ids)
+        .bind(BUG_NOTES_PATTERN)
+        .fetch_all(db)
+        .await?
+        .into_iter()
+        .collect();
+
+        let mut templates = HashMap::new();
+        for instance in &instances {
+            if templates.contains_key(&instance.template_id) {
+                continue;
+            }
+            if let Some(template) = get_template(db, instance.template_id).await? {
+                let steps = template
+                    .steps()
+                    .iter()
+                    .map(|s| (s.name.clone(), i64::from(s.estimated_minutes) * 60))
+                    .collect();
+                templates.insert(
+                    instance.template_id,
+                    TemplateSteps {
+                        name: template.name,
+                        steps,
+                    },
+                );
+            }
+        }
+
+        Ok(build_report(
+            scope,
+            instances,
+            &step_times,
+            &bugs,
+            &templates,
+        ))
+    }
+
+    /// Human-readable title, also used as the email subject.
+    pub fn title(&self) -> String {
+        let scope = match &self.sprint {
+            Some(sprint) if sprint.chars().all(|c| c.is_ascii_digit()) => {
+                format!("sprint {sprint}")
+            }
+            Some(sprint) => sprint.clone(),
+            None => format!(
+                "{} to {}",
+                self.start.format("%Y-%m-%d"),
+                self.end.format("%Y-%m-%d")
+            ),
+        };
+        let count = self.totals.workflows;
+        format!(
+            "QA report {scope}: {count} workflow{} completed",
+            if count == 1 { "" } else { "s" }
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn scope() -> AggregateScope {
+        AggregateScope {
+            start: Utc.with_ymd_and_hms(2024, 5, 1, 0, 0, 0).unwrap(),
+            end: Utc.with_ymd_and_hms(2024, 5, 8, 0, 0, 0).unwrap(),
+            sprint: None,
+            ticket_ids: None,
+            tenant_id: None,
+        }
+    }
+
+    fn instance(ticket_id: &str, template_id: Uuid) -> InstanceRow {
+        InstanceRow {
+            id: Uuid::new_v4(),
+            template_id,
+            ticket_id: ticket_id.to_string(),
+            ticket_title: None,
+            completed_at: Utc.with_ymd_and_hms(2024, 5, 3, 12, 0, 0).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_estimate_accuracy() {
+        assert_eq!(estimate_accuracy(600, 600), Some(100.0));
+        assert_eq!(estimate_accuracy(900, 600), Some(50.0));
+        assert_eq!(estimate_accuracy(3000, 600), Some(0.0));
+        assert_eq!(estimate_accuracy(0, 600), None);
+        assert_eq!(estimate_accuracy(600, 0), None);
+    }
+
+    #[test]
+    fn test_build_report() {
+        let template_id = Uuid::new_v4();
+        let templates = HashMap::from([(
+            template_id,
+            TemplateSteps {
+                name: "Bug fix".to_string(),
+                steps: vec![("Reproduce".to_string(), 600), ("Verify".to_string(), 600)],
+            },
+        )]);
+        let a = instance("PROJ-1", template_id);
+        let b = instance("PROJ-2", template_id);
+        let step_times = HashMap::from([((a.id, 0), 600), ((a.id, 1), 600), ((b.id, 0), 1800)]);
+        let bugs = HashMap::from([(b.id, 2)]);
+
+        let report = build_report(&scope(), vec![a, b], &step_times, &bugs, &templates);
+
+        assert_eq!(report.totals.workflows, 2);
+        assert_eq!(report.totals.time_seconds, 3000);
+        assert_eq!(report.totals.estimated_seconds, 2400);
+        assert_eq!(report.totals.bugs_found, 2);
+        // PROJ-1 is exact (100%), PROJ-2 took 1800s against 1200s (50%)
+        assert_eq!(report.totals.estimate_accuracy_percent, Some(75.0));
+
+        assert_eq!(report.slowest_steps[0].step_name, "Reproduce");
+        assert_eq!(report.slowest_steps[0].runs, 2);
+        assert_eq!(report.slowest_steps[0].average_seconds, 1200);
+        assert_eq!(report.slowest_steps[0].max_seconds, 1800);
+        assert_eq!(report.workflows[1].template_name, "Bug fix");
+        assert_eq!(
+            report.title(),
+            "QA report 2024-05-01 to 2024-05-08: 2 workflows completed"
+        );
+    }
+}
@@ -0,0 +1,203 @@
+//! Report comparison.
+//!
+//! Lines up two reports for the same ticket and workflow template step by
+//! step, so a retest after a fix shows where time went and what changed.
+
+use serde::Serialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::routes::reports::{ReportResponse, ReportStep};
+
+const SKIPPED: &str = "skipped";
+
+/// One step in both reports.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct StepComparison {
+    /// Step index in the workflow template
+    pub index: usize,
+    /// Step name
+    pub name: String,
+    /// Status in report A (absent if the step is missing)
+    pub status_a: Option<String>,
+    /// Status in report B (absent if the step is missing)
+    pub status_b: Option<String>,
+    /// Time in report A, in seconds
+    pub time_seconds_a: i32,
+    /// Time in report B, in seconds
+    pub time_seconds_b: i32,
+    /// B minus A, in seconds
+    pub time_delta_seconds: i32,
+    /// Skipped in A but not in B
+    pub skipped_only_in_a: bool,
+    /// Skipped in B but not in A
+    pub skipped_only_in_b: bool,
+    /// Whether the step notes differ
+    pub notes_changed: bool,
+    /// Notes in report A
+    pub notes_a: Option<String>,
+    /// Notes in report B
+    pub notes_b: Option<String>,
+}
+
+/// Step-by-step difference between two reports.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ReportComparison {
+    /// Report A ID
+    pub report_a: Uuid,
+    /// Report B ID
+    pub report_b: Uuid,
+    /// Ticket both reports belong to
+    pub ticket_id: String,
+    /// Workflow template both reports were generated from
+    pub template_name: String,
+    /// Total time in report A, in seconds
+    pub total_time_seconds_a: i32,
+    /// Total time in report B, in seconds
+    pub total_time_seconds_b: i32,
+    /// B minus A, in seconds
+    pub total_time_delta_seconds: i32,
+    /// Per-step comparison, in step order
+    pub steps: Vec<StepComparison>,
+}
+
+fn find_step<'a>(steps: &'a [ReportStep], index: usize, name: &str) -> Option<&'a ReportStep> {
+    steps.iter().find(|s| s.index == index && s.name == name)
+}
+
+fn is_skipped(step: Option<&ReportStep>) -> bool {
+    step.is_some_and(|s| s.status == SKIPPED)
+}
+
+/// Compare two reports step by step.
+///
+/// Steps are matched on index and name; a step present in only one report
+/// is listed with the other side's status absent.
+pub fn compare_reports(a: &ReportResponse, b: &ReportResponse) -> ReportComparison {
+    let mut keys: Vec<(usize, &str)> = a
+        .content
+        .steps
+        .iter()
+        .chain(&b.content.steps)
+        .map(|s| (s.index, s.name.as_str()))
+        .collect();
+    keys.sort_unstable();
+    keys.dedup();
+
+    let steps = keys
+        .into_iter()
+        .map(|(index, name)| {
+            let step_a = find_step(&a.content.steps, index, name);
+            let step_b = find_step(&b.content.steps, index, name);
+            let time_a = step_a.map_or(0, |s| s.time_seconds);
+            let time_b = step_b.map_or(0, |s| s.time_seconds);
+            let notes_a = step_a.and_then(|s| s.notes.clone());
+            let notes_b = step_b.and_then(|s| s.notes.clone());
+            StepComparison {
+                index,
+                name: name.to_string(),
+                status_a: step_a.map(|s| s.status.clone()),
+                status_b: step_b.map(|s| s.status.clone()),
+                time_seconds_a: time_a,
+                time_seconds_b: time_b,
+                time_delta_seconds: time_b - time_a,
+                skipped_only_in_a: is_skipped(step_a) && !is_skipped(step_b),
+                skipped_only_in_b: is_skipped(step_b) && !is_skipped(step_a),
+                notes_changed: notes_a != notes_b,
+                notes_a,
+                notes_b,
+            }
+        })
+        .collect();
+
+    ReportComparison {
+        report_a: a.id,
+        report_b: b.id,
+        ticket_id: a.ticket_id.clone(),
+        template_name: a.template_name.clone(),
+        total_time_seconds_a: a.total_time_seconds,
+        total_time_seconds_b: b.total_time_seconds,
+        total_time_delta_seconds: b.total_time_seconds - a.total_time_seconds,
+        steps,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::routes::reports::ReportContent;
+
+    fn step(index: usize, name: &str, status: &str, notes: Option<&str>, time: i32) -> ReportStep {
+        ReportStep {
+            index,
+            name: name.to_string(),
+            status: status.to_string(),
+            notes: notes.map(String::from),
+            time_seconds: time,
+            links: vec![],
+        }
+    }
+
+    fn report(steps: Vec<ReportStep>) -> ReportResponse {
+        let total_time_seconds = steps.iter().map(|s| s.time_seconds).sum();
+        ReportResponse {
+            id: Uuid::new_v4(),
+            workflow_instance_id: Uuid::new_v4(),
+            ticket_id: "PROJ-7".to_string(),
+            ticket_title: None,
+            template_name: "Bug fix".to_string(),
+            content: ReportContent {
+                steps,
+                ..Default::default()
+            },
+            total_time_seconds,
+            generated_at: "2024-05-01T10:00:00+00:00".to_string(),
+            checksum: None,
+            signature: None,
+            jira_publication: None,
+        }
+    }
+
+    #[test]
+    fn test_compare_reports() {
+        let a = report(vec![
+            step(0, "Reproduce", "completed", Some("Fails on zero"), 600),
+            step(1, "Regression", "skipped", None, 0),
+        ]);
+        let b = report(vec![
+            step(0, "Reproduce", "completed", Some("Fixed"), 240),
+            step(1, "Regression", "completed", None, 900),
+        ]);
+
+        let diff = compare_reports(&a, &b);
+        assert_eq!(diff.total_time_delta_seconds, 540);
+        assert_eq!(diff.steps.len(), 2);
+
+        let reproduce = &diff.steps[0];
+        assert_eq!(reproduce.time_delta_seconds, -360);
+        assert!(reproduce.notes_changed);
+        assert!(!reproduce.skipped_only_in_a);
+
+        let regression = &diff.steps[1];
+        assert!(regression.skipped_only_in_a);
+        assert!(!regression.skipped_only_in_b);
+        assert!(!regression.notes_changed);
+    }
+
+    #[test]
+    fn test_compare_reports_with_missing_steps() {
+        let a = report(vec![step(0, "Reproduce", "completed", None, 60)]);
+        let b = report(vec![
+            step(0, "Reproduce", "completed", None, 60),
+            step(1, "Verify", "completed", None, 30),
+        ]);
+
+        let diff = compare_reports(&a, &b);
+        let verify = &diff.steps[1];
+        assert_eq!(verify.status_a, None);
+        assert_eq!(verify.status_b.as_deref(), Some("completed"));
+        assert_eq!(verify.time_delta_seconds, 30);
+    }
+}
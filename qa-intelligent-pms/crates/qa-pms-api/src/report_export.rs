@@ -0,0 +1,897 @@
+//! Report export.
+//!
+//! Renders stored reports as Markdown (Confluence, Slack, repository docs),
+//! as a standalone HTML page, as a printable PDF for stakeholders, or as an
+//! Atlassian Document Format body for Jira comments. Section order, branding
+//! and custom fields come from the layout stored with the report.
+
+use std::fmt::Write;
+
+use qa_pms_jira::adf;
+use serde::Deserialize;
+use serde_json::Value;
+use utoipa::ToSchema;
+
+use crate::report_aggregate::AggregateReport;
+use crate::report_pdf::{Column, PdfDocument};
+use crate::routes::report_templates::{ReportLayout, ReportSection};
+use crate::routes::reports::ReportResponse;
+use crate::routes::tickets::html_escape;
+
+/// Output format for an exported report.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    /// GitHub-flavored Markdown.
+    #[default]
+    #[serde(alias = "md")]
+    Markdown,
+    /// Standalone HTML page.
+    Html,
+    /// Printable A4 PDF.
+    Pdf,
+}
+
+impl ExportFormat {
+    /// MIME type for the rendered document.
+    pub const fn content_type(self) -> &'static str {
+        match self {
+            Self::Markdown => "text/markdown; charset=utf-8",
+            Self::Html => "text/html; charset=utf-8",
+            Self::Pdf => "application/pdf",
+        }
+    }
+
+    /// File extension for downloads.
+    pub const fn extension(self) -> &'static str {
+        match self {
+            Self::Markdown => "md",
+            Self::Html => "html",
+            Self::Pdf => "pdf",
+        }
+    }
+
+    /// Render a report in this format.
+    pub fn render(self, report: &ReportResponse) -> Vec<u8> {
+        match self {
+            Self::Markdown => render_markdown(report).into_bytes(),
+            Self::Html => render_html(report).into_bytes(),
+            Self::Pdf => render_pdf(report),
+        }
+    }
+
+    /// Render an aggregate report in this format.
+    pub fn render_aggregate(self, report: &AggregateReport) -> Vec<u8> {
+        match self {
+            Self::Markdown => render_aggregate_markdown(report).into_bytes(),
+            Self::Html => render_aggregate_html(report).into_bytes(),
+            Self::Pdf => render_aggregate_pdf(report),
+        }
+    }
+}
+
+/// Format seconds as `1h 05m` or `4m 10s`.
+fn format_duration(total_seconds: i32) -> String {
+    let hours = total_seconds / 3600;
+    let minutes = (total_seconds % 3600) / 60;
+    let seconds = total_seconds % 60;
+    if hours > 0 {
+        format!("{hours}h {minutes:02}m")
+    } else {
+        format!("{minutes}m {seconds:02}s")
+    }
+}
+
+pub(crate) fn format_long_duration(total_seconds: i64) -> String {
+    format_duration(i32::try_from(total_seconds).unwrap_or(i32::MAX))
+}
+
+fn accuracy(report: &AggregateReport) -> String {
+    report
+        .totals
+        .estimate_accuracy_percent
+        .map_or_else(|| "n/a".to_string(), |p| format!("{p:.0}%"))
+}
+
+fn title(report: &ReportResponse) -> String {
+    match &report.ticket_title {
+        Some(title) => format!("QA Report: {} - {title}", report.ticket_id),
+        None => format!("QA Report: {}", report.ticket_id),
+    }
+}
+
+/// Keep a value inside one Markdown table cell.
+fn table_cell(text: &str) -> String {
+    text.replace('|', "\\|").replace(['\r', '\n'], " ")
+}
+
+fn markdown_list(out: &mut String, heading: &str, items: &[String]) {
+    if items.is_empty() {
+        return;
+    }
+    let _ = writeln!(out, "\n## {heading}\n");
+    for item in items {
+        let _ = writeln!(out, "- {}", item.replace('\n', " "));
+    }
+}
+
+/// Links of every step, as `(step number, title, url)`.
+fn step_links(report: &ReportResponse) -> Vec<(usize, &str, &str)> {
+    report
+        .content
+        .steps
+        .iter()
+        .flat_map(|step| {
+            step.links
+                .iter()
+                .map(move |link| (step.index + 1, link.title.as_str(), link.url.as_str()))
+        })
+        .collect()
+}
+
+/// Layout stored with the report; reports from before templates existed get
+/// every built-in section.
+fn layout(report: &ReportResponse) -> ReportLayout {
+    report.content.layout.clone().unwrap_or_default()
+}
+
+/// Render a report as Markdown.
+pub fn render_markdown(report: &ReportResponse) -> String {
+    let layout = layout(report);
+    let branding = &layout.branding;
+    let mut out = String::new();
+    if let Some(logo) = &branding.logo_url {
+        let _ = writeln!(out, "![logo]({logo})\n");
+    }
+    if let Some(header) = &branding.header {
+        let _ = writeln!(out, "**{}**\n", header.replace('\n', " "));
+    }
+    let _ = writeln!(out, "# {}", title(report));
+
+    let content = &report.content;
+    for section in &layout.sections {
+        match section {
+            ReportSection::Summary => {
+                let _ = writeln!(out, "\n- **Template:** {}", report.template_name);
+                let _ = writeln!(out, "- **Generated:** {}", report.generated_at);
+                let _ = writeln!(
+                    out,
+                    "- **Total time:** {}",
+                    format_duration(report.total_time_seconds)
+                );
+            }
+            ReportSection::Steps if !content.steps.is_empty() => {
+                out.push_str(
+                    "\n## Steps\n\n| # | Step | Status | Time | Notes |\n|---|---|---|---|---|\n",
+                );
+                for step in &content.steps {
+                    let _ = writeln!(
+                        out,
+                        "| {} | {} | {} | {} | {} |",
+                        step.index + 1,
+                        table_cell(&step.name),
+                        table_cell(&step.status),
+                        format_duration(step.time_seconds),
+                        table_cell(step.notes.as_deref().unwrap_or_default()),
+                    );
+                }
+                let links: Vec<String> = step_links(report)
+                    .into_iter()
+                    .map(|(number, title, url)| format!("Step {number}: [{title}]({url})"))
+                    .collect();
+                markdown_list(&mut out, "Links", &links);
+            }
+            ReportSection::Steps => {}
+            ReportSection::Notes => markdown_list(&mut out, "Notes", &content.notes),
+            ReportSection::TestsCovered => {
+                markdown_list(&mut out, "Tests Covered", &content.tests_covered);
+            }
+            ReportSection::Strategies => markdown_list(&mut out, "Strategies", &content.strategies),
+            ReportSection::CustomFields if !layout.custom_fields.is_empty() => {
+                out.push_str("\n## Details\n\n");
+                for field in &layout.custom_fields {
+                    let _ = writeln!(
+                        out,
+                        "- **{}:** {}",
+                        field.label.replace('\n', " "),
+                        field.value.replace('\n', " ")
+                    );
+                }
+            }
+            ReportSection::CustomFields => {}
+        }
+    }
+    if let Some(footer) = &branding.footer {
+        let _ = writeln!(out, "\n---\n\n{footer}");
+    }
+    if let Some(checksum) = &report.checksum {
+        let _ = writeln!(out, "\nSHA-256: `{checksum}`");
+        if let Some(signature) = &report.signature {
+            let _ = writeln!(out, "Signature (HMAC-SHA256): `{signature}`");
+        }
+    }
+    out
+}
+
+fn html_list(out: &mut String, heading: &str, items: &[String]) {
+    if items.is_empty() {
+        return;
+    }
+    let _ = write!(out, "<h2>{heading}</h2>\n<ul>\n");
+    for item in items {
+        let _ = writeln!(out, "<li>{}</li>", html_escape(item));
+    }
+    out.push_str("</ul>\n");
+}
+
+/// Render a report as a standalone HTML page.
+pub fn render_html(report: &ReportResponse) -> String {
+    let layout = layout(report);
+    let branding = &layout.branding;
+    let title = html_escape(&title(report));
+    let mut out = String::new();
+    let _ = write!(
+        out,
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>{title}</title>\n\
+         <style>body{{font-family:sans-serif;max-width:960px;margin:2rem auto}}\
+         table{{border-collapse:collapse;width:100%}}th,td{{border:1px solid #ccc;padding:4px 8px;text-align:left}}</style>\n\
+         </head>\n<body>\n"
+    );
+    if branding.logo_url.is_some() || branding.header.is_some() {
+        out.push_str("<header>\n");
+        if let Some(logo) = &branding.logo_url {
+            let _ = writeln!(
+                out,
+                "<img src=\"{}\" alt=\"logo\" height=\"48\">",
+                html_escape(logo)
+            );
+        }
+        if let Some(header) = &branding.header {
+            let _ = writeln!(out, "<p><strong>{}</strong></p>", html_escape(header));
+        }
+        out.push_str("</header>\n");
+    }
+    let _ = writeln!(out, "<h1>{title}</h1>");
+
+    let content = &report.content;
+    for section in &layout.sections {
+        match section {
+            ReportSection::Summary => {
+                let _ = write!(
+                    out,
+                    "<ul>\n<li><strong>Template:</strong> {}</li>\n<li><strong>Generated:</strong> {}</li>\n\
+                     <li><strong>Total time:</strong> {}</li>\n</ul>\n",
+                    html_escape(&report.template_name),
+                    html_escape(&report.generated_at),
+                    format_duration(report.total_time_seconds),
+                );
+            }
+            ReportSection::Steps if !content.steps.is_empty() => {
+                out.push_str(
+                    "<h2>Steps</h2>\n<table>\n<tr><th>#</th><th>Step</th><th>Status</th><th>Time</th><th>Notes</th></tr>\n",
+                );
+                for step in &content.steps {
+                    let _ = writeln!(
+                        out,
+                        "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                        step.index + 1,
+                        html_escape(&step.name),
+                        html_escape(&step.status),
+                        format_duration(step.time_seconds),
+                        html_escape(step.notes.as_deref().unwrap_or_default()),
+                    );
+                }
+                out.push_str("</table>\n");
+                let links = step_links(report);
+                if !links.is_empty() {
+                    out.push_str("<h2>Links</h2>\n<ul>\n");
+                    for (number, title, url) in links {
+                        let _ = writeln!(
+                            out,
+                            "<li>Step {number}: <a href=\"{}\">{}</a></li>",
+                            html_escape(url),
+                            html_escape(title)
+                        );
+                    }
+                    out.push_str("</ul>\n");
+                }
+            }
+            ReportSection::Steps => {}
+            ReportSection::Notes => html_list(&mut out, "Notes", &content.notes),
+            ReportSection::TestsCovered => {
+                html_list(&mut out, "Tests Covered", &content.tests_covered)
+            }
+            ReportSection::Strategies => html_list(&mut out, "Strategies", &content.strategies),
+            ReportSection::CustomFields if !layout.custom_fields.is_empty() => {
+                out.push_str("<h2>Details</h2>\n<ul>\n");
+                for field in &layout.custom_fields {
+                    let _ = writeln!(
+                        out,
+                        "<li><strong>{}:</strong> {}</li>",
+                        html_escape(&field.label),
+                        html_escape(&field.value)
+                    );
+                }
+                out.push_str("</ul>\n");
+            }
+            ReportSection::CustomFields => {}
+        }
+    }
+    if let Some(footer) = &branding.footer {
+        let _ = writeln!(out, "<footer><p>{}</p></footer>", html_escape(footer));
+    }
+    if let Some(checksum) = &report.checksum {
+        let _ = write!(
+            out,
+            "<p><small>SHA-256: <code>{}</code>",
+            html_escape(checksum)
+        );
+        if let Some(signature) = &report.signature {
+            let _ = write!(
+                out,
+                "<br>Signature (HMAC-SHA256): <code>{}</code>",
+                html_escape(signature)
+            );
+        }
+        out.push_str("</small></p>\n");
+    }
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+/// Render a report as a printable PDF.
+///
+/// Follows the same layout as the other formats. The logo is left out, as
+/// it would have to be fetched.
+pub fn render_pdf(report: &ReportResponse) -> Vec<u8> {
+    let layout = layout(report);
+    let branding = &layout.branding;
+    let title = title(report);
+    let mut doc = PdfDocument::new(&title);
+    if let Some(header) = &branding.header {
+        doc.small(header);
+    }
+    doc.heading(1, &title);
+
+    let content = &report.content;
+    for section in &layout.sections {
+        match section {
+            ReportSection::Summary => {
+                doc.field("Template", &report.template_name);
+                doc.field("Generated", &report.generated_at);
+                doc.field("Total time", &format_duration(report.total_time_seconds));
+            }
+            ReportSection::Steps if !content.steps.is_empty() => {
+                doc.heading(2, "Steps");
+                let rows: Vec<Vec<String>> = content
+                    .steps
+                    .iter()
+                    .map(|step| {
+                        vec![
+                            (step.index + 1).to_string(),
+                            step.name.clone(),
+                            step.status.clone(),
+                            format_duration(step.time_seconds),
+                            step.notes.clone().unwrap_or_default(),
+                        ]
+                    })
+                    .collect();
+                doc.table(
+                    &[
+                        Column { header: "#", width: 0.06 },
+                        Column { header: "Step", width: 0.28 },
+                        Column { header: "Status", width: 0.14 },
+                        Column { header: "Time", width: 0.12 },
+                        Column { header: "Notes", width: 0.40 },
+                    ],
+                    &rows,
+                );
+                let links: Vec<String> = step_links(report)
+                    .into_iter()
+                    .map(|(number, title, url)| format!("Step {number}: {title} - {url}"))
+                    .collect();
+                if !links.is_empty() {
+                    doc.heading(2, "Links");
+                    doc.bullets(&links);
+                }
+            }
+            ReportSection::Steps => {}
+            ReportSection::Notes => pdf_list(&mut doc, "Notes", &content.notes),
+            ReportSection::TestsCovered => {
+                pdf_list(&mut doc, "Tests Covered", &content.tests_covered);
+            }
+            ReportSection::Strategies => pdf_list(&mut doc, "Strategies", &content.strategies),
+            ReportSection::CustomFields if !layout.custom_fields.is_empty() => {
+                doc.heading(2, "Details");
+                for field in &layout.custom_fields {
+                    doc.field(&field.label, &field.value);
+                }
+            }
+            ReportSection::CustomFields => {}
+        }
+    }
+    if let Some(footer) = &branding.footer {
+        doc.rule();
+        doc.paragraph(footer);
+    }
+    if let Some(checksum) = &report.checksum {
+        doc.small(&format!("SHA-256: {checksum}"));
+        if let Some(signature) = &report.signature {
+            doc.small(&format!("Signature (HMAC-SHA256): {signature}"));
+        }
+    }
+    doc.finish()
+}
+
+fn pdf_list(doc: &mut PdfDocument, heading: &str, items: &[String]) {
+    if items.is_empty() {
+        return;
+    }
+    doc.heading(2, heading);
+    doc.bullets(items);
+}
+
+/// Render an aggregate report as a printable PDF.
+pub fn render_aggregate_pdf(report: &AggregateReport) -> Vec<u8> {
+    let totals = &report.totals;
+    let title = report.title();
+    let mut doc = PdfDocument::new(&title);
+    doc.heading(1, &title);
+    doc.field("Workflows", &totals.workflows.to_string());
+    doc.field(
+        "Total time",
+        &format!(
+            "{} (estimated {})",
+            format_long_duration(totals.time_seconds),
+            format_long_duration(totals.estimated_seconds)
+        ),
+    );
+    doc.field("Estimate accuracy", &accuracy(report));
+    doc.field("Bugs found", &totals.bugs_found.to_string());
+
+    if !report.slowest_steps.is_empty() {
+        doc.heading(2, "Slowest Steps");
+        let rows: Vec<Vec<String>> = report
+            .slowest_steps
+            .iter()
+            .map(|step| {
+                vec![
+                    step.template_name.clone(),
+                    step.step_name.clone(),
+                    step.runs.to_string(),
+                    format_long_duration(step.average_seconds),
+                    format_long_duration(step.max_seconds),
+                    format_long_duration(step.estimated_seconds),
+                ]
+            })
+            .collect();
+        doc.table(
+            &[
+                Column { header: "Template", width: 0.22 },
+                Column { header: "Step", width: 0.30 },
+                Column { header: "Runs", width: 0.09 },
+                Column { header: "Average", width: 0.13 },
+                Column { header: "Max", width: 0.13 },
+                Column { header: "Estimate", width: 0.13 },
+            ],
+            &rows,
+        );
+    }
+
+    if report.workflows.is_empty() {
+        doc.paragraph("No workflows were completed in this period.");
+    } else {
+        doc.heading(2, "Workflows");
+        let rows: Vec<Vec<String>> = report
+            .workflows
+            .iter()
+            .map(|w| {
+                vec![
+                    w.ticket_id.clone(),
+                    w.ticket_title.clone().unwrap_or_default(),
+                    w.template_name.clone(),
+                    w.completed_at.format("%Y-%m-%d %H:%M").to_string(),
+                    format_long_duration(w.time_seconds),
+                    format_long_duration(w.estimated_seconds),
+                    w.bugs_found.to_string(),
+                ]
+            })
+            .collect();
+        doc.table(
+            &[
+                Column { header: "Ticket", width: 0.12 },
+                Column { header: "Title", width: 0.24 },
+                Column { header: "Template", width: 0.14 },
+                Column { header: "Completed", width: 0.16 },
+                Column { header: "Time", width: 0.11 },
+                Column { header: "Estimate", width: 0.12 },
+                Column { header: "Bugs", width: 0.11 },
+            ],
+            &rows,
+        );
+    }
+    doc.finish()
+}
+
+/// Render an aggregate report as Markdown.
+pub fn render_aggregate_markdown(report: &AggregateReport) -> String {
+    let totals = &report.totals;
+    let mut out = String::new();
+    let _ = writeln!(out, "# {}\n", report.title());
+    let _ = writeln!(out, "- **Workflows:** {}", totals.workflows);
+    let _ = writeln!(
+        out,
+        "- **Total time:** {} (estimated {})",
+        format_long_duration(totals.time_seconds),
+        format_long_duration(totals.estimated_seconds)
+    );
+    let _ = writeln!(out, "- **Estimate accuracy:** {}", accuracy(report));
+    let _ = writeln!(out, "- **Bugs found:** {}", totals.bugs_found);
+
+    if !report.slowest_steps.is_empty() {
+        out.push_str("\n## Slowest Steps\n\n| Template | Step | Runs | Average | Max | Estimate |\n|---|---|---|---|---|---|\n");
+        for step in &report.slowest_steps {
+            let _ = writeln!(
+                out,
+                "| {} | {} | {} | {} | {} | {} |",
+                table_cell(&step.template_name),
+                table_cell(&step.step_name),
+                step.runs,
+                format_long_duration(step.average_seconds),
+                format_long_duration(step.max_seconds),
+                format_long_duration(step.estimated_seconds),
+            );
+        }
+    }
+
+    if report.workflows.is_empty() {
+        out.push_str("\nNo workflows were completed in this period.\n");
+    } else {
+        out.push_str("\n## Workflows\n\n| Ticket | Title | Template | Completed | Time | Estimate | Bugs |\n|---|---|---|---|---|---|---|\n");
+        for w in &report.workflows {
+            let _ = writeln!(
+                out,
+                "| {} | {} | {} | {} | {} | {} | {} |",
+                table_cell(&w.ticket_id),
+                table_cell(w.ticket_title.as_deref().unwrap_or_default()),
+                table_cell(&w.template_name),
+                w.completed_at.format("%Y-%m-%d %H:%M"),
+                format_long_duration(w.time_seconds),
+                format_long_duration(w.estimated_seconds),
+                w.bugs_found,
+            );
+        }
+    }
+    out
+}
+
+/// Render an aggregate report as a standalone HTML page.
+pub fn render_aggregate_html(report: &AggregateReport) -> String {
+    let totals = &report.totals;
+    let title = html_escape(&report.title());
+    let mut out = String::new();
+    let _ = write!(
+        out,
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>{title}</title>\n\
+         <style>body{{font-family:sans-serif;max-width:960px;margin:2rem auto}}\
+         table{{border-collapse:collapse;width:100%}}th,td{{border:1px solid #ccc;padding:4px 8px;text-align:left}}</style>\n\
+         </head>\n<body>\n<h1>{title}</h1>\n"
+    );
+    let _ = write!(
+        out,
+        "<ul>\n<li><strong>Workflows:</strong> {}</li>\n<li><strong>Total time:</strong> {} (estimated {})</li>\n\
+         <li><strong>Estimate accuracy:</strong> {}</li>\n<li><strong>Bugs found:</strong> {}</li>\n</ul>\n",
+        totals.workflows,
+        format_long_duration(totals.time_seconds),
+        format_long_duration(totals.estimated_seconds),
+        accuracy(report),
+        totals.bugs_found,
+    );
+
+    if !report.slowest_steps.is_empty() {
+        out.push_str(
+            "<h2>Slowest Steps</h2>\n<table>\n<tr><th>Template</th><th>Step</th><th>Runs</th><th>Average</th><th>Max</th><th>Estimate</th></tr>\n",
+        );
+        for step in &report.slowest_steps {
+            let _ = writeln!(
+                out,
+                "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                html_escape(&step.template_name),
+                html_escape(&step.step_name),
+                step.runs,
+                format_long_duration(step.average_seconds),
+                format_long_duration(step.max_seconds),
+                format_long_duration(step.estimated_seconds),
+            );
+        }
+        out.push_str("</table>\n");
+    }
+
+    if report.workflows.is_empty() {
+        out.push_str("<p>No workflows were completed in this period.</p>\n");
+    } else {
+        out.push_str(
+            "<h2>Workflows</h2>\n<table>\n<tr><th>Ticket</th><th>Title</th><th>Template</th><th>Completed</th><th>Time</th><th>Estimate</th><th>Bugs</th></tr>\n",
+        );
+        for w in &report.workflows {
+            let _ = writeln!(
+                out,
+                "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                html_escape(&w.ticket_id),
+                html_escape(w.ticket_title.as_deref().unwrap_or_default()),
+                html_escape(&w.template_name),
+                w.completed_at.format("%Y-%m-%d %H:%M"),
+                format_long_duration(w.time_seconds),
+                format_long_duration(w.estimated_seconds),
+                w.bugs_found,
+            );
+        }
+        out.push_str("</table>\n");
+    }
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+fn adf_section(out: &mut Vec<Value>, heading: &str, items: &[String]) {
+    if items.is_empty() {
+        return;
+    }
+    out.push(adf::heading(3, heading));
+    out.push(adf::bullet_list(items.iter().map(|item| adf::paragraph(item))));
+}
+
+/// Render a report as an Atlassian Document Format body.
+///
+/// ADF has no empty text nodes, so absent values are left out rather than
+/// rendered blank.
+pub fn render_adf(report: &ReportResponse) -> Value {
+    let layout = layout(report);
+    let content = &report.content;
+    let mut out = Vec::new();
+    if let Some(header) = layout.branding.header.as_deref().filter(|h| !h.is_empty()) {
+        out.push(adf::paragraph(header));
+    }
+    out.push(adf::heading(2, &title(report)));
+
+    for section in &layout.sections {
+        match section {
+            ReportSection::Summary => out.push(adf::paragraph(&format!(
+                "Template: {} | Generated: {} | Total time: {}",
+                report.template_name,
+                report.generated_at,
+                format_duration(report.total_time_seconds)
+            ))),
+            ReportSection::Steps if !content.steps.is_empty() => {
+                out.push(adf::heading(3, "Steps"));
+                out.push(adf::bullet_list(content.steps.iter().map(|step| {
+                    adf::paragraph(&format!(
+                        "{}. {} - {} ({})",
+                        step.index + 1,
+                        step.name,
+                        step.status,
+                        format_duration(step.time_seconds)
+                    ))
+                })));
+            }
+            ReportSection::Steps => {}
+            ReportSection::Notes => adf_section(&mut out, "Notes", &content.notes),
+            ReportSection::TestsCovered => {
+                adf_section(&mut out, "Tests Covered", &content.tests_covered);
+            }
+            ReportSection::Strategies => adf_section(&mut out, "Strategies", &content.strategies),
+            ReportSection::CustomFields if !layout.custom_fields.is_empty() => {
+                out.push(adf::heading(3, "Details"));
+                out.push(adf::bullet_list(layout.custom_fields.iter().map(|field| {
+                    adf::paragraph(&format!("{}: {}", field.label, field.value))
+                })));
+            }
+            ReportSection::CustomFields => {}
+        }
+    }
+    if let Some(footer) = layout.branding.footer.as_deref().filter(|f| !f.is_empty()) {
+        out.push(adf::rule());
+        out.push(adf::paragraph(footer));
+    }
+    if let Some(checksum) = &report.checksum {
+        out.push(adf::paragraph(&format!("SHA-256: {checksum}")));
+    }
+    adf::document(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::routes::report_templates::{ReportBranding, ReportCustomField};
+    use crate::routes::reports::{ReportContent, ReportLink, ReportStep};
+    use uuid::Uuid;
+
+    fn report() -> ReportResponse {
+        ReportResponse {
+            id: Uuid::nil(),
+            workflow_instance_id: Uuid::nil(),
+            ticket_id: "PROJ-7".to_string(),
+            ticket_title: Some("Checkout <b>coupon</b>".to_string()),
+            template_name: "Bug fix".to_string(),
+            content: ReportContent {
+                steps: vec![ReportStep {
+                    index: 0,
+                    name: "Reproduce".to_string(),
+                    status: "completed".to_string(),
+                    notes: Some("Fails when total | discount\nis zero".to_string()),
+                    time_seconds: 250,
+                    links: vec![ReportLink {
+                        title: "Failing run".to_string(),
+                        url: "https://ci.example.com/runs/9".to_string(),
+                    }],
+                }],
+                notes: vec!["Fails when total | discount\nis zero".to_string()],
+                tests_covered: vec!["Coupon applied".to_string()],
+                strategies: vec![],
+                layout: None,
+            },
+            total_time_seconds: 3900,
+            generated_at: "2024-05-01T10:00:00+00:00".to_string(),
+            checksum: None,
+            signature: None,
+            jira_publication: None,
+        }
+    }
+
+    #[test]
+    fn test_render_markdown() {
+        let markdown = render_markdown(&report());
+        assert!(markdown.starts_with("# QA Report: PROJ-7 - Checkout <b>coupon</b>\n"));
+        assert!(markdown.contains("- **Total time:** 1h 05m"));
+        assert!(markdown.contains(
+            "| 1 | Reproduce | completed | 4m 10s | Fails when total \\| discount is zero |"
+        ));
+        assert!(markdown.contains("## Links\n\n- Step 1: [Failing run](https://ci.example.com/runs/9)"));
+        assert!(markdown.contains("## Tests Covered\n\n- Coupon applied"));
+        assert!(!markdown.contains("## Strategies"));
+    }
+
+    #[test]
+    fn test_render_html_escapes_content() {
+        let html = render_html(&report());
+        assert!(html.contains("<h1>QA Report: PROJ-7 - Checkout &lt;b&gt;coupon&lt;/b&gt;</h1>"));
+        assert!(html.contains("<td>Reproduce</td><td>completed</td><td>4m 10s</td>"));
+        assert!(html.contains(
+            "<li>Step 1: <a href=\"https://ci.example.com/runs/9\">Failing run</a></li>"
+        ));
+        assert!(html.ends_with("</html>\n"));
+    }
+
+    #[test]
+    fn test_render_pdf() {
+        let pdf = ExportFormat::Pdf.render(&report());
+        assert!(pdf.starts_with(b"%PDF-"));
+        let text = String::from_utf8_lossy(&pdf);
+        assert!(text.contains("(QA Report: PROJ-7 - Checkout <b>coupon</b>)"));
+        assert!(text.contains("(Reproduce)"));
+        assert!(text.contains("(Step 1: Failing run - https://ci.example.com/runs/9)"));
+        assert!(text.contains("(Page 1 of 1)"));
+    }
+
+    #[test]
+    fn test_export_format_accepts_md() {
+        let format: ExportFormat = serde_json::from_str("\"md\"").unwrap();
+        assert_eq!(format, ExportFormat::Markdown);
+        let format: ExportFormat = serde_json::from_str("\"pdf\"").unwrap();
+        assert_eq!(format.content_type(), "application/pdf");
+    }
+
+    #[test]
+    fn test_render_aggregate() {
+        use crate::report_aggregate::{AggregateTotals, SlowStep, WorkflowSummary};
+        use chrono::{TimeZone, Utc};
+
+        let report = AggregateReport {
+            start: Utc.with_ymd_and_hms(2024, 5, 1, 0, 0, 0).unwrap(),
+            end: Utc.with_ymd_and_hms(2024, 5, 15, 0, 0, 0).unwrap(),
+            sprint: Some("Sprint 42".to_string()),
+            totals: AggregateTotals {
+                workflows: 1,
+                time_seconds: 3900,
+                estimated_seconds: 3600,
+                bugs_found: 2,
+                estimate_accuracy_percent: Some(91.7),
+            },
+            slowest_steps: vec![SlowStep {
+                template_name: "Bug fix".to_string(),
+                step_name: "Reproduce".to_string(),
+                runs: 1,
+                average_seconds: 2400,
+                max_seconds: 2400,
+                estimated_seconds: 1800,
+            }],
+            workflows: vec![WorkflowSummary {
+                workflow_instance_id: Uuid::nil(),
+                ticket_id: "PROJ-7".to_string(),
+                ticket_title: Some("Coupon <b>".to_string()),
+                template_name: "Bug fix".to_string(),
+                completed_at: Utc.with_ymd_and_hms(2024, 5, 3, 9, 0, 0).unwrap(),
+                time_seconds: 3900,
+                estimated_seconds: 3600,
+                bugs_found: 2,
+            }],
+        };
+
+        let markdown = render_aggregate_markdown(&report);
+        assert!(markdown.starts_with("# QA report Sprint 42: 1 workflow completed\n"));
+        assert!(markdown.contains("- **Estimate accuracy:** 92%"));
+        assert!(markdown.contains("| Bug fix | Reproduce | 1 | 40m 00s | 40m 00s | 30m 00s |"));
+        assert!(markdown.contains(
+            "| PROJ-7 | Coupon <b> | Bug fix | 2024-05-03 09:00 | 1h 05m | 1h 00m | 2 |"
+        ));
+
+        let html = render_aggregate_html(&report);
+        assert!(html.contains("<td>Coupon &lt;b&gt;</td>"));
+    }
+
+    #[test]
+    fn test_exports_embed_checksum() {
+        let mut report = report();
+        report.checksum = Some("ab12".to_string());
+        report.signature = Some("cd34".to_string());
+
+        let markdown = render_markdown(&report);
+        assert!(markdown.ends_with("SHA-256: `ab12`\nSignature (HMAC-SHA256): `cd34`\n"));
+        let html = render_html(&report);
+        assert!(html
+            .contains("SHA-256: <code>ab12</code><br>Signature (HMAC-SHA256): <code>cd34</code>"));
+    }
+
+    #[test]
+    fn test_render_adf() {
+        let adf = render_adf(&report());
+        assert_eq!(adf["type"], "doc");
+        let blocks = adf["content"].as_array().unwrap();
+        assert_eq!(blocks[0]["type"], "heading");
+        assert_eq!(
+            blocks[0]["content"][0]["text"],
+            "QA Report: PROJ-7 - Checkout <b>coupon</b>"
+        );
+        let steps = blocks
+            .iter()
+            .position(|b| b["content"][0]["text"] == "Steps")
+            .unwrap();
+        assert_eq!(
+            blocks[steps + 1]["content"][0]["content"][0]["content"][0]["text"],
+            "1. Reproduce - completed (4m 10s)"
+        );
+        // Empty sections are skipped
+        assert!(!blocks
+            .iter()
+            .any(|b| b["content"][0]["text"] == "Strategies"));
+    }
+
+    #[test]
+    fn test_render_follows_template_layout() {
+        let mut report = report();
+        report.content.layout = Some(ReportLayout {
+            sections: vec![ReportSection::CustomFields, ReportSection::TestsCovered],
+            branding: ReportBranding {
+                header: Some("Acme Corp".to_string()),
+                logo_url: None,
+                footer: Some("Confidential".to_string()),
+            },
+            custom_fields: vec![ReportCustomField {
+                label: "Contract".to_string(),
+                value: "C-42".to_string(),
+            }],
+        });
+
+        let markdown = render_markdown(&report);
+        assert!(markdown.starts_with("**Acme Corp**\n\n# QA Report"));
+        assert!(!markdown.contains("## Steps"));
+        assert!(!markdown.contains("**Template:**"));
+        let details = markdown.find("- **Contract:** C-42").unwrap();
+        assert!(details < markdown.find("## Tests Covered").unwrap());
+        assert!(markdown.ends_with("---\n\nConfidential\n"));
+
+        let html = render_html(&report);
+        assert!(html.contains("<p><strong>Acme Corp</strong></p>"));
+        assert!(html.contains("<li><strong>Contract:</strong> C-42</li>"));
+        assert!(!html.contains("<h2>Steps</h2>"));
+    }
+}
@@ -0,0 +1,163 @@
+//! Report checksums and signatures.
+//!
+//! Each report is hashed when it is generated. The SHA-256 covers a canonical
+//! JSON form of the report (everything but the checksum itself and the Jira
+//! publication outcome); when `REPORT_SIGNING_KEY` is set the same bytes are
+//! also signed with HMAC-SHA256, so an auditor holding the key can tell a
+//! report was produced by this installation and not edited since.
+
+use hmac::{Hmac, Mac};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::routes::reports::{ReportContent, ReportResponse};
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Fields covered by the checksum, in a fixed order.
+#[derive(Serialize)]
+struct Canonical<'a> {
+    id: Uuid,
+    workflow_instance_id: Uuid,
+    ticket_id: &'a str,
+    ticket_title: Option<&'a str>,
+    template_name: &'a str,
+    content: &'a ReportContent,
+    total_time_seconds: i32,
+    generated_at: &'a str,
+}
+
+fn canonical_bytes(report: &ReportResponse) -> Vec<u8> {
+    let canonical = Canonical {
+        id: report.id,
+        workflow_instance_id: report.workflow_instance_id,
+        ticket_id: &report.ticket_id,
+        ticket_title: report.ticket_title.as_deref(),
+        template_name: &report.template_name,
+        content: &report.content,
+        total_time_seconds: report.total_time_seconds,
+        generated_at: &report.generated_at,
+    };
+    serde_json::to_vec(&canonical).unwrap_or_default()
+}
+
+/// Hex-encoded SHA-256 of the report.
+pub fn checksum(report: &ReportResponse) -> String {
+    hex::encode(Sha256::digest(canonical_bytes(report)))
+}
+
+fn mac(report: &ReportResponse, key: &str) -> Option<HmacSha256> {
+    let mut mac = HmacSha256::new_from_slice(key.as_bytes()).ok()?;
+    mac.update(&canonical_bytes(report));
+    Some(mac)
+}
+
+/// Hex-encoded HMAC-SHA256 of the report.
+pub fn sign(report: &ReportResponse, key: &str) -> Option<String> {
+    mac(report, key).map(|mac| hex::encode(mac.finalize().into_bytes()))
+}
+
+/// Result of checking a stored report against its checksum and signature.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ReportVerification {
+    /// Report ID
+    pub report_id: Uuid,
+    /// Checksum stored at generation (absent for older reports)
+    pub checksum: Option<String>,
+    /// Checksum of the report as stored now
+    pub computed_checksum: String,
+    /// Whether the stored checksum matches
+    pub checksum_valid: bool,
+    /// Whether the signature matches; absent when the report is unsigned or
+    /// no signing key is configured
+    pub signature_valid: Option<bool>,
+}
+
+/// Check a report's stored checksum and signature.
+pub fn verify(report: &ReportResponse, key: Option<&str>) -> ReportVerification {
+    let computed_checksum = checksum(report);
+    let signature_valid = match (&report.signature, key) {
+        (Some(signature), Some(key)) => Some(
+            hex::decode(signature)
+                .ok()
+                .zip(mac(report, key))
+                .is_some_and(|(bytes, mac)| mac.verify_slice(&bytes).is_ok()),
+        ),
+        _ => None,
+    };
+    ReportVerification {
+        report_id: report.id,
+        checksum_valid: report.checksum.as_deref() == Some(computed_checksum.as_str()),
+        checksum: report.checksum.clone(),
+        computed_checksum,
+        signature_valid,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::routes::reports::ReportStep;
+
+    fn report() -> ReportResponse {
+        let mut report = ReportResponse {
+            id: Uuid::nil(),
+            workflow_instance_id: Uuid::nil(),
+            ticket_id: "PROJ-7".to_string(),
+            ticket_title: None,
+            template_name: "Bug fix".to_string(),
+            content: ReportContent {
+                steps: vec![ReportStep {
+                    index: 0,
+                    name: "Reproduce".to_string(),
+                    status: "completed".to_string(),
+                    notes: None,
+                    time_seconds: 60,
+                    links: vec![],
+                }],
+                ..Default::default()
+            },
+            total_time_seconds: 60,
+            generated_at: "2024-05-01T10:00:00.123456+00:00".to_string(),
+            checksum: None,
+            signature: None,
+            jira_publication: None,
+        };
+        report.checksum = Some(checksum(&report));
+        report.signature = sign(&report, "installation-key");
+        report
+    }
+
+    #[test]
+    fn test_checksum_is_stable_and_ignores_seal_fields() {
+        let report = report();
+        let value = report.checksum.clone().unwrap();
+        assert_eq!(value.len(), 64);
+        assert_eq!(checksum(&report), value);
+    }
+
+    #[test]
+    fn test_verify_untouched_report() {
+        let result = verify(&report(), Some("installation-key"));
+        assert!(result.checksum_valid);
+        assert_eq!(result.signature_valid, Some(true));
+    }
+
+    #[test]
+    fn test_verify_detects_edits_and_wrong_key() {
+        let mut edited = report();
+        edited.content.steps[0].status = "skipped".to_string();
+        let result = verify(&edited, Some("installation-key"));
+        assert!(!result.checksum_valid);
+        assert_eq!(result.signature_valid, Some(false));
+
+        assert_eq!(
+            verify(&report(), Some("other-key")).signature_valid,
+            Some(false)
+        );
+        assert_eq!(verify(&report(), None).signature_valid, None);
+    }
+}
@@ -0,0 +1,386 @@
+//! PDF rendering for report exports.
+//!
+//! A small flowing layout on A4 pages: headings, paragraphs, bullet lists
+//! and wrapped tables, set in the standard Helvetica fonts so nothing has to
+//! be embedded. Text is WinAnsi-encoded; characters outside it print as `?`.
+
+use pdf_writer::{Content, Finish, Name, Pdf, Rect, Ref, Str, TextStr};
+
+const PAGE_WIDTH: f32 = 595.0;
+const PAGE_HEIGHT: f32 = 842.0;
+const MARGIN: f32 = 50.0;
+const CONTENT_WIDTH: f32 = PAGE_WIDTH - 2.0 * MARGIN;
+/// Lowest baseline for body text; the page number sits below it.
+const BOTTOM: f32 = MARGIN + 20.0;
+
+const BODY_SIZE: f32 = 10.0;
+const SMALL_SIZE: f32 = 8.0;
+const CELL_PADDING: f32 = 4.0;
+
+const REGULAR: Name<'static> = Name(b"F1");
+const BOLD: Name<'static> = Name(b"F2");
+
+/// Helvetica advance widths for ASCII 32..=126, in thousandths of an em.
+const HELVETICA_WIDTHS: [u16; 95] = [
+    278, 278, 355, 556, 556, 889, 667, 191, 333, 333, 389, 584, 278, 333, 278, 278, 556, 556, 556,
+    556, 556, 556, 556, 556, 556, 556, 278, 278, 584, 584, 584, 556, 1015, 667, 667, 722, 722, 667,
+    611, 778, 722, 278, 500, 667, 556, 833, 722, 778, 667, 778, 722, 667, 611, 722, 667, 944, 667,
+    667, 611, 278, 278, 278, 469, 556, 333, 556, 556, 500, 556, 556, 278, 556, 556, 222, 222, 500,
+    222, 833, 556, 556, 556, 556, 333, 500, 278, 556, 500, 722, 500, 500, 500, 334, 260, 334, 584,
+];
+
+/// Approximate text width in points; bold runs about 5% wider.
+fn text_width(text: &str, size: f32, bold: bool) -> f32 {
+    let units: u32 = text
+        .chars()
+        .map(|c| {
+            u32::from(
+                (c as usize)
+                    .checked_sub(32)
+                    .and_then(|i| HELVETICA_WIDTHS.get(i))
+                    .copied()
+                    .unwrap_or(556),
+            )
+        })
+        .sum();
+    #[allow(clippy::cast_precision_loss)]
+    let width = units as f32 * size / 1000.0;
+    if bold {
+        width * 1.05
+    } else {
+        width
+    }
+}
+
+/// Break text into lines no wider than `width`, splitting overlong words.
+fn wrap(text: &str, size: f32, bold: bool, width: f32) -> Vec<String> {
+    let mut lines = Vec::new();
+    for paragraph in text.lines() {
+        let mut line = String::new();
+        for word in paragraph.split_whitespace() {
+            let candidate = if line.is_empty() {
+                word.to_string()
+            } else {
+                format!("{line} {word}")
+            };
+            if text_width(&candidate, size, bold) <= width {
+                line = candidate;
+                continue;
+            }
+            if !line.is_empty() {
+                lines.push(std::mem::take(&mut line));
+            }
+            for c in word.chars() {
+                line.push(c);
+                if text_width(&line, size, bold) > width && line.chars().count() > 1 {
+                    line.pop();
+                    lines.push(std::mem::replace(&mut line, c.to_string()));
+                }
+            }
+        }
+        lines.push(line);
+    }
+    if lines.is_empty() {
+        lines.push(String::new());
+    }
+    lines
+}
+
+/// Encode text for the standard fonts' WinAnsi encoding.
+fn encode(text: &str) -> Vec<u8> {
+    text.chars()
+        .map(|c| match c {
+            ' '..='~' => c as u8,
+            '\u{a0}'..='\u{ff}' => u8::try_from(u32::from(c)).unwrap_or(b'?'),
+            '\u{20ac}' => 0x80,
+            '\u{2026}' => 0x85,
+            '\u{2018}' => 0x91,
+            '\u{2019}' => 0x92,
+            '\u{201c}' => 0x93,
+            '\u{201d}' => 0x94,
+            '\u{2022}' => 0x95,
+            '\u{2013}' => 0x96,
+            '\u{2014}' => 0x97,
+            '\t' => b' ',
+            _ => b'?',
+        })
+        .collect()
+}
+
+/// A table column: header and share of the content width.
+pub(crate) struct Column<'a> {
+    pub header: &'a str,
+    pub width: f32,
+}
+
+/// Flowing document writer; content moves to a new page when one fills up.
+pub(crate) struct PdfDocument {
+    title: String,
+    pages: Vec<Content>,
+    current: Content,
+    y: f32,
+}
+
+impl PdfDocument {
+    /// Start a document; `title` becomes the PDF metadata title.
+    pub fn new(title: &str) -> Self {
+        Self {
+            title: title.to_string(),
+            pages: Vec::new(),
+            current: Content::new(),
+            y: PAGE_HEIGHT - MARGIN,
+        }
+    }
+
+    fn new_page(&mut self) {
+        self.pages
+            .push(std::mem::replace(&mut self.current, Content::new()));
+        self.y = PAGE_HEIGHT - MARGIN;
+    }
+
+    /// Make room for `height` points, breaking the page if needed.
+    fn reserve(&mut self, height: f32) {
+        if self.y - height < BOTTOM && self.y < PAGE_HEIGHT - MARGIN {
+            self.new_page();
+        }
+    }
+
+    fn show(&mut self, x: f32, y: f32, text: &str, size: f32, bold: bool) {
+        self.current
+            .begin_text()
+            .set_font(if bold { BOLD } else { REGULAR }, size)
+            .next_line(x, y)
+            .show(Str(&encode(text)))
+            .end_text();
+    }
+
+    fn lines(&mut self, indent: f32, text: &str, size: f32, bold: bool) {
+        let leading = size * 1.4;
+        for line in wrap(text, size, bold, CONTENT_WIDTH - indent) {
+            self.reserve(leading);
+            self.y -= leading;
+            self.show(MARGIN + indent, self.y, &line, size, bold);
+        }
+    }
+
+    fn rule_at(&mut self, y: f32) {
+        self.current
+            .set_line_width(0.5)
+            .set_stroke_gray(0.6)
+            .move_to(MARGIN, y)
+            .line_to(PAGE_WIDTH - MARGIN, y)
+            .stroke();
+    }
+
+    /// Heading; level 1 is the document title.
+    pub fn heading(&mut self, level: u8, text: &str) {
+        let size = if level <= 1 { 18.0 } else { 13.0 };
+        self.reserve(size * 3.0);
+        self.y -= size * 0.6;
+        self.lines(0.0, text, size, true);
+        self.y -= size * 0.3;
+    }
+
+    /// Body paragraph.
+    pub fn paragraph(&mut self, text: &str) {
+        self.lines(0.0, text, BODY_SIZE, false);
+        self.y -= BODY_SIZE * 0.4;
+    }
+
+    /// Small grey print, for checksums and the like.
+    pub fn small(&mut self, text: &str) {
+        self.current.set_fill_gray(0.35);
+        self.lines(0.0, text, SMALL_SIZE, false);
+        self.current.set_fill_gray(0.0);
+    }
+
+    /// `label: value` line with a bold label.
+    pub fn field(&mut self, label: &str, value: &str) {
+        let label = format!("{label}:");
+        let indent = text_width(&label, BODY_SIZE, true) + 4.0;
+        let leading = BODY_SIZE * 1.4;
+        self.reserve(leading);
+        self.show(MARGIN, self.y - leading, &label, BODY_SIZE, true);
+        let mut first = true;
+        for line in wrap(value, BODY_SIZE, false, CONTENT_WIDTH - indent) {
+            if !first {
+                self.reserve(leading);
+            }
+            first = false;
+            self.y -= leading;
+            self.show(MARGIN + indent, self.y, &line, BODY_SIZE, false);
+        }
+    }
+
+    /// Bulleted list; nothing is written for an empty list.
+    pub fn bullets<S: AsRef<str>>(&mut self, items: &[S]) {
+        let leading = BODY_SIZE * 1.4;
+        for item in items {
+            self.reserve(leading);
+            self.show(MARGIN + 4.0, self.y - leading, "\u{2022}", BODY_SIZE, false);
+            self.lines(14.0, item.as_ref(), BODY_SIZE, false);
+        }
+        if !items.is_empty() {
+            self.y -= BODY_SIZE * 0.4;
+        }
+    }
+
+    /// Horizontal rule across the content width.
+    pub fn rule(&mut self) {
+        self.reserve(12.0);
+        self.y -= 6.0;
+        let y = self.y;
+        self.rule_at(y);
+        self.y -= 6.0;
+    }
+
+    /// Table with wrapped cells; the header repeats after a page break.
+    pub fn table(&mut self, columns: &[Column<'_>], rows: &[Vec<String>]) {
+        let headers: Vec<String> = columns.iter().map(|c| c.header.to_string()).collect();
+        self.table_row(columns, &headers, true);
+        for row in rows {
+            if !self.table_row(columns, row, false) {
+                self.table_row(columns, &headers, true);
+                self.table_row(columns, row, false);
+            }
+        }
+        self.y -= BODY_SIZE * 0.6;
+    }
+
+    /// Write one row; returns `false` if it started a new page first.
+    fn table_row(&mut self, columns: &[Column<'_>], cells: &[String], bold: bool) -> bool {
+        let size = if bold { BODY_SIZE } else { BODY_SIZE - 1.0 };
+        let leading = size * 1.3;
+        let wrapped: Vec<Vec<String>> = columns
+            .iter()
+            .zip(cells)
+            .map(|(column, cell)| {
+                wrap(
+                    cell,
+                    size,
+                    bold,
+                    column.width * CONTENT_WIDTH - 2.0 * CELL_PADDING,
+                )
+            })
+            .collect();
+        let line_count = wrapped.iter().map(Vec::len).max().unwrap_or(1);
+        #[allow(clippy::cast_precision_loss)]
+        let height = line_count as f32 * leading + 2.0 * CELL_PADDING;
+
+        let mut same_page = true;
+        if self.y - height < BOTTOM && self.y < PAGE_HEIGHT - MARGIN {
+            self.new_page();
+            same_page = false;
+        }
+        let mut x = MARGIN;
+        for (column, lines) in columns.iter().zip(&wrapped) {
+            let mut y = self.y - CELL_PADDING;
+            for line in lines {
+                y -= leading;
+                self.show(x + CELL_PADDING, y + size * 0.25, line, size, bold);
+            }
+            x += column.width * CONTENT_WIDTH;
+        }
+        self.y -= height;
+        let y = self.y;
+        self.rule_at(y);
+        same_page
+    }
+
+    /// Lay out the pages and serialize the document.
+    pub fn finish(mut self) -> Vec<u8> {
+        let current = std::mem::replace(&mut self.current, Content::new());
+        self.pages.push(current);
+
+        let catalog_id = Ref::new(1);
+        let page_tree_id = Ref::new(2);
+        let regular_id = Ref::new(3);
+        let bold_id = Ref::new(4);
+        let info_id = Ref::new(5);
+        let page_ids: Vec<(Ref, Ref)> = (0..self.pages.len())
+            .map(|i| {
+                let i = i32::try_from(i).unwrap_or(i32::MAX / 2 - 3);
+                (Ref::new(6 + 2 * i), Ref::new(7 + 2 * i))
+            })
+            .collect();
+
+        let mut pdf = Pdf::new();
+        pdf.catalog(catalog_id).pages(page_tree_id);
+        pdf.pages(page_tree_id)
+            .kids(page_ids.iter().map(|(page, _)| *page))
+            .count(i32::try_from(page_ids.len()).unwrap_or(i32::MAX));
+        pdf.type1_font(regular_id)
+            .base_font(Name(b"Helvetica"))
+            .encoding_predefined(Name(b"WinAnsiEncoding"));
+        pdf.type1_font(bold_id)
+            .base_font(Name(b"Helvetica-Bold"))
+            .encoding_predefined(Name(b"WinAnsiEncoding"));
+        pdf.document_info(info_id)
+            .title(TextStr(&self.title))
+            .producer(TextStr("QA Intelligent PMS"));
+
+        let total = page_ids.len();
+        for (number, (mut content, (page_id, content_id))) in
+            self.pages.into_iter().zip(&page_ids).enumerate()
+        {
+            let footer = format!("Page {} of {total}", number + 1);
+            let x = PAGE_WIDTH - MARGIN - text_width(&footer, SMALL_SIZE, false);
+            content
+                .set_fill_gray(0.35)
+                .begin_text()
+                .set_font(REGULAR, SMALL_SIZE)
+                .next_line(x, MARGIN - 10.0)
+                .show(Str(footer.as_bytes()))
+                .end_text();
+
+            let mut page = pdf.page(*page_id);
+            page.media_box(Rect::new(0.0, 0.0, PAGE_WIDTH, PAGE_HEIGHT))
+                .parent(page_tree_id)
+                .contents(*content_id);
+            page.resources()
+                .fonts()
+                .pair(REGULAR, regular_id)
+                .pair(BOLD, bold_id);
+            page.finish();
+            pdf.stream(*content_id, &content.finish());
+        }
+        pdf.finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wrap() {
+        let lines = wrap("alpha beta gamma delta", BODY_SIZE, false, 60.0);
+        assert!(lines.len() > 1);
+        assert!(lines
+            .iter()
+            .all(|line| text_width(line, BODY_SIZE, false) <= 60.0));
+        assert_eq!(lines.join(" "), "alpha beta gamma delta");
+
+        let lines = wrap(&"x".repeat(80), BODY_SIZE, false, 100.0);
+        assert!(lines.len() > 1);
+        assert_eq!(lines.concat(), "x".repeat(80));
+    }
+
+    #[test]
+    fn test_encode() {
+        assert_eq!(encode("Caf\u{e9} \u{2013} \u{4e2d}"), b"Caf\xe9 \x96 ?");
+    }
+
+    #[test]
+    fn test_document_breaks_pages() {
+        let mut doc = PdfDocument::new("Long");
+        doc.heading(1, "Long report");
+        let items: Vec<String> = (0..200).map(|i| format!("Item {i}")).collect();
+        doc.bullets(&items);
+        let bytes = doc.finish();
+        let text = String::from_utf8_lossy(&bytes);
+        assert!(bytes.starts_with(b"%PDF-"));
+        assert!(text.contains("/Count 4"));
+        assert!(text.contains("(Page 4 of 4)"));
+    }
+}
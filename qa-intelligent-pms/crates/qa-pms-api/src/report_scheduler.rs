@@ -0,0 +1,108 @@
+//! Scheduled report delivery.
+//!
+//! Background task that, on a cron schedule, builds the aggregate report for
+//! the preceding period and mails it to the configured recipients.
+
+use std::str::FromStr;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use cron::Schedule;
+use qa_pms_config::settings::ReportScheduleSettings;
+use sqlx::PgPool;
+use tracing::{info, warn};
+
+use crate::mailer::Mailer;
+use crate::report_aggregate::{AggregateReport, AggregateScope};
+use crate::report_export::{render_aggregate_html, render_aggregate_markdown};
+
+/// Cron-driven report mailer.
+pub struct ReportScheduler {
+    db: PgPool,
+    mailer: Mailer,
+    schedule: Schedule,
+    recipients: Vec<String>,
+    period: Duration,
+}
+
+impl ReportScheduler {
+    /// Create a scheduler from settings.
+    ///
+    /// # Errors
+    /// Returns error if the cron expression is invalid.
+    pub fn new(db: PgPool, mailer: Mailer, settings: &ReportScheduleSettings) -> Result<Self> {
+        let schedule = Schedule::from_str(&settings.cron)
+            .with_context(|| format!("Invalid REPORT_SCHEDULE_CRON: {}", settings.cron))?;
+        Ok(Self {
+            db,
+            mailer,
+            schedule,
+            recipients: settings.recipients.clone(),
+            period: Duration::days(i64::from(settings.period_days)),
+        })
+    }
+
+    /// Generate the report for the period ending at `end` and mail it.
+    ///
+    /// # Errors
+    /// Returns error if the report cannot be loaded or delivered.
+    pub async fn run_once(&self, end: DateTime<Utc>) -> Result<AggregateReport> {
+        let scope = AggregateScope {
+            start: end - self.period,
+            end,
+            sprint: None,
+            ticket_ids: None,
+            tenant_id: None,
+        };
+        let report = AggregateReport::load(&self.db, &scope)
+            .await
+            .context("Failed to load completed workflows")?;
+        self.mailer
+            .send(
+                &self.recipients,
+                &report.title(),
+                render_aggregate_markdown(&report),
+                render_aggregate_html(&report),
+            )
+            .await?;
+        info!(
+            workflows = report.totals.workflows,
+            recipients = self.recipients.len(),
+            "Scheduled report delivered"
+        );
+        Ok(report)
+    }
+
+    /// Run on the schedule in the background.
+    pub fn start(self) {
+        tokio::spawn(async move {
+            info!(
+                recipients = self.recipients.len(),
+                "Report scheduler started"
+            );
+            while let Some(next) = self.schedule.upcoming(Utc).next() {
+                let wait = (next - Utc::now()).to_std().unwrap_or_default();
+                tokio::time::sleep(wait).await;
+                if let Err(e) = self.run_once(next).await {
+                    warn!(error = %e, "Scheduled report failed");
+                }
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_cron_expression_with_seconds() {
+        let schedule = Schedule::from_str("0 0 8 * * Mon").unwrap();
+        let next = schedule
+            .after(&Utc.with_ymd_and_hms(2024, 5, 1, 0, 0, 0).unwrap())
+            .next()
+            .unwrap();
+        assert_eq!(next, Utc.with_ymd_and_hms(2024, 5, 6, 8, 0, 0).unwrap());
+    }
+}
@@ -6,28 +6,42 @@
 //! - API keys encrypted with AES-256-GCM before storage
 //! - Input validation for API keys
 //!
+//! Chat responses are returned whole from `/chat`, or streamed token by token
+//! over the `/chat/ws` WebSocket.
+//!
+//! Token usage is recorded per user and feature and reported by `/usage`;
+//! requests fail once the user's monthly hard limit is reached.
+//!
 //! TODO: Add rate limiting when `tower_governor/axum` version compatibility is resolved
 
 use axum::{
-    extract::State,
+    extract::{
+        ws::{Message, WebSocket, WebSocketUpgrade},
+        Query, State,
+    },
+    response::Response,
     routing::{get, post},
     Json, Router,
 };
+use futures::StreamExt;
 use serde::{Deserialize, Serialize};
-use tracing::{info, warn};
+use tracing::{debug, info, warn};
 use utoipa::ToSchema;
 use uuid::Uuid;
+use validator::Validate;
 
 use qa_pms_ai::{
-    AIClient, ChatContext, ChatInput, ChatMessage, ChatService,
+    AIClient, AIError, AIFeature, ChatContext, ChatInput, ChatMessage, ChatService, ChatStream,
     ConnectionTestResult, GherkinAnalyzer, GherkinInput,
-    ProviderModels, ProviderType, SemanticSearchInput, SemanticSearchService,
+    ModelInfo, ProviderModels, ProviderType, SemanticSearchInput, SemanticSearchService, UsageReport,
 };
 use qa_pms_config::Encryptor;
-use qa_pms_core::ApiError;
+use qa_pms_core::{ApiError, RetryPolicy};
 use secrecy::ExposeSecret;
 
 use crate::app::AppState;
+use crate::auth::CurrentUser;
+use crate::validation::{not_blank, violations, ValidatedJson};
 
 type ApiResult<T> = Result<T, ApiError>;
 
@@ -42,31 +56,54 @@ pub fn router() -> Router<AppState> {
         // Configuration
         .route("/status", get(get_ai_status))
         .route("/providers", get(get_providers))
+        .route("/models", post(list_models))
         .route("/configure", post(configure_ai))
         .route("/test", post(test_connection))
         .route("/disable", post(disable_ai))
         // Chat
         .route("/chat", post(chat))
+        .route("/chat/ws", get(chat_ws))
         .route("/chat/suggestions", post(get_chat_suggestions))
         // Semantic search
         .route("/semantic-search", post(semantic_search))
         // Gherkin analysis
         .route("/gherkin", post(analyze_gherkin))
+        // Token usage
+        .route("/usage", get(get_usage))
 }
 
 // ==================== Request/Response Types ====================
 
 /// Request to configure AI.
-#[derive(Debug, Deserialize, ToSchema)]
+#[derive(Debug, Deserialize, ToSchema, Validate)]
 #[serde(rename_all = "camelCase")]
 pub struct ConfigureAIRequest {
     /// Provider type
     pub provider: String,
-    /// API key
+    /// API key (not needed for Ollama)
+    #[serde(default)]
     pub api_key: String,
-    /// Model ID
+    /// Model ID (the deployment name for Azure `OpenAI`)
+    #[validate(custom(function = "not_blank"))]
     pub model_id: String,
-    /// Custom base URL (for custom provider)
+    /// Custom base URL (custom provider, remote Ollama server, or Azure
+    /// `OpenAI` resource endpoint, optionally with `?api-version=`)
+    #[validate(url)]
+    pub custom_base_url: Option<String>,
+}
+
+/// Request to list the models a provider can serve.
+#[derive(Debug, Deserialize, ToSchema, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct ListModelsRequest {
+    /// Provider type
+    pub provider: String,
+    /// API key (not needed for Ollama)
+    #[serde(default)]
+    pub api_key: String,
+    /// Custom base URL (custom provider, remote Ollama server, or Azure
+    /// `OpenAI` resource endpoint, optionally with `?api-version=`)
+    #[validate(url)]
     pub custom_base_url: Option<String>,
 }
 
@@ -84,6 +121,14 @@ pub struct AIStatusResponse {
     pub message: String,
 }
 
+/// Response for a provider's model list.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelsResponse {
+    /// Models the provider can serve
+    pub models: Vec<ModelInfo>,
+}
+
 /// Response for providers list.
 #[derive(Debug, Serialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
@@ -93,10 +138,11 @@ pub struct ProvidersResponse {
 }
 
 /// Request for chat.
-#[derive(Debug, Deserialize, ToSchema)]
+#[derive(Debug, Deserialize, ToSchema, Validate)]
 #[serde(rename_all = "camelCase")]
 pub struct ChatRequest {
     /// User message
+    #[validate(custom(function = "not_blank"))]
     pub message: String,
     /// Chat history
     #[serde(default)]
@@ -186,6 +232,42 @@ pub struct TokenUsageDto {
     pub total_tokens: u32,
 }
 
+/// Message sent by the server over the chat WebSocket.
+///
+/// Each `ChatRequest` the client sends is answered by zero or more `delta`
+/// messages followed by one `done` or `error` message.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum ChatStreamMessage {
+    /// Text to append to the response being generated
+    Delta {
+        /// New text
+        text: String,
+    },
+    /// The response is complete
+    Done {
+        /// Full response message
+        message: ChatMessageDto,
+        /// Token usage, when the provider reports it
+        usage: Option<TokenUsageDto>,
+    },
+    /// The request failed; the socket stays open for the next one
+    Error {
+        /// What went wrong
+        message: String,
+    },
+}
+
+/// Query for the usage report.
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageQuery {
+    /// First day, inclusive (default: start of the current month)
+    pub from: Option<chrono::NaiveDate>,
+    /// Last day, inclusive (default: today)
+    pub to: Option<chrono::NaiveDate>,
+}
+
 /// Request for chat suggestions.
 #[derive(Debug, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
@@ -341,6 +423,11 @@ pub async fn get_providers() -> ApiResult<Json<ProvidersResponse>> {
 
 /// Validate API key format.
 fn validate_api_key(api_key: &str, provider: ProviderType) -> Result<(), ApiError> {
+    // Local providers need no key
+    if !provider.requires_api_key() && api_key.is_empty() {
+        return Ok(());
+    }
+
     // Check minimum length
     if api_key.len() < MIN_API_KEY_LENGTH {
         return Err(ApiError::Validation(format!(
@@ -374,8 +461,7 @@ fn validate_api_key(api_key: &str, provider: ProviderType) -> Result<(), ApiErro
 
 /// Get encryption key from settings.
 fn get_encryption_key(state: &AppState) -> Result<Encryptor, ApiError> {
-    let key = state.settings.encryption_key.expose_secret();
-    Encryptor::from_hex_key(key).map_err(ApiError::Internal)
+    state.settings.encryptor().map_err(ApiError::Internal)
 }
 
 /// Configure AI provider.
@@ -391,7 +477,7 @@ fn get_encryption_key(state: &AppState) -> Result<Encryptor, ApiError> {
 )]
 pub async fn configure_ai(
     State(state): State<AppState>,
-    Json(req): Json<ConfigureAIRequest>,
+    ValidatedJson(req): ValidatedJson<ConfigureAIRequest>,
 ) -> ApiResult<Json<SuccessResponse>> {
     let provider = parse_provider(&req.provider)?;
 
@@ -399,7 +485,13 @@ pub async fn configure_ai(
     validate_api_key(&req.api_key, provider)?;
 
     // Validate by testing connection
-    let client = create_client(provider, &req.api_key, &req.model_id, req.custom_base_url.clone())?;
+    let client = create_client(
+        provider,
+        &req.api_key,
+        &req.model_id,
+        req.custom_base_url.clone(),
+        RetryPolicy::none(),
+    )?;
     let test_result = client.test_connection().await.map_err(|e| {
         ApiError::Validation(format!("Connection test failed: {e}"))
     })?;
@@ -412,10 +504,14 @@ pub async fn configure_ai(
     }
 
     // CR-HIGH-001: Encrypt API key before storage
-    let encryptor = get_encryption_key(&state)?;
-    let encrypted_key = encryptor.encrypt(&req.api_key).map_err(|e| {
-        ApiError::Internal(anyhow::anyhow!("Failed to encrypt API key: {e}"))
-    })?;
+    let encrypted_key = if req.api_key.is_empty() {
+        None
+    } else {
+        let encryptor = get_encryption_key(&state)?;
+        Some(encryptor.encrypt(&req.api_key).map_err(|e| {
+            ApiError::Internal(anyhow::anyhow!("Failed to encrypt API key: {e}"))
+        })?)
+    };
 
     info!(provider = %req.provider, model = %req.model_id, "Storing encrypted AI configuration");
 
@@ -458,10 +554,16 @@ pub async fn configure_ai(
     tag = "AI"
 )]
 pub async fn test_connection(
-    Json(req): Json<ConfigureAIRequest>,
+    ValidatedJson(req): ValidatedJson<ConfigureAIRequest>,
 ) -> ApiResult<Json<ConnectionTestResult>> {
     let provider = parse_provider(&req.provider)?;
-    let client = create_client(provider, &req.api_key, &req.model_id, req.custom_base_url)?;
+    let client = create_client(
+        provider,
+        &req.api_key,
+        &req.model_id,
+        req.custom_base_url,
+        RetryPolicy::none(),
+    )?;
 
     let result = client.test_connection().await.map_err(|e| {
         ApiError::Validation(format!("Connection test failed: {e}"))
@@ -470,6 +572,34 @@ pub async fn test_connection(
     Ok(Json(result))
 }
 
+/// List the models a provider can serve.
+///
+/// Ollama is asked which models it has pulled; other providers return their
+/// built-in list.
+#[utoipa::path(
+    post,
+    path = "/api/v1/ai/models",
+    request_body = ListModelsRequest,
+    responses(
+        (status = 200, description = "Available models", body = ModelsResponse),
+        (status = 400, description = "Unknown provider"),
+        (status = 502, description = "Provider unreachable")
+    ),
+    tag = "AI"
+)]
+pub async fn list_models(
+    ValidatedJson(req): ValidatedJson<ListModelsRequest>,
+) -> ApiResult<Json<ModelsResponse>> {
+    let provider = parse_provider(&req.provider)?;
+    let client = create_client(provider, &req.api_key, "", req.custom_base_url, RetryPolicy::none())?;
+
+    let models = client.list_models().await.map_err(|e| {
+        ApiError::ExternalService(format!("Could not list models: {e}"))
+    })?;
+
+    Ok(Json(ModelsResponse { models }))
+}
+
 /// Disable AI.
 #[utoipa::path(
     post,
@@ -516,7 +646,8 @@ async fn get_decrypted_api_key(state: &AppState) -> Result<(String, String, Stri
         std::env::var("AI_API_KEY").unwrap_or_default()
     };
 
-    if api_key.is_empty() {
+    let needs_key = parse_provider(&provider_str).map_or(true, ProviderType::requires_api_key);
+    if api_key.is_empty() && needs_key {
         return Err(ApiError::ServiceUnavailable(
             "AI API key not configured".into(),
         ));
@@ -532,25 +663,177 @@ async fn get_decrypted_api_key(state: &AppState) -> Result<(String, String, Stri
     request_body = ChatRequest,
     responses(
         (status = 200, description = "Chat response", body = ChatResponseDto),
+        (status = 403, description = "Monthly token budget exhausted"),
         (status = 503, description = "AI not available")
     ),
     tag = "AI"
 )]
 pub async fn chat(
     State(state): State<AppState>,
-    Json(req): Json<ChatRequest>,
+    user: CurrentUser,
+    ValidatedJson(req): ValidatedJson<ChatRequest>,
 ) -> ApiResult<Json<ChatResponseDto>> {
-    // Get decrypted AI configuration
-    let (provider_str, model_id, api_key, custom_url) = get_decrypted_api_key(&state).await?;
+    let chat_service = ChatService::new(configured_client(&state, &user, AIFeature::Chat).await?);
 
-    let provider = parse_provider(&provider_str)?;
+    let response = chat_service.chat(chat_input(req, false)).await.map_err(|e| match e {
+        AIError::BudgetExceeded { .. } => ApiError::Forbidden(e.to_string()),
+        e => ApiError::Internal(anyhow::anyhow!("Chat failed: {e}")),
+    })?;
+
+    Ok(Json(ChatResponseDto {
+        message: ChatMessageDto {
+            id: response.message.id.to_string(),
+            role: format!("{:?}", response.message.role).to_lowercase(),
+            content: response.message.content,
+            timestamp: response.message.timestamp.to_rfc3339(),
+        },
+        usage: response.usage.map(|u| TokenUsageDto {
+            prompt_tokens: u.prompt_tokens,
+            completion_tokens: u.completion_tokens,
+            total_tokens: u.total_tokens,
+        }),
+    }))
+}
+
+/// Stream chat responses over a WebSocket.
+///
+/// The client sends `ChatRequest` JSON text messages, one at a time; each is
+/// answered with `ChatStreamMessage`s as the provider generates tokens.
+/// Closing the socket mid-response stops generation.
+#[utoipa::path(
+    get,
+    path = "/api/v1/ai/chat/ws",
+    responses(
+        (status = 101, description = "Switching to WebSocket; messages are ChatStreamMessage JSON"),
+    ),
+    tag = "AI"
+)]
+pub async fn chat_ws(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    ws: WebSocketUpgrade,
+) -> Response {
+    ws.on_upgrade(move |socket| handle_chat_socket(socket, state, user))
+}
+
+async fn handle_chat_socket(mut socket: WebSocket, state: AppState, user: CurrentUser) {
+    debug!("Chat stream client connected");
+    while let Some(Ok(message)) = socket.recv().await {
+        let text = match message {
+            Message::Text(text) => text,
+            Message::Close(_) => break,
+            _ => continue,
+        };
+        if stream_reply(&mut socket, &state, &user, &text).await.is_err() {
+            break;
+        }
+    }
+    debug!("Chat stream client disconnected");
+}
+
+/// Answer one chat request. Fails only when the client has gone away.
+async fn stream_reply(
+    socket: &mut WebSocket,
+    state: &AppState,
+    user: &CurrentUser,
+    text: &str,
+) -> Result<(), axum::Error> {
+    let stream = match parse_chat_request(text) {
+        Ok(req) => start_chat_stream(state, user, req).await,
+        Err(e) => Err(e),
+    };
+    let mut stream = match stream {
+        Ok(stream) => stream,
+        Err(message) => return send_chat_message(socket, &ChatStreamMessage::Error { message }).await,
+    };
+
+    let mut content = String::new();
+    let mut usage = None;
+    loop {
+        tokio::select! {
+            chunk = stream.next() => {
+                match chunk {
+                    Some(Ok(chunk)) => {
+                        if chunk.usage.is_some() {
+                            usage = chunk.usage;
+                        }
+                        if !chunk.delta.is_empty() {
+                            content.push_str(&chunk.delta);
+                            send_chat_message(socket, &ChatStreamMessage::Delta { text: chunk.delta }).await?;
+                        }
+                    }
+                    Some(Err(e)) => {
+                        warn!(error = %e, "Chat stream failed");
+                        let message = format!("Chat failed: {e}");
+                        return send_chat_message(socket, &ChatStreamMessage::Error { message }).await;
+                    }
+                    None => break,
+                }
+            }
+            incoming = socket.recv() => {
+                match incoming {
+                    // Dropping the provider stream cancels the upstream request
+                    Some(Ok(Message::Close(_)) | Err(_)) | None => {
+                        return Err(axum::Error::new("chat client disconnected"));
+                    }
+                    // Requests sent mid-response are not queued
+                    Some(Ok(_)) => {}
+                }
+            }
+        }
+    }
+
+    let done = ChatStreamMessage::Done {
+        message: ChatMessageDto {
+            id: Uuid::new_v4().to_string(),
+            role: "assistant".to_string(),
+            content,
+            timestamp: chrono::Utc::now().to_rfc3339(),
+        },
+        usage: usage.map(|u| TokenUsageDto {
+            prompt_tokens: u.prompt_tokens,
+            completion_tokens: u.completion_tokens,
+            total_tokens: u.total_tokens,
+        }),
+    };
+    send_chat_message(socket, &done).await
+}
+
+/// Parse and validate a chat request received over the socket.
+fn parse_chat_request(text: &str) -> Result<ChatRequest, String> {
+    let req: ChatRequest =
+        serde_json::from_str(text).map_err(|e| format!("Invalid chat request: {e}"))?;
+    req.validate().map_err(|e| {
+        let fields: Vec<String> = violations(&e)
+            .into_iter()
+            .map(|v| format!("{}: {}", v.field, v.message))
+            .collect();
+        format!("Invalid chat request: {}", fields.join(", "))
+    })?;
+    Ok(req)
+}
 
-    let custom_base_url = custom_url.filter(|s| !s.is_empty());
+async fn start_chat_stream(
+    state: &AppState,
+    user: &CurrentUser,
+    req: ChatRequest,
+) -> Result<ChatStream, String> {
+    let client = configured_client(state, user, AIFeature::Chat)
+        .await
+        .map_err(|e| e.to_string())?;
+    ChatService::new(client)
+        .chat_stream(chat_input(req, true))
+        .await
+        .map_err(|e| format!("Chat failed: {e}"))
+}
 
-    let client = create_client(provider, &api_key, &model_id, custom_base_url)?;
-    let chat_service = ChatService::new(client);
+async fn send_chat_message(socket: &mut WebSocket, message: &ChatStreamMessage) -> Result<(), axum::Error> {
+    let text = serde_json::to_string(message).unwrap_or_default();
+    socket.send(Message::Text(text)).await
+}
 
-    // Convert DTOs to domain types
+/// Convert a chat request DTO to the service input.
+fn chat_input(req: ChatRequest, stream: bool) -> ChatInput {
     let history: Vec<ChatMessage> = req
         .history
         .into_iter()
@@ -584,30 +867,12 @@ pub async fn chat(
         recent_actions: c.recent_actions,
     });
 
-    let input = ChatInput {
+    ChatInput {
         message: req.message,
         history,
         context,
-        stream: false,
-    };
-
-    let response = chat_service.chat(input).await.map_err(|e| {
-        ApiError::Internal(anyhow::anyhow!("Chat failed: {e}"))
-    })?;
-
-    Ok(Json(ChatResponseDto {
-        message: ChatMessageDto {
-            id: response.message.id.to_string(),
-            role: format!("{:?}", response.message.role).to_lowercase(),
-            content: response.message.content,
-            timestamp: response.message.timestamp.to_rfc3339(),
-        },
-        usage: response.usage.map(|u| TokenUsageDto {
-            prompt_tokens: u.prompt_tokens,
-            completion_tokens: u.completion_tokens,
-            total_tokens: u.total_tokens,
-        }),
-    }))
+        stream,
+    }
 }
 
 /// Get chat suggestions based on context.
@@ -658,6 +923,7 @@ pub async fn get_chat_suggestions(
 )]
 pub async fn semantic_search(
     State(state): State<AppState>,
+    user: CurrentUser,
     Json(req): Json<SemanticSearchRequest>,
 ) -> ApiResult<Json<SemanticSearchResponse>> {
     let input = SemanticSearchInput {
@@ -671,7 +937,9 @@ pub async fn semantic_search(
         if let Ok(provider) = parse_provider(&provider_str) {
             let custom_base_url = custom_url.filter(|s| !s.is_empty());
 
-            if let Ok(client) = create_client(provider, &api_key, &model_id, custom_base_url) {
+            let retry = state.settings.retry.policy();
+            if let Ok(client) = create_client(provider, &api_key, &model_id, custom_base_url, retry) {
+                let client = track_usage(client, &state, &user, AIFeature::SemanticSearch);
                 let service = SemanticSearchService::new(client);
                 if let Ok(result) = service.analyze(input.clone()).await {
                     return Ok(Json(SemanticSearchResponse {
@@ -707,6 +975,7 @@ pub async fn semantic_search(
 )]
 pub async fn analyze_gherkin(
     State(state): State<AppState>,
+    user: CurrentUser,
     Json(req): Json<GherkinRequest>,
 ) -> ApiResult<Json<GherkinResponse>> {
     let input = GherkinInput {
@@ -725,7 +994,9 @@ pub async fn analyze_gherkin(
         if let Ok(provider) = parse_provider(&provider_str) {
             let custom_base_url = custom_url.filter(|s| !s.is_empty());
 
-            if let Ok(client) = create_client(provider, &api_key, &model_id, custom_base_url) {
+            let retry = state.settings.retry.policy();
+            if let Ok(client) = create_client(provider, &api_key, &model_id, custom_base_url, retry) {
+                let client = track_usage(client, &state, &user, AIFeature::Gherkin);
                 let analyzer = GherkinAnalyzer::new(client);
                 if let Ok(result) = analyzer.analyze(input.clone()).await {
                     return Ok(Json(GherkinResponse {
@@ -769,6 +1040,40 @@ pub async fn analyze_gherkin(
     }))
 }
 
+/// Report the caller's token usage.
+///
+/// Breaks usage down by feature, provider and day, and shows month-to-date
+/// usage against the configured budget.
+#[utoipa::path(
+    get,
+    path = "/api/v1/ai/usage",
+    params(UsageQuery),
+    responses(
+        (status = 200, description = "Token usage", body = UsageReport),
+        (status = 400, description = "Invalid date range")
+    ),
+    tag = "AI"
+)]
+pub async fn get_usage(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Query(query): Query<UsageQuery>,
+) -> ApiResult<Json<UsageReport>> {
+    let today = chrono::Utc::now().date_naive();
+    let to = query.to.unwrap_or(today);
+    let from = query.from.unwrap_or_else(|| qa_pms_ai::usage::month_start(to));
+    if from > to {
+        return Err(ApiError::Validation("from must not be after to".into()));
+    }
+
+    let report = state
+        .ai_usage
+        .report(&user.owner(None), from, to)
+        .await
+        .map_err(|e| ApiError::Internal(e.into()))?;
+    Ok(Json(report))
+}
+
 // ==================== Helper Functions ====================
 
 fn parse_provider(s: &str) -> Result<ProviderType, ApiError> {
@@ -778,17 +1083,98 @@ fn parse_provider(s: &str) -> Result<ProviderType, ApiError> {
         "deepseek" => Ok(ProviderType::Deepseek),
         "zai" | "z.ai" => Ok(ProviderType::Zai),
         "custom" => Ok(ProviderType::Custom),
+        "ollama" => Ok(ProviderType::Ollama),
+        "azure" | "azure_openai" => Ok(ProviderType::AzureOpenAi),
         _ => Err(ApiError::Validation(format!("Unknown provider: {s}"))),
     }
 }
 
+/// AI client from the stored configuration, recording usage for `user`.
+/// `AI_EMBEDDING_MODEL` overrides the provider's embedding model.
+pub(crate) async fn configured_client(
+    state: &AppState,
+    user: &CurrentUser,
+    feature: AIFeature,
+) -> ApiResult<AIClient> {
+    let (provider_str, model_id, api_key, custom_url) = get_decrypted_api_key(state).await?;
+    let provider = parse_provider(&provider_str)?;
+    let client = create_client(
+        provider,
+        &api_key,
+        &model_id,
+        custom_url.filter(|s| !s.is_empty()),
+        state.settings.retry.policy(),
+    )?;
+    let client = track_usage(client, state, user, feature);
+    Ok(match std::env::var("AI_EMBEDDING_MODEL") {
+        Ok(model) if !model.is_empty() => client.with_embedding_model(model),
+        _ => client,
+    })
+}
+
+fn track_usage(client: AIClient, state: &AppState, user: &CurrentUser, feature: AIFeature) -> AIClient {
+    client.with_usage(state.ai_usage.clone(), user.owner(None), feature)
+}
+
+/// Build a client for the given provider.
+///
+/// Connection tests pass [`RetryPolicy::none`] so a bad key or URL is
+/// reported at once.
 fn create_client(
     provider: ProviderType,
     api_key: &str,
     model: &str,
     custom_base_url: Option<String>,
+    retry: RetryPolicy,
 ) -> Result<AIClient, ApiError> {
     let secret_key = secrecy::SecretString::new(api_key.to_string());
-    AIClient::from_config(provider, secret_key, model.to_string(), custom_base_url)
+    AIClient::from_config(provider, secret_key, model.to_string(), custom_base_url, retry)
         .map_err(|e| ApiError::Validation(format!("Failed to create AI client: {e}")))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_chat_request_reports_invalid_fields() {
+        let req = parse_chat_request(r#"{"message":"What next?","history":[]}"#).unwrap();
+        assert_eq!(req.message, "What next?");
+
+        let blank = parse_chat_request(r#"{"message":"  "}"#).unwrap_err();
+        assert!(blank.contains("message"), "{blank}");
+        assert!(parse_chat_request("not json").is_err());
+    }
+
+    #[test]
+    fn test_stream_messages_are_tagged() {
+        let delta = serde_json::to_value(ChatStreamMessage::Delta {
+            text: "Hi".to_string(),
+        })
+        .unwrap();
+        assert_eq!(delta, serde_json::json!({"type": "delta", "text": "Hi"}));
+    }
+
+    #[test]
+    fn test_ollama_needs_no_api_key() {
+        assert_eq!(parse_provider("Ollama").unwrap(), ProviderType::Ollama);
+        assert!(validate_api_key("", ProviderType::Ollama).is_ok());
+        assert!(validate_api_key("", ProviderType::OpenAi).is_err());
+    }
+
+    #[test]
+    fn test_azure_requires_endpoint() {
+        assert_eq!(parse_provider("azure_openai").unwrap(), ProviderType::AzureOpenAi);
+        let key = "0123456789abcdef0123456789abcdef";
+        assert!(create_client(ProviderType::AzureOpenAi, key, "gpt-4o", None, RetryPolicy::none()).is_err());
+        let client = create_client(
+            ProviderType::AzureOpenAi,
+            key,
+            "gpt-4o",
+            Some("https://acme.openai.azure.com".into()),
+            RetryPolicy::none(),
+        )
+        .unwrap();
+        assert_eq!(client.provider_type(), ProviderType::AzureOpenAi);
+    }
+}
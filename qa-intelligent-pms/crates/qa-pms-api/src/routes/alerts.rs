@@ -3,16 +3,30 @@
 //! Provides endpoints for managing alerts from pattern detection.
 
 use axum::{
-    extract::{Path, State},
-    routing::{get, post},
-    Json, Router,
+    extract::{Path, Query, State},
+    http::StatusCode,
+    routing::{delete, get, post},
+    Extension, Json, Router,
 };
-use serde::Serialize;
-use utoipa::ToSchema;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
+use tracing::info;
+use validator::{Validate, ValidationError};
 
 use crate::app::AppState;
+use crate::audit::AuditChange;
+use crate::auth::CurrentUser;
+use crate::routes::projects::ProjectQuery;
+use crate::validation::{not_blank, one_of, ValidatedJson};
 use qa_pms_core::error::ApiError;
+use qa_pms_core::types::{CursorInfo, CursorQuery};
+use qa_pms_patterns::thresholds::DEFAULT_TEAM;
+use qa_pms_patterns::{
+    Alert, AlertFilter, AlertService, AlertSort, MuteRule, NewMuteRule, PatternRepository, PatternThresholds,
+    PatternType, Severity, TeamThresholds, ThresholdRepository,
+};
 
 type ApiResult<T> = Result<T, ApiError>;
 
@@ -21,9 +35,17 @@ pub fn router() -> Router<AppState> {
     Router::new()
         .route("/api/v1/alerts", get(get_alerts))
         .route("/api/v1/alerts/count", get(get_unread_count))
+        .route("/api/v1/alerts/rules", get(list_mute_rules).post(create_mute_rule))
+        .route("/api/v1/alerts/rules/:id", delete(delete_mute_rule))
         .route("/api/v1/alerts/:id/read", post(mark_read))
         .route("/api/v1/alerts/:id/dismiss", post(dismiss_alert))
+        .route("/api/v1/alerts/:id/snooze", post(snooze_alert))
         .route("/api/v1/patterns", get(get_patterns))
+        .route(
+            "/api/v1/patterns/thresholds",
+            get(list_pattern_thresholds).put(put_pattern_thresholds),
+        )
+        .route("/api/v1/patterns/thresholds/:team", delete(delete_pattern_thresholds))
         .route("/api/v1/patterns/:id", get(get_pattern))
 }
 
@@ -44,12 +66,31 @@ pub struct AlertResponse {
     pub created_at: String,
 }
 
+/// Query parameters for listing alerts.
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub struct AlertQuery {
+    /// Only alerts of this severity (info, warning, critical)
+    pub severity: Option<String>,
+    /// Only alerts of this type, e.g. time_excess or kpi_breach
+    #[serde(rename = "type")]
+    pub alert_type: Option<String>,
+    /// Only alerts created at or after this time
+    pub from_date: Option<DateTime<Utc>>,
+    /// Only alerts created at or before this time
+    pub to_date: Option<DateTime<Utc>>,
+    /// Sort field: createdAt (default) or severity, then creation time
+    pub sort: Option<String>,
+}
+
 /// Alerts list response.
 #[derive(Debug, Serialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct AlertsResponse {
     pub alerts: Vec<AlertResponse>,
+    /// Total number of undismissed alerts matching the filters
     pub total: i64,
+    pub page_info: CursorInfo,
 }
 
 /// Unread count response.
@@ -59,6 +100,142 @@ pub struct UnreadCountResponse {
     pub count: i64,
 }
 
+/// Request to mute or snooze an alert type.
+#[derive(Debug, Deserialize, ToSchema, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct MuteRuleRequest {
+    /// Alert type to mute, e.g. time_excess or kpi_breach
+    #[validate(custom(function = "valid_alert_type"))]
+    pub alert_type: String,
+    /// Only mute alerts about this component (a pattern's common factor or a
+    /// watched ticket key); all alerts of the type when absent
+    pub component: Option<String>,
+    /// Snooze until this time; mute until the rule is deleted when absent
+    #[validate(custom(function = "in_future"))]
+    pub until: Option<DateTime<Utc>>,
+    /// Why the alerts are muted
+    pub reason: Option<String>,
+}
+
+/// Request to snooze an alert.
+#[derive(Debug, Deserialize, ToSchema, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct SnoozeRequest {
+    /// Raise no alert of the same type and component until this time
+    #[validate(custom(function = "in_future"))]
+    pub until: DateTime<Utc>,
+    /// Why the alert is snoozed
+    pub reason: Option<String>,
+}
+
+/// Mute rule response.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct MuteRuleResponse {
+    pub id: String,
+    pub alert_type: String,
+    pub component: Option<String>,
+    /// End of a snooze; absent for permanent mutes
+    pub until: Option<String>,
+    pub reason: Option<String>,
+    pub created_by: Option<String>,
+    pub created_at: String,
+}
+
+/// Mute rules list response.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct MuteRulesResponse {
+    pub rules: Vec<MuteRuleResponse>,
+}
+
+/// Request to set a team's pattern detection thresholds.
+///
+/// Omitted thresholds take their built-in default.
+#[derive(Debug, Deserialize, ToSchema, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct PatternThresholdsRequest {
+    /// Team: a Jira project key, or "default" for projects without their own
+    #[validate(custom(function = "not_blank"), length(max = 100))]
+    pub team: Option<String>,
+    /// Percent over its estimate a workflow must run to be a time excess
+    #[validate(range(min = 1.0, max = 1000.0))]
+    pub time_excess_percent: Option<f64>,
+    /// Recent tickets that must share a keyword to be a consecutive problem,
+    /// and tickets of a component reporting unclear requirements to be a gap
+    #[validate(range(min = 2, max = 50))]
+    pub consecutive_count: Option<i32>,
+    /// Most recent tickets compared for consecutive problems
+    #[validate(range(min = 2, max = 100))]
+    pub consecutive_window: Option<i32>,
+    /// Earlier periods averaged for the spike baseline
+    #[validate(range(min = 1, max = 90))]
+    pub spike_window: Option<i32>,
+    /// Times its average the volume must exceed to be a spike
+    #[validate(range(min = 1.1, max = 20.0))]
+    pub spike_multiplier: Option<f64>,
+    /// Trips from QA back to development that make a ticket's fix flaky
+    #[validate(range(min = 1, max = 20))]
+    pub flaky_round_trips: Option<i32>,
+    /// Days of status history counted for flaky verification
+    #[validate(range(min = 1, max = 90))]
+    pub flaky_window_days: Option<i32>,
+}
+
+impl PatternThresholdsRequest {
+    fn into_thresholds(self) -> ApiResult<(String, PatternThresholds)> {
+        let defaults = PatternThresholds::default();
+        let thresholds = PatternThresholds {
+            time_excess_percent: self.time_excess_percent.unwrap_or(defaults.time_excess_percent),
+            consecutive_count: self.consecutive_count.unwrap_or(defaults.consecutive_count),
+            consecutive_window: self.consecutive_window.unwrap_or(defaults.consecutive_window),
+            spike_window: self.spike_window.unwrap_or(defaults.spike_window),
+            spike_multiplier: self.spike_multiplier.unwrap_or(defaults.spike_multiplier),
+            flaky_round_trips: self.flaky_round_trips.unwrap_or(defaults.flaky_round_trips),
+            flaky_window_days: self.flaky_window_days.unwrap_or(defaults.flaky_window_days),
+        };
+        if thresholds.consecutive_window < thresholds.consecutive_count {
+            return Err(ApiError::Validation(
+                "consecutiveWindow must be at least consecutiveCount".to_string(),
+            ));
+        }
+        let team = self.team.map_or_else(|| DEFAULT_TEAM.to_string(), |t| t.trim().to_string());
+        Ok((team, thresholds))
+    }
+}
+
+/// Pattern detection thresholds.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PatternThresholdsResponse {
+    pub time_excess_percent: f64,
+    pub consecutive_count: i32,
+    pub consecutive_window: i32,
+    pub spike_window: i32,
+    pub spike_multiplier: f64,
+    pub flaky_round_trips: i32,
+    pub flaky_window_days: i32,
+}
+
+/// A team's pattern detection thresholds.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TeamThresholdsResponse {
+    pub team: String,
+    #[serde(flatten)]
+    pub thresholds: PatternThresholdsResponse,
+    pub updated_at: String,
+}
+
+/// Stored and built-in pattern detection thresholds.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PatternThresholdsListResponse {
+    /// Used for teams without thresholds when no "default" team is stored
+    pub defaults: PatternThresholdsResponse,
+    pub teams: Vec<TeamThresholdsResponse>,
+}
+
 /// Pattern response.
 #[derive(Debug, Serialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
@@ -81,49 +258,65 @@ pub struct PatternResponse {
 #[serde(rename_all = "camelCase")]
 pub struct PatternsResponse {
     pub patterns: Vec<PatternResponse>,
+    pub page_info: CursorInfo,
 }
 
-/// Get all unread alerts.
+/// Get the tenant's undismissed alerts, newest first.
+///
+/// With a `projectId`, only alerts affecting one of the project's tickets
+/// are listed. `sort=severity` lists the most severe alerts first, or the
+/// least severe with `direction=asc`.
 #[utoipa::path(
     get,
     path = "/api/v1/alerts",
+    params(AlertQuery, ProjectQuery, CursorQuery),
     responses(
         (status = 200, description = "List of alerts", body = AlertsResponse),
+        (status = 400, description = "Invalid cursor or filter"),
+        (status = 404, description = "Project not found"),
     ),
     tag = "Alerts"
 )]
 pub async fn get_alerts(
     State(state): State<AppState>,
+    user: CurrentUser,
+    Query(query): Query<AlertQuery>,
+    Query(project): Query<ProjectQuery>,
+    Query(cursor): Query<CursorQuery>,
 ) -> ApiResult<Json<AlertsResponse>> {
-    // Query alerts from database
-    let rows: Vec<AlertRow> = sqlx::query_as(
-        r"
-        SELECT 
-            id, pattern_id, alert_type, severity, title, message,
-            affected_tickets, suggested_actions, is_read, is_dismissed, created_at
-        FROM alerts
-        WHERE NOT is_dismissed
-        ORDER BY 
-            CASE severity 
-                WHEN 'critical' THEN 1 
-                WHEN 'warning' THEN 2 
-                ELSE 3 
-            END,
-            created_at DESC
-        LIMIT 50
-        ",
-    )
-    .fetch_all(&state.db)
-    .await
-    .map_err(|e| ApiError::Internal(anyhow::anyhow!("Failed to fetch alerts: {e}")))?;
+    let page = cursor.page_request()?;
+    let sort = query.sort.as_deref().map(parse_sort).transpose()?.unwrap_or_default();
+    let filter = AlertFilter {
+        tenant_id: Some(user.tenant.to_string()),
+        project_key: project.resolve(&state).await?.map(|p| p.key),
+        severity: query.severity.as_deref().map(parse_severity).transpose()?,
+        alert_type: query.alert_type.as_deref().map(parse_alert_type).transpose()?,
+        from_date: query.from_date,
+        to_date: query.to_date,
+    };
 
-    let total = rows.len() as i64;
-    let alerts = rows.into_iter().map(Into::into).collect();
+    let repo = PatternRepository::new(state.db.clone());
+    let alerts = repo
+        .list_alerts(&filter, sort, &page)
+        .await
+        .map_err(|e| ApiError::Internal(anyhow::anyhow!("Failed to fetch alerts: {e}")))?;
+    let total = repo
+        .count_alerts(&filter)
+        .await
+        .map_err(|e| ApiError::Internal(anyhow::anyhow!("Failed to count alerts: {e}")))?;
+
+    let result = page
+        .finish(alerts, |alert| (alert.created_at, alert.id))
+        .map(AlertResponse::from);
 
-    Ok(Json(AlertsResponse { alerts, total }))
+    Ok(Json(AlertsResponse {
+        alerts: result.data,
+        total,
+        page_info: result.page_info,
+    }))
 }
 
-/// Get unread alert count.
+/// Get the tenant's unread alert count.
 #[utoipa::path(
     get,
     path = "/api/v1/alerts/count",
@@ -134,10 +327,12 @@ pub async fn get_alerts(
 )]
 pub async fn get_unread_count(
     State(state): State<AppState>,
+    user: CurrentUser,
 ) -> ApiResult<Json<UnreadCountResponse>> {
     let (count,): (i64,) = sqlx::query_as(
-        "SELECT COUNT(*) FROM alerts WHERE NOT is_read AND NOT is_dismissed",
+        "SELECT COUNT(*) FROM alerts WHERE NOT is_read AND NOT is_dismissed AND tenant_id = $1",
     )
+    .bind(user.tenant.as_str())
     .fetch_one(&state.db)
     .await
     .map_err(|e| ApiError::Internal(anyhow::anyhow!("Failed to count alerts: {e}")))?;
@@ -160,10 +355,12 @@ pub async fn get_unread_count(
 )]
 pub async fn mark_read(
     State(state): State<AppState>,
+    user: CurrentUser,
     Path(id): Path<Uuid>,
-) -> ApiResult<Json<serde_json::Value>> {
-    let result = sqlx::query("UPDATE alerts SET is_read = TRUE WHERE id = $1")
+) -> ApiResult<(Extension<AuditChange>, Json<serde_json::Value>)> {
+    let result = sqlx::query("UPDATE alerts SET is_read = TRUE WHERE id = $1 AND tenant_id = $2")
         .bind(id)
+        .bind(user.tenant.as_str())
         .execute(&state.db)
         .await
         .map_err(|e| ApiError::Internal(anyhow::anyhow!("Failed to mark alert read: {e}")))?;
@@ -172,7 +369,8 @@ pub async fn mark_read(
         return Err(ApiError::NotFound(format!("Alert {id} not found")));
     }
 
-    Ok(Json(serde_json::json!({ "success": true })))
+    let change = AuditChange::new(id).after(&serde_json::json!({ "isRead": true }));
+    Ok((change.into_extension(), Json(serde_json::json!({ "success": true }))))
 }
 
 /// Dismiss an alert.
@@ -190,12 +388,15 @@ pub async fn mark_read(
 )]
 pub async fn dismiss_alert(
     State(state): State<AppState>,
+    user: CurrentUser,
     Path(id): Path<Uuid>,
-) -> ApiResult<Json<serde_json::Value>> {
+) -> ApiResult<(Extension<AuditChange>, Json<serde_json::Value>)> {
     let result = sqlx::query(
-        "UPDATE alerts SET is_dismissed = TRUE, dismissed_at = NOW() WHERE id = $1",
+        "UPDATE alerts SET is_dismissed = TRUE, dismissed_at = NOW() \
+         WHERE id = $1 AND tenant_id = $2",
     )
     .bind(id)
+    .bind(user.tenant.as_str())
     .execute(&state.db)
     .await
     .map_err(|e| ApiError::Internal(anyhow::anyhow!("Failed to dismiss alert: {e}")))?;
@@ -204,38 +405,328 @@ pub async fn dismiss_alert(
         return Err(ApiError::NotFound(format!("Alert {id} not found")));
     }
 
-    Ok(Json(serde_json::json!({ "success": true })))
+    let change = AuditChange::new(id).after(&serde_json::json!({ "isDismissed": true }));
+    Ok((change.into_extension(), Json(serde_json::json!({ "success": true }))))
+}
+
+/// Snooze an alert: dismiss it and raise no alert of the same type and
+/// component until the given time.
+#[utoipa::path(
+    post,
+    path = "/api/v1/alerts/{id}/snooze",
+    params(
+        ("id" = String, Path, description = "Alert ID")
+    ),
+    request_body = SnoozeRequest,
+    responses(
+        (status = 201, description = "Alert snoozed", body = MuteRuleResponse),
+        (status = 404, description = "Alert not found"),
+        (status = 422, description = "Snooze time is not in the future"),
+    ),
+    tag = "Alerts"
+)]
+pub async fn snooze_alert(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Path(id): Path<Uuid>,
+    ValidatedJson(request): ValidatedJson<SnoozeRequest>,
+) -> ApiResult<(StatusCode, Extension<AuditChange>, Json<MuteRuleResponse>)> {
+    let row: Option<(String, Vec<String>, Option<String>)> = sqlx::query_as(
+        r"
+        SELECT a.alert_type, a.affected_tickets, p.common_factor
+        FROM alerts a
+        LEFT JOIN detected_patterns p ON p.id = a.pattern_id
+        WHERE a.id = $1 AND a.tenant_id = $2
+        ",
+    )
+    .bind(id)
+    .bind(user.tenant.as_str())
+    .fetch_optional(&state.db)
+    .await
+    .map_err(|e| ApiError::Internal(anyhow::anyhow!("Failed to fetch alert: {e}")))?;
+    let Some((alert_type, affected_tickets, common_factor)) = row else {
+        return Err(ApiError::NotFound(format!("Alert {id} not found")));
+    };
+    let alert_type = parse_alert_type(&alert_type)?;
+    // Ticket alerts are about the ticket; pattern alerts about their common factor
+    let component = match alert_type {
+        PatternType::TicketChange => affected_tickets.into_iter().next(),
+        _ => common_factor,
+    };
+
+    let rule = AlertService::new(PatternRepository::new(state.db.clone()))
+        .mute(NewMuteRule {
+            tenant_id: user.tenant.to_string(),
+            alert_type,
+            component,
+            until: Some(request.until),
+            reason: request.reason,
+            created_by: Some(user.owner(None)),
+        })
+        .await
+        .map_err(|e| ApiError::Internal(anyhow::anyhow!("Failed to snooze alert: {e}")))?;
+
+    sqlx::query(
+        "UPDATE alerts SET is_dismissed = TRUE, dismissed_at = NOW() \
+         WHERE id = $1 AND tenant_id = $2",
+    )
+    .bind(id)
+    .bind(user.tenant.as_str())
+    .execute(&state.db)
+    .await
+    .map_err(|e| ApiError::Internal(anyhow::anyhow!("Failed to dismiss alert: {e}")))?;
+
+    let rule = MuteRuleResponse::from(rule);
+    let change = AuditChange::new(id).after(&rule);
+    Ok((StatusCode::CREATED, change.into_extension(), Json(rule)))
+}
+
+/// List the tenant's mute rules and snoozes that have not expired.
+#[utoipa::path(
+    get,
+    path = "/api/v1/alerts/rules",
+    responses(
+        (status = 200, description = "Mute rules", body = MuteRulesResponse),
+    ),
+    tag = "Alerts"
+)]
+pub async fn list_mute_rules(
+    State(state): State<AppState>,
+    user: CurrentUser,
+) -> ApiResult<Json<MuteRulesResponse>> {
+    let rules = AlertService::new(PatternRepository::new(state.db.clone()))
+        .mute_rules(user.tenant.as_str())
+        .await
+        .map_err(|e| ApiError::Internal(anyhow::anyhow!("Failed to fetch mute rules: {e}")))?;
+
+    Ok(Json(MuteRulesResponse {
+        rules: rules.into_iter().map(Into::into).collect(),
+    }))
+}
+
+/// Mute an alert type, optionally for one component and until a time.
+///
+/// New alerts covered by the rule are not raised; existing alerts are kept.
+#[utoipa::path(
+    post,
+    path = "/api/v1/alerts/rules",
+    request_body = MuteRuleRequest,
+    responses(
+        (status = 201, description = "Mute rule created", body = MuteRuleResponse),
+        (status = 422, description = "Invalid alert type or snooze time"),
+    ),
+    tag = "Alerts"
+)]
+pub async fn create_mute_rule(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    ValidatedJson(request): ValidatedJson<MuteRuleRequest>,
+) -> ApiResult<(StatusCode, Extension<AuditChange>, Json<MuteRuleResponse>)> {
+    let rule = AlertService::new(PatternRepository::new(state.db.clone()))
+        .mute(NewMuteRule {
+            tenant_id: user.tenant.to_string(),
+            alert_type: parse_alert_type(&request.alert_type)?,
+            component: request
+                .component
+                .map(|c| c.trim().to_string())
+                .filter(|c| !c.is_empty()),
+            until: request.until,
+            reason: request.reason,
+            created_by: Some(user.owner(None)),
+        })
+        .await
+        .map_err(|e| ApiError::Internal(anyhow::anyhow!("Failed to create mute rule: {e}")))?;
+
+    let rule = MuteRuleResponse::from(rule);
+    let change = AuditChange::new(&rule.id).after(&rule);
+    Ok((StatusCode::CREATED, change.into_extension(), Json(rule)))
+}
+
+/// Delete a mute rule, so its alerts are raised again.
+#[utoipa::path(
+    delete,
+    path = "/api/v1/alerts/rules/{id}",
+    params(
+        ("id" = String, Path, description = "Mute rule ID")
+    ),
+    responses(
+        (status = 204, description = "Mute rule deleted"),
+        (status = 404, description = "Mute rule not found"),
+    ),
+    tag = "Alerts"
+)]
+pub async fn delete_mute_rule(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Path(id): Path<Uuid>,
+) -> ApiResult<(StatusCode, Extension<AuditChange>)> {
+    let service = AlertService::new(PatternRepository::new(state.db.clone()));
+    let before = service
+        .mute_rules(user.tenant.as_str())
+        .await
+        .map_err(|e| ApiError::Internal(anyhow::anyhow!("Failed to fetch mute rules: {e}")))?
+        .into_iter()
+        .find(|rule| rule.id == id)
+        .map(MuteRuleResponse::from);
+    let deleted = service
+        .unmute(user.tenant.as_str(), id)
+        .await
+        .map_err(|e| ApiError::Internal(anyhow::anyhow!("Failed to delete mute rule: {e}")))?;
+
+    if !deleted {
+        return Err(ApiError::NotFound(format!("Mute rule {id} not found")));
+    }
+    let change = AuditChange::new(id).before(&before);
+    Ok((StatusCode::NO_CONTENT, change.into_extension()))
 }
 
 /// Get recent patterns.
 #[utoipa::path(
     get,
     path = "/api/v1/patterns",
+    params(CursorQuery),
     responses(
         (status = 200, description = "List of patterns", body = PatternsResponse),
+        (status = 400, description = "Invalid cursor"),
     ),
     tag = "Alerts"
 )]
 pub async fn get_patterns(
     State(state): State<AppState>,
+    Query(query): Query<CursorQuery>,
 ) -> ApiResult<Json<PatternsResponse>> {
-    let rows: Vec<PatternRow> = sqlx::query_as(
+    let page = query.page_request()?;
+    let keyset = page
+        .keyset_sql("detected_at", "id", 1)
+        .map(|clause| format!("WHERE {clause}"))
+        .unwrap_or_default();
+
+    let sql = format!(
         r"
         SELECT 
             id, pattern_type, severity, title, description,
             affected_tickets, common_factor, average_excess_percent,
             confidence_score, suggested_actions, detected_at
         FROM detected_patterns
-        ORDER BY detected_at DESC
-        LIMIT 50
+        {keyset}
+        ORDER BY {order}
+        LIMIT {limit}
         ",
-    )
-    .fetch_all(&state.db)
-    .await
-    .map_err(|e| ApiError::Internal(anyhow::anyhow!("Failed to fetch patterns: {e}")))?;
+        order = page.order_sql("detected_at", "id"),
+        limit = page.fetch_limit(),
+    );
+    let mut rows_query = sqlx::query_as::<_, PatternRow>(&sql);
+    if let Some((at, id)) = page.keyset() {
+        rows_query = rows_query.bind(at).bind(id);
+    }
+    let rows = rows_query
+        .fetch_all(&state.db)
+        .await
+        .map_err(|e| ApiError::Internal(anyhow::anyhow!("Failed to fetch patterns: {e}")))?;
+
+    let result = page.finish(rows, |row| (row.detected_at, row.id)).map(PatternResponse::from);
+    Ok(Json(PatternsResponse {
+        patterns: result.data,
+        page_info: result.page_info,
+    }))
+}
+
+/// List the teams' pattern detection thresholds and the built-in defaults.
+#[utoipa::path(
+    get,
+    path = "/api/v1/patterns/thresholds",
+    responses(
+        (status = 200, description = "Pattern thresholds", body = PatternThresholdsListResponse),
+    ),
+    tag = "Alerts"
+)]
+pub async fn list_pattern_thresholds(
+    State(state): State<AppState>,
+) -> ApiResult<Json<PatternThresholdsListResponse>> {
+    let teams = ThresholdRepository::new(state.db.clone())
+        .list()
+        .await
+        .map_err(|e| ApiError::Internal(anyhow::anyhow!("Failed to fetch pattern thresholds: {e}")))?;
+
+    Ok(Json(PatternThresholdsListResponse {
+        defaults: PatternThresholds::default().into(),
+        teams: teams.into_iter().map(Into::into).collect(),
+    }))
+}
+
+/// Set a team's pattern detection thresholds, replacing any existing ones.
+///
+/// A workflow is checked with the thresholds of its ticket's Jira project
+/// key, else of the "default" team, else the built-in defaults.
+#[utoipa::path(
+    put,
+    path = "/api/v1/patterns/thresholds",
+    request_body = PatternThresholdsRequest,
+    responses(
+        (status = 200, description = "Thresholds saved", body = TeamThresholdsResponse),
+        (status = 400, description = "Window smaller than the consecutive count"),
+        (status = 422, description = "Threshold out of range"),
+    ),
+    tag = "Alerts"
+)]
+pub async fn put_pattern_thresholds(
+    State(state): State<AppState>,
+    ValidatedJson(request): ValidatedJson<PatternThresholdsRequest>,
+) -> ApiResult<(Extension<AuditChange>, Json<TeamThresholdsResponse>)> {
+    let (team, thresholds) = request.into_thresholds()?;
+    let before = team_thresholds(&state, &team).await?;
+    let saved = ThresholdRepository::new(state.db.clone())
+        .upsert(&team, &thresholds)
+        .await
+        .map_err(|e| ApiError::Internal(anyhow::anyhow!("Failed to save pattern thresholds: {e}")))?;
+
+    info!(team = %saved.team, "Saved pattern thresholds");
+    let saved = TeamThresholdsResponse::from(saved);
+    let change = AuditChange::new(&team).before(&before).after(&saved);
+    Ok((change.into_extension(), Json(saved)))
+}
 
-    let patterns = rows.into_iter().map(Into::into).collect();
-    Ok(Json(PatternsResponse { patterns }))
+/// Delete a team's pattern detection thresholds, so the defaults apply.
+#[utoipa::path(
+    delete,
+    path = "/api/v1/patterns/thresholds/{team}",
+    params(("team" = String, Path, description = "Team")),
+    responses(
+        (status = 204, description = "Thresholds deleted"),
+        (status = 404, description = "Team has no thresholds"),
+    ),
+    tag = "Alerts"
+)]
+pub async fn delete_pattern_thresholds(
+    State(state): State<AppState>,
+    Path(team): Path<String>,
+) -> ApiResult<(StatusCode, Extension<AuditChange>)> {
+    let before = team_thresholds(&state, &team).await?;
+    let deleted = ThresholdRepository::new(state.db.clone())
+        .delete(&team)
+        .await
+        .map_err(|e| ApiError::Internal(anyhow::anyhow!("Failed to delete pattern thresholds: {e}")))?;
+
+    if !deleted {
+        return Err(ApiError::NotFound(format!("No pattern thresholds for team {team}")));
+    }
+    let change = AuditChange::new(&team).before(&before);
+    Ok((StatusCode::NO_CONTENT, change.into_extension()))
+}
+
+/// A team's stored thresholds, if it has any.
+async fn team_thresholds(
+    state: &AppState,
+    team: &str,
+) -> ApiResult<Option<TeamThresholdsResponse>> {
+    let teams = ThresholdRepository::new(state.db.clone())
+        .list()
+        .await
+        .map_err(|e| ApiError::Internal(anyhow::anyhow!("Failed to fetch pattern thresholds: {e}")))?;
+    Ok(teams
+        .into_iter()
+        .find(|t| t.team == team)
+        .map(TeamThresholdsResponse::from))
 }
 
 /// Get pattern by ID.
@@ -276,40 +767,116 @@ pub async fn get_pattern(
     }
 }
 
-// Internal row types
-#[derive(sqlx::FromRow)]
-struct AlertRow {
-    id: Uuid,
-    pattern_id: Option<Uuid>,
-    alert_type: String,
-    severity: String,
-    title: String,
-    message: Option<String>,
-    affected_tickets: Vec<String>,
-    suggested_actions: Vec<String>,
-    is_read: bool,
-    is_dismissed: bool,
-    created_at: chrono::DateTime<chrono::Utc>,
+fn parse_severity(s: &str) -> ApiResult<Severity> {
+    match s.to_lowercase().as_str() {
+        "info" => Ok(Severity::Info),
+        "warning" => Ok(Severity::Warning),
+        "critical" => Ok(Severity::Critical),
+        _ => Err(ApiError::Validation(format!("Invalid severity: {s}"))),
+    }
+}
+
+fn parse_alert_type(s: &str) -> ApiResult<PatternType> {
+    match s.to_lowercase().as_str() {
+        "time_excess" => Ok(PatternType::TimeExcess),
+        "consecutive_problem" => Ok(PatternType::ConsecutiveProblem),
+        "spike" => Ok(PatternType::Spike),
+        "ticket_change" => Ok(PatternType::TicketChange),
+        "kpi_breach" => Ok(PatternType::KpiBreach),
+        "flaky_verification" => Ok(PatternType::FlakyVerification),
+        "requirement_gap" => Ok(PatternType::RequirementGap),
+        _ => Err(ApiError::Validation(format!("Invalid alert type: {s}"))),
+    }
+}
+
+fn valid_alert_type(s: &str) -> Result<(), ValidationError> {
+    one_of(
+        s,
+        &[
+            "time_excess",
+            "consecutive_problem",
+            "spike",
+            "ticket_change",
+            "kpi_breach",
+            "flaky_verification",
+            "requirement_gap",
+        ],
+    )
+}
+
+fn in_future(until: &DateTime<Utc>) -> Result<(), ValidationError> {
+    if *until <= Utc::now() {
+        return Err(ValidationError::new("in_future")
+            .with_message(std::borrow::Cow::Borrowed("must be in the future")));
+    }
+    Ok(())
+}
+
+fn parse_sort(s: &str) -> ApiResult<AlertSort> {
+    match s {
+        "createdAt" => Ok(AlertSort::CreatedAt),
+        "severity" => Ok(AlertSort::Severity),
+        _ => Err(ApiError::Validation(format!("Invalid sort: {s}"))),
+    }
 }
 
-impl From<AlertRow> for AlertResponse {
-    fn from(row: AlertRow) -> Self {
+impl From<Alert> for AlertResponse {
+    fn from(alert: Alert) -> Self {
         Self {
-            id: row.id.to_string(),
-            pattern_id: row.pattern_id.map(|id| id.to_string()),
-            alert_type: row.alert_type,
-            severity: row.severity,
-            title: row.title,
-            message: row.message,
-            affected_tickets: row.affected_tickets,
-            suggested_actions: row.suggested_actions,
-            is_read: row.is_read,
-            is_dismissed: row.is_dismissed,
-            created_at: row.created_at.to_rfc3339(),
+            id: alert.id.to_string(),
+            pattern_id: alert.pattern_id.map(|id| id.to_string()),
+            alert_type: alert.alert_type.to_string(),
+            severity: alert.severity.to_string(),
+            title: alert.title,
+            message: alert.message,
+            affected_tickets: alert.affected_tickets,
+            suggested_actions: alert.suggested_actions,
+            is_read: alert.is_read,
+            is_dismissed: alert.is_dismissed,
+            created_at: alert.created_at.to_rfc3339(),
         }
     }
 }
 
+impl From<MuteRule> for MuteRuleResponse {
+    fn from(rule: MuteRule) -> Self {
+        Self {
+            id: rule.id.to_string(),
+            alert_type: rule.alert_type.to_string(),
+            component: rule.component,
+            until: rule.until.map(|until| until.to_rfc3339()),
+            reason: rule.reason,
+            created_by: rule.created_by,
+            created_at: rule.created_at.to_rfc3339(),
+        }
+    }
+}
+
+impl From<PatternThresholds> for PatternThresholdsResponse {
+    fn from(t: PatternThresholds) -> Self {
+        Self {
+            time_excess_percent: t.time_excess_percent,
+            consecutive_count: t.consecutive_count,
+            consecutive_window: t.consecutive_window,
+            spike_window: t.spike_window,
+            spike_multiplier: t.spike_multiplier,
+            flaky_round_trips: t.flaky_round_trips,
+            flaky_window_days: t.flaky_window_days,
+        }
+    }
+}
+
+impl From<TeamThresholds> for TeamThresholdsResponse {
+    fn from(t: TeamThresholds) -> Self {
+        Self {
+            team: t.team,
+            thresholds: t.thresholds.into(),
+            updated_at: t.updated_at.to_rfc3339(),
+        }
+    }
+}
+
+// Internal row types
 #[derive(sqlx::FromRow)]
 struct PatternRow {
     id: Uuid,
@@ -342,3 +909,73 @@ impl From<PatternRow> for PatternResponse {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_alert_filters() {
+        assert_eq!(parse_severity("Critical").unwrap(), Severity::Critical);
+        assert_eq!(parse_alert_type("kpi_breach").unwrap(), PatternType::KpiBreach);
+        assert_eq!(
+            parse_alert_type("flaky_verification").unwrap(),
+            PatternType::FlakyVerification
+        );
+        assert_eq!(parse_sort("severity").unwrap(), AlertSort::Severity);
+        assert!(parse_severity("urgent").is_err());
+        assert!(parse_alert_type("outage").is_err());
+        assert!(parse_sort("title").is_err());
+    }
+
+    #[test]
+    fn test_thresholds_request_fills_defaults() {
+        let request = PatternThresholdsRequest {
+            team: Some(" PAY ".into()),
+            time_excess_percent: Some(80.0),
+            consecutive_count: None,
+            consecutive_window: None,
+            spike_window: None,
+            spike_multiplier: None,
+            flaky_round_trips: None,
+            flaky_window_days: None,
+        };
+        let (team, thresholds) = request.into_thresholds().unwrap();
+        assert_eq!(team, "PAY");
+        assert!((thresholds.time_excess_percent - 80.0).abs() < f64::EPSILON);
+        assert_eq!(thresholds.consecutive_count, PatternThresholds::default().consecutive_count);
+
+        let request = PatternThresholdsRequest {
+            team: None,
+            time_excess_percent: None,
+            consecutive_count: Some(6),
+            consecutive_window: Some(4),
+            spike_window: None,
+            spike_multiplier: None,
+            flaky_round_trips: None,
+            flaky_window_days: None,
+        };
+        assert!(request.into_thresholds().is_err());
+    }
+
+    #[test]
+    fn test_mute_rule_request_validation() {
+        let request = MuteRuleRequest {
+            alert_type: "spike".into(),
+            component: None,
+            until: Some(Utc::now() - chrono::Duration::hours(1)),
+            reason: None,
+        };
+        let errors = request.validate().unwrap_err();
+        assert!(errors.field_errors().contains_key("until"));
+
+        let request = MuteRuleRequest {
+            alert_type: "outage".into(),
+            component: None,
+            until: None,
+            reason: None,
+        };
+        let errors = request.validate().unwrap_err();
+        assert!(errors.field_errors().contains_key("alert_type"));
+    }
+}
@@ -0,0 +1,199 @@
+//! API key management endpoints.
+//!
+//! Signed-in users create keys for their CI pipelines and scripts, rotate
+//! them and revoke them. The full key is returned only when it is created
+//! or rotated; listings show its prefix.
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    routing::{delete, get, post},
+    Extension, Json, Router,
+};
+use chrono::{Duration, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::info;
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::Validate;
+
+use qa_pms_core::error::ApiError;
+
+use crate::api_keys::{ApiKey, ApiKeyScope, NewApiKey};
+use crate::app::AppState;
+use crate::audit::AuditChange;
+use crate::auth::CurrentUser;
+use crate::validation::{not_blank, ValidatedJson};
+
+type ApiResult<T> = Result<T, ApiError>;
+
+/// Create the API key router.
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/api/v1/auth/api-keys", get(list_api_keys).post(create_api_key))
+        .route("/api/v1/auth/api-keys/:id", delete(revoke_api_key))
+        .route("/api/v1/auth/api-keys/:id/rotate", post(rotate_api_key))
+}
+
+/// New API key.
+#[derive(Debug, Deserialize, ToSchema, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateApiKeyRequest {
+    /// Name to tell the key apart, such as the pipeline using it
+    #[validate(custom(function = "not_blank"), length(max = 100))]
+    pub name: String,
+    /// What the key may do
+    #[validate(length(min = 1, message = "at least one scope is required"))]
+    pub scopes: Vec<ApiKeyScope>,
+    /// Requests per minute (defaults to the client rate)
+    #[validate(range(min = 1, max = 10000))]
+    pub rate_limit_per_minute: Option<u32>,
+    /// Days until the key expires (never by default)
+    #[validate(range(min = 1, max = 3650))]
+    pub expires_in_days: Option<u32>,
+}
+
+/// A key with its secret, shown once.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct IssuedApiKey {
+    /// The stored key
+    #[serde(flatten)]
+    pub key: ApiKey,
+    /// Full key for the `X-Api-Key` header; it cannot be shown again
+    pub secret: String,
+}
+
+fn db_error(e: sqlx::Error) -> ApiError {
+    ApiError::Internal(e.into())
+}
+
+/// Keys need an account to act as.
+fn require_auth(state: &AppState) -> ApiResult<()> {
+    if state.settings.auth.is_none() {
+        return Err(ApiError::ServiceUnavailable(
+            "Authentication is not enabled; the API is open without keys".into(),
+        ));
+    }
+    Ok(())
+}
+
+/// List the caller's API keys.
+#[utoipa::path(
+    get,
+    path = "/api/v1/auth/api-keys",
+    responses(
+        (status = 200, description = "Live API keys", body = Vec<ApiKey>),
+        (status = 503, description = "Authentication is not enabled")
+    ),
+    tag = "Auth"
+)]
+pub async fn list_api_keys(
+    State(state): State<AppState>,
+    user: CurrentUser,
+) -> ApiResult<Json<Vec<ApiKey>>> {
+    require_auth(&state)?;
+    let keys = state.api_keys.list(&user).await.map_err(db_error)?;
+    Ok(Json(keys))
+}
+
+/// Create an API key acting as the caller.
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/api-keys",
+    request_body = CreateApiKeyRequest,
+    responses(
+        (status = 201, description = "Key created; the secret is shown only now", body = IssuedApiKey),
+        (status = 422, description = "Invalid request"),
+        (status = 503, description = "Authentication is not enabled")
+    ),
+    tag = "Auth"
+)]
+pub async fn create_api_key(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    ValidatedJson(request): ValidatedJson<CreateApiKeyRequest>,
+) -> ApiResult<(StatusCode, Extension<AuditChange>, Json<IssuedApiKey>)> {
+    require_auth(&state)?;
+    let mut scopes = request.scopes;
+    scopes.sort_by_key(|scope| scope.as_str());
+    scopes.dedup();
+    let new = NewApiKey {
+        name: request.name,
+        scopes,
+        rate_limit_per_minute: request.rate_limit_per_minute,
+        expires_at: request
+            .expires_in_days
+            .map(|days| Utc::now() + Duration::days(i64::from(days))),
+    };
+    let (key, secret) = state.api_keys.create(&user, new).await.map_err(db_error)?;
+
+    info!(key_id = %key.id, user_id = %user.id, "API key created");
+    let change = AuditChange::new(key.id).after(&key);
+    Ok((StatusCode::CREATED, change.into_extension(), Json(IssuedApiKey { key, secret })))
+}
+
+/// Replace an API key's secret; the old secret stops working at once.
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/api-keys/{id}/rotate",
+    params(("id" = Uuid, Path, description = "API key ID")),
+    responses(
+        (status = 200, description = "Key rotated; the new secret is shown only now", body = IssuedApiKey),
+        (status = 404, description = "API key not found"),
+        (status = 503, description = "Authentication is not enabled")
+    ),
+    tag = "Auth"
+)]
+pub async fn rotate_api_key(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Path(id): Path<Uuid>,
+) -> ApiResult<(Extension<AuditChange>, Json<IssuedApiKey>)> {
+    require_auth(&state)?;
+    let before = user_key(&state, &user, id).await?;
+    let (key, secret) = state
+        .api_keys
+        .rotate(&user, id)
+        .await
+        .map_err(db_error)?
+        .ok_or_else(|| ApiError::NotFound(format!("API key {id} not found")))?;
+
+    info!(key_id = %id, user_id = %user.id, "API key rotated");
+    let change = AuditChange::new(id).before(&before).after(&key);
+    Ok((change.into_extension(), Json(IssuedApiKey { key, secret })))
+}
+
+/// Revoke an API key.
+#[utoipa::path(
+    delete,
+    path = "/api/v1/auth/api-keys/{id}",
+    params(("id" = Uuid, Path, description = "API key ID")),
+    responses(
+        (status = 204, description = "Key revoked"),
+        (status = 404, description = "API key not found"),
+        (status = 503, description = "Authentication is not enabled")
+    ),
+    tag = "Auth"
+)]
+pub async fn revoke_api_key(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Path(id): Path<Uuid>,
+) -> ApiResult<(StatusCode, Extension<AuditChange>)> {
+    require_auth(&state)?;
+    let before = user_key(&state, &user, id).await?;
+    if !state.api_keys.revoke(&user, id).await.map_err(db_error)? {
+        return Err(ApiError::NotFound(format!("API key {id} not found")));
+    }
+
+    info!(key_id = %id, user_id = %user.id, "API key revoked");
+    let change = AuditChange::new(id).before(&before);
+    Ok((StatusCode::NO_CONTENT, change.into_extension()))
+}
+
+/// One of the user's keys, if it exists.
+async fn user_key(state: &AppState, user: &CurrentUser, id: Uuid) -> ApiResult<Option<ApiKey>> {
+    let keys = state.api_keys.list(user).await.map_err(db_error)?;
+    Ok(keys.into_iter().find(|key| key.id == id))
+}
@@ -0,0 +1,63 @@
+//! Audit log API endpoints.
+
+use axum::{
+    extract::{Query, State},
+    routing::get,
+    Json, Router,
+};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use qa_pms_core::error::ApiError;
+use qa_pms_core::types::{CursorInfo, CursorQuery};
+
+use crate::app::AppState;
+use crate::audit::{AuditEntry, AuditFilter};
+use crate::auth::CurrentUser;
+
+type ApiResult<T> = Result<T, ApiError>;
+
+/// Create the audit router.
+pub fn router() -> Router<AppState> {
+    Router::new().route("/api/v1/audit", get(list_audit_entries))
+}
+
+/// A page of audit entries.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditLogResponse {
+    /// Entries, newest first unless `direction=asc`
+    pub entries: Vec<AuditEntry>,
+    /// Pagination information
+    pub page_info: CursorInfo,
+}
+
+/// List changes made through the API in the caller's tenant (admin only).
+#[utoipa::path(
+    get,
+    path = "/api/v1/audit",
+    tag = "Audit",
+    params(AuditFilter, CursorQuery),
+    responses(
+        (status = 200, description = "Audit entries", body = AuditLogResponse),
+        (status = 400, description = "Invalid cursor"),
+        (status = 403, description = "Caller is not an admin"),
+    )
+)]
+pub async fn list_audit_entries(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Query(filter): Query<AuditFilter>,
+    Query(query): Query<CursorQuery>,
+) -> ApiResult<Json<AuditLogResponse>> {
+    let page = query.page_request()?;
+    let entries = state
+        .audit_log
+        .list(user.tenant.as_str(), &filter, &page)
+        .await
+        .map_err(|e| ApiError::Internal(anyhow::anyhow!("Failed to fetch audit log: {e}")))?;
+    Ok(Json(AuditLogResponse {
+        entries: entries.data,
+        page_info: entries.page_info,
+    }))
+}
@@ -0,0 +1,434 @@
+//! Authentication and user management API endpoints.
+//!
+//! Login exchanges an email and password for a bearer token. Admins manage
+//! the accounts of their own tenant and their roles; accounts are deactivated
+//! rather than deleted so the workflows and templates they own keep a valid
+//! owner. Browser clients get a CSRF token here when CSRF protection is on.
+
+use axum::{
+    extract::{Path, State},
+    http::{header, HeaderMap, StatusCode},
+    routing::{get, post, put},
+    Extension, Json, Router,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use tracing::info;
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::Validate;
+
+use qa_pms_core::error::ApiError;
+use qa_pms_core::TenantId;
+
+use crate::app::AppState;
+use crate::audit::AuditChange;
+use crate::auth::{hash_password, issue_token, verify_password, CurrentUser, Role};
+use crate::cross_origin::{csrf_cookie, csrf_set_cookie, new_csrf_token, CSRF_HEADER};
+use crate::validation::{not_blank, ValidatedJson};
+
+type ApiResult<T> = Result<T, ApiError>;
+
+const USER_COLUMNS: &str =
+    "id, email, display_name, role, tenant_id, is_active, created_at, last_login_at";
+
+/// Create the auth router.
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/api/v1/auth/login", post(login))
+        .route("/api/v1/auth/me", get(me))
+        .route("/api/v1/auth/csrf", get(csrf_token))
+        .route("/api/v1/auth/users", get(list_users).post(create_user))
+        .route("/api/v1/auth/users/:id", put(update_user))
+}
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/// Login credentials.
+#[derive(Debug, Deserialize, ToSchema, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct LoginRequest {
+    /// Account email
+    #[validate(custom(function = "not_blank"))]
+    pub email: String,
+    /// Account password
+    #[validate(custom(function = "not_blank"))]
+    pub password: String,
+}
+
+/// Issued access token.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct LoginResponse {
+    /// Bearer token for the `Authorization` header
+    pub token: String,
+    /// When the token stops being accepted
+    pub expires_at: DateTime<Utc>,
+    /// The signed-in user
+    pub user: UserResponse,
+}
+
+/// The caller's identity.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CurrentUserResponse {
+    /// User ID (nil while authentication is disabled)
+    pub id: Uuid,
+    /// Login email
+    pub email: String,
+    /// Granted role
+    pub role: Role,
+    /// Tenant the caller works in
+    pub tenant: String,
+    /// Whether authentication is enabled on this server
+    pub auth_enabled: bool,
+}
+
+/// CSRF token for state-changing browser requests.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CsrfTokenResponse {
+    /// Token to send back in the header
+    pub token: String,
+    /// Header carrying the token
+    pub header_name: String,
+    /// Whether this server checks the token
+    pub enforced: bool,
+}
+
+/// A user account.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UserResponse {
+    /// User ID
+    pub id: Uuid,
+    /// Login email
+    pub email: String,
+    /// Name shown in the UI
+    pub display_name: String,
+    /// Granted role
+    pub role: Role,
+    /// Tenant the account belongs to
+    pub tenant: String,
+    /// Whether the account can sign in
+    pub is_active: bool,
+    /// When the account was created
+    pub created_at: DateTime<Utc>,
+    /// Last successful login
+    pub last_login_at: Option<DateTime<Utc>>,
+}
+
+/// New user account.
+#[derive(Debug, Deserialize, ToSchema, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateUserRequest {
+    /// Login email
+    #[validate(email)]
+    pub email: String,
+    /// Name shown in the UI (defaults to the email)
+    pub display_name: Option<String>,
+    /// Granted role
+    pub role: Role,
+    /// Initial password (at least 12 characters)
+    #[validate(length(min = 12, message = "must be at least 12 characters"))]
+    pub password: String,
+}
+
+/// Changes to a user account; omitted fields are kept.
+#[derive(Debug, Deserialize, ToSchema, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateUserRequest {
+    /// New display name
+    #[validate(custom(function = "not_blank"))]
+    pub display_name: Option<String>,
+    /// New role
+    pub role: Option<Role>,
+    /// Enable or disable sign-in
+    pub is_active: Option<bool>,
+    /// New password (at least 12 characters)
+    #[validate(length(min = 12, message = "must be at least 12 characters"))]
+    pub password: Option<String>,
+}
+
+#[derive(Debug, FromRow)]
+struct UserRow {
+    id: Uuid,
+    email: String,
+    display_name: String,
+    role: String,
+    tenant_id: String,
+    is_active: bool,
+    created_at: DateTime<Utc>,
+    last_login_at: Option<DateTime<Utc>>,
+}
+
+impl From<UserRow> for UserResponse {
+    fn from(row: UserRow) -> Self {
+        Self {
+            id: row.id,
+            email: row.email,
+            display_name: row.display_name,
+            // Unknown stored roles get the least privilege
+            role: Role::parse(&row.role).unwrap_or(Role::Qa),
+            tenant: row.tenant_id,
+            is_active: row.is_active,
+            created_at: row.created_at,
+            last_login_at: row.last_login_at,
+        }
+    }
+}
+
+fn db_error(e: sqlx::Error) -> ApiError {
+    ApiError::Internal(e.into())
+}
+
+// ============================================================================
+// Handlers
+// ============================================================================
+
+/// Sign in and get an access token.
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/login",
+    request_body = LoginRequest,
+    responses(
+        (status = 200, description = "Signed in", body = LoginResponse),
+        (status = 401, description = "Wrong email or password, or account disabled"),
+        (status = 503, description = "Authentication is not enabled")
+    ),
+    tag = "Auth"
+)]
+pub async fn login(
+    State(state): State<AppState>,
+    ValidatedJson(request): ValidatedJson<LoginRequest>,
+) -> ApiResult<Json<LoginResponse>> {
+    let settings = state
+        .settings
+        .auth
+        .as_ref()
+        .ok_or_else(|| ApiError::ServiceUnavailable("Authentication is not enabled".into()))?;
+
+    let email = request.email.trim().to_lowercase();
+    let account: Option<(Uuid, String)> =
+        sqlx::query_as("SELECT id, password_hash FROM users WHERE email = $1 AND is_active")
+            .bind(&email)
+            .fetch_optional(&state.db)
+            .await
+            .map_err(db_error)?;
+    let Some((id, _)) = account.filter(|(_, hash)| verify_password(&request.password, hash)) else {
+        return Err(ApiError::Unauthorized("Invalid email or password".into()));
+    };
+
+    let row: UserRow = sqlx::query_as(&format!(
+        "UPDATE users SET last_login_at = NOW() WHERE id = $1 RETURNING {USER_COLUMNS}"
+    ))
+    .bind(id)
+    .fetch_one(&state.db)
+    .await
+    .map_err(db_error)?;
+    let tenant = TenantId::parse(&row.tenant_id)
+        .ok_or_else(|| ApiError::Internal(anyhow::anyhow!("Invalid tenant {}", row.tenant_id)))?;
+    let user = UserResponse::from(row);
+    let (token, expires_at) = issue_token(settings, user.id, &user.email, user.role, &tenant)?;
+
+    info!(user_id = %user.id, "User signed in");
+    Ok(Json(LoginResponse {
+        token,
+        expires_at,
+        user,
+    }))
+}
+
+/// Get the signed-in user.
+#[utoipa::path(
+    get,
+    path = "/api/v1/auth/me",
+    responses(
+        (status = 200, description = "Current user", body = CurrentUserResponse),
+        (status = 401, description = "Not signed in")
+    ),
+    tag = "Auth"
+)]
+pub async fn me(State(state): State<AppState>, user: CurrentUser) -> Json<CurrentUserResponse> {
+    Json(CurrentUserResponse {
+        id: user.id.0,
+        email: user.email,
+        role: user.role,
+        tenant: user.tenant.to_string(),
+        auth_enabled: state.settings.auth.is_some(),
+    })
+}
+
+/// Get a CSRF token, also set as a cookie.
+///
+/// A browser already holding a token gets the same one back, so tabs
+/// sharing the cookie keep working.
+#[utoipa::path(
+    get,
+    path = "/api/v1/auth/csrf",
+    responses(
+        (status = 200, description = "CSRF token", body = CsrfTokenResponse)
+    ),
+    tag = "Auth"
+)]
+pub async fn csrf_token(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> ([(header::HeaderName, String); 1], Json<CsrfTokenResponse>) {
+    let token = csrf_cookie(&headers).unwrap_or_else(new_csrf_token);
+    (
+        [(header::SET_COOKIE, csrf_set_cookie(&state.settings.cors, &token))],
+        Json(CsrfTokenResponse {
+            token,
+            header_name: CSRF_HEADER.to_string(),
+            enforced: state.settings.cors.csrf_enabled,
+        }),
+    )
+}
+
+/// List the accounts of the caller's tenant (admin only).
+#[utoipa::path(
+    get,
+    path = "/api/v1/auth/users",
+    responses(
+        (status = 200, description = "User accounts", body = Vec<UserResponse>),
+        (status = 403, description = "Caller is not an admin")
+    ),
+    tag = "Auth"
+)]
+pub async fn list_users(
+    State(state): State<AppState>,
+    caller: CurrentUser,
+) -> ApiResult<Json<Vec<UserResponse>>> {
+    let rows: Vec<UserRow> = sqlx::query_as(&format!(
+        "SELECT {USER_COLUMNS} FROM users WHERE tenant_id = $1 ORDER BY email"
+    ))
+    .bind(caller.tenant.as_str())
+    .fetch_all(&state.db)
+    .await
+    .map_err(db_error)?;
+    Ok(Json(rows.into_iter().map(Into::into).collect()))
+}
+
+/// Create an account in the caller's tenant (admin only).
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/users",
+    request_body = CreateUserRequest,
+    responses(
+        (status = 201, description = "User created", body = UserResponse),
+        (status = 403, description = "Caller is not an admin"),
+        (status = 409, description = "Email already in use"),
+        (status = 422, description = "Invalid request")
+    ),
+    tag = "Auth"
+)]
+pub async fn create_user(
+    State(state): State<AppState>,
+    caller: CurrentUser,
+    ValidatedJson(request): ValidatedJson<CreateUserRequest>,
+) -> ApiResult<(StatusCode, Extension<AuditChange>, Json<UserResponse>)> {
+    let email = request.email.trim().to_lowercase();
+    let display_name = request
+        .display_name
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| email.clone());
+    let hash = hash_password(&request.password)?;
+
+    let row: UserRow = sqlx::query_as(&format!(
+        r"
+        INSERT INTO users
+            (id, email, display_name, role, password_hash, tenant_id, is_active, created_at)
+        SELECT $1, $2, $3, $4, $5, $6, TRUE, NOW()
+        WHERE NOT EXISTS (SELECT 1 FROM users WHERE email = $2)
+        RETURNING {USER_COLUMNS}
+        "
+    ))
+    .bind(Uuid::new_v4())
+    .bind(&email)
+    .bind(&display_name)
+    .bind(request.role.as_str())
+    .bind(hash)
+    .bind(caller.tenant.as_str())
+    .fetch_optional(&state.db)
+    .await
+    .map_err(db_error)?
+    .ok_or_else(|| ApiError::Conflict(format!("A user with email {email} already exists")))?;
+
+    info!(user_id = %row.id, role = %row.role, "User created");
+    let created = UserResponse::from(row);
+    let change = AuditChange::new(created.id).after(&created);
+    Ok((StatusCode::CREATED, change.into_extension(), Json(created)))
+}
+
+/// Update an account in the caller's tenant (admin only).
+#[utoipa::path(
+    put,
+    path = "/api/v1/auth/users/{id}",
+    params(("id" = Uuid, Path, description = "User ID")),
+    request_body = UpdateUserRequest,
+    responses(
+        (status = 200, description = "User updated", body = UserResponse),
+        (status = 400, description = "Admins cannot demote or disable themselves"),
+        (status = 403, description = "Caller is not an admin"),
+        (status = 404, description = "User not found")
+    ),
+    tag = "Auth"
+)]
+pub async fn update_user(
+    State(state): State<AppState>,
+    caller: CurrentUser,
+    Path(id): Path<Uuid>,
+    ValidatedJson(request): ValidatedJson<UpdateUserRequest>,
+) -> ApiResult<(Extension<AuditChange>, Json<UserResponse>)> {
+    if caller.id.0 == id
+        && (request.is_active == Some(false)
+            || request.role.is_some_and(|role| role != Role::Admin))
+    {
+        return Err(ApiError::Validation(
+            "Admins cannot demote or disable their own account".into(),
+        ));
+    }
+    let hash = request.password.as_deref().map(hash_password).transpose()?;
+    let before: Option<UserRow> = sqlx::query_as(&format!(
+        "SELECT {USER_COLUMNS} FROM users WHERE id = $1 AND tenant_id = $2"
+    ))
+    .bind(id)
+    .bind(caller.tenant.as_str())
+    .fetch_optional(&state.db)
+    .await
+    .map_err(db_error)?;
+
+    let row: UserRow = sqlx::query_as(&format!(
+        r"
+        UPDATE users SET
+            display_name = COALESCE($2, display_name),
+            role = COALESCE($3, role),
+            is_active = COALESCE($4, is_active),
+            password_hash = COALESCE($5, password_hash)
+        WHERE id = $1 AND tenant_id = $6
+        RETURNING {USER_COLUMNS}
+        "
+    ))
+    .bind(id)
+    .bind(request.display_name.as_deref().map(str::trim))
+    .bind(request.role.map(Role::as_str))
+    .bind(request.is_active)
+    .bind(hash)
+    .bind(caller.tenant.as_str())
+    .fetch_optional(&state.db)
+    .await
+    .map_err(db_error)?
+    .ok_or_else(|| ApiError::NotFound(format!("User {id} not found")))?;
+
+    info!(user_id = %id, role = %row.role, is_active = row.is_active, "User updated");
+    let updated = UserResponse::from(row);
+    let change = AuditChange::new(id)
+        .before(&before.map(UserResponse::from))
+        .after(&updated);
+    Ok((change.into_extension(), Json(updated)))
+}
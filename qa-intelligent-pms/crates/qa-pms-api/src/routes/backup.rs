@@ -0,0 +1,109 @@
+//! Workspace backup and restore API endpoints.
+//!
+//! Backups are downloaded as one JSON archive and uploaded unchanged to
+//! restore them, on the same server or a new one.
+
+use axum::{
+    extract::{Query, State},
+    http::header,
+    response::IntoResponse,
+    routing::{get, post},
+    Json, Router,
+};
+use serde::Deserialize;
+use tracing::info;
+use utoipa::IntoParams;
+
+use qa_pms_core::error::ApiError;
+
+use crate::app::{start_search_index_rebuild, AppState};
+use crate::backup::{self, BackupArchive, BackupError, RestoreReport};
+
+type ApiResult<T> = Result<T, ApiError>;
+
+/// Create the backup router.
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/api/v1/backup", get(export_backup))
+        .route("/api/v1/backup/restore", post(restore_backup))
+}
+
+/// Query parameters for a restore.
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[serde(rename_all = "camelCase", default)]
+pub struct RestoreQuery {
+    /// Validate and count without keeping any change
+    pub dry_run: bool,
+    /// Delete existing rows of the archived tables before restoring
+    pub replace: bool,
+}
+
+fn backup_error(e: BackupError) -> ApiError {
+    match e {
+        BackupError::Invalid(_) => ApiError::Validation(e.to_string()),
+        BackupError::Database(e) => ApiError::Internal(e.into()),
+    }
+}
+
+/// Download a backup of the workspace.
+///
+/// Credentials are never included; the configuration snapshot lists
+/// non-secret settings only.
+#[utoipa::path(
+    get,
+    path = "/api/v1/backup",
+    responses(
+        (status = 200, description = "Backup archive", body = BackupArchive)
+    ),
+    tag = "Backup"
+)]
+pub async fn export_backup(State(state): State<AppState>) -> ApiResult<impl IntoResponse> {
+    let archive = backup::export(&state.db, &state.settings)
+        .await
+        .map_err(|e| ApiError::Internal(e.into()))?;
+    let rows: usize = archive.tables.values().map(Vec::len).sum();
+    info!(rows, "Workspace backup exported");
+
+    let disposition = format!(
+        "attachment; filename=\"qa-pms-backup-{}.json\"",
+        archive.created_at.format("%Y%m%dT%H%M%SZ")
+    );
+    Ok(([(header::CONTENT_DISPOSITION, disposition)], Json(archive)))
+}
+
+/// Restore a backup archive.
+///
+/// Existing rows are kept unless `replace` is set. A dry run validates the
+/// archive against the database and reports the exact counts without
+/// changing anything.
+#[utoipa::path(
+    post,
+    path = "/api/v1/backup/restore",
+    params(RestoreQuery),
+    request_body = BackupArchive,
+    responses(
+        (status = 200, description = "Restore report", body = RestoreReport),
+        (status = 400, description = "Invalid or unsupported archive")
+    ),
+    tag = "Backup"
+)]
+pub async fn restore_backup(
+    State(state): State<AppState>,
+    Query(query): Query<RestoreQuery>,
+    Json(archive): Json<BackupArchive>,
+) -> ApiResult<Json<RestoreReport>> {
+    let report = backup::restore(&state.db, &archive, query.dry_run, query.replace)
+        .await
+        .map_err(backup_error)?;
+
+    if !report.dry_run {
+        let inserted: u64 = report.tables.iter().map(|t| t.inserted).sum();
+        info!(
+            inserted,
+            replace = report.replace,
+            "Workspace backup restored"
+        );
+        start_search_index_rebuild(&state.search_index, &state.db, &state.test_cases);
+    }
+    Ok(Json(report))
+}
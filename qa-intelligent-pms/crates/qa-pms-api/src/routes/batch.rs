@@ -0,0 +1,332 @@
+//! Batch request endpoint.
+//!
+//! Executes several API calls in one round trip. Sub-requests are dispatched
+//! in-process against the API router with bounded concurrency and their
+//! responses are returned in request order. The caller's `Authorization`
+//! or `X-Api-Key` header, `X-Tenant-Id` and `X-User-Id` headers and address
+//! are forwarded so each sub-request runs as the same user in the same
+//! tenant and counts against the same rate limit.
+
+use std::net::SocketAddr;
+
+use axum::{
+    body::{to_bytes, Body},
+    extract::ConnectInfo,
+    http::{header, HeaderMap, HeaderValue, Method, Request, StatusCode},
+    routing::post,
+    Extension, Json, Router,
+};
+use futures::stream::{self, StreamExt};
+use serde::{Deserialize, Serialize};
+use tower::ServiceExt;
+use utoipa::ToSchema;
+
+use qa_pms_core::error::ApiError;
+
+use crate::api_keys::API_KEY_HEADER;
+use crate::auth::{TENANT_HEADER, USER_ID_HEADER};
+
+/// Maximum number of sub-requests in one batch.
+pub const MAX_BATCH_SIZE: usize = 20;
+
+/// Number of sub-requests executed concurrently.
+pub const BATCH_CONCURRENCY: usize = 4;
+
+/// Maximum size of a single sub-response body.
+const MAX_ITEM_BODY_BYTES: usize = 5 * 1024 * 1024;
+
+/// What each sub-request inherits from the batch request.
+#[derive(Debug, Clone, Default)]
+pub struct BatchOrigin {
+    /// Caller's `Authorization` header
+    pub authorization: Option<HeaderValue>,
+    /// Caller's `X-Api-Key` header
+    pub api_key: Option<HeaderValue>,
+    /// Caller's `X-Tenant-Id` header
+    pub tenant: Option<HeaderValue>,
+    /// Caller's `X-User-Id` header, naming the user without authentication
+    pub user_id: Option<HeaderValue>,
+    /// Caller's address
+    pub client_addr: Option<ConnectInfo<SocketAddr>>,
+}
+
+/// Create the batch router over the given API router.
+///
+/// Takes the already-stated API router so sub-requests hit the same handlers.
+pub fn router(api: Router) -> Router {
+    Router::new()
+        .route("/api/v1/batch", post(execute_batch_handler))
+        .layer(Extension(api))
+}
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/// Batch request body.
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchRequest {
+    /// Sub-requests to execute
+    pub requests: Vec<BatchItem>,
+}
+
+/// A single sub-request.
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchItem {
+    /// Client-chosen identifier echoed in the response
+    pub id: Option<String>,
+    /// HTTP method (GET, POST, PUT, PATCH, DELETE)
+    #[schema(example = "GET")]
+    pub method: String,
+    /// API path including query string
+    #[schema(example = "/api/v1/dashboard?period=30d")]
+    pub path: String,
+    /// Optional JSON body
+    #[schema(value_type = Option<Object>)]
+    pub body: Option<serde_json::Value>,
+}
+
+/// Batch response body.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchResponse {
+    /// Responses in the same order as the requests
+    pub responses: Vec<BatchItemResponse>,
+}
+
+/// Response to a single sub-request.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchItemResponse {
+    /// Identifier from the matching request
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// HTTP status code
+    pub status: u16,
+    /// Response body (JSON if parseable, otherwise a string)
+    #[schema(value_type = Object)]
+    pub body: serde_json::Value,
+}
+
+// ============================================================================
+// Handlers
+// ============================================================================
+
+/// Execute multiple API requests in one call.
+#[utoipa::path(
+    post,
+    path = "/api/v1/batch",
+    request_body = BatchRequest,
+    responses(
+        (status = 200, description = "Per-item responses", body = BatchResponse),
+        (status = 400, description = "Batch is empty or too large"),
+    ),
+    tag = "Batch"
+)]
+pub async fn execute_batch_handler(
+    Extension(api): Extension<Router>,
+    client_addr: Option<ConnectInfo<SocketAddr>>,
+    headers: HeaderMap,
+    Json(request): Json<BatchRequest>,
+) -> Result<Json<BatchResponse>, ApiError> {
+    if request.requests.is_empty() {
+        return Err(ApiError::Validation("Batch must contain at least one request".into()));
+    }
+    if request.requests.len() > MAX_BATCH_SIZE {
+        return Err(ApiError::Validation(format!(
+            "Batch may contain at most {MAX_BATCH_SIZE} requests"
+        )));
+    }
+
+    Ok(Json(BatchResponse {
+        responses: execute_batch(
+            api,
+            request.requests,
+            BatchOrigin {
+                authorization: headers.get(header::AUTHORIZATION).cloned(),
+                api_key: headers.get(API_KEY_HEADER).cloned(),
+                tenant: headers.get(TENANT_HEADER).cloned(),
+                user_id: headers.get(USER_ID_HEADER).cloned(),
+                client_addr,
+            },
+        )
+        .await,
+    }))
+}
+
+/// Execute sub-requests against `api`, preserving order.
+pub async fn execute_batch(
+    api: Router,
+    items: Vec<BatchItem>,
+    origin: BatchOrigin,
+) -> Vec<BatchItemResponse> {
+    stream::iter(items)
+        .map(|item| execute_item(api.clone(), item, &origin))
+        .buffered(BATCH_CONCURRENCY)
+        .collect()
+        .await
+}
+
+async fn execute_item(
+    api: Router,
+    item: BatchItem,
+    origin: &BatchOrigin,
+) -> BatchItemResponse {
+    let request = match build_request(&item, origin) {
+        Ok(request) => request,
+        Err(message) => {
+            return BatchItemResponse {
+                id: item.id,
+                status: StatusCode::BAD_REQUEST.as_u16(),
+                body: serde_json::json!({ "error": message, "code": "VALIDATION_ERROR" }),
+            }
+        }
+    };
+
+    let response = match api.oneshot(request).await {
+        Ok(response) => response,
+        Err(infallible) => match infallible {},
+    };
+    let status = response.status().as_u16();
+    let body = match to_bytes(response.into_body(), MAX_ITEM_BODY_BYTES).await {
+        Ok(bytes) if bytes.is_empty() => serde_json::Value::Null,
+        Ok(bytes) => serde_json::from_slice(&bytes)
+            .unwrap_or_else(|_| serde_json::Value::String(String::from_utf8_lossy(&bytes).into_owned())),
+        Err(e) => serde_json::json!({ "error": format!("Failed to read response: {e}") }),
+    };
+
+    BatchItemResponse {
+        id: item.id,
+        status,
+        body,
+    }
+}
+
+/// Validate a sub-request and turn it into an HTTP request.
+fn build_request(item: &BatchItem, origin: &BatchOrigin) -> Result<Request<Body>, String> {
+    let method = match item.method.to_ascii_uppercase().as_str() {
+        "GET" => Method::GET,
+        "POST" => Method::POST,
+        "PUT" => Method::PUT,
+        "PATCH" => Method::PATCH,
+        "DELETE" => Method::DELETE,
+        other => return Err(format!("Unsupported method: {other}")),
+    };
+
+    if !item.path.starts_with("/api/v1/") {
+        return Err("Path must start with /api/v1/".to_string());
+    }
+    if item.path.starts_with("/api/v1/batch") {
+        return Err("Nested batch requests are not allowed".to_string());
+    }
+
+    let mut builder = Request::builder().method(method).uri(&item.path);
+    if let Some(value) = &origin.authorization {
+        builder = builder.header(header::AUTHORIZATION, value);
+    }
+    if let Some(value) = &origin.api_key {
+        builder = builder.header(API_KEY_HEADER, value);
+    }
+    if let Some(value) = &origin.tenant {
+        builder = builder.header(TENANT_HEADER, value);
+    }
+    if let Some(value) = &origin.user_id {
+        builder = builder.header(USER_ID_HEADER, value);
+    }
+    if let Some(client_addr) = origin.client_addr {
+        builder = builder.extension(client_addr);
+    }
+    let request = match &item.body {
+        Some(body) => builder
+            .header("content-type", "application/json")
+            .body(Body::from(body.to_string())),
+        None => builder.body(Body::empty()),
+    };
+    request.map_err(|e| format!("Invalid request: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::routing::get;
+
+    fn item(method: &str, path: &str) -> BatchItem {
+        BatchItem {
+            id: Some(path.to_string()),
+            method: method.to_string(),
+            path: path.to_string(),
+            body: None,
+        }
+    }
+
+    #[test]
+    fn test_build_request_rejects_bad_items() {
+        let origin = BatchOrigin::default();
+        assert!(build_request(&item("TRACE", "/api/v1/health"), &origin).is_err());
+        assert!(build_request(&item("GET", "/metrics"), &origin).is_err());
+        assert!(build_request(&item("POST", "/api/v1/batch"), &origin).is_err());
+        assert!(build_request(&item("get", "/api/v1/health"), &origin).is_ok());
+    }
+
+    #[test]
+    fn test_build_request_forwards_authorization() {
+        let token = HeaderValue::from_static("Bearer abc");
+        let addr = SocketAddr::from(([10, 0, 0, 7], 5000));
+        let key = HeaderValue::from_static("qpk_0011aabb_secret");
+        let origin = BatchOrigin {
+            authorization: Some(token.clone()),
+            api_key: Some(key.clone()),
+            client_addr: Some(ConnectInfo(addr)),
+            ..BatchOrigin::default()
+        };
+        let request = build_request(&item("GET", "/api/v1/health"), &origin)
+            .expect("valid request");
+        assert_eq!(request.headers().get(header::AUTHORIZATION), Some(&token));
+        assert_eq!(request.headers().get(API_KEY_HEADER), Some(&key));
+        assert_eq!(
+            request.extensions().get::<ConnectInfo<SocketAddr>>().map(|c| c.0),
+            Some(addr)
+        );
+
+        // Without JWT auth the tenant and user come from headers
+        let tenant = HeaderValue::from_static("acme");
+        let user = HeaderValue::from_static("qa.lead@acme.test");
+        let local = BatchOrigin {
+            tenant: Some(tenant.clone()),
+            user_id: Some(user.clone()),
+            ..BatchOrigin::default()
+        };
+        let request = build_request(&item("POST", "/api/v1/workflows"), &local)
+            .expect("valid request");
+        assert_eq!(request.headers().get(TENANT_HEADER), Some(&tenant));
+        assert_eq!(request.headers().get(USER_ID_HEADER), Some(&user));
+        assert!(request.headers().get(header::AUTHORIZATION).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_execute_batch_preserves_order_and_status() {
+        let api = Router::new()
+            .route("/api/v1/ping", get(|| async { Json(serde_json::json!({ "pong": true })) }))
+            .route("/api/v1/text", get(|| async { "plain" }));
+
+        let responses = execute_batch(
+            api,
+            vec![
+                item("GET", "/api/v1/ping"),
+                item("GET", "/api/v1/missing"),
+                item("GET", "/api/v1/text"),
+                item("GET", "/elsewhere"),
+            ],
+            BatchOrigin::default(),
+        )
+        .await;
+
+        let statuses: Vec<u16> = responses.iter().map(|r| r.status).collect();
+        assert_eq!(statuses, vec![200, 404, 200, 400]);
+        assert_eq!(responses[0].id.as_deref(), Some("/api/v1/ping"));
+        assert_eq!(responses[0].body["pong"], true);
+        assert_eq!(responses[2].body, "plain");
+    }
+}
@@ -2,15 +2,36 @@
 //!
 //! Provides QA performance metrics, trends, and recent activity.
 //! Story 6.7: Updated to use real efficiency from time aggregates.
-
-use axum::{extract::Query, extract::State, routing::get, Json, Router};
-use chrono::{Duration, NaiveDate, Utc};
+//! KPIs are compared with the team's targets, managed under
+//! `/api/v1/dashboard/targets`. Only the caller's tenant counts and, with a
+//! `projectId`, only workflows for the project's tickets. Deleted workflows
+//! never count; archived ones still count toward the period they finished in
+//! but are left out of recent activity.
+
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    routing::{delete, get},
+    Extension, Json, Router,
+};
+use chrono::{NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
-use utoipa::ToSchema;
+use tracing::info;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+use validator::{Validate, ValidationError};
 
 use crate::app::AppState;
+use crate::audit::AuditChange;
+use crate::auth::CurrentUser;
+use crate::period::{period_boundaries, Period, PeriodBoundaries, PeriodQuery, Window};
+use crate::routes::projects::ProjectQuery;
+use crate::validation::{not_blank, one_of, ValidatedJson};
 use qa_pms_core::error::ApiError;
+use qa_pms_dashboard::{
+    Comparison, Kpi, KpiTarget, KpiTargetRepository, NewKpiTarget, DEFAULT_TEAM,
+};
 
 type ApiResult<T> = Result<T, ApiError>;
 
@@ -26,23 +47,17 @@ impl<T> SqlxResultExt<T> for Result<T, sqlx::Error> {
 
 /// Create the dashboard router.
 pub fn router() -> Router<AppState> {
-    Router::new().route("/api/v1/dashboard", get(get_dashboard))
-}
-
-/// Query parameters for dashboard data.
-#[derive(Debug, Deserialize)]
-pub struct DashboardQuery {
-    /// Period: 7d, 30d, 90d, 1y
-    #[serde(default = "default_period")]
-    pub period: String,
-}
-
-fn default_period() -> String {
-    "30d".to_string()
+    Router::new()
+        .route("/api/v1/dashboard", get(get_dashboard))
+        .route(
+            "/api/v1/dashboard/targets",
+            get(list_kpi_targets).put(put_kpi_target),
+        )
+        .route("/api/v1/dashboard/targets/:id", delete(delete_kpi_target))
 }
 
 /// Dashboard response with KPIs, trend, and activity.
-#[derive(Debug, Serialize, ToSchema)]
+#[derive(Debug, Serialize, ToSchema, async_graphql::SimpleObject)]
 pub struct DashboardResponse {
     pub kpis: DashboardKPIs,
     pub trend: Vec<TrendDataPoint>,
@@ -50,7 +65,7 @@ pub struct DashboardResponse {
 }
 
 /// KPI metrics for the dashboard.
-#[derive(Debug, Serialize, ToSchema)]
+#[derive(Debug, Serialize, ToSchema, async_graphql::SimpleObject)]
 pub struct DashboardKPIs {
     pub tickets_completed: KPIMetric,
     pub avg_time_per_ticket: KPIMetric,
@@ -58,16 +73,42 @@ pub struct DashboardKPIs {
     pub total_hours: KPIMetric,
 }
 
+impl DashboardKPIs {
+    fn metric_mut(&mut self, kpi: Kpi) -> &mut KPIMetric {
+        match kpi {
+            Kpi::TicketsCompleted => &mut self.tickets_completed,
+            Kpi::AvgTimePerTicket => &mut self.avg_time_per_ticket,
+            Kpi::Efficiency => &mut self.efficiency,
+            Kpi::TotalHours => &mut self.total_hours,
+        }
+    }
+
+    /// Set the target of each KPI the team has one for.
+    pub(crate) fn apply_targets(&mut self, targets: &[KpiTarget]) {
+        for target in targets {
+            let metric = self.metric_mut(target.kpi);
+            metric.target = Some(target.target_value);
+            metric.target_met = Some(target.is_met(metric.value));
+        }
+    }
+}
+
 /// Individual KPI metric with value, change, and trend.
-#[derive(Debug, Serialize, ToSchema)]
+#[derive(Debug, Serialize, ToSchema, async_graphql::SimpleObject)]
 pub struct KPIMetric {
     pub value: f64,
     pub change: f64,
     pub trend: String, // "up", "down", "neutral"
+    /// Team target, when one is set
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target: Option<f64>,
+    /// Whether the value meets the target
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target_met: Option<bool>,
 }
 
 /// Trend data point for charts.
-#[derive(Debug, Serialize, ToSchema)]
+#[derive(Debug, Serialize, ToSchema, async_graphql::SimpleObject)]
 pub struct TrendDataPoint {
     pub date: String,
     pub tickets: i32,
@@ -75,7 +116,7 @@ pub struct TrendDataPoint {
 }
 
 /// Recent activity item.
-#[derive(Debug, Serialize, ToSchema)]
+#[derive(Debug, Serialize, ToSchema, async_graphql::SimpleObject)]
 pub struct ActivityItem {
     pub id: String,
     #[serde(rename = "type")]
@@ -88,29 +129,64 @@ pub struct ActivityItem {
     pub duration: Option<i64>,
 }
 
+/// Team whose KPI targets apply.
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct TeamQuery {
+    /// Team name (default: "default")
+    pub team: Option<String>,
+}
+
+impl TeamQuery {
+    pub(crate) fn team(&self) -> &str {
+        self.team
+            .as_deref()
+            .map(str::trim)
+            .filter(|team| !team.is_empty())
+            .unwrap_or(DEFAULT_TEAM)
+    }
+}
+
 /// Get dashboard data.
 #[utoipa::path(
     get,
     path = "/api/v1/dashboard",
     params(
-        ("period" = String, Query, description = "Period: 7d, 30d, 90d, 1y")
+        ("period" = Option<String>, Query, description = "Period ending today: 7d, 30d, 90d, 1y (default: 30d)"),
+        ("from" = Option<String>, Query, description = "First day, YYYY-MM-DD; overrides period"),
+        ("to" = Option<String>, Query, description = "Last day, YYYY-MM-DD (default: today)"),
+        ("tz" = Option<String>, Query, description = "IANA timezone days are counted in (default: UTC)"),
+        ("team" = Option<String>, Query, description = "Team whose KPI targets are shown (default: default)"),
+        ProjectQuery
     ),
     responses(
         (status = 200, description = "Dashboard data", body = DashboardResponse),
+        (status = 400, description = "Invalid period"),
+        (status = 404, description = "Project not found"),
         (status = 500, description = "Internal server error")
     ),
     tag = "Dashboard"
 )]
 pub async fn get_dashboard(
     State(state): State<AppState>,
-    Query(query): Query<DashboardQuery>,
+    user: CurrentUser,
+    Query(query): Query<PeriodQuery>,
+    Query(team): Query<TeamQuery>,
+    Query(project): Query<ProjectQuery>,
 ) -> ApiResult<Json<DashboardResponse>> {
-    let days = parse_period(&query.period);
+    let period = query.resolve().map_err(ApiError::Validation)?;
+    let project = project.resolve(&state).await?;
+    let project = project.as_ref().map(|p| p.key.as_str());
+    let tenant = Some(user.tenant.as_str());
     let pool = &state.db;
 
-    let kpis = calculate_kpis(pool, days).await?;
-    let trend = get_trend_data(pool, days).await?;
-    let recent_activity = get_recent_activity(pool, 10).await?;
+    let mut kpis = calculate_kpis(pool, &period_boundaries(&period), tenant, project).await?;
+    let targets = KpiTargetRepository::new(pool.clone())
+        .list(Some(team.team()))
+        .await
+        .map_internal("Failed to load KPI targets")?;
+    kpis.apply_targets(&targets);
+    let trend = get_trend_data(pool, &period, tenant, project).await?;
+    let recent_activity = get_recent_activity(pool, 10, tenant, project).await?;
 
     Ok(Json(DashboardResponse {
         kpis,
@@ -119,67 +195,87 @@ pub async fn get_dashboard(
     }))
 }
 
-fn parse_period(period: &str) -> i64 {
-    match period {
-        "7d" => 7,
-        "30d" => 30,
-        "90d" => 90,
-        "1y" => 365,
-        _ => 30,
-    }
-}
-
-async fn calculate_kpis(pool: &PgPool, days: i64) -> Result<DashboardKPIs, ApiError> {
-    let now = Utc::now();
-    let period_start = now - Duration::days(days);
-    let prev_period_start = period_start - Duration::days(days);
-
+/// KPIs of the current window compared with the previous one, optionally
+/// limited to one tenant and one project key.
+pub(crate) async fn calculate_kpis(
+    pool: &PgPool,
+    bounds: &PeriodBoundaries,
+    tenant: Option<&str>,
+    project: Option<&str>,
+) -> Result<DashboardKPIs, ApiError> {
     // Current period metrics
-    let current = get_period_metrics(pool, period_start, now).await?;
+    let current = get_period_metrics(pool, &bounds.current, tenant, project).await?;
     // Previous period metrics for comparison
-    let previous = get_period_metrics(pool, prev_period_start, period_start).await?;
+    let previous = get_period_metrics(pool, &bounds.previous, tenant, project).await?;
 
     Ok(DashboardKPIs {
         tickets_completed: KPIMetric {
             value: current.tickets_completed as f64,
             change: calculate_change(current.tickets_completed as f64, previous.tickets_completed as f64),
             trend: calculate_trend(current.tickets_completed as f64, previous.tickets_completed as f64),
+            target: None,
+            target_met: None,
         },
         avg_time_per_ticket: KPIMetric {
             value: current.avg_time_seconds,
             change: calculate_change(current.avg_time_seconds, previous.avg_time_seconds),
             trend: calculate_trend(previous.avg_time_seconds, current.avg_time_seconds), // Inverted: lower is better
+            target: None,
+            target_met: None,
         },
         efficiency: KPIMetric {
             value: current.efficiency,
             change: calculate_change(current.efficiency, previous.efficiency),
             trend: calculate_trend(current.efficiency, previous.efficiency),
+            target: None,
+            target_met: None,
         },
         total_hours: KPIMetric {
             value: current.total_hours,
             change: calculate_change(current.total_hours, previous.total_hours),
             trend: calculate_trend(current.total_hours, previous.total_hours),
+            target: None,
+            target_met: None,
         },
     })
 }
 
-struct PeriodMetrics {
+pub(crate) struct PeriodMetrics {
     tickets_completed: i64,
     avg_time_seconds: f64,
     efficiency: f64,
     total_hours: f64,
 }
 
-async fn get_period_metrics(
+impl PeriodMetrics {
+    /// Value of a KPI, in the units the dashboard shows it in.
+    pub(crate) fn kpi_value(&self, kpi: Kpi) -> f64 {
+        match kpi {
+            Kpi::TicketsCompleted => self.tickets_completed as f64,
+            Kpi::AvgTimePerTicket => self.avg_time_seconds,
+            Kpi::Efficiency => self.efficiency,
+            Kpi::TotalHours => self.total_hours,
+        }
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub(crate) async fn get_period_metrics(
     pool: &PgPool,
-    start: chrono::DateTime<Utc>,
-    end: chrono::DateTime<Utc>,
+    window: &Window,
+    tenant: Option<&str>,
+    project: Option<&str>,
 ) -> Result<PeriodMetrics, ApiError> {
-    let start_date: NaiveDate = start.date_naive();
-    let end_date: NaiveDate = end.date_naive();
+    let (start, end) = (window.start, window.end);
 
+    // Daily aggregates are kept per UTC day, so they are selected by date.
+    // They are kept per user, so a tenant is selected through its users, but
+    // they span all projects, so a project is measured from its workflows.
     // Story 6.7: Try to get metrics from time_daily_aggregates first (more accurate)
-    let aggregate_stats: Option<(i64, Option<i64>, Option<i64>, Option<f64>)> = sqlx::query_as(
+    let aggregate_stats: Option<(i64, Option<i64>, Option<i64>, Option<f64>)> = if project.is_some() {
+        None
+    } else {
+        sqlx::query_as(
         r"
         SELECT 
             COALESCE(SUM(tickets_completed), 0) as tickets,
@@ -188,13 +284,16 @@ async fn get_period_metrics(
             AVG(efficiency_ratio)::FLOAT8 as avg_efficiency
         FROM time_daily_aggregates
         WHERE aggregate_date >= $1 AND aggregate_date < $2
+          AND ($3::TEXT IS NULL OR user_id IN (SELECT id FROM users WHERE tenant_id = $3))
         ",
-    )
-    .bind(start_date)
-    .bind(end_date)
-    .fetch_optional(pool)
-    .await
-    .map_internal("Failed to fetch aggregate stats")?;
+        )
+        .bind(window.first_day)
+        .bind(window.end_day)
+        .bind(tenant)
+        .fetch_optional(pool)
+        .await
+        .map_internal("Failed to fetch aggregate stats")?
+    };
 
     // If we have aggregate data, use it
     if let Some((tickets, total_time, total_estimated, avg_eff)) = aggregate_stats {
@@ -228,10 +327,15 @@ async fn get_period_metrics(
         WHERE status = 'completed'
           AND completed_at >= $1
           AND completed_at < $2
+          AND ($3::TEXT IS NULL OR SPLIT_PART(ticket_id, '-', 1) = $3)
+          AND ($4::TEXT IS NULL OR tenant_id = $4)
+          AND deleted_at IS NULL
         ",
     )
     .bind(start)
     .bind(end)
+    .bind(project)
+    .bind(tenant)
     .fetch_optional(pool)
     .await
     .map_internal("Failed to fetch workflow stats")?;
@@ -245,10 +349,17 @@ async fn get_period_metrics(
         FROM time_sessions
         WHERE ended_at >= $1
           AND ended_at < $2
+          AND ($3::TEXT IS NULL OR workflow_instance_id IN (
+              SELECT id FROM workflow_instances WHERE SPLIT_PART(ticket_id, '-', 1) = $3
+          ))
+          AND ($4::TEXT IS NULL OR tenant_id = $4)
+          AND workflow_instance_id IN (SELECT id FROM workflow_instances WHERE deleted_at IS NULL)
         ",
     )
     .bind(start)
     .bind(end)
+    .bind(project)
+    .bind(tenant)
     .fetch_optional(pool)
     .await
     .map_internal("Failed to fetch time stats")?;
@@ -267,10 +378,15 @@ async fn get_period_metrics(
         LEFT JOIN workflow_instances wi ON ts.workflow_instance_id = wi.id
         LEFT JOIN time_estimates te ON wi.template_id = te.template_id AND ts.step_index = te.step_index
         WHERE ts.ended_at >= $1 AND ts.ended_at < $2
+          AND ($3::TEXT IS NULL OR SPLIT_PART(wi.ticket_id, '-', 1) = $3)
+          AND ($4::TEXT IS NULL OR ts.tenant_id = $4)
+          AND wi.deleted_at IS NULL
         ",
     )
     .bind(start)
     .bind(end)
+    .bind(project)
+    .bind(tenant)
     .fetch_optional(pool)
     .await
     .map_internal("Failed to fetch efficiency data")?;
@@ -308,26 +424,37 @@ fn calculate_trend(current: f64, previous: f64) -> String {
     }
 }
 
-async fn get_trend_data(pool: &PgPool, days: i64) -> Result<Vec<TrendDataPoint>, ApiError> {
-    let now = Utc::now();
-    let start_date = now.date_naive() - chrono::Duration::days(days);
-
-    // Story 6.7: Try to get trend from time_daily_aggregates first
-    let aggregate_rows: Vec<(NaiveDate, i32, i32)> = sqlx::query_as(
-        r"
+pub(crate) async fn get_trend_data(
+    pool: &PgPool,
+    period: &Period,
+    tenant: Option<&str>,
+    project: Option<&str>,
+) -> Result<Vec<TrendDataPoint>, ApiError> {
+    let window = period_boundaries(period).current;
+
+    // Story 6.7: Try to get trend from time_daily_aggregates first (all projects only)
+    let aggregate_rows: Vec<(NaiveDate, i32, i32)> = if project.is_some() {
+        Vec::new()
+    } else {
+        sqlx::query_as(
+            r"
         SELECT 
             aggregate_date,
             tickets_completed,
             total_time_seconds
         FROM time_daily_aggregates
-        WHERE aggregate_date >= $1
+        WHERE aggregate_date >= $1 AND aggregate_date < $2
+          AND ($3::TEXT IS NULL OR user_id IN (SELECT id FROM users WHERE tenant_id = $3))
         ORDER BY aggregate_date
         ",
-    )
-    .bind(start_date)
-    .fetch_all(pool)
-    .await
-    .map_internal("Failed to fetch aggregate trend data")?;
+        )
+        .bind(window.first_day)
+        .bind(window.end_day)
+        .bind(tenant)
+        .fetch_all(pool)
+        .await
+        .map_internal("Failed to fetch aggregate trend data")?
+    };
 
     if !aggregate_rows.is_empty() {
         return Ok(aggregate_rows
@@ -340,22 +467,29 @@ async fn get_trend_data(pool: &PgPool, days: i64) -> Result<Vec<TrendDataPoint>,
             .collect());
     }
 
-    // Fallback: Query workflow_instances directly
-    let start = now - Duration::days(days);
+    // Fallback: Query workflow_instances directly, by day in the period's timezone
     let rows: Vec<(NaiveDate, i64, Option<f64>)> = sqlx::query_as(
         r"
         SELECT 
-            DATE(completed_at) as date,
+            DATE(completed_at AT TIME ZONE $3) as date,
             COUNT(*) as tickets,
             SUM(EXTRACT(EPOCH FROM (completed_at - started_at)) / 3600.0) as hours
         FROM workflow_instances
         WHERE status = 'completed'
           AND completed_at >= $1
-        GROUP BY DATE(completed_at)
+          AND completed_at < $2
+          AND ($4::TEXT IS NULL OR SPLIT_PART(ticket_id, '-', 1) = $4)
+          AND ($5::TEXT IS NULL OR tenant_id = $5)
+          AND deleted_at IS NULL
+        GROUP BY 1
         ORDER BY date
         ",
     )
-    .bind(start)
+    .bind(window.start)
+    .bind(window.end)
+    .bind(period.timezone_name())
+    .bind(project)
+    .bind(tenant)
     .fetch_all(pool)
     .await
     .map_internal("Failed to fetch trend data")?;
@@ -370,24 +504,36 @@ async fn get_trend_data(pool: &PgPool, days: i64) -> Result<Vec<TrendDataPoint>,
         .collect())
 }
 
-async fn get_recent_activity(pool: &PgPool, limit: i32) -> Result<Vec<ActivityItem>, ApiError> {
-    // Get recent completed workflows
+#[allow(clippy::type_complexity)]
+pub(crate) async fn get_recent_activity(
+    pool: &PgPool,
+    limit: i32,
+    tenant: Option<&str>,
+    project: Option<&str>,
+) -> Result<Vec<ActivityItem>, ApiError> {
+    // Get recent completed workflows that are still listed
     let workflows: Vec<(String, String, Option<String>, chrono::DateTime<Utc>, Option<i64>)> = sqlx::query_as(
         r"
         SELECT 
             wi.id::text,
             wt.name,
-            wi.ticket_key,
+            wi.ticket_id,
             wi.completed_at,
             EXTRACT(EPOCH FROM (wi.completed_at - wi.started_at))::bigint as duration
         FROM workflow_instances wi
         JOIN workflow_templates wt ON wi.template_id = wt.id
         WHERE wi.status = 'completed'
+          AND ($2::TEXT IS NULL OR SPLIT_PART(wi.ticket_id, '-', 1) = $2)
+          AND ($3::TEXT IS NULL OR wi.tenant_id = $3)
+          AND wi.archived_at IS NULL
+          AND wi.deleted_at IS NULL
         ORDER BY wi.completed_at DESC
         LIMIT $1
         ",
     )
     .bind(limit)
+    .bind(project)
+    .bind(tenant)
     .fetch_all(pool)
     .await
     .map_internal("Failed to fetch recent activity")?;
@@ -404,3 +550,235 @@ async fn get_recent_activity(pool: &PgPool, limit: i32) -> Result<Vec<ActivityIt
         })
         .collect())
 }
+
+// ============================================================================
+// KPI targets
+// ============================================================================
+
+/// Request to set a team's target for a KPI.
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct KpiTargetRequest {
+    /// Team name (default: "default")
+    #[validate(custom(function = "not_blank"), length(max = 100))]
+    pub team: Option<String>,
+    /// KPI: tickets_completed, avg_time_per_ticket (seconds), efficiency, total_hours
+    #[validate(custom(function = "validate_kpi"))]
+    pub kpi: String,
+    /// "below" (at most the target) or "above" (at least the target)
+    #[validate(custom(function = "validate_comparison"))]
+    pub comparison: String,
+    /// Target value, in the KPI's units
+    pub target: f64,
+    /// Consecutive days the target may be missed before an alert is raised
+    #[serde(default = "default_breach_days")]
+    #[validate(range(min = 1, max = 90))]
+    pub breach_days: i32,
+}
+
+const fn default_breach_days() -> i32 {
+    3
+}
+
+fn validate_kpi(kpi: &str) -> Result<(), ValidationError> {
+    one_of(kpi, &Kpi::ALL.map(Kpi::as_str))
+}
+
+fn validate_comparison(comparison: &str) -> Result<(), ValidationError> {
+    one_of(comparison, &[Comparison::Below.as_str(), Comparison::Above.as_str()])
+}
+
+impl KpiTargetRequest {
+    fn into_new_target(self) -> ApiResult<NewKpiTarget> {
+        let invalid = |field: &str| ApiError::Validation(format!("Invalid {field}"));
+        if !self.target.is_finite() {
+            return Err(invalid("target"));
+        }
+        Ok(NewKpiTarget {
+            team: self.team.map_or_else(|| DEFAULT_TEAM.to_string(), |t| t.trim().to_string()),
+            kpi: Kpi::parse(&self.kpi.to_ascii_lowercase()).ok_or_else(|| invalid("kpi"))?,
+            comparison: Comparison::parse(&self.comparison.to_ascii_lowercase())
+                .ok_or_else(|| invalid("comparison"))?,
+            target_value: self.target,
+            breach_days: self.breach_days,
+        })
+    }
+}
+
+/// A team's target for a KPI.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct KpiTargetResponse {
+    pub id: Uuid,
+    pub team: String,
+    pub kpi: String,
+    pub comparison: String,
+    pub target: f64,
+    pub breach_days: i32,
+    /// When the current breach was alerted
+    pub last_alerted_at: Option<String>,
+    pub updated_at: String,
+}
+
+impl From<KpiTarget> for KpiTargetResponse {
+    fn from(t: KpiTarget) -> Self {
+        Self {
+            id: t.id,
+            team: t.team,
+            kpi: t.kpi.to_string(),
+            comparison: t.comparison.as_str().to_string(),
+            target: t.target_value,
+            breach_days: t.breach_days,
+            last_alerted_at: t.last_alerted_at.map(|at| at.to_rfc3339()),
+            updated_at: t.updated_at.to_rfc3339(),
+        }
+    }
+}
+
+/// Query for listing KPI targets.
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct KpiTargetsQuery {
+    /// Only this team's targets
+    pub team: Option<String>,
+}
+
+/// List KPI targets.
+#[utoipa::path(
+    get,
+    path = "/api/v1/dashboard/targets",
+    params(KpiTargetsQuery),
+    responses(
+        (status = 200, description = "KPI targets", body = Vec<KpiTargetResponse>),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Dashboard"
+)]
+pub async fn list_kpi_targets(
+    State(state): State<AppState>,
+    Query(query): Query<KpiTargetsQuery>,
+) -> ApiResult<Json<Vec<KpiTargetResponse>>> {
+    let targets = KpiTargetRepository::new(state.db.clone())
+        .list(query.team.as_deref())
+        .await
+        .map_internal("Failed to load KPI targets")?;
+    Ok(Json(targets.into_iter().map(Into::into).collect()))
+}
+
+/// Set a team's target for a KPI, replacing any existing one.
+#[utoipa::path(
+    put,
+    path = "/api/v1/dashboard/targets",
+    request_body = KpiTargetRequest,
+    responses(
+        (status = 200, description = "Target saved", body = KpiTargetResponse),
+        (status = 400, description = "Invalid target"),
+        (status = 422, description = "Invalid request", body = qa_pms_core::error::ErrorResponse)
+    ),
+    tag = "Dashboard"
+)]
+pub async fn put_kpi_target(
+    State(state): State<AppState>,
+    ValidatedJson(request): ValidatedJson<KpiTargetRequest>,
+) -> ApiResult<(Extension<AuditChange>, Json<KpiTargetResponse>)> {
+    let new_target = request.into_new_target()?;
+    let targets = KpiTargetRepository::new(state.db.clone());
+    let before = targets
+        .list(Some(&new_target.team))
+        .await
+        .map_internal("Failed to fetch KPI targets")?
+        .into_iter()
+        .find(|target| target.kpi == new_target.kpi)
+        .map(KpiTargetResponse::from);
+    let target = targets
+        .upsert(&new_target)
+        .await
+        .map_internal("Failed to save KPI target")?;
+    info!(team = %target.team, kpi = %target.kpi, "Saved KPI target");
+    let target = KpiTargetResponse::from(target);
+    let change = AuditChange::new(target.id).before(&before).after(&target);
+    Ok((change.into_extension(), Json(target)))
+}
+
+/// Delete a KPI target.
+#[utoipa::path(
+    delete,
+    path = "/api/v1/dashboard/targets/{id}",
+    params(("id" = Uuid, Path, description = "Target ID")),
+    responses(
+        (status = 204, description = "Target deleted"),
+        (status = 404, description = "Target not found")
+    ),
+    tag = "Dashboard"
+)]
+pub async fn delete_kpi_target(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> ApiResult<(StatusCode, Extension<AuditChange>)> {
+    let targets = KpiTargetRepository::new(state.db.clone());
+    let before = targets
+        .list(None)
+        .await
+        .map_internal("Failed to fetch KPI targets")?
+        .into_iter()
+        .find(|target| target.id == id)
+        .map(KpiTargetResponse::from);
+    if !targets
+        .delete(id)
+        .await
+        .map_internal("Failed to delete KPI target")?
+    {
+        return Err(ApiError::NotFound(format!("KPI target {id}")));
+    }
+    let change = AuditChange::new(id).before(&before);
+    Ok((StatusCode::NO_CONTENT, change.into_extension()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metric(value: f64) -> KPIMetric {
+        KPIMetric {
+            value,
+            change: 0.0,
+            trend: "neutral".to_string(),
+            target: None,
+            target_met: None,
+        }
+    }
+
+    #[test]
+    fn test_apply_targets_marks_kpis_with_targets() {
+        let mut kpis = DashboardKPIs {
+            tickets_completed: metric(12.0),
+            avg_time_per_ticket: metric(9000.0),
+            efficiency: metric(1.1),
+            total_hours: metric(40.0),
+        };
+        let request = KpiTargetRequest {
+            team: None,
+            kpi: "avg_time_per_ticket".to_string(),
+            comparison: "Below".to_string(),
+            target: 7200.0,
+            breach_days: 3,
+        };
+        let new = request.into_new_target().unwrap();
+        let target = KpiTarget {
+            id: Uuid::new_v4(),
+            team: new.team,
+            kpi: new.kpi,
+            comparison: new.comparison,
+            target_value: new.target_value,
+            breach_days: new.breach_days,
+            last_alerted_at: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+        assert_eq!(target.team, DEFAULT_TEAM);
+
+        kpis.apply_targets(&[target]);
+        assert_eq!(kpis.avg_time_per_ticket.target, Some(7200.0));
+        assert_eq!(kpis.avg_time_per_ticket.target_met, Some(false));
+        assert_eq!(kpis.tickets_completed.target, None);
+    }
+}
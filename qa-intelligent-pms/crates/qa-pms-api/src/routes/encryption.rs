@@ -0,0 +1,59 @@
+//! Encryption key API endpoints.
+
+use axum::{
+    extract::{Query, State},
+    routing::post,
+    Json, Router,
+};
+use serde::Deserialize;
+use tracing::info;
+use utoipa::IntoParams;
+
+use qa_pms_core::error::ApiError;
+
+use crate::app::AppState;
+use crate::key_rotation::{self, RotationReport};
+
+type ApiResult<T> = Result<T, ApiError>;
+
+/// Create the encryption router.
+pub fn router() -> Router<AppState> {
+    Router::new().route("/api/v1/encryption/rotate-keys", post(rotate_keys))
+}
+
+/// Query parameters for a key rotation.
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[serde(rename_all = "camelCase", default)]
+pub struct RotateKeysQuery {
+    /// Count the secrets still sealed with a previous key without changing them
+    pub dry_run: bool,
+}
+
+/// Re-encrypt every stored secret with the current encryption key (admin
+/// only).
+///
+/// Run after moving the old key to `PREVIOUS_ENCRYPTION_KEYS`. Once no store
+/// reports rotated secrets in a dry run, the old key can be removed.
+#[utoipa::path(
+    post,
+    path = "/api/v1/encryption/rotate-keys",
+    params(RotateKeysQuery),
+    responses(
+        (status = 200, description = "Rotation report", body = RotationReport),
+        (status = 403, description = "Caller is not an admin"),
+        (status = 409, description = "A stored secret could not be rotated")
+    ),
+    tag = "Encryption"
+)]
+pub async fn rotate_keys(
+    State(state): State<AppState>,
+    Query(query): Query<RotateKeysQuery>,
+) -> ApiResult<Json<RotationReport>> {
+    let report = key_rotation::rotate_keys(&state, query.dry_run)
+        .await
+        .map_err(|e| ApiError::Conflict(format!("{e:#}")))?;
+
+    let rotated: usize = report.stores.iter().map(|store| store.rotated).sum();
+    info!(key_id = %report.key_id, dry_run = report.dry_run, rotated, "Encryption keys rotated");
+    Ok(Json(report))
+}
@@ -0,0 +1,37 @@
+//! GraphQL endpoint.
+//!
+//! Provides `/api/v1/graphql` for nested queries and
+//! `/api/v1/graphql/schema` for the SDL.
+
+use axum::{
+    extract::State,
+    routing::{get, post},
+    Extension, Json, Router,
+};
+
+use crate::app::AppState;
+use crate::auth::CurrentUser;
+use crate::graphql::{build_schema, AppSchema};
+
+/// GraphQL routes.
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/api/v1/graphql", post(graphql_handler))
+        .route("/api/v1/graphql/schema", get(graphql_sdl))
+        .layer(Extension(build_schema()))
+}
+
+/// Execute a GraphQL request against the application schema.
+async fn graphql_handler(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Extension(schema): Extension<AppSchema>,
+    Json(request): Json<async_graphql::Request>,
+) -> Json<async_graphql::Response> {
+    Json(schema.execute(request.data(state).data(user)).await)
+}
+
+/// Serve the schema definition language.
+async fn graphql_sdl(Extension(schema): Extension<AppSchema>) -> String {
+    schema.sdl()
+}
@@ -4,21 +4,24 @@
 //! - `/api/v1/health` - Overall application health
 //! - `/api/v1/health/integrations` - Integration-specific health status
 //! - `/api/v1/health/integrations/refresh` - Trigger manual health check
+//! - `/api/v1/integrations/{id}/health/history` - Uptime and downtime over a period
 
 use axum::{
-    extract::State,
+    extract::{Path, Query, State},
     http::StatusCode,
     routing::{get, post},
     Json, Router,
 };
 use chrono::{DateTime, Utc};
-use qa_pms_core::IntegrationHealth;
-use serde::Serialize;
+use qa_pms_core::error::ApiError;
+use qa_pms_core::{CircuitSnapshot, IntegrationHealth};
+use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
 use tracing::info;
 use utoipa::ToSchema;
 
 use crate::app::AppState;
+use crate::health_history::{bucket_for, parse_period, summarize, HealthSummary};
 
 /// Health check router.
 pub fn router() -> Router<AppState> {
@@ -29,6 +32,10 @@ pub fn router() -> Router<AppState> {
             "/api/v1/health/integrations/refresh",
             post(trigger_health_check),
         )
+        .route(
+            "/api/v1/integrations/:id/health/history",
+            get(get_health_history),
+        )
 }
 
 /// Health check response.
@@ -57,6 +64,9 @@ pub struct DatabaseStatus {
     /// Error message (if not connected)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error: Option<String>,
+    /// Failed reconnection attempts (in degraded mode)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reconnect_attempts: Option<u32>,
 }
 
 /// Health check endpoint.
@@ -97,11 +107,13 @@ async fn check_database(pool: &PgPool) -> DatabaseStatus {
             #[allow(clippy::cast_possible_truncation)]
             response_time_ms: Some(start.elapsed().as_millis() as u64),
             error: None,
+            reconnect_attempts: None,
         },
         Err(e) => DatabaseStatus {
             connected: false,
             response_time_ms: None,
             error: Some(e.to_string()),
+            reconnect_attempts: None,
         },
     }
 }
@@ -130,6 +142,9 @@ pub struct IntegrationHealthResponse {
     /// When downtime started (if offline)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub downtime_start: Option<DateTime<Utc>>,
+    /// Circuit breaker guarding requests to this integration
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub circuit: Option<CircuitSnapshot>,
 }
 
 impl From<IntegrationHealth> for IntegrationHealthResponse {
@@ -143,13 +158,15 @@ impl From<IntegrationHealth> for IntegrationHealthResponse {
             error_message: h.error_message,
             consecutive_failures: h.consecutive_failures,
             downtime_start: h.downtime_start,
+            circuit: None,
         }
     }
 }
 
 /// Get integration health status.
 ///
-/// Returns the health status of all configured integrations.
+/// Returns the health status of all configured integrations, with the state
+/// of the circuit breaker in front of each client.
 #[utoipa::path(
     get,
     path = "/api/v1/health/integrations",
@@ -162,7 +179,16 @@ pub async fn get_integration_health(
     State(state): State<AppState>,
 ) -> Json<Vec<IntegrationHealthResponse>> {
     let health = state.health_store.get_all().await;
-    let response: Vec<IntegrationHealthResponse> = health.into_iter().map(Into::into).collect();
+    let response: Vec<IntegrationHealthResponse> = health
+        .into_iter()
+        .map(|h| {
+            let circuit = state.circuit_breakers.get(&h.integration).map(|b| b.snapshot());
+            IntegrationHealthResponse {
+                circuit,
+                ..h.into()
+            }
+        })
+        .collect();
     Json(response)
 }
 
@@ -187,3 +213,79 @@ pub async fn trigger_health_check(State(_state): State<AppState>) -> StatusCode
     // The next scheduled check will run within 60 seconds.
     StatusCode::OK
 }
+
+/// Query parameters for health history.
+#[derive(Debug, Deserialize)]
+pub struct HealthHistoryQuery {
+    /// Period ending now, e.g. 24h, 7d or 2w
+    #[serde(default = "default_history_period")]
+    pub period: String,
+}
+
+fn default_history_period() -> String {
+    "7d".to_string()
+}
+
+/// Health history of one integration over a period.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct HealthHistoryResponse {
+    /// Integration name
+    pub integration: String,
+    /// Start of the period (capped by the retention policy)
+    pub period_start: DateTime<Utc>,
+    /// End of the period
+    pub period_end: DateTime<Utc>,
+    /// Uptime, response time trend and downtime windows
+    #[serde(flatten)]
+    pub summary: HealthSummary,
+}
+
+/// Get health check history for an integration.
+///
+/// Returns uptime percentage, mean response time per hour (periods up to
+/// two days) or per day, and the windows the integration was offline.
+#[utoipa::path(
+    get,
+    path = "/api/v1/integrations/{id}/health/history",
+    tag = "health",
+    params(
+        ("id" = String, Path, description = "Integration name (jira, postman, testmo)"),
+        ("period" = Option<String>, Query, description = "Period ending now: 24h, 7d, 2w (default 7d)"),
+    ),
+    responses(
+        (status = 200, description = "Health history", body = HealthHistoryResponse),
+        (status = 400, description = "Invalid period"),
+        (status = 404, description = "Integration not monitored"),
+    )
+)]
+pub async fn get_health_history(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Query(query): Query<HealthHistoryQuery>,
+) -> Result<Json<HealthHistoryResponse>, ApiError> {
+    let period = parse_period(&query.period).ok_or_else(|| {
+        ApiError::Validation(format!(
+            "Invalid period '{}': use a number followed by h, d or w",
+            query.period
+        ))
+    })?;
+    let history = &state.health_history;
+    let period_end = Utc::now();
+    let period_start = period_end - period.min(history.retention());
+
+    let samples = history
+        .samples(&id, period_start)
+        .await
+        .map_err(|e| ApiError::Internal(e.into()))?;
+    if samples.is_empty() && state.health_store.get(&id).await.is_none() {
+        return Err(ApiError::NotFound(format!("Integration not monitored: {id}")));
+    }
+
+    Ok(Json(HealthHistoryResponse {
+        integration: id,
+        period_start,
+        period_end,
+        summary: summarize(&samples, bucket_for(period), period_end),
+    }))
+}
@@ -0,0 +1,88 @@
+//! Database migration API endpoints.
+
+use axum::{
+    extract::{Query, State},
+    routing::{get, post},
+    Json, Router,
+};
+use serde::Deserialize;
+use tracing::info;
+use utoipa::IntoParams;
+
+use qa_pms_core::error::ApiError;
+
+use crate::app::AppState;
+use crate::migrations::{self, MigrationReport};
+
+type ApiResult<T> = Result<T, ApiError>;
+
+/// Create the migration router.
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/api/v1/admin/migrations", get(get_migrations))
+        .route("/api/v1/admin/migrations/run", post(run_migrations))
+}
+
+/// Query parameters for applying migrations.
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[serde(rename_all = "camelCase", default)]
+pub struct RunMigrationsQuery {
+    /// Report the pending migrations without applying them
+    pub dry_run: bool,
+}
+
+async fn status(state: &AppState) -> ApiResult<MigrationReport> {
+    migrations::status(&state.db)
+        .await
+        .map_err(|e| ApiError::Internal(anyhow::anyhow!("Failed to read migrations: {e}")))
+}
+
+/// List applied and pending migrations (admin only).
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/migrations",
+    responses(
+        (status = 200, description = "Migration status", body = MigrationReport),
+        (status = 403, description = "Caller is not an admin")
+    ),
+    tag = "Admin"
+)]
+pub async fn get_migrations(State(state): State<AppState>) -> ApiResult<Json<MigrationReport>> {
+    Ok(Json(status(&state).await?))
+}
+
+/// Apply pending migrations (admin only).
+///
+/// Refused while the applied migrations differ from this build's, as sqlx
+/// would stop at the first difference anyway.
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/migrations/run",
+    params(RunMigrationsQuery),
+    responses(
+        (status = 200, description = "Migration status afterwards", body = MigrationReport),
+        (status = 403, description = "Caller is not an admin"),
+        (status = 409, description = "Applied migrations have drifted")
+    ),
+    tag = "Admin"
+)]
+pub async fn run_migrations(
+    State(state): State<AppState>,
+    Query(query): Query<RunMigrationsQuery>,
+) -> ApiResult<Json<MigrationReport>> {
+    let before = status(&state).await?;
+    if before.drift {
+        return Err(ApiError::Conflict(
+            "Applied migrations differ from this build; see GET /api/v1/admin/migrations".into(),
+        ));
+    }
+    if query.dry_run || before.pending == 0 {
+        return Ok(Json(before));
+    }
+
+    let report = migrations::run(&state.db)
+        .await
+        .map_err(ApiError::Internal)?;
+    info!(applied = before.pending, "Database migrations applied");
+    Ok(Json(report))
+}
@@ -9,18 +9,36 @@ use crate::app::AppState;
 
 pub mod ai;
 pub mod alerts;
+pub mod api_keys;
+pub mod audit;
+pub mod auth;
+pub mod backup;
+pub mod batch;
 pub mod dashboard;
+pub mod encryption;
+pub mod graphql;
 pub mod health;
+pub mod migrations;
+pub mod notifications;
 pub mod pm_dashboard;
+pub mod postman;
+pub mod projects;
+pub mod report_templates;
 pub mod reports;
+pub mod saved_filters;
 pub mod search;
+pub mod search_history;
 pub mod setup;
 pub mod splunk;
 pub mod startup;
 pub mod support;
+pub mod tenant;
+pub mod test_cases;
 pub mod testmo;
+pub mod ticket_watch;
 pub mod tickets;
 pub mod time;
+pub mod webhooks;
 pub mod workflows;
 
 /// `OpenAPI` documentation.
@@ -38,40 +56,140 @@ pub mod workflows;
         alerts::get_unread_count,
         alerts::mark_read,
         alerts::dismiss_alert,
+        alerts::snooze_alert,
+        alerts::list_mute_rules,
+        alerts::create_mute_rule,
+        alerts::delete_mute_rule,
         alerts::get_patterns,
+        alerts::list_pattern_thresholds,
+        alerts::put_pattern_thresholds,
+        alerts::delete_pattern_thresholds,
         alerts::get_pattern,
+        auth::login,
+        auth::me,
+        auth::csrf_token,
+        auth::list_users,
+        auth::create_user,
+        auth::update_user,
+        api_keys::list_api_keys,
+        api_keys::create_api_key,
+        api_keys::rotate_api_key,
+        api_keys::revoke_api_key,
+        batch::execute_batch_handler,
         dashboard::get_dashboard,
+        dashboard::list_kpi_targets,
+        dashboard::put_kpi_target,
+        dashboard::delete_kpi_target,
         health::health_check,
         health::get_integration_health,
+        health::get_health_history,
         health::trigger_health_check,
+        notifications::notifications_ws,
+        notifications::test_digest,
+        webhooks::jira_webhook,
         setup::save_profile,
         setup::test_jira,
+        setup::start_jira_oauth,
+        setup::jira_oauth_callback,
         setup::test_postman,
         setup::test_testmo,
         setup::complete_setup,
         setup::get_status,
         tickets::list_tickets,
+        tickets::get_ticket_cache_stats,
+        tickets::validate_jql,
         tickets::get_ticket,
         tickets::get_transitions,
+        tickets::download_attachment,
         tickets::transition_ticket,
+        tickets::bulk_transition_tickets,
         startup::validate_startup,
+        startup::list_validations,
         search::contextual_search,
         search::search_postman_endpoint,
+        postman::run_collection,
+        postman::get_collection_run,
+        postman::diff_collection,
+        postman::generate_from_ticket,
+        postman::get_cache_status,
+        postman::refresh_cache,
+        postman::list_environments,
+        postman::get_environment,
+        postman::create_environment,
+        postman::update_environment,
         search::search_testmo_endpoint,
         search::search_all,
+        search::semantic_search,
+        ticket_watch::list_watches,
+        ticket_watch::watch_ticket,
+        ticket_watch::unwatch_ticket,
+        saved_filters::list_filters,
+        saved_filters::create_filter,
+        saved_filters::get_filter,
+        saved_filters::update_filter,
+        saved_filters::delete_filter,
+        saved_filters::set_default,
+        saved_filters::unset_default,
+        search_history::list_history,
+        search_history::clear_history,
+        search_history::list_saved,
+        search_history::create_saved,
+        search_history::delete_saved,
+        search_history::run_saved,
+        backup::export_backup,
+        backup::restore_backup,
+        projects::list_projects,
+        projects::get_project,
+        projects::create_project,
+        projects::update_project,
+        projects::delete_project,
+        tenant::list_credentials,
+        tenant::set_credential,
+        tenant::delete_credential,
+        encryption::rotate_keys,
+        migrations::get_migrations,
+        migrations::run_migrations,
+        audit::list_audit_entries,
+        test_cases::list_test_cases,
+        test_cases::get_test_case,
+        test_cases::create_test_case,
+        test_cases::update_test_case,
+        test_cases::delete_test_case,
+        test_cases::list_duplicates,
+        test_cases::merge_test_cases,
+        testmo::list_milestones,
+        testmo::create_milestone,
         testmo::create_test_run,
+        testmo::submit_results,
+        testmo::create_automation_run,
+        testmo::submit_automation_results,
+        testmo::bulk_create_cases,
+        testmo::sync_test_cases,
         workflows::list_templates,
         workflows::get_template_by_id,
+        workflows::get_calibrated_estimates,
+        workflows::create_template,
+        workflows::update_template,
+        workflows::delete_template,
+        workflows::list_template_versions,
+        workflows::get_template_version_by_number,
+        workflows::diff_template_versions,
+        workflows::list_workflows,
         workflows::create_workflow,
         workflows::get_workflow,
         workflows::get_active_workflow_for_ticket,
         workflows::complete_step,
         workflows::skip_step,
+        workflows::spawn_sub_workflow,
         workflows::pause_workflow,
         workflows::resume_workflow,
         workflows::complete_workflow,
         workflows::get_workflow_summary,
         workflows::cancel_workflow,
+        workflows::archive_workflow,
+        workflows::delete_workflow,
+        workflows::restore_workflow,
+        workflows::list_archived_workflows,
         workflows::get_user_active_workflows,
         time::start_time_session,
         time::end_time_session,
@@ -79,17 +197,31 @@ pub mod workflows;
         time::resume_time_session,
         time::get_active_time_session,
         time::get_all_time_sessions,
+        time::record_heartbeat,
         // Story 6.7: Historical time data
         time::get_historical_stats,
         time::get_time_trend,
         time::get_averages,
         time::get_gap_alerts,
         time::dismiss_alert,
+        time::export_time,
         reports::generate_report,
         reports::get_report,
+        reports::export_report,
+        reports::verify_report,
+        reports::compare_reports,
+        reports::aggregate_report,
+        reports::export_aggregate_report,
         reports::get_report_by_workflow,
+        report_templates::list_templates,
+        report_templates::get_template,
+        report_templates::create_template,
+        report_templates::update_template,
+        report_templates::delete_template,
         pm_dashboard::get_pm_dashboard,
         pm_dashboard::export_pm_dashboard,
+        pm_dashboard::get_team_capacity,
+        pm_dashboard::list_pm_exports,
         // Epic 11: Splunk
         splunk::list_templates,
         splunk::get_template,
@@ -106,10 +238,13 @@ pub mod workflows;
         support::get_error_log,
         support::update_error_status,
         support::get_suggestions,
+        support::promote_error_to_kb,
         support::get_dashboard_summary,
         support::run_all_diagnostics,
         support::run_diagnostic,
+        support::export_support_bundle,
         support::list_kb_entries,
+        support::search_kb_entries,
         support::create_kb_entry,
         support::get_kb_entry,
         support::update_kb_entry,
@@ -118,30 +253,52 @@ pub mod workflows;
         // Epic 13: AI
         ai::get_ai_status,
         ai::get_providers,
+        ai::list_models,
         ai::configure_ai,
         ai::test_connection,
         ai::disable_ai,
         ai::chat,
+        ai::chat_ws,
         ai::get_chat_suggestions,
         ai::semantic_search,
         ai::analyze_gherkin,
+        ai::get_usage,
     ),
     components(
         schemas(
             health::HealthResponse,
+            batch::BatchRequest,
+            batch::BatchItem,
+            batch::BatchResponse,
+            batch::BatchItemResponse,
             health::DatabaseStatus,
             health::IntegrationHealthResponse,
+            qa_pms_core::CircuitSnapshot,
+            health::HealthHistoryResponse,
+            crate::health_history::HealthSummary,
+            crate::health_history::ResponseTimeBucket,
+            crate::health_history::DowntimeWindow,
+            qa_pms_core::CircuitState,
+            crate::notifications::Notification,
+            crate::notifications::NotificationKind,
+            notifications::DigestTestRequest,
+            notifications::DigestPreviewResponse,
+            webhooks::WebhookResponse,
             setup::ProfileRequest,
             setup::JiraTestRequest,
             setup::PostmanTestRequest,
             setup::TestmoTestRequest,
             setup::SplunkConfigRequest,
             setup::ConnectionTestResponse,
+            setup::OAuthStartResponse,
             setup::CompleteSetupRequest,
             setup::CompleteSetupResponse,
             setup::SetupStatusResponse,
             setup::SuccessResponse,
             tickets::TicketListResponse,
+            tickets::TicketCacheStats,
+            tickets::JqlValidationRequest,
+            tickets::JqlValidationResponse,
             tickets::TicketSummary,
             tickets::TicketDetailResponse,
             tickets::UserInfo,
@@ -150,20 +307,116 @@ pub mod workflows;
             tickets::TransitionInfo,
             tickets::TransitionRequest,
             tickets::TransitionResponse,
+            tickets::BulkTransitionRequest,
+            tickets::BulkTransitionItem,
+            tickets::BulkTransitionResponse,
             qa_pms_core::error::ErrorResponse,
+            qa_pms_core::error::FieldViolation,
+            qa_pms_core::types::CursorInfo,
+            qa_pms_core::types::SortDirection,
             crate::startup::ValidationResult,
             crate::startup::StartupValidationReport,
+            crate::startup_history::StartupValidationRun,
+            crate::startup_history::ValidationTrigger,
+            startup::ValidationHistoryResponse,
             search::ContextualSearchRequest,
             search::KeywordSearchRequest,
             search::UnifiedSearchResult,
             search::SearchResponse,
             search::SingleSourceSearchResponse,
+            search::SearchQuery,
+            search::VectorSearchRequest,
+            search::VectorSearchResponse,
+            crate::semantic_index::SemanticSource,
+            crate::search_facets::SearchFilters,
+            crate::search_facets::SearchFacets,
+            crate::search_facets::FacetCount,
+            crate::search_facets::DateRangeFacet,
+            saved_filters::SavedFilterRequest,
+            crate::saved_filters::SavedFilter,
+            crate::ticket_watch::TicketWatch,
+            search_history::SearchHistoryEntry,
+            search_history::SavedSearchRequest,
+            search_history::SavedSearchResponse,
+            crate::backup::BackupArchive,
+            crate::backup::RestoreReport,
+            crate::backup::TableRestore,
+            projects::ProjectRequest,
+            tenant::TenantCredentialsResponse,
+            tenant::SetCredentialRequest,
+            crate::key_rotation::RotationReport,
+            crate::key_rotation::StoreRotation,
+            crate::migrations::MigrationReport,
+            crate::migrations::MigrationStatus,
+            crate::migrations::MigrationState,
+            crate::audit::AuditEntry,
+            audit::AuditLogResponse,
+            qa_pms_core::Project,
+            test_cases::TestCaseRequest,
+            test_cases::TestCaseStepRequest,
+            test_cases::TestCasesResponse,
+            test_cases::MergeTestCasesRequest,
+            test_cases::DuplicateCaseResponse,
+            test_cases::DuplicatePairResponse,
+            qa_pms_core::TestCase,
+            qa_pms_core::TestCaseStep,
+            qa_pms_core::RemoteLink,
+            qa_pms_core::MergedCase,
             testmo::CreateTestRunRequest,
             testmo::CreateTestRunResponse,
+            testmo::CreateMilestoneRequest,
+            testmo::MilestoneResponse,
+            testmo::SubmitResultsRequest,
+            testmo::TestResultRequest,
+            testmo::ResultAttachmentRequest,
+            testmo::SubmitResultsResponse,
+            testmo::CreateAutomationRunRequest,
+            testmo::AutomationRunResponse,
+            testmo::AutomationResultsRequest,
+            testmo::AutomationTestRequest,
+            testmo::AutomationResultsResponse,
+            testmo::BulkCreateCasesRequest,
+            testmo::NewTestCaseRequest,
+            testmo::NewTestStepRequest,
+            testmo::CreatedCaseResponse,
+            testmo::FailedCaseResponse,
+            testmo::BulkCreateCasesResponse,
+            testmo::SyncTestCasesRequest,
+            testmo::SyncConflictResponse,
+            testmo::SyncTestCasesResponse,
             testmo::ErrorResponse,
+            postman::RunCollectionRequest,
+            postman::AssertionResponse,
+            postman::RequestExecutionResponse,
+            postman::CollectionRunResponse,
+            postman::DiffRequestResponse,
+            postman::FieldChangeResponse,
+            postman::ChangedRequestResponse,
+            postman::CollectionDiffResponse,
+            postman::GenerateFromTicketRequest,
+            postman::GeneratedEndpointResponse,
+            postman::GeneratedCollectionResponse,
+            postman::CacheStatusResponse,
+            postman::CacheRefreshResponse,
+            postman::EnvironmentSummaryResponse,
+            postman::EnvironmentVariableDto,
+            postman::EnvironmentResponse,
+            postman::EnvironmentRequest,
+            postman::EnvironmentRefResponse,
             workflows::TemplatesListResponse,
             workflows::TemplateResponse,
             workflows::TemplateDetailResponse,
+            workflows::CalibratedEstimatesResponse,
+            workflows::CalibratedStepResponse,
+            workflows::TemplateRequest,
+            workflows::TemplateStepRequest,
+            workflows::StepBranchRule,
+            workflows::BranchConditionRule,
+            workflows::TemplateVersionResponse,
+            workflows::TemplateVersionsResponse,
+            workflows::TemplateDiffResponse,
+            workflows::FieldChangeResponse,
+            workflows::StepDiffResponse,
             workflows::StepResponse,
             workflows::CreateWorkflowRequest,
             workflows::CreateWorkflowResponse,
@@ -171,15 +424,23 @@ pub mod workflows;
             workflows::WorkflowStepWithStatus,
             workflows::ActiveWorkflowResponse,
             workflows::WorkflowSummary,
+            workflows::WorkflowListItem,
+            workflows::WorkflowListResponse,
             workflows::CompleteStepRequest,
             workflows::StepLinkRequest,
             workflows::StepActionResponse,
             workflows::WorkflowStatusResponse,
             workflows::WorkflowSummaryResponse,
             workflows::StepSummary,
+            workflows::TestRunSummary,
             workflows::UserActiveWorkflowsResponse,
+            workflows::CreateSubWorkflowRequest,
+            workflows::SubWorkflowResponse,
+        time::HeartbeatRequest,
         time::TimeSessionResponse,
         time::TimeSessionsResponse,
+        time::TimeSummaryResponse,
+        time::StepTimeResponse,
         // Story 6.7: Historical time data schemas
         time::HistoricalStatsResponse,
         time::TicketTypeStats,
@@ -193,22 +454,66 @@ pub mod workflows;
         reports::ReportResponse,
         reports::ReportContent,
         reports::ReportStep,
+        reports::ReportLink,
+        reports::JiraPublishMode,
+        reports::PublishToJira,
+        reports::JiraPublication,
+        crate::report_integrity::ReportVerification,
+        crate::report_compare::ReportComparison,
+        crate::report_compare::StepComparison,
+        crate::report_aggregate::AggregateReport,
+        crate::report_aggregate::AggregateTotals,
+        crate::report_aggregate::SlowStep,
+        crate::report_aggregate::WorkflowSummary,
+        crate::report_export::ExportFormat,
+        report_templates::ReportSection,
+        report_templates::ReportBranding,
+        report_templates::ReportCustomField,
+        report_templates::ReportLayout,
+        report_templates::ReportTemplateRequest,
+        report_templates::ReportTemplateResponse,
         dashboard::DashboardResponse,
         dashboard::DashboardKPIs,
         dashboard::KPIMetric,
         dashboard::TrendDataPoint,
         dashboard::ActivityItem,
+        dashboard::KpiTargetRequest,
+        dashboard::KpiTargetResponse,
         alerts::AlertResponse,
         alerts::AlertsResponse,
         alerts::UnreadCountResponse,
+        alerts::MuteRuleRequest,
+        alerts::SnoozeRequest,
+        alerts::MuteRuleResponse,
+        alerts::MuteRulesResponse,
         alerts::PatternResponse,
         alerts::PatternsResponse,
+        alerts::PatternThresholdsRequest,
+        alerts::PatternThresholdsResponse,
+        alerts::TeamThresholdsResponse,
+        alerts::PatternThresholdsListResponse,
+        crate::auth::Role,
+        auth::LoginRequest,
+        auth::LoginResponse,
+        auth::CurrentUserResponse,
+        auth::CsrfTokenResponse,
+        auth::UserResponse,
+        auth::CreateUserRequest,
+        auth::UpdateUserRequest,
+        crate::api_keys::ApiKey,
+        crate::api_keys::ApiKeyScope,
+        api_keys::CreateApiKeyRequest,
+        api_keys::IssuedApiKey,
         pm_dashboard::PMDashboardResponse,
         pm_dashboard::PMSummary,
         pm_dashboard::BugsMetrics,
         pm_dashboard::EconomyMetrics,
         pm_dashboard::ComponentHealth,
         pm_dashboard::ProblematicEndpoint,
+        pm_dashboard::CapacityResponse,
+        pm_dashboard::UserCapacityResponse,
+        pm_dashboard::InFlightWorkflowResponse,
+        crate::pm_export::PmExportRecord,
         // Epic 11: Splunk schemas
         splunk::TemplateResponse,
         splunk::TemplatesListResponse,
@@ -228,9 +533,12 @@ pub mod workflows;
         support::CreateErrorRequest,
         support::UpdateStatusRequest,
         support::SuggestionsResponse,
+        support::PromoteToKbRequest,
+        support::PromoteToKbResponse,
         support::DashboardSummaryResponse,
         support::DiagnosticsResponse,
         support::KbEntriesResponse,
+        support::KbSearchResponse,
         support::CreateKbRequest,
         support::UpdateKbRequest,
         support::RateKbRequest,
@@ -239,6 +547,9 @@ pub mod workflows;
         qa_pms_support::ErrorSeverity,
         qa_pms_support::ErrorSource,
         qa_pms_support::KnowledgeBaseEntry,
+        qa_pms_support::KbSearchResult,
+        qa_pms_support::KbDraft,
+        qa_pms_support::CreateKbEntryInput,
         qa_pms_support::TroubleshootingSuggestion,
         qa_pms_support::SuggestionSource,
         qa_pms_support::DiagnosticResult,
@@ -249,6 +560,8 @@ pub mod workflows;
         // Epic 13: AI schemas
         ai::AIStatusResponse,
         ai::ProvidersResponse,
+        ai::ListModelsRequest,
+        ai::ModelsResponse,
         ai::ConfigureAIRequest,
         ai::ChatRequest,
         ai::ChatMessageDto,
@@ -257,6 +570,7 @@ pub mod workflows;
         ai::WorkflowStepContextDto,
         ai::ChatResponseDto,
         ai::TokenUsageDto,
+        ai::ChatStreamMessage,
         ai::SuggestionsRequest,
         ai::SuggestionsResponse,
         ai::SemanticSearchRequest,
@@ -268,23 +582,40 @@ pub mod workflows;
         qa_pms_ai::ModelInfo,
         qa_pms_ai::ConnectionTestResult,
         qa_pms_ai::ProviderType,
+        qa_pms_ai::UsageReport,
+        qa_pms_ai::usage::UsageTotals,
+        qa_pms_ai::usage::DailyUsage,
+        qa_pms_ai::usage::BudgetReport,
+        qa_pms_ai::BudgetStatus,
         )
     ),
     tags(
         (name = "Alerts", description = "Alert and pattern detection endpoints"),
+        (name = "Auth", description = "Sign-in and user management"),
+        (name = "Backup", description = "Workspace backup and restore"),
+        (name = "Batch", description = "Batched API requests"),
         (name = "Dashboard", description = "Dashboard metrics endpoints"),
         (name = "health", description = "Health check endpoints"),
+        (name = "Notifications", description = "Real-time notification stream"),
         (name = "Setup", description = "Setup wizard endpoints"),
         (name = "Tickets", description = "Ticket management endpoints"),
         (name = "Startup", description = "Startup validation endpoints"),
         (name = "Search", description = "Contextual search endpoints"),
+        (name = "Test Cases", description = "Local test case endpoints"),
         (name = "testmo", description = "Testmo integration endpoints"),
+        (name = "Postman", description = "Postman collection run endpoints"),
+        (name = "Projects", description = "Project registration and scoping"),
+        (name = "Tenant", description = "Per-tenant credential storage"),
+        (name = "Encryption", description = "Encryption key rotation"),
+        (name = "Admin", description = "Database administration"),
+        (name = "Audit", description = "Audit log of changes made through the API"),
         (name = "Workflows", description = "Workflow template endpoints"),
         (name = "Time Tracking", description = "Time tracking endpoints"),
         (name = "Reports", description = "Report generation endpoints"),
         (name = "PM Dashboard", description = "PM observability dashboard endpoints"),
         (name = "Splunk", description = "Splunk query template and log endpoints"),
         (name = "Support", description = "Support portal and troubleshooting endpoints"),
+        (name = "Webhooks", description = "Inbound webhooks from integrations"),
         (name = "AI", description = "AI companion endpoints (BYOK)")
     )
 )]
@@ -299,6 +630,6 @@ pub fn api_docs() -> Router<AppState> {
 }
 
 /// Serve `OpenAPI` JSON specification.
-async fn openapi_json() -> Json<utoipa::openapi::OpenApi> {
+pub(crate) async fn openapi_json() -> Json<utoipa::openapi::OpenApi> {
     Json(ApiDoc::openapi())
 }
@@ -0,0 +1,243 @@
+//! Real-time notification endpoint.
+//!
+//! One WebSocket per client replaces polling of the alerts, workflow, time
+//! and health endpoints. Messages are JSON-encoded `Notification`s. The
+//! daily digest email can also be previewed or sent from here.
+
+use axum::{
+    extract::{
+        ws::{Message, WebSocket, WebSocketUpgrade},
+        Query, State,
+    },
+    response::Response,
+    routing::{get, post},
+    Json, Router,
+};
+use qa_pms_core::error::ApiError;
+use qa_pms_core::TenantId;
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast::error::RecvError;
+use tracing::{debug, warn};
+use utoipa::{IntoParams, ToSchema};
+
+use crate::app::AppState;
+use crate::auth::CurrentUser;
+use crate::digest::Digest;
+use crate::mailer::Mailer;
+use crate::notifications::{Notification, NotificationKind};
+
+/// Create the notifications router.
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/api/v1/notifications/ws", get(notifications_ws))
+        .route("/api/v1/notifications/digest/test", post(test_digest))
+}
+
+/// Query parameters for the notification socket.
+#[derive(Debug, Deserialize, IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub struct NotificationsQuery {
+    /// User to receive personal notifications for; the signed-in user when
+    /// authentication is enabled
+    pub user_id: Option<String>,
+    /// Comma-separated kinds to receive (alert, workflow, time_warning, health)
+    #[param(example = "alert,workflow")]
+    pub topics: Option<String>,
+}
+
+impl NotificationsQuery {
+    fn topics(&self) -> Vec<NotificationKind> {
+        self.topics
+            .as_deref()
+            .map(|t| t.split(',').filter_map(NotificationKind::parse).collect())
+            .unwrap_or_default()
+    }
+}
+
+/// Open a notification WebSocket.
+///
+/// Browsers cannot set headers on the upgrade, so the token may be passed as
+/// an `access_token` query parameter instead.
+#[utoipa::path(
+    get,
+    path = "/api/v1/notifications/ws",
+    params(NotificationsQuery),
+    responses(
+        (status = 101, description = "Switching to WebSocket; messages are Notification JSON"),
+    ),
+    tag = "Notifications"
+)]
+pub async fn notifications_ws(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Query(query): Query<NotificationsQuery>,
+    ws: WebSocketUpgrade,
+) -> Response {
+    let topics = query.topics();
+    let user_id = if user.is_local() {
+        query.user_id.or(user.claimed_id)
+    } else {
+        Some(user.id.to_string())
+    };
+    ws.on_upgrade(move |socket| handle_socket(socket, state, user.tenant, user_id, topics))
+}
+
+async fn handle_socket(
+    mut socket: WebSocket,
+    state: AppState,
+    tenant: TenantId,
+    user_id: Option<String>,
+    topics: Vec<NotificationKind>,
+) {
+    let mut rx = state.notifications.subscribe();
+    debug!(user_id = ?user_id, subscribers = state.notifications.subscriber_count(), "Notification client connected");
+
+    loop {
+        tokio::select! {
+            received = rx.recv() => {
+                let notification = match received {
+                    Ok(n) => n,
+                    Err(RecvError::Lagged(skipped)) => {
+                        warn!(skipped, "Notification client lagging, events dropped");
+                        continue;
+                    }
+                    Err(RecvError::Closed) => break,
+                };
+                if !notification.is_visible_to(&tenant, user_id.as_deref(), &topics) {
+                    continue;
+                }
+                if send_notification(&mut socket, &notification).await.is_err() {
+                    break;
+                }
+            }
+            incoming = socket.recv() => {
+                match incoming {
+                    Some(Ok(Message::Close(_)) | Err(_)) | None => break,
+                    // Pings are answered automatically; clients have nothing else to send
+                    Some(Ok(_)) => {}
+                }
+            }
+        }
+    }
+
+    debug!(user_id = ?user_id, "Notification client disconnected");
+}
+
+async fn send_notification(socket: &mut WebSocket, notification: &Notification) -> Result<(), axum::Error> {
+    let text = serde_json::to_string(notification).unwrap_or_default();
+    socket.send(Message::Text(text)).await
+}
+
+/// Request to preview or send the digest.
+#[derive(Debug, Default, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase", default)]
+pub struct DigestTestRequest {
+    /// Mail the digest instead of only rendering it
+    pub send: bool,
+    /// Recipients for this send; defaults to `DIGEST_RECIPIENTS`
+    pub recipients: Vec<String>,
+}
+
+/// Rendered digest.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DigestPreviewResponse {
+    /// Email subject
+    pub subject: String,
+    /// Plain-text body
+    pub text: String,
+    /// HTML body
+    pub html: String,
+    /// Whether the digest was mailed
+    pub sent: bool,
+    /// Addresses it was mailed to
+    pub recipients: Vec<String>,
+}
+
+/// Preview the daily digest, or send it now.
+#[utoipa::path(
+    post,
+    path = "/api/v1/notifications/digest/test",
+    request_body = DigestTestRequest,
+    responses(
+        (status = 200, description = "Digest rendered, and sent if requested", body = DigestPreviewResponse),
+        (status = 400, description = "No recipients to send to"),
+        (status = 502, description = "Mail server rejected the digest"),
+        (status = 503, description = "SMTP not configured")
+    ),
+    tag = "Notifications"
+)]
+pub async fn test_digest(
+    State(state): State<AppState>,
+    Json(request): Json<DigestTestRequest>,
+) -> Result<Json<DigestPreviewResponse>, ApiError> {
+    let digest = Digest::load(&state.db, &state.health_store).await?;
+    let mut preview = DigestPreviewResponse {
+        subject: digest.subject(),
+        text: digest.render_text(),
+        html: digest.render_html(),
+        sent: false,
+        recipients: Vec::new(),
+    };
+    if !request.send {
+        return Ok(Json(preview));
+    }
+
+    let smtp = state
+        .settings
+        .smtp
+        .as_ref()
+        .ok_or_else(|| ApiError::ServiceUnavailable("SMTP is not configured".to_string()))?;
+    let recipients = if request.recipients.is_empty() {
+        state
+            .settings
+            .digest
+            .as_ref()
+            .map(|digest| digest.recipients.clone())
+            .unwrap_or_default()
+    } else {
+        request.recipients
+    };
+    if recipients.is_empty() {
+        return Err(ApiError::Validation(
+            "No recipients given and DIGEST_RECIPIENTS is not set".to_string(),
+        ));
+    }
+
+    let mailer = Mailer::from_settings(smtp)?;
+    mailer
+        .send(
+            &recipients,
+            &preview.subject,
+            preview.text.clone(),
+            preview.html.clone(),
+        )
+        .await
+        .map_err(|e| ApiError::ExternalService(format!("{e:#}")))?;
+    preview.sent = true;
+    preview.recipients = recipients;
+    Ok(Json(preview))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_topics_parsing_ignores_unknown() {
+        let query = NotificationsQuery {
+            user_id: None,
+            topics: Some("alert, health,unknown".to_string()),
+        };
+        assert_eq!(
+            query.topics(),
+            vec![NotificationKind::Alert, NotificationKind::Health]
+        );
+
+        let all = NotificationsQuery {
+            user_id: None,
+            topics: None,
+        };
+        assert!(all.topics().is_empty());
+    }
+}
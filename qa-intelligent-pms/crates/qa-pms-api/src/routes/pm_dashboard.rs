@@ -5,21 +5,34 @@
 //! - Economy metrics (hours saved, cost savings)
 //! - Component health visualization
 //! - Problematic endpoints tracking
-//! - Dashboard export
+//! - Dashboard export, on demand and scheduled (see `pm_export`)
+//! - Team capacity
+//!
+//! Only workflows of the caller's tenant that have not been deleted count.
+//! The dashboard and its export can be limited to one project's tickets with
+//! `projectId`.
 
 use axum::{
     extract::{Query, State},
+    http::header,
+    response::{IntoResponse, Response},
     routing::get,
     Json, Router,
 };
-use chrono::{Duration, NaiveDate, Utc};
+use chrono::{NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
 use std::collections::HashMap;
-use utoipa::ToSchema;
+use utoipa::{IntoParams, ToSchema};
 
 use crate::app::AppState;
+use crate::auth::CurrentUser;
+use crate::period::{period_boundaries, Period, PeriodQuery};
+use crate::pm_export::{list_exports, PmExportRecord};
+use crate::routes::projects::ProjectQuery;
+use qa_pms_config::settings::PmExportFormat;
 use qa_pms_core::error::ApiError;
+use qa_pms_dashboard::{load_capacity, CapacityReport, InFlightWorkflow, UserCapacity};
 
 type ApiResult<T> = Result<T, ApiError>;
 
@@ -38,19 +51,8 @@ pub fn router() -> Router<AppState> {
     Router::new()
         .route("/api/v1/pm-dashboard", get(get_pm_dashboard))
         .route("/api/v1/pm-dashboard/export", get(export_pm_dashboard))
-}
-
-/// Query parameters for PM dashboard.
-#[derive(Debug, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct PMDashboardQuery {
-    /// Period: 7d, 30d, 90d, 1y
-    #[serde(default = "default_period")]
-    pub period: String,
-}
-
-fn default_period() -> String {
-    "30d".to_string()
+        .route("/api/v1/pm-dashboard/exports", get(list_pm_exports))
+        .route("/api/v1/pm-dashboard/capacity", get(get_team_capacity))
 }
 
 /// PM Dashboard response.
@@ -63,6 +65,9 @@ pub struct PMDashboardResponse {
     pub component_health: Vec<ComponentHealth>,
     pub problematic_endpoints: Vec<ProblematicEndpoint>,
     pub period: String,
+    /// Project key the dashboard is limited to
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project: Option<String>,
     pub generated_at: String,
 }
 
@@ -139,66 +144,146 @@ pub struct ProblematicEndpoint {
     get,
     path = "/api/v1/pm-dashboard",
     params(
-        ("period" = String, Query, description = "Period: 7d, 30d, 90d, 1y")
+        ("period" = Option<String>, Query, description = "Period ending today: 7d, 30d, 90d, 1y (default: 30d)"),
+        ("from" = Option<String>, Query, description = "First day, YYYY-MM-DD; overrides period"),
+        ("to" = Option<String>, Query, description = "Last day, YYYY-MM-DD (default: today)"),
+        ("tz" = Option<String>, Query, description = "IANA timezone days are counted in (default: UTC)"),
+        ProjectQuery
     ),
     responses(
         (status = 200, description = "PM Dashboard data", body = PMDashboardResponse),
+        (status = 400, description = "Invalid period"),
+        (status = 404, description = "Project not found"),
         (status = 500, description = "Internal server error")
     ),
     tag = "PM Dashboard"
 )]
 pub async fn get_pm_dashboard(
     State(state): State<AppState>,
-    Query(query): Query<PMDashboardQuery>,
+    user: CurrentUser,
+    Query(query): Query<PeriodQuery>,
+    Query(project): Query<ProjectQuery>,
 ) -> ApiResult<Json<PMDashboardResponse>> {
-    let days = parse_period(&query.period);
-    let pool = &state.db;
-
-    let summary = get_pm_summary(pool, days).await?;
-    let bugs_metrics = get_bugs_metrics(pool, days).await?;
-    let economy_metrics = get_economy_metrics(pool, days).await?;
-    let component_health = get_component_health(pool, days).await?;
-    let problematic_endpoints = get_problematic_endpoints(pool, days).await?;
-
-    Ok(Json(PMDashboardResponse {
-        summary,
-        bugs_metrics,
-        economy_metrics,
-        component_health,
-        problematic_endpoints,
-        period: query.period,
+    let period = query.resolve().map_err(ApiError::Validation)?;
+    let project = project.resolve(&state).await?.map(|p| p.key);
+    Ok(Json(
+        load_pm_dashboard(&state.db, &period, Some(user.tenant.as_str()), project.as_deref()).await?,
+    ))
+}
+
+/// Load the PM dashboard for a period, optionally for one tenant and one
+/// project key.
+pub(crate) async fn load_pm_dashboard(
+    pool: &PgPool,
+    period: &Period,
+    tenant: Option<&str>,
+    project: Option<&str>,
+) -> ApiResult<PMDashboardResponse> {
+    Ok(PMDashboardResponse {
+        summary: get_pm_summary(pool, period, tenant, project).await?,
+        bugs_metrics: get_bugs_metrics(pool, period, tenant, project).await?,
+        economy_metrics: get_economy_metrics(pool, period, tenant, project).await?,
+        component_health: get_component_health(pool, period, tenant, project).await?,
+        problematic_endpoints: get_problematic_endpoints(pool, period, tenant, project).await?,
+        period: period.label.clone(),
+        project: project.map(str::to_string),
         generated_at: Utc::now().to_rfc3339(),
-    }))
+    })
 }
 
-/// Export PM dashboard as CSV.
+/// Query selecting the export format.
+#[derive(Debug, Deserialize)]
+pub struct ExportFormatQuery {
+    /// csv or json (default: csv)
+    #[serde(default)]
+    pub format: PmExportFormat,
+}
+
+/// Export PM dashboard as CSV or JSON.
 #[utoipa::path(
     get,
     path = "/api/v1/pm-dashboard/export",
     params(
-        ("period" = String, Query, description = "Period: 7d, 30d, 90d, 1y")
+        ("period" = Option<String>, Query, description = "Period ending today: 7d, 30d, 90d, 1y (default: 30d)"),
+        ("from" = Option<String>, Query, description = "First day, YYYY-MM-DD; overrides period"),
+        ("to" = Option<String>, Query, description = "Last day, YYYY-MM-DD (default: today)"),
+        ("tz" = Option<String>, Query, description = "IANA timezone days are counted in (default: UTC)"),
+        ("format" = Option<String>, Query, description = "csv or json (default: csv)"),
+        ProjectQuery
     ),
     responses(
-        (status = 200, description = "CSV export", content_type = "text/csv"),
+        (status = 200, description = "Dashboard export", content(
+            (String = "text/csv"),
+            (PMDashboardResponse = "application/json")
+        )),
+        (status = 400, description = "Invalid period"),
+        (status = 404, description = "Project not found"),
         (status = 500, description = "Internal server error")
     ),
     tag = "PM Dashboard"
 )]
 pub async fn export_pm_dashboard(
     State(state): State<AppState>,
-    Query(query): Query<PMDashboardQuery>,
-) -> ApiResult<String> {
-    let days = parse_period(&query.period);
-    let pool = &state.db;
+    user: CurrentUser,
+    Query(query): Query<PeriodQuery>,
+    Query(ExportFormatQuery { format }): Query<ExportFormatQuery>,
+    Query(project): Query<ProjectQuery>,
+) -> ApiResult<Response> {
+    let period = query.resolve().map_err(ApiError::Validation)?;
+    let project = project.resolve(&state).await?.map(|p| p.key);
+    let dashboard =
+        load_pm_dashboard(&state.db, &period, Some(user.tenant.as_str()), project.as_deref()).await?;
+    let body = render_export(format, &period, &dashboard)?;
+    let disposition = format!(
+        "attachment; filename=\"{}\"",
+        export_filename(format, &period)
+    );
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, format.content_type().to_string()),
+            (header::CONTENT_DISPOSITION, disposition),
+        ],
+        body,
+    )
+        .into_response())
+}
 
-    let summary = get_pm_summary(pool, days).await?;
-    let bugs_metrics = get_bugs_metrics(pool, days).await?;
-    let economy_metrics = get_economy_metrics(pool, days).await?;
+/// File name of a dashboard export.
+pub(crate) fn export_filename(format: PmExportFormat, period: &Period) -> String {
+    format!(
+        "pm-dashboard-{}-{}.{}",
+        period.from,
+        period.to,
+        format.extension()
+    )
+}
+
+/// Render a dashboard export.
+pub(crate) fn render_export(
+    format: PmExportFormat,
+    period: &Period,
+    dashboard: &PMDashboardResponse,
+) -> ApiResult<Vec<u8>> {
+    match format {
+        PmExportFormat::Csv => Ok(render_csv(period, dashboard).into_bytes()),
+        PmExportFormat::Json => serde_json::to_vec_pretty(dashboard)
+            .map_err(|e| ApiError::Internal(anyhow::anyhow!("Failed to serialize export: {e}"))),
+    }
+}
+
+fn render_csv(period: &Period, dashboard: &PMDashboardResponse) -> String {
+    let summary = &dashboard.summary;
+    let bugs_metrics = &dashboard.bugs_metrics;
+    let economy_metrics = &dashboard.economy_metrics;
 
-    // Generate CSV
     let mut csv = String::new();
     csv.push_str("QA Metrics Report\n");
-    csv.push_str(&format!("Period,{}\n", query.period));
+    csv.push_str(&format!("Period,{}\n", period.label));
+    csv.push_str(&format!("Timezone,{}\n", period.timezone_name()));
+    if let Some(project) = &dashboard.project {
+        csv.push_str(&format!("Project,{project}\n"));
+    }
     csv.push_str(&format!("Generated,{}\n\n", Utc::now().format("%Y-%m-%d %H:%M UTC")));
     
     csv.push_str("Summary\n");
@@ -221,35 +306,188 @@ pub async fn export_pm_dashboard(
     csv.push_str(&format!("Bug Prevention Value,${:.2}\n", economy_metrics.bug_prevention_value));
     csv.push_str(&format!("Total Economy,${:.2}\n", economy_metrics.total_economy));
 
-    Ok(csv)
+    csv
+}
+
+/// Query for listing past exports.
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct ExportsQuery {
+    /// Maximum number of exports (default: 50, max: 200)
+    pub limit: Option<i64>,
+}
+
+/// List past scheduled exports, newest first.
+#[utoipa::path(
+    get,
+    path = "/api/v1/pm-dashboard/exports",
+    params(ExportsQuery),
+    responses(
+        (status = 200, description = "Past exports", body = Vec<PmExportRecord>),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "PM Dashboard"
+)]
+pub async fn list_pm_exports(
+    State(state): State<AppState>,
+    Query(query): Query<ExportsQuery>,
+) -> ApiResult<Json<Vec<PmExportRecord>>> {
+    let limit = query.limit.unwrap_or(50).clamp(1, 200);
+    let exports = list_exports(&state.db, limit)
+        .await
+        .map_internal("Failed to list PM dashboard exports")?;
+    Ok(Json(exports))
+}
+
+/// Team capacity response.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CapacityResponse {
+    pub period: String,
+    /// Active and paused workflows across the team
+    pub active_workflows: usize,
+    /// Workflows completed per week over the period
+    pub throughput_per_week: f64,
+    pub overloaded_users: usize,
+    /// Overloaded users first, then by backlog
+    pub users: Vec<UserCapacityResponse>,
+    pub generated_at: String,
+}
+
+/// Load and pace of one user.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UserCapacityResponse {
+    pub user_id: String,
+    pub active_workflows: usize,
+    /// Workflows completed in the period
+    pub completed_workflows: i64,
+    pub throughput_per_week: f64,
+    pub avg_completion_minutes: Option<f64>,
+    /// Days needed to clear the active workflows at the user's pace
+    pub backlog_days: Option<f64>,
+    pub overloaded: bool,
+    pub workflows: Vec<InFlightWorkflowResponse>,
 }
 
-fn parse_period(period: &str) -> i64 {
-    match period {
-        "7d" => 7,
-        "30d" => 30,
-        "90d" => 90,
-        "1y" => 365,
-        _ => 30,
+/// A workflow in progress with its expected completion.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct InFlightWorkflowResponse {
+    pub workflow_id: uuid::Uuid,
+    pub ticket_id: String,
+    /// "active" or "paused"
+    pub status: String,
+    pub started_at: String,
+    pub current_step: i32,
+    pub step_count: i32,
+    pub remaining_minutes: f64,
+    pub expected_completion: String,
+}
+
+impl From<InFlightWorkflow> for InFlightWorkflowResponse {
+    fn from(w: InFlightWorkflow) -> Self {
+        Self {
+            workflow_id: w.workflow_id,
+            ticket_id: w.ticket_id,
+            status: w.status,
+            started_at: w.started_at.to_rfc3339(),
+            current_step: w.current_step,
+            step_count: w.step_count,
+            remaining_minutes: w.remaining_minutes,
+            expected_completion: w.expected_completion.to_rfc3339(),
+        }
     }
 }
 
-async fn get_pm_summary(pool: &PgPool, days: i64) -> Result<PMSummary, ApiError> {
-    let start = Utc::now() - Duration::days(days);
+impl From<UserCapacity> for UserCapacityResponse {
+    fn from(u: UserCapacity) -> Self {
+        Self {
+            user_id: u.user_id,
+            active_workflows: u.active_workflows,
+            completed_workflows: u.completed_workflows,
+            throughput_per_week: u.throughput_per_week,
+            avg_completion_minutes: u.avg_completion_minutes,
+            backlog_days: u.backlog_days,
+            overloaded: u.overloaded,
+            workflows: u.workflows.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl CapacityResponse {
+    fn new(period: String, report: CapacityReport) -> Self {
+        Self {
+            period,
+            active_workflows: report.active_workflows,
+            throughput_per_week: report.throughput_per_week,
+            overloaded_users: report.overloaded_users,
+            users: report.users.into_iter().map(Into::into).collect(),
+            generated_at: report.generated_at.to_rfc3339(),
+        }
+    }
+}
+
+/// Get team capacity: who is overloaded and when in-flight work should finish.
+///
+/// Throughput and average completion times are taken from workflows
+/// completed in the period.
+#[utoipa::path(
+    get,
+    path = "/api/v1/pm-dashboard/capacity",
+    params(
+        ("period" = Option<String>, Query, description = "Period ending today: 7d, 30d, 90d, 1y (default: 30d)"),
+        ("from" = Option<String>, Query, description = "First day, YYYY-MM-DD; overrides period"),
+        ("to" = Option<String>, Query, description = "Last day, YYYY-MM-DD (default: today)"),
+        ("tz" = Option<String>, Query, description = "IANA timezone days are counted in (default: UTC)")
+    ),
+    responses(
+        (status = 200, description = "Team capacity", body = CapacityResponse),
+        (status = 400, description = "Invalid period"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "PM Dashboard"
+)]
+pub async fn get_team_capacity(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Query(query): Query<PeriodQuery>,
+) -> ApiResult<Json<CapacityResponse>> {
+    let period = query.resolve().map_err(ApiError::Validation)?;
+    let report = load_capacity(&state.db, user.tenant.as_str(), period.days())
+        .await
+        .map_internal("Failed to load team capacity")?;
+
+    Ok(Json(CapacityResponse::new(period.label, report)))
+}
+
+async fn get_pm_summary(
+    pool: &PgPool,
+    period: &Period,
+    tenant: Option<&str>,
+    project: Option<&str>,
+) -> Result<PMSummary, ApiError> {
+    let window = period_boundaries(period).current;
 
     // Get workflow stats
     let stats: Option<(i64, i64, Option<f64>)> = sqlx::query_as(
         r"
         SELECT 
-            COUNT(DISTINCT ticket_key) as tickets,
+            COUNT(DISTINCT ticket_id) as tickets,
             COUNT(*) as workflows,
             AVG(EXTRACT(EPOCH FROM (completed_at - started_at)) / 60.0) as avg_minutes
         FROM workflow_instances
         WHERE status = 'completed'
           AND completed_at >= $1
+          AND completed_at < $2
+          AND ($3::TEXT IS NULL OR SPLIT_PART(ticket_id, '-', 1) = $3)
+          AND ($4::TEXT IS NULL OR tenant_id = $4)
+          AND deleted_at IS NULL
         ",
     )
-    .bind(start)
+    .bind(window.start)
+    .bind(window.end)
+    .bind(project)
+    .bind(tenant)
     .fetch_optional(pool)
     .await
     .map_internal("Failed to fetch PM summary")?;
@@ -263,10 +501,17 @@ async fn get_pm_summary(pool: &PgPool, days: i64) -> Result<PMSummary, ApiError>
         FROM workflow_instances
         WHERE status = 'completed'
           AND completed_at >= $1
+          AND completed_at < $2
           AND user_id IS NOT NULL
+          AND ($3::TEXT IS NULL OR SPLIT_PART(ticket_id, '-', 1) = $3)
+          AND ($4::TEXT IS NULL OR tenant_id = $4)
+          AND deleted_at IS NULL
         ",
     )
-    .bind(start)
+    .bind(window.start)
+    .bind(window.end)
+    .bind(project)
+    .bind(tenant)
     .fetch_one(pool)
     .await
     .map_internal("Failed to count active users")?;
@@ -279,10 +524,14 @@ async fn get_pm_summary(pool: &PgPool, days: i64) -> Result<PMSummary, ApiError>
     })
 }
 
-async fn get_bugs_metrics(pool: &PgPool, days: i64) -> Result<BugsMetrics, ApiError> {
-    let now = Utc::now();
-    let period_start = now - Duration::days(days);
-    let prev_period_start = period_start - Duration::days(days);
+async fn get_bugs_metrics(
+    pool: &PgPool,
+    period: &Period,
+    tenant: Option<&str>,
+    project: Option<&str>,
+) -> Result<BugsMetrics, ApiError> {
+    let bounds = period_boundaries(period);
+    let (current, previous) = (bounds.current, bounds.previous);
 
     // Bugs discovered: Count workflow step notes containing bug-related keywords
     let bug_keywords = ["bug", "defect", "issue", "error", "fail", "broken", "crash"];
@@ -296,11 +545,16 @@ async fn get_bugs_metrics(pool: &PgPool, days: i64) -> Result<BugsMetrics, ApiEr
         WHERE wi.completed_at >= $1
           AND wi.completed_at < $2
           AND wsr.notes ~* $3
+          AND ($4::TEXT IS NULL OR SPLIT_PART(wi.ticket_id, '-', 1) = $4)
+          AND ($5::TEXT IS NULL OR wi.tenant_id = $5)
+          AND wi.deleted_at IS NULL
         ",
     )
-    .bind(period_start)
-    .bind(now)
+    .bind(current.start)
+    .bind(current.end)
     .bind(&keyword_pattern)
+    .bind(project)
+    .bind(tenant)
     .fetch_one(pool)
     .await
     .map_internal("Failed to count discovered bugs")?;
@@ -313,11 +567,16 @@ async fn get_bugs_metrics(pool: &PgPool, days: i64) -> Result<BugsMetrics, ApiEr
         WHERE wi.completed_at >= $1
           AND wi.completed_at < $2
           AND wsr.notes ~* $3
+          AND ($4::TEXT IS NULL OR SPLIT_PART(wi.ticket_id, '-', 1) = $4)
+          AND ($5::TEXT IS NULL OR wi.tenant_id = $5)
+          AND wi.deleted_at IS NULL
         ",
     )
-    .bind(prev_period_start)
-    .bind(period_start)
+    .bind(previous.start)
+    .bind(previous.end)
     .bind(&keyword_pattern)
+    .bind(project)
+    .bind(tenant)
     .fetch_one(pool)
     .await
     .map_internal("Failed to count previous discovered bugs")?;
@@ -330,10 +589,17 @@ async fn get_bugs_metrics(pool: &PgPool, days: i64) -> Result<BugsMetrics, ApiEr
         WHERE created_at >= $1
           AND created_at < $2
           AND severity IN ('warning', 'critical')
+          AND ($3::TEXT IS NULL OR EXISTS (
+              SELECT 1 FROM unnest(affected_tickets) AS ticket
+              WHERE SPLIT_PART(ticket, '-', 1) = $3
+          ))
+          AND ($4::TEXT IS NULL OR tenant_id = $4)
         ",
     )
-    .bind(period_start)
-    .bind(now)
+    .bind(current.start)
+    .bind(current.end)
+    .bind(project)
+    .bind(tenant)
     .fetch_one(pool)
     .await
     .map_internal("Failed to count prevented bugs")?;
@@ -345,10 +611,17 @@ async fn get_bugs_metrics(pool: &PgPool, days: i64) -> Result<BugsMetrics, ApiEr
         WHERE created_at >= $1
           AND created_at < $2
           AND severity IN ('warning', 'critical')
+          AND ($3::TEXT IS NULL OR EXISTS (
+              SELECT 1 FROM unnest(affected_tickets) AS ticket
+              WHERE SPLIT_PART(ticket, '-', 1) = $3
+          ))
+          AND ($4::TEXT IS NULL OR tenant_id = $4)
         ",
     )
-    .bind(prev_period_start)
-    .bind(period_start)
+    .bind(previous.start)
+    .bind(previous.end)
+    .bind(project)
+    .bind(tenant)
     .fetch_one(pool)
     .await
     .map_internal("Failed to count previous prevented bugs")?;
@@ -385,8 +658,13 @@ async fn get_bugs_metrics(pool: &PgPool, days: i64) -> Result<BugsMetrics, ApiEr
     })
 }
 
-async fn get_economy_metrics(pool: &PgPool, days: i64) -> Result<EconomyMetrics, ApiError> {
-    let start = Utc::now() - Duration::days(days);
+async fn get_economy_metrics(
+    pool: &PgPool,
+    period: &Period,
+    tenant: Option<&str>,
+    project: Option<&str>,
+) -> Result<EconomyMetrics, ApiError> {
+    let window = period_boundaries(period).current;
 
     // Configurable rates (could be stored in config)
     let hourly_rate = 50.0; // $50/hour
@@ -402,9 +680,16 @@ async fn get_economy_metrics(pool: &PgPool, days: i64) -> Result<EconomyMetrics,
         JOIN workflow_instances wi ON ts.workflow_instance_id = wi.id
         LEFT JOIN time_estimates te ON wi.template_id = te.template_id AND ts.step_index = te.step_index
         WHERE ts.ended_at >= $1
+          AND ts.ended_at < $2
+          AND ($3::TEXT IS NULL OR SPLIT_PART(wi.ticket_id, '-', 1) = $3)
+          AND ($4::TEXT IS NULL OR wi.tenant_id = $4)
+          AND wi.deleted_at IS NULL
         ",
     )
-    .bind(start)
+    .bind(window.start)
+    .bind(window.end)
+    .bind(project)
+    .bind(tenant)
     .fetch_optional(pool)
     .await
     .map_internal("Failed to fetch time stats")?;
@@ -422,10 +707,19 @@ async fn get_economy_metrics(pool: &PgPool, days: i64) -> Result<EconomyMetrics,
         SELECT COUNT(*)
         FROM alerts
         WHERE created_at >= $1
+          AND created_at < $2
           AND severity IN ('warning', 'critical')
+          AND ($3::TEXT IS NULL OR EXISTS (
+              SELECT 1 FROM unnest(affected_tickets) AS ticket
+              WHERE SPLIT_PART(ticket, '-', 1) = $3
+          ))
+          AND ($4::TEXT IS NULL OR tenant_id = $4)
         ",
     )
-    .bind(start)
+    .bind(window.start)
+    .bind(window.end)
+    .bind(project)
+    .bind(tenant)
     .fetch_one(pool)
     .await
     .map_internal("Failed to count prevented bugs for economy")?;
@@ -444,30 +738,42 @@ async fn get_economy_metrics(pool: &PgPool, days: i64) -> Result<EconomyMetrics,
     })
 }
 
-async fn get_component_health(pool: &PgPool, days: i64) -> Result<Vec<ComponentHealth>, ApiError> {
-    let now = Utc::now();
-    let period_start = now - Duration::days(days);
-    let prev_period_start = period_start - Duration::days(days);
+async fn get_component_health(
+    pool: &PgPool,
+    period: &Period,
+    tenant: Option<&str>,
+    project: Option<&str>,
+) -> Result<Vec<ComponentHealth>, ApiError> {
+    let bounds = period_boundaries(period);
+    let (current, previous) = (bounds.current, bounds.previous);
 
     // Extract components from ticket keys (e.g., "PMP-1234" -> "PMP")
     // and from workflow notes mentioning components
     let current_stats: Vec<(String, i64, i64, Option<NaiveDate>)> = sqlx::query_as(
         r"
         SELECT 
-            COALESCE(SPLIT_PART(wi.ticket_key, '-', 1), 'Unknown') as component,
+            COALESCE(SPLIT_PART(wi.ticket_id, '-', 1), 'Unknown') as component,
             COUNT(*) FILTER (WHERE wsr.notes ~* 'bug|error|fail|issue') as bug_count,
-            COUNT(DISTINCT wi.ticket_key) as ticket_count,
-            MAX(DATE(wi.completed_at)) as last_issue_date
+            COUNT(DISTINCT wi.ticket_id) as ticket_count,
+            MAX(DATE(wi.completed_at AT TIME ZONE $3)) as last_issue_date
         FROM workflow_instances wi
         LEFT JOIN workflow_step_results wsr ON wsr.instance_id = wi.id
         WHERE wi.completed_at >= $1
+          AND wi.completed_at < $2
           AND wi.status = 'completed'
-        GROUP BY SPLIT_PART(wi.ticket_key, '-', 1)
+          AND ($4::TEXT IS NULL OR SPLIT_PART(wi.ticket_id, '-', 1) = $4)
+          AND ($5::TEXT IS NULL OR wi.tenant_id = $5)
+          AND wi.deleted_at IS NULL
+        GROUP BY SPLIT_PART(wi.ticket_id, '-', 1)
         ORDER BY bug_count DESC
         LIMIT 10
         ",
     )
-    .bind(period_start)
+    .bind(current.start)
+    .bind(current.end)
+    .bind(period.timezone_name())
+    .bind(project)
+    .bind(tenant)
     .fetch_all(pool)
     .await
     .map_internal("Failed to fetch component health")?;
@@ -476,18 +782,23 @@ async fn get_component_health(pool: &PgPool, days: i64) -> Result<Vec<ComponentH
     let prev_stats: Vec<(String, i64)> = sqlx::query_as(
         r"
         SELECT 
-            COALESCE(SPLIT_PART(wi.ticket_key, '-', 1), 'Unknown') as component,
+            COALESCE(SPLIT_PART(wi.ticket_id, '-', 1), 'Unknown') as component,
             COUNT(*) FILTER (WHERE wsr.notes ~* 'bug|error|fail|issue') as bug_count
         FROM workflow_instances wi
         LEFT JOIN workflow_step_results wsr ON wsr.instance_id = wi.id
         WHERE wi.completed_at >= $1
           AND wi.completed_at < $2
           AND wi.status = 'completed'
-        GROUP BY SPLIT_PART(wi.ticket_key, '-', 1)
+          AND ($3::TEXT IS NULL OR SPLIT_PART(wi.ticket_id, '-', 1) = $3)
+          AND ($4::TEXT IS NULL OR wi.tenant_id = $4)
+          AND wi.deleted_at IS NULL
+        GROUP BY SPLIT_PART(wi.ticket_id, '-', 1)
         ",
     )
-    .bind(prev_period_start)
-    .bind(period_start)
+    .bind(previous.start)
+    .bind(previous.end)
+    .bind(project)
+    .bind(tenant)
     .fetch_all(pool)
     .await
     .map_internal("Failed to fetch previous component stats")?;
@@ -526,8 +837,13 @@ async fn get_component_health(pool: &PgPool, days: i64) -> Result<Vec<ComponentH
         .collect())
 }
 
-async fn get_problematic_endpoints(pool: &PgPool, days: i64) -> Result<Vec<ProblematicEndpoint>, ApiError> {
-    let start = Utc::now() - Duration::days(days);
+async fn get_problematic_endpoints(
+    pool: &PgPool,
+    period: &Period,
+    tenant: Option<&str>,
+    project: Option<&str>,
+) -> Result<Vec<ProblematicEndpoint>, ApiError> {
+    let window = period_boundaries(period).current;
 
     // Extract endpoints from workflow notes (looking for API paths like /api/v1/...)
     let endpoint_stats: Vec<(String, i64, Vec<String>, Vec<String>)> = sqlx::query_as(
@@ -536,24 +852,31 @@ async fn get_problematic_endpoints(pool: &PgPool, days: i64) -> Result<Vec<Probl
             SELECT 
                 regexp_matches(wsr.notes, '/api/[a-zA-Z0-9/_-]+', 'g') as endpoint_match,
                 wsr.notes,
-                wi.ticket_key
+                wi.ticket_id
             FROM workflow_step_results wsr
             JOIN workflow_instances wi ON wsr.instance_id = wi.id
             WHERE wi.completed_at >= $1
+              AND wi.completed_at < $2
               AND wsr.notes ~* '/api/'
+              AND ($3::TEXT IS NULL OR SPLIT_PART(wi.ticket_id, '-', 1) = $3)
+              AND ($4::TEXT IS NULL OR wi.tenant_id = $4)
+              AND wi.deleted_at IS NULL
         )
         SELECT 
             endpoint_match[1] as endpoint,
             COUNT(*) as issue_count,
             ARRAY_AGG(DISTINCT LEFT(notes, 100)) as sample_issues,
-            ARRAY_AGG(DISTINCT ticket_key) as tickets
+            ARRAY_AGG(DISTINCT ticket_id) as tickets
         FROM endpoint_mentions
         GROUP BY endpoint_match[1]
         ORDER BY issue_count DESC
         LIMIT 10
         ",
     )
-    .bind(start)
+    .bind(window.start)
+    .bind(window.end)
+    .bind(project)
+    .bind(tenant)
     .fetch_all(pool)
     .await
     .unwrap_or_default(); // Return empty if no matches
@@ -571,3 +894,67 @@ async fn get_problematic_endpoints(pool: &PgPool, days: i64) -> Result<Vec<Probl
         })
         .collect())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dashboard() -> PMDashboardResponse {
+        PMDashboardResponse {
+            summary: PMSummary {
+                total_tickets_tested: 12,
+                total_workflows_completed: 14,
+                active_qa_users: 3,
+                avg_time_per_ticket_minutes: 42.5,
+            },
+            bugs_metrics: BugsMetrics {
+                bugs_discovered: 4,
+                bugs_prevented: 6,
+                prevention_rate: 0.6,
+                discovered_change: 0.0,
+                prevented_change: 0.0,
+            },
+            economy_metrics: EconomyMetrics {
+                hours_saved: 2.0,
+                cost_saved: 100.0,
+                bug_prevention_value: 3000.0,
+                total_economy: 3100.0,
+                hourly_rate: 50.0,
+                avg_bug_fix_cost: 500.0,
+            },
+            component_health: Vec::new(),
+            problematic_endpoints: Vec::new(),
+            period: "7d".to_string(),
+            project: None,
+            generated_at: Utc::now().to_rfc3339(),
+        }
+    }
+
+    fn period() -> Period {
+        PeriodQuery {
+            from: Some("2026-03-01".parse().unwrap()),
+            to: Some("2026-03-07".parse().unwrap()),
+            ..PeriodQuery::default()
+        }
+        .resolve()
+        .unwrap()
+    }
+
+    #[test]
+    fn test_render_csv_and_json() {
+        let period = period();
+        let csv = String::from_utf8(render_export(PmExportFormat::Csv, &period, &dashboard()).unwrap())
+            .unwrap();
+        assert!(csv.contains("Period,2026-03-01..2026-03-07\n"));
+        assert!(csv.contains("Prevention Rate,60.0%\n"));
+
+        let json: serde_json::Value =
+            serde_json::from_slice(&render_export(PmExportFormat::Json, &period, &dashboard()).unwrap())
+                .unwrap();
+        assert_eq!(json["summary"]["totalTicketsTested"], 12);
+        assert_eq!(
+            export_filename(PmExportFormat::Json, &period),
+            "pm-dashboard-2026-03-01-2026-03-07.json"
+        );
+    }
+}
@@ -0,0 +1,1139 @@
+//! Postman API routes.
+//!
+//! Collection runs executed with Newman. Results are stored so they can be
+//! reviewed later and linked from workflow steps. Environments can be
+//! managed here and selected per run to target staging, production, etc.
+//! Collection versions can be diffed to see how the API surface changed,
+//! and skeleton collections can be generated from Jira tickets. The local
+//! collection cache used by search can be inspected and refreshed.
+
+use std::collections::HashMap;
+
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    routing::{get, post},
+    Extension, Json, Router,
+};
+use chrono::{DateTime, Utc};
+use qa_pms_postman::{
+    build_collection, diff_collections, extract_endpoints, CacheRefreshReport, CacheStatus,
+    CollectionDiff, CollectionRun, CollectionRunReport,
+    EndpointSource, EndpointSpec, Environment, EnvironmentInput, EnvironmentRef, EnvironmentSummary,
+    EnvironmentVariable, NewmanRunner, PostmanCache, PostmanClient, PostmanError, RequestExecution, RunOptions,
+};
+use qa_pms_workflow::{add_step_link, get_instance, StepLink};
+use secrecy::ExposeSecret;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::Validate;
+
+use qa_pms_config::settings::PostmanSettings;
+use qa_pms_core::error::ApiError;
+use qa_pms_core::TenantId;
+
+use crate::app::AppState;
+use crate::audit::AuditChange;
+use crate::auth::CurrentUser;
+use crate::routes::tenant::{tenant_credential, POSTMAN_API_KEY};
+use crate::routes::tickets::{adf_to_text, get_jira_client};
+use crate::validation::{not_blank, one_of, ValidatedJson};
+
+type ApiResult<T> = Result<T, ApiError>;
+
+/// Create the Postman router.
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/api/v1/postman/collections/:id/run", post(run_collection))
+        .route(
+            "/api/v1/integrations/postman/collections/:id/run",
+            post(run_collection),
+        )
+        .route("/api/v1/postman/collections/:id/diff", get(diff_collection))
+        .route(
+            "/api/v1/postman/collections/from-ticket",
+            post(generate_from_ticket),
+        )
+        .route("/api/v1/postman/runs/:run_id", get(get_collection_run))
+        .route("/api/v1/postman/cache", get(get_cache_status))
+        .route("/api/v1/postman/cache/refresh", post(refresh_cache))
+        .route(
+            "/api/v1/postman/environments",
+            get(list_environments).post(create_environment),
+        )
+        .route(
+            "/api/v1/postman/environments/:id",
+            get(get_environment).put(update_environment),
+        )
+}
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/// Collection run request.
+#[derive(Debug, Default, Deserialize, ToSchema, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct RunCollectionRequest {
+    /// Environment to run against
+    pub environment_id: Option<String>,
+    /// Variables passed to Newman, overriding collection and environment values
+    #[serde(default)]
+    pub variables: HashMap<String, String>,
+    /// Run only this folder
+    pub folder: Option<String>,
+    /// Number of iterations
+    #[validate(range(min = 1, max = 100))]
+    pub iterations: Option<u32>,
+    /// Workflow whose step should link to the run
+    pub workflow_id: Option<Uuid>,
+    /// Step index to link, defaulting to the workflow's current step
+    #[validate(range(min = 0))]
+    pub step_index: Option<i32>,
+}
+
+/// Result of one assertion.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AssertionResponse {
+    /// Assertion name
+    pub name: String,
+    /// Whether it passed
+    pub passed: bool,
+    /// Failure message
+    pub error: Option<String>,
+}
+
+/// Outcome of one request.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RequestExecutionResponse {
+    /// Request name
+    pub name: String,
+    /// HTTP method
+    pub method: Option<String>,
+    /// Request URL
+    pub url: Option<String>,
+    /// Response status code
+    pub status_code: Option<u16>,
+    /// Response time in milliseconds
+    pub response_time_ms: Option<u64>,
+    /// Transport error
+    pub error: Option<String>,
+    /// Whether the request passed
+    pub passed: bool,
+    /// Assertion results
+    pub assertions: Vec<AssertionResponse>,
+}
+
+/// Stored collection run.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CollectionRunResponse {
+    /// Run ID
+    pub run_id: Uuid,
+    /// Postman collection ID
+    pub collection_id: String,
+    /// Collection name
+    pub collection_name: String,
+    /// Whether every request and assertion passed
+    pub passed: bool,
+    /// Run start
+    pub started_at: Option<DateTime<Utc>>,
+    /// Run end
+    pub finished_at: Option<DateTime<Utc>>,
+    /// Requests executed
+    pub total_requests: u32,
+    /// Requests that could not be sent
+    pub failed_requests: u32,
+    /// Assertions evaluated
+    pub total_assertions: u32,
+    /// Assertions that failed
+    pub failed_assertions: u32,
+    /// Per-request outcomes
+    pub executions: Vec<RequestExecutionResponse>,
+    /// Workflow the run is linked to
+    pub workflow_id: Option<Uuid>,
+    /// Step the run is linked to
+    pub step_index: Option<i32>,
+}
+
+impl From<RequestExecution> for RequestExecutionResponse {
+    fn from(execution: RequestExecution) -> Self {
+        Self {
+            passed: execution.passed(),
+            name: execution.name,
+            method: execution.method,
+            url: execution.url,
+            status_code: execution.status_code,
+            response_time_ms: execution.response_time_ms,
+            error: execution.error,
+            assertions: execution
+                .assertions
+                .into_iter()
+                .map(|a| AssertionResponse {
+                    name: a.name,
+                    passed: a.passed,
+                    error: a.error,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Query parameters for listing environments.
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub struct ListEnvironmentsQuery {
+    /// Only environments in this workspace
+    pub workspace_id: Option<String>,
+}
+
+/// Environment summary.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct EnvironmentSummaryResponse {
+    /// Environment ID
+    pub id: String,
+    /// Environment name
+    pub name: String,
+    /// Last update
+    pub updated_at: Option<String>,
+}
+
+/// Environment variable.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct EnvironmentVariableDto {
+    /// Variable name
+    #[validate(custom(function = "not_blank"))]
+    pub key: String,
+    /// Variable value
+    #[serde(default)]
+    pub value: String,
+    /// Whether the variable is used
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Variable type ("default" or "secret")
+    #[serde(default = "default_variable_type")]
+    #[validate(custom(function = "validate_variable_type"))]
+    pub variable_type: String,
+}
+
+const fn default_true() -> bool {
+    true
+}
+
+fn default_variable_type() -> String {
+    "default".to_string()
+}
+
+fn validate_variable_type(value: &str) -> Result<(), validator::ValidationError> {
+    one_of(value, &["default", "secret"])
+}
+
+/// Environment with variables. Secret values are masked.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct EnvironmentResponse {
+    /// Environment ID
+    pub id: Option<String>,
+    /// Environment name
+    pub name: String,
+    /// Variables
+    pub variables: Vec<EnvironmentVariableDto>,
+}
+
+/// Create or replace an environment.
+#[derive(Debug, Deserialize, ToSchema, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct EnvironmentRequest {
+    /// Environment name
+    #[validate(custom(function = "not_blank"), length(max = 255))]
+    pub name: String,
+    /// Workspace to create the environment in (ignored on update)
+    pub workspace_id: Option<String>,
+    /// Variables; on update these replace the existing set
+    #[serde(default)]
+    #[validate(nested)]
+    pub variables: Vec<EnvironmentVariableDto>,
+}
+
+/// Written environment.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct EnvironmentRefResponse {
+    /// Environment ID
+    pub id: String,
+    /// Environment name
+    pub name: String,
+}
+
+impl From<EnvironmentSummary> for EnvironmentSummaryResponse {
+    fn from(environment: EnvironmentSummary) -> Self {
+        Self {
+            id: environment.id,
+            name: environment.name,
+            updated_at: environment.updated_at,
+        }
+    }
+}
+
+impl From<Environment> for EnvironmentResponse {
+    fn from(environment: Environment) -> Self {
+        Self {
+            id: environment.id,
+            name: environment.name,
+            variables: environment
+                .values
+                .into_iter()
+                .map(|v| EnvironmentVariableDto {
+                    value: if v.variable_type == "secret" {
+                        SECRET_MASK.to_string()
+                    } else {
+                        v.value
+                    },
+                    key: v.key,
+                    enabled: v.enabled,
+                    variable_type: v.variable_type,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<EnvironmentRef> for EnvironmentRefResponse {
+    fn from(environment: EnvironmentRef) -> Self {
+        Self {
+            id: environment.id,
+            name: environment.name,
+        }
+    }
+}
+
+/// Placeholder returned instead of secret variable values.
+const SECRET_MASK: &str = "********";
+
+impl EnvironmentRequest {
+    /// Build the Postman payload. Secret variables sent back masked keep
+    /// their current value from `existing`.
+    fn to_input(&self, existing: Option<&Environment>) -> EnvironmentInput {
+        let current = |key: &str| {
+            existing
+                .and_then(|e| e.values.iter().find(|v| v.key == key))
+                .map(|v| v.value.clone())
+        };
+        EnvironmentInput {
+            name: self.name.trim().to_string(),
+            values: self
+                .variables
+                .iter()
+                .map(|v| EnvironmentVariable {
+                    value: if v.value == SECRET_MASK {
+                        current(&v.key).unwrap_or_default()
+                    } else {
+                        v.value.clone()
+                    },
+                    key: v.key.clone(),
+                    enabled: v.enabled,
+                    variable_type: v.variable_type.clone(),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Query parameters for a collection diff.
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct DiffQuery {
+    /// Collection to compare against, e.g. the copy forked at the last release
+    pub against: String,
+}
+
+/// Request present in only one version.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DiffRequestResponse {
+    /// Folder path and request name
+    pub path: String,
+    /// HTTP method
+    pub method: Option<String>,
+    /// Request URL
+    pub url: Option<String>,
+}
+
+/// Changed request attribute.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct FieldChangeResponse {
+    /// "method", "url", "body" or "header:<name>"
+    pub field: String,
+    /// Value in the `against` collection
+    pub before: Option<String>,
+    /// Value in this collection
+    pub after: Option<String>,
+}
+
+/// Request changed between versions.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangedRequestResponse {
+    /// Folder path and request name
+    pub path: String,
+    /// Changed attributes
+    pub changes: Vec<FieldChangeResponse>,
+}
+
+/// Structural diff between two collections.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CollectionDiffResponse {
+    /// Collection ID
+    pub collection_id: String,
+    /// Collection compared against
+    pub against: String,
+    /// Requests only in this collection
+    pub added: Vec<DiffRequestResponse>,
+    /// Requests only in the `against` collection
+    pub removed: Vec<DiffRequestResponse>,
+    /// Requests present in both with different content
+    pub changed: Vec<ChangedRequestResponse>,
+    /// Requests identical in both
+    pub unchanged: usize,
+}
+
+impl CollectionDiffResponse {
+    fn new(collection_id: String, against: String, diff: CollectionDiff) -> Self {
+        let request = |r: qa_pms_postman::RequestRef| DiffRequestResponse {
+            path: r.path,
+            method: r.method,
+            url: r.url,
+        };
+        Self {
+            collection_id,
+            against,
+            added: diff.added.into_iter().map(request).collect(),
+            removed: diff.removed.into_iter().map(request).collect(),
+            changed: diff
+                .changed
+                .into_iter()
+                .map(|c| ChangedRequestResponse {
+                    path: c.path,
+                    changes: c
+                        .changes
+                        .into_iter()
+                        .map(|f| FieldChangeResponse {
+                            field: f.field,
+                            before: f.before,
+                            after: f.after,
+                        })
+                        .collect(),
+                })
+                .collect(),
+            unchanged: diff.unchanged,
+        }
+    }
+}
+
+/// Generate a collection from a Jira ticket.
+#[derive(Debug, Deserialize, ToSchema, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct GenerateFromTicketRequest {
+    /// Jira ticket key (e.g., PROJ-123)
+    #[validate(custom(function = "not_blank"))]
+    pub ticket_key: String,
+    /// Workspace to create the collection in
+    pub workspace_id: Option<String>,
+    /// Return the collection without creating it in Postman
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// Endpoint found in the ticket.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GeneratedEndpointResponse {
+    /// HTTP method
+    pub method: String,
+    /// Path with `{param}` placeholders
+    pub path: String,
+    /// "explicit", "openApi" or "keyword" (guessed, needs review)
+    pub source: String,
+}
+
+/// Generated collection.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GeneratedCollectionResponse {
+    /// Created collection ID (absent for dry runs)
+    pub collection_id: Option<String>,
+    /// Collection name
+    pub name: String,
+    /// Endpoints stubbed, in order of mention
+    pub endpoints: Vec<GeneratedEndpointResponse>,
+    /// Collection JSON (dry runs only)
+    #[schema(value_type = Option<Object>)]
+    pub collection: Option<serde_json::Value>,
+}
+
+impl From<EndpointSpec> for GeneratedEndpointResponse {
+    fn from(endpoint: EndpointSpec) -> Self {
+        let source = match endpoint.source {
+            EndpointSource::Explicit => "explicit",
+            EndpointSource::OpenApi => "openApi",
+            EndpointSource::Keyword => "keyword",
+        };
+        Self {
+            method: endpoint.method,
+            path: endpoint.path,
+            source: source.to_string(),
+        }
+    }
+}
+
+/// Collection cache status.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CacheStatusResponse {
+    /// Cached collections
+    pub collections: i64,
+    /// Oldest entry's refresh time
+    pub oldest_refresh: Option<DateTime<Utc>>,
+    /// Newest entry's refresh time
+    pub last_refresh: Option<DateTime<Utc>>,
+}
+
+/// Collection cache refresh result.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CacheRefreshResponse {
+    /// Collections written
+    pub collections: usize,
+    /// Requests across those collections
+    pub requests: usize,
+    /// Collections removed because they no longer exist
+    pub removed: usize,
+    /// Collections that could not be fetched (previous entry kept)
+    pub failed: usize,
+}
+
+impl From<CacheStatus> for CacheStatusResponse {
+    fn from(status: CacheStatus) -> Self {
+        Self {
+            collections: status.collections,
+            oldest_refresh: status.oldest_refresh,
+            last_refresh: status.last_refresh,
+        }
+    }
+}
+
+impl From<CacheRefreshReport> for CacheRefreshResponse {
+    fn from(report: CacheRefreshReport) -> Self {
+        Self {
+            collections: report.collections,
+            requests: report.requests,
+            removed: report.removed,
+            failed: report.failed,
+        }
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct CollectionRunRow {
+    id: Uuid,
+    collection_id: String,
+    collection_name: String,
+    report: serde_json::Value,
+    workflow_instance_id: Option<Uuid>,
+    step_index: Option<i32>,
+}
+
+impl CollectionRunRow {
+    fn into_response(self) -> ApiResult<CollectionRunResponse> {
+        let report: CollectionRunReport = serde_json::from_value(self.report)
+            .map_err(|e| ApiError::Internal(anyhow::anyhow!("Corrupt run report {}: {e}", self.id)))?;
+        Ok(run_response(
+            self.id,
+            self.collection_id,
+            self.collection_name,
+            report,
+            self.workflow_instance_id.zip(self.step_index),
+        ))
+    }
+}
+
+fn run_response(
+    run_id: Uuid,
+    collection_id: String,
+    collection_name: String,
+    report: CollectionRunReport,
+    step: Option<(Uuid, i32)>,
+) -> CollectionRunResponse {
+    CollectionRunResponse {
+        run_id,
+        collection_id,
+        collection_name,
+        passed: report.passed,
+        started_at: report.started_at,
+        finished_at: report.finished_at,
+        total_requests: report.total_requests,
+        failed_requests: report.failed_requests,
+        total_assertions: report.total_assertions,
+        failed_assertions: report.failed_assertions,
+        executions: report.executions.into_iter().map(Into::into).collect(),
+        workflow_id: step.map(|(id, _)| id),
+        step_index: step.map(|(_, index)| index),
+    }
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+/// Create a Postman client from the instance settings.
+fn instance_postman_client(state: &AppState) -> ApiResult<PostmanClient> {
+    state
+        .settings
+        .postman
+        .as_ref()
+        .filter(|p| !p.api_key.expose_secret().is_empty())
+        .map(|settings| crate::app::postman_client(settings, &state.settings.retry, &state.circuit_breakers))
+        .ok_or_else(|| ApiError::ServiceUnavailable("Postman integration not configured".into()))
+}
+
+/// Create a Postman client with the tenant's API key, or from the instance
+/// settings when the tenant has not stored one.
+///
+/// The instance workspace policy applies to tenant keys too.
+pub(crate) fn postman_client(state: &AppState, tenant: &TenantId) -> ApiResult<PostmanClient> {
+    let Some(api_key) = tenant_credential(state, tenant, POSTMAN_API_KEY)? else {
+        return instance_postman_client(state);
+    };
+    let settings = match &state.settings.postman {
+        Some(settings) => PostmanSettings {
+            api_key,
+            ..settings.clone()
+        },
+        None => PostmanSettings {
+            api_key,
+            newman_path: None,
+            cache_refresh_secs: None,
+            allowed_workspaces: Vec::new(),
+            read_only: false,
+        },
+    };
+    Ok(crate::app::postman_client(
+        &settings,
+        &state.settings.retry,
+        &state.circuit_breakers,
+    ))
+}
+
+/// Map a Postman client error to an API error.
+pub(crate) fn postman_error(error: PostmanError) -> ApiError {
+    match error {
+        PostmanError::NotFound(what) => ApiError::NotFound(what),
+        PostmanError::RateLimited => ApiError::RateLimited,
+        PostmanError::Forbidden(message) => ApiError::Forbidden(message),
+        PostmanError::Runner(message) => ApiError::Internal(anyhow::anyhow!(message)),
+        PostmanError::Database(e) => ApiError::Internal(e.into()),
+        PostmanError::CircuitOpen(e) => ApiError::ServiceUnavailable(e.to_string()),
+        other => ApiError::ExternalService(other.to_string()),
+    }
+}
+
+fn newman_runner(state: &AppState) -> NewmanRunner {
+    state
+        .settings
+        .postman
+        .as_ref()
+        .and_then(|p| p.newman_path.as_deref())
+        .map_or_else(NewmanRunner::default, NewmanRunner::new)
+}
+
+// ============================================================================
+// Handlers
+// ============================================================================
+
+/// Run a collection with Newman and store the results.
+///
+/// Failing assertions do not fail the request; check `passed`. With a
+/// `workflowId`, the run is linked to a step of that workflow. Also served
+/// under `/api/v1/integrations/postman/collections/{id}/run`.
+#[utoipa::path(
+    post,
+    path = "/api/v1/postman/collections/{id}/run",
+    params(("id" = String, Path, description = "Postman collection ID")),
+    request_body = RunCollectionRequest,
+    responses(
+        (status = 200, description = "Run finished", body = CollectionRunResponse),
+        (status = 404, description = "Collection, environment or workflow not found"),
+        (status = 422, description = "Invalid request", body = qa_pms_core::error::ErrorResponse),
+        (status = 500, description = "Newman could not run the collection"),
+        (status = 502, description = "Postman request failed"),
+        (status = 503, description = "Postman not configured")
+    ),
+    tag = "Postman"
+)]
+pub async fn run_collection(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Path(collection_id): Path<String>,
+    ValidatedJson(request): ValidatedJson<RunCollectionRequest>,
+) -> ApiResult<Json<CollectionRunResponse>> {
+    let client = postman_client(&state, &user.tenant)?;
+
+    let step = match request.workflow_id {
+        Some(workflow_id) => {
+            let instance = get_instance(&state.db, workflow_id)
+                .await
+                .map_err(|e| ApiError::Internal(e.into()))?
+                .ok_or_else(|| ApiError::NotFound(format!("Workflow {workflow_id}")))?;
+            Some((workflow_id, request.step_index.unwrap_or(instance.current_step)))
+        }
+        None => None,
+    };
+
+    let options = RunOptions {
+        environment: None,
+        variables: request.variables.into_iter().collect(),
+        folder: request.folder,
+        iterations: request.iterations,
+    };
+    let CollectionRun {
+        collection_name,
+        report,
+    } = client
+        .run_collection(
+            &collection_id,
+            request.environment_id.as_deref(),
+            &newman_runner(&state),
+            options,
+        )
+        .await
+        .map_err(postman_error)?;
+
+    let run_id = Uuid::new_v4();
+    let report_json = serde_json::to_value(&report).map_err(|e| ApiError::Internal(e.into()))?;
+    sqlx::query(
+        r"
+        INSERT INTO postman_collection_runs
+            (id, collection_id, collection_name, passed, report, workflow_instance_id, step_index, started_at, finished_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+        ",
+    )
+    .bind(run_id)
+    .bind(&collection_id)
+    .bind(&collection_name)
+    .bind(report.passed)
+    .bind(report_json)
+    .bind(step.map(|(id, _)| id))
+    .bind(step.map(|(_, index)| index))
+    .bind(report.started_at)
+    .bind(report.finished_at)
+    .execute(&state.db)
+    .await
+    .map_err(|e| ApiError::Internal(e.into()))?;
+
+    if let Some((workflow_id, step_index)) = step {
+        let link = StepLink {
+            title: format!(
+                "Postman run: {} ({})",
+                collection_name,
+                if report.passed { "passed" } else { "failed" }
+            ),
+            url: format!("/api/v1/postman/runs/{run_id}"),
+        };
+        add_step_link(&state.db, workflow_id, step_index, &link)
+            .await
+            .map_err(|e| ApiError::Internal(e.into()))?;
+    }
+
+    tracing::info!(
+        run_id = %run_id,
+        collection_id = %collection_id,
+        passed = report.passed,
+        failed_assertions = report.failed_assertions,
+        "Postman collection run stored"
+    );
+    Ok(Json(run_response(run_id, collection_id, collection_name, report, step)))
+}
+
+/// Get a stored collection run.
+#[utoipa::path(
+    get,
+    path = "/api/v1/postman/runs/{run_id}",
+    params(("run_id" = Uuid, Path, description = "Collection run ID")),
+    responses(
+        (status = 200, description = "Collection run", body = CollectionRunResponse),
+        (status = 404, description = "Run not found")
+    ),
+    tag = "Postman"
+)]
+pub async fn get_collection_run(
+    State(state): State<AppState>,
+    Path(run_id): Path<Uuid>,
+) -> ApiResult<Json<CollectionRunResponse>> {
+    let row: CollectionRunRow = sqlx::query_as(
+        r"
+        SELECT id, collection_id, collection_name, report, workflow_instance_id, step_index
+        FROM postman_collection_runs
+        WHERE id = $1
+        ",
+    )
+    .bind(run_id)
+    .fetch_optional(&state.db)
+    .await
+    .map_err(|e| ApiError::Internal(e.into()))?
+    .ok_or_else(|| ApiError::NotFound(format!("Collection run {run_id}")))?;
+
+    Ok(Json(row.into_response()?))
+}
+
+/// Diff a collection against another version.
+///
+/// `against` is the base (older) version; `added` and `removed` are relative
+/// to it.
+#[utoipa::path(
+    get,
+    path = "/api/v1/postman/collections/{id}/diff",
+    params(
+        ("id" = String, Path, description = "Postman collection ID"),
+        DiffQuery
+    ),
+    responses(
+        (status = 200, description = "Collection diff", body = CollectionDiffResponse),
+        (status = 404, description = "Collection not found"),
+        (status = 502, description = "Postman request failed"),
+        (status = 503, description = "Postman not configured")
+    ),
+    tag = "Postman"
+)]
+pub async fn diff_collection(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Path(collection_id): Path<String>,
+    Query(query): Query<DiffQuery>,
+) -> ApiResult<Json<CollectionDiffResponse>> {
+    let client = postman_client(&state, &user.tenant)?;
+    let (current, base) = tokio::try_join!(
+        client.get_collection_json(&collection_id),
+        client.get_collection_json(&query.against),
+    )
+    .map_err(postman_error)?;
+
+    let diff = diff_collections(&base, &current);
+    Ok(Json(CollectionDiffResponse::new(collection_id, query.against, diff)))
+}
+
+/// Generate a skeleton collection from a Jira ticket.
+///
+/// Endpoints are taken from the summary and description: explicit
+/// "METHOD /path" mentions and OpenAPI `#/paths/...` references, or action
+/// keywords ("create order") when neither is present.
+#[utoipa::path(
+    post,
+    path = "/api/v1/postman/collections/from-ticket",
+    request_body = GenerateFromTicketRequest,
+    responses(
+        (status = 200, description = "Dry run result", body = GeneratedCollectionResponse),
+        (status = 201, description = "Collection created", body = GeneratedCollectionResponse),
+        (status = 400, description = "No endpoints found in the ticket"),
+        (status = 404, description = "Ticket not found"),
+        (status = 422, description = "Invalid request", body = qa_pms_core::error::ErrorResponse),
+        (status = 403, description = "Outside the Postman workspace policy"),
+        (status = 502, description = "Postman request failed"),
+        (status = 503, description = "Jira or Postman not configured")
+    ),
+    tag = "Postman"
+)]
+pub async fn generate_from_ticket(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    ValidatedJson(request): ValidatedJson<GenerateFromTicketRequest>,
+) -> ApiResult<(StatusCode, Json<GeneratedCollectionResponse>)> {
+    let key = request.ticket_key.trim();
+    let ticket = get_jira_client(&state, &user.tenant)
+        .await?
+        .get_ticket(key)
+        .await
+        .map_err(|e| {
+            if e.to_string().contains("not found") {
+                ApiError::NotFound(format!("Ticket not found: {key}"))
+            } else {
+                ApiError::ServiceUnavailable(format!("Jira error: {e}"))
+            }
+        })?;
+
+    let description = adf_to_text(&ticket.fields.description).unwrap_or_default();
+    let endpoints = extract_endpoints(&format!("{}\n{description}", ticket.fields.summary));
+    if endpoints.is_empty() {
+        return Err(ApiError::Validation(format!(
+            "No endpoints found in {key}; mention them as \"METHOD /path\""
+        )));
+    }
+
+    let name = format!("{key}: {}", ticket.fields.summary);
+    let collection = build_collection(
+        &name,
+        &format!("Generated from Jira ticket {key}."),
+        &endpoints,
+    );
+    let endpoints = endpoints.into_iter().map(Into::into).collect();
+
+    if request.dry_run {
+        return Ok((
+            StatusCode::OK,
+            Json(GeneratedCollectionResponse {
+                collection_id: None,
+                name,
+                endpoints,
+                collection: Some(collection),
+            }),
+        ));
+    }
+
+    let created = postman_client(&state, &user.tenant)?
+        .create_collection(request.workspace_id.as_deref(), &collection)
+        .await
+        .map_err(postman_error)?;
+    tracing::info!(ticket_key = %key, collection_id = %created.id, "Postman collection generated");
+    Ok((
+        StatusCode::CREATED,
+        Json(GeneratedCollectionResponse {
+            collection_id: Some(created.id),
+            name: created.name,
+            endpoints,
+            collection: None,
+        }),
+    ))
+}
+
+/// Get the collection cache status.
+#[utoipa::path(
+    get,
+    path = "/api/v1/postman/cache",
+    responses(
+        (status = 200, description = "Cache status", body = CacheStatusResponse)
+    ),
+    tag = "Postman"
+)]
+pub async fn get_cache_status(State(state): State<AppState>) -> ApiResult<Json<CacheStatusResponse>> {
+    let status = PostmanCache::new(state.db.clone())
+        .status()
+        .await
+        .map_err(postman_error)?;
+    Ok(Json(status.into()))
+}
+
+/// Refresh the collection cache now.
+#[utoipa::path(
+    post,
+    path = "/api/v1/postman/cache/refresh",
+    responses(
+        (status = 200, description = "Cache refreshed", body = CacheRefreshResponse),
+        (status = 502, description = "Postman request failed"),
+        (status = 503, description = "Postman not configured")
+    ),
+    tag = "Postman"
+)]
+pub async fn refresh_cache(State(state): State<AppState>) -> ApiResult<Json<CacheRefreshResponse>> {
+    // The cache is shared, so it is filled with the instance's collections.
+    let client = instance_postman_client(&state)?;
+    let report = PostmanCache::new(state.db.clone())
+        .refresh(&client)
+        .await
+        .map_err(postman_error)?;
+    Ok(Json(report.into()))
+}
+
+/// List Postman environments.
+#[utoipa::path(
+    get,
+    path = "/api/v1/postman/environments",
+    params(ListEnvironmentsQuery),
+    responses(
+        (status = 200, description = "Environments", body = Vec<EnvironmentSummaryResponse>),
+        (status = 403, description = "Outside the Postman workspace policy"),
+        (status = 502, description = "Postman request failed"),
+        (status = 503, description = "Postman not configured")
+    ),
+    tag = "Postman"
+)]
+pub async fn list_environments(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Query(query): Query<ListEnvironmentsQuery>,
+) -> ApiResult<Json<Vec<EnvironmentSummaryResponse>>> {
+    let environments = postman_client(&state, &user.tenant)?
+        .list_environments(query.workspace_id.as_deref())
+        .await
+        .map_err(postman_error)?;
+    Ok(Json(environments.into_iter().map(Into::into).collect()))
+}
+
+/// Get an environment with its variables.
+///
+/// Secret values are masked.
+#[utoipa::path(
+    get,
+    path = "/api/v1/postman/environments/{id}",
+    params(("id" = String, Path, description = "Postman environment ID")),
+    responses(
+        (status = 200, description = "Environment", body = EnvironmentResponse),
+        (status = 404, description = "Environment not found"),
+        (status = 502, description = "Postman request failed"),
+        (status = 503, description = "Postman not configured")
+    ),
+    tag = "Postman"
+)]
+pub async fn get_environment(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Path(environment_id): Path<String>,
+) -> ApiResult<Json<EnvironmentResponse>> {
+    let environment = postman_client(&state, &user.tenant)?
+        .get_environment(&environment_id)
+        .await
+        .map_err(postman_error)?;
+    Ok(Json(environment.into()))
+}
+
+/// Create an environment.
+#[utoipa::path(
+    post,
+    path = "/api/v1/postman/environments",
+    request_body = EnvironmentRequest,
+    responses(
+        (status = 201, description = "Environment created", body = EnvironmentRefResponse),
+        (status = 422, description = "Invalid request", body = qa_pms_core::error::ErrorResponse),
+        (status = 403, description = "Outside the Postman workspace policy"),
+        (status = 502, description = "Postman request failed"),
+        (status = 503, description = "Postman not configured")
+    ),
+    tag = "Postman"
+)]
+pub async fn create_environment(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    ValidatedJson(request): ValidatedJson<EnvironmentRequest>,
+) -> ApiResult<(StatusCode, Extension<AuditChange>, Json<EnvironmentRefResponse>)> {
+    let created = postman_client(&state, &user.tenant)?
+        .create_environment(request.workspace_id.as_deref(), &request.to_input(None))
+        .await
+        .map_err(postman_error)?;
+    tracing::info!(environment_id = %created.id, "Postman environment created");
+    let created = EnvironmentRefResponse::from(created);
+    let change = AuditChange::new(&created.id).after(&created);
+    Ok((StatusCode::CREATED, change.into_extension(), Json(created)))
+}
+
+/// Replace an environment's name and variables.
+///
+/// Secret variables sent with the masked value keep their current value.
+#[utoipa::path(
+    put,
+    path = "/api/v1/postman/environments/{id}",
+    params(("id" = String, Path, description = "Postman environment ID")),
+    request_body = EnvironmentRequest,
+    responses(
+        (status = 200, description = "Environment updated", body = EnvironmentRefResponse),
+        (status = 404, description = "Environment not found"),
+        (status = 422, description = "Invalid request", body = qa_pms_core::error::ErrorResponse),
+        (status = 403, description = "Outside the Postman workspace policy"),
+        (status = 502, description = "Postman request failed"),
+        (status = 503, description = "Postman not configured")
+    ),
+    tag = "Postman"
+)]
+pub async fn update_environment(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Path(environment_id): Path<String>,
+    ValidatedJson(request): ValidatedJson<EnvironmentRequest>,
+) -> ApiResult<(Extension<AuditChange>, Json<EnvironmentRefResponse>)> {
+    let client = postman_client(&state, &user.tenant)?;
+    let existing = client
+        .get_environment(&environment_id)
+        .await
+        .map_err(postman_error)?;
+    let updated = client
+        .update_environment(&environment_id, &request.to_input(Some(&existing)))
+        .await
+        .map_err(postman_error)?;
+    tracing::info!(environment_id = %environment_id, "Postman environment updated");
+    let updated = EnvironmentRefResponse::from(updated);
+    let change = AuditChange::new(&environment_id)
+        .before(&serde_json::json!({ "name": existing.name }))
+        .after(&updated);
+    Ok((change.into_extension(), Json(updated)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use qa_pms_postman::AssertionResult;
+
+    #[test]
+    fn test_execution_response_marks_failures() {
+        let response = RequestExecutionResponse::from(RequestExecution {
+            name: "Create order".to_string(),
+            method: Some("POST".to_string()),
+            url: None,
+            status_code: Some(500),
+            response_time_ms: Some(12),
+            error: None,
+            assertions: vec![AssertionResult {
+                name: "Status is 201".to_string(),
+                passed: false,
+                error: Some("expected 500 to equal 201".to_string()),
+            }],
+        });
+        assert!(!response.passed);
+        assert_eq!(response.assertions.len(), 1);
+    }
+
+    #[test]
+    fn test_environment_secrets_masked_and_preserved() {
+        let existing = Environment {
+            id: Some("env-1".to_string()),
+            name: "Staging".to_string(),
+            values: vec![
+                EnvironmentVariable {
+                    key: "baseUrl".to_string(),
+                    value: "https://staging.example.com".to_string(),
+                    enabled: true,
+                    variable_type: "default".to_string(),
+                },
+                EnvironmentVariable {
+                    key: "token".to_string(),
+                    value: "s3cret".to_string(),
+                    enabled: true,
+                    variable_type: "secret".to_string(),
+                },
+            ],
+        };
+
+        let response = EnvironmentResponse::from(existing.clone());
+        assert_eq!(response.variables[0].value, "https://staging.example.com");
+        assert_eq!(response.variables[1].value, SECRET_MASK);
+
+        let request = EnvironmentRequest {
+            name: " Staging ".to_string(),
+            workspace_id: None,
+            variables: response.variables,
+        };
+        let input = request.to_input(Some(&existing));
+        assert_eq!(input.name, "Staging");
+        assert_eq!(input.values, existing.values);
+    }
+
+    #[test]
+    fn test_postman_error_mapping() {
+        assert_eq!(postman_error(PostmanError::NotFound("col".into())).status_code(), 404);
+        assert_eq!(postman_error(PostmanError::Runner("no newman".into())).status_code(), 500);
+        assert_eq!(postman_error(PostmanError::Unauthorized).status_code(), 502);
+        assert_eq!(postman_error(PostmanError::Forbidden("read-only".into())).status_code(), 403);
+    }
+}
@@ -0,0 +1,276 @@
+//! Project API endpoints.
+//!
+//! Projects are registered by Jira key. The `projectId` query parameter
+//! accepted by the dashboard, workflow, ticket and alert lists is resolved
+//! here to the project whose key scopes them.
+
+use std::collections::HashMap;
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    routing::get,
+    Extension, Json, Router,
+};
+use chrono::Utc;
+use serde::Deserialize;
+use tracing::info;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+use validator::{Validate, ValidationError};
+
+use qa_pms_core::error::ApiError;
+use qa_pms_core::Project;
+use qa_pms_workflow::get_template;
+
+use crate::app::AppState;
+use crate::audit::AuditChange;
+use crate::validation::{not_blank, ValidatedJson};
+
+type ApiResult<T> = Result<T, ApiError>;
+
+/// Create the project router.
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/api/v1/projects", get(list_projects).post(create_project))
+        .route(
+            "/api/v1/projects/:id",
+            get(get_project).put(update_project).delete(delete_project),
+        )
+}
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/// Project selector for scoped lists.
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectQuery {
+    /// Only include this project's tickets
+    pub project_id: Option<Uuid>,
+}
+
+impl ProjectQuery {
+    /// Look up the selected project; `None` when no project is selected.
+    pub(crate) async fn resolve(&self, state: &AppState) -> ApiResult<Option<Project>> {
+        match self.project_id {
+            Some(id) => find(state, id).await.map(Some),
+            None => Ok(None),
+        }
+    }
+}
+
+/// Request to create or replace a project.
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectRequest {
+    /// Jira project key (e.g., "PAY")
+    #[validate(custom(function = "valid_key"))]
+    pub key: String,
+    /// Display name
+    #[validate(custom(function = "not_blank"), length(max = 200))]
+    pub name: String,
+    /// Default workflow template per ticket type; `*` applies to any type
+    #[serde(default)]
+    pub default_templates: HashMap<String, Uuid>,
+}
+
+fn valid_key(key: &str) -> Result<(), ValidationError> {
+    if Project::is_valid_key(key) {
+        Ok(())
+    } else {
+        Err(ValidationError::new("project_key")
+            .with_message("must be an uppercase Jira project key, e.g. PAY".into()))
+    }
+}
+
+impl ProjectRequest {
+    /// Copy the editable fields onto a project.
+    fn apply(self, project: &mut Project) {
+        project.key = self.key;
+        project.name = self.name.trim().to_string();
+        project.default_templates = self
+            .default_templates
+            .into_iter()
+            .map(|(ticket_type, id)| (ticket_type.trim().to_string(), id))
+            .collect();
+    }
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+fn repo_error(e: anyhow::Error) -> ApiError {
+    ApiError::Internal(e)
+}
+
+async fn find(state: &AppState, id: Uuid) -> ApiResult<Project> {
+    state
+        .projects
+        .get(id)
+        .await
+        .map_err(repo_error)?
+        .ok_or_else(|| ApiError::NotFound(format!("Project {id}")))
+}
+
+/// Reject a key held by another project and templates that do not exist.
+async fn check(state: &AppState, project: &Project) -> ApiResult<()> {
+    let holder = state
+        .projects
+        .find_by_key(&project.key)
+        .await
+        .map_err(repo_error)?;
+    if holder.is_some_and(|holder| holder.id != project.id) {
+        return Err(ApiError::Conflict(format!(
+            "Project key {} is already in use",
+            project.key
+        )));
+    }
+    for (ticket_type, id) in &project.default_templates {
+        let template = get_template(&state.db, *id)
+            .await
+            .map_err(|e| ApiError::Internal(e.into()))?
+            .filter(|template| template.archived_at.is_none());
+        if template.is_none() {
+            return Err(ApiError::Validation(format!(
+                "Default template for {ticket_type} not found: {id}"
+            )));
+        }
+    }
+    Ok(())
+}
+
+// ============================================================================
+// Handlers
+// ============================================================================
+
+/// List projects by key.
+#[utoipa::path(
+    get,
+    path = "/api/v1/projects",
+    responses(
+        (status = 200, description = "Projects", body = Vec<Project>)
+    ),
+    tag = "Projects"
+)]
+pub async fn list_projects(State(state): State<AppState>) -> ApiResult<Json<Vec<Project>>> {
+    Ok(Json(state.projects.list().await.map_err(repo_error)?))
+}
+
+/// Get a project.
+#[utoipa::path(
+    get,
+    path = "/api/v1/projects/{id}",
+    params(("id" = Uuid, Path, description = "Project ID")),
+    responses(
+        (status = 200, description = "Project", body = Project),
+        (status = 404, description = "Project not found")
+    ),
+    tag = "Projects"
+)]
+pub async fn get_project(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> ApiResult<Json<Project>> {
+    Ok(Json(find(&state, id).await?))
+}
+
+/// Register a project.
+#[utoipa::path(
+    post,
+    path = "/api/v1/projects",
+    request_body = ProjectRequest,
+    responses(
+        (status = 201, description = "Project created", body = Project),
+        (status = 400, description = "Default template not found"),
+        (status = 409, description = "Project key already in use"),
+        (status = 422, description = "Invalid request", body = qa_pms_core::error::ErrorResponse)
+    ),
+    tag = "Projects"
+)]
+pub async fn create_project(
+    State(state): State<AppState>,
+    ValidatedJson(request): ValidatedJson<ProjectRequest>,
+) -> ApiResult<(StatusCode, Extension<AuditChange>, Json<Project>)> {
+    let mut project = Project::new(String::new(), String::new());
+    request.apply(&mut project);
+    check(&state, &project).await?;
+    let project = state.projects.save(project).await.map_err(repo_error)?;
+
+    info!(project_id = %project.id, key = %project.key, "Project created");
+    let change = AuditChange::new(project.id).after(&project);
+    Ok((StatusCode::CREATED, change.into_extension(), Json(project)))
+}
+
+/// Replace a project's key, name and default templates.
+#[utoipa::path(
+    put,
+    path = "/api/v1/projects/{id}",
+    params(("id" = Uuid, Path, description = "Project ID")),
+    request_body = ProjectRequest,
+    responses(
+        (status = 200, description = "Project updated", body = Project),
+        (status = 400, description = "Default template not found"),
+        (status = 404, description = "Project not found"),
+        (status = 409, description = "Project key already in use"),
+        (status = 422, description = "Invalid request", body = qa_pms_core::error::ErrorResponse)
+    ),
+    tag = "Projects"
+)]
+pub async fn update_project(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    ValidatedJson(request): ValidatedJson<ProjectRequest>,
+) -> ApiResult<(Extension<AuditChange>, Json<Project>)> {
+    let mut project = find(&state, id).await?;
+    let change = AuditChange::new(id).before(&project);
+    request.apply(&mut project);
+    project.updated_at = Utc::now();
+    check(&state, &project).await?;
+    let project = state.projects.save(project).await.map_err(repo_error)?;
+    Ok((change.after(&project).into_extension(), Json(project)))
+}
+
+/// Delete a project.
+///
+/// Workflows and alerts for its tickets are kept.
+#[utoipa::path(
+    delete,
+    path = "/api/v1/projects/{id}",
+    params(("id" = Uuid, Path, description = "Project ID")),
+    responses(
+        (status = 204, description = "Project deleted"),
+        (status = 404, description = "Project not found")
+    ),
+    tag = "Projects"
+)]
+pub async fn delete_project(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> ApiResult<(StatusCode, Extension<AuditChange>)> {
+    let before = find(&state, id).await?;
+    if !state.projects.delete(id).await.map_err(repo_error)? {
+        return Err(ApiError::NotFound(format!("Project {id}")));
+    }
+    let change = AuditChange::new(id).before(&before);
+    Ok((StatusCode::NO_CONTENT, change.into_extension()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_request_validation() {
+        let request = |key: &str, name: &str| ProjectRequest {
+            key: key.to_string(),
+            name: name.to_string(),
+            default_templates: HashMap::new(),
+        };
+        assert!(request("PAY", "Payments").validate().is_ok());
+        assert!(request("pay", "Payments").validate().is_err());
+        assert!(request("PAY", "  ").validate().is_err());
+    }
+}
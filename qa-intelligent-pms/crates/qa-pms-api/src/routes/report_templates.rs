@@ -0,0 +1,462 @@
+//! Report template API endpoints.
+//!
+//! A report template picks which sections a report shows and in what order,
+//! plus a branding header and fixed custom fields (client name, contract
+//! number, ...). Templates are stored per installation; a report keeps a
+//! copy of the layout it was generated with.
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    routing::get,
+    Extension, Json, Router,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::info;
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::{Validate, ValidationError};
+
+use qa_pms_core::error::ApiError;
+
+use crate::app::AppState;
+use crate::audit::AuditChange;
+use crate::validation::{not_blank, ValidatedJson};
+
+type ApiResult<T> = Result<T, ApiError>;
+
+/// Create the report templates router.
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/api/v1/reports/templates",
+            get(list_templates).post(create_template),
+        )
+        .route(
+            "/api/v1/reports/templates/:id",
+            get(get_template)
+                .put(update_template)
+                .delete(delete_template),
+        )
+}
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/// Report section.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum ReportSection {
+    /// Template, generation time and total time
+    Summary,
+    /// Step table
+    Steps,
+    /// Step notes
+    Notes,
+    /// Tests covered
+    TestsCovered,
+    /// Testing strategies
+    Strategies,
+    /// Template custom fields
+    CustomFields,
+}
+
+/// Branding shown around the report.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct ReportBranding {
+    /// Line above the title (e.g., company or client name)
+    #[validate(length(max = 200))]
+    pub header: Option<String>,
+    /// Logo image URL
+    #[validate(url)]
+    pub logo_url: Option<String>,
+    /// Footer text
+    #[validate(length(max = 1000))]
+    pub footer: Option<String>,
+}
+
+/// Fixed label/value pair printed on every report.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct ReportCustomField {
+    /// Label
+    #[validate(custom(function = "not_blank"), length(max = 100))]
+    pub label: String,
+    /// Value
+    #[validate(length(max = 1000))]
+    pub value: String,
+}
+
+/// Sections, branding and custom fields of a report.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ReportLayout {
+    /// Sections in display order
+    pub sections: Vec<ReportSection>,
+    /// Branding
+    #[serde(default)]
+    pub branding: ReportBranding,
+    /// Custom fields
+    #[serde(default)]
+    pub custom_fields: Vec<ReportCustomField>,
+}
+
+impl Default for ReportLayout {
+    /// Every built-in section, no branding.
+    fn default() -> Self {
+        Self {
+            sections: vec![
+                ReportSection::Summary,
+                ReportSection::Steps,
+                ReportSection::Notes,
+                ReportSection::TestsCovered,
+                ReportSection::Strategies,
+            ],
+            branding: ReportBranding::default(),
+            custom_fields: Vec::new(),
+        }
+    }
+}
+
+/// Create or replace a report template.
+#[derive(Debug, Deserialize, ToSchema, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct ReportTemplateRequest {
+    /// Template name
+    #[validate(custom(function = "not_blank"), length(max = 100))]
+    pub name: String,
+    /// Sections in display order
+    #[validate(custom(function = "validate_sections"))]
+    pub sections: Vec<ReportSection>,
+    /// Branding
+    #[serde(default)]
+    #[validate(nested)]
+    pub branding: ReportBranding,
+    /// Custom fields
+    #[serde(default)]
+    #[validate(nested)]
+    pub custom_fields: Vec<ReportCustomField>,
+    /// Use when a report request names no template
+    #[serde(default)]
+    pub is_default: bool,
+}
+
+fn validate_sections(sections: &[ReportSection]) -> Result<(), ValidationError> {
+    if sections.is_empty() {
+        return Err(
+            ValidationError::new("length").with_message("must include at least one section".into())
+        );
+    }
+    let mut seen = Vec::with_capacity(sections.len());
+    for section in sections {
+        if seen.contains(section) {
+            return Err(
+                ValidationError::new("duplicate").with_message("sections must be unique".into())
+            );
+        }
+        seen.push(*section);
+    }
+    Ok(())
+}
+
+impl ReportTemplateRequest {
+    fn layout(&self) -> ReportLayout {
+        ReportLayout {
+            sections: self.sections.clone(),
+            branding: self.branding.clone(),
+            custom_fields: self.custom_fields.clone(),
+        }
+    }
+}
+
+/// Stored report template.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ReportTemplateResponse {
+    /// Template ID
+    pub id: Uuid,
+    /// Template name
+    pub name: String,
+    /// Layout
+    #[serde(flatten)]
+    pub layout: ReportLayout,
+    /// Default template
+    pub is_default: bool,
+    /// Creation time
+    pub created_at: DateTime<Utc>,
+    /// Last update
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(sqlx::FromRow)]
+struct ReportTemplateRow {
+    id: Uuid,
+    name: String,
+    layout: sqlx::types::Json<ReportLayout>,
+    is_default: bool,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+impl From<ReportTemplateRow> for ReportTemplateResponse {
+    fn from(row: ReportTemplateRow) -> Self {
+        Self {
+            id: row.id,
+            name: row.name,
+            layout: row.layout.0,
+            is_default: row.is_default,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+        }
+    }
+}
+
+const TEMPLATE_COLUMNS: &str = "id, name, layout, is_default, created_at, updated_at";
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+fn db_error(e: sqlx::Error) -> ApiError {
+    ApiError::Internal(e.into())
+}
+
+/// Layout for a new report: the named template, else the default template,
+/// else every built-in section.
+///
+/// # Errors
+/// Returns `NotFound` if `template_id` does not exist.
+pub(crate) async fn resolve_layout(
+    db: &sqlx::PgPool,
+    template_id: Option<Uuid>,
+) -> ApiResult<ReportLayout> {
+    let layout: Option<sqlx::types::Json<ReportLayout>> = match template_id {
+        Some(id) => Some(
+            sqlx::query_scalar("SELECT layout FROM report_templates WHERE id = $1")
+                .bind(id)
+                .fetch_optional(db)
+                .await
+                .map_err(db_error)?
+                .ok_or_else(|| ApiError::NotFound(format!("Report template {id}")))?,
+        ),
+        None => sqlx::query_scalar("SELECT layout FROM report_templates WHERE is_default LIMIT 1")
+            .fetch_optional(db)
+            .await
+            .map_err(db_error)?,
+    };
+    Ok(layout.map(|l| l.0).unwrap_or_default())
+}
+
+async fn clear_default(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    except: Uuid,
+) -> ApiResult<()> {
+    sqlx::query("UPDATE report_templates SET is_default = false WHERE is_default AND id <> $1")
+        .bind(except)
+        .execute(&mut **tx)
+        .await
+        .map_err(db_error)?;
+    Ok(())
+}
+
+// ============================================================================
+// Handlers
+// ============================================================================
+
+/// List report templates.
+#[utoipa::path(
+    get,
+    path = "/api/v1/reports/templates",
+    responses(
+        (status = 200, description = "Report templates", body = Vec<ReportTemplateResponse>)
+    ),
+    tag = "Reports"
+)]
+pub async fn list_templates(
+    State(state): State<AppState>,
+) -> ApiResult<Json<Vec<ReportTemplateResponse>>> {
+    let rows: Vec<ReportTemplateRow> = sqlx::query_as(&format!(
+        "SELECT {TEMPLATE_COLUMNS} FROM report_templates ORDER BY is_default DESC, name"
+    ))
+    .fetch_all(&state.db)
+    .await
+    .map_err(db_error)?;
+    Ok(Json(rows.into_iter().map(Into::into).collect()))
+}
+
+/// Get a report template.
+#[utoipa::path(
+    get,
+    path = "/api/v1/reports/templates/{id}",
+    params(("id" = Uuid, Path, description = "Report template ID")),
+    responses(
+        (status = 200, description = "Report template", body = ReportTemplateResponse),
+        (status = 404, description = "Template not found")
+    ),
+    tag = "Reports"
+)]
+pub async fn get_template(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> ApiResult<Json<ReportTemplateResponse>> {
+    Ok(Json(fetch_template(&state, id).await?))
+}
+
+/// Get a report template, or fail with `NotFound`.
+async fn fetch_template(state: &AppState, id: Uuid) -> ApiResult<ReportTemplateResponse> {
+    let row: ReportTemplateRow = sqlx::query_as(&format!(
+        "SELECT {TEMPLATE_COLUMNS} FROM report_templates WHERE id = $1"
+    ))
+    .bind(id)
+    .fetch_optional(&state.db)
+    .await
+    .map_err(db_error)?
+    .ok_or_else(|| ApiError::NotFound(format!("Report template {id}")))?;
+    Ok(row.into())
+}
+
+/// Create a report template.
+#[utoipa::path(
+    post,
+    path = "/api/v1/reports/templates",
+    request_body = ReportTemplateRequest,
+    responses(
+        (status = 201, description = "Template created", body = ReportTemplateResponse),
+        (status = 422, description = "Invalid request", body = qa_pms_core::error::ErrorResponse)
+    ),
+    tag = "Reports"
+)]
+pub async fn create_template(
+    State(state): State<AppState>,
+    ValidatedJson(request): ValidatedJson<ReportTemplateRequest>,
+) -> ApiResult<(StatusCode, Extension<AuditChange>, Json<ReportTemplateResponse>)> {
+    let id = Uuid::new_v4();
+    let mut tx = state.db.begin().await.map_err(db_error)?;
+    if request.is_default {
+        clear_default(&mut tx, id).await?;
+    }
+    let row: ReportTemplateRow = sqlx::query_as(&format!(
+        r"
+        INSERT INTO report_templates (id, name, layout, is_default, created_at, updated_at)
+        VALUES ($1, $2, $3, $4, NOW(), NOW())
+        RETURNING {TEMPLATE_COLUMNS}
+        "
+    ))
+    .bind(id)
+    .bind(request.name.trim())
+    .bind(sqlx::types::Json(request.layout()))
+    .bind(request.is_default)
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(db_error)?;
+    tx.commit().await.map_err(db_error)?;
+
+    info!(template_id = %id, name = %row.name, "Report template created");
+    let created = ReportTemplateResponse::from(row);
+    let change = AuditChange::new(id).after(&created);
+    Ok((StatusCode::CREATED, change.into_extension(), Json(created)))
+}
+
+/// Replace a report template.
+///
+/// Reports already generated keep the layout they were generated with.
+#[utoipa::path(
+    put,
+    path = "/api/v1/reports/templates/{id}",
+    params(("id" = Uuid, Path, description = "Report template ID")),
+    request_body = ReportTemplateRequest,
+    responses(
+        (status = 200, description = "Template updated", body = ReportTemplateResponse),
+        (status = 404, description = "Template not found"),
+        (status = 422, description = "Invalid request", body = qa_pms_core::error::ErrorResponse)
+    ),
+    tag = "Reports"
+)]
+pub async fn update_template(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    ValidatedJson(request): ValidatedJson<ReportTemplateRequest>,
+) -> ApiResult<(Extension<AuditChange>, Json<ReportTemplateResponse>)> {
+    let before = fetch_template(&state, id).await?;
+    let mut tx = state.db.begin().await.map_err(db_error)?;
+    if request.is_default {
+        clear_default(&mut tx, id).await?;
+    }
+    let row: ReportTemplateRow = sqlx::query_as(&format!(
+        r"
+        UPDATE report_templates
+        SET name = $2, layout = $3, is_default = $4, updated_at = NOW()
+        WHERE id = $1
+        RETURNING {TEMPLATE_COLUMNS}
+        "
+    ))
+    .bind(id)
+    .bind(request.name.trim())
+    .bind(sqlx::types::Json(request.layout()))
+    .bind(request.is_default)
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(db_error)?
+    .ok_or_else(|| ApiError::NotFound(format!("Report template {id}")))?;
+    tx.commit().await.map_err(db_error)?;
+
+    let updated = ReportTemplateResponse::from(row);
+    let change = AuditChange::new(id).before(&before).after(&updated);
+    Ok((change.into_extension(), Json(updated)))
+}
+
+/// Delete a report template.
+#[utoipa::path(
+    delete,
+    path = "/api/v1/reports/templates/{id}",
+    params(("id" = Uuid, Path, description = "Report template ID")),
+    responses(
+        (status = 204, description = "Template deleted"),
+        (status = 404, description = "Template not found")
+    ),
+    tag = "Reports"
+)]
+pub async fn delete_template(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> ApiResult<(StatusCode, Extension<AuditChange>)> {
+    let before = fetch_template(&state, id).await?;
+    let deleted = sqlx::query("DELETE FROM report_templates WHERE id = $1")
+        .bind(id)
+        .execute(&state.db)
+        .await
+        .map_err(db_error)?
+        .rows_affected();
+    if deleted == 0 {
+        return Err(ApiError::NotFound(format!("Report template {id}")));
+    }
+    let change = AuditChange::new(id).before(&before);
+    Ok((StatusCode::NO_CONTENT, change.into_extension()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_sections() {
+        assert!(validate_sections(&[ReportSection::Steps, ReportSection::Notes]).is_ok());
+        assert!(validate_sections(&[]).is_err());
+        assert!(validate_sections(&[ReportSection::Steps, ReportSection::Steps]).is_err());
+    }
+
+    #[test]
+    fn test_layout_deserializes_with_defaults() {
+        let layout: ReportLayout =
+            serde_json::from_str(r#"{"sections":["steps","customFields"]}"#).unwrap();
+        assert_eq!(
+            layout.sections,
+            vec![ReportSection::Steps, ReportSection::CustomFields]
+        );
+        assert_eq!(layout.branding, ReportBranding::default());
+        assert!(layout.custom_fields.is_empty());
+    }
+}
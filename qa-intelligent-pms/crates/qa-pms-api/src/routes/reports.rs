@@ -1,23 +1,38 @@
 //! Report generation API endpoints.
 //!
 //! Refactored to use unified `ApiError` for cleaner error handling.
+//! Reports can be exported as Markdown, HTML or PDF, and posted back to the
+//! originating Jira ticket when generated.
 
 use axum::{
-    extract::{Path, State},
-    http::StatusCode,
+    extract::{Path, Query, State},
+    http::{header, StatusCode},
     response::IntoResponse,
     routing::{get, post},
     Json, Router,
 };
+use chrono::SubsecRound;
+use secrecy::ExposeSecret;
 use serde::{Deserialize, Serialize};
-use tracing::info;
+use tracing::{info, warn};
 use utoipa::ToSchema;
 use uuid::Uuid;
 
-use qa_pms_workflow::{get_instance, get_step_results, get_template};
+use qa_pms_time::get_workflow_sessions;
+use qa_pms_workflow::{get_instance_template, get_step_results};
 
 use crate::app::AppState;
+use crate::audit::AuditChange;
+use crate::auth::CurrentUser;
+use crate::report_aggregate::{AggregateReport, AggregateScope};
+use crate::report_compare::{self, ReportComparison};
+use crate::report_export::{render_adf, ExportFormat};
+use crate::report_integrity::{self, ReportVerification};
+use crate::routes::report_templates::{resolve_layout, ReportLayout};
+use crate::routes::workflows::fetch_instance;
+use crate::search_index::IndexDocument;
 use qa_pms_core::error::ApiError;
+use qa_pms_core::TenantId;
 
 /// Result type alias for API handlers.
 type ApiResult<T> = Result<T, ApiError>;
@@ -37,7 +52,12 @@ impl<T> SqlxResultExt<T> for Result<T, sqlx::Error> {
 pub fn router() -> Router<AppState> {
     Router::new()
         .route("/api/v1/reports", post(generate_report))
+        .route("/api/v1/reports/compare", get(compare_reports))
+        .route("/api/v1/reports/aggregate", get(aggregate_report))
+        .route("/api/v1/reports/aggregate/export", get(export_aggregate_report))
         .route("/api/v1/reports/:id", get(get_report))
+        .route("/api/v1/reports/:id/export", get(export_report))
+        .route("/api/v1/reports/:id/verify", get(verify_report))
         .route("/api/v1/reports/workflow/:workflow_id", get(get_report_by_workflow))
 }
 
@@ -51,6 +71,45 @@ pub fn router() -> Router<AppState> {
 pub struct GenerateReportRequest {
     pub workflow_instance_id: Uuid,
     pub ticket_title: Option<String>,
+    /// Report template to lay out the report with (default template if omitted)
+    pub report_template_id: Option<Uuid>,
+    /// Also post the report to the ticket in Jira
+    pub publish_to_jira: Option<PublishToJira>,
+}
+
+/// How a report is posted to its Jira ticket.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum JiraPublishMode {
+    /// Upload the rendered report as a file
+    Attachment,
+    /// Post the report as a comment
+    Comment,
+}
+
+/// Options for posting a generated report to Jira.
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PublishToJira {
+    /// Attachment or comment
+    pub mode: JiraPublishMode,
+    /// File format for attachments (default: markdown)
+    #[serde(default)]
+    pub format: ExportFormat,
+}
+
+/// Outcome of posting a report to Jira.
+///
+/// The report is saved even when posting fails; `error` says why.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct JiraPublication {
+    /// Attachment or comment
+    pub mode: JiraPublishMode,
+    /// Attachment or comment ID in Jira
+    pub id: Option<String>,
+    /// Error message if posting failed
+    pub error: Option<String>,
 }
 
 /// Report content structure.
@@ -62,6 +121,9 @@ pub struct ReportContent {
     pub notes: Vec<String>,
     pub tests_covered: Vec<String>,
     pub strategies: Vec<String>,
+    /// Layout of the report template in effect when the report was generated
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub layout: Option<ReportLayout>,
 }
 
 
@@ -74,6 +136,18 @@ pub struct ReportStep {
     pub status: String,
     pub notes: Option<String>,
     pub time_seconds: i32,
+    /// Links attached to the step (absent in reports generated before links were kept)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub links: Vec<ReportLink>,
+}
+
+/// Link attached to a report step.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ReportLink {
+    /// Link title
+    pub title: String,
+    /// Link URL
+    pub url: String,
 }
 
 /// Report response.
@@ -88,6 +162,47 @@ pub struct ReportResponse {
     pub content: ReportContent,
     pub total_time_seconds: i32,
     pub generated_at: String,
+    /// Hex SHA-256 of the report (absent for reports generated before checksums)
+    pub checksum: Option<String>,
+    /// Hex HMAC-SHA256 with the installation signing key, if one is configured
+    pub signature: Option<String>,
+    /// Result of posting to Jira, when requested
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub jira_publication: Option<JiraPublication>,
+}
+
+/// Query parameters for report export.
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct ExportQuery {
+    /// Output format: `markdown` (or `md`), `html` or `pdf` (default: markdown)
+    #[serde(default)]
+    pub format: ExportFormat,
+}
+
+/// Query parameters for report comparison.
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct CompareQuery {
+    /// Baseline report ID
+    pub a: Uuid,
+    /// Report ID compared against the baseline
+    pub b: Uuid,
+}
+
+/// Default length of an aggregate report period, in days.
+const DEFAULT_AGGREGATE_DAYS: i64 = 7;
+
+/// Upper bound on sprint tickets fetched from Jira for an aggregate report.
+const MAX_SPRINT_TICKETS: u32 = 1000;
+
+/// Query parameters for aggregate reports.
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct AggregateQuery {
+    /// First day, inclusive (default: 7 days before `to`, or unbounded with `sprint`)
+    pub from: Option<chrono::NaiveDate>,
+    /// Last day, inclusive (default: today)
+    pub to: Option<chrono::NaiveDate>,
+    /// Only tickets in this Jira sprint (name or ID)
+    pub sprint: Option<String>,
 }
 
 /// Database row for report queries.
@@ -101,6 +216,8 @@ struct ReportRow {
     content: serde_json::Value,
     total_time_seconds: i32,
     generated_at: chrono::DateTime<chrono::Utc>,
+    checksum: Option<String>,
+    signature: Option<String>,
 }
 
 impl From<ReportRow> for ReportResponse {
@@ -117,6 +234,9 @@ impl From<ReportRow> for ReportResponse {
             content,
             total_time_seconds: row.total_time_seconds,
             generated_at: row.generated_at.to_rfc3339(),
+            checksum: row.checksum,
+            signature: row.signature,
+            jira_publication: None,
         }
     }
 }
@@ -140,6 +260,123 @@ async fn fetch_report(
         .ok_or_else(|| ApiError::NotFound("Report not found".into()))
 }
 
+/// Load every stored report, for the search index.
+pub(crate) async fn all_reports(db: &sqlx::PgPool) -> Result<Vec<ReportResponse>, sqlx::Error> {
+    let rows = sqlx::query_as::<_, ReportRow>(
+        r"
+        SELECT id, workflow_instance_id, ticket_id, ticket_title, template_name, content, total_time_seconds, generated_at, checksum, signature
+        FROM workflow_reports
+        ",
+    )
+    .fetch_all(db)
+    .await?;
+    Ok(rows.into_iter().map(ReportResponse::from).collect())
+}
+
+/// Resolve the period and, for sprints, the ticket keys of an aggregate report
+/// over the caller's tenant.
+async fn aggregate_scope(
+    state: &AppState,
+    user: &CurrentUser,
+    query: AggregateQuery,
+) -> ApiResult<AggregateScope> {
+    let to = query.to.unwrap_or_else(|| chrono::Utc::now().date_naive());
+    let from = match (query.from, &query.sprint) {
+        (Some(from), _) => from,
+        (None, Some(_)) => chrono::NaiveDate::default(),
+        (None, None) => to - chrono::Duration::days(DEFAULT_AGGREGATE_DAYS - 1),
+    };
+    if from > to {
+        return Err(ApiError::Validation(format!(
+            "from ({from}) must not be after to ({to})"
+        )));
+    }
+    let start = from.and_time(chrono::NaiveTime::MIN).and_utc();
+    let end = (to + chrono::Duration::days(1))
+        .and_time(chrono::NaiveTime::MIN)
+        .and_utc();
+
+    let ticket_ids = match &query.sprint {
+        Some(sprint) => Some(sprint_ticket_keys(state, &user.tenant, sprint).await?),
+        None => None,
+    };
+
+    Ok(AggregateScope {
+        start,
+        end,
+        sprint: query.sprint,
+        ticket_ids,
+        tenant_id: Some(user.tenant.as_str().to_string()),
+    })
+}
+
+/// Keys of every ticket in a Jira sprint.
+async fn sprint_ticket_keys(
+    state: &AppState,
+    tenant: &TenantId,
+    sprint: &str,
+) -> ApiResult<Vec<String>> {
+    const PAGE_SIZE: u32 = 100;
+
+    let client = crate::routes::tickets::get_jira_client(state, tenant).await?;
+    let filters = qa_pms_jira::TicketFilters {
+        sprint: Some(sprint.to_string()),
+        ..Default::default()
+    };
+    let mut keys = Vec::new();
+    let mut start_at = 0;
+    while start_at < MAX_SPRINT_TICKETS {
+        let page = client
+            .list_tickets(&filters, start_at, PAGE_SIZE)
+            .await
+            .map_err(|e| ApiError::ServiceUnavailable(format!("Jira error: {e}")))?;
+        let fetched = u32::try_from(page.issues.len()).unwrap_or(u32::MAX);
+        keys.extend(page.issues.into_iter().map(|issue| issue.key));
+        start_at += fetched;
+        if fetched == 0 || start_at >= page.total {
+            break;
+        }
+    }
+    Ok(keys)
+}
+
+/// Post a report to its Jira ticket as an attachment or a comment.
+async fn publish_to_jira(
+    state: &AppState,
+    tenant: &TenantId,
+    report: &ReportResponse,
+    options: &PublishToJira,
+) -> Result<String, ApiError> {
+    let client = crate::routes::tickets::get_jira_client(state, tenant).await?;
+    let key = &report.ticket_id;
+    let id = match options.mode {
+        JiraPublishMode::Attachment => {
+            let format = options.format;
+            let filename = format!("qa-report-{key}.{}", format.extension());
+            client
+                .add_attachment(
+                    key,
+                    &filename,
+                    format.content_type(),
+                    format.render(report),
+                )
+                .await
+                .map_err(|e| ApiError::ExternalService(e.to_string()))?
+                .into_iter()
+                .next()
+                .map(|a| a.id)
+        }
+        JiraPublishMode::Comment => Some(
+            client
+                .add_comment(key, render_adf(report))
+                .await
+                .map_err(|e| ApiError::ExternalService(e.to_string()))?
+                .id,
+        ),
+    };
+    Ok(id.unwrap_or_default())
+}
+
 // ============================================================================
 // Handlers
 // ============================================================================
@@ -151,23 +388,21 @@ async fn fetch_report(
     request_body = GenerateReportRequest,
     responses(
         (status = 201, description = "Report generated", body = ReportResponse),
-        (status = 404, description = "Workflow not found"),
+        (status = 404, description = "Workflow or report template not found"),
         (status = 500, description = "Internal server error")
     ),
     tag = "Reports"
 )]
 pub async fn generate_report(
     State(state): State<AppState>,
+    user: CurrentUser,
     Json(request): Json<GenerateReportRequest>,
 ) -> ApiResult<impl IntoResponse> {
     // Get workflow instance
-    let instance = get_instance(&state.db, request.workflow_instance_id)
-        .await
-        .map_db_err()?
-        .ok_or_else(|| ApiError::NotFound("Workflow not found".into()))?;
+    let instance = fetch_instance(&state, request.workflow_instance_id, &user).await?;
 
     // Get template
-    let template = get_template(&state.db, instance.template_id)
+    let template = get_instance_template(&state.db, &instance)
         .await
         .map_db_err()?
         .ok_or_else(|| ApiError::NotFound("Template not found".into()))?;
@@ -177,6 +412,12 @@ pub async fn generate_report(
         .await
         .unwrap_or_default();
 
+    let sessions = get_workflow_sessions(&state.db, request.workflow_instance_id)
+        .await
+        .unwrap_or_default();
+
+    let layout = resolve_layout(&state.db, request.report_template_id).await?;
+
     // Build report content
     let steps: Vec<ReportStep> = template
         .steps()
@@ -189,10 +430,27 @@ pub async fn generate_report(
                 name: step.name.clone(),
                 status: result.map_or("pending".to_string(), |r| r.status.clone()),
                 notes: result.and_then(|r| r.notes.clone()),
-                time_seconds: 0, // TODO: Get from time sessions
+                time_seconds: sessions
+                    .iter()
+                    .filter(|s| s.step_index == i as i32)
+                    .map(|s| s.total_seconds)
+                    .sum(),
+                links: result
+                    .and_then(|r| r.links.as_ref())
+                    .map(|links| {
+                        links
+                            .iter()
+                            .map(|link| ReportLink {
+                                title: link.title.clone(),
+                                url: link.url.clone(),
+                            })
+                            .collect()
+                    })
+                    .unwrap_or_default(),
             }
         })
         .collect();
+    let total_time_seconds = steps.iter().map(|s| s.time_seconds).sum();
 
     let notes: Vec<String> = steps.iter().filter_map(|s| s.notes.clone()).collect();
 
@@ -201,44 +459,79 @@ pub async fn generate_report(
         notes,
         tests_covered: vec![],
         strategies: vec![],
+        layout: Some(layout),
     };
 
     let content_json = serde_json::to_value(&content).unwrap_or_default();
 
-    // Save report to database
+    // Postgres keeps microseconds; truncate so the checksum survives a reload
+    let generated_at = chrono::Utc::now().trunc_subsecs(6);
     let report_id = Uuid::new_v4();
+    let mut report = ReportResponse {
+        id: report_id,
+        workflow_instance_id: request.workflow_instance_id,
+        ticket_id: instance.ticket_id,
+        ticket_title: request.ticket_title,
+        template_name: template.name,
+        content,
+        total_time_seconds,
+        generated_at: generated_at.to_rfc3339(),
+        checksum: None,
+        signature: None,
+        jira_publication: None,
+    };
+    report.checksum = Some(report_integrity::checksum(&report));
+    report.signature = state
+        .settings
+        .report_signing_key
+        .as_ref()
+        .and_then(|key| report_integrity::sign(&report, key.expose_secret()));
+
+    // Save report to database
     sqlx::query(
         r"
-        INSERT INTO workflow_reports (id, workflow_instance_id, ticket_id, ticket_title, template_name, content, total_time_seconds)
-        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        INSERT INTO workflow_reports (id, workflow_instance_id, ticket_id, ticket_title, template_name, content, total_time_seconds, generated_at, checksum, signature)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
         ",
     )
     .bind(report_id)
     .bind(request.workflow_instance_id)
-    .bind(&instance.ticket_id)
-    .bind(&request.ticket_title)
-    .bind(&template.name)
+    .bind(&report.ticket_id)
+    .bind(&report.ticket_title)
+    .bind(&report.template_name)
     .bind(&content_json)
-    .bind(0i32)
+    .bind(report.total_time_seconds)
+    .bind(generated_at)
+    .bind(&report.checksum)
+    .bind(&report.signature)
     .execute(&state.db)
     .await
     .map_db_err()?;
 
     info!(report_id = %report_id, workflow_id = %request.workflow_instance_id, "Generated report");
+    state.search_index.upsert([IndexDocument::report(&report)]);
 
-    Ok((
-        StatusCode::CREATED,
-        Json(ReportResponse {
-            id: report_id,
-            workflow_instance_id: request.workflow_instance_id,
-            ticket_id: instance.ticket_id,
-            ticket_title: request.ticket_title,
-            template_name: template.name,
-            content,
-            total_time_seconds: 0,
-            generated_at: chrono::Utc::now().to_rfc3339(),
-        }),
-    ))
+    if let Some(options) = &request.publish_to_jira {
+        let publication = match publish_to_jira(&state, &user.tenant, &report, options).await {
+            Ok(id) => JiraPublication {
+                mode: options.mode,
+                id: Some(id),
+                error: None,
+            },
+            Err(e) => {
+                warn!(report_id = %report_id, error = %e, "Failed to post report to Jira");
+                JiraPublication {
+                    mode: options.mode,
+                    id: None,
+                    error: Some(e.to_string()),
+                }
+            }
+        };
+        report.jira_publication = Some(publication);
+    }
+
+    let change = AuditChange::new(report_id).after(&report);
+    Ok((StatusCode::CREATED, change.into_extension(), Json(report)))
 }
 
 /// Get a report by ID.
@@ -262,7 +555,7 @@ pub async fn get_report(
     fetch_report(
         &state.db,
         r"
-        SELECT id, workflow_instance_id, ticket_id, ticket_title, template_name, content, total_time_seconds, generated_at
+        SELECT id, workflow_instance_id, ticket_id, ticket_title, template_name, content, total_time_seconds, generated_at, checksum, signature
         FROM workflow_reports WHERE id = $1
         ",
         id,
@@ -271,6 +564,198 @@ pub async fn get_report(
     .map(Json)
 }
 
+/// Export a report as Markdown, HTML or PDF.
+#[utoipa::path(
+    get,
+    path = "/api/v1/reports/{id}/export",
+    params(
+        ("id" = Uuid, Path, description = "Report ID"),
+        ExportQuery
+    ),
+    responses(
+        (status = 200, description = "Rendered report", content(
+            (String = "text/markdown"),
+            (String = "text/html"),
+            (Vec<u8> = "application/pdf")
+        )),
+        (status = 404, description = "Report not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Reports"
+)]
+pub async fn export_report(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Query(query): Query<ExportQuery>,
+) -> ApiResult<impl IntoResponse> {
+    let report = fetch_report(
+        &state.db,
+        r"
+        SELECT id, workflow_instance_id, ticket_id, ticket_title, template_name, content, total_time_seconds, generated_at, checksum, signature
+        FROM workflow_reports WHERE id = $1
+        ",
+        id,
+    )
+    .await?;
+
+    let format = query.format;
+    let disposition = format!(
+        "inline; filename=\"report-{}.{}\"",
+        report.ticket_id,
+        format.extension()
+    );
+    Ok((
+        [
+            (header::CONTENT_TYPE, format.content_type().to_string()),
+            (header::CONTENT_DISPOSITION, disposition),
+        ],
+        format.render(&report),
+    ))
+}
+
+/// Check a report against its stored checksum and signature.
+#[utoipa::path(
+    get,
+    path = "/api/v1/reports/{id}/verify",
+    params(
+        ("id" = Uuid, Path, description = "Report ID")
+    ),
+    responses(
+        (status = 200, description = "Verification result", body = ReportVerification),
+        (status = 404, description = "Report not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Reports"
+)]
+pub async fn verify_report(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> ApiResult<Json<ReportVerification>> {
+    let report = fetch_report(
+        &state.db,
+        r"
+        SELECT id, workflow_instance_id, ticket_id, ticket_title, template_name, content, total_time_seconds, generated_at, checksum, signature
+        FROM workflow_reports WHERE id = $1
+        ",
+        id,
+    )
+    .await?;
+
+    let key = state.settings.report_signing_key.as_ref();
+    let verification = report_integrity::verify(&report, key.map(|k| k.expose_secret().as_str()));
+    if !verification.checksum_valid {
+        warn!(report_id = %id, "Report checksum mismatch");
+    }
+    Ok(Json(verification))
+}
+
+/// Compare two reports for the same ticket and workflow template.
+#[utoipa::path(
+    get,
+    path = "/api/v1/reports/compare",
+    params(CompareQuery),
+    responses(
+        (status = 200, description = "Step-by-step comparison", body = ReportComparison),
+        (status = 400, description = "Reports are for different tickets or templates"),
+        (status = 404, description = "Report not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Reports"
+)]
+pub async fn compare_reports(
+    State(state): State<AppState>,
+    Query(query): Query<CompareQuery>,
+) -> ApiResult<Json<ReportComparison>> {
+    let select = r"
+        SELECT id, workflow_instance_id, ticket_id, ticket_title, template_name, content, total_time_seconds, generated_at, checksum, signature
+        FROM workflow_reports WHERE id = $1
+        ";
+    let a = fetch_report(&state.db, select, query.a).await?;
+    let b = fetch_report(&state.db, select, query.b).await?;
+
+    if a.ticket_id != b.ticket_id {
+        return Err(ApiError::Validation(format!(
+            "Reports are for different tickets ({} and {})",
+            a.ticket_id, b.ticket_id
+        )));
+    }
+    if a.template_name != b.template_name {
+        return Err(ApiError::Validation(format!(
+            "Reports use different workflow templates ({} and {})",
+            a.template_name, b.template_name
+        )));
+    }
+
+    Ok(Json(report_compare::compare_reports(&a, &b)))
+}
+
+/// Summarize the workflows completed in a period or sprint.
+#[utoipa::path(
+    get,
+    path = "/api/v1/reports/aggregate",
+    params(AggregateQuery),
+    responses(
+        (status = 200, description = "Aggregate report", body = AggregateReport),
+        (status = 400, description = "Invalid date range"),
+        (status = 503, description = "Jira unavailable (sprint reports)"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Reports"
+)]
+pub async fn aggregate_report(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Query(query): Query<AggregateQuery>,
+) -> ApiResult<Json<AggregateReport>> {
+    let scope = aggregate_scope(&state, &user, query).await?;
+    AggregateReport::load(&state.db, &scope)
+        .await
+        .map_db_err()
+        .map(Json)
+}
+
+/// Export an aggregate report as Markdown or HTML.
+#[utoipa::path(
+    get,
+    path = "/api/v1/reports/aggregate/export",
+    params(AggregateQuery, ExportQuery),
+    responses(
+        (status = 200, description = "Rendered aggregate report", content(
+            (String = "text/markdown"),
+            (String = "text/html"),
+            (Vec<u8> = "application/pdf")
+        )),
+        (status = 400, description = "Invalid date range"),
+        (status = 503, description = "Jira unavailable (sprint reports)"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Reports"
+)]
+pub async fn export_aggregate_report(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Query(query): Query<AggregateQuery>,
+    Query(export): Query<ExportQuery>,
+) -> ApiResult<impl IntoResponse> {
+    let scope = aggregate_scope(&state, &user, query).await?;
+    let report = AggregateReport::load(&state.db, &scope).await.map_db_err()?;
+
+    let format = export.format;
+    let disposition = format!(
+        "inline; filename=\"qa-report-{}-{}.{}\"",
+        report.start.format("%Y%m%d"),
+        (report.end - chrono::Duration::days(1)).format("%Y%m%d"),
+        format.extension()
+    );
+    Ok((
+        [
+            (header::CONTENT_TYPE, format.content_type().to_string()),
+            (header::CONTENT_DISPOSITION, disposition),
+        ],
+        format.render_aggregate(&report),
+    ))
+}
+
 /// Get report by workflow ID.
 #[utoipa::path(
     get,
@@ -292,7 +777,7 @@ pub async fn get_report_by_workflow(
     fetch_report(
         &state.db,
         r"
-        SELECT id, workflow_instance_id, ticket_id, ticket_title, template_name, content, total_time_seconds, generated_at
+        SELECT id, workflow_instance_id, ticket_id, ticket_title, template_name, content, total_time_seconds, generated_at, checksum, signature
         FROM workflow_reports WHERE workflow_instance_id = $1
         ORDER BY generated_at DESC
         LIMIT 1
@@ -0,0 +1,388 @@
+//! Saved ticket filter API endpoints.
+//!
+//! Users save the filters they use on the ticket list under a name and
+//! list tickets with `GET /api/v1/tickets?filterId=...`. One filter can be
+//! marked as the default, applied whenever the list is requested without
+//! filters.
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    routing::{get, put},
+    Extension, Json, Router,
+};
+use serde::Deserialize;
+use tracing::info;
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::{Validate, ValidationError};
+
+use qa_pms_core::error::ApiError;
+use qa_pms_jira::jql;
+
+use crate::app::AppState;
+use crate::audit::AuditChange;
+use crate::auth::CurrentUser;
+use crate::saved_filters::{FilterFields, SaveOutcome, SavedFilter};
+use crate::validation::{not_blank, ValidatedJson};
+
+type ApiResult<T> = Result<T, ApiError>;
+
+/// Create the saved filters router.
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/api/v1/tickets/filters",
+            get(list_filters).post(create_filter),
+        )
+        .route(
+            "/api/v1/tickets/filters/:id",
+            get(get_filter).put(update_filter).delete(delete_filter),
+        )
+        .route(
+            "/api/v1/tickets/filters/:id/default",
+            put(set_default).delete(unset_default),
+        )
+}
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/// Request to create or replace a saved filter.
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SavedFilterRequest {
+    /// Name, unique per user
+    #[validate(custom(function = "not_blank"), length(max = 100))]
+    pub name: String,
+    /// Statuses to include
+    #[serde(default)]
+    #[validate(custom(function = "validate_statuses"))]
+    pub statuses: Vec<String>,
+    /// Project key
+    #[validate(length(max = 100))]
+    pub project: Option<String>,
+    /// Assignee email or account ID
+    #[validate(length(max = 255))]
+    pub assignee: Option<String>,
+    /// Extra JQL condition, combined with the other filters using AND
+    #[validate(custom(function = "validate_jql_condition"))]
+    pub jql: Option<String>,
+}
+
+impl From<SavedFilterRequest> for FilterFields {
+    fn from(request: SavedFilterRequest) -> Self {
+        let non_empty = |value: Option<String>| {
+            value
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+        };
+        Self {
+            name: request.name.trim().to_string(),
+            statuses: request
+                .statuses
+                .into_iter()
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+                .collect(),
+            project: non_empty(request.project),
+            assignee: non_empty(request.assignee),
+            jql: non_empty(request.jql),
+        }
+    }
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+fn db_error(e: sqlx::Error) -> ApiError {
+    ApiError::Internal(e.into())
+}
+
+fn validate_statuses(statuses: &[String]) -> Result<(), ValidationError> {
+    if statuses.len() > 50 || statuses.iter().any(|s| s.len() > 100) {
+        return Err(ValidationError::new("too_many_statuses")
+            .with_message("at most 50 statuses of up to 100 characters each".into()));
+    }
+    Ok(())
+}
+
+/// Check that a JQL condition can be combined with the other filters.
+///
+/// A blank condition is allowed and saved as none.
+fn validate_jql_condition(jql: &str) -> Result<(), ValidationError> {
+    if jql.trim().is_empty() {
+        return Ok(());
+    }
+    jql::validate_condition(jql)
+        .map_err(|e| ValidationError::new("invalid_jql").with_message(e.to_string().into()))
+}
+
+/// Load a filter the user owns.
+///
+/// Filters owned by someone else are reported as missing rather than
+/// forbidden, so their IDs are not confirmed to exist.
+pub(crate) async fn owned_filter(
+    state: &AppState,
+    user: &CurrentUser,
+    id: Uuid,
+) -> ApiResult<SavedFilter> {
+    state
+        .saved_filters
+        .get(id)
+        .await
+        .map_err(db_error)?
+        .filter(|filter| user.owns(&filter.owner))
+        .ok_or_else(|| ApiError::NotFound(format!("Saved filter {id}")))
+}
+
+// ============================================================================
+// Handlers
+// ============================================================================
+
+/// List the user's saved filters.
+#[utoipa::path(
+    get,
+    path = "/api/v1/tickets/filters",
+    responses(
+        (status = 200, description = "Saved filters", body = Vec<SavedFilter>)
+    ),
+    tag = "Tickets"
+)]
+pub async fn list_filters(
+    State(state): State<AppState>,
+    user: CurrentUser,
+) -> ApiResult<Json<Vec<SavedFilter>>> {
+    let filters = state
+        .saved_filters
+        .list(&user.owner(None))
+        .await
+        .map_err(db_error)?;
+    Ok(Json(filters))
+}
+
+/// Save a filter under a name.
+#[utoipa::path(
+    post,
+    path = "/api/v1/tickets/filters",
+    request_body = SavedFilterRequest,
+    responses(
+        (status = 201, description = "Filter saved", body = SavedFilter),
+        (status = 409, description = "Name already used by this user"),
+        (status = 422, description = "Invalid request", body = qa_pms_core::error::ErrorResponse)
+    ),
+    tag = "Tickets"
+)]
+pub async fn create_filter(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    ValidatedJson(request): ValidatedJson<SavedFilterRequest>,
+) -> ApiResult<(StatusCode, Extension<AuditChange>, Json<SavedFilter>)> {
+    let fields = FilterFields::from(request);
+    match state
+        .saved_filters
+        .create(&user.owner(None), &fields)
+        .await
+        .map_err(db_error)?
+    {
+        SaveOutcome::Saved(filter) => {
+            info!(filter_id = %filter.id, name = %filter.name, "Saved ticket filter");
+            let change = AuditChange::new(filter.id).after(&filter);
+            Ok((StatusCode::CREATED, change.into_extension(), Json(filter)))
+        }
+        SaveOutcome::NameTaken | SaveOutcome::NotFound => Err(ApiError::Conflict(format!(
+            "A filter named '{}' already exists",
+            fields.name
+        ))),
+    }
+}
+
+/// Get a saved filter.
+#[utoipa::path(
+    get,
+    path = "/api/v1/tickets/filters/{id}",
+    params(("id" = Uuid, Path, description = "Filter ID")),
+    responses(
+        (status = 200, description = "Saved filter", body = SavedFilter),
+        (status = 404, description = "Filter not found")
+    ),
+    tag = "Tickets"
+)]
+pub async fn get_filter(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Path(id): Path<Uuid>,
+) -> ApiResult<Json<SavedFilter>> {
+    owned_filter(&state, &user, id).await.map(Json)
+}
+
+/// Replace a saved filter.
+#[utoipa::path(
+    put,
+    path = "/api/v1/tickets/filters/{id}",
+    params(("id" = Uuid, Path, description = "Filter ID")),
+    request_body = SavedFilterRequest,
+    responses(
+        (status = 200, description = "Filter updated", body = SavedFilter),
+        (status = 404, description = "Filter not found"),
+        (status = 409, description = "Name already used by this user"),
+        (status = 422, description = "Invalid request", body = qa_pms_core::error::ErrorResponse)
+    ),
+    tag = "Tickets"
+)]
+pub async fn update_filter(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Path(id): Path<Uuid>,
+    ValidatedJson(request): ValidatedJson<SavedFilterRequest>,
+) -> ApiResult<(Extension<AuditChange>, Json<SavedFilter>)> {
+    let current = owned_filter(&state, &user, id).await?;
+    let fields = FilterFields::from(request);
+    match state
+        .saved_filters
+        .update(&current.owner, id, &fields)
+        .await
+        .map_err(db_error)?
+    {
+        SaveOutcome::Saved(filter) => {
+            let change = AuditChange::new(id).before(&current).after(&filter);
+            Ok((change.into_extension(), Json(filter)))
+        }
+        SaveOutcome::NameTaken => Err(ApiError::Conflict(format!(
+            "A filter named '{}' already exists",
+            fields.name
+        ))),
+        SaveOutcome::NotFound => Err(ApiError::NotFound(format!("Saved filter {id}"))),
+    }
+}
+
+/// Delete a saved filter.
+#[utoipa::path(
+    delete,
+    path = "/api/v1/tickets/filters/{id}",
+    params(("id" = Uuid, Path, description = "Filter ID")),
+    responses(
+        (status = 204, description = "Filter deleted"),
+        (status = 404, description = "Filter not found")
+    ),
+    tag = "Tickets"
+)]
+pub async fn delete_filter(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Path(id): Path<Uuid>,
+) -> ApiResult<(StatusCode, Extension<AuditChange>)> {
+    let filter = owned_filter(&state, &user, id).await?;
+    if !state
+        .saved_filters
+        .delete(&filter.owner, id)
+        .await
+        .map_err(db_error)?
+    {
+        return Err(ApiError::NotFound(format!("Saved filter {id}")));
+    }
+    let change = AuditChange::new(id).before(&filter);
+    Ok((StatusCode::NO_CONTENT, change.into_extension()))
+}
+
+/// Make a saved filter the default for the ticket list.
+#[utoipa::path(
+    put,
+    path = "/api/v1/tickets/filters/{id}/default",
+    params(("id" = Uuid, Path, description = "Filter ID")),
+    responses(
+        (status = 200, description = "Filter is now the default", body = SavedFilter),
+        (status = 404, description = "Filter not found")
+    ),
+    tag = "Tickets"
+)]
+pub async fn set_default(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Path(id): Path<Uuid>,
+) -> ApiResult<(Extension<AuditChange>, Json<SavedFilter>)> {
+    let filter = owned_filter(&state, &user, id).await?;
+    let updated = state
+        .saved_filters
+        .set_default(&filter.owner, id)
+        .await
+        .map_err(db_error)?
+        .ok_or_else(|| ApiError::NotFound(format!("Saved filter {id}")))?;
+    let change = AuditChange::new(id).before(&filter).after(&updated);
+    Ok((change.into_extension(), Json(updated)))
+}
+
+/// Stop a saved filter being the default.
+#[utoipa::path(
+    delete,
+    path = "/api/v1/tickets/filters/{id}/default",
+    params(("id" = Uuid, Path, description = "Filter ID")),
+    responses(
+        (status = 204, description = "Filter is no longer the default"),
+        (status = 404, description = "Filter not found")
+    ),
+    tag = "Tickets"
+)]
+pub async fn unset_default(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Path(id): Path<Uuid>,
+) -> ApiResult<(StatusCode, Extension<AuditChange>)> {
+    let filter = owned_filter(&state, &user, id).await?;
+    if !state
+        .saved_filters
+        .unset_default(&filter.owner, id)
+        .await
+        .map_err(db_error)?
+    {
+        return Err(ApiError::NotFound(format!("Saved filter {id}")));
+    }
+    let updated = SavedFilter {
+        is_default: false,
+        ..filter.clone()
+    };
+    let change = AuditChange::new(id).before(&filter).after(&updated);
+    Ok((StatusCode::NO_CONTENT, change.into_extension()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(jql: Option<&str>) -> SavedFilterRequest {
+        SavedFilterRequest {
+            name: " Regression ".to_string(),
+            statuses: vec!["Open".to_string(), " ".to_string(), " In QA ".to_string()],
+            project: Some("  ".to_string()),
+            assignee: None,
+            jql: jql.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_request_fields_are_trimmed() {
+        let fields = FilterFields::from(request(Some(" labels = smoke ")));
+        assert_eq!(fields.name, "Regression");
+        assert_eq!(fields.statuses, vec!["Open", "In QA"]);
+        assert_eq!(fields.project, None);
+        assert_eq!(fields.jql.as_deref(), Some("labels = smoke"));
+    }
+
+    #[test]
+    fn test_jql_fragment_validation() {
+        assert!(request(Some("priority = High")).validate().is_ok());
+        assert!(request(Some("  ")).validate().is_ok());
+        assert!(request(Some("updated > lastLogin()")).validate().is_err());
+        assert!(request(Some("priority = High ORDER BY created"))
+            .validate()
+            .is_err());
+        assert!(request(Some("priority = High order\n  by created"))
+            .validate()
+            .is_err());
+        assert!(request(Some(&"x".repeat(jql::MAX_JQL_LENGTH + 1)))
+            .validate()
+            .is_err());
+    }
+}
@@ -1,18 +1,58 @@
 //! Search API endpoints.
 //!
-//! Provides contextual search across Postman and Testmo.
-
-use axum::{extract::State, response::IntoResponse, routing::post, Json, Router};
-use qa_pms_core::KeywordExtractor;
-use qa_pms_postman::{PostmanClient, SearchResult as PostmanSearchResult};
+//! Provides contextual search across Postman and Testmo. Postman searches
+//! use the local collection cache once it has been filled, and unified
+//! search runs entirely against local data. Searches made with a `userId`
+//! are recorded in that user's search history.
+//!
+//! Results are returned a page at a time. The full result list of a search
+//! is cached briefly, so following `pageInfo.nextCursor` with the same
+//! request body does not run the search again.
+//!
+//! Semantic search ranks test cases and Postman requests by embedding
+//! similarity instead of keywords, using the configured AI provider.
+
+use axum::{
+    extract::{Query, State},
+    routing::post,
+    Json, Router,
+};
+use chrono::{DateTime, Utc};
+use qa_pms_ai::{AIError, AIFeature, EmbeddingService};
+use qa_pms_core::error::ApiError;
+use qa_pms_core::types::{CursorInfo, CursorQuery, PageRequest};
+use qa_pms_core::TenantId;
+use qa_pms_core::{KeywordExtractor, SynonymDictionary};
+use qa_pms_postman::{PostmanCache, PostmanClient, SearchResult as PostmanSearchResult};
 use qa_pms_testmo::{SearchResult as TestmoSearchResult, TestmoClient};
-use secrecy::ExposeSecret;
 use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::sync::Arc;
 use std::time::Instant;
 use tracing::{debug, info, warn};
 use utoipa::ToSchema;
 
 use crate::app::AppState;
+use crate::auth::CurrentUser;
+use crate::routes::{ai, postman, search_history, testmo};
+use crate::search_facets::{SearchFacets, SearchFilters};
+use crate::search_index::IndexSource;
+use crate::search_ranking::SearchRanking;
+use crate::semantic_index::{EmbeddingDocument, SemanticIndex, SemanticSource};
+
+/// Most hits taken from the local search index per query.
+const MAX_INDEX_RESULTS: usize = 100;
+
+/// Most hits taken from the local search index when results are filtered.
+const MAX_FILTERED_INDEX_RESULTS: usize = 1_000;
+
+/// Semantic search results returned when no limit is given.
+const DEFAULT_SEMANTIC_RESULTS: usize = 20;
+
+/// Most semantic search results returned.
+const MAX_SEMANTIC_RESULTS: usize = 100;
+
+type ApiResult<T> = Result<T, ApiError>;
 
 /// Create the search router.
 pub fn router() -> Router<AppState> {
@@ -21,6 +61,7 @@ pub fn router() -> Router<AppState> {
         .route("/api/v1/search/postman", post(search_postman_endpoint))
         .route("/api/v1/search/testmo", post(search_testmo_endpoint))
         .route("/api/v1/search/all", post(search_all))
+        .route("/api/v1/search/semantic", post(semantic_search))
 }
 
 // ============================================================================
@@ -37,14 +78,42 @@ pub struct ContextualSearchRequest {
     pub title: String,
     /// Optional ticket description for additional context.
     pub description: Option<String>,
+    /// User whose search history records this search.
+    pub user_id: Option<String>,
+}
+
+/// A search that can be stored and run again.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum SearchQuery {
+    /// Contextual search from a ticket's title and description.
+    #[serde(rename_all = "camelCase")]
+    Contextual {
+        /// Ticket key.
+        ticket_key: String,
+        /// Ticket title.
+        title: String,
+        /// Ticket description.
+        description: Option<String>,
+    },
+    /// Unified search for keywords.
+    Keywords {
+        /// Keywords to search for.
+        keywords: Vec<String>,
+        /// Filters narrowing the results.
+        #[serde(default)]
+        filters: SearchFilters,
+    },
 }
 
 /// Unified search result from any source.
 #[derive(Debug, Clone, Serialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct UnifiedSearchResult {
-    /// Source integration (postman, testmo).
+    /// Source (postman, testmo, jira, kb, errors, splunk, report).
     pub source: String,
+    /// Item type (collection, testCase, ticket, kbEntry, errorLog, queryTemplate, report).
+    pub result_type: String,
     /// Item ID.
     pub id: String,
     /// Item name/title.
@@ -57,22 +126,116 @@ pub struct UnifiedSearchResult {
     pub score: f32,
     /// Matching text snippets.
     pub matches: Vec<String>,
+    /// Tags or labels of the item.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Last update of the item, when the source reports one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+/// Request body for semantic search.
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct VectorSearchRequest {
+    /// Free-text description of what to find.
+    pub query: String,
+    /// Sources to search (all when empty).
+    #[serde(default)]
+    pub sources: Vec<SemanticSource>,
+    /// Most results to return (default 20, at most 100).
+    pub limit: Option<usize>,
 }
 
-/// Search response with results and metadata.
+/// Semantic search response.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct VectorSearchResponse {
+    /// Nearest items, most similar first; `score` is the cosine similarity.
+    pub results: Vec<UnifiedSearchResult>,
+    /// Embedding model used.
+    pub model: String,
+    /// Items embedded before this search because they were new or changed.
+    pub embedded: usize,
+    /// Search time in milliseconds.
+    pub search_time_ms: u64,
+}
+
+/// Search response with one page of results and metadata.
 #[derive(Debug, Serialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct SearchResponse {
     /// Search results sorted by score.
     pub results: Vec<UnifiedSearchResult>,
-    /// Number of results from Postman.
+    /// Number of results across all pages.
+    pub total_results: usize,
+    /// Number of results from Postman, across all pages.
     pub postman_count: usize,
-    /// Number of results from Testmo.
+    /// Number of results from Testmo, across all pages.
     pub testmo_count: usize,
     /// Total search time in milliseconds.
     pub search_time_ms: u64,
+    /// Whether the results came from the search cache.
+    pub cached: bool,
     /// Keywords used for search.
     pub keywords_used: Vec<String>,
+    /// Facet counts for narrowing the results.
+    pub facets: SearchFacets,
+    /// Pagination information.
+    pub page_info: CursorInfo,
+}
+
+/// Complete, ranked results of one search before paging.
+#[derive(Debug, Default)]
+pub(crate) struct SearchResults {
+    pub(crate) results: Vec<UnifiedSearchResult>,
+    pub(crate) postman_count: usize,
+    pub(crate) testmo_count: usize,
+    pub(crate) search_time_ms: u64,
+    pub(crate) keywords_used: Vec<String>,
+    pub(crate) facets: SearchFacets,
+}
+
+impl SearchResults {
+    /// Results on the requested page, and whether more follow.
+    fn page_items(&self, page: &PageRequest) -> (Vec<UnifiedSearchResult>, bool) {
+        let start = usize::try_from(page.offset())
+            .unwrap_or(usize::MAX)
+            .min(self.results.len());
+        let end = start
+            .saturating_add(page.limit as usize)
+            .min(self.results.len());
+        (self.results[start..end].to_vec(), end < self.results.len())
+    }
+
+    fn page(&self, page: &PageRequest, cached: bool) -> SearchResponse {
+        let (items, has_more) = self.page_items(page);
+        let page = page.finish_offset(items, has_more);
+        SearchResponse {
+            results: page.data,
+            total_results: self.results.len(),
+            postman_count: self.postman_count,
+            testmo_count: self.testmo_count,
+            search_time_ms: self.search_time_ms,
+            cached,
+            keywords_used: self.keywords_used.clone(),
+            facets: self.facets.clone(),
+            page_info: page.page_info,
+        }
+    }
+
+    fn single_source_page(&self, page: &PageRequest, cached: bool) -> SingleSourceSearchResponse {
+        let (items, has_more) = self.page_items(page);
+        let page = page.finish_offset(items, has_more);
+        SingleSourceSearchResponse {
+            count: page.data.len(),
+            results: page.data,
+            total_results: self.results.len(),
+            search_time_ms: self.search_time_ms,
+            cached,
+            page_info: page.page_info,
+        }
+    }
 }
 
 /// Request body for keyword-based search.
@@ -83,6 +246,11 @@ pub struct KeywordSearchRequest {
     pub keywords: Vec<String>,
     /// Optional ticket ID for logging.
     pub ticket_id: Option<String>,
+    /// User whose search history records this search (unified search only).
+    pub user_id: Option<String>,
+    /// Filters narrowing the results (unified search only).
+    #[serde(default)]
+    pub filters: SearchFilters,
 }
 
 /// Single-source search response.
@@ -91,10 +259,16 @@ pub struct KeywordSearchRequest {
 pub struct SingleSourceSearchResponse {
     /// Search results sorted by score.
     pub results: Vec<UnifiedSearchResult>,
-    /// Number of results.
+    /// Number of results on this page.
     pub count: usize,
+    /// Number of results across all pages.
+    pub total_results: usize,
     /// Search time in milliseconds.
     pub search_time_ms: u64,
+    /// Whether the results came from the search cache.
+    pub cached: bool,
+    /// Pagination information.
+    pub page_info: CursorInfo,
 }
 
 // ============================================================================
@@ -108,48 +282,69 @@ pub struct SingleSourceSearchResponse {
 #[utoipa::path(
     post,
     path = "/api/v1/search/contextual",
+    params(CursorQuery),
     request_body = ContextualSearchRequest,
     responses(
         (status = 200, description = "Search results", body = SearchResponse),
-        (status = 400, description = "Invalid request"),
+        (status = 400, description = "Invalid request or cursor"),
         (status = 500, description = "Search failed")
     ),
     tag = "Search"
 )]
 pub async fn contextual_search(
     State(state): State<AppState>,
+    user: CurrentUser,
+    Query(cursor): Query<CursorQuery>,
     Json(request): Json<ContextualSearchRequest>,
-) -> impl IntoResponse {
+) -> ApiResult<Json<SearchResponse>> {
+    let page = cursor.page_request()?;
+    let query = SearchQuery::Contextual {
+        ticket_key: request.ticket_key,
+        title: request.title,
+        description: request.description,
+    };
+    let response = search_page(&state, &user.tenant, &query, &page).await;
+    record_first_page(&state, request.user_id.as_deref(), &query, &page, &response).await;
+    Ok(Json(response))
+}
+
+/// Run a contextual search for a ticket.
+async fn run_contextual_search(
+    state: &AppState,
+    tenant: &TenantId,
+    ticket_key: &str,
+    title: &str,
+    description: Option<&str>,
+) -> SearchResults {
     let start = Instant::now();
 
     info!(
-        ticket_key = %request.ticket_key,
+        ticket_key = %ticket_key,
         "Starting contextual search"
     );
 
     // Extract keywords
-    let extractor = KeywordExtractor::default();
-    let keywords = extractor.extract_from_ticket(&request.title, request.description.as_deref());
+    let extractor = KeywordExtractor::default().with_synonyms(SynonymDictionary::new(
+        state.settings.search.synonyms.clone(),
+    ));
+    let keywords = extractor.extract_from_ticket(title, description);
 
     if keywords.is_empty() {
         debug!("No keywords extracted, returning empty results");
-        return Json(SearchResponse {
-            results: vec![],
-            postman_count: 0,
-            testmo_count: 0,
+        return SearchResults {
             search_time_ms: start.elapsed().as_millis() as u64,
-            keywords_used: keywords,
-        });
+            ..SearchResults::default()
+        };
     }
 
     debug!(keywords = ?keywords, "Extracted keywords");
 
     // Create clients from settings
-    let postman_client = create_postman_client(&state);
-    let (testmo_client, testmo_project_id) = create_testmo_client(&state);
+    let postman_client = create_postman_client(state, tenant);
+    let (testmo_client, testmo_project_id) = create_testmo_client(state, tenant);
 
     // Run searches in parallel
-    let postman_future = search_postman(postman_client, &keywords);
+    let postman_future = search_postman(state, postman_client, &keywords);
     let testmo_future = search_testmo(testmo_client, testmo_project_id, &keywords);
 
     let (postman_results, testmo_results) = tokio::join!(postman_future, testmo_future);
@@ -163,12 +358,15 @@ pub async fn contextual_search(
             debug!(count = count, "Postman search completed");
             all_results.extend(results.into_iter().map(|r| UnifiedSearchResult {
                 source: r.source,
+                result_type: "collection".to_string(),
                 id: r.id,
                 name: r.name,
                 description: r.description,
                 url: r.url,
                 score: r.score,
                 matches: r.matches,
+                tags: vec![],
+                updated_at: None,
             }));
             count
         }
@@ -184,12 +382,15 @@ pub async fn contextual_search(
             debug!(count = count, "Testmo search completed");
             all_results.extend(results.into_iter().map(|r| UnifiedSearchResult {
                 source: r.source,
+                result_type: "testCase".to_string(),
                 id: r.id,
                 name: r.name,
                 description: r.description,
                 url: r.url,
                 score: r.score,
                 matches: r.matches,
+                tags: vec![],
+                updated_at: None,
             }));
             count
         }
@@ -199,12 +400,10 @@ pub async fn contextual_search(
         }
     };
 
-    // Sort all results by score (descending)
-    all_results.sort_by(|a, b| {
-        b.score
-            .partial_cmp(&a.score)
-            .unwrap_or(std::cmp::Ordering::Equal)
-    });
+    // Weight by source and recency, best first
+    let now = Utc::now();
+    SearchRanking::from_settings(&state.settings.search).apply(&mut all_results, now);
+    let facets = SearchFacets::compute(&all_results, &SearchFilters::default(), now);
 
     let duration = start.elapsed();
     let search_time_ms = duration.as_millis() as u64;
@@ -212,14 +411,14 @@ pub async fn contextual_search(
     // Log slow searches (NFR-PERF-03: < 3s)
     if duration.as_secs() > 3 {
         warn!(
-            ticket_key = %request.ticket_key,
+            ticket_key = %ticket_key,
             duration_ms = search_time_ms,
             "Slow contextual search exceeded 3s threshold"
         );
     }
 
     info!(
-        ticket_key = %request.ticket_key,
+        ticket_key = %ticket_key,
         postman_count = postman_count,
         testmo_count = testmo_count,
         total_results = all_results.len(),
@@ -227,59 +426,76 @@ pub async fn contextual_search(
         "Contextual search completed"
     );
 
-    Json(SearchResponse {
+    SearchResults {
         results: all_results,
         postman_count,
         testmo_count,
         search_time_ms,
         keywords_used: keywords,
-    })
+        facets,
+    }
 }
 
 /// Search Postman collections only.
 #[utoipa::path(
     post,
     path = "/api/v1/search/postman",
+    params(CursorQuery),
     request_body = KeywordSearchRequest,
     responses(
         (status = 200, description = "Postman search results", body = SingleSourceSearchResponse),
-        (status = 400, description = "Invalid request"),
+        (status = 400, description = "Invalid request or cursor"),
         (status = 503, description = "Postman not configured")
     ),
     tag = "Search"
 )]
 pub async fn search_postman_endpoint(
     State(state): State<AppState>,
+    user: CurrentUser,
+    Query(cursor): Query<CursorQuery>,
     Json(request): Json<KeywordSearchRequest>,
-) -> impl IntoResponse {
-    let start = Instant::now();
-
+) -> ApiResult<Json<SingleSourceSearchResponse>> {
+    let page = cursor.page_request()?;
     info!(
         ticket_id = ?request.ticket_id,
         keywords = ?request.keywords,
         "Starting Postman search"
     );
 
-    if request.keywords.is_empty() {
-        return Json(SingleSourceSearchResponse {
-            results: vec![],
-            count: 0,
-            search_time_ms: start.elapsed().as_millis() as u64,
-        });
+    let key = cache_key(&format!("postman:{}", user.tenant), &request.keywords);
+    let (results, cached) = cached_search(&state, key, || {
+        run_postman_search(&state, &user.tenant, &request.keywords)
+    })
+    .await;
+    Ok(Json(results.single_source_page(&page, cached)))
+}
+
+/// Search Postman collections for keywords.
+async fn run_postman_search(
+    state: &AppState,
+    tenant: &TenantId,
+    keywords: &[String],
+) -> SearchResults {
+    let start = Instant::now();
+    if keywords.is_empty() {
+        return SearchResults::default();
     }
 
-    let postman_client = create_postman_client(&state);
-    let results = search_postman(postman_client, &request.keywords).await;
+    let postman_client = create_postman_client(state, tenant);
+    let results = search_postman(state, postman_client, keywords).await;
 
     let mapped_results: Vec<UnifiedSearchResult> = match results {
         Ok(r) => r.into_iter().map(|r| UnifiedSearchResult {
             source: r.source,
+            result_type: "collection".to_string(),
             id: r.id,
             name: r.name,
             description: r.description,
             url: r.url,
             score: r.score,
             matches: r.matches,
+            tags: vec![],
+            updated_at: None,
         }).collect(),
         Err(e) => {
             warn!(error = %e, "Postman search failed");
@@ -290,57 +506,75 @@ pub async fn search_postman_endpoint(
     let count = mapped_results.len();
     info!(count = count, "Postman search completed");
 
-    Json(SingleSourceSearchResponse {
+    SearchResults {
         results: mapped_results,
-        count,
+        postman_count: count,
         search_time_ms: start.elapsed().as_millis() as u64,
-    })
+        keywords_used: keywords.to_vec(),
+        ..SearchResults::default()
+    }
 }
 
 /// Search Testmo test cases only.
 #[utoipa::path(
     post,
     path = "/api/v1/search/testmo",
+    params(CursorQuery),
     request_body = KeywordSearchRequest,
     responses(
         (status = 200, description = "Testmo search results", body = SingleSourceSearchResponse),
-        (status = 400, description = "Invalid request"),
+        (status = 400, description = "Invalid request or cursor"),
         (status = 503, description = "Testmo not configured")
     ),
     tag = "Search"
 )]
 pub async fn search_testmo_endpoint(
     State(state): State<AppState>,
+    user: CurrentUser,
+    Query(cursor): Query<CursorQuery>,
     Json(request): Json<KeywordSearchRequest>,
-) -> impl IntoResponse {
-    let start = Instant::now();
-
+) -> ApiResult<Json<SingleSourceSearchResponse>> {
+    let page = cursor.page_request()?;
     info!(
         ticket_id = ?request.ticket_id,
         keywords = ?request.keywords,
         "Starting Testmo search"
     );
 
-    if request.keywords.is_empty() {
-        return Json(SingleSourceSearchResponse {
-            results: vec![],
-            count: 0,
-            search_time_ms: start.elapsed().as_millis() as u64,
-        });
+    let key = cache_key(&format!("testmo:{}", user.tenant), &request.keywords);
+    let (results, cached) = cached_search(&state, key, || {
+        run_testmo_search(&state, &user.tenant, &request.keywords)
+    })
+    .await;
+    Ok(Json(results.single_source_page(&page, cached)))
+}
+
+/// Search Testmo test cases for keywords.
+async fn run_testmo_search(
+    state: &AppState,
+    tenant: &TenantId,
+    keywords: &[String],
+) -> SearchResults {
+    let start = Instant::now();
+    if keywords.is_empty() {
+        return SearchResults::default();
     }
 
-    let (testmo_client, testmo_project_id) = create_testmo_client(&state);
-    let results = search_testmo(testmo_client, testmo_project_id, &request.keywords).await;
+    let (testmo_client, testmo_project_id) = create_testmo_client(state, tenant);
+    let results = search_testmo(testmo_client, testmo_project_id, keywords).await;
 
     let mapped_results: Vec<UnifiedSearchResult> = match results {
         Ok(r) => r.into_iter().map(|r| UnifiedSearchResult {
             source: r.source,
+            result_type: "testCase".to_string(),
             id: r.id,
             name: r.name,
             description: r.description,
             url: r.url,
             score: r.score,
             matches: r.matches,
+            tags: vec![],
+            updated_at: None,
         }).collect(),
         Err(e) => {
             warn!(error = %e, "Testmo search failed");
@@ -351,159 +585,340 @@ pub async fn search_testmo_endpoint(
     let count = mapped_results.len();
     info!(count = count, "Testmo search completed");
 
-    Json(SingleSourceSearchResponse {
+    SearchResults {
         results: mapped_results,
-        count,
+        testmo_count: count,
         search_time_ms: start.elapsed().as_millis() as u64,
-    })
+        keywords_used: keywords.to_vec(),
+        ..SearchResults::default()
+    }
 }
 
-/// Search all integrations in parallel.
+/// Search everything held locally.
+///
+/// Tickets, test cases, knowledge base entries, error logs, Splunk templates
+/// and reports come from the local search index and Postman collections from
+/// the collection cache, so no integration is called per query.
+///
+/// Results can be narrowed with `filters`; the response carries source,
+/// type, tag and update-date facet counts for the hits.
 #[utoipa::path(
     post,
     path = "/api/v1/search/all",
+    params(CursorQuery),
     request_body = KeywordSearchRequest,
     responses(
         (status = 200, description = "Combined search results", body = SearchResponse),
-        (status = 400, description = "Invalid request")
+        (status = 400, description = "Invalid request or cursor")
     ),
     tag = "Search"
 )]
 pub async fn search_all(
     State(state): State<AppState>,
+    user: CurrentUser,
+    Query(cursor): Query<CursorQuery>,
     Json(request): Json<KeywordSearchRequest>,
-) -> impl IntoResponse {
-    let start = Instant::now();
+) -> ApiResult<Json<SearchResponse>> {
+    let page = cursor.page_request()?;
+    info!(ticket_id = ?request.ticket_id, "Unified search requested");
 
-    info!(
-        ticket_id = ?request.ticket_id,
-        keywords = ?request.keywords,
-        "Starting parallel search"
-    );
+    let query = SearchQuery::Keywords {
+        keywords: request.keywords,
+        filters: request.filters,
+    };
+    let response = search_page(&state, &user.tenant, &query, &page).await;
+    record_first_page(&state, request.user_id.as_deref(), &query, &page, &response).await;
+    Ok(Json(response))
+}
 
-    if request.keywords.is_empty() {
-        return Json(SearchResponse {
-            results: vec![],
-            postman_count: 0,
-            testmo_count: 0,
-            search_time_ms: start.elapsed().as_millis() as u64,
-            keywords_used: vec![],
-        });
+/// Find test cases and Postman requests by meaning.
+///
+/// The query and every item are embedded with the configured AI provider
+/// and results are the nearest items by cosine similarity. Items that are
+/// new or changed since the last search are embedded first.
+#[utoipa::path(
+    post,
+    path = "/api/v1/search/semantic",
+    request_body = VectorSearchRequest,
+    responses(
+        (status = 200, description = "Nearest items", body = VectorSearchResponse),
+        (status = 400, description = "Blank query"),
+        (status = 403, description = "Monthly token budget exhausted"),
+        (status = 503, description = "AI not configured or without embeddings")
+    ),
+    tag = "Search"
+)]
+pub async fn semantic_search(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Json(request): Json<VectorSearchRequest>,
+) -> ApiResult<Json<VectorSearchResponse>> {
+    let start = Instant::now();
+    let query = request.query.trim();
+    if query.is_empty() {
+        return Err(ApiError::Validation("query must not be blank".into()));
     }
+    let limit = request
+        .limit
+        .unwrap_or(DEFAULT_SEMANTIC_RESULTS)
+        .clamp(1, MAX_SEMANTIC_RESULTS);
+    let sources = if request.sources.is_empty() {
+        SemanticSource::ALL.to_vec()
+    } else {
+        request.sources
+    };
+
+    let client = ai::configured_client(&state, &user, AIFeature::SemanticSearch).await?;
+    let embedder = EmbeddingService::new(client);
+    let model = embedder.model().map(str::to_string).ok_or_else(|| {
+        ApiError::ServiceUnavailable(
+            "The configured AI provider has no embedding model; set AI_EMBEDDING_MODEL".into(),
+        )
+    })?;
+
+    let index = SemanticIndex::new(state.db.clone());
+    let documents = semantic_documents(&state).await?;
+    let report = index
+        .sync(&embedder, &documents)
+        .await
+        .map_err(embedding_error)?;
+    let vector = embedder
+        .embed_query(query)
+        .await
+        .map_err(|e| embedding_error(e.into()))?;
+    let results = index
+        .nearest(&model, &vector, &sources, limit)
+        .await
+        .map_err(ApiError::Internal)?;
 
-    let postman_client = create_postman_client(&state);
-    let (testmo_client, testmo_project_id) = create_testmo_client(&state);
+    info!(
+        results = results.len(),
+        embedded = report.embedded,
+        model = %model,
+        "Semantic search completed"
+    );
+    Ok(Json(VectorSearchResponse {
+        results,
+        model,
+        embedded: report.embedded,
+        search_time_ms: start.elapsed().as_millis() as u64,
+    }))
+}
 
-    // Run searches in parallel
-    let postman_future = search_postman(postman_client, &request.keywords);
-    let testmo_future = search_testmo(testmo_client, testmo_project_id, &request.keywords);
+/// Run a unified search over local data.
+async fn run_unified_search(
+    state: &AppState,
+    keywords: &[String],
+    filters: &SearchFilters,
+) -> SearchResults {
+    let start = Instant::now();
 
-    let (postman_results, testmo_results) = tokio::join!(postman_future, testmo_future);
+    info!(keywords = ?keywords, filters = ?filters, "Starting unified search");
 
-    let mut all_results: Vec<UnifiedSearchResult> = Vec::new();
+    if keywords.is_empty() {
+        return SearchResults {
+            search_time_ms: start.elapsed().as_millis() as u64,
+            ..SearchResults::default()
+        };
+    }
 
-    let postman_count = match postman_results {
-        Ok(results) => {
-            let count = results.len();
-            all_results.extend(results.into_iter().map(|r| UnifiedSearchResult {
-                source: r.source,
-                id: r.id,
-                name: r.name,
-                description: r.description,
-                url: r.url,
-                score: r.score,
-                matches: r.matches,
-            }));
-            count
-        }
-        Err(e) => {
-            warn!(error = %e, "Postman search failed in parallel");
-            0
-        }
+    // Filtering happens after the lookup, so filtered searches dig deeper
+    let index_limit = if filters.is_empty() {
+        MAX_INDEX_RESULTS
+    } else {
+        MAX_FILTERED_INDEX_RESULTS
     };
+    let mut all_results = state
+        .search_index
+        .search(keywords, index_limit)
+        .unwrap_or_else(|e| {
+            warn!(error = %e, "Search index lookup failed");
+            vec![]
+        });
 
-    let testmo_count = match testmo_results {
+    match PostmanCache::new(state.db.clone()).search(keywords).await {
         Ok(results) => {
-            let count = results.len();
             all_results.extend(results.into_iter().map(|r| UnifiedSearchResult {
                 source: r.source,
+                result_type: "collection".to_string(),
                 id: r.id,
                 name: r.name,
                 description: r.description,
                 url: r.url,
                 score: r.score,
                 matches: r.matches,
+                tags: vec![],
+                updated_at: None,
             }));
-            count
-        }
-        Err(e) => {
-            warn!(error = %e, "Testmo search failed in parallel");
-            0
         }
-    };
+        Err(e) => warn!(error = %e, "Cached Postman search failed"),
+    }
 
-    // Sort by score descending
-    all_results.sort_by(|a, b| {
-        b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal)
-    });
+    // Weight by source and recency, best first
+    let now = Utc::now();
+    SearchRanking::from_settings(&state.settings.search).apply(&mut all_results, now);
+    let facets = SearchFacets::compute(&all_results, filters, now);
+    filters.apply(&mut all_results);
 
-    let duration = start.elapsed();
-    if duration.as_secs() > 3 {
-        warn!(duration_ms = duration.as_millis(), "Slow parallel search exceeded 3s");
-    }
+    let count_source =
+        |source: &str| all_results.iter().filter(|r| r.source == source).count();
+    let postman_count = count_source("postman");
+    let testmo_count = count_source(IndexSource::TestCase.as_str());
 
+    let duration = start.elapsed();
     info!(
+        total_results = all_results.len(),
         postman_count = postman_count,
         testmo_count = testmo_count,
         duration_ms = duration.as_millis(),
-        "Parallel search completed"
+        "Unified search completed"
     );
 
-    Json(SearchResponse {
+    SearchResults {
         results: all_results,
         postman_count,
         testmo_count,
         search_time_ms: duration.as_millis() as u64,
-        keywords_used: request.keywords,
-    })
+        keywords_used: keywords.to_vec(),
+        facets,
+    }
+}
+
+/// Run a search described by `query` with the tenant's integrations.
+async fn run_search(state: &AppState, tenant: &TenantId, query: &SearchQuery) -> SearchResults {
+    match query {
+        SearchQuery::Contextual {
+            ticket_key,
+            title,
+            description,
+        } => {
+            run_contextual_search(state, tenant, ticket_key, title, description.as_deref()).await
+        }
+        SearchQuery::Keywords { keywords, filters } => {
+            run_unified_search(state, keywords, filters).await
+        }
+    }
+}
+
+/// One page of the results of `query`, from the cache when it is fresh.
+pub(crate) async fn search_page(
+    state: &AppState,
+    tenant: &TenantId,
+    query: &SearchQuery,
+    page: &PageRequest,
+) -> SearchResponse {
+    let key = cache_key(&format!("query:{tenant}"), query);
+    let (results, cached) = cached_search(state, key, || run_search(state, tenant, query)).await;
+    results.page(page, cached)
+}
+
+/// Record a search in the user's history, once per search rather than per page.
+async fn record_first_page(
+    state: &AppState,
+    user_id: Option<&str>,
+    query: &SearchQuery,
+    page: &PageRequest,
+    response: &SearchResponse,
+) {
+    if let (Some(user_id), None) = (user_id, page.after) {
+        search_history::record(&state.db, user_id, query, response.total_results).await;
+    }
+}
+
+fn cache_key(scope: &str, value: &impl Serialize) -> String {
+    format!("{scope}:{}", serde_json::to_string(value).unwrap_or_default())
+}
+
+/// Cached results for `key`, running the search on a miss.
+///
+/// Returns whether the results came from the cache.
+async fn cached_search<F, Fut>(state: &AppState, key: String, run: F) -> (Arc<SearchResults>, bool)
+where
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = SearchResults>,
+{
+    if let Some(results) = state.search_cache.get(&key) {
+        debug!(key = %key, "Search served from cache");
+        return (results, true);
+    }
+    let results = Arc::new(run().await);
+    state.search_cache.insert(key, Arc::clone(&results));
+    (results, false)
 }
 
 // ============================================================================
 // Helper Functions
 // ============================================================================
 
-/// Create Postman client from settings.
-fn create_postman_client(state: &AppState) -> Option<PostmanClient> {
-    let postman_settings = state.settings.postman.as_ref()?;
-    let api_key = postman_settings.api_key.expose_secret();
-    if api_key.is_empty() {
-        return None;
+/// Every item the semantic index should hold.
+async fn semantic_documents(state: &AppState) -> ApiResult<Vec<EmbeddingDocument>> {
+    let testmo_base_url = state.search_index.testmo_base_url();
+    let cases = state.test_cases.list().await.map_err(ApiError::Internal)?;
+    let collections = PostmanCache::new(state.db.clone())
+        .collections()
+        .await
+        .map_err(|e| ApiError::Internal(e.into()))?;
+
+    let mut documents: Vec<EmbeddingDocument> = cases
+        .iter()
+        .map(|case| EmbeddingDocument::test_case(case, testmo_base_url))
+        .collect();
+    documents.extend(collections.iter().flat_map(EmbeddingDocument::postman_requests));
+    Ok(documents)
+}
+
+/// Map an embedding failure, keeping provider errors distinct.
+fn embedding_error(error: anyhow::Error) -> ApiError {
+    match error.downcast::<AIError>() {
+        Ok(AIError::RateLimited) => ApiError::RateLimited,
+        Ok(e @ AIError::BudgetExceeded { .. }) => ApiError::Forbidden(e.to_string()),
+        Ok(e @ (AIError::UnsupportedProvider(_) | AIError::NotConfigured)) => {
+            ApiError::ServiceUnavailable(e.to_string())
+        }
+        Ok(e) => ApiError::ExternalService(format!("Embedding request failed: {e}")),
+        Err(e) => ApiError::Internal(e),
     }
-    Some(PostmanClient::new(api_key.clone()))
 }
 
-/// Create Testmo client from settings.
-fn create_testmo_client(state: &AppState) -> (Option<TestmoClient>, Option<i64>) {
-    let Some(testmo_settings) = state.settings.testmo.as_ref() else {
-        return (None, None);
-    };
-    
-    let api_key = testmo_settings.api_key.expose_secret();
-    let base_url = &testmo_settings.base_url;
-    
-    if api_key.is_empty() || base_url.is_empty() {
-        return (None, None);
+/// Create the tenant's Postman client, if Postman is configured for it.
+fn create_postman_client(state: &AppState, tenant: &TenantId) -> Option<PostmanClient> {
+    match postman::postman_client(state, tenant) {
+        Ok(client) => Some(client),
+        Err(ApiError::ServiceUnavailable(_)) => None,
+        Err(e) => {
+            warn!(error = %e, "Postman client unavailable, skipping search");
+            None
+        }
     }
-    
-    let client = TestmoClient::new(base_url.clone(), api_key.clone());
-    (Some(client), testmo_settings.project_id)
 }
 
-/// Search Postman collections.
+/// Create the tenant's Testmo client, if Testmo is configured for it.
+fn create_testmo_client(
+    state: &AppState,
+    tenant: &TenantId,
+) -> (Option<Arc<TestmoClient>>, Option<i64>) {
+    testmo::tenant_client(state, tenant).unwrap_or_else(|e| {
+        warn!(error = %e, "Testmo client unavailable, skipping search");
+        (None, None)
+    })
+}
+
+/// Search Postman collections, from the cache when it has entries.
 async fn search_postman(
+    state: &AppState,
     client: Option<PostmanClient>,
     keywords: &[String],
 ) -> Result<Vec<PostmanSearchResult>, String> {
+    let cache = PostmanCache::new(state.db.clone());
+    match cache.status().await {
+        Ok(status) if status.collections > 0 => match cache.search(keywords).await {
+            Ok(results) => return Ok(results),
+            Err(e) => warn!(error = %e, "Cached Postman search failed, searching live"),
+        },
+        Ok(_) => debug!("Postman cache empty, searching live"),
+        Err(e) => warn!(error = %e, "Postman cache unavailable, searching live"),
+    }
+
     let Some(client) = client else {
         debug!("Postman client not configured, skipping search");
         return Ok(vec![]);
@@ -517,7 +932,7 @@ async fn search_postman(
 
 /// Search Testmo test cases.
 async fn search_testmo(
-    client: Option<TestmoClient>,
+    client: Option<Arc<TestmoClient>>,
     project_id: Option<i64>,
     keywords: &[String],
 ) -> Result<Vec<TestmoSearchResult>, String> {
@@ -540,32 +955,77 @@ mod tests {
     fn test_unified_search_result_serialization() {
         let result = UnifiedSearchResult {
             source: "postman".to_string(),
+            result_type: "collection".to_string(),
             id: "123".to_string(),
             name: "Login API".to_string(),
             description: Some("Tests for login endpoint".to_string()),
             url: "https://go.postman.co/collection/123".to_string(),
             score: 2.5,
             matches: vec!["login".to_string(), "api".to_string()],
+            tags: vec![],
+            updated_at: None,
         };
 
         let json = serde_json::to_string(&result).unwrap();
         assert!(json.contains("\"source\":\"postman\""));
+        assert!(json.contains("\"resultType\":\"collection\""));
+        assert!(!json.contains("\"tags\""));
         assert!(json.contains("\"score\":2.5"));
     }
 
     #[test]
     fn test_search_response_serialization() {
-        let response = SearchResponse {
-            results: vec![],
+        let response = SearchResults {
             postman_count: 5,
             testmo_count: 3,
             search_time_ms: 150,
             keywords_used: vec!["login".to_string(), "auth".to_string()],
-        };
+            ..SearchResults::default()
+        }
+        .page(&PageRequest::default(), false);
 
         let json = serde_json::to_string(&response).unwrap();
         assert!(json.contains("\"postmanCount\":5"));
         assert!(json.contains("\"testmoCount\":3"));
         assert!(json.contains("\"searchTimeMs\":150"));
+        assert!(json.contains("\"hasMore\":false"));
+    }
+
+    #[test]
+    fn test_results_are_paged_by_offset_cursor() {
+        let results = SearchResults {
+            results: (0..25)
+                .map(|i| UnifiedSearchResult {
+                    source: "kb".to_string(),
+                    result_type: "kbEntry".to_string(),
+                    id: i.to_string(),
+                    name: format!("Entry {i}"),
+                    description: None,
+                    url: String::new(),
+                    score: 1.0,
+                    matches: vec![],
+                    tags: vec![],
+                    updated_at: None,
+                })
+                .collect(),
+            ..SearchResults::default()
+        };
+
+        let first = results.page(&PageRequest::new(None, Some(10), Default::default()), true);
+        assert_eq!(first.results.len(), 10);
+        assert_eq!(first.total_results, 25);
+        assert!(first.cached);
+        let cursor = first.page_info.next_cursor.unwrap();
+
+        let query = CursorQuery {
+            cursor: Some(cursor),
+            limit: Some(20),
+            direction: None,
+        };
+        let second = results.page(&query.page_request().unwrap(), true);
+        assert_eq!(second.results.len(), 15);
+        assert_eq!(second.results[0].id, "10");
+        assert!(!second.page_info.has_more);
+        assert!(second.page_info.next_cursor.is_none());
     }
 }
@@ -0,0 +1,482 @@
+//! Search history and saved search API endpoints.
+//!
+//! Contextual and unified searches made with a `userId` are appended to that
+//! user's history, which keeps the most recent entries only. A search can
+//! also be saved under a name and run again later with one request.
+
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    routing::{delete, get, post},
+    Extension, Json, Router,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+use validator::{Validate, ValidationError};
+
+use qa_pms_core::error::ApiError;
+use qa_pms_core::types::CursorQuery;
+
+use crate::app::AppState;
+use crate::audit::AuditChange;
+use crate::auth::CurrentUser;
+use crate::routes::search::{search_page, SearchQuery, SearchResponse};
+use crate::validation::{not_blank, ValidatedJson};
+
+type ApiResult<T> = Result<T, ApiError>;
+
+/// History entries kept per user; older ones are pruned on insert.
+const HISTORY_LIMIT: i64 = 50;
+
+/// Default number of history entries returned.
+const DEFAULT_HISTORY_PAGE: i64 = 20;
+
+/// Create the search history router.
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/api/v1/search/history",
+            get(list_history).delete(clear_history),
+        )
+        .route("/api/v1/search/saved", get(list_saved).post(create_saved))
+        .route("/api/v1/search/saved/:id", delete(delete_saved))
+        .route("/api/v1/search/saved/:id/run", post(run_saved))
+}
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/// Query parameters selecting a user.
+#[derive(Debug, Deserialize, IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub struct UserQuery {
+    /// User the searches belong to
+    pub user_id: String,
+}
+
+/// Query parameters for listing search history.
+#[derive(Debug, Deserialize, IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryQuery {
+    /// User the searches belong to
+    pub user_id: String,
+    /// Most entries to return (default 20, max 50)
+    pub limit: Option<i64>,
+}
+
+/// A past search.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchHistoryEntry {
+    /// Entry ID
+    pub id: Uuid,
+    /// Search that was run
+    pub query: SearchQuery,
+    /// Number of results it returned
+    pub result_count: i32,
+    /// When it was run
+    pub created_at: DateTime<Utc>,
+}
+
+/// Request to save a search.
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SavedSearchRequest {
+    /// Owner of the saved search
+    #[validate(custom(function = "not_blank"))]
+    pub user_id: String,
+    /// Name, unique per user
+    #[validate(custom(function = "not_blank"), length(max = 100))]
+    pub name: String,
+    /// Search to save
+    #[validate(custom(function = "validate_query"))]
+    pub query: SearchQuery,
+}
+
+/// A saved search.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SavedSearchResponse {
+    /// Saved search ID
+    pub id: Uuid,
+    /// Owner
+    pub user_id: String,
+    /// Name
+    pub name: String,
+    /// Saved search
+    pub query: SearchQuery,
+    /// Creation time
+    pub created_at: DateTime<Utc>,
+    /// Last time it was run
+    pub last_run_at: Option<DateTime<Utc>>,
+}
+
+#[derive(sqlx::FromRow)]
+struct HistoryRow {
+    id: Uuid,
+    query: sqlx::types::Json<SearchQuery>,
+    result_count: i32,
+    created_at: DateTime<Utc>,
+}
+
+impl From<HistoryRow> for SearchHistoryEntry {
+    fn from(row: HistoryRow) -> Self {
+        Self {
+            id: row.id,
+            query: row.query.0,
+            result_count: row.result_count,
+            created_at: row.created_at,
+        }
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct SavedSearchRow {
+    id: Uuid,
+    user_id: String,
+    name: String,
+    query: sqlx::types::Json<SearchQuery>,
+    created_at: DateTime<Utc>,
+    last_run_at: Option<DateTime<Utc>>,
+}
+
+impl From<SavedSearchRow> for SavedSearchResponse {
+    fn from(row: SavedSearchRow) -> Self {
+        Self {
+            id: row.id,
+            user_id: row.user_id,
+            name: row.name,
+            query: row.query.0,
+            created_at: row.created_at,
+            last_run_at: row.last_run_at,
+        }
+    }
+}
+
+const SAVED_COLUMNS: &str = "id, user_id, name, query, created_at, last_run_at";
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+fn db_error(e: sqlx::Error) -> ApiError {
+    ApiError::Internal(e.into())
+}
+
+fn validate_query(query: &SearchQuery) -> Result<(), ValidationError> {
+    let empty = match query {
+        SearchQuery::Contextual { title, .. } => title.trim().is_empty(),
+        SearchQuery::Keywords { keywords, .. } => keywords.iter().all(|k| k.trim().is_empty()),
+    };
+    if empty {
+        return Err(ValidationError::new("empty_query")
+            .with_message("a search needs a title or at least one keyword".into()));
+    }
+    Ok(())
+}
+
+/// Append a search to a user's history and prune entries past the limit.
+///
+/// Failures are logged; history must never fail the search itself.
+pub(crate) async fn record(
+    db: &sqlx::PgPool,
+    user_id: &str,
+    query: &SearchQuery,
+    result_count: usize,
+) {
+    if let Err(e) = try_record(db, user_id, query, result_count).await {
+        warn!(error = %e, user_id = %user_id, "Failed to record search history");
+    }
+}
+
+async fn try_record(
+    db: &sqlx::PgPool,
+    user_id: &str,
+    query: &SearchQuery,
+    result_count: usize,
+) -> Result<(), sqlx::Error> {
+    let mut tx = db.begin().await?;
+    sqlx::query(
+        r"
+        INSERT INTO search_history (id, user_id, query, result_count, created_at)
+        VALUES ($1, $2, $3, $4, NOW())
+        ",
+    )
+    .bind(Uuid::new_v4())
+    .bind(user_id)
+    .bind(sqlx::types::Json(query))
+    .bind(i32::try_from(result_count).unwrap_or(i32::MAX))
+    .execute(&mut *tx)
+    .await?;
+    sqlx::query(
+        r"
+        DELETE FROM search_history
+        WHERE user_id = $1 AND id NOT IN (
+            SELECT id FROM search_history
+            WHERE user_id = $1
+            ORDER BY created_at DESC
+            LIMIT $2
+        )
+        ",
+    )
+    .bind(user_id)
+    .bind(HISTORY_LIMIT)
+    .execute(&mut *tx)
+    .await?;
+    tx.commit().await
+}
+
+// ============================================================================
+// Handlers
+// ============================================================================
+
+/// List a user's recent searches, newest first.
+#[utoipa::path(
+    get,
+    path = "/api/v1/search/history",
+    params(HistoryQuery),
+    responses(
+        (status = 200, description = "Recent searches", body = Vec<SearchHistoryEntry>)
+    ),
+    tag = "Search"
+)]
+pub async fn list_history(
+    State(state): State<AppState>,
+    Query(query): Query<HistoryQuery>,
+) -> ApiResult<Json<Vec<SearchHistoryEntry>>> {
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_HISTORY_PAGE)
+        .clamp(1, HISTORY_LIMIT);
+    let rows: Vec<HistoryRow> = sqlx::query_as(
+        r"
+        SELECT id, query, result_count, created_at
+        FROM search_history
+        WHERE user_id = $1
+        ORDER BY created_at DESC
+        LIMIT $2
+        ",
+    )
+    .bind(&query.user_id)
+    .bind(limit)
+    .fetch_all(&state.db)
+    .await
+    .map_err(db_error)?;
+    Ok(Json(rows.into_iter().map(Into::into).collect()))
+}
+
+/// Clear a user's search history.
+#[utoipa::path(
+    delete,
+    path = "/api/v1/search/history",
+    params(UserQuery),
+    responses(
+        (status = 204, description = "History cleared")
+    ),
+    tag = "Search"
+)]
+pub async fn clear_history(
+    State(state): State<AppState>,
+    Query(query): Query<UserQuery>,
+) -> ApiResult<(StatusCode, Extension<AuditChange>)> {
+    let deleted = sqlx::query("DELETE FROM search_history WHERE user_id = $1")
+        .bind(&query.user_id)
+        .execute(&state.db)
+        .await
+        .map_err(db_error)?
+        .rows_affected();
+    let change =
+        AuditChange::new(&query.user_id).before(&serde_json::json!({ "entries": deleted }));
+    Ok((StatusCode::NO_CONTENT, change.into_extension()))
+}
+
+/// List a user's saved searches.
+#[utoipa::path(
+    get,
+    path = "/api/v1/search/saved",
+    params(UserQuery),
+    responses(
+        (status = 200, description = "Saved searches", body = Vec<SavedSearchResponse>)
+    ),
+    tag = "Search"
+)]
+pub async fn list_saved(
+    State(state): State<AppState>,
+    Query(query): Query<UserQuery>,
+) -> ApiResult<Json<Vec<SavedSearchResponse>>> {
+    let rows: Vec<SavedSearchRow> = sqlx::query_as(&format!(
+        "SELECT {SAVED_COLUMNS} FROM saved_searches WHERE user_id = $1 ORDER BY name"
+    ))
+    .bind(&query.user_id)
+    .fetch_all(&state.db)
+    .await
+    .map_err(db_error)?;
+    Ok(Json(rows.into_iter().map(Into::into).collect()))
+}
+
+/// Save a search under a name.
+#[utoipa::path(
+    post,
+    path = "/api/v1/search/saved",
+    request_body = SavedSearchRequest,
+    responses(
+        (status = 201, description = "Search saved", body = SavedSearchResponse),
+        (status = 409, description = "Name already used by this user"),
+        (status = 422, description = "Invalid request", body = qa_pms_core::error::ErrorResponse)
+    ),
+    tag = "Search"
+)]
+pub async fn create_saved(
+    State(state): State<AppState>,
+    ValidatedJson(request): ValidatedJson<SavedSearchRequest>,
+) -> ApiResult<(StatusCode, Extension<AuditChange>, Json<SavedSearchResponse>)> {
+    let name = request.name.trim();
+    let row: SavedSearchRow = sqlx::query_as(&format!(
+        r"
+        INSERT INTO saved_searches (id, user_id, name, query, created_at)
+        SELECT $1, $2, $3, $4, NOW()
+        WHERE NOT EXISTS (
+            SELECT 1 FROM saved_searches WHERE user_id = $2 AND lower(name) = lower($3)
+        )
+        RETURNING {SAVED_COLUMNS}
+        "
+    ))
+    .bind(Uuid::new_v4())
+    .bind(&request.user_id)
+    .bind(name)
+    .bind(sqlx::types::Json(&request.query))
+    .fetch_optional(&state.db)
+    .await
+    .map_err(db_error)?
+    .ok_or_else(|| ApiError::Conflict(format!("Saved search '{name}' already exists")))?;
+
+    info!(saved_search_id = %row.id, user_id = %row.user_id, name = %row.name, "Search saved");
+    let saved = SavedSearchResponse::from(row);
+    let change = AuditChange::new(saved.id).after(&saved);
+    Ok((StatusCode::CREATED, change.into_extension(), Json(saved)))
+}
+
+/// Delete a saved search.
+#[utoipa::path(
+    delete,
+    path = "/api/v1/search/saved/{id}",
+    params(("id" = Uuid, Path, description = "Saved search ID")),
+    responses(
+        (status = 204, description = "Saved search deleted"),
+        (status = 404, description = "Saved search not found")
+    ),
+    tag = "Search"
+)]
+pub async fn delete_saved(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> ApiResult<(StatusCode, Extension<AuditChange>)> {
+    let deleted: SavedSearchRow = sqlx::query_as(&format!(
+        "DELETE FROM saved_searches WHERE id = $1 RETURNING {SAVED_COLUMNS}"
+    ))
+    .bind(id)
+    .fetch_optional(&state.db)
+    .await
+    .map_err(db_error)?
+    .ok_or_else(|| ApiError::NotFound(format!("Saved search {id}")))?;
+    let change = AuditChange::new(id).before(&SavedSearchResponse::from(deleted));
+    Ok((StatusCode::NO_CONTENT, change.into_extension()))
+}
+
+/// Run a saved search, one page at a time.
+///
+/// The run is also recorded in the owner's search history.
+#[utoipa::path(
+    post,
+    path = "/api/v1/search/saved/{id}/run",
+    params(("id" = Uuid, Path, description = "Saved search ID"), CursorQuery),
+    responses(
+        (status = 200, description = "Search results", body = SearchResponse),
+        (status = 400, description = "Invalid cursor"),
+        (status = 404, description = "Saved search not found")
+    ),
+    tag = "Search"
+)]
+pub async fn run_saved(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Path(id): Path<Uuid>,
+    Query(cursor): Query<CursorQuery>,
+) -> ApiResult<Json<SearchResponse>> {
+    let page = cursor.page_request()?;
+    let row: SavedSearchRow = sqlx::query_as(&format!(
+        "UPDATE saved_searches SET last_run_at = NOW() WHERE id = $1 RETURNING {SAVED_COLUMNS}"
+    ))
+    .bind(id)
+    .fetch_optional(&state.db)
+    .await
+    .map_err(db_error)?
+    .ok_or_else(|| ApiError::NotFound(format!("Saved search {id}")))?;
+
+    let response = search_page(&state, &user.tenant, &row.query.0, &page).await;
+    if page.after.is_none() {
+        record(&state.db, &row.user_id, &row.query.0, response.total_results).await;
+    }
+    Ok(Json(response))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search_facets::SearchFilters;
+
+    #[test]
+    fn test_search_query_round_trips_as_tagged_json() {
+        let query = SearchQuery::Contextual {
+            ticket_key: "PROJ-12".to_string(),
+            title: "Checkout payment fails".to_string(),
+            description: None,
+        };
+        let json = serde_json::to_value(&query).unwrap();
+        assert_eq!(json["kind"], "contextual");
+        assert_eq!(json["ticketKey"], "PROJ-12");
+        assert_eq!(serde_json::from_value::<SearchQuery>(json).unwrap(), query);
+
+        let keywords: SearchQuery =
+            serde_json::from_str(r#"{"kind":"keywords","keywords":["refund"]}"#).unwrap();
+        assert_eq!(
+            keywords,
+            SearchQuery::Keywords {
+                keywords: vec!["refund".to_string()],
+                filters: SearchFilters::default(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_validate_query_rejects_empty_searches() {
+        assert!(validate_query(&SearchQuery::Keywords {
+            keywords: vec![],
+            filters: SearchFilters::default(),
+        })
+        .is_err());
+        assert!(validate_query(&SearchQuery::Keywords {
+            keywords: vec![" ".to_string()],
+            filters: SearchFilters::default(),
+        })
+        .is_err());
+        assert!(validate_query(&SearchQuery::Contextual {
+            ticket_key: "PROJ-1".to_string(),
+            title: String::new(),
+            description: Some("details".to_string()),
+        })
+        .is_err());
+        assert!(validate_query(&SearchQuery::Keywords {
+            keywords: vec!["login".to_string()],
+            filters: SearchFilters {
+                sources: vec!["testmo".to_string()],
+                ..SearchFilters::default()
+            },
+        })
+        .is_ok());
+    }
+}
@@ -3,25 +3,36 @@
 //! Provides endpoints for the initial configuration wizard:
 //! - Profile configuration
 //! - Integration testing (Jira, Postman, Testmo)
+//! - Jira OAuth 2.0 + PKCE authorization
 //! - Setup completion and status
 
-use std::sync::Arc;
+use std::sync::{Arc, PoisonError};
 
 use axum::{
-    extract::{Json, State},
+    extract::{Json, Query, State},
     http::StatusCode,
     response::IntoResponse,
     routing::{get, post},
     Router,
 };
 use serde::{Deserialize, Serialize};
-use tokio::sync::Mutex;
+use tokio::sync::{Mutex, MutexGuard};
+use tokio::task::JoinHandle;
 use tracing::{info, warn};
 use utoipa::ToSchema;
+use validator::Validate;
 
 use crate::app::AppState;
+use crate::setup_state::{InMemorySetupStateStore, SetupStateStore};
+use crate::validation::{not_blank, ValidatedJson};
+use qa_pms_config::Settings;
 use qa_pms_core::error::ApiError;
 use qa_pms_core::health::HealthCheck;
+use qa_pms_core::{AuthStateStore, StoredTokens, TokenStore};
+use qa_pms_jira::{
+    find_cloud_id, spawn_token_refresh_task, InMemoryAuthStateStore, JiraAuthError,
+    JiraOAuthClient, JiraOAuthConfig,
+};
 
 // ============================================================================
 // Router
@@ -34,6 +45,11 @@ pub fn router() -> Router<AppState> {
     Router::new()
         .route("/api/v1/setup/profile", post(save_profile))
         .route("/api/v1/setup/integrations/jira/test", post(test_jira))
+        .route(
+            "/api/v1/setup/integrations/jira/oauth/start",
+            post(start_jira_oauth),
+        )
+        .route(JIRA_OAUTH_CALLBACK_PATH, get(jira_oauth_callback))
         .route(
             "/api/v1/setup/integrations/postman/test",
             post(test_postman),
@@ -43,27 +59,37 @@ pub fn router() -> Router<AppState> {
         .route("/api/v1/setup/status", get(get_status))
 }
 
+/// Path Atlassian redirects to after the user authorizes the app.
+pub const JIRA_OAUTH_CALLBACK_PATH: &str = "/api/v1/setup/integrations/jira/oauth/callback";
+
+/// Integration name Jira OAuth tokens are stored under.
+const JIRA_TOKEN_INTEGRATION: &str = "jira";
+
 // ============================================================================
 // Request/Response Types
 // ============================================================================
 
 /// User profile configuration request.
-#[derive(Debug, Clone, Deserialize, ToSchema)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Validate)]
 #[serde(rename_all = "camelCase")]
 pub struct ProfileRequest {
     /// User's display name
+    #[validate(custom(function = "not_blank"))]
     pub display_name: String,
     /// Jira email/username
+    #[validate(email)]
     pub jira_email: String,
     /// Ticket states to show (e.g., "Ready for QA", "In Progress")
+    #[validate(length(min = 1, message = "at least one ticket state is required"))]
     pub ticket_states: Vec<String>,
 }
 
 /// Jira connection test request.
-#[derive(Debug, Clone, Deserialize, ToSchema)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Validate)]
 #[serde(rename_all = "camelCase")]
 pub struct JiraTestRequest {
     /// Jira instance URL (e.g., `https://company.atlassian.net`)
+    #[validate(url)]
     pub instance_url: String,
 
     // === API Token Authentication (recommended) ===
@@ -96,17 +122,17 @@ impl JiraTestRequest {
     }
 
     /// Check if OAuth auth is configured.
-    #[allow(dead_code)]
     pub const fn has_oauth(&self) -> bool {
         self.cloud_id.is_some() && self.access_token.is_some()
     }
 }
 
 /// Postman connection test request.
-#[derive(Debug, Clone, Deserialize, ToSchema)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Validate)]
 #[serde(rename_all = "camelCase")]
 pub struct PostmanTestRequest {
     /// Postman API key
+    #[validate(custom(function = "not_blank"))]
     pub api_key: String,
     /// Optional workspace ID to validate
     #[serde(default)]
@@ -114,26 +140,52 @@ pub struct PostmanTestRequest {
 }
 
 /// Testmo connection test request.
-#[derive(Debug, Clone, Deserialize, ToSchema)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Validate)]
 #[serde(rename_all = "camelCase")]
 pub struct TestmoTestRequest {
     /// Testmo instance URL
+    #[validate(url)]
     pub instance_url: String,
     /// Testmo API key
+    #[validate(custom(function = "not_blank"))]
     pub api_key: String,
 }
 
 /// Splunk configuration request (manual, no test).
-#[derive(Debug, Clone, Deserialize, ToSchema)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Validate)]
 #[serde(rename_all = "camelCase")]
 pub struct SplunkConfigRequest {
     /// Splunk base URL
+    #[validate(url)]
     pub base_url: String,
     /// Default index to search
     #[serde(default)]
     pub default_index: Option<String>,
 }
 
+/// Jira OAuth authorization start response.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct OAuthStartResponse {
+    /// Atlassian URL to send the user to
+    pub authorization_url: String,
+    /// Where Atlassian redirects back to; must match the app's configured callback
+    pub redirect_uri: String,
+}
+
+/// Query parameters Atlassian adds to the OAuth callback.
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct OAuthCallbackQuery {
+    /// Authorization code
+    pub code: Option<String>,
+    /// State issued by the start endpoint
+    pub state: Option<String>,
+    /// Error code when the user did not authorize the app
+    pub error: Option<String>,
+    /// Error details
+    pub error_description: Option<String>,
+}
+
 /// Connection test response for all integrations.
 #[derive(Debug, Serialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
@@ -186,20 +238,24 @@ impl ConnectionTestResponse {
 }
 
 /// Setup completion request.
-#[derive(Debug, Deserialize, ToSchema)]
+#[derive(Debug, Deserialize, ToSchema, Validate)]
 #[serde(rename_all = "camelCase")]
 pub struct CompleteSetupRequest {
     /// Jira configuration
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[validate(nested)]
     pub jira: Option<JiraTestRequest>,
     /// Postman configuration
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[validate(nested)]
     pub postman: Option<PostmanTestRequest>,
     /// Testmo configuration
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[validate(nested)]
     pub testmo: Option<TestmoTestRequest>,
     /// Splunk configuration (manual)
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[validate(nested)]
     pub splunk: Option<SplunkConfigRequest>,
 }
 
@@ -246,8 +302,10 @@ pub struct SuccessResponse {
 
 /// Temporary setup state stored during the wizard flow.
 ///
-/// This is stored in memory until setup is complete, then persisted to config.
-#[derive(Debug, Default, Clone)]
+/// Saved through a `SetupStateStore` on every change, so the wizard can
+/// resume after a restart, and written to the user config on completion.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct SetupState {
     /// User profile configuration
     pub profile: Option<ProfileRequest>,
@@ -291,12 +349,140 @@ impl SetupState {
     }
 }
 
-/// Thread-safe setup state store.
-pub type SetupStore = Arc<Mutex<SetupState>>;
+/// Shared setup state, written through to a `SetupStateStore`.
+#[derive(Clone)]
+pub struct SetupStore {
+    state: Arc<Mutex<SetupState>>,
+    backend: Arc<dyn SetupStateStore>,
+}
+
+impl SetupStore {
+    /// Open a store, resuming any state the backend has saved.
+    ///
+    /// Unreadable saved state is logged and the wizard starts over.
+    pub async fn open(backend: Arc<dyn SetupStateStore>) -> Self {
+        let state = match backend.load().await {
+            Ok(Some(state)) => {
+                info!(
+                    integrations = ?state.configured_integrations(),
+                    "Resuming saved setup wizard state"
+                );
+                state
+            }
+            Ok(None) => SetupState::default(),
+            Err(e) => {
+                warn!(error = %e, "Failed to load setup wizard state, starting over");
+                SetupState::default()
+            }
+        };
+        Self {
+            state: Arc::new(Mutex::new(state)),
+            backend,
+        }
+    }
+
+    /// Lock the state for reading.
+    pub async fn lock(&self) -> MutexGuard<'_, SetupState> {
+        self.state.lock().await
+    }
+
+    /// Change the state and save it.
+    ///
+    /// The lock is held while saving, so saves land in the order of the changes.
+    pub async fn update(&self, change: impl FnOnce(&mut SetupState) + Send) -> Result<(), ApiError> {
+        let mut state = self.state.lock().await;
+        change(&mut state);
+        self.backend
+            .save(&state)
+            .await
+            .map_err(|e| ApiError::Internal(e.context("Failed to save setup wizard state")))
+    }
+}
+
+/// OAuth state shared by the Jira authorization routes.
+#[derive(Clone)]
+pub struct JiraOAuthFlow {
+    auth_states: Arc<dyn AuthStateStore>,
+    tokens: Option<Arc<dyn TokenStore>>,
+    refresh_task: Arc<std::sync::Mutex<Option<JoinHandle<()>>>>,
+}
+
+impl JiraOAuthFlow {
+    /// Create a flow; without a token store, tokens live only in the setup state
+    /// and are not refreshed.
+    pub fn new(tokens: Option<Arc<dyn TokenStore>>) -> Self {
+        Self {
+            auth_states: Arc::new(InMemoryAuthStateStore::new()),
+            tokens,
+            refresh_task: Arc::new(std::sync::Mutex::new(None)),
+        }
+    }
+
+    /// Current Jira access token from the token store, if one was stored.
+    pub async fn access_token(&self) -> Option<String> {
+        let tokens = self.tokens.as_ref()?;
+        match tokens.get_tokens(JIRA_TOKEN_INTEGRATION).await {
+            Ok(stored) => stored.map(|t| t.access_token),
+            Err(e) => {
+                warn!(error = %e, "Failed to read stored Jira tokens");
+                None
+            }
+        }
+    }
+
+    /// Keep the stored Jira tokens fresh, replacing any running refresh task.
+    pub fn start_refresh(&self, client: JiraOAuthClient) {
+        let Some(tokens) = self.tokens.clone() else {
+            return;
+        };
+        let handle = spawn_token_refresh_task(tokens, Arc::new(client));
+        let previous = self
+            .refresh_task
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .replace(handle);
+        if let Some(previous) = previous {
+            previous.abort();
+        }
+    }
+}
+
+/// Redirect URI for the Jira OAuth flow.
+///
+/// `JIRA_REDIRECT_URI` wins; otherwise the callback route on this server.
+pub fn jira_redirect_uri(settings: &Settings) -> String {
+    settings
+        .jira
+        .as_ref()
+        .and_then(|jira| jira.redirect_uri.clone())
+        .unwrap_or_else(|| format!("http://{}{JIRA_OAUTH_CALLBACK_PATH}", settings.server_addr()))
+}
 
-/// Create a new setup store.
-pub fn create_setup_store() -> SetupStore {
-    Arc::new(Mutex::new(SetupState::default()))
+/// OAuth client for the credentials entered in the wizard.
+pub fn jira_oauth_client(settings: &Settings, jira: &JiraTestRequest) -> Option<JiraOAuthClient> {
+    let client_id = jira.client_id.as_ref().filter(|s| !s.trim().is_empty())?;
+    let client_secret = jira.client_secret.as_ref().filter(|s| !s.trim().is_empty())?;
+    Some(JiraOAuthClient::new(JiraOAuthConfig {
+        client_id: client_id.clone(),
+        client_secret: client_secret.clone().into(),
+        redirect_uri: jira_redirect_uri(settings),
+        scopes: JiraOAuthConfig::default_scopes(),
+    }))
+}
+
+fn oauth_error(e: JiraAuthError) -> ApiError {
+    match e {
+        JiraAuthError::InvalidState | JiraAuthError::InvalidCode | JiraAuthError::UserDenied => {
+            ApiError::Unauthorized(e.to_string())
+        }
+        JiraAuthError::Network(_) => ApiError::ServiceUnavailable(e.to_string()),
+        _ => ApiError::ExternalService(e.to_string()),
+    }
+}
+
+/// Create a setup store that keeps state in memory only.
+pub async fn create_setup_store() -> SetupStore {
+    SetupStore::open(Arc::new(InMemorySetupStateStore::default())).await
 }
 
 // ============================================================================
@@ -312,32 +498,20 @@ pub fn create_setup_store() -> SetupStore {
     request_body = ProfileRequest,
     responses(
         (status = 200, description = "Profile saved successfully", body = SuccessResponse),
-        (status = 400, description = "Invalid request body", body = qa_pms_core::error::ErrorResponse)
+        (status = 422, description = "Invalid request body", body = qa_pms_core::error::ErrorResponse)
     ),
     tag = "Setup"
 )]
 pub async fn save_profile(
     State(state): State<AppState>,
-    Json(req): Json<ProfileRequest>,
+    ValidatedJson(req): ValidatedJson<ProfileRequest>,
 ) -> Result<impl IntoResponse, ApiError> {
-    // Validate required fields
-    if req.display_name.trim().is_empty() {
-        return Err(ApiError::Validation("Display name is required".into()));
-    }
-    if req.jira_email.trim().is_empty() {
-        return Err(ApiError::Validation("Jira email is required".into()));
-    }
-    if req.ticket_states.is_empty() {
-        return Err(ApiError::Validation(
-            "At least one ticket state is required".into(),
-        ));
-    }
-
     // Store in setup state
-    {
-        let mut setup = state.setup_store.lock().await;
-        setup.profile = Some(req.clone());
-    }
+    let profile = req.clone();
+    state
+        .setup_store
+        .update(|setup| setup.profile = Some(profile))
+        .await?;
 
     info!(
         display_name = %req.display_name,
@@ -417,10 +591,7 @@ pub async fn test_jira(
         }
 
         // Store successful test in setup state
-        {
-            let mut setup = state.setup_store.lock().await;
-            setup.jira = Some(req);
-        }
+        state.setup_store.update(|setup| setup.jira = Some(req)).await?;
 
         Ok(Json(
             ConnectionTestResponse::success(format!(
@@ -430,19 +601,154 @@ pub async fn test_jira(
             .with_projects(1),
         ))
     } else {
-        // OAuth flow - just validate and store for now
-        info!(url = %req.instance_url, "Storing Jira OAuth credentials (OAuth flow not implemented)");
+        // OAuth flow - store the client credentials until the user authorizes
+        info!(url = %req.instance_url, "Storing Jira OAuth credentials");
 
-        // Store credentials for OAuth flow
-        {
-            let mut setup = state.setup_store.lock().await;
-            setup.jira = Some(req);
-        }
+        state.setup_store.update(|setup| setup.jira = Some(req)).await?;
 
-        Ok(Json(
-            ConnectionTestResponse::success("OAuth credentials stored. Complete OAuth flow to connect."),
+        Ok(Json(ConnectionTestResponse::success(
+            "OAuth credentials stored. Start the OAuth authorization to connect.",
+        )))
+    }
+}
+
+/// Start the Jira OAuth authorization.
+///
+/// Uses the client ID and secret saved by the Jira connection test. The user
+/// should be sent to the returned URL; Atlassian then redirects to the
+/// callback route.
+#[utoipa::path(
+    post,
+    path = "/api/v1/setup/integrations/jira/oauth/start",
+    responses(
+        (status = 200, description = "Authorization URL", body = OAuthStartResponse),
+        (status = 400, description = "No OAuth client credentials saved", body = qa_pms_core::error::ErrorResponse)
+    ),
+    tag = "Setup"
+)]
+pub async fn start_jira_oauth(
+    State(state): State<AppState>,
+) -> Result<Json<OAuthStartResponse>, ApiError> {
+    let setup = state.setup_store.lock().await;
+    let client = setup
+        .jira
+        .as_ref()
+        .and_then(|jira| jira_oauth_client(&state.settings, jira))
+        .ok_or_else(|| {
+            ApiError::Validation(
+                "Save Jira OAuth client credentials (client_id + client_secret) first".into(),
+            )
+        })?;
+    drop(setup);
+
+    let (authorization_url, auth_state) = client.build_authorization_url();
+    state
+        .jira_oauth
+        .auth_states
+        .store(&auth_state.state, &auth_state.code_verifier)
+        .await
+        .map_err(ApiError::Internal)?;
+    info!(redirect_uri = %client.redirect_uri(), "Started Jira OAuth authorization");
+
+    Ok(Json(OAuthStartResponse {
+        authorization_url,
+        redirect_uri: client.redirect_uri().to_string(),
+    }))
+}
+
+/// Finish the Jira OAuth authorization.
+///
+/// Exchanges the authorization code for tokens, stores them, and records the
+/// Jira site's cloud ID so setup can be completed with OAuth.
+#[utoipa::path(
+    get,
+    path = "/api/v1/setup/integrations/jira/oauth/callback",
+    params(OAuthCallbackQuery),
+    responses(
+        (status = 200, description = "Authorization result", body = ConnectionTestResponse),
+        (status = 400, description = "Missing code or no matching Jira site", body = qa_pms_core::error::ErrorResponse),
+        (status = 401, description = "Unknown or expired state, or code rejected", body = qa_pms_core::error::ErrorResponse)
+    ),
+    tag = "Setup"
+)]
+pub async fn jira_oauth_callback(
+    State(state): State<AppState>,
+    Query(query): Query<OAuthCallbackQuery>,
+) -> Result<Json<ConnectionTestResponse>, ApiError> {
+    if let Some(error) = query.error {
+        warn!(error = %error, "Jira OAuth authorization was not granted");
+        return Ok(Json(ConnectionTestResponse::failure(format!(
+            "Jira authorization failed: {}",
+            query.error_description.unwrap_or(error)
+        ))));
+    }
+    let (Some(code), Some(auth_state)) = (query.code, query.state) else {
+        return Err(ApiError::Validation(
+            "OAuth callback requires code and state".into(),
+        ));
+    };
+
+    // The state is single-use, which also guards against replayed callbacks
+    let code_verifier = state
+        .jira_oauth
+        .auth_states
+        .get_and_remove(&auth_state)
+        .await
+        .map_err(ApiError::Internal)?
+        .ok_or_else(|| oauth_error(JiraAuthError::InvalidState))?;
+
+    let jira = state.setup_store.lock().await.jira.clone();
+    let (client, instance_url) = jira
+        .as_ref()
+        .and_then(|jira| {
+            jira_oauth_client(&state.settings, jira).map(|c| (c, jira.instance_url.clone()))
+        })
+        .ok_or_else(|| ApiError::Validation("Jira OAuth client credentials are missing".into()))?;
+
+    let tokens = client
+        .exchange_code_for_tokens(&code, &code_verifier)
+        .await
+        .map_err(oauth_error)?;
+    let resources = client
+        .accessible_resources(&tokens.access_token)
+        .await
+        .map_err(oauth_error)?;
+    let cloud_id = find_cloud_id(&resources, &instance_url).ok_or_else(|| {
+        ApiError::Validation(format!(
+            "The authorized account has no access to {instance_url}"
         ))
+    })?;
+
+    if let Some(store) = &state.jira_oauth.tokens {
+        let stored = StoredTokens::new(
+            JIRA_TOKEN_INTEGRATION,
+            tokens.access_token.clone(),
+            tokens.refresh_token.clone().unwrap_or_default(),
+            tokens.expires_in,
+        );
+        store.store_tokens(stored).await.map_err(ApiError::Internal)?;
     }
+
+    let access_token = tokens.access_token.clone();
+    let saved_cloud_id = cloud_id.clone();
+    state
+        .setup_store
+        .update(|setup| {
+            if let Some(jira) = setup.jira.as_mut() {
+                jira.cloud_id = Some(saved_cloud_id);
+                jira.access_token = Some(access_token);
+            }
+        })
+        .await?;
+
+    if tokens.refresh_token.is_some() {
+        state.jira_oauth.start_refresh(client);
+    }
+    info!(cloud_id = %cloud_id, url = %instance_url, "Jira OAuth authorization completed");
+
+    Ok(Json(ConnectionTestResponse::success(
+        "Connected to Jira with OAuth. Setup can now be completed.",
+    )))
 }
 
 /// Test Postman connection.
@@ -480,10 +786,7 @@ pub async fn test_postman(
     );
 
     // Store successful test in setup state
-    {
-        let mut setup = state.setup_store.lock().await;
-        setup.postman = Some(req);
-    }
+    state.setup_store.update(|setup| setup.postman = Some(req)).await?;
 
     Ok(Json(
         ConnectionTestResponse::success("Connected to Postman successfully").with_workspaces(3),
@@ -522,10 +825,7 @@ pub async fn test_testmo(
     info!(url = %req.instance_url, "Testing Testmo connection");
 
     // Store successful test in setup state
-    {
-        let mut setup = state.setup_store.lock().await;
-        setup.testmo = Some(req);
-    }
+    state.setup_store.update(|setup| setup.testmo = Some(req)).await?;
 
     Ok(Json(
         ConnectionTestResponse::success("Connected to Testmo successfully").with_projects(2),
@@ -541,20 +841,21 @@ pub async fn test_testmo(
     request_body = CompleteSetupRequest,
     responses(
         (status = 200, description = "Setup completion result", body = CompleteSetupResponse),
-        (status = 400, description = "Validation failed", body = qa_pms_core::error::ErrorResponse)
+        (status = 400, description = "Validation failed", body = qa_pms_core::error::ErrorResponse),
+        (status = 422, description = "Invalid request body", body = qa_pms_core::error::ErrorResponse)
     ),
     tag = "Setup"
 )]
 #[allow(clippy::too_many_lines)]
 pub async fn complete_setup(
     State(state): State<AppState>,
-    Json(req): Json<CompleteSetupRequest>,
+    ValidatedJson(req): ValidatedJson<CompleteSetupRequest>,
 ) -> Result<Json<CompleteSetupResponse>, ApiError> {
     use qa_pms_config::{
         JiraAuthInput, JiraInput, PostmanInput, ProfileInput, SetupWizardInput, SplunkInput,
         TestmoInput, UserConfig,
     };
-    use secrecy::{ExposeSecret, SecretString};
+    use secrecy::SecretString;
 
     let mut errors = Vec::new();
     let setup = state.setup_store.lock().await;
@@ -580,30 +881,31 @@ pub async fn complete_setup(
 
     // Update setup state with any new configuration from the request
     drop(setup); // Release lock
-    {
-        let mut setup = state.setup_store.lock().await;
-
-        if let Some(jira) = req.jira {
-            info!(url = %jira.instance_url, "Saving Jira configuration");
-            setup.jira = Some(jira);
-        }
-        if let Some(postman) = req.postman {
-            info!("Saving Postman configuration");
-            setup.postman = Some(postman);
-        }
-        if let Some(testmo) = req.testmo {
-            info!(url = %testmo.instance_url, "Saving Testmo configuration");
-            setup.testmo = Some(testmo);
-        }
-        if let Some(splunk) = req.splunk {
-            info!(
-                base_url = %splunk.base_url,
-                default_index = ?splunk.default_index,
-                "Saving Splunk configuration"
-            );
-            setup.splunk = Some(splunk);
-        }
-    }
+    state
+        .setup_store
+        .update(|setup| {
+            if let Some(jira) = req.jira {
+                info!(url = %jira.instance_url, "Saving Jira configuration");
+                setup.jira = Some(jira);
+            }
+            if let Some(postman) = req.postman {
+                info!("Saving Postman configuration");
+                setup.postman = Some(postman);
+            }
+            if let Some(testmo) = req.testmo {
+                info!(url = %testmo.instance_url, "Saving Testmo configuration");
+                setup.testmo = Some(testmo);
+            }
+            if let Some(splunk) = req.splunk {
+                info!(
+                    base_url = %splunk.base_url,
+                    default_index = ?splunk.default_index,
+                    "Saving Splunk configuration"
+                );
+                setup.splunk = Some(splunk);
+            }
+        })
+        .await?;
 
     // Build user config from setup state
     let setup = state.setup_store.lock().await;
@@ -621,6 +923,15 @@ pub async fn complete_setup(
             api_token: SecretString::from(api_token.clone()),
         }
     } else if let (Some(client_id), Some(client_secret)) = (&jira.client_id, &jira.client_secret) {
+        if !jira.has_oauth() {
+            return Ok(Json(CompleteSetupResponse {
+                success: false,
+                errors: vec![
+                    "Authorize Jira through the OAuth flow before completing setup".to_string(),
+                ],
+                configured_integrations: setup.configured_integrations(),
+            }));
+        }
         JiraAuthInput::OAuth {
             client_id: client_id.clone(),
             client_secret: SecretString::from(client_secret.clone()),
@@ -656,9 +967,7 @@ pub async fn complete_setup(
     };
 
     // Create encryptor using app encryption key
-    let encryptor = qa_pms_config::Encryptor::from_hex_key(
-        state.settings.encryption_key.expose_secret()
-    ).map_err(ApiError::Internal)?;
+    let encryptor = state.settings.encryptor().map_err(ApiError::Internal)?;
 
     // Generate user config with encrypted secrets
     let user_config = UserConfig::from_wizard_input(wizard_input, &encryptor)
@@ -711,14 +1020,19 @@ pub async fn complete_setup(
     tag = "Setup"
 )]
 pub async fn get_status(State(state): State<AppState>) -> Json<SetupStatusResponse> {
-    let setup = state.setup_store.lock().await;
+    Json(setup_status(&state.setup_store, state.settings.server_addr()).await)
+}
+
+/// Current setup wizard status.
+pub(crate) async fn setup_status(store: &SetupStore, server_address: String) -> SetupStatusResponse {
+    let setup = store.lock().await;
 
-    Json(SetupStatusResponse {
+    SetupStatusResponse {
         complete: setup.is_complete(),
         configured_integrations: setup.configured_integrations(),
         profile_configured: setup.is_profile_configured(),
-        server_address: state.settings.server_addr(),
-    })
+        server_address,
+    }
 }
 
 // ============================================================================
@@ -796,4 +1110,42 @@ mod tests {
         });
         assert!(state.is_complete());
     }
+
+    #[tokio::test]
+    async fn test_setup_store_resumes_saved_state() {
+        let backend: Arc<dyn SetupStateStore> = Arc::new(InMemorySetupStateStore::default());
+        let store = SetupStore::open(Arc::clone(&backend)).await;
+        store
+            .update(|setup| {
+                setup.postman = Some(PostmanTestRequest {
+                    api_key: "pmak-0123456789abcdef0123456789abcdef".to_string(),
+                    workspace_id: None,
+                });
+            })
+            .await
+            .unwrap();
+
+        // A store opened later, as after a restart, picks up the change
+        let resumed = SetupStore::open(backend).await;
+        assert_eq!(
+            resumed.lock().await.configured_integrations(),
+            vec!["postman".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_oauth_errors_map_to_api_errors() {
+        assert!(matches!(
+            oauth_error(JiraAuthError::InvalidState),
+            ApiError::Unauthorized(_)
+        ));
+        assert!(matches!(
+            oauth_error(JiraAuthError::UserDenied),
+            ApiError::Unauthorized(_)
+        ));
+        assert!(matches!(
+            oauth_error(JiraAuthError::ParseError("bad".to_string())),
+            ApiError::ExternalService(_)
+        ));
+    }
 }
@@ -2,25 +2,31 @@
 //!
 //! Epic 11: Provides endpoints for:
 //! - Query template CRUD operations
-//! - Query preparation and execution simulation
+//! - Query preparation, and execution through the Splunk REST API when
+//!   configured (simulated otherwise)
 //! - Query history tracking
 
 use axum::{
     extract::{Path, Query, State},
     routing::{delete, get, post, put},
-    Json, Router,
+    Extension, Json, Router,
 };
 use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use utoipa::ToSchema;
 use uuid::Uuid;
+use validator::Validate;
 
-use crate::app::AppState;
+use crate::app::{splunk_client, AppState};
+use crate::audit::AuditChange;
+use crate::auth::{CurrentUser, Role};
+use crate::search_index::{IndexDocument, IndexSource};
+use crate::validation::{not_blank, ValidatedJson};
 use qa_pms_core::error::ApiError;
 use qa_pms_splunk::{
-    CreateTemplateInput, PreparedQuery, QueryTemplate, QueryTemplateService,
-    TemplateCategory, UpdateTemplateInput, LogEntry,
+    CreateTemplateInput, PreparedQuery, QueryResult, QueryTemplate, QueryTemplateService,
+    SplunkError, TemplateCategory, UpdateTemplateInput, LogEntry,
 };
 
 type ApiResult<T> = Result<T, ApiError>;
@@ -55,14 +61,16 @@ pub struct ListTemplatesQuery {
 }
 
 /// Request to create a new template.
-#[derive(Debug, Deserialize, ToSchema)]
+#[derive(Debug, Deserialize, ToSchema, Validate)]
 #[serde(rename_all = "camelCase")]
 pub struct CreateTemplateRequest {
     /// Template name.
+    #[validate(custom(function = "not_blank"))]
     pub name: String,
     /// Template description.
     pub description: Option<String>,
     /// SPL query with placeholders.
+    #[validate(custom(function = "not_blank"))]
     pub query: String,
     /// Category for grouping.
     pub category: TemplateCategory,
@@ -152,11 +160,12 @@ pub struct PrepareQueryResponse {
     pub splunk_url: Option<String>,
 }
 
-/// Request to execute a query (simulation).
-#[derive(Debug, Deserialize, ToSchema)]
+/// Request to execute a query.
+#[derive(Debug, Deserialize, ToSchema, Validate)]
 #[serde(rename_all = "camelCase")]
 pub struct ExecuteQueryRequest {
     /// The query to execute.
+    #[validate(custom(function = "not_blank"))]
     pub query: String,
     /// Time range start.
     pub time_start: DateTime<Utc>,
@@ -166,6 +175,7 @@ pub struct ExecuteQueryRequest {
     pub index: Option<String>,
     /// Maximum results to return.
     #[serde(default = "default_limit")]
+    #[validate(range(min = 1, max = 1000))]
     pub limit: i32,
 }
 
@@ -255,13 +265,14 @@ pub struct PlaceholdersResponse {
 )]
 pub async fn list_templates(
     State(state): State<AppState>,
+    user: CurrentUser,
     Query(query): Query<ListTemplatesQuery>,
 ) -> ApiResult<Json<TemplatesListResponse>> {
     let service = QueryTemplateService::new(state.db.clone());
-    
-    // TODO: Get user_id from auth context
-    let user_id: Option<Uuid> = None;
-    
+
+    // Admins see every template, everyone else system ones plus their own
+    let user_id = (!user.role.satisfies(Role::Admin)).then_some(user.id.0);
+
     let templates = service
         .list_templates(query.category, user_id)
         .await
@@ -295,20 +306,21 @@ pub async fn get_template(
     Path(id): Path<Uuid>,
 ) -> ApiResult<Json<TemplateResponse>> {
     let service = QueryTemplateService::new(state.db.clone());
-    
-    let template = service
-        .get_template(id)
-        .await
-        .map_err(|e| match e {
-            qa_pms_splunk::SplunkError::TemplateNotFound(_) => {
-                ApiError::NotFound(format!("Template {id} not found"))
-            }
-            _ => ApiError::Internal(anyhow::anyhow!("Failed to get template: {e}")),
-        })?;
+    let template = fetch_template(&service, id).await?;
 
     Ok(Json(template.into()))
 }
 
+/// Get a template, or fail with `NotFound`.
+async fn fetch_template(service: &QueryTemplateService, id: Uuid) -> ApiResult<QueryTemplate> {
+    service.get_template(id).await.map_err(|e| match e {
+        qa_pms_splunk::SplunkError::TemplateNotFound(_) => {
+            ApiError::NotFound(format!("Template {id} not found"))
+        }
+        _ => ApiError::Internal(anyhow::anyhow!("Failed to get template: {e}")),
+    })
+}
+
 /// Create a new template.
 #[utoipa::path(
     post,
@@ -323,13 +335,12 @@ pub async fn get_template(
 )]
 pub async fn create_template(
     State(state): State<AppState>,
-    Json(req): Json<CreateTemplateRequest>,
-) -> ApiResult<Json<TemplateResponse>> {
+    user: CurrentUser,
+    ValidatedJson(req): ValidatedJson<CreateTemplateRequest>,
+) -> ApiResult<(Extension<AuditChange>, Json<TemplateResponse>)> {
     let service = QueryTemplateService::new(state.db.clone());
-    
-    // TODO: Get user_id from auth context
-    let user_id = Uuid::new_v4(); // Placeholder
-    
+    let user_id = user.id.0;
+
     let input = CreateTemplateInput {
         name: req.name,
         description: req.description,
@@ -346,8 +357,11 @@ pub async fn create_template(
             }
             _ => ApiError::Internal(anyhow::anyhow!("Failed to create template: {e}")),
         })?;
+    state.search_index.upsert([IndexDocument::splunk_template(&template)]);
 
-    Ok(Json(template.into()))
+    let created = TemplateResponse::from(template);
+    let change = AuditChange::new(created.id).after(&created);
+    Ok((change.into_extension(), Json(created)))
 }
 
 /// Update a template.
@@ -368,14 +382,14 @@ pub async fn create_template(
 )]
 pub async fn update_template(
     State(state): State<AppState>,
+    user: CurrentUser,
     Path(id): Path<Uuid>,
     Json(req): Json<UpdateTemplateRequest>,
-) -> ApiResult<Json<TemplateResponse>> {
+) -> ApiResult<(Extension<AuditChange>, Json<TemplateResponse>)> {
     let service = QueryTemplateService::new(state.db.clone());
-    
-    // TODO: Get user_id from auth context
-    let user_id = Uuid::new_v4(); // Placeholder
-    
+    let user_id = user.id.0;
+    let before = TemplateResponse::from(fetch_template(&service, id).await?);
+
     let input = UpdateTemplateInput {
         name: req.name,
         description: req.description,
@@ -395,8 +409,11 @@ pub async fn update_template(
             }
             _ => ApiError::Internal(anyhow::anyhow!("Failed to update template: {e}")),
         })?;
+    state.search_index.upsert([IndexDocument::splunk_template(&template)]);
 
-    Ok(Json(template.into()))
+    let updated = TemplateResponse::from(template);
+    let change = AuditChange::new(id).before(&before).after(&updated);
+    Ok((change.into_extension(), Json(updated)))
 }
 
 /// Delete a template.
@@ -416,13 +433,13 @@ pub async fn update_template(
 )]
 pub async fn delete_template(
     State(state): State<AppState>,
+    user: CurrentUser,
     Path(id): Path<Uuid>,
-) -> ApiResult<axum::http::StatusCode> {
+) -> ApiResult<(axum::http::StatusCode, Extension<AuditChange>)> {
     let service = QueryTemplateService::new(state.db.clone());
-    
-    // TODO: Get user_id from auth context
-    let user_id = Uuid::new_v4(); // Placeholder
-    
+    let user_id = user.id.0;
+    let before = TemplateResponse::from(fetch_template(&service, id).await?);
+
     service
         .delete_template(id, user_id)
         .await
@@ -435,8 +452,10 @@ pub async fn delete_template(
             }
             _ => ApiError::Internal(anyhow::anyhow!("Failed to delete template: {e}")),
         })?;
+    state.search_index.remove(IndexSource::SplunkTemplate, &id.to_string());
 
-    Ok(axum::http::StatusCode::NO_CONTENT)
+    let change = AuditChange::new(id).before(&before);
+    Ok((axum::http::StatusCode::NO_CONTENT, change.into_extension()))
 }
 
 /// Prepare a query by filling placeholders.
@@ -508,33 +527,55 @@ pub async fn prepare_query(
     }))
 }
 
-/// Execute a query (simulation - returns mock data).
+/// Execute a query.
 ///
-/// Note: This is a simulation endpoint. Real Splunk Cloud integration
-/// requires direct access to Splunk which is not available via API.
+/// Runs the search through the Splunk REST API when `SPLUNK_API_URL` and
+/// `SPLUNK_TOKEN` are set. Otherwise returns simulated data, since Splunk
+/// Cloud stacks usually offer no search API.
 #[utoipa::path(
     post,
     path = "/api/v1/splunk/query/execute",
     request_body = ExecuteQueryRequest,
     responses(
-        (status = 200, description = "Query results (simulated)", body = ExecuteQueryResponse),
-        (status = 400, description = "Invalid request"),
-        (status = 500, description = "Internal server error")
+        (status = 200, description = "Query results", body = ExecuteQueryResponse),
+        (status = 400, description = "Invalid request or search failed in Splunk"),
+        (status = 500, description = "Internal server error"),
+        (status = 502, description = "Splunk request failed"),
+        (status = 503, description = "Search did not finish in time")
     ),
     tag = "Splunk"
 )]
 pub async fn execute_query(
     State(state): State<AppState>,
-    Json(req): Json<ExecuteQueryRequest>,
+    user: CurrentUser,
+    ValidatedJson(req): ValidatedJson<ExecuteQueryRequest>,
 ) -> ApiResult<Json<ExecuteQueryResponse>> {
     let start_time = std::time::Instant::now();
-    
-    // TODO: Get user_id from auth context
-    let user_id = Uuid::new_v4();
-
-    // Generate mock log entries for demonstration
-    let mock_entries = generate_mock_logs(&req.query, req.limit as usize);
-    let total_count = mock_entries.len() as i64;
+    let user_id = user.id.0;
+
+    let limit = usize::try_from(req.limit).unwrap_or_default();
+    let (result, message) = match state.settings.splunk.as_ref() {
+        Some(settings) => {
+            let result = splunk_client(settings)
+                .search(&req.query, req.time_start, req.time_end, req.index.as_deref(), limit)
+                .await
+                .map_err(splunk_error)?;
+            (result, "Results from the Splunk search API.")
+        }
+        None => {
+            // Generate mock log entries for demonstration
+            let entries = generate_mock_logs(&req.query, limit);
+            let result = QueryResult {
+                query: req.query.clone(),
+                total_count: entries.len() as i64,
+                entries,
+                truncated: false,
+                execution_time_ms: 0,
+            };
+            (result, "This is simulated data. For real Splunk queries, use the Splunk web interface with the prepared query.")
+        }
+    };
+    let total_count = result.total_count;
     
     let execution_time_ms = start_time.elapsed().as_millis() as i64;
 
@@ -556,7 +597,8 @@ pub async fn execute_query(
     .execute(&state.db)
     .await;
 
-    let entries: Vec<LogEntryResponse> = mock_entries
+    let entries: Vec<LogEntryResponse> = result
+        .entries
         .into_iter()
         .map(|e| LogEntryResponse {
             timestamp: e.timestamp,
@@ -569,15 +611,24 @@ pub async fn execute_query(
         .collect();
 
     Ok(Json(ExecuteQueryResponse {
-        query: req.query,
+        query: result.query,
         entries,
         total_count,
-        truncated: false,
+        truncated: result.truncated,
         execution_time_ms,
-        message: "This is simulated data. For real Splunk queries, use the Splunk web interface with the prepared query.".to_string(),
+        message: message.to_string(),
     }))
 }
 
+/// Map a Splunk search error to an API error.
+fn splunk_error(error: SplunkError) -> ApiError {
+    match error {
+        SplunkError::SearchFailed(message) => ApiError::Validation(message),
+        SplunkError::Timeout(_) => ApiError::ServiceUnavailable(error.to_string()),
+        other => ApiError::ExternalService(other.to_string()),
+    }
+}
+
 /// Get query history for the current user.
 #[utoipa::path(
     get,
@@ -590,9 +641,9 @@ pub async fn execute_query(
 )]
 pub async fn get_query_history(
     State(state): State<AppState>,
+    user: CurrentUser,
 ) -> ApiResult<Json<QueryHistoryResponse>> {
-    // TODO: Get user_id from auth context
-    let user_id = Uuid::new_v4();
+    let user_id = user.id.0;
 
     let entries: Vec<QueryHistoryEntry> = sqlx::query_as(
         r"
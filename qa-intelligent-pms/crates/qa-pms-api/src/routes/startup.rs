@@ -1,21 +1,56 @@
-//! Startup validation endpoint.
+//! Startup validation endpoints.
 //!
-//! Provides `/api/v1/startup/validate` for frontend startup checks.
+//! Provides `/api/v1/startup/validate` for frontend startup checks and
+//! `/api/v1/startup/validations` for the history of past runs.
 
-use axum::{extract::State, routing::get, Json, Router};
+use axum::{
+    extract::{Query, State},
+    routing::get,
+    Json, Router,
+};
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+use utoipa::{IntoParams, ToSchema};
+
+use qa_pms_core::error::ApiError;
+use qa_pms_core::types::{CursorInfo, CursorQuery};
 
 use crate::app::AppState;
 use crate::startup::StartupValidationReport;
+use crate::startup_history::{StartupValidationRun, ValidationTrigger};
+
+type ApiResult<T> = Result<T, ApiError>;
 
 /// Startup routes.
 pub fn router() -> Router<AppState> {
-    Router::new().route("/api/v1/startup/validate", get(validate_startup))
+    Router::new()
+        .route("/api/v1/startup/validate", get(validate_startup))
+        .route("/api/v1/startup/validations", get(list_validations))
+}
+
+/// Query parameters for the validation history.
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidationHistoryQuery {
+    /// Only include runs started this way
+    pub trigger: Option<ValidationTrigger>,
+}
+
+/// A page of past validation runs.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidationHistoryResponse {
+    /// Runs, newest first unless `direction=asc`
+    pub runs: Vec<StartupValidationRun>,
+    /// Pagination information
+    pub page_info: CursorInfo,
 }
 
 /// Validate all configured integrations.
 ///
 /// Returns a report indicating which integrations are working
-/// and whether the application can start.
+/// and whether the application can start. The run is added to the
+/// validation history.
 #[utoipa::path(
     get,
     path = "/api/v1/startup/validate",
@@ -26,5 +61,42 @@ pub fn router() -> Router<AppState> {
 )]
 pub async fn validate_startup(State(state): State<AppState>) -> Json<StartupValidationReport> {
     let report = state.startup_validator.validate().await;
+    if let Err(e) = state
+        .startup_history
+        .record(ValidationTrigger::Request, &report)
+        .await
+    {
+        warn!(error = %e, "Failed to store startup validation run");
+    }
     Json(report)
 }
+
+/// List past validation runs with their per-integration results.
+#[utoipa::path(
+    get,
+    path = "/api/v1/startup/validations",
+    tag = "Startup",
+    params(ValidationHistoryQuery, CursorQuery),
+    responses(
+        (status = 200, description = "Validation history", body = ValidationHistoryResponse),
+        (status = 400, description = "Invalid cursor"),
+    )
+)]
+pub async fn list_validations(
+    State(state): State<AppState>,
+    Query(filter): Query<ValidationHistoryQuery>,
+    Query(query): Query<CursorQuery>,
+) -> ApiResult<Json<ValidationHistoryResponse>> {
+    let page = query.page_request()?;
+    let runs = state
+        .startup_history
+        .list(&page, filter.trigger)
+        .await
+        .map_err(|e| {
+            ApiError::Internal(anyhow::anyhow!("Failed to fetch validation history: {e}"))
+        })?;
+    Ok(Json(ValidationHistoryResponse {
+        runs: runs.data,
+        page_info: runs.page_info,
+    }))
+}
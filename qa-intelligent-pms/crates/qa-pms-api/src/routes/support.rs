@@ -4,21 +4,36 @@
 
 use axum::{
     extract::{Path, Query, State},
+    http::header,
+    response::IntoResponse,
     routing::{get, post},
-    Json, Router,
+    Extension, Json, Router,
 };
 use serde::{Deserialize, Serialize};
+use tracing::info;
 use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
+use validator::{Validate, ValidationError};
 
+use qa_pms_core::types::{CursorInfo, CursorQuery};
 use qa_pms_core::ApiError;
 use qa_pms_support::{
     CreateErrorLogInput, CreateKbEntryInput, DiagnosticsService, ErrorLog, ErrorLogFilter,
-    ErrorLogSort, ErrorStatus, KnowledgeBaseEntry, KnowledgeBaseService, Pagination, SupportDashboardSummary, SupportRepository, TroubleshootingSuggestion,
+    ErrorStatus, KbDraft, KbDrafter, KbSearchResult, KnowledgeBaseEntry, KnowledgeBaseService, Pagination, SupportDashboardSummary, SupportRepository, SystemTargets, TroubleshootingSuggestion,
     UpdateErrorStatusInput, UpdateKbEntryInput, DiagnosticsReport,
 };
 
+use qa_pms_ai::AIFeature;
+use qa_pms_config::UserConfig;
+use qa_pms_postman::POSTMAN_BASE_URL;
+
 use crate::app::AppState;
+use crate::audit::AuditChange;
+use crate::auth::CurrentUser;
+use crate::routes::ai;
+use crate::search_index::{IndexDocument, IndexSource};
+use crate::support_bundle;
+use crate::validation::{not_blank, one_of, ValidatedJson};
 
 type ApiResult<T> = Result<T, ApiError>;
 
@@ -29,13 +44,17 @@ pub fn router() -> Router<AppState> {
         .route("/errors", get(list_error_logs).post(create_error_log))
         .route("/errors/:id", get(get_error_log).put(update_error_status))
         .route("/errors/:id/suggestions", get(get_suggestions))
+        .route("/errors/:id/promote-to-kb", post(promote_error_to_kb))
         // Dashboard
         .route("/dashboard", get(get_dashboard_summary))
         // Diagnostics
         .route("/diagnostics", get(run_all_diagnostics))
         .route("/diagnostics/:integration", get(run_diagnostic))
+        // Support bundle
+        .route("/bundle", post(export_support_bundle))
         // Knowledge base
         .route("/kb", get(list_kb_entries).post(create_kb_entry))
+        .route("/kb/search", get(search_kb_entries))
         .route("/kb/:id", get(get_kb_entry).put(update_kb_entry).delete(delete_kb_entry))
         .route("/kb/:id/rate", post(rate_kb_entry))
 }
@@ -56,20 +75,8 @@ pub struct ErrorLogQuery {
     pub user_id: Option<Uuid>,
     /// Search in error message
     pub search: Option<String>,
-    /// Sort order
-    #[serde(default)]
-    pub sort: Option<String>,
-    /// Page number (1-indexed)
-    #[serde(default = "default_page")]
-    pub page: i32,
-    /// Items per page
-    #[serde(default = "default_per_page")]
-    pub per_page: i32,
 }
 
-const fn default_page() -> i32 { 1 }
-const fn default_per_page() -> i32 { 20 }
-
 /// Response for error log list.
 #[derive(Debug, Serialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
@@ -78,25 +85,24 @@ pub struct ErrorLogsResponse {
     pub items: Vec<ErrorLog>,
     /// Total count
     pub total: i64,
-    /// Current page
-    pub page: i32,
-    /// Items per page
-    pub per_page: i32,
-    /// Total pages
-    pub total_pages: i32,
+    /// Cursor pagination information
+    pub page_info: CursorInfo,
 }
 
 /// Request to create an error log.
-#[derive(Debug, Deserialize, ToSchema)]
+#[derive(Debug, Deserialize, ToSchema, Validate)]
 #[serde(rename_all = "camelCase")]
 pub struct CreateErrorRequest {
     /// Error message
+    #[validate(custom(function = "not_blank"))]
     pub message: String,
     /// Stack trace
     pub stack_trace: Option<String>,
     /// Severity (low, medium, high, critical)
+    #[validate(custom(function = "valid_severity"))]
     pub severity: Option<String>,
     /// Source (frontend, backend, integration, database)
+    #[validate(custom(function = "valid_source"))]
     pub source: Option<String>,
     /// User ID
     pub user_id: Option<Uuid>,
@@ -116,10 +122,11 @@ pub struct CreateErrorRequest {
 }
 
 /// Request to update error status.
-#[derive(Debug, Deserialize, ToSchema)]
+#[derive(Debug, Deserialize, ToSchema, Validate)]
 #[serde(rename_all = "camelCase")]
 pub struct UpdateStatusRequest {
     /// New status (new, investigating, resolved, dismissed)
+    #[validate(custom(function = "valid_status"))]
     pub status: String,
     /// Resolution notes
     pub resolution_notes: Option<String>,
@@ -135,6 +142,35 @@ pub struct SuggestionsResponse {
     pub suggestions: Vec<TroubleshootingSuggestion>,
 }
 
+/// Query parameters for a support bundle.
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SupportBundleQuery {
+    /// Most recent error logs to include (default 200, at most 1000)
+    pub error_log_limit: Option<usize>,
+}
+
+/// Request for turning a resolved error into a knowledge base entry.
+#[derive(Debug, Default, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase", default)]
+pub struct PromoteToKbRequest {
+    /// Create the entry and link it to the error, instead of only drafting it
+    pub create: bool,
+    /// Have the configured AI provider write the draft
+    pub use_ai: bool,
+}
+
+/// Response for promoting an error to the knowledge base.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PromoteToKbResponse {
+    /// Drafted entry
+    pub draft: KbDraft,
+    /// Created entry, when `create` was set
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub entry: Option<KnowledgeBaseEntry>,
+}
+
 /// Response for dashboard summary.
 #[derive(Debug, Serialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
@@ -167,6 +203,9 @@ pub struct KbQuery {
     pub per_page: i32,
 }
 
+const fn default_page() -> i32 { 1 }
+const fn default_per_page() -> i32 { 20 }
+
 /// Response for KB list.
 #[derive(Debug, Serialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
@@ -183,17 +222,51 @@ pub struct KbEntriesResponse {
     pub total_pages: i32,
 }
 
+/// Query parameters for KB full-text search.
+#[derive(Debug, Deserialize, IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub struct KbSearchQuery {
+    /// Search text; supports quoted phrases, `or` and `-word`
+    pub q: String,
+    /// Page number
+    #[serde(default = "default_page")]
+    pub page: i32,
+    /// Items per page
+    #[serde(default = "default_per_page")]
+    pub per_page: i32,
+}
+
+/// Response for KB full-text search.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct KbSearchResponse {
+    /// Matching entries, most relevant first
+    pub items: Vec<KbSearchResult>,
+    /// Total matches
+    pub total: i64,
+    /// Current page
+    pub page: i32,
+    /// Items per page
+    pub per_page: i32,
+    /// Total pages
+    pub total_pages: i32,
+}
+
 /// Request to create KB entry.
-#[derive(Debug, Deserialize, ToSchema)]
+#[derive(Debug, Deserialize, ToSchema, Validate)]
 #[serde(rename_all = "camelCase")]
 pub struct CreateKbRequest {
     /// Title
+    #[validate(custom(function = "not_blank"))]
     pub title: String,
     /// Problem description
+    #[validate(custom(function = "not_blank"))]
     pub problem: String,
     /// Cause
+    #[validate(custom(function = "not_blank"))]
     pub cause: String,
     /// Solution
+    #[validate(custom(function = "not_blank"))]
     pub solution: String,
     /// Related error messages
     #[serde(default)]
@@ -239,20 +312,23 @@ pub struct SuccessResponse {
 
 // ==================== Handlers ====================
 
-/// List error logs with filtering and pagination.
+/// List error logs with filtering, most recently seen first.
 #[utoipa::path(
     get,
     path = "/api/v1/support/errors",
-    params(ErrorLogQuery),
+    params(ErrorLogQuery, CursorQuery),
     responses(
-        (status = 200, description = "Error logs retrieved", body = ErrorLogsResponse)
+        (status = 200, description = "Error logs retrieved", body = ErrorLogsResponse),
+        (status = 400, description = "Invalid cursor")
     ),
     tag = "Support"
 )]
 pub async fn list_error_logs(
     State(state): State<AppState>,
     Query(query): Query<ErrorLogQuery>,
+    Query(cursor): Query<CursorQuery>,
 ) -> ApiResult<Json<ErrorLogsResponse>> {
+    let page = cursor.page_request()?;
     let repo = SupportRepository::new(state.db.clone());
 
     let filter = ErrorLogFilter {
@@ -265,21 +341,16 @@ pub async fn list_error_logs(
         to_date: None,
     };
 
-    let sort = query.sort.map(|s| parse_sort(&s)).unwrap_or_default();
-    let pagination = Pagination {
-        page: query.page,
-        per_page: query.per_page,
-    };
-
-    let result = repo.list_error_logs(filter, sort, pagination).await
+    let rows = repo.list_error_logs(&filter, &page).await
+        .map_err(|e| ApiError::Internal(e.into()))?;
+    let total = repo.count_error_logs().await
         .map_err(|e| ApiError::Internal(e.into()))?;
 
+    let result = page.finish(rows, |e| (e.last_seen_at, e.id));
     Ok(Json(ErrorLogsResponse {
-        items: result.items,
-        total: result.total,
-        page: result.page,
-        per_page: result.per_page,
-        total_pages: result.total_pages,
+        items: result.data,
+        total,
+        page_info: result.page_info,
     }))
 }
 
@@ -295,8 +366,8 @@ pub async fn list_error_logs(
 )]
 pub async fn create_error_log(
     State(state): State<AppState>,
-    Json(req): Json<CreateErrorRequest>,
-) -> ApiResult<Json<ErrorLog>> {
+    ValidatedJson(req): ValidatedJson<CreateErrorRequest>,
+) -> ApiResult<(Extension<AuditChange>, Json<ErrorLog>)> {
     let repo = SupportRepository::new(state.db.clone());
 
     let input = CreateErrorLogInput {
@@ -315,8 +386,10 @@ pub async fn create_error_log(
 
     let error = repo.create_or_increment_error(input).await
         .map_err(|e| ApiError::Internal(e.into()))?;
+    state.search_index.upsert([IndexDocument::error_log(&error)]);
 
-    Ok(Json(error))
+    let change = AuditChange::new(error.id).after(&error);
+    Ok((change.into_extension(), Json(error)))
 }
 
 /// Get a specific error log.
@@ -360,9 +433,10 @@ pub async fn get_error_log(
 pub async fn update_error_status(
     State(state): State<AppState>,
     Path(id): Path<Uuid>,
-    Json(req): Json<UpdateStatusRequest>,
-) -> ApiResult<Json<ErrorLog>> {
+    ValidatedJson(req): ValidatedJson<UpdateStatusRequest>,
+) -> ApiResult<(Extension<AuditChange>, Json<ErrorLog>)> {
     let repo = SupportRepository::new(state.db.clone());
+    let before = fetch_error_log(&repo, id).await?;
 
     let status = parse_status(&req.status)
         .ok_or_else(|| ApiError::Validation(format!("Invalid status: {}", req.status)))?;
@@ -378,8 +452,10 @@ pub async fn update_error_status(
             qa_pms_support::SupportError::ErrorLogNotFound(_) => ApiError::NotFound("Error log not found".into()),
             _ => ApiError::Internal(e.into()),
         })?;
+    state.search_index.upsert([IndexDocument::error_log(&error)]);
 
-    Ok(Json(error))
+    let change = AuditChange::new(id).before(&before).after(&error);
+    Ok((change.into_extension(), Json(error)))
 }
 
 /// Get troubleshooting suggestions for an error.
@@ -412,6 +488,73 @@ pub async fn get_suggestions(
     Ok(Json(SuggestionsResponse { suggestions }))
 }
 
+/// Draft a knowledge base entry from a resolved error and its resolution
+/// notes, optionally creating it and linking it to the error.
+#[utoipa::path(
+    post,
+    path = "/api/v1/support/errors/{id}/promote-to-kb",
+    params(("id" = Uuid, Path, description = "Error log ID")),
+    request_body = PromoteToKbRequest,
+    responses(
+        (status = 200, description = "Entry drafted, and created if requested", body = PromoteToKbResponse),
+        (status = 403, description = "Monthly token budget exhausted"),
+        (status = 404, description = "Error log not found"),
+        (status = 409, description = "Error not resolved, without notes, or already linked to an entry"),
+        (status = 503, description = "AI requested but not configured")
+    ),
+    tag = "Support"
+)]
+pub async fn promote_error_to_kb(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Path(id): Path<Uuid>,
+    Json(req): Json<PromoteToKbRequest>,
+) -> ApiResult<(Extension<AuditChange>, Json<PromoteToKbResponse>)> {
+    let repo = SupportRepository::new(state.db.clone());
+
+    let error = repo.get_error_log(id).await
+        .map_err(|e| match e {
+            qa_pms_support::SupportError::ErrorLogNotFound(_) => ApiError::NotFound("Error log not found".into()),
+            _ => ApiError::Internal(e.into()),
+        })?;
+    if error.kb_entry_id.is_some() {
+        return Err(ApiError::Conflict("Error is already linked to a KB entry".into()));
+    }
+
+    let client = if req.use_ai {
+        Some(ai::configured_client(&state, &user, AIFeature::KnowledgeBase).await?)
+    } else {
+        None
+    };
+    let draft = KbDrafter::new(client).draft(&error).await
+        .map_err(|e| match e {
+            qa_pms_support::SupportError::InvalidInput(msg) => ApiError::Conflict(msg),
+            _ => ApiError::Internal(e.into()),
+        })?;
+
+    let entry = if req.create {
+        let entry = repo.create_kb_entry(draft.entry.clone()).await
+            .map_err(|e| ApiError::Internal(e.into()))?;
+        state.search_index.upsert([IndexDocument::kb_entry(&entry)]);
+
+        let input = UpdateErrorStatusInput {
+            status: ErrorStatus::Resolved,
+            resolution_notes: None,
+            kb_entry_id: Some(entry.id),
+        };
+        let error = repo.update_error_status(id, input).await
+            .map_err(|e| ApiError::Internal(e.into()))?;
+        state.search_index.upsert([IndexDocument::error_log(&error)]);
+        Some(entry)
+    } else {
+        None
+    };
+
+    let response = PromoteToKbResponse { draft, entry };
+    let change = AuditChange::new(id).before(&error).after(&response);
+    Ok((change.into_extension(), Json(response)))
+}
+
 /// Get support dashboard summary.
 #[utoipa::path(
     get,
@@ -432,7 +575,7 @@ pub async fn get_dashboard_summary(
     Ok(Json(DashboardSummaryResponse { summary }))
 }
 
-/// Run diagnostics on all integrations.
+/// Run diagnostics on all integrations and the system.
 #[utoipa::path(
     get,
     path = "/api/v1/support/diagnostics",
@@ -444,7 +587,7 @@ pub async fn get_dashboard_summary(
 pub async fn run_all_diagnostics(
     State(state): State<AppState>,
 ) -> ApiResult<Json<DiagnosticsResponse>> {
-    let service = DiagnosticsService::new(state.db.clone());
+    let service = diagnostics_service(&state);
 
     let report = service.run_all_diagnostics().await
         .map_err(|e| ApiError::Internal(e.into()))?;
@@ -452,11 +595,56 @@ pub async fn run_all_diagnostics(
     Ok(Json(DiagnosticsResponse { report }))
 }
 
-/// Run diagnostic for a specific integration.
+/// Download a support bundle to attach to a bug report.
+///
+/// The zip holds recent error logs, a diagnostics report, startup validation
+/// history, the non-secret configuration and version information, with
+/// secrets redacted.
+#[utoipa::path(
+    post,
+    path = "/api/v1/support/bundle",
+    params(SupportBundleQuery),
+    responses(
+        (status = 200, description = "Support bundle", content_type = "application/zip", body = Vec<u8>),
+        (status = 400, description = "Invalid error log limit")
+    ),
+    tag = "Support"
+)]
+pub async fn export_support_bundle(
+    State(state): State<AppState>,
+    Query(query): Query<SupportBundleQuery>,
+) -> ApiResult<impl IntoResponse> {
+    let limit = query
+        .error_log_limit
+        .unwrap_or(support_bundle::DEFAULT_ERROR_LOG_LIMIT);
+    if !(1..=support_bundle::MAX_ERROR_LOG_LIMIT).contains(&limit) {
+        return Err(ApiError::Validation(format!(
+            "errorLogLimit must be between 1 and {}",
+            support_bundle::MAX_ERROR_LOG_LIMIT
+        )));
+    }
+
+    let bundle = support_bundle::build(&state, limit).await?;
+    info!(bytes = bundle.bytes.len(), "Support bundle exported");
+
+    let disposition = format!(
+        "attachment; filename=\"qa-pms-support-bundle-{}.zip\"",
+        bundle.created_at.format("%Y%m%dT%H%M%SZ")
+    );
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/zip".to_string()),
+            (header::CONTENT_DISPOSITION, disposition),
+        ],
+        bundle.bytes,
+    ))
+}
+
+/// Run diagnostic for a specific integration or system check.
 #[utoipa::path(
     get,
     path = "/api/v1/support/diagnostics/{integration}",
-    params(("integration" = String, Path, description = "Integration name")),
+    params(("integration" = String, Path, description = "Integration (database, jira, postman, testmo) or system check (disk, clock, tls)")),
     responses(
         (status = 200, description = "Diagnostic result", body = qa_pms_support::DiagnosticResult)
     ),
@@ -466,7 +654,7 @@ pub async fn run_diagnostic(
     State(state): State<AppState>,
     Path(integration): Path<String>,
 ) -> ApiResult<Json<qa_pms_support::DiagnosticResult>> {
-    let service = DiagnosticsService::new(state.db.clone());
+    let service = diagnostics_service(&state);
 
     let result = service.run_diagnostic(&integration).await
         .map_err(|e| match e {
@@ -510,6 +698,51 @@ pub async fn list_kb_entries(
     }))
 }
 
+/// Search knowledge base entries by full text.
+///
+/// Results are ranked by relevance, with the title weighing most, and carry
+/// passages with the matched words wrapped in `<mark>` tags.
+#[utoipa::path(
+    get,
+    path = "/api/v1/support/kb/search",
+    params(KbSearchQuery),
+    responses(
+        (status = 200, description = "Matching KB entries", body = KbSearchResponse),
+        (status = 400, description = "Blank query or invalid paging")
+    ),
+    tag = "Support"
+)]
+pub async fn search_kb_entries(
+    State(state): State<AppState>,
+    Query(query): Query<KbSearchQuery>,
+) -> ApiResult<Json<KbSearchResponse>> {
+    let q = query.q.trim();
+    if q.is_empty() {
+        return Err(ApiError::Validation("q must not be blank".to_string()));
+    }
+    if query.page < 1 || !(1..=100).contains(&query.per_page) {
+        return Err(ApiError::Validation(
+            "page must be at least 1 and perPage between 1 and 100".to_string(),
+        ));
+    }
+
+    let repo = SupportRepository::new(state.db.clone());
+    let pagination = Pagination {
+        page: query.page,
+        per_page: query.per_page,
+    };
+    let result = repo.search_kb_entries(q, pagination).await
+        .map_err(|e| ApiError::Internal(e.into()))?;
+
+    Ok(Json(KbSearchResponse {
+        items: result.items,
+        total: result.total,
+        page: result.page,
+        per_page: result.per_page,
+        total_pages: result.total_pages,
+    }))
+}
+
 /// Create a knowledge base entry.
 #[utoipa::path(
     post,
@@ -522,8 +755,8 @@ pub async fn list_kb_entries(
 )]
 pub async fn create_kb_entry(
     State(state): State<AppState>,
-    Json(req): Json<CreateKbRequest>,
-) -> ApiResult<Json<KnowledgeBaseEntry>> {
+    ValidatedJson(req): ValidatedJson<CreateKbRequest>,
+) -> ApiResult<(Extension<AuditChange>, Json<KnowledgeBaseEntry>)> {
     let repo = SupportRepository::new(state.db.clone());
 
     let input = CreateKbEntryInput {
@@ -537,8 +770,10 @@ pub async fn create_kb_entry(
 
     let entry = repo.create_kb_entry(input).await
         .map_err(|e| ApiError::Internal(e.into()))?;
+    state.search_index.upsert([IndexDocument::kb_entry(&entry)]);
 
-    Ok(Json(entry))
+    let change = AuditChange::new(entry.id).after(&entry);
+    Ok((change.into_extension(), Json(entry)))
 }
 
 /// Get a knowledge base entry.
@@ -586,8 +821,9 @@ pub async fn update_kb_entry(
     State(state): State<AppState>,
     Path(id): Path<Uuid>,
     Json(req): Json<UpdateKbRequest>,
-) -> ApiResult<Json<KnowledgeBaseEntry>> {
+) -> ApiResult<(Extension<AuditChange>, Json<KnowledgeBaseEntry>)> {
     let repo = SupportRepository::new(state.db.clone());
+    let before = fetch_kb_entry(&repo, id).await?;
 
     let input = UpdateKbEntryInput {
         title: req.title,
@@ -603,8 +839,10 @@ pub async fn update_kb_entry(
             qa_pms_support::SupportError::KbEntryNotFound(_) => ApiError::NotFound("KB entry not found".into()),
             _ => ApiError::Internal(e.into()),
         })?;
+    state.search_index.upsert([IndexDocument::kb_entry(&entry)]);
 
-    Ok(Json(entry))
+    let change = AuditChange::new(id).before(&before).after(&entry);
+    Ok((change.into_extension(), Json(entry)))
 }
 
 /// Delete a knowledge base entry.
@@ -621,18 +859,24 @@ pub async fn update_kb_entry(
 pub async fn delete_kb_entry(
     State(state): State<AppState>,
     Path(id): Path<Uuid>,
-) -> ApiResult<Json<SuccessResponse>> {
+) -> ApiResult<(Extension<AuditChange>, Json<SuccessResponse>)> {
     let repo = SupportRepository::new(state.db.clone());
+    let before = fetch_kb_entry(&repo, id).await?;
 
     repo.delete_kb_entry(id).await
         .map_err(|e| match e {
             qa_pms_support::SupportError::KbEntryNotFound(_) => ApiError::NotFound("KB entry not found".into()),
             _ => ApiError::Internal(e.into()),
         })?;
+    state.search_index.remove(IndexSource::KnowledgeBase, &id.to_string());
 
-    Ok(Json(SuccessResponse {
-        message: "KB entry deleted successfully".into(),
-    }))
+    let change = AuditChange::new(id).before(&before);
+    Ok((
+        change.into_extension(),
+        Json(SuccessResponse {
+            message: "KB entry deleted successfully".into(),
+        }),
+    ))
 }
 
 /// Rate a knowledge base entry as helpful or not.
@@ -663,6 +907,47 @@ pub async fn rate_kb_entry(
 
 // ==================== Helper Functions ====================
 
+/// Get an error log, or fail with `NotFound`.
+async fn fetch_error_log(repo: &SupportRepository, id: Uuid) -> ApiResult<ErrorLog> {
+    repo.get_error_log(id).await.map_err(|e| match e {
+        qa_pms_support::SupportError::ErrorLogNotFound(_) => ApiError::NotFound("Error log not found".into()),
+        _ => ApiError::Internal(e.into()),
+    })
+}
+
+/// Get a KB entry, or fail with `NotFound`.
+async fn fetch_kb_entry(repo: &SupportRepository, id: Uuid) -> ApiResult<KnowledgeBaseEntry> {
+    repo.get_kb_entry(id).await.map_err(|e| match e {
+        qa_pms_support::SupportError::KbEntryNotFound(_) => ApiError::NotFound("KB entry not found".into()),
+        _ => ApiError::Internal(e.into()),
+    })
+}
+
+/// Diagnostics over the configured integrations' endpoints and the config
+/// directory.
+pub(crate) fn diagnostics_service(state: &AppState) -> DiagnosticsService {
+    let settings = &state.settings;
+    let mut tls_targets: Vec<String> = [
+        settings.jira.as_ref().map(|jira| jira.instance_url.clone()),
+        settings.testmo.as_ref().map(|testmo| testmo.base_url.clone()),
+        settings.postman.as_ref().map(|_| POSTMAN_BASE_URL.to_string()),
+        settings.splunk.as_ref().map(|splunk| splunk.api_url.clone()),
+    ]
+    .into_iter()
+    .flatten()
+    .filter(|url| url.starts_with("https://"))
+    .collect();
+    tls_targets.extend(settings.diagnostics.extra_tls_targets.iter().cloned());
+
+    DiagnosticsService::new(state.db.clone()).with_system_targets(SystemTargets {
+        config_dir: UserConfig::default_path()
+            .ok()
+            .and_then(|path| path.parent().map(std::path::Path::to_path_buf)),
+        ntp_server: Some(settings.diagnostics.ntp_server.clone()),
+        tls_targets,
+    })
+}
+
 fn parse_status(s: &str) -> Option<ErrorStatus> {
     match s.to_lowercase().as_str() {
         "new" => Some(ErrorStatus::New),
@@ -673,6 +958,18 @@ fn parse_status(s: &str) -> Option<ErrorStatus> {
     }
 }
 
+fn valid_status(s: &str) -> Result<(), ValidationError> {
+    one_of(s, &["new", "investigating", "resolved", "dismissed"])
+}
+
+fn valid_severity(s: &str) -> Result<(), ValidationError> {
+    one_of(s, &["low", "medium", "high", "critical"])
+}
+
+fn valid_source(s: &str) -> Result<(), ValidationError> {
+    one_of(s, &["frontend", "backend", "integration", "database", "unknown"])
+}
+
 fn parse_severity(s: &str) -> Option<qa_pms_support::ErrorSeverity> {
     match s.to_lowercase().as_str() {
         "low" => Some(qa_pms_support::ErrorSeverity::Low),
@@ -693,12 +990,3 @@ fn parse_source(s: &str) -> Option<qa_pms_support::ErrorSource> {
         _ => None,
     }
 }
-
-fn parse_sort(s: &str) -> ErrorLogSort {
-    match s.to_lowercase().as_str() {
-        "last_seen_asc" => ErrorLogSort::LastSeenAsc,
-        "severity_desc" | "severity" => ErrorLogSort::SeverityDesc,
-        "occurrence_desc" | "occurrence" => ErrorLogSort::OccurrenceDesc,
-        _ => ErrorLogSort::LastSeenDesc,
-    }
-}
@@ -0,0 +1,228 @@
+//! Tenant API endpoints.
+//!
+//! Admins keep their tenant's integration credentials here. Values are
+//! write-only: they are stored encrypted and never returned.
+//!
+//! Requests reach Jira, Testmo and Postman with their tenant's credentials
+//! when it stored them under the names below (`jira_api_token`,
+//! `testmo_api_key`, `postman_api_key`, ...); anything the tenant has not
+//! stored falls back to the instance settings.
+
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    routing::{get, put},
+    Extension, Json, Router,
+};
+use secrecy::SecretString;
+use serde::{Deserialize, Serialize};
+use tracing::info;
+use utoipa::ToSchema;
+use validator::Validate;
+
+use qa_pms_config::TenantCredentialStore;
+use qa_pms_core::error::ApiError;
+use qa_pms_core::TenantId;
+
+use crate::app::AppState;
+use crate::audit::AuditChange;
+use crate::auth::CurrentUser;
+use crate::redact::SECRET_MASK;
+use crate::validation::{not_blank, ValidatedJson};
+
+type ApiResult<T> = Result<T, ApiError>;
+
+/// Longest accepted credential name.
+const MAX_NAME_LEN: usize = 64;
+
+/// Jira Cloud instance URL.
+pub(crate) const JIRA_URL: &str = "jira_url";
+/// Email the Jira API token belongs to.
+pub(crate) const JIRA_EMAIL: &str = "jira_email";
+/// Jira API token.
+pub(crate) const JIRA_API_TOKEN: &str = "jira_api_token";
+/// Testmo instance URL.
+pub(crate) const TESTMO_URL: &str = "testmo_url";
+/// Testmo API key.
+pub(crate) const TESTMO_API_KEY: &str = "testmo_api_key";
+/// Testmo project searched and written to.
+pub(crate) const TESTMO_PROJECT_ID: &str = "testmo_project_id";
+/// Postman API key.
+pub(crate) const POSTMAN_API_KEY: &str = "postman_api_key";
+
+/// Create the tenant router.
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/api/v1/tenant/credentials", get(list_credentials))
+        .route(
+            "/api/v1/tenant/credentials/:name",
+            put(set_credential).delete(delete_credential),
+        )
+}
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/// Names of the tenant's stored credentials.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TenantCredentialsResponse {
+    /// Tenant the credentials belong to
+    pub tenant: String,
+    /// Stored credential names, sorted
+    pub names: Vec<String>,
+}
+
+/// New value for a credential.
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SetCredentialRequest {
+    /// Secret value; stored encrypted and never returned
+    #[validate(custom(function = "not_blank"))]
+    pub value: String,
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+fn store(state: &AppState) -> ApiResult<&Arc<TenantCredentialStore>> {
+    state.tenant_credentials.as_ref().ok_or_else(|| {
+        ApiError::ServiceUnavailable("Tenant credential storage is not available".into())
+    })
+}
+
+/// Get a credential of a tenant, or `None` when it is not stored or there is
+/// no credential storage.
+pub(crate) fn tenant_credential(
+    state: &AppState,
+    tenant: &TenantId,
+    name: &str,
+) -> ApiResult<Option<SecretString>> {
+    match &state.tenant_credentials {
+        Some(store) => store.get(tenant, name).map_err(ApiError::Internal),
+        None => Ok(None),
+    }
+}
+
+/// Names are used as JSON keys and in URLs, so keep them simple.
+fn check_name(name: &str) -> ApiResult<()> {
+    let valid = !name.is_empty()
+        && name.len() <= MAX_NAME_LEN
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'));
+    if valid {
+        Ok(())
+    } else {
+        Err(ApiError::Validation(format!(
+            "Invalid credential name {name:?}: use up to {MAX_NAME_LEN} letters, digits, '_', '-' or '.'"
+        )))
+    }
+}
+
+// ============================================================================
+// Handlers
+// ============================================================================
+
+/// List the names of the caller's tenant credentials (admin only).
+#[utoipa::path(
+    get,
+    path = "/api/v1/tenant/credentials",
+    responses(
+        (status = 200, description = "Stored credential names", body = TenantCredentialsResponse),
+        (status = 403, description = "Caller is not an admin"),
+        (status = 503, description = "Credential storage is not available")
+    ),
+    tag = "Tenant"
+)]
+pub async fn list_credentials(
+    State(state): State<AppState>,
+    user: CurrentUser,
+) -> ApiResult<Json<TenantCredentialsResponse>> {
+    let names = store(&state)?
+        .names(&user.tenant)
+        .map_err(ApiError::Internal)?;
+    Ok(Json(TenantCredentialsResponse {
+        tenant: user.tenant.to_string(),
+        names,
+    }))
+}
+
+/// Store or replace a credential of the caller's tenant (admin only).
+#[utoipa::path(
+    put,
+    path = "/api/v1/tenant/credentials/{name}",
+    params(("name" = String, Path, description = "Credential name (e.g., jira_api_token)")),
+    request_body = SetCredentialRequest,
+    responses(
+        (status = 204, description = "Credential stored"),
+        (status = 400, description = "Invalid credential name"),
+        (status = 403, description = "Caller is not an admin"),
+        (status = 422, description = "Invalid request", body = qa_pms_core::error::ErrorResponse),
+        (status = 503, description = "Credential storage is not available")
+    ),
+    tag = "Tenant"
+)]
+pub async fn set_credential(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Path(name): Path<String>,
+    ValidatedJson(request): ValidatedJson<SetCredentialRequest>,
+) -> ApiResult<(StatusCode, Extension<AuditChange>)> {
+    check_name(&name)?;
+    store(&state)?
+        .set(&user.tenant, &name, &SecretString::from(request.value))
+        .map_err(ApiError::Internal)?;
+
+    info!(tenant = %user.tenant, name = %name, "Tenant credential stored");
+    // Keeps the value out of the audit log, which would otherwise store the body
+    let change = AuditChange::new(&name).after(&serde_json::json!({ "value": SECRET_MASK }));
+    Ok((StatusCode::NO_CONTENT, change.into_extension()))
+}
+
+/// Delete a credential of the caller's tenant (admin only).
+#[utoipa::path(
+    delete,
+    path = "/api/v1/tenant/credentials/{name}",
+    params(("name" = String, Path, description = "Credential name")),
+    responses(
+        (status = 204, description = "Credential deleted"),
+        (status = 403, description = "Caller is not an admin"),
+        (status = 404, description = "Credential not found"),
+        (status = 503, description = "Credential storage is not available")
+    ),
+    tag = "Tenant"
+)]
+pub async fn delete_credential(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Path(name): Path<String>,
+) -> ApiResult<StatusCode> {
+    if !store(&state)?
+        .remove(&user.tenant, &name)
+        .map_err(ApiError::Internal)?
+    {
+        return Err(ApiError::NotFound(format!("Credential {name}")));
+    }
+
+    info!(tenant = %user.tenant, name = %name, "Tenant credential deleted");
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_credential_names() {
+        assert!(check_name("jira_api_token").is_ok());
+        assert!(check_name("testmo.key-2").is_ok());
+        assert!(check_name("").is_err());
+        assert!(check_name("../other").is_err());
+        assert!(check_name(&"x".repeat(MAX_NAME_LEN + 1)).is_err());
+    }
+}
@@ -0,0 +1,470 @@
+//! Local test case API endpoints.
+//!
+//! Cases curated by hand or generated by AI are stored locally, whether or
+//! not they are linked to Testmo. Every change is mirrored into the unified
+//! search index. Near-identical cases can be listed and merged into one.
+
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    routing::{get, post},
+    Extension, Json, Router,
+};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use tracing::info;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+use validator::Validate;
+
+use qa_pms_core::duplicates::DEFAULT_DUPLICATE_THRESHOLD;
+use qa_pms_core::error::ApiError;
+use qa_pms_core::types::{CursorInfo, CursorQuery};
+use qa_pms_core::{
+    find_duplicates, merge_cases, MergeError, TestCase, TestCaseFilter, TestCaseStep,
+};
+
+use crate::app::AppState;
+use crate::audit::AuditChange;
+use crate::search_index::{IndexDocument, IndexSource};
+use crate::validation::{not_blank, ValidatedJson};
+
+type ApiResult<T> = Result<T, ApiError>;
+
+/// Create the test case router.
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/api/v1/test-cases",
+            get(list_test_cases).post(create_test_case),
+        )
+        .route("/api/v1/test-cases/duplicates", get(list_duplicates))
+        .route("/api/v1/test-cases/:id/merge", post(merge_test_cases))
+        .route(
+            "/api/v1/test-cases/:id",
+            get(get_test_case)
+                .put(update_test_case)
+                .delete(delete_test_case),
+        )
+}
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/// Query parameters for searching test cases.
+#[derive(Debug, Deserialize, IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub struct TestCaseQuery {
+    /// Text found in the title, preconditions or a step
+    pub q: Option<String>,
+    /// Tag the case must carry
+    pub tag: Option<String>,
+    /// Category
+    pub category: Option<String>,
+    /// Priority label
+    pub priority: Option<String>,
+}
+
+/// Request to create or replace a test case.
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TestCaseRequest {
+    /// Case title
+    #[validate(custom(function = "not_blank"), length(max = 500))]
+    pub title: String,
+    /// Preconditions
+    pub preconditions: Option<String>,
+    /// Priority label
+    pub priority: Option<String>,
+    /// Category (e.g., "functional", "regression")
+    pub category: Option<String>,
+    /// Free-form tags
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Ordered steps
+    #[serde(default)]
+    #[validate(nested)]
+    pub steps: Vec<TestCaseStepRequest>,
+}
+
+/// A step of a test case.
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TestCaseStepRequest {
+    /// Action to perform
+    #[validate(custom(function = "not_blank"))]
+    pub action: String,
+    /// Expected result
+    pub expected: Option<String>,
+}
+
+/// A page of test cases.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TestCasesResponse {
+    /// Matching cases
+    pub items: Vec<TestCase>,
+    /// Cursor pagination info
+    pub page_info: CursorInfo,
+}
+
+/// Query parameters for duplicate detection.
+#[derive(Debug, Deserialize, IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub struct DuplicatesQuery {
+    /// Minimum similarity from 0.0 to 1.0 (default 0.85)
+    pub threshold: Option<f32>,
+}
+
+/// Request to merge duplicates into a case.
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct MergeTestCasesRequest {
+    /// Cases folded into the target and then deleted, in merge order
+    #[validate(length(min = 1, message = "at least one duplicate is required"))]
+    pub duplicate_ids: Vec<Uuid>,
+}
+
+/// A case in a duplicate pair.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DuplicateCaseResponse {
+    /// Test case ID
+    pub id: Uuid,
+    /// Case title
+    pub title: String,
+}
+
+/// Two cases likely to be duplicates.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DuplicatePairResponse {
+    /// Older case, the usual merge target
+    pub first: DuplicateCaseResponse,
+    /// Newer case
+    pub second: DuplicateCaseResponse,
+    /// Similarity from 0.0 to 1.0
+    pub similarity: f32,
+}
+
+impl TestCaseRequest {
+    /// Copy the editable fields onto a case.
+    fn apply(self, case: &mut TestCase) {
+        case.title = self.title.trim().to_string();
+        case.preconditions = self.preconditions;
+        case.priority = self.priority;
+        case.category = self.category;
+        case.tags = self.tags;
+        case.steps = self
+            .steps
+            .into_iter()
+            .map(|step| TestCaseStep {
+                action: step.action,
+                expected: step.expected,
+            })
+            .collect();
+    }
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+fn repo_error(e: anyhow::Error) -> ApiError {
+    ApiError::Internal(e)
+}
+
+async fn find(state: &AppState, id: Uuid) -> ApiResult<TestCase> {
+    state
+        .test_cases
+        .get(id)
+        .await
+        .map_err(repo_error)?
+        .ok_or_else(|| ApiError::NotFound(format!("Test case {id}")))
+}
+
+fn merge_error(e: MergeError) -> ApiError {
+    match e {
+        MergeError::SelfMerge(_) => ApiError::Validation(e.to_string()),
+        MergeError::MultipleRemoteLinks => ApiError::Conflict(e.to_string()),
+    }
+}
+
+fn index(state: &AppState, case: &TestCase) {
+    state.search_index.upsert([IndexDocument::test_case(
+        case,
+        state.search_index.testmo_base_url(),
+    )]);
+}
+
+// ============================================================================
+// Handlers
+// ============================================================================
+
+/// Search test cases, newest first by default.
+#[utoipa::path(
+    get,
+    path = "/api/v1/test-cases",
+    params(TestCaseQuery, CursorQuery),
+    responses(
+        (status = 200, description = "Matching test cases", body = TestCasesResponse),
+        (status = 400, description = "Invalid cursor")
+    ),
+    tag = "Test Cases"
+)]
+pub async fn list_test_cases(
+    State(state): State<AppState>,
+    Query(query): Query<TestCaseQuery>,
+    Query(cursor): Query<CursorQuery>,
+) -> ApiResult<Json<TestCasesResponse>> {
+    let page = cursor.page_request()?;
+    let non_blank = |value: Option<String>| value.filter(|v| !v.trim().is_empty());
+    let filter = TestCaseFilter {
+        text: non_blank(query.q),
+        tag: non_blank(query.tag),
+        category: non_blank(query.category),
+        priority: non_blank(query.priority),
+    };
+    let cases = state
+        .test_cases
+        .search(&filter, &page)
+        .await
+        .map_err(repo_error)?;
+    let result = page.finish(cases, |case| (case.created_at, case.id));
+    Ok(Json(TestCasesResponse {
+        items: result.data,
+        page_info: result.page_info,
+    }))
+}
+
+/// Get a test case.
+#[utoipa::path(
+    get,
+    path = "/api/v1/test-cases/{id}",
+    params(("id" = Uuid, Path, description = "Test case ID")),
+    responses(
+        (status = 200, description = "Test case", body = TestCase),
+        (status = 404, description = "Test case not found")
+    ),
+    tag = "Test Cases"
+)]
+pub async fn get_test_case(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> ApiResult<Json<TestCase>> {
+    Ok(Json(find(&state, id).await?))
+}
+
+/// Create a local test case.
+#[utoipa::path(
+    post,
+    path = "/api/v1/test-cases",
+    request_body = TestCaseRequest,
+    responses(
+        (status = 201, description = "Test case created", body = TestCase),
+        (status = 422, description = "Invalid request", body = qa_pms_core::error::ErrorResponse)
+    ),
+    tag = "Test Cases"
+)]
+pub async fn create_test_case(
+    State(state): State<AppState>,
+    ValidatedJson(request): ValidatedJson<TestCaseRequest>,
+) -> ApiResult<(StatusCode, Extension<AuditChange>, Json<TestCase>)> {
+    let mut case = TestCase::new(String::new());
+    request.apply(&mut case);
+    let case = state.test_cases.save(case).await.map_err(repo_error)?;
+    index(&state, &case);
+
+    info!(test_case_id = %case.id, title = %case.title, "Test case created");
+    let change = AuditChange::new(case.id).after(&case);
+    Ok((StatusCode::CREATED, change.into_extension(), Json(case)))
+}
+
+/// Replace a test case's content.
+///
+/// The Testmo link is kept; the edit is pushed on the next sync.
+#[utoipa::path(
+    put,
+    path = "/api/v1/test-cases/{id}",
+    params(("id" = Uuid, Path, description = "Test case ID")),
+    request_body = TestCaseRequest,
+    responses(
+        (status = 200, description = "Test case updated", body = TestCase),
+        (status = 404, description = "Test case not found"),
+        (status = 422, description = "Invalid request", body = qa_pms_core::error::ErrorResponse)
+    ),
+    tag = "Test Cases"
+)]
+pub async fn update_test_case(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    ValidatedJson(request): ValidatedJson<TestCaseRequest>,
+) -> ApiResult<(Extension<AuditChange>, Json<TestCase>)> {
+    let mut case = find(&state, id).await?;
+    let change = AuditChange::new(id).before(&case);
+    request.apply(&mut case);
+    case.updated_at = Utc::now();
+    let case = state.test_cases.save(case).await.map_err(repo_error)?;
+    index(&state, &case);
+    Ok((change.after(&case).into_extension(), Json(case)))
+}
+
+/// Delete a local test case.
+///
+/// A linked Testmo case is left in place.
+#[utoipa::path(
+    delete,
+    path = "/api/v1/test-cases/{id}",
+    params(("id" = Uuid, Path, description = "Test case ID")),
+    responses(
+        (status = 204, description = "Test case deleted"),
+        (status = 404, description = "Test case not found")
+    ),
+    tag = "Test Cases"
+)]
+pub async fn delete_test_case(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> ApiResult<(StatusCode, Extension<AuditChange>)> {
+    let change = AuditChange::new(id).before(&find(&state, id).await?);
+    if !state.test_cases.delete(id).await.map_err(repo_error)? {
+        return Err(ApiError::NotFound(format!("Test case {id}")));
+    }
+    state
+        .search_index
+        .remove(IndexSource::TestCase, &id.to_string());
+    Ok((StatusCode::NO_CONTENT, change.into_extension()))
+}
+
+/// List pairs of cases similar enough to be duplicates, most similar first.
+#[utoipa::path(
+    get,
+    path = "/api/v1/test-cases/duplicates",
+    params(DuplicatesQuery),
+    responses(
+        (status = 200, description = "Likely duplicates", body = Vec<DuplicatePairResponse>),
+        (status = 400, description = "Threshold outside 0.0 to 1.0")
+    ),
+    tag = "Test Cases"
+)]
+pub async fn list_duplicates(
+    State(state): State<AppState>,
+    Query(query): Query<DuplicatesQuery>,
+) -> ApiResult<Json<Vec<DuplicatePairResponse>>> {
+    let threshold = query.threshold.unwrap_or(DEFAULT_DUPLICATE_THRESHOLD);
+    if !(0.0..=1.0).contains(&threshold) {
+        return Err(ApiError::Validation(
+            "threshold must be between 0.0 and 1.0".to_string(),
+        ));
+    }
+    let cases = state.test_cases.list().await.map_err(repo_error)?;
+    let summary = |id: Uuid| DuplicateCaseResponse {
+        id,
+        title: cases
+            .iter()
+            .find(|case| case.id == id)
+            .map(|case| case.title.clone())
+            .unwrap_or_default(),
+    };
+    let pairs = find_duplicates(&cases, threshold)
+        .into_iter()
+        .map(|pair| DuplicatePairResponse {
+            first: summary(pair.first),
+            second: summary(pair.second),
+            similarity: pair.similarity,
+        })
+        .collect();
+    Ok(Json(pairs))
+}
+
+/// Merge duplicates into a case and delete them.
+///
+/// Steps and tags only the duplicates had are added to the target, and each
+/// duplicate is recorded in the target's `mergedFrom` list.
+#[utoipa::path(
+    post,
+    path = "/api/v1/test-cases/{id}/merge",
+    params(("id" = Uuid, Path, description = "Test case to keep")),
+    request_body = MergeTestCasesRequest,
+    responses(
+        (status = 200, description = "Merged test case", body = TestCase),
+        (status = 400, description = "Case listed as its own duplicate"),
+        (status = 404, description = "Test case not found"),
+        (status = 409, description = "More than one case is linked to Testmo"),
+        (status = 422, description = "Invalid request", body = qa_pms_core::error::ErrorResponse)
+    ),
+    tag = "Test Cases"
+)]
+pub async fn merge_test_cases(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    ValidatedJson(request): ValidatedJson<MergeTestCasesRequest>,
+) -> ApiResult<(Extension<AuditChange>, Json<TestCase>)> {
+    let primary = find(&state, id).await?;
+    let mut duplicate_ids = request.duplicate_ids;
+    let mut seen = std::collections::HashSet::new();
+    duplicate_ids.retain(|id| seen.insert(*id));
+    let mut duplicates = Vec::with_capacity(duplicate_ids.len());
+    for duplicate_id in &duplicate_ids {
+        duplicates.push(find(&state, *duplicate_id).await?);
+    }
+
+    let change = AuditChange::new(id).before(&serde_json::json!({
+        "primary": &primary,
+        "duplicates": &duplicates,
+    }));
+    let merged = merge_cases(primary, duplicates, Utc::now()).map_err(merge_error)?;
+    let merged = state.test_cases.save(merged).await.map_err(repo_error)?;
+    for duplicate_id in &duplicate_ids {
+        state
+            .test_cases
+            .delete(*duplicate_id)
+            .await
+            .map_err(repo_error)?;
+        state
+            .search_index
+            .remove(IndexSource::TestCase, &duplicate_id.to_string());
+    }
+    index(&state, &merged);
+
+    info!(test_case_id = %merged.id, merged = duplicate_ids.len(), "Test cases merged");
+    Ok((change.after(&merged).into_extension(), Json(merged)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_request_apply_keeps_identity_and_link() {
+        let mut case = TestCase::new("Old title");
+        case.remote = Some(qa_pms_core::RemoteLink {
+            system: "testmo".to_string(),
+            project_id: "1".to_string(),
+            remote_id: "42".to_string(),
+            remote_updated_at: "2024-01-01T00:00:00Z".to_string(),
+            synced_at: case.updated_at,
+        });
+        let id = case.id;
+
+        let request: TestCaseRequest = serde_json::from_str(
+            r#"{"title":"  Login works ","tags":["smoke"],"steps":[{"action":"Sign in","expected":"Dashboard"}]}"#,
+        )
+        .unwrap();
+        request.apply(&mut case);
+
+        assert_eq!(case.id, id);
+        assert_eq!(case.title, "Login works");
+        assert_eq!(case.tags, vec!["smoke".to_string()]);
+        assert_eq!(case.steps[0].expected.as_deref(), Some("Dashboard"));
+        assert!(case.is_linked_to("testmo", "42"));
+    }
+
+    #[test]
+    fn test_request_rejects_blank_steps() {
+        let request: TestCaseRequest =
+            serde_json::from_str(r#"{"title":"Login","steps":[{"action":" "}]}"#).unwrap();
+        assert!(request.validate().is_err());
+    }
+}
@@ -2,28 +2,53 @@
 //!
 //! Endpoints for interacting with Testmo test management.
 
+use std::sync::Arc;
+
 use axum::{
-    extract::State,
+    extract::{Path, State},
     http::StatusCode,
-    routing::post,
+    routing::{get, post},
     Json, Router,
 };
-use chrono::Utc;
+use chrono::{DateTime, Utc};
+use qa_pms_testmo::{
+    parse_junit, AutomationTest, CreateAutomationRunRequest as TestmoAutomationRunRequest,
+    CreateTestCaseRequest as TestmoCreateCaseRequest,
+    CreateMilestoneRequest as TestmoMilestoneRequest, Milestone, ResultAttachment, ResultStatus,
+    SyncConflict, SyncDirection, SyncReport, TestmoClient, TestmoSync, TestResultInput,
+};
+use qa_pms_core::error::ApiError;
+use qa_pms_core::{TenantId, TestCase, TestCaseStep};
+use qa_pms_workflow::{add_step_link, get_instance, StepLink};
+use secrecy::ExposeSecret;
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::{Validate, ValidationError};
 
-use crate::app::AppState;
+use crate::app::{testmo_client_options, AppState};
+use crate::auth::CurrentUser;
+use crate::routes::tenant::{tenant_credential, TESTMO_API_KEY, TESTMO_PROJECT_ID, TESTMO_URL};
+use crate::search_index::{IndexDocument, IndexSource};
+use crate::test_run_sync::record_run;
+use crate::validation::{not_blank, one_of, ValidatedJson};
 
 /// Create test run request.
-#[derive(Debug, Deserialize, ToSchema)]
+#[derive(Debug, Deserialize, ToSchema, Validate)]
 #[serde(rename_all = "camelCase")]
 pub struct CreateTestRunRequest {
     /// Ticket key for naming convention (e.g., "PROJ-123")
+    #[validate(custom(function = "not_blank"))]
     pub ticket_key: String,
     /// Test case IDs to include in the run
+    #[validate(length(min = 1, message = "at least one test case is required"))]
     pub case_ids: Vec<i64>,
     /// Optional custom name (overrides generated name)
     pub custom_name: Option<String>,
+    /// Milestone (sprint/release) to group the run under
+    pub milestone_id: Option<i64>,
+    /// Workflow the run belongs to, for its summary
+    pub workflow_id: Option<Uuid>,
 }
 
 /// Create test run response.
@@ -38,6 +63,335 @@ pub struct CreateTestRunResponse {
     pub url: String,
     /// Number of test cases included
     pub case_count: usize,
+    /// Milestone the run was grouped under
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub milestone_id: Option<i64>,
+}
+
+/// Create milestone request.
+#[derive(Debug, Deserialize, ToSchema, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateMilestoneRequest {
+    /// Milestone name (e.g., "Sprint 42")
+    #[validate(custom(function = "not_blank"))]
+    pub name: String,
+    /// Optional description
+    pub description: Option<String>,
+    /// Optional due date
+    pub due_at: Option<DateTime<Utc>>,
+}
+
+/// Testmo milestone.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct MilestoneResponse {
+    /// Milestone ID
+    pub id: i64,
+    /// Milestone name
+    pub name: String,
+    /// Description
+    pub description: Option<String>,
+    /// Whether the milestone is closed
+    pub is_completed: bool,
+    /// Due date
+    pub due_at: Option<String>,
+    /// URL to the milestone in Testmo
+    pub url: String,
+}
+
+/// Submit results request.
+#[derive(Debug, Deserialize, ToSchema, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct SubmitResultsRequest {
+    /// One result per test case
+    #[validate(length(min = 1, message = "at least one result is required"), nested)]
+    pub results: Vec<TestResultRequest>,
+}
+
+/// Outcome of a single test case.
+#[derive(Debug, Serialize, Deserialize, ToSchema, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct TestResultRequest {
+    /// Test case ID
+    pub case_id: i64,
+    /// Outcome (passed, failed, blocked, skipped, retest)
+    #[validate(custom(function = "valid_result_status"))]
+    pub status: String,
+    /// Execution time in seconds
+    #[validate(range(min = 0))]
+    pub duration_seconds: Option<i64>,
+    /// Tester notes
+    pub notes: Option<String>,
+    /// Evidence links (screenshots, logs)
+    #[serde(default)]
+    #[validate(nested)]
+    pub attachments: Vec<ResultAttachmentRequest>,
+}
+
+/// Evidence attached to a result.
+#[derive(Debug, Serialize, Deserialize, ToSchema, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct ResultAttachmentRequest {
+    /// Display name
+    #[validate(custom(function = "not_blank"))]
+    pub name: String,
+    /// File URL
+    #[validate(url)]
+    pub url: String,
+}
+
+/// Submit results response.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SubmitResultsResponse {
+    /// Run the results were recorded in
+    pub run_id: i64,
+    /// Number of results stored
+    pub submitted: usize,
+    /// Number of passed results
+    pub passed: usize,
+    /// Number of failed results
+    pub failed: usize,
+    /// Number of blocked results
+    pub blocked: usize,
+    /// URL to the run in Testmo
+    pub url: String,
+}
+
+/// Create automation run request.
+#[derive(Debug, Deserialize, ToSchema, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateAutomationRunRequest {
+    /// Run name (e.g., "Checkout e2e - PROJ-123")
+    #[validate(custom(function = "not_blank"))]
+    pub name: String,
+    /// Source label, typically the CI job (default: "qa-pms")
+    pub source: Option<String>,
+    /// Milestone to group the run under
+    pub milestone_id: Option<i64>,
+    /// Workflow whose step should link to the run
+    pub workflow_id: Option<Uuid>,
+    /// Step index to link (requires `workflowId`)
+    #[validate(range(min = 0))]
+    pub step_index: Option<i32>,
+}
+
+/// Automation run response.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AutomationRunResponse {
+    /// Automation run ID
+    pub run_id: i64,
+    /// Run name
+    pub name: String,
+    /// URL to the run in Testmo
+    pub url: String,
+    /// Whether the run was linked to a workflow step
+    pub linked_to_step: bool,
+}
+
+/// Automation results request.
+///
+/// Provide either `junitXml` (a JUnit report) or `tests`.
+#[derive(Debug, Deserialize, ToSchema, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct AutomationResultsRequest {
+    /// JUnit XML report
+    pub junit_xml: Option<String>,
+    /// Individual test results
+    #[serde(default)]
+    #[validate(nested)]
+    pub tests: Vec<AutomationTestRequest>,
+    /// Complete the run after appending (default: true)
+    #[serde(default = "default_true")]
+    pub complete: bool,
+}
+
+const fn default_true() -> bool {
+    true
+}
+
+/// A single automated test outcome.
+#[derive(Debug, Deserialize, ToSchema, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct AutomationTestRequest {
+    /// Test name
+    #[validate(custom(function = "not_blank"))]
+    pub name: String,
+    /// Suite or class the test belongs to
+    pub folder: Option<String>,
+    /// Outcome (passed, failed, blocked, skipped, retest)
+    #[validate(custom(function = "valid_result_status"))]
+    pub status: String,
+    /// Execution time in milliseconds
+    #[validate(range(min = 0))]
+    pub elapsed_ms: Option<i64>,
+    /// Failure message
+    pub message: Option<String>,
+}
+
+/// Automation results response.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AutomationResultsResponse {
+    /// Automation run ID
+    pub run_id: i64,
+    /// Number of tests appended
+    pub submitted: usize,
+    /// Number of passed tests
+    pub passed: usize,
+    /// Number of failed tests
+    pub failed: usize,
+    /// Number of skipped tests
+    pub skipped: usize,
+    /// Whether the run was completed
+    pub completed: bool,
+}
+
+/// Bulk test case create request.
+#[derive(Debug, Deserialize, ToSchema, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkCreateCasesRequest {
+    /// Folder to create the cases in
+    pub folder_id: Option<i64>,
+    /// Cases to create
+    #[validate(length(min = 1, max = 500), nested)]
+    pub cases: Vec<NewTestCaseRequest>,
+}
+
+/// A test case to create.
+#[derive(Debug, Serialize, Deserialize, ToSchema, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct NewTestCaseRequest {
+    /// Case title
+    #[validate(custom(function = "not_blank"))]
+    pub title: String,
+    /// Preconditions
+    pub preconditions: Option<String>,
+    /// Priority label, translated by the configured field mapping
+    pub priority: Option<String>,
+    /// Category, translated by the configured field mapping
+    pub category: Option<String>,
+    /// Tags, translated by the configured field mapping
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Ordered steps
+    #[serde(default)]
+    #[validate(nested)]
+    pub steps: Vec<NewTestStepRequest>,
+}
+
+/// A step of a test case to create.
+#[derive(Debug, Serialize, Deserialize, ToSchema, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct NewTestStepRequest {
+    /// Action to perform
+    #[validate(custom(function = "not_blank"))]
+    pub action: String,
+    /// Expected result
+    pub expected: Option<String>,
+}
+
+/// A case created in Testmo.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CreatedCaseResponse {
+    /// Position in the request
+    pub index: usize,
+    /// Testmo case ID
+    pub case_id: i64,
+    /// Case title
+    pub title: String,
+    /// URL to the case in Testmo
+    pub url: String,
+}
+
+/// A case Testmo did not create.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct FailedCaseResponse {
+    /// Position in the request
+    pub index: usize,
+    /// Case title
+    pub title: String,
+    /// Failure reason
+    pub error: String,
+}
+
+/// Bulk test case create response.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkCreateCasesResponse {
+    /// Created cases
+    pub created: Vec<CreatedCaseResponse>,
+    /// Cases that failed
+    pub failed: Vec<FailedCaseResponse>,
+}
+
+/// Test case sync request.
+#[derive(Debug, Default, Deserialize, ToSchema, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncTestCasesRequest {
+    /// "pull", "push" or "both" (default)
+    #[validate(custom(function = "valid_sync_direction"))]
+    #[schema(example = "both")]
+    pub direction: Option<String>,
+}
+
+/// A case edited both locally and in Testmo since the last sync.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncConflictResponse {
+    /// Local case ID
+    pub local_id: Uuid,
+    /// Testmo case ID
+    pub remote_id: i64,
+    /// Case title
+    pub title: String,
+    /// Last local edit
+    pub local_updated_at: DateTime<Utc>,
+    /// Current Testmo update timestamp
+    pub remote_updated_at: String,
+}
+
+/// Test case sync response.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncTestCasesResponse {
+    /// Cases imported from Testmo for the first time
+    pub imported: usize,
+    /// Local cases refreshed from Testmo
+    pub updated_local: usize,
+    /// Local edits pushed to Testmo
+    pub pushed: usize,
+    /// Cases unchanged on both sides
+    pub unchanged: usize,
+    /// Cases left untouched because both sides changed
+    pub conflicts: Vec<SyncConflictResponse>,
+}
+
+impl From<SyncReport> for SyncTestCasesResponse {
+    fn from(report: SyncReport) -> Self {
+        Self {
+            imported: report.imported,
+            updated_local: report.updated_local,
+            pushed: report.pushed,
+            unchanged: report.unchanged,
+            conflicts: report.conflicts.into_iter().map(SyncConflictResponse::from).collect(),
+        }
+    }
+}
+
+impl From<SyncConflict> for SyncConflictResponse {
+    fn from(conflict: SyncConflict) -> Self {
+        Self {
+            local_id: conflict.local_id,
+            remote_id: conflict.remote_id,
+            title: conflict.title,
+            local_updated_at: conflict.local_updated_at,
+            remote_updated_at: conflict.remote_updated_at,
+        }
+    }
 }
 
 /// Error response.
@@ -46,9 +400,158 @@ pub struct ErrorResponse {
     pub message: String,
 }
 
+type TestmoResult<T> = Result<T, (StatusCode, Json<ErrorResponse>)>;
+
 /// Create Testmo routes.
 pub fn router() -> Router<AppState> {
-    Router::new().route("/runs", post(create_test_run))
+    Router::new()
+        .route("/milestones", get(list_milestones).post(create_milestone))
+        .route("/runs", post(create_test_run))
+        .route("/runs/:run_id/results", post(submit_results))
+        .route("/automation/runs", post(create_automation_run))
+        .route("/automation/runs/:run_id/results", post(submit_automation_results))
+        .route("/cases/bulk", post(bulk_create_cases))
+        .route("/sync", post(sync_test_cases))
+}
+
+fn error(status: StatusCode, message: impl Into<String>) -> (StatusCode, Json<ErrorResponse>) {
+    (
+        status,
+        Json(ErrorResponse {
+            message: message.into(),
+        }),
+    )
+}
+
+/// Testmo client and project of a tenant.
+///
+/// A tenant that stored a Testmo API key gets its own client, with its URL
+/// and project defaulting to the instance settings; other tenants share the
+/// instance client.
+pub(crate) fn tenant_client(
+    state: &AppState,
+    tenant: &TenantId,
+) -> Result<(Option<Arc<TestmoClient>>, Option<i64>), ApiError> {
+    let Some(api_key) = tenant_credential(state, tenant, TESTMO_API_KEY)? else {
+        return Ok((state.testmo_client.clone(), state.testmo_project_id));
+    };
+    let settings = state.settings.testmo.as_ref();
+    let base_url = match tenant_credential(state, tenant, TESTMO_URL)? {
+        Some(url) => url.expose_secret().clone(),
+        None => settings.map(|t| t.base_url.clone()).unwrap_or_default(),
+    };
+    if base_url.is_empty() {
+        return Ok((None, None));
+    }
+    let project_id = match tenant_credential(state, tenant, TESTMO_PROJECT_ID)? {
+        Some(id) => Some(id.expose_secret().trim().parse().map_err(|_| {
+            ApiError::Validation(format!(
+                "Tenant credential {TESTMO_PROJECT_ID} is not a project ID"
+            ))
+        })?),
+        None => settings.and_then(|t| t.project_id),
+    };
+    let options = settings.map_or_else(Default::default, |t| {
+        testmo_client_options(t, &state.settings.retry)
+    });
+    let client = TestmoClient::with_options(base_url, api_key.expose_secret().clone(), options)
+        .with_circuit_breaker(Arc::clone(&state.circuit_breakers.testmo));
+    Ok((Some(Arc::new(client)), project_id))
+}
+
+/// Get the tenant's client and project, or a 503.
+fn configured(state: &AppState, tenant: &TenantId) -> TestmoResult<(Arc<TestmoClient>, i64)> {
+    let (client, project_id) = tenant_client(state, tenant)
+        .map_err(|e| error(StatusCode::SERVICE_UNAVAILABLE, e.to_string()))?;
+    let client = client
+        .ok_or_else(|| error(StatusCode::SERVICE_UNAVAILABLE, "Testmo integration not configured"))?;
+    let project_id = project_id
+        .ok_or_else(|| error(StatusCode::SERVICE_UNAVAILABLE, "Testmo project ID not configured"))?;
+    Ok((client, project_id))
+}
+
+fn run_url(client: &TestmoClient, project_id: i64, run_id: i64) -> String {
+    format!("{}/projects/{}/runs/{}", client.base_url(), project_id, run_id)
+}
+
+fn milestone_response(client: &TestmoClient, milestone: Milestone) -> MilestoneResponse {
+    MilestoneResponse {
+        url: format!(
+            "{}/projects/{}/milestones/{}",
+            client.base_url(),
+            milestone.project_id,
+            milestone.id
+        ),
+        id: milestone.id,
+        name: milestone.name,
+        description: milestone.description,
+        is_completed: milestone.is_completed,
+        due_at: milestone.due_at,
+    }
+}
+
+/// List milestones in the configured Testmo project.
+#[utoipa::path(
+    get,
+    path = "/api/v1/testmo/milestones",
+    responses(
+        (status = 200, description = "Milestones", body = Vec<MilestoneResponse>),
+        (status = 502, description = "Testmo request failed", body = ErrorResponse),
+        (status = 503, description = "Testmo not configured", body = ErrorResponse)
+    ),
+    tag = "testmo"
+)]
+async fn list_milestones(
+    State(state): State<AppState>,
+    user: CurrentUser,
+) -> TestmoResult<Json<Vec<MilestoneResponse>>> {
+    let (testmo_client, project_id) = configured(&state, &user.tenant)?;
+
+    let milestones = testmo_client.list_milestones(project_id).await.map_err(|e| {
+        tracing::error!(error = %e, "Failed to list Testmo milestones");
+        error(StatusCode::BAD_GATEWAY, format!("Failed to list milestones: {e}"))
+    })?;
+
+    Ok(Json(
+        milestones
+            .into_iter()
+            .map(|m| milestone_response(&testmo_client, m))
+            .collect(),
+    ))
+}
+
+/// Create a milestone in the configured Testmo project.
+#[utoipa::path(
+    post,
+    path = "/api/v1/testmo/milestones",
+    request_body = CreateMilestoneRequest,
+    responses(
+        (status = 201, description = "Milestone created", body = MilestoneResponse),
+        (status = 422, description = "Invalid request", body = qa_pms_core::error::ErrorResponse),
+        (status = 502, description = "Testmo request failed", body = ErrorResponse),
+        (status = 503, description = "Testmo not configured", body = ErrorResponse)
+    ),
+    tag = "testmo"
+)]
+async fn create_milestone(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    ValidatedJson(request): ValidatedJson<CreateMilestoneRequest>,
+) -> TestmoResult<(StatusCode, Json<MilestoneResponse>)> {
+    let (testmo_client, project_id) = configured(&state, &user.tenant)?;
+
+    let body = TestmoMilestoneRequest {
+        name: request.name.trim().to_string(),
+        description: request.description,
+        due_at: request.due_at.map(|d| d.to_rfc3339()),
+    };
+    let milestone = testmo_client.create_milestone(project_id, &body).await.map_err(|e| {
+        tracing::error!(error = %e, "Failed to create Testmo milestone");
+        error(StatusCode::BAD_GATEWAY, format!("Failed to create milestone: {e}"))
+    })?;
+
+    tracing::info!(milestone_id = milestone.id, name = %milestone.name, "Created Testmo milestone");
+    Ok((StatusCode::CREATED, Json(milestone_response(&testmo_client, milestone))))
 }
 
 /// Create a test run in Testmo.
@@ -61,43 +564,17 @@ pub fn router() -> Router<AppState> {
     request_body = CreateTestRunRequest,
     responses(
         (status = 201, description = "Test run created successfully", body = CreateTestRunResponse),
-        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 422, description = "Invalid request", body = qa_pms_core::error::ErrorResponse),
         (status = 503, description = "Testmo not configured", body = ErrorResponse)
     ),
     tag = "testmo"
 )]
 async fn create_test_run(
     State(state): State<AppState>,
-    Json(request): Json<CreateTestRunRequest>,
-) -> Result<(StatusCode, Json<CreateTestRunResponse>), (StatusCode, Json<ErrorResponse>)> {
-    // Check if Testmo is configured
-    let testmo_client = state.testmo_client.as_ref().ok_or_else(|| {
-        (
-            StatusCode::SERVICE_UNAVAILABLE,
-            Json(ErrorResponse {
-                message: "Testmo integration not configured".to_string(),
-            }),
-        )
-    })?;
-
-    let project_id = state.testmo_project_id.ok_or_else(|| {
-        (
-            StatusCode::SERVICE_UNAVAILABLE,
-            Json(ErrorResponse {
-                message: "Testmo project ID not configured".to_string(),
-            }),
-        )
-    })?;
-
-    // Validate request
-    if request.case_ids.is_empty() {
-        return Err((
-            StatusCode::BAD_REQUEST,
-            Json(ErrorResponse {
-                message: "At least one test case is required".to_string(),
-            }),
-        ));
-    }
+    user: CurrentUser,
+    ValidatedJson(request): ValidatedJson<CreateTestRunRequest>,
+) -> TestmoResult<(StatusCode, Json<CreateTestRunResponse>)> {
+    let (testmo_client, project_id) = configured(&state, &user.tenant)?;
 
     // Generate run name
     let run_name = match &request.custom_name {
@@ -107,25 +584,27 @@ async fn create_test_run(
 
     // Create test run
     let test_run = testmo_client
-        .create_test_run(project_id, &run_name, &request.case_ids)
+        .create_test_run(project_id, &run_name, &request.case_ids, request.milestone_id)
         .await
         .map_err(|e| {
             tracing::error!(error = %e, "Failed to create Testmo test run");
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ErrorResponse {
-                    message: format!("Failed to create test run: {e}"),
-                }),
-            )
+            error(StatusCode::INTERNAL_SERVER_ERROR, format!("Failed to create test run: {e}"))
         })?;
 
-    // Generate URL to the run
-    let url = format!(
-        "{}/projects/{}/runs/{}",
-        testmo_client.base_url(),
-        project_id,
-        test_run.id
-    );
+    let url = run_url(&testmo_client, project_id, test_run.id);
+
+    // The run exists in Testmo either way; only result sync is lost
+    if let Err(e) = record_run(
+        &state.db,
+        test_run.id,
+        request.workflow_id,
+        &request.ticket_key,
+        &run_name,
+    )
+    .await
+    {
+        tracing::warn!(run_id = test_run.id, error = %e, "Failed to record Testmo run for result sync");
+    }
 
     tracing::info!(
         run_id = test_run.id,
@@ -141,10 +620,377 @@ async fn create_test_run(
             name: run_name,
             url,
             case_count: request.case_ids.len(),
+            milestone_id: test_run.milestone_id.or(request.milestone_id),
         }),
     ))
 }
 
+/// Record test results in a Testmo run.
+///
+/// Pushes the outcome of a QA session (status, duration, notes and evidence
+/// per test case) into the run created for it.
+#[utoipa::path(
+    post,
+    path = "/api/v1/testmo/runs/{run_id}/results",
+    params(("run_id" = i64, Path, description = "Testmo run ID")),
+    request_body = SubmitResultsRequest,
+    responses(
+        (status = 200, description = "Results recorded", body = SubmitResultsResponse),
+        (status = 422, description = "Invalid request", body = qa_pms_core::error::ErrorResponse),
+        (status = 502, description = "Testmo rejected the results", body = ErrorResponse),
+        (status = 503, description = "Testmo not configured", body = ErrorResponse)
+    ),
+    tag = "testmo"
+)]
+async fn submit_results(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Path(run_id): Path<i64>,
+    ValidatedJson(request): ValidatedJson<SubmitResultsRequest>,
+) -> TestmoResult<Json<SubmitResultsResponse>> {
+    let (testmo_client, project_id) = configured(&state, &user.tenant)?;
+
+    let results: Vec<TestResultInput> = request.results.into_iter().map(to_result_input).collect();
+    let stored = testmo_client.add_results(run_id, &results).await.map_err(|e| {
+        tracing::error!(error = %e, run_id, "Failed to submit Testmo results");
+        error(StatusCode::BAD_GATEWAY, format!("Failed to submit results: {e}"))
+    })?;
+
+    let count = |status: ResultStatus| results.iter().filter(|r| r.status == status).count();
+    tracing::info!(run_id, submitted = stored.len(), "Submitted Testmo results");
+
+    Ok(Json(SubmitResultsResponse {
+        run_id,
+        submitted: stored.len(),
+        passed: count(ResultStatus::Passed),
+        failed: count(ResultStatus::Failed),
+        blocked: count(ResultStatus::Blocked),
+        url: run_url(&testmo_client, project_id, run_id),
+    }))
+}
+
+/// Create a Testmo automation run.
+///
+/// When `workflowId` and `stepIndex` are given, the run URL is attached to
+/// that workflow step so automated suites show up next to manual results.
+#[utoipa::path(
+    post,
+    path = "/api/v1/testmo/automation/runs",
+    request_body = CreateAutomationRunRequest,
+    responses(
+        (status = 201, description = "Automation run created", body = AutomationRunResponse),
+        (status = 404, description = "Workflow not found", body = ErrorResponse),
+        (status = 422, description = "Invalid request", body = qa_pms_core::error::ErrorResponse),
+        (status = 502, description = "Testmo request failed", body = ErrorResponse),
+        (status = 503, description = "Testmo not configured", body = ErrorResponse)
+    ),
+    tag = "testmo"
+)]
+async fn create_automation_run(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    ValidatedJson(request): ValidatedJson<CreateAutomationRunRequest>,
+) -> TestmoResult<(StatusCode, Json<AutomationRunResponse>)> {
+    let (testmo_client, project_id) = configured(&state, &user.tenant)?;
+
+    let step = match (request.workflow_id, request.step_index) {
+        (Some(workflow_id), Some(step_index)) => {
+            let found = get_instance(&state.db, workflow_id)
+                .await
+                .map_err(|e| error(StatusCode::INTERNAL_SERVER_ERROR, format!("Database error: {e}")))?;
+            if found.is_none() {
+                return Err(error(StatusCode::NOT_FOUND, format!("Workflow {workflow_id} not found")));
+            }
+            Some((workflow_id, step_index))
+        }
+        _ => None,
+    };
+
+    let body = TestmoAutomationRunRequest {
+        name: request.name.trim().to_string(),
+        source: request.source.unwrap_or_else(|| "qa-pms".to_string()),
+        milestone_id: request.milestone_id,
+    };
+    let run = testmo_client.create_automation_run(project_id, &body).await.map_err(|e| {
+        tracing::error!(error = %e, "Failed to create Testmo automation run");
+        error(StatusCode::BAD_GATEWAY, format!("Failed to create automation run: {e}"))
+    })?;
+    let url = format!(
+        "{}/projects/{}/automation/runs/{}",
+        testmo_client.base_url(),
+        project_id,
+        run.id
+    );
+
+    if let Some((workflow_id, step_index)) = step {
+        let link = StepLink {
+            title: format!("Testmo automation: {}", run.name),
+            url: url.clone(),
+        };
+        add_step_link(&state.db, workflow_id, step_index, &link)
+            .await
+            .map_err(|e| error(StatusCode::INTERNAL_SERVER_ERROR, format!("Database error: {e}")))?;
+    }
+
+    tracing::info!(run_id = run.id, linked = step.is_some(), "Created Testmo automation run");
+    Ok((
+        StatusCode::CREATED,
+        Json(AutomationRunResponse {
+            run_id: run.id,
+            name: run.name,
+            url,
+            linked_to_step: step.is_some(),
+        }),
+    ))
+}
+
+/// Append automated test results to a Testmo automation run.
+#[utoipa::path(
+    post,
+    path = "/api/v1/testmo/automation/runs/{run_id}/results",
+    params(("run_id" = i64, Path, description = "Testmo automation run ID")),
+    request_body = AutomationResultsRequest,
+    responses(
+        (status = 200, description = "Results appended", body = AutomationResultsResponse),
+        (status = 400, description = "No results or invalid JUnit XML", body = ErrorResponse),
+        (status = 422, description = "Invalid request", body = qa_pms_core::error::ErrorResponse),
+        (status = 502, description = "Testmo request failed", body = ErrorResponse),
+        (status = 503, description = "Testmo not configured", body = ErrorResponse)
+    ),
+    tag = "testmo"
+)]
+async fn submit_automation_results(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Path(run_id): Path<i64>,
+    ValidatedJson(request): ValidatedJson<AutomationResultsRequest>,
+) -> TestmoResult<Json<AutomationResultsResponse>> {
+    let (testmo_client, _) = configured(&state, &user.tenant)?;
+
+    let complete = request.complete;
+    let tests = collect_automation_tests(request)?;
+
+    testmo_client.append_automation_results(run_id, &tests).await.map_err(|e| {
+        tracing::error!(error = %e, run_id, "Failed to append Testmo automation results");
+        error(StatusCode::BAD_GATEWAY, format!("Failed to append results: {e}"))
+    })?;
+    if complete {
+        testmo_client.complete_automation_run(run_id).await.map_err(|e| {
+            error(StatusCode::BAD_GATEWAY, format!("Failed to complete automation run: {e}"))
+        })?;
+    }
+
+    let count = |status: ResultStatus| tests.iter().filter(|t| t.status == status).count();
+    Ok(Json(AutomationResultsResponse {
+        run_id,
+        submitted: tests.len(),
+        passed: count(ResultStatus::Passed),
+        failed: count(ResultStatus::Failed),
+        skipped: count(ResultStatus::Skipped),
+        completed: complete,
+    }))
+}
+
+/// Create many test cases in the configured Testmo project.
+///
+/// Cases are sent in batches; one rejected case does not fail the others.
+/// Responds 201 when every case was created and 207 when some failed.
+#[utoipa::path(
+    post,
+    path = "/api/v1/testmo/cases/bulk",
+    request_body = BulkCreateCasesRequest,
+    responses(
+        (status = 201, description = "All cases created", body = BulkCreateCasesResponse),
+        (status = 207, description = "Some cases failed", body = BulkCreateCasesResponse),
+        (status = 422, description = "Invalid request", body = qa_pms_core::error::ErrorResponse),
+        (status = 502, description = "No case could be created", body = BulkCreateCasesResponse),
+        (status = 503, description = "Testmo not configured", body = ErrorResponse)
+    ),
+    tag = "testmo"
+)]
+async fn bulk_create_cases(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    ValidatedJson(request): ValidatedJson<BulkCreateCasesRequest>,
+) -> TestmoResult<(StatusCode, Json<BulkCreateCasesResponse>)> {
+    let (testmo_client, project_id) = configured(&state, &user.tenant)?;
+
+    let cases: Vec<TestmoCreateCaseRequest> = request
+        .cases
+        .into_iter()
+        .map(|case| {
+            state
+                .testmo_field_mapping
+                .to_create_request(&to_local_case(case), request.folder_id)
+        })
+        .collect();
+
+    let report = testmo_client.create_test_cases(project_id, &cases).await;
+    tracing::info!(
+        created = report.created.len(),
+        failed = report.failed.len(),
+        "Bulk created Testmo test cases"
+    );
+
+    let status = match (report.created.is_empty(), report.failed.is_empty()) {
+        (_, true) => StatusCode::CREATED,
+        (true, false) => StatusCode::BAD_GATEWAY,
+        (false, false) => StatusCode::MULTI_STATUS,
+    };
+    let response = BulkCreateCasesResponse {
+        created: report
+            .created
+            .into_iter()
+            .map(|created| CreatedCaseResponse {
+                index: created.index,
+                case_id: created.case.id,
+                url: format!(
+                    "{}/projects/{}/cases/{}",
+                    testmo_client.base_url(),
+                    project_id,
+                    created.case.id
+                ),
+                title: created.case.title,
+            })
+            .collect(),
+        failed: report
+            .failed
+            .into_iter()
+            .map(|failure| FailedCaseResponse {
+                index: failure.index,
+                title: failure.title,
+                error: failure.error,
+            })
+            .collect(),
+    };
+    Ok((status, Json(response)))
+}
+
+fn to_local_case(request: NewTestCaseRequest) -> TestCase {
+    let mut case = TestCase::new(request.title.trim());
+    case.preconditions = request.preconditions;
+    case.priority = request.priority;
+    case.category = request.category;
+    case.tags = request.tags;
+    case.steps = request
+        .steps
+        .into_iter()
+        .map(|step| TestCaseStep {
+            action: step.action,
+            expected: step.expected,
+        })
+        .collect();
+    case
+}
+
+/// Sync test cases between Testmo and the local repository.
+///
+/// Cases edited on both sides since the last sync are reported as conflicts
+/// and left unchanged.
+#[utoipa::path(
+    post,
+    path = "/api/v1/testmo/sync",
+    request_body = SyncTestCasesRequest,
+    responses(
+        (status = 200, description = "Sync report", body = SyncTestCasesResponse),
+        (status = 422, description = "Invalid request", body = qa_pms_core::error::ErrorResponse),
+        (status = 502, description = "Testmo request failed", body = ErrorResponse),
+        (status = 503, description = "Testmo not configured", body = ErrorResponse)
+    ),
+    tag = "testmo"
+)]
+async fn sync_test_cases(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    ValidatedJson(request): ValidatedJson<SyncTestCasesRequest>,
+) -> TestmoResult<Json<SyncTestCasesResponse>> {
+    let (testmo_client, project_id) = configured(&state, &user.tenant)?;
+    let direction = parse_sync_direction(request.direction.as_deref());
+
+    let report = TestmoSync::new(&testmo_client, state.test_cases.as_ref())
+        .with_field_mapping(state.testmo_field_mapping.as_ref().clone())
+        .sync(project_id, direction)
+        .await
+        .map_err(|e| {
+            tracing::error!(error = %e, project_id, "Testmo test case sync failed");
+            error(StatusCode::BAD_GATEWAY, format!("Failed to sync test cases: {e}"))
+        })?;
+    reindex_test_cases(&state).await;
+
+    Ok(Json(report.into()))
+}
+
+/// Refresh the search index's copy of the local test cases after a sync.
+async fn reindex_test_cases(state: &AppState) {
+    let cases = match state.test_cases.list().await {
+        Ok(cases) => cases,
+        Err(e) => {
+            tracing::warn!(error = %e, "Failed to list test cases for search index");
+            return;
+        }
+    };
+    let testmo = state.search_index.testmo_base_url();
+    if let Err(e) = state.search_index.replace_source(
+        IndexSource::TestCase,
+        cases.iter().map(|case| IndexDocument::test_case(case, testmo)),
+    ) {
+        tracing::warn!(error = %e, "Failed to reindex test cases");
+    }
+}
+
+fn valid_sync_direction(direction: &str) -> Result<(), ValidationError> {
+    one_of(direction, &["pull", "push", "both"])
+}
+
+fn parse_sync_direction(direction: Option<&str>) -> SyncDirection {
+    match direction.map(str::to_ascii_lowercase).as_deref() {
+        Some("pull") => SyncDirection::Pull,
+        Some("push") => SyncDirection::Push,
+        _ => SyncDirection::Both,
+    }
+}
+
+/// Gather tests from the JUnit report and the explicit list.
+fn collect_automation_tests(request: AutomationResultsRequest) -> TestmoResult<Vec<AutomationTest>> {
+    let mut tests = match request.junit_xml.as_deref() {
+        Some(xml) => parse_junit(xml).map_err(|e| error(StatusCode::BAD_REQUEST, e.to_string()))?,
+        None => Vec::new(),
+    };
+    tests.extend(request.tests.into_iter().map(|t| AutomationTest {
+        name: t.name,
+        folder: t.folder,
+        // Validated by `valid_result_status`
+        status: ResultStatus::parse(&t.status).unwrap_or(ResultStatus::Retest),
+        elapsed_ms: t.elapsed_ms,
+        message: t.message,
+    }));
+
+    if tests.is_empty() {
+        return Err(error(StatusCode::BAD_REQUEST, "Provide junitXml or at least one test"));
+    }
+    Ok(tests)
+}
+
+fn valid_result_status(status: &str) -> Result<(), ValidationError> {
+    one_of(status, &["passed", "failed", "blocked", "skipped", "retest"])
+}
+
+/// Convert a validated request item into the client input.
+fn to_result_input(result: TestResultRequest) -> TestResultInput {
+    TestResultInput {
+        case_id: result.case_id,
+        // Validated by `valid_result_status`
+        status: ResultStatus::parse(&result.status).unwrap_or(ResultStatus::Retest),
+        elapsed: result.duration_seconds,
+        note: result.notes.filter(|n| !n.trim().is_empty()),
+        attachments: result
+            .attachments
+            .into_iter()
+            .map(|a| ResultAttachment { name: a.name, url: a.url })
+            .collect(),
+    }
+}
+
 /// Generate test run name from ticket key.
 ///
 /// Format: QA-{ticket-key}-{YYYY-MM-DD}
@@ -165,6 +1011,91 @@ mod tests {
         assert!(name.contains(&Utc::now().format("%Y-%m-%d").to_string()));
     }
 
+    #[test]
+    fn test_to_local_case_trims_title_and_maps_steps() {
+        let case = to_local_case(NewTestCaseRequest {
+            title: "  Refund order ".to_string(),
+            preconditions: None,
+            priority: Some("high".to_string()),
+            category: None,
+            tags: vec!["ai-generated".to_string()],
+            steps: vec![NewTestStepRequest {
+                action: "Request refund".to_string(),
+                expected: Some("Refund issued".to_string()),
+            }],
+        });
+        assert_eq!(case.title, "Refund order");
+        assert_eq!(case.priority.as_deref(), Some("high"));
+        assert_eq!(case.steps[0].action, "Request refund");
+        assert!(case.remote.is_none());
+    }
+
+    #[test]
+    fn test_parse_sync_direction_defaults_to_both() {
+        assert_eq!(parse_sync_direction(Some("Pull")), SyncDirection::Pull);
+        assert_eq!(parse_sync_direction(Some("push")), SyncDirection::Push);
+        assert_eq!(parse_sync_direction(None), SyncDirection::Both);
+    }
+
+    #[test]
+    fn test_to_result_input_maps_fields() {
+        let input = to_result_input(TestResultRequest {
+            case_id: 7,
+            status: "Failed".to_string(),
+            duration_seconds: Some(42),
+            notes: Some("  ".to_string()),
+            attachments: vec![ResultAttachmentRequest {
+                name: "log.txt".to_string(),
+                url: "https://files.example.com/log.txt".to_string(),
+            }],
+        });
+        assert_eq!(input.case_id, 7);
+        assert_eq!(input.status, ResultStatus::Failed);
+        assert_eq!(input.elapsed, Some(42));
+        assert!(input.note.is_none());
+        assert_eq!(input.attachments.len(), 1);
+    }
+
+    #[test]
+    fn test_submit_results_validation() {
+        let request: SubmitResultsRequest = serde_json::from_value(serde_json::json!({
+            "results": [{ "caseId": 1, "status": "exploded", "durationSeconds": -5 }]
+        }))
+        .unwrap();
+        let errors = request.validate().unwrap_err();
+        let fields: Vec<String> = crate::validation::violations(&errors)
+            .into_iter()
+            .map(|v| v.field)
+            .collect();
+        assert_eq!(fields, vec!["results[0].durationSeconds", "results[0].status"]);
+    }
+
+    #[test]
+    fn test_collect_automation_tests_merges_sources() {
+        let request = AutomationResultsRequest {
+            junit_xml: Some(r#"<testsuite name="api"><testcase name="health"/></testsuite>"#.to_string()),
+            tests: vec![AutomationTestRequest {
+                name: "manual smoke".to_string(),
+                folder: None,
+                status: "failed".to_string(),
+                elapsed_ms: Some(1200),
+                message: Some("500 on /login".to_string()),
+            }],
+            complete: true,
+        };
+        let tests = collect_automation_tests(request).unwrap();
+        assert_eq!(tests.len(), 2);
+        assert_eq!(tests[0].folder.as_deref(), Some("api"));
+        assert_eq!(tests[1].status, ResultStatus::Failed);
+
+        let empty = AutomationResultsRequest {
+            junit_xml: None,
+            tests: vec![],
+            complete: true,
+        };
+        assert_eq!(collect_automation_tests(empty).unwrap_err().0, StatusCode::BAD_REQUEST);
+    }
+
     #[test]
     fn test_generate_run_name_format() {
         let name = generate_run_name("TEST-456");
@@ -0,0 +1,137 @@
+//! Ticket watchlist API endpoints.
+//!
+//! Watching a ticket records its current state; changes found later by the
+//! watch poller are raised as alerts.
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    routing::{get, post},
+    Extension, Json, Router,
+};
+use tracing::{info, warn};
+
+use qa_pms_core::error::ApiError;
+
+use crate::app::AppState;
+use crate::audit::AuditChange;
+use crate::auth::CurrentUser;
+use crate::routes::tickets::get_jira_client;
+use crate::ticket_watch::{TicketState, TicketWatch};
+
+type ApiResult<T> = Result<T, ApiError>;
+
+/// Create the ticket watchlist router.
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/api/v1/tickets/watched", get(list_watches))
+        .route(
+            "/api/v1/tickets/:key/watch",
+            post(watch_ticket).delete(unwatch_ticket),
+        )
+}
+
+fn db_error(e: sqlx::Error) -> ApiError {
+    ApiError::Internal(e.into())
+}
+
+/// List the tickets the user watches.
+#[utoipa::path(
+    get,
+    path = "/api/v1/tickets/watched",
+    responses(
+        (status = 200, description = "Watched tickets", body = Vec<TicketWatch>)
+    ),
+    tag = "Tickets"
+)]
+pub async fn list_watches(
+    State(state): State<AppState>,
+    user: CurrentUser,
+) -> ApiResult<Json<Vec<TicketWatch>>> {
+    let watches = state
+        .ticket_watches
+        .list(&user.owner(None))
+        .await
+        .map_err(db_error)?;
+    Ok(Json(watches))
+}
+
+/// Watch a ticket for status changes and new comments.
+///
+/// Watching a ticket that is already watched returns the existing watch.
+#[utoipa::path(
+    post,
+    path = "/api/v1/tickets/{key}/watch",
+    params(("key" = String, Path, description = "Ticket key (e.g., PROJ-123)")),
+    responses(
+        (status = 201, description = "Ticket watched", body = TicketWatch),
+        (status = 200, description = "Ticket was already watched", body = TicketWatch),
+        (status = 401, description = "Not authenticated with Jira"),
+        (status = 404, description = "Ticket not found"),
+        (status = 503, description = "Jira service unavailable"),
+    ),
+    tag = "Tickets"
+)]
+pub async fn watch_ticket(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Path(key): Path<String>,
+) -> ApiResult<(StatusCode, Extension<AuditChange>, Json<TicketWatch>)> {
+    let jira_client = get_jira_client(&state, &user.tenant).await?;
+    let ticket = jira_client.get_ticket(&key).await.map_err(|e| {
+        if e.to_string().contains("not found") {
+            ApiError::NotFound(format!("Ticket not found: {key}"))
+        } else {
+            warn!(error = %e, key = %key, "Failed to fetch ticket from Jira");
+            ApiError::ServiceUnavailable(format!("Jira error: {e}"))
+        }
+    })?;
+
+    let (watch, created) = state
+        .ticket_watches
+        .watch(&user.owner(None), &ticket.key, &TicketState::of(&ticket))
+        .await
+        .map_err(db_error)?;
+    let change = AuditChange::new(&ticket.key).after(&watch);
+    if !created {
+        return Ok((StatusCode::OK, change.into_extension(), Json(watch)));
+    }
+    info!(key = %ticket.key, "Ticket watched");
+    Ok((StatusCode::CREATED, change.into_extension(), Json(watch)))
+}
+
+/// Stop watching a ticket.
+#[utoipa::path(
+    delete,
+    path = "/api/v1/tickets/{key}/watch",
+    params(("key" = String, Path, description = "Ticket key (e.g., PROJ-123)")),
+    responses(
+        (status = 204, description = "Watch removed"),
+        (status = 404, description = "Ticket not watched")
+    ),
+    tag = "Tickets"
+)]
+pub async fn unwatch_ticket(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Path(key): Path<String>,
+) -> ApiResult<(StatusCode, Extension<AuditChange>)> {
+    let owner = user.owner(None);
+    let before = state
+        .ticket_watches
+        .list(&owner)
+        .await
+        .map_err(db_error)?
+        .into_iter()
+        .find(|watch| watch.ticket_key == key);
+    if !state
+        .ticket_watches
+        .unwatch(&owner, &key)
+        .await
+        .map_err(db_error)?
+    {
+        return Err(ApiError::NotFound(format!("Ticket {key} is not watched")));
+    }
+    let change = AuditChange::new(&key).before(&before);
+    Ok((StatusCode::NO_CONTENT, change.into_extension()))
+}
@@ -4,32 +4,59 @@
 //! - Listing tickets with filters
 //! - Retrieving ticket details with comments and attachments
 //! - Getting available transitions and transitioning tickets
+//! - Downloading attachments through the server with the stored Jira credentials
 
 use axum::{
+    body::Body,
     extract::{Path, Query, State},
-    http::StatusCode,
+    http::{header, HeaderMap, HeaderName, StatusCode},
+    response::{IntoResponse, Response},
     routing::{get, post},
-    Json, Router,
+    Extension, Json, Router,
 };
 use qa_pms_core::error::ApiError;
-use qa_pms_jira::{JiraTicketsClient, TicketFilters};
+use qa_pms_core::TenantId;
+use qa_pms_core::types::{CursorInfo, CursorQuery};
+use qa_pms_jira::{
+    jql, CacheStats, JiraTicket, JiraTicketsClient, JqlCheck, TicketFilters, Transition,
+};
+use qa_pms_patterns::{PatternRepository, StatusChange};
 use secrecy::ExposeSecret;
+use futures::stream::{self, Stream, StreamExt};
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::sync::Arc;
 use std::time::Instant;
 use tracing::{info, warn};
 use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+use validator::Validate;
 
 use crate::app::AppState;
+use crate::audit::AuditChange;
+use crate::auth::CurrentUser;
+use crate::routes::projects::ProjectQuery;
+use crate::routes::saved_filters::owned_filter;
+use crate::routes::tenant::{tenant_credential, JIRA_API_TOKEN, JIRA_EMAIL, JIRA_URL};
+use crate::search_index::IndexDocument;
+use crate::validation::{not_blank, ValidatedJson};
 
 /// Create the tickets router.
 pub fn router() -> Router<AppState> {
     Router::new()
         .route("/api/v1/tickets", get(list_tickets))
-        .route("/api/v1/tickets/{key}", get(get_ticket))
-        .route("/api/v1/tickets/{key}/transitions", get(get_transitions))
-        .route("/api/v1/tickets/{key}/transition", post(transition_ticket))
+        .route("/api/v1/tickets/cache", get(get_ticket_cache_stats))
+        .route("/api/v1/tickets/jql/validate", post(validate_jql))
+        .route("/api/v1/tickets/:key", get(get_ticket))
+        .route("/api/v1/tickets/:key/transitions", get(get_transitions))
+        .route("/api/v1/tickets/:key/attachments/:id", get(download_attachment))
+        .route("/api/v1/tickets/:key/transition", post(transition_ticket))
+        .route("/api/v1/tickets/transitions/bulk", post(bulk_transition_tickets))
 }
 
+/// Response header telling whether the ticket list came from the cache.
+const X_CACHE: HeaderName = HeaderName::from_static("x-cache");
+
 /// Query parameters for listing tickets.
 #[derive(Debug, Deserialize, IntoParams)]
 #[serde(rename_all = "camelCase")]
@@ -43,12 +70,50 @@ pub struct ListTicketsQuery {
     /// Project key filter
     #[param(example = "MYPROJ")]
     pub project: Option<String>,
-    /// Page number (1-indexed, default: 1)
-    #[param(example = 1)]
-    pub page: Option<u32>,
-    /// Items per page (max 100, default: 20)
-    #[param(example = 20)]
-    pub page_size: Option<u32>,
+    /// Saved filter to start from; the other parameters override its fields
+    pub filter_id: Option<Uuid>,
+    /// Complete JQL query, used instead of all other filters
+    #[param(example = "project = MYPROJ AND labels = regression ORDER BY priority DESC")]
+    pub jql: Option<String>,
+}
+
+impl ListTicketsQuery {
+    /// Whether any filter was given explicitly.
+    fn has_filters(&self) -> bool {
+        self.status.is_some() || self.assignee.is_some() || self.project.is_some()
+    }
+
+    /// Filters for a raw JQL query, which stands alone.
+    fn raw_jql_filters(&self, jql: &str) -> Result<TicketFilters, ApiError> {
+        if self.has_filters() || self.filter_id.is_some() {
+            return Err(ApiError::Validation(
+                "jql cannot be combined with other filters".into(),
+            ));
+        }
+        jql::validate(jql).map_err(|e| ApiError::Validation(e.to_string()))?;
+        Ok(TicketFilters {
+            raw_jql: Some(jql.to_string()),
+            ..TicketFilters::default()
+        })
+    }
+
+    /// Combine the parameters with the filters of a saved filter.
+    fn apply_to(self, mut filters: TicketFilters) -> TicketFilters {
+        if let Some(status) = self.status {
+            filters.statuses = status
+                .split(',')
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+                .collect();
+        }
+        if self.assignee.is_some() {
+            filters.assignee = self.assignee;
+        }
+        if self.project.is_some() {
+            filters.project = self.project;
+        }
+        filters
+    }
 }
 
 /// Response for ticket list endpoint.
@@ -59,19 +124,16 @@ pub struct TicketListResponse {
     pub tickets: Vec<TicketSummary>,
     /// Total number of matching tickets
     pub total: u32,
-    /// Current page number
-    pub page: u32,
-    /// Items per page
-    pub page_size: u32,
-    /// Whether there are more pages
-    pub has_more: bool,
+    /// Cursor pagination information
+    pub page_info: CursorInfo,
     /// Load time in milliseconds (for performance monitoring)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub load_time_ms: Option<u64>,
 }
 
 /// Summary of a ticket for list display.
-#[derive(Debug, Serialize, ToSchema)]
+#[derive(Debug, Serialize, ToSchema, async_graphql::SimpleObject)]
+#[graphql(complex)]
 #[serde(rename_all = "camelCase")]
 pub struct TicketSummary {
     /// Ticket key (e.g., "PROJ-123")
@@ -94,6 +156,25 @@ pub struct TicketSummary {
     pub updated_at: String,
 }
 
+impl From<JiraTicket> for TicketSummary {
+    fn from(t: JiraTicket) -> Self {
+        Self {
+            key: t.key,
+            title: t.fields.summary,
+            status: t.fields.status.name,
+            status_color: t.fields.status.status_category.color_name,
+            priority: t.fields.priority.as_ref().map(|p| p.name.clone()),
+            priority_color: get_priority_color(t.fields.priority.as_ref().map(|p| p.name.as_str())),
+            assignee_name: t.fields.assignee.as_ref().map(|a| a.display_name.clone()),
+            assignee_avatar: t
+                .fields
+                .assignee
+                .and_then(|a| a.avatar_urls.and_then(|av| av.small)),
+            updated_at: t.fields.updated,
+        }
+    }
+}
+
 // ============================================================================
 // Ticket Detail Types (Story 3.3)
 // ============================================================================
@@ -179,7 +260,7 @@ pub struct AttachmentInfo {
     pub size: u64,
     /// Human-readable file size
     pub size_human: String,
-    /// Download URL
+    /// Download URL, proxied through this API
     pub download_url: String,
 }
 
@@ -219,82 +300,224 @@ pub struct TransitionResponse {
     pub new_status: String,
 }
 
+/// Tickets transitioned concurrently during a bulk transition.
+const BULK_TRANSITION_CONCURRENCY: usize = 5;
+
+/// Request body for transitioning several tickets at once.
+#[derive(Debug, Deserialize, ToSchema, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkTransitionRequest {
+    /// Ticket keys, at most 100; duplicates are transitioned once
+    #[validate(length(min = 1, max = 100))]
+    pub keys: Vec<String>,
+    /// Transition or target status name, e.g. "Ready for QA" (case-insensitive)
+    #[validate(custom(function = "not_blank"))]
+    pub transition: String,
+}
+
+/// Outcome of transitioning one ticket in a bulk request.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkTransitionItem {
+    /// Ticket key
+    pub key: String,
+    /// Whether the ticket was transitioned
+    pub success: bool,
+    /// Status the ticket moved to
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub new_status: Option<String>,
+    /// Why the ticket was not transitioned
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Response for a bulk transition.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkTransitionResponse {
+    /// Tickets transitioned
+    pub succeeded: usize,
+    /// Tickets not transitioned
+    pub failed: usize,
+    /// Per-ticket outcomes, in request order
+    pub results: Vec<BulkTransitionItem>,
+}
+
+/// Request body for checking a JQL query.
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct JqlValidationRequest {
+    /// JQL query to check
+    #[schema(example = "project = MYPROJ AND status = \"Ready for QA\"")]
+    #[validate(custom(function = "not_blank"))]
+    pub jql: String,
+}
+
+/// Result of checking a JQL query.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct JqlValidationResponse {
+    /// Whether the query can be used to list tickets
+    pub valid: bool,
+    /// Approximate number of matching tickets, when valid
+    pub count: Option<u64>,
+    /// Problems found by this server or by Jira
+    pub errors: Vec<String>,
+}
+
+/// Ticket search cache counters.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TicketCacheStats {
+    /// Ticket lists answered from the cache
+    pub hits: u64,
+    /// Ticket lists fetched from Jira
+    pub misses: u64,
+    /// Share of lists answered from the cache, 0 when nothing was listed yet
+    pub hit_rate: f64,
+    /// Searches currently cached
+    pub entries: u64,
+    /// Lifetime of a cached search, in seconds (0 when caching is disabled)
+    pub ttl_secs: u64,
+}
+
+impl From<CacheStats> for TicketCacheStats {
+    fn from(stats: CacheStats) -> Self {
+        let total = stats.hits + stats.misses;
+        let hit_rate = if total == 0 {
+            0.0
+        } else {
+            stats.hits as f64 / total as f64
+        };
+        Self {
+            hits: stats.hits,
+            misses: stats.misses,
+            hit_rate,
+            entries: stats.entries,
+            ttl_secs: stats.ttl_secs,
+        }
+    }
+}
+
 /// List tickets with optional filters.
 ///
 /// Returns a paginated list of Jira tickets filtered by status, assignee, and project.
+/// Jira searches are cached for `JIRA_CACHE_TTL_SECS`; the `X-Cache` header
+/// reports `HIT` or `MISS`. Responses carry an `ETag`, and a request whose
+/// `If-None-Match` matches it gets `304 Not Modified` without a body.
 #[utoipa::path(
     get,
     path = "/api/v1/tickets",
-    params(ListTicketsQuery),
+    params(
+        ListTicketsQuery,
+        ProjectQuery,
+        CursorQuery,
+        ("If-None-Match" = Option<String>, Header, description = "ETag of a previously received page")
+    ),
     responses(
-        (status = 200, description = "Ticket list", body = TicketListResponse),
+        (status = 200, description = "Ticket list", body = TicketListResponse,
+            headers(
+                ("ETag" = String, description = "Tag of this page of results"),
+                ("X-Cache" = String, description = "HIT or MISS")
+            )),
+        (status = 304, description = "Page unchanged since the given ETag"),
+        (status = 400, description = "Invalid cursor or JQL query"),
         (status = 401, description = "Not authenticated with Jira"),
+        (status = 404, description = "Saved filter or project not found"),
         (status = 503, description = "Jira service unavailable"),
     ),
     tag = "Tickets"
 )]
 pub async fn list_tickets(
     State(state): State<AppState>,
-    Query(query): Query<ListTicketsQuery>,
-) -> Result<Json<TicketListResponse>, ApiError> {
+    user: CurrentUser,
+    headers: HeaderMap,
+    Query(mut query): Query<ListTicketsQuery>,
+    Query(project): Query<ProjectQuery>,
+    Query(cursor): Query<CursorQuery>,
+) -> Result<Response, ApiError> {
     let start = Instant::now();
 
-    // Get Jira client from setup store
-    let jira_client = get_jira_client(&state).await?;
+    // Jira only pages by offset, so cursors carry the next `startAt`
+    let page = cursor.page_request()?;
+    let start_at = u32::try_from(page.offset())
+        .map_err(|_| ApiError::Validation("Invalid pagination cursor".into()))?;
 
-    // Parse pagination
-    let page = query.page.unwrap_or(1).max(1);
-    let page_size = query.page_size.unwrap_or(20).min(100);
-    let start_at = (page - 1) * page_size;
+    // A registered project selects its Jira key, over any `project` given
+    if let Some(project) = project.resolve(&state).await? {
+        query.project = Some(project.key);
+    }
 
-    // Parse status filters
-    let statuses = query
-        .status
-        .map(|s| s.split(',').map(|s| s.trim().to_string()).collect())
-        .unwrap_or_default();
+    // Get Jira client from setup store
+    let jira_client = get_jira_client(&state, &user.tenant).await?;
 
-    // Build filters
-    let filters = TicketFilters {
-        statuses,
-        assignee: query.assignee,
-        project: query.project,
+    let filters = if let Some(jql) = query.jql.as_deref() {
+        query.raw_jql_filters(jql)?
+    } else {
+        // Start from the requested saved filter, or the user's default when
+        // no filters were given at all
+        let saved = match query.filter_id {
+            Some(id) => Some(owned_filter(&state, &user, id).await?),
+            None if !query.has_filters() => state
+                .saved_filters
+                .default_for(&user.owner(None))
+                .await
+                .map_err(|e| ApiError::Internal(e.into()))?,
+            None => None,
+        };
+        query.apply_to(saved.map(|f| f.ticket_filters()).unwrap_or_default())
     };
 
     info!(
-        page = page,
-        page_size = page_size,
+        start_at = start_at,
+        limit = page.limit,
         has_status_filter = !filters.statuses.is_empty(),
         has_assignee = filters.assignee.is_some(),
         "Fetching tickets from Jira"
     );
 
-    // Fetch tickets
-    let response = jira_client
-        .list_tickets(&filters, start_at, page_size)
+    // Fetch tickets, from the cache when a fresh result exists
+    let search = state
+        .ticket_cache
+        .list_tickets(&jira_client, &filters, start_at, page.limit)
         .await
         .map_err(|e| {
             warn!(error = %e, "Failed to fetch tickets from Jira");
             ApiError::ServiceUnavailable(format!("Jira error: {e}"))
         })?;
+    let cache_headers = [
+        (header::ETAG, search.etag.clone()),
+        (X_CACHE, if search.hit { "HIT" } else { "MISS" }.to_string()),
+    ];
+
+    let not_modified = headers
+        .get(header::IF_NONE_MATCH)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| search.matches(value));
+    if not_modified {
+        return Ok((StatusCode::NOT_MODIFIED, cache_headers).into_response());
+    }
+
+    let response = &search.response;
+    if !search.hit {
+        state
+            .search_index
+            .upsert(response.issues.iter().map(|t| {
+                IndexDocument::ticket(
+                    &t.key,
+                    &t.fields.summary,
+                    adf_to_text(&t.fields.description),
+                    &[],
+                    &t.fields.updated,
+                )
+            }));
+    }
 
     // Map to API response
     let tickets: Vec<TicketSummary> = response
         .issues
-        .into_iter()
-        .map(|t| TicketSummary {
-            key: t.key,
-            title: t.fields.summary,
-            status: t.fields.status.name,
-            status_color: t.fields.status.status_category.color_name,
-            priority: t.fields.priority.as_ref().map(|p| p.name.clone()),
-            priority_color: get_priority_color(t.fields.priority.as_ref().map(|p| p.name.as_str())),
-            assignee_name: t.fields.assignee.as_ref().map(|a| a.display_name.clone()),
-            assignee_avatar: t
-                .fields
-                .assignee
-                .and_then(|a| a.avatar_urls.and_then(|av| av.small)),
-            updated_at: t.fields.updated,
-        })
+        .iter()
+        .cloned()
+        .map(TicketSummary::from)
         .collect();
 
     let duration = start.elapsed();
@@ -313,19 +536,223 @@ pub async fn list_tickets(
         duration_ms = load_time_ms,
         returned = tickets.len(),
         total = response.total,
+        cache_hit = search.hit,
         "Tickets fetched successfully"
     );
 
-    Ok(Json(TicketListResponse {
-        tickets,
-        total: response.total,
-        page,
-        page_size,
-        has_more: start_at + page_size < response.total,
-        load_time_ms: Some(load_time_ms),
+    let has_more = start_at + page.limit < response.total;
+    let result = page.finish_offset(tickets, has_more);
+
+    Ok((
+        cache_headers,
+        Json(TicketListResponse {
+            tickets: result.data,
+            total: response.total,
+            page_info: result.page_info,
+            load_time_ms: Some(load_time_ms),
+        }),
+    )
+        .into_response())
+}
+
+/// Transition several tickets at once.
+///
+/// Each ticket is moved with the transition whose name, or whose target
+/// status name, matches `transition`. Tickets are processed concurrently and
+/// one failing ticket does not stop the others; each transition is retried
+/// per NFR-REL-03.
+#[utoipa::path(
+    post,
+    path = "/api/v1/tickets/transitions/bulk",
+    request_body = BulkTransitionRequest,
+    responses(
+        (status = 200, description = "Per-ticket outcomes", body = BulkTransitionResponse),
+        (status = 400, description = "No tickets, too many tickets or blank transition"),
+        (status = 401, description = "Not authenticated with Jira"),
+    ),
+    tag = "Tickets"
+)]
+pub async fn bulk_transition_tickets(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    ValidatedJson(req): ValidatedJson<BulkTransitionRequest>,
+) -> Result<(Extension<AuditChange>, Json<BulkTransitionResponse>), ApiError> {
+    let jira_client = get_jira_client(&state, &user.tenant).await?;
+
+    let mut seen = HashSet::new();
+    let keys: Vec<String> = req
+        .keys
+        .iter()
+        .map(|key| key.trim().to_string())
+        .filter(|key| !key.is_empty() && seen.insert(key.clone()))
+        .collect();
+
+    info!(
+        count = keys.len(),
+        transition = %req.transition,
+        "Bulk transitioning tickets"
+    );
+
+    let results: Vec<BulkTransitionItem> = stream::iter(keys)
+        .map(|key| {
+            let jira_client = &jira_client;
+            let transition = req.transition.as_str();
+            async move {
+                match transition_by_name(jira_client, &key, transition).await {
+                    Ok(new_status) => BulkTransitionItem {
+                        key,
+                        success: true,
+                        new_status: Some(new_status),
+                        error: None,
+                    },
+                    Err(error) => {
+                        warn!(key = %key, error = %error, "Bulk transition failed for ticket");
+                        BulkTransitionItem {
+                            key,
+                            success: false,
+                            new_status: None,
+                            error: Some(error),
+                        }
+                    }
+                }
+            }
+        })
+        .buffered(BULK_TRANSITION_CONCURRENCY)
+        .collect()
+        .await;
+
+    let succeeded = results.iter().filter(|r| r.success).count();
+    if succeeded > 0 {
+        state.ticket_cache.invalidate();
+    }
+    for result in &results {
+        if let Some(new_status) = &result.new_status {
+            record_status_change(&state, StatusChange::new(&result.key, None, new_status)).await;
+        }
+    }
+
+    info!(
+        succeeded,
+        failed = results.len() - succeeded,
+        "Bulk transition completed"
+    );
+
+    let response = BulkTransitionResponse {
+        succeeded,
+        failed: results.len() - succeeded,
+        results,
+    };
+    // Spans several tickets, so the change has no single entity ID
+    let change = AuditChange::default().after(&response);
+    Ok((change.into_extension(), Json(response)))
+}
+
+/// Move one ticket with the transition named `name`, returning its new status.
+async fn transition_by_name(
+    jira_client: &JiraTicketsClient,
+    key: &str,
+    name: &str,
+) -> Result<String, String> {
+    let transitions = jira_client
+        .get_transitions(key)
+        .await
+        .map_err(|e| e.to_string())?;
+    let target = find_transition(&transitions, name).ok_or_else(|| {
+        format!(
+            "No transition to '{name}'. Available: {}",
+            transitions
+                .iter()
+                .map(|t| t.name.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    })?;
+
+    jira_client
+        .transition_ticket(key, &target.id)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(target.to.name.clone())
+}
+
+/// Transition named `name`, preferring a transition name over a status name.
+fn find_transition<'a>(transitions: &'a [Transition], name: &str) -> Option<&'a Transition> {
+    let name = name.trim();
+    transitions
+        .iter()
+        .find(|t| t.name.eq_ignore_ascii_case(name))
+        .or_else(|| {
+            transitions
+                .iter()
+                .find(|t| t.to.name.eq_ignore_ascii_case(name))
+        })
+}
+
+/// Check a JQL query without listing tickets.
+///
+/// The query is first checked by this server, as for the `jql` parameter of
+/// the ticket list, then dry-run against Jira, which only counts the
+/// matching tickets.
+#[utoipa::path(
+    post,
+    path = "/api/v1/tickets/jql/validate",
+    request_body = JqlValidationRequest,
+    responses(
+        (status = 200, description = "Validation result", body = JqlValidationResponse),
+        (status = 401, description = "Not authenticated with Jira"),
+        (status = 422, description = "Invalid request", body = qa_pms_core::error::ErrorResponse),
+        (status = 503, description = "Jira service unavailable"),
+    ),
+    tag = "Tickets"
+)]
+pub async fn validate_jql(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    ValidatedJson(req): ValidatedJson<JqlValidationRequest>,
+) -> Result<Json<JqlValidationResponse>, ApiError> {
+    if let Err(e) = jql::validate(&req.jql) {
+        return Ok(Json(JqlValidationResponse {
+            valid: false,
+            count: None,
+            errors: vec![e.to_string()],
+        }));
+    }
+
+    let jira_client = get_jira_client(&state, &user.tenant).await?;
+    let check = jira_client.check_jql(req.jql.trim()).await.map_err(|e| {
+        warn!(error = %e, "Failed to check JQL with Jira");
+        ApiError::ServiceUnavailable(format!("Jira error: {e}"))
+    })?;
+
+    Ok(Json(match check {
+        JqlCheck::Valid { count } => JqlValidationResponse {
+            valid: true,
+            count: Some(count),
+            errors: Vec::new(),
+        },
+        JqlCheck::Invalid { errors } => JqlValidationResponse {
+            valid: false,
+            count: None,
+            errors,
+        },
     }))
 }
 
+/// Get ticket search cache statistics.
+///
+/// Counts are kept since the server started.
+#[utoipa::path(
+    get,
+    path = "/api/v1/tickets/cache",
+    responses(
+        (status = 200, description = "Cache statistics", body = TicketCacheStats),
+    ),
+    tag = "Tickets"
+)]
+pub async fn get_ticket_cache_stats(State(state): State<AppState>) -> Json<TicketCacheStats> {
+    Json(state.ticket_cache.stats().into())
+}
+
 /// Get ticket details by key.
 ///
 /// Returns full ticket information including description, comments, and attachments.
@@ -345,12 +772,13 @@ pub async fn list_tickets(
 )]
 pub async fn get_ticket(
     State(state): State<AppState>,
+    user: CurrentUser,
     Path(key): Path<String>,
 ) -> Result<Json<TicketDetailResponse>, ApiError> {
     let start = Instant::now();
 
     // Get Jira client from setup store
-    let jira_client = get_jira_client(&state).await?;
+    let jira_client = get_jira_client(&state, &user.tenant).await?;
 
     info!(key = %key, "Fetching ticket details from Jira");
 
@@ -370,6 +798,14 @@ pub async fn get_ticket(
     let description_raw = adf_to_text(&ticket.fields.description);
     let description_html = adf_to_html(&ticket.fields.description);
 
+    state.search_index.upsert([IndexDocument::ticket(
+        &ticket.key,
+        &ticket.fields.summary,
+        description_raw.clone(),
+        &ticket.fields.labels,
+        &ticket.fields.updated,
+    )]);
+
     // Detect Gherkin syntax in description
     let has_gherkin = description_raw
         .as_ref()
@@ -408,12 +844,12 @@ pub async fn get_ticket(
         .unwrap_or_default()
         .into_iter()
         .map(|a| AttachmentInfo {
-            id: a.id,
             filename: a.filename,
             mime_type: a.mime_type,
             size: a.size,
             size_human: humanize_bytes(a.size),
-            download_url: a.content,
+            download_url: format!("/api/v1/tickets/{key}/attachments/{}", a.id),
+            id: a.id,
         })
         .collect();
 
@@ -487,10 +923,11 @@ pub async fn get_ticket(
 )]
 pub async fn get_transitions(
     State(state): State<AppState>,
+    user: CurrentUser,
     Path(key): Path<String>,
 ) -> Result<Json<Vec<TransitionInfo>>, ApiError> {
     // Get Jira client from setup store
-    let jira_client = get_jira_client(&state).await?;
+    let jira_client = get_jira_client(&state, &user.tenant).await?;
 
     info!(key = %key, "Fetching available transitions from Jira");
 
@@ -526,6 +963,151 @@ pub async fn get_transitions(
     Ok(Json(transition_infos))
 }
 
+/// Download a ticket attachment.
+///
+/// Streams the file from Jira with the server's credentials, so browsers
+/// without a Jira session can open it. Attachments larger than the
+/// configured limit or of a content type outside the allowlist are refused.
+#[utoipa::path(
+    get,
+    path = "/api/v1/tickets/{key}/attachments/{id}",
+    params(
+        ("key" = String, Path, description = "Jira ticket key (e.g., PROJ-123)"),
+        ("id" = String, Path, description = "Attachment ID")
+    ),
+    responses(
+        (status = 200, description = "Attachment content", content_type = "application/octet-stream"),
+        (status = 400, description = "Invalid attachment ID"),
+        (status = 401, description = "Not authenticated with Jira"),
+        (status = 404, description = "Ticket or attachment not found"),
+        (status = 413, description = "Attachment exceeds the size limit"),
+        (status = 415, description = "Attachment content type not allowed"),
+        (status = 503, description = "Jira service unavailable"),
+    ),
+    tag = "Tickets"
+)]
+pub async fn download_attachment(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Path((key, id)): Path<(String, String)>,
+) -> Result<Response, ApiError> {
+    if id.is_empty() || !id.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(ApiError::Validation("Attachment ID must be numeric".into()));
+    }
+
+    let jira_client = get_jira_client(&state, &user.tenant).await?;
+
+    // Look the attachment up on the ticket, so only attachments of the
+    // requested ticket can be fetched
+    let attachments = jira_client.get_attachments(&key).await.map_err(|e| {
+        if e.to_string().contains("not found") {
+            ApiError::NotFound(format!("Ticket not found: {key}"))
+        } else {
+            warn!(error = %e, key = %key, "Failed to fetch attachments from Jira");
+            ApiError::ServiceUnavailable(format!("Jira error: {e}"))
+        }
+    })?;
+    let attachment = attachments
+        .into_iter()
+        .find(|a| a.id == id)
+        .ok_or_else(|| ApiError::NotFound(format!("Attachment {id} not found on {key}")))?;
+
+    let limits = &state.settings.attachments;
+    if !limits.allows_type(&attachment.mime_type) {
+        return Err(ApiError::UnsupportedMediaType(format!(
+            "Attachments of type {} cannot be downloaded",
+            attachment.mime_type
+        )));
+    }
+    if attachment.size > limits.max_bytes {
+        return Err(ApiError::PayloadTooLarge(format!(
+            "Attachment is {}, the limit is {}",
+            humanize_bytes(attachment.size),
+            humanize_bytes(limits.max_bytes)
+        )));
+    }
+
+    let response = jira_client.download_attachment(&id).await.map_err(|e| {
+        if e.to_string().contains("not found") {
+            ApiError::NotFound(format!("Attachment {id} not found on {key}"))
+        } else {
+            warn!(error = %e, key = %key, attachment_id = %id, "Attachment download failed");
+            ApiError::ServiceUnavailable(format!("Jira error: {e}"))
+        }
+    })?;
+    if response.content_length().is_some_and(|len| len > limits.max_bytes) {
+        return Err(ApiError::PayloadTooLarge(format!(
+            "Attachment exceeds the limit of {}",
+            humanize_bytes(limits.max_bytes)
+        )));
+    }
+
+    info!(key = %key, attachment_id = %id, size = attachment.size, "Proxying attachment download");
+
+    let mut headers = HeaderMap::new();
+    if let Ok(value) = attachment.mime_type.parse() {
+        headers.insert(header::CONTENT_TYPE, value);
+    }
+    if let Some(len) = response.content_length() {
+        headers.insert(header::CONTENT_LENGTH, len.into());
+    }
+    if let Ok(value) = content_disposition(&attachment.filename).parse() {
+        headers.insert(header::CONTENT_DISPOSITION, value);
+    }
+    headers.insert(header::X_CONTENT_TYPE_OPTIONS, header::HeaderValue::from_static("nosniff"));
+
+    let body = Body::from_stream(limit_body(response.bytes_stream(), limits.max_bytes));
+    Ok((headers, body).into_response())
+}
+
+/// `Content-Disposition` for a download, with an ASCII fallback name and
+/// the exact name percent-encoded (RFC 6266).
+fn content_disposition(filename: &str) -> String {
+    let fallback: String = filename
+        .chars()
+        .map(|c| {
+            if c == ' ' || (c.is_ascii_graphic() && !matches!(c, '"' | '\\')) {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    let encoded: String = filename
+        .bytes()
+        .map(|b| {
+            if b.is_ascii_alphanumeric() || b"!#$&+-.^_`|~".contains(&b) {
+                char::from(b).to_string()
+            } else {
+                format!("%{b:02X}")
+            }
+        })
+        .collect();
+    format!("attachment; filename=\"{fallback}\"; filename*=UTF-8''{encoded}")
+}
+
+/// Pass a body through, failing once more than `max_bytes` have been sent.
+///
+/// Guards against content larger than Jira's metadata announced.
+fn limit_body<S, B, E>(body: S, max_bytes: u64) -> impl Stream<Item = Result<B, std::io::Error>>
+where
+    S: Stream<Item = Result<B, E>>,
+    B: AsRef<[u8]>,
+    E: Into<Box<dyn std::error::Error + Send + Sync>>,
+{
+    body.scan(0u64, move |sent, chunk| {
+        let chunk = chunk.map_err(std::io::Error::other).and_then(|bytes| {
+            *sent += bytes.as_ref().len() as u64;
+            if *sent > max_bytes {
+                Err(std::io::Error::other("attachment exceeds the size limit"))
+            } else {
+                Ok(bytes)
+            }
+        });
+        futures::future::ready(Some(chunk))
+    })
+}
+
 /// Transition a ticket to a new status.
 ///
 /// Performs the specified transition on the ticket, moving it to a new status.
@@ -548,11 +1130,12 @@ pub async fn get_transitions(
 )]
 pub async fn transition_ticket(
     State(state): State<AppState>,
+    user: CurrentUser,
     Path(key): Path<String>,
     Json(req): Json<TransitionRequest>,
-) -> Result<(StatusCode, Json<TransitionResponse>), ApiError> {
+) -> Result<(StatusCode, Extension<AuditChange>, Json<TransitionResponse>), ApiError> {
     // Get Jira client from setup store
-    let jira_client = get_jira_client(&state).await?;
+    let jira_client = get_jira_client(&state, &user.tenant).await?;
 
     info!(
         key = %key,
@@ -586,6 +1169,11 @@ pub async fn transition_ticket(
         })?;
 
     let new_status = target_transition.to.name.clone();
+    let change = AuditChange::new(&key).after(&serde_json::json!({
+        "transitionId": req.transition_id,
+        "transition": target_transition.name,
+        "status": new_status,
+    }));
 
     // Perform the transition
     jira_client
@@ -604,6 +1192,10 @@ pub async fn transition_ticket(
             }
         })?;
 
+    // Cached lists may still show the old status
+    state.ticket_cache.invalidate();
+    record_status_change(&state, StatusChange::new(&key, None, &new_status)).await;
+
     info!(
         key = %key,
         new_status = %new_status,
@@ -612,6 +1204,7 @@ pub async fn transition_ticket(
 
     Ok((
         StatusCode::OK,
+        change.into_extension(),
         Json(TransitionResponse {
             message: format!("Ticket {key} transitioned to {new_status}"),
             new_status,
@@ -619,6 +1212,16 @@ pub async fn transition_ticket(
     ))
 }
 
+/// Record a ticket status change for flaky verification detection.
+///
+/// The ticket has already moved by then, so a failure is only logged.
+pub(crate) async fn record_status_change(state: &AppState, change: StatusChange) {
+    let repo = PatternRepository::new(state.db.clone());
+    if let Err(e) = repo.record_status_change(&change).await {
+        warn!(key = %change.ticket_key, error = %e, "Failed to record ticket status change");
+    }
+}
+
 /// Get priority color based on priority name.
 fn get_priority_color(priority: Option<&str>) -> String {
     match priority {
@@ -640,7 +1243,7 @@ fn detect_gherkin(text: &str) -> bool {
 }
 
 /// Convert Atlassian Document Format (ADF) to plain text.
-fn adf_to_text(adf: &Option<serde_json::Value>) -> Option<String> {
+pub(crate) fn adf_to_text(adf: &Option<serde_json::Value>) -> Option<String> {
     let doc = adf.as_ref()?;
     let content = doc.get("content")?;
     let mut text = String::new();
@@ -888,7 +1491,7 @@ fn convert_adf_to_html(node: &serde_json::Value, output: &mut String) {
 }
 
 /// Escape HTML special characters.
-fn html_escape(text: &str) -> String {
+pub(crate) fn html_escape(text: &str) -> String {
     text.replace('&', "&amp;")
         .replace('<', "&lt;")
         .replace('>', "&gt;")
@@ -915,17 +1518,45 @@ fn humanize_bytes(bytes: u64) -> String {
 
 /// Get or create Jira client from app state.
 ///
-/// For now, this creates a mock client. In production, it will use
-/// stored OAuth tokens from the setup wizard.
-async fn get_jira_client(state: &AppState) -> Result<JiraTicketsClient, ApiError> {
-    // First, check if we have Jira settings from environment (API Token)
+/// A tenant that stored a Jira API token uses it, with the URL and email
+/// defaulting to the instance settings. Otherwise the instance credentials
+/// are used: environment settings first, then those from the setup wizard.
+pub(crate) async fn get_jira_client(
+    state: &AppState,
+    tenant: &TenantId,
+) -> Result<JiraTicketsClient, ApiError> {
+    if let Some(api_token) = tenant_credential(state, tenant, JIRA_API_TOKEN)? {
+        let settings = state.settings.jira.as_ref();
+        let instance_url = tenant_credential(state, tenant, JIRA_URL)?
+            .map(|url| url.expose_secret().clone())
+            .or_else(|| settings.map(|j| j.instance_url.clone()));
+        let email = tenant_credential(state, tenant, JIRA_EMAIL)?
+            .map(|email| email.expose_secret().clone())
+            .or_else(|| settings.and_then(|j| j.email.clone()));
+        let (Some(instance_url), Some(email)) = (instance_url, email) else {
+            return Err(ApiError::Unauthorized(format!(
+                "Tenant Jira token is set but {JIRA_URL} or {JIRA_EMAIL} is missing"
+            )));
+        };
+        return Ok(JiraTicketsClient::with_api_token(
+            instance_url,
+            email,
+            api_token.expose_secret().clone(),
+        )
+        .with_retry_policy(state.settings.retry.policy())
+        .with_circuit_breaker(Arc::clone(&state.circuit_breakers.jira)));
+    }
+
+    // Then, check if we have Jira settings from environment (API Token)
     if let Some(jira_settings) = state.settings.jira.as_ref() {
         if let (Some(email), Some(api_token)) = (&jira_settings.email, &jira_settings.api_token) {
             return Ok(JiraTicketsClient::with_api_token(
                 jira_settings.instance_url.clone(),
                 email.clone(),
                 api_token.expose_secret().clone(),
-            ));
+            )
+            .with_retry_policy(state.settings.retry.policy())
+            .with_circuit_breaker(Arc::clone(&state.circuit_breakers.jira)));
         }
     }
 
@@ -948,16 +1579,19 @@ async fn get_jira_client(state: &AppState) -> Result<JiraTicketsClient, ApiError
 
     // Prefer API Token if available
     if let (Some(email), Some(api_token)) = (email, api_token) {
-        return Ok(JiraTicketsClient::with_api_token(
-            instance_url,
-            email,
-            api_token,
-        ));
+        return Ok(
+            JiraTicketsClient::with_api_token(instance_url, email, api_token)
+                .with_retry_policy(state.settings.retry.policy())
+                .with_circuit_breaker(Arc::clone(&state.circuit_breakers.jira)),
+        );
     }
 
-    // Fallback to OAuth if available
+    // Fallback to OAuth if available; the token store holds the refreshed token
+    let access_token = state.jira_oauth.access_token().await.or(access_token);
     if let (Some(cloud_id), Some(access_token)) = (cloud_id, access_token) {
-        return Ok(JiraTicketsClient::with_oauth(cloud_id, access_token));
+        return Ok(JiraTicketsClient::with_oauth(cloud_id, access_token)
+            .with_retry_policy(state.settings.retry.policy())
+            .with_circuit_breaker(Arc::clone(&state.circuit_breakers.jira)));
     }
 
     Err(ApiError::Unauthorized(
@@ -969,6 +1603,80 @@ async fn get_jira_client(state: &AppState) -> Result<JiraTicketsClient, ApiError
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_query_params_override_saved_filter() {
+        let saved = TicketFilters {
+            statuses: vec!["Open".to_string()],
+            project: Some("SAVED".to_string()),
+            jql: Some("labels = smoke".to_string()),
+            ..Default::default()
+        };
+        let query = ListTicketsQuery {
+            status: Some("In QA, Done,".to_string()),
+            assignee: None,
+            project: Some("OVERRIDE".to_string()),
+            filter_id: None,
+            jql: None,
+        };
+        assert!(query.has_filters());
+
+        let filters = query.apply_to(saved);
+        assert_eq!(filters.statuses, vec!["In QA", "Done"]);
+        assert_eq!(filters.project.as_deref(), Some("OVERRIDE"));
+        assert_eq!(filters.assignee, None);
+        assert_eq!(filters.jql.as_deref(), Some("labels = smoke"));
+    }
+
+    #[test]
+    fn test_raw_jql_stands_alone() {
+        let query = |project: Option<&str>| ListTicketsQuery {
+            status: None,
+            assignee: None,
+            project: project.map(str::to_string),
+            filter_id: None,
+            jql: None,
+        };
+
+        let filters = query(None).raw_jql_filters("labels = smoke").unwrap();
+        assert_eq!(filters.raw_jql.as_deref(), Some("labels = smoke"));
+        assert!(matches!(
+            query(Some("QA")).raw_jql_filters("labels = smoke"),
+            Err(ApiError::Validation(_))
+        ));
+        assert!(matches!(
+            query(None).raw_jql_filters("updated > lastLogin()"),
+            Err(ApiError::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn test_content_disposition_escapes_filename() {
+        assert_eq!(
+            content_disposition("log file.txt"),
+            "attachment; filename=\"log file.txt\"; filename*=UTF-8''log%20file.txt"
+        );
+        assert_eq!(
+            content_disposition("a\"b\r\nc.png"),
+            "attachment; filename=\"a_b__c.png\"; filename*=UTF-8''a%22b%0D%0Ac.png"
+        );
+        assert_eq!(
+            content_disposition("relatório.pdf"),
+            "attachment; filename=\"relat_rio.pdf\"; filename*=UTF-8''relat%C3%B3rio.pdf"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_limit_body_stops_past_max_bytes() {
+        let chunks = || stream::iter([Ok::<_, std::io::Error>(vec![0u8; 4]), Ok(vec![0u8; 4])]);
+
+        let within: Vec<_> = limit_body(chunks(), 8).collect().await;
+        assert!(within.iter().all(Result::is_ok));
+
+        let over: Vec<_> = limit_body(chunks(), 6).collect().await;
+        assert!(over[0].is_ok());
+        assert!(over[1].is_err());
+    }
+
     #[test]
     fn test_priority_color_highest() {
         assert_eq!(get_priority_color(Some("Highest")), "error");
@@ -1114,4 +1822,57 @@ mod tests {
             Some("<pre><code class=\"language-rust\">fn main() {}</code></pre>".to_string())
         );
     }
+
+    #[test]
+    fn test_cache_stats_hit_rate() {
+        let stats = |hits, misses| CacheStats {
+            hits,
+            misses,
+            entries: 1,
+            ttl_secs: 30,
+        };
+        assert!(TicketCacheStats::from(stats(0, 0)).hit_rate.abs() < f64::EPSILON);
+        let stats = TicketCacheStats::from(stats(3, 1));
+        assert!((stats.hit_rate - 0.75).abs() < f64::EPSILON);
+        assert_eq!(stats.ttl_secs, 30);
+    }
+
+    fn transition(id: &str, name: &str, to: &str) -> Transition {
+        serde_json::from_value(serde_json::json!({
+            "id": id,
+            "name": name,
+            "to": {
+                "id": format!("s{id}"),
+                "name": to,
+                "statusCategory": { "key": "indeterminate", "colorName": "yellow" }
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_find_transition_by_name_or_status() {
+        let transitions = vec![
+            transition("11", "Send to QA", "Ready for QA"),
+            transition("21", "Ready for QA", "QA Backlog"),
+            transition("31", "Done", "Done"),
+        ];
+        // A transition name wins over a status of the same name
+        assert_eq!(find_transition(&transitions, "ready for qa").unwrap().id, "21");
+        assert_eq!(find_transition(&transitions, " QA Backlog ").unwrap().id, "21");
+        assert_eq!(find_transition(&transitions, "done").unwrap().id, "31");
+        assert!(find_transition(&transitions, "Blocked").is_none());
+    }
+
+    #[test]
+    fn test_bulk_transition_request_limits() {
+        let request = |count: usize, transition: &str| BulkTransitionRequest {
+            keys: (0..count).map(|i| format!("PROJ-{i}")).collect(),
+            transition: transition.to_string(),
+        };
+        assert!(request(50, "Ready for QA").validate().is_ok());
+        assert!(request(0, "Ready for QA").validate().is_err());
+        assert!(request(101, "Ready for QA").validate().is_err());
+        assert!(request(1, "  ").validate().is_err());
+    }
 }
@@ -4,27 +4,36 @@
 //! Story 6.7: Added historical time data endpoints.
 
 use axum::{
+    body::Body,
     extract::{Path, Query, State},
-    http::StatusCode,
-    response::IntoResponse,
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
     routing::{get, post},
-    Json, Router,
+    Extension, Json, Router,
 };
+use chrono::{Duration, NaiveDate, Utc};
+use futures::TryStreamExt;
 use serde::{Deserialize, Serialize};
 use tracing::info;
 use utoipa::ToSchema;
 use uuid::Uuid;
 
 use qa_pms_time::{
-    end_session, get_active_session, get_workflow_sessions, pause_session, resume_session,
-    start_session, TimeSession,
+    end_session, get_active_session, get_estimate, get_tenant_session, get_workflow_sessions,
+    heartbeat, pause_session, resume_session, start_session, StepTime, TimeSession, TimeSummary,
     // Story 6.7: Historical aggregates
     get_historical_summary, get_trend_data, get_user_averages, get_undismissed_alerts,
     dismiss_alert as dismiss_gap_alert, HistoricalSummary, TrendPoint, UserAverage, TimeGapAlert,
 };
+use qa_pms_time::export::{csv_stream, xlsx_export, ExportFormat, ExportRange};
 
 use crate::app::AppState;
+use crate::audit::AuditChange;
+use crate::auth::CurrentUser;
+use crate::notifications::{Notification, NotificationKind};
+use crate::routes::workflows::{fetch_instance, fetch_managed_instance, AdminOverrideQuery};
 use qa_pms_core::error::ApiError;
+use qa_pms_core::TenantId;
 
 /// Result type alias for API handlers.
 type ApiResult<T> = Result<T, ApiError>;
@@ -49,12 +58,26 @@ pub fn router() -> Router<AppState> {
         .route("/api/v1/time/sessions/:session_id/resume", post(resume_time_session))
         .route("/api/v1/time/sessions/:workflow_id/active", get(get_active_time_session))
         .route("/api/v1/time/sessions/:workflow_id", get(get_all_time_sessions))
+        .route("/api/v1/time/heartbeat", post(record_heartbeat))
         // Story 6.7: Historical time data endpoints
         .route("/api/v1/time/history/:user_id", get(get_historical_stats))
         .route("/api/v1/time/history/:user_id/trend", get(get_time_trend))
         .route("/api/v1/time/history/:user_id/averages", get(get_averages))
         .route("/api/v1/time/history/:user_id/alerts", get(get_gap_alerts))
         .route("/api/v1/time/alerts/:alert_id/dismiss", post(dismiss_alert))
+        .route("/api/v1/time/export", get(export_time))
+}
+
+// ============================================================================
+// Request Types
+// ============================================================================
+
+/// Heartbeat sent while the user is active on a timed step.
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct HeartbeatRequest {
+    /// Running time session
+    pub session_id: Uuid,
 }
 
 // ============================================================================
@@ -62,7 +85,7 @@ pub fn router() -> Router<AppState> {
 // ============================================================================
 
 /// Time session response.
-#[derive(Debug, Serialize, ToSchema)]
+#[derive(Debug, Serialize, ToSchema, async_graphql::SimpleObject)]
 #[serde(rename_all = "camelCase")]
 pub struct TimeSessionResponse {
     pub id: Uuid,
@@ -70,6 +93,8 @@ pub struct TimeSessionResponse {
     pub step_index: i32,
     pub started_at: String,
     pub paused_at: Option<String>,
+    /// Why the session is paused when the user did not pause it (e.g. "auto-paused")
+    pub pause_reason: Option<String>,
     pub ended_at: Option<String>,
     pub total_seconds: i32,
     pub is_active: bool,
@@ -83,6 +108,7 @@ impl From<TimeSession> for TimeSessionResponse {
             step_index: s.step_index,
             started_at: s.started_at.to_rfc3339(),
             paused_at: s.paused_at.map(|t| t.to_rfc3339()),
+            pause_reason: s.pause_reason,
             ended_at: s.ended_at.map(|t| t.to_rfc3339()),
             total_seconds: s.total_seconds,
             is_active: s.is_active,
@@ -91,13 +117,61 @@ impl From<TimeSession> for TimeSessionResponse {
 }
 
 /// List of time sessions.
-#[derive(Debug, Serialize, ToSchema)]
+#[derive(Debug, Serialize, ToSchema, async_graphql::SimpleObject)]
 #[serde(rename_all = "camelCase")]
 pub struct TimeSessionsResponse {
     pub sessions: Vec<TimeSessionResponse>,
     pub total_seconds: i32,
 }
 
+/// Time spent on a workflow, including its sub-workflows.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TimeSummaryResponse {
+    /// Own tracked time plus sub-workflow time
+    pub total_seconds: i32,
+    /// Part of the total spent in sub-workflows
+    pub sub_workflow_seconds: i32,
+    /// Per-step breakdown, for steps with tracked time
+    pub step_times: Vec<StepTimeResponse>,
+}
+
+/// Time spent on one step.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct StepTimeResponse {
+    pub step_index: i32,
+    /// Time on the step itself plus sub-workflows spawned from it
+    pub actual_seconds: i32,
+    /// Part of the actual time spent in sub-workflows
+    pub sub_workflow_seconds: i32,
+    pub estimated_seconds: Option<i32>,
+    /// How far actual time is above (positive) or below the estimate
+    pub gap_percentage: Option<f32>,
+}
+
+impl From<TimeSummary> for TimeSummaryResponse {
+    fn from(summary: TimeSummary) -> Self {
+        Self {
+            total_seconds: summary.total_seconds,
+            sub_workflow_seconds: summary.sub_workflow_seconds,
+            step_times: summary.step_times.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<StepTime> for StepTimeResponse {
+    fn from(step: StepTime) -> Self {
+        Self {
+            step_index: step.step_index,
+            actual_seconds: step.actual_seconds,
+            sub_workflow_seconds: step.sub_workflow_seconds,
+            estimated_seconds: step.estimated_seconds,
+            gap_percentage: step.gap_percentage,
+        }
+    }
+}
+
 // ============================================================================
 // Handlers
 // ============================================================================
@@ -108,25 +182,34 @@ pub struct TimeSessionsResponse {
     path = "/api/v1/time/sessions/{workflow_id}/start/{step_index}",
     params(
         ("workflow_id" = Uuid, Path, description = "Workflow instance ID"),
-        ("step_index" = i32, Path, description = "Step index")
+        ("step_index" = i32, Path, description = "Step index"),
+        AdminOverrideQuery
     ),
     responses(
         (status = 201, description = "Time session started", body = TimeSessionResponse),
+        (status = 403, description = "Workflow belongs to another user"),
+        (status = 404, description = "Workflow not found"),
         (status = 500, description = "Internal server error")
     ),
     tag = "Time Tracking"
 )]
 pub async fn start_time_session(
     State(state): State<AppState>,
+    user: CurrentUser,
     Path((workflow_id, step_index)): Path<(Uuid, i32)>,
+    Query(admin): Query<AdminOverrideQuery>,
 ) -> ApiResult<impl IntoResponse> {
+    fetch_managed_instance(&state, workflow_id, &user, &admin).await?;
+
     let session = start_session(&state.db, workflow_id, step_index)
         .await
         .map_db_err()?;
 
     info!(workflow_id = %workflow_id, step_index, "Started time session");
 
-    Ok((StatusCode::CREATED, Json(TimeSessionResponse::from(session))))
+    let session = TimeSessionResponse::from(session);
+    let change = AuditChange::new(session.id).after(&session);
+    Ok((StatusCode::CREATED, change.into_extension(), Json(session)))
 }
 
 /// End a time session.
@@ -134,25 +217,107 @@ pub async fn start_time_session(
     post,
     path = "/api/v1/time/sessions/{session_id}/end",
     params(
-        ("session_id" = Uuid, Path, description = "Session ID")
+        ("session_id" = Uuid, Path, description = "Session ID"),
+        AdminOverrideQuery
     ),
     responses(
         (status = 200, description = "Time session ended", body = TimeSessionResponse),
+        (status = 403, description = "Workflow belongs to another user"),
+        (status = 404, description = "Session not found"),
         (status = 500, description = "Internal server error")
     ),
     tag = "Time Tracking"
 )]
 pub async fn end_time_session(
     State(state): State<AppState>,
+    user: CurrentUser,
     Path(session_id): Path<Uuid>,
-) -> ApiResult<Json<TimeSessionResponse>> {
+    Query(admin): Query<AdminOverrideQuery>,
+) -> ApiResult<(Extension<AuditChange>, Json<TimeSessionResponse>)> {
+    let before = fetch_managed_session(&state, session_id, &user, &admin).await?;
+
     let session = end_session(&state.db, session_id)
         .await
         .map_db_err()?;
 
     info!(session_id = %session_id, total_seconds = session.total_seconds, "Ended time session");
 
-    Ok(Json(TimeSessionResponse::from(session)))
+    notify_if_over_estimate(&state, &session).await;
+
+    let session = TimeSessionResponse::from(session);
+    let change = AuditChange::new(session_id)
+        .before(&TimeSessionResponse::from(before))
+        .after(&session);
+    Ok((change.into_extension(), Json(session)))
+}
+
+/// Push a time warning when a finished session exceeded the step estimate.
+///
+/// Best effort: lookup failures are logged and otherwise ignored.
+pub(crate) async fn notify_if_over_estimate(state: &AppState, session: &TimeSession) {
+    let lookup = async {
+        let Some(instance) = qa_pms_workflow::get_instance(&state.db, session.workflow_instance_id).await? else {
+            return Ok(None);
+        };
+        let estimate = get_estimate(&state.db, instance.template_id, session.step_index).await?;
+        Ok::<_, sqlx::Error>(estimate.map(|e| (instance, e.estimated_seconds)))
+    };
+
+    match lookup.await {
+        Ok(Some((instance, estimated_seconds))) if session.total_seconds > estimated_seconds => {
+            state.notifications.publish(Notification::for_workflow_owner(
+                &instance,
+                NotificationKind::TimeWarning,
+                "over_estimate",
+                serde_json::json!({
+                    "workflowId": instance.id,
+                    "ticketId": instance.ticket_id,
+                    "stepIndex": session.step_index,
+                    "totalSeconds": session.total_seconds,
+                    "estimatedSeconds": estimated_seconds,
+                }),
+            ));
+        }
+        Ok(_) => {}
+        Err(e) => tracing::warn!(error = %e, "Failed to check time estimate"),
+    }
+}
+
+/// Fetch a time session whose workflow the caller may change, or fail with
+/// `NotFound` (other tenants) or `Forbidden` (other users).
+async fn fetch_managed_session(
+    state: &AppState,
+    id: Uuid,
+    user: &CurrentUser,
+    admin: &AdminOverrideQuery,
+) -> ApiResult<TimeSession> {
+    let session = get_tenant_session(&state.db, id, user.tenant.as_str())
+        .await
+        .map_db_err()?
+        .ok_or_else(|| ApiError::NotFound(format!("Time session {id}")))?;
+    fetch_managed_instance(state, session.workflow_instance_id, user, admin).await?;
+    Ok(session)
+}
+
+/// Fail with `NotFound` unless the user belongs to the caller's tenant.
+///
+/// The local user has no account, so there is nothing to check.
+async fn ensure_tenant_user(state: &AppState, user_id: Uuid, user: &CurrentUser) -> ApiResult<()> {
+    if user.is_local() {
+        return Ok(());
+    }
+    let (exists,): (bool,) =
+        sqlx::query_as("SELECT EXISTS(SELECT 1 FROM users WHERE id = $1 AND tenant_id = $2)")
+            .bind(user_id)
+            .bind(user.tenant.as_str())
+            .fetch_one(&state.db)
+            .await
+            .map_db_err()?;
+    if exists {
+        Ok(())
+    } else {
+        Err(ApiError::NotFound(format!("User {user_id}")))
+    }
 }
 
 /// Pause a time session.
@@ -160,25 +325,33 @@ pub async fn end_time_session(
     post,
     path = "/api/v1/time/sessions/{session_id}/pause",
     params(
-        ("session_id" = Uuid, Path, description = "Session ID")
+        ("session_id" = Uuid, Path, description = "Session ID"),
+        AdminOverrideQuery
     ),
     responses(
         (status = 200, description = "Time session paused"),
+        (status = 403, description = "Workflow belongs to another user"),
+        (status = 404, description = "Session not found"),
         (status = 500, description = "Internal server error")
     ),
     tag = "Time Tracking"
 )]
 pub async fn pause_time_session(
     State(state): State<AppState>,
+    user: CurrentUser,
     Path(session_id): Path<Uuid>,
-) -> ApiResult<Json<serde_json::Value>> {
+    Query(admin): Query<AdminOverrideQuery>,
+) -> ApiResult<(Extension<AuditChange>, Json<serde_json::Value>)> {
+    let before = fetch_managed_session(&state, session_id, &user, &admin).await?;
+
     pause_session(&state.db, session_id)
         .await
         .map_db_err()?;
 
     info!(session_id = %session_id, "Paused time session");
 
-    Ok(Json(serde_json::json!({ "status": "paused" })))
+    let change = session_change(&state, before, &user).await?;
+    Ok((change.into_extension(), Json(serde_json::json!({ "status": "paused" }))))
 }
 
 /// Resume a paused time session.
@@ -186,25 +359,81 @@ pub async fn pause_time_session(
     post,
     path = "/api/v1/time/sessions/{session_id}/resume",
     params(
-        ("session_id" = Uuid, Path, description = "Session ID")
+        ("session_id" = Uuid, Path, description = "Session ID"),
+        AdminOverrideQuery
     ),
     responses(
         (status = 200, description = "Time session resumed"),
+        (status = 403, description = "Workflow belongs to another user"),
+        (status = 404, description = "Session not found"),
         (status = 500, description = "Internal server error")
     ),
     tag = "Time Tracking"
 )]
 pub async fn resume_time_session(
     State(state): State<AppState>,
+    user: CurrentUser,
     Path(session_id): Path<Uuid>,
-) -> ApiResult<Json<serde_json::Value>> {
+    Query(admin): Query<AdminOverrideQuery>,
+) -> ApiResult<(Extension<AuditChange>, Json<serde_json::Value>)> {
+    let before = fetch_managed_session(&state, session_id, &user, &admin).await?;
+
     resume_session(&state.db, session_id)
         .await
         .map_db_err()?;
 
     info!(session_id = %session_id, "Resumed time session");
 
-    Ok(Json(serde_json::json!({ "status": "resumed" })))
+    let change = session_change(&state, before, &user).await?;
+    Ok((change.into_extension(), Json(serde_json::json!({ "status": "resumed" }))))
+}
+
+/// Audit change of a session, from `before` to its stored state.
+async fn session_change(
+    state: &AppState,
+    before: TimeSession,
+    user: &CurrentUser,
+) -> ApiResult<AuditChange> {
+    let after = get_tenant_session(&state.db, before.id, user.tenant.as_str())
+        .await
+        .map_db_err()?
+        .map(TimeSessionResponse::from);
+    Ok(AuditChange::new(before.id)
+        .before(&TimeSessionResponse::from(before))
+        .after(&after))
+}
+
+/// Record activity on a running time session.
+///
+/// Sessions without a heartbeat for the configured idle period are
+/// auto-paused; a heartbeat on an auto-paused session resumes it.
+#[utoipa::path(
+    post,
+    path = "/api/v1/time/heartbeat",
+    params(AdminOverrideQuery),
+    request_body = HeartbeatRequest,
+    responses(
+        (status = 200, description = "Heartbeat recorded", body = TimeSessionResponse),
+        (status = 403, description = "Workflow belongs to another user"),
+        (status = 404, description = "Session not found or already ended"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Time Tracking"
+)]
+pub async fn record_heartbeat(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Query(admin): Query<AdminOverrideQuery>,
+    Json(request): Json<HeartbeatRequest>,
+) -> ApiResult<Json<TimeSessionResponse>> {
+    fetch_managed_session(&state, request.session_id, &user, &admin).await?;
+
+    let session = heartbeat(&state.db, request.session_id)
+        .await
+        .map_db_err()?
+        .ok_or_else(|| ApiError::NotFound(format!("Running time session {}", request.session_id)))?;
+
+    Ok(Json(TimeSessionResponse::from(session)))
 }
 
 /// Get active time session for a workflow.
@@ -216,14 +445,18 @@ pub async fn resume_time_session(
     ),
     responses(
         (status = 200, description = "Active time session", body = Option<TimeSessionResponse>),
+        (status = 404, description = "Workflow not found"),
         (status = 500, description = "Internal server error")
     ),
     tag = "Time Tracking"
 )]
 pub async fn get_active_time_session(
     State(state): State<AppState>,
+    user: CurrentUser,
     Path(workflow_id): Path<Uuid>,
 ) -> ApiResult<Json<Option<TimeSessionResponse>>> {
+    fetch_instance(&state, workflow_id, &user).await?;
+
     let session = get_active_session(&state.db, workflow_id)
         .await
         .map_db_err()?;
@@ -240,14 +473,18 @@ pub async fn get_active_time_session(
     ),
     responses(
         (status = 200, description = "All time sessions", body = TimeSessionsResponse),
+        (status = 404, description = "Workflow not found"),
         (status = 500, description = "Internal server error")
     ),
     tag = "Time Tracking"
 )]
 pub async fn get_all_time_sessions(
     State(state): State<AppState>,
+    user: CurrentUser,
     Path(workflow_id): Path<Uuid>,
 ) -> ApiResult<Json<TimeSessionsResponse>> {
+    fetch_instance(&state, workflow_id, &user).await?;
+
     let sessions = get_workflow_sessions(&state.db, workflow_id)
         .await
         .map_db_err()?;
@@ -446,15 +683,19 @@ impl From<TimeGapAlert> for GapAlertResponse {
     ),
     responses(
         (status = 200, description = "Historical stats", body = HistoricalStatsResponse),
+        (status = 404, description = "User not found"),
         (status = 500, description = "Internal server error")
     ),
     tag = "Time Tracking"
 )]
 pub async fn get_historical_stats(
     State(state): State<AppState>,
+    user: CurrentUser,
     Path(user_id): Path<Uuid>,
     Query(query): Query<HistoryQuery>,
 ) -> ApiResult<Json<HistoricalStatsResponse>> {
+    ensure_tenant_user(&state, user_id, &user).await?;
+
     let summary = get_historical_summary(&state.db, user_id, query.days)
         .await
         .map_db_err()?;
@@ -474,15 +715,19 @@ pub async fn get_historical_stats(
     ),
     responses(
         (status = 200, description = "Trend data", body = TrendResponse),
+        (status = 404, description = "User not found"),
         (status = 500, description = "Internal server error")
     ),
     tag = "Time Tracking"
 )]
 pub async fn get_time_trend(
     State(state): State<AppState>,
+    user: CurrentUser,
     Path(user_id): Path<Uuid>,
     Query(query): Query<HistoryQuery>,
 ) -> ApiResult<Json<TrendResponse>> {
+    ensure_tenant_user(&state, user_id, &user).await?;
+
     let trend = get_trend_data(&state.db, user_id, query.days)
         .await
         .map_db_err()?;
@@ -501,14 +746,18 @@ pub async fn get_time_trend(
     ),
     responses(
         (status = 200, description = "User averages", body = UserAveragesResponse),
+        (status = 404, description = "User not found"),
         (status = 500, description = "Internal server error")
     ),
     tag = "Time Tracking"
 )]
 pub async fn get_averages(
     State(state): State<AppState>,
+    user: CurrentUser,
     Path(user_id): Path<Uuid>,
 ) -> ApiResult<Json<UserAveragesResponse>> {
+    ensure_tenant_user(&state, user_id, &user).await?;
+
     let averages = get_user_averages(&state.db, user_id)
         .await
         .map_db_err()?;
@@ -527,14 +776,18 @@ pub async fn get_averages(
     ),
     responses(
         (status = 200, description = "Gap alerts", body = GapAlertsResponse),
+        (status = 404, description = "User not found"),
         (status = 500, description = "Internal server error")
     ),
     tag = "Time Tracking"
 )]
 pub async fn get_gap_alerts(
     State(state): State<AppState>,
+    user: CurrentUser,
     Path(user_id): Path<Uuid>,
 ) -> ApiResult<Json<GapAlertsResponse>> {
+    ensure_tenant_user(&state, user_id, &user).await?;
+
     let alerts = get_undismissed_alerts(&state.db, user_id, 50)
         .await
         .map_db_err()?;
@@ -553,19 +806,143 @@ pub async fn get_gap_alerts(
     ),
     responses(
         (status = 200, description = "Alert dismissed"),
+        (status = 404, description = "Alert not found"),
         (status = 500, description = "Internal server error")
     ),
     tag = "Time Tracking"
 )]
 pub async fn dismiss_alert(
     State(state): State<AppState>,
+    user: CurrentUser,
     Path(alert_id): Path<Uuid>,
-) -> ApiResult<Json<serde_json::Value>> {
-    dismiss_gap_alert(&state.db, alert_id)
+) -> ApiResult<(Extension<AuditChange>, Json<serde_json::Value>)> {
+    if !dismiss_gap_alert(&state.db, alert_id, user.tenant.as_str())
         .await
-        .map_db_err()?;
+        .map_db_err()?
+    {
+        return Err(ApiError::NotFound(format!("Gap alert {alert_id}")));
+    }
 
     info!(alert_id = %alert_id, "Dismissed gap alert");
 
-    Ok(Json(serde_json::json!({ "status": "dismissed" })))
+    let change = AuditChange::new(alert_id).after(&serde_json::json!({ "dismissed": true }));
+    Ok((change.into_extension(), Json(serde_json::json!({ "status": "dismissed" }))))
+}
+
+// ============================================================================
+// Time Report Export
+// ============================================================================
+
+/// Query parameters for a time report export.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TimeExportQuery {
+    /// First day, inclusive (default: 30 days before `to`)
+    pub from: Option<NaiveDate>,
+    /// Last day, inclusive (default: today)
+    pub to: Option<NaiveDate>,
+    /// Only this user's workflows
+    pub user_id: Option<String>,
+    /// `csv` or `xlsx` (default: csv)
+    #[serde(default)]
+    pub format: ExportFormat,
+}
+
+impl TimeExportQuery {
+    fn range(self, tenant: &TenantId) -> ApiResult<ExportRange> {
+        let to = self.to.unwrap_or_else(|| Utc::now().date_naive());
+        let from = self.from.unwrap_or(to - Duration::days(30));
+        let user_id = self.user_id.filter(|id| !id.trim().is_empty());
+        let mut range = ExportRange::new(from, to, user_id).map_err(ApiError::Validation)?;
+        range.tenant_id = Some(tenant.as_str().to_string());
+        Ok(range)
+    }
+}
+
+/// Export time spent across workflows, per user, ticket type and day.
+///
+/// Only ended sessions are included, dated by the day (UTC) they started.
+#[utoipa::path(
+    get,
+    path = "/api/v1/time/export",
+    params(
+        ("from" = Option<String>, Query, description = "First day, YYYY-MM-DD (default: 30 days before to)"),
+        ("to" = Option<String>, Query, description = "Last day, YYYY-MM-DD (default: today)"),
+        ("userId" = Option<String>, Query, description = "Only this user's workflows"),
+        ("format" = Option<String>, Query, description = "csv or xlsx (default: csv)")
+    ),
+    responses(
+        (status = 200, description = "Time report", content(
+            (String = "text/csv"),
+            (Vec<u8> = "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet")
+        )),
+        (status = 400, description = "Invalid date range"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Time Tracking"
+)]
+pub async fn export_time(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Query(query): Query<TimeExportQuery>,
+) -> ApiResult<Response> {
+    let format = query.format;
+    let range = query.range(&user.tenant)?;
+    let disposition = format!(
+        "attachment; filename=\"time-{}-{}.{}\"",
+        range.from,
+        range.to,
+        format.extension()
+    );
+
+    info!(from = %range.from, to = %range.to, user_id = ?range.user_id, ?format, "Exporting time report");
+
+    let body = match format {
+        ExportFormat::Csv => Body::from_stream(csv_stream(state.db.clone(), range).inspect_err(|e| {
+            tracing::warn!(error = %e, "Time export failed mid-stream");
+        })),
+        ExportFormat::Xlsx => Body::from(
+            xlsx_export(&state.db, &range)
+                .await
+                .map_err(|e| ApiError::Internal(e.into()))?,
+        ),
+    };
+    Ok((
+        [
+            (header::CONTENT_TYPE, format.content_type().to_string()),
+            (header::CONTENT_DISPOSITION, disposition),
+        ],
+        body,
+    )
+        .into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn query(from: Option<&str>, to: Option<&str>) -> TimeExportQuery {
+        TimeExportQuery {
+            from: from.map(|d| d.parse().unwrap()),
+            to: to.map(|d| d.parse().unwrap()),
+            user_id: Some("  ".to_string()),
+            format: ExportFormat::Csv,
+        }
+    }
+
+    #[test]
+    fn test_export_range_defaults_to_last_30_days() {
+        let range = query(None, Some("2026-03-31")).range(&TenantId::default()).unwrap();
+        assert_eq!(range.from.to_string(), "2026-03-01");
+        assert_eq!(range.user_id, None);
+        assert_eq!(range.tenant_id.as_deref(), Some("default"));
+    }
+
+    #[test]
+    fn test_export_range_rejects_reversed_dates() {
+        assert!(matches!(
+            query(Some("2026-04-01"), Some("2026-03-01")).range(&TenantId::default()),
+            Err(ApiError::Validation(_))
+        ));
+    }
 }
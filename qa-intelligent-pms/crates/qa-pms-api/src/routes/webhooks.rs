@@ -0,0 +1,299 @@
+//! Inbound webhook endpoints.
+//!
+//! Jira pushes issue updates and comments here instead of being polled.
+//! Events for a ticket with an active workflow are forwarded to the
+//! workflow's owner; status transitions also raise an alert notification.
+//! Every status transition is recorded for flaky verification detection.
+
+use axum::{body::Bytes, extract::State, http::HeaderMap, routing::post, Json, Router};
+use secrecy::ExposeSecret;
+use serde::Serialize;
+use serde_json::{json, Value};
+use tracing::{debug, info};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use qa_pms_core::error::ApiError;
+use qa_pms_jira::webhooks::{self, IssueUpdate, JiraWebhookEvent, WebhookError, SIGNATURE_HEADER};
+use qa_pms_patterns::StatusChange;
+use qa_pms_workflow::WorkflowInstance;
+
+use crate::app::AppState;
+use crate::notifications::{Notification, NotificationKind};
+use crate::routes::tickets::record_status_change;
+use crate::routes::workflows::publish_workflow_event;
+
+type ApiResult<T> = Result<T, ApiError>;
+
+/// Create the webhooks router.
+pub fn router() -> Router<AppState> {
+    Router::new().route("/api/v1/webhooks/jira", post(jira_webhook))
+}
+
+/// Outcome of a webhook delivery.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookResponse {
+    /// Issue the event was about, when it was one the framework handles
+    pub issue_key: Option<String>,
+    /// Active workflow the event was forwarded to
+    pub workflow_id: Option<Uuid>,
+}
+
+fn webhook_error(e: WebhookError) -> ApiError {
+    match e {
+        WebhookError::MissingSignature | WebhookError::InvalidSignature => {
+            ApiError::Unauthorized(e.to_string())
+        }
+        WebhookError::InvalidPayload(_) => ApiError::Validation(e.to_string()),
+    }
+}
+
+/// Receive a Jira webhook.
+///
+/// Deliveries must be signed with `JIRA_WEBHOOK_SECRET`. Events other than
+/// issue updates and new comments are acknowledged and ignored.
+#[utoipa::path(
+    post,
+    path = "/api/v1/webhooks/jira",
+    request_body(content = Object, description = "Jira webhook payload"),
+    params(
+        ("X-Hub-Signature" = String, Header, description = "sha256=<hex HMAC-SHA256 of the body>")
+    ),
+    responses(
+        (status = 200, description = "Event accepted", body = WebhookResponse),
+        (status = 400, description = "Malformed payload"),
+        (status = 401, description = "Missing or invalid signature"),
+        (status = 503, description = "Webhook secret not configured")
+    ),
+    tag = "Webhooks"
+)]
+pub async fn jira_webhook(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> ApiResult<Json<WebhookResponse>> {
+    let secret = state
+        .settings
+        .jira
+        .as_ref()
+        .and_then(|jira| jira.webhook_secret.as_ref())
+        .ok_or_else(|| {
+            ApiError::ServiceUnavailable("Jira webhook secret not configured".to_string())
+        })?;
+    let signature = headers
+        .get(SIGNATURE_HEADER)
+        .and_then(|value| value.to_str().ok());
+    webhooks::verify_signature(secret.expose_secret(), &body, signature).map_err(webhook_error)?;
+
+    let Some(event) = webhooks::parse_event(&body).map_err(webhook_error)? else {
+        debug!("Ignoring unhandled Jira webhook event");
+        return Ok(Json(WebhookResponse {
+            issue_key: None,
+            workflow_id: None,
+        }));
+    };
+    let issue_key = event.issue_key().to_string();
+    if let JiraWebhookEvent::IssueUpdated(update) = &event {
+        // Cached ticket lists may show the old fields
+        state.ticket_cache.invalidate();
+        if let Some(change) = status_change(update) {
+            record_status_change(&state, change).await;
+        }
+    }
+
+    let instance = qa_pms_workflow::get_active_workflow(&state.db, &issue_key, None)
+        .await
+        .map_err(|e| ApiError::Internal(e.into()))?;
+    let Some(instance) = instance else {
+        debug!(issue_key = %issue_key, "No active workflow for Jira webhook event");
+        return Ok(Json(WebhookResponse {
+            issue_key: Some(issue_key),
+            workflow_id: None,
+        }));
+    };
+
+    let (name, details) = workflow_event(&event);
+    publish_workflow_event(&state.notifications, &instance, name, details);
+    if let Some(alert) = transition_alert(&event, &instance) {
+        state.notifications.publish(alert);
+    }
+    info!(issue_key = %issue_key, workflow_id = %instance.id, event = name, "Jira webhook forwarded to workflow");
+
+    Ok(Json(WebhookResponse {
+        issue_key: Some(issue_key),
+        workflow_id: Some(instance.id),
+    }))
+}
+
+/// Workflow notification name and details for an event.
+fn workflow_event(event: &JiraWebhookEvent) -> (&'static str, Value) {
+    match event {
+        JiraWebhookEvent::IssueUpdated(update) if update.transition.is_some() => (
+            "ticket_transitioned",
+            json!({
+                "fromStatus": update.transition.as_ref().and_then(|t| t.from.clone()),
+                "toStatus": update.transition.as_ref().map(|t| t.to.clone()),
+                "changedBy": update.changed_by,
+            }),
+        ),
+        JiraWebhookEvent::IssueUpdated(update) => (
+            "ticket_updated",
+            json!({
+                "fields": update.changes.iter().map(|c| c.field.clone()).collect::<Vec<_>>(),
+                "changedBy": update.changed_by,
+            }),
+        ),
+        JiraWebhookEvent::CommentAdded(comment) => (
+            "ticket_commented",
+            json!({
+                "commentId": comment.comment_id,
+                "author": comment.author,
+                "body": comment.body,
+            }),
+        ),
+    }
+}
+
+/// Status change of an update that transitioned the issue, attributed to
+/// its first component.
+fn status_change(update: &IssueUpdate) -> Option<StatusChange> {
+    let transition = update.transition.as_ref()?;
+    Some(StatusChange {
+        ticket_key: update.issue_key.clone(),
+        from_status: transition.from.clone(),
+        to_status: transition.to.clone(),
+        component: update.components.first().cloned(),
+        changed_at: update.timestamp,
+    })
+}
+
+/// Alert for a ticket whose status changed while a workflow is open on it.
+fn transition_alert(event: &JiraWebhookEvent, instance: &WorkflowInstance) -> Option<Notification> {
+    let JiraWebhookEvent::IssueUpdated(update) = event else {
+        return None;
+    };
+    let transition = update.transition.as_ref()?;
+    let message = format!(
+        "{} moved from {} to {} during an open workflow",
+        update.issue_key,
+        transition.from.as_deref().unwrap_or("(none)"),
+        transition.to
+    );
+    Some(Notification::for_workflow_owner(
+        instance,
+        NotificationKind::Alert,
+        "ticket_transitioned",
+        json!({
+            "ticketId": update.issue_key,
+            "workflowId": instance.id,
+            "severity": "info",
+            "title": format!("{} changed status", update.issue_key),
+            "message": message,
+        }),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use qa_pms_jira::webhooks::{CommentAdded, FieldChange, StatusTransition};
+
+    fn instance() -> WorkflowInstance {
+        let now = Utc::now();
+        WorkflowInstance {
+            id: Uuid::new_v4(),
+            template_id: Uuid::new_v4(),
+            template_version: 1,
+            ticket_id: "PROJ-7".to_string(),
+            user_id: "dana".to_string(),
+            tenant_id: "default".to_string(),
+            status: "active".to_string(),
+            current_step: 1,
+            started_at: now,
+            paused_at: None,
+            resumed_at: None,
+            completed_at: None,
+            auto_track: false,
+            archived_at: None,
+            deleted_at: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    fn update(transition: Option<StatusTransition>) -> JiraWebhookEvent {
+        JiraWebhookEvent::IssueUpdated(IssueUpdate {
+            issue_key: "PROJ-7".to_string(),
+            summary: None,
+            components: vec!["Auth".to_string()],
+            changed_by: Some("Sam Dev".to_string()),
+            transition,
+            changes: vec![FieldChange {
+                field: "status".to_string(),
+                from: Some("In Progress".to_string()),
+                to: Some("Done".to_string()),
+            }],
+            timestamp: Utc::now(),
+        })
+    }
+
+    #[test]
+    fn test_transition_raises_alert_for_workflow_owner() {
+        let event = update(Some(StatusTransition {
+            from: Some("In Progress".to_string()),
+            to: "Done".to_string(),
+        }));
+        let (name, details) = workflow_event(&event);
+        assert_eq!(name, "ticket_transitioned");
+        assert_eq!(details["toStatus"], "Done");
+
+        let JiraWebhookEvent::IssueUpdated(issue_update) = &event else {
+            unreachable!();
+        };
+        let change = status_change(issue_update).unwrap();
+        assert_eq!(change.from_status.as_deref(), Some("In Progress"));
+        assert_eq!(change.component.as_deref(), Some("Auth"));
+
+        let alert = transition_alert(&event, &instance()).unwrap();
+        assert_eq!(alert.kind, NotificationKind::Alert);
+        assert_eq!(alert.user_id.as_deref(), Some("dana"));
+        assert!(alert.payload["message"]
+            .as_str()
+            .unwrap()
+            .contains("from In Progress to Done"));
+    }
+
+    #[test]
+    fn test_comments_and_field_edits_are_not_alerts() {
+        let comment = JiraWebhookEvent::CommentAdded(CommentAdded {
+            issue_key: "PROJ-7".to_string(),
+            comment_id: "55".to_string(),
+            author: None,
+            body: "Please retest".to_string(),
+            timestamp: Utc::now(),
+        });
+        assert_eq!(workflow_event(&comment).0, "ticket_commented");
+        assert!(transition_alert(&comment, &instance()).is_none());
+
+        let edit = update(None);
+        assert_eq!(workflow_event(&edit).0, "ticket_updated");
+        if let JiraWebhookEvent::IssueUpdated(issue_update) = &edit {
+            assert!(status_change(issue_update).is_none());
+        }
+        assert!(transition_alert(&edit, &instance()).is_none());
+    }
+
+    #[test]
+    fn test_signature_errors_are_unauthorized() {
+        assert!(matches!(
+            webhook_error(WebhookError::MissingSignature),
+            ApiError::Unauthorized(_)
+        ));
+        assert!(matches!(
+            webhook_error(WebhookError::InvalidPayload("x".to_string())),
+            ApiError::Validation(_)
+        ));
+    }
+}
@@ -4,26 +4,52 @@
 //! Refactored to use unified `ApiError` for cleaner error handling.
 
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
     http::StatusCode,
     routing::{get, post},
-    Json, Router,
+    Extension, Json, Router,
 };
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize};
 use tracing::info;
-use utoipa::ToSchema;
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
+use validator::Validate;
 
 use qa_pms_workflow::{
     cancel_workflow as db_cancel_workflow, complete_step as db_complete_step,
-    complete_workflow as db_complete_workflow, create_instance, get_active_workflow,
-    get_all_templates, get_all_user_active_workflows, get_instance, get_step_results, get_template,
-    pause_workflow as db_pause_workflow, resume_workflow as db_resume_workflow,
-    skip_step as db_skip_step, start_step, StepLink, TemplateSummary, WorkflowStep,
+    complete_workflow as db_complete_workflow, create_instance, create_sub_workflow,
+    create_template as db_create_template, delete_template as db_delete_template, get_active_workflow,
+    archive_instance, delete_instance, get_instance_with_deleted, restore_instance,
+    get_all_templates, get_all_user_active_workflows, get_instance, get_instance_template, get_instances,
+    get_parent_link, get_step_results, get_sub_workflow_tree, get_template,
+    get_template_version, get_template_versions, list_instances, pause_workflow as db_pause_workflow,
+    resolve_next_step, resume_workflow as db_resume_workflow, skip_bypassed_steps,
+    skip_step as db_skip_step, start_step, update_instance_step,
+    update_template as db_update_template, validate_branches, diff_steps, BranchCondition,
+    InstanceFilter, InstanceVisibility, StepBranch, StepChange, StepChangeKind, StepLink, StepMetadata, SubWorkflowLink,
+    TemplateChanges,
+    TemplateDeletion, TemplateSummary, TemplateUpdate, TemplateVersion, WorkflowInstance,
+    WorkflowStep, WorkflowTemplate,
 };
 
 use crate::app::AppState;
-use qa_pms_core::error::ApiError;
+use crate::audit::AuditChange;
+use crate::auth::CurrentUser;
+use crate::notifications::{Notification, NotificationHub, NotificationKind};
+use crate::pattern_scheduler::publish_pattern_alerts;
+use crate::routes::projects::ProjectQuery;
+use crate::routes::time::{notify_if_over_estimate, TimeSummaryResponse};
+use crate::test_run_sync::{runs_for_workflow, TestRunRecord};
+use crate::validation::{not_blank, ValidatedJson};
+use qa_pms_core::error::{ApiError, FieldViolation};
+use qa_pms_jira::adf;
+use qa_pms_core::types::{CursorInfo, CursorQuery, PageRequest};
+use qa_pms_core::TenantId;
+use qa_pms_time::{
+    get_sessions_for_workflows, CalibrationService, StepCalibration, TimeSummary, TrackingService,
+};
 
 /// Result type alias for API handlers.
 type ApiResult<T> = Result<T, ApiError>;
@@ -42,18 +68,41 @@ impl<T> SqlxResultExt<T> for Result<T, sqlx::Error> {
 /// Create the workflows router.
 pub fn router() -> Router<AppState> {
     Router::new()
-        .route("/api/v1/workflows/templates", get(list_templates))
-        .route("/api/v1/workflows/templates/:id", get(get_template_by_id))
-        .route("/api/v1/workflows", post(create_workflow))
-        .route("/api/v1/workflows/:id", get(get_workflow))
+        .route(
+            "/api/v1/workflows/templates",
+            get(list_templates).post(create_template),
+        )
+        .route(
+            "/api/v1/workflows/templates/:id",
+            get(get_template_by_id).put(update_template).delete(delete_template),
+        )
+        .route("/api/v1/workflows/templates/:id/versions", get(list_template_versions))
+        .route(
+            "/api/v1/workflows/templates/:id/versions/:version",
+            get(get_template_version_by_number),
+        )
+        .route("/api/v1/workflows/templates/:id/diff", get(diff_template_versions))
+        .route(
+            "/api/v1/workflows/templates/:id/calibrated-estimates",
+            get(get_calibrated_estimates),
+        )
+        .route("/api/v1/workflows", get(list_workflows).post(create_workflow))
+        .route("/api/v1/workflows/archived", get(list_archived_workflows))
+        .route("/api/v1/workflows/:id", get(get_workflow).delete(delete_workflow))
         .route("/api/v1/workflows/active/:ticket_id", get(get_active_workflow_for_ticket))
         .route("/api/v1/workflows/:id/steps/:step_index/complete", post(complete_step))
         .route("/api/v1/workflows/:id/steps/:step_index/skip", post(skip_step))
+        .route(
+            "/api/v1/workflows/:id/steps/:step_index/sub-workflows",
+            post(spawn_sub_workflow),
+        )
         .route("/api/v1/workflows/:id/pause", post(pause_workflow))
         .route("/api/v1/workflows/:id/resume", post(resume_workflow))
         .route("/api/v1/workflows/:id/complete", post(complete_workflow))
         .route("/api/v1/workflows/:id/summary", get(get_workflow_summary))
         .route("/api/v1/workflows/:id/cancel", post(cancel_workflow))
+        .route("/api/v1/workflows/:id/archive", post(archive_workflow))
+        .route("/api/v1/workflows/:id/restore", post(restore_workflow))
         .route("/api/v1/workflows/user/active", get(get_user_active_workflows))
 }
 
@@ -69,7 +118,7 @@ pub struct TemplatesListResponse {
 }
 
 /// Single template response.
-#[derive(Debug, Serialize, ToSchema)]
+#[derive(Debug, Serialize, ToSchema, async_graphql::SimpleObject)]
 #[serde(rename_all = "camelCase")]
 pub struct TemplateResponse {
     pub id: Uuid,
@@ -79,6 +128,7 @@ pub struct TemplateResponse {
     pub step_count: usize,
     pub estimated_minutes: i32,
     pub is_default: bool,
+    pub version: i32,
 }
 
 impl From<TemplateSummary> for TemplateResponse {
@@ -91,6 +141,7 @@ impl From<TemplateSummary> for TemplateResponse {
             step_count: t.step_count,
             estimated_minutes: t.estimated_minutes,
             is_default: t.is_default,
+            version: t.version,
         }
     }
 }
@@ -105,17 +156,236 @@ pub struct TemplateDetailResponse {
     pub ticket_type: String,
     pub steps: Vec<StepResponse>,
     pub estimated_minutes: i32,
+    /// Estimate corrected by completion history, when there is any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub adjusted_estimated_minutes: Option<i32>,
     pub is_default: bool,
+    pub version: i32,
 }
 
-/// Step response.
+impl From<qa_pms_workflow::WorkflowTemplate> for TemplateDetailResponse {
+    fn from(template: qa_pms_workflow::WorkflowTemplate) -> Self {
+        Self {
+            id: template.id,
+            estimated_minutes: template.total_estimated_minutes(),
+            adjusted_estimated_minutes: None,
+            steps: step_responses(template.steps()),
+            name: template.name,
+            description: template.description,
+            ticket_type: template.ticket_type,
+            is_default: template.is_default,
+            version: template.version,
+        }
+    }
+}
+
+/// Calibrated estimate of a template step.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CalibratedStepResponse {
+    pub index: usize,
+    pub name: String,
+    /// Estimate from the template
+    pub estimated_minutes: i32,
+    /// Completions the calibration is based on
+    pub sample_count: i32,
+    /// Average actual time, when the step has completions
+    pub average_minutes: Option<f64>,
+    /// Multiplier applied to the estimate
+    pub correction_factor: f64,
+    /// Estimate after correction
+    pub calibrated_minutes: i32,
+}
+
+/// Template estimates corrected by completion history.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CalibratedEstimatesResponse {
+    pub template_id: Uuid,
+    pub version: i32,
+    pub estimated_minutes: i32,
+    pub calibrated_minutes: i32,
+    pub steps: Vec<CalibratedStepResponse>,
+}
+
+impl CalibratedEstimatesResponse {
+    fn new(template: &WorkflowTemplate, calibration: Vec<StepCalibration>) -> Self {
+        let steps: Vec<_> = template
+            .steps()
+            .iter()
+            .zip(calibration)
+            .enumerate()
+            .map(|(index, (step, c))| CalibratedStepResponse {
+                index,
+                name: step.name.clone(),
+                estimated_minutes: c.estimated_minutes,
+                sample_count: c.sample_count,
+                average_minutes: c.average_minutes,
+                correction_factor: c.correction_factor,
+                calibrated_minutes: c.calibrated_minutes,
+            })
+            .collect();
+        Self {
+            template_id: template.id,
+            version: template.version,
+            estimated_minutes: template.total_estimated_minutes(),
+            calibrated_minutes: steps.iter().map(|s| s.calibrated_minutes).sum(),
+            steps,
+        }
+    }
+}
+
+/// Earlier definition of a template.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TemplateVersionResponse {
+    pub version: i32,
+    pub name: String,
+    pub description: Option<String>,
+    pub ticket_type: String,
+    pub steps: Vec<StepResponse>,
+    pub estimated_minutes: i32,
+    /// When this version was saved
+    pub created_at: String,
+}
+
+impl From<TemplateVersion> for TemplateVersionResponse {
+    fn from(v: TemplateVersion) -> Self {
+        Self {
+            version: v.version,
+            steps: step_responses(v.steps()),
+            estimated_minutes: v.steps().iter().map(|s| s.estimated_minutes).sum(),
+            name: v.name,
+            description: v.description,
+            ticket_type: v.ticket_type,
+            created_at: v.created_at.to_rfc3339(),
+        }
+    }
+}
+
+/// Version history of a template.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TemplateVersionsResponse {
+    pub template_id: Uuid,
+    pub current_version: i32,
+    /// Earlier versions, newest first
+    pub versions: Vec<TemplateVersionResponse>,
+}
+
+/// Versions to compare.
+#[derive(Debug, Deserialize, IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub struct TemplateDiffQuery {
+    /// Older version
+    pub from: i32,
+    /// Newer version (defaults to the current one)
+    pub to: Option<i32>,
+}
+
+/// A top-level template field that changed.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct FieldChangeResponse {
+    /// Field name (name, description, ticketType)
+    pub field: String,
+    pub before: Option<String>,
+    pub after: Option<String>,
+}
+
+/// How one step differs between the versions.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct StepDiffResponse {
+    /// added, removed, modified or unchanged
+    pub change: String,
+    /// Position in the older version
+    pub from_index: Option<usize>,
+    /// Position in the newer version
+    pub to_index: Option<usize>,
+    pub before: Option<StepResponse>,
+    pub after: Option<StepResponse>,
+}
+
+impl From<StepChange> for StepDiffResponse {
+    fn from(c: StepChange) -> Self {
+        let change = match c.kind {
+            StepChangeKind::Added => "added",
+            StepChangeKind::Removed => "removed",
+            StepChangeKind::Modified => "modified",
+            StepChangeKind::Unchanged => "unchanged",
+        };
+        Self {
+            change: change.to_string(),
+            before: c.from_index.zip(c.before.as_ref()).map(Into::into),
+            after: c.to_index.zip(c.after.as_ref()).map(Into::into),
+            from_index: c.from_index,
+            to_index: c.to_index,
+        }
+    }
+}
+
+/// Differences between two versions of a template.
 #[derive(Debug, Serialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
+pub struct TemplateDiffResponse {
+    pub template_id: Uuid,
+    pub from_version: i32,
+    pub to_version: i32,
+    /// Top-level fields that changed
+    pub changed_fields: Vec<FieldChangeResponse>,
+    /// Every step of both versions with its change
+    pub steps: Vec<StepDiffResponse>,
+    /// Change in total estimated minutes
+    pub estimated_minutes_delta: i32,
+}
+
+fn template_diff(from: &TemplateVersion, to: &TemplateVersion) -> TemplateDiffResponse {
+    let mut changed_fields = Vec::new();
+    for (field, before, after) in [
+        ("name", Some(&from.name), Some(&to.name)),
+        ("description", from.description.as_ref(), to.description.as_ref()),
+        ("ticketType", Some(&from.ticket_type), Some(&to.ticket_type)),
+    ] {
+        if before != after {
+            changed_fields.push(FieldChangeResponse {
+                field: field.to_string(),
+                before: before.cloned(),
+                after: after.cloned(),
+            });
+        }
+    }
+    let minutes = |v: &TemplateVersion| v.steps().iter().map(|s| s.estimated_minutes).sum::<i32>();
+
+    TemplateDiffResponse {
+        template_id: to.template_id,
+        from_version: from.version,
+        to_version: to.version,
+        changed_fields,
+        steps: diff_steps(from.steps(), to.steps())
+            .into_iter()
+            .map(Into::into)
+            .collect(),
+        estimated_minutes_delta: minutes(to) - minutes(from),
+    }
+}
+
+/// Step response.
+#[derive(Debug, Serialize, ToSchema, async_graphql::SimpleObject)]
+#[serde(rename_all = "camelCase")]
 pub struct StepResponse {
     pub index: usize,
     pub name: String,
     pub description: String,
     pub estimated_minutes: i32,
+    /// Conditional jumps taken when the step is completed
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[graphql(skip)]
+    pub branches: Vec<StepBranchRule>,
+}
+
+fn step_responses(steps: &[WorkflowStep]) -> Vec<StepResponse> {
+    steps.iter().enumerate().map(Into::into).collect()
 }
 
 impl From<(usize, &WorkflowStep)> for StepResponse {
@@ -125,6 +395,52 @@ impl From<(usize, &WorkflowStep)> for StepResponse {
             name: step.name.clone(),
             description: step.description.clone(),
             estimated_minutes: step.estimated_minutes,
+            branches: step.branches.iter().map(Into::into).collect(),
+        }
+    }
+}
+
+/// Rule picking the step that follows a completed step.
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct StepBranchRule {
+    /// Condition on the completion metadata; omit for a default branch
+    pub when: Option<BranchConditionRule>,
+    /// Index of the step to continue with; the step count finishes the workflow
+    pub next_step: usize,
+}
+
+/// Metadata test for a branch, e.g. `{"key": "reproduced", "equals": true}`.
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BranchConditionRule {
+    /// Metadata key
+    pub key: String,
+    /// Value the key must hold
+    #[schema(value_type = Object)]
+    pub equals: serde_json::Value,
+}
+
+impl From<&StepBranch> for StepBranchRule {
+    fn from(branch: &StepBranch) -> Self {
+        Self {
+            when: branch.when.as_ref().map(|c| BranchConditionRule {
+                key: c.key.clone(),
+                equals: c.equals.clone(),
+            }),
+            next_step: branch.next_step,
+        }
+    }
+}
+
+impl From<&StepBranchRule> for StepBranch {
+    fn from(rule: &StepBranchRule) -> Self {
+        Self {
+            when: rule.when.as_ref().map(|c| BranchCondition {
+                key: c.key.trim().to_string(),
+                equals: c.equals.clone(),
+            }),
+            next_step: rule.next_step,
         }
     }
 }
@@ -133,15 +449,87 @@ impl From<(usize, &WorkflowStep)> for StepResponse {
 // Workflow Instance Types
 // ============================================================================
 
+/// Step of a template being created or updated.
+#[derive(Debug, Serialize, Deserialize, ToSchema, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct TemplateStepRequest {
+    /// Step name
+    #[validate(custom(function = "not_blank"), length(max = 200))]
+    pub name: String,
+    /// What to do in this step
+    #[serde(default)]
+    pub description: String,
+    /// Estimated time in minutes
+    #[validate(range(min = 1, max = 1440))]
+    pub estimated_minutes: i32,
+    /// Conditional jumps, tried in order when the step is completed
+    #[serde(default)]
+    pub branches: Vec<StepBranchRule>,
+}
+
+/// Definition of a template being created or updated.
+#[derive(Debug, Deserialize, ToSchema, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct TemplateRequest {
+    /// Template name
+    #[validate(custom(function = "not_blank"), length(max = 200))]
+    pub name: String,
+    /// Template description
+    pub description: Option<String>,
+    /// Ticket type the template is for (bug, feature, regression, custom)
+    #[validate(custom(function = "not_blank"))]
+    pub ticket_type: String,
+    /// Steps in order
+    #[validate(length(min = 1, message = "at least one step is required"), nested)]
+    pub steps: Vec<TemplateStepRequest>,
+    /// Version the client last read; updates fail with 409 if it changed since
+    pub expected_version: Option<i32>,
+}
+
+impl TemplateRequest {
+    fn workflow_steps(&self) -> ApiResult<Vec<WorkflowStep>> {
+        let steps: Vec<WorkflowStep> = self
+            .steps
+            .iter()
+            .map(|step| WorkflowStep {
+                name: step.name.trim().to_string(),
+                description: step.description.trim().to_string(),
+                estimated_minutes: step.estimated_minutes,
+                branches: step.branches.iter().map(Into::into).collect(),
+            })
+            .collect();
+        validate_branches(&steps).map_err(|message| {
+            ApiError::Unprocessable(vec![FieldViolation::new("steps", "invalid_branch", message)])
+        })?;
+        Ok(steps)
+    }
+
+    fn description(&self) -> Option<&str> {
+        self.description
+            .as_deref()
+            .map(str::trim)
+            .filter(|d| !d.is_empty())
+    }
+}
+
 /// Request to create a new workflow instance.
-#[derive(Debug, Deserialize, ToSchema)]
+#[derive(Debug, Deserialize, ToSchema, Validate)]
 #[serde(rename_all = "camelCase")]
 pub struct CreateWorkflowRequest {
-    pub template_id: Uuid,
+    /// Template to run; defaults to the ticket's project template for its type
+    pub template_id: Option<Uuid>,
+    #[validate(custom(function = "not_blank"))]
     pub ticket_id: String,
     #[allow(dead_code)]
     pub ticket_title: String,
-    pub user_id: String,
+    /// Ticket type (e.g., "Bug"), used to pick the project's default template
+    pub ticket_type: Option<String>,
+    /// Owner of the workflow; ignored when authentication is enabled
+    #[validate(custom(function = "not_blank"))]
+    pub user_id: Option<String>,
+    /// Time each step automatically while it is current
+    #[serde(default)]
+    pub auto_track: bool,
 }
 
 /// Response after creating a workflow.
@@ -160,6 +548,8 @@ pub struct CreateWorkflowResponse {
 pub struct WorkflowDetailResponse {
     pub id: Uuid,
     pub template_id: Uuid,
+    /// Template version the workflow runs on
+    pub template_version: i32,
     pub template_name: String,
     pub ticket_id: String,
     pub status: String,
@@ -167,11 +557,46 @@ pub struct WorkflowDetailResponse {
     pub steps: Vec<WorkflowStepWithStatus>,
     pub estimated_minutes: i32,
     pub started_at: String,
+    /// Whether steps are timed automatically while current
+    pub auto_track: bool,
+    /// Workflow this one was spawned from, when it is a sub-workflow
+    pub parent_workflow_id: Option<Uuid>,
+    /// Parent step this workflow was spawned from
+    pub parent_step_index: Option<i32>,
+    /// Workflows spawned from this workflow's steps
+    pub sub_workflows: Vec<SubWorkflowResponse>,
+    /// Time spent so far, with sub-workflow time rolled up
+    pub time_summary: TimeSummaryResponse,
 }
 
-/// Step with completion status.
+/// Workflow spawned from a step of another workflow.
 #[derive(Debug, Serialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
+pub struct SubWorkflowResponse {
+    pub id: Uuid,
+    pub template_id: Uuid,
+    pub template_name: String,
+    pub status: String,
+    /// Parent step the workflow was spawned from
+    pub parent_step_index: i32,
+    pub current_step: i32,
+    pub total_steps: usize,
+    /// Tracked time, including its own sub-workflows
+    pub total_seconds: i32,
+    pub started_at: String,
+}
+
+/// Request to spawn a sub-workflow from a step.
+#[derive(Debug, Deserialize, ToSchema, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateSubWorkflowRequest {
+    /// Template the sub-workflow runs (e.g. a regression suite)
+    pub template_id: Uuid,
+}
+
+/// Step with completion status.
+#[derive(Debug, Serialize, ToSchema, async_graphql::SimpleObject)]
+#[serde(rename_all = "camelCase")]
 pub struct WorkflowStepWithStatus {
     pub index: usize,
     pub name: String,
@@ -201,24 +626,87 @@ pub struct WorkflowSummary {
     pub started_at: String,
 }
 
+/// Filters for listing workflows.
+#[derive(Debug, Deserialize, IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkflowListQuery {
+    /// Only workflows started by this user
+    pub user_id: Option<String>,
+    /// Only workflows in this status (active, paused, completed, cancelled)
+    pub status: Option<String>,
+    /// Only workflows for this ticket
+    pub ticket_id: Option<String>,
+}
+
+/// Filters for listing archived workflows.
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchivedWorkflowsQuery {
+    /// List deleted workflows instead of archived ones
+    pub deleted: Option<bool>,
+}
+
+/// Lets an admin act on a workflow owned by someone else.
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub struct AdminOverrideQuery {
+    /// Set to `true` to act as admin on another user's workflow
+    pub as_admin: Option<bool>,
+}
+
+/// Workflow entry in a list.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkflowListItem {
+    pub id: Uuid,
+    pub ticket_id: String,
+    pub user_id: String,
+    pub template_name: String,
+    pub status: String,
+    pub current_step: i32,
+    pub total_steps: usize,
+    pub started_at: String,
+    pub completed_at: Option<String>,
+    pub archived_at: Option<String>,
+    pub deleted_at: Option<String>,
+}
+
+/// Page of workflows.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkflowListResponse {
+    pub workflows: Vec<WorkflowListItem>,
+    pub page_info: CursorInfo,
+}
+
 // ============================================================================
 // Step Completion Types
 // ============================================================================
 
 /// Request to complete a step.
-#[derive(Debug, Deserialize, ToSchema)]
+#[derive(Debug, Deserialize, ToSchema, Validate)]
 #[serde(rename_all = "camelCase")]
 pub struct CompleteStepRequest {
     pub notes: Option<String>,
     #[serde(default)]
+    #[validate(nested)]
     pub links: Vec<StepLinkRequest>,
+    /// Also post the notes and links as a comment on the workflow's Jira ticket
+    #[serde(default)]
+    pub post_to_jira: bool,
+    /// Outcome of the step (e.g. `{"reproduced": false}`), matched against the step's branches
+    #[serde(default)]
+    #[schema(value_type = Object)]
+    pub metadata: StepMetadata,
 }
 
 /// Link to attach to a step.
-#[derive(Debug, Deserialize, Serialize, ToSchema, Clone)]
+#[derive(Debug, Deserialize, Serialize, ToSchema, Clone, Validate)]
 #[serde(rename_all = "camelCase")]
 pub struct StepLinkRequest {
+    #[validate(custom(function = "not_blank"))]
     pub label: String,
+    #[validate(url)]
     pub url: String,
 }
 
@@ -227,8 +715,18 @@ pub struct StepLinkRequest {
 #[serde(rename_all = "camelCase")]
 pub struct StepActionResponse {
     pub workflow_completed: bool,
+    /// Step to continue with, after evaluating the step's branches
     pub next_step: Option<StepResponse>,
     pub current_step_index: i32,
+    /// Steps a branch jumped over; they are marked as skipped
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub bypassed_steps: Vec<usize>,
+    /// Jira comment created from the step notes, when requested
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub jira_comment_id: Option<String>,
+    /// Why the Jira comment could not be posted; the step is completed regardless
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub jira_comment_error: Option<String>,
 }
 
 /// Response for pause/resume operations.
@@ -254,6 +752,43 @@ pub struct WorkflowSummaryResponse {
     pub skipped_steps: usize,
     pub steps: Vec<StepSummary>,
     pub all_notes: Vec<String>,
+    /// Testmo runs created for the workflow, with synced results
+    pub test_runs: Vec<TestRunSummary>,
+}
+
+/// Results of a Testmo run, as last synced.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TestRunSummary {
+    pub run_id: i64,
+    pub name: String,
+    pub passed: i64,
+    pub failed: i64,
+    pub blocked: i64,
+    pub skipped: i64,
+    pub retest: i64,
+    /// Whether the run is closed in Testmo
+    pub is_completed: bool,
+    /// Last sync, absent until results were pulled once
+    pub synced_at: Option<String>,
+    pub created_at: String,
+}
+
+impl From<TestRunRecord> for TestRunSummary {
+    fn from(record: TestRunRecord) -> Self {
+        Self {
+            run_id: record.run_id,
+            name: record.name,
+            passed: record.passed,
+            failed: record.failed,
+            blocked: record.blocked,
+            skipped: record.skipped,
+            retest: record.retest,
+            is_completed: record.is_completed,
+            synced_at: record.synced_at.map(|t| t.to_rfc3339()),
+            created_at: record.created_at.to_rfc3339(),
+        }
+    }
 }
 
 /// Step summary for completed workflow.
@@ -277,22 +812,156 @@ pub struct UserActiveWorkflowsResponse {
 // Helper Functions
 // ============================================================================
 
-/// Fetch template or return `NotFound` error.
+/// Fetch a template that has not been deleted or return `NotFound` error.
 async fn fetch_template(state: &AppState, id: Uuid) -> ApiResult<qa_pms_workflow::WorkflowTemplate> {
     get_template(&state.db, id)
+        .await
+        .map_err(|e| ApiError::Internal(e.into()))?
+        .filter(|template| template.archived_at.is_none())
+        .ok_or_else(|| ApiError::NotFound("Template not found".to_string()))
+}
+
+/// Default template of the ticket's project for its type.
+async fn default_template_id(state: &AppState, ticket_id: &str, ticket_type: Option<&str>) -> ApiResult<Uuid> {
+    let project = state
+        .projects
+        .find_for_ticket(ticket_id)
+        .await
+        .map_err(ApiError::Internal)?;
+    project
+        .and_then(|p| p.default_template(ticket_type))
+        .ok_or_else(|| {
+            ApiError::Validation(format!(
+                "templateId is required: the project of {ticket_id} has no default template for {}",
+                ticket_type.unwrap_or("this ticket type")
+            ))
+        })
+}
+
+/// Fetch the template version an instance runs on or return `NotFound` error.
+async fn fetch_instance_template(
+    state: &AppState,
+    instance: &qa_pms_workflow::WorkflowInstance,
+) -> ApiResult<qa_pms_workflow::WorkflowTemplate> {
+    get_instance_template(&state.db, instance)
         .await
         .map_err(|e| ApiError::Internal(e.into()))?
         .ok_or_else(|| ApiError::NotFound("Template not found".to_string()))
 }
 
-/// Fetch workflow instance or return `NotFound` error.
-async fn fetch_instance(state: &AppState, id: Uuid) -> ApiResult<qa_pms_workflow::WorkflowInstance> {
+/// Fetch a workflow of the caller's tenant or return `NotFound` error.
+pub(crate) async fn fetch_instance(
+    state: &AppState,
+    id: Uuid,
+    user: &CurrentUser,
+) -> ApiResult<qa_pms_workflow::WorkflowInstance> {
     get_instance(&state.db, id)
         .await
         .map_err(|e| ApiError::Internal(e.into()))?
+        .filter(|instance| instance.tenant_id == user.tenant.as_str())
         .ok_or_else(|| ApiError::NotFound("Workflow not found".to_string()))
 }
 
+/// Fetch a workflow the caller may change, or fail with `Forbidden`.
+pub(crate) async fn fetch_managed_instance(
+    state: &AppState,
+    id: Uuid,
+    user: &CurrentUser,
+    admin: &AdminOverrideQuery,
+) -> ApiResult<qa_pms_workflow::WorkflowInstance> {
+    let instance = fetch_instance(state, id, user).await?;
+    ensure_manageable(&instance, user, admin)?;
+    Ok(instance)
+}
+
+/// Fail with `Forbidden` unless the caller may change the workflow.
+fn ensure_manageable(
+    instance: &qa_pms_workflow::WorkflowInstance,
+    user: &CurrentUser,
+    admin: &AdminOverrideQuery,
+) -> ApiResult<()> {
+    let admin_override = admin.as_admin.unwrap_or(false);
+    if !user.can_manage(&instance.user_id, admin_override) {
+        return Err(ApiError::Forbidden("Workflow belongs to another user".to_string()));
+    }
+    if !user.owns(&instance.user_id) {
+        info!(
+            workflow_id = %instance.id,
+            owner = %instance.user_id,
+            admin = %user.email,
+            "Admin acting on another user's workflow"
+        );
+    }
+    Ok(())
+}
+
+/// Publish a workflow lifecycle event to the workflow's owner.
+///
+/// `details` (a JSON object) is merged into the base payload.
+pub(crate) fn publish_workflow_event(
+    hub: &NotificationHub,
+    instance: &qa_pms_workflow::WorkflowInstance,
+    event: &str,
+    details: serde_json::Value,
+) {
+    let mut payload = serde_json::json!({
+        "workflowId": instance.id,
+        "ticketId": instance.ticket_id,
+    });
+    if let (Some(base), serde_json::Value::Object(extra)) = (payload.as_object_mut(), details) {
+        base.extend(extra);
+    }
+    hub.publish(Notification::for_workflow_owner(instance, NotificationKind::Workflow, event, payload));
+}
+
+/// Run pattern detection for a completed workflow in the background.
+///
+/// Detected patterns are turned into alerts and pushed to connected clients;
+/// failures are logged only.
+pub(crate) fn spawn_pattern_detection(pool: sqlx::PgPool, hub: NotificationHub, id: Uuid) {
+    tokio::spawn(async move {
+        let detector = qa_pms_patterns::PatternDetector::new(pool.clone());
+        match detector.analyze_workflow(id).await {
+            Ok(patterns) => {
+                if !patterns.is_empty() {
+                    info!(
+                        workflow_id = %id,
+                        patterns_detected = patterns.len(),
+                        "Pattern detection completed"
+                    );
+                    publish_pattern_alerts(pool, &hub, patterns).await;
+                }
+            }
+            Err(e) => {
+                tracing::warn!(workflow_id = %id, error = %e, "Pattern detection failed");
+            }
+        }
+    });
+}
+
+/// Merge template steps with their recorded results.
+pub(crate) fn steps_with_status(
+    template: &qa_pms_workflow::WorkflowTemplate,
+    step_results: &[qa_pms_workflow::WorkflowStepResult],
+) -> Vec<WorkflowStepWithStatus> {
+    template
+        .steps()
+        .iter()
+        .enumerate()
+        .map(|(i, step)| {
+            let result = step_results.iter().find(|r| r.step_index == i as i32);
+            WorkflowStepWithStatus {
+                index: i,
+                name: step.name.clone(),
+                description: step.description.clone(),
+                estimated_minutes: step.estimated_minutes,
+                status: result.map_or("pending".to_string(), |r| r.status.clone()),
+                notes: result.and_then(|r| r.notes.clone()),
+            }
+        })
+        .collect()
+}
+
 // ============================================================================
 // Handlers - Simplified with ApiError
 // ============================================================================
@@ -336,27 +1005,268 @@ pub async fn get_template_by_id(
     Path(id): Path<Uuid>,
 ) -> ApiResult<Json<TemplateDetailResponse>> {
     let template = fetch_template(&state, id).await?;
-    let estimated_minutes = template.total_estimated_minutes();
-    let steps: Vec<StepResponse> = template
-        .steps()
-        .iter()
-        .enumerate()
-        .map(|(i, s)| (i, s).into())
-        .collect();
+    let adjusted = match calibrate_template(&state, &template).await {
+        Ok(calibration) => calibration
+            .iter()
+            .any(|step| step.sample_count > 0)
+            .then(|| calibration.iter().map(|step| step.calibrated_minutes).sum()),
+        Err(e) => {
+            tracing::warn!(template_id = %id, error = %e, "Failed to calibrate template estimates");
+            None
+        }
+    };
 
     info!(template_id = %id, "Retrieved workflow template");
 
-    Ok(Json(TemplateDetailResponse {
-        id: template.id,
-        name: template.name,
-        description: template.description,
-        ticket_type: template.ticket_type,
-        steps,
-        estimated_minutes,
-        is_default: template.is_default,
+    let mut response = TemplateDetailResponse::from(template);
+    response.adjusted_estimated_minutes = adjusted;
+    Ok(Json(response))
+}
+
+/// Calibrate a template's step estimates against completion history.
+async fn calibrate_template(
+    state: &AppState,
+    template: &WorkflowTemplate,
+) -> Result<Vec<StepCalibration>, sqlx::Error> {
+    let estimates: Vec<i32> = template.steps().iter().map(|s| s.estimated_minutes).collect();
+    CalibrationService::new(state.db.clone())
+        .calibrated_estimates(template.id, &estimates)
+        .await
+}
+
+/// Get a template's step estimates corrected by completion history.
+///
+/// Each step's estimate is scaled by how long the step has actually taken
+/// in completed workflows. Steps with few completions stay close to their
+/// estimate.
+#[utoipa::path(
+    get,
+    path = "/api/v1/workflows/templates/{id}/calibrated-estimates",
+    params(("id" = Uuid, Path, description = "Template ID")),
+    responses(
+        (status = 200, description = "Calibrated estimates", body = CalibratedEstimatesResponse),
+        (status = 404, description = "Template not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Workflows"
+)]
+pub async fn get_calibrated_estimates(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> ApiResult<Json<CalibratedEstimatesResponse>> {
+    let template = fetch_template(&state, id).await?;
+    let calibration = calibrate_template(&state, &template).await.map_db_err()?;
+
+    Ok(Json(CalibratedEstimatesResponse::new(&template, calibration)))
+}
+
+/// Create a custom workflow template.
+#[utoipa::path(
+    post,
+    path = "/api/v1/workflows/templates",
+    request_body = TemplateRequest,
+    responses(
+        (status = 201, description = "Template created", body = TemplateDetailResponse),
+        (status = 422, description = "Invalid template"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Workflows"
+)]
+pub async fn create_template(
+    State(state): State<AppState>,
+    ValidatedJson(request): ValidatedJson<TemplateRequest>,
+) -> ApiResult<(StatusCode, Extension<AuditChange>, Json<TemplateDetailResponse>)> {
+    let template = db_create_template(
+        &state.db,
+        request.name.trim(),
+        request.description(),
+        request.ticket_type.trim(),
+        &request.workflow_steps()?,
+        false,
+    )
+    .await
+    .map_db_err()?;
+
+    info!(template_id = %template.id, name = %template.name, "Created workflow template");
+
+    let created = TemplateDetailResponse::from(template);
+    let change = AuditChange::new(created.id).after(&created);
+    Ok((StatusCode::CREATED, change.into_extension(), Json(created)))
+}
+
+/// Update a workflow template.
+///
+/// Each update creates a new version. Workflows already running keep the
+/// steps of the version they started on.
+#[utoipa::path(
+    put,
+    path = "/api/v1/workflows/templates/{id}",
+    params(("id" = Uuid, Path, description = "Template ID")),
+    request_body = TemplateRequest,
+    responses(
+        (status = 200, description = "Template updated", body = TemplateDetailResponse),
+        (status = 404, description = "Template not found"),
+        (status = 409, description = "Template changed since expectedVersion"),
+        (status = 422, description = "Invalid template"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Workflows"
+)]
+pub async fn update_template(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    ValidatedJson(request): ValidatedJson<TemplateRequest>,
+) -> ApiResult<(Extension<AuditChange>, Json<TemplateDetailResponse>)> {
+    let before = TemplateDetailResponse::from(fetch_template(&state, id).await?);
+    let steps = request.workflow_steps()?;
+    let changes = TemplateChanges {
+        name: request.name.trim(),
+        description: request.description(),
+        ticket_type: request.ticket_type.trim(),
+        steps: &steps,
+    };
+
+    match db_update_template(&state.db, id, &changes, request.expected_version)
+        .await
+        .map_db_err()?
+    {
+        TemplateUpdate::Updated(template) => {
+            info!(template_id = %id, version = template.version, "Updated workflow template");
+            let updated = TemplateDetailResponse::from(template);
+            let change = AuditChange::new(id).before(&before).after(&updated);
+            Ok((change.into_extension(), Json(updated)))
+        }
+        TemplateUpdate::NotFound => Err(ApiError::NotFound("Template not found".to_string())),
+        TemplateUpdate::VersionConflict { current } => Err(ApiError::Conflict(format!(
+            "Template is at version {current}; reload it and apply your changes again"
+        ))),
+    }
+}
+
+/// Delete a workflow template.
+///
+/// Default templates and templates with active or paused workflows cannot be
+/// deleted. Finished workflows keep their steps.
+#[utoipa::path(
+    delete,
+    path = "/api/v1/workflows/templates/{id}",
+    params(("id" = Uuid, Path, description = "Template ID")),
+    responses(
+        (status = 204, description = "Template deleted"),
+        (status = 404, description = "Template not found"),
+        (status = 409, description = "Template is a default or still in use"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Workflows"
+)]
+pub async fn delete_template(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> ApiResult<(StatusCode, Extension<AuditChange>)> {
+    let before = TemplateDetailResponse::from(fetch_template(&state, id).await?);
+    match db_delete_template(&state.db, id).await.map_db_err()? {
+        TemplateDeletion::Deleted => {
+            info!(template_id = %id, "Deleted workflow template");
+            let change = AuditChange::new(id).before(&before);
+            Ok((StatusCode::NO_CONTENT, change.into_extension()))
+        }
+        TemplateDeletion::NotFound => Err(ApiError::NotFound("Template not found".to_string())),
+        TemplateDeletion::Default => Err(ApiError::Conflict(
+            "Default templates cannot be deleted".to_string(),
+        )),
+        TemplateDeletion::InUse { active_instances } => Err(ApiError::Conflict(format!(
+            "Template is used by {active_instances} active workflow(s); complete or cancel them first"
+        ))),
+    }
+}
+
+/// List earlier versions of a workflow template.
+#[utoipa::path(
+    get,
+    path = "/api/v1/workflows/templates/{id}/versions",
+    params(("id" = Uuid, Path, description = "Template ID")),
+    responses(
+        (status = 200, description = "Template version history", body = TemplateVersionsResponse),
+        (status = 404, description = "Template not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Workflows"
+)]
+pub async fn list_template_versions(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> ApiResult<Json<TemplateVersionsResponse>> {
+    let template = fetch_template(&state, id).await?;
+    let versions = get_template_versions(&state.db, id)
+        .await
+        .map_db_err()?
+        .into_iter()
+        .map(Into::into)
+        .collect();
+
+    Ok(Json(TemplateVersionsResponse {
+        template_id: id,
+        current_version: template.version,
+        versions,
     }))
 }
 
+/// Fetch one version of a template or return `NotFound` error.
+async fn fetch_template_version(state: &AppState, id: Uuid, version: i32) -> ApiResult<TemplateVersion> {
+    get_template_version(&state.db, id, version)
+        .await
+        .map_db_err()?
+        .ok_or_else(|| ApiError::NotFound(format!("Template version {version} not found")))
+}
+
+/// Get one version of a workflow template.
+#[utoipa::path(
+    get,
+    path = "/api/v1/workflows/templates/{id}/versions/{version}",
+    params(
+        ("id" = Uuid, Path, description = "Template ID"),
+        ("version" = i32, Path, description = "Version number")
+    ),
+    responses(
+        (status = 200, description = "Template version", body = TemplateVersionResponse),
+        (status = 404, description = "Template or version not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Workflows"
+)]
+pub async fn get_template_version_by_number(
+    State(state): State<AppState>,
+    Path((id, version)): Path<(Uuid, i32)>,
+) -> ApiResult<Json<TemplateVersionResponse>> {
+    Ok(Json(fetch_template_version(&state, id, version).await?.into()))
+}
+
+/// Compare two versions of a workflow template.
+#[utoipa::path(
+    get,
+    path = "/api/v1/workflows/templates/{id}/diff",
+    params(("id" = Uuid, Path, description = "Template ID"), TemplateDiffQuery),
+    responses(
+        (status = 200, description = "Differences between the versions", body = TemplateDiffResponse),
+        (status = 404, description = "Template or version not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Workflows"
+)]
+pub async fn diff_template_versions(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Query(query): Query<TemplateDiffQuery>,
+) -> ApiResult<Json<TemplateDiffResponse>> {
+    let to = match query.to {
+        Some(version) => fetch_template_version(&state, id, version).await?,
+        None => TemplateVersion::from(&fetch_template(&state, id).await?),
+    };
+    let from = fetch_template_version(&state, id, query.from).await?;
+
+    Ok(Json(template_diff(&from, &to)))
+}
+
 /// Create a new workflow instance.
 #[utoipa::path(
     post,
@@ -364,7 +1274,7 @@ pub async fn get_template_by_id(
     request_body = CreateWorkflowRequest,
     responses(
         (status = 201, description = "Workflow created", body = CreateWorkflowResponse),
-        (status = 400, description = "Invalid request"),
+        (status = 400, description = "Invalid request or no default template for the ticket"),
         (status = 404, description = "Template not found"),
         (status = 500, description = "Internal server error")
     ),
@@ -372,23 +1282,40 @@ pub async fn get_template_by_id(
 )]
 pub async fn create_workflow(
     State(state): State<AppState>,
-    Json(request): Json<CreateWorkflowRequest>,
-) -> ApiResult<(StatusCode, Json<CreateWorkflowResponse>)> {
-    let template = fetch_template(&state, request.template_id).await?;
-    
+    user: CurrentUser,
+    ValidatedJson(request): ValidatedJson<CreateWorkflowRequest>,
+) -> ApiResult<(StatusCode, Extension<AuditChange>, Json<CreateWorkflowResponse>)> {
+    let template_id = match request.template_id {
+        Some(id) => id,
+        None => default_template_id(&state, &request.ticket_id, request.ticket_type.as_deref()).await?,
+    };
+    let template = fetch_template(&state, template_id).await?;
+    let owner = user.owner(request.user_id);
+
     let instance = create_instance(
         &state.db,
-        request.template_id,
+        template_id,
         &request.ticket_id,
-        &request.user_id,
+        &owner,
+        user.tenant.as_str(),
+        request.auto_track,
     )
     .await
-    .map_db_err()?;
+    .map_err(|e| match e {
+        // Deleted between the lookup and the insert
+        sqlx::Error::RowNotFound => ApiError::NotFound("Template not found".to_string()),
+        e => ApiError::Internal(e.into()),
+    })?;
 
     // Start the first step (non-critical if fails)
     if let Err(e) = start_step(&state.db, instance.id, 0).await {
         tracing::warn!(error = %e, "Failed to start first step");
     }
+    if instance.auto_track {
+        if let Err(e) = TrackingService::new(state.db.clone()).step_started(instance.id, 0).await {
+            tracing::warn!(workflow_id = %instance.id, error = %e, "Failed to start time session for first step");
+        }
+    }
 
     let steps = template.steps();
     let total_steps = steps.len();
@@ -399,12 +1326,8 @@ pub async fn create_workflow(
         name: "No steps".to_string(),
         description: String::new(),
         estimated_minutes: 0,
-    }, |s| StepResponse {
-        index: 0,
-        name: s.name.clone(),
-        description: s.description.clone(),
-        estimated_minutes: s.estimated_minutes,
-    });
+        branches: Vec::new(),
+    }, |s| StepResponse::from((0, s)));
 
     info!(
         workflow_id = %instance.id,
@@ -413,12 +1336,21 @@ pub async fn create_workflow(
         "Created workflow instance"
     );
 
-    Ok((StatusCode::CREATED, Json(CreateWorkflowResponse {
+    publish_workflow_event(
+        &state.notifications,
+        &instance,
+        "created",
+        serde_json::json!({ "templateName": template_name, "totalSteps": total_steps }),
+    );
+
+    let created = CreateWorkflowResponse {
         id: instance.id,
         template_name,
         current_step: first_step,
         total_steps,
-    })))
+    };
+    let change = AuditChange::new(instance.id).after(&created);
+    Ok((StatusCode::CREATED, change.into_extension(), Json(created)))
 }
 
 /// Get workflow instance by ID.
@@ -435,37 +1367,26 @@ pub async fn create_workflow(
 )]
 pub async fn get_workflow(
     State(state): State<AppState>,
+    user: CurrentUser,
     Path(id): Path<Uuid>,
 ) -> ApiResult<Json<WorkflowDetailResponse>> {
-    let instance = fetch_instance(&state, id).await?;
-    let template = fetch_template(&state, instance.template_id).await?;
+    let instance = fetch_instance(&state, id, &user).await?;
+    let template = fetch_instance_template(&state, &instance).await?;
     let step_results = get_step_results(&state.db, id).await.unwrap_or_default();
 
-    let estimated_minutes = template.total_estimated_minutes();
-    let template_name = template.name.clone();
-    
-    let steps: Vec<WorkflowStepWithStatus> = template
-        .steps()
-        .iter()
-        .enumerate()
-        .map(|(i, step)| {
-            let result = step_results.iter().find(|r| r.step_index == i as i32);
-            WorkflowStepWithStatus {
-                index: i,
-                name: step.name.clone(),
-                description: step.description.clone(),
-                estimated_minutes: step.estimated_minutes,
-                status: result.map_or("pending".to_string(), |r| r.status.clone()),
-                notes: result.and_then(|r| r.notes.clone()),
-            }
-        })
-        .collect();
-
+    let estimated_minutes = template.total_estimated_minutes();
+    let template_name = template.name.clone();
+    
+    let steps = steps_with_status(&template, &step_results);
+    let parent = get_parent_link(&state.db, id).await.map_db_err()?;
+    let (sub_workflows, time_summary) = sub_workflow_time(&state, &instance, &template).await?;
+
     info!(workflow_id = %id, "Retrieved workflow details");
 
     Ok(Json(WorkflowDetailResponse {
         id: instance.id,
         template_id: instance.template_id,
+        template_version: instance.template_version,
         template_name,
         ticket_id: instance.ticket_id,
         status: instance.status,
@@ -473,9 +1394,79 @@ pub async fn get_workflow(
         steps,
         estimated_minutes,
         started_at: instance.started_at.to_rfc3339(),
+        auto_track: instance.auto_track,
+        parent_workflow_id: parent.as_ref().map(|link| link.parent_instance_id),
+        parent_step_index: parent.map(|link| link.parent_step_index),
+        sub_workflows,
+        time_summary: time_summary.into(),
     }))
 }
 
+/// Direct sub-workflows of `instance` and its time summary, with the time
+/// of nested sub-workflows rolled up into the step that spawned them.
+async fn sub_workflow_time(
+    state: &AppState,
+    instance: &WorkflowInstance,
+    template: &WorkflowTemplate,
+) -> ApiResult<(Vec<SubWorkflowResponse>, TimeSummary)> {
+    let links = get_sub_workflow_tree(&state.db, instance.id).await.map_db_err()?;
+    let ids: Vec<Uuid> = std::iter::once(instance.id)
+        .chain(links.iter().map(|link| link.child_instance_id))
+        .collect();
+    let sessions = get_sessions_for_workflows(&state.db, &ids).await.map_db_err()?;
+    let seconds_of = |id: Uuid| -> i32 {
+        sessions
+            .iter()
+            .filter(|s| s.workflow_instance_id == id)
+            .map(|s| s.total_seconds)
+            .sum()
+    };
+
+    let mut summary = TimeSummary::from_sessions(instance.id, &sessions);
+    for (child, step_index) in SubWorkflowLink::root_steps(instance.id, &links) {
+        summary.add_sub_workflow_time(step_index, seconds_of(child));
+    }
+    let tracked: Vec<i32> = summary.step_times.iter().map(|s| s.step_index).collect();
+    for step_index in tracked {
+        if let Some(step) = template.steps().get(step_index as usize) {
+            summary.set_estimate(step_index, step.estimated_minutes * 60);
+        }
+    }
+
+    let direct: Vec<&SubWorkflowLink> = links
+        .iter()
+        .filter(|link| link.parent_instance_id == instance.id)
+        .collect();
+    let child_ids: Vec<Uuid> = direct.iter().map(|link| link.child_instance_id).collect();
+    let children = get_instances(&state.db, &child_ids).await.map_db_err()?;
+
+    let mut sub_workflows = Vec::with_capacity(direct.len());
+    for link in direct {
+        let Some(child) = children.iter().find(|c| c.id == link.child_instance_id) else {
+            continue;
+        };
+        let child_template = fetch_instance_template(state, child).await?;
+        let total_seconds = seconds_of(child.id)
+            + SubWorkflowLink::root_steps(child.id, &links)
+                .into_keys()
+                .map(seconds_of)
+                .sum::<i32>();
+        sub_workflows.push(SubWorkflowResponse {
+            id: child.id,
+            template_id: child.template_id,
+            template_name: child_template.name.clone(),
+            status: child.status.clone(),
+            parent_step_index: link.parent_step_index,
+            current_step: child.current_step,
+            total_steps: child_template.steps().len(),
+            total_seconds,
+            started_at: child.started_at.to_rfc3339(),
+        });
+    }
+
+    Ok((sub_workflows, summary))
+}
+
 /// Check for active workflow on a ticket.
 #[utoipa::path(
     get,
@@ -489,14 +1480,15 @@ pub async fn get_workflow(
 )]
 pub async fn get_active_workflow_for_ticket(
     State(state): State<AppState>,
+    user: CurrentUser,
     Path(ticket_id): Path<String>,
 ) -> ApiResult<Json<ActiveWorkflowResponse>> {
-    let instance = get_active_workflow(&state.db, &ticket_id).await.map_db_err()?;
+    let instance = get_active_workflow(&state.db, &ticket_id, Some(user.tenant.as_str()))
+        .await
+        .map_db_err()?;
 
     let response = if let Some(inst) = instance {
-        let template = get_template(&state.db, inst.template_id).await.map_db_err()?.unwrap_or_else(|| {
-            panic!("Template not found for instance")
-        });
+        let template = fetch_instance_template(&state, &inst).await?;
         let total_steps = template.steps().len();
         
         info!(ticket_id = %ticket_id, workflow_id = %inst.id, "Found active workflow");
@@ -549,11 +1541,12 @@ pub struct StepActionPath {
 )]
 pub async fn complete_step(
     State(state): State<AppState>,
+    user: CurrentUser,
     Path(path): Path<StepActionPath>,
-    Json(request): Json<CompleteStepRequest>,
-) -> ApiResult<Json<StepActionResponse>> {
-    let instance = fetch_instance(&state, path.id).await?;
-    let template = fetch_template(&state, instance.template_id).await?;
+    ValidatedJson(request): ValidatedJson<CompleteStepRequest>,
+) -> ApiResult<(Extension<AuditChange>, Json<StepActionResponse>)> {
+    let instance = fetch_instance(&state, path.id, &user).await?;
+    let template = fetch_instance_template(&state, &instance).await?;
     let total_steps = template.steps().len() as i32;
 
     if path.step_index < 0 || path.step_index >= total_steps {
@@ -574,27 +1567,169 @@ pub async fn complete_step(
     
     db_complete_step(&state.db, path.id, path.step_index, notes_ref, links_ref).await.map_db_err()?;
 
-    let next_step_index = path.step_index + 1;
-    let workflow_completed = next_step_index >= total_steps;
+    let advance = advance_workflow(&state, &instance, &template, path.step_index, &request.metadata).await?;
+    let workflow_completed = advance.next_step.is_none();
+    auto_track_advance(&state, &instance, path.step_index, &advance).await;
+
+    info!(
+        workflow_id = %path.id,
+        step_index = path.step_index,
+        next_step = ?advance.next_step.as_ref().map(|s| s.index),
+        workflow_completed,
+        "Completed workflow step"
+    );
+
+    publish_workflow_event(
+        &state.notifications,
+        &instance,
+        "step_completed",
+        serde_json::json!({ "stepIndex": path.step_index, "workflowCompleted": workflow_completed }),
+    );
 
-    let next_step = if workflow_completed {
-        None
+    let (jira_comment_id, jira_comment_error) = if request.post_to_jira {
+        let step_name = template
+            .steps()
+            .get(path.step_index as usize)
+            .map_or_else(|| format!("Step {}", path.step_index + 1), |s| s.name.clone());
+        let comment =
+            post_step_comment(&state, &user.tenant, &instance.ticket_id, &step_name, &request);
+        match comment.await {
+            Ok(id) => (id, None),
+            Err(e) => {
+                tracing::warn!(workflow_id = %path.id, ticket_id = %instance.ticket_id, error = %e, "Failed to post step notes to Jira");
+                (None, Some(e.to_string()))
+            }
+        }
     } else {
-        template.steps().get(next_step_index as usize).map(|s| StepResponse {
-            index: next_step_index as usize,
-            name: s.name.clone(),
-            description: s.description.clone(),
-            estimated_minutes: s.estimated_minutes,
-        })
+        (None, None)
     };
 
-    info!(workflow_id = %path.id, step_index = path.step_index, workflow_completed, "Completed workflow step");
+    let response = advance.into_response(path.step_index, jira_comment_id, jira_comment_error);
+    let change = AuditChange::new(instance.id)
+        .before(&instance_snapshot(&instance))
+        .after(&response);
+    Ok((change.into_extension(), Json(response)))
+}
 
-    Ok(Json(StepActionResponse {
-        workflow_completed,
-        next_step,
-        current_step_index: if workflow_completed { path.step_index } else { next_step_index },
-    }))
+/// Where a workflow moved after a step was completed or skipped.
+struct StepAdvance {
+    next_step: Option<StepResponse>,
+    bypassed_steps: Vec<usize>,
+}
+
+impl StepAdvance {
+    fn into_response(
+        self,
+        step_index: i32,
+        jira_comment_id: Option<String>,
+        jira_comment_error: Option<String>,
+    ) -> StepActionResponse {
+        StepActionResponse {
+            workflow_completed: self.next_step.is_none(),
+            current_step_index: self.next_step.as_ref().map_or(step_index, |s| s.index as i32),
+            next_step: self.next_step,
+            bypassed_steps: self.bypassed_steps,
+            jira_comment_id,
+            jira_comment_error,
+        }
+    }
+}
+
+/// Resolve the step after `step_index` from its branches and move the
+/// instance there, skipping any steps a branch jumped over.
+async fn advance_workflow(
+    state: &AppState,
+    instance: &WorkflowInstance,
+    template: &WorkflowTemplate,
+    step_index: i32,
+    metadata: &StepMetadata,
+) -> ApiResult<StepAdvance> {
+    let steps = template.steps();
+    let current = step_index as usize;
+    let next = resolve_next_step(steps, current, metadata);
+    let target = next.unwrap_or(steps.len());
+
+    skip_bypassed_steps(&state.db, instance.id, step_index, target as i32)
+        .await
+        .map_db_err()?;
+    if let Some(next) = next {
+        update_instance_step(&state.db, instance.id, next as i32).await.map_db_err()?;
+    }
+
+    Ok(StepAdvance {
+        next_step: next.map(|index| StepResponse::from((index, &steps[index]))),
+        bypassed_steps: (current + 1..target).collect(),
+    })
+}
+
+/// Move an auto-tracked workflow's time session to the step it advanced to.
+///
+/// Best effort, like starting the first step: failures are logged and the
+/// step action still succeeds.
+async fn auto_track_advance(
+    state: &AppState,
+    instance: &WorkflowInstance,
+    step_index: i32,
+    advance: &StepAdvance,
+) {
+    if !instance.auto_track {
+        return;
+    }
+    let next = advance.next_step.as_ref().map(|s| s.index as i32);
+    match TrackingService::new(state.db.clone())
+        .advance(instance.id, step_index, next)
+        .await
+    {
+        Ok(Some(session)) => notify_if_over_estimate(state, &session).await,
+        Ok(None) => {}
+        Err(e) => tracing::warn!(workflow_id = %instance.id, step_index, error = %e, "Failed to auto-track step time"),
+    }
+}
+
+/// Post a completed step's notes and links to the Jira ticket.
+///
+/// Returns the comment ID, or `None` when there was nothing to post.
+async fn post_step_comment(
+    state: &AppState,
+    tenant: &TenantId,
+    ticket_id: &str,
+    step_name: &str,
+    request: &CompleteStepRequest,
+) -> ApiResult<Option<String>> {
+    let Some(body) = step_comment(step_name, request.notes.as_deref(), &request.links) else {
+        return Ok(None);
+    };
+    let client = crate::routes::tickets::get_jira_client(state, tenant).await?;
+    let comment = client
+        .add_comment(ticket_id, body)
+        .await
+        .map_err(|e| ApiError::ExternalService(e.to_string()))?;
+    info!(ticket_id = %ticket_id, comment_id = %comment.id, "Posted step notes to Jira");
+    Ok(Some(comment.id))
+}
+
+/// ADF comment for a completed step, or `None` without notes or links.
+fn step_comment(step_name: &str, notes: Option<&str>, links: &[StepLinkRequest]) -> Option<serde_json::Value> {
+    let paragraphs: Vec<&str> = notes
+        .unwrap_or_default()
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect();
+    if paragraphs.is_empty() && links.is_empty() {
+        return None;
+    }
+
+    let mut content = vec![adf::heading(3, &format!("QA step completed: {step_name}"))];
+    content.extend(paragraphs.into_iter().map(adf::paragraph));
+    if !links.is_empty() {
+        content.push(adf::bullet_list(
+            links
+                .iter()
+                .map(|l| adf::paragraph_of(vec![adf::link(&l.label, &l.url)])),
+        ));
+    }
+    Some(adf::document(content))
 }
 
 /// Skip a workflow step.
@@ -615,10 +1750,11 @@ pub async fn complete_step(
 )]
 pub async fn skip_step(
     State(state): State<AppState>,
+    user: CurrentUser,
     Path(path): Path<StepActionPath>,
-) -> ApiResult<Json<StepActionResponse>> {
-    let instance = fetch_instance(&state, path.id).await?;
-    let template = fetch_template(&state, instance.template_id).await?;
+) -> ApiResult<(Extension<AuditChange>, Json<StepActionResponse>)> {
+    let instance = fetch_instance(&state, path.id, &user).await?;
+    let template = fetch_instance_template(&state, &instance).await?;
     let total_steps = template.steps().len() as i32;
 
     if path.step_index < 0 || path.step_index >= total_steps {
@@ -627,36 +1763,129 @@ pub async fn skip_step(
 
     db_skip_step(&state.db, path.id, path.step_index).await.map_db_err()?;
 
-    let next_step_index = path.step_index + 1;
-    let workflow_completed = next_step_index >= total_steps;
-
-    let next_step = if workflow_completed {
-        None
-    } else {
-        template.steps().get(next_step_index as usize).map(|s| StepResponse {
-            index: next_step_index as usize,
-            name: s.name.clone(),
-            description: s.description.clone(),
-            estimated_minutes: s.estimated_minutes,
-        })
-    };
+    // A skipped step has no outcome, so only its default branch applies
+    let advance = advance_workflow(&state, &instance, &template, path.step_index, &StepMetadata::new()).await?;
+    let workflow_completed = advance.next_step.is_none();
+    auto_track_advance(&state, &instance, path.step_index, &advance).await;
 
     info!(workflow_id = %path.id, step_index = path.step_index, workflow_completed, "Skipped workflow step");
 
-    Ok(Json(StepActionResponse {
-        workflow_completed,
-        next_step,
-        current_step_index: if workflow_completed { path.step_index } else { next_step_index },
-    }))
+    publish_workflow_event(
+        &state.notifications,
+        &instance,
+        "step_skipped",
+        serde_json::json!({ "stepIndex": path.step_index, "workflowCompleted": workflow_completed }),
+    );
+
+    let response = advance.into_response(path.step_index, None, None);
+    let change = AuditChange::new(instance.id)
+        .before(&instance_snapshot(&instance))
+        .after(&response);
+    Ok((change.into_extension(), Json(response)))
+}
+
+/// Spawn a sub-workflow from a workflow step.
+///
+/// The sub-workflow runs on the same ticket and for the same user as its
+/// parent; its tracked time rolls up into the parent step.
+#[utoipa::path(
+    post,
+    path = "/api/v1/workflows/{id}/steps/{step_index}/sub-workflows",
+    params(
+        ("id" = Uuid, Path, description = "Parent workflow instance ID"),
+        ("step_index" = i32, Path, description = "Step index (0-based)")
+    ),
+    request_body = CreateSubWorkflowRequest,
+    responses(
+        (status = 201, description = "Sub-workflow started", body = SubWorkflowResponse),
+        (status = 400, description = "Invalid step index or parent not active"),
+        (status = 404, description = "Workflow or template not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Workflows"
+)]
+pub async fn spawn_sub_workflow(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Path(path): Path<StepActionPath>,
+    ValidatedJson(request): ValidatedJson<CreateSubWorkflowRequest>,
+) -> ApiResult<(StatusCode, Extension<AuditChange>, Json<SubWorkflowResponse>)> {
+    let parent = fetch_instance(&state, path.id, &user).await?;
+    let parent_template = fetch_instance_template(&state, &parent).await?;
+    if path.step_index < 0 || path.step_index as usize >= parent_template.steps().len() {
+        return Err(ApiError::Validation("Invalid step index".to_string()));
+    }
+    if !parent.can_resume() {
+        return Err(ApiError::Validation(
+            "Sub-workflows can only be spawned from an active or paused workflow".to_string(),
+        ));
+    }
+    let template = fetch_template(&state, request.template_id).await?;
+
+    let child = create_sub_workflow(
+        &state.db,
+        parent.id,
+        path.step_index,
+        template.id,
+        &parent.ticket_id,
+        &parent.user_id,
+    )
+    .await
+    .map_err(|e| match e {
+        sqlx::Error::RowNotFound => ApiError::NotFound("Template not found".to_string()),
+        e => ApiError::Internal(e.into()),
+    })?;
+
+    if let Err(e) = start_step(&state.db, child.id, 0).await {
+        tracing::warn!(error = %e, "Failed to start first step");
+    }
+
+    info!(
+        workflow_id = %child.id,
+        parent_workflow_id = %parent.id,
+        parent_step_index = path.step_index,
+        template = %template.name,
+        "Spawned sub-workflow"
+    );
+
+    publish_workflow_event(
+        &state.notifications,
+        &child,
+        "created",
+        serde_json::json!({
+            "templateName": template.name,
+            "totalSteps": template.steps().len(),
+            "parentWorkflowId": parent.id,
+            "parentStepIndex": path.step_index,
+        }),
+    );
+
+    let created = SubWorkflowResponse {
+        id: child.id,
+        template_id: child.template_id,
+        template_name: template.name.clone(),
+        status: child.status,
+        parent_step_index: path.step_index,
+        current_step: child.current_step,
+        total_steps: template.steps().len(),
+        total_seconds: 0,
+        started_at: child.started_at.to_rfc3339(),
+    };
+    let change = AuditChange::new(child.id).after(&created);
+    Ok((StatusCode::CREATED, change.into_extension(), Json(created)))
 }
 
 /// Pause a workflow.
 #[utoipa::path(
     post,
     path = "/api/v1/workflows/{id}/pause",
-    params(("id" = Uuid, Path, description = "Workflow instance ID")),
+    params(
+        ("id" = Uuid, Path, description = "Workflow instance ID"),
+        AdminOverrideQuery
+    ),
     responses(
         (status = 200, description = "Workflow paused", body = WorkflowStatusResponse),
+        (status = 403, description = "Workflow belongs to another user"),
         (status = 404, description = "Workflow not found"),
         (status = 400, description = "Workflow cannot be paused"),
         (status = 500, description = "Internal server error")
@@ -665,9 +1894,11 @@ pub async fn skip_step(
 )]
 pub async fn pause_workflow(
     State(state): State<AppState>,
+    user: CurrentUser,
     Path(id): Path<Uuid>,
-) -> ApiResult<Json<WorkflowStatusResponse>> {
-    let instance = fetch_instance(&state, id).await?;
+    Query(admin): Query<AdminOverrideQuery>,
+) -> ApiResult<(Extension<AuditChange>, Json<WorkflowStatusResponse>)> {
+    let instance = fetch_managed_instance(&state, id, &user, &admin).await?;
 
     if instance.status != "active" {
         return Err(ApiError::Validation("Workflow is not active".to_string()));
@@ -676,20 +1907,27 @@ pub async fn pause_workflow(
     db_pause_workflow(&state.db, id).await.map_db_err()?;
 
     info!(workflow_id = %id, "Paused workflow");
+    publish_workflow_event(&state.notifications, &instance, "paused", serde_json::json!({}));
 
-    Ok(Json(WorkflowStatusResponse {
+    let response = WorkflowStatusResponse {
         status: "paused".to_string(),
         message: "Workflow paused successfully".to_string(),
-    }))
+    };
+    let change = AuditChange::new(id).before(&instance_snapshot(&instance)).after(&response);
+    Ok((change.into_extension(), Json(response)))
 }
 
 /// Resume a paused workflow.
 #[utoipa::path(
     post,
     path = "/api/v1/workflows/{id}/resume",
-    params(("id" = Uuid, Path, description = "Workflow instance ID")),
+    params(
+        ("id" = Uuid, Path, description = "Workflow instance ID"),
+        AdminOverrideQuery
+    ),
     responses(
         (status = 200, description = "Workflow resumed", body = WorkflowStatusResponse),
+        (status = 403, description = "Workflow belongs to another user"),
         (status = 404, description = "Workflow not found"),
         (status = 400, description = "Workflow cannot be resumed"),
         (status = 500, description = "Internal server error")
@@ -698,9 +1936,11 @@ pub async fn pause_workflow(
 )]
 pub async fn resume_workflow(
     State(state): State<AppState>,
+    user: CurrentUser,
     Path(id): Path<Uuid>,
-) -> ApiResult<Json<WorkflowStatusResponse>> {
-    let instance = fetch_instance(&state, id).await?;
+    Query(admin): Query<AdminOverrideQuery>,
+) -> ApiResult<(Extension<AuditChange>, Json<WorkflowStatusResponse>)> {
+    let instance = fetch_managed_instance(&state, id, &user, &admin).await?;
 
     if instance.status != "paused" {
         return Err(ApiError::Validation("Workflow is not paused".to_string()));
@@ -709,20 +1949,27 @@ pub async fn resume_workflow(
     db_resume_workflow(&state.db, id).await.map_db_err()?;
 
     info!(workflow_id = %id, "Resumed workflow");
+    publish_workflow_event(&state.notifications, &instance, "resumed", serde_json::json!({}));
 
-    Ok(Json(WorkflowStatusResponse {
+    let response = WorkflowStatusResponse {
         status: "active".to_string(),
         message: "Workflow resumed successfully".to_string(),
-    }))
+    };
+    let change = AuditChange::new(id).before(&instance_snapshot(&instance)).after(&response);
+    Ok((change.into_extension(), Json(response)))
 }
 
 /// Complete a workflow.
 #[utoipa::path(
     post,
     path = "/api/v1/workflows/{id}/complete",
-    params(("id" = Uuid, Path, description = "Workflow instance ID")),
+    params(
+        ("id" = Uuid, Path, description = "Workflow instance ID"),
+        AdminOverrideQuery
+    ),
     responses(
         (status = 200, description = "Workflow completed", body = WorkflowStatusResponse),
+        (status = 403, description = "Workflow belongs to another user"),
         (status = 404, description = "Workflow not found"),
         (status = 500, description = "Internal server error")
     ),
@@ -730,46 +1977,26 @@ pub async fn resume_workflow(
 )]
 pub async fn complete_workflow(
     State(state): State<AppState>,
+    user: CurrentUser,
     Path(id): Path<Uuid>,
-) -> ApiResult<Json<WorkflowStatusResponse>> {
-    let _ = fetch_instance(&state, id).await?;
+    Query(admin): Query<AdminOverrideQuery>,
+) -> ApiResult<(Extension<AuditChange>, Json<WorkflowStatusResponse>)> {
+    let instance = fetch_managed_instance(&state, id, &user, &admin).await?;
     
     db_complete_workflow(&state.db, id).await.map_db_err()?;
 
     info!(workflow_id = %id, "Completed workflow");
+    publish_workflow_event(&state.notifications, &instance, "completed", serde_json::json!({}));
 
     // Trigger pattern detection in background (Story 9.1, 9.2, 9.3)
-    let pool = state.db.clone();
-    tokio::spawn(async move {
-        let detector = qa_pms_patterns::PatternDetector::new(pool.clone());
-        match detector.analyze_workflow(id).await {
-            Ok(patterns) => {
-                if !patterns.is_empty() {
-                    info!(
-                        workflow_id = %id,
-                        patterns_detected = patterns.len(),
-                        "Pattern detection completed"
-                    );
-                    // Generate alerts for detected patterns
-                    let repo = qa_pms_patterns::PatternRepository::new(pool);
-                    let alert_service = qa_pms_patterns::AlertService::new(repo);
-                    for pattern in patterns {
-                        if let Err(e) = alert_service.generate_alert(&pattern).await {
-                            tracing::warn!(error = %e, "Failed to generate alert for pattern");
-                        }
-                    }
-                }
-            }
-            Err(e) => {
-                tracing::warn!(workflow_id = %id, error = %e, "Pattern detection failed");
-            }
-        }
-    });
+    spawn_pattern_detection(state.db.clone(), state.notifications.clone(), id);
 
-    Ok(Json(WorkflowStatusResponse {
+    let response = WorkflowStatusResponse {
         status: "completed".to_string(),
         message: "Workflow completed successfully".to_string(),
-    }))
+    };
+    let change = AuditChange::new(id).before(&instance_snapshot(&instance)).after(&response);
+    Ok((change.into_extension(), Json(response)))
 }
 
 /// Get workflow summary.
@@ -786,10 +2013,11 @@ pub async fn complete_workflow(
 )]
 pub async fn get_workflow_summary(
     State(state): State<AppState>,
+    user: CurrentUser,
     Path(id): Path<Uuid>,
 ) -> ApiResult<Json<WorkflowSummaryResponse>> {
-    let instance = fetch_instance(&state, id).await?;
-    let template = fetch_template(&state, instance.template_id).await?;
+    let instance = fetch_instance(&state, id, &user).await?;
+    let template = fetch_instance_template(&state, &instance).await?;
     let step_results = get_step_results(&state.db, id).await.unwrap_or_default();
 
     let steps: Vec<StepSummary> = template
@@ -810,6 +2038,12 @@ pub async fn get_workflow_summary(
     let completed_steps = steps.iter().filter(|s| s.status == "completed").count();
     let skipped_steps = steps.iter().filter(|s| s.status == "skipped").count();
     let all_notes: Vec<String> = steps.iter().filter_map(|s| s.notes.clone()).collect();
+    let test_runs = runs_for_workflow(&state.db, id, &instance.ticket_id)
+        .await
+        .map_db_err()?
+        .into_iter()
+        .map(TestRunSummary::from)
+        .collect();
 
     Ok(Json(WorkflowSummaryResponse {
         id: instance.id,
@@ -823,6 +2057,7 @@ pub async fn get_workflow_summary(
         skipped_steps,
         steps,
         all_notes,
+        test_runs,
     }))
 }
 
@@ -830,9 +2065,13 @@ pub async fn get_workflow_summary(
 #[utoipa::path(
     post,
     path = "/api/v1/workflows/{id}/cancel",
-    params(("id" = Uuid, Path, description = "Workflow instance ID")),
+    params(
+        ("id" = Uuid, Path, description = "Workflow instance ID"),
+        AdminOverrideQuery
+    ),
     responses(
         (status = 200, description = "Workflow cancelled", body = WorkflowStatusResponse),
+        (status = 403, description = "Workflow belongs to another user"),
         (status = 404, description = "Workflow not found"),
         (status = 500, description = "Internal server error")
     ),
@@ -840,18 +2079,291 @@ pub async fn get_workflow_summary(
 )]
 pub async fn cancel_workflow(
     State(state): State<AppState>,
+    user: CurrentUser,
     Path(id): Path<Uuid>,
-) -> ApiResult<Json<WorkflowStatusResponse>> {
-    let _ = fetch_instance(&state, id).await?;
+    Query(admin): Query<AdminOverrideQuery>,
+) -> ApiResult<(Extension<AuditChange>, Json<WorkflowStatusResponse>)> {
+    let instance = fetch_managed_instance(&state, id, &user, &admin).await?;
 
     db_cancel_workflow(&state.db, id).await.map_db_err()?;
 
     info!(workflow_id = %id, "Cancelled workflow");
+    publish_workflow_event(&state.notifications, &instance, "cancelled", serde_json::json!({}));
 
-    Ok(Json(WorkflowStatusResponse {
+    let response = WorkflowStatusResponse {
         status: "cancelled".to_string(),
         message: "Workflow cancelled".to_string(),
-    }))
+    };
+    let change = AuditChange::new(id).before(&instance_snapshot(&instance)).after(&response);
+    Ok((change.into_extension(), Json(response)))
+}
+
+/// Archive a finished workflow.
+///
+/// Archived workflows leave the default listings but can still be opened and
+/// are restored with `POST /api/v1/workflows/{id}/restore`.
+#[utoipa::path(
+    post,
+    path = "/api/v1/workflows/{id}/archive",
+    params(
+        ("id" = Uuid, Path, description = "Workflow instance ID"),
+        AdminOverrideQuery
+    ),
+    responses(
+        (status = 200, description = "Workflow archived", body = WorkflowStatusResponse),
+        (status = 403, description = "Workflow belongs to another user"),
+        (status = 404, description = "Workflow not found"),
+        (status = 409, description = "Workflow is still running or already archived"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Workflows"
+)]
+pub async fn archive_workflow(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Path(id): Path<Uuid>,
+    Query(admin): Query<AdminOverrideQuery>,
+) -> ApiResult<(Extension<AuditChange>, Json<WorkflowStatusResponse>)> {
+    let instance = fetch_managed_instance(&state, id, &user, &admin).await?;
+    ensure_finished(&instance, "archived")?;
+
+    let before = instance_snapshot(&instance);
+    let instance = archive_instance(&state.db, id)
+        .await
+        .map_db_err()?
+        .ok_or_else(|| ApiError::Conflict("Workflow is already archived".to_string()))?;
+
+    info!(workflow_id = %id, "Archived workflow");
+    publish_workflow_event(&state.notifications, &instance, "archived", serde_json::json!({}));
+
+    let change = AuditChange::new(id).before(&before).after(&instance_snapshot(&instance));
+    Ok((change.into_extension(), Json(WorkflowStatusResponse {
+        status: instance.status,
+        message: "Workflow archived".to_string(),
+    })))
+}
+
+/// Soft-delete a finished workflow.
+///
+/// The workflow is hidden everywhere except `GET /api/v1/workflows/archived?deleted=true`
+/// and can be restored; its tracked time and step results are kept.
+#[utoipa::path(
+    delete,
+    path = "/api/v1/workflows/{id}",
+    params(
+        ("id" = Uuid, Path, description = "Workflow instance ID"),
+        AdminOverrideQuery
+    ),
+    responses(
+        (status = 200, description = "Workflow deleted", body = WorkflowStatusResponse),
+        (status = 403, description = "Workflow belongs to another user"),
+        (status = 404, description = "Workflow not found"),
+        (status = 409, description = "Workflow is still running"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Workflows"
+)]
+pub async fn delete_workflow(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Path(id): Path<Uuid>,
+    Query(admin): Query<AdminOverrideQuery>,
+) -> ApiResult<(Extension<AuditChange>, Json<WorkflowStatusResponse>)> {
+    let instance = fetch_managed_instance(&state, id, &user, &admin).await?;
+    ensure_finished(&instance, "deleted")?;
+
+    let before = instance_snapshot(&instance);
+    let instance = delete_instance(&state.db, id)
+        .await
+        .map_db_err()?
+        .ok_or_else(|| ApiError::NotFound("Workflow not found".to_string()))?;
+
+    info!(workflow_id = %id, "Deleted workflow");
+    publish_workflow_event(&state.notifications, &instance, "deleted", serde_json::json!({}));
+
+    let change = AuditChange::new(id).before(&before).after(&instance_snapshot(&instance));
+    Ok((change.into_extension(), Json(WorkflowStatusResponse {
+        status: instance.status,
+        message: "Workflow deleted".to_string(),
+    })))
+}
+
+/// Restore an archived or deleted workflow to the default listings.
+#[utoipa::path(
+    post,
+    path = "/api/v1/workflows/{id}/restore",
+    params(
+        ("id" = Uuid, Path, description = "Workflow instance ID"),
+        AdminOverrideQuery
+    ),
+    responses(
+        (status = 200, description = "Workflow restored", body = WorkflowStatusResponse),
+        (status = 403, description = "Workflow belongs to another user"),
+        (status = 404, description = "Workflow not found"),
+        (status = 409, description = "Workflow is neither archived nor deleted"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Workflows"
+)]
+pub async fn restore_workflow(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Path(id): Path<Uuid>,
+    Query(admin): Query<AdminOverrideQuery>,
+) -> ApiResult<(Extension<AuditChange>, Json<WorkflowStatusResponse>)> {
+    let instance = get_instance_with_deleted(&state.db, id)
+        .await
+        .map_db_err()?
+        .filter(|instance| instance.tenant_id == user.tenant.as_str())
+        .ok_or_else(|| ApiError::NotFound("Workflow not found".to_string()))?;
+    ensure_manageable(&instance, &user, &admin)?;
+
+    let before = instance_snapshot(&instance);
+    let instance = restore_instance(&state.db, id)
+        .await
+        .map_db_err()?
+        .ok_or_else(|| ApiError::Conflict("Workflow is neither archived nor deleted".to_string()))?;
+
+    info!(workflow_id = %id, "Restored workflow");
+    publish_workflow_event(&state.notifications, &instance, "restored", serde_json::json!({}));
+
+    let change = AuditChange::new(id).before(&before).after(&instance_snapshot(&instance));
+    Ok((change.into_extension(), Json(WorkflowStatusResponse {
+        status: instance.status,
+        message: "Workflow restored".to_string(),
+    })))
+}
+
+/// Only finished workflows may be archived or deleted.
+/// The audited fields of a workflow instance, which has no serialized form of its own.
+fn instance_snapshot(instance: &WorkflowInstance) -> serde_json::Value {
+    serde_json::json!({
+        "ticketId": instance.ticket_id,
+        "userId": instance.user_id,
+        "status": instance.status,
+        "currentStep": instance.current_step,
+    })
+}
+
+fn ensure_finished(instance: &WorkflowInstance, action: &str) -> ApiResult<()> {
+    if instance.is_finished() {
+        Ok(())
+    } else {
+        Err(ApiError::Conflict(format!(
+            "Only completed or cancelled workflows can be {action}"
+        )))
+    }
+}
+
+/// List workflows, newest first.
+#[utoipa::path(
+    get,
+    path = "/api/v1/workflows",
+    params(WorkflowListQuery, ProjectQuery, CursorQuery),
+    responses(
+        (status = 200, description = "Page of workflows", body = WorkflowListResponse),
+        (status = 400, description = "Invalid cursor"),
+        (status = 404, description = "Project not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Workflows"
+)]
+pub async fn list_workflows(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Query(query): Query<WorkflowListQuery>,
+    Query(project): Query<ProjectQuery>,
+    Query(cursor): Query<CursorQuery>,
+) -> ApiResult<Json<WorkflowListResponse>> {
+    let filter = InstanceFilter {
+        user_id: query.user_id,
+        status: query.status,
+        ticket_id: query.ticket_id,
+        project_key: project.resolve(&state).await?.map(|p| p.key),
+        tenant_id: Some(user.tenant.to_string()),
+        visibility: InstanceVisibility::Current,
+    };
+    list_workflow_page(&state, &filter, &cursor.page_request()?).await.map(Json)
+}
+
+/// List archived workflows, or deleted ones with `deleted=true`, newest first.
+#[utoipa::path(
+    get,
+    path = "/api/v1/workflows/archived",
+    params(ArchivedWorkflowsQuery, WorkflowListQuery, ProjectQuery, CursorQuery),
+    responses(
+        (status = 200, description = "Page of archived or deleted workflows", body = WorkflowListResponse),
+        (status = 400, description = "Invalid cursor"),
+        (status = 404, description = "Project not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Workflows"
+)]
+pub async fn list_archived_workflows(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Query(archived): Query<ArchivedWorkflowsQuery>,
+    Query(query): Query<WorkflowListQuery>,
+    Query(project): Query<ProjectQuery>,
+    Query(cursor): Query<CursorQuery>,
+) -> ApiResult<Json<WorkflowListResponse>> {
+    let filter = InstanceFilter {
+        user_id: query.user_id,
+        status: query.status,
+        ticket_id: query.ticket_id,
+        project_key: project.resolve(&state).await?.map(|p| p.key),
+        tenant_id: Some(user.tenant.to_string()),
+        visibility: if archived.deleted.unwrap_or(false) {
+            InstanceVisibility::Deleted
+        } else {
+            InstanceVisibility::Archived
+        },
+    };
+    list_workflow_page(&state, &filter, &cursor.page_request()?).await.map(Json)
+}
+
+async fn list_workflow_page(
+    state: &AppState,
+    filter: &InstanceFilter,
+    page: &PageRequest,
+) -> ApiResult<WorkflowListResponse> {
+    let rows = list_instances(&state.db, filter, page).await.map_db_err()?;
+    // Resolved per template version, since instances keep the steps they started with
+    let mut templates: HashMap<(Uuid, i32), (String, usize)> = HashMap::new();
+    for inst in &rows {
+        let key = (inst.template_id, inst.template_version);
+        if templates.contains_key(&key) {
+            continue;
+        }
+        if let Some(t) = get_instance_template(&state.db, inst).await.map_db_err()? {
+            templates.insert(key, (t.name.clone(), t.steps().len()));
+        }
+    }
+
+    let result = page.finish(rows, |inst| (inst.started_at, inst.id)).map(|inst| {
+        let (template_name, total_steps) = templates
+            .get(&(inst.template_id, inst.template_version))
+            .cloned()
+            .unwrap_or_else(|| ("Unknown".to_string(), 0));
+        WorkflowListItem {
+            id: inst.id,
+            ticket_id: inst.ticket_id,
+            user_id: inst.user_id,
+            template_name,
+            status: inst.status,
+            current_step: inst.current_step,
+            total_steps,
+            started_at: inst.started_at.to_rfc3339(),
+            completed_at: inst.completed_at.map(|t| t.to_rfc3339()),
+            archived_at: inst.archived_at.map(|t| t.to_rfc3339()),
+            deleted_at: inst.deleted_at.map(|t| t.to_rfc3339()),
+        }
+    });
+
+    Ok(WorkflowListResponse {
+        workflows: result.data,
+        page_info: result.page_info,
+    })
 }
 
 /// Get all active workflows for current user.
@@ -864,15 +2376,17 @@ pub async fn cancel_workflow(
     ),
     tag = "Workflows"
 )]
-pub async fn get_user_active_workflows(State(state): State<AppState>) -> ApiResult<Json<UserActiveWorkflowsResponse>> {
-    // TODO: Get user_id from auth context
-    let user_id = "current-user@example.com";
+pub async fn get_user_active_workflows(
+    State(state): State<AppState>,
+    user: CurrentUser,
+) -> ApiResult<Json<UserActiveWorkflowsResponse>> {
+    let user_id = user.owner(None);
 
-    let instances = get_all_user_active_workflows(&state.db, user_id).await.map_db_err()?;
+    let instances = get_all_user_active_workflows(&state.db, &user_id).await.map_db_err()?;
 
     let mut workflows = Vec::with_capacity(instances.len());
     for inst in instances {
-        if let Ok(Some(template)) = get_template(&state.db, inst.template_id).await {
+        if let Ok(Some(template)) = get_instance_template(&state.db, &inst).await {
             let total_steps = template.steps().len();
             workflows.push(WorkflowSummary {
                 id: inst.id,
@@ -887,3 +2401,131 @@ pub async fn get_user_active_workflows(State(state): State<AppState>) -> ApiResu
 
     Ok(Json(UserActiveWorkflowsResponse { workflows }))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_step_comment_includes_notes_and_links() {
+        let links = vec![StepLinkRequest {
+            label: "Test run".to_string(),
+            url: "https://ci.example.com/runs/42".to_string(),
+        }];
+        let body = step_comment("Regression", Some("Login passed\n\nCheckout slow"), &links).unwrap();
+
+        let content = body["content"].as_array().unwrap();
+        assert_eq!(content[0]["content"][0]["text"], "QA step completed: Regression");
+        assert_eq!(content[1]["content"][0]["text"], "Login passed");
+        assert_eq!(content[2]["content"][0]["text"], "Checkout slow");
+        let link = &content[3]["content"][0]["content"][0]["content"][0];
+        assert_eq!(link["marks"][0]["attrs"]["href"], "https://ci.example.com/runs/42");
+
+        assert!(step_comment("Regression", Some("  \n"), &[]).is_none());
+    }
+
+    #[test]
+    fn test_template_diff_reports_fields_steps_and_minutes() {
+        let step = |name: &str, minutes| WorkflowStep {
+            name: name.to_string(),
+            description: String::new(),
+            estimated_minutes: minutes,
+            branches: Vec::new(),
+        };
+        let version = |version, name: &str, steps| TemplateVersion {
+            template_id: Uuid::nil(),
+            version,
+            name: name.to_string(),
+            description: None,
+            ticket_type: "bug".to_string(),
+            steps_json: sqlx::types::Json(steps),
+            created_at: chrono::Utc::now(),
+        };
+        let v1 = version(1, "Bug Fix", vec![step("Reproduce", 15), step("Close", 5)]);
+        let v2 = version(2, "Bug Verification", vec![step("Reproduce", 15), step("Retest", 20), step("Close", 5)]);
+
+        let diff = template_diff(&v1, &v2);
+        assert_eq!((diff.from_version, diff.to_version), (1, 2));
+        assert_eq!(diff.changed_fields.len(), 1);
+        assert_eq!(diff.changed_fields[0].field, "name");
+        assert_eq!(diff.changed_fields[0].after.as_deref(), Some("Bug Verification"));
+        let changes: Vec<&str> = diff.steps.iter().map(|s| s.change.as_str()).collect();
+        assert_eq!(changes, vec!["unchanged", "added", "unchanged"]);
+        assert_eq!(diff.steps[1].after.as_ref().map(|s| s.index), Some(1));
+        assert_eq!(diff.estimated_minutes_delta, 20);
+    }
+
+    #[test]
+    fn test_template_request_validation() {
+        let request: TemplateRequest = serde_json::from_value(serde_json::json!({
+            "name": " Smoke ",
+            "description": "  ",
+            "ticketType": "custom",
+            "steps": [{ "name": "Deploy check", "estimatedMinutes": 5 }]
+        }))
+        .unwrap();
+        assert!(request.validate().is_ok());
+        assert_eq!(request.description(), None);
+        assert_eq!(request.workflow_steps().unwrap()[0].description, "");
+
+        let request: TemplateRequest = serde_json::from_value(serde_json::json!({
+            "name": "Smoke",
+            "ticketType": "custom",
+            "steps": [{ "name": "Deploy check", "estimatedMinutes": 0 }]
+        }))
+        .unwrap();
+        let errors = request.validate().unwrap_err();
+        assert!(errors.to_string().contains("estimated_minutes"));
+
+        let request: TemplateRequest = serde_json::from_value(serde_json::json!({
+            "name": "Smoke",
+            "ticketType": "custom",
+            "steps": []
+        }))
+        .unwrap();
+        assert!(request.validate().is_err());
+    }
+
+    #[test]
+    fn test_template_request_branches() {
+        let request: TemplateRequest = serde_json::from_value(serde_json::json!({
+            "name": "Bug Verification",
+            "ticketType": "bug",
+            "steps": [
+                {
+                    "name": "Reproduce",
+                    "estimatedMinutes": 15,
+                    "branches": [
+                        { "when": { "key": "reproduced", "equals": true }, "nextStep": 1 },
+                        { "nextStep": 2 }
+                    ]
+                },
+                { "name": "Verify fix", "estimatedMinutes": 20, "branches": [{ "nextStep": 3 }] },
+                { "name": "Report not reproducible", "estimatedMinutes": 5 }
+            ]
+        }))
+        .unwrap();
+        let steps = request.workflow_steps().unwrap();
+        assert_eq!(steps[0].branches.len(), 2);
+        assert_eq!(steps[0].branches[0].when.as_ref().unwrap().equals, serde_json::json!(true));
+
+        let response = StepResponse::from((0, &steps[0]));
+        let json = serde_json::to_value(&response).unwrap();
+        assert_eq!(json["branches"][1]["nextStep"], 2);
+        assert!(serde_json::to_value(StepResponse::from((2, &steps[2])))
+            .unwrap()
+            .get("branches")
+            .is_none());
+
+        let request: TemplateRequest = serde_json::from_value(serde_json::json!({
+            "name": "Loop",
+            "ticketType": "bug",
+            "steps": [
+                { "name": "A", "estimatedMinutes": 5 },
+                { "name": "B", "estimatedMinutes": 5, "branches": [{ "nextStep": 0 }] }
+            ]
+        }))
+        .unwrap();
+        assert!(matches!(request.workflow_steps(), Err(ApiError::Unprocessable(_))));
+    }
+}
@@ -0,0 +1,249 @@
+//! Postgres storage for saved ticket filters.
+//!
+//! A saved filter is a named set of ticket list filters owned by one user.
+//! Names are unique per owner, and at most one filter per owner is marked
+//! as the default applied when the ticket list is opened without filters.
+
+use chrono::{DateTime, Utc};
+use qa_pms_jira::TicketFilters;
+use serde::Serialize;
+use sqlx::{FromRow, PgPool};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+const COLUMNS: &str =
+    "id, owner, name, statuses, project, assignee, jql, is_default, created_at, updated_at";
+
+/// A named set of ticket filters.
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SavedFilter {
+    /// Filter ID
+    pub id: Uuid,
+    /// Owner
+    pub owner: String,
+    /// Name, unique per owner
+    pub name: String,
+    /// Statuses to include (empty for any)
+    pub statuses: Vec<String>,
+    /// Project key
+    pub project: Option<String>,
+    /// Assignee email or account ID
+    pub assignee: Option<String>,
+    /// Extra JQL condition
+    pub jql: Option<String>,
+    /// Applied when tickets are listed without filters
+    pub is_default: bool,
+    /// Creation time
+    pub created_at: DateTime<Utc>,
+    /// Last update time
+    pub updated_at: DateTime<Utc>,
+}
+
+impl SavedFilter {
+    /// Ticket search filters this saved filter stands for.
+    #[must_use]
+    pub fn ticket_filters(&self) -> TicketFilters {
+        TicketFilters {
+            statuses: self.statuses.clone(),
+            assignee: self.assignee.clone(),
+            project: self.project.clone(),
+            jql: self.jql.clone(),
+            ..TicketFilters::default()
+        }
+    }
+}
+
+/// Fields of a filter being created or replaced.
+#[derive(Debug, Clone, Default)]
+pub struct FilterFields {
+    /// Name, unique per owner
+    pub name: String,
+    /// Statuses to include
+    pub statuses: Vec<String>,
+    /// Project key
+    pub project: Option<String>,
+    /// Assignee email or account ID
+    pub assignee: Option<String>,
+    /// Extra JQL condition
+    pub jql: Option<String>,
+}
+
+/// Outcome of a write that may clash with another filter's name.
+#[derive(Debug)]
+pub enum SaveOutcome {
+    /// The filter was written
+    Saved(SavedFilter),
+    /// The owner already has another filter with this name
+    NameTaken,
+    /// No filter with this ID belongs to the owner
+    NotFound,
+}
+
+/// Saved filter repository backed by the `saved_ticket_filters` table.
+#[derive(Debug, Clone)]
+pub struct SavedFilterStore {
+    db: PgPool,
+}
+
+impl SavedFilterStore {
+    /// Create a store on the given pool.
+    #[must_use]
+    pub const fn new(db: PgPool) -> Self {
+        Self { db }
+    }
+
+    /// An owner's filters, by name.
+    pub async fn list(&self, owner: &str) -> Result<Vec<SavedFilter>, sqlx::Error> {
+        sqlx::query_as(&format!(
+            "SELECT {COLUMNS} FROM saved_ticket_filters WHERE owner = $1 ORDER BY name"
+        ))
+        .bind(owner)
+        .fetch_all(&self.db)
+        .await
+    }
+
+    /// A filter by ID, whoever owns it.
+    pub async fn get(&self, id: Uuid) -> Result<Option<SavedFilter>, sqlx::Error> {
+        sqlx::query_as(&format!(
+            "SELECT {COLUMNS} FROM saved_ticket_filters WHERE id = $1"
+        ))
+        .bind(id)
+        .fetch_optional(&self.db)
+        .await
+    }
+
+    /// The owner's default filter, if one is set.
+    pub async fn default_for(&self, owner: &str) -> Result<Option<SavedFilter>, sqlx::Error> {
+        sqlx::query_as(&format!(
+            "SELECT {COLUMNS} FROM saved_ticket_filters WHERE owner = $1 AND is_default"
+        ))
+        .bind(owner)
+        .fetch_optional(&self.db)
+        .await
+    }
+
+    /// Save a new filter for `owner`.
+    pub async fn create(
+        &self,
+        owner: &str,
+        fields: &FilterFields,
+    ) -> Result<SaveOutcome, sqlx::Error> {
+        let row: Option<SavedFilter> = sqlx::query_as(&format!(
+            r"
+            INSERT INTO saved_ticket_filters
+                (id, owner, name, statuses, project, assignee, jql, is_default, created_at, updated_at)
+            SELECT $1, $2, $3, $4, $5, $6, $7, FALSE, NOW(), NOW()
+            WHERE NOT EXISTS (
+                SELECT 1 FROM saved_ticket_filters WHERE owner = $2 AND name = $3
+            )
+            RETURNING {COLUMNS}
+            "
+        ))
+        .bind(Uuid::new_v4())
+        .bind(owner)
+        .bind(&fields.name)
+        .bind(&fields.statuses)
+        .bind(&fields.project)
+        .bind(&fields.assignee)
+        .bind(&fields.jql)
+        .fetch_optional(&self.db)
+        .await?;
+        Ok(row.map_or(SaveOutcome::NameTaken, SaveOutcome::Saved))
+    }
+
+    /// Replace the fields of one of `owner`'s filters.
+    pub async fn update(
+        &self,
+        owner: &str,
+        id: Uuid,
+        fields: &FilterFields,
+    ) -> Result<SaveOutcome, sqlx::Error> {
+        let row: Option<SavedFilter> = sqlx::query_as(&format!(
+            r"
+            UPDATE saved_ticket_filters
+            SET name = $3, statuses = $4, project = $5, assignee = $6, jql = $7,
+                updated_at = NOW()
+            WHERE id = $1 AND owner = $2 AND NOT EXISTS (
+                SELECT 1 FROM saved_ticket_filters
+                WHERE owner = $2 AND name = $3 AND id <> $1
+            )
+            RETURNING {COLUMNS}
+            "
+        ))
+        .bind(id)
+        .bind(owner)
+        .bind(&fields.name)
+        .bind(&fields.statuses)
+        .bind(&fields.project)
+        .bind(&fields.assignee)
+        .bind(&fields.jql)
+        .fetch_optional(&self.db)
+        .await?;
+        if let Some(filter) = row {
+            return Ok(SaveOutcome::Saved(filter));
+        }
+        let exists = self.get(id).await?.is_some_and(|f| f.owner == owner);
+        Ok(if exists {
+            SaveOutcome::NameTaken
+        } else {
+            SaveOutcome::NotFound
+        })
+    }
+
+    /// Delete one of `owner`'s filters. Returns whether it existed.
+    pub async fn delete(&self, owner: &str, id: Uuid) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query("DELETE FROM saved_ticket_filters WHERE id = $1 AND owner = $2")
+            .bind(id)
+            .bind(owner)
+            .execute(&self.db)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Make one of `owner`'s filters the default, unsetting any other.
+    ///
+    /// Returns `None` when no such filter belongs to the owner, leaving the
+    /// current default in place.
+    pub async fn set_default(
+        &self,
+        owner: &str,
+        id: Uuid,
+    ) -> Result<Option<SavedFilter>, sqlx::Error> {
+        let mut tx = self.db.begin().await?;
+        sqlx::query(
+            "UPDATE saved_ticket_filters SET is_default = FALSE WHERE owner = $1 AND is_default",
+        )
+        .bind(owner)
+        .execute(&mut *tx)
+        .await?;
+        let row: Option<SavedFilter> = sqlx::query_as(&format!(
+            r"
+            UPDATE saved_ticket_filters SET is_default = TRUE, updated_at = NOW()
+            WHERE id = $1 AND owner = $2
+            RETURNING {COLUMNS}
+            "
+        ))
+        .bind(id)
+        .bind(owner)
+        .fetch_optional(&mut *tx)
+        .await?;
+        if row.is_some() {
+            tx.commit().await?;
+        }
+        Ok(row)
+    }
+
+    /// Stop one of `owner`'s filters being the default. Returns whether
+    /// the filter exists.
+    pub async fn unset_default(&self, owner: &str, id: Uuid) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            "UPDATE saved_ticket_filters SET is_default = FALSE WHERE id = $1 AND owner = $2",
+        )
+        .bind(id)
+        .bind(owner)
+        .execute(&self.db)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+}
@@ -0,0 +1,126 @@
+//! Short-lived cache of complete search results.
+//!
+//! A search is run once and its full ranked result list kept for a short
+//! time, so paging through it with a cursor reads from memory instead of
+//! querying every source again. Entries are not invalidated when items
+//! change; the time-to-live bounds how stale a page can be.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+use crate::routes::search::SearchResults;
+
+/// Most searches kept at once; the oldest is dropped to make room.
+const MAX_ENTRIES: usize = 256;
+
+struct CacheEntry {
+    stored_at: Instant,
+    /// Insertion order, to find the oldest entry
+    seq: u64,
+    results: Arc<SearchResults>,
+}
+
+#[derive(Default)]
+struct Entries {
+    by_key: HashMap<String, CacheEntry>,
+    next_seq: u64,
+}
+
+/// Search results keyed by query, expiring after a fixed time-to-live.
+pub struct SearchCache {
+    ttl: Duration,
+    entries: Mutex<Entries>,
+}
+
+impl SearchCache {
+    /// Create a cache; a zero `ttl` disables caching.
+    #[must_use]
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: Mutex::new(Entries::default()),
+        }
+    }
+
+    /// Results stored under `key`, unless expired.
+    #[must_use]
+    pub fn get(&self, key: &str) -> Option<Arc<SearchResults>> {
+        let entries = self.entries.lock().unwrap_or_else(PoisonError::into_inner);
+        entries
+            .by_key
+            .get(key)
+            .filter(|entry| entry.stored_at.elapsed() < self.ttl)
+            .map(|entry| Arc::clone(&entry.results))
+    }
+
+    /// Store results under `key`, pruning expired entries first.
+    pub fn insert(&self, key: String, results: Arc<SearchResults>) {
+        if self.ttl.is_zero() {
+            return;
+        }
+        let mut entries = self.entries.lock().unwrap_or_else(PoisonError::into_inner);
+        let ttl = self.ttl;
+        entries
+            .by_key
+            .retain(|_, entry| entry.stored_at.elapsed() < ttl);
+        if entries.by_key.len() >= MAX_ENTRIES {
+            let oldest = entries
+                .by_key
+                .iter()
+                .min_by_key(|(_, entry)| entry.seq)
+                .map(|(key, _)| key.clone());
+            if let Some(oldest) = oldest {
+                entries.by_key.remove(&oldest);
+            }
+        }
+        let seq = entries.next_seq;
+        entries.next_seq += 1;
+        entries.by_key.insert(
+            key,
+            CacheEntry {
+                stored_at: Instant::now(),
+                seq,
+                results,
+            },
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn results(keyword: &str) -> Arc<SearchResults> {
+        Arc::new(SearchResults {
+            keywords_used: vec![keyword.to_string()],
+            ..SearchResults::default()
+        })
+    }
+
+    #[test]
+    fn test_get_returns_stored_results() {
+        let cache = SearchCache::new(Duration::from_secs(60));
+        assert!(cache.get("login").is_none());
+        cache.insert("login".to_string(), results("login"));
+        let cached = cache.get("login").unwrap();
+        assert_eq!(cached.keywords_used, vec!["login".to_string()]);
+    }
+
+    #[test]
+    fn test_zero_ttl_disables_cache() {
+        let cache = SearchCache::new(Duration::ZERO);
+        cache.insert("login".to_string(), results("login"));
+        assert!(cache.get("login").is_none());
+    }
+
+    #[test]
+    fn test_full_cache_drops_oldest_entry() {
+        let cache = SearchCache::new(Duration::from_secs(60));
+        for i in 0..=MAX_ENTRIES {
+            cache.insert(format!("query-{i}"), results("x"));
+        }
+        assert!(cache.get("query-0").is_none());
+        assert!(cache.get(&format!("query-{MAX_ENTRIES}")).is_some());
+    }
+}
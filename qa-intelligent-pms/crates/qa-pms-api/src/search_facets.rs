@@ -0,0 +1,311 @@
+//! Facets and filters for unified search.
+//!
+//! Facet counts tell the user how many results each filter value would leave.
+//! Each facet is counted over the results that pass every *other* filter, so
+//! picking a source still shows how many hits the remaining sources have.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::routes::search::UnifiedSearchResult;
+
+/// Most tag values returned in the tag facet.
+const MAX_TAG_FACETS: usize = 20;
+
+/// Update-age ranges offered in the date facet, as (name, days).
+const DATE_RANGES: [(&str, i64); 4] = [
+    ("last7Days", 7),
+    ("last30Days", 30),
+    ("last90Days", 90),
+    ("last365Days", 365),
+];
+
+/// Filters narrowing unified search results.
+///
+/// Values within one filter are alternatives; different filters must all
+/// match. Source, type and tag values are compared case-insensitively.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SearchFilters {
+    /// Keep only these sources (postman, testmo, jira, kb, errors, splunk, report)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub sources: Vec<String>,
+    /// Keep only these item types (collection, testCase, ticket, ...)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub types: Vec<String>,
+    /// Keep items carrying at least one of these tags
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Keep items updated at or after this time
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub updated_after: Option<DateTime<Utc>>,
+    /// Keep items updated before this time
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub updated_before: Option<DateTime<Utc>>,
+}
+
+impl SearchFilters {
+    /// Whether no filter is set.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+
+    /// Whether a result passes every filter.
+    ///
+    /// Results without an update time fail any date filter.
+    #[must_use]
+    pub fn matches(&self, result: &UnifiedSearchResult) -> bool {
+        let in_list = |values: &[String], value: &str| {
+            values.is_empty() || values.iter().any(|v| v.eq_ignore_ascii_case(value))
+        };
+        let in_range = match (self.updated_after, self.updated_before) {
+            (None, None) => true,
+            (after, before) => result.updated_at.is_some_and(|updated| {
+                after.map_or(true, |after| updated >= after)
+                    && before.map_or(true, |before| updated < before)
+            }),
+        };
+        in_list(&self.sources, &result.source)
+            && in_list(&self.types, &result.result_type)
+            && (self.tags.is_empty() || result.tags.iter().any(|tag| in_list(&self.tags, tag)))
+            && in_range
+    }
+
+    /// Drop results that fail the filters.
+    pub fn apply(&self, results: &mut Vec<UnifiedSearchResult>) {
+        if !self.is_empty() {
+            results.retain(|result| self.matches(result));
+        }
+    }
+}
+
+/// Number of results sharing a facet value.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct FacetCount {
+    /// Facet value
+    pub value: String,
+    /// Matching results
+    pub count: usize,
+}
+
+/// Number of results updated within a range ending now.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DateRangeFacet {
+    /// Range name (last7Days, last30Days, last90Days, last365Days)
+    pub range: String,
+    /// Start of the range, usable as the `updatedAfter` filter
+    pub updated_after: DateTime<Utc>,
+    /// Matching results
+    pub count: usize,
+}
+
+/// Facet counts for a set of search results.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchFacets {
+    /// Results per source
+    pub sources: Vec<FacetCount>,
+    /// Results per item type
+    pub types: Vec<FacetCount>,
+    /// Results per tag, most common first
+    pub tags: Vec<FacetCount>,
+    /// Results per update-age range
+    pub updated: Vec<DateRangeFacet>,
+}
+
+impl SearchFacets {
+    /// Count facets over `results` under `filters`.
+    ///
+    /// `results` are the hits before filtering.
+    #[must_use]
+    pub fn compute(
+        results: &[UnifiedSearchResult],
+        filters: &SearchFilters,
+        now: DateTime<Utc>,
+    ) -> Self {
+        let passing = |clear: fn(&mut SearchFilters)| {
+            let mut others = filters.clone();
+            clear(&mut others);
+            results.iter().filter(move |result| others.matches(result))
+        };
+
+        let sources = count_values(passing(|f| f.sources.clear()).map(|r| r.source.clone()));
+        let types = count_values(
+            passing(|f| f.types.clear())
+                .filter(|r| !r.result_type.is_empty())
+                .map(|r| r.result_type.clone()),
+        );
+        let mut tags = count_values(
+            passing(|f| f.tags.clear()).flat_map(|r| r.tags.iter().map(|tag| tag.to_lowercase())),
+        );
+        tags.truncate(MAX_TAG_FACETS);
+
+        let dated: Vec<DateTime<Utc>> = passing(|f| {
+            f.updated_after = None;
+            f.updated_before = None;
+        })
+        .filter_map(|r| r.updated_at)
+        .collect();
+        let updated = DATE_RANGES
+            .iter()
+            .map(|(range, days)| {
+                let updated_after = now - Duration::days(*days);
+                DateRangeFacet {
+                    range: (*range).to_string(),
+                    updated_after,
+                    count: dated.iter().filter(|at| **at >= updated_after).count(),
+                }
+            })
+            .collect();
+
+        Self {
+            sources,
+            types,
+            tags,
+            updated,
+        }
+    }
+}
+
+/// Count occurrences, most common first and then by value.
+fn count_values(values: impl Iterator<Item = String>) -> Vec<FacetCount> {
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for value in values {
+        *counts.entry(value).or_default() += 1;
+    }
+    let mut facets: Vec<FacetCount> = counts
+        .into_iter()
+        .map(|(value, count)| FacetCount { value, count })
+        .collect();
+    facets.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.value.cmp(&b.value)));
+    facets
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(
+        source: &str,
+        result_type: &str,
+        tags: &[&str],
+        updated_at: Option<DateTime<Utc>>,
+    ) -> UnifiedSearchResult {
+        UnifiedSearchResult {
+            source: source.to_string(),
+            result_type: result_type.to_string(),
+            id: format!("{source}-{}", tags.join("-")),
+            name: "Checkout".to_string(),
+            description: None,
+            url: String::new(),
+            score: 1.0,
+            matches: vec![],
+            tags: tags.iter().map(|t| (*t).to_string()).collect(),
+            updated_at,
+        }
+    }
+
+    fn count(facets: &[FacetCount], value: &str) -> usize {
+        facets
+            .iter()
+            .find(|f| f.value == value)
+            .map_or(0, |f| f.count)
+    }
+
+    #[test]
+    fn test_filters_combine_sources_and_dates() {
+        let now = Utc::now();
+        let filters = SearchFilters {
+            sources: vec!["Testmo".to_string()],
+            updated_after: Some(now - Duration::days(90)),
+            ..SearchFilters::default()
+        };
+        let mut results = vec![
+            result("testmo", "testCase", &[], Some(now - Duration::days(10))),
+            result("testmo", "testCase", &[], Some(now - Duration::days(200))),
+            result("testmo", "testCase", &[], None),
+            result("jira", "ticket", &[], Some(now)),
+        ];
+        filters.apply(&mut results);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].updated_at, Some(now - Duration::days(10)));
+    }
+
+    #[test]
+    fn test_tag_filter_matches_any_tag() {
+        let filters = SearchFilters {
+            tags: vec!["SMOKE".to_string(), "payments".to_string()],
+            ..SearchFilters::default()
+        };
+        assert!(filters.matches(&result("testmo", "testCase", &["smoke"], None)));
+        assert!(filters.matches(&result("kb", "kbEntry", &["payments", "gateway"], None)));
+        assert!(!filters.matches(&result("kb", "kbEntry", &["gateway"], None)));
+        assert!(!filters.matches(&result("postman", "collection", &[], None)));
+    }
+
+    #[test]
+    fn test_facets_ignore_their_own_filter() {
+        let now = Utc::now();
+        let results = vec![
+            result(
+                "testmo",
+                "testCase",
+                &["smoke"],
+                Some(now - Duration::days(3)),
+            ),
+            result(
+                "testmo",
+                "testCase",
+                &["Smoke"],
+                Some(now - Duration::days(45)),
+            ),
+            result("jira", "ticket", &["smoke"], None),
+            result("postman", "collection", &[], None),
+        ];
+        let filters = SearchFilters {
+            sources: vec!["testmo".to_string()],
+            ..SearchFilters::default()
+        };
+        let facets = SearchFacets::compute(&results, &filters, now);
+
+        // Source counts still cover every source
+        assert_eq!(count(&facets.sources, "testmo"), 2);
+        assert_eq!(count(&facets.sources, "jira"), 1);
+        assert_eq!(count(&facets.sources, "postman"), 1);
+        // Other facets only count Testmo results
+        assert_eq!(
+            facets.types,
+            vec![FacetCount {
+                value: "testCase".to_string(),
+                count: 2
+            }]
+        );
+        assert_eq!(
+            facets.tags,
+            vec![FacetCount {
+                value: "smoke".to_string(),
+                count: 2
+            }]
+        );
+        let updated: Vec<(&str, usize)> = facets
+            .updated
+            .iter()
+            .map(|f| (f.range.as_str(), f.count))
+            .collect();
+        assert_eq!(
+            updated,
+            vec![
+                ("last7Days", 1),
+                ("last30Days", 1),
+                ("last90Days", 2),
+                ("last365Days", 2)
+            ]
+        );
+    }
+}
@@ -0,0 +1,869 @@
+//! Local full-text search index.
+//!
+//! Tickets, test cases, knowledge base entries, error logs, Splunk query
+//! templates and reports are mirrored into an in-memory tantivy index, so unified search is
+//! a local lookup rather than a round of calls to every integration. The index
+//! is filled from the database at startup and then kept current by the routes
+//! that change those items. Tickets are indexed as they are fetched from Jira.
+
+use std::sync::Mutex;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use qa_pms_core::matching::typo_budget;
+use qa_pms_core::TestCase;
+use qa_pms_splunk::{QueryTemplate, QueryTemplateService};
+use qa_pms_core::types::{Cursor, PageRequest, SortDirection, MAX_PAGE_LIMIT};
+use qa_pms_support::{ErrorLog, ErrorLogFilter, KnowledgeBaseEntry, Pagination, SupportRepository};
+use sqlx::PgPool;
+use tantivy::collector::TopDocs;
+use tantivy::query::{BooleanQuery, BoostQuery, FuzzyTermQuery, Occur, Query, QueryParser};
+use tantivy::schema::{
+    Field, IndexRecordOption, Schema, TantivyDocument, TextFieldIndexing, TextOptions, Value, FAST,
+    INDEXED, STORED, STRING,
+};
+use tantivy::tokenizer::TokenStream;
+use tantivy::{doc, Index, IndexReader, IndexWriter, ReloadPolicy, Term};
+use tracing::{debug, info, warn};
+
+use crate::routes::reports::{self, ReportResponse};
+use crate::routes::search::UnifiedSearchResult;
+
+/// Memory budget for the index writer.
+const WRITER_MEMORY_BYTES: usize = 50_000_000;
+
+/// Extra weight of a match in an item's name over one in its body.
+const NAME_BOOST: f32 = 2.0;
+
+/// Weight of a typo-tolerant match relative to an exact one.
+const FUZZY_BOOST: f32 = 0.5;
+
+/// Page size used when loading knowledge base entries.
+const KB_PAGE_SIZE: i32 = 500;
+
+/// Most error logs indexed at startup, most recently seen first.
+const MAX_INDEXED_ERROR_LOGS: usize = 10_000;
+
+/// Longest error message used as a result title.
+const ERROR_TITLE_CHARS: usize = 200;
+
+/// Kind of item held in the index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IndexSource {
+    /// Jira ticket
+    Ticket,
+    /// Local test case (synced with Testmo)
+    TestCase,
+    /// Support knowledge base entry
+    KnowledgeBase,
+    /// Captured application error
+    ErrorLog,
+    /// Splunk query template
+    SplunkTemplate,
+    /// Generated workflow report
+    Report,
+}
+
+impl IndexSource {
+    /// Source name reported in search results.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Ticket => "jira",
+            Self::TestCase => "testmo",
+            Self::KnowledgeBase => "kb",
+            Self::ErrorLog => "errors",
+            Self::SplunkTemplate => "splunk",
+            Self::Report => "report",
+        }
+    }
+
+    /// Item type reported in search results.
+    #[must_use]
+    pub const fn result_type(self) -> &'static str {
+        match self {
+            Self::Ticket => "ticket",
+            Self::TestCase => "testCase",
+            Self::KnowledgeBase => "kbEntry",
+            Self::ErrorLog => "errorLog",
+            Self::SplunkTemplate => "queryTemplate",
+            Self::Report => "report",
+        }
+    }
+}
+
+/// An item as stored in the index.
+#[derive(Debug, Clone)]
+pub struct IndexDocument {
+    /// Kind of item
+    pub source: IndexSource,
+    /// Item ID within its source
+    pub id: String,
+    /// Title
+    pub name: String,
+    /// Short description shown in results
+    pub description: Option<String>,
+    /// Full searchable text
+    pub body: String,
+    /// Where to view the item
+    pub url: String,
+    /// Labels offered as search facets
+    pub tags: Vec<String>,
+    /// Last modification
+    pub updated_at: DateTime<Utc>,
+}
+
+impl IndexDocument {
+    /// Index entry for a Jira ticket.
+    #[must_use]
+    pub fn ticket(
+        key: &str,
+        summary: &str,
+        description: Option<String>,
+        labels: &[String],
+        updated: &str,
+    ) -> Self {
+        let body = join_text(
+            description
+                .iter()
+                .map(String::as_str)
+                .chain(labels.iter().map(String::as_str)),
+        );
+        Self {
+            source: IndexSource::Ticket,
+            id: key.to_string(),
+            name: format!("{key}: {summary}"),
+            description,
+            body,
+            url: format!("/api/v1/tickets/{key}"),
+            tags: labels.to_vec(),
+            updated_at: parse_timestamp(updated),
+        }
+    }
+
+    /// Index entry for a local test case; linked cases point at Testmo.
+    #[must_use]
+    pub fn test_case(case: &TestCase, testmo_base_url: Option<&str>) -> Self {
+        let steps = case
+            .steps
+            .iter()
+            .flat_map(|step| std::iter::once(step.action.as_str()).chain(step.expected.as_deref()));
+        let body = join_text(
+            case.preconditions
+                .as_deref()
+                .into_iter()
+                .chain(steps)
+                .chain(case.category.as_deref())
+                .chain(case.tags.iter().map(String::as_str)),
+        );
+        let url = match (&case.remote, testmo_base_url) {
+            (Some(remote), Some(base)) => format!(
+                "{}/projects/{}/cases/{}",
+                base.trim_end_matches('/'),
+                remote.project_id,
+                remote.remote_id
+            ),
+            _ => String::new(),
+        };
+        Self {
+            source: IndexSource::TestCase,
+            id: case.id.to_string(),
+            name: case.title.clone(),
+            description: case.preconditions.clone(),
+            body,
+            url,
+            tags: case.tags.clone(),
+            updated_at: case.updated_at,
+        }
+    }
+
+    /// Index entry for a knowledge base entry.
+    #[must_use]
+    pub fn kb_entry(entry: &KnowledgeBaseEntry) -> Self {
+        let body = join_text(
+            [
+                entry.problem.as_str(),
+                entry.cause.as_str(),
+                entry.solution.as_str(),
+            ]
+            .into_iter()
+            .chain(entry.related_errors.iter().map(String::as_str))
+            .chain(entry.tags.iter().map(String::as_str)),
+        );
+        Self {
+            source: IndexSource::KnowledgeBase,
+            id: entry.id.to_string(),
+            name: entry.title.clone(),
+            description: Some(entry.problem.clone()),
+            body,
+            url: format!("/api/v1/support/kb/{}", entry.id),
+            tags: entry.tags.clone(),
+            updated_at: entry.updated_at,
+        }
+    }
+
+    /// Index entry for an error log, titled by the first line of its message.
+    #[must_use]
+    pub fn error_log(error: &ErrorLog) -> Self {
+        let title: String = error
+            .message
+            .lines()
+            .next()
+            .unwrap_or_default()
+            .chars()
+            .take(ERROR_TITLE_CHARS)
+            .collect();
+        let body = join_text(
+            [error.message.as_str()]
+                .into_iter()
+                .chain(error.action.as_deref())
+                .chain(error.page_url.as_deref())
+                .chain(error.resolution_notes.as_deref()),
+        );
+        Self {
+            source: IndexSource::ErrorLog,
+            id: error.id.to_string(),
+            name: title,
+            description: error.resolution_notes.clone(),
+            body,
+            url: format!("/api/v1/support/errors/{}", error.id),
+            tags: vec![
+                error.severity.to_string(),
+                error.source.to_string(),
+                error.status.to_string(),
+            ],
+            updated_at: error.last_seen_at,
+        }
+    }
+
+    /// Index entry for a Splunk query template.
+    #[must_use]
+    pub fn splunk_template(template: &QueryTemplate) -> Self {
+        let category = template.category.to_string();
+        let body = join_text(
+            template
+                .description
+                .as_deref()
+                .into_iter()
+                .chain([template.query.as_str(), category.as_str()]),
+        );
+        Self {
+            source: IndexSource::SplunkTemplate,
+            id: template.id.to_string(),
+            name: template.name.clone(),
+            description: template.description.clone(),
+            body,
+            url: format!("/api/v1/splunk/templates/{}", template.id),
+            tags: vec![category],
+            updated_at: template.updated_at,
+        }
+    }
+
+    /// Index entry for a generated report.
+    #[must_use]
+    pub fn report(report: &ReportResponse) -> Self {
+        let content = &report.content;
+        let steps = content
+            .steps
+            .iter()
+            .flat_map(|step| std::iter::once(step.name.as_str()).chain(step.notes.as_deref()));
+        let body = join_text(
+            report
+                .ticket_title
+                .as_deref()
+                .into_iter()
+                .chain([report.template_name.as_str()])
+                .chain(steps)
+                .chain(content.notes.iter().map(String::as_str))
+                .chain(content.tests_covered.iter().map(String::as_str))
+                .chain(content.strategies.iter().map(String::as_str)),
+        );
+        Self {
+            source: IndexSource::Report,
+            id: report.id.to_string(),
+            name: format!("{} report: {}", report.ticket_id, report.template_name),
+            description: report.ticket_title.clone(),
+            body,
+            url: format!("/api/v1/reports/{}", report.id),
+            tags: vec![],
+            updated_at: parse_timestamp(&report.generated_at),
+        }
+    }
+}
+
+fn join_text<'a>(parts: impl IntoIterator<Item = &'a str>) -> String {
+    parts
+        .into_iter()
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn parse_timestamp(value: &str) -> DateTime<Utc> {
+    DateTime::parse_from_rfc3339(value)
+        .or_else(|_| DateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S%.f%z"))
+        .map_or_else(|_| Utc::now(), |ts| ts.with_timezone(&Utc))
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Fields {
+    key: Field,
+    source: Field,
+    result_type: Field,
+    id: Field,
+    name: Field,
+    description: Field,
+    body: Field,
+    url: Field,
+    tag: Field,
+    updated_at: Field,
+}
+
+impl Fields {
+    fn schema() -> (Schema, Self) {
+        let text = TextOptions::default().set_indexing_options(
+            TextFieldIndexing::default()
+                .set_tokenizer("en_stem")
+                .set_index_option(IndexRecordOption::WithFreqsAndPositions),
+        );
+
+        let mut builder = Schema::builder();
+        let fields = Self {
+            key: builder.add_text_field("key", STRING),
+            source: builder.add_text_field("source", STRING | STORED),
+            result_type: builder.add_text_field("result_type", STORED),
+            id: builder.add_text_field("id", STORED),
+            name: builder.add_text_field("name", text.clone() | STORED),
+            description: builder.add_text_field("description", STORED),
+            body: builder.add_text_field("body", text | STORED),
+            url: builder.add_text_field("url", STORED),
+            tag: builder.add_text_field("tag", STORED),
+            updated_at: builder.add_i64_field("updated_at", INDEXED | STORED | FAST),
+        };
+        (builder.build(), fields)
+    }
+}
+
+fn doc_key(source: IndexSource, id: &str) -> String {
+    format!("{}:{id}", source.as_str())
+}
+
+fn stored_str(doc: &TantivyDocument, field: Field) -> Option<String> {
+    doc.get_first(field)
+        .and_then(|value| value.as_str())
+        .map(String::from)
+}
+
+/// In-memory full-text index over local items.
+pub struct SearchIndex {
+    index: Index,
+    fields: Fields,
+    writer: Mutex<IndexWriter>,
+    reader: IndexReader,
+    testmo_base_url: Option<String>,
+    fuzzy_distance: u8,
+}
+
+impl SearchIndex {
+    /// Create an empty index.
+    ///
+    /// `testmo_base_url` is used to link indexed test cases to Testmo;
+    /// `fuzzy_distance` is the most edits tolerated in a search term.
+    ///
+    /// # Errors
+    /// Returns error if the index cannot be created.
+    pub fn new(testmo_base_url: Option<String>, fuzzy_distance: u8) -> Result<Self> {
+        let (schema, fields) = Fields::schema();
+        let index = Index::create_in_ram(schema);
+        let writer = index
+            .writer(WRITER_MEMORY_BYTES)
+            .context("Failed to create search index writer")?;
+        let reader = index
+            .reader_builder()
+            .reload_policy(ReloadPolicy::Manual)
+            .try_into()
+            .context("Failed to create search index reader")?;
+        Ok(Self {
+            index,
+            fields,
+            writer: Mutex::new(writer),
+            reader,
+            testmo_base_url,
+            fuzzy_distance,
+        })
+    }
+
+    /// Base URL used for Testmo links.
+    #[must_use]
+    pub fn testmo_base_url(&self) -> Option<&str> {
+        self.testmo_base_url.as_deref()
+    }
+
+    fn writer(&self) -> Result<std::sync::MutexGuard<'_, IndexWriter>> {
+        self.writer
+            .lock()
+            .map_err(|_| anyhow::anyhow!("Search index writer lock poisoned"))
+    }
+
+    fn commit(&self, writer: &mut IndexWriter) -> Result<()> {
+        writer.commit().context("Failed to commit search index")?;
+        self.reader
+            .reload()
+            .context("Failed to reload search index")
+    }
+
+    /// Add or replace items.
+    ///
+    /// The index is a convenience copy, so a failed write is logged rather
+    /// than failing the change that triggered it.
+    pub fn upsert(&self, documents: impl IntoIterator<Item = IndexDocument>) {
+        if let Err(e) = self.try_upsert(documents) {
+            warn!(error = %e, "Failed to update search index");
+        }
+    }
+
+    /// Drop an item; failures are logged.
+    pub fn remove(&self, source: IndexSource, id: &str) {
+        let result = self.writer().and_then(|mut writer| {
+            writer.delete_term(Term::from_field_text(self.fields.key, &doc_key(source, id)));
+            self.commit(&mut writer)
+        });
+        if let Err(e) = result {
+            warn!(error = %e, source = source.as_str(), id, "Failed to remove item from search index");
+        }
+    }
+
+    fn try_upsert(&self, documents: impl IntoIterator<Item = IndexDocument>) -> Result<()> {
+        let f = self.fields;
+        let mut writer = self.writer()?;
+        for document in documents {
+            let key = doc_key(document.source, &document.id);
+            writer.delete_term(Term::from_field_text(f.key, &key));
+            let mut entry = doc!(
+                f.key => key,
+                f.source => document.source.as_str(),
+                f.result_type => document.source.result_type(),
+                f.id => document.id,
+                f.name => document.name,
+                f.body => document.body,
+                f.url => document.url,
+                f.updated_at => document.updated_at.timestamp(),
+            );
+            if let Some(description) = document.description {
+                entry.add_text(f.description, description);
+            }
+            for tag in document.tags {
+                entry.add_text(f.tag, tag);
+            }
+            writer.add_document(entry)?;
+        }
+        self.commit(&mut writer)
+    }
+
+    /// Replace every item of one source in a single commit.
+    ///
+    /// # Errors
+    /// Returns error if the index cannot be written.
+    pub fn replace_source(
+        &self,
+        source: IndexSource,
+        documents: impl IntoIterator<Item = IndexDocument>,
+    ) -> Result<()> {
+        self.writer()?
+            .delete_term(Term::from_field_text(self.fields.source, source.as_str()));
+        self.try_upsert(documents)
+    }
+
+    /// Number of indexed items.
+    #[must_use]
+    pub fn num_docs(&self) -> u64 {
+        self.reader.searcher().num_docs()
+    }
+
+    /// Typo-tolerant alternative for each search term long enough to allow one.
+    fn fuzzy_query(&self, text: &str) -> Result<Option<Box<dyn Query>>> {
+        if self.fuzzy_distance == 0 {
+            return Ok(None);
+        }
+        let f = self.fields;
+        let mut terms = Vec::new();
+        self.index
+            .tokenizer_for_field(f.body)?
+            .token_stream(text)
+            .process(&mut |token| {
+                let budget = typo_budget(token.text.chars().count());
+                let distance = u8::try_from(budget)
+                    .unwrap_or(u8::MAX)
+                    .min(self.fuzzy_distance);
+                if distance > 0 {
+                    terms.push((token.text.clone(), distance));
+                }
+            });
+
+        let clauses: Vec<(Occur, Box<dyn Query>)> = terms
+            .iter()
+            .flat_map(|(term, distance)| {
+                [f.name, f.body].map(|field| {
+                    let query =
+                        FuzzyTermQuery::new(Term::from_field_text(field, term), *distance, true);
+                    (Occur::Should, Box::new(query) as Box<dyn Query>)
+                })
+            })
+            .collect();
+        Ok((!clauses.is_empty()).then(|| {
+            Box::new(BoostQuery::new(
+                Box::new(BooleanQuery::new(clauses)),
+                FUZZY_BOOST,
+            )) as Box<dyn Query>
+        }))
+    }
+
+    /// Search by keywords, best match first.
+    ///
+    /// Exact (stemmed) matches rank above typo-tolerant ones.
+    ///
+    /// # Errors
+    /// Returns error if the index cannot be read.
+    pub fn search(&self, keywords: &[String], limit: usize) -> Result<Vec<UnifiedSearchResult>> {
+        let f = self.fields;
+        let text = keywords.join(" ");
+        if text.trim().is_empty() || limit == 0 {
+            return Ok(vec![]);
+        }
+
+        let mut parser = QueryParser::for_index(&self.index, vec![f.name, f.body]);
+        parser.set_field_boost(f.name, NAME_BOOST);
+        let (exact, errors) = parser.parse_query_lenient(&text);
+        if !errors.is_empty() {
+            debug!(errors = errors.len(), "Ignored unparseable search terms");
+        }
+        let query: Box<dyn Query> = match self.fuzzy_query(&text)? {
+            Some(fuzzy) => Box::new(BooleanQuery::new(vec![
+                (Occur::Should, exact),
+                (Occur::Should, fuzzy),
+            ])),
+            None => exact,
+        };
+
+        let searcher = self.reader.searcher();
+        let hits = searcher.search(&query, &TopDocs::with_limit(limit))?;
+        let lowered: Vec<String> = keywords.iter().map(|k| k.to_lowercase()).collect();
+
+        hits.into_iter()
+            .map(|(score, address)| {
+                let doc: TantivyDocument = searcher.doc(address)?;
+                let name = stored_str(&doc, f.name).unwrap_or_default();
+                let haystack =
+                    format!("{}\n{}", name, stored_str(&doc, f.body).unwrap_or_default())
+                        .to_lowercase();
+                Ok(UnifiedSearchResult {
+                    source: stored_str(&doc, f.source).unwrap_or_default(),
+                    result_type: stored_str(&doc, f.result_type).unwrap_or_default(),
+                    id: stored_str(&doc, f.id).unwrap_or_default(),
+                    name,
+                    description: stored_str(&doc, f.description),
+                    url: stored_str(&doc, f.url).unwrap_or_default(),
+                    score,
+                    matches: lowered
+                        .iter()
+                        .filter(|k| haystack.contains(k.as_str()))
+                        .cloned()
+                        .collect(),
+                    tags: doc
+                        .get_all(f.tag)
+                        .filter_map(|value| value.as_str())
+                        .map(String::from)
+                        .collect(),
+                    updated_at: doc
+                        .get_first(f.updated_at)
+                        .and_then(|value| value.as_i64())
+                        .and_then(|ts| DateTime::from_timestamp(ts, 0)),
+                })
+            })
+            .collect()
+    }
+
+    /// Reload everything kept in the database and the test case repository.
+    ///
+    /// Tickets are not stored locally, so they are left as they are.
+    ///
+    /// # Errors
+    /// Returns error if the index cannot be written; sources that fail to load
+    /// are logged and skipped.
+    pub async fn rebuild(&self, db: &PgPool, test_cases: &[TestCase]) -> Result<()> {
+        let testmo = self.testmo_base_url();
+        self.replace_source(
+            IndexSource::TestCase,
+            test_cases
+                .iter()
+                .map(|case| IndexDocument::test_case(case, testmo)),
+        )?;
+
+        match load_kb_entries(db).await {
+            Ok(entries) => self.replace_source(
+                IndexSource::KnowledgeBase,
+                entries.iter().map(IndexDocument::kb_entry),
+            )?,
+            Err(e) => warn!(error = %e, "Failed to index knowledge base"),
+        }
+
+        match load_error_logs(db).await {
+            Ok(errors) => self.replace_source(
+                IndexSource::ErrorLog,
+                errors.iter().map(IndexDocument::error_log),
+            )?,
+            Err(e) => warn!(error = %e, "Failed to index error logs"),
+        }
+
+        match QueryTemplateService::new(db.clone())
+            .list_templates(None, None)
+            .await
+        {
+            Ok(templates) => self.replace_source(
+                IndexSource::SplunkTemplate,
+                templates.iter().map(IndexDocument::splunk_template),
+            )?,
+            Err(e) => warn!(error = %e, "Failed to index Splunk templates"),
+        }
+
+        match reports::all_reports(db).await {
+            Ok(reports) => self.replace_source(
+                IndexSource::Report,
+                reports.iter().map(IndexDocument::report),
+            )?,
+            Err(e) => warn!(error = %e, "Failed to index reports"),
+        }
+
+        info!(documents = self.num_docs(), "Search index rebuilt");
+        Ok(())
+    }
+}
+
+async fn load_kb_entries(db: &PgPool) -> Result<Vec<KnowledgeBaseEntry>> {
+    let repo = SupportRepository::new(db.clone());
+    let mut entries = Vec::new();
+    for page in 1.. {
+        let batch = repo
+            .list_kb_entries(
+                None,
+                Pagination {
+                    page,
+                    per_page: KB_PAGE_SIZE,
+                },
+            )
+            .await?
+            .items;
+        let done = batch.len() < KB_PAGE_SIZE as usize;
+        entries.extend(batch);
+        if done {
+            break;
+        }
+    }
+    Ok(entries)
+}
+
+async fn load_error_logs(db: &PgPool) -> Result<Vec<ErrorLog>> {
+    let repo = SupportRepository::new(db.clone());
+    let filter = ErrorLogFilter::default();
+    let mut errors: Vec<ErrorLog> = Vec::new();
+    let mut page = PageRequest::new(None, Some(MAX_PAGE_LIMIT), SortDirection::Desc);
+    while errors.len() < MAX_INDEXED_ERROR_LOGS {
+        let batch = page.finish(repo.list_error_logs(&filter, &page).await?, |e| {
+            (e.last_seen_at, e.id)
+        });
+        errors.extend(batch.data);
+        if !batch.page_info.has_more {
+            break;
+        }
+        let Some(last) = errors.last() else { break };
+        page = PageRequest::new(
+            Some(Cursor::Key {
+                at: last.last_seen_at,
+                id: last.id,
+            }),
+            Some(MAX_PAGE_LIMIT),
+            SortDirection::Desc,
+        );
+    }
+    errors.truncate(MAX_INDEXED_ERROR_LOGS);
+    Ok(errors)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn document(source: IndexSource, id: &str, name: &str, body: &str) -> IndexDocument {
+        IndexDocument {
+            source,
+            id: id.to_string(),
+            name: name.to_string(),
+            description: None,
+            body: body.to_string(),
+            url: format!("/items/{id}"),
+            tags: vec![],
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn keywords(words: &[&str]) -> Vec<String> {
+        words.iter().map(|w| (*w).to_string()).collect()
+    }
+
+    #[test]
+    fn test_search_across_sources() {
+        let index = SearchIndex::new(None, 1).unwrap();
+        let mut kb_entry = document(
+            IndexSource::KnowledgeBase,
+            "kb-1",
+            "Payment timeout",
+            "Gateway retries exhausted",
+        );
+        kb_entry.tags = keywords(&["payments", "gateway"]);
+        index.upsert([
+            kb_entry,
+            document(
+                IndexSource::TestCase,
+                "tc-1",
+                "Checkout pays by card",
+                "Payment is captured",
+            ),
+            document(
+                IndexSource::SplunkTemplate,
+                "sp-1",
+                "Login errors",
+                "index=auth failed",
+            ),
+        ]);
+
+        let results = index
+            .search(&keywords(&["payment", "timeout"]), 10)
+            .unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].id, "kb-1");
+        assert_eq!(results[0].source, "kb");
+        assert_eq!(results[0].result_type, "kbEntry");
+        assert_eq!(results[0].tags, keywords(&["payments", "gateway"]));
+        assert_eq!(results[0].matches, keywords(&["payment", "timeout"]));
+        assert_eq!(results[1].source, "testmo");
+    }
+
+    #[test]
+    fn test_upsert_replaces_and_remove_drops() {
+        let index = SearchIndex::new(None, 1).unwrap();
+        index.upsert([document(IndexSource::Report, "r-1", "Booking flow", "")]);
+        index.upsert([document(IndexSource::Report, "r-1", "Refund flow", "")]);
+        assert_eq!(index.num_docs(), 1);
+        assert!(index
+            .search(&keywords(&["booking"]), 10)
+            .unwrap()
+            .is_empty());
+        assert_eq!(index.search(&keywords(&["refunds"]), 10).unwrap().len(), 1);
+
+        index.remove(IndexSource::Report, "r-1");
+        assert_eq!(index.num_docs(), 0);
+    }
+
+    #[test]
+    fn test_replace_source_keeps_other_sources() {
+        let index = SearchIndex::new(None, 1).unwrap();
+        index.upsert([
+            document(IndexSource::Ticket, "PROJ-1", "Cart totals", ""),
+            document(IndexSource::TestCase, "tc-1", "Cart badge", ""),
+        ]);
+        index
+            .replace_source(
+                IndexSource::TestCase,
+                [document(IndexSource::TestCase, "tc-2", "Cart discount", "")],
+            )
+            .unwrap();
+
+        let mut ids: Vec<String> = index
+            .search(&keywords(&["cart"]), 10)
+            .unwrap()
+            .into_iter()
+            .map(|r| r.id)
+            .collect();
+        ids.sort();
+        assert_eq!(ids, ["PROJ-1", "tc-2"]);
+    }
+
+    #[test]
+    fn test_search_tolerates_typos() {
+        let index = SearchIndex::new(None, 1).unwrap();
+        index.upsert([
+            document(IndexSource::KnowledgeBase, "kb-1", "Payment timeout", ""),
+            document(IndexSource::KnowledgeBase, "kb-2", "Paymnet timeout", ""),
+        ]);
+
+        let results = index.search(&keywords(&["payment"]), 10).unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].id, "kb-1");
+        assert!(results[0].score > results[1].score);
+
+        let strict = SearchIndex::new(None, 0).unwrap();
+        strict.upsert([document(
+            IndexSource::KnowledgeBase,
+            "kb-2",
+            "Paymnet timeout",
+            "",
+        )]);
+        assert!(strict
+            .search(&keywords(&["payment"]), 10)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_linked_test_case_points_at_testmo() {
+        let mut case = TestCase::new("Apply voucher");
+        case.remote = Some(qa_pms_core::RemoteLink {
+            system: "testmo".to_string(),
+            project_id: "3".to_string(),
+            remote_id: "42".to_string(),
+            remote_updated_at: String::new(),
+            synced_at: Utc::now(),
+        });
+        let document = IndexDocument::test_case(&case, Some("https://acme.testmo.net/"));
+        assert_eq!(document.url, "https://acme.testmo.net/projects/3/cases/42");
+        assert!(IndexDocument::test_case(&case, None).url.is_empty());
+    }
+
+    #[test]
+    fn test_error_log_is_searchable_with_its_fix() {
+        let now = Utc::now();
+        let error = ErrorLog {
+            id: uuid::Uuid::new_v4(),
+            message: "Payment gateway timeout\n  at checkout.submit".to_string(),
+            stack_trace: None,
+            severity: qa_pms_support::ErrorSeverity::High,
+            source: qa_pms_support::ErrorSource::Integration,
+            status: qa_pms_support::ErrorStatus::Resolved,
+            user_id: None,
+            session_id: None,
+            page_url: Some("/checkout".to_string()),
+            action: None,
+            browser_info: None,
+            device_info: None,
+            context: serde_json::Value::Null,
+            occurrence_count: 3,
+            first_seen_at: now,
+            last_seen_at: now,
+            resolution_notes: Some("Raise the gateway retry budget".to_string()),
+            kb_entry_id: None,
+            created_at: now,
+            updated_at: now,
+        };
+        let document = IndexDocument::error_log(&error);
+        assert_eq!(document.name, "Payment gateway timeout");
+        assert_eq!(document.tags, keywords(&["high", "integration", "resolved"]));
+
+        let index = SearchIndex::new(None, 1).unwrap();
+        index.upsert([document]);
+        let results = index.search(&keywords(&["retry"]), 10).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].source, "errors");
+        assert_eq!(results[0].result_type, "errorLog");
+        assert_eq!(results[0].url, format!("/api/v1/support/errors/{}", error.id));
+    }
+}
@@ -0,0 +1,132 @@
+//! Ranking of merged search results.
+//!
+//! Each source scores its own hits, so raw scores are not comparable across
+//! sources. When results are merged they are scaled by the configured
+//! weight of their source and boosted when recently updated.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use qa_pms_config::settings::SearchSettings;
+
+use crate::routes::search::UnifiedSearchResult;
+
+/// Largest extra share of the score given to a just-updated item.
+const MAX_RECENCY_BOOST: f64 = 0.5;
+
+/// Per-source weights and recency boost applied to search results.
+#[derive(Debug, Clone, Default)]
+pub struct SearchRanking {
+    weights: HashMap<String, f32>,
+    half_life_days: u32,
+}
+
+impl SearchRanking {
+    /// Ranking from settings.
+    #[must_use]
+    pub fn from_settings(settings: &SearchSettings) -> Self {
+        Self {
+            weights: settings.source_weights.clone(),
+            half_life_days: settings.recency_half_life_days,
+        }
+    }
+
+    /// Weight of a source (1.0 unless configured).
+    #[must_use]
+    pub fn weight(&self, source: &str) -> f32 {
+        self.weights.get(source).copied().unwrap_or(1.0)
+    }
+
+    /// Score multiplier for an item last updated at `updated_at`.
+    ///
+    /// Decays from `1 + MAX_RECENCY_BOOST` for a fresh item towards 1.0, halving
+    /// the boost every half-life. Items without a date are not boosted.
+    #[must_use]
+    pub fn recency_boost(&self, updated_at: Option<DateTime<Utc>>, now: DateTime<Utc>) -> f32 {
+        let Some(updated_at) = updated_at.filter(|_| self.half_life_days > 0) else {
+            return 1.0;
+        };
+        let age_days = (now - updated_at).num_seconds().max(0) as f64 / 86_400.0;
+        let decay = 0.5_f64.powf(age_days / f64::from(self.half_life_days));
+        MAX_RECENCY_BOOST.mul_add(decay, 1.0) as f32
+    }
+
+    /// Rescore results and sort them best first.
+    pub fn apply(&self, results: &mut [UnifiedSearchResult], now: DateTime<Utc>) {
+        for result in results.iter_mut() {
+            result.score *=
+                self.weight(&result.source) * self.recency_boost(result.updated_at, now);
+        }
+        results.sort_by(|a, b| {
+            b.score
+                .partial_cmp(&a.score)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn result(source: &str, score: f32, updated_at: Option<DateTime<Utc>>) -> UnifiedSearchResult {
+        UnifiedSearchResult {
+            source: source.to_string(),
+            result_type: String::new(),
+            id: format!("{source}-{score}"),
+            name: "Checkout".to_string(),
+            description: None,
+            url: String::new(),
+            score,
+            matches: vec![],
+            tags: vec![],
+            updated_at,
+        }
+    }
+
+    fn ranking(weights: &[(&str, f32)], half_life_days: u32) -> SearchRanking {
+        SearchRanking::from_settings(&SearchSettings {
+            source_weights: weights
+                .iter()
+                .map(|(source, weight)| ((*source).to_string(), *weight))
+                .collect(),
+            fuzzy_distance: 1,
+            recency_half_life_days: half_life_days,
+            synonyms: Vec::new(),
+            cache_ttl_secs: 0,
+        })
+    }
+
+    #[test]
+    fn test_source_weights_reorder_results() {
+        let mut results = vec![result("postman", 3.0, None), result("testmo", 2.5, None)];
+        ranking(&[("testmo", 1.5)], 0).apply(&mut results, Utc::now());
+        assert_eq!(results[0].source, "testmo");
+        assert!((results[0].score - 3.75).abs() < f32::EPSILON);
+        assert!((results[1].score - 3.0).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_recency_boost_halves_each_half_life() {
+        let now = Utc::now();
+        let ranking = ranking(&[], 30);
+        assert!((ranking.recency_boost(Some(now), now) - 1.5).abs() < 1e-6);
+        assert!((ranking.recency_boost(Some(now - Duration::days(30)), now) - 1.25).abs() < 1e-6);
+        assert!((ranking.recency_boost(None, now) - 1.0).abs() < f32::EPSILON);
+        assert!(
+            (SearchRanking::default().recency_boost(Some(now), now) - 1.0).abs() < f32::EPSILON
+        );
+    }
+
+    #[test]
+    fn test_recent_items_rank_first_on_equal_scores() {
+        let now = Utc::now();
+        let mut results = vec![
+            result("kb", 1.0, Some(now - Duration::days(365))),
+            result("report", 1.0, Some(now - Duration::days(1))),
+        ];
+        ranking(&[], 90).apply(&mut results, now);
+        assert_eq!(results[0].source, "report");
+    }
+}
@@ -0,0 +1,383 @@
+//! Embedding index for semantic search.
+//!
+//! Local test cases (synced with Testmo) and cached Postman requests are
+//! embedded with the configured AI provider and stored in the pgvector
+//! `search_embeddings` table. Each row keeps a hash of the embedded text and
+//! the model used, so a sync only embeds new or changed items and a model
+//! change re-embeds everything. Queries are ranked by cosine similarity.
+
+use std::collections::{HashMap, HashSet};
+
+use anyhow::{ensure, Context, Result};
+use qa_pms_ai::EmbeddingService;
+use qa_pms_core::TestCase;
+use qa_pms_postman::CachedCollection;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use sqlx::{FromRow, PgPool};
+use tracing::info;
+use utoipa::ToSchema;
+
+use crate::routes::search::UnifiedSearchResult;
+use crate::search_index::{IndexDocument, IndexSource};
+
+/// Kind of item held in the embedding index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum SemanticSource {
+    /// Local test case (synced with Testmo)
+    Testmo,
+    /// Request in a cached Postman collection
+    Postman,
+}
+
+impl SemanticSource {
+    /// Every source, searched when none is requested.
+    pub const ALL: [Self; 2] = [Self::Testmo, Self::Postman];
+
+    /// Source name reported in search results.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Testmo => IndexSource::TestCase.as_str(),
+            Self::Postman => "postman",
+        }
+    }
+
+    /// Item type reported in search results.
+    #[must_use]
+    pub const fn result_type(self) -> &'static str {
+        match self {
+            Self::Testmo => IndexSource::TestCase.result_type(),
+            Self::Postman => "request",
+        }
+    }
+}
+
+/// An item to embed.
+#[derive(Debug, Clone)]
+pub struct EmbeddingDocument {
+    /// Kind of item
+    pub source: SemanticSource,
+    /// Item ID within its source
+    pub id: String,
+    /// Title shown in results
+    pub title: String,
+    /// Short description shown in results
+    pub description: Option<String>,
+    /// Where to view the item
+    pub url: String,
+    /// Text that is embedded
+    pub text: String,
+}
+
+impl EmbeddingDocument {
+    /// Document for a local test case, embedding the same text the
+    /// full-text index holds.
+    #[must_use]
+    pub fn test_case(case: &TestCase, testmo_base_url: Option<&str>) -> Self {
+        let doc = IndexDocument::test_case(case, testmo_base_url);
+        Self {
+            source: SemanticSource::Testmo,
+            text: format!("{}\n{}", doc.name, doc.body),
+            id: doc.id,
+            title: doc.name,
+            description: doc.description,
+            url: doc.url,
+        }
+    }
+
+    /// One document per request of a cached collection.
+    ///
+    /// Requests are identified by their position, as names may repeat.
+    #[must_use]
+    pub fn postman_requests(collection: &CachedCollection) -> Vec<Self> {
+        collection
+            .requests
+            .iter()
+            .enumerate()
+            .map(|(index, request)| {
+                let method = request.method.as_deref().unwrap_or("");
+                let text = [
+                    collection.name.as_str(),
+                    request.folder.as_str(),
+                    method,
+                    request.name.as_str(),
+                    request.url.as_deref().unwrap_or(""),
+                ]
+                .into_iter()
+                .filter(|part| !part.is_empty())
+                .collect::<Vec<_>>()
+                .join("\n");
+                Self {
+                    source: SemanticSource::Postman,
+                    id: format!("{}#{index}", collection.collection_id),
+                    title: format!("{method} {}", request.name).trim().to_string(),
+                    description: request.url.clone(),
+                    url: format!("https://go.postman.co/collection/{}", collection.uid),
+                    text,
+                }
+            })
+            .collect()
+    }
+
+    fn key(&self) -> String {
+        row_key(self.source.as_str(), &self.id)
+    }
+
+    fn content_hash(&self) -> String {
+        hex::encode(Sha256::digest(self.text.as_bytes()))
+    }
+}
+
+fn row_key(source: &str, id: &str) -> String {
+    format!("{source}:{id}")
+}
+
+/// pgvector text form of a vector, e.g. `[0.1,0.2]`.
+fn vector_literal(vector: &[f32]) -> String {
+    let values: Vec<String> = vector.iter().map(f32::to_string).collect();
+    format!("[{}]", values.join(","))
+}
+
+/// Outcome of an index sync.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SyncReport {
+    /// Items embedded because they were new or changed
+    pub embedded: usize,
+    /// Rows dropped for removed items or an old model
+    pub removed: u64,
+}
+
+#[derive(Debug, FromRow)]
+struct MatchRow {
+    source: String,
+    item_id: String,
+    title: String,
+    description: Option<String>,
+    url: String,
+    similarity: f64,
+}
+
+/// Embedding index backed by the `search_embeddings` table.
+#[derive(Debug, Clone)]
+pub struct SemanticIndex {
+    db: PgPool,
+}
+
+impl SemanticIndex {
+    /// Create an index over the given pool.
+    #[must_use]
+    pub const fn new(db: PgPool) -> Self {
+        Self { db }
+    }
+
+    /// Bring the index in line with `documents`, embedding only what changed.
+    ///
+    /// # Errors
+    /// Returns an error if embedding fails, the provider returns a different
+    /// number of embeddings than texts, or the database is unavailable.
+    pub async fn sync(
+        &self,
+        embedder: &EmbeddingService,
+        documents: &[EmbeddingDocument],
+    ) -> Result<SyncReport> {
+        let model = embedder.model().context("No embedding model configured")?;
+
+        let stored: Vec<(String, String, String)> = sqlx::query_as(
+            "SELECT source, item_id, content_hash FROM search_embeddings WHERE model = $1",
+        )
+        .bind(model)
+        .fetch_all(&self.db)
+        .await?;
+        let stored: HashMap<String, String> = stored
+            .into_iter()
+            .map(|(source, id, hash)| (row_key(&source, &id), hash))
+            .collect();
+
+        let mut seen = HashSet::new();
+        let stale: Vec<(&EmbeddingDocument, String)> = documents
+            .iter()
+            .filter(|doc| seen.insert(doc.key()))
+            .map(|doc| (doc, doc.content_hash()))
+            .filter(|(doc, hash)| stored.get(&doc.key()) != Some(hash))
+            .collect();
+
+        let texts: Vec<String> = stale.iter().map(|(doc, _)| doc.text.clone()).collect();
+        let vectors = embedder.embed_documents(&texts).await?;
+        check_embedding_count(texts.len(), vectors.len())?;
+
+        let mut tx = self.db.begin().await?;
+        for ((doc, hash), vector) in stale.iter().zip(&vectors) {
+            sqlx::query(
+                r"
+                INSERT INTO search_embeddings
+                    (source, item_id, model, content_hash, title, description, url, embedding, updated_at)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8::vector, NOW())
+                ON CONFLICT (source, item_id) DO UPDATE SET
+                    model = EXCLUDED.model,
+                    content_hash = EXCLUDED.content_hash,
+                    title = EXCLUDED.title,
+                    description = EXCLUDED.description,
+                    url = EXCLUDED.url,
+                    embedding = EXCLUDED.embedding,
+                    updated_at = NOW()
+                ",
+            )
+            .bind(doc.source.as_str())
+            .bind(&doc.id)
+            .bind(model)
+            .bind(hash)
+            .bind(&doc.title)
+            .bind(&doc.description)
+            .bind(&doc.url)
+            .bind(vector_literal(vector))
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        let keys: Vec<String> = seen.into_iter().collect();
+        let removed = sqlx::query(
+            "DELETE FROM search_embeddings \
+             WHERE model <> $1 OR NOT (source || ':' || item_id = ANY($2))",
+        )
+        .bind(model)
+        .bind(&keys)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+        tx.commit().await?;
+
+        let report = SyncReport {
+            embedded: vectors.len(),
+            removed,
+        };
+        if report != SyncReport::default() {
+            info!(
+                embedded = report.embedded,
+                removed = report.removed,
+                model,
+                "Embedding index synced"
+            );
+        }
+        Ok(report)
+    }
+
+    /// Items nearest to `query`, most similar first.
+    ///
+    /// # Errors
+    /// Returns an error if the database is unavailable.
+    pub async fn nearest(
+        &self,
+        model: &str,
+        query: &[f32],
+        sources: &[SemanticSource],
+        limit: usize,
+    ) -> Result<Vec<UnifiedSearchResult>> {
+        let sources: Vec<&str> = sources.iter().map(|s| s.as_str()).collect();
+        let rows: Vec<MatchRow> = sqlx::query_as(
+            r"
+            SELECT source, item_id, title, description, url,
+                   1 - (embedding <=> $1::vector) AS similarity
+            FROM search_embeddings
+            WHERE model = $2 AND source = ANY($3)
+            ORDER BY embedding <=> $1::vector
+            LIMIT $4
+            ",
+        )
+        .bind(vector_literal(query))
+        .bind(model)
+        .bind(&sources)
+        .bind(i64::try_from(limit).unwrap_or(i64::MAX))
+        .fetch_all(&self.db)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                let result_type = SemanticSource::ALL
+                    .into_iter()
+                    .find(|s| s.as_str() == row.source)
+                    .map_or("", SemanticSource::result_type);
+                UnifiedSearchResult {
+                    result_type: result_type.to_string(),
+                    source: row.source,
+                    id: row.item_id,
+                    name: row.title,
+                    description: row.description,
+                    url: row.url,
+                    score: row.similarity as f32,
+                    matches: vec![],
+                    tags: vec![],
+                    updated_at: None,
+                }
+            })
+            .collect())
+    }
+}
+
+/// Every text must get its embedding, or items would silently go unindexed.
+fn check_embedding_count(texts: usize, vectors: usize) -> Result<()> {
+    ensure!(
+        vectors == texts,
+        "Embedding provider returned {vectors} embeddings for {texts} texts"
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use qa_pms_postman::CachedRequest;
+
+    #[test]
+    fn test_postman_requests_are_keyed_by_position() {
+        let request = |name: &str, method: Option<&str>| CachedRequest {
+            name: name.to_string(),
+            folder: "Auth".to_string(),
+            method: method.map(str::to_string),
+            url: Some("{{base}}/login".to_string()),
+        };
+        let collection = CachedCollection {
+            collection_id: "c1".to_string(),
+            uid: "u-c1".to_string(),
+            name: "Accounts".to_string(),
+            requests: vec![request("Login", Some("POST")), request("Login", None)],
+        };
+
+        let docs = EmbeddingDocument::postman_requests(&collection);
+        assert_eq!(docs.len(), 2);
+        assert_eq!(docs[0].id, "c1#0");
+        assert_eq!(docs[1].id, "c1#1");
+        assert_eq!(docs[0].title, "POST Login");
+        assert_eq!(docs[1].title, "Login");
+        assert_eq!(docs[0].text, "Accounts\nAuth\nPOST\nLogin\n{{base}}/login");
+        assert_eq!(docs[0].url, "https://go.postman.co/collection/u-c1");
+        assert_ne!(docs[0].content_hash(), docs[1].content_hash());
+    }
+
+    #[test]
+    fn test_embedding_count_must_match_texts() {
+        assert!(check_embedding_count(3, 3).is_ok());
+        assert!(check_embedding_count(0, 0).is_ok());
+        let err = check_embedding_count(3, 2).unwrap_err();
+        assert_eq!(err.to_string(), "Embedding provider returned 2 embeddings for 3 texts");
+    }
+
+    #[test]
+    fn test_vector_literal() {
+        assert_eq!(vector_literal(&[0.5, -1.0, 0.125]), "[0.5,-1,0.125]");
+        assert_eq!(vector_literal(&[]), "[]");
+    }
+
+    #[test]
+    fn test_sources_match_search_result_names() {
+        assert_eq!(SemanticSource::Testmo.as_str(), "testmo");
+        assert_eq!(SemanticSource::Testmo.result_type(), "testCase");
+        let parsed: Vec<SemanticSource> = serde_json::from_str(r#"["postman","testmo"]"#).unwrap();
+        assert_eq!(
+            parsed,
+            vec![SemanticSource::Postman, SemanticSource::Testmo]
+        );
+    }
+}
@@ -0,0 +1,244 @@
+//! Storage for the setup wizard's progress.
+//!
+//! The wizard collects credentials over several requests before writing the
+//! user config. Keeping that progress in a `SetupStateStore` lets the wizard
+//! resume where it left off after a restart. The file backend encrypts the
+//! whole state with the application key, since it holds API tokens.
+
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use qa_pms_config::{Encryptor, RotationCount};
+use secrecy::ExposeSecret;
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+use tracing::{debug, warn};
+
+use crate::routes::setup::SetupState;
+
+/// Persistence for setup wizard state.
+#[async_trait]
+pub trait SetupStateStore: Send + Sync {
+    /// Load the saved state, or `None` if nothing was saved yet.
+    async fn load(&self) -> Result<Option<SetupState>>;
+
+    /// Replace the saved state.
+    async fn save(&self, state: &SetupState) -> Result<()>;
+}
+
+/// Store that keeps state only for the life of the process.
+#[derive(Default)]
+pub struct InMemorySetupStateStore {
+    state: Mutex<Option<SetupState>>,
+}
+
+#[async_trait]
+impl SetupStateStore for InMemorySetupStateStore {
+    async fn load(&self) -> Result<Option<SetupState>> {
+        Ok(self.state.lock().await.clone())
+    }
+
+    async fn save(&self, state: &SetupState) -> Result<()> {
+        *self.state.lock().await = Some(state.clone());
+        Ok(())
+    }
+}
+
+/// On-disk format of the setup state file.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SetupStateFile {
+    version: u32,
+    /// Encrypted JSON of the state
+    state: String,
+    updated_at: DateTime<Utc>,
+}
+
+/// Store that keeps state in an encrypted file.
+pub struct FileSetupStateStore {
+    path: PathBuf,
+    encryptor: Encryptor,
+}
+
+impl FileSetupStateStore {
+    /// Current file format version.
+    const VERSION: u32 = 1;
+
+    /// Create a store writing to `path`.
+    #[must_use]
+    pub const fn new(path: PathBuf, encryptor: Encryptor) -> Self {
+        Self { path, encryptor }
+    }
+
+    /// Default location, next to the user config file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the config directory cannot be determined.
+    pub fn default_path() -> Result<PathBuf> {
+        Ok(qa_pms_config::UserConfig::default_path()?.with_file_name("setup-state.json"))
+    }
+
+    /// Re-encrypt the saved state with the current key. A dry run only
+    /// checks that it decrypts.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or written, or the state
+    /// does not decrypt with any configured key.
+    pub async fn rotate(&self, dry_run: bool) -> Result<RotationCount> {
+        let mut count = RotationCount::default();
+        if !tokio::fs::try_exists(&self.path).await.unwrap_or(false) {
+            return Ok(count);
+        }
+        let contents = tokio::fs::read_to_string(&self.path)
+            .await
+            .context("Failed to read setup state file")?;
+        let mut file: SetupStateFile =
+            serde_json::from_str(&contents).context("Failed to parse setup state file")?;
+        if file.version != Self::VERSION {
+            return Ok(count);
+        }
+        self.encryptor
+            .rotate(&mut file.state, &mut count)
+            .context("Failed to rotate setup state")?;
+        if count.rotated > 0 && !dry_run {
+            self.write(&file).await?;
+        }
+        Ok(count)
+    }
+
+    /// Write then rename, so a crash mid-write keeps the previous state.
+    async fn write(&self, file: &SetupStateFile) -> Result<()> {
+        let contents = serde_json::to_string_pretty(file)?;
+        if let Some(parent) = self.path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        let tmp = self.path.with_extension("json.tmp");
+        tokio::fs::write(&tmp, contents)
+            .await
+            .context("Failed to write setup state file")?;
+        tokio::fs::rename(&tmp, &self.path)
+            .await
+            .context("Failed to replace setup state file")
+    }
+}
+
+#[async_trait]
+impl SetupStateStore for FileSetupStateStore {
+    async fn load(&self) -> Result<Option<SetupState>> {
+        if !tokio::fs::try_exists(&self.path).await.unwrap_or(false) {
+            debug!(path = %self.path.display(), "No saved setup state");
+            return Ok(None);
+        }
+        let contents = tokio::fs::read_to_string(&self.path)
+            .await
+            .context("Failed to read setup state file")?;
+        let file: SetupStateFile =
+            serde_json::from_str(&contents).context("Failed to parse setup state file")?;
+        if file.version != Self::VERSION {
+            warn!(
+                file_version = file.version,
+                expected = Self::VERSION,
+                "Setup state file version mismatch, starting fresh"
+            );
+            return Ok(None);
+        }
+
+        let json = self
+            .encryptor
+            .decrypt(&file.state)
+            .context("Failed to decrypt setup state")?;
+        let state =
+            serde_json::from_str(json.expose_secret()).context("Failed to parse setup state")?;
+        Ok(Some(state))
+    }
+
+    async fn save(&self, state: &SetupState) -> Result<()> {
+        let json = serde_json::to_string(state)?;
+        let file = SetupStateFile {
+            version: Self::VERSION,
+            state: self.encryptor.encrypt(&json)?,
+            updated_at: Utc::now(),
+        };
+        self.write(&file).await?;
+
+        debug!(path = %self.path.display(), "Saved setup state");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::routes::setup::{JiraTestRequest, ProfileRequest};
+
+    const KEY: &str = "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f";
+
+    fn state() -> SetupState {
+        SetupState {
+            profile: Some(ProfileRequest {
+                display_name: "Dana".to_string(),
+                jira_email: "dana@acme.test".to_string(),
+                ticket_states: vec!["Ready for QA".to_string()],
+            }),
+            jira: Some(JiraTestRequest {
+                instance_url: "https://acme.atlassian.net".to_string(),
+                email: Some("dana@acme.test".to_string()),
+                api_token: Some("jira-token-value".to_string()),
+                client_id: None,
+                client_secret: None,
+                cloud_id: None,
+                access_token: None,
+            }),
+            ..SetupState::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_file_store_round_trip_is_encrypted() {
+        let path = std::env::temp_dir()
+            .join(format!("qa-pms-setup-{}", uuid::Uuid::new_v4()))
+            .join("setup-state.json");
+        let store = FileSetupStateStore::new(path.clone(), Encryptor::from_hex_key(KEY).unwrap());
+        assert!(store.load().await.unwrap().is_none());
+
+        store.save(&state()).await.unwrap();
+        let contents = std::fs::read_to_string(&path).unwrap();
+        assert!(!contents.contains("jira-token-value"));
+
+        // A new store over the same file sees the saved progress
+        let reopened =
+            FileSetupStateStore::new(path.clone(), Encryptor::from_hex_key(KEY).unwrap());
+        let loaded = reopened.load().await.unwrap().unwrap();
+        assert!(loaded.is_complete());
+        assert_eq!(loaded.configured_integrations(), vec!["jira".to_string()]);
+
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_rotate_reencrypts_saved_state() {
+        let path = std::env::temp_dir()
+            .join(format!("qa-pms-setup-{}", uuid::Uuid::new_v4()))
+            .join("setup-state.json");
+        let old = FileSetupStateStore::new(path.clone(), Encryptor::from_hex_key(KEY).unwrap());
+        old.save(&state()).await.unwrap();
+
+        const NEW_KEY: &str = "1f1e1d1c1b1a191817161514131211100f0e0d0c0b0a09080706050403020100";
+        let encryptor = Encryptor::from_hex_key(NEW_KEY)
+            .unwrap()
+            .with_previous_keys([KEY])
+            .unwrap();
+        let store = FileSetupStateStore::new(path.clone(), encryptor);
+        assert_eq!(store.rotate(false).await.unwrap().rotated, 1);
+
+        let new_only =
+            FileSetupStateStore::new(path.clone(), Encryptor::from_hex_key(NEW_KEY).unwrap());
+        assert!(new_only.load().await.unwrap().unwrap().is_complete());
+
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+}
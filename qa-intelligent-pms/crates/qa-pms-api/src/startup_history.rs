@@ -0,0 +1,268 @@
+//! Startup validation history.
+//!
+//! Every validation run, the one at boot and any requested later, is stored
+//! in `startup_validation_runs` with one `startup_validation_results` row per
+//! integration. This keeps the state of the integrations at boot available
+//! when investigating later incidents. Only the most recent runs are kept.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use qa_pms_core::types::{CursorPage, PageRequest};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::startup::{StartupValidationReport, ValidationResult};
+
+/// Runs kept; older ones are pruned on insert.
+const KEPT_RUNS: i64 = 500;
+
+/// What started a validation run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ValidationTrigger {
+    /// Application startup
+    Boot,
+    /// A call to the validation endpoint
+    Request,
+}
+
+impl ValidationTrigger {
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::Boot => "boot",
+            Self::Request => "request",
+        }
+    }
+}
+
+/// A stored validation run.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct StartupValidationRun {
+    /// Run ID
+    pub id: Uuid,
+    /// What started the run: "boot" or "request"
+    pub trigger: String,
+    /// Whether no critical integration failed
+    pub valid: bool,
+    /// Whether any critical integration failed
+    pub has_critical_failure: bool,
+    /// Total validation time in milliseconds
+    pub total_time_ms: i64,
+    /// When the run finished
+    pub ran_at: DateTime<Utc>,
+    /// Result per integration, by name
+    pub results: Vec<ValidationResult>,
+}
+
+#[derive(Debug, FromRow)]
+struct RunRow {
+    id: Uuid,
+    trigger: String,
+    valid: bool,
+    has_critical_failure: bool,
+    total_time_ms: i64,
+    ran_at: DateTime<Utc>,
+}
+
+#[derive(Debug, FromRow)]
+struct ResultRow {
+    run_id: Uuid,
+    integration: String,
+    success: bool,
+    is_critical: bool,
+    response_time_ms: Option<i64>,
+    error_message: Option<String>,
+}
+
+/// Validation history backed by the `startup_validation_*` tables.
+#[derive(Debug, Clone)]
+pub struct StartupHistory {
+    db: PgPool,
+}
+
+impl StartupHistory {
+    /// Create a history store.
+    #[must_use]
+    pub const fn new(db: PgPool) -> Self {
+        Self { db }
+    }
+
+    /// Store a run and prune the oldest beyond the kept count.
+    ///
+    /// # Errors
+    /// Returns an error if the database is unavailable.
+    pub async fn record(
+        &self,
+        trigger: ValidationTrigger,
+        report: &StartupValidationReport,
+    ) -> Result<Uuid, sqlx::Error> {
+        let id = Uuid::new_v4();
+        let mut tx = self.db.begin().await?;
+        sqlx::query(
+            "INSERT INTO startup_validation_runs \
+             (id, trigger, valid, has_critical_failure, total_time_ms, ran_at) \
+             VALUES ($1, $2, $3, $4, $5, $6)",
+        )
+        .bind(id)
+        .bind(trigger.as_str())
+        .bind(report.valid)
+        .bind(report.has_critical_failure)
+        .bind(i64::try_from(report.total_time_ms).unwrap_or(i64::MAX))
+        .bind(Utc::now())
+        .execute(&mut *tx)
+        .await?;
+        for result in &report.results {
+            sqlx::query(
+                "INSERT INTO startup_validation_results \
+                 (run_id, integration, success, is_critical, response_time_ms, error_message) \
+                 VALUES ($1, $2, $3, $4, $5, $6)",
+            )
+            .bind(id)
+            .bind(&result.integration)
+            .bind(result.success)
+            .bind(result.is_critical)
+            .bind(
+                result
+                    .response_time_ms
+                    .and_then(|ms| i64::try_from(ms).ok()),
+            )
+            .bind(&result.error_message)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        let kept = "SELECT id FROM startup_validation_runs ORDER BY ran_at DESC, id DESC LIMIT $1";
+        sqlx::query(&format!(
+            "DELETE FROM startup_validation_results WHERE run_id NOT IN ({kept})"
+        ))
+        .bind(KEPT_RUNS)
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query(&format!(
+            "DELETE FROM startup_validation_runs WHERE id NOT IN ({kept})"
+        ))
+        .bind(KEPT_RUNS)
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(id)
+    }
+
+    /// One page of runs, optionally only those with the given trigger.
+    ///
+    /// # Errors
+    /// Returns an error if the database is unavailable.
+    pub async fn list(
+        &self,
+        page: &PageRequest,
+        trigger: Option<ValidationTrigger>,
+    ) -> Result<CursorPage<StartupValidationRun>, sqlx::Error> {
+        let keyset = page
+            .keyset_sql("ran_at", "id", 2)
+            .map(|clause| format!("AND {clause}"))
+            .unwrap_or_default();
+        let sql = format!(
+            "SELECT id, trigger, valid, has_critical_failure, total_time_ms, ran_at \
+             FROM startup_validation_runs \
+             WHERE ($1::text IS NULL OR trigger = $1) {keyset} \
+             ORDER BY {order} LIMIT {limit}",
+            order = page.order_sql("ran_at", "id"),
+            limit = page.fetch_limit(),
+        );
+        let mut query =
+            sqlx::query_as::<_, RunRow>(&sql).bind(trigger.map(ValidationTrigger::as_str));
+        if let Some((at, id)) = page.keyset() {
+            query = query.bind(at).bind(id);
+        }
+        let runs = page.finish(query.fetch_all(&self.db).await?, |run| (run.ran_at, run.id));
+
+        let ids: Vec<Uuid> = runs.data.iter().map(|run| run.id).collect();
+        let results: Vec<ResultRow> = sqlx::query_as(
+            "SELECT run_id, integration, success, is_critical, response_time_ms, error_message \
+             FROM startup_validation_results WHERE run_id = ANY($1) ORDER BY integration",
+        )
+        .bind(&ids)
+        .fetch_all(&self.db)
+        .await?;
+
+        let mut by_run = group_results(results);
+        Ok(runs.map(|run| StartupValidationRun {
+            results: by_run.remove(&run.id).unwrap_or_default(),
+            id: run.id,
+            trigger: run.trigger,
+            valid: run.valid,
+            has_critical_failure: run.has_critical_failure,
+            total_time_ms: run.total_time_ms,
+            ran_at: run.ran_at,
+        }))
+    }
+}
+
+/// Group result rows by run, keeping their order.
+fn group_results(rows: Vec<ResultRow>) -> HashMap<Uuid, Vec<ValidationResult>> {
+    let mut by_run: HashMap<Uuid, Vec<ValidationResult>> = HashMap::new();
+    for row in rows {
+        by_run
+            .entry(row.run_id)
+            .or_default()
+            .push(ValidationResult {
+                integration: row.integration,
+                success: row.success,
+                error_message: row.error_message,
+                response_time_ms: row.response_time_ms.and_then(|ms| u64::try_from(ms).ok()),
+                is_critical: row.is_critical,
+            });
+    }
+    by_run
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(run_id: Uuid, integration: &str, success: bool) -> ResultRow {
+        ResultRow {
+            run_id,
+            integration: integration.to_string(),
+            success,
+            is_critical: integration == "jira",
+            response_time_ms: success.then_some(120),
+            error_message: (!success).then(|| "Connection failed".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_group_results_by_run() {
+        let (boot, later) = (Uuid::new_v4(), Uuid::new_v4());
+        let grouped = group_results(vec![
+            row(boot, "jira", false),
+            row(later, "jira", true),
+            row(boot, "postman", true),
+        ]);
+
+        let boot_results = &grouped[&boot];
+        assert_eq!(boot_results.len(), 2);
+        assert_eq!(boot_results[0].integration, "jira");
+        assert!(!boot_results[0].success);
+        assert!(boot_results[0].is_critical);
+        assert_eq!(
+            boot_results[0].error_message.as_deref(),
+            Some("Connection failed")
+        );
+        assert_eq!(boot_results[1].response_time_ms, Some(120));
+        assert_eq!(grouped[&later].len(), 1);
+    }
+
+    #[test]
+    fn test_trigger_serialization() {
+        assert_eq!(
+            serde_json::to_string(&ValidationTrigger::Boot).unwrap(),
+            "\"boot\""
+        );
+        assert_eq!(ValidationTrigger::Request.as_str(), "request");
+    }
+}
@@ -0,0 +1,168 @@
+//! Support bundles for bug reports.
+//!
+//! A bundle is a zip archive a user attaches to a bug report. It holds the
+//! most recent error logs, a fresh diagnostics report, the startup
+//! validation history, the non-secret configuration and version information,
+//! one JSON file each, plus a manifest listing them. Every file passes
+//! through [`redact`] before it is written, so the bundle can be shared as
+//! is: secret fields are masked and credentials are scrubbed from messages
+//! and URLs.
+
+use std::io::{Cursor, Write};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use qa_pms_core::types::{Cursor as PageCursor, PageRequest, SortDirection, MAX_PAGE_LIMIT};
+use qa_pms_support::{ErrorLog, ErrorLogFilter, SupportRepository};
+use serde::Serialize;
+use serde_json::{json, Value};
+use tracing::warn;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+use crate::app::AppState;
+use crate::backup::config_snapshot;
+use crate::migrations;
+use crate::redact::redact;
+use crate::routes::support::diagnostics_service;
+
+/// Bundle format marker, in the manifest.
+pub const BUNDLE_FORMAT: &str = "qa-pms-support-bundle";
+
+/// Current bundle layout version.
+pub const BUNDLE_VERSION: u32 = 1;
+
+/// Error logs included when the request does not say.
+pub const DEFAULT_ERROR_LOG_LIMIT: usize = 200;
+
+/// Most error logs a bundle holds.
+pub const MAX_ERROR_LOG_LIMIT: usize = 1_000;
+
+/// Startup validation runs included.
+const STARTUP_RUNS: u32 = 20;
+
+/// A built bundle.
+#[derive(Debug)]
+pub struct SupportBundle {
+    /// When the bundle was assembled
+    pub created_at: DateTime<Utc>,
+    /// Zip archive bytes
+    pub bytes: Vec<u8>,
+}
+
+/// Assemble a support bundle with up to `error_log_limit` recent error logs.
+///
+/// A section that cannot be collected is replaced by its error, so a
+/// failing database still yields a bundle describing the failure.
+///
+/// # Errors
+/// Returns an error if the archive cannot be written.
+pub async fn build(state: &AppState, error_log_limit: usize) -> Result<SupportBundle> {
+    let created_at = Utc::now();
+
+    let error_logs = section(
+        recent_error_logs(state, error_log_limit)
+            .await
+            .map(|errors| json!({ "count": errors.len(), "errorLogs": errors })),
+    );
+    let diagnostics = section(
+        diagnostics_service(state)
+            .run_all_diagnostics()
+            .await
+            .map_err(anyhow::Error::from),
+    );
+    let startup_history = section(
+        state
+            .startup_history
+            .list(&PageRequest::new(None, Some(STARTUP_RUNS), SortDirection::Desc), None)
+            .await
+            .map(|page| json!({ "runs": page.data }))
+            .map_err(anyhow::Error::from),
+    );
+    let version = version_info(state).await;
+
+    let files = [
+        ("version.json", version),
+        ("config.json", config_snapshot(&state.settings)),
+        ("diagnostics.json", diagnostics),
+        ("error-logs.json", error_logs),
+        ("startup-history.json", startup_history),
+    ];
+    let manifest = json!({
+        "format": BUNDLE_FORMAT,
+        "version": BUNDLE_VERSION,
+        "createdAt": created_at,
+        "appVersion": env!("CARGO_PKG_VERSION"),
+        "files": files.iter().map(|(name, _)| *name).collect::<Vec<_>>(),
+        "redacted": true,
+    });
+
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+    for (name, value) in std::iter::once(("manifest.json", manifest)).chain(files) {
+        zip.start_file(name, options)
+            .with_context(|| format!("Failed to add {name} to the bundle"))?;
+        let contents = serde_json::to_vec_pretty(&redact(value))?;
+        zip.write_all(&contents)
+            .with_context(|| format!("Failed to write {name} to the bundle"))?;
+    }
+    let bytes = zip.finish().context("Failed to finish the bundle")?.into_inner();
+
+    Ok(SupportBundle { created_at, bytes })
+}
+
+/// Section contents, or its error when it could not be collected.
+fn section<T: Serialize>(result: Result<T>) -> Value {
+    match result.and_then(|value| serde_json::to_value(value).map_err(Into::into)) {
+        Ok(value) => value,
+        Err(e) => {
+            warn!(error = %e, "Support bundle section unavailable");
+            json!({ "error": format!("{e:#}") })
+        }
+    }
+}
+
+/// The most recently seen error logs, newest first.
+async fn recent_error_logs(state: &AppState, limit: usize) -> Result<Vec<ErrorLog>> {
+    let repo = SupportRepository::new(state.db.clone());
+    let filter = ErrorLogFilter::default();
+    let mut errors: Vec<ErrorLog> = Vec::new();
+    let mut page = PageRequest::new(None, Some(MAX_PAGE_LIMIT), SortDirection::Desc);
+    while errors.len() < limit {
+        let batch = page.finish(repo.list_error_logs(&filter, &page).await?, |e| {
+            (e.last_seen_at, e.id)
+        });
+        errors.extend(batch.data);
+        if !batch.page_info.has_more {
+            break;
+        }
+        let Some(last) = errors.last() else { break };
+        page = PageRequest::new(
+            Some(PageCursor::Key {
+                at: last.last_seen_at,
+                id: last.id,
+            }),
+            Some(MAX_PAGE_LIMIT),
+            SortDirection::Desc,
+        );
+    }
+    errors.truncate(limit);
+    Ok(errors)
+}
+
+/// Server, platform, database and schema versions.
+async fn version_info(state: &AppState) -> Value {
+    let database: Option<String> = sqlx::query_scalar("SELECT version()")
+        .fetch_one(&state.db)
+        .await
+        .map_err(|e| warn!(error = %e, "Failed to read the database version"))
+        .ok();
+    let migrations = section(migrations::status(&state.db).await.map_err(anyhow::Error::from));
+    json!({
+        "appVersion": env!("CARGO_PKG_VERSION"),
+        "os": std::env::consts::OS,
+        "arch": std::env::consts::ARCH,
+        "database": database,
+        "migrations": migrations,
+    })
+}
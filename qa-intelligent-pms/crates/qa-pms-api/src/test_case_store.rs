@@ -0,0 +1,286 @@
+//! Postgres storage for local test cases.
+//!
+//! Steps and merge provenance are kept as JSON arrays and tags as a text
+//! array. The remote link is flattened into nullable columns, all set or all
+//! null.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use qa_pms_core::types::PageRequest;
+use qa_pms_core::{
+    MergedCase, RemoteLink, TestCase, TestCaseFilter, TestCaseRepository, TestCaseStep,
+};
+use sqlx::{FromRow, PgPool};
+use uuid::Uuid;
+
+const COLUMNS: &str = "id, title, preconditions, steps, priority, category, tags, \
+    remote_system, remote_project_id, remote_id, remote_updated_at, synced_at, \
+    merged_from, created_at, updated_at";
+
+#[derive(Debug, FromRow)]
+struct TestCaseRow {
+    id: Uuid,
+    title: String,
+    preconditions: Option<String>,
+    steps: sqlx::types::Json<Vec<TestCaseStep>>,
+    priority: Option<String>,
+    category: Option<String>,
+    tags: Vec<String>,
+    remote_system: Option<String>,
+    remote_project_id: Option<String>,
+    remote_id: Option<String>,
+    remote_updated_at: Option<String>,
+    synced_at: Option<DateTime<Utc>>,
+    merged_from: sqlx::types::Json<Vec<MergedCase>>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+impl From<TestCaseRow> for TestCase {
+    fn from(row: TestCaseRow) -> Self {
+        let remote = match (
+            row.remote_system,
+            row.remote_project_id,
+            row.remote_id,
+            row.remote_updated_at,
+            row.synced_at,
+        ) {
+            (
+                Some(system),
+                Some(project_id),
+                Some(remote_id),
+                Some(remote_updated_at),
+                Some(synced_at),
+            ) => Some(RemoteLink {
+                system,
+                project_id,
+                remote_id,
+                remote_updated_at,
+                synced_at,
+            }),
+            _ => None,
+        };
+        Self {
+            id: row.id,
+            title: row.title,
+            preconditions: row.preconditions,
+            steps: row.steps.0,
+            priority: row.priority,
+            category: row.category,
+            tags: row.tags,
+            remote,
+            merged_from: row.merged_from.0,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+        }
+    }
+}
+
+/// Test case repository backed by the `test_cases` table.
+#[derive(Debug, Clone)]
+pub struct PgTestCaseRepository {
+    pool: PgPool,
+}
+
+impl PgTestCaseRepository {
+    /// Create a repository over a connection pool.
+    #[must_use]
+    pub const fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl TestCaseRepository for PgTestCaseRepository {
+    async fn get(&self, id: Uuid) -> anyhow::Result<Option<TestCase>> {
+        let row: Option<TestCaseRow> =
+            sqlx::query_as(&format!("SELECT {COLUMNS} FROM test_cases WHERE id = $1"))
+                .bind(id)
+                .fetch_optional(&self.pool)
+                .await?;
+        Ok(row.map(Into::into))
+    }
+
+    async fn find_by_remote(
+        &self,
+        system: &str,
+        remote_id: &str,
+    ) -> anyhow::Result<Option<TestCase>> {
+        let row: Option<TestCaseRow> = sqlx::query_as(&format!(
+            "SELECT {COLUMNS} FROM test_cases WHERE remote_system = $1 AND remote_id = $2 LIMIT 1"
+        ))
+        .bind(system)
+        .bind(remote_id)
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(row.map(Into::into))
+    }
+
+    async fn list(&self) -> anyhow::Result<Vec<TestCase>> {
+        let rows: Vec<TestCaseRow> = sqlx::query_as(&format!(
+            "SELECT {COLUMNS} FROM test_cases ORDER BY created_at, id"
+        ))
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    async fn search(
+        &self,
+        filter: &TestCaseFilter,
+        page: &PageRequest,
+    ) -> anyhow::Result<Vec<TestCase>> {
+        let mut conditions = Vec::new();
+        let mut params = 0;
+        let mut next_param = || {
+            params += 1;
+            params
+        };
+
+        if filter.text.is_some() {
+            let n = next_param();
+            conditions.push(format!(
+                "(title ILIKE '%' || ${n} || '%' OR preconditions ILIKE '%' || ${n} || '%' \
+                 OR EXISTS (SELECT 1 FROM jsonb_array_elements(steps) AS step \
+                            WHERE step->>'action' ILIKE '%' || ${n} || '%' \
+                               OR step->>'expected' ILIKE '%' || ${n} || '%'))"
+            ));
+        }
+        if filter.tag.is_some() {
+            conditions.push(format!(
+                "EXISTS (SELECT 1 FROM unnest(tags) AS tag WHERE lower(tag) = lower(${}))",
+                next_param()
+            ));
+        }
+        if filter.category.is_some() {
+            conditions.push(format!("lower(category) = lower(${})", next_param()));
+        }
+        if filter.priority.is_some() {
+            conditions.push(format!("lower(priority) = lower(${})", next_param()));
+        }
+        if let Some(keyset) = page.keyset_sql("created_at", "id", next_param()) {
+            conditions.push(keyset);
+        }
+
+        let where_clause = if conditions.is_empty() {
+            String::new()
+        } else {
+            format!("WHERE {}", conditions.join(" AND "))
+        };
+        let sql = format!(
+            "SELECT {COLUMNS} FROM test_cases {where_clause} ORDER BY {order} LIMIT {limit}",
+            order = page.order_sql("created_at", "id"),
+            limit = page.fetch_limit(),
+        );
+
+        // Unset filters have no placeholder, so only bind the ones in use
+        let mut query = sqlx::query_as::<_, TestCaseRow>(&sql);
+        for value in [
+            &filter.text,
+            &filter.tag,
+            &filter.category,
+            &filter.priority,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            query = query.bind(value);
+        }
+        if let Some((at, id)) = page.keyset() {
+            query = query.bind(at).bind(id);
+        }
+        let rows = query.fetch_all(&self.pool).await?;
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    async fn save(&self, case: TestCase) -> anyhow::Result<TestCase> {
+        let remote = case.remote.as_ref();
+        let row: TestCaseRow = sqlx::query_as(&format!(
+            r"
+            INSERT INTO test_cases ({COLUMNS})
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15)
+            ON CONFLICT (id) DO UPDATE SET
+                title = EXCLUDED.title,
+                preconditions = EXCLUDED.preconditions,
+                steps = EXCLUDED.steps,
+                priority = EXCLUDED.priority,
+                category = EXCLUDED.category,
+                tags = EXCLUDED.tags,
+                remote_system = EXCLUDED.remote_system,
+                remote_project_id = EXCLUDED.remote_project_id,
+                remote_id = EXCLUDED.remote_id,
+                remote_updated_at = EXCLUDED.remote_updated_at,
+                synced_at = EXCLUDED.synced_at,
+                merged_from = EXCLUDED.merged_from,
+                updated_at = EXCLUDED.updated_at
+            RETURNING {COLUMNS}
+            "
+        ))
+        .bind(case.id)
+        .bind(&case.title)
+        .bind(&case.preconditions)
+        .bind(sqlx::types::Json(&case.steps))
+        .bind(&case.priority)
+        .bind(&case.category)
+        .bind(&case.tags)
+        .bind(remote.map(|r| &r.system))
+        .bind(remote.map(|r| &r.project_id))
+        .bind(remote.map(|r| &r.remote_id))
+        .bind(remote.map(|r| &r.remote_updated_at))
+        .bind(remote.map(|r| r.synced_at))
+        .bind(sqlx::types::Json(&case.merged_from))
+        .bind(case.created_at)
+        .bind(case.updated_at)
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(row.into())
+    }
+
+    async fn delete(&self, id: Uuid) -> anyhow::Result<bool> {
+        let deleted = sqlx::query("DELETE FROM test_cases WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await?
+            .rows_affected();
+        Ok(deleted > 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(remote_id: Option<&str>) -> TestCaseRow {
+        let now = Utc::now();
+        TestCaseRow {
+            id: Uuid::new_v4(),
+            title: "Login works".to_string(),
+            preconditions: None,
+            steps: sqlx::types::Json(vec![TestCaseStep {
+                action: "Sign in".to_string(),
+                expected: Some("Dashboard shown".to_string()),
+            }]),
+            priority: Some("high".to_string()),
+            category: None,
+            tags: vec!["smoke".to_string()],
+            remote_system: Some("testmo".to_string()),
+            remote_project_id: Some("1".to_string()),
+            remote_id: remote_id.map(str::to_string),
+            remote_updated_at: Some("2024-01-01T00:00:00Z".to_string()),
+            synced_at: Some(now),
+            merged_from: sqlx::types::Json(Vec::new()),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    #[test]
+    fn test_row_maps_remote_link_only_when_complete() {
+        let linked = TestCase::from(row(Some("42")));
+        assert!(linked.is_linked_to("testmo", "42"));
+        assert_eq!(linked.steps.len(), 1);
+
+        let partial = TestCase::from(row(None));
+        assert!(partial.remote.is_none());
+    }
+}
@@ -0,0 +1,218 @@
+//! Testmo run result sync.
+//!
+//! Runs created through the API are recorded in `test_run_results`. A
+//! background job polls Testmo for their results and stores pass/fail/blocked
+//! counts, so workflow summaries show how testing went without a trip to
+//! Testmo. Polling stops once a run is closed or has aged out. Calls draw
+//! from Testmo's shared rate limit, and a pass stops early when it runs out.
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use qa_pms_testmo::{RunResultCounts, TestmoClient};
+use sqlx::{FromRow, PgPool};
+use tokio::time::{interval, MissedTickBehavior};
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+use crate::rate_limit::{Integration, RateLimiter};
+
+/// Default seconds between result pulls.
+pub const DEFAULT_RESULT_SYNC_SECS: u64 = 300;
+
+/// Open runs older than this are no longer polled.
+const MAX_RUN_AGE_DAYS: i32 = 14;
+
+const COLUMNS: &str =
+    "run_id, name, passed, failed, blocked, skipped, retest, is_completed, synced_at, created_at";
+
+/// Stored results of a Testmo run.
+#[derive(Debug, Clone, FromRow)]
+pub struct TestRunRecord {
+    /// Testmo run ID
+    pub run_id: i64,
+    /// Run name
+    pub name: String,
+    /// Cases that passed
+    pub passed: i64,
+    /// Cases that failed
+    pub failed: i64,
+    /// Cases that are blocked
+    pub blocked: i64,
+    /// Cases that were skipped
+    pub skipped: i64,
+    /// Cases marked for retest
+    pub retest: i64,
+    /// Whether the run is closed in Testmo
+    pub is_completed: bool,
+    /// Last successful pull, `None` before the first
+    pub synced_at: Option<DateTime<Utc>>,
+    /// When the run was created
+    pub created_at: DateTime<Utc>,
+}
+
+/// Record a run created from the framework so its results get synced.
+///
+/// # Errors
+/// Returns error if the insert fails.
+pub async fn record_run(
+    db: &PgPool,
+    run_id: i64,
+    workflow_id: Option<Uuid>,
+    ticket_key: &str,
+    name: &str,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "INSERT INTO test_run_results (run_id, workflow_id, ticket_key, name, created_at) \
+         VALUES ($1, $2, $3, $4, NOW()) ON CONFLICT (run_id) DO NOTHING",
+    )
+    .bind(run_id)
+    .bind(workflow_id)
+    .bind(ticket_key)
+    .bind(name)
+    .execute(db)
+    .await?;
+    Ok(())
+}
+
+/// Runs created for a workflow, or for its ticket without a workflow.
+///
+/// # Errors
+/// Returns error if the query fails.
+pub async fn runs_for_workflow(
+    db: &PgPool,
+    workflow_id: Uuid,
+    ticket_key: &str,
+) -> Result<Vec<TestRunRecord>, sqlx::Error> {
+    sqlx::query_as(&format!(
+        "SELECT {COLUMNS} FROM test_run_results \
+         WHERE workflow_id = $1 OR (workflow_id IS NULL AND ticket_key = $2) \
+         ORDER BY created_at"
+    ))
+    .bind(workflow_id)
+    .bind(ticket_key)
+    .fetch_all(db)
+    .await
+}
+
+async fn save_counts(
+    db: &PgPool,
+    run_id: i64,
+    counts: RunResultCounts,
+    is_completed: bool,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "UPDATE test_run_results SET passed = $2, failed = $3, blocked = $4, skipped = $5, \
+         retest = $6, is_completed = $7, synced_at = NOW() WHERE run_id = $1",
+    )
+    .bind(run_id)
+    .bind(counts.passed)
+    .bind(counts.failed)
+    .bind(counts.blocked)
+    .bind(counts.skipped)
+    .bind(counts.retest)
+    .bind(is_completed)
+    .execute(db)
+    .await?;
+    Ok(())
+}
+
+/// Interval-driven puller of Testmo run results.
+pub struct TestRunResultSync {
+    db: PgPool,
+    client: Arc<TestmoClient>,
+    rate_limiter: Arc<RateLimiter>,
+    interval: Duration,
+}
+
+impl TestRunResultSync {
+    /// Create a sync job polling every `interval`.
+    #[must_use]
+    pub const fn new(
+        db: PgPool,
+        client: Arc<TestmoClient>,
+        rate_limiter: Arc<RateLimiter>,
+        interval: Duration,
+    ) -> Self {
+        Self {
+            db,
+            client,
+            rate_limiter,
+            interval,
+        }
+    }
+
+    /// Pull results for every open run once.
+    ///
+    /// A run that fails to sync is logged and retried on the next pass, as
+    /// are the runs left when Testmo's rate limit runs out.
+    ///
+    /// # Errors
+    /// Returns error if the open runs cannot be listed.
+    pub async fn run_once(&self) -> Result<usize> {
+        let open: Vec<(i64,)> = sqlx::query_as(
+            "SELECT run_id FROM test_run_results \
+             WHERE NOT is_completed AND created_at >= NOW() - make_interval(days => $1)",
+        )
+        .bind(MAX_RUN_AGE_DAYS)
+        .fetch_all(&self.db)
+        .await?;
+
+        let mut synced = 0;
+        for (run_id,) in open {
+            if let Err(retry_after) = self.take_calls(2) {
+                debug!(
+                    retry_after_ms = retry_after.as_millis() as u64,
+                    "Testmo rate limit reached, resuming result sync next pass"
+                );
+                break;
+            }
+            match self.sync_run(run_id).await {
+                Ok(counts) => {
+                    debug!(run_id, total = counts.total(), "Synced Testmo run results");
+                    synced += 1;
+                }
+                Err(e) => warn!(run_id, error = %e, "Failed to sync Testmo run results"),
+            }
+        }
+        Ok(synced)
+    }
+
+    /// Take `calls` Testmo calls from the shared rate limit.
+    fn take_calls(&self, calls: usize) -> std::result::Result<(), Duration> {
+        (0..calls).try_for_each(|_| {
+            self.rate_limiter
+                .take_integration(Integration::Testmo, Instant::now())
+        })
+    }
+
+    async fn sync_run(&self, run_id: i64) -> Result<RunResultCounts> {
+        let run = self.client.get_test_run(run_id).await?;
+        let results = self.client.get_test_run_results(run_id).await?;
+        let counts = RunResultCounts::from_results(&results);
+        save_counts(&self.db, run_id, counts, run.is_completed).await?;
+        Ok(counts)
+    }
+
+    /// Run the sync in the background.
+    pub fn start(self) {
+        tokio::spawn(async move {
+            info!(
+                interval_secs = self.interval.as_secs(),
+                "Testmo result sync started"
+            );
+            let mut ticker = interval(self.interval);
+            ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            loop {
+                ticker.tick().await;
+                match self.run_once().await {
+                    Ok(synced) if synced > 0 => info!(synced, "Testmo run results synced"),
+                    Ok(_) => {}
+                    Err(e) => warn!(error = %e, "Testmo result sync failed"),
+                }
+            }
+        });
+    }
+}
@@ -0,0 +1,453 @@
+//! Ticket watchlist.
+//!
+//! Users watch Jira tickets they care about. Each watch remembers the
+//! ticket's status, last update time and comment count as last seen, shared
+//! by every watcher of the ticket. A background poller searches Jira for
+//! the watched tickets at a fixed interval, fetches the ones updated since,
+//! and raises an alert when a status changed or comments were added. Its
+//! Jira calls draw from Jira's shared rate limit; tickets left when that
+//! runs out are checked on the next pass.
+
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use qa_pms_jira::{jql, JiraTicketsClient, TicketDetail, TicketFilters};
+use qa_pms_core::TenantId;
+use qa_pms_patterns::{AlertService, PatternRepository, StatusChange};
+use serde::Serialize;
+use sqlx::{FromRow, PgPool};
+use tokio::time::{interval, MissedTickBehavior};
+use tracing::{debug, info, warn};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::app::AppState;
+use crate::notifications::Notification;
+use crate::rate_limit::Integration;
+use crate::routes::tickets::{get_jira_client, record_status_change};
+
+/// Tickets looked up per Jira search.
+const SEARCH_BATCH: usize = 50;
+
+const COLUMNS: &str =
+    "id, owner, ticket_key, last_status, last_updated, last_comment_count, created_at";
+
+/// A user's watch on a ticket.
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TicketWatch {
+    /// Watch ID
+    pub id: Uuid,
+    /// Watching user
+    pub owner: String,
+    /// Watched ticket key
+    pub ticket_key: String,
+    /// Ticket status when last checked
+    pub last_status: Option<String>,
+    /// Jira update timestamp when last checked
+    pub last_updated: Option<String>,
+    /// Number of comments when last checked
+    pub last_comment_count: Option<i32>,
+    /// When the watch was added
+    pub created_at: DateTime<Utc>,
+}
+
+/// What is known about a ticket at one point in time.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TicketState {
+    /// Status name
+    pub status: Option<String>,
+    /// Jira update timestamp
+    pub updated: Option<String>,
+    /// Number of comments
+    pub comment_count: Option<i32>,
+}
+
+impl TicketState {
+    /// State of a fetched ticket.
+    #[must_use]
+    pub fn of(ticket: &TicketDetail) -> Self {
+        Self {
+            status: Some(ticket.fields.status.name.clone()),
+            updated: Some(ticket.fields.updated.clone()),
+            comment_count: Some(
+                ticket
+                    .fields
+                    .comment
+                    .as_ref()
+                    .map_or(0, |c| i32::try_from(c.total).unwrap_or(i32::MAX)),
+            ),
+        }
+    }
+
+    /// Describe what changed since `previous`, for the alert message.
+    ///
+    /// Fields that were never recorded are not compared, so a ticket seen
+    /// for the first time yields no changes.
+    #[must_use]
+    pub fn changes_since(&self, previous: &Self) -> Vec<String> {
+        let mut changes = Vec::new();
+        if let (Some(before), Some(after)) = (&previous.status, &self.status) {
+            if before != after {
+                changes.push(format!("Status changed from {before} to {after}"));
+            }
+        }
+        if let (Some(before), Some(after)) = (previous.comment_count, self.comment_count) {
+            match after - before {
+                1 => changes.push("1 new comment".to_string()),
+                added if added > 1 => changes.push(format!("{added} new comments")),
+                _ => {}
+            }
+        }
+        changes
+    }
+}
+
+/// A watched ticket with its last seen state and all its watchers.
+#[derive(Debug, Clone, FromRow)]
+struct WatchedTicket {
+    ticket_key: String,
+    last_status: Option<String>,
+    last_updated: Option<String>,
+    last_comment_count: Option<i32>,
+    watchers: Vec<String>,
+}
+
+impl WatchedTicket {
+    fn state(&self) -> TicketState {
+        TicketState {
+            status: self.last_status.clone(),
+            updated: self.last_updated.clone(),
+            comment_count: self.last_comment_count,
+        }
+    }
+}
+
+/// Watch repository backed by the `ticket_watches` table.
+#[derive(Debug, Clone)]
+pub struct WatchStore {
+    db: PgPool,
+}
+
+impl WatchStore {
+    /// Create a store on the given pool.
+    #[must_use]
+    pub const fn new(db: PgPool) -> Self {
+        Self { db }
+    }
+
+    /// An owner's watches, most recent first.
+    pub async fn list(&self, owner: &str) -> Result<Vec<TicketWatch>, sqlx::Error> {
+        sqlx::query_as(&format!(
+            "SELECT {COLUMNS} FROM ticket_watches WHERE owner = $1 ORDER BY created_at DESC"
+        ))
+        .bind(owner)
+        .fetch_all(&self.db)
+        .await
+    }
+
+    /// Watch a ticket, starting from its current state.
+    ///
+    /// A ticket others already watch keeps the state they last saw, so
+    /// changes not yet alerted are not lost. Returns the watch and whether
+    /// it is new; watching a ticket twice keeps the existing watch.
+    pub async fn watch(
+        &self,
+        owner: &str,
+        ticket_key: &str,
+        state: &TicketState,
+    ) -> Result<(TicketWatch, bool), sqlx::Error> {
+        let created: Option<TicketWatch> = sqlx::query_as(&format!(
+            r"
+            INSERT INTO ticket_watches
+                (id, owner, ticket_key, last_status, last_updated, last_comment_count, created_at)
+            SELECT $1, $2, $3,
+                   COALESCE(seen.last_status, $4),
+                   COALESCE(seen.last_updated, $5),
+                   COALESCE(seen.last_comment_count, $6),
+                   NOW()
+            FROM (SELECT 1) AS one
+            LEFT JOIN LATERAL (
+                SELECT last_status, last_updated, last_comment_count
+                FROM ticket_watches WHERE ticket_key = $3 LIMIT 1
+            ) AS seen ON TRUE
+            WHERE NOT EXISTS (
+                SELECT 1 FROM ticket_watches WHERE owner = $2 AND ticket_key = $3
+            )
+            RETURNING {COLUMNS}
+            "
+        ))
+        .bind(Uuid::new_v4())
+        .bind(owner)
+        .bind(ticket_key)
+        .bind(&state.status)
+        .bind(&state.updated)
+        .bind(state.comment_count)
+        .fetch_optional(&self.db)
+        .await?;
+        if let Some(watch) = created {
+            return Ok((watch, true));
+        }
+        let existing = sqlx::query_as(&format!(
+            "SELECT {COLUMNS} FROM ticket_watches WHERE owner = $1 AND ticket_key = $2"
+        ))
+        .bind(owner)
+        .bind(ticket_key)
+        .fetch_one(&self.db)
+        .await?;
+        Ok((existing, false))
+    }
+
+    /// Stop watching a ticket. Returns whether a watch existed.
+    pub async fn unwatch(&self, owner: &str, ticket_key: &str) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query("DELETE FROM ticket_watches WHERE owner = $1 AND ticket_key = $2")
+            .bind(owner)
+            .bind(ticket_key)
+            .execute(&self.db)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Every watched ticket once, with its watchers.
+    ///
+    /// All watches of a ticket share the same state, so any row's values
+    /// will do.
+    async fn watched_tickets(&self) -> Result<Vec<WatchedTicket>, sqlx::Error> {
+        sqlx::query_as(
+            r"
+            SELECT ticket_key,
+                   MAX(last_status) AS last_status,
+                   MAX(last_updated) AS last_updated,
+                   MAX(last_comment_count) AS last_comment_count,
+                   ARRAY_AGG(owner ORDER BY owner) AS watchers
+            FROM ticket_watches
+            GROUP BY ticket_key
+            ORDER BY ticket_key
+            ",
+        )
+        .fetch_all(&self.db)
+        .await
+    }
+
+    /// Record the state of a ticket for all its watchers.
+    async fn record_state(&self, ticket_key: &str, state: &TicketState) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r"
+            UPDATE ticket_watches
+            SET last_status = $2, last_updated = $3, last_comment_count = $4
+            WHERE ticket_key = $1
+            ",
+        )
+        .bind(ticket_key)
+        .bind(&state.status)
+        .bind(&state.updated)
+        .bind(state.comment_count)
+        .execute(&self.db)
+        .await?;
+        Ok(())
+    }
+}
+
+/// Interval-driven check of watched tickets.
+pub struct WatchPoller {
+    state: AppState,
+    interval: Duration,
+}
+
+impl WatchPoller {
+    /// Create a poller from settings, or `None` if polling is disabled.
+    #[must_use]
+    pub fn new(state: AppState) -> Option<Self> {
+        let secs = state.settings.ticket_watch_interval_secs;
+        if secs == 0 {
+            return None;
+        }
+        Some(Self {
+            state,
+            interval: Duration::from_secs(secs),
+        })
+    }
+
+    /// Check every watched ticket once and raise alerts for changes.
+    ///
+    /// Returns the number of alerts raised.
+    ///
+    /// # Errors
+    /// Returns error if the watches cannot be read or Jira is not configured.
+    pub async fn run_once(&self) -> Result<usize> {
+        let watched = self.state.ticket_watches.watched_tickets().await?;
+        if watched.is_empty() {
+            return Ok(0);
+        }
+        let client = get_jira_client(&self.state, &TenantId::default())
+            .await
+            .map_err(|e| anyhow!("Jira unavailable: {e}"))?;
+
+        let mut alerts = 0;
+        for batch in watched.chunks(SEARCH_BATCH) {
+            if !self.take_jira_call() {
+                return Ok(alerts);
+            }
+            let updated = match updated_tickets(&client, batch).await {
+                Ok(updated) => updated,
+                Err(e) => {
+                    warn!(error = %e, "Failed to search watched tickets");
+                    continue;
+                }
+            };
+            for ticket in updated {
+                if !self.take_jira_call() {
+                    return Ok(alerts);
+                }
+                match self.check(&client, ticket).await {
+                    Ok(true) => alerts += 1,
+                    Ok(false) => {}
+                    Err(e) => {
+                        warn!(ticket = %ticket.ticket_key, error = %e, "Failed to check watched ticket")
+                    }
+                }
+            }
+        }
+        Ok(alerts)
+    }
+
+    /// Take one call from Jira's shared rate limit.
+    fn take_jira_call(&self) -> bool {
+        match self
+            .state
+            .rate_limiter
+            .take_integration(Integration::Jira, Instant::now())
+        {
+            Ok(()) => true,
+            Err(retry_after) => {
+                debug!(
+                    retry_after_ms = retry_after.as_millis() as u64,
+                    "Jira rate limit reached, resuming watched ticket checks next pass"
+                );
+                false
+            }
+        }
+    }
+
+    /// Fetch one ticket, alert on changes and record its new state.
+    async fn check(&self, client: &JiraTicketsClient, ticket: &WatchedTicket) -> Result<bool> {
+        let detail = client.get_ticket(&ticket.ticket_key).await?;
+        let current = TicketState::of(&detail);
+        let previous = ticket.state();
+        let changes = current.changes_since(&previous);
+        if let (Some(before), Some(after)) = (&previous.status, &current.status) {
+            if before != after {
+                record_status_change(
+                    &self.state,
+                    StatusChange::new(&ticket.ticket_key, Some(before), after),
+                )
+                .await;
+            }
+        }
+
+        let mut raised = false;
+        if !changes.is_empty() {
+            let alert = AlertService::new(PatternRepository::new(self.state.db.clone()))
+                .generate_ticket_alert(
+                    &ticket.ticket_key,
+                    format!("{}: {}", ticket.ticket_key, detail.fields.summary),
+                    format!(
+                        "{}. Watched by {}.",
+                        changes.join("; "),
+                        ticket.watchers.join(", ")
+                    ),
+                )
+                .await?;
+            if let Some(alert) = alert {
+                raised = true;
+                self.state.notifications.publish(Notification::alert_created(&alert));
+            }
+        }
+        self.state
+            .ticket_watches
+            .record_state(&ticket.ticket_key, &current)
+            .await?;
+        Ok(raised)
+    }
+
+    /// Run the checks in the background.
+    pub fn start(self) {
+        tokio::spawn(async move {
+            info!(
+                interval_secs = self.interval.as_secs(),
+                "Ticket watch poller started"
+            );
+            let mut ticker = interval(self.interval);
+            ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            ticker.tick().await;
+            loop {
+                ticker.tick().await;
+                match self.run_once().await {
+                    Ok(alerts) => debug!(alerts, "Checked watched tickets"),
+                    Err(e) => warn!(error = %e, "Watched ticket check failed"),
+                }
+            }
+        });
+    }
+}
+
+/// The tickets of `batch` whose Jira update time differs from the one seen.
+async fn updated_tickets<'a>(
+    client: &JiraTicketsClient,
+    batch: &'a [WatchedTicket],
+) -> Result<Vec<&'a WatchedTicket>> {
+    let keys = batch
+        .iter()
+        .map(|t| jql::quote(&t.ticket_key))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let filters = TicketFilters {
+        raw_jql: Some(format!("key IN ({keys})")),
+        ..TicketFilters::default()
+    };
+    let limit = u32::try_from(batch.len()).unwrap_or(u32::MAX);
+    let found = client.list_tickets(&filters, 0, limit).await?;
+    Ok(batch
+        .iter()
+        .filter(|watched| {
+            found.issues.iter().any(|issue| {
+                issue.key == watched.ticket_key
+                    && watched.last_updated.as_deref() != Some(issue.fields.updated.as_str())
+            })
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state(status: &str, comments: i32) -> TicketState {
+        TicketState {
+            status: Some(status.to_string()),
+            updated: None,
+            comment_count: Some(comments),
+        }
+    }
+
+    #[test]
+    fn test_changes_since_reports_status_and_comments() {
+        assert_eq!(
+            state("QA", 5).changes_since(&state("In Progress", 3)),
+            vec!["Status changed from In Progress to QA", "2 new comments"]
+        );
+        assert_eq!(
+            state("QA", 4).changes_since(&state("QA", 3)),
+            vec!["1 new comment"]
+        );
+        assert!(state("QA", 2).changes_since(&state("QA", 3)).is_empty());
+    }
+
+    #[test]
+    fn test_changes_since_ignores_unrecorded_fields() {
+        assert!(state("QA", 3)
+            .changes_since(&TicketState::default())
+            .is_empty());
+    }
+}
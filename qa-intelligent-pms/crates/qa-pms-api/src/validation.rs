@@ -0,0 +1,212 @@
+//! Declarative request validation.
+//!
+//! Request DTOs derive `validator::Validate`; handlers take `ValidatedJson<T>`
+//! instead of `Json<T>`. Every failed rule is reported in a single 422
+//! response (`ApiError::Unprocessable`) with camelCase field paths matching
+//! the JSON body.
+
+use std::borrow::Cow;
+
+use axum::{
+    async_trait,
+    extract::{rejection::JsonRejection, FromRequest, Request},
+    Json,
+};
+use serde::de::DeserializeOwned;
+use validator::{Validate, ValidationError, ValidationErrors, ValidationErrorsKind};
+
+use qa_pms_core::error::{ApiError, FieldViolation};
+
+/// JSON body extractor that runs `Validate` after deserializing.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ValidatedJson<T>(pub T);
+
+#[async_trait]
+impl<T, S> FromRequest<S> for ValidatedJson<T>
+where
+    T: DeserializeOwned + Validate,
+    S: Send + Sync,
+{
+    type Rejection = ApiError;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let Json(value) = Json::<T>::from_request(req, state)
+            .await
+            .map_err(rejection_to_error)?;
+        value
+            .validate()
+            .map_err(|e| ApiError::Unprocessable(violations(&e)))?;
+        Ok(Self(value))
+    }
+}
+
+fn rejection_to_error(rejection: JsonRejection) -> ApiError {
+    match rejection {
+        // Well-formed JSON with wrong shape/types is a validation failure
+        JsonRejection::JsonDataError(e) => {
+            ApiError::Unprocessable(vec![FieldViolation::new("body", "invalid", e.body_text())])
+        }
+        other => ApiError::Validation(other.body_text()),
+    }
+}
+
+/// Flatten `ValidationErrors` into violations sorted by field path.
+pub fn violations(errors: &ValidationErrors) -> Vec<FieldViolation> {
+    let mut out = Vec::new();
+    collect(errors, "", &mut out);
+    out.sort_by(|a, b| a.field.cmp(&b.field));
+    out
+}
+
+fn collect(errors: &ValidationErrors, prefix: &str, out: &mut Vec<FieldViolation>) {
+    for (field, kind) in errors.errors() {
+        let path = join_path(prefix, &camel_case(field));
+        match kind {
+            ValidationErrorsKind::Field(field_errors) => {
+                out.extend(field_errors.iter().map(|e| FieldViolation::new(&path, e.code.as_ref(), message(e))));
+            }
+            ValidationErrorsKind::Struct(nested) => collect(nested, &path, out),
+            ValidationErrorsKind::List(items) => {
+                for (index, nested) in items {
+                    collect(nested, &format!("{path}[{index}]"), out);
+                }
+            }
+        }
+    }
+}
+
+fn join_path(prefix: &str, field: &str) -> String {
+    if prefix.is_empty() {
+        field.to_string()
+    } else {
+        format!("{prefix}.{field}")
+    }
+}
+
+fn camel_case(field: &str) -> String {
+    let mut out = String::with_capacity(field.len());
+    let mut upper = false;
+    for c in field.chars() {
+        if c == '_' {
+            upper = true;
+        } else if upper {
+            out.extend(c.to_uppercase());
+            upper = false;
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+fn message(error: &ValidationError) -> String {
+    if let Some(message) = &error.message {
+        return message.to_string();
+    }
+    match error.code.as_ref() {
+        "email" => "must be a valid email address".to_string(),
+        "url" => "must be a valid URL".to_string(),
+        "length" => match (error.params.get("min"), error.params.get("max")) {
+            (Some(min), Some(max)) => format!("length must be between {min} and {max}"),
+            (Some(min), None) => format!("length must be at least {min}"),
+            (None, Some(max)) => format!("length must be at most {max}"),
+            (None, None) => "invalid length".to_string(),
+        },
+        "range" => match (error.params.get("min"), error.params.get("max")) {
+            (Some(min), Some(max)) => format!("must be between {min} and {max}"),
+            (Some(min), None) => format!("must be at least {min}"),
+            (None, Some(max)) => format!("must be at most {max}"),
+            (None, None) => "out of range".to_string(),
+        },
+        code => format!("failed `{code}` check"),
+    }
+}
+
+// ============================================================================
+// Custom rules
+// ============================================================================
+
+/// Reject empty or whitespace-only strings.
+///
+/// # Errors
+/// Returns a `blank` validation error.
+pub fn not_blank(value: &str) -> Result<(), ValidationError> {
+    if value.trim().is_empty() {
+        return Err(ValidationError::new("blank").with_message(Cow::Borrowed("must not be blank")));
+    }
+    Ok(())
+}
+
+/// Check that `value` is one of `allowed` (case-insensitive).
+///
+/// # Errors
+/// Returns a `one_of` validation error listing the allowed values.
+pub fn one_of(value: &str, allowed: &[&str]) -> Result<(), ValidationError> {
+    if allowed.iter().any(|a| a.eq_ignore_ascii_case(value)) {
+        return Ok(());
+    }
+    Err(ValidationError::new("one_of")
+        .with_message(Cow::Owned(format!("must be one of: {}", allowed.join(", ")))))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Debug, Deserialize, Validate)]
+    struct Link {
+        #[validate(url)]
+        url: String,
+    }
+
+    #[derive(Debug, Deserialize, Validate)]
+    struct Sample {
+        #[validate(custom(function = "not_blank"))]
+        display_name: String,
+        #[validate(email)]
+        jira_email: String,
+        #[validate(length(min = 1))]
+        ticket_states: Vec<String>,
+        #[validate(range(min = 1, max = 100))]
+        page_size: u32,
+        #[validate(nested)]
+        links: Vec<Link>,
+    }
+
+    #[test]
+    fn test_violations_collect_all_fields() {
+        let sample = Sample {
+            display_name: "   ".into(),
+            jira_email: "not-an-email".into(),
+            ticket_states: vec![],
+            page_size: 500,
+            links: vec![
+                Link { url: "https://example.com".into() },
+                Link { url: "nope".into() },
+            ],
+        };
+
+        let violations = violations(&sample.validate().unwrap_err());
+        let fields: Vec<&str> = violations.iter().map(|v| v.field.as_str()).collect();
+        assert_eq!(
+            fields,
+            vec!["displayName", "jiraEmail", "links[1].url", "pageSize", "ticketStates"]
+        );
+        assert_eq!(violations[0].message, "must not be blank");
+        assert_eq!(violations[3].message, "must be between 1 and 100");
+    }
+
+    #[test]
+    fn test_one_of_is_case_insensitive() {
+        assert!(one_of("Resolved", &["new", "resolved"]).is_ok());
+        let err = one_of("closed", &["new", "resolved"]).unwrap_err();
+        assert_eq!(err.message.as_deref(), Some("must be one of: new, resolved"));
+    }
+
+    #[test]
+    fn test_camel_case() {
+        assert_eq!(camel_case("ticket_states"), "ticketStates");
+        assert_eq!(camel_case("id"), "id");
+    }
+}
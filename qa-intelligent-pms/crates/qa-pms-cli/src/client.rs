@@ -0,0 +1,189 @@
+//! Thin HTTP client for the QA Intelligent PMS API.
+
+use anyhow::{bail, Context, Result};
+use reqwest::{Client, RequestBuilder};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Default API base URL (matches the server's default port).
+pub const DEFAULT_API_URL: &str = "http://localhost:3000";
+
+/// Error body returned by the API.
+#[derive(Debug, Deserialize)]
+struct ErrorBody {
+    error: String,
+    code: String,
+}
+
+/// Header carrying an API key.
+const API_KEY_HEADER: &str = "x-api-key";
+
+/// How the client authenticates.
+#[derive(Debug, Clone, Default)]
+pub struct Credentials {
+    /// Bearer token from signing in
+    pub token: Option<String>,
+    /// API key, sent as `X-Api-Key`
+    pub api_key: Option<String>,
+}
+
+/// API client bound to a base URL.
+pub struct ApiClient {
+    client: Client,
+    base_url: String,
+    credentials: Credentials,
+}
+
+impl ApiClient {
+    /// Create a client for the given base URL, sending `credentials` when the
+    /// API requires authentication.
+    ///
+    /// # Errors
+    /// Returns error if the HTTP client cannot be built.
+    pub fn new(base_url: &str, credentials: Credentials) -> Result<Self> {
+        let client = Client::builder()
+            .timeout(Duration::from_secs(30))
+            .build()
+            .context("Failed to build HTTP client")?;
+        Ok(Self {
+            client,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            credentials,
+        })
+    }
+
+    /// Build the full URL for an API path.
+    pub fn url(&self, path: &str) -> String {
+        format!("{}/api/v1/{}", self.base_url, path.trim_start_matches('/'))
+    }
+
+    /// GET a path and decode the JSON response.
+    ///
+    /// # Errors
+    /// Returns error on transport failures or non-2xx responses.
+    pub async fn get<T: DeserializeOwned>(&self, path: &str) -> Result<T> {
+        self.send(self.client.get(self.url(path))).await
+    }
+
+    /// GET a path with query parameters.
+    ///
+    /// # Errors
+    /// Returns error on transport failures or non-2xx responses.
+    pub async fn get_with_query<T: DeserializeOwned, Q: Serialize + ?Sized>(
+        &self,
+        path: &str,
+        query: &Q,
+    ) -> Result<T> {
+        self.send(self.client.get(self.url(path)).query(query)).await
+    }
+
+    /// POST a JSON body to a path.
+    ///
+    /// # Errors
+    /// Returns error on transport failures or non-2xx responses.
+    pub async fn post<T: DeserializeOwned, B: Serialize + ?Sized>(
+        &self,
+        path: &str,
+        body: &B,
+    ) -> Result<T> {
+        self.send(self.client.post(self.url(path)).json(body)).await
+    }
+
+    /// POST a JSON body to a path with query parameters.
+    ///
+    /// # Errors
+    /// Returns error on transport failures or non-2xx responses.
+    pub async fn post_with_query<T: DeserializeOwned, B: Serialize + ?Sized, Q: Serialize + ?Sized>(
+        &self,
+        path: &str,
+        body: &B,
+        query: &Q,
+    ) -> Result<T> {
+        self.send(self.client.post(self.url(path)).query(query).json(body)).await
+    }
+
+    /// POST to a path without a body.
+    ///
+    /// # Errors
+    /// Returns error on transport failures or non-2xx responses.
+    pub async fn post_empty<T: DeserializeOwned>(&self, path: &str) -> Result<T> {
+        self.send(self.client.post(self.url(path))).await
+    }
+
+    /// Attach the configured credentials to a request.
+    fn authorize(&self, mut request: RequestBuilder) -> RequestBuilder {
+        if let Some(token) = &self.credentials.token {
+            request = request.bearer_auth(token);
+        }
+        if let Some(api_key) = &self.credentials.api_key {
+            request = request.header(API_KEY_HEADER, api_key);
+        }
+        request
+    }
+
+    async fn send<T: DeserializeOwned>(&self, request: RequestBuilder) -> Result<T> {
+        let response = self
+            .authorize(request)
+            .send()
+            .await
+            .with_context(|| format!("Could not reach API at {}", self.base_url))?;
+
+        let status = response.status();
+        let body = response.text().await.context("Failed to read response body")?;
+
+        if !status.is_success() {
+            match serde_json::from_str::<ErrorBody>(&body) {
+                Ok(err) => bail!("{} ({}): {}", status, err.code, err.error),
+                Err(_) => bail!("{status}: {body}"),
+            }
+        }
+
+        // Some endpoints return no body; decode that as JSON null
+        let body = if body.is_empty() { "null" } else { body.as_str() };
+        serde_json::from_str(body).context("Unexpected response format")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_url_joins_base_and_path() {
+        let client = ApiClient::new("http://localhost:3000/", Credentials::default()).unwrap();
+        assert_eq!(
+            client.url("/workflows/templates"),
+            "http://localhost:3000/api/v1/workflows/templates"
+        );
+        assert_eq!(client.url("tickets"), "http://localhost:3000/api/v1/tickets");
+    }
+
+    fn headers_sent(credentials: Credentials) -> reqwest::header::HeaderMap {
+        let client = ApiClient::new(DEFAULT_API_URL, credentials).unwrap();
+        let request = client
+            .authorize(client.client.get(client.url("tickets")))
+            .build()
+            .unwrap();
+        request.headers().clone()
+    }
+
+    #[test]
+    fn test_requests_carry_credentials() {
+        let headers = headers_sent(Credentials {
+            token: Some("jwt-token".to_string()),
+            api_key: None,
+        });
+        assert_eq!(headers["authorization"], "Bearer jwt-token");
+        assert!(headers.get(API_KEY_HEADER).is_none());
+
+        let headers = headers_sent(Credentials {
+            token: None,
+            api_key: Some("qpk_0123abcd_secret".to_string()),
+        });
+        assert_eq!(headers[API_KEY_HEADER], "qpk_0123abcd_secret");
+        assert!(headers.get("authorization").is_none());
+
+        assert!(headers_sent(Credentials::default()).is_empty());
+    }
+}
@@ -0,0 +1,467 @@
+//! Subcommand implementations.
+//!
+//! Responses are decoded as loose JSON so the CLI keeps working when the API
+//! adds fields; `--json` prints them verbatim.
+
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::client::ApiClient;
+
+/// Output settings shared by all commands.
+#[derive(Debug, Clone, Copy)]
+pub struct Output {
+    /// Print raw JSON instead of formatted text
+    pub json: bool,
+}
+
+impl Output {
+    /// Print a response either as raw JSON or via the formatter.
+    fn emit(self, value: &Value, format: impl FnOnce(&Value)) {
+        if self.json {
+            println!("{}", serde_json::to_string_pretty(value).unwrap_or_default());
+        } else {
+            format(value);
+        }
+    }
+}
+
+fn str_field<'a>(value: &'a Value, key: &str) -> &'a str {
+    value.get(key).and_then(Value::as_str).unwrap_or("-")
+}
+
+fn i64_field(value: &Value, key: &str) -> i64 {
+    value.get(key).and_then(Value::as_i64).unwrap_or_default()
+}
+
+/// Format seconds as `1h 02m 03s`.
+pub fn format_duration(total_seconds: i64) -> String {
+    let hours = total_seconds / 3600;
+    let minutes = (total_seconds % 3600) / 60;
+    let seconds = total_seconds % 60;
+    if hours > 0 {
+        format!("{hours}h {minutes:02}m {seconds:02}s")
+    } else {
+        format!("{minutes}m {seconds:02}s")
+    }
+}
+
+// ============================================================================
+// Tickets
+// ============================================================================
+
+/// List tickets with optional filters.
+pub async fn list_tickets(
+    api: &ApiClient,
+    out: Output,
+    statuses: &[String],
+    assignee: Option<String>,
+    project: Option<String>,
+    cursor: Option<String>,
+    limit: Option<u32>,
+) -> Result<()> {
+    let mut query = Vec::new();
+    if let Some(cursor) = cursor {
+        query.push(("cursor", cursor));
+    }
+    if let Some(limit) = limit {
+        query.push(("limit", limit.to_string()));
+    }
+    if !statuses.is_empty() {
+        query.push(("status", statuses.join(",")));
+    }
+    if let Some(assignee) = assignee {
+        query.push(("assignee", assignee));
+    }
+    if let Some(project) = project {
+        query.push(("project", project));
+    }
+
+    let response: Value = api.get_with_query("tickets", &query).await?;
+    out.emit(&response, |r| {
+        let tickets = r.get("tickets").and_then(Value::as_array);
+        for ticket in tickets.into_iter().flatten() {
+            println!(
+                "{:<12} {:<18} {}",
+                str_field(ticket, "key"),
+                str_field(ticket, "status"),
+                str_field(ticket, "title")
+            );
+        }
+        println!("\n{} tickets total", i64_field(r, "total"));
+        if let Some(next) = r.pointer("/pageInfo/nextCursor").and_then(Value::as_str) {
+            println!("More available: --cursor {next}");
+        }
+    });
+    Ok(())
+}
+
+// ============================================================================
+// Workflows
+// ============================================================================
+
+/// One line per template in a `GET /workflows/templates` response.
+fn template_lines(response: &Value) -> Vec<String> {
+    response
+        .get("templates")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .map(|template| {
+            format!(
+                "{}  {:<28} {} steps, ~{} min",
+                str_field(template, "id"),
+                str_field(template, "name"),
+                i64_field(template, "stepCount"),
+                i64_field(template, "estimatedMinutes")
+            )
+        })
+        .collect()
+}
+
+/// List workflow templates.
+pub async fn list_templates(api: &ApiClient, out: Output) -> Result<()> {
+    let templates: Value = api.get("workflows/templates").await?;
+    out.emit(&templates, |t| {
+        for line in template_lines(t) {
+            println!("{line}");
+        }
+    });
+    Ok(())
+}
+
+/// Start a workflow for a ticket.
+pub async fn start_workflow(
+    api: &ApiClient,
+    out: Output,
+    template_id: Uuid,
+    ticket_id: &str,
+    user_id: &str,
+) -> Result<()> {
+    let body = json!({
+        "templateId": template_id,
+        "ticketId": ticket_id,
+        // Title is informational only; the server does not store it
+        "ticketTitle": ticket_id,
+        "userId": user_id,
+    });
+    let response: Value = api.post("workflows", &body).await?;
+    out.emit(&response, |r| {
+        println!("Started workflow {} ({})", str_field(r, "id"), str_field(r, "templateName"));
+        if let Some(step) = r.get("currentStep") {
+            println!(
+                "Current step: {} of {} — {}",
+                i64_field(step, "index") + 1,
+                i64_field(r, "totalSteps"),
+                str_field(step, "name")
+            );
+        }
+    });
+    Ok(())
+}
+
+/// Show a workflow with per-step status.
+pub async fn show_workflow(api: &ApiClient, out: Output, id: Uuid) -> Result<()> {
+    let workflow: Value = api.get(&format!("workflows/{id}")).await?;
+    out.emit(&workflow, |w| {
+        println!(
+            "{} — {} [{}]",
+            str_field(w, "ticketId"),
+            str_field(w, "templateName"),
+            str_field(w, "status")
+        );
+        let current = i64_field(w, "currentStep");
+        for step in w.get("steps").and_then(Value::as_array).into_iter().flatten() {
+            let index = i64_field(step, "index");
+            let marker = if index == current { ">" } else { " " };
+            println!(
+                "{marker} {index:>2}. {:<32} {}",
+                str_field(step, "name"),
+                str_field(step, "status")
+            );
+        }
+    });
+    Ok(())
+}
+
+/// Complete or skip a step.
+pub async fn step_action(
+    api: &ApiClient,
+    out: Output,
+    id: Uuid,
+    step: i32,
+    action: &str,
+    notes: Option<String>,
+) -> Result<()> {
+    let path = format!("workflows/{id}/steps/{step}/{action}");
+    let response: Value = if action == "complete" {
+        api.post(&path, &json!({ "notes": notes })).await?
+    } else {
+        api.post_empty(&path).await?
+    };
+    out.emit(&response, |r| {
+        if r.get("workflowCompleted").and_then(Value::as_bool).unwrap_or(false) {
+            println!("Last step done — run `qa-pms workflow finish {id}` to close the workflow");
+        } else if let Some(next) = r.get("nextStep") {
+            println!("Next step {}: {}", i64_field(next, "index"), str_field(next, "name"));
+        }
+    });
+    Ok(())
+}
+
+/// Pause, resume, complete or cancel a workflow.
+pub async fn workflow_action(api: &ApiClient, out: Output, id: Uuid, action: &str) -> Result<()> {
+    let response: Value = api.post_empty(&format!("workflows/{id}/{action}")).await?;
+    out.emit(&response, |r| println!("{}", str_field(r, "message")));
+    Ok(())
+}
+
+// ============================================================================
+// Timers
+// ============================================================================
+
+/// Start timing a step.
+pub async fn start_timer(api: &ApiClient, out: Output, workflow: Uuid, step: i32) -> Result<()> {
+    let session: Value = api
+        .post_empty(&format!("time/sessions/{workflow}/start/{step}"))
+        .await?;
+    out.emit(&session, |s| {
+        println!("Timer {} started for step {step}", str_field(s, "id"));
+    });
+    Ok(())
+}
+
+/// End, pause or resume the active timer of a workflow.
+pub async fn timer_action(api: &ApiClient, out: Output, workflow: Uuid, action: &str) -> Result<()> {
+    let active: Value = api.get(&format!("time/sessions/{workflow}/active")).await?;
+    let Some(session_id) = active.get("id").and_then(Value::as_str) else {
+        bail!("No active timer for workflow {workflow}");
+    };
+
+    let response: Value = api
+        .post_empty(&format!("time/sessions/{session_id}/{action}"))
+        .await?;
+    out.emit(&response, |r| match action {
+        "end" => println!(
+            "Timer stopped after {}",
+            format_duration(i64_field(r, "totalSeconds"))
+        ),
+        _ => println!("Timer {}", str_field(r, "status")),
+    });
+    Ok(())
+}
+
+/// Show recorded time for a workflow.
+pub async fn timer_status(api: &ApiClient, out: Output, workflow: Uuid) -> Result<()> {
+    let response: Value = api.get(&format!("time/sessions/{workflow}")).await?;
+    out.emit(&response, |r| {
+        for session in r.get("sessions").and_then(Value::as_array).into_iter().flatten() {
+            let state = if session.get("isActive").and_then(Value::as_bool).unwrap_or(false) {
+                "running"
+            } else {
+                "stopped"
+            };
+            println!(
+                "step {:>2}  {:<8} {}",
+                i64_field(session, "stepIndex"),
+                state,
+                format_duration(i64_field(session, "totalSeconds"))
+            );
+        }
+        println!("Total: {}", format_duration(i64_field(r, "totalSeconds")));
+    });
+    Ok(())
+}
+
+// ============================================================================
+// Diagnostics
+// ============================================================================
+
+/// Run diagnostics for all integrations or a single one.
+pub async fn diagnostics(api: &ApiClient, out: Output, integration: Option<&str>) -> Result<()> {
+    let response: Value = match integration {
+        Some(name) => api.get(&format!("support/diagnostics/{name}")).await?,
+        None => api.get("support/diagnostics").await?,
+    };
+
+    out.emit(&response, |r| {
+        let results = match r.get("results").and_then(Value::as_array) {
+            Some(results) => results.iter().collect(),
+            None => vec![r],
+        };
+        for result in results {
+            let passed = result.get("passed").and_then(Value::as_bool).unwrap_or(false);
+            println!(
+                "[{}] {:<10} {}",
+                if passed { " ok " } else { "FAIL" },
+                str_field(result, "integration"),
+                str_field(result, "message")
+            );
+        }
+        if let Some(summary) = r.get("summary").and_then(Value::as_str) {
+            println!("\n{summary}");
+        }
+    });
+    Ok(())
+}
+
+// ============================================================================
+// Backup
+// ============================================================================
+
+/// Total rows per table in a backup archive.
+fn archive_row_counts(archive: &Value) -> Vec<(String, usize)> {
+    archive
+        .get("tables")
+        .and_then(Value::as_object)
+        .map(|tables| {
+            tables
+                .iter()
+                .map(|(table, rows)| (table.clone(), rows.as_array().map_or(0, Vec::len)))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Download a workspace backup to a file.
+pub async fn export_backup(api: &ApiClient, out: Output, path: &Path) -> Result<()> {
+    let archive: Value = api.get("backup").await?;
+    let text = serde_json::to_string_pretty(&archive)?;
+    std::fs::write(path, text).with_context(|| format!("Failed to write {}", path.display()))?;
+
+    let counts = archive_row_counts(&archive);
+    let summary = json!({
+        "path": path.display().to_string(),
+        "createdAt": archive.get("createdAt"),
+        "tables": counts
+            .iter()
+            .map(|(table, rows)| json!({ "table": table, "rows": rows }))
+            .collect::<Vec<_>>(),
+    });
+    out.emit(&summary, |_| {
+        for (table, rows) in &counts {
+            println!("{table:<26} {rows:>7} rows");
+        }
+        println!("\nBackup written to {}", path.display());
+    });
+    Ok(())
+}
+
+/// Restore a backup file, or check it with `dry_run`.
+pub async fn restore_backup(
+    api: &ApiClient,
+    out: Output,
+    path: &Path,
+    dry_run: bool,
+    replace: bool,
+) -> Result<()> {
+    let text = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let archive: Value = serde_json::from_str(&text).context("Backup file is not valid JSON")?;
+
+    let query = [("dryRun", dry_run), ("replace", replace)];
+    let report: Value = api.post_with_query("backup/restore", &archive, &query).await?;
+    out.emit(&report, |r| {
+        for table in r.get("tables").and_then(Value::as_array).into_iter().flatten() {
+            println!(
+                "{:<26} {:>7} inserted {:>7} skipped",
+                str_field(table, "table"),
+                i64_field(table, "inserted"),
+                i64_field(table, "skipped")
+            );
+        }
+        if dry_run {
+            println!("\nDry run: nothing was changed");
+        } else {
+            println!("\nBackup from {} restored", str_field(r, "archiveCreatedAt"));
+        }
+    });
+    Ok(())
+}
+
+// ============================================================================
+// Encryption
+// ============================================================================
+
+/// Re-encrypt stored secrets with the current key, or count them with `dry_run`.
+pub async fn rotate_keys(api: &ApiClient, out: Output, dry_run: bool) -> Result<()> {
+    let report: Value = api
+        .post_with_query("encryption/rotate-keys", &json!({}), &[("dryRun", dry_run)])
+        .await?;
+    out.emit(&report, |r| {
+        for store in r.get("stores").and_then(Value::as_array).into_iter().flatten() {
+            println!(
+                "{:<20} {:>5} rotated {:>5} unchanged",
+                str_field(store, "store"),
+                i64_field(store, "rotated"),
+                i64_field(store, "unchanged")
+            );
+        }
+        if dry_run {
+            println!("\nDry run: secrets marked rotated still use a previous key");
+        } else {
+            println!("\nAll secrets now use key {}", str_field(r, "keyId"));
+        }
+    });
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(0), "0m 00s");
+        assert_eq!(format_duration(125), "2m 05s");
+        assert_eq!(format_duration(3723), "1h 02m 03s");
+    }
+
+    #[test]
+    fn test_field_helpers_default_missing_values() {
+        let value = json!({ "key": "PROJ-1", "total": 3 });
+        assert_eq!(str_field(&value, "key"), "PROJ-1");
+        assert_eq!(str_field(&value, "missing"), "-");
+        assert_eq!(i64_field(&value, "total"), 3);
+        assert_eq!(i64_field(&value, "missing"), 0);
+    }
+
+    #[test]
+    fn test_template_lines_read_templates_list_response() {
+        let response = json!({
+            "templates": [{
+                "id": "6f1c2a4e-0000-4000-8000-000000000001",
+                "name": "Bug fix",
+                "description": null,
+                "ticketType": "bug",
+                "stepCount": 5,
+                "estimatedMinutes": 45,
+                "isDefault": true,
+                "version": 1
+            }]
+        });
+        assert_eq!(
+            template_lines(&response),
+            vec![format!(
+                "6f1c2a4e-0000-4000-8000-000000000001  {:<28} 5 steps, ~45 min",
+                "Bug fix"
+            )]
+        );
+        assert!(template_lines(&json!({ "templates": [] })).is_empty());
+    }
+
+    #[test]
+    fn test_archive_row_counts() {
+        let archive = json!({
+            "tables": { "test_cases": [{ "id": 1 }, { "id": 2 }], "time_sessions": [] }
+        });
+        assert_eq!(
+            archive_row_counts(&archive),
+            vec![("test_cases".to_string(), 2), ("time_sessions".to_string(), 0)]
+        );
+        assert!(archive_row_counts(&json!({})).is_empty());
+    }
+}
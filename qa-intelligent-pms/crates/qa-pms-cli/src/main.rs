@@ -0,0 +1,343 @@
+//! QA Intelligent PMS command-line client.
+//!
+//! Drives workflows, timers, tickets and diagnostics through the HTTP API so
+//! testers can track work from a terminal.
+
+use std::path::PathBuf;
+
+use anyhow::Result;
+use clap::{Parser, Subcommand};
+use uuid::Uuid;
+
+mod client;
+mod commands;
+
+use client::{ApiClient, Credentials, DEFAULT_API_URL};
+
+/// QA Intelligent PMS from the terminal.
+#[derive(Debug, Parser)]
+#[command(name = "qa-pms", version, about)]
+struct Cli {
+    /// Base URL of the QA PMS API
+    #[arg(long, env = "QA_PMS_API_URL", default_value = DEFAULT_API_URL, global = true)]
+    api_url: String,
+
+    /// Bearer token for an API with authentication enabled
+    #[arg(long, env = "QA_PMS_TOKEN", hide_env_values = true, global = true)]
+    token: Option<String>,
+
+    /// API key for CI pipelines and scripts
+    #[arg(long, env = "QA_PMS_API_KEY", hide_env_values = true, global = true)]
+    api_key: Option<String>,
+
+    /// Print raw JSON responses instead of formatted output
+    #[arg(long, global = true)]
+    json: bool,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// List Jira tickets
+    Tickets {
+        /// Status filter (repeatable)
+        #[arg(long = "status")]
+        statuses: Vec<String>,
+        /// Filter by assignee
+        #[arg(long)]
+        assignee: Option<String>,
+        /// Filter by project key
+        #[arg(long)]
+        project: Option<String>,
+        /// Continue from the cursor printed by a previous call
+        #[arg(long)]
+        cursor: Option<String>,
+        /// Tickets per page
+        #[arg(long)]
+        limit: Option<u32>,
+    },
+    /// Manage workflows
+    #[command(subcommand)]
+    Workflow(WorkflowCommand),
+    /// Start and stop step timers
+    #[command(subcommand)]
+    Timer(TimerCommand),
+    /// Run integration diagnostics
+    Diagnostics {
+        /// Only check one integration (jira, postman, testmo, splunk, database)
+        integration: Option<String>,
+    },
+    /// Back up and restore the workspace
+    #[command(subcommand)]
+    Backup(BackupCommand),
+    /// Re-encrypt stored secrets with the server's current encryption key
+    RotateKeys {
+        /// Only count the secrets still sealed with a previous key
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum BackupCommand {
+    /// Download a backup archive
+    Export {
+        /// File to write the archive to
+        output: PathBuf,
+    },
+    /// Restore a backup archive
+    Restore {
+        /// Archive written by `backup export`
+        input: PathBuf,
+        /// Check the archive and report counts without changing anything
+        #[arg(long)]
+        dry_run: bool,
+        /// Delete existing rows of the archived tables first
+        #[arg(long)]
+        replace: bool,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum WorkflowCommand {
+    /// List available workflow templates
+    Templates,
+    /// Start a workflow for a ticket
+    Start {
+        /// Jira ticket key
+        ticket: String,
+        /// Template ID
+        #[arg(long)]
+        template: Uuid,
+        /// User starting the workflow
+        #[arg(long, env = "QA_PMS_USER")]
+        user: String,
+    },
+    /// Show workflow details and step progress
+    Show {
+        /// Workflow instance ID
+        id: Uuid,
+    },
+    /// Complete a step
+    Complete {
+        /// Workflow instance ID
+        id: Uuid,
+        /// Step index (0-based)
+        step: i32,
+        /// Notes to record with the step
+        #[arg(long)]
+        notes: Option<String>,
+    },
+    /// Skip a step
+    Skip {
+        /// Workflow instance ID
+        id: Uuid,
+        /// Step index (0-based)
+        step: i32,
+    },
+    /// Pause a workflow
+    Pause {
+        /// Workflow instance ID
+        id: Uuid,
+    },
+    /// Resume a paused workflow
+    Resume {
+        /// Workflow instance ID
+        id: Uuid,
+    },
+    /// Mark a workflow as completed
+    Finish {
+        /// Workflow instance ID
+        id: Uuid,
+    },
+    /// Cancel a workflow
+    Cancel {
+        /// Workflow instance ID
+        id: Uuid,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum TimerCommand {
+    /// Start timing a workflow step
+    Start {
+        /// Workflow instance ID
+        workflow: Uuid,
+        /// Step index (0-based)
+        step: i32,
+    },
+    /// Stop the active timer of a workflow
+    Stop {
+        /// Workflow instance ID
+        workflow: Uuid,
+    },
+    /// Pause the active timer of a workflow
+    Pause {
+        /// Workflow instance ID
+        workflow: Uuid,
+    },
+    /// Resume the paused timer of a workflow
+    Resume {
+        /// Workflow instance ID
+        workflow: Uuid,
+    },
+    /// Show time recorded for a workflow
+    Status {
+        /// Workflow instance ID
+        workflow: Uuid,
+    },
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let credentials = Credentials {
+        token: cli.token,
+        api_key: cli.api_key,
+    };
+    let api = ApiClient::new(&cli.api_url, credentials)?;
+    let out = commands::Output { json: cli.json };
+
+    match cli.command {
+        Command::Tickets {
+            statuses,
+            assignee,
+            project,
+            cursor,
+            limit,
+        } => commands::list_tickets(&api, out, &statuses, assignee, project, cursor, limit).await,
+        Command::Workflow(cmd) => run_workflow(&api, out, cmd).await,
+        Command::Timer(cmd) => run_timer(&api, out, cmd).await,
+        Command::Diagnostics { integration } => {
+            commands::diagnostics(&api, out, integration.as_deref()).await
+        }
+        Command::Backup(cmd) => run_backup(&api, out, cmd).await,
+        Command::RotateKeys { dry_run } => commands::rotate_keys(&api, out, dry_run).await,
+    }
+}
+
+async fn run_backup(api: &ApiClient, out: commands::Output, cmd: BackupCommand) -> Result<()> {
+    match cmd {
+        BackupCommand::Export { output } => commands::export_backup(api, out, &output).await,
+        BackupCommand::Restore {
+            input,
+            dry_run,
+            replace,
+        } => commands::restore_backup(api, out, &input, dry_run, replace).await,
+    }
+}
+
+async fn run_workflow(api: &ApiClient, out: commands::Output, cmd: WorkflowCommand) -> Result<()> {
+    match cmd {
+        WorkflowCommand::Templates => commands::list_templates(api, out).await,
+        WorkflowCommand::Start {
+            ticket,
+            template,
+            user,
+        } => commands::start_workflow(api, out, template, &ticket, &user).await,
+        WorkflowCommand::Show { id } => commands::show_workflow(api, out, id).await,
+        WorkflowCommand::Complete { id, step, notes } => {
+            commands::step_action(api, out, id, step, "complete", notes).await
+        }
+        WorkflowCommand::Skip { id, step } => {
+            commands::step_action(api, out, id, step, "skip", None).await
+        }
+        WorkflowCommand::Pause { id } => commands::workflow_action(api, out, id, "pause").await,
+        WorkflowCommand::Resume { id } => commands::workflow_action(api, out, id, "resume").await,
+        WorkflowCommand::Finish { id } => commands::workflow_action(api, out, id, "complete").await,
+        WorkflowCommand::Cancel { id } => commands::workflow_action(api, out, id, "cancel").await,
+    }
+}
+
+async fn run_timer(api: &ApiClient, out: commands::Output, cmd: TimerCommand) -> Result<()> {
+    match cmd {
+        TimerCommand::Start { workflow, step } => commands::start_timer(api, out, workflow, step).await,
+        TimerCommand::Stop { workflow } => commands::timer_action(api, out, workflow, "end").await,
+        TimerCommand::Pause { workflow } => commands::timer_action(api, out, workflow, "pause").await,
+        TimerCommand::Resume { workflow } => {
+            commands::timer_action(api, out, workflow, "resume").await
+        }
+        TimerCommand::Status { workflow } => commands::timer_status(api, out, workflow).await,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::CommandFactory;
+
+    #[test]
+    fn test_cli_definition_is_valid() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn test_parse_complete_step() {
+        let id = Uuid::new_v4();
+        let cli = Cli::try_parse_from([
+            "qa-pms",
+            "workflow",
+            "complete",
+            &id.to_string(),
+            "2",
+            "--notes",
+            "looks good",
+        ])
+        .unwrap();
+
+        match cli.command {
+            Command::Workflow(WorkflowCommand::Complete { id: parsed, step, notes }) => {
+                assert_eq!(parsed, id);
+                assert_eq!(step, 2);
+                assert_eq!(notes.as_deref(), Some("looks good"));
+            }
+            other => panic!("unexpected command: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_parse_backup_restore_dry_run() {
+        let cli = Cli::try_parse_from(["qa-pms", "backup", "restore", "backup.json", "--dry-run"])
+            .unwrap();
+
+        match cli.command {
+            Command::Backup(BackupCommand::Restore { input, dry_run, replace }) => {
+                assert_eq!(input, PathBuf::from("backup.json"));
+                assert!(dry_run);
+                assert!(!replace);
+            }
+            other => panic!("unexpected command: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_parse_rotate_keys() {
+        let cli = Cli::try_parse_from(["qa-pms", "rotate-keys", "--dry-run"]).unwrap();
+        assert!(matches!(cli.command, Command::RotateKeys { dry_run: true }));
+    }
+
+    #[test]
+    fn test_parse_tickets_with_repeated_status() {
+        let cli = Cli::try_parse_from([
+            "qa-pms",
+            "tickets",
+            "--status",
+            "In Progress",
+            "--status",
+            "Ready for QA",
+            "--json",
+        ])
+        .unwrap();
+
+        assert!(cli.json);
+        match cli.command {
+            Command::Tickets { statuses, cursor, .. } => {
+                assert_eq!(statuses, vec!["In Progress", "Ready for QA"]);
+                assert!(cursor.is_none());
+            }
+            other => panic!("unexpected command: {other:?}"),
+        }
+    }
+}
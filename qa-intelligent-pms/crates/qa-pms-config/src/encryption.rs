@@ -2,6 +2,12 @@
 //!
 //! Uses the `aes-gcm` crate (security audited by NCC Group) for encryption
 //! and `secrecy` for secure secret handling.
+//!
+//! Ciphertexts carry a header naming the key that sealed them
+//! (`v2:<key id>:<hex>`), so the key can be rotated: the new key encrypts,
+//! while retired keys still decrypt until every stored secret has been
+//! re-encrypted. Ciphertexts written before key IDs existed are plain hex and
+//! are tried against every configured key.
 
 use aes_gcm::{
     aead::{Aead, KeyInit},
@@ -10,26 +16,25 @@ use aes_gcm::{
 use anyhow::{Context, Result};
 use rand::RngCore;
 use secrecy::{ExposeSecret, SecretString};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
 use zeroize::Zeroizing;
 
 /// Nonce size for AES-256-GCM (96 bits = 12 bytes)
 const NONCE_SIZE: usize = 12;
 
-/// Encryptor for sensitive configuration data.
-///
-/// Uses AES-256-GCM for authenticated encryption.
+/// Header of ciphertexts that name their key.
+const HEADER: &str = "v2:";
+
+/// A key and the ID written into the ciphertexts it seals.
 #[derive(Clone)]
-pub struct Encryptor {
+struct Key {
+    id: String,
     cipher: Aes256Gcm,
 }
 
-impl Encryptor {
-    /// Create a new encryptor from a hex-encoded 256-bit key.
-    ///
-    /// # Errors
-    ///
-    /// Returns an error if the key is not a valid 64-character hex string.
-    pub fn from_hex_key(hex_key: &str) -> Result<Self> {
+impl Key {
+    fn from_hex(hex_key: &str) -> Result<Self> {
         let key_bytes = Zeroizing::new(
             hex::decode(hex_key).context("Invalid hex encoding for encryption key")?,
         );
@@ -41,15 +46,96 @@ impl Encryptor {
             );
         }
 
+        // A fingerprint, so IDs need no configuration and never collide by accident
+        let id = hex::encode(&Sha256::digest(key_bytes.as_slice())[..4]);
         let key = aes_gcm::Key::<Aes256Gcm>::from_slice(&key_bytes);
-        let cipher = Aes256Gcm::new(key);
+        Ok(Self {
+            id,
+            cipher: Aes256Gcm::new(key),
+        })
+    }
+
+    fn decrypt(&self, data: &[u8]) -> Result<Vec<u8>> {
+        if data.len() < NONCE_SIZE {
+            anyhow::bail!("Ciphertext too short (must include nonce)");
+        }
+        let (nonce_bytes, ciphertext) = data.split_at(NONCE_SIZE);
+        self.cipher
+            .decrypt(Nonce::from_slice(nonce_bytes), ciphertext)
+            .map_err(|e| anyhow::anyhow!("Decryption failed: {e}"))
+    }
+}
+
+/// Values visited by a key rotation.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RotationCount {
+    /// Values re-encrypted with the current key
+    pub rotated: usize,
+    /// Values that already used the current key
+    pub unchanged: usize,
+}
+
+impl RotationCount {
+    /// Add another count to this one.
+    pub fn add(&mut self, other: Self) {
+        self.rotated += other.rotated;
+        self.unchanged += other.unchanged;
+    }
+}
+
+/// Encryptor for sensitive configuration data.
+///
+/// Uses AES-256-GCM for authenticated encryption. Encrypts with the current
+/// key and decrypts with the current or any previous key.
+#[derive(Clone)]
+pub struct Encryptor {
+    current: Key,
+    previous: Vec<Key>,
+}
+
+impl Encryptor {
+    /// Create a new encryptor from a hex-encoded 256-bit key.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the key is not a valid 64-character hex string.
+    pub fn from_hex_key(hex_key: &str) -> Result<Self> {
+        Ok(Self {
+            current: Key::from_hex(hex_key)?,
+            previous: Vec::new(),
+        })
+    }
+
+    /// Also decrypt data sealed with these retired hex-encoded keys.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a key is not a valid 64-character hex string.
+    pub fn with_previous_keys<'a>(
+        mut self,
+        hex_keys: impl IntoIterator<Item = &'a str>,
+    ) -> Result<Self> {
+        for (index, hex_key) in hex_keys.into_iter().enumerate() {
+            let key = Key::from_hex(hex_key)
+                .with_context(|| format!("Invalid previous encryption key #{}", index + 1))?;
+            if key.id != self.current.id {
+                self.previous.push(key);
+            }
+        }
+        Ok(self)
+    }
 
-        Ok(Self { cipher })
+    /// ID of the key new ciphertexts are sealed with.
+    #[must_use]
+    pub fn key_id(&self) -> &str {
+        &self.current.id
     }
 
-    /// Encrypt a plaintext string.
+    /// Encrypt a plaintext string with the current key.
     ///
-    /// Returns the ciphertext as a hex-encoded string with the nonce prepended.
+    /// Returns the key header followed by the hex-encoded ciphertext with the
+    /// nonce prepended.
     ///
     /// # Errors
     ///
@@ -60,6 +146,7 @@ impl Encryptor {
         let nonce = Nonce::from_slice(&nonce_bytes);
 
         let ciphertext = self
+            .current
             .cipher
             .encrypt(nonce, plaintext.as_bytes())
             .map_err(|e| anyhow::anyhow!("Encryption failed: {e}"))?;
@@ -67,30 +154,46 @@ impl Encryptor {
         // Prepend nonce to ciphertext and hex encode
         let mut result = nonce_bytes.to_vec();
         result.extend(ciphertext);
-        Ok(hex::encode(result))
+        Ok(format!(
+            "{HEADER}{}:{}",
+            self.current.id,
+            hex::encode(result)
+        ))
     }
 
-    /// Decrypt a hex-encoded ciphertext (with prepended nonce).
+    /// Decrypt a ciphertext produced by [`Encryptor::encrypt`], including
+    /// ones sealed with a previous key or written without a key header.
     ///
     /// Returns the plaintext as a `SecretString` to prevent accidental logging.
     ///
     /// # Errors
     ///
-    /// Returns an error if decryption fails or the ciphertext is malformed.
-    pub fn decrypt(&self, ciphertext_hex: &str) -> Result<SecretString> {
-        let data = hex::decode(ciphertext_hex).context("Invalid hex encoding for ciphertext")?;
-
-        if data.len() < NONCE_SIZE {
-            anyhow::bail!("Ciphertext too short (must include nonce)");
-        }
-
-        let (nonce_bytes, ciphertext) = data.split_at(NONCE_SIZE);
-        let nonce = Nonce::from_slice(nonce_bytes);
-
-        let plaintext = self
-            .cipher
-            .decrypt(nonce, ciphertext)
-            .map_err(|e| anyhow::anyhow!("Decryption failed: {e}"))?;
+    /// Returns an error if decryption fails, the ciphertext is malformed, or
+    /// its key is not configured.
+    pub fn decrypt(&self, ciphertext: &str) -> Result<SecretString> {
+        let plaintext = match ciphertext.strip_prefix(HEADER) {
+            Some(rest) => {
+                let (id, data) = rest
+                    .split_once(':')
+                    .context("Ciphertext header is missing the key ID")?;
+                let key = self.keys().find(|key| key.id == id).with_context(|| {
+                    format!("Ciphertext was encrypted with key {id}, which is not configured")
+                })?;
+                key.decrypt(&hex::decode(data).context("Invalid hex encoding for ciphertext")?)?
+            }
+            None => {
+                let data =
+                    hex::decode(ciphertext).context("Invalid hex encoding for ciphertext")?;
+                let mut result = Err(anyhow::anyhow!("Decryption failed"));
+                for key in self.keys() {
+                    result = key.decrypt(&data);
+                    if result.is_ok() {
+                        break;
+                    }
+                }
+                result?
+            }
+        };
 
         let plaintext_str =
             String::from_utf8(plaintext).context("Decrypted data is not valid UTF-8")?;
@@ -98,6 +201,31 @@ impl Encryptor {
         Ok(SecretString::from(plaintext_str))
     }
 
+    /// Re-encrypt a ciphertext with the current key unless it already uses
+    /// it, counting the outcome.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the ciphertext cannot be decrypted.
+    pub fn rotate(&self, ciphertext: &mut String, count: &mut RotationCount) -> Result<()> {
+        let current = ciphertext
+            .strip_prefix(HEADER)
+            .and_then(|rest| rest.split_once(':'))
+            .is_some_and(|(id, _)| id == self.current.id);
+        if current {
+            count.unchanged += 1;
+            return Ok(());
+        }
+        let plaintext = self.decrypt(ciphertext)?;
+        *ciphertext = self.encrypt(plaintext.expose_secret())?;
+        count.rotated += 1;
+        Ok(())
+    }
+
+    fn keys(&self) -> impl Iterator<Item = &Key> {
+        std::iter::once(&self.current).chain(&self.previous)
+    }
+
     /// Encrypt a `SecretString`.
     ///
     /// # Errors
@@ -160,4 +288,62 @@ mod tests {
         let result = encryptor.decrypt("invalid");
         assert!(result.is_err());
     }
+
+    const NEW_KEY: &str = "fedcba9876543210fedcba9876543210fedcba9876543210fedcba9876543210";
+
+    #[test]
+    fn test_rotated_key_still_decrypts_old_data() {
+        let old = test_encryptor();
+        let sealed = old.encrypt("jira-token").unwrap();
+        assert!(sealed.starts_with(&format!("v2:{}:", old.key_id())));
+
+        let rotated = Encryptor::from_hex_key(NEW_KEY)
+            .unwrap()
+            .with_previous_keys([
+                "0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef",
+            ])
+            .unwrap();
+        assert_ne!(rotated.key_id(), old.key_id());
+        assert_eq!(
+            rotated.decrypt(&sealed).unwrap().expose_secret(),
+            "jira-token"
+        );
+
+        // Without the old key the error names the missing key
+        let new_only = Encryptor::from_hex_key(NEW_KEY).unwrap();
+        let error = new_only.decrypt(&sealed).unwrap_err().to_string();
+        assert!(error.contains(old.key_id()));
+    }
+
+    #[test]
+    fn test_rotate_reencrypts_only_old_values() {
+        let old = test_encryptor();
+        let rotated = Encryptor::from_hex_key(NEW_KEY)
+            .unwrap()
+            .with_previous_keys([
+                "0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef",
+            ])
+            .unwrap();
+
+        // Ciphertext from before key IDs: bare hex
+        let mut legacy = old.encrypt("legacy").unwrap();
+        legacy = legacy.rsplit(':').next().unwrap().to_string();
+        let mut current = rotated.encrypt("current").unwrap();
+        let before = current.clone();
+
+        let mut count = RotationCount::default();
+        rotated.rotate(&mut legacy, &mut count).unwrap();
+        rotated.rotate(&mut current, &mut count).unwrap();
+        assert_eq!(
+            count,
+            RotationCount {
+                rotated: 1,
+                unchanged: 1
+            }
+        );
+        assert_eq!(current, before);
+
+        let new_only = Encryptor::from_hex_key(NEW_KEY).unwrap();
+        assert_eq!(new_only.decrypt(&legacy).unwrap().expose_secret(), "legacy");
+    }
 }
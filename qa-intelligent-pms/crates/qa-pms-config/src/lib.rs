@@ -4,17 +4,20 @@
 //!
 //! This crate provides:
 //! - YAML configuration file parsing
-//! - AES-256-GCM encryption for sensitive data
+//! - AES-256-GCM encryption for sensitive data, with key rotation
 //! - Environment variable loading via `dotenvy`
 //! - Configuration validation
 //! - User config generation from setup wizard
+//! - Per-tenant encrypted credential storage
 
 pub mod encryption;
 pub mod settings;
+pub mod tenant_credentials;
 pub mod user_config;
 
-pub use encryption::Encryptor;
+pub use encryption::{Encryptor, RotationCount};
 pub use settings::Settings;
+pub use tenant_credentials::TenantCredentialStore;
 pub use user_config::{
     JiraAuthInput, JiraAuthType, JiraConfig, JiraInput, PostmanConfig, PostmanInput, ProfileInput,
     SetupWizardInput, SplunkConfig, SplunkInput, TestmoConfig, TestmoInput, UserConfig, UserProfile,
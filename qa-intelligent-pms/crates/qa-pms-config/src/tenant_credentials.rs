@@ -0,0 +1,311 @@
+//! Per-tenant encrypted credential storage.
+//!
+//! Each tenant's integration credentials (API tokens, passwords) are kept in
+//! their own file, `<tenant>.json`, with every value encrypted under the
+//! application key. The tenant ID is sealed into each value, so a value
+//! copied into another tenant's file fails to decrypt.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use anyhow::{Context, Result};
+use qa_pms_core::TenantId;
+use secrecy::{ExposeSecret, SecretString};
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroizing;
+
+use crate::{Encryptor, RotationCount};
+
+/// On-disk format of a tenant's credential file.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CredentialFile {
+    version: u32,
+    /// Encrypted value per credential name
+    credentials: BTreeMap<String, String>,
+}
+
+/// Encrypted credential files, one per tenant.
+pub struct TenantCredentialStore {
+    dir: PathBuf,
+    encryptor: Encryptor,
+    /// Serializes read-modify-write cycles on the files
+    write_lock: Mutex<()>,
+}
+
+impl TenantCredentialStore {
+    /// Current file format version.
+    const VERSION: u32 = 1;
+
+    /// Create a store keeping files in `dir`.
+    #[must_use]
+    pub const fn new(dir: PathBuf, encryptor: Encryptor) -> Self {
+        Self {
+            dir,
+            encryptor,
+            write_lock: Mutex::new(()),
+        }
+    }
+
+    /// Default location, next to the user config file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the config directory cannot be determined.
+    pub fn default_dir() -> Result<PathBuf> {
+        let config_dir = dirs::config_dir().context("Could not determine config directory")?;
+        Ok(config_dir.join("qa-intelligent-pms").join("tenants"))
+    }
+
+    /// Names of a tenant's stored credentials, sorted.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the tenant's file cannot be read.
+    pub fn names(&self, tenant: &TenantId) -> Result<Vec<String>> {
+        Ok(self.read(tenant)?.credentials.into_keys().collect())
+    }
+
+    /// Get a tenant's credential.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or the value does not
+    /// decrypt for this tenant.
+    pub fn get(&self, tenant: &TenantId, name: &str) -> Result<Option<SecretString>> {
+        let Some(ciphertext) = self.read(tenant)?.credentials.remove(name) else {
+            return Ok(None);
+        };
+        let sealed = self.encryptor.decrypt(&ciphertext)?;
+        let value = sealed
+            .expose_secret()
+            .strip_prefix(&seal_prefix(tenant))
+            .with_context(|| format!("Credential {name} does not belong to tenant {tenant}"))?;
+        Ok(Some(SecretString::from(value.to_string())))
+    }
+
+    /// Store or replace a tenant's credential.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if encryption fails or the file cannot be written.
+    pub fn set(&self, tenant: &TenantId, name: &str, value: &SecretString) -> Result<()> {
+        let sealed = Zeroizing::new(format!("{}{}", seal_prefix(tenant), value.expose_secret()));
+        let ciphertext = self.encryptor.encrypt(&sealed)?;
+        let _guard = self.lock();
+        let mut file = self.read(tenant)?;
+        file.credentials.insert(name.to_string(), ciphertext);
+        self.write(tenant, &file)
+    }
+
+    /// Delete a tenant's credential. Returns whether it existed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or written.
+    pub fn remove(&self, tenant: &TenantId, name: &str) -> Result<bool> {
+        let _guard = self.lock();
+        let mut file = self.read(tenant)?;
+        if file.credentials.remove(name).is_none() {
+            return Ok(false);
+        }
+        self.write(tenant, &file)?;
+        Ok(true)
+    }
+
+    /// Re-encrypt every tenant's credentials with the current key. A dry run
+    /// checks that each value decrypts without writing anything.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a file cannot be read or written, or a value does
+    /// not decrypt with any configured key.
+    pub fn rotate(&self, dry_run: bool) -> Result<RotationCount> {
+        let mut count = RotationCount::default();
+        if !self.dir.exists() {
+            return Ok(count);
+        }
+        let _guard = self.lock();
+        let entries = std::fs::read_dir(&self.dir)
+            .with_context(|| format!("Failed to list {}", self.dir.display()))?;
+        for entry in entries {
+            let path = entry?.path();
+            let tenant = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .and_then(|name| name.strip_suffix(".json"))
+                .and_then(TenantId::parse);
+            let Some(tenant) = tenant else {
+                continue;
+            };
+            let mut file = self.read(&tenant)?;
+            let mut tenant_count = RotationCount::default();
+            for (name, ciphertext) in &mut file.credentials {
+                self.encryptor
+                    .rotate(ciphertext, &mut tenant_count)
+                    .with_context(|| format!("Failed to rotate {name} of tenant {tenant}"))?;
+            }
+            if tenant_count.rotated > 0 && !dry_run {
+                self.write(&tenant, &file)?;
+            }
+            count.add(tenant_count);
+        }
+        Ok(count)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, ()> {
+        self.write_lock
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    fn path(&self, tenant: &TenantId) -> PathBuf {
+        self.dir.join(format!("{tenant}.json"))
+    }
+
+    fn read(&self, tenant: &TenantId) -> Result<CredentialFile> {
+        let path = self.path(tenant);
+        if !path.exists() {
+            return Ok(CredentialFile::default());
+        }
+        let contents = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        serde_json::from_str(&contents)
+            .with_context(|| format!("Failed to parse {}", path.display()))
+    }
+
+    /// Replace a tenant's file through a temporary file, so a crash never
+    /// leaves it half written.
+    fn write(&self, tenant: &TenantId, file: &CredentialFile) -> Result<()> {
+        std::fs::create_dir_all(&self.dir)
+            .with_context(|| format!("Failed to create {}", self.dir.display()))?;
+        let path = self.path(tenant);
+        let temp = path.with_extension("json.tmp");
+        let contents = serde_json::to_string_pretty(&CredentialFile {
+            version: Self::VERSION,
+            credentials: file.credentials.clone(),
+        })?;
+        write_private(&temp, &contents)?;
+        std::fs::rename(&temp, &path)
+            .with_context(|| format!("Failed to replace {}", path.display()))
+    }
+}
+
+/// Text sealed in front of each value, binding it to its tenant.
+fn seal_prefix(tenant: &TenantId) -> String {
+    format!("{tenant}\n")
+}
+
+/// Write a file readable only by its owner where the platform allows it.
+fn write_private(path: &Path, contents: &str) -> Result<()> {
+    std::fs::write(path, contents)
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))
+            .with_context(|| format!("Failed to restrict {}", path.display()))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn store() -> (TenantCredentialStore, PathBuf) {
+        let dir = std::env::temp_dir().join(format!(
+            "tenant-credentials-{}-{}",
+            std::process::id(),
+            rand::random::<u64>()
+        ));
+        let encryptor = Encryptor::from_hex_key(
+            "0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef",
+        )
+        .unwrap();
+        (TenantCredentialStore::new(dir.clone(), encryptor), dir)
+    }
+
+    fn tenant(id: &str) -> TenantId {
+        TenantId::parse(id).unwrap()
+    }
+
+    #[test]
+    fn test_credentials_are_kept_per_tenant() {
+        let (store, dir) = store();
+        let (payments, mobile) = (tenant("payments"), tenant("mobile"));
+        store
+            .set(
+                &payments,
+                "jira_api_token",
+                &SecretString::from("p-token".to_string()),
+            )
+            .unwrap();
+
+        let token = store.get(&payments, "jira_api_token").unwrap().unwrap();
+        assert_eq!(token.expose_secret(), "p-token");
+        assert!(store.get(&mobile, "jira_api_token").unwrap().is_none());
+        assert_eq!(store.names(&payments).unwrap(), vec!["jira_api_token"]);
+
+        let contents = std::fs::read_to_string(dir.join("payments.json")).unwrap();
+        assert!(!contents.contains("p-token"));
+
+        assert!(store.remove(&payments, "jira_api_token").unwrap());
+        assert!(!store.remove(&payments, "jira_api_token").unwrap());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_value_copied_to_another_tenant_does_not_decrypt() {
+        let (store, dir) = store();
+        let (payments, mobile) = (tenant("payments"), tenant("mobile"));
+        store
+            .set(
+                &payments,
+                "token",
+                &SecretString::from("secret".to_string()),
+            )
+            .unwrap();
+        std::fs::copy(dir.join("payments.json"), dir.join("mobile.json")).unwrap();
+
+        assert!(store.get(&mobile, "token").is_err());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_rotate_reencrypts_all_tenants() {
+        let (store, dir) = store();
+        store
+            .set(
+                &tenant("payments"),
+                "token",
+                &SecretString::from("p".to_string()),
+            )
+            .unwrap();
+        store
+            .set(
+                &tenant("mobile"),
+                "token",
+                &SecretString::from("m".to_string()),
+            )
+            .unwrap();
+
+        let new_key = "fedcba9876543210fedcba9876543210fedcba9876543210fedcba9876543210";
+        let rotated = Encryptor::from_hex_key(new_key)
+            .unwrap()
+            .with_previous_keys([
+                "0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef",
+            ])
+            .unwrap();
+        let store = TenantCredentialStore::new(dir.clone(), rotated);
+        assert_eq!(store.rotate(true).unwrap().rotated, 2);
+        assert_eq!(store.rotate(false).unwrap().rotated, 2);
+        assert_eq!(store.rotate(false).unwrap().unchanged, 2);
+
+        let store =
+            TenantCredentialStore::new(dir.clone(), Encryptor::from_hex_key(new_key).unwrap());
+        let value = store.get(&tenant("mobile"), "token").unwrap().unwrap();
+        assert_eq!(value.expose_secret(), "m");
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
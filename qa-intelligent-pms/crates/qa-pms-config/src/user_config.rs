@@ -8,7 +8,7 @@ use secrecy::{ExposeSecret, SecretString};
 use serde::{Deserialize, Serialize};
 use std::path::Path;
 
-use crate::Encryptor;
+use crate::{Encryptor, RotationCount};
 
 /// User configuration generated by the setup wizard.
 ///
@@ -312,6 +312,38 @@ impl UserConfig {
         serde_yaml::from_str(&contents).context("Failed to parse config YAML")
     }
 
+    /// Re-encrypt every secret with the encryptor's current key.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error naming the first secret that cannot be decrypted.
+    pub fn rotate_secrets(&mut self, encryptor: &Encryptor) -> Result<RotationCount> {
+        let jira = &mut self.integrations.jira;
+        let mut secrets: Vec<(&str, &mut String)> = [
+            ("jira.email", jira.email_encrypted.as_mut()),
+            ("jira.apiToken", jira.api_token_encrypted.as_mut()),
+            ("jira.clientId", jira.client_id_encrypted.as_mut()),
+            ("jira.clientSecret", jira.client_secret_encrypted.as_mut()),
+        ]
+        .into_iter()
+        .filter_map(|(field, value)| value.map(|value| (field, value)))
+        .collect();
+        if let Some(postman) = self.integrations.postman.as_mut() {
+            secrets.push(("postman.apiKey", &mut postman.api_key_encrypted));
+        }
+        if let Some(testmo) = self.integrations.testmo.as_mut() {
+            secrets.push(("testmo.apiKey", &mut testmo.api_key_encrypted));
+        }
+
+        let mut count = RotationCount::default();
+        for (field, value) in secrets {
+            encryptor
+                .rotate(value, &mut count)
+                .with_context(|| format!("Failed to rotate {field}"))?;
+        }
+        Ok(count)
+    }
+
     /// Get the default config file path.
     ///
     /// # Errors
@@ -683,4 +715,42 @@ mod tests {
         assert!(yaml.contains("displayName: Test User"));
         assert!(yaml.contains("instanceUrl: https://test.atlassian.net"));
     }
+    #[test]
+    fn test_rotate_secrets_moves_every_secret_to_the_new_key() {
+        let input = SetupWizardInput {
+            profile: ProfileInput {
+                display_name: "Test User".to_string(),
+                jira_email: "test@example.com".to_string(),
+                ticket_states: vec![],
+            },
+            jira: JiraInput {
+                instance_url: "https://test.atlassian.net".to_string(),
+                auth: JiraAuthInput::ApiToken {
+                    email: "test@example.com".to_string(),
+                    api_token: SecretString::from("token-123".to_string()),
+                },
+            },
+            postman: Some(PostmanInput {
+                api_key: SecretString::from("PMAK-123".to_string()),
+                workspace_id: None,
+            }),
+            testmo: None,
+            splunk: None,
+        };
+        let mut config = UserConfig::from_wizard_input(input, &test_encryptor()).unwrap();
+
+        let new_key = "fedcba9876543210fedcba9876543210fedcba9876543210fedcba9876543210";
+        let rotated = Encryptor::from_hex_key(new_key)
+            .unwrap()
+            .with_previous_keys([
+                "0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef",
+            ])
+            .unwrap();
+        let count = config.rotate_secrets(&rotated).unwrap();
+        assert_eq!(count.rotated, 3);
+        assert_eq!(config.rotate_secrets(&rotated).unwrap().unchanged, 3);
+
+        let new_only = Encryptor::from_hex_key(new_key).unwrap();
+        assert!(config.validate_decryption(&new_only).success);
+    }
 }
@@ -0,0 +1,340 @@
+//! Circuit breaker shared by the external integration clients.
+//!
+//! After `failure_threshold` consecutive outage failures the breaker opens and
+//! requests fail immediately instead of waiting for the upstream timeout.
+//! Once `open_for` has elapsed a single probe request is let through
+//! (half-open): success closes the breaker, failure opens it again.
+//!
+//! Only outages count as failures. Callers decide what an outage is, usually
+//! network errors and 5xx responses; a 404 or a validation error means the
+//! service is up and resets the failure count.
+
+use std::future::Future;
+use std::sync::{Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// State of a circuit breaker.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(feature = "axum", derive(utoipa::ToSchema))]
+pub enum CircuitState {
+    /// Requests flow normally
+    Closed,
+    /// Requests are rejected without reaching the service
+    Open,
+    /// One probe request is allowed to test whether the service recovered
+    HalfOpen,
+}
+
+/// Point-in-time view of a breaker, for health responses.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "axum", derive(utoipa::ToSchema))]
+pub struct CircuitSnapshot {
+    /// Current state
+    pub state: CircuitState,
+    /// Outage failures since the last success
+    pub consecutive_failures: u32,
+    /// Failures that open the breaker
+    pub failure_threshold: u32,
+    /// Seconds until a probe request is allowed (when open)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retry_after_secs: Option<u64>,
+}
+
+/// Returned instead of calling the service while the breaker is open.
+#[derive(Debug, Clone, Error)]
+#[error("{service} is unavailable (circuit open, retry in {retry_after_secs}s)")]
+pub struct CircuitOpenError {
+    /// Name of the protected service
+    pub service: String,
+    /// Seconds until a probe request is allowed
+    pub retry_after_secs: u64,
+}
+
+#[derive(Debug)]
+struct Inner {
+    state: CircuitState,
+    consecutive_failures: u32,
+    opened_at: Option<Instant>,
+    probe_started_at: Option<Instant>,
+}
+
+/// Consecutive-failure circuit breaker.
+#[derive(Debug)]
+pub struct CircuitBreaker {
+    name: String,
+    failure_threshold: u32,
+    open_for: Duration,
+    inner: Mutex<Inner>,
+}
+
+impl CircuitBreaker {
+    /// Create a closed breaker that opens after `failure_threshold`
+    /// consecutive failures and stays open for `open_for`.
+    #[must_use]
+    pub fn new(name: impl Into<String>, failure_threshold: u32, open_for: Duration) -> Self {
+        Self {
+            name: name.into(),
+            failure_threshold: failure_threshold.max(1),
+            open_for,
+            inner: Mutex::new(Inner {
+                state: CircuitState::Closed,
+                consecutive_failures: 0,
+                opened_at: None,
+                probe_started_at: None,
+            }),
+        }
+    }
+
+    /// Name of the protected service.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Inner> {
+        self.inner.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Ask to send a request now.
+    ///
+    /// # Errors
+    /// Returns `CircuitOpenError` while the breaker is open, or while another
+    /// half-open probe is still in flight.
+    pub fn try_acquire(&self) -> Result<(), CircuitOpenError> {
+        self.try_acquire_at(Instant::now())
+    }
+
+    /// [`Self::try_acquire`] at a given instant.
+    ///
+    /// # Errors
+    /// See [`Self::try_acquire`].
+    pub fn try_acquire_at(&self, now: Instant) -> Result<(), CircuitOpenError> {
+        let mut inner = self.lock();
+        match inner.state {
+            CircuitState::Closed => Ok(()),
+            CircuitState::Open => {
+                let opened_at = inner.opened_at.unwrap_or(now);
+                let elapsed = now.saturating_duration_since(opened_at);
+                if elapsed >= self.open_for {
+                    inner.state = CircuitState::HalfOpen;
+                    inner.probe_started_at = Some(now);
+                    Ok(())
+                } else {
+                    Err(self.open_error(self.open_for - elapsed))
+                }
+            }
+            CircuitState::HalfOpen => {
+                // A probe that never reported back (dropped future) must not
+                // keep the breaker half-open forever.
+                let stale = inner.probe_started_at.map_or(true, |started| {
+                    now.saturating_duration_since(started) >= self.open_for
+                });
+                if stale {
+                    inner.probe_started_at = Some(now);
+                    Ok(())
+                } else {
+                    Err(self.open_error(self.open_for))
+                }
+            }
+        }
+    }
+
+    fn open_error(&self, remaining: Duration) -> CircuitOpenError {
+        CircuitOpenError {
+            service: self.name.clone(),
+            retry_after_secs: remaining.as_secs().max(1),
+        }
+    }
+
+    /// Record a request that reached a healthy service.
+    pub fn record_success(&self) {
+        let mut inner = self.lock();
+        if inner.state != CircuitState::Closed {
+            tracing::info!(service = %self.name, "Circuit closed");
+        }
+        inner.state = CircuitState::Closed;
+        inner.consecutive_failures = 0;
+        inner.opened_at = None;
+        inner.probe_started_at = None;
+    }
+
+    /// Record an outage failure.
+    pub fn record_failure(&self) {
+        self.record_failure_at(Instant::now());
+    }
+
+    /// [`Self::record_failure`] at a given instant.
+    pub fn record_failure_at(&self, now: Instant) {
+        let mut inner = self.lock();
+        inner.consecutive_failures = inner.consecutive_failures.saturating_add(1);
+        let reopen = inner.state == CircuitState::HalfOpen;
+        if reopen
+            || (inner.state == CircuitState::Closed
+                && inner.consecutive_failures >= self.failure_threshold)
+        {
+            tracing::warn!(
+                service = %self.name,
+                failures = inner.consecutive_failures,
+                "Circuit opened"
+            );
+            inner.state = CircuitState::Open;
+            inner.opened_at = Some(now);
+            inner.probe_started_at = None;
+        }
+    }
+
+    /// Current state, without side effects.
+    #[must_use]
+    pub fn snapshot(&self) -> CircuitSnapshot {
+        self.snapshot_at(Instant::now())
+    }
+
+    /// [`Self::snapshot`] at a given instant.
+    #[must_use]
+    pub fn snapshot_at(&self, now: Instant) -> CircuitSnapshot {
+        let inner = self.lock();
+        let retry_after_secs = match (inner.state, inner.opened_at) {
+            (CircuitState::Open, Some(opened_at)) => Some(
+                self.open_for
+                    .saturating_sub(now.saturating_duration_since(opened_at))
+                    .as_secs(),
+            ),
+            _ => None,
+        };
+        CircuitSnapshot {
+            state: inner.state,
+            consecutive_failures: inner.consecutive_failures,
+            failure_threshold: self.failure_threshold,
+            retry_after_secs,
+        }
+    }
+
+    /// Run `request` through the breaker.
+    ///
+    /// `is_outage` decides whether an error counts against the service; other
+    /// errors are passed through and count as a healthy response.
+    ///
+    /// # Errors
+    /// Returns `CircuitOpenError` without running `request` while open. The
+    /// inner result is the request's own outcome.
+    pub async fn call<T, E, F>(
+        &self,
+        request: F,
+        is_outage: impl FnOnce(&E) -> bool,
+    ) -> Result<Result<T, E>, CircuitOpenError>
+    where
+        F: Future<Output = Result<T, E>>,
+    {
+        self.try_acquire()?;
+        let result = request.await;
+        match &result {
+            Err(e) if is_outage(e) => self.record_failure(),
+            _ => self.record_success(),
+        }
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OPEN_FOR: Duration = Duration::from_secs(30);
+
+    #[test]
+    fn test_opens_after_threshold_and_rejects() {
+        let breaker = CircuitBreaker::new("jira", 3, OPEN_FOR);
+        let now = Instant::now();
+
+        breaker.record_failure_at(now);
+        breaker.record_failure_at(now);
+        assert!(breaker.try_acquire_at(now).is_ok());
+        assert_eq!(breaker.snapshot_at(now).state, CircuitState::Closed);
+
+        breaker.record_failure_at(now);
+        let snapshot = breaker.snapshot_at(now);
+        assert_eq!(snapshot.state, CircuitState::Open);
+        assert_eq!(snapshot.consecutive_failures, 3);
+        assert_eq!(snapshot.retry_after_secs, Some(30));
+
+        let err = breaker
+            .try_acquire_at(now + Duration::from_secs(10))
+            .unwrap_err();
+        assert_eq!(err.service, "jira");
+        assert_eq!(err.retry_after_secs, 20);
+    }
+
+    #[test]
+    fn test_success_resets_failure_count() {
+        let breaker = CircuitBreaker::new("testmo", 2, OPEN_FOR);
+        let now = Instant::now();
+
+        breaker.record_failure_at(now);
+        breaker.record_success();
+        breaker.record_failure_at(now);
+        assert_eq!(breaker.snapshot_at(now).state, CircuitState::Closed);
+        assert_eq!(breaker.snapshot_at(now).consecutive_failures, 1);
+    }
+
+    #[test]
+    fn test_half_open_allows_single_probe() {
+        let breaker = CircuitBreaker::new("postman", 1, OPEN_FOR);
+        let now = Instant::now();
+        breaker.record_failure_at(now);
+
+        let later = now + OPEN_FOR;
+        assert!(breaker.try_acquire_at(later).is_ok());
+        assert_eq!(breaker.snapshot_at(later).state, CircuitState::HalfOpen);
+        assert!(breaker.try_acquire_at(later).is_err());
+
+        // Failed probe reopens for a full period
+        breaker.record_failure_at(later);
+        assert!(breaker
+            .try_acquire_at(later + Duration::from_secs(29))
+            .is_err());
+
+        // Successful probe closes
+        let probe = later + OPEN_FOR;
+        assert!(breaker.try_acquire_at(probe).is_ok());
+        breaker.record_success();
+        assert_eq!(breaker.snapshot_at(probe).state, CircuitState::Closed);
+        assert!(breaker.try_acquire_at(probe).is_ok());
+    }
+
+    #[test]
+    fn test_stale_probe_is_replaced() {
+        let breaker = CircuitBreaker::new("jira", 1, OPEN_FOR);
+        let now = Instant::now();
+        breaker.record_failure_at(now);
+
+        assert!(breaker.try_acquire_at(now + OPEN_FOR).is_ok());
+        assert!(breaker.try_acquire_at(now + OPEN_FOR * 2).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_call_only_counts_outages() {
+        let breaker = CircuitBreaker::new("jira", 1, OPEN_FOR);
+
+        let not_found = breaker
+            .call(async { Err::<(), _>(404) }, |status| *status >= 500)
+            .await
+            .unwrap();
+        assert_eq!(not_found, Err(404));
+        assert_eq!(breaker.snapshot().state, CircuitState::Closed);
+
+        let outage = breaker
+            .call(async { Err::<(), _>(503) }, |status| *status >= 500)
+            .await
+            .unwrap();
+        assert_eq!(outage, Err(503));
+        assert_eq!(breaker.snapshot().state, CircuitState::Open);
+
+        let rejected = breaker.call(async { Ok::<_, u16>(()) }, |_| false).await;
+        assert!(rejected.is_err());
+    }
+}
@@ -0,0 +1,317 @@
+//! Duplicate detection and merging for local test cases.
+//!
+//! Two cases are compared by the edit distance between their titles and,
+//! when both have steps, between their step actions. Merging keeps the
+//! primary case, adds the steps and tags only the duplicates had, and records
+//! each duplicate in the primary's `merged_from` list.
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::matching::edit_distance;
+use crate::test_cases::{MergedCase, TestCase};
+
+/// Similarity at or above which two cases are reported as duplicates.
+pub const DEFAULT_DUPLICATE_THRESHOLD: f32 = 0.85;
+
+/// Share of the similarity taken from the title when both cases have steps.
+const TITLE_WEIGHT: f32 = 0.7;
+
+/// Two cases similar enough to be duplicates.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DuplicatePair {
+    /// Older case of the pair
+    pub first: Uuid,
+    /// Newer case of the pair
+    pub second: Uuid,
+    /// Similarity from 0.0 to 1.0
+    pub similarity: f32,
+}
+
+/// Why a set of cases cannot be merged.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum MergeError {
+    /// A case was listed as a duplicate of itself.
+    #[error("Test case {0} cannot be merged into itself")]
+    SelfMerge(Uuid),
+    /// More than one case is linked to a remote case.
+    ///
+    /// Only one link can be kept, and dropping the others would make the next
+    /// sync import their remote cases again.
+    #[error("At most one merged test case may be linked to a remote case")]
+    MultipleRemoteLinks,
+}
+
+/// Lowercase and collapse whitespace so formatting does not count as edits.
+fn normalize(text: &str) -> String {
+    text.split_whitespace()
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Similarity of two normalized texts: 1.0 when equal, 0.0 when nothing is shared.
+fn text_similarity(a: &str, b: &str) -> f32 {
+    let (a_len, b_len) = (a.chars().count(), b.chars().count());
+    let longest = a_len.max(b_len);
+    if longest == 0 {
+        return 1.0;
+    }
+    1.0 - edit_distance(a, b) as f32 / longest as f32
+}
+
+/// Best similarity two texts of these lengths could reach, without comparing them.
+fn similarity_bound(a_len: usize, b_len: usize) -> f32 {
+    let longest = a_len.max(b_len);
+    if longest == 0 {
+        return 1.0;
+    }
+    a_len.min(b_len) as f32 / longest as f32
+}
+
+/// Normalized text compared for one case.
+struct Fingerprint<'a> {
+    case: &'a TestCase,
+    title: String,
+    title_len: usize,
+    steps: String,
+}
+
+impl<'a> Fingerprint<'a> {
+    fn new(case: &'a TestCase) -> Self {
+        let title = normalize(&case.title);
+        let steps = case
+            .steps
+            .iter()
+            .map(|step| normalize(&step.action))
+            .collect::<Vec<_>>()
+            .join("\n");
+        Self {
+            case,
+            title_len: title.chars().count(),
+            title,
+            steps,
+        }
+    }
+
+    /// Similarity to another case, or `None` when it cannot reach `threshold`.
+    fn similarity(&self, other: &Self, threshold: f32) -> Option<f32> {
+        let both_have_steps = !self.steps.is_empty() && !other.steps.is_empty();
+        let title_weight = if both_have_steps { TITLE_WEIGHT } else { 1.0 };
+        let step_share = 1.0 - title_weight;
+
+        // Skip the edit distance when even identical steps could not lift the score
+        let title_bound = similarity_bound(self.title_len, other.title_len);
+        if title_bound.mul_add(title_weight, step_share) < threshold {
+            return None;
+        }
+        let title = text_similarity(&self.title, &other.title);
+        if title.mul_add(title_weight, step_share) < threshold {
+            return None;
+        }
+        let score = if both_have_steps {
+            let steps = text_similarity(&self.steps, &other.steps);
+            title.mul_add(title_weight, steps * step_share)
+        } else {
+            title
+        };
+        (score >= threshold).then_some(score)
+    }
+}
+
+/// Similarity of two cases from 0.0 to 1.0.
+#[must_use]
+pub fn case_similarity(a: &TestCase, b: &TestCase) -> f32 {
+    Fingerprint::new(a)
+        .similarity(&Fingerprint::new(b), 0.0)
+        .unwrap_or(0.0)
+}
+
+/// Pairs of cases at least `threshold` similar, most similar first.
+#[must_use]
+pub fn find_duplicates(cases: &[TestCase], threshold: f32) -> Vec<DuplicatePair> {
+    let mut fingerprints: Vec<Fingerprint> = cases.iter().map(Fingerprint::new).collect();
+    fingerprints.sort_by(|a, b| {
+        a.case
+            .created_at
+            .cmp(&b.case.created_at)
+            .then(a.case.id.cmp(&b.case.id))
+    });
+
+    let mut pairs = Vec::new();
+    for (i, first) in fingerprints.iter().enumerate() {
+        for second in &fingerprints[i + 1..] {
+            if let Some(similarity) = first.similarity(second, threshold) {
+                pairs.push(DuplicatePair {
+                    first: first.case.id,
+                    second: second.case.id,
+                    similarity,
+                });
+            }
+        }
+    }
+    pairs.sort_by(|a, b| {
+        b.similarity
+            .partial_cmp(&a.similarity)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    pairs
+}
+
+/// Fold `duplicates` into `primary`.
+///
+/// The primary keeps its own fields, filling empty ones from the duplicates
+/// in order. Steps whose action the primary lacks are appended, tags are
+/// unioned, and a remote link is taken over from a duplicate when the
+/// primary has none. The caller deletes the duplicates afterwards.
+///
+/// # Errors
+///
+/// Returns an error if a duplicate is the primary itself or if more than one
+/// of the cases is linked to a remote case.
+pub fn merge_cases(
+    mut primary: TestCase,
+    duplicates: Vec<TestCase>,
+    now: DateTime<Utc>,
+) -> Result<TestCase, MergeError> {
+    if let Some(same) = duplicates.iter().find(|d| d.id == primary.id) {
+        return Err(MergeError::SelfMerge(same.id));
+    }
+    let linked = std::iter::once(&primary)
+        .chain(&duplicates)
+        .filter(|case| case.remote.is_some())
+        .count();
+    if linked > 1 {
+        return Err(MergeError::MultipleRemoteLinks);
+    }
+
+    for duplicate in duplicates {
+        if primary.preconditions.is_none() {
+            primary.preconditions = duplicate.preconditions;
+        }
+        if primary.priority.is_none() {
+            primary.priority = duplicate.priority;
+        }
+        if primary.category.is_none() {
+            primary.category = duplicate.category;
+        }
+        for step in duplicate.steps {
+            let action = normalize(&step.action);
+            if !primary.steps.iter().any(|s| normalize(&s.action) == action) {
+                primary.steps.push(step);
+            }
+        }
+        for tag in duplicate.tags {
+            if !primary.tags.iter().any(|t| t.eq_ignore_ascii_case(&tag)) {
+                primary.tags.push(tag);
+            }
+        }
+        if primary.remote.is_none() {
+            primary.remote.clone_from(&duplicate.remote);
+        }
+        primary.merged_from.extend(duplicate.merged_from);
+        primary.merged_from.push(MergedCase {
+            id: duplicate.id,
+            title: duplicate.title,
+            remote: duplicate.remote,
+            merged_at: now,
+        });
+    }
+    primary.updated_at = now;
+    Ok(primary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_cases::{RemoteLink, TestCaseStep};
+
+    fn case(title: &str, actions: &[&str]) -> TestCase {
+        let mut case = TestCase::new(title);
+        case.steps = actions
+            .iter()
+            .map(|action| TestCaseStep {
+                action: (*action).to_string(),
+                expected: None,
+            })
+            .collect();
+        case
+    }
+
+    fn link(case: &mut TestCase, remote_id: &str) {
+        case.remote = Some(RemoteLink {
+            system: "testmo".to_string(),
+            project_id: "1".to_string(),
+            remote_id: remote_id.to_string(),
+            remote_updated_at: "2024-01-01T00:00:00Z".to_string(),
+            synced_at: case.updated_at,
+        });
+    }
+
+    #[test]
+    fn test_similarity_ignores_case_and_spacing() {
+        let a = case("Login with valid credentials", &["Open login page"]);
+        let b = case("login  with VALID credentials", &["open login page"]);
+        assert!((case_similarity(&a, &b) - 1.0).abs() < f32::EPSILON);
+
+        let c = case("Export monthly report as PDF", &["Open reports"]);
+        assert!(case_similarity(&a, &c) < 0.5);
+    }
+
+    #[test]
+    fn test_find_duplicates_reports_close_titles_only() {
+        let cases = vec![
+            case("Login with valid credentials", &[]),
+            case("Login with valid credential", &[]),
+            case("Logout clears the session", &[]),
+        ];
+        let pairs = find_duplicates(&cases, DEFAULT_DUPLICATE_THRESHOLD);
+        assert_eq!(pairs.len(), 1);
+        let ids = [pairs[0].first, pairs[0].second];
+        assert!(ids.contains(&cases[0].id) && ids.contains(&cases[1].id));
+    }
+
+    #[test]
+    fn test_merge_combines_steps_tags_and_records_provenance() {
+        let mut primary = case("Login works", &["Open login page", "Submit form"]);
+        primary.tags = vec!["smoke".to_string()];
+        let mut duplicate = case("Login work", &["open login page", "Check dashboard"]);
+        duplicate.tags = vec!["Smoke".to_string(), "auth".to_string()];
+        duplicate.priority = Some("high".to_string());
+        link(&mut duplicate, "42");
+        let duplicate_id = duplicate.id;
+
+        let now = Utc::now();
+        let merged = merge_cases(primary, vec![duplicate], now).unwrap();
+
+        let actions: Vec<&str> = merged.steps.iter().map(|s| s.action.as_str()).collect();
+        assert_eq!(
+            actions,
+            vec!["Open login page", "Submit form", "Check dashboard"]
+        );
+        assert_eq!(merged.tags, vec!["smoke".to_string(), "auth".to_string()]);
+        assert_eq!(merged.priority.as_deref(), Some("high"));
+        assert!(merged.is_linked_to("testmo", "42"));
+        assert_eq!(merged.merged_from.len(), 1);
+        assert_eq!(merged.merged_from[0].id, duplicate_id);
+        assert_eq!(merged.updated_at, now);
+    }
+
+    #[test]
+    fn test_merge_rejects_two_remote_links_and_self_merge() {
+        let mut primary = case("Login works", &[]);
+        link(&mut primary, "1");
+        let mut duplicate = case("Login works", &[]);
+        link(&mut duplicate, "2");
+        assert_eq!(
+            merge_cases(primary.clone(), vec![duplicate], Utc::now()),
+            Err(MergeError::MultipleRemoteLinks)
+        );
+        assert_eq!(
+            merge_cases(primary.clone(), vec![primary.clone()], Utc::now()),
+            Err(MergeError::SelfMerge(primary.id))
+        );
+    }
+}
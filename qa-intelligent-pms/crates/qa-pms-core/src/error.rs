@@ -18,6 +18,10 @@ pub enum ApiError {
     #[error("Validation failed: {0}")]
     Validation(String),
 
+    /// Request body failed field-level validation (one entry per violation)
+    #[error("Validation failed: {} invalid field(s)", .0.len())]
+    Unprocessable(Vec<FieldViolation>),
+
     /// Authentication required
     #[error("Authentication required: {0}")]
     Unauthorized(String),
@@ -30,6 +34,14 @@ pub enum ApiError {
     #[error("Conflict: {0}")]
     Conflict(String),
 
+    /// Payload exceeds a size limit
+    #[error("Payload too large: {0}")]
+    PayloadTooLarge(String),
+
+    /// Content type is not allowed
+    #[error("Unsupported media type: {0}")]
+    UnsupportedMediaType(String),
+
     /// External service error
     #[error("External service error: {0}")]
     ExternalService(String),
@@ -54,9 +66,12 @@ impl ApiError {
         match self {
             Self::NotFound(_) => "NOT_FOUND",
             Self::Validation(_) => "VALIDATION_ERROR",
+            Self::Unprocessable(_) => "UNPROCESSABLE_ENTITY",
             Self::Unauthorized(_) => "UNAUTHORIZED",
             Self::Forbidden(_) => "FORBIDDEN",
             Self::Conflict(_) => "CONFLICT",
+            Self::PayloadTooLarge(_) => "PAYLOAD_TOO_LARGE",
+            Self::UnsupportedMediaType(_) => "UNSUPPORTED_MEDIA_TYPE",
             Self::ExternalService(_) => "EXTERNAL_SERVICE_ERROR",
             Self::ServiceUnavailable(_) => "SERVICE_UNAVAILABLE",
             Self::RateLimited => "RATE_LIMITED",
@@ -70,9 +85,12 @@ impl ApiError {
         match self {
             Self::NotFound(_) => 404,
             Self::Validation(_) => 400,
+            Self::Unprocessable(_) => 422,
             Self::Unauthorized(_) => 401,
             Self::Forbidden(_) => 403,
             Self::Conflict(_) => 409,
+            Self::PayloadTooLarge(_) => 413,
+            Self::UnsupportedMediaType(_) => 415,
             Self::ExternalService(_) => 502,
             Self::ServiceUnavailable(_) => 503,
             Self::RateLimited => 429,
@@ -81,6 +99,56 @@ impl ApiError {
     }
 }
 
+/// The error behind an error response.
+///
+/// Attached to the response's extensions, so middleware can report what the
+/// body leaves out, such as the cause of an internal error.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ErrorDetail {
+    /// Error code, as in the response body
+    pub code: &'static str,
+    /// Full message, including the chain of causes
+    pub message: String,
+}
+
+impl From<&ApiError> for ErrorDetail {
+    fn from(err: &ApiError) -> Self {
+        let message = match err {
+            ApiError::Internal(cause) => format!("{cause:#}"),
+            _ => err.to_string(),
+        };
+        Self {
+            code: err.code(),
+            message,
+        }
+    }
+}
+
+/// A single field-level validation failure.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[cfg_attr(feature = "axum", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct FieldViolation {
+    /// Field path (e.g., `links[0].url`)
+    pub field: String,
+    /// Machine-readable rule that failed (e.g., `email`, `length`)
+    pub code: String,
+    /// Human-readable explanation
+    pub message: String,
+}
+
+impl FieldViolation {
+    /// Create a new violation.
+    #[must_use]
+    pub fn new(field: impl Into<String>, code: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            field: field.into(),
+            code: code.into(),
+            message: message.into(),
+        }
+    }
+}
+
 /// Standardized error response format for API.
 #[derive(Debug, Serialize)]
 #[cfg_attr(feature = "axum", derive(utoipa::ToSchema))]
@@ -123,14 +191,21 @@ impl ErrorResponse {
 
 impl From<&ApiError> for ErrorResponse {
     fn from(err: &ApiError) -> Self {
-        Self::new(err.to_string(), err.code())
+        match err {
+            ApiError::Unprocessable(violations) => Self::with_details(
+                err.to_string(),
+                err.code(),
+                serde_json::json!({ "violations": violations }),
+            ),
+            _ => Self::new(err.to_string(), err.code()),
+        }
     }
 }
 
 // Axum integration: IntoResponse for ApiError
 #[cfg(feature = "axum")]
 mod axum_impl {
-    use super::{ApiError, ErrorResponse};
+    use super::{ApiError, ErrorDetail, ErrorResponse};
     use axum::{
         http::StatusCode,
         response::{IntoResponse, Response},
@@ -145,6 +220,9 @@ mod axum_impl {
                 403 => StatusCode::FORBIDDEN,
                 404 => StatusCode::NOT_FOUND,
                 409 => StatusCode::CONFLICT,
+                413 => StatusCode::PAYLOAD_TOO_LARGE,
+                415 => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+                422 => StatusCode::UNPROCESSABLE_ENTITY,
                 429 => StatusCode::TOO_MANY_REQUESTS,
                 502 => StatusCode::BAD_GATEWAY,
                 503 => StatusCode::SERVICE_UNAVAILABLE,
@@ -152,7 +230,9 @@ mod axum_impl {
             };
 
             let body = ErrorResponse::from(&self);
-            (status, Json(body)).into_response()
+            let mut response = (status, Json(body)).into_response();
+            response.extensions_mut().insert(ErrorDetail::from(&self));
+            response
         }
     }
 }
@@ -175,6 +255,35 @@ mod tests {
         assert_eq!(ApiError::Validation("test".into()).status_code(), 400);
         assert_eq!(ApiError::Unauthorized("test".into()).status_code(), 401);
         assert_eq!(ApiError::ServiceUnavailable("test".into()).status_code(), 503);
+        assert_eq!(ApiError::PayloadTooLarge("test".into()).status_code(), 413);
+        assert_eq!(ApiError::UnsupportedMediaType("test".into()).status_code(), 415);
+    }
+
+    #[test]
+    fn test_unprocessable_lists_violations() {
+        let err = ApiError::Unprocessable(vec![
+            FieldViolation::new("jiraEmail", "email", "must be a valid email"),
+            FieldViolation::new("ticketStates", "length", "must not be empty"),
+        ]);
+        assert_eq!(err.status_code(), 422);
+        assert_eq!(err.code(), "UNPROCESSABLE_ENTITY");
+
+        let response = ErrorResponse::from(&err);
+        let violations = &response.details.expect("details")["violations"];
+        assert_eq!(violations.as_array().map(Vec::len), Some(2));
+        assert_eq!(violations[0]["field"], "jiraEmail");
+        assert_eq!(violations[1]["code"], "length");
+    }
+
+    #[test]
+    fn test_error_detail_keeps_internal_cause() {
+        let cause = anyhow::anyhow!("connection refused").context("Failed to load workflows");
+        let detail = ErrorDetail::from(&ApiError::Internal(cause));
+        assert_eq!(detail.code, "INTERNAL_ERROR");
+        assert_eq!(detail.message, "Failed to load workflows: connection refused");
+
+        let detail = ErrorDetail::from(&ApiError::ExternalService("Jira returned 500".into()));
+        assert_eq!(detail.message, "External service error: Jira returned 500");
     }
 
     #[test]
@@ -1,11 +1,17 @@
 //! Keyword extraction for contextual search.
 //!
-//! Extracts meaningful keywords from text for search operations.
+//! Extracts meaningful keywords from text for search operations. Stop words
+//! are filtered for English, Portuguese and Spanish, inflected forms of a word
+//! ("fails", "failed") are counted together through stemming, and a synonym
+//! dictionary can widen the keywords with domain terms ("booking" for
+//! "reservation").
 
 use std::collections::HashMap;
 
-/// Common stop words to filter out from keyword extraction.
-const STOP_WORDS: &[&str] = &[
+use rust_stemmers::{Algorithm, Stemmer};
+
+/// English stop words, plus QA words too generic to search for.
+const ENGLISH_STOP_WORDS: &[&str] = &[
     // Articles and pronouns
     "a", "an", "the", "i", "me", "my", "we", "our", "you", "your", "he", "she", "it", "its",
     "they", "them", "their", "this", "that", "these", "those", "who", "what", "which",
@@ -31,27 +37,140 @@ const STOP_WORDS: &[&str] = &[
     "then", "scenario", "feature",
 ];
 
+/// Portuguese stop words and generic QA terms.
+const PORTUGUESE_STOP_WORDS: &[&str] = &[
+    "o", "os", "um", "uma", "uns", "umas", "de", "do", "da", "dos", "das", "em", "no", "na",
+    "nos", "nas", "por", "pelo", "pela", "para", "com", "sem", "sob", "sobre", "entre", "ao",
+    "aos", "que", "se", "mas", "como", "quando", "onde", "porque", "ou", "nem", "ele", "ela",
+    "eles", "elas", "eu", "nós", "você", "vocês", "seu", "sua", "seus", "suas", "meu", "minha",
+    "este", "esta", "isto", "esse", "essa", "isso", "aquele", "aquela", "não", "sim", "mais",
+    "menos", "muito", "já", "ainda", "também", "só", "foi", "são", "ser", "está", "estão",
+    "tem", "têm", "ter", "deve", "deveria", "pode", "após", "antes", "depois",
+    // QA terms
+    "teste", "testes", "testar", "caso", "casos", "passo", "passos", "esperado", "resultado",
+    "resultados", "cenário", "verificar", "validar",
+];
+
+/// Spanish stop words and generic QA terms.
+const SPANISH_STOP_WORDS: &[&str] = &[
+    "el", "la", "los", "las", "un", "una", "unos", "unas", "de", "del", "al", "en", "por",
+    "para", "con", "sin", "sobre", "entre", "hasta", "desde", "que", "se", "pero", "como",
+    "cuando", "donde", "porque", "o", "ni", "él", "ella", "ellos", "ellas", "yo", "nosotros",
+    "usted", "ustedes", "su", "sus", "mi", "mis", "este", "esta", "esto", "ese", "esa", "eso",
+    "no", "sí", "más", "menos", "muy", "ya", "todavía", "también", "solo", "fue", "son", "ser",
+    "está", "están", "tiene", "tienen", "debe", "debería", "puede", "después", "antes",
+    // QA terms
+    "prueba", "pruebas", "probar", "caso", "casos", "paso", "pasos", "esperado", "resultado",
+    "resultados", "escenario", "verificar", "validar",
+];
+
+/// Language whose stop words are filtered and whose stemmer is used.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Language {
+    /// English
+    English,
+    /// Portuguese
+    Portuguese,
+    /// Spanish
+    Spanish,
+}
+
+impl Language {
+    /// Every supported language, English first.
+    pub const ALL: &'static [Self] = &[Self::English, Self::Portuguese, Self::Spanish];
+
+    const fn stop_words(self) -> &'static [&'static str] {
+        match self {
+            Self::English => ENGLISH_STOP_WORDS,
+            Self::Portuguese => PORTUGUESE_STOP_WORDS,
+            Self::Spanish => SPANISH_STOP_WORDS,
+        }
+    }
+
+    fn stemmer(self) -> Stemmer {
+        Stemmer::create(match self {
+            Self::English => Algorithm::English,
+            Self::Portuguese => Algorithm::Portuguese,
+            Self::Spanish => Algorithm::Spanish,
+        })
+    }
+}
+
+/// Groups of interchangeable domain terms, e.g. "booking" and "reservation".
+///
+/// Terms are matched by stem, so "reservations" finds the "reservation" group.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SynonymDictionary {
+    groups: Vec<Vec<String>>,
+}
+
+impl SynonymDictionary {
+    /// Dictionary from groups of terms; groups with fewer than two terms are
+    /// dropped.
+    #[must_use]
+    pub fn new(groups: Vec<Vec<String>>) -> Self {
+        let groups = groups
+            .into_iter()
+            .map(|group| {
+                let mut terms: Vec<String> = Vec::new();
+                for term in group {
+                    let term = term.trim().to_lowercase();
+                    if !term.is_empty() && !terms.contains(&term) {
+                        terms.push(term);
+                    }
+                }
+                terms
+            })
+            .filter(|terms| terms.len() > 1)
+            .collect();
+        Self { groups }
+    }
+
+    /// Whether the dictionary has no groups.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.groups.is_empty()
+    }
+
+    /// Terms sharing a group with `word`, compared by stem.
+    fn synonyms<'a>(
+        &'a self,
+        word: &str,
+        stemmer: &'a Stemmer,
+    ) -> impl Iterator<Item = &'a str> + 'a {
+        let stem = stemmer.stem(word).into_owned();
+        self.groups
+            .iter()
+            .filter(move |group| group.iter().any(|term| stemmer.stem(term) == stem.as_str()))
+            .flatten()
+            .map(String::as_str)
+    }
+}
+
 /// Keyword extractor for contextual search.
 ///
 /// Extracts meaningful keywords from text by:
 /// - Tokenizing text into words
-/// - Filtering stop words
+/// - Filtering stop words of every configured language
 /// - Removing short words and numbers
+/// - Counting inflections of a word together (by stem)
 /// - Ranking by frequency
+/// - Adding synonyms of the chosen keywords from the domain dictionary
 #[derive(Debug, Clone)]
 pub struct KeywordExtractor {
     /// Minimum word length to consider.
     min_length: usize,
-    /// Maximum number of keywords to return.
+    /// Maximum number of keywords to return, synonyms aside.
     max_keywords: usize,
+    /// Languages whose stop words are filtered.
+    languages: &'static [Language],
+    /// Domain synonyms added to the extracted keywords.
+    synonyms: SynonymDictionary,
 }
 
 impl Default for KeywordExtractor {
     fn default() -> Self {
-        Self {
-            min_length: 3,
-            max_keywords: 10,
-        }
+        Self::new(3, 10)
     }
 }
 
@@ -62,15 +181,37 @@ impl KeywordExtractor {
     /// * `min_length` - Minimum word length to consider
     /// * `max_keywords` - Maximum number of keywords to return
     #[must_use]
-    pub const fn new(min_length: usize, max_keywords: usize) -> Self {
+    pub fn new(min_length: usize, max_keywords: usize) -> Self {
         Self {
             min_length,
             max_keywords,
+            languages: Language::ALL,
+            synonyms: SynonymDictionary::default(),
         }
     }
 
+    /// Only filter stop words of these languages.
+    ///
+    /// The first language is used for stemming when the text gives no hint.
+    #[must_use]
+    pub fn with_languages(mut self, languages: &'static [Language]) -> Self {
+        self.languages = languages;
+        self
+    }
+
+    /// Widen extracted keywords with synonyms from `synonyms`.
+    #[must_use]
+    pub fn with_synonyms(mut self, synonyms: SynonymDictionary) -> Self {
+        self.synonyms = synonyms;
+        self
+    }
+
     /// Extract keywords from multiple text sources.
     ///
+    /// Inflections of a word count together and are reported by their most
+    /// frequent form. Synonyms of the chosen keywords follow them and do not
+    /// count towards the keyword limit.
+    ///
     /// # Arguments
     /// * `texts` - Slice of text strings to extract keywords from
     ///
@@ -78,25 +219,53 @@ impl KeywordExtractor {
     /// Vector of keywords sorted by frequency (most frequent first)
     #[must_use]
     pub fn extract(&self, texts: &[&str]) -> Vec<String> {
-        let mut word_counts: HashMap<String, usize> = HashMap::new();
+        let words: Vec<String> = texts.iter().flat_map(|text| self.tokenize(text)).collect();
+        let stemmer = self.detect_language(&words).stemmer();
+
+        // Group words by stem, remembering first appearance and each form's count
+        let mut groups: HashMap<String, StemGroup> = HashMap::new();
+        for (position, word) in words.into_iter().enumerate() {
+            if !self.is_valid_keyword(&word) {
+                continue;
+            }
+            let group = groups
+                .entry(stemmer.stem(&word).into_owned())
+                .or_insert_with(|| StemGroup {
+                    count: 0,
+                    first_seen: position,
+                    forms: Vec::new(),
+                });
+            group.count += 1;
+            match group.forms.iter_mut().find(|(form, _)| *form == word) {
+                Some((_, count)) => *count += 1,
+                None => group.forms.push((word, 1)),
+            }
+        }
 
-        for text in texts {
-            for word in self.tokenize(text) {
-                if self.is_valid_keyword(&word) {
-                    *word_counts.entry(word).or_insert(0) += 1;
+        // Sort by frequency (descending), earlier words first on ties
+        let mut ranked: Vec<(String, StemGroup)> = groups.into_iter().collect();
+        ranked.sort_by_key(|(_, group)| (std::cmp::Reverse(group.count), group.first_seen));
+        ranked.truncate(self.max_keywords);
+
+        let mut keywords: Vec<String> = ranked
+            .iter()
+            .map(|(_, group)| group.representative().to_string())
+            .collect();
+        let mut stems: Vec<String> = ranked.into_iter().map(|(stem, _)| stem).collect();
+
+        if !self.synonyms.is_empty() {
+            for keyword in keywords.clone() {
+                for synonym in self.synonyms.synonyms(&keyword, &stemmer) {
+                    let stem = stemmer.stem(synonym).into_owned();
+                    if !stems.contains(&stem) {
+                        stems.push(stem);
+                        keywords.push(synonym.to_string());
+                    }
                 }
             }
         }
 
-        // Sort by frequency (descending) and take top keywords
-        let mut keywords: Vec<_> = word_counts.into_iter().collect();
-        keywords.sort_by(|a, b| b.1.cmp(&a.1));
-
         keywords
-            .into_iter()
-            .take(self.max_keywords)
-            .map(|(word, _)| word)
-            .collect()
     }
 
     /// Extract keywords from a ticket's title and description.
@@ -118,6 +287,31 @@ impl KeywordExtractor {
         self.extract(&texts)
     }
 
+    /// Phrases from `phrases` that appear in `text`, in the order given.
+    ///
+    /// Words are compared by stem in the text's language, so "missing ACs"
+    /// matches the phrase "missing AC". Stop words are kept: in a short
+    /// phrase such as "no acceptance criteria" they carry the meaning.
+    #[must_use]
+    pub fn find_phrases<'a>(&self, text: &str, phrases: &'a [String]) -> Vec<&'a str> {
+        let words = self.tokenize(text);
+        let stemmer = self.detect_language(&words).stemmer();
+        let stems: Vec<String> = words.iter().map(|word| stemmer.stem(word).into_owned()).collect();
+
+        phrases
+            .iter()
+            .filter(|phrase| {
+                let phrase: Vec<String> = self
+                    .tokenize(phrase)
+                    .iter()
+                    .map(|word| stemmer.stem(word).into_owned())
+                    .collect();
+                !phrase.is_empty() && stems.windows(phrase.len()).any(|window| window == phrase.as_slice())
+            })
+            .map(String::as_str)
+            .collect()
+    }
+
     /// Tokenize text into lowercase words.
     fn tokenize(&self, text: &str) -> Vec<String> {
         text.to_lowercase()
@@ -127,6 +321,21 @@ impl KeywordExtractor {
             .collect()
     }
 
+    /// Language of the words, judged by which stop words they use most.
+    fn detect_language(&self, words: &[String]) -> Language {
+        let mut best = (self.languages.first().copied().unwrap_or(Language::English), 0);
+        for &language in self.languages {
+            let hits = words
+                .iter()
+                .filter(|word| language.stop_words().contains(&word.as_str()))
+                .count();
+            if hits > best.1 {
+                best = (language, hits);
+            }
+        }
+        best.0
+    }
+
     /// Check if a word is a valid keyword.
     fn is_valid_keyword(&self, word: &str) -> bool {
         // Must meet minimum length
@@ -135,7 +344,11 @@ impl KeywordExtractor {
         }
 
         // Must not be a stop word
-        if STOP_WORDS.contains(&word) {
+        if self
+            .languages
+            .iter()
+            .any(|language| language.stop_words().contains(&word))
+        {
             return false;
         }
 
@@ -153,6 +366,26 @@ impl KeywordExtractor {
     }
 }
 
+/// Occurrences of the words sharing one stem.
+struct StemGroup {
+    count: usize,
+    first_seen: usize,
+    /// Each form seen, with its count, in order of appearance
+    forms: Vec<(String, usize)>,
+}
+
+impl StemGroup {
+    /// Most frequent form, the earliest on ties.
+    fn representative(&self) -> &str {
+        // `max_by_key` keeps the last maximum, so walk the forms backwards
+        self.forms
+            .iter()
+            .rev()
+            .max_by_key(|(_, count)| *count)
+            .map_or("", |(form, _)| form.as_str())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -346,4 +579,89 @@ mod tests {
         // Keywords are limited to max 10, so we just verify we got some relevant ones
         assert!(keywords.len() <= 10);
     }
+
+    #[test]
+    fn test_groups_inflections_by_stem() {
+        let extractor = KeywordExtractor::default();
+        let keywords = extractor.extract(&["Refund fails", "refunds failed", "refund failing again"]);
+
+        // Three refund forms and three fail forms; the most frequent form wins
+        assert_eq!(keywords.len(), 2);
+        assert!(keywords.contains(&"refund".to_string()));
+        assert!(keywords.contains(&"fails".to_string()));
+    }
+
+    #[test]
+    fn test_filters_portuguese_and_spanish_stop_words() {
+        let extractor = KeywordExtractor::default();
+        let keywords = extractor.extract(&["O pagamento da reserva não funciona para clientes"]);
+        assert!(!keywords.contains(&"não".to_string()));
+        assert!(!keywords.contains(&"para".to_string()));
+        assert!(keywords.contains(&"pagamento".to_string()));
+        assert!(keywords.contains(&"reserva".to_string()));
+
+        let keywords = extractor.extract(&["Los casos de prueba del carrito fallan"]);
+        assert_eq!(keywords, vec!["carrito".to_string(), "fallan".to_string()]);
+
+        let english_only = KeywordExtractor::default().with_languages(&[Language::English]);
+        assert!(english_only
+            .extract(&["pagamento para clientes"])
+            .contains(&"para".to_string()));
+    }
+
+    #[test]
+    fn test_adds_domain_synonyms() {
+        let synonyms = SynonymDictionary::new(vec![
+            vec!["Booking".to_string(), "reservation".to_string()],
+            vec!["lonely".to_string()],
+        ]);
+        let extractor = KeywordExtractor::new(3, 2).with_synonyms(synonyms);
+        let keywords = extractor.extract(&["Reservations cannot be cancelled after booking date"]);
+
+        // Synonyms follow the keywords, beyond the keyword limit
+        assert_eq!(
+            keywords,
+            vec!["reservations".to_string(), "cannot".to_string(), "booking".to_string()]
+        );
+
+        let keywords = extractor.extract(&["Reservation reservation timeout"]);
+        assert_eq!(
+            keywords,
+            vec!["reservation".to_string(), "timeout".to_string(), "booking".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_synonym_dictionary_drops_single_terms() {
+        let synonyms = SynonymDictionary::new(vec![
+            vec!["login".to_string(), " LOGIN ".to_string()],
+            vec![],
+        ]);
+        assert!(synonyms.is_empty());
+    }
+
+    #[test]
+    fn test_find_phrases_matches_inflections_in_order() {
+        let phrases = vec![
+            "missing AC".to_string(),
+            "unclear requirement".to_string(),
+            "no acceptance criteria".to_string(),
+        ];
+        let extractor = KeywordExtractor::default();
+
+        let found = extractor.find_phrases(
+            "Blocked: unclear requirements for refunds, and missing ACs on the story",
+            &phrases,
+        );
+        assert_eq!(found, vec!["missing AC", "unclear requirement"]);
+
+        assert_eq!(
+            extractor.find_phrases("There are no acceptance criteria", &phrases),
+            vec!["no acceptance criteria"]
+        );
+        // Words must be adjacent and in order
+        assert!(extractor
+            .find_phrases("Requirement was clear, nothing unclear", &phrases)
+            .is_empty());
+    }
 }
@@ -8,23 +8,41 @@
 //! - Shared traits for integrations
 //! - Authentication types and token storage traits
 //! - Health check types and traits for integration monitoring
-//! - Keyword extraction for contextual search
+//! - A circuit breaker shared by the external integration clients
+//! - A retry policy with jittered exponential backoff for HTTP clients
+//! - Keyword extraction and typo-tolerant match scoring for contextual search
+//! - Local test case storage (`TestCaseRepository`) with duplicate detection and merging
+//! - Projects (`ProjectRepository`) keyed by Jira project key, with default templates
 //! - Result type aliases using `anyhow` for internal operations
 
 pub mod auth;
+pub mod circuit_breaker;
+pub mod duplicates;
 pub mod error;
 pub mod health;
 pub mod health_store;
 pub mod keywords;
+pub mod matching;
+pub mod projects;
+pub mod retry;
+pub mod test_cases;
 pub mod types;
 
 // Re-export commonly used types at crate root
 pub use auth::{AuthStateStore, StoredTokens, TokenStore};
-pub use error::{ApiError, ErrorResponse};
+pub use circuit_breaker::{CircuitBreaker, CircuitOpenError, CircuitSnapshot, CircuitState};
+pub use duplicates::{find_duplicates, merge_cases, DuplicatePair, MergeError};
+pub use error::{ApiError, ErrorDetail, ErrorResponse, FieldViolation};
 pub use health::{HealthCheck, HealthCheckResult, HealthStatus, IntegrationHealth};
 pub use health_store::HealthStore;
-pub use keywords::KeywordExtractor;
-pub use types::{TicketId, UserId, WorkflowId};
+pub use keywords::{KeywordExtractor, Language, SynonymDictionary};
+pub use matching::match_score;
+pub use projects::{InMemoryProjectRepository, Project, ProjectRepository, ANY_TICKET_TYPE};
+pub use retry::{is_retryable_status, RetryDecision, RetryPolicy};
+pub use test_cases::{
+    InMemoryTestCaseRepository, MergedCase, RemoteLink, TestCase, TestCaseFilter, TestCaseRepository, TestCaseStep,
+};
+pub use types::{TenantId, TicketId, UserId, WorkflowId};
 
 /// Result type alias for internal operations using `anyhow`
 pub type Result<T> = anyhow::Result<T>;
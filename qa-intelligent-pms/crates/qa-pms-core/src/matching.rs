@@ -0,0 +1,115 @@
+//! Keyword match scoring shared by the integration searches.
+//!
+//! A keyword found in the text scores 1.0, plus 0.5 when it is a whole word.
+//! A keyword that only appears misspelled (within a small edit distance of a
+//! word in the text) scores 0.5, so "paymnet" still finds "Payment refund".
+
+/// Score for a keyword matched only through a typo.
+const FUZZY_MATCH_SCORE: f32 = 0.5;
+
+/// Edits tolerated for a keyword of the given length.
+///
+/// Short keywords must match exactly; otherwise "api" would match "app".
+#[must_use]
+pub const fn typo_budget(keyword_len: usize) -> usize {
+    match keyword_len {
+        0..=4 => 0,
+        5..=8 => 1,
+        _ => 2,
+    }
+}
+
+/// Edit distance between two strings, counting an adjacent swap as one edit.
+#[must_use]
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut prev_prev = vec![0; b.len() + 1];
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+
+    for i in 1..=a.len() {
+        let mut row = vec![i; b.len() + 1];
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            row[j] = (prev[j] + 1).min(row[j - 1] + 1).min(prev[j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                row[j] = row[j].min(prev_prev[j - 2] + 1);
+            }
+        }
+        prev_prev = std::mem::replace(&mut prev, row);
+    }
+    prev[b.len()]
+}
+
+/// Score how well `text` matches `keywords`; 0.0 when nothing matches.
+#[must_use]
+pub fn match_score(text: &str, keywords: &[String]) -> f32 {
+    if keywords.is_empty() {
+        return 0.0;
+    }
+
+    let text_lower = text.to_lowercase();
+    let words: Vec<&str> = text_lower
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .collect();
+    let mut score = 0.0;
+
+    for keyword in keywords {
+        let keyword_lower = keyword.to_lowercase();
+        if text_lower.contains(&keyword_lower) {
+            score += 1.0;
+            // Bonus for exact word match
+            if text_lower.split_whitespace().any(|w| w == keyword_lower) {
+                score += 0.5;
+            }
+            continue;
+        }
+
+        let budget = typo_budget(keyword_lower.chars().count());
+        if budget > 0
+            && words
+                .iter()
+                .any(|w| edit_distance(w, &keyword_lower) <= budget)
+        {
+            score += FUZZY_MATCH_SCORE;
+        }
+    }
+
+    score
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keywords(words: &[&str]) -> Vec<String> {
+        words.iter().map(|w| (*w).to_string()).collect()
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("payment", "payment"), 0);
+        assert_eq!(edit_distance("payment", "paymnet"), 1);
+        assert_eq!(edit_distance("booking", "boking"), 1);
+        assert_eq!(edit_distance("refund", "refunds"), 1);
+        assert_eq!(edit_distance("login", "logout"), 3);
+        assert_eq!(edit_distance("", "abc"), 3);
+    }
+
+    #[test]
+    fn test_match_score_exact_and_word_bonus() {
+        assert_eq!(match_score("Payment refund", &keywords(&["payment"])), 1.5);
+        assert_eq!(match_score("PaymentRefund", &keywords(&["payment"])), 1.0);
+        assert_eq!(match_score("Anything", &[]), 0.0);
+    }
+
+    #[test]
+    fn test_match_score_tolerates_typos() {
+        assert_eq!(match_score("Payment refund", &keywords(&["paymnet"])), 0.5);
+        assert_eq!(match_score("Checkout/payment", &keywords(&["pyment"])), 0.5);
+        // Short keywords must match exactly
+        assert_eq!(match_score("App settings", &keywords(&["api"])), 0.0);
+        assert_eq!(match_score("Login page", &keywords(&["payment"])), 0.0);
+    }
+}
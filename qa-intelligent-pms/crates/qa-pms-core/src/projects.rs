@@ -0,0 +1,256 @@
+//! Projects.
+//!
+//! A project groups the tickets whose Jira keys share its key, e.g. `PAY` for
+//! `PAY-123`. Workflows, dashboards and alerts are scoped to a project through
+//! the ticket keys they refer to, so records created before the project was
+//! registered are included. Each project can also name the workflow template
+//! to start by default for each ticket type.
+
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+/// Ticket type whose default template applies to types without their own.
+pub const ANY_TICKET_TYPE: &str = "*";
+
+/// Longest project key Jira accepts.
+const MAX_KEY_LEN: usize = 10;
+
+/// A project, identified by its Jira project key.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "axum", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct Project {
+    /// Unique identifier
+    pub id: Uuid,
+    /// Jira project key (e.g., "PAY")
+    pub key: String,
+    /// Display name
+    pub name: String,
+    /// Default workflow template per ticket type; `*` applies to any type
+    #[serde(default)]
+    pub default_templates: HashMap<String, Uuid>,
+    /// Creation timestamp
+    pub created_at: DateTime<Utc>,
+    /// Last modification
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Project {
+    /// Create a project without default templates.
+    #[must_use]
+    pub fn new(key: impl Into<String>, name: impl Into<String>) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4(),
+            key: key.into(),
+            name: name.into(),
+            default_templates: HashMap::new(),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Whether `key` is a valid Jira project key: an uppercase letter
+    /// followed by up to nine uppercase letters, digits or underscores.
+    #[must_use]
+    pub fn is_valid_key(key: &str) -> bool {
+        let mut chars = key.chars();
+        chars.next().is_some_and(|c| c.is_ascii_uppercase())
+            && key.len() <= MAX_KEY_LEN
+            && chars.all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+    }
+
+    /// Project key of a ticket key (`PAY-123` -> `PAY`).
+    #[must_use]
+    pub fn key_of_ticket(ticket_key: &str) -> Option<&str> {
+        ticket_key
+            .split_once('-')
+            .map(|(key, _)| key)
+            .filter(|key| !key.is_empty())
+    }
+
+    /// Whether a ticket belongs to this project.
+    #[must_use]
+    pub fn owns_ticket(&self, ticket_key: &str) -> bool {
+        Self::key_of_ticket(ticket_key) == Some(self.key.as_str())
+    }
+
+    /// Default template for a ticket type, ignoring case, falling back to
+    /// the `*` entry.
+    #[must_use]
+    pub fn default_template(&self, ticket_type: Option<&str>) -> Option<Uuid> {
+        ticket_type
+            .and_then(|wanted| {
+                self.default_templates
+                    .iter()
+                    .find(|(ticket_type, _)| ticket_type.eq_ignore_ascii_case(wanted))
+                    .map(|(_, id)| *id)
+            })
+            .or_else(|| self.default_templates.get(ANY_TICKET_TYPE).copied())
+    }
+}
+
+/// Storage for projects.
+#[async_trait]
+pub trait ProjectRepository: Send + Sync {
+    /// Get a project by ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if retrieval fails.
+    async fn get(&self, id: Uuid) -> anyhow::Result<Option<Project>>;
+
+    /// Get a project by its key.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if retrieval fails.
+    async fn find_by_key(&self, key: &str) -> anyhow::Result<Option<Project>>;
+
+    /// List all projects, ordered by key.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if retrieval fails.
+    async fn list(&self) -> anyhow::Result<Vec<Project>>;
+
+    /// Create or replace a project.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if storage fails, including when another project
+    /// already has the key.
+    async fn save(&self, project: Project) -> anyhow::Result<Project>;
+
+    /// Delete a project. Returns whether it existed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if deletion fails.
+    async fn delete(&self, id: Uuid) -> anyhow::Result<bool>;
+
+    /// Project owning a ticket, if its key is registered.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if retrieval fails.
+    async fn find_for_ticket(&self, ticket_key: &str) -> anyhow::Result<Option<Project>> {
+        match Project::key_of_ticket(ticket_key) {
+            Some(key) => self.find_by_key(key).await,
+            None => Ok(None),
+        }
+    }
+}
+
+/// In-memory project repository, for tests and local development.
+#[derive(Debug, Default)]
+pub struct InMemoryProjectRepository {
+    projects: RwLock<HashMap<Uuid, Project>>,
+}
+
+impl InMemoryProjectRepository {
+    /// Create an empty repository.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl ProjectRepository for InMemoryProjectRepository {
+    async fn get(&self, id: Uuid) -> anyhow::Result<Option<Project>> {
+        Ok(self.projects.read().await.get(&id).cloned())
+    }
+
+    async fn find_by_key(&self, key: &str) -> anyhow::Result<Option<Project>> {
+        Ok(self
+            .projects
+            .read()
+            .await
+            .values()
+            .find(|p| p.key == key)
+            .cloned())
+    }
+
+    async fn list(&self) -> anyhow::Result<Vec<Project>> {
+        let mut projects: Vec<Project> = self.projects.read().await.values().cloned().collect();
+        projects.sort_by(|a, b| a.key.cmp(&b.key));
+        Ok(projects)
+    }
+
+    async fn save(&self, project: Project) -> anyhow::Result<Project> {
+        let mut projects = self.projects.write().await;
+        if projects
+            .values()
+            .any(|p| p.key == project.key && p.id != project.id)
+        {
+            anyhow::bail!("Project key {} is already in use", project.key);
+        }
+        projects.insert(project.id, project.clone());
+        Ok(project)
+    }
+
+    async fn delete(&self, id: Uuid) -> anyhow::Result<bool> {
+        Ok(self.projects.write().await.remove(&id).is_some())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_key_validation() {
+        assert!(Project::is_valid_key("PAY"));
+        assert!(Project::is_valid_key("QA_2"));
+        assert!(!Project::is_valid_key("pay"));
+        assert!(!Project::is_valid_key("2QA"));
+        assert!(!Project::is_valid_key("PAY-1"));
+        assert!(!Project::is_valid_key("ABCDEFGHIJK"));
+        assert!(!Project::is_valid_key(""));
+    }
+
+    #[test]
+    fn test_ticket_ownership() {
+        let project = Project::new("PAY", "Payments");
+        assert!(project.owns_ticket("PAY-123"));
+        assert!(!project.owns_ticket("PAYOUT-1"));
+        assert!(!project.owns_ticket("PAY"));
+        assert_eq!(Project::key_of_ticket("-1"), None);
+    }
+
+    #[test]
+    fn test_default_template_falls_back_to_any_type() {
+        let (bug, any) = (Uuid::new_v4(), Uuid::new_v4());
+        let mut project = Project::new("PAY", "Payments");
+        project.default_templates.insert("Bug".to_string(), bug);
+        assert_eq!(project.default_template(Some("bug")), Some(bug));
+        assert_eq!(project.default_template(Some("Story")), None);
+
+        project.default_templates.insert(ANY_TICKET_TYPE.to_string(), any);
+        assert_eq!(project.default_template(Some("Story")), Some(any));
+        assert_eq!(project.default_template(None), Some(any));
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_repository_keeps_keys_unique() {
+        let repo = InMemoryProjectRepository::new();
+        let project = repo.save(Project::new("PAY", "Payments")).await.unwrap();
+
+        assert_eq!(repo.find_for_ticket("PAY-7").await.unwrap(), Some(project.clone()));
+        assert_eq!(repo.find_for_ticket("OPS-7").await.unwrap(), None);
+        assert!(repo.save(Project::new("PAY", "Other")).await.is_err());
+
+        let mut renamed = project.clone();
+        renamed.name = "Payments team".to_string();
+        repo.save(renamed).await.unwrap();
+        assert_eq!(repo.list().await.unwrap().len(), 1);
+        assert!(repo.delete(project.id).await.unwrap());
+        assert_eq!(repo.get(project.id).await.unwrap(), None);
+    }
+}
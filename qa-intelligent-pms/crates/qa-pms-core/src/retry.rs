@@ -0,0 +1,351 @@
+//! Retry policy shared by the external HTTP clients.
+//!
+//! Failed requests are retried with exponential backoff: the delay doubles
+//! after each attempt, capped at `max_delay`. With jitter enabled, a random
+//! share of up to half the delay is taken off so that clients failing at the
+//! same moment do not retry in lockstep. A `Retry-After` hint from the server
+//! replaces the backoff, up to [`MAX_RETRY_AFTER`].
+//!
+//! Each client decides which of its errors are worth retrying, usually with
+//! [`is_retryable_status`] for HTTP responses plus network errors.
+
+use std::collections::hash_map::RandomState;
+use std::fmt::Display;
+use std::future::Future;
+use std::hash::{BuildHasher, Hasher};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use tracing::warn;
+
+/// Default attempts per request, including the first.
+pub const DEFAULT_MAX_ATTEMPTS: u32 = 3;
+
+/// Default delay before the first retry.
+pub const DEFAULT_BASE_DELAY: Duration = Duration::from_secs(1);
+
+/// Default cap on the backoff delay.
+pub const DEFAULT_MAX_DELAY: Duration = Duration::from_secs(30);
+
+/// Longest `Retry-After` honored; longer hints are shortened to this.
+pub const MAX_RETRY_AFTER: Duration = Duration::from_secs(60);
+
+/// Whether a response status is worth retrying.
+///
+/// Request timeouts, rate limiting and server errors are transient; other
+/// client errors will fail the same way again.
+#[must_use]
+pub const fn is_retryable_status(status: u16) -> bool {
+    matches!(status, 408 | 429 | 500..=599)
+}
+
+/// What to do after a failed attempt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RetryDecision {
+    /// Give up and return the error
+    Stop,
+    /// Retry after the policy's backoff
+    Retry,
+    /// Retry after the delay requested by the server
+    RetryAfter(Duration),
+}
+
+/// Attempts and backoff for retried requests.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Attempts per request, including the first
+    pub max_attempts: u32,
+    /// Delay before the first retry, doubled on each further retry
+    pub base_delay: Duration,
+    /// Longest backoff delay
+    pub max_delay: Duration,
+    /// Randomize delays so concurrent clients spread their retries
+    pub jitter: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_ATTEMPTS, DEFAULT_BASE_DELAY, DEFAULT_MAX_DELAY)
+    }
+}
+
+impl RetryPolicy {
+    /// Create a jittered policy.
+    #[must_use]
+    pub const fn new(max_attempts: u32, base_delay: Duration, max_delay: Duration) -> Self {
+        Self {
+            max_attempts,
+            base_delay,
+            max_delay,
+            jitter: true,
+        }
+    }
+
+    /// A policy that makes a single attempt.
+    #[must_use]
+    pub const fn none() -> Self {
+        Self::new(1, Duration::ZERO, Duration::ZERO)
+    }
+
+    /// Enable or disable jitter.
+    #[must_use]
+    pub const fn with_jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Backoff before retry `attempt` (1-based), without jitter.
+    #[must_use]
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+        self.base_delay
+            .checked_mul(factor)
+            .unwrap_or(self.max_delay)
+            .min(self.max_delay)
+    }
+
+    /// Delay before retry `attempt` (1-based) after the given decision.
+    #[must_use]
+    pub fn delay(&self, attempt: u32, decision: RetryDecision) -> Duration {
+        match decision {
+            RetryDecision::RetryAfter(delay) => delay.min(MAX_RETRY_AFTER),
+            _ => {
+                let backoff = self.backoff(attempt);
+                if self.jitter {
+                    backoff - backoff.mul_f64(random_fraction() / 2.0)
+                } else {
+                    backoff
+                }
+            }
+        }
+    }
+
+    /// Run `op` until it succeeds, `classify` says stop, or the attempts run
+    /// out. The last error is returned.
+    ///
+    /// # Errors
+    /// Returns the error of the last attempt.
+    pub async fn run<T, E, F, Fut>(
+        &self,
+        service: &str,
+        mut op: F,
+        classify: impl Fn(&E) -> RetryDecision,
+    ) -> Result<T, E>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+        E: Display,
+    {
+        let max_attempts = self.max_attempts.max(1);
+        let mut attempt = 0;
+
+        loop {
+            attempt += 1;
+            let error = match op().await {
+                Ok(value) => return Ok(value),
+                Err(error) => error,
+            };
+            let decision = classify(&error);
+            if decision == RetryDecision::Stop || attempt >= max_attempts {
+                return Err(error);
+            }
+            let delay = self.delay(attempt, decision);
+            warn!(
+                service,
+                attempt,
+                max_attempts,
+                delay_ms = delay.as_millis(),
+                error = %error,
+                "Request failed, retrying"
+            );
+            tokio::time::sleep(delay).await;
+        }
+    }
+}
+
+/// Send a request with `policy`, retrying network errors and transient
+/// statuses.
+///
+/// The last response is returned even when its status is an error, so
+/// callers keep their own status handling. A `Retry-After` header in seconds
+/// sets the delay. Multipart uploads stream their body and are sent once.
+///
+/// # Errors
+/// Returns the network error of the last attempt.
+#[cfg(feature = "http")]
+#[allow(clippy::expect_used)]
+pub async fn send(
+    policy: &RetryPolicy,
+    service: &str,
+    request: reqwest::RequestBuilder,
+) -> reqwest::Result<reqwest::Response> {
+    let Some(template) = request.try_clone() else {
+        return request.send().await;
+    };
+    let mut first = Some(request);
+    let outcome = policy
+        .run(
+            service,
+            || {
+                // A builder that cloned once has a buffered body, so it clones again
+                let request = first
+                    .take()
+                    .unwrap_or_else(|| template.try_clone().expect("buffered request is cloneable"));
+                async move {
+                    match request.send().await {
+                        Ok(response) if is_retryable_status(response.status().as_u16()) => {
+                            Err(SendFailure::Status(response))
+                        }
+                        Ok(response) => Ok(response),
+                        Err(e) => Err(SendFailure::Network(e)),
+                    }
+                }
+            },
+            SendFailure::retry_decision,
+        )
+        .await;
+    match outcome {
+        Ok(response) | Err(SendFailure::Status(response)) => Ok(response),
+        Err(SendFailure::Network(e)) => Err(e),
+    }
+}
+
+/// A failed attempt in [`send`].
+#[cfg(feature = "http")]
+#[derive(Debug)]
+enum SendFailure {
+    /// The service answered with a transient status
+    Status(reqwest::Response),
+    /// The request did not get a response
+    Network(reqwest::Error),
+}
+
+#[cfg(feature = "http")]
+impl SendFailure {
+    fn retry_decision(&self) -> RetryDecision {
+        match self {
+            Self::Status(response) => retry_after(response.headers())
+                .map_or(RetryDecision::Retry, RetryDecision::RetryAfter),
+            Self::Network(e) if e.is_builder() => RetryDecision::Stop,
+            Self::Network(_) => RetryDecision::Retry,
+        }
+    }
+}
+
+#[cfg(feature = "http")]
+impl Display for SendFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Status(response) => write!(f, "HTTP {}", response.status()),
+            Self::Network(e) => e.fmt(f),
+        }
+    }
+}
+
+/// Delay from a `Retry-After` header given in seconds.
+#[cfg(feature = "http")]
+#[must_use]
+pub fn retry_after(headers: &reqwest::header::HeaderMap) -> Option<Duration> {
+    headers
+        .get(reqwest::header::RETRY_AFTER)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.trim().parse::<u64>().ok())
+        .map(Duration::from_secs)
+}
+
+/// A random number in `[0, 1)`.
+fn random_fraction() -> f64 {
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u128(
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos(),
+    );
+    (hasher.finish() >> 11) as f64 / (1u64 << 53) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    fn fixed(max_attempts: u32) -> RetryPolicy {
+        RetryPolicy::new(max_attempts, Duration::from_secs(1), Duration::from_secs(5)).with_jitter(false)
+    }
+
+    #[test]
+    fn test_backoff_doubles_up_to_max_delay() {
+        let policy = fixed(10);
+        let delays: Vec<u64> = (1..=5).map(|a| policy.backoff(a).as_secs()).collect();
+        assert_eq!(delays, vec![1, 2, 4, 5, 5]);
+        assert_eq!(policy.backoff(100), Duration::from_secs(5));
+    }
+
+    #[test]
+    fn test_delay_honors_retry_after() {
+        let policy = fixed(3);
+        assert_eq!(
+            policy.delay(1, RetryDecision::RetryAfter(Duration::from_secs(7))),
+            Duration::from_secs(7)
+        );
+        assert_eq!(
+            policy.delay(1, RetryDecision::RetryAfter(Duration::from_secs(3600))),
+            MAX_RETRY_AFTER
+        );
+        assert_eq!(policy.delay(3, RetryDecision::Retry), Duration::from_secs(4));
+    }
+
+    #[test]
+    fn test_jitter_stays_within_half_of_backoff() {
+        let policy = fixed(3).with_jitter(true);
+        for _ in 0..100 {
+            let delay = policy.delay(2, RetryDecision::Retry);
+            assert!(delay > Duration::from_secs(1) && delay <= Duration::from_secs(2));
+        }
+    }
+
+    #[test]
+    fn test_retryable_statuses() {
+        for status in [408, 429, 500, 502, 503, 504] {
+            assert!(is_retryable_status(status), "{status}");
+        }
+        for status in [200, 400, 401, 403, 404, 409, 422] {
+            assert!(!is_retryable_status(status), "{status}");
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_run_retries_until_success() {
+        let calls = AtomicU32::new(0);
+        let result: Result<u32, String> = fixed(3)
+            .run(
+                "test",
+                || async {
+                    let n = calls.fetch_add(1, Ordering::SeqCst) + 1;
+                    if n < 3 {
+                        Err(format!("attempt {n}"))
+                    } else {
+                        Ok(n)
+                    }
+                },
+                |_| RetryDecision::Retry,
+            )
+            .await;
+        assert_eq!(result, Ok(3));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_run_stops_on_permanent_error_and_after_max_attempts() {
+        let calls = AtomicU32::new(0);
+        let op = || async {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Err::<(), _>("boom")
+        };
+
+        assert!(fixed(3).run("test", op, |_| RetryDecision::Stop).await.is_err());
+        assert_eq!(calls.swap(0, Ordering::SeqCst), 1);
+
+        assert!(fixed(3).run("test", op, |_| RetryDecision::Retry).await.is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+}
@@ -0,0 +1,363 @@
+//! Local test case storage.
+//!
+//! Test cases live locally so they can be searched and enriched offline, and
+//! may be linked to a copy in an external test management system (Testmo).
+
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use crate::types::{Cursor, PageRequest, SortDirection};
+
+/// A step within a test case.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "axum", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct TestCaseStep {
+    /// Action to perform
+    pub action: String,
+    /// Expected result
+    pub expected: Option<String>,
+}
+
+/// Link between a local case and its copy in an external system.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "axum", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct RemoteLink {
+    /// External system (e.g., "testmo")
+    pub system: String,
+    /// Remote project the case belongs to
+    pub project_id: String,
+    /// ID of the case in the external system
+    pub remote_id: String,
+    /// Remote `updated_at` as of the last sync, used to detect remote edits
+    pub remote_updated_at: String,
+    /// When the case was last synced
+    pub synced_at: DateTime<Utc>,
+}
+
+/// A case folded into another by a merge, kept as provenance.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "axum", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct MergedCase {
+    /// ID the merged case had
+    pub id: Uuid,
+    /// Its title at merge time
+    pub title: String,
+    /// Its remote link, if it had one
+    pub remote: Option<RemoteLink>,
+    /// When it was merged
+    pub merged_at: DateTime<Utc>,
+}
+
+/// A locally stored test case.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "axum", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct TestCase {
+    /// Unique identifier
+    pub id: Uuid,
+    /// Case title
+    pub title: String,
+    /// Preconditions
+    pub preconditions: Option<String>,
+    /// Ordered steps
+    pub steps: Vec<TestCaseStep>,
+    /// Priority label
+    pub priority: Option<String>,
+    /// Category (e.g., "functional", "regression")
+    pub category: Option<String>,
+    /// Free-form tags
+    pub tags: Vec<String>,
+    /// Remote copy, if linked
+    pub remote: Option<RemoteLink>,
+    /// Cases merged into this one, oldest merge first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub merged_from: Vec<MergedCase>,
+    /// Creation timestamp
+    pub created_at: DateTime<Utc>,
+    /// Last local modification
+    pub updated_at: DateTime<Utc>,
+}
+
+impl TestCase {
+    /// Create an unlinked test case.
+    #[must_use]
+    pub fn new(title: impl Into<String>) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4(),
+            title: title.into(),
+            preconditions: None,
+            steps: Vec::new(),
+            priority: None,
+            category: None,
+            tags: Vec::new(),
+            remote: None,
+            merged_from: Vec::new(),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Whether the case was edited locally since its last sync.
+    ///
+    /// Unlinked cases always count as changed.
+    #[must_use]
+    pub fn has_local_changes(&self) -> bool {
+        self.remote
+            .as_ref()
+            .map_or(true, |remote| self.updated_at > remote.synced_at)
+    }
+
+    /// Whether the case is linked to the given remote case.
+    #[must_use]
+    pub fn is_linked_to(&self, system: &str, remote_id: &str) -> bool {
+        self.remote
+            .as_ref()
+            .is_some_and(|r| r.system == system && r.remote_id == remote_id)
+    }
+}
+
+/// Criteria for searching test cases; unset fields match every case.
+///
+/// All comparisons ignore case.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TestCaseFilter {
+    /// Text found in the title, preconditions or a step
+    pub text: Option<String>,
+    /// Tag the case must carry
+    pub tag: Option<String>,
+    /// Category
+    pub category: Option<String>,
+    /// Priority label
+    pub priority: Option<String>,
+}
+
+impl TestCaseFilter {
+    /// Whether a case meets every set criterion.
+    #[must_use]
+    pub fn matches(&self, case: &TestCase) -> bool {
+        let same = |wanted: &Option<String>, value: Option<&str>| {
+            wanted
+                .as_deref()
+                .map_or(true, |w| value.is_some_and(|v| v.eq_ignore_ascii_case(w)))
+        };
+        let text = self.text.as_deref().map(str::to_lowercase);
+        let has_text = text.as_deref().map_or(true, |text| {
+            let contains = |value: &str| value.to_lowercase().contains(text);
+            contains(&case.title)
+                || case.preconditions.as_deref().is_some_and(contains)
+                || case.steps.iter().any(|step| {
+                    contains(&step.action) || step.expected.as_deref().is_some_and(contains)
+                })
+        });
+        has_text
+            && same(&self.category, case.category.as_deref())
+            && same(&self.priority, case.priority.as_deref())
+            && self.tag.as_deref().map_or(true, |tag| {
+                case.tags.iter().any(|t| t.eq_ignore_ascii_case(tag))
+            })
+    }
+}
+
+/// Storage for local test cases.
+#[async_trait]
+pub trait TestCaseRepository: Send + Sync {
+    /// Get a case by ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if retrieval fails.
+    async fn get(&self, id: Uuid) -> anyhow::Result<Option<TestCase>>;
+
+    /// Find the case linked to a remote case.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if retrieval fails.
+    async fn find_by_remote(&self, system: &str, remote_id: &str) -> anyhow::Result<Option<TestCase>>;
+
+    /// List all cases.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if retrieval fails.
+    async fn list(&self) -> anyhow::Result<Vec<TestCase>>;
+
+    /// Cases matching `filter`, ordered by creation time and then ID.
+    ///
+    /// Returns up to `page.fetch_limit()` cases after the page's cursor, ready
+    /// for `PageRequest::finish`. The default filters `list` in memory.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if retrieval fails.
+    async fn search(&self, filter: &TestCaseFilter, page: &PageRequest) -> anyhow::Result<Vec<TestCase>> {
+        let mut cases: Vec<TestCase> = self
+            .list()
+            .await?
+            .into_iter()
+            .filter(|case| filter.matches(case))
+            .collect();
+        cases.sort_by(|a, b| a.created_at.cmp(&b.created_at).then(a.id.cmp(&b.id)));
+        if page.direction == SortDirection::Desc {
+            cases.reverse();
+        }
+        if let Some(Cursor::Key { at, id }) = page.after {
+            // Cursors keep microseconds, like Postgres timestamps
+            let after = (at.timestamp_micros(), id);
+            cases.retain(|case| {
+                let key = (case.created_at.timestamp_micros(), case.id);
+                match page.direction {
+                    SortDirection::Asc => key > after,
+                    SortDirection::Desc => key < after,
+                }
+            });
+        }
+        cases.truncate(usize::try_from(page.fetch_limit()).unwrap_or(usize::MAX));
+        Ok(cases)
+    }
+
+    /// Insert or replace a case.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if storage fails.
+    async fn save(&self, case: TestCase) -> anyhow::Result<TestCase>;
+
+    /// Delete a case, returning whether it existed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if deletion fails.
+    async fn delete(&self, id: Uuid) -> anyhow::Result<bool>;
+}
+
+/// In-memory repository, for tests and tools without a database.
+#[derive(Debug, Default)]
+pub struct InMemoryTestCaseRepository {
+    cases: RwLock<HashMap<Uuid, TestCase>>,
+}
+
+impl InMemoryTestCaseRepository {
+    /// Create an empty repository.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl TestCaseRepository for InMemoryTestCaseRepository {
+    async fn get(&self, id: Uuid) -> anyhow::Result<Option<TestCase>> {
+        Ok(self.cases.read().await.get(&id).cloned())
+    }
+
+    async fn find_by_remote(&self, system: &str, remote_id: &str) -> anyhow::Result<Option<TestCase>> {
+        Ok(self
+            .cases
+            .read()
+            .await
+            .values()
+            .find(|c| c.is_linked_to(system, remote_id))
+            .cloned())
+    }
+
+    async fn list(&self) -> anyhow::Result<Vec<TestCase>> {
+        let mut cases: Vec<TestCase> = self.cases.read().await.values().cloned().collect();
+        cases.sort_by(|a, b| a.created_at.cmp(&b.created_at).then(a.id.cmp(&b.id)));
+        Ok(cases)
+    }
+
+    async fn save(&self, case: TestCase) -> anyhow::Result<TestCase> {
+        self.cases.write().await.insert(case.id, case.clone());
+        Ok(case)
+    }
+
+    async fn delete(&self, id: Uuid) -> anyhow::Result<bool> {
+        Ok(self.cases.write().await.remove(&id).is_some())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn linked(remote_id: &str) -> TestCase {
+        let mut case = TestCase::new("Login works");
+        case.remote = Some(RemoteLink {
+            system: "testmo".to_string(),
+            project_id: "1".to_string(),
+            remote_id: remote_id.to_string(),
+            remote_updated_at: "2024-01-01T00:00:00Z".to_string(),
+            synced_at: case.updated_at,
+        });
+        case
+    }
+
+    #[test]
+    fn test_local_changes_detection() {
+        assert!(TestCase::new("unlinked").has_local_changes());
+
+        let mut case = linked("42");
+        assert!(!case.has_local_changes());
+        case.updated_at += chrono::Duration::seconds(1);
+        assert!(case.has_local_changes());
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_repository_round_trip() {
+        let repo = InMemoryTestCaseRepository::new();
+        let case = repo.save(linked("42")).await.unwrap();
+
+        assert_eq!(repo.get(case.id).await.unwrap(), Some(case.clone()));
+        assert_eq!(repo.find_by_remote("testmo", "42").await.unwrap().map(|c| c.id), Some(case.id));
+        assert!(repo.find_by_remote("testmo", "43").await.unwrap().is_none());
+        assert_eq!(repo.list().await.unwrap().len(), 1);
+
+        assert!(repo.delete(case.id).await.unwrap());
+        assert!(!repo.delete(case.id).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_search_filters_and_pages() {
+        let repo = InMemoryTestCaseRepository::new();
+        let start = Utc::now();
+        for (i, title) in ["Login works", "Logout works", "Checkout total"].iter().enumerate() {
+            let mut case = TestCase::new(*title);
+            case.created_at = start + chrono::Duration::seconds(i as i64);
+            case.tags = vec!["Smoke".to_string()];
+            repo.save(case).await.unwrap();
+        }
+
+        let filter = TestCaseFilter {
+            text: Some("LOG".to_string()),
+            tag: Some("smoke".to_string()),
+            ..TestCaseFilter::default()
+        };
+        let page = PageRequest::new(None, Some(1), SortDirection::Asc);
+        let first = page.finish(repo.search(&filter, &page).await.unwrap(), |c| (c.created_at, c.id));
+        assert_eq!(first.data[0].title, "Login works");
+        assert!(first.page_info.has_more);
+
+        let cursor = Cursor::decode(first.page_info.next_cursor.as_deref().unwrap()).unwrap();
+        let page = PageRequest::new(Some(cursor), Some(1), SortDirection::Asc);
+        let second = page.finish(repo.search(&filter, &page).await.unwrap(), |c| (c.created_at, c.id));
+        assert_eq!(second.data[0].title, "Logout works");
+        assert!(!second.page_info.has_more);
+
+        let none = TestCaseFilter {
+            category: Some("regression".to_string()),
+            ..TestCaseFilter::default()
+        };
+        assert!(repo.search(&none, &PageRequest::default()).await.unwrap().is_empty());
+    }
+}
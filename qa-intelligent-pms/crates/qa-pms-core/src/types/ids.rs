@@ -160,6 +160,68 @@ impl From<&str> for TicketId {
     }
 }
 
+/// Tenant (workspace) identifier: a lowercase slug such as `payments-qa`.
+///
+/// Every request runs in one tenant; single-team installs only ever use
+/// [`TenantId::DEFAULT`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct TenantId(String);
+
+impl TenantId {
+    /// Tenant used when none is named.
+    pub const DEFAULT: &'static str = "default";
+
+    /// Longest accepted tenant ID.
+    const MAX_LEN: usize = 63;
+
+    /// Parse a tenant ID: 1 to 63 lowercase letters, digits and hyphens, not
+    /// starting or ending with a hyphen.
+    #[must_use]
+    pub fn parse(value: &str) -> Option<Self> {
+        let valid = !value.is_empty()
+            && value.len() <= Self::MAX_LEN
+            && !value.starts_with('-')
+            && !value.ends_with('-')
+            && value
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-');
+        valid.then(|| Self(value.to_string()))
+    }
+
+    /// The ID as a string.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Default for TenantId {
+    fn default() -> Self {
+        Self(Self::DEFAULT.to_string())
+    }
+}
+
+impl std::fmt::Display for TenantId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl TryFrom<String> for TenantId {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::parse(&value).ok_or_else(|| format!("invalid tenant ID: {value}"))
+    }
+}
+
+impl From<TenantId> for String {
+    fn from(id: TenantId) -> Self {
+        id.0
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -177,4 +239,18 @@ mod tests {
         let id: TicketId = "PROJ-123".into();
         assert_eq!(id.to_string(), "PROJ-123");
     }
+
+    #[test]
+    fn test_tenant_id_validation() {
+        assert_eq!(TenantId::default().as_str(), "default");
+        assert!(TenantId::parse("payments-qa").is_some());
+        assert!(TenantId::parse("Payments").is_none());
+        assert!(TenantId::parse("-qa").is_none());
+        assert!(TenantId::parse("").is_none());
+        assert!(serde_json::from_str::<TenantId>("\"qa team\"").is_err());
+        assert_eq!(
+            serde_json::to_string(&TenantId::default()).unwrap(),
+            "\"default\""
+        );
+    }
 }
@@ -8,6 +8,9 @@ mod ids;
 mod integration;
 mod pagination;
 
-pub use ids::{TicketId, UserId, WorkflowId, WorkflowInstanceId, WorkflowStepId};
+pub use ids::{TenantId, TicketId, UserId, WorkflowId, WorkflowInstanceId, WorkflowStepId};
 pub use integration::{Integration, IntegrationHealth, IntegrationStatus};
-pub use pagination::{PageInfo, Paginated};
+pub use pagination::{
+    Cursor, CursorInfo, CursorPage, CursorQuery, PageInfo, PageRequest, Paginated, SortDirection,
+    DEFAULT_PAGE_LIMIT, MAX_PAGE_LIMIT,
+};
@@ -1,6 +1,26 @@
 //! Pagination types for API responses.
+//!
+//! Two styles are supported:
+//! - [`Paginated`]/[`PageInfo`]: page-number pagination with totals.
+//! - [`PageRequest`]/[`CursorPage`]: keyset pagination with opaque cursors.
+//!   List endpoints take a [`CursorQuery`], turn it into a [`PageRequest`],
+//!   fetch `fetch_limit()` rows ordered by `(timestamp, id)` and hand them to
+//!   [`PageRequest::finish`], which trims the extra row and issues the next
+//!   cursor.
 
+use std::fmt::Write as _;
+
+use chrono::{DateTime, TimeZone, Utc};
 use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::error::ApiError;
+
+/// Page size used when the client does not ask for one.
+pub const DEFAULT_PAGE_LIMIT: u32 = 20;
+
+/// Largest page size a client may request.
+pub const MAX_PAGE_LIMIT: u32 = 100;
 
 /// Pagination information for list responses.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -69,6 +89,265 @@ impl<T> Paginated<T> {
     }
 }
 
+// ============================================================================
+// Cursor (keyset) pagination
+// ============================================================================
+
+/// Sort direction of a cursor-paginated list.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "axum", derive(utoipa::ToSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum SortDirection {
+    /// Oldest first
+    Asc,
+    /// Newest first
+    #[default]
+    Desc,
+}
+
+impl SortDirection {
+    /// SQL keyword for `ORDER BY`.
+    #[must_use]
+    pub const fn as_sql(self) -> &'static str {
+        match self {
+            Self::Asc => "ASC",
+            Self::Desc => "DESC",
+        }
+    }
+
+    /// Row-comparison operator selecting rows after the cursor.
+    const fn after_operator(self) -> &'static str {
+        match self {
+            Self::Asc => ">",
+            Self::Desc => "<",
+        }
+    }
+}
+
+/// Position of the last item a client has seen.
+///
+/// Serialized as an opaque string; clients must not build cursors themselves.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cursor {
+    /// Keyset position: sort timestamp and tie-breaking id of the last item
+    Key {
+        /// Sort timestamp of the last item
+        at: DateTime<Utc>,
+        /// Id of the last item
+        id: Uuid,
+    },
+    /// Offset position, for upstream sources that only support offsets
+    Offset(u64),
+}
+
+impl Cursor {
+    /// Encode the cursor as an opaque URL-safe string.
+    #[must_use]
+    pub fn encode(&self) -> String {
+        let raw = match self {
+            Self::Key { at, id } => format!("k:{}:{}", at.timestamp_micros(), id.simple()),
+            Self::Offset(offset) => format!("o:{offset}"),
+        };
+        raw.bytes().fold(String::with_capacity(raw.len() * 2), |mut out, b| {
+            let _ = write!(out, "{b:02x}");
+            out
+        })
+    }
+
+    /// Decode a cursor produced by [`Cursor::encode`].
+    ///
+    /// # Errors
+    /// Returns `ApiError::Validation` if the string is not a valid cursor.
+    pub fn decode(encoded: &str) -> Result<Self, ApiError> {
+        Self::parse(encoded).ok_or_else(|| ApiError::Validation("Invalid pagination cursor".into()))
+    }
+
+    fn parse(encoded: &str) -> Option<Self> {
+        if encoded.len() % 2 != 0 || !encoded.is_ascii() {
+            return None;
+        }
+        let bytes = (0..encoded.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&encoded[i..i + 2], 16).ok())
+            .collect::<Option<Vec<u8>>>()?;
+        let raw = String::from_utf8(bytes).ok()?;
+
+        match raw.split(':').collect::<Vec<_>>().as_slice() {
+            ["k", micros, id] => Some(Self::Key {
+                at: Utc.timestamp_micros(micros.parse().ok()?).single()?,
+                id: Uuid::parse_str(id).ok()?,
+            }),
+            ["o", offset] => Some(Self::Offset(offset.parse().ok()?)),
+            _ => None,
+        }
+    }
+}
+
+/// Query parameters accepted by cursor-paginated list endpoints.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "axum", derive(utoipa::IntoParams))]
+#[serde(rename_all = "camelCase")]
+pub struct CursorQuery {
+    /// Cursor from a previous response's `pageInfo.nextCursor`
+    pub cursor: Option<String>,
+    /// Items per page (default 20, max 100)
+    pub limit: Option<u32>,
+    /// Sort direction (default `desc`, newest first)
+    pub direction: Option<SortDirection>,
+}
+
+impl CursorQuery {
+    /// Validate the query and clamp the limit.
+    ///
+    /// # Errors
+    /// Returns `ApiError::Validation` if the cursor cannot be decoded.
+    pub fn page_request(&self) -> Result<PageRequest, ApiError> {
+        let after = self.cursor.as_deref().map(Cursor::decode).transpose()?;
+        Ok(PageRequest::new(after, self.limit, self.direction.unwrap_or_default()))
+    }
+}
+
+/// A validated page request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PageRequest {
+    /// Position to continue after, `None` for the first page
+    pub after: Option<Cursor>,
+    /// Items per page, clamped to `1..=MAX_PAGE_LIMIT`
+    pub limit: u32,
+    /// Sort direction
+    pub direction: SortDirection,
+}
+
+impl Default for PageRequest {
+    fn default() -> Self {
+        Self::new(None, None, SortDirection::default())
+    }
+}
+
+impl PageRequest {
+    /// Create a page request, clamping `limit`.
+    #[must_use]
+    pub fn new(after: Option<Cursor>, limit: Option<u32>, direction: SortDirection) -> Self {
+        Self {
+            after,
+            limit: limit.unwrap_or(DEFAULT_PAGE_LIMIT).clamp(1, MAX_PAGE_LIMIT),
+            direction,
+        }
+    }
+
+    /// Number of rows to fetch: one more than `limit` to detect a next page.
+    #[must_use]
+    pub const fn fetch_limit(&self) -> i64 {
+        self.limit as i64 + 1
+    }
+
+    /// Keyset position to bind into the `keyset_sql` predicate.
+    #[must_use]
+    pub const fn keyset(&self) -> Option<(DateTime<Utc>, Uuid)> {
+        match self.after {
+            Some(Cursor::Key { at, id }) => Some((at, id)),
+            _ => None,
+        }
+    }
+
+    /// Offset to start at for offset-based sources.
+    #[must_use]
+    pub const fn offset(&self) -> u64 {
+        match self.after {
+            Some(Cursor::Offset(offset)) => offset,
+            _ => 0,
+        }
+    }
+
+    /// SQL predicate selecting rows after the cursor.
+    ///
+    /// Binds two parameters starting at `$first_param`: the timestamp, then
+    /// the id. Returns `None` on the first page.
+    #[must_use]
+    pub fn keyset_sql(&self, at_column: &str, id_column: &str, first_param: usize) -> Option<String> {
+        self.keyset().map(|_| {
+            format!(
+                "({at_column}, {id_column}) {} (${first_param}, ${})",
+                self.direction.after_operator(),
+                first_param + 1
+            )
+        })
+    }
+
+    /// SQL `ORDER BY` body matching `keyset_sql`.
+    #[must_use]
+    pub fn order_sql(&self, at_column: &str, id_column: &str) -> String {
+        let dir = self.direction.as_sql();
+        format!("{at_column} {dir}, {id_column} {dir}")
+    }
+
+    /// Build a page from up to `fetch_limit()` keyset-ordered rows.
+    pub fn finish<T>(&self, mut rows: Vec<T>, key: impl Fn(&T) -> (DateTime<Utc>, Uuid)) -> CursorPage<T> {
+        let has_more = rows.len() > self.limit as usize;
+        rows.truncate(self.limit as usize);
+        let next_cursor = if has_more {
+            rows.last().map(|row| {
+                let (at, id) = key(row);
+                Cursor::Key { at, id }.encode()
+            })
+        } else {
+            None
+        };
+        CursorPage::new(rows, self.limit, next_cursor)
+    }
+
+    /// Build a page for offset-based sources.
+    pub fn finish_offset<T>(&self, items: Vec<T>, has_more: bool) -> CursorPage<T> {
+        let next_cursor = has_more.then(|| Cursor::Offset(self.offset() + items.len() as u64).encode());
+        CursorPage::new(items, self.limit, next_cursor)
+    }
+}
+
+/// Cursor pagination information for list responses.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "axum", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct CursorInfo {
+    /// Cursor for the next page, absent on the last page
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+    /// Whether more items follow
+    pub has_more: bool,
+    /// Items per page used for this response
+    pub limit: u32,
+}
+
+/// Cursor-paginated response wrapper.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CursorPage<T> {
+    /// The data items for this page
+    pub data: Vec<T>,
+    /// Pagination information
+    pub page_info: CursorInfo,
+}
+
+impl<T> CursorPage<T> {
+    fn new(data: Vec<T>, limit: u32, next_cursor: Option<String>) -> Self {
+        Self {
+            data,
+            page_info: CursorInfo {
+                has_more: next_cursor.is_some(),
+                next_cursor,
+                limit,
+            },
+        }
+    }
+
+    /// Map the items while keeping pagination information.
+    pub fn map<U>(self, f: impl FnMut(T) -> U) -> CursorPage<U> {
+        CursorPage {
+            data: self.data.into_iter().map(f).collect(),
+            page_info: self.page_info,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -88,4 +367,81 @@ mod tests {
         assert!(json.contains("\"data\":[\"item1\",\"item2\"]"));
         assert!(json.contains("\"pageSize\":10"));
     }
+
+    #[test]
+    fn test_cursor_round_trip() {
+        let key = Cursor::Key {
+            at: Utc.timestamp_micros(1_700_000_000_123_456).unwrap(),
+            id: Uuid::new_v4(),
+        };
+        assert_eq!(Cursor::decode(&key.encode()).unwrap(), key);
+        assert_eq!(Cursor::decode(&Cursor::Offset(40).encode()).unwrap(), Cursor::Offset(40));
+
+        assert!(Cursor::decode("not-a-cursor").is_err());
+        assert!(Cursor::decode("6b3a31").is_err());
+    }
+
+    #[test]
+    fn test_page_request_clamps_limit() {
+        let query = CursorQuery {
+            limit: Some(1000),
+            ..CursorQuery::default()
+        };
+        assert_eq!(query.page_request().unwrap().limit, MAX_PAGE_LIMIT);
+        assert_eq!(PageRequest::new(None, Some(0), SortDirection::Asc).limit, 1);
+        assert_eq!(PageRequest::default().limit, DEFAULT_PAGE_LIMIT);
+    }
+
+    #[test]
+    fn test_keyset_sql_follows_direction() {
+        let at = Utc::now();
+        let after = Some(Cursor::Key { at, id: Uuid::nil() });
+
+        let desc = PageRequest::new(after, None, SortDirection::Desc);
+        assert_eq!(
+            desc.keyset_sql("created_at", "id", 2).as_deref(),
+            Some("(created_at, id) < ($2, $3)")
+        );
+        assert_eq!(desc.order_sql("created_at", "id"), "created_at DESC, id DESC");
+
+        let asc = PageRequest::new(after, None, SortDirection::Asc);
+        assert_eq!(
+            asc.keyset_sql("created_at", "id", 1).as_deref(),
+            Some("(created_at, id) > ($1, $2)")
+        );
+        assert!(PageRequest::default().keyset_sql("created_at", "id", 1).is_none());
+    }
+
+    #[test]
+    fn test_finish_trims_and_issues_cursor() {
+        let page = PageRequest::new(None, Some(2), SortDirection::Desc);
+        let rows: Vec<(DateTime<Utc>, Uuid)> = (0..3).map(|_| (Utc::now(), Uuid::new_v4())).collect();
+        let last = rows[1];
+
+        let result = page.finish(rows, |r| *r);
+        assert_eq!(result.data.len(), 2);
+        assert!(result.page_info.has_more);
+        let next = Cursor::decode(result.page_info.next_cursor.as_deref().unwrap()).unwrap();
+        assert_eq!(
+            next,
+            Cursor::Key {
+                at: Utc.timestamp_micros(last.0.timestamp_micros()).unwrap(),
+                id: last.1
+            }
+        );
+
+        let short = page.finish(vec![(Utc::now(), Uuid::new_v4())], |r| *r);
+        assert!(!short.page_info.has_more);
+        assert!(short.page_info.next_cursor.is_none());
+    }
+
+    #[test]
+    fn test_finish_offset_advances() {
+        let page = PageRequest::new(Some(Cursor::Offset(20)), Some(10), SortDirection::Desc);
+        let result = page.finish_offset(vec![1; 10], true);
+        assert_eq!(
+            Cursor::decode(result.page_info.next_cursor.as_deref().unwrap()).unwrap(),
+            Cursor::Offset(30)
+        );
+    }
 }
@@ -0,0 +1,330 @@
+//! Team capacity analytics.
+//!
+//! Combines each user's in-flight workflows with how fast they have been
+//! completing workflows recently. The backlog of a user is the number of
+//! days their active workflows would take at that pace; users whose backlog
+//! exceeds [`OVERLOAD_BACKLOG_DAYS`] are reported as overloaded. Each
+//! in-flight workflow gets an expected completion date from the share of its
+//! steps still to do and the user's average completion time, or from the
+//! template estimates of the remaining steps when the user has no history.
+
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+use sqlx::{FromRow, PgPool};
+use uuid::Uuid;
+
+/// Backlog, in days of recent throughput, above which a user is overloaded.
+pub const OVERLOAD_BACKLOG_DAYS: f64 = 7.0;
+
+/// Active workflows above which a user without recent completions is
+/// overloaded.
+pub const OVERLOAD_ACTIVE_WITHOUT_HISTORY: usize = 3;
+
+/// An active or paused workflow, as loaded for the report.
+#[derive(Debug, Clone, FromRow)]
+pub struct ActiveWorkflow {
+    pub id: Uuid,
+    pub user_id: String,
+    pub ticket_id: String,
+    pub status: String,
+    pub started_at: DateTime<Utc>,
+    pub current_step: i32,
+    pub step_count: i32,
+    /// Template estimates of the current and later steps
+    pub remaining_estimated_minutes: i64,
+}
+
+/// Completed workflows of a user in the lookback window.
+#[derive(Debug, Clone, FromRow)]
+pub struct UserThroughput {
+    pub user_id: String,
+    pub completed: i64,
+    /// Average time from start to completion
+    pub avg_completion_minutes: Option<f64>,
+}
+
+/// A workflow still in progress with its expected completion.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InFlightWorkflow {
+    pub workflow_id: Uuid,
+    pub ticket_id: String,
+    pub status: String,
+    pub started_at: DateTime<Utc>,
+    pub current_step: i32,
+    pub step_count: i32,
+    /// Expected minutes until completion
+    pub remaining_minutes: f64,
+    pub expected_completion: DateTime<Utc>,
+}
+
+/// Load and pace of one user.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserCapacity {
+    pub user_id: String,
+    pub active_workflows: usize,
+    /// Workflows completed in the lookback window
+    pub completed_workflows: i64,
+    pub throughput_per_week: f64,
+    pub avg_completion_minutes: Option<f64>,
+    /// Days needed to clear the active workflows at the recent pace, when
+    /// the user has completed any
+    pub backlog_days: Option<f64>,
+    pub overloaded: bool,
+    pub workflows: Vec<InFlightWorkflow>,
+}
+
+/// Capacity of the whole team.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CapacityReport {
+    pub lookback_days: i64,
+    pub active_workflows: usize,
+    pub throughput_per_week: f64,
+    pub overloaded_users: usize,
+    /// Overloaded users first, then by backlog
+    pub users: Vec<UserCapacity>,
+    pub generated_at: DateTime<Utc>,
+}
+
+/// Build the capacity report from loaded workflows and throughput.
+#[must_use]
+pub fn build_report(
+    active: Vec<ActiveWorkflow>,
+    throughput: &[UserThroughput],
+    lookback_days: i64,
+    now: DateTime<Utc>,
+) -> CapacityReport {
+    let lookback_days = lookback_days.max(1);
+    let mut by_user: BTreeMap<String, Vec<ActiveWorkflow>> = BTreeMap::new();
+    for workflow in active {
+        by_user
+            .entry(workflow.user_id.clone())
+            .or_default()
+            .push(workflow);
+    }
+    for history in throughput {
+        by_user.entry(history.user_id.clone()).or_default();
+    }
+
+    let mut users: Vec<UserCapacity> = by_user
+        .into_iter()
+        .map(|(user_id, workflows)| {
+            let history = throughput.iter().find(|t| t.user_id == user_id);
+            let completed = history.map_or(0, |t| t.completed);
+            let avg_completion_minutes = history.and_then(|t| t.avg_completion_minutes);
+            let per_day = completed as f64 / lookback_days as f64;
+            let backlog_days = (per_day > 0.0).then(|| workflows.len() as f64 / per_day);
+            let overloaded = match backlog_days {
+                Some(days) => days > OVERLOAD_BACKLOG_DAYS,
+                None => workflows.len() > OVERLOAD_ACTIVE_WITHOUT_HISTORY,
+            };
+
+            let mut in_flight: Vec<InFlightWorkflow> = workflows
+                .into_iter()
+                .map(|w| in_flight(w, avg_completion_minutes, now))
+                .collect();
+            in_flight.sort_by_key(|w| w.expected_completion);
+
+            UserCapacity {
+                user_id,
+                active_workflows: in_flight.len(),
+                completed_workflows: completed,
+                throughput_per_week: per_day * 7.0,
+                avg_completion_minutes,
+                backlog_days,
+                overloaded,
+                workflows: in_flight,
+            }
+        })
+        .collect();
+    users.sort_by(|a, b| {
+        b.overloaded
+            .cmp(&a.overloaded)
+            .then_with(|| {
+                let backlog = |u: &UserCapacity| u.backlog_days.unwrap_or(f64::INFINITY);
+                let has_work = |u: &UserCapacity| u.active_workflows > 0;
+                has_work(b)
+                    .cmp(&has_work(a))
+                    .then_with(|| backlog(b).total_cmp(&backlog(a)))
+            })
+            .then_with(|| a.user_id.cmp(&b.user_id))
+    });
+
+    CapacityReport {
+        lookback_days,
+        active_workflows: users.iter().map(|u| u.active_workflows).sum(),
+        throughput_per_week: users.iter().map(|u| u.throughput_per_week).sum(),
+        overloaded_users: users.iter().filter(|u| u.overloaded).count(),
+        users,
+        generated_at: now,
+    }
+}
+
+fn in_flight(
+    workflow: ActiveWorkflow,
+    avg_completion_minutes: Option<f64>,
+    now: DateTime<Utc>,
+) -> InFlightWorkflow {
+    let remaining_minutes = match avg_completion_minutes {
+        Some(avg) if workflow.step_count > 0 => {
+            let remaining_steps = (workflow.step_count - workflow.current_step).max(0);
+            avg * f64::from(remaining_steps) / f64::from(workflow.step_count)
+        }
+        _ => workflow.remaining_estimated_minutes as f64,
+    };
+    InFlightWorkflow {
+        workflow_id: workflow.id,
+        ticket_id: workflow.ticket_id,
+        status: workflow.status,
+        started_at: workflow.started_at,
+        current_step: workflow.current_step,
+        step_count: workflow.step_count,
+        remaining_minutes,
+        expected_completion: now + Duration::seconds((remaining_minutes * 60.0).round() as i64),
+    }
+}
+
+/// Load the capacity report of a tenant over the last `lookback_days` days.
+///
+/// # Errors
+/// Returns error if a database query fails.
+pub async fn load_capacity(
+    pool: &PgPool,
+    tenant_id: &str,
+    lookback_days: i64,
+) -> Result<CapacityReport, sqlx::Error> {
+    let now = Utc::now();
+
+    // Instances pinned to an older template version are measured against
+    // the steps of that version.
+    let active = sqlx::query_as::<_, ActiveWorkflow>(
+        r"
+        SELECT wi.id, wi.user_id, wi.ticket_id, wi.status, wi.started_at, wi.current_step,
+               jsonb_array_length(steps.json)::INT AS step_count,
+               COALESCE((
+                   SELECT SUM((step.value->>'estimatedMinutes')::BIGINT)
+                   FROM jsonb_array_elements(steps.json) WITH ORDINALITY AS step(value, position)
+                   WHERE step.position > wi.current_step
+               ), 0)::BIGINT AS remaining_estimated_minutes
+        FROM workflow_instances wi
+        JOIN workflow_templates t ON t.id = wi.template_id
+        LEFT JOIN workflow_template_versions v
+            ON v.template_id = wi.template_id
+           AND v.version = wi.template_version
+           AND t.version <> wi.template_version
+        CROSS JOIN LATERAL (SELECT COALESCE(v.steps_json, t.steps_json)::JSONB AS json) steps
+        WHERE wi.status IN ('active', 'paused')
+          AND wi.tenant_id = $1
+          AND wi.archived_at IS NULL
+          AND wi.deleted_at IS NULL
+        ORDER BY wi.started_at
+        ",
+    )
+    .bind(tenant_id)
+    .fetch_all(pool)
+    .await?;
+
+    let throughput = sqlx::query_as::<_, UserThroughput>(
+        r"
+        SELECT user_id,
+               COUNT(*) AS completed,
+               AVG(EXTRACT(EPOCH FROM (completed_at - started_at)) / 60.0)::FLOAT8
+                   AS avg_completion_minutes
+        FROM workflow_instances
+        WHERE status = 'completed'
+          AND completed_at >= $1
+          AND tenant_id = $2
+          AND deleted_at IS NULL
+        GROUP BY user_id
+        ",
+    )
+    .bind(now - Duration::days(lookback_days))
+    .bind(tenant_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(build_report(active, &throughput, lookback_days, now))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn workflow(user_id: &str, current_step: i32) -> ActiveWorkflow {
+        ActiveWorkflow {
+            id: Uuid::new_v4(),
+            user_id: user_id.to_string(),
+            ticket_id: format!("QA-{current_step}"),
+            status: "active".to_string(),
+            started_at: Utc::now(),
+            current_step,
+            step_count: 4,
+            remaining_estimated_minutes: 90,
+        }
+    }
+
+    fn history(user_id: &str, completed: i64, avg: f64) -> UserThroughput {
+        UserThroughput {
+            user_id: user_id.to_string(),
+            completed,
+            avg_completion_minutes: Some(avg),
+        }
+    }
+
+    #[test]
+    fn test_overloaded_when_backlog_exceeds_threshold() {
+        let active = vec![workflow("ana", 0), workflow("ana", 1), workflow("bo", 2)];
+        // ana completes 1 a day: 2 active -> 2 days of backlog.
+        // bo completes 1 every 10 days: 1 active -> 10 days of backlog.
+        let throughput = [history("ana", 70, 120.0), history("bo", 7, 60.0)];
+        let report = build_report(active, &throughput, 70, Utc::now());
+
+        assert_eq!(report.active_workflows, 3);
+        assert_eq!(report.overloaded_users, 1);
+        assert_eq!(report.users[0].user_id, "bo");
+        assert!(report.users[0].overloaded);
+        assert_eq!(report.users[0].backlog_days, Some(10.0));
+        assert!(!report.users[1].overloaded);
+    }
+
+    #[test]
+    fn test_expected_completion_uses_pace_or_estimates() {
+        let now = Utc::now();
+        let report = build_report(
+            vec![workflow("ana", 1), workflow("cy", 0)],
+            &[history("ana", 3, 120.0)],
+            30,
+            now,
+        );
+
+        let ana = report.users.iter().find(|u| u.user_id == "ana").unwrap();
+        assert!((ana.workflows[0].remaining_minutes - 90.0).abs() < f64::EPSILON);
+        assert_eq!(
+            ana.workflows[0].expected_completion,
+            now + Duration::minutes(90)
+        );
+
+        let cy = report.users.iter().find(|u| u.user_id == "cy").unwrap();
+        assert_eq!(cy.backlog_days, None);
+        assert!(!cy.overloaded);
+        assert!((cy.workflows[0].remaining_minutes - 90.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_users_without_active_work_are_listed_last() {
+        let report = build_report(
+            vec![workflow("bo", 0)],
+            &[history("ana", 10, 30.0), history("bo", 10, 30.0)],
+            30,
+            Utc::now(),
+        );
+
+        assert_eq!(report.users[0].user_id, "bo");
+        assert_eq!(report.users[1].user_id, "ana");
+        assert_eq!(report.users[1].active_workflows, 0);
+    }
+}
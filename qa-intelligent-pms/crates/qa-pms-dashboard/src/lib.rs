@@ -7,5 +7,15 @@
 //! - PM observability dashboard
 //! - Trend calculations
 //! - Data aggregation
+//! - Team capacity (`capacity`)
+//! - KPI targets (`targets`)
 
 // TODO: Implement in Epic 8 and Epic 10
+
+pub mod capacity;
+pub mod targets;
+
+pub use capacity::{load_capacity, CapacityReport, InFlightWorkflow, UserCapacity};
+pub use targets::{
+    Comparison, Kpi, KpiTarget, KpiTargetRepository, NewKpiTarget, TargetStatus, DEFAULT_TEAM,
+};
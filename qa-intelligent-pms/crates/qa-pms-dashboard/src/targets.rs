@@ -0,0 +1,366 @@
+//! KPI targets.
+//!
+//! Each team can set a target for any dashboard KPI, e.g. average time per
+//! ticket below two hours, and how many consecutive days the KPI may miss it
+//! before an alert is raised. A breach is alerted once; the target is armed
+//! again when the KPI next meets it. Targets are kept in `kpi_targets`, one
+//! row per team and KPI. The KPIs themselves are measured across all
+//! workflows; the team only selects which set of targets applies.
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool};
+use uuid::Uuid;
+
+/// Team whose targets apply when none is given.
+pub const DEFAULT_TEAM: &str = "default";
+
+/// A dashboard KPI a target can be set for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Kpi {
+    /// Workflows completed
+    TicketsCompleted,
+    /// Average seconds per completed ticket
+    AvgTimePerTicket,
+    /// Estimated over actual time
+    Efficiency,
+    /// Hours tracked
+    TotalHours,
+}
+
+impl Kpi {
+    /// Every KPI, in dashboard order.
+    pub const ALL: [Self; 4] = [
+        Self::TicketsCompleted,
+        Self::AvgTimePerTicket,
+        Self::Efficiency,
+        Self::TotalHours,
+    ];
+
+    /// Name used in the API and the database.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::TicketsCompleted => "tickets_completed",
+            Self::AvgTimePerTicket => "avg_time_per_ticket",
+            Self::Efficiency => "efficiency",
+            Self::TotalHours => "total_hours",
+        }
+    }
+
+    /// Parse a KPI name.
+    #[must_use]
+    pub fn parse(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|kpi| kpi.as_str() == name)
+    }
+}
+
+impl std::fmt::Display for Kpi {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Which side of the target a KPI has to stay on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Comparison {
+    /// At most the target
+    Below,
+    /// At least the target
+    Above,
+}
+
+impl Comparison {
+    /// Name used in the API and the database.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Below => "below",
+            Self::Above => "above",
+        }
+    }
+
+    /// Parse a comparison name.
+    #[must_use]
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "below" => Some(Self::Below),
+            "above" => Some(Self::Above),
+            _ => None,
+        }
+    }
+
+    /// Whether `value` meets `target`.
+    #[must_use]
+    pub fn is_met(self, value: f64, target: f64) -> bool {
+        match self {
+            Self::Below => value <= target,
+            Self::Above => value >= target,
+        }
+    }
+}
+
+/// A team's target for one KPI.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KpiTarget {
+    pub id: Uuid,
+    pub team: String,
+    pub kpi: Kpi,
+    pub comparison: Comparison,
+    pub target_value: f64,
+    /// Consecutive days the target may be missed before alerting
+    pub breach_days: i32,
+    /// When the current breach was alerted, cleared once the target is met
+    pub last_alerted_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl KpiTarget {
+    /// Whether `value` meets this target.
+    #[must_use]
+    pub fn is_met(&self, value: f64) -> bool {
+        self.comparison.is_met(value, self.target_value)
+    }
+
+    /// Check the KPI's value on each of the most recent days, oldest first.
+    #[must_use]
+    pub fn evaluate(&self, daily: &[(NaiveDate, f64)]) -> TargetStatus {
+        let missed = daily
+            .iter()
+            .rev()
+            .take_while(|(_, value)| !self.is_met(*value))
+            .count();
+        let alerted = self.last_alerted_at.is_some();
+        if missed == 0 {
+            return if alerted && !daily.is_empty() {
+                TargetStatus::Recovered
+            } else {
+                TargetStatus::Unchanged
+            };
+        }
+        if alerted || missed < usize::try_from(self.breach_days).unwrap_or(usize::MAX) {
+            return TargetStatus::Unchanged;
+        }
+        TargetStatus::Breached {
+            since: daily[daily.len() - missed].0,
+        }
+    }
+}
+
+/// Outcome of checking a target against recent days.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TargetStatus {
+    /// Missed for `breach_days` days in a row, starting `since`, and not yet
+    /// alerted
+    Breached { since: NaiveDate },
+    /// Met again after an alerted breach
+    Recovered,
+    /// Nothing to do
+    Unchanged,
+}
+
+/// Fields of a target to create or replace.
+#[derive(Debug, Clone)]
+pub struct NewKpiTarget {
+    pub team: String,
+    pub kpi: Kpi,
+    pub comparison: Comparison,
+    pub target_value: f64,
+    pub breach_days: i32,
+}
+
+#[derive(FromRow)]
+struct KpiTargetRow {
+    id: Uuid,
+    team: String,
+    kpi: String,
+    comparison: String,
+    target_value: f64,
+    breach_days: i32,
+    last_alerted_at: Option<DateTime<Utc>>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+impl KpiTargetRow {
+    /// Rows naming a KPI this version does not know are skipped.
+    fn into_target(self) -> Option<KpiTarget> {
+        Some(KpiTarget {
+            id: self.id,
+            team: self.team,
+            kpi: Kpi::parse(&self.kpi)?,
+            comparison: Comparison::parse(&self.comparison)?,
+            target_value: self.target_value,
+            breach_days: self.breach_days,
+            last_alerted_at: self.last_alerted_at,
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+        })
+    }
+}
+
+/// Repository for KPI targets.
+#[derive(Debug, Clone)]
+pub struct KpiTargetRepository {
+    pool: PgPool,
+}
+
+impl KpiTargetRepository {
+    /// Create a repository on the given pool.
+    #[must_use]
+    pub const fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// List targets, of one team or of all teams.
+    ///
+    /// # Errors
+    /// Returns error if the query fails.
+    pub async fn list(&self, team: Option<&str>) -> Result<Vec<KpiTarget>, sqlx::Error> {
+        let rows = sqlx::query_as::<_, KpiTargetRow>(
+            r"
+            SELECT * FROM kpi_targets
+            WHERE $1::TEXT IS NULL OR team = $1
+            ORDER BY team, kpi
+            ",
+        )
+        .bind(team)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows
+            .into_iter()
+            .filter_map(KpiTargetRow::into_target)
+            .collect())
+    }
+
+    /// Create a target, or replace the team's existing target for the KPI.
+    ///
+    /// Replacing a target resets its alert state.
+    ///
+    /// # Errors
+    /// Returns error if the query fails.
+    pub async fn upsert(&self, target: &NewKpiTarget) -> Result<KpiTarget, sqlx::Error> {
+        let row = sqlx::query_as::<_, KpiTargetRow>(
+            r"
+            INSERT INTO kpi_targets (id, team, kpi, comparison, target_value, breach_days)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            ON CONFLICT (team, kpi) DO UPDATE
+            SET comparison = EXCLUDED.comparison,
+                target_value = EXCLUDED.target_value,
+                breach_days = EXCLUDED.breach_days,
+                last_alerted_at = NULL,
+                updated_at = NOW()
+            RETURNING *
+            ",
+        )
+        .bind(Uuid::new_v4())
+        .bind(&target.team)
+        .bind(target.kpi.as_str())
+        .bind(target.comparison.as_str())
+        .bind(target.target_value)
+        .bind(target.breach_days)
+        .fetch_one(&self.pool)
+        .await?;
+        row.into_target().ok_or(sqlx::Error::RowNotFound)
+    }
+
+    /// Delete a target. Returns whether it existed.
+    ///
+    /// # Errors
+    /// Returns error if the query fails.
+    pub async fn delete(&self, id: Uuid) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query("DELETE FROM kpi_targets WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Record when the current breach of a target was alerted, or clear it
+    /// once the target is met again.
+    ///
+    /// # Errors
+    /// Returns error if the query fails.
+    pub async fn set_last_alerted(
+        &self,
+        id: Uuid,
+        at: Option<DateTime<Utc>>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query("UPDATE kpi_targets SET last_alerted_at = $2 WHERE id = $1")
+            .bind(id)
+            .bind(at)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn target(breach_days: i32, last_alerted_at: Option<&str>) -> KpiTarget {
+        KpiTarget {
+            id: Uuid::new_v4(),
+            team: DEFAULT_TEAM.to_string(),
+            kpi: Kpi::AvgTimePerTicket,
+            comparison: Comparison::Below,
+            target_value: 7200.0,
+            breach_days,
+            last_alerted_at: last_alerted_at.map(|at| at.parse().unwrap()),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn days(values: &[f64]) -> Vec<(NaiveDate, f64)> {
+        let first = NaiveDate::from_ymd_opt(2026, 3, 1).unwrap();
+        (0..)
+            .zip(values)
+            .map(|(offset, &value)| (first + chrono::Duration::days(offset), value))
+            .collect()
+    }
+
+    #[test]
+    fn test_comparison() {
+        assert!(Comparison::Below.is_met(5.0, 5.0));
+        assert!(!Comparison::Below.is_met(5.1, 5.0));
+        assert!(Comparison::Above.is_met(5.0, 5.0));
+        assert!(!Comparison::Above.is_met(4.9, 5.0));
+        assert_eq!(Kpi::parse("efficiency"), Some(Kpi::Efficiency));
+        assert_eq!(Kpi::parse("reopen_rate"), None);
+    }
+
+    #[test]
+    fn test_breach_needs_consecutive_missed_days() {
+        let daily = days(&[9000.0, 3600.0, 9000.0, 9000.0]);
+        assert_eq!(target(3, None).evaluate(&daily), TargetStatus::Unchanged);
+        assert_eq!(
+            target(2, None).evaluate(&daily),
+            TargetStatus::Breached {
+                since: NaiveDate::from_ymd_opt(2026, 3, 3).unwrap()
+            }
+        );
+        assert_eq!(
+            target(1, None).evaluate(&days(&[9000.0, 3600.0])),
+            TargetStatus::Unchanged
+        );
+    }
+
+    #[test]
+    fn test_breach_is_alerted_once_until_met_again() {
+        let alerted = target(2, Some("2026-03-02T06:00:00Z"));
+        assert_eq!(
+            alerted.evaluate(&days(&[9000.0, 9000.0, 9000.0])),
+            TargetStatus::Unchanged
+        );
+        assert_eq!(
+            alerted.evaluate(&days(&[9000.0, 3600.0])),
+            TargetStatus::Recovered
+        );
+    }
+}
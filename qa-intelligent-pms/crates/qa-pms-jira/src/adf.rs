@@ -0,0 +1,82 @@
+//! Atlassian Document Format builders.
+//!
+//! Jira Cloud's v3 API takes comment and description bodies as ADF documents
+//! rather than plain text. These helpers build the few node types the
+//! framework writes. ADF rejects empty text nodes, so callers should leave out
+//! blank values instead of passing them here.
+
+use serde_json::{json, Value};
+
+/// A complete document from block nodes.
+#[must_use]
+pub fn document(content: Vec<Value>) -> Value {
+    json!({ "type": "doc", "version": 1, "content": content })
+}
+
+/// A plain text node.
+#[must_use]
+pub fn text(text: &str) -> Value {
+    json!({ "type": "text", "text": text })
+}
+
+/// A text node linking to `href`.
+#[must_use]
+pub fn link(text: &str, href: &str) -> Value {
+    json!({
+        "type": "text",
+        "text": text,
+        "marks": [{ "type": "link", "attrs": { "href": href } }]
+    })
+}
+
+/// A paragraph holding one plain text node.
+#[must_use]
+pub fn paragraph(value: &str) -> Value {
+    paragraph_of(vec![text(value)])
+}
+
+/// A paragraph from inline nodes.
+#[must_use]
+pub fn paragraph_of(content: Vec<Value>) -> Value {
+    json!({ "type": "paragraph", "content": content })
+}
+
+/// A heading of `level` 1 to 6.
+#[must_use]
+pub fn heading(level: u8, value: &str) -> Value {
+    json!({ "type": "heading", "attrs": { "level": level }, "content": [text(value)] })
+}
+
+/// A bullet list with one paragraph per item.
+#[must_use]
+pub fn bullet_list<I: IntoIterator<Item = Value>>(paragraphs: I) -> Value {
+    let items: Vec<Value> = paragraphs
+        .into_iter()
+        .map(|paragraph| json!({ "type": "listItem", "content": [paragraph] }))
+        .collect();
+    json!({ "type": "bulletList", "content": items })
+}
+
+/// A horizontal rule.
+#[must_use]
+pub fn rule() -> Value {
+    json!({ "type": "rule" })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_document_nests_lists_and_links() {
+        let doc = document(vec![
+            heading(3, "Links"),
+            bullet_list([paragraph_of(vec![link("Run", "https://ci.test/1")])]),
+        ]);
+        assert_eq!(doc["type"], "doc");
+        assert_eq!(doc["content"][0]["attrs"]["level"], 3);
+        let node = &doc["content"][1]["content"][0]["content"][0]["content"][0];
+        assert_eq!(node["text"], "Run");
+        assert_eq!(node["marks"][0]["attrs"]["href"], "https://ci.test/1");
+    }
+}
@@ -0,0 +1,261 @@
+//! Cache of Jira ticket searches.
+//!
+//! Listing tickets runs a JQL search against Jira, which is often slower than
+//! the two-second budget for the ticket list. Results are kept per connection,
+//! JQL and page for a configurable time. Each result carries an `ETag`
+//! derived from its content, so clients can make conditional requests and
+//! skip downloading a page that has not changed.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use sha2::{Digest, Sha256};
+use tracing::debug;
+
+use crate::tickets::{JiraTicketsClient, SearchResponse, TicketFilters};
+
+/// Most searches kept at once; the oldest is dropped to make room.
+const MAX_ENTRIES: usize = 512;
+
+/// A search result and its entity tag.
+#[derive(Debug, Clone)]
+pub struct CachedSearch {
+    /// The Jira response
+    pub response: Arc<SearchResponse>,
+    /// Quoted strong entity tag of the response
+    pub etag: String,
+    /// Whether the result came from the cache
+    pub hit: bool,
+}
+
+impl CachedSearch {
+    /// Whether an `If-None-Match` header value matches this result.
+    #[must_use]
+    pub fn matches(&self, if_none_match: &str) -> bool {
+        etag_matches(if_none_match, &self.etag)
+    }
+}
+
+/// Cache counters since startup.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CacheStats {
+    /// Searches answered from the cache
+    pub hits: u64,
+    /// Searches sent to Jira
+    pub misses: u64,
+    /// Searches currently cached
+    pub entries: u64,
+    /// Lifetime of an entry, in seconds
+    pub ttl_secs: u64,
+}
+
+struct CacheEntry {
+    stored_at: Instant,
+    /// Insertion order, to find the oldest entry
+    seq: u64,
+    search: CachedSearch,
+}
+
+#[derive(Default)]
+struct Entries {
+    by_key: HashMap<String, CacheEntry>,
+    next_seq: u64,
+}
+
+/// Ticket searches keyed by connection, JQL and page.
+pub struct TicketSearchCache {
+    ttl: Duration,
+    entries: Mutex<Entries>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl TicketSearchCache {
+    /// Create a cache; a zero `ttl` disables caching.
+    #[must_use]
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: Mutex::new(Entries::default()),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /// Search tickets, answering from the cache when a fresh result exists.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the result was not cached and the Jira search fails.
+    pub async fn list_tickets(
+        &self,
+        client: &JiraTicketsClient,
+        filters: &TicketFilters,
+        start_at: u32,
+        max_results: u32,
+    ) -> Result<CachedSearch> {
+        let key = client.search_key(filters, start_at, max_results);
+        if let Some(search) = self.get(&key) {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+            debug!(start_at, max_results, "Ticket search cache hit");
+            return Ok(search);
+        }
+        self.misses.fetch_add(1, Ordering::Relaxed);
+
+        let response = client.list_tickets(filters, start_at, max_results).await?;
+        let search = CachedSearch {
+            etag: compute_etag(&response),
+            response: Arc::new(response),
+            hit: false,
+        };
+        self.insert(key, search.clone());
+        Ok(search)
+    }
+
+    /// Drop every cached search, e.g. after a ticket changed in Jira.
+    pub fn invalidate(&self) {
+        let mut entries = self.entries.lock().unwrap_or_else(PoisonError::into_inner);
+        if !entries.by_key.is_empty() {
+            debug!(dropped = entries.by_key.len(), "Ticket search cache cleared");
+            entries.by_key.clear();
+        }
+    }
+
+    /// Hit and miss counts since startup.
+    #[must_use]
+    pub fn stats(&self) -> CacheStats {
+        let entries = self.entries.lock().unwrap_or_else(PoisonError::into_inner);
+        let ttl = self.ttl;
+        let live = entries
+            .by_key
+            .values()
+            .filter(|entry| entry.stored_at.elapsed() < ttl)
+            .count();
+        CacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            entries: live as u64,
+            ttl_secs: ttl.as_secs(),
+        }
+    }
+
+    fn get(&self, key: &str) -> Option<CachedSearch> {
+        let entries = self.entries.lock().unwrap_or_else(PoisonError::into_inner);
+        entries
+            .by_key
+            .get(key)
+            .filter(|entry| entry.stored_at.elapsed() < self.ttl)
+            .map(|entry| CachedSearch {
+                hit: true,
+                ..entry.search.clone()
+            })
+    }
+
+    fn insert(&self, key: String, search: CachedSearch) {
+        if self.ttl.is_zero() {
+            return;
+        }
+        let mut entries = self.entries.lock().unwrap_or_else(PoisonError::into_inner);
+        let ttl = self.ttl;
+        entries
+            .by_key
+            .retain(|_, entry| entry.stored_at.elapsed() < ttl);
+        if entries.by_key.len() >= MAX_ENTRIES {
+            let oldest = entries
+                .by_key
+                .iter()
+                .min_by_key(|(_, entry)| entry.seq)
+                .map(|(key, _)| key.clone());
+            if let Some(oldest) = oldest {
+                entries.by_key.remove(&oldest);
+            }
+        }
+        let seq = entries.next_seq;
+        entries.next_seq += 1;
+        entries.by_key.insert(
+            key,
+            CacheEntry {
+                stored_at: Instant::now(),
+                seq,
+                search,
+            },
+        );
+    }
+}
+
+/// Strong entity tag over the serialized response.
+fn compute_etag(response: &SearchResponse) -> String {
+    let body = serde_json::to_vec(response).unwrap_or_default();
+    let digest = Sha256::digest(&body);
+    format!("\"{}\"", hex::encode(&digest[..16]))
+}
+
+/// Whether an `If-None-Match` value names `etag`.
+///
+/// Accepts `*`, comma-separated lists and weak tags, which compare equal to
+/// the strong tag with the same value for this purpose.
+fn etag_matches(if_none_match: &str, etag: &str) -> bool {
+    if_none_match.split(',').map(str::trim).any(|candidate| {
+        candidate == "*" || candidate.strip_prefix("W/").unwrap_or(candidate) == etag
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response(total: u32) -> SearchResponse {
+        SearchResponse {
+            issues: Vec::new(),
+            total,
+            start_at: 0,
+            max_results: 20,
+        }
+    }
+
+    fn search(total: u32) -> CachedSearch {
+        let response = response(total);
+        CachedSearch {
+            etag: compute_etag(&response),
+            response: Arc::new(response),
+            hit: false,
+        }
+    }
+
+    #[test]
+    fn test_cached_search_is_a_hit_until_invalidated() {
+        let cache = TicketSearchCache::new(Duration::from_secs(30));
+        assert!(cache.get("a").is_none());
+
+        cache.insert("a".to_string(), search(3));
+        let cached = cache.get("a").unwrap();
+        assert!(cached.hit);
+        assert_eq!(cached.response.total, 3);
+        assert_eq!(cache.stats().entries, 1);
+
+        cache.invalidate();
+        assert!(cache.get("a").is_none());
+    }
+
+    #[test]
+    fn test_zero_ttl_disables_cache() {
+        let cache = TicketSearchCache::new(Duration::ZERO);
+        cache.insert("a".to_string(), search(3));
+        assert!(cache.get("a").is_none());
+    }
+
+    #[test]
+    fn test_etag_follows_content() {
+        assert_eq!(search(3).etag, search(3).etag);
+        assert_ne!(search(3).etag, search(4).etag);
+
+        let cached = search(3);
+        assert!(cached.matches(&cached.etag));
+        assert!(cached.matches(&format!("\"other\", W/{}", cached.etag)));
+        assert!(cached.matches("*"));
+        assert!(!cached.matches(&search(4).etag));
+    }
+}
@@ -49,6 +49,7 @@ impl JiraHealthCheck {
     /// * `email` - User email address
     /// * `api_token` - API token from <https://id.atlassian.com/manage-profile/security/api-tokens>
     #[must_use]
+    #[allow(clippy::expect_used)]
     pub fn with_api_token(instance_url: String, email: String, api_token: String) -> Self {
         let http_client = Client::builder()
             .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS))
@@ -68,6 +69,7 @@ impl JiraHealthCheck {
     /// * `cloud_id` - Jira Cloud ID (from OAuth flow)
     /// * `access_token` - OAuth access token
     #[must_use]
+    #[allow(clippy::expect_used)]
     pub fn with_oauth(cloud_id: String, access_token: String) -> Self {
         let http_client = Client::builder()
             .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS))
@@ -0,0 +1,240 @@
+//! JQL validation and quoting.
+//!
+//! Queries typed by users are checked before they reach Jira: they must fit
+//! within [`MAX_JQL_LENGTH`], have closed string literals and balanced
+//! parentheses, and must not call any of the [`FORBIDDEN_FUNCTIONS`].
+//! Values the server puts into JQL itself go through [`quote`], so a status
+//! or project name can never end its string literal early.
+//!
+//! The check is lexical only. Jira still parses the query and reports field
+//! and syntax errors of its own.
+
+use thiserror::Error;
+
+/// Longest JQL query accepted from a user.
+pub const MAX_JQL_LENGTH: usize = 2000;
+
+/// Functions a user query may not call.
+///
+/// These report on the Jira account the server connects with rather than
+/// the person using the app, or scan issue history, which is slow on large
+/// sites.
+pub const FORBIDDEN_FUNCTIONS: &[&str] = &[
+    "currentLogin",
+    "lastLogin",
+    "issueHistory",
+    "watchedIssues",
+    "votedIssues",
+    "updatedBy",
+];
+
+/// Why a JQL query was rejected.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum JqlError {
+    /// Nothing but whitespace
+    #[error("JQL query is empty")]
+    Empty,
+
+    /// Longer than allowed
+    #[error("JQL query is longer than {0} characters")]
+    TooLong(usize),
+
+    /// A string literal is never closed
+    #[error("JQL query has an unterminated string")]
+    UnterminatedString,
+
+    /// Parentheses do not pair up
+    #[error("JQL query has unbalanced parentheses")]
+    UnbalancedParentheses,
+
+    /// Calls a function on the deny list
+    #[error("JQL function {0}() is not allowed")]
+    ForbiddenFunction(String),
+
+    /// A condition carries its own ordering
+    #[error("JQL condition must not contain ORDER BY")]
+    OrderBy,
+}
+
+/// Quote a value as a JQL string literal.
+#[must_use]
+pub fn quote(value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for c in value.chars() {
+        if matches!(c, '"' | '\\') {
+            quoted.push('\\');
+        }
+        quoted.push(c);
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Check a complete JQL query.
+///
+/// # Errors
+/// Returns the first problem found.
+pub fn validate(jql: &str) -> Result<(), JqlError> {
+    scan(jql).map(|_| ())
+}
+
+/// Check a JQL condition that is combined with other clauses.
+///
+/// The condition is wrapped in parentheses and followed by the caller's
+/// own ordering, so it must not bring an `ORDER BY`.
+///
+/// # Errors
+/// Returns the first problem found.
+pub fn validate_condition(jql: &str) -> Result<(), JqlError> {
+    let words = scan(jql)?;
+    if words
+        .windows(2)
+        .any(|w| w[0].eq_ignore_ascii_case("order") && w[1].eq_ignore_ascii_case("by"))
+    {
+        return Err(JqlError::OrderBy);
+    }
+    Ok(())
+}
+
+/// Run the shared checks and return the words outside string literals.
+fn scan(jql: &str) -> Result<Vec<&str>, JqlError> {
+    let jql = jql.trim();
+    if jql.is_empty() {
+        return Err(JqlError::Empty);
+    }
+    if jql.chars().count() > MAX_JQL_LENGTH {
+        return Err(JqlError::TooLong(MAX_JQL_LENGTH));
+    }
+
+    let mut words = Vec::new();
+    let mut depth = 0usize;
+    let mut word_start = None;
+    let mut chars = jql.char_indices();
+    while let Some((i, c)) = chars.next() {
+        let is_word = c.is_alphanumeric() || c == '_' || c == '.';
+        if is_word {
+            word_start.get_or_insert(i);
+            continue;
+        }
+        if let Some(start) = word_start.take() {
+            words.push(&jql[start..i]);
+        }
+        match c {
+            '"' | '\'' => {
+                let mut closed = false;
+                while let Some((_, next)) = chars.next() {
+                    if next == '\\' {
+                        chars.next();
+                    } else if next == c {
+                        closed = true;
+                        break;
+                    }
+                }
+                if !closed {
+                    return Err(JqlError::UnterminatedString);
+                }
+            }
+            '(' => {
+                if let Some(name) = words.last().filter(|_| called(jql, i)) {
+                    if let Some(forbidden) = FORBIDDEN_FUNCTIONS
+                        .iter()
+                        .find(|f| f.eq_ignore_ascii_case(name))
+                    {
+                        return Err(JqlError::ForbiddenFunction((*forbidden).to_string()));
+                    }
+                }
+                depth += 1;
+            }
+            ')' => {
+                depth = depth
+                    .checked_sub(1)
+                    .ok_or(JqlError::UnbalancedParentheses)?
+            }
+            _ => {}
+        }
+    }
+    if let Some(start) = word_start {
+        words.push(&jql[start..]);
+    }
+    if depth != 0 {
+        return Err(JqlError::UnbalancedParentheses);
+    }
+    Ok(words)
+}
+
+/// Whether the `(` at byte `paren` directly follows a word, making the word
+/// a function name.
+fn called(jql: &str, paren: usize) -> bool {
+    jql[..paren]
+        .chars()
+        .rev()
+        .find(|c| !c.is_whitespace())
+        .is_some_and(|c| c.is_alphanumeric() || c == '_' || c == '.')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quote_escapes_quotes_and_backslashes() {
+        assert_eq!(quote("Ready for QA"), "\"Ready for QA\"");
+        assert_eq!(
+            quote(r#"x" OR project = "SECRET"#),
+            r#""x\" OR project = \"SECRET""#
+        );
+        assert_eq!(quote(r"a\b"), r#""a\\b""#);
+    }
+
+    #[test]
+    fn test_validate_accepts_ordinary_queries() {
+        for jql in [
+            "project = QA AND status IN (\"In Progress\", 'Ready for QA')",
+            "assignee = currentUser() ORDER BY updated DESC",
+            "summary ~ \"lastLogin()\" AND labels = \"a\\\"b\"",
+            "sprint in openSprints() AND (priority = High OR priority = Highest)",
+        ] {
+            assert_eq!(validate(jql), Ok(()), "{jql}");
+        }
+    }
+
+    #[test]
+    fn test_validate_rejects_bad_queries() {
+        assert_eq!(validate("   "), Err(JqlError::Empty));
+        assert_eq!(
+            validate(&"a".repeat(MAX_JQL_LENGTH + 1)),
+            Err(JqlError::TooLong(MAX_JQL_LENGTH))
+        );
+        assert_eq!(
+            validate("summary ~ \"open"),
+            Err(JqlError::UnterminatedString)
+        );
+        assert_eq!(
+            validate("(project = QA"),
+            Err(JqlError::UnbalancedParentheses)
+        );
+        assert_eq!(
+            validate("project = QA)"),
+            Err(JqlError::UnbalancedParentheses)
+        );
+        assert_eq!(
+            validate("issuekey in issueHistory ()"),
+            Err(JqlError::ForbiddenFunction("issueHistory".into()))
+        );
+        assert_eq!(
+            validate("updated > LASTLOGIN()"),
+            Err(JqlError::ForbiddenFunction("lastLogin".into()))
+        );
+    }
+
+    #[test]
+    fn test_validate_condition_rejects_order_by() {
+        assert_eq!(validate_condition("labels = smoke"), Ok(()));
+        assert_eq!(validate_condition("summary ~ \"order by\""), Ok(()));
+        assert_eq!(
+            validate_condition("labels = smoke order\n  BY created"),
+            Err(JqlError::OrderBy)
+        );
+    }
+}
@@ -6,26 +6,39 @@
 //! - OAuth 2.0 + PKCE authentication flow
 //! - Secure token storage with encryption
 //! - Automatic token refresh
-//! - Ticket listing and filtering
+//! - Ticket listing and filtering, including validated raw JQL queries
+//! - Cached ticket searches with entity tags
 //! - Ticket detail retrieval with comments and attachments
 //! - Ticket status transitions with retry logic
+//! - Comments (built as Atlassian Document Format) and attachment uploads
 //! - Health check for integration monitoring
+//! - Signed webhook parsing for issue updates and comments
 
+pub mod adf;
+pub mod cache;
 pub mod error;
 pub mod health;
+pub mod jql;
 pub mod oauth;
 pub mod pkce;
 pub mod tickets;
 pub mod token_refresh;
 pub mod token_store;
+pub mod webhooks;
 
 // Re-export main types
+pub use cache::{CacheStats, CachedSearch, TicketSearchCache};
 pub use error::{JiraApiError, JiraAuthError};
 pub use health::JiraHealthCheck;
-pub use oauth::{AuthorizationState, JiraOAuthClient, JiraOAuthConfig, TokenResponse};
+pub use jql::JqlError;
+pub use oauth::{
+    find_cloud_id, AccessibleResource, AuthorizationState, JiraOAuthClient, JiraOAuthConfig,
+    TokenResponse,
+};
 pub use tickets::{
-    Attachment, Comment, CommentContainer, JiraTicket, JiraTicketsClient, SearchResponse,
+    Attachment, Comment, CommentContainer, JiraTicket, JiraTicketsClient, JqlCheck, SearchResponse,
     TicketDetail, TicketDetailFields, TicketFields, TicketFilters, Transition, TransitionTarget,
 };
 pub use token_refresh::spawn_token_refresh_task;
 pub use token_store::{FileTokenStore, InMemoryAuthStateStore};
+pub use webhooks::{JiraWebhookEvent, WebhookError};
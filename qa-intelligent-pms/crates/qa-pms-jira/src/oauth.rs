@@ -62,6 +62,34 @@ pub struct AuthorizationState {
     pub state: String,
 }
 
+/// A Jira site the access token can reach.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccessibleResource {
+    /// Cloud ID used in API URLs
+    pub id: String,
+    /// Site URL (e.g., "<https://company.atlassian.net>")
+    pub url: String,
+    /// Site name
+    pub name: String,
+}
+
+/// Cloud ID of the site at `instance_url`.
+///
+/// Falls back to the only site when the token reaches exactly one, since
+/// users often enter a custom domain that differs from the site URL.
+#[must_use]
+pub fn find_cloud_id(resources: &[AccessibleResource], instance_url: &str) -> Option<String> {
+    let wanted = instance_url.trim_end_matches('/');
+    resources
+        .iter()
+        .find(|r| r.url.trim_end_matches('/').eq_ignore_ascii_case(wanted))
+        .or(match resources {
+            [only] => Some(only),
+            _ => None,
+        })
+        .map(|r| r.id.clone())
+}
+
 /// Jira OAuth 2.0 client.
 pub struct JiraOAuthClient {
     config: JiraOAuthConfig,
@@ -75,6 +103,9 @@ impl JiraOAuthClient {
     const TOKEN_URL: &'static str = "https://auth.atlassian.com/oauth/token";
     /// Atlassian API audience.
     const AUDIENCE: &'static str = "api.atlassian.com";
+    /// Sites reachable with an access token.
+    const RESOURCES_URL: &'static str =
+        "https://api.atlassian.com/oauth/token/accessible-resources";
 
     /// Create a new OAuth client with the given configuration.
     #[must_use]
@@ -231,6 +262,39 @@ impl JiraOAuthClient {
         Ok(tokens)
     }
 
+    /// List the Jira sites an access token can reach.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails or the response cannot be parsed.
+    pub async fn accessible_resources(
+        &self,
+        access_token: &str,
+    ) -> Result<Vec<AccessibleResource>, JiraAuthError> {
+        let response = self
+            .http_client
+            .get(Self::RESOURCES_URL)
+            .bearer_auth(access_token)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            warn!(status = %status, "Listing accessible resources failed");
+            return Err(JiraAuthError::ParseError(format!(
+                "Accessible resources request failed: {status} - {body}"
+            )));
+        }
+
+        let resources: Vec<AccessibleResource> = response
+            .json()
+            .await
+            .map_err(|e| JiraAuthError::ParseError(e.to_string()))?;
+        debug!(count = resources.len(), "Fetched accessible resources");
+        Ok(resources)
+    }
+
     /// Get the configured redirect URI.
     #[must_use]
     pub fn redirect_uri(&self) -> &str {
@@ -272,6 +336,28 @@ mod tests {
         assert!(!state.state.is_empty());
     }
 
+    #[test]
+    fn test_find_cloud_id() {
+        let site = |id: &str, url: &str| AccessibleResource {
+            id: id.to_string(),
+            url: url.to_string(),
+            name: id.to_string(),
+        };
+        let sites = vec![
+            site("c1", "https://acme.atlassian.net"),
+            site("c2", "https://other.atlassian.net"),
+        ];
+        assert_eq!(
+            find_cloud_id(&sites, "https://OTHER.atlassian.net/").as_deref(),
+            Some("c2")
+        );
+        assert!(find_cloud_id(&sites, "https://jira.acme.test").is_none());
+        assert_eq!(
+            find_cloud_id(&sites[..1], "https://jira.acme.test").as_deref(),
+            Some("c1")
+        );
+    }
+
     #[test]
     fn test_default_scopes() {
         let scopes = JiraOAuthConfig::default_scopes();
@@ -4,16 +4,25 @@
 //! - List tickets with JQL filters
 //! - Retrieve ticket details with comments and attachments
 //! - Update ticket status
+//! - Add comments, upload attachments and download attachment content
 //!
-//! Supports both API Token (Basic Auth) and OAuth authentication.
+//! Supports both API Token (Basic Auth) and OAuth authentication. Transient
+//! failures are retried with a [`RetryPolicy`], and requests can be guarded
+//! by a shared circuit breaker so an unreachable Jira fails fast instead of
+//! waiting out the timeout.
 
 use anyhow::Result;
 use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
-use reqwest::Client;
+use qa_pms_core::retry::{self, RetryPolicy};
+use qa_pms_core::{CircuitBreaker, CircuitOpenError};
+use reqwest::{Client, RequestBuilder, Response};
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 use std::time::Duration;
 use tracing::{debug, info, instrument, warn};
 
+use crate::jql;
+
 /// Jira authentication credentials.
 #[derive(Clone)]
 pub enum JiraAuth {
@@ -237,6 +246,27 @@ pub struct TicketFilters {
     pub assignee: Option<String>,
     /// Filter by project key
     pub project: Option<String>,
+    /// Filter by sprint name or ID
+    pub sprint: Option<String>,
+    /// Extra JQL condition, combined with the other filters using AND
+    pub jql: Option<String>,
+    /// Complete JQL query, used instead of all the other filters
+    pub raw_jql: Option<String>,
+}
+
+/// Outcome of checking a JQL query against Jira.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JqlCheck {
+    /// Jira accepted the query
+    Valid {
+        /// Approximate number of matching issues
+        count: u64,
+    },
+    /// Jira rejected the query
+    Invalid {
+        /// Jira's error messages
+        errors: Vec<String>,
+    },
 }
 
 // ============================================================================
@@ -302,6 +332,8 @@ pub struct TransitionId {
 pub struct JiraTicketsClient {
     http_client: Client,
     auth: JiraAuth,
+    retry: RetryPolicy,
+    circuit_breaker: Option<Arc<CircuitBreaker>>,
 }
 
 impl JiraTicketsClient {
@@ -321,6 +353,7 @@ impl JiraTicketsClient {
     /// * `email` - User email address
     /// * `api_token` - API token from Atlassian account settings
     #[must_use]
+    #[allow(clippy::expect_used)]
     pub fn with_api_token(instance_url: String, email: String, api_token: String) -> Self {
         let http_client = Client::builder()
             .timeout(Duration::from_secs(30))
@@ -334,6 +367,8 @@ impl JiraTicketsClient {
                 email,
                 api_token,
             },
+            retry: RetryPolicy::default(),
+            circuit_breaker: None,
         }
     }
 
@@ -343,6 +378,7 @@ impl JiraTicketsClient {
     /// * `cloud_id` - Jira Cloud ID for the site (from OAuth flow)
     /// * `access_token` - Valid OAuth access token
     #[must_use]
+    #[allow(clippy::expect_used)]
     pub fn with_oauth(cloud_id: String, access_token: String) -> Self {
         let http_client = Client::builder()
             .timeout(Duration::from_secs(30))
@@ -355,6 +391,8 @@ impl JiraTicketsClient {
                 cloud_id,
                 access_token,
             },
+            retry: RetryPolicy::default(),
+            circuit_breaker: None,
         }
     }
 
@@ -365,6 +403,40 @@ impl JiraTicketsClient {
         Self::with_oauth(cloud_id, access_token)
     }
 
+    /// Retry failed requests with the given policy.
+    #[must_use]
+    pub const fn with_retry_policy(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Guard requests with a circuit breaker shared across clients.
+    #[must_use]
+    pub fn with_circuit_breaker(mut self, breaker: Arc<CircuitBreaker>) -> Self {
+        self.circuit_breaker = Some(breaker);
+        self
+    }
+
+    /// Send a request through the circuit breaker, if any.
+    ///
+    /// Network errors and 5xx responses left after retrying count as one
+    /// failure; any other response shows Jira is reachable.
+    async fn send(
+        &self,
+        request: RequestBuilder,
+    ) -> std::result::Result<reqwest::Result<Response>, CircuitOpenError> {
+        let Some(breaker) = &self.circuit_breaker else {
+            return Ok(retry::send(&self.retry, "Jira", request).await);
+        };
+        breaker.try_acquire()?;
+        let result = retry::send(&self.retry, "Jira", request).await;
+        match &result {
+            Ok(response) if !response.status().is_server_error() => breaker.record_success(),
+            _ => breaker.record_failure(),
+        }
+        Ok(result)
+    }
+
     /// Get the base URL for API requests.
     fn base_url(&self) -> String {
         match &self.auth {
@@ -425,7 +497,7 @@ impl JiraTicketsClient {
 
         debug!(jql = %jql, start_at, max_results, "Searching Jira tickets");
 
-        let response = self
+        let request = self
             .http_client
             .get(&url)
             .header("Authorization", self.auth_header())
@@ -434,9 +506,8 @@ impl JiraTicketsClient {
                 ("startAt", &start_at.to_string()),
                 ("maxResults", &max_results.to_string()),
                 ("fields", Self::SEARCH_FIELDS),
-            ])
-            .send()
-            .await?;
+            ]);
+        let response = self.send(request).await??;
 
         if !response.status().is_success() {
             let status = response.status();
@@ -456,33 +527,92 @@ impl JiraTicketsClient {
         Ok(search_response)
     }
 
+    /// Check a JQL query with Jira without fetching any issues.
+    ///
+    /// # Errors
+    /// Returns an error if Jira cannot be reached or fails for another
+    /// reason than the query itself.
+    pub async fn check_jql(&self, jql: &str) -> Result<JqlCheck> {
+        #[derive(Deserialize)]
+        struct Count {
+            count: u64,
+        }
+        #[derive(Deserialize, Default)]
+        #[serde(rename_all = "camelCase")]
+        struct Errors {
+            #[serde(default)]
+            error_messages: Vec<String>,
+        }
+
+        let url = format!("{}/rest/api/3/search/approximate-count", self.base_url());
+        let request = self
+            .http_client
+            .post(&url)
+            .header("Authorization", self.auth_header())
+            .json(&serde_json::json!({ "jql": jql }));
+        let response = self.send(request).await??;
+
+        let status = response.status();
+        if status.as_u16() == 400 {
+            let errors: Errors = response.json().await.unwrap_or_default();
+            return Ok(JqlCheck::Invalid {
+                errors: errors.error_messages,
+            });
+        }
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            warn!(status = %status, body = %body, "Jira JQL check failed");
+            anyhow::bail!("Jira API error: {status} - {body}");
+        }
+
+        let Count { count } = response.json().await?;
+        Ok(JqlCheck::Valid { count })
+    }
+
     /// Build JQL query from filters.
     fn build_jql(filters: &TicketFilters) -> String {
+        if let Some(raw) = filters.raw_jql.as_deref().map(str::trim).filter(|j| !j.is_empty()) {
+            return raw.to_string();
+        }
+
         let mut clauses = Vec::new();
 
         if let Some(project) = &filters.project {
-            clauses.push(format!("project = \"{project}\""));
+            clauses.push(format!("project = {}", jql::quote(project)));
         }
 
         if !filters.statuses.is_empty() {
             let statuses = filters
                 .statuses
                 .iter()
-                .map(|s| format!("\"{s}\""))
+                .map(|s| jql::quote(s))
                 .collect::<Vec<_>>()
                 .join(", ");
             clauses.push(format!("status IN ({statuses})"));
         }
 
+        if let Some(sprint) = &filters.sprint {
+            // Numeric values are sprint IDs, anything else a sprint name
+            if sprint.chars().all(|c| c.is_ascii_digit()) {
+                clauses.push(format!("sprint = {sprint}"));
+            } else {
+                clauses.push(format!("sprint = {}", jql::quote(sprint)));
+            }
+        }
+
         if let Some(assignee) = &filters.assignee {
             // Support both email and "currentUser()" special value
             if assignee == "currentUser()" {
                 clauses.push("assignee = currentUser()".to_string());
             } else {
-                clauses.push(format!("assignee = \"{assignee}\""));
+                clauses.push(format!("assignee = {}", jql::quote(assignee)));
             }
         }
 
+        if let Some(jql) = filters.jql.as_deref().map(str::trim).filter(|j| !j.is_empty()) {
+            clauses.push(format!("({jql})"));
+        }
+
         let base = if clauses.is_empty() {
             String::new()
         } else {
@@ -496,6 +626,21 @@ impl JiraTicketsClient {
         }
     }
 
+    /// Cache key for a search: the connection, the JQL and the page.
+    pub(crate) fn search_key(
+        &self,
+        filters: &TicketFilters,
+        start_at: u32,
+        max_results: u32,
+    ) -> String {
+        format!(
+            "{}|{}|{start_at}|{}",
+            self.display_name(),
+            Self::build_jql(filters),
+            max_results.min(100)
+        )
+    }
+
     /// Update the access token (after refresh).
     /// Update OAuth access token (for token refresh).
     ///
@@ -525,13 +670,12 @@ impl JiraTicketsClient {
 
         debug!(key = %key, "Fetching ticket details from Jira");
 
-        let response = self
+        let request = self
             .http_client
             .get(&url)
             .header("Authorization", self.auth_header())
-            .query(&[("fields", fields)])
-            .send()
-            .await?;
+            .query(&[("fields", fields)]);
+        let response = self.send(request).await??;
 
         if !response.status().is_success() {
             let status = response.status();
@@ -557,6 +701,78 @@ impl JiraTicketsClient {
         Ok(ticket)
     }
 
+    /// List the attachments on a ticket.
+    ///
+    /// # Errors
+    /// Returns error if API call fails, ticket not found, or response cannot be parsed.
+    #[instrument(skip(self), fields(jira = %self.display_name(), ticket_key = %key))]
+    pub async fn get_attachments(&self, key: &str) -> Result<Vec<Attachment>> {
+        #[derive(Deserialize)]
+        struct Fields {
+            #[serde(default)]
+            attachment: Vec<Attachment>,
+        }
+        #[derive(Deserialize)]
+        struct Issue {
+            fields: Fields,
+        }
+
+        let url = format!("{}/rest/api/3/issue/{}", self.base_url(), key);
+        let request = self
+            .http_client
+            .get(&url)
+            .header("Authorization", self.auth_header())
+            .query(&[("fields", "attachment")]);
+        let response = self.send(request).await??;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+
+            if status.as_u16() == 404 {
+                anyhow::bail!("Ticket not found: {key}");
+            }
+
+            warn!(status = %status, body = %body, "Jira get attachments failed");
+            anyhow::bail!("Jira API error: {status} - {body}");
+        }
+
+        let issue: Issue = response.json().await?;
+        Ok(issue.fields.attachment)
+    }
+
+    /// Start downloading an attachment's content.
+    ///
+    /// The body is left unread so callers can stream it. The download goes
+    /// through the REST API rather than the attachment's `content` URL, so
+    /// it works with OAuth as well as API tokens.
+    ///
+    /// # Errors
+    /// Returns error if the request fails or Jira does not return the content.
+    #[instrument(skip(self), fields(jira = %self.display_name(), attachment_id = %id))]
+    pub async fn download_attachment(&self, id: &str) -> Result<Response> {
+        let url = format!("{}/rest/api/3/attachment/content/{}", self.base_url(), id);
+        let request = self
+            .http_client
+            .get(&url)
+            .header("Authorization", self.auth_header());
+        let response = self.send(request).await??;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+
+            if status.as_u16() == 404 {
+                anyhow::bail!("Attachment not found: {id}");
+            }
+
+            warn!(status = %status, body = %body, "Jira attachment download failed");
+            anyhow::bail!("Jira API error: {status} - {body}");
+        }
+
+        Ok(response)
+    }
+
     /// Get available transitions for a ticket.
     ///
     /// # Arguments
@@ -573,12 +789,11 @@ impl JiraTicketsClient {
 
         debug!(key = %key, "Fetching available transitions from Jira");
 
-        let response = self
+        let request = self
             .http_client
             .get(&url)
-            .header("Authorization", self.auth_header())
-            .send()
-            .await?;
+            .header("Authorization", self.auth_header());
+        let response = self.send(request).await??;
 
         if !response.status().is_success() {
             let status = response.status();
@@ -612,10 +827,8 @@ impl JiraTicketsClient {
 
     /// Transition a ticket to a new status.
     ///
-    /// Implements retry with exponential backoff per NFR-REL-03:
-    /// - Attempt 1: Immediate
-    /// - Attempt 2: Wait 1 second
-    /// - Attempt 3: Wait 2 seconds
+    /// Server errors and network failures are retried with the client's
+    /// retry policy (NFR-REL-03).
     ///
     /// # Arguments
     /// * `key` - Jira ticket key (e.g., "PROJ-123")
@@ -633,85 +846,123 @@ impl JiraTicketsClient {
             },
         };
 
-        // Retry configuration per NFR-REL-03
-        const MAX_ATTEMPTS: u32 = 3;
-        let base_delay = Duration::from_secs(1);
-
-        let mut attempt = 0;
-
-        loop {
-            attempt += 1;
-
-            debug!(
-                key = %key,
-                transition_id = %transition_id,
-                attempt = attempt,
-                "Attempting ticket transition"
-            );
-
-            let result = self
-                .http_client
-                .post(&url)
-                .header("Authorization", self.auth_header())
-                .json(&body)
-                .send()
-                .await;
-
-            match result {
-                Ok(response) if response.status().is_success() => {
-                    info!(
-                        key = %key,
-                        transition_id = %transition_id,
-                        attempt = attempt,
-                        "Ticket transition successful"
-                    );
-                    return Ok(());
-                }
-                Ok(response) if response.status().is_server_error() && attempt < MAX_ATTEMPTS => {
-                    let delay = base_delay * 2u32.pow(attempt - 1);
-                    warn!(
-                        key = %key,
-                        status = %response.status(),
-                        attempt = attempt,
-                        delay_ms = delay.as_millis(),
-                        "Jira transition failed with server error, retrying"
-                    );
-                    tokio::time::sleep(delay).await;
-                }
-                Ok(response) => {
-                    let status = response.status();
-                    let error_text = response.text().await.unwrap_or_default();
-
-                    if status.as_u16() == 400 {
-                        anyhow::bail!("Invalid transition: {error_text}");
-                    } else if status.as_u16() == 404 {
-                        anyhow::bail!("Ticket not found: {key}");
-                    }
+        debug!(key = %key, transition_id = %transition_id, "Attempting ticket transition");
 
-                    warn!(
-                        key = %key,
-                        status = %status,
-                        error = %error_text,
-                        "Transition failed"
-                    );
-                    anyhow::bail!("Transition failed: {status} - {error_text}");
-                }
-                Err(e) if attempt < MAX_ATTEMPTS => {
-                    let delay = base_delay * 2u32.pow(attempt - 1);
-                    warn!(
-                        key = %key,
-                        error = %e,
-                        attempt = attempt,
-                        delay_ms = delay.as_millis(),
-                        "Network error during transition, retrying"
-                    );
-                    tokio::time::sleep(delay).await;
-                }
-                Err(e) => {
-                    return Err(e.into());
-                }
+        let request = self
+            .http_client
+            .post(&url)
+            .header("Authorization", self.auth_header())
+            .json(&body);
+        let response = self.send(request).await??;
+
+        if response.status().is_success() {
+            info!(key = %key, transition_id = %transition_id, "Ticket transition successful");
+            return Ok(());
+        }
+
+        let status = response.status();
+        let error_text = response.text().await.unwrap_or_default();
+
+        if status.as_u16() == 400 {
+            anyhow::bail!("Invalid transition: {error_text}");
+        } else if status.as_u16() == 404 {
+            anyhow::bail!("Ticket not found: {key}");
+        }
+
+        warn!(
+            key = %key,
+            status = %status,
+            error = %error_text,
+            "Transition failed"
+        );
+        anyhow::bail!("Transition failed: {status} - {error_text}");
+    }
+
+    /// Add a comment to a ticket.
+    ///
+    /// # Arguments
+    /// * `key` - Jira ticket key (e.g., "PROJ-123")
+    /// * `body` - Comment body in Atlassian Document Format
+    ///
+    /// # Errors
+    /// Returns error if API call fails or response cannot be parsed.
+    #[instrument(skip(self, body), fields(jira = %self.display_name(), ticket_key = %key))]
+    pub async fn add_comment(&self, key: &str, body: serde_json::Value) -> Result<Comment> {
+        let url = format!("{}/rest/api/3/issue/{}/comment", self.base_url(), key);
+
+        let request = self
+            .http_client
+            .post(&url)
+            .header("Authorization", self.auth_header())
+            .json(&serde_json::json!({ "body": body }));
+        let response = self.send(request).await??;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+
+            if status.as_u16() == 404 {
+                anyhow::bail!("Ticket not found: {key}");
             }
+
+            warn!(status = %status, body = %body, "Jira add comment failed");
+            anyhow::bail!("Jira API error: {status} - {body}");
         }
+
+        let comment: Comment = response.json().await?;
+        info!(key = %key, comment_id = %comment.id, "Comment added");
+        Ok(comment)
+    }
+
+    /// Upload a file as an attachment to a ticket.
+    ///
+    /// # Arguments
+    /// * `key` - Jira ticket key (e.g., "PROJ-123")
+    /// * `filename` - File name shown in Jira
+    /// * `mime_type` - Content type of the file
+    /// * `content` - File bytes
+    ///
+    /// # Errors
+    /// Returns error if API call fails or response cannot be parsed.
+    #[instrument(skip(self, content), fields(jira = %self.display_name(), ticket_key = %key))]
+    pub async fn add_attachment(
+        &self,
+        key: &str,
+        filename: &str,
+        mime_type: &str,
+        content: Vec<u8>,
+    ) -> Result<Vec<Attachment>> {
+        let url = format!("{}/rest/api/3/issue/{}/attachments", self.base_url(), key);
+
+        let part = reqwest::multipart::Part::bytes(content)
+            .file_name(filename.to_string())
+            .mime_str(mime_type)?;
+        let form = reqwest::multipart::Form::new().part("file", part);
+
+        let request = self
+            .http_client
+            .post(&url)
+            .header("Authorization", self.auth_header())
+            // Jira rejects multipart uploads without this XSRF opt-out
+            .header("X-Atlassian-Token", "no-check")
+            .multipart(form);
+        let response = self.send(request).await??;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+
+            if status.as_u16() == 404 {
+                anyhow::bail!("Ticket not found: {key}");
+            }
+
+            warn!(status = %status, body = %body, "Jira attachment upload failed");
+            anyhow::bail!("Jira API error: {status} - {body}");
+        }
+
+        let attachments: Vec<Attachment> = response.json().await?;
+        info!(key = %key, filename = %filename, "Attachment uploaded");
+        Ok(attachments)
     }
 }
 
@@ -739,6 +990,21 @@ mod tests {
         assert!(jql.ends_with("ORDER BY updated DESC"));
     }
 
+    #[test]
+    fn test_build_jql_with_sprint() {
+        let by_name = TicketFilters {
+            sprint: Some("Sprint 42".to_string()),
+            ..Default::default()
+        };
+        assert!(JiraTicketsClient::build_jql(&by_name).starts_with("sprint = \"Sprint 42\""));
+
+        let by_id = TicketFilters {
+            sprint: Some("118".to_string()),
+            ..Default::default()
+        };
+        assert!(JiraTicketsClient::build_jql(&by_id).starts_with("sprint = 118 "));
+    }
+
     #[test]
     fn test_build_jql_with_assignee() {
         let filters = TicketFilters {
@@ -775,6 +1041,9 @@ mod tests {
             statuses: vec!["Open".to_string()],
             assignee: Some("user@example.com".to_string()),
             project: Some("TEST".to_string()),
+            sprint: None,
+            jql: None,
+            raw_jql: None,
         };
         let jql = JiraTicketsClient::build_jql(&filters);
         assert!(jql.contains("project = \"TEST\""));
@@ -783,6 +1052,50 @@ mod tests {
         assert!(jql.contains(" AND "));
     }
 
+    #[test]
+    fn test_build_jql_with_fragment() {
+        let filters = TicketFilters {
+            project: Some("TEST".to_string()),
+            jql: Some(" labels = regression OR priority = High ".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            JiraTicketsClient::build_jql(&filters),
+            "project = \"TEST\" AND (labels = regression OR priority = High) ORDER BY updated DESC"
+        );
+
+        let blank = TicketFilters {
+            jql: Some("  ".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(JiraTicketsClient::build_jql(&blank), "ORDER BY updated DESC");
+    }
+
+    #[test]
+    fn test_build_jql_quotes_values() {
+        let filters = TicketFilters {
+            project: Some(r#"QA" OR project = "HR"#.to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            JiraTicketsClient::build_jql(&filters),
+            r#"project = "QA\" OR project = \"HR" ORDER BY updated DESC"#
+        );
+    }
+
+    #[test]
+    fn test_build_jql_raw_query_replaces_filters() {
+        let filters = TicketFilters {
+            project: Some("IGNORED".to_string()),
+            raw_jql: Some(" labels = smoke ORDER BY priority ".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            JiraTicketsClient::build_jql(&filters),
+            "labels = smoke ORDER BY priority"
+        );
+    }
+
     #[test]
     fn test_ticket_fields_deserialization() {
         let json = r#"{
@@ -1082,4 +1395,19 @@ mod tests {
         assert!(!transition.has_screen); // default false
         assert!(transition.is_available); // default true
     }
+
+    #[tokio::test]
+    async fn test_open_circuit_fails_fast() {
+        let breaker = Arc::new(CircuitBreaker::new("jira", 1, Duration::from_secs(60)));
+        breaker.record_failure();
+        let client = JiraTicketsClient::with_api_token(
+            "http://127.0.0.1:9".to_string(),
+            "qa@example.com".to_string(),
+            "token".to_string(),
+        )
+        .with_circuit_breaker(breaker);
+
+        let err = client.get_ticket("PROJ-1").await.unwrap_err();
+        assert!(err.downcast_ref::<CircuitOpenError>().is_some());
+    }
 }
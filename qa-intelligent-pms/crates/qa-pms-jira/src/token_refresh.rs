@@ -114,6 +114,7 @@ mod tests {
     // a dedicated test module or integration test file.
 
     #[test]
+    #[allow(clippy::assertions_on_constants)]
     fn test_constants() {
         // Verify our constants are reasonable
         assert!(CHECK_INTERVAL_SECS >= 30); // Don't check too frequently
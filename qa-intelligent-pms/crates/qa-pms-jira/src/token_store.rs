@@ -4,12 +4,12 @@
 
 use anyhow::{Context, Result};
 use async_trait::async_trait;
-use qa_pms_config::Encryptor;
+use qa_pms_config::{Encryptor, RotationCount};
 use qa_pms_core::{AuthStateStore, StoredTokens, TokenStore};
 use secrecy::ExposeSecret;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use tracing::{debug, info, warn};
@@ -62,6 +62,47 @@ impl FileTokenStore {
         Ok(store)
     }
 
+    /// Re-encrypt the tokens in a token file with the encryptor's current
+    /// key. A dry run only checks that every token decrypts.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or written, or a token
+    /// does not decrypt with any configured key.
+    pub async fn rotate_file(
+        file_path: &Path,
+        encryptor: &Encryptor,
+        dry_run: bool,
+    ) -> Result<RotationCount> {
+        let mut count = RotationCount::default();
+        if !tokio::fs::try_exists(file_path).await.unwrap_or(false) {
+            return Ok(count);
+        }
+        let contents = tokio::fs::read_to_string(file_path)
+            .await
+            .context("Failed to read tokens file")?;
+        let mut file: TokensFile =
+            serde_json::from_str(&contents).context("Failed to parse tokens file")?;
+        if file.version != Self::VERSION {
+            // Such files are cleared when loaded, so there is nothing to keep
+            return Ok(count);
+        }
+
+        for (integration, tokens) in &mut file.tokens {
+            for token in [&mut tokens.access_token, &mut tokens.refresh_token] {
+                encryptor
+                    .rotate(token, &mut count)
+                    .with_context(|| format!("Failed to rotate {integration} tokens"))?;
+            }
+        }
+        if count.rotated > 0 && !dry_run {
+            tokio::fs::write(file_path, serde_json::to_string_pretty(&file)?)
+                .await
+                .context("Failed to write tokens file")?;
+        }
+        Ok(count)
+    }
+
     /// Load tokens from file into cache.
     async fn load_from_file(&self) -> Result<()> {
         if !self.file_path.exists() {
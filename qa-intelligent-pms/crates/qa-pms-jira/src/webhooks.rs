@@ -0,0 +1,400 @@
+//! Jira webhook payloads.
+//!
+//! Jira signs webhook deliveries with the secret set on the webhook, sending
+//! `X-Hub-Signature: sha256=<hex HMAC of the body>`. Only issue updates and new
+//! comments are parsed; other events are acknowledged and ignored.
+
+use chrono::{DateTime, TimeZone, Utc};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::Sha256;
+use thiserror::Error;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Header carrying the webhook signature.
+pub const SIGNATURE_HEADER: &str = "x-hub-signature";
+
+/// Why a webhook delivery was rejected.
+#[derive(Debug, Error)]
+pub enum WebhookError {
+    /// The signature header is absent
+    #[error("Missing webhook signature")]
+    MissingSignature,
+
+    /// The signature does not match the body
+    #[error("Invalid webhook signature")]
+    InvalidSignature,
+
+    /// The body is not a webhook payload
+    #[error("Invalid webhook payload: {0}")]
+    InvalidPayload(String),
+}
+
+/// Check a delivery's `X-Hub-Signature` header against its raw body.
+///
+/// # Errors
+///
+/// Returns an error if the header is missing, malformed, or does not match.
+pub fn verify_signature(
+    secret: &str,
+    body: &[u8],
+    signature: Option<&str>,
+) -> Result<(), WebhookError> {
+    let signature = signature.ok_or(WebhookError::MissingSignature)?;
+    let hex_digest = signature
+        .trim()
+        .strip_prefix("sha256=")
+        .ok_or(WebhookError::InvalidSignature)?;
+    let expected = hex::decode(hex_digest).map_err(|_| WebhookError::InvalidSignature)?;
+
+    let mut mac = HmacSha256::new_from_slice(secret.as_bytes())
+        .map_err(|_| WebhookError::InvalidSignature)?;
+    mac.update(body);
+    // Constant-time comparison
+    mac.verify_slice(&expected)
+        .map_err(|_| WebhookError::InvalidSignature)
+}
+
+/// A webhook event the framework reacts to.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum JiraWebhookEvent {
+    /// Fields of an issue changed
+    IssueUpdated(IssueUpdate),
+    /// A comment was added to an issue
+    CommentAdded(CommentAdded),
+}
+
+impl JiraWebhookEvent {
+    /// Key of the issue the event is about.
+    #[must_use]
+    pub fn issue_key(&self) -> &str {
+        match self {
+            Self::IssueUpdated(update) => &update.issue_key,
+            Self::CommentAdded(comment) => &comment.issue_key,
+        }
+    }
+}
+
+/// An issue update.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IssueUpdate {
+    /// Issue key (e.g., "PROJ-123")
+    pub issue_key: String,
+    /// Issue summary after the update
+    pub summary: Option<String>,
+    /// Names of the issue's components
+    pub components: Vec<String>,
+    /// Who made the change
+    pub changed_by: Option<String>,
+    /// Status transition, when the status changed
+    pub transition: Option<StatusTransition>,
+    /// Every changed field, including the status
+    pub changes: Vec<FieldChange>,
+    /// When Jira sent the event
+    pub timestamp: DateTime<Utc>,
+}
+
+/// A status transition.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StatusTransition {
+    /// Previous status name
+    pub from: Option<String>,
+    /// New status name
+    pub to: String,
+}
+
+/// One changed field from the changelog.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FieldChange {
+    /// Field name
+    pub field: String,
+    /// Previous value, as displayed
+    pub from: Option<String>,
+    /// New value, as displayed
+    pub to: Option<String>,
+}
+
+/// A new comment.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommentAdded {
+    /// Issue key (e.g., "PROJ-123")
+    pub issue_key: String,
+    /// Comment ID
+    pub comment_id: String,
+    /// Comment author
+    pub author: Option<String>,
+    /// Comment text
+    pub body: String,
+    /// When Jira sent the event
+    pub timestamp: DateTime<Utc>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Payload {
+    webhook_event: String,
+    #[serde(rename = "issue_event_type_name")]
+    issue_event_type: Option<String>,
+    /// Milliseconds since the epoch
+    timestamp: Option<i64>,
+    user: Option<PayloadUser>,
+    issue: Option<PayloadIssue>,
+    changelog: Option<PayloadChangelog>,
+    comment: Option<PayloadComment>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PayloadUser {
+    display_name: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct PayloadIssue {
+    key: String,
+    #[serde(default)]
+    fields: PayloadIssueFields,
+}
+
+#[derive(Default, Deserialize)]
+struct PayloadIssueFields {
+    summary: Option<String>,
+    #[serde(default)]
+    components: Vec<PayloadComponent>,
+}
+
+#[derive(Deserialize)]
+struct PayloadComponent {
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct PayloadChangelog {
+    #[serde(default)]
+    items: Vec<PayloadChange>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PayloadChange {
+    field: String,
+    from_string: Option<String>,
+    to_string: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct PayloadComment {
+    id: String,
+    author: Option<PayloadUser>,
+    body: Option<Value>,
+}
+
+/// Parse a webhook body.
+///
+/// Returns `None` for events other than issue updates and new comments.
+///
+/// # Errors
+///
+/// Returns an error if the body is not a Jira webhook payload or lacks the
+/// issue or comment its event needs.
+pub fn parse_event(body: &[u8]) -> Result<Option<JiraWebhookEvent>, WebhookError> {
+    let payload: Payload =
+        serde_json::from_slice(body).map_err(|e| WebhookError::InvalidPayload(e.to_string()))?;
+    let timestamp = payload
+        .timestamp
+        .and_then(|ms| Utc.timestamp_millis_opt(ms).single())
+        .unwrap_or_else(Utc::now);
+
+    // Comments also arrive as issue updates of type "issue_commented"
+    let commented = payload.webhook_event == "comment_created"
+        || (payload.webhook_event == "jira:issue_updated"
+            && payload.issue_event_type.as_deref() == Some("issue_commented"));
+    if commented {
+        let issue = payload.issue.ok_or_else(|| missing("issue"))?;
+        let comment = payload.comment.ok_or_else(|| missing("comment"))?;
+        return Ok(Some(JiraWebhookEvent::CommentAdded(CommentAdded {
+            issue_key: issue.key,
+            comment_id: comment.id,
+            author: comment.author.and_then(|a| a.display_name),
+            body: comment.body.as_ref().map(body_text).unwrap_or_default(),
+            timestamp,
+        })));
+    }
+
+    if payload.webhook_event != "jira:issue_updated" {
+        return Ok(None);
+    }
+    let issue = payload.issue.ok_or_else(|| missing("issue"))?;
+    let changes: Vec<FieldChange> = payload
+        .changelog
+        .map(|changelog| changelog.items)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|item| FieldChange {
+            field: item.field,
+            from: item.from_string,
+            to: item.to_string,
+        })
+        .collect();
+    let transition = changes
+        .iter()
+        .find(|change| change.field.eq_ignore_ascii_case("status"))
+        .and_then(|change| {
+            Some(StatusTransition {
+                from: change.from.clone(),
+                to: change.to.clone()?,
+            })
+        });
+
+    Ok(Some(JiraWebhookEvent::IssueUpdated(IssueUpdate {
+        issue_key: issue.key,
+        summary: issue.fields.summary,
+        components: issue.fields.components.into_iter().map(|c| c.name).collect(),
+        changed_by: payload.user.and_then(|u| u.display_name),
+        transition,
+        changes,
+        timestamp,
+    })))
+}
+
+fn missing(field: &str) -> WebhookError {
+    WebhookError::InvalidPayload(format!("missing {field}"))
+}
+
+/// Comment text: plain strings as-is, rich text (ADF) flattened to its text nodes.
+fn body_text(body: &Value) -> String {
+    fn collect(node: &Value, out: &mut String) {
+        match node {
+            Value::Array(items) => items.iter().for_each(|item| collect(item, out)),
+            Value::Object(obj) => {
+                if obj.get("type").and_then(Value::as_str) == Some("paragraph") && !out.is_empty() {
+                    out.push('\n');
+                }
+                if let Some(Value::String(text)) = obj.get("text") {
+                    out.push_str(text);
+                }
+                if let Some(content) = obj.get("content") {
+                    collect(content, out);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    match body {
+        Value::String(text) => text.clone(),
+        other => {
+            let mut out = String::new();
+            collect(other, &mut out);
+            out
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ISSUE_UPDATED: &str = r#"{
+        "timestamp": 1717243200000,
+        "webhookEvent": "jira:issue_updated",
+        "issue_event_type_name": "issue_generic",
+        "user": {"accountId": "1", "displayName": "Dana QA"},
+        "issue": {"id": "10001", "key": "PROJ-7", "fields": {"summary": "Login fails", "components": [{"id": "3", "name": "Auth"}]}},
+        "changelog": {"items": [
+            {"field": "assignee", "fromString": null, "toString": "Dana QA"},
+            {"field": "status", "fromString": "In Progress", "toString": "Ready for QA"}
+        ]}
+    }"#;
+
+    fn sign(secret: &str, body: &[u8]) -> String {
+        let mut mac = HmacSha256::new_from_slice(secret.as_bytes()).unwrap();
+        mac.update(body);
+        format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+    }
+
+    #[test]
+    fn test_signature_round_trip() {
+        let body = ISSUE_UPDATED.as_bytes();
+        let signature = sign("s3cret", body);
+        assert!(verify_signature("s3cret", body, Some(&signature)).is_ok());
+
+        assert!(matches!(
+            verify_signature("other", body, Some(&signature)),
+            Err(WebhookError::InvalidSignature)
+        ));
+        assert!(matches!(
+            verify_signature("s3cret", b"{}", Some(&signature)),
+            Err(WebhookError::InvalidSignature)
+        ));
+        assert!(matches!(
+            verify_signature("s3cret", body, Some("sha1=abc")),
+            Err(WebhookError::InvalidSignature)
+        ));
+        assert!(matches!(
+            verify_signature("s3cret", body, None),
+            Err(WebhookError::MissingSignature)
+        ));
+    }
+
+    #[test]
+    fn test_parse_issue_updated_with_transition() {
+        let Some(JiraWebhookEvent::IssueUpdated(update)) =
+            parse_event(ISSUE_UPDATED.as_bytes()).unwrap()
+        else {
+            panic!("expected an issue update");
+        };
+        assert_eq!(update.issue_key, "PROJ-7");
+        assert_eq!(update.components, vec!["Auth".to_string()]);
+        assert_eq!(update.changed_by.as_deref(), Some("Dana QA"));
+        assert_eq!(update.changes.len(), 2);
+        assert_eq!(
+            update.transition,
+            Some(StatusTransition {
+                from: Some("In Progress".to_string()),
+                to: "Ready for QA".to_string(),
+            })
+        );
+        assert_eq!(update.timestamp.timestamp_millis(), 1_717_243_200_000);
+    }
+
+    #[test]
+    fn test_parse_comments_and_ignored_events() {
+        let created = r#"{
+            "webhookEvent": "comment_created",
+            "comment": {"id": "55", "author": {"displayName": "Sam Dev"},
+                        "body": {"type": "doc", "content": [
+                            {"type": "paragraph", "content": [{"type": "text", "text": "Fixed in"}]},
+                            {"type": "paragraph", "content": [{"type": "text", "text": "build 42"}]}
+                        ]}},
+            "issue": {"key": "PROJ-7", "fields": {}}
+        }"#;
+        let Some(JiraWebhookEvent::CommentAdded(comment)) =
+            parse_event(created.as_bytes()).unwrap()
+        else {
+            panic!("expected a comment");
+        };
+        assert_eq!(comment.body, "Fixed in\nbuild 42");
+        assert_eq!(comment.author.as_deref(), Some("Sam Dev"));
+
+        let commented = r#"{
+            "webhookEvent": "jira:issue_updated",
+            "issue_event_type_name": "issue_commented",
+            "issue": {"key": "PROJ-8"},
+            "comment": {"id": "56", "body": "Please retest"}
+        }"#;
+        let event = parse_event(commented.as_bytes()).unwrap().unwrap();
+        assert_eq!(event.issue_key(), "PROJ-8");
+
+        let deleted = r#"{"webhookEvent": "jira:issue_deleted", "issue": {"key": "PROJ-9"}}"#;
+        assert!(parse_event(deleted.as_bytes()).unwrap().is_none());
+        assert!(parse_event(b"[]").is_err());
+    }
+}
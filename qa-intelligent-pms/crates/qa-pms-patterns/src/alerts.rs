@@ -1,7 +1,14 @@
 //! Alert service for generating and managing alerts.
+//!
+//! New alerts are checked against the tenant's mute rules first; a muted
+//! alert is not stored and the `generate_*` methods return `None`.
+
+use chrono::Utc;
+use tracing::debug;
+use uuid::Uuid;
 
 use crate::repository::PatternRepository;
-use crate::types::{DetectedPattern, Alert, NewAlert};
+use crate::types::{DetectedPattern, Alert, MuteRule, NewAlert, NewMuteRule, PatternType, Severity};
 
 /// Alert service for generating alerts from patterns.
 pub struct AlertService {
@@ -14,8 +21,9 @@ impl AlertService {
         Self { repo }
     }
 
-    /// Generate an alert from a detected pattern.
-    pub async fn generate_alert(&self, pattern: &DetectedPattern) -> anyhow::Result<Alert> {
+    /// Generate an alert from a detected pattern, unless its type and
+    /// common factor are muted.
+    pub async fn generate_alert(&self, pattern: &DetectedPattern) -> anyhow::Result<Option<Alert>> {
         let alert = NewAlert {
             pattern_id: Some(pattern.id),
             alert_type: pattern.pattern_type,
@@ -26,7 +34,81 @@ impl AlertService {
             suggested_actions: pattern.suggested_actions.clone(),
         };
 
-        self.repo.create_alert(alert).await
+        self.raise(alert, pattern.common_factor.as_deref()).await
+    }
+
+    /// Generate an informational alert about a change to a watched ticket,
+    /// unless ticket changes are muted for it.
+    pub async fn generate_ticket_alert(
+        &self,
+        ticket_key: &str,
+        title: String,
+        message: String,
+    ) -> anyhow::Result<Option<Alert>> {
+        let alert = NewAlert {
+            pattern_id: None,
+            alert_type: PatternType::TicketChange,
+            severity: Severity::Info,
+            title,
+            message: Some(message),
+            affected_tickets: vec![ticket_key.to_string()],
+            suggested_actions: Vec::new(),
+        };
+
+        self.raise(alert, Some(ticket_key)).await
+    }
+
+    /// Generate a warning about a dashboard KPI missing its target, unless
+    /// KPI breaches are muted.
+    pub async fn generate_kpi_alert(
+        &self,
+        title: String,
+        message: String,
+        suggested_actions: Vec<String>,
+    ) -> anyhow::Result<Option<Alert>> {
+        let alert = NewAlert {
+            pattern_id: None,
+            alert_type: PatternType::KpiBreach,
+            severity: Severity::Warning,
+            title,
+            message: Some(message),
+            affected_tickets: Vec::new(),
+            suggested_actions,
+        };
+
+        self.raise(alert, None).await
+    }
+
+    /// Store an alert unless a mute rule of its tenant covers it.
+    async fn raise(&self, alert: NewAlert, component: Option<&str>) -> anyhow::Result<Option<Alert>> {
+        let tenant = self.repo.alert_tenant(&alert.affected_tickets).await?;
+        let now = Utc::now();
+        let muted = self
+            .repo
+            .get_active_mute_rules(&tenant)
+            .await?
+            .iter()
+            .any(|rule| rule.mutes(alert.alert_type, component, now));
+        if muted {
+            debug!(alert_type = %alert.alert_type, ?component, "Alert muted");
+            return Ok(None);
+        }
+        self.repo.create_alert(alert).await.map(Some)
+    }
+
+    /// Add a mute rule.
+    pub async fn mute(&self, rule: NewMuteRule) -> anyhow::Result<MuteRule> {
+        self.repo.create_mute_rule(rule).await
+    }
+
+    /// Get a tenant's mute rules that have not expired.
+    pub async fn mute_rules(&self, tenant_id: &str) -> anyhow::Result<Vec<MuteRule>> {
+        self.repo.get_active_mute_rules(tenant_id).await
+    }
+
+    /// Remove a tenant's mute rule. Returns whether it existed.
+    pub async fn unmute(&self, tenant_id: &str, rule_id: Uuid) -> anyhow::Result<bool> {
+        self.repo.delete_mute_rule(tenant_id, rule_id).await
     }
 
     /// Get all unread alerts.
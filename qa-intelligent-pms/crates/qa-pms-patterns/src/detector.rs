@@ -1,26 +1,54 @@
 //! Pattern detection logic.
 //!
 //! Analyzes workflow data to detect patterns:
-//! - Time excess (>50% over estimate)
+//! - Time excess (>50% over estimate, or a step far above its usual duration)
 //! - Consecutive problems (3+ tickets with same issue)
 //! - Spikes (sudden increase in tickets)
+//! - Flaky verification (tickets sent back from QA to development repeatedly)
+//! - Requirement gaps (tickets of a component whose notes report unclear specs)
+//!
+//! Detection runs after each workflow completes, and periodically through
+//! [`PatternDetector::sweep`] so problems on tickets whose workflows are still
+//! open are found too. Flaky verification and requirement gaps are only
+//! checked by the sweep. The limits come from the team's
+//! [`PatternThresholds`], loaded for every analysis.
+
+use std::collections::BTreeMap;
 
+use chrono::{DateTime, Duration, Utc};
+use qa_pms_core::KeywordExtractor;
 use sqlx::PgPool;
-use tracing::info;
+use tracing::{debug, info};
 
-use crate::types::{DetectedPattern, WorkflowAnalysisData, Severity, NewPattern, PatternType};
+use crate::types::{
+    DetectedPattern, NewPattern, PatternType, Severity, StatusChange, StepBaseline, StepDuration,
+    WorkflowAnalysisData,
+};
 use crate::repository::PatternRepository;
+use crate::thresholds::{PatternThresholds, ThresholdRepository};
+
+/// Completed runs of a step needed before its baseline is trusted.
+const MIN_BASELINE_SAMPLES: i64 = 5;
+
+/// Standard deviations above the mean at which a step duration is anomalous.
+const STEP_ANOMALY_Z_SCORE: f64 = 3.0;
 
-/// Time excess threshold (50% over estimate).
-const TIME_EXCESS_THRESHOLD: f64 = 0.5;
+/// Factor over the mean a step must also exceed, so steps with very steady
+/// timings are not flagged for small drifts.
+const STEP_ANOMALY_MIN_RATIO: f64 = 2.0;
 
-/// Minimum consecutive tickets for problem detection.
-const CONSECUTIVE_THRESHOLD: usize = 3;
+/// Statuses in which a ticket waits for or is under verification.
+const QA_STATUSES: &[&str] = &["ready for qa", "in qa", "qa", "testing", "in testing"];
+
+/// Statuses that send a ticket back to development.
+const DEV_STATUSES: &[&str] = &["in progress", "reopened"];
 
 /// Pattern detector service.
 pub struct PatternDetector {
     pool: PgPool,
     repo: PatternRepository,
+    thresholds: ThresholdRepository,
+    requirement_gap_phrases: Vec<String>,
 }
 
 impl PatternDetector {
@@ -28,34 +56,49 @@ impl PatternDetector {
     pub fn new(pool: PgPool) -> Self {
         Self {
             repo: PatternRepository::new(pool.clone()),
+            thresholds: ThresholdRepository::new(pool.clone()),
             pool,
+            requirement_gap_phrases: Vec::new(),
         }
     }
 
+    /// Phrases in workflow notes that point at a requirements gap, e.g.
+    /// "missing AC". Without any, sweeps do not look for requirement gaps.
+    #[must_use]
+    pub fn with_requirement_gap_phrases(mut self, phrases: Vec<String>) -> Self {
+        self.requirement_gap_phrases = phrases;
+        self
+    }
+
     /// Run pattern detection after a workflow completion.
     ///
     /// This should be called in the background after each workflow completes.
     pub async fn analyze_workflow(&self, workflow_id: uuid::Uuid) -> anyhow::Result<Vec<DetectedPattern>> {
         let mut detected = Vec::new();
 
-        // Get workflow data
+        // Get workflow data and the thresholds of its ticket's team
         let workflow_data = self.get_workflow_data(workflow_id).await?;
+        let team = workflow_data.ticket_key.split('-').next();
+        let thresholds = self.thresholds.effective(team).await?;
         
         // 1. Check for time excess
-        if let Some(pattern) = self.detect_time_excess(&workflow_data).await? {
+        if let Some(pattern) = self.detect_time_excess(&workflow_data, &thresholds).await? {
             detected.push(pattern);
         }
 
-        // 2. Check for consecutive problems (last 5 tickets)
-        if let Some(pattern) = self.detect_consecutive_problems(&workflow_data).await? {
+        // 2. Check for consecutive problems (most recent tickets)
+        if let Some(pattern) = self.detect_consecutive_problems(&thresholds).await? {
             detected.push(pattern);
         }
 
         // 3. Check for spikes (compared to baseline)
-        if let Some(pattern) = self.detect_spike(&workflow_data).await? {
+        if let Some(pattern) = self.detect_spike(&workflow_data, &thresholds).await? {
             detected.push(pattern);
         }
 
+        // 4. Check each step against its template's step baseline
+        detected.extend(self.detect_step_anomalies(&workflow_data).await?);
+
         info!(
             workflow_id = %workflow_id,
             patterns_detected = detected.len(),
@@ -65,18 +108,151 @@ impl PatternDetector {
         Ok(detected)
     }
 
+    /// Look for cross-ticket patterns in workflow activity since `since`.
+    ///
+    /// Unlike [`Self::analyze_workflow`], this includes workflows that are
+    /// still in progress. A pattern already detected in the same window with
+    /// the same common factor is not reported again. Sweeps span all teams,
+    /// so the default team's thresholds apply.
+    pub async fn sweep(&self, since: DateTime<Utc>) -> anyhow::Result<Vec<DetectedPattern>> {
+        let thresholds = self.thresholds.effective(None).await?;
+        let mut found = Vec::new();
+
+        let recent: Vec<(String, Option<String>)> = sqlx::query_as(
+            r"
+            SELECT ticket_id, all_notes FROM (
+                SELECT DISTINCT ON (wi.ticket_id)
+                    wi.ticket_id,
+                    (SELECT string_agg(notes, ' ') FROM workflow_step_results WHERE instance_id = wi.id) as all_notes,
+                    wi.updated_at
+                FROM workflow_instances wi
+                WHERE wi.updated_at >= $1
+                ORDER BY wi.ticket_id, wi.updated_at DESC
+            ) latest
+            ORDER BY updated_at DESC
+            LIMIT $2
+            ",
+        )
+        .bind(since)
+        .bind(thresholds.consecutive_window)
+        .fetch_all(&self.pool)
+        .await?;
+        if let Some(pattern) = self.consecutive_problem_pattern(&recent, &thresholds) {
+            found.push(pattern);
+        }
+
+        let window = Utc::now() - since;
+        let (current, earlier, tickets): (i64, i64, Vec<String>) = sqlx::query_as(
+            r"
+            SELECT
+                COUNT(*) FILTER (WHERE started_at >= $1),
+                COUNT(*) FILTER (WHERE started_at < $1),
+                COALESCE(array_agg(DISTINCT ticket_id) FILTER (WHERE started_at >= $1), '{}')
+            FROM workflow_instances
+            WHERE started_at >= $2
+            ",
+        )
+        .bind(since)
+        .bind(since - window * thresholds.spike_window)
+        .fetch_one(&self.pool)
+        .await?;
+        let average = earlier as f64 / f64::from(thresholds.spike_window);
+        if let Some(ratio) = thresholds.spike_ratio(current, average) {
+            found.push(NewPattern {
+                pattern_type: PatternType::Spike,
+                severity: thresholds.spike_severity(ratio),
+                title: "Workflow volume spike detected".to_string(),
+                description: Some(format!(
+                    "{current} workflows started in the last {}h, {ratio:.1}x the average of the previous {} periods ({average:.1})",
+                    window.num_hours(),
+                    thresholds.spike_window
+                )),
+                affected_tickets: tickets,
+                common_factor: None,
+                average_excess_percent: Some((ratio - 1.0) * 100.0),
+                confidence_score: 0.8,
+                suggested_actions: vec![
+                    "Check for new deployments or changes".to_string(),
+                    "Review recent tickets for common issues".to_string(),
+                ],
+                metadata: serde_json::json!({
+                    "current_count": current,
+                    "avg_count": average,
+                    "spike_ratio": ratio,
+                    "window_hours": window.num_hours()
+                }),
+            });
+        }
+
+        if !self.requirement_gap_phrases.is_empty() {
+            let notes: Vec<(String, Option<String>, Option<String>)> = sqlx::query_as(
+                r"
+                SELECT
+                    wi.ticket_id,
+                    string_agg(r.notes, ' '),
+                    (SELECT c.component FROM ticket_status_changes c
+                     WHERE c.ticket_key = wi.ticket_id AND c.component IS NOT NULL
+                     ORDER BY c.changed_at DESC
+                     LIMIT 1)
+                FROM workflow_instances wi
+                JOIN workflow_step_results r ON r.instance_id = wi.id
+                WHERE wi.updated_at >= $1 AND r.notes IS NOT NULL
+                GROUP BY wi.ticket_id
+                ",
+            )
+            .bind(since)
+            .fetch_all(&self.pool)
+            .await?;
+            found.extend(requirement_gap_patterns(&notes, &self.requirement_gap_phrases, &thresholds));
+        }
+
+        let mut detected = Vec::new();
+        for pattern in found {
+            let seen = self
+                .repo
+                .has_pattern_since(pattern.pattern_type, pattern.common_factor.as_deref(), since)
+                .await?;
+            if seen {
+                debug!(pattern_type = %pattern.pattern_type, "Pattern already reported in this window");
+                continue;
+            }
+            detected.push(self.repo.create_pattern(pattern).await?);
+        }
+
+        // Round trips span days, so a flaky component is reported once per
+        // flaky window rather than once per sweep
+        let flaky_since = Utc::now() - Duration::days(thresholds.flaky_window_days.into());
+        let changes = self.repo.get_status_changes_since(flaky_since).await?;
+        for pattern in flaky_verification_patterns(&changes, &thresholds) {
+            let seen = self
+                .repo
+                .has_pattern_since(pattern.pattern_type, pattern.common_factor.as_deref(), flaky_since)
+                .await?;
+            if seen {
+                debug!(component = ?pattern.common_factor, "Flaky verification already reported in this window");
+                continue;
+            }
+            detected.push(self.repo.create_pattern(pattern).await?);
+        }
+
+        info!(patterns_detected = detected.len(), since = %since, "Pattern sweep complete");
+        Ok(detected)
+    }
+
     async fn get_workflow_data(&self, workflow_id: uuid::Uuid) -> anyhow::Result<WorkflowAnalysisData> {
-        let row: (uuid::Uuid, String, String, i64, Option<i64>, Option<String>, chrono::DateTime<chrono::Utc>) = 
+        #[allow(clippy::type_complexity)]
+        let row: (uuid::Uuid, String, uuid::Uuid, String, i64, Option<i64>, Option<String>, chrono::DateTime<chrono::Utc>) = 
             sqlx::query_as(
                 r"
                 SELECT 
                     wi.id,
-                    wi.ticket_key,
+                    wi.ticket_id,
+                    wt.id as template_id,
                     wt.name as template_name,
                     EXTRACT(EPOCH FROM (wi.completed_at - wi.started_at))::BIGINT as actual_duration,
                     (SELECT SUM((step->>'estimatedMinutes')::INT * 60) 
                      FROM jsonb_array_elements(wt.steps_json) as step) as estimated_duration,
-                    wi.ticket_key as component,
+                    wi.ticket_id as component,
                     wi.completed_at
                 FROM workflow_instances wi
                 JOIN workflow_templates wt ON wi.template_id = wt.id
@@ -98,20 +274,54 @@ impl PatternDetector {
         .fetch_all(&self.pool)
         .await?;
 
+        // Get how long each completed step took
+        let steps: Vec<(i32, Option<String>, i64)> = sqlx::query_as(
+            r"
+            SELECT
+                r.step_index,
+                wt.steps_json->r.step_index->>'name' as step_name,
+                EXTRACT(EPOCH FROM (r.completed_at - r.started_at))::BIGINT as duration
+            FROM workflow_step_results r
+            JOIN workflow_instances wi ON r.instance_id = wi.id
+            JOIN workflow_templates wt ON wi.template_id = wt.id
+            WHERE r.instance_id = $1
+              AND r.status = 'completed'
+              AND r.started_at IS NOT NULL
+              AND r.completed_at IS NOT NULL
+            ORDER BY r.step_index
+            ",
+        )
+        .bind(workflow_id)
+        .fetch_all(&self.pool)
+        .await?;
+
         Ok(WorkflowAnalysisData {
             workflow_id: row.0,
             ticket_key: row.1,
-            template_name: row.2,
-            actual_duration_seconds: row.3,
-            estimated_duration_seconds: row.4,
+            template_id: row.2,
+            template_name: row.3,
+            actual_duration_seconds: row.4,
+            estimated_duration_seconds: row.5,
             step_notes: notes.into_iter().filter_map(|(n,)| n).collect(),
-            component: row.5,
-            completed_at: row.6,
+            component: row.6,
+            completed_at: row.7,
+            step_durations: steps
+                .into_iter()
+                .map(|(step_index, step_name, duration_seconds)| StepDuration {
+                    step_index,
+                    step_name,
+                    duration_seconds,
+                })
+                .collect(),
         })
     }
 
-    /// Detect time excess pattern (>50% over estimate).
-    async fn detect_time_excess(&self, data: &WorkflowAnalysisData) -> anyhow::Result<Option<DetectedPattern>> {
+    /// Detect time excess pattern (over the threshold, 50% by default).
+    async fn detect_time_excess(
+        &self,
+        data: &WorkflowAnalysisData,
+        thresholds: &PatternThresholds,
+    ) -> anyhow::Result<Option<DetectedPattern>> {
         let Some(estimated) = data.estimated_duration_seconds else {
             return Ok(None);
         };
@@ -122,17 +332,11 @@ impl PatternDetector {
 
         let excess_percent = (data.actual_duration_seconds as f64 - estimated as f64) / estimated as f64;
 
-        if excess_percent <= TIME_EXCESS_THRESHOLD {
+        if excess_percent <= thresholds.time_excess_ratio() {
             return Ok(None);
         }
 
-        let severity = if excess_percent > 1.0 {
-            Severity::Critical
-        } else if excess_percent > 0.75 {
-            Severity::Warning
-        } else {
-            Severity::Info
-        };
+        let severity = thresholds.time_excess_severity(excess_percent);
 
         let pattern = NewPattern {
             pattern_type: PatternType::TimeExcess,
@@ -163,86 +367,74 @@ impl PatternDetector {
         Ok(Some(saved))
     }
 
-    /// Detect consecutive problems (3+ tickets with same issue).
-    async fn detect_consecutive_problems(&self, _data: &WorkflowAnalysisData) -> anyhow::Result<Option<DetectedPattern>> {
-        // Get last 5 completed workflows
+    /// Detect steps that took far longer than usual for their template.
+    ///
+    /// A step can blow up while the whole workflow stays within its estimate,
+    /// e.g. when other steps finish early. Each step is compared to the mean
+    /// and standard deviation of earlier runs of the same template step, then
+    /// added to that baseline.
+    async fn detect_step_anomalies(&self, data: &WorkflowAnalysisData) -> anyhow::Result<Vec<DetectedPattern>> {
+        let mut baselines: std::collections::HashMap<i32, StepBaseline> = self
+            .repo
+            .get_step_baselines(data.template_id)
+            .await?
+            .into_iter()
+            .map(|b| (b.step_index, b))
+            .collect();
+
+        let mut detected = Vec::new();
+        for step in &data.step_durations {
+            let baseline = baselines
+                .entry(step.step_index)
+                .or_insert_with(|| StepBaseline::new(data.template_id, step.step_index));
+
+            if let Some(ratio) = step_anomaly_ratio(baseline, step.duration_seconds) {
+                let pattern = step_anomaly_pattern(data, step, baseline, ratio);
+                detected.push(self.repo.create_pattern(pattern).await?);
+            }
+
+            baseline.add_sample(step.duration_seconds as f64);
+            self.repo.save_step_baseline(baseline).await?;
+        }
+
+        Ok(detected)
+    }
+
+    /// Detect consecutive problems (3+ tickets with same issue by default).
+    async fn detect_consecutive_problems(
+        &self,
+        thresholds: &PatternThresholds,
+    ) -> anyhow::Result<Option<DetectedPattern>> {
+        // Get the most recently completed workflows
         let recent: Vec<(String, Option<String>)> = sqlx::query_as(
             r"
             SELECT 
-                wi.ticket_key,
+                wi.ticket_id,
                 (SELECT string_agg(notes, ' ') FROM workflow_step_results WHERE instance_id = wi.id) as all_notes
             FROM workflow_instances wi
             WHERE wi.status = 'completed'
             ORDER BY wi.completed_at DESC
-            LIMIT 5
+            LIMIT $1
             ",
         )
+        .bind(thresholds.consecutive_window)
         .fetch_all(&self.pool)
         .await?;
 
-        if recent.len() < CONSECUTIVE_THRESHOLD {
-            return Ok(None);
-        }
-
-        // Extract keywords from notes and find common factors
-        let keywords = self.extract_common_keywords(&recent);
-        
-        if keywords.is_empty() {
-            return Ok(None);
-        }
-
-        // Find most common keyword
-        let (common_keyword, count) = keywords
-            .iter()
-            .max_by_key(|(_, c)| *c)
-            .map(|(k, c)| (k.clone(), *c))
-            .unwrap_or_default();
-
-        if count < CONSECUTIVE_THRESHOLD {
+        let Some(pattern) = self.consecutive_problem_pattern(&recent, thresholds) else {
             return Ok(None);
-        }
-
-        let affected: Vec<String> = recent.iter().map(|(k, _)| k.clone()).collect();
-        let confidence = count as f64 / recent.len() as f64;
-
-        let severity = if count >= 5 {
-            Severity::Critical
-        } else if count >= 4 {
-            Severity::Warning
-        } else {
-            Severity::Info
         };
-
-        let pattern = NewPattern {
-            pattern_type: PatternType::ConsecutiveProblem,
-            severity,
-            title: format!("Recurring issue: {common_keyword}"),
-            description: Some(format!(
-                "{} of the last {} tickets mention '{}'",
-                count, recent.len(), common_keyword
-            )),
-            affected_tickets: affected,
-            common_factor: Some(common_keyword),
-            average_excess_percent: None,
-            confidence_score: confidence,
-            suggested_actions: vec![
-                "Investigate root cause of recurring issue".to_string(),
-                "Consider creating a dedicated workflow for this issue type".to_string(),
-                "Review affected component for systemic problems".to_string(),
-            ],
-            metadata: serde_json::json!({
-                "keyword_count": count,
-                "total_analyzed": recent.len()
-            }),
-        };
-
         let saved = self.repo.create_pattern(pattern).await?;
         Ok(Some(saved))
     }
 
     /// Detect spike in tickets for an area.
-    async fn detect_spike(&self, data: &WorkflowAnalysisData) -> anyhow::Result<Option<DetectedPattern>> {
-        // Compare today's count to 7-day average
+    async fn detect_spike(
+        &self,
+        data: &WorkflowAnalysisData,
+        thresholds: &PatternThresholds,
+    ) -> anyhow::Result<Option<DetectedPattern>> {
+        // Compare today's count to the average of the previous days
         let stats: Option<(i64, f64)> = sqlx::query_as(
             r"
             WITH today_count AS (
@@ -256,7 +448,7 @@ impl PatternDetector {
                 FROM (
                     SELECT DATE(completed_at) as day, COUNT(*) as daily_count
                     FROM workflow_instances
-                    WHERE completed_at >= CURRENT_DATE - INTERVAL '7 days'
+                    WHERE completed_at >= CURRENT_DATE - make_interval(days => $1)
                       AND completed_at < CURRENT_DATE
                       AND status = 'completed'
                     GROUP BY DATE(completed_at)
@@ -266,6 +458,7 @@ impl PatternDetector {
             FROM today_count, avg_count
             ",
         )
+        .bind(thresholds.spike_window)
         .fetch_optional(&self.pool)
         .await?;
 
@@ -273,27 +466,18 @@ impl PatternDetector {
             return Ok(None);
         };
 
-        // Spike if today > 2x average
-        if avg_count <= 0.0 || (today_count as f64) <= avg_count * 2.0 {
+        let Some(spike_ratio) = thresholds.spike_ratio(today_count, avg_count) else {
             return Ok(None);
-        }
-
-        let spike_ratio = today_count as f64 / avg_count;
-
-        let severity = if spike_ratio > 3.0 {
-            Severity::Critical
-        } else if spike_ratio > 2.5 {
-            Severity::Warning
-        } else {
-            Severity::Info
         };
+        let severity = thresholds.spike_severity(spike_ratio);
 
         let pattern = NewPattern {
             pattern_type: PatternType::Spike,
             severity,
             title: "Ticket volume spike detected".to_string(),
             description: Some(format!(
-                "Today's ticket count ({today_count}) is {spike_ratio:.1}x the 7-day average ({avg_count:.1})"
+                "Today's ticket count ({today_count}) is {spike_ratio:.1}x the {}-day average ({avg_count:.1})",
+                thresholds.spike_window
             )),
             affected_tickets: vec![data.ticket_key.clone()],
             common_factor: None,
@@ -315,6 +499,64 @@ impl PatternDetector {
         Ok(Some(saved))
     }
 
+    /// Pattern for a keyword shared by enough of the given tickets' notes.
+    fn consecutive_problem_pattern(
+        &self,
+        recent: &[(String, Option<String>)],
+        thresholds: &PatternThresholds,
+    ) -> Option<NewPattern> {
+        let threshold = usize::try_from(thresholds.consecutive_count).unwrap_or(usize::MAX);
+        if recent.len() < threshold {
+            return None;
+        }
+
+        // Extract keywords from notes and find common factors
+        let keywords = self.extract_common_keywords(recent);
+        
+        if keywords.is_empty() {
+            return None;
+        }
+
+        // Find most common keyword
+        let (common_keyword, count) = keywords
+            .iter()
+            .max_by_key(|(_, c)| *c)
+            .map(|(k, c)| (k.clone(), *c))
+            .unwrap_or_default();
+
+        if count < threshold {
+            return None;
+        }
+
+        let affected: Vec<String> = recent.iter().map(|(k, _)| k.clone()).collect();
+        let confidence = count as f64 / recent.len() as f64;
+
+        let severity = thresholds.consecutive_severity(count);
+
+        Some(NewPattern {
+            pattern_type: PatternType::ConsecutiveProblem,
+            severity,
+            title: format!("Recurring issue: {common_keyword}"),
+            description: Some(format!(
+                "{} of the last {} tickets mention '{}'",
+                count, recent.len(), common_keyword
+            )),
+            affected_tickets: affected,
+            common_factor: Some(common_keyword),
+            average_excess_percent: None,
+            confidence_score: confidence,
+            suggested_actions: vec![
+                "Investigate root cause of recurring issue".to_string(),
+                "Consider creating a dedicated workflow for this issue type".to_string(),
+                "Review affected component for systemic problems".to_string(),
+            ],
+            metadata: serde_json::json!({
+                "keyword_count": count,
+                "total_analyzed": recent.len()
+            }),
+        })
+    }
+
     /// Extract common keywords from notes.
     fn extract_common_keywords(&self, data: &[(String, Option<String>)]) -> Vec<(String, usize)> {
         use std::collections::HashMap;
@@ -341,12 +583,210 @@ impl PatternDetector {
         }
 
         let mut sorted: Vec<_> = keyword_counts.into_iter().collect();
-        sorted.sort_by(|a, b| b.1.cmp(&a.1));
+        sorted.sort_by_key(|s| std::cmp::Reverse(s.1));
         sorted.truncate(10);
         sorted
     }
 }
 
+/// How many times its mean a step took, when that is anomalous for the baseline.
+fn step_anomaly_ratio(baseline: &StepBaseline, seconds: i64) -> Option<f64> {
+    if baseline.sample_count < MIN_BASELINE_SAMPLES || baseline.mean_seconds <= 0.0 {
+        return None;
+    }
+    let seconds = seconds as f64;
+    let ratio = seconds / baseline.mean_seconds;
+    let threshold = baseline.mean_seconds + STEP_ANOMALY_Z_SCORE * baseline.stddev_seconds();
+    (seconds > threshold && ratio >= STEP_ANOMALY_MIN_RATIO).then_some(ratio)
+}
+
+fn step_anomaly_pattern(
+    data: &WorkflowAnalysisData,
+    step: &StepDuration,
+    baseline: &StepBaseline,
+    ratio: f64,
+) -> NewPattern {
+    let step_name = step
+        .step_name
+        .clone()
+        .unwrap_or_else(|| format!("Step {}", step.step_index + 1));
+    let severity = if ratio >= 5.0 {
+        Severity::Critical
+    } else if ratio >= 3.0 {
+        Severity::Warning
+    } else {
+        Severity::Info
+    };
+    let mean_seconds = baseline.mean_seconds.round() as i64;
+
+    NewPattern {
+        pattern_type: PatternType::TimeExcess,
+        severity,
+        title: format!("Slow step '{step_name}' on {}", data.ticket_key),
+        description: Some(format!(
+            "Step took {ratio:.1}x its usual time ({} actual vs {} average over {} runs)",
+            format_duration(step.duration_seconds),
+            format_duration(mean_seconds),
+            baseline.sample_count
+        )),
+        affected_tickets: vec![data.ticket_key.clone()],
+        common_factor: Some(step_name.clone()),
+        average_excess_percent: Some((ratio - 1.0) * 100.0),
+        confidence_score: (baseline.sample_count as f64 / 20.0).min(1.0),
+        suggested_actions: vec![
+            format!("Check what blocked '{step_name}' on this ticket"),
+            "Review the step estimate if this becomes common".to_string(),
+        ],
+        metadata: serde_json::json!({
+            "template": data.template_name,
+            "step_index": step.step_index,
+            "step_name": step_name,
+            "actual_seconds": step.duration_seconds,
+            "baseline_mean_seconds": baseline.mean_seconds,
+            "baseline_stddev_seconds": baseline.stddev_seconds(),
+            "baseline_samples": baseline.sample_count
+        }),
+    }
+}
+
+/// Times a ticket went from QA back to development in its status changes,
+/// which must be in the order they happened.
+fn verification_round_trips(changes: &[&StatusChange]) -> usize {
+    let is = |statuses: &[&str], status: &str| statuses.iter().any(|s| s.eq_ignore_ascii_case(status.trim()));
+    let mut previous: Option<&str> = None;
+    let mut round_trips = 0;
+    for change in changes {
+        let from = change.from_status.as_deref().or(previous);
+        if from.is_some_and(|from| is(QA_STATUSES, from)) && is(DEV_STATUSES, &change.to_status) {
+            round_trips += 1;
+        }
+        previous = Some(&change.to_status);
+    }
+    round_trips
+}
+
+/// One pattern per component with tickets that went back from QA to
+/// development at least the threshold number of times.
+///
+/// A ticket's component is the latest one recorded with its changes, or its
+/// Jira project key when none was.
+fn flaky_verification_patterns(changes: &[StatusChange], thresholds: &PatternThresholds) -> Vec<NewPattern> {
+    let mut tickets: BTreeMap<&str, Vec<&StatusChange>> = BTreeMap::new();
+    for change in changes {
+        tickets.entry(change.ticket_key.as_str()).or_default().push(change);
+    }
+
+    let threshold = usize::try_from(thresholds.flaky_round_trips).unwrap_or(usize::MAX).max(1);
+    let mut components: BTreeMap<String, Vec<(&str, usize)>> = BTreeMap::new();
+    for (ticket_key, mut ticket_changes) in tickets {
+        ticket_changes.sort_by_key(|change| change.changed_at);
+        let round_trips = verification_round_trips(&ticket_changes);
+        if round_trips < threshold {
+            continue;
+        }
+        let component = ticket_changes
+            .iter()
+            .rev()
+            .find_map(|change| change.component.clone())
+            .unwrap_or_else(|| ticket_key.split('-').next().unwrap_or(ticket_key).to_string());
+        components.entry(component).or_default().push((ticket_key, round_trips));
+    }
+
+    components
+        .into_iter()
+        .map(|(component, flaky)| {
+            let worst = flaky.iter().map(|(_, trips)| *trips).max().unwrap_or(threshold);
+            let total: usize = flaky.iter().map(|(_, trips)| trips).sum();
+            NewPattern {
+                pattern_type: PatternType::FlakyVerification,
+                severity: thresholds.flaky_severity(worst),
+                title: format!("Flaky fixes in {component}"),
+                description: Some(format!(
+                    "{} ticket(s) went back from QA to development {total} times in the last {} days (up to {worst} times each)",
+                    flaky.len(),
+                    thresholds.flaky_window_days
+                )),
+                affected_tickets: flaky.iter().map(|(key, _)| (*key).to_string()).collect(),
+                common_factor: Some(component.clone()),
+                average_excess_percent: None,
+                confidence_score: (worst as f64 / (2 * threshold) as f64).min(1.0),
+                suggested_actions: vec![
+                    format!("Review how fixes in {component} are verified before handoff"),
+                    "Check that acceptance criteria are clear before development".to_string(),
+                    "Add regression tests for the reopened scenarios".to_string(),
+                ],
+                metadata: serde_json::json!({
+                    "component": component,
+                    "round_trips": flaky
+                        .iter()
+                        .map(|(key, trips)| ((*key).to_string(), *trips))
+                        .collect::<BTreeMap<_, _>>(),
+                    "window_days": thresholds.flaky_window_days
+                }),
+            }
+        })
+        .collect()
+}
+
+/// One pattern per component with at least the consecutive problem count
+/// of tickets whose notes contain a requirement gap phrase.
+///
+/// Each entry is a ticket key, its notes and its component, if known; the
+/// Jira project key stands in for a missing component.
+fn requirement_gap_patterns(
+    notes: &[(String, Option<String>, Option<String>)],
+    phrases: &[String],
+    thresholds: &PatternThresholds,
+) -> Vec<NewPattern> {
+    let extractor = KeywordExtractor::default();
+    let mut components: BTreeMap<String, Vec<(&str, Vec<&str>)>> = BTreeMap::new();
+    for (ticket_key, text, component) in notes {
+        let found = extractor.find_phrases(text.as_deref().unwrap_or_default(), phrases);
+        if found.is_empty() {
+            continue;
+        }
+        let component = component
+            .clone()
+            .unwrap_or_else(|| ticket_key.split('-').next().unwrap_or(ticket_key).to_string());
+        components.entry(component).or_default().push((ticket_key, found));
+    }
+
+    let threshold = usize::try_from(thresholds.consecutive_count).unwrap_or(usize::MAX);
+    components
+        .into_iter()
+        .filter(|(_, tickets)| tickets.len() >= threshold)
+        .map(|(component, tickets)| {
+            let mut mentions: BTreeMap<&str, usize> = BTreeMap::new();
+            for phrase in tickets.iter().flat_map(|(_, found)| found) {
+                *mentions.entry(phrase).or_default() += 1;
+            }
+            NewPattern {
+                pattern_type: PatternType::RequirementGap,
+                severity: thresholds.consecutive_severity(tickets.len()),
+                title: format!("Requirements quality: unclear specs in {component}"),
+                description: Some(format!(
+                    "{} tickets in {component} have workflow notes about missing or unclear requirements",
+                    tickets.len()
+                )),
+                affected_tickets: tickets.iter().map(|(key, _)| (*key).to_string()).collect(),
+                common_factor: Some(component.clone()),
+                average_excess_percent: None,
+                confidence_score: (tickets.len() as f64 / (threshold.max(1) * 2) as f64).min(1.0),
+                suggested_actions: vec![
+                    format!("Review how stories for {component} are refined before development"),
+                    "Agree on acceptance criteria before tickets are ready for work".to_string(),
+                    "Bring the affected tickets to the next refinement session".to_string(),
+                ],
+                metadata: serde_json::json!({
+                    "component": component,
+                    "phrases": mentions,
+                    "ticket_count": tickets.len()
+                }),
+            }
+        })
+        .collect()
+}
+
 fn format_duration(seconds: i64) -> String {
     if seconds < 60 {
         format!("{seconds}s")
@@ -362,3 +802,135 @@ fn format_duration(seconds: i64) -> String {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn baseline(samples: &[f64]) -> StepBaseline {
+        let mut baseline = StepBaseline::new(uuid::Uuid::new_v4(), 2);
+        for &seconds in samples {
+            baseline.add_sample(seconds);
+        }
+        baseline
+    }
+
+    #[test]
+    fn test_baseline_tracks_mean_and_stddev() {
+        let baseline = baseline(&[2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0]);
+        assert!((baseline.mean_seconds - 5.0).abs() < 1e-9);
+        assert!((baseline.stddev_seconds() - 2.138_089_935).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_step_five_times_slower_is_anomalous() {
+        let baseline = baseline(&[600.0, 540.0, 660.0, 620.0, 580.0]);
+        let ratio = step_anomaly_ratio(&baseline, 3000).unwrap();
+        assert!((ratio - 5.0).abs() < 0.01);
+        assert!(step_anomaly_ratio(&baseline, 700).is_none());
+
+        // Too few runs to judge
+        let young = super::tests::baseline(&[600.0, 620.0]);
+        assert!(step_anomaly_ratio(&young, 3000).is_none());
+    }
+
+    #[test]
+    fn test_spike_needs_more_than_double_the_average() {
+        let thresholds = PatternThresholds::default();
+        assert!(thresholds.spike_ratio(4, 2.0).is_none());
+        assert!(thresholds.spike_ratio(3, 0.0).is_none());
+        let ratio = thresholds.spike_ratio(7, 2.0).unwrap();
+        assert!((ratio - 3.5).abs() < 1e-9);
+        assert_eq!(thresholds.spike_severity(ratio), Severity::Critical);
+        assert_eq!(thresholds.spike_severity(2.2), Severity::Info);
+    }
+
+    fn change(ticket_key: &str, from: Option<&str>, to: &str, minutes: i64, component: Option<&str>) -> StatusChange {
+        StatusChange {
+            ticket_key: ticket_key.to_string(),
+            from_status: from.map(str::to_string),
+            to_status: to.to_string(),
+            component: component.map(str::to_string),
+            changed_at: Utc::now() - Duration::days(1) + Duration::minutes(minutes),
+        }
+    }
+
+    #[test]
+    fn test_round_trips_count_returns_from_qa_to_development() {
+        let changes = [
+            change("PAY-1", Some("In Progress"), "Ready for QA", 0, None),
+            change("PAY-1", Some("Ready for QA"), "Reopened", 1, None),
+            // The previous status is taken from the last change when missing
+            change("PAY-1", None, "Ready for QA", 2, None),
+            change("PAY-1", None, "In Progress", 3, None),
+            change("PAY-1", Some("In Progress"), "Ready for QA", 4, None),
+            change("PAY-1", Some("Ready for QA"), "Done", 5, None),
+        ];
+        assert_eq!(verification_round_trips(&changes.iter().collect::<Vec<_>>()), 2);
+
+        let straight = [
+            change("PAY-2", Some("To Do"), "In Progress", 0, None),
+            change("PAY-2", Some("In Progress"), "Ready for QA", 1, None),
+            change("PAY-2", Some("Ready for QA"), "Done", 2, None),
+        ];
+        assert_eq!(verification_round_trips(&straight.iter().collect::<Vec<_>>()), 0);
+    }
+
+    #[test]
+    fn test_flaky_tickets_are_grouped_by_component() {
+        let mut changes = Vec::new();
+        let tickets = [
+            ("PAY-1", 2, Some("Checkout")),
+            ("PAY-2", 4, Some("Checkout")),
+            ("PAY-3", 1, None),
+            ("WEB-9", 2, None),
+        ];
+        for (key, trips, component) in tickets {
+            for trip in 0..trips {
+                changes.push(change(key, Some("In Progress"), "Ready for QA", trip * 2, component));
+                changes.push(change(key, Some("Ready for QA"), "Reopened", trip * 2 + 1, component));
+            }
+        }
+
+        let patterns = flaky_verification_patterns(&changes, &PatternThresholds::default());
+        assert_eq!(patterns.len(), 2);
+        let checkout = &patterns[0];
+        assert_eq!(checkout.common_factor.as_deref(), Some("Checkout"));
+        assert_eq!(checkout.affected_tickets, vec!["PAY-1", "PAY-2"]);
+        assert_eq!(checkout.severity, Severity::Critical);
+        let web = &patterns[1];
+        assert_eq!(web.common_factor.as_deref(), Some("WEB"));
+        assert_eq!(web.severity, Severity::Warning);
+    }
+
+    #[test]
+    fn test_requirement_gaps_cluster_by_component() {
+        let phrases = vec!["missing AC".to_string(), "unclear requirement".to_string()];
+        let note = |key: &str, text: &str, component: Option<&str>| {
+            (key.to_string(), Some(text.to_string()), component.map(str::to_string))
+        };
+        let notes = [
+            note("PAY-1", "Blocked on missing ACs", Some("Checkout")),
+            note("PAY-2", "Unclear requirements for refunds", Some("Checkout")),
+            note("PAY-3", "Dev asked about missing AC again", Some("Checkout")),
+            note("PAY-4", "All good", Some("Checkout")),
+            note("WEB-1", "missing AC", None),
+            note("WEB-2", "unclear requirement", None),
+        ];
+
+        let patterns = requirement_gap_patterns(&notes, &phrases, &PatternThresholds::default());
+        assert_eq!(patterns.len(), 1);
+        let checkout = &patterns[0];
+        assert_eq!(checkout.pattern_type, PatternType::RequirementGap);
+        assert_eq!(checkout.common_factor.as_deref(), Some("Checkout"));
+        assert_eq!(checkout.affected_tickets, vec!["PAY-1", "PAY-2", "PAY-3"]);
+        assert_eq!(checkout.metadata["phrases"]["missing AC"], 2);
+
+        let lenient = PatternThresholds {
+            consecutive_count: 2,
+            ..PatternThresholds::default()
+        };
+        let patterns = requirement_gap_patterns(&notes, &phrases, &lenient);
+        assert_eq!(patterns[1].common_factor.as_deref(), Some("WEB"));
+    }
+}
@@ -6,13 +6,22 @@
 //! - Time Excess: Steps/tickets taking >50% longer than estimated
 //! - Consecutive Problem: 3+ tickets with same component/issue
 //! - Spike: Sudden increase in tickets for an area
+//! - Flaky Verification: Tickets bouncing between QA and development, by component
+//! - Requirement Gap: Tickets of a component whose notes report unclear requirements
+//!
+//! The thresholds above are defaults; teams can tune them (see [`thresholds`]).
+//!
+//! Alerts are also raised for watched tickets that change status or get new
+//! comments.
 
 pub mod types;
 pub mod detector;
 pub mod repository;
 pub mod alerts;
+pub mod thresholds;
 
 pub use types::*;
 pub use detector::PatternDetector;
 pub use repository::PatternRepository;
 pub use alerts::AlertService;
+pub use thresholds::{PatternThresholds, TeamThresholds, ThresholdRepository};
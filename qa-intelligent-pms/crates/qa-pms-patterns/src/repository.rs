@@ -1,10 +1,32 @@
 //! Pattern repository for database operations.
 
 use chrono::{DateTime, Utc};
+use qa_pms_core::types::{PageRequest, SortDirection};
+use sqlx::postgres::{PgArguments, Postgres};
+use sqlx::query::QueryAs;
 use sqlx::PgPool;
 use uuid::Uuid;
 
-use crate::types::{NewPattern, DetectedPattern, PatternType, NewAlert, Alert, Severity};
+use crate::types::{
+    Alert, AlertFilter, AlertSort, DetectedPattern, MuteRule, NewAlert, NewMuteRule, NewPattern, PatternType,
+    Severity, StatusChange, StepBaseline,
+};
+
+/// Tenant of an alert: that of the latest workflow on its tickets, which are
+/// bound as `$param`.
+fn alert_tenant_sql(param: usize) -> String {
+    format!(
+        "COALESCE(
+            (SELECT tenant_id FROM workflow_instances
+             WHERE ticket_id = ANY(${param})
+             ORDER BY created_at DESC LIMIT 1),
+            'default'
+        )"
+    )
+}
+
+/// Rank of an alert's severity, higher is more severe.
+const SEVERITY_RANK: &str = "CASE severity WHEN 'critical' THEN 3 WHEN 'warning' THEN 2 ELSE 1 END";
 
 /// Repository for pattern and alert data.
 pub struct PatternRepository {
@@ -110,6 +132,26 @@ impl PatternRepository {
         Ok(rows.into_iter().map(Into::into).collect())
     }
 
+    /// Get patterns detected since a point in time, newest first.
+    pub async fn get_patterns_since(&self, since: DateTime<Utc>) -> anyhow::Result<Vec<DetectedPattern>> {
+        let rows: Vec<PatternRow> = sqlx::query_as(
+            r"
+            SELECT 
+                id, pattern_type, severity, title, description,
+                affected_tickets, common_factor, average_excess_percent,
+                confidence_score, suggested_actions, metadata, detected_at, created_at
+            FROM detected_patterns
+            WHERE detected_at >= $1
+            ORDER BY detected_at DESC
+            ",
+        )
+        .bind(since)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
     /// Get pattern by ID.
     pub async fn get_pattern(&self, id: Uuid) -> anyhow::Result<Option<DetectedPattern>> {
         let row: Option<PatternRow> = sqlx::query_as(
@@ -129,19 +171,137 @@ impl PatternRepository {
         Ok(row.map(Into::into))
     }
 
-    /// Create an alert.
+    /// Whether a pattern of this type and common factor was detected since `since`.
+    pub async fn has_pattern_since(
+        &self,
+        pattern_type: PatternType,
+        common_factor: Option<&str>,
+        since: DateTime<Utc>,
+    ) -> anyhow::Result<bool> {
+        let exists: bool = sqlx::query_scalar(
+            r"
+            SELECT EXISTS (
+                SELECT 1 FROM detected_patterns
+                WHERE pattern_type = $1
+                  AND common_factor IS NOT DISTINCT FROM $2
+                  AND detected_at >= $3
+            )
+            ",
+        )
+        .bind(pattern_type.to_string())
+        .bind(common_factor)
+        .bind(since)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(exists)
+    }
+
+    /// Get the step duration baselines of a template.
+    pub async fn get_step_baselines(&self, template_id: Uuid) -> anyhow::Result<Vec<StepBaseline>> {
+        let rows: Vec<StepBaselineRow> = sqlx::query_as(
+            r"
+            SELECT template_id, step_index, sample_count, mean_seconds, m2, updated_at
+            FROM workflow_step_baselines
+            WHERE template_id = $1
+            ORDER BY step_index
+            ",
+        )
+        .bind(template_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    /// Save a step duration baseline, replacing the stored one.
+    pub async fn save_step_baseline(&self, baseline: &StepBaseline) -> anyhow::Result<()> {
+        sqlx::query(
+            r"
+            INSERT INTO workflow_step_baselines (
+                template_id, step_index, sample_count, mean_seconds, m2, updated_at
+            ) VALUES ($1, $2, $3, $4, $5, $6)
+            ON CONFLICT (template_id, step_index) DO UPDATE SET
+                sample_count = EXCLUDED.sample_count,
+                mean_seconds = EXCLUDED.mean_seconds,
+                m2 = EXCLUDED.m2,
+                updated_at = EXCLUDED.updated_at
+            ",
+        )
+        .bind(baseline.template_id)
+        .bind(baseline.step_index)
+        .bind(baseline.sample_count)
+        .bind(baseline.mean_seconds)
+        .bind(baseline.m2)
+        .bind(baseline.updated_at)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Record a ticket status change.
+    ///
+    /// The same transition can be reported by a webhook, the ticket poller
+    /// and the API that made it, so a change to the status the ticket
+    /// already has is skipped. Returns whether the change was recorded.
+    pub async fn record_status_change(&self, change: &StatusChange) -> anyhow::Result<bool> {
+        let result = sqlx::query(
+            r"
+            INSERT INTO ticket_status_changes (
+                id, ticket_key, from_status, to_status, component, changed_at
+            )
+            SELECT $1, $2, $3, $4, $5, $6
+            WHERE (
+                SELECT to_status FROM ticket_status_changes
+                WHERE ticket_key = $2
+                ORDER BY changed_at DESC
+                LIMIT 1
+            ) IS DISTINCT FROM $4
+            ",
+        )
+        .bind(Uuid::new_v4())
+        .bind(&change.ticket_key)
+        .bind(&change.from_status)
+        .bind(&change.to_status)
+        .bind(&change.component)
+        .bind(change.changed_at)
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Get status changes since a point in time, oldest first per ticket.
+    pub async fn get_status_changes_since(&self, since: DateTime<Utc>) -> anyhow::Result<Vec<StatusChange>> {
+        let rows: Vec<StatusChangeRow> = sqlx::query_as(
+            r"
+            SELECT ticket_key, from_status, to_status, component, changed_at
+            FROM ticket_status_changes
+            WHERE changed_at >= $1
+            ORDER BY ticket_key, changed_at
+            ",
+        )
+        .bind(since)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    /// Create an alert, in the tenant of the latest workflow on its tickets.
     pub async fn create_alert(&self, alert: NewAlert) -> anyhow::Result<Alert> {
         let id = Uuid::new_v4();
         let now = Utc::now();
 
-        sqlx::query(
+        let tenant_id: String = sqlx::query_scalar(&format!(
             r"
             INSERT INTO alerts (
                 id, pattern_id, alert_type, severity, title, message,
-                affected_tickets, suggested_actions, created_at
-            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+                affected_tickets, suggested_actions, created_at, tenant_id
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, {tenant})
+            RETURNING tenant_id
             ",
-        )
+            tenant = alert_tenant_sql(7),
+        ))
         .bind(id)
         .bind(alert.pattern_id)
         .bind(alert.alert_type.to_string())
@@ -151,7 +311,7 @@ impl PatternRepository {
         .bind(&alert.affected_tickets)
         .bind(&alert.suggested_actions)
         .bind(now)
-        .execute(&self.pool)
+        .fetch_one(&self.pool)
         .await?;
 
         Ok(Alert {
@@ -168,9 +328,81 @@ impl PatternRepository {
             dismissed_at: None,
             dismissed_by: None,
             created_at: now,
+            tenant_id,
+        })
+    }
+
+    /// Tenant a new alert on these tickets would be created in.
+    pub async fn alert_tenant(&self, affected_tickets: &[String]) -> anyhow::Result<String> {
+        let tenant: String = sqlx::query_scalar(&format!("SELECT {}", alert_tenant_sql(1)))
+            .bind(affected_tickets)
+            .fetch_one(&self.pool)
+            .await?;
+        Ok(tenant)
+    }
+
+    /// Get a tenant's mute rules that have not expired, newest first.
+    pub async fn get_active_mute_rules(&self, tenant_id: &str) -> anyhow::Result<Vec<MuteRule>> {
+        let rows: Vec<MuteRuleRow> = sqlx::query_as(
+            r"
+            SELECT id, tenant_id, alert_type, component, until, reason, created_by, created_at
+            FROM alert_mute_rules
+            WHERE tenant_id = $1 AND (until IS NULL OR until > NOW())
+            ORDER BY created_at DESC
+            ",
+        )
+        .bind(tenant_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    /// Create a mute rule.
+    pub async fn create_mute_rule(&self, rule: NewMuteRule) -> anyhow::Result<MuteRule> {
+        let id = Uuid::new_v4();
+        let now = Utc::now();
+
+        sqlx::query(
+            r"
+            INSERT INTO alert_mute_rules (
+                id, tenant_id, alert_type, component, until, reason, created_by, created_at
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            ",
+        )
+        .bind(id)
+        .bind(&rule.tenant_id)
+        .bind(rule.alert_type.to_string())
+        .bind(&rule.component)
+        .bind(rule.until)
+        .bind(&rule.reason)
+        .bind(&rule.created_by)
+        .bind(now)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(MuteRule {
+            id,
+            tenant_id: rule.tenant_id,
+            alert_type: rule.alert_type,
+            component: rule.component,
+            until: rule.until,
+            reason: rule.reason,
+            created_by: rule.created_by,
+            created_at: now,
         })
     }
 
+    /// Delete a tenant's mute rule. Returns whether it existed.
+    pub async fn delete_mute_rule(&self, tenant_id: &str, id: Uuid) -> anyhow::Result<bool> {
+        let result = sqlx::query("DELETE FROM alert_mute_rules WHERE id = $1 AND tenant_id = $2")
+            .bind(id)
+            .bind(tenant_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
     /// Get unread alerts.
     pub async fn get_unread_alerts(&self) -> anyhow::Result<Vec<Alert>> {
         let rows: Vec<AlertRow> = sqlx::query_as(
@@ -178,7 +410,7 @@ impl PatternRepository {
             SELECT 
                 id, pattern_id, alert_type, severity, title, message,
                 affected_tickets, suggested_actions, is_read, is_dismissed,
-                dismissed_at, dismissed_by, created_at
+                dismissed_at, dismissed_by, created_at, tenant_id
             FROM alerts
             WHERE NOT is_read AND NOT is_dismissed
             ORDER BY 
@@ -196,6 +428,76 @@ impl PatternRepository {
         Ok(rows.into_iter().map(Into::into).collect())
     }
 
+    /// List undismissed alerts matching the filter, one keyset page at a time.
+    ///
+    /// Returns up to `page.fetch_limit()` rows; pass them to `PageRequest::finish`
+    /// keyed by `(created_at, id)`. With [`AlertSort::Severity`] the cursor's
+    /// row is looked up again to continue after its severity.
+    pub async fn list_alerts(
+        &self,
+        filter: &AlertFilter,
+        sort: AlertSort,
+        page: &PageRequest,
+    ) -> anyhow::Result<Vec<Alert>> {
+        let (mut conditions, params) = alert_conditions(filter);
+        let keyset = match sort {
+            AlertSort::CreatedAt => page.keyset_sql("created_at", "id", params + 1),
+            AlertSort::Severity => page.keyset().map(|_| {
+                let operator = match page.direction {
+                    SortDirection::Asc => ">",
+                    SortDirection::Desc => "<",
+                };
+                let (at, id) = (params + 1, params + 2);
+                format!(
+                    "({SEVERITY_RANK}, created_at, id) {operator} \
+                     ((SELECT {SEVERITY_RANK} FROM alerts WHERE id = ${id}), ${at}, ${id})"
+                )
+            }),
+        };
+        conditions.extend(keyset);
+        let order = match sort {
+            AlertSort::CreatedAt => page.order_sql("created_at", "id"),
+            AlertSort::Severity => format!(
+                "{SEVERITY_RANK} {}, {}",
+                page.direction.as_sql(),
+                page.order_sql("created_at", "id")
+            ),
+        };
+        let sql = format!(
+            r"
+            SELECT 
+                id, pattern_id, alert_type, severity, title, message,
+                affected_tickets, suggested_actions, is_read, is_dismissed,
+                dismissed_at, dismissed_by, created_at, tenant_id
+            FROM alerts
+            WHERE {conditions}
+            ORDER BY {order}
+            LIMIT {limit}
+            ",
+            conditions = conditions.join(" AND "),
+            limit = page.fetch_limit(),
+        );
+
+        let mut query = bind_alert_filter(sqlx::query_as::<_, AlertRow>(&sql), filter);
+        if let Some((at, id)) = page.keyset() {
+            query = query.bind(at).bind(id);
+        }
+        let rows = query.fetch_all(&self.pool).await?;
+
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    /// Count undismissed alerts matching the filter.
+    pub async fn count_alerts(&self, filter: &AlertFilter) -> anyhow::Result<i64> {
+        let (conditions, _) = alert_conditions(filter);
+        let sql = format!("SELECT COUNT(*) FROM alerts WHERE {}", conditions.join(" AND "));
+        let (count,) = bind_alert_filter(sqlx::query_as::<_, (i64,)>(&sql), filter)
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(count)
+    }
+
     /// Get unread alert count.
     pub async fn get_unread_count(&self) -> anyhow::Result<i64> {
         let (count,): (i64,) = sqlx::query_as(
@@ -236,6 +538,67 @@ impl PatternRepository {
     }
 }
 
+/// `WHERE` conditions for an alert filter and the number of parameters they use.
+fn alert_conditions(filter: &AlertFilter) -> (Vec<String>, usize) {
+    let mut conditions = vec!["NOT is_dismissed".to_string()];
+    let mut params = 0;
+    let mut next_param = || {
+        params += 1;
+        params
+    };
+
+    if filter.tenant_id.is_some() {
+        conditions.push(format!("tenant_id = ${}", next_param()));
+    }
+    if filter.project_key.is_some() {
+        conditions.push(format!(
+            "EXISTS (SELECT 1 FROM unnest(affected_tickets) AS ticket \
+             WHERE SPLIT_PART(ticket, '-', 1) = ${})",
+            next_param()
+        ));
+    }
+    if filter.severity.is_some() {
+        conditions.push(format!("severity = ${}", next_param()));
+    }
+    if filter.alert_type.is_some() {
+        conditions.push(format!("alert_type = ${}", next_param()));
+    }
+    if filter.from_date.is_some() {
+        conditions.push(format!("created_at >= ${}", next_param()));
+    }
+    if filter.to_date.is_some() {
+        conditions.push(format!("created_at <= ${}", next_param()));
+    }
+    (conditions, params)
+}
+
+/// Bind the parameters of [`alert_conditions`], in the same order.
+fn bind_alert_filter<'q, O>(
+    mut query: QueryAs<'q, Postgres, O, PgArguments>,
+    filter: &'q AlertFilter,
+) -> QueryAs<'q, Postgres, O, PgArguments> {
+    // Unset filters have no placeholder, so only bind the ones in use
+    if let Some(tenant_id) = &filter.tenant_id {
+        query = query.bind(tenant_id);
+    }
+    if let Some(project_key) = &filter.project_key {
+        query = query.bind(project_key);
+    }
+    if let Some(severity) = filter.severity {
+        query = query.bind(severity.to_string());
+    }
+    if let Some(alert_type) = filter.alert_type {
+        query = query.bind(alert_type.to_string());
+    }
+    if let Some(from) = filter.from_date {
+        query = query.bind(from);
+    }
+    if let Some(to) = filter.to_date {
+        query = query.bind(to);
+    }
+    query
+}
+
 // Internal row types for sqlx
 #[derive(sqlx::FromRow)]
 struct PatternRow {
@@ -262,6 +625,10 @@ impl From<PatternRow> for DetectedPattern {
                 "time_excess" => PatternType::TimeExcess,
                 "consecutive_problem" => PatternType::ConsecutiveProblem,
                 "spike" => PatternType::Spike,
+                "ticket_change" => PatternType::TicketChange,
+                "kpi_breach" => PatternType::KpiBreach,
+                "flaky_verification" => PatternType::FlakyVerification,
+                "requirement_gap" => PatternType::RequirementGap,
                 _ => PatternType::TimeExcess,
             },
             severity: match row.severity.as_str() {
@@ -298,6 +665,7 @@ struct AlertRow {
     dismissed_at: Option<DateTime<Utc>>,
     dismissed_by: Option<String>,
     created_at: DateTime<Utc>,
+    tenant_id: String,
 }
 
 impl From<AlertRow> for Alert {
@@ -309,6 +677,10 @@ impl From<AlertRow> for Alert {
                 "time_excess" => PatternType::TimeExcess,
                 "consecutive_problem" => PatternType::ConsecutiveProblem,
                 "spike" => PatternType::Spike,
+                "ticket_change" => PatternType::TicketChange,
+                "kpi_breach" => PatternType::KpiBreach,
+                "flaky_verification" => PatternType::FlakyVerification,
+                "requirement_gap" => PatternType::RequirementGap,
                 _ => PatternType::TimeExcess,
             },
             severity: match row.severity.as_str() {
@@ -325,6 +697,87 @@ impl From<AlertRow> for Alert {
             dismissed_at: row.dismissed_at,
             dismissed_by: row.dismissed_by,
             created_at: row.created_at,
+            tenant_id: row.tenant_id,
+        }
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct MuteRuleRow {
+    id: Uuid,
+    tenant_id: String,
+    alert_type: String,
+    component: Option<String>,
+    until: Option<DateTime<Utc>>,
+    reason: Option<String>,
+    created_by: Option<String>,
+    created_at: DateTime<Utc>,
+}
+
+impl From<MuteRuleRow> for MuteRule {
+    fn from(row: MuteRuleRow) -> Self {
+        Self {
+            id: row.id,
+            tenant_id: row.tenant_id,
+            alert_type: match row.alert_type.as_str() {
+                "time_excess" => PatternType::TimeExcess,
+                "consecutive_problem" => PatternType::ConsecutiveProblem,
+                "spike" => PatternType::Spike,
+                "ticket_change" => PatternType::TicketChange,
+                "kpi_breach" => PatternType::KpiBreach,
+                "flaky_verification" => PatternType::FlakyVerification,
+                "requirement_gap" => PatternType::RequirementGap,
+                _ => PatternType::TimeExcess,
+            },
+            component: row.component,
+            until: row.until,
+            reason: row.reason,
+            created_by: row.created_by,
+            created_at: row.created_at,
+        }
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct StepBaselineRow {
+    template_id: Uuid,
+    step_index: i32,
+    sample_count: i64,
+    mean_seconds: f64,
+    m2: f64,
+    updated_at: DateTime<Utc>,
+}
+
+impl From<StepBaselineRow> for StepBaseline {
+    fn from(row: StepBaselineRow) -> Self {
+        Self {
+            template_id: row.template_id,
+            step_index: row.step_index,
+            sample_count: row.sample_count,
+            mean_seconds: row.mean_seconds,
+            m2: row.m2,
+            updated_at: row.updated_at,
+        }
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct StatusChangeRow {
+    ticket_key: String,
+    from_status: Option<String>,
+    to_status: String,
+    component: Option<String>,
+    changed_at: DateTime<Utc>,
+}
+
+impl From<StatusChangeRow> for StatusChange {
+    fn from(row: StatusChangeRow) -> Self {
+        Self {
+            ticket_key: row.ticket_key,
+            from_status: row.from_status,
+            to_status: row.to_status,
+            component: row.component,
+            changed_at: row.changed_at,
         }
     }
 }
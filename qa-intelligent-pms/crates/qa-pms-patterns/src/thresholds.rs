@@ -0,0 +1,314 @@
+//! Pattern detection thresholds.
+//!
+//! Each team can tune when patterns are reported: how far over its estimate
+//! a workflow may run, how many recent tickets must share an issue, how far
+//! workflow volume must rise above its average, and how often a ticket may
+//! bounce back from QA before its fix counts as flaky. Thresholds are kept
+//! in `pattern_thresholds`, one row per team. A workflow is checked with the
+//! thresholds of the team named after its ticket's Jira project key (`PAY`
+//! for `PAY-123`), falling back to the `default` team and then to
+//! [`PatternThresholds::default`].
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool};
+
+use crate::types::Severity;
+
+/// Team whose thresholds apply when a project has none.
+pub const DEFAULT_TEAM: &str = "default";
+
+/// When the detector reports a pattern.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PatternThresholds {
+    /// Percent over its estimate a workflow must run to be a time excess
+    pub time_excess_percent: f64,
+    /// Recent tickets that must share a keyword to be a consecutive problem,
+    /// and tickets of a component that must report unclear requirements to
+    /// be a requirement gap
+    pub consecutive_count: i32,
+    /// Most recent tickets compared for consecutive problems
+    pub consecutive_window: i32,
+    /// Earlier periods averaged for the spike baseline: days after a
+    /// workflow completes, sweep windows during a sweep
+    pub spike_window: i32,
+    /// Times its average the volume must exceed to be a spike
+    pub spike_multiplier: f64,
+    /// Trips from QA back to development that make a ticket's fix flaky
+    pub flaky_round_trips: i32,
+    /// Days of status history counted for flaky verification
+    pub flaky_window_days: i32,
+}
+
+impl Default for PatternThresholds {
+    fn default() -> Self {
+        Self {
+            time_excess_percent: 50.0,
+            consecutive_count: 3,
+            consecutive_window: 5,
+            spike_window: 7,
+            spike_multiplier: 2.0,
+            flaky_round_trips: 2,
+            flaky_window_days: 14,
+        }
+    }
+}
+
+impl PatternThresholds {
+    /// Time excess threshold as a fraction of the estimate.
+    #[must_use]
+    pub fn time_excess_ratio(&self) -> f64 {
+        self.time_excess_percent / 100.0
+    }
+
+    /// Severity of a workflow that ran `excess` (a fraction of its estimate)
+    /// over, once past the threshold: critical at twice the threshold,
+    /// warning at one and a half times.
+    #[must_use]
+    pub fn time_excess_severity(&self, excess: f64) -> Severity {
+        let threshold = self.time_excess_ratio();
+        if excess > threshold * 2.0 {
+            Severity::Critical
+        } else if excess > threshold * 1.5 {
+            Severity::Warning
+        } else {
+            Severity::Info
+        }
+    }
+
+    /// Severity of a keyword shared by `count` tickets: critical two over the
+    /// threshold, warning one over.
+    #[must_use]
+    pub const fn consecutive_severity(&self, count: usize) -> Severity {
+        let threshold = self.consecutive_count as usize;
+        if count >= threshold + 2 {
+            Severity::Critical
+        } else if count > threshold {
+            Severity::Warning
+        } else {
+            Severity::Info
+        }
+    }
+
+    /// How many times the average `count` is, when that makes it a spike.
+    #[must_use]
+    pub fn spike_ratio(&self, count: i64, average: f64) -> Option<f64> {
+        if average <= 0.0 || (count as f64) <= average * self.spike_multiplier {
+            return None;
+        }
+        Some(count as f64 / average)
+    }
+
+    /// Severity of a spike: critical at one and a half times the multiplier,
+    /// warning at one and a quarter.
+    #[must_use]
+    pub fn spike_severity(&self, ratio: f64) -> Severity {
+        if ratio > self.spike_multiplier * 1.5 {
+            Severity::Critical
+        } else if ratio > self.spike_multiplier * 1.25 {
+            Severity::Warning
+        } else {
+            Severity::Info
+        }
+    }
+
+    /// Severity of a component whose worst ticket went back to development
+    /// `round_trips` times, once past the threshold: critical at twice it.
+    #[must_use]
+    pub const fn flaky_severity(&self, round_trips: usize) -> Severity {
+        if round_trips >= 2 * self.flaky_round_trips as usize {
+            Severity::Critical
+        } else {
+            Severity::Warning
+        }
+    }
+}
+
+/// A team's stored thresholds.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TeamThresholds {
+    pub team: String,
+    #[serde(flatten)]
+    pub thresholds: PatternThresholds,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(FromRow)]
+struct ThresholdsRow {
+    team: String,
+    time_excess_percent: f64,
+    consecutive_count: i32,
+    consecutive_window: i32,
+    spike_window: i32,
+    spike_multiplier: f64,
+    flaky_round_trips: i32,
+    flaky_window_days: i32,
+    updated_at: DateTime<Utc>,
+}
+
+impl From<ThresholdsRow> for TeamThresholds {
+    fn from(row: ThresholdsRow) -> Self {
+        Self {
+            team: row.team,
+            thresholds: PatternThresholds {
+                time_excess_percent: row.time_excess_percent,
+                consecutive_count: row.consecutive_count,
+                consecutive_window: row.consecutive_window,
+                spike_window: row.spike_window,
+                spike_multiplier: row.spike_multiplier,
+                flaky_round_trips: row.flaky_round_trips,
+                flaky_window_days: row.flaky_window_days,
+            },
+            updated_at: row.updated_at,
+        }
+    }
+}
+
+/// Repository for pattern thresholds.
+#[derive(Debug, Clone)]
+pub struct ThresholdRepository {
+    pool: PgPool,
+}
+
+impl ThresholdRepository {
+    /// Create a repository on the given pool.
+    #[must_use]
+    pub const fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// List every team's stored thresholds.
+    ///
+    /// # Errors
+    /// Returns error if the query fails.
+    pub async fn list(&self) -> Result<Vec<TeamThresholds>, sqlx::Error> {
+        let rows = sqlx::query_as::<_, ThresholdsRow>(
+            r"
+            SELECT team, time_excess_percent, consecutive_count, consecutive_window,
+                   spike_window, spike_multiplier, flaky_round_trips, flaky_window_days,
+                   updated_at
+            FROM pattern_thresholds
+            ORDER BY team
+            ",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    /// Thresholds for a team: its own, else the default team's, else the
+    /// built-in defaults.
+    ///
+    /// # Errors
+    /// Returns error if the query fails.
+    pub async fn effective(&self, team: Option<&str>) -> Result<PatternThresholds, sqlx::Error> {
+        let row = sqlx::query_as::<_, ThresholdsRow>(
+            r"
+            SELECT team, time_excess_percent, consecutive_count, consecutive_window,
+                   spike_window, spike_multiplier, flaky_round_trips, flaky_window_days,
+                   updated_at
+            FROM pattern_thresholds
+            WHERE team = $1 OR team = $2
+            ORDER BY team = $2
+            LIMIT 1
+            ",
+        )
+        .bind(team.unwrap_or(DEFAULT_TEAM))
+        .bind(DEFAULT_TEAM)
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(row.map_or_else(PatternThresholds::default, |row| {
+            TeamThresholds::from(row).thresholds
+        }))
+    }
+
+    /// Set a team's thresholds, replacing any stored ones.
+    ///
+    /// # Errors
+    /// Returns error if the query fails.
+    pub async fn upsert(
+        &self,
+        team: &str,
+        thresholds: &PatternThresholds,
+    ) -> Result<TeamThresholds, sqlx::Error> {
+        let row = sqlx::query_as::<_, ThresholdsRow>(
+            r"
+            INSERT INTO pattern_thresholds (
+                team, time_excess_percent, consecutive_count, consecutive_window,
+                spike_window, spike_multiplier, flaky_round_trips, flaky_window_days,
+                updated_at
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, NOW())
+            ON CONFLICT (team) DO UPDATE
+            SET time_excess_percent = EXCLUDED.time_excess_percent,
+                consecutive_count = EXCLUDED.consecutive_count,
+                consecutive_window = EXCLUDED.consecutive_window,
+                spike_window = EXCLUDED.spike_window,
+                spike_multiplier = EXCLUDED.spike_multiplier,
+                flaky_round_trips = EXCLUDED.flaky_round_trips,
+                flaky_window_days = EXCLUDED.flaky_window_days,
+                updated_at = NOW()
+            RETURNING team, time_excess_percent, consecutive_count, consecutive_window,
+                      spike_window, spike_multiplier, flaky_round_trips, flaky_window_days,
+                      updated_at
+            ",
+        )
+        .bind(team)
+        .bind(thresholds.time_excess_percent)
+        .bind(thresholds.consecutive_count)
+        .bind(thresholds.consecutive_window)
+        .bind(thresholds.spike_window)
+        .bind(thresholds.spike_multiplier)
+        .bind(thresholds.flaky_round_trips)
+        .bind(thresholds.flaky_window_days)
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(row.into())
+    }
+
+    /// Delete a team's thresholds, so the defaults apply again. Returns
+    /// whether it had any.
+    ///
+    /// # Errors
+    /// Returns error if the query fails.
+    pub async fn delete(&self, team: &str) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query("DELETE FROM pattern_thresholds WHERE team = $1")
+            .bind(team)
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_defaults_keep_built_in_severities() {
+        let defaults = PatternThresholds::default();
+        assert_eq!(defaults.time_excess_severity(1.01), Severity::Critical);
+        assert_eq!(defaults.time_excess_severity(0.8), Severity::Warning);
+        assert_eq!(defaults.time_excess_severity(0.6), Severity::Info);
+        assert_eq!(defaults.consecutive_severity(5), Severity::Critical);
+        assert_eq!(defaults.consecutive_severity(4), Severity::Warning);
+        assert_eq!(defaults.consecutive_severity(3), Severity::Info);
+        assert_eq!(defaults.spike_severity(3.1), Severity::Critical);
+        assert_eq!(defaults.spike_severity(2.6), Severity::Warning);
+        assert_eq!(defaults.flaky_severity(2), Severity::Warning);
+        assert_eq!(defaults.flaky_severity(4), Severity::Critical);
+    }
+
+    #[test]
+    fn test_spike_multiplier_moves_the_threshold() {
+        let strict = PatternThresholds {
+            spike_multiplier: 4.0,
+            ..PatternThresholds::default()
+        };
+        assert!(strict.spike_ratio(7, 2.0).is_none());
+        let ratio = strict.spike_ratio(9, 2.0).unwrap();
+        assert_eq!(strict.spike_severity(ratio), Severity::Info);
+        assert!(PatternThresholds::default().spike_ratio(7, 2.0).is_some());
+    }
+}
@@ -15,6 +15,14 @@ pub enum PatternType {
     ConsecutiveProblem,
     /// Sudden increase in tickets for an area
     Spike,
+    /// A watched ticket changed status or got new comments
+    TicketChange,
+    /// A dashboard KPI missed its target for several days
+    KpiBreach,
+    /// Tickets sent back from QA to development again and again
+    FlakyVerification,
+    /// Several tickets of a component whose notes report unclear or missing requirements
+    RequirementGap,
 }
 
 impl std::fmt::Display for PatternType {
@@ -23,6 +31,10 @@ impl std::fmt::Display for PatternType {
             Self::TimeExcess => write!(f, "time_excess"),
             Self::ConsecutiveProblem => write!(f, "consecutive_problem"),
             Self::Spike => write!(f, "spike"),
+            Self::TicketChange => write!(f, "ticket_change"),
+            Self::KpiBreach => write!(f, "kpi_breach"),
+            Self::FlakyVerification => write!(f, "flaky_verification"),
+            Self::RequirementGap => write!(f, "requirement_gap"),
         }
     }
 }
@@ -98,6 +110,7 @@ pub struct Alert {
     pub dismissed_at: Option<DateTime<Utc>>,
     pub dismissed_by: Option<String>,
     pub created_at: DateTime<Utc>,
+    pub tenant_id: String,
 }
 
 /// Input for creating a new alert.
@@ -112,6 +125,103 @@ pub struct NewAlert {
     pub suggested_actions: Vec<String>,
 }
 
+/// Order of listed alerts.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum AlertSort {
+    /// By creation time
+    #[default]
+    CreatedAt,
+    /// By severity, then creation time
+    Severity,
+}
+
+/// Filters for listing alerts.
+#[derive(Debug, Clone, Default)]
+pub struct AlertFilter {
+    /// Only alerts of this tenant
+    pub tenant_id: Option<String>,
+    /// Only alerts affecting a ticket of this project key (`PAY` for `PAY-123`)
+    pub project_key: Option<String>,
+    /// Only alerts of this severity
+    pub severity: Option<Severity>,
+    /// Only alerts of this type
+    pub alert_type: Option<PatternType>,
+    /// Only alerts created at or after this time
+    pub from_date: Option<DateTime<Utc>>,
+    /// Only alerts created at or before this time
+    pub to_date: Option<DateTime<Utc>>,
+}
+
+/// A rule that stops new alerts of a type from being raised.
+///
+/// Without `until` the rule mutes alerts until it is deleted; with it the
+/// alerts are snoozed until that time. A `component` limits the rule to
+/// alerts about that component (a pattern's common factor or a watched
+/// ticket key), compared case-insensitively.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MuteRule {
+    pub id: Uuid,
+    pub tenant_id: String,
+    pub alert_type: PatternType,
+    pub component: Option<String>,
+    pub until: Option<DateTime<Utc>>,
+    pub reason: Option<String>,
+    pub created_by: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl MuteRule {
+    /// Whether the rule suppresses an alert of this type and component at `now`.
+    pub fn mutes(&self, alert_type: PatternType, component: Option<&str>, now: DateTime<Utc>) -> bool {
+        if self.alert_type != alert_type || self.until.is_some_and(|until| until <= now) {
+            return false;
+        }
+        match (&self.component, component) {
+            (None, _) => true,
+            (Some(scope), Some(component)) => scope.eq_ignore_ascii_case(component),
+            (Some(_), None) => false,
+        }
+    }
+}
+
+/// Input for creating a mute rule.
+#[derive(Debug, Clone)]
+pub struct NewMuteRule {
+    pub tenant_id: String,
+    pub alert_type: PatternType,
+    pub component: Option<String>,
+    pub until: Option<DateTime<Utc>>,
+    pub reason: Option<String>,
+    pub created_by: Option<String>,
+}
+
+/// A recorded ticket status change.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StatusChange {
+    pub ticket_key: String,
+    pub from_status: Option<String>,
+    pub to_status: String,
+    /// Component the ticket belonged to when it changed, if known
+    pub component: Option<String>,
+    pub changed_at: DateTime<Utc>,
+}
+
+impl StatusChange {
+    /// A change to `to_status` happening now, without a known component.
+    pub fn new(ticket_key: &str, from_status: Option<&str>, to_status: &str) -> Self {
+        Self {
+            ticket_key: ticket_key.to_string(),
+            from_status: from_status.map(str::to_string),
+            to_status: to_status.to_string(),
+            component: None,
+            changed_at: Utc::now(),
+        }
+    }
+}
+
 /// Resolution status for patterns.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -138,12 +248,68 @@ pub struct PatternResolution {
 pub struct WorkflowAnalysisData {
     pub workflow_id: Uuid,
     pub ticket_key: String,
+    pub template_id: Uuid,
     pub template_name: String,
     pub actual_duration_seconds: i64,
     pub estimated_duration_seconds: Option<i64>,
     pub step_notes: Vec<String>,
     pub component: Option<String>,
     pub completed_at: DateTime<Utc>,
+    pub step_durations: Vec<StepDuration>,
+}
+
+/// How long one completed step of a workflow took.
+#[derive(Debug, Clone)]
+pub struct StepDuration {
+    pub step_index: i32,
+    pub step_name: Option<String>,
+    pub duration_seconds: i64,
+}
+
+/// Duration statistics for one step of a template.
+///
+/// Kept as a running mean and sum of squared deviations (Welford), so a
+/// new sample updates the baseline without revisiting earlier workflows.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StepBaseline {
+    pub template_id: Uuid,
+    pub step_index: i32,
+    pub sample_count: i64,
+    pub mean_seconds: f64,
+    /// Sum of squared deviations from the mean
+    pub m2: f64,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl StepBaseline {
+    /// Empty baseline for a template step.
+    pub fn new(template_id: Uuid, step_index: i32) -> Self {
+        Self {
+            template_id,
+            step_index,
+            sample_count: 0,
+            mean_seconds: 0.0,
+            m2: 0.0,
+            updated_at: Utc::now(),
+        }
+    }
+
+    /// Add one observed duration.
+    pub fn add_sample(&mut self, seconds: f64) {
+        self.sample_count += 1;
+        let delta = seconds - self.mean_seconds;
+        self.mean_seconds += delta / self.sample_count as f64;
+        self.m2 += delta * (seconds - self.mean_seconds);
+        self.updated_at = Utc::now();
+    }
+
+    /// Sample standard deviation (0 with fewer than two samples).
+    pub fn stddev_seconds(&self) -> f64 {
+        if self.sample_count < 2 {
+            return 0.0;
+        }
+        (self.m2 / (self.sample_count - 1) as f64).sqrt()
+    }
 }
 
 /// Time excess detection result.
@@ -164,3 +330,43 @@ pub struct ConsecutiveProblemResult {
     pub factor_type: String, // "component", "keyword", "step"
     pub confidence: f64,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(component: Option<&str>, until: Option<DateTime<Utc>>) -> MuteRule {
+        MuteRule {
+            id: Uuid::new_v4(),
+            tenant_id: "default".to_string(),
+            alert_type: PatternType::TimeExcess,
+            component: component.map(str::to_string),
+            until,
+            reason: None,
+            created_by: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_mute_rule_scope() {
+        let now = Utc::now();
+        let all = rule(None, None);
+        assert!(all.mutes(PatternType::TimeExcess, Some("Checkout"), now));
+        assert!(all.mutes(PatternType::TimeExcess, None, now));
+        assert!(!all.mutes(PatternType::Spike, None, now));
+
+        let scoped = rule(Some("checkout"), None);
+        assert!(scoped.mutes(PatternType::TimeExcess, Some("Checkout"), now));
+        assert!(!scoped.mutes(PatternType::TimeExcess, Some("Payments"), now));
+        assert!(!scoped.mutes(PatternType::TimeExcess, None, now));
+    }
+
+    #[test]
+    fn test_snooze_expires() {
+        let now = Utc::now();
+        let snoozed = rule(None, Some(now + chrono::Duration::hours(1)));
+        assert!(snoozed.mutes(PatternType::TimeExcess, None, now));
+        assert!(!snoozed.mutes(PatternType::TimeExcess, None, now + chrono::Duration::hours(2)));
+    }
+}
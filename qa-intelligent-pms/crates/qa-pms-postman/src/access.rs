@@ -0,0 +1,129 @@
+//! Workspace access policy.
+//!
+//! A Postman API key carries its owner's full permissions. When the
+//! framework shares one key, the policy narrows what the client will do with
+//! it: listing stays inside the allowed workspaces, and writes are refused
+//! outright in read-only mode or when they target anything outside them.
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::PostmanError;
+
+/// Which workspaces the client may touch, and whether it may write.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspacePolicy {
+    /// Allowed workspace IDs; empty means every workspace the key can see.
+    pub allowed_workspaces: Vec<String>,
+    /// Refuse every create and update.
+    pub read_only: bool,
+}
+
+impl WorkspacePolicy {
+    /// Policy limited to the given workspaces.
+    #[must_use]
+    pub fn new(allowed_workspaces: Vec<String>, read_only: bool) -> Self {
+        Self {
+            allowed_workspaces,
+            read_only,
+        }
+    }
+
+    /// Whether an allow-list is configured.
+    #[must_use]
+    pub fn is_restricted(&self) -> bool {
+        !self.allowed_workspaces.is_empty()
+    }
+
+    /// Whether the workspace may be used.
+    #[must_use]
+    pub fn allows(&self, workspace_id: &str) -> bool {
+        !self.is_restricted() || self.allowed_workspaces.iter().any(|w| w == workspace_id)
+    }
+
+    /// Check a read scoped to a workspace.
+    ///
+    /// # Errors
+    /// Returns `Forbidden` if the workspace is outside the allow-list.
+    pub fn check_read(&self, workspace_id: Option<&str>) -> Result<(), PostmanError> {
+        match workspace_id {
+            Some(id) if !self.allows(id) => Err(outside_allow_list(id)),
+            _ => Ok(()),
+        }
+    }
+
+    /// Check a write that creates something in a workspace.
+    ///
+    /// With an allow-list the workspace must be named, since Postman would
+    /// otherwise pick the key owner's default workspace.
+    ///
+    /// # Errors
+    /// Returns `Forbidden` in read-only mode, or if the workspace is missing
+    /// or outside the allow-list.
+    pub fn check_create(&self, workspace_id: Option<&str>) -> Result<(), PostmanError> {
+        self.check_writable()?;
+        match workspace_id {
+            None if self.is_restricted() => Err(PostmanError::Forbidden(
+                "A workspace ID is required when workspaces are restricted".to_string(),
+            )),
+            Some(id) if !self.allows(id) => Err(outside_allow_list(id)),
+            _ => Ok(()),
+        }
+    }
+
+    /// Check that writes are allowed at all.
+    ///
+    /// # Errors
+    /// Returns `Forbidden` in read-only mode.
+    pub fn check_writable(&self) -> Result<(), PostmanError> {
+        if self.read_only {
+            Err(PostmanError::Forbidden(
+                "Postman integration is read-only".to_string(),
+            ))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+fn outside_allow_list(workspace_id: &str) -> PostmanError {
+    PostmanError::Forbidden(format!("Workspace {workspace_id} is not in the allow-list"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unrestricted_policy_allows_everything() {
+        let policy = WorkspacePolicy::default();
+        assert!(policy.check_read(Some("any")).is_ok());
+        assert!(policy.check_create(None).is_ok());
+        assert!(policy.check_writable().is_ok());
+    }
+
+    #[test]
+    fn test_allow_list_limits_workspaces() {
+        let policy = WorkspacePolicy::new(vec!["qa".to_string()], false);
+        assert!(policy.check_read(Some("qa")).is_ok());
+        assert!(policy.check_read(None).is_ok());
+        assert!(matches!(
+            policy.check_read(Some("prod")),
+            Err(PostmanError::Forbidden(_))
+        ));
+        assert!(policy.check_create(Some("qa")).is_ok());
+        assert!(policy.check_create(None).is_err());
+        assert!(policy.check_create(Some("prod")).is_err());
+    }
+
+    #[test]
+    fn test_read_only_refuses_writes() {
+        let policy = WorkspacePolicy::new(vec!["qa".to_string()], true);
+        assert!(policy.check_read(Some("qa")).is_ok());
+        assert!(matches!(
+            policy.check_create(Some("qa")),
+            Err(PostmanError::Forbidden(_))
+        ));
+        assert!(policy.check_writable().is_err());
+    }
+}
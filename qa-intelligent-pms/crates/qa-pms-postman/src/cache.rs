@@ -0,0 +1,395 @@
+//! Local collection cache.
+//!
+//! Collection summaries and request metadata are mirrored into Postgres and
+//! refreshed periodically, so keyword search is a local lookup that keeps
+//! working while the Postman API is slow or down.
+
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool};
+use tracing::{debug, info, warn};
+
+use crate::client::{calculate_match_score, PostmanClient};
+use crate::error::PostmanError;
+use crate::types::{Collection, CollectionItem, SearchResult};
+
+/// Default interval between cache refreshes.
+pub const DEFAULT_REFRESH_SECS: u64 = 900;
+
+/// Request metadata kept for each cached collection.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CachedRequest {
+    /// Request name.
+    pub name: String,
+    /// Folder path ("" at the collection root).
+    pub folder: String,
+    /// HTTP method.
+    pub method: Option<String>,
+    /// Request URL.
+    pub url: Option<String>,
+}
+
+/// A cached collection with its requests.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CachedCollection {
+    /// Collection ID.
+    pub collection_id: String,
+    /// Collection UID, used in links.
+    pub uid: String,
+    /// Collection name.
+    pub name: String,
+    /// Requests in the collection.
+    pub requests: Vec<CachedRequest>,
+}
+
+/// Outcome of a cache refresh.
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CacheRefreshReport {
+    /// Collections written to the cache.
+    pub collections: usize,
+    /// Requests across those collections.
+    pub requests: usize,
+    /// Collections dropped because they no longer exist.
+    pub removed: usize,
+    /// Collections whose details could not be fetched; their old entry is kept.
+    pub failed: usize,
+}
+
+/// Cache freshness.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CacheStatus {
+    /// Cached collections.
+    pub collections: i64,
+    /// Oldest entry's refresh time.
+    pub oldest_refresh: Option<DateTime<Utc>>,
+    /// Newest entry's refresh time.
+    pub last_refresh: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, FromRow)]
+struct CachedCollectionRow {
+    collection_id: String,
+    uid: String,
+    name: String,
+    description: Option<String>,
+    requests: sqlx::types::Json<Vec<CachedRequest>>,
+}
+
+/// Flatten a collection's requests with their folder path.
+fn cached_requests(collection: &Collection) -> Vec<CachedRequest> {
+    fn walk(items: &[CollectionItem], folder: &str, out: &mut Vec<CachedRequest>) {
+        for item in items {
+            let name = item.name.clone().unwrap_or_default();
+            if let Some(children) = &item.item {
+                let path = if folder.is_empty() {
+                    name
+                } else {
+                    format!("{folder}/{name}")
+                };
+                walk(children, &path, out);
+            } else {
+                out.push(CachedRequest {
+                    name,
+                    folder: folder.to_string(),
+                    method: item.request.as_ref().and_then(|r| r.method.clone()),
+                    url: item
+                        .request
+                        .as_ref()
+                        .and_then(|r| r.url.as_ref())
+                        .map(crate::types::RequestUrl::as_string),
+                });
+            }
+        }
+    }
+
+    let mut out = Vec::new();
+    if let Some(items) = &collection.item {
+        walk(items, "", &mut out);
+    }
+    out
+}
+
+/// Score a cached collection; `None` when nothing matches.
+///
+/// Same weighting as the live search, except request matches count even when
+/// the collection name does not.
+fn score_cached(row: &CachedCollectionRow, keywords: &[String]) -> Option<SearchResult> {
+    let name_score = calculate_match_score(&row.name, keywords);
+    let matches: Vec<String> = row
+        .requests
+        .iter()
+        .filter(|r| {
+            calculate_match_score(&r.name, keywords) > 0.0
+                || r.url
+                    .as_deref()
+                    .is_some_and(|url| calculate_match_score(url, keywords) > 0.0)
+        })
+        .map(|r| r.name.clone())
+        .collect();
+
+    let score = (matches.len() as f32).mul_add(0.1, name_score);
+    (score > 0.0).then(|| SearchResult {
+        source: "postman".to_string(),
+        id: row.collection_id.clone(),
+        name: row.name.clone(),
+        description: row.description.clone(),
+        url: format!("https://go.postman.co/collection/{}", row.uid),
+        score,
+        matches,
+    })
+}
+
+/// Postgres-backed cache of collections for search.
+#[derive(Debug, Clone)]
+pub struct PostmanCache {
+    pool: PgPool,
+}
+
+impl PostmanCache {
+    /// Create a cache over the given pool.
+    #[must_use]
+    pub const fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Re-read all collections from Postman into the cache.
+    ///
+    /// # Errors
+    /// Returns error if collections cannot be listed or the database fails.
+    /// Per-collection fetch failures keep the previous entry and are counted
+    /// in the report instead.
+    pub async fn refresh(
+        &self,
+        client: &PostmanClient,
+    ) -> Result<CacheRefreshReport, PostmanError> {
+        let summaries = client.list_collections(None).await?;
+        let mut report = CacheRefreshReport::default();
+
+        for summary in &summaries {
+            let collection = match client.get_collection(&summary.id).await {
+                Ok(collection) => collection,
+                Err(e) => {
+                    warn!(collection_id = %summary.id, error = %e, "Failed to cache collection");
+                    report.failed += 1;
+                    continue;
+                }
+            };
+            let requests = cached_requests(&collection);
+            report.requests += requests.len();
+
+            sqlx::query(
+                r"
+                INSERT INTO postman_collection_cache
+                    (collection_id, uid, name, description, requests, postman_updated_at, refreshed_at)
+                VALUES ($1, $2, $3, $4, $5, $6, NOW())
+                ON CONFLICT (collection_id) DO UPDATE SET
+                    uid = EXCLUDED.uid,
+                    name = EXCLUDED.name,
+                    description = EXCLUDED.description,
+                    requests = EXCLUDED.requests,
+                    postman_updated_at = EXCLUDED.postman_updated_at,
+                    refreshed_at = NOW()
+                ",
+            )
+            .bind(&summary.id)
+            .bind(&summary.uid)
+            .bind(&summary.name)
+            .bind(&collection.info.description)
+            .bind(sqlx::types::Json(&requests))
+            .bind(&summary.updated_at)
+            .execute(&self.pool)
+            .await?;
+            report.collections += 1;
+        }
+
+        let ids: Vec<String> = summaries.into_iter().map(|s| s.id).collect();
+        report.removed =
+            sqlx::query("DELETE FROM postman_collection_cache WHERE NOT (collection_id = ANY($1))")
+                .bind(&ids)
+                .execute(&self.pool)
+                .await?
+                .rows_affected()
+                .try_into()
+                .unwrap_or(usize::MAX);
+
+        info!(
+            collections = report.collections,
+            requests = report.requests,
+            removed = report.removed,
+            failed = report.failed,
+            "Postman cache refreshed"
+        );
+        Ok(report)
+    }
+
+    /// Search cached collections by keywords, best match first.
+    ///
+    /// # Errors
+    /// Returns error if the database query fails.
+    pub async fn search(&self, keywords: &[String]) -> Result<Vec<SearchResult>, PostmanError> {
+        let rows: Vec<CachedCollectionRow> = sqlx::query_as(
+            r"
+            SELECT collection_id, uid, name, description, requests
+            FROM postman_collection_cache
+            ",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut results: Vec<SearchResult> = rows
+            .iter()
+            .filter_map(|row| score_cached(row, keywords))
+            .collect();
+        results.sort_by(|a, b| {
+            b.score
+                .partial_cmp(&a.score)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        debug!(
+            results_count = results.len(),
+            "Cached Postman search completed"
+        );
+        Ok(results)
+    }
+
+    /// Every cached collection with its requests.
+    ///
+    /// # Errors
+    /// Returns error if the database query fails.
+    pub async fn collections(&self) -> Result<Vec<CachedCollection>, PostmanError> {
+        let rows: Vec<CachedCollectionRow> = sqlx::query_as(
+            r"
+            SELECT collection_id, uid, name, description, requests
+            FROM postman_collection_cache
+            ORDER BY name
+            ",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| CachedCollection {
+                collection_id: row.collection_id,
+                uid: row.uid,
+                name: row.name,
+                requests: row.requests.0,
+            })
+            .collect())
+    }
+
+    /// Size and age of the cache.
+    ///
+    /// # Errors
+    /// Returns error if the database query fails.
+    pub async fn status(&self) -> Result<CacheStatus, PostmanError> {
+        let (collections, oldest_refresh, last_refresh): (
+            i64,
+            Option<DateTime<Utc>>,
+            Option<DateTime<Utc>>,
+        ) = sqlx::query_as(
+            "SELECT COUNT(*), MIN(refreshed_at), MAX(refreshed_at) FROM postman_collection_cache",
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(CacheStatus {
+            collections,
+            oldest_refresh,
+            last_refresh,
+        })
+    }
+
+    /// Refresh the cache now and then at a fixed interval, in the background.
+    pub fn start_refresh(self, client: PostmanClient, interval: Duration) {
+        tokio::spawn(async move {
+            info!(
+                interval_secs = interval.as_secs(),
+                "Postman cache refresh started"
+            );
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                if let Err(e) = self.refresh(&client).await {
+                    warn!(error = %e, "Postman cache refresh failed; serving stale entries");
+                }
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{CollectionInfo, RequestInfo, RequestUrl};
+
+    fn item(
+        name: &str,
+        url: Option<&str>,
+        children: Option<Vec<CollectionItem>>,
+    ) -> CollectionItem {
+        CollectionItem {
+            id: None,
+            name: Some(name.to_string()),
+            description: None,
+            request: url.map(|u| RequestInfo {
+                method: Some("GET".to_string()),
+                url: Some(RequestUrl::Simple(u.to_string())),
+                description: None,
+            }),
+            item: children,
+        }
+    }
+
+    #[test]
+    fn test_cached_requests_keep_folder_paths() {
+        let collection = Collection {
+            info: CollectionInfo {
+                postman_id: None,
+                name: "Shop".to_string(),
+                description: None,
+                schema: None,
+            },
+            item: Some(vec![
+                item("Health", Some("/health"), None),
+                item(
+                    "Orders",
+                    None,
+                    Some(vec![item("List orders", Some("/orders"), None)]),
+                ),
+            ]),
+        };
+
+        let requests = cached_requests(&collection);
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[1].folder, "Orders");
+        assert_eq!(requests[1].url.as_deref(), Some("/orders"));
+    }
+
+    #[test]
+    fn test_score_cached_matches_requests_without_name_match() {
+        let row = CachedCollectionRow {
+            collection_id: "c1".to_string(),
+            uid: "u-c1".to_string(),
+            name: "Shop API".to_string(),
+            description: None,
+            requests: sqlx::types::Json(vec![CachedRequest {
+                name: "Refund".to_string(),
+                folder: String::new(),
+                method: Some("POST".to_string()),
+                url: Some("{{baseUrl}}/payments/refund".to_string()),
+            }]),
+        };
+
+        let result = score_cached(&row, &["payments".to_string()]).unwrap();
+        assert_eq!(result.matches, vec!["Refund".to_string()]);
+        assert!(result.url.ends_with("u-c1"));
+        assert!(score_cached(&row, &["inventory".to_string()]).is_none());
+    }
+}
@@ -2,37 +2,41 @@
 //!
 //! HTTP client for interacting with the Postman API.
 
+use crate::access::WorkspacePolicy;
 use crate::error::PostmanError;
+use crate::runner::{CollectionRun, NewmanRunner, RunOptions};
 use crate::types::{
-    Collection, CollectionResponse, CollectionSummary, CollectionsResponse, SearchResult,
-    Workspace, WorkspacesResponse,
+    Collection, CollectionRef, CollectionRefResponse, CollectionResponse, CollectionSummary,
+    CollectionsResponse, Environment,
+    EnvironmentBody, EnvironmentInput, EnvironmentRef, EnvironmentRefResponse,
+    EnvironmentResponse, EnvironmentSummary, EnvironmentsResponse, SearchResult, Workspace,
+    WorkspaceDetail, WorkspaceResponse, WorkspacesResponse,
 };
-use reqwest::Client;
+use qa_pms_core::{CircuitBreaker, RetryPolicy};
+use reqwest::{Client, Method, Response};
+use std::sync::Arc;
 use std::time::Duration;
-use tokio::time::sleep;
 use tracing::{debug, warn};
 
 /// Postman API base URL.
-const BASE_URL: &str = "https://api.getpostman.com";
+pub const BASE_URL: &str = "https://api.getpostman.com";
 
 /// Default request timeout in seconds.
 const DEFAULT_TIMEOUT_SECS: u64 = 10;
 
-/// Maximum retry attempts.
-const MAX_RETRIES: u32 = 3;
-
-/// Base delay for exponential backoff (1 second).
-const BASE_DELAY_SECS: u64 = 1;
-
 /// Postman API client.
 ///
 /// Provides methods for interacting with the Postman API including
-/// listing workspaces, collections, and searching.
+/// listing workspaces, collections, and searching. A `WorkspacePolicy`
+/// limits which workspaces are listed and whether writes are allowed.
 #[derive(Clone)]
 pub struct PostmanClient {
     http_client: Client,
     api_key: String,
     base_url: String,
+    policy: WorkspacePolicy,
+    retry: RetryPolicy,
+    circuit_breaker: Option<Arc<CircuitBreaker>>,
 }
 
 impl PostmanClient {
@@ -44,6 +48,7 @@ impl PostmanClient {
     /// # Panics
     /// Panics if the HTTP client cannot be created.
     #[must_use]
+    #[allow(clippy::expect_used)]
     pub fn new(api_key: String) -> Self {
         let http_client = Client::builder()
             .timeout(Duration::from_secs(DEFAULT_TIMEOUT_SECS))
@@ -54,13 +59,44 @@ impl PostmanClient {
             http_client,
             api_key,
             base_url: BASE_URL.to_string(),
+            policy: WorkspacePolicy::default(),
+            retry: RetryPolicy::default(),
+            circuit_breaker: None,
         }
     }
 
+    /// Restrict the client to a workspace policy.
+    #[must_use]
+    pub fn with_policy(mut self, policy: WorkspacePolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Retry failed requests with the given policy.
+    #[must_use]
+    pub const fn with_retry_policy(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Guard requests with a circuit breaker shared across clients.
+    #[must_use]
+    pub fn with_circuit_breaker(mut self, breaker: Arc<CircuitBreaker>) -> Self {
+        self.circuit_breaker = Some(breaker);
+        self
+    }
+
+    /// Workspace policy in effect.
+    #[must_use]
+    pub const fn policy(&self) -> &WorkspacePolicy {
+        &self.policy
+    }
+
     /// Create a new Postman client with custom base URL.
     ///
     /// Useful for testing with mock servers.
     #[cfg(test)]
+    #[allow(clippy::expect_used)]
     pub fn with_base_url(api_key: String, base_url: String) -> Self {
         let http_client = Client::builder()
             .timeout(Duration::from_secs(DEFAULT_TIMEOUT_SECS))
@@ -71,6 +107,9 @@ impl PostmanClient {
             http_client,
             api_key,
             base_url,
+            policy: WorkspacePolicy::default(),
+            retry: RetryPolicy::default(),
+            circuit_breaker: None,
         }
     }
 
@@ -91,54 +130,64 @@ impl PostmanClient {
                 .send()
                 .await?;
 
-            let status = response.status();
-
-            if status.is_success() {
-                let body = response.text().await?;
-                serde_json::from_str(&body)
-                    .map_err(|e| PostmanError::Parse(format!("{}: {}", e, &body[..200.min(body.len())])))
-            } else if status == reqwest::StatusCode::UNAUTHORIZED {
-                Err(PostmanError::Unauthorized)
-            } else if status == reqwest::StatusCode::NOT_FOUND {
-                Err(PostmanError::NotFound(endpoint.to_string()))
-            } else if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
-                Err(PostmanError::RateLimited)
-            } else {
-                let body = response.text().await.unwrap_or_default();
-                Err(PostmanError::ApiError { status, body })
-            }
+            parse_response(response, endpoint).await
+        })
+        .await
+    }
+
+    /// Make an authenticated request with a JSON body and retry logic.
+    async fn send_json<T, B>(
+        &self,
+        method: Method,
+        endpoint: &str,
+        body: &B,
+    ) -> Result<T, PostmanError>
+    where
+        T: serde::de::DeserializeOwned,
+        B: serde::Serialize,
+    {
+        let url = format!("{}{}", self.base_url, endpoint);
+
+        self.with_retry(|| async {
+            debug!(endpoint = %endpoint, method = %method, "Making Postman API request");
+
+            let response = self
+                .http_client
+                .request(method.clone(), &url)
+                .header("X-Api-Key", &self.api_key)
+                .json(body)
+                .send()
+                .await?;
+
+            parse_response(response, endpoint).await
         })
         .await
     }
 
-    /// Execute a function with exponential backoff retry.
+    /// Execute a function with the configured retry policy.
+    ///
+    /// While the circuit breaker is open the request is not sent; a request
+    /// that still fails after its retries counts as one breaker failure.
     async fn with_retry<T, F, Fut>(&self, f: F) -> Result<T, PostmanError>
     where
         F: Fn() -> Fut,
         Fut: std::future::Future<Output = Result<T, PostmanError>>,
     {
-        let mut attempt = 0;
-
-        loop {
-            attempt += 1;
-            match f().await {
-                Ok(result) => return Ok(result),
-                Err(e) if e.is_retryable() && attempt < MAX_RETRIES => {
-                    let delay = Duration::from_secs(BASE_DELAY_SECS * 2u64.pow(attempt - 1));
-                    warn!(
-                        attempt = attempt,
-                        max_attempts = MAX_RETRIES,
-                        delay_ms = delay.as_millis(),
-                        error = %e,
-                        "Postman API error, retrying"
-                    );
-                    sleep(delay).await;
-                }
-                Err(e) => return Err(e),
-            }
+        match &self.circuit_breaker {
+            Some(breaker) => breaker.call(self.retry(f), PostmanError::is_outage).await?,
+            None => self.retry(f).await,
         }
     }
 
+    /// Retry loop behind [`Self::with_retry`].
+    async fn retry<T, F, Fut>(&self, f: F) -> Result<T, PostmanError>
+    where
+        F: Fn() -> Fut,
+        Fut: std::future::Future<Output = Result<T, PostmanError>>,
+    {
+        self.retry.run("Postman", f, PostmanError::retry_decision).await
+    }
+
     // ========================================================================
     // Workspace Operations
     // ========================================================================
@@ -153,7 +202,40 @@ impl PostmanClient {
         debug!("Listing Postman workspaces");
         let response: WorkspacesResponse = self.request("/workspaces").await?;
         debug!(count = response.workspaces.len(), "Retrieved workspaces");
-        Ok(response.workspaces)
+        Ok(response
+            .workspaces
+            .into_iter()
+            .filter(|w| self.policy.allows(&w.id))
+            .collect())
+    }
+
+    /// Get a workspace with the collections and environments it contains.
+    ///
+    /// # Errors
+    /// Returns error if the workspace is outside the allow-list, not found,
+    /// or the API call fails.
+    pub async fn get_workspace(&self, workspace_id: &str) -> Result<WorkspaceDetail, PostmanError> {
+        self.policy.check_read(Some(workspace_id))?;
+        let endpoint = format!("/workspaces/{workspace_id}");
+        let response: WorkspaceResponse = self.request(&endpoint).await?;
+        Ok(response.workspace)
+    }
+
+    /// Check that an existing environment may be modified.
+    async fn check_environment_writable(&self, environment_id: &str) -> Result<(), PostmanError> {
+        self.policy.check_writable()?;
+        if !self.policy.is_restricted() {
+            return Ok(());
+        }
+        for workspace_id in &self.policy.allowed_workspaces {
+            let workspace = self.get_workspace(workspace_id).await?;
+            if workspace.environments.iter().any(|e| e.matches(environment_id)) {
+                return Ok(());
+            }
+        }
+        Err(PostmanError::Forbidden(format!(
+            "Environment {environment_id} is outside the allowed workspaces"
+        )))
     }
 
     // ========================================================================
@@ -172,6 +254,21 @@ impl PostmanClient {
     pub async fn list_collections(
         &self,
         workspace_id: Option<&str>,
+    ) -> Result<Vec<CollectionSummary>, PostmanError> {
+        self.policy.check_read(workspace_id)?;
+        if workspace_id.is_none() && self.policy.is_restricted() {
+            let mut collections = Vec::new();
+            for id in &self.policy.allowed_workspaces {
+                collections.extend(self.fetch_collections(Some(id)).await?);
+            }
+            return Ok(collections);
+        }
+        self.fetch_collections(workspace_id).await
+    }
+
+    async fn fetch_collections(
+        &self,
+        workspace_id: Option<&str>,
     ) -> Result<Vec<CollectionSummary>, PostmanError> {
         let endpoint = match workspace_id {
             Some(id) => format!("/collections?workspace={id}"),
@@ -204,6 +301,177 @@ impl PostmanClient {
         Ok(response.collection)
     }
 
+    /// Get a collection as raw JSON, in the format Newman runs.
+    ///
+    /// # Errors
+    /// Returns error if the collection is not found or API call fails.
+    pub async fn get_collection_json(&self, collection_id: &str) -> Result<serde_json::Value, PostmanError> {
+        let endpoint = format!("/collections/{collection_id}");
+        debug!(collection_id = %collection_id, "Getting collection JSON");
+        let mut response: serde_json::Value = self.request(&endpoint).await?;
+        match response.get_mut("collection") {
+            Some(collection) => Ok(collection.take()),
+            None => Err(PostmanError::Parse("Response has no collection".to_string())),
+        }
+    }
+
+    /// Run a collection with Newman, optionally against a saved environment.
+    ///
+    /// The collection and environment are fetched fresh, so the run uses
+    /// their current state in Postman. `options.environment` is replaced when
+    /// `environment_id` is given. Failing assertions are reported in the
+    /// result, not as an error.
+    ///
+    /// # Errors
+    /// Returns error if the collection or environment cannot be fetched, or
+    /// Newman fails to run.
+    pub async fn run_collection(
+        &self,
+        collection_id: &str,
+        environment_id: Option<&str>,
+        runner: &NewmanRunner,
+        mut options: RunOptions,
+    ) -> Result<CollectionRun, PostmanError> {
+        let collection = self.get_collection_json(collection_id).await?;
+        let collection_name = collection
+            .pointer("/info/name")
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or(collection_id)
+            .to_string();
+
+        if let Some(environment_id) = environment_id {
+            let environment = self.get_environment(environment_id).await?;
+            options.environment = Some(
+                serde_json::to_value(environment).map_err(|e| PostmanError::Parse(e.to_string()))?,
+            );
+        }
+
+        debug!(collection_id = %collection_id, "Running collection with Newman");
+        let report = runner.run(&collection, &options).await?;
+        Ok(CollectionRun {
+            collection_name,
+            report,
+        })
+    }
+
+    /// Create a collection from Postman v2.1 JSON.
+    ///
+    /// # Arguments
+    /// * `workspace_id` - Optional workspace to create the collection in
+    /// * `collection` - Collection JSON with `info` and `item`
+    ///
+    /// # Errors
+    /// Returns error if the API call fails.
+    pub async fn create_collection(
+        &self,
+        workspace_id: Option<&str>,
+        collection: &serde_json::Value,
+    ) -> Result<CollectionRef, PostmanError> {
+        self.policy.check_create(workspace_id)?;
+        let endpoint = match workspace_id {
+            Some(id) => format!("/collections?workspace={id}"),
+            None => "/collections".to_string(),
+        };
+        debug!(workspace_id = workspace_id, "Creating Postman collection");
+        let body = serde_json::json!({ "collection": collection });
+        let response: CollectionRefResponse = self.send_json(Method::POST, &endpoint, &body).await?;
+        Ok(response.collection)
+    }
+
+    // ========================================================================
+    // Environment Operations
+    // ========================================================================
+
+    /// List environments.
+    ///
+    /// # Arguments
+    /// * `workspace_id` - Optional workspace ID to filter environments
+    ///
+    /// # Errors
+    /// Returns error if the API call fails or response cannot be parsed.
+    pub async fn list_environments(
+        &self,
+        workspace_id: Option<&str>,
+    ) -> Result<Vec<EnvironmentSummary>, PostmanError> {
+        self.policy.check_read(workspace_id)?;
+        if workspace_id.is_none() && self.policy.is_restricted() {
+            let mut environments = Vec::new();
+            for id in &self.policy.allowed_workspaces {
+                environments.extend(self.fetch_environments(Some(id)).await?);
+            }
+            return Ok(environments);
+        }
+        self.fetch_environments(workspace_id).await
+    }
+
+    async fn fetch_environments(
+        &self,
+        workspace_id: Option<&str>,
+    ) -> Result<Vec<EnvironmentSummary>, PostmanError> {
+        let endpoint = match workspace_id {
+            Some(id) => format!("/environments?workspace={id}"),
+            None => "/environments".to_string(),
+        };
+        debug!(workspace_id = workspace_id, "Listing Postman environments");
+        let response: EnvironmentsResponse = self.request(&endpoint).await?;
+        debug!(count = response.environments.len(), "Retrieved environments");
+        Ok(response.environments)
+    }
+
+    /// Get an environment with its variables.
+    ///
+    /// # Errors
+    /// Returns error if the environment is not found or API call fails.
+    pub async fn get_environment(&self, environment_id: &str) -> Result<Environment, PostmanError> {
+        let endpoint = format!("/environments/{environment_id}");
+        debug!(environment_id = %environment_id, "Getting Postman environment");
+        let response: EnvironmentResponse = self.request(&endpoint).await?;
+        Ok(response.environment)
+    }
+
+    /// Create an environment.
+    ///
+    /// # Arguments
+    /// * `workspace_id` - Optional workspace to create the environment in
+    /// * `environment` - Name and variables
+    ///
+    /// # Errors
+    /// Returns error if the API call fails.
+    pub async fn create_environment(
+        &self,
+        workspace_id: Option<&str>,
+        environment: &EnvironmentInput,
+    ) -> Result<EnvironmentRef, PostmanError> {
+        self.policy.check_create(workspace_id)?;
+        let endpoint = match workspace_id {
+            Some(id) => format!("/environments?workspace={id}"),
+            None => "/environments".to_string(),
+        };
+        debug!(name = %environment.name, "Creating Postman environment");
+        let response: EnvironmentRefResponse = self
+            .send_json(Method::POST, &endpoint, &EnvironmentBody { environment })
+            .await?;
+        Ok(response.environment)
+    }
+
+    /// Replace an environment's name and variables.
+    ///
+    /// # Errors
+    /// Returns error if the environment is not found or API call fails.
+    pub async fn update_environment(
+        &self,
+        environment_id: &str,
+        environment: &EnvironmentInput,
+    ) -> Result<EnvironmentRef, PostmanError> {
+        self.check_environment_writable(environment_id).await?;
+        let endpoint = format!("/environments/{environment_id}");
+        debug!(environment_id = %environment_id, "Updating Postman environment");
+        let response: EnvironmentRefResponse = self
+            .send_json(Method::PUT, &endpoint, &EnvironmentBody { environment })
+            .await?;
+        Ok(response.environment)
+    }
+
     // ========================================================================
     // Search Operations
     // ========================================================================
@@ -282,30 +550,32 @@ impl PostmanClient {
     }
 }
 
-/// Calculate match score for text against keywords.
-fn calculate_match_score(text: &str, keywords: &[String]) -> f32 {
-    if keywords.is_empty() {
-        return 0.0;
-    }
-
-    let text_lower = text.to_lowercase();
-    let mut score = 0.0;
-
-    for keyword in keywords {
-        let keyword_lower = keyword.to_lowercase();
-        if text_lower.contains(&keyword_lower) {
-            score += 1.0;
-            // Bonus for exact word match
-            if text_lower
-                .split_whitespace()
-                .any(|w| w == keyword_lower)
-            {
-                score += 0.5;
-            }
-        }
+/// Map a response to its JSON body or a typed error.
+async fn parse_response<T: serde::de::DeserializeOwned>(
+    response: Response,
+    endpoint: &str,
+) -> Result<T, PostmanError> {
+    let status = response.status();
+
+    if status.is_success() {
+        let body = response.text().await?;
+        serde_json::from_str(&body)
+            .map_err(|e| PostmanError::Parse(format!("{}: {}", e, &body[..200.min(body.len())])))
+    } else if status == reqwest::StatusCode::UNAUTHORIZED {
+        Err(PostmanError::Unauthorized)
+    } else if status == reqwest::StatusCode::NOT_FOUND {
+        Err(PostmanError::NotFound(endpoint.to_string()))
+    } else if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+        Err(PostmanError::RateLimited)
+    } else {
+        let body = response.text().await.unwrap_or_default();
+        Err(PostmanError::ApiError { status, body })
     }
+}
 
-    score
+/// Calculate match score for text against keywords, tolerating typos.
+pub(crate) fn calculate_match_score(text: &str, keywords: &[String]) -> f32 {
+    qa_pms_core::match_score(text, keywords)
 }
 
 /// Search for matching requests within a collection.
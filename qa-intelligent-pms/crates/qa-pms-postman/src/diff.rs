@@ -0,0 +1,329 @@
+//! Structural collection diff.
+//!
+//! Compares two collection versions request by request. Requests are matched
+//! by folder path and name, so a forked or re-imported collection with new
+//! item IDs still lines up with its ancestor.
+
+use std::collections::{BTreeMap, HashMap};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::types::RequestUrl;
+
+/// A request present in only one version.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RequestRef {
+    /// Folder path and request name, e.g. "Orders/Create order".
+    pub path: String,
+    /// HTTP method.
+    pub method: Option<String>,
+    /// Request URL.
+    pub url: Option<String>,
+}
+
+/// One changed attribute of a request.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FieldChange {
+    /// "method", "url", "body" or "header:<name>".
+    pub field: String,
+    /// Value in the base version.
+    pub before: Option<String>,
+    /// Value in the current version.
+    pub after: Option<String>,
+}
+
+/// A request present in both versions with different content.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RequestChange {
+    /// Folder path and request name.
+    pub path: String,
+    /// Changed attributes.
+    pub changes: Vec<FieldChange>,
+}
+
+/// Differences between a base and a current collection.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CollectionDiff {
+    /// Requests only in the current version.
+    pub added: Vec<RequestRef>,
+    /// Requests only in the base version.
+    pub removed: Vec<RequestRef>,
+    /// Requests whose method, URL, headers or body changed.
+    pub changed: Vec<RequestChange>,
+    /// Requests identical in both versions.
+    pub unchanged: usize,
+}
+
+impl CollectionDiff {
+    /// Whether the versions expose the same requests.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// Comparable view of one request.
+#[derive(Debug, Default)]
+struct RequestShape {
+    method: Option<String>,
+    url: Option<String>,
+    headers: BTreeMap<String, String>,
+    body: Option<String>,
+}
+
+impl RequestShape {
+    fn from_json(request: &Value) -> Self {
+        // A request may be a bare URL string
+        if let Value::String(url) = request {
+            return Self {
+                method: Some("GET".to_string()),
+                url: Some(url.clone()),
+                ..Self::default()
+            };
+        }
+
+        let headers = request
+            .get("header")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter(|h| !h.get("disabled").and_then(Value::as_bool).unwrap_or(false))
+            .filter_map(|h| {
+                let key = h.get("key")?.as_str()?.to_ascii_lowercase();
+                let value = h.get("value").and_then(Value::as_str).unwrap_or_default();
+                Some((key, value.to_string()))
+            })
+            .collect();
+
+        Self {
+            method: request
+                .get("method")
+                .and_then(Value::as_str)
+                .map(str::to_ascii_uppercase),
+            url: request
+                .get("url")
+                .and_then(|u| serde_json::from_value::<RequestUrl>(u.clone()).ok())
+                .map(|u| u.as_string()),
+            headers,
+            body: request.get("body").and_then(body_text),
+        }
+    }
+
+    fn to_ref(&self, path: &str) -> RequestRef {
+        RequestRef {
+            path: path.to_string(),
+            method: self.method.clone(),
+            url: self.url.clone(),
+        }
+    }
+}
+
+/// Body content for its mode; raw JSON bodies are normalized so formatting
+/// changes do not show up as deltas.
+fn body_text(body: &Value) -> Option<String> {
+    let mode = body.get("mode").and_then(Value::as_str)?;
+    let content = body.get(mode)?;
+    match content {
+        Value::String(raw) if raw.trim().is_empty() => None,
+        Value::String(raw) => Some(match serde_json::from_str::<Value>(raw) {
+            Ok(json) => json.to_string(),
+            Err(_) => raw.trim().to_string(),
+        }),
+        Value::Null => None,
+        other => Some(format!("{mode}: {other}")),
+    }
+}
+
+/// Flatten a collection into requests keyed by folder path and name.
+///
+/// Duplicate names within a folder get a `#n` suffix in order of appearance.
+fn flatten(collection: &Value) -> Vec<(String, RequestShape)> {
+    fn walk(items: &[Value], prefix: &str, out: &mut Vec<(String, RequestShape)>) {
+        let mut seen: HashMap<String, usize> = HashMap::new();
+        for item in items {
+            let name = item
+                .get("name")
+                .and_then(Value::as_str)
+                .unwrap_or("Unnamed");
+            let count = seen.entry(name.to_string()).or_insert(0);
+            *count += 1;
+            let name = if *count > 1 {
+                format!("{name}#{count}")
+            } else {
+                name.to_string()
+            };
+            let path = if prefix.is_empty() {
+                name
+            } else {
+                format!("{prefix}/{name}")
+            };
+
+            if let Some(children) = item.get("item").and_then(Value::as_array) {
+                walk(children, &path, out);
+            } else if let Some(request) = item.get("request") {
+                out.push((path, RequestShape::from_json(request)));
+            }
+        }
+    }
+
+    let mut out = Vec::new();
+    if let Some(items) = collection.get("item").and_then(Value::as_array) {
+        walk(items, "", &mut out);
+    }
+    out
+}
+
+fn change(field: &str, before: Option<&String>, after: Option<&String>) -> Option<FieldChange> {
+    (before != after).then(|| FieldChange {
+        field: field.to_string(),
+        before: before.cloned(),
+        after: after.cloned(),
+    })
+}
+
+fn compare(base: &RequestShape, current: &RequestShape) -> Vec<FieldChange> {
+    let mut changes: Vec<FieldChange> = [
+        change("method", base.method.as_ref(), current.method.as_ref()),
+        change("url", base.url.as_ref(), current.url.as_ref()),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+
+    let mut names: Vec<&String> = base.headers.keys().chain(current.headers.keys()).collect();
+    names.sort();
+    names.dedup();
+    changes.extend(names.into_iter().filter_map(|name| {
+        change(
+            &format!("header:{name}"),
+            base.headers.get(name),
+            current.headers.get(name),
+        )
+    }));
+
+    changes.extend(change("body", base.body.as_ref(), current.body.as_ref()));
+    changes
+}
+
+/// Diff two collection JSON documents (Postman v2.1 format).
+///
+/// `base` is the older version, e.g. the last release.
+#[must_use]
+pub fn diff_collections(base: &Value, current: &Value) -> CollectionDiff {
+    let base_requests = flatten(base);
+    let current_requests = flatten(current);
+    let base_by_path: HashMap<&str, &RequestShape> = base_requests
+        .iter()
+        .map(|(path, shape)| (path.as_str(), shape))
+        .collect();
+    let current_by_path: HashMap<&str, &RequestShape> = current_requests
+        .iter()
+        .map(|(path, shape)| (path.as_str(), shape))
+        .collect();
+
+    let mut diff = CollectionDiff::default();
+    for (path, shape) in &current_requests {
+        match base_by_path.get(path.as_str()) {
+            None => diff.added.push(shape.to_ref(path)),
+            Some(before) => {
+                let changes = compare(before, shape);
+                if changes.is_empty() {
+                    diff.unchanged += 1;
+                } else {
+                    diff.changed.push(RequestChange {
+                        path: path.clone(),
+                        changes,
+                    });
+                }
+            }
+        }
+    }
+    diff.removed = base_requests
+        .iter()
+        .filter(|(path, _)| !current_by_path.contains_key(path.as_str()))
+        .map(|(path, shape)| shape.to_ref(path))
+        .collect();
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn collection(items: Value) -> Value {
+        json!({ "info": { "name": "Orders API" }, "item": items })
+    }
+
+    #[test]
+    fn test_diff_detects_added_removed_and_changed() {
+        let base = collection(json!([
+            { "name": "Orders", "item": [
+                { "name": "Create order", "request": {
+                    "method": "POST",
+                    "url": { "raw": "{{baseUrl}}/orders" },
+                    "header": [{ "key": "Content-Type", "value": "application/json" }],
+                    "body": { "mode": "raw", "raw": "{\"sku\": \"A1\"}" }
+                }},
+                { "name": "Cancel order", "request": { "method": "DELETE", "url": "{{baseUrl}}/orders/1" } }
+            ]},
+            { "name": "Health", "request": "{{baseUrl}}/health" }
+        ]));
+        let current = collection(json!([
+            { "name": "Orders", "item": [
+                { "name": "Create order", "request": {
+                    "method": "POST",
+                    "url": { "raw": "{{baseUrl}}/v2/orders" },
+                    "header": [
+                        { "key": "content-type", "value": "application/json" },
+                        { "key": "Idempotency-Key", "value": "{{key}}" }
+                    ],
+                    "body": { "mode": "raw", "raw": "{\n  \"sku\": \"A1\"\n}" }
+                }},
+                { "name": "Refund order", "request": { "method": "POST", "url": "{{baseUrl}}/orders/1/refund" } }
+            ]},
+            { "name": "Health", "request": "{{baseUrl}}/health" }
+        ]));
+
+        let diff = diff_collections(&base, &current);
+        assert_eq!(diff.unchanged, 1);
+        assert_eq!(diff.added[0].path, "Orders/Refund order");
+        assert_eq!(diff.removed[0].path, "Orders/Cancel order");
+        assert_eq!(diff.removed[0].method.as_deref(), Some("DELETE"));
+
+        let fields: Vec<&str> = diff.changed[0]
+            .changes
+            .iter()
+            .map(|c| c.field.as_str())
+            .collect();
+        assert_eq!(diff.changed[0].path, "Orders/Create order");
+        assert_eq!(fields, vec!["url", "header:idempotency-key"]);
+    }
+
+    #[test]
+    fn test_diff_ignores_disabled_headers_and_handles_duplicates() {
+        let request = |header_disabled: bool| {
+            json!({ "method": "GET", "url": "/items", "header": [
+                { "key": "X-Debug", "value": "1", "disabled": header_disabled }
+            ]})
+        };
+        let base = collection(json!([
+            { "name": "List", "request": request(true) },
+            { "name": "List", "request": request(true) }
+        ]));
+        let current = collection(json!([
+            { "name": "List", "request": request(true) },
+            { "name": "List", "request": request(false) }
+        ]));
+
+        let diff = diff_collections(&base, &current);
+        assert_eq!(diff.unchanged, 1);
+        assert_eq!(diff.changed[0].path, "List#2");
+        assert!(diff_collections(&base, &base).is_empty());
+    }
+}
@@ -2,6 +2,7 @@
 //!
 //! Domain-specific error types for Postman API operations.
 
+use qa_pms_core::{is_retryable_status, CircuitOpenError, RetryDecision};
 use reqwest::StatusCode;
 use thiserror::Error;
 
@@ -16,6 +17,10 @@ pub enum PostmanError {
     #[error("Resource not found: {0}")]
     NotFound(String),
 
+    /// Operation refused by the workspace policy.
+    #[error("Forbidden: {0}")]
+    Forbidden(String),
+
     /// Rate limit exceeded.
     #[error("Rate limited - too many requests")]
     RateLimited,
@@ -33,19 +38,54 @@ pub enum PostmanError {
     #[error("Network error: {0}")]
     Network(#[from] reqwest::Error),
 
+    /// Request skipped because the circuit breaker is open.
+    #[error(transparent)]
+    CircuitOpen(#[from] CircuitOpenError),
+
     /// JSON parsing error.
     #[error("Failed to parse response: {0}")]
     Parse(String),
+
+    /// Collection runner (Newman) failure.
+    #[error("Collection run failed: {0}")]
+    Runner(String),
+
+    /// Collection cache database error.
+    #[error("Cache database error: {0}")]
+    Database(#[from] sqlx::Error),
 }
 
 impl PostmanError {
     /// Check if the error is retryable.
     ///
-    /// Returns `true` for rate limiting, network errors, and server errors (5xx).
+    /// Returns `true` for rate limiting, network errors, and transient
+    /// statuses (timeouts and 5xx).
     #[must_use]
     pub fn is_retryable(&self) -> bool {
         match self {
             Self::RateLimited | Self::Network(_) => true,
+            Self::ApiError { status, .. } => is_retryable_status(status.as_u16()),
+            _ => false,
+        }
+    }
+
+    /// How the retry policy should handle this error.
+    #[must_use]
+    pub fn retry_decision(&self) -> RetryDecision {
+        if self.is_retryable() {
+            RetryDecision::Retry
+        } else {
+            RetryDecision::Stop
+        }
+    }
+
+    /// Check if the error means the service is down.
+    ///
+    /// Only these count towards opening the circuit breaker.
+    #[must_use]
+    pub fn is_outage(&self) -> bool {
+        match self {
+            Self::Network(_) => true,
             Self::ApiError { status, .. } => status.is_server_error(),
             _ => false,
         }
@@ -83,6 +123,17 @@ mod tests {
         assert!(err.is_retryable());
     }
 
+    #[test]
+    fn test_only_network_and_server_errors_are_outages() {
+        let server = PostmanError::ApiError {
+            status: StatusCode::BAD_GATEWAY,
+            body: String::new(),
+        };
+        assert!(server.is_outage());
+        assert!(!PostmanError::NotFound("/x".to_string()).is_outage());
+        assert!(!PostmanError::RateLimited.is_outage());
+    }
+
     #[test]
     fn test_client_error_not_retryable() {
         let err = PostmanError::ApiError {
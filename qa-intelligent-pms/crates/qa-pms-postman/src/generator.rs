@@ -0,0 +1,287 @@
+//! Skeleton collections from ticket text.
+//!
+//! Finds the endpoints a ticket talks about and turns them into request
+//! stubs. Explicit mentions ("POST /orders/{id}/refund") and OpenAPI path
+//! references ("openapi.yaml#/paths/~1orders/get") win; action keywords
+//! ("create order") are only used when the text names no endpoint at all.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+/// Postman collection schema written into generated collections.
+pub const COLLECTION_SCHEMA: &str =
+    "https://schema.getpostman.com/json/collection/v2.1.0/collection.json";
+
+const METHODS: [&str; 7] = ["GET", "POST", "PUT", "PATCH", "DELETE", "HEAD", "OPTIONS"];
+
+/// Keyword verbs and the method they imply.
+const KEYWORD_VERBS: [(&str, &str); 12] = [
+    ("create", "POST"),
+    ("add", "POST"),
+    ("submit", "POST"),
+    ("update", "PUT"),
+    ("edit", "PUT"),
+    ("change", "PATCH"),
+    ("delete", "DELETE"),
+    ("remove", "DELETE"),
+    ("get", "GET"),
+    ("list", "GET"),
+    ("fetch", "GET"),
+    ("view", "GET"),
+];
+
+/// Where an endpoint was found.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum EndpointSource {
+    /// Method and path written in the text.
+    Explicit,
+    /// OpenAPI `#/paths/...` reference.
+    OpenApi,
+    /// Guessed from an action keyword; needs review.
+    Keyword,
+}
+
+/// An endpoint to stub.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EndpointSpec {
+    /// HTTP method.
+    pub method: String,
+    /// Path with `{param}` placeholders, without host.
+    pub path: String,
+    /// Where the endpoint was found.
+    pub source: EndpointSource,
+}
+
+impl EndpointSpec {
+    fn new(method: &str, path: &str, source: EndpointSource) -> Self {
+        Self {
+            method: method.to_ascii_uppercase(),
+            path: path.to_string(),
+            source,
+        }
+    }
+}
+
+/// Strip punctuation around a token and any scheme/host prefix.
+fn clean_path(token: &str) -> Option<String> {
+    let token = token.trim_matches(['`', '"', '\'', '(', ')', ',', ';', '<', '>']);
+    let token = token.trim_end_matches(['.', ':']);
+    let path = match token.find("://") {
+        Some(scheme) => {
+            let rest = &token[scheme + 3..];
+            &rest[rest.find('/')?..]
+        }
+        None => token,
+    };
+    let path = path.split(['?', '#']).next().unwrap_or_default();
+    (path.starts_with('/') && path.len() > 1 && !path.starts_with("//")).then(|| path.to_string())
+}
+
+/// Decode an OpenAPI reference such as `spec.yaml#/paths/~1orders~1{id}/get`.
+fn openapi_reference(token: &str) -> Option<EndpointSpec> {
+    let pointer = &token[token.find("#/paths/")? + "#/paths/".len()..];
+    let pointer = pointer.trim_end_matches(['`', '"', '\'', ')', ',', '.']);
+    let mut segments = pointer.split('/');
+    let path = segments.next()?.replace("~1", "/").replace("~0", "~");
+    let method = segments
+        .next()
+        .map(str::to_ascii_uppercase)
+        .filter(|m| METHODS.contains(&m.as_str()))
+        .unwrap_or_else(|| "GET".to_string());
+    path.starts_with('/')
+        .then(|| EndpointSpec::new(&method, &path, EndpointSource::OpenApi))
+}
+
+/// Guess endpoints from "verb noun" phrases.
+fn keyword_endpoints(words: &[&str]) -> Vec<EndpointSpec> {
+    words
+        .windows(2)
+        .filter_map(|pair| {
+            let verb = pair[0].to_ascii_lowercase();
+            let (_, method) = KEYWORD_VERBS.iter().find(|(v, _)| *v == verb)?;
+            let noun: String = pair[1]
+                .chars()
+                .filter(char::is_ascii_alphanumeric)
+                .collect::<String>()
+                .to_ascii_lowercase();
+            if noun.len() < 3 || matches!(noun.as_str(), "the" | "new" | "and" | "for" | "all") {
+                return None;
+            }
+            let resource = if noun.ends_with('s') {
+                noun
+            } else {
+                format!("{noun}s")
+            };
+            let path = match *method {
+                "POST" => format!("/{resource}"),
+                "GET" if verb == "list" => format!("/{resource}"),
+                _ => format!("/{resource}/{{id}}"),
+            };
+            Some(EndpointSpec::new(method, &path, EndpointSource::Keyword))
+        })
+        .collect()
+}
+
+/// Extract endpoints from ticket text, in order of first mention.
+#[must_use]
+pub fn extract_endpoints(text: &str) -> Vec<EndpointSpec> {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    let mut found: Vec<EndpointSpec> = Vec::new();
+    let push = |spec: EndpointSpec, found: &mut Vec<EndpointSpec>| {
+        if !found
+            .iter()
+            .any(|f| f.method == spec.method && f.path == spec.path)
+        {
+            found.push(spec);
+        }
+    };
+
+    for (i, word) in words.iter().enumerate() {
+        if let Some(spec) = openapi_reference(word) {
+            push(spec, &mut found);
+            continue;
+        }
+        let method = word.trim_matches(|c: char| !c.is_ascii_alphabetic());
+        if METHODS.contains(&method) {
+            if let Some(path) = words.get(i + 1).and_then(|next| clean_path(next)) {
+                push(
+                    EndpointSpec::new(method, &path, EndpointSource::Explicit),
+                    &mut found,
+                );
+            }
+        }
+    }
+
+    if found.is_empty() {
+        for spec in keyword_endpoints(&words) {
+            push(spec, &mut found);
+        }
+    }
+    found
+}
+
+/// Request item for one endpoint, with a status check test.
+fn request_item(endpoint: &EndpointSpec) -> Value {
+    let segments: Vec<String> = endpoint
+        .path
+        .trim_start_matches('/')
+        .split('/')
+        .map(
+            |s| match s.strip_prefix('{').and_then(|s| s.strip_suffix('}')) {
+                Some(param) => format!(":{param}"),
+                None => s.to_string(),
+            },
+        )
+        .collect();
+    let variables: Vec<Value> = segments
+        .iter()
+        .filter_map(|s| s.strip_prefix(':'))
+        .map(|param| json!({ "key": param, "value": "" }))
+        .collect();
+
+    let mut request = json!({
+        "method": endpoint.method,
+        "header": [],
+        "url": {
+            "raw": format!("{{{{baseUrl}}}}/{}", segments.join("/")),
+            "host": ["{{baseUrl}}"],
+            "path": segments,
+            "variable": variables,
+        },
+    });
+    if matches!(endpoint.method.as_str(), "POST" | "PUT" | "PATCH") {
+        request["header"] = json!([{ "key": "Content-Type", "value": "application/json" }]);
+        request["body"] = json!({ "mode": "raw", "raw": "{}" });
+    }
+
+    let mut item = json!({
+        "name": format!("{} {}", endpoint.method, endpoint.path),
+        "request": request,
+        "event": [{
+            "listen": "test",
+            "script": {
+                "type": "text/javascript",
+                "exec": [
+                    "pm.test(\"Status code is successful\", function () {",
+                    "    pm.expect(pm.response.code).to.be.within(200, 299);",
+                    "});"
+                ]
+            }
+        }],
+    });
+    if endpoint.source == EndpointSource::Keyword {
+        item["description"] = json!("Guessed from the ticket wording; check the path.");
+    }
+    item
+}
+
+/// Build a Postman v2.1 collection with one stub request per endpoint.
+///
+/// Requests use a `baseUrl` collection variable so they can be pointed at
+/// any environment.
+#[must_use]
+pub fn build_collection(name: &str, description: &str, endpoints: &[EndpointSpec]) -> Value {
+    json!({
+        "info": {
+            "name": name,
+            "description": description,
+            "schema": COLLECTION_SCHEMA,
+        },
+        "item": endpoints.iter().map(request_item).collect::<Vec<_>>(),
+        "variable": [{ "key": "baseUrl", "value": "" }],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_explicit_and_openapi_endpoints() {
+        let text = "Add refunds. Call `POST /api/orders/{id}/refund` then GET \
+                    https://api.example.com/api/orders/{id}?expand=refunds. \
+                    Spec: openapi.yaml#/paths/~1api~1refunds/get. POST /api/orders/{id}/refund again.";
+        let endpoints = extract_endpoints(text);
+
+        assert_eq!(
+            endpoints,
+            vec![
+                EndpointSpec::new("POST", "/api/orders/{id}/refund", EndpointSource::Explicit),
+                EndpointSpec::new("GET", "/api/orders/{id}", EndpointSource::Explicit),
+                EndpointSpec::new("GET", "/api/refunds", EndpointSource::OpenApi),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_keywords_only_used_without_explicit_endpoints() {
+        let endpoints = extract_endpoints("Users can create invoice and delete invoice drafts");
+        assert_eq!(
+            endpoints[0],
+            EndpointSpec::new("POST", "/invoices", EndpointSource::Keyword)
+        );
+        assert_eq!(endpoints[1].path, "/invoices/{id}");
+
+        let endpoints = extract_endpoints("Create invoice via POST /billing/invoices");
+        assert_eq!(endpoints.len(), 1);
+        assert_eq!(endpoints[0].source, EndpointSource::Explicit);
+    }
+
+    #[test]
+    fn test_build_collection_stubs_requests() {
+        let endpoints = vec![EndpointSpec::new(
+            "PUT",
+            "/orders/{id}",
+            EndpointSource::Explicit,
+        )];
+        let collection = build_collection("PROJ-1: Orders", "Generated", &endpoints);
+
+        let request = &collection["item"][0]["request"];
+        assert_eq!(collection["info"]["schema"], COLLECTION_SCHEMA);
+        assert_eq!(request["url"]["raw"], "{{baseUrl}}/orders/:id");
+        assert_eq!(request["url"]["variable"][0]["key"], "id");
+        assert_eq!(request["body"]["mode"], "raw");
+    }
+}
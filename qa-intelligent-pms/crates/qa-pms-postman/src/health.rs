@@ -28,6 +28,7 @@ impl PostmanHealthCheck {
     /// # Arguments
     /// * `api_key` - Postman API key
     #[must_use]
+    #[allow(clippy::expect_used)]
     pub fn new(api_key: String) -> Self {
         let http_client = Client::builder()
             .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS))
@@ -4,20 +4,43 @@
 //!
 //! This crate provides:
 //! - API key authentication
+//! - Workspace allow-lists and read-only mode for shared keys
 //! - Workspace and collection listing
-//! - Collection search by keywords
+//! - Collection search by keywords, live or from a Postgres cache
 //! - Test case retrieval
+//! - Environment management (list, get, create, update variables)
+//! - Collection runs through Newman with structured results, fetched straight
+//!   from the API by ID
+//! - Structural diff between collection versions
+//! - Skeleton collection generation from ticket text
 //! - Health check for integration monitoring
 
+mod access;
+mod cache;
 mod client;
 mod error;
 mod types;
+pub mod diff;
+pub mod generator;
 pub mod health;
+pub mod runner;
 
-pub use client::PostmanClient;
+pub use access::WorkspacePolicy;
+pub use cache::{
+    CacheRefreshReport, CacheStatus, CachedCollection, CachedRequest, PostmanCache,
+    DEFAULT_REFRESH_SECS,
+};
+pub use client::{PostmanClient, BASE_URL as POSTMAN_BASE_URL};
+pub use diff::{diff_collections, CollectionDiff, FieldChange, RequestChange, RequestRef};
 pub use error::PostmanError;
+pub use generator::{build_collection, extract_endpoints, EndpointSource, EndpointSpec};
 pub use health::PostmanHealthCheck;
+pub use runner::{
+    AssertionResult, CollectionRun, CollectionRunReport, NewmanRunner, RequestExecution,
+    RunOptions,
+};
 pub use types::{
-    Collection, CollectionInfo, CollectionItem, CollectionSummary, RequestInfo, RequestUrl,
-    SearchResult, Workspace,
+    Collection, CollectionInfo, CollectionItem, CollectionRef, CollectionSummary, Environment,
+    EnvironmentInput, EnvironmentRef, EnvironmentSummary, EnvironmentVariable, RequestInfo,
+    RequestUrl, SearchResult, Workspace, WorkspaceDetail, WorkspaceResource,
 };
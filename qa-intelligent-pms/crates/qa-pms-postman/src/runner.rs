@@ -0,0 +1,422 @@
+//! Collection runs via Newman.
+//!
+//! The Postman API cannot execute collections, so runs shell out to the
+//! Newman CLI with the collection JSON fetched from the API and read back
+//! Newman's JSON reporter output.
+
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::process::Command;
+use tracing::{debug, warn};
+
+use crate::error::PostmanError;
+use crate::types::RequestUrl;
+
+/// Default Newman executable, resolved from `PATH`.
+pub const DEFAULT_NEWMAN_BINARY: &str = "newman";
+
+/// Default limit for a whole collection run.
+const DEFAULT_RUN_TIMEOUT_SECS: u64 = 600;
+
+/// Counter keeping concurrent run directories apart.
+static RUN_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// Options for a single collection run.
+#[derive(Debug, Clone, Default)]
+pub struct RunOptions {
+    /// Postman environment JSON to run against.
+    pub environment: Option<serde_json::Value>,
+    /// Extra variables, overriding the environment.
+    pub variables: Vec<(String, String)>,
+    /// Run only this folder of the collection.
+    pub folder: Option<String>,
+    /// Number of iterations.
+    pub iterations: Option<u32>,
+}
+
+/// Result of one assertion (`pm.test`).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssertionResult {
+    /// Assertion name.
+    pub name: String,
+    /// Whether it passed.
+    pub passed: bool,
+    /// Failure message.
+    pub error: Option<String>,
+}
+
+/// Outcome of one request in a run.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RequestExecution {
+    /// Request name.
+    pub name: String,
+    /// HTTP method.
+    pub method: Option<String>,
+    /// Resolved URL.
+    pub url: Option<String>,
+    /// Response status code (absent if the request failed).
+    pub status_code: Option<u16>,
+    /// Response time in milliseconds.
+    pub response_time_ms: Option<u64>,
+    /// Transport error, if the request could not be sent.
+    pub error: Option<String>,
+    /// Assertions evaluated on the response.
+    pub assertions: Vec<AssertionResult>,
+}
+
+impl RequestExecution {
+    /// Whether the request was sent and all its assertions passed.
+    #[must_use]
+    pub fn passed(&self) -> bool {
+        self.error.is_none() && self.assertions.iter().all(|a| a.passed)
+    }
+}
+
+/// Structured result of a collection run.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CollectionRunReport {
+    /// Whether every request and assertion passed.
+    pub passed: bool,
+    /// Run start.
+    pub started_at: Option<DateTime<Utc>>,
+    /// Run end.
+    pub finished_at: Option<DateTime<Utc>>,
+    /// Requests executed.
+    pub total_requests: u32,
+    /// Requests that could not be sent.
+    pub failed_requests: u32,
+    /// Assertions evaluated.
+    pub total_assertions: u32,
+    /// Assertions that failed.
+    pub failed_assertions: u32,
+    /// Per-request outcomes in execution order.
+    pub executions: Vec<RequestExecution>,
+}
+
+/// A finished run of a collection fetched from the Postman API.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CollectionRun {
+    /// Collection name at run time, falling back to its ID.
+    pub collection_name: String,
+    /// Newman results.
+    pub report: CollectionRunReport,
+}
+
+/// Runs collections with the Newman CLI.
+#[derive(Debug, Clone)]
+pub struct NewmanRunner {
+    binary: PathBuf,
+    timeout: Duration,
+}
+
+impl Default for NewmanRunner {
+    fn default() -> Self {
+        Self::new(DEFAULT_NEWMAN_BINARY)
+    }
+}
+
+impl NewmanRunner {
+    /// Create a runner for the given Newman executable.
+    #[must_use]
+    pub fn new(binary: impl Into<PathBuf>) -> Self {
+        Self {
+            binary: binary.into(),
+            timeout: Duration::from_secs(DEFAULT_RUN_TIMEOUT_SECS),
+        }
+    }
+
+    /// Set the limit for a whole run.
+    #[must_use]
+    pub const fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Run a collection.
+    ///
+    /// Failing assertions are reported in the result, not as an error.
+    ///
+    /// # Errors
+    /// Returns `PostmanError::Runner` if Newman cannot be started, times out
+    /// or produces no report.
+    pub async fn run(
+        &self,
+        collection: &serde_json::Value,
+        options: &RunOptions,
+    ) -> Result<CollectionRunReport, PostmanError> {
+        let dir = std::env::temp_dir().join(format!(
+            "qa-pms-newman-{}-{}",
+            std::process::id(),
+            RUN_COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        tokio::fs::create_dir_all(&dir).await.map_err(runner_error)?;
+
+        let result = self.run_in(&dir, collection, options).await;
+        if let Err(e) = tokio::fs::remove_dir_all(&dir).await {
+            warn!(error = %e, dir = %dir.display(), "Failed to clean up Newman run directory");
+        }
+        result
+    }
+
+    async fn run_in(
+        &self,
+        dir: &Path,
+        collection: &serde_json::Value,
+        options: &RunOptions,
+    ) -> Result<CollectionRunReport, PostmanError> {
+        let collection_path = dir.join("collection.json");
+        let report_path = dir.join("report.json");
+        write_json(&collection_path, collection).await?;
+
+        let mut command = Command::new(&self.binary);
+        command
+            .arg("run")
+            .arg(&collection_path)
+            .args(["--reporters", "json", "--reporter-json-export"])
+            .arg(&report_path)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true);
+
+        if let Some(environment) = &options.environment {
+            let environment_path = dir.join("environment.json");
+            write_json(&environment_path, environment).await?;
+            command.arg("--environment").arg(environment_path);
+        }
+        for (key, value) in &options.variables {
+            command.arg("--env-var").arg(format!("{key}={value}"));
+        }
+        if let Some(folder) = &options.folder {
+            command.arg("--folder").arg(folder);
+        }
+        if let Some(iterations) = options.iterations {
+            command.arg("--iteration-count").arg(iterations.to_string());
+        }
+
+        debug!(binary = %self.binary.display(), "Starting Newman run");
+        let output = tokio::time::timeout(self.timeout, command.output())
+            .await
+            .map_err(|_| PostmanError::Runner(format!("Newman run exceeded {}s", self.timeout.as_secs())))?
+            .map_err(|e| PostmanError::Runner(format!("Failed to start {}: {e}", self.binary.display())))?;
+
+        // Newman exits non-zero when assertions fail, so the report decides
+        match tokio::fs::read_to_string(&report_path).await {
+            Ok(report) => parse_newman_report(&report),
+            Err(_) => Err(PostmanError::Runner(format!(
+                "Newman produced no report (exit {}): {}",
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim()
+            ))),
+        }
+    }
+}
+
+async fn write_json(path: &Path, value: &serde_json::Value) -> Result<(), PostmanError> {
+    let json = serde_json::to_vec(value).map_err(|e| PostmanError::Parse(e.to_string()))?;
+    tokio::fs::write(path, json).await.map_err(runner_error)
+}
+
+fn runner_error(e: std::io::Error) -> PostmanError {
+    PostmanError::Runner(e.to_string())
+}
+
+// ============================================================================
+// Newman JSON reporter format
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+struct NewmanReport {
+    run: NewmanRun,
+}
+
+#[derive(Debug, Deserialize)]
+struct NewmanRun {
+    #[serde(default)]
+    timings: NewmanTimings,
+    #[serde(default)]
+    executions: Vec<NewmanExecution>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct NewmanTimings {
+    started: Option<i64>,
+    completed: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct NewmanExecution {
+    item: NewmanItem,
+    request: Option<NewmanRequest>,
+    response: Option<NewmanResponse>,
+    #[serde(default)]
+    assertions: Vec<NewmanAssertion>,
+    request_error: Option<NewmanError>,
+}
+
+#[derive(Debug, Deserialize)]
+struct NewmanItem {
+    #[serde(default)]
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct NewmanRequest {
+    method: Option<String>,
+    url: Option<RequestUrl>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct NewmanResponse {
+    code: Option<u16>,
+    response_time: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct NewmanAssertion {
+    assertion: String,
+    error: Option<NewmanError>,
+}
+
+#[derive(Debug, Deserialize)]
+struct NewmanError {
+    message: Option<String>,
+}
+
+impl NewmanError {
+    fn into_message(self) -> String {
+        self.message.unwrap_or_else(|| "unknown error".to_string())
+    }
+}
+
+/// Parse Newman's JSON reporter output.
+///
+/// # Errors
+/// Returns `PostmanError::Parse` if the document is not a Newman report.
+pub fn parse_newman_report(json: &str) -> Result<CollectionRunReport, PostmanError> {
+    let report: NewmanReport =
+        serde_json::from_str(json).map_err(|e| PostmanError::Parse(format!("Invalid Newman report: {e}")))?;
+
+    let executions: Vec<RequestExecution> = report
+        .run
+        .executions
+        .into_iter()
+        .map(|execution| RequestExecution {
+            name: execution.item.name,
+            method: execution.request.as_ref().and_then(|r| r.method.clone()),
+            url: execution
+                .request
+                .as_ref()
+                .and_then(|r| r.url.as_ref())
+                .map(RequestUrl::as_string),
+            status_code: execution.response.as_ref().and_then(|r| r.code),
+            response_time_ms: execution.response.as_ref().and_then(|r| r.response_time),
+            error: execution.request_error.map(NewmanError::into_message),
+            assertions: execution
+                .assertions
+                .into_iter()
+                .map(|a| AssertionResult {
+                    name: a.assertion,
+                    passed: a.error.is_none(),
+                    error: a.error.map(NewmanError::into_message),
+                })
+                .collect(),
+        })
+        .collect();
+
+    let count = |n: usize| u32::try_from(n).unwrap_or(u32::MAX);
+    let assertions = executions.iter().flat_map(|e| &e.assertions);
+    let total_assertions = count(assertions.clone().count());
+    let failed_assertions = count(assertions.filter(|a| !a.passed).count());
+    let failed_requests = count(executions.iter().filter(|e| e.error.is_some()).count());
+
+    Ok(CollectionRunReport {
+        passed: failed_requests == 0 && failed_assertions == 0,
+        started_at: report.run.timings.started.and_then(DateTime::from_timestamp_millis),
+        finished_at: report.run.timings.completed.and_then(DateTime::from_timestamp_millis),
+        total_requests: count(executions.len()),
+        failed_requests,
+        total_assertions,
+        failed_assertions,
+        executions,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REPORT: &str = r#"{
+        "collection": {"info": {"name": "Orders"}},
+        "run": {
+            "stats": {"requests": {"total": 2, "failed": 1}},
+            "timings": {"started": 1704067200000, "completed": 1704067201500},
+            "executions": [
+                {
+                    "item": {"name": "Create order"},
+                    "request": {"method": "POST", "url": {"protocol": "https", "host": ["api", "example", "com"], "path": ["orders"]}},
+                    "response": {"code": 201, "responseTime": 87},
+                    "assertions": [
+                        {"assertion": "Status is 201"},
+                        {"assertion": "Has id", "error": {"name": "AssertionError", "message": "expected undefined to exist"}}
+                    ]
+                },
+                {
+                    "item": {"name": "Get order"},
+                    "request": {"method": "GET", "url": {"raw": "https://api.example.com/orders/1"}},
+                    "requestError": {"message": "ECONNREFUSED"}
+                }
+            ]
+        }
+    }"#;
+
+    #[test]
+    fn test_parse_newman_report() {
+        let report = parse_newman_report(REPORT).unwrap();
+
+        assert!(!report.passed);
+        assert_eq!(report.total_requests, 2);
+        assert_eq!(report.failed_requests, 1);
+        assert_eq!(report.total_assertions, 2);
+        assert_eq!(report.failed_assertions, 1);
+        assert_eq!(
+            report.finished_at.unwrap() - report.started_at.unwrap(),
+            chrono::Duration::milliseconds(1500)
+        );
+
+        let create = &report.executions[0];
+        assert_eq!(create.url.as_deref(), Some("api.example.com/orders"));
+        assert_eq!(create.status_code, Some(201));
+        assert_eq!(create.assertions[1].error.as_deref(), Some("expected undefined to exist"));
+        assert!(!create.passed());
+
+        let get = &report.executions[1];
+        assert_eq!(get.error.as_deref(), Some("ECONNREFUSED"));
+        assert_eq!(get.status_code, None);
+    }
+
+    #[test]
+    fn test_parse_newman_report_rejects_other_json() {
+        assert!(parse_newman_report(r#"{"collection": {}}"#).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_run_reports_missing_binary() {
+        let runner = NewmanRunner::new("/nonexistent/newman");
+        let err = runner
+            .run(&serde_json::json!({"info": {"name": "x"}}), &RunOptions::default())
+            .await
+            .unwrap_err();
+        assert!(matches!(err, PostmanError::Runner(_)));
+    }
+}
@@ -27,6 +27,47 @@ pub struct Workspace {
     pub workspace_type: String,
 }
 
+/// Response wrapper for single workspace.
+#[derive(Debug, Deserialize)]
+pub struct WorkspaceResponse {
+    /// Workspace with its contents.
+    pub workspace: WorkspaceDetail,
+}
+
+/// Workspace with the collections and environments it contains.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkspaceDetail {
+    /// Workspace unique ID.
+    pub id: String,
+    /// Workspace name.
+    pub name: String,
+    /// Collections in the workspace.
+    #[serde(default)]
+    pub collections: Vec<WorkspaceResource>,
+    /// Environments in the workspace.
+    #[serde(default)]
+    pub environments: Vec<WorkspaceResource>,
+}
+
+/// Collection or environment listed in a workspace.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkspaceResource {
+    /// Resource ID.
+    pub id: String,
+    /// Resource UID (owner-id format).
+    pub uid: Option<String>,
+    /// Resource name.
+    pub name: Option<String>,
+}
+
+impl WorkspaceResource {
+    /// Whether this resource has the given ID or UID.
+    #[must_use]
+    pub fn matches(&self, id: &str) -> bool {
+        self.id == id || self.uid.as_deref() == Some(id)
+    }
+}
+
 // ============================================================================
 // Collection Types
 // ============================================================================
@@ -73,6 +114,24 @@ pub struct Collection {
     pub item: Option<Vec<CollectionItem>>,
 }
 
+/// Response wrapper for collection writes.
+#[derive(Debug, Deserialize)]
+pub struct CollectionRefResponse {
+    /// Written collection.
+    pub collection: CollectionRef,
+}
+
+/// Identifiers of a written collection.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CollectionRef {
+    /// Collection unique ID.
+    pub id: String,
+    /// Collection name.
+    pub name: String,
+    /// Collection UID (owner-id format).
+    pub uid: Option<String>,
+}
+
 /// Collection metadata.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CollectionInfo {
@@ -155,6 +214,110 @@ impl RequestUrl {
     }
 }
 
+// ============================================================================
+// Environment Types
+// ============================================================================
+
+/// Response wrapper for environments list.
+#[derive(Debug, Deserialize)]
+pub struct EnvironmentsResponse {
+    /// List of environments.
+    pub environments: Vec<EnvironmentSummary>,
+}
+
+/// Environment summary (from list endpoint).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnvironmentSummary {
+    /// Environment unique ID.
+    pub id: String,
+    /// Environment UID (owner-id format).
+    pub uid: Option<String>,
+    /// Environment name.
+    pub name: String,
+    /// Owner user ID.
+    pub owner: Option<String>,
+    /// Last update timestamp.
+    #[serde(rename = "updatedAt")]
+    pub updated_at: Option<String>,
+}
+
+/// Response wrapper for single environment.
+#[derive(Debug, Deserialize)]
+pub struct EnvironmentResponse {
+    /// Full environment data.
+    pub environment: Environment,
+}
+
+/// Environment with variables, in the format Newman accepts.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Environment {
+    /// Environment unique ID.
+    pub id: Option<String>,
+    /// Environment name.
+    pub name: String,
+    /// Variables.
+    #[serde(default)]
+    pub values: Vec<EnvironmentVariable>,
+}
+
+/// Environment variable.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EnvironmentVariable {
+    /// Variable name.
+    pub key: String,
+    /// Variable value.
+    #[serde(default)]
+    pub value: String,
+    /// Whether the variable is used.
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// Variable type ("default" or "secret").
+    #[serde(rename = "type", default = "default_variable_type")]
+    pub variable_type: String,
+}
+
+const fn default_enabled() -> bool {
+    true
+}
+
+fn default_variable_type() -> String {
+    "default".to_string()
+}
+
+/// Name and variables for creating or replacing an environment.
+#[derive(Debug, Clone, Serialize)]
+pub struct EnvironmentInput {
+    /// Environment name.
+    pub name: String,
+    /// Variables.
+    pub values: Vec<EnvironmentVariable>,
+}
+
+/// Request body wrapper for environment writes.
+#[derive(Debug, Serialize)]
+pub struct EnvironmentBody<'a> {
+    /// Environment to write.
+    pub environment: &'a EnvironmentInput,
+}
+
+/// Response wrapper for environment writes.
+#[derive(Debug, Deserialize)]
+pub struct EnvironmentRefResponse {
+    /// Written environment.
+    pub environment: EnvironmentRef,
+}
+
+/// Identifiers of a written environment.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnvironmentRef {
+    /// Environment unique ID.
+    pub id: String,
+    /// Environment name.
+    pub name: String,
+    /// Environment UID (owner-id format).
+    pub uid: Option<String>,
+}
+
 // ============================================================================
 // Search Types
 // ============================================================================
@@ -209,6 +372,23 @@ mod tests {
         assert_eq!(url.as_string(), "api.example.com/users/123");
     }
 
+    #[test]
+    fn test_deserialize_environment_defaults() {
+        let json = r#"{
+            "id": "env-1",
+            "name": "Staging",
+            "values": [
+                {"key": "baseUrl", "value": "https://staging.example.com"},
+                {"key": "token", "value": "s3cret", "enabled": false, "type": "secret"}
+            ]
+        }"#;
+        let environment: Environment = serde_json::from_str(json).unwrap();
+        assert_eq!(environment.values.len(), 2);
+        assert!(environment.values[0].enabled);
+        assert_eq!(environment.values[0].variable_type, "default");
+        assert_eq!(environment.values[1].variable_type, "secret");
+    }
+
     #[test]
     fn test_deserialize_workspace() {
         let json = r#"{"id": "ws-123", "name": "My Workspace", "type": "personal"}"#;
@@ -0,0 +1,422 @@
+//! Splunk REST search API client.
+//!
+//! Splunk Enterprise exposes search jobs on its management port. A search is
+//! submitted as a job, polled until it finishes, and its results fetched as
+//! JSON. Splunk Cloud stacks without API access keep using prepared queries.
+
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+use reqwest::{Client, Method, RequestBuilder, Response, StatusCode};
+use serde::Deserialize;
+use serde_json::{Map, Value};
+use tokio::time::sleep;
+use tracing::{debug, warn};
+
+use crate::error::SplunkError;
+use crate::types::{LogEntry, QueryResult};
+
+/// Default per-request timeout in seconds.
+const DEFAULT_REQUEST_TIMEOUT_SECS: u64 = 30;
+
+/// Default limit for a whole search job.
+pub const DEFAULT_SEARCH_TIMEOUT_SECS: u64 = 60;
+
+/// Delay between job status checks.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Result fields tried, in order, for the log level.
+const LEVEL_FIELDS: [&str; 3] = ["log_level", "level", "severity"];
+
+/// Splunk REST search API client.
+#[derive(Clone)]
+pub struct SplunkApiClient {
+    http_client: Client,
+    base_url: String,
+    token: String,
+    search_timeout: Duration,
+}
+
+/// Status of a search job.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchJobStatus {
+    /// Dispatch state (`QUEUED`, `RUNNING`, `DONE`, `FAILED`, ...).
+    pub dispatch_state: String,
+    /// Whether the job finished.
+    pub is_done: bool,
+    /// Whether the job failed.
+    pub is_failed: bool,
+    /// Results produced so far.
+    pub result_count: i64,
+    /// Error messages reported by Splunk.
+    pub messages: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct CreateJobResponse {
+    sid: String,
+}
+
+#[derive(Deserialize)]
+struct JobResponse {
+    entry: Vec<JobEntry>,
+}
+
+#[derive(Deserialize)]
+struct JobEntry {
+    content: JobContent,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JobContent {
+    #[serde(default)]
+    dispatch_state: String,
+    #[serde(default)]
+    is_done: bool,
+    #[serde(default)]
+    is_failed: bool,
+    #[serde(default)]
+    result_count: i64,
+    #[serde(default)]
+    messages: Vec<JobMessage>,
+}
+
+#[derive(Deserialize)]
+struct JobMessage {
+    #[serde(rename = "type", default)]
+    kind: String,
+    #[serde(default)]
+    text: String,
+}
+
+#[derive(Deserialize)]
+struct ResultsResponse {
+    #[serde(default)]
+    results: Vec<Map<String, Value>>,
+}
+
+impl SplunkApiClient {
+    /// Create a client for the management API.
+    ///
+    /// # Arguments
+    /// * `base_url` - Management API URL (e.g., "<https://splunk.example.com:8089>")
+    /// * `token` - Splunk authentication token
+    ///
+    /// # Panics
+    /// Panics if the HTTP client cannot be created.
+    #[must_use]
+    #[allow(clippy::expect_used)]
+    pub fn new(base_url: &str, token: String) -> Self {
+        let http_client = Client::builder()
+            .timeout(Duration::from_secs(DEFAULT_REQUEST_TIMEOUT_SECS))
+            .build()
+            .expect("Failed to create HTTP client");
+
+        Self {
+            http_client,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            token,
+            search_timeout: Duration::from_secs(DEFAULT_SEARCH_TIMEOUT_SECS),
+        }
+    }
+
+    /// Set how long a search job may run before it is cancelled.
+    #[must_use]
+    pub const fn with_search_timeout(mut self, timeout: Duration) -> Self {
+        self.search_timeout = timeout;
+        self
+    }
+
+    /// Get the base URL.
+    #[must_use]
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Run a search and return its results as log entries.
+    ///
+    /// Submits a job, waits for it to finish and fetches up to `limit` rows.
+    /// A job still running after the search timeout is cancelled.
+    ///
+    /// # Errors
+    /// Returns error if the job cannot be created, fails, times out, or the
+    /// results cannot be read.
+    pub async fn search(
+        &self,
+        query: &str,
+        time_start: DateTime<Utc>,
+        time_end: DateTime<Utc>,
+        index: Option<&str>,
+        limit: usize,
+    ) -> Result<QueryResult, SplunkError> {
+        let started = Instant::now();
+        let spl = search_command(query, index);
+        let sid = self.create_search_job(&spl, time_start, time_end).await?;
+
+        let status = match self.wait_for_job(&sid).await {
+            Ok(status) => status,
+            Err(e) => {
+                if let Err(cancel_error) = self.cancel_job(&sid).await {
+                    warn!(sid = %sid, error = %cancel_error, "Failed to cancel Splunk search job");
+                }
+                return Err(e);
+            }
+        };
+
+        let entries = self.job_results(&sid, limit, time_end).await?;
+        let total_count = status
+            .result_count
+            .max(i64::try_from(entries.len()).unwrap_or(i64::MAX));
+        Ok(QueryResult {
+            query: spl,
+            truncated: total_count > i64::try_from(entries.len()).unwrap_or(i64::MAX),
+            entries,
+            total_count,
+            execution_time_ms: i64::try_from(started.elapsed().as_millis()).unwrap_or(i64::MAX),
+        })
+    }
+
+    /// Submit a search job.
+    ///
+    /// # Errors
+    /// Returns error if the API call fails.
+    pub async fn create_search_job(
+        &self,
+        spl: &str,
+        time_start: DateTime<Utc>,
+        time_end: DateTime<Utc>,
+    ) -> Result<String, SplunkError> {
+        debug!(spl = %spl, "Creating Splunk search job");
+        let form = [
+            ("search", spl.to_string()),
+            ("earliest_time", time_start.timestamp().to_string()),
+            ("latest_time", time_end.timestamp().to_string()),
+            ("output_mode", "json".to_string()),
+        ];
+        let response = self
+            .send(
+                self.request(Method::POST, "/services/search/jobs")
+                    .form(&form),
+            )
+            .await?;
+        let job: CreateJobResponse = parse(response).await?;
+        Ok(job.sid)
+    }
+
+    /// Get the status of a search job.
+    ///
+    /// # Errors
+    /// Returns error if the job is not found or the API call fails.
+    pub async fn job_status(&self, sid: &str) -> Result<SearchJobStatus, SplunkError> {
+        let response = self
+            .send(
+                self.request(Method::GET, &format!("/services/search/jobs/{sid}"))
+                    .query(&[("output_mode", "json")]),
+            )
+            .await?;
+        let job: JobResponse = parse(response).await?;
+        let content = job
+            .entry
+            .into_iter()
+            .next()
+            .ok_or_else(|| SplunkError::Parse("Job response has no entry".to_string()))?
+            .content;
+        Ok(SearchJobStatus {
+            dispatch_state: content.dispatch_state,
+            is_done: content.is_done,
+            is_failed: content.is_failed,
+            result_count: content.result_count,
+            messages: content
+                .messages
+                .into_iter()
+                .filter(|m| matches!(m.kind.as_str(), "FATAL" | "ERROR"))
+                .map(|m| m.text)
+                .collect(),
+        })
+    }
+
+    /// Cancel a search job and discard its results.
+    ///
+    /// # Errors
+    /// Returns error if the API call fails.
+    pub async fn cancel_job(&self, sid: &str) -> Result<(), SplunkError> {
+        debug!(sid = %sid, "Cancelling Splunk search job");
+        self.send(self.request(Method::DELETE, &format!("/services/search/jobs/{sid}")))
+            .await?;
+        Ok(())
+    }
+
+    async fn wait_for_job(&self, sid: &str) -> Result<SearchJobStatus, SplunkError> {
+        let deadline = Instant::now() + self.search_timeout;
+        loop {
+            let status = self.job_status(sid).await?;
+            if status.is_failed || status.dispatch_state == "FAILED" {
+                return Err(SplunkError::SearchFailed(if status.messages.is_empty() {
+                    format!("job {sid} failed")
+                } else {
+                    status.messages.join("; ")
+                }));
+            }
+            if status.is_done {
+                return Ok(status);
+            }
+            if Instant::now() >= deadline {
+                return Err(SplunkError::Timeout(self.search_timeout.as_secs()));
+            }
+            sleep(POLL_INTERVAL).await;
+        }
+    }
+
+    async fn job_results(
+        &self,
+        sid: &str,
+        limit: usize,
+        fallback_time: DateTime<Utc>,
+    ) -> Result<Vec<LogEntry>, SplunkError> {
+        let response = self
+            .send(
+                self.request(Method::GET, &format!("/services/search/jobs/{sid}/results"))
+                    .query(&[("output_mode", "json"), ("count", &limit.to_string())]),
+            )
+            .await?;
+        let results: ResultsResponse = parse(response).await?;
+        Ok(results
+            .results
+            .iter()
+            .map(|row| log_entry_from_result(row, fallback_time))
+            .collect())
+    }
+
+    fn request(&self, method: Method, path: &str) -> RequestBuilder {
+        self.http_client
+            .request(method, format!("{}{path}", self.base_url))
+            .bearer_auth(&self.token)
+    }
+
+    async fn send(&self, request: RequestBuilder) -> Result<Response, SplunkError> {
+        let response = request.send().await?;
+        match response.status() {
+            status if status.is_success() => Ok(response),
+            StatusCode::UNAUTHORIZED => Err(SplunkError::Unauthorized),
+            status => Err(SplunkError::Api {
+                status,
+                body: response.text().await.unwrap_or_default(),
+            }),
+        }
+    }
+}
+
+async fn parse<T: serde::de::DeserializeOwned>(response: Response) -> Result<T, SplunkError> {
+    response
+        .json()
+        .await
+        .map_err(|e| SplunkError::Parse(e.to_string()))
+}
+
+/// Turn a query into a search command Splunk accepts.
+///
+/// The REST API needs an explicit leading command, which the search bar adds
+/// implicitly. An index is only added to plain searches that name none.
+#[must_use]
+pub fn search_command(query: &str, index: Option<&str>) -> String {
+    let query = query.trim();
+    if query.starts_with('|') {
+        return query.to_string();
+    }
+    let body = query
+        .strip_prefix("search ")
+        .or_else(|| query.strip_prefix("SEARCH "))
+        .unwrap_or(query)
+        .trim_start();
+    match index.filter(|i| !i.trim().is_empty()) {
+        Some(index) if !body.contains("index=") => format!("search index={} {body}", index.trim()),
+        _ => format!("search {body}"),
+    }
+}
+
+/// Map a result row to a log entry.
+///
+/// `_raw` becomes the message and the level is taken from the first of
+/// `log_level`, `level` or `severity` present. Rows without a parsable
+/// `_time` get `fallback_time`. Every field except `host`, `source` and
+/// Splunk's `_`-prefixed ones is kept in `fields`.
+#[must_use]
+pub fn log_entry_from_result(row: &Map<String, Value>, fallback_time: DateTime<Utc>) -> LogEntry {
+    let text = |key: &str| row.get(key).and_then(Value::as_str).map(str::to_string);
+
+    let timestamp = text("_time")
+        .and_then(|t| DateTime::parse_from_rfc3339(&t).ok())
+        .map_or(fallback_time, |t| t.with_timezone(&Utc));
+    let level = LEVEL_FIELDS
+        .iter()
+        .find_map(|key| text(key))
+        .map_or_else(|| "INFO".to_string(), |l| l.to_ascii_uppercase());
+    let message = text("_raw").or_else(|| text("message")).unwrap_or_default();
+    let fields = row
+        .iter()
+        .filter(|(key, _)| !key.starts_with('_') && !matches!(key.as_str(), "host" | "source"))
+        .map(|(key, value)| (key.clone(), value.clone()))
+        .collect();
+
+    LogEntry {
+        timestamp,
+        level,
+        message,
+        source: text("source"),
+        host: text("host"),
+        fields: Value::Object(fields),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_search_command() {
+        assert_eq!(search_command("error", None), "search error");
+        assert_eq!(search_command("  search error ", None), "search error");
+        assert_eq!(
+            search_command("error", Some("main")),
+            "search index=main error"
+        );
+        assert_eq!(
+            search_command("index=app error", Some("main")),
+            "search index=app error"
+        );
+        assert_eq!(
+            search_command("| tstats count", Some("main")),
+            "| tstats count"
+        );
+    }
+
+    #[test]
+    fn test_log_entry_from_result() {
+        let fallback = Utc::now();
+        let row = json!({
+            "_time": "2024-01-15T10:23:45.000+00:00",
+            "_raw": "NullPointerException in checkout",
+            "_cd": "1:2",
+            "log_level": "error",
+            "host": "web-01",
+            "source": "/var/log/app.log",
+            "ticket": "PROJ-1"
+        });
+        let Value::Object(row) = row else {
+            unreachable!()
+        };
+
+        let entry = log_entry_from_result(&row, fallback);
+        assert_eq!(entry.timestamp.to_rfc3339(), "2024-01-15T10:23:45+00:00");
+        assert_eq!(entry.level, "ERROR");
+        assert_eq!(entry.message, "NullPointerException in checkout");
+        assert_eq!(entry.host.as_deref(), Some("web-01"));
+        assert_eq!(entry.fields, json!({ "log_level": "error", "ticket": "PROJ-1" }));
+
+        let entry = log_entry_from_result(&Map::new(), fallback);
+        assert_eq!(entry.timestamp, fallback);
+        assert_eq!(entry.level, "INFO");
+    }
+}
@@ -1,5 +1,6 @@
 //! Splunk module error types.
 
+use reqwest::StatusCode;
 use thiserror::Error;
 
 /// Errors that can occur in the Splunk module.
@@ -20,4 +21,33 @@ pub enum SplunkError {
     /// Placeholder error.
     #[error("Missing placeholder value: {0}")]
     MissingPlaceholder(String),
+
+    /// Invalid or missing Splunk token.
+    #[error("Unauthorized - invalid Splunk token")]
+    Unauthorized,
+
+    /// Splunk API error with status code.
+    #[error("Splunk API error (HTTP {status}): {body}")]
+    Api {
+        /// HTTP status code.
+        status: StatusCode,
+        /// Response body.
+        body: String,
+    },
+
+    /// Network or connection error.
+    #[error("Network error: {0}")]
+    Network(#[from] reqwest::Error),
+
+    /// Response could not be parsed.
+    #[error("Failed to parse Splunk response: {0}")]
+    Parse(String),
+
+    /// Search job failed in Splunk.
+    #[error("Search failed: {0}")]
+    SearchFailed(String),
+
+    /// Search job did not finish in time.
+    #[error("Search did not finish within {0}s")]
+    Timeout(u64),
 }
@@ -1,19 +1,22 @@
 //! # QA PMS Splunk
 //!
-//! Splunk manual query integration for QA Intelligent PMS.
+//! Splunk query integration for QA Intelligent PMS.
 //!
 //! Epic 11: Provides:
 //! - Query template management (CRUD)
 //! - SPL query building with placeholders
 //! - Log display formatting
+//! - Optional search execution through the Splunk REST API
 //!
 //! Note: Splunk Cloud does not support direct API integration for log queries.
-//! This module provides a manual query interface with pre-built templates.
+//! Without API access, queries are prepared for the Splunk web interface.
 
+pub mod client;
 pub mod error;
 pub mod templates;
 pub mod types;
 
+pub use client::{SearchJobStatus, SplunkApiClient, DEFAULT_SEARCH_TIMEOUT_SECS};
 pub use error::SplunkError;
 pub use templates::QueryTemplateService;
 pub use types::*;
@@ -1,17 +1,58 @@
-//! Integration diagnostics service.
+//! Integration and system diagnostics service.
+//!
+//! Besides the integrations, the service checks the system the server runs
+//! on: database latency and connection pool use, free disk space where the
+//! configuration is kept, clock skew against an NTP server, and whether the
+//! integrations' HTTPS endpoints can be reached, through the proxy if one is
+//! set. Every result carries remediation hints when it fails.
 
 use chrono::Utc;
 use sqlx::PgPool;
-use std::time::Instant;
+use std::path::PathBuf;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tokio::net::UdpSocket;
 
 use crate::error::SupportError;
 use crate::repository::SupportRepository;
 use crate::types::{DiagnosticResult, DiagnosticsReport};
 
-/// Service for running integration diagnostics.
+/// Database round trip above which the database counts as slow.
+const DB_SLOW_MS: u64 = 500;
+
+/// Share of the pool in use at which the pool counts as saturated.
+const POOL_SATURATION: f64 = 0.9;
+
+/// Free disk space below which the disk check fails.
+const MIN_FREE_DISK_BYTES: u64 = 512 * 1024 * 1024;
+
+/// Free share of the disk below which the disk check fails.
+const MIN_FREE_DISK_RATIO: f64 = 0.05;
+
+/// Clock difference beyond which the clock check fails.
+const MAX_CLOCK_SKEW_MS: i64 = 5_000;
+
+/// How long to wait for the NTP server or an HTTPS endpoint.
+const NETWORK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Seconds from the NTP epoch (1900) to the Unix epoch (1970).
+const NTP_UNIX_OFFSET_SECS: u64 = 2_208_988_800;
+
+/// Where the system checks look.
+#[derive(Debug, Clone, Default)]
+pub struct SystemTargets {
+    /// Directory holding the configuration files
+    pub config_dir: Option<PathBuf>,
+    /// NTP server the clock is compared with, as `host:port`
+    pub ntp_server: Option<String>,
+    /// HTTPS URLs the server must be able to reach
+    pub tls_targets: Vec<String>,
+}
+
+/// Service for running integration and system diagnostics.
 pub struct DiagnosticsService {
     pool: PgPool,
     repo: SupportRepository,
+    system: SystemTargets,
 }
 
 impl DiagnosticsService {
@@ -19,10 +60,21 @@ impl DiagnosticsService {
     #[must_use] 
     pub fn new(pool: PgPool) -> Self {
         let repo = SupportRepository::new(pool.clone());
-        Self { pool, repo }
+        Self {
+            pool,
+            repo,
+            system: SystemTargets::default(),
+        }
     }
 
-    /// Run diagnostics on all integrations.
+    /// Set where the system checks look.
+    #[must_use]
+    pub fn with_system_targets(mut self, system: SystemTargets) -> Self {
+        self.system = system;
+        self
+    }
+
+    /// Run diagnostics on all integrations and the system.
     pub async fn run_all_diagnostics(&self) -> Result<DiagnosticsReport, SupportError> {
         let mut results = Vec::new();
 
@@ -38,13 +90,18 @@ impl DiagnosticsService {
         // Check Testmo integration
         results.push(self.check_testmo().await);
 
+        // Check the host
+        results.push(self.check_disk());
+        results.push(self.check_clock().await);
+        results.push(self.check_outbound_tls().await);
+
         let overall_healthy = results.iter().all(|r| r.passed);
         let failed_count = results.iter().filter(|r| !r.passed).count();
 
         let summary = if overall_healthy {
-            "All integrations are healthy".to_string()
+            "All checks passed".to_string()
         } else {
-            format!("{failed_count} integration(s) have issues")
+            format!("{failed_count} check(s) have issues")
         };
 
         Ok(DiagnosticsReport {
@@ -55,20 +112,23 @@ impl DiagnosticsService {
         })
     }
 
-    /// Run diagnostics for a specific integration.
+    /// Run diagnostics for a specific integration or system check.
     pub async fn run_diagnostic(&self, integration: &str) -> Result<DiagnosticResult, SupportError> {
         match integration.to_lowercase().as_str() {
             "database" | "db" => Ok(self.check_database().await),
             "jira" => Ok(self.check_jira().await),
             "postman" => Ok(self.check_postman().await),
             "testmo" => Ok(self.check_testmo().await),
+            "disk" => Ok(self.check_disk()),
+            "clock" | "ntp" => Ok(self.check_clock().await),
+            "tls" | "network" => Ok(self.check_outbound_tls().await),
             _ => Err(SupportError::InvalidInput(format!(
                 "Unknown integration: {integration}"
             ))),
         }
     }
 
-    /// Check database connectivity and health.
+    /// Check database connectivity, latency and connection pool use.
     async fn check_database(&self) -> DiagnosticResult {
         let start = Instant::now();
         
@@ -83,16 +143,34 @@ impl DiagnosticsService {
             .await
             .unwrap_or(0);
 
+        let max = self.pool.options().get_max_connections();
+        let in_use = (self.pool.size() as usize).saturating_sub(self.pool.num_idle()) as u32;
+        let saturated = max > 0 && f64::from(in_use) / f64::from(max) >= POOL_SATURATION;
+        let slow = latency_ms > DB_SLOW_MS;
+
         match check_result {
-            Ok(_) => DiagnosticResult {
-                integration: "Database".to_string(),
-                passed: true,
-                message: "Database connection successful".to_string(),
-                latency_ms: Some(latency_ms),
-                recent_error_count,
-                suggestions: vec![],
-                checked_at: Utc::now(),
-            },
+            Ok(_) => {
+                let mut suggestions = Vec::new();
+                if slow {
+                    suggestions.push("Check the database server for load and slow queries".to_string());
+                    suggestions.push("Check network latency to the database host".to_string());
+                }
+                if saturated {
+                    suggestions.push("Raise DATABASE_MAX_CONNECTIONS if the server allows more connections".to_string());
+                    suggestions.push("Look for long-running requests holding connections".to_string());
+                }
+                DiagnosticResult {
+                    integration: "Database".to_string(),
+                    passed: !slow && !saturated,
+                    message: format!(
+                        "Database responded in {latency_ms} ms; {in_use} of {max} pooled connections in use"
+                    ),
+                    latency_ms: Some(latency_ms),
+                    recent_error_count,
+                    suggestions,
+                    checked_at: Utc::now(),
+                }
+            }
             Err(e) => DiagnosticResult {
                 integration: "Database".to_string(),
                 passed: false,
@@ -317,4 +395,295 @@ impl DiagnosticsService {
             },
         }
     }
+
+    /// Check free disk space on the volume holding the configuration.
+    fn check_disk(&self) -> DiagnosticResult {
+        let result = |passed: bool, message: String, suggestions: Vec<String>| DiagnosticResult {
+            integration: "Disk".to_string(),
+            passed,
+            message,
+            latency_ms: None,
+            recent_error_count: 0,
+            suggestions,
+            checked_at: Utc::now(),
+        };
+
+        let Some(dir) = self.system.config_dir.as_deref() else {
+            return result(
+                false,
+                "Configuration directory could not be determined".to_string(),
+                vec!["Set HOME (or XDG_CONFIG_HOME) for the server process".to_string()],
+            );
+        };
+        // The directory may not exist before the first setup
+        let path = dir.ancestors().find(|p| p.exists()).unwrap_or(dir);
+        match fs4::statvfs(path) {
+            Ok(stats) => {
+                let passed = disk_has_room(stats.available_space(), stats.total_space());
+                result(
+                    passed,
+                    format!(
+                        "{} MiB free of {} MiB at {}",
+                        stats.available_space() / (1024 * 1024),
+                        stats.total_space() / (1024 * 1024),
+                        path.display()
+                    ),
+                    if passed {
+                        vec![]
+                    } else {
+                        vec![
+                            format!("Free space on the volume holding {}", path.display()),
+                            "Move old backups and report exports off the volume".to_string(),
+                        ]
+                    },
+                )
+            }
+            Err(e) => result(
+                false,
+                format!("Failed to read disk space at {}: {e}", path.display()),
+                vec![format!("Check that the server can read {}", path.display())],
+            ),
+        }
+    }
+
+    /// Check the local clock against the NTP server, or against the
+    /// database clock when the NTP server cannot be reached.
+    async fn check_clock(&self) -> DiagnosticResult {
+        let start = Instant::now();
+        let server = self.system.ntp_server.as_deref();
+        let ntp = match server {
+            Some(server) => Some(ntp_offset_ms(server).await),
+            None => None,
+        };
+        let (reference, offset, mut suggestions) = match ntp {
+            Some(Ok(offset)) => (format!("NTP server {}", server.unwrap_or_default()), Ok(offset), vec![]),
+            Some(Err(e)) => (
+                "database clock".to_string(),
+                self.database_offset_ms().await,
+                vec![format!(
+                    "NTP server {} unreachable ({e}); allow outbound UDP port 123 or set DIAGNOSTICS_NTP_SERVER",
+                    server.unwrap_or_default()
+                )],
+            ),
+            None => ("database clock".to_string(), self.database_offset_ms().await, vec![]),
+        };
+        let latency_ms = start.elapsed().as_millis() as u64;
+
+        let (passed, message) = match offset {
+            Ok(offset) if offset.abs() <= MAX_CLOCK_SKEW_MS => {
+                (true, format!("Clock is {offset} ms off the {reference}"))
+            }
+            Ok(offset) => {
+                suggestions.push(
+                    "Enable time synchronization on the host (chrony or systemd-timesyncd)".to_string(),
+                );
+                suggestions.push("Expired-token and schedule errors may follow until the clock is fixed".to_string());
+                (false, format!("Clock is {offset} ms off the {reference}"))
+            }
+            Err(e) => {
+                suggestions.push("Check database connectivity".to_string());
+                (false, format!("Failed to compare the clock with the {reference}: {e}"))
+            }
+        };
+        DiagnosticResult {
+            integration: "Clock".to_string(),
+            passed,
+            message,
+            latency_ms: Some(latency_ms),
+            recent_error_count: 0,
+            suggestions,
+            checked_at: Utc::now(),
+        }
+    }
+
+    /// Local clock minus the database clock, in milliseconds.
+    async fn database_offset_ms(&self) -> Result<i64, String> {
+        let before = unix_ms();
+        let (db_ms,): (f64,) =
+            sqlx::query_as("SELECT EXTRACT(EPOCH FROM clock_timestamp())::FLOAT8 * 1000")
+                .fetch_one(&self.pool)
+                .await
+                .map_err(|e| e.to_string())?;
+        let after = unix_ms();
+        Ok((before + after) / 2 - db_ms as i64)
+    }
+
+    /// Check that each HTTPS target completes a TLS handshake, through the
+    /// proxy from the environment when one is set.
+    async fn check_outbound_tls(&self) -> DiagnosticResult {
+        let start = Instant::now();
+        let via = proxy_from_env().map_or_else(String::new, |name| format!(" through the proxy in {name}"));
+        let result = |passed: bool, message: String, suggestions: Vec<String>| DiagnosticResult {
+            integration: "Outbound TLS".to_string(),
+            passed,
+            message,
+            latency_ms: Some(start.elapsed().as_millis() as u64),
+            recent_error_count: 0,
+            suggestions,
+            checked_at: Utc::now(),
+        };
+        if self.system.tls_targets.is_empty() {
+            return result(true, "No outbound endpoints configured".to_string(), vec![]);
+        }
+
+        let client = match reqwest::Client::builder().timeout(NETWORK_TIMEOUT).build() {
+            Ok(client) => client,
+            Err(e) => {
+                return result(
+                    false,
+                    format!("Failed to create an HTTPS client: {e}"),
+                    vec!["Check HTTPS_PROXY and ALL_PROXY for a malformed proxy URL".to_string()],
+                )
+            }
+        };
+
+        let mut reached = Vec::new();
+        let mut failed = Vec::new();
+        let mut suggestions = Vec::new();
+        for target in &self.system.tls_targets {
+            match client.head(target).send().await {
+                Ok(_) => reached.push(host_of(target)),
+                Err(e) => {
+                    let host = host_of(target);
+                    let hint = tls_failure_hint(&e, &host);
+                    if !suggestions.contains(&hint) {
+                        suggestions.push(hint);
+                    }
+                    failed.push(format!("{host} ({})", error_chain(&e)));
+                }
+            }
+        }
+
+        if failed.is_empty() {
+            result(true, format!("Reached {}{via}", reached.join(", ")), vec![])
+        } else {
+            result(false, format!("Could not reach {}{via}", failed.join("; ")), suggestions)
+        }
+    }
+}
+
+/// Whether there is enough free space on a disk.
+fn disk_has_room(available: u64, total: u64) -> bool {
+    available >= MIN_FREE_DISK_BYTES
+        && (total == 0 || available as f64 / total as f64 >= MIN_FREE_DISK_RATIO)
+}
+
+/// Milliseconds since the Unix epoch by the local clock.
+fn unix_ms() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_millis() as i64)
+}
+
+/// Local clock minus the NTP server's, in milliseconds, from one SNTP query.
+async fn ntp_offset_ms(server: &str) -> Result<i64, String> {
+    let socket = UdpSocket::bind("0.0.0.0:0").await.map_err(|e| e.to_string())?;
+    socket.connect(server).await.map_err(|e| e.to_string())?;
+
+    // Version 4, client mode
+    let mut request = [0u8; 48];
+    request[0] = 0x23;
+    let sent = unix_ms();
+    socket.send(&request).await.map_err(|e| e.to_string())?;
+
+    let mut response = [0u8; 48];
+    let received = tokio::time::timeout(NETWORK_TIMEOUT, socket.recv(&mut response))
+        .await
+        .map_err(|_| "timed out".to_string())?
+        .map_err(|e| e.to_string())?;
+    let arrived = unix_ms();
+    if received < 48 {
+        return Err("short response".to_string());
+    }
+
+    let server_received = ntp_timestamp_ms(&response[32..40]);
+    let server_sent = ntp_timestamp_ms(&response[40..48]);
+    if server_sent == 0 {
+        return Err("server sent no time".to_string());
+    }
+    // Offset of the server from the local clock, negated
+    Ok(-((server_received - sent) + (server_sent - arrived)) / 2)
+}
+
+/// Unix milliseconds of an 8-byte NTP timestamp, or 0 when it is unset.
+fn ntp_timestamp_ms(bytes: &[u8]) -> i64 {
+    let mut seconds = [0u8; 4];
+    let mut fraction = [0u8; 4];
+    seconds.copy_from_slice(&bytes[..4]);
+    fraction.copy_from_slice(&bytes[4..8]);
+    let seconds = u64::from(u32::from_be_bytes(seconds));
+    if seconds == 0 {
+        return 0;
+    }
+    let millis = (u64::from(u32::from_be_bytes(fraction)) * 1000) >> 32;
+    (seconds.saturating_sub(NTP_UNIX_OFFSET_SECS) * 1000 + millis) as i64
+}
+
+/// Environment variable naming the proxy HTTPS requests go through.
+fn proxy_from_env() -> Option<&'static str> {
+    ["HTTPS_PROXY", "https_proxy", "ALL_PROXY", "all_proxy"]
+        .into_iter()
+        .find(|name| std::env::var(name).is_ok_and(|value| !value.is_empty()))
+}
+
+fn host_of(url: &str) -> String {
+    reqwest::Url::parse(url)
+        .ok()
+        .and_then(|url| url.host_str().map(str::to_string))
+        .unwrap_or_else(|| url.to_string())
+}
+
+/// Error with its causes, which name the TLS or connection failure.
+fn error_chain(error: &reqwest::Error) -> String {
+    let mut message = error.to_string();
+    let mut source = std::error::Error::source(error);
+    while let Some(cause) = source {
+        message.push_str(&format!(": {cause}"));
+        source = cause.source();
+    }
+    message
+}
+
+fn tls_failure_hint(error: &reqwest::Error, host: &str) -> String {
+    let chain = error_chain(error).to_lowercase();
+    if chain.contains("certificate") || chain.contains("tls") || chain.contains("handshake") {
+        format!(
+            "TLS handshake with {host} failed; if a proxy inspects HTTPS traffic, exclude {host} from inspection"
+        )
+    } else if chain.contains("dns") || chain.contains("resolve") || chain.contains("lookup") {
+        format!("{host} could not be resolved; check the server's DNS settings")
+    } else if error.is_timeout() {
+        format!("Timed out reaching {host}; check firewall rules, or set HTTPS_PROXY if traffic must go through a proxy")
+    } else {
+        format!("Could not connect to {host}; check firewall rules, HTTPS_PROXY and NO_PROXY")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_disk_needs_absolute_and_relative_room() {
+        const GIB: u64 = 1024 * 1024 * 1024;
+        assert!(disk_has_room(10 * GIB, 100 * GIB));
+        assert!(!disk_has_room(GIB / 4, GIB));
+        assert!(!disk_has_room(4 * GIB, 100 * GIB));
+    }
+
+    #[test]
+    fn test_ntp_timestamp_to_unix_millis() {
+        // 2024-01-01T00:00:00.5Z
+        let seconds = (1_704_067_200 + NTP_UNIX_OFFSET_SECS) as u32;
+        let mut bytes = seconds.to_be_bytes().to_vec();
+        bytes.extend_from_slice(&0x8000_0000_u32.to_be_bytes());
+        assert_eq!(ntp_timestamp_ms(&bytes), 1_704_067_200_500);
+        assert_eq!(ntp_timestamp_ms(&[0; 8]), 0);
+    }
+
+    #[test]
+    fn test_host_of_target() {
+        assert_eq!(host_of("https://acme.atlassian.net/rest"), "acme.atlassian.net");
+        assert_eq!(host_of("not a url"), "not a url");
+    }
 }
@@ -0,0 +1,326 @@
+//! Knowledge base drafts from resolved error logs.
+//!
+//! Once an error is resolved and its resolution written down, the fix can be
+//! kept as a knowledge base entry so the next occurrence is matched to it.
+//! The draft takes its title and symptoms from the error log and its solution
+//! from the resolution notes. With an AI client the draft is written by the
+//! model instead, falling back to the plain draft when that fails.
+
+use serde::{Deserialize, Serialize};
+use tracing::{debug, warn};
+use utoipa::ToSchema;
+
+use qa_pms_ai::{AIClient, ChatMessage, MessageRole};
+
+use crate::error::SupportError;
+use crate::types::{CreateKbEntryInput, ErrorLog, ErrorStatus};
+
+/// Longest draft title, in characters.
+const MAX_TITLE_CHARS: usize = 80;
+
+/// Longest related error pattern taken from the message, in characters.
+const MAX_PATTERN_CHARS: usize = 120;
+
+/// Prefixes of a resolution notes line stating the cause.
+const CAUSE_PREFIXES: &[&str] = &["root cause:", "cause:"];
+
+/// A knowledge base entry drafted from an error log.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct KbDraft {
+    /// Entry fields, ready to be created or edited first
+    pub entry: CreateKbEntryInput,
+    /// Whether an AI model wrote the draft
+    pub ai_assisted: bool,
+}
+
+/// Drafts knowledge base entries from resolved error logs.
+pub struct KbDrafter {
+    client: Option<AIClient>,
+}
+
+impl KbDrafter {
+    /// Create a drafter; without a client, drafts are built from the error
+    /// log fields alone.
+    #[must_use]
+    pub const fn new(client: Option<AIClient>) -> Self {
+        Self { client }
+    }
+
+    /// Draft an entry for a resolved error with resolution notes.
+    ///
+    /// # Errors
+    /// Returns `InvalidInput` if the error is not resolved or has no notes.
+    pub async fn draft(&self, error: &ErrorLog) -> Result<KbDraft, SupportError> {
+        let plain = plain_draft(error)?;
+        let Some(client) = &self.client else {
+            return Ok(KbDraft {
+                entry: plain,
+                ai_assisted: false,
+            });
+        };
+
+        let messages = vec![
+            ChatMessage {
+                id: uuid::Uuid::new_v4(),
+                role: MessageRole::System,
+                content: KB_DRAFT_SYSTEM_PROMPT.to_string(),
+                timestamp: chrono::Utc::now(),
+            },
+            ChatMessage {
+                id: uuid::Uuid::new_v4(),
+                role: MessageRole::User,
+                content: build_prompt(error),
+                timestamp: chrono::Utc::now(),
+            },
+        ];
+
+        debug!(error_id = %error.id, "Drafting knowledge base entry with AI");
+        match client.chat(messages).await {
+            Ok((response, _)) => match parse_response(&response.content, plain.clone()) {
+                Some(entry) => Ok(KbDraft {
+                    entry,
+                    ai_assisted: true,
+                }),
+                None => {
+                    warn!(error_id = %error.id, "AI draft was not valid JSON, using plain draft");
+                    Ok(KbDraft {
+                        entry: plain,
+                        ai_assisted: false,
+                    })
+                }
+            },
+            Err(e) => {
+                warn!(error_id = %error.id, error = %e, "AI draft failed, using plain draft");
+                Ok(KbDraft {
+                    entry: plain,
+                    ai_assisted: false,
+                })
+            }
+        }
+    }
+}
+
+/// Draft built from the error log fields and resolution notes.
+fn plain_draft(error: &ErrorLog) -> Result<CreateKbEntryInput, SupportError> {
+    let notes = resolution_notes(error)?;
+    let headline = error.message.lines().next().unwrap_or_default().trim();
+
+    let mut problem = format!("{} error: {headline}", capitalize(&error.source.to_string()));
+    if let Some(action) = &error.action {
+        problem.push_str(&format!("\nWhile: {action}"));
+    }
+    if let Some(page) = &error.page_url {
+        problem.push_str(&format!("\nPage: {page}"));
+    }
+    problem.push_str(&format!(
+        "\nSeen {} time(s) between {} and {}.",
+        error.occurrence_count,
+        error.first_seen_at.format("%Y-%m-%d"),
+        error.last_seen_at.format("%Y-%m-%d")
+    ));
+
+    let cause = notes
+        .lines()
+        .find_map(|line| {
+            let lower = line.trim().to_lowercase();
+            CAUSE_PREFIXES
+                .iter()
+                .find(|prefix| lower.starts_with(*prefix))
+                .map(|prefix| line.trim()[prefix.len()..].trim().to_string())
+        })
+        .filter(|cause| !cause.is_empty())
+        .unwrap_or_else(|| "Not recorded; see the solution.".to_string());
+
+    let mut tags = vec![error.source.to_string()];
+    if let Some(integration) = error.context.get("integration").and_then(|v| v.as_str()) {
+        tags.push(integration.to_lowercase());
+    }
+
+    Ok(CreateKbEntryInput {
+        title: truncate(headline, MAX_TITLE_CHARS),
+        problem,
+        cause,
+        solution: notes.to_string(),
+        related_errors: vec![truncate(headline, MAX_PATTERN_CHARS)],
+        tags,
+    })
+}
+
+/// Resolution notes of a resolved error.
+fn resolution_notes(error: &ErrorLog) -> Result<&str, SupportError> {
+    if error.status != ErrorStatus::Resolved {
+        return Err(SupportError::InvalidInput(
+            "Only resolved errors can be turned into knowledge base entries".to_string(),
+        ));
+    }
+    error
+        .resolution_notes
+        .as_deref()
+        .map(str::trim)
+        .filter(|notes| !notes.is_empty())
+        .ok_or_else(|| {
+            SupportError::InvalidInput("The error has no resolution notes to draft from".to_string())
+        })
+}
+
+fn build_prompt(error: &ErrorLog) -> String {
+    let mut prompt = format!(
+        "Error ({} source, {} severity, seen {} times):\n{}\n",
+        error.source, error.severity, error.occurrence_count, error.message
+    );
+    if let Some(action) = &error.action {
+        prompt.push_str(&format!("\nUser action: {action}\n"));
+    }
+    if let Some(stack) = &error.stack_trace {
+        let head: Vec<&str> = stack.lines().take(10).collect();
+        prompt.push_str(&format!("\nStack trace (top):\n{}\n", head.join("\n")));
+    }
+    prompt.push_str(&format!(
+        "\nResolution notes:\n{}\n\nProvide the entry as JSON.",
+        error.resolution_notes.as_deref().unwrap_or_default()
+    ));
+    prompt
+}
+
+/// Fields the model is asked for.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AiDraft {
+    title: String,
+    problem: String,
+    #[serde(default)]
+    cause: String,
+    solution: String,
+    #[serde(default)]
+    tags: Vec<String>,
+}
+
+/// Entry from the model's JSON answer, keeping the plain draft's related
+/// errors and filling blank fields from it.
+fn parse_response(content: &str, plain: CreateKbEntryInput) -> Option<CreateKbEntryInput> {
+    let start = content.find('{')?;
+    let end = content.rfind('}')?;
+    let draft: AiDraft = serde_json::from_str(content.get(start..=end)?).ok()?;
+    let pick = |ai: String, fallback: String| if ai.trim().is_empty() { fallback } else { ai.trim().to_string() };
+
+    let mut tags = plain.tags;
+    for tag in draft.tags {
+        let tag = tag.trim().to_lowercase();
+        if !tag.is_empty() && !tags.contains(&tag) {
+            tags.push(tag);
+        }
+    }
+
+    Some(CreateKbEntryInput {
+        title: truncate(&pick(draft.title, plain.title), MAX_TITLE_CHARS),
+        problem: pick(draft.problem, plain.problem),
+        cause: pick(draft.cause, plain.cause),
+        solution: pick(draft.solution, plain.solution),
+        related_errors: plain.related_errors,
+        tags,
+    })
+}
+
+fn truncate(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+    let cut: String = text.chars().take(max_chars - 1).collect();
+    format!("{}…", cut.trim_end())
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    chars.next().map_or_else(String::new, |first| {
+        first.to_uppercase().chain(chars).collect()
+    })
+}
+
+const KB_DRAFT_SYSTEM_PROMPT: &str = r#"You are a support engineer writing knowledge base articles from resolved incidents.
+
+Given an error and the notes describing how it was resolved, write an article another engineer can follow the next time the error appears.
+
+Respond with JSON only:
+{
+  "title": "Short title naming the problem (under 80 characters)",
+  "problem": "Symptoms: what the user sees and when",
+  "cause": "Root cause, or an empty string if the notes do not say",
+  "solution": "Numbered steps to resolve it",
+  "tags": ["lowercase", "keywords"]
+}
+
+Use only facts from the error and the notes. Do not include secrets, tokens or personal data."#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{ErrorSeverity, ErrorSource};
+    use chrono::Utc;
+
+    fn resolved(notes: Option<&str>) -> ErrorLog {
+        let now = Utc::now();
+        ErrorLog {
+            id: uuid::Uuid::new_v4(),
+            message: "Jira request failed: 401 Unauthorized\nat client.rs:42".to_string(),
+            stack_trace: None,
+            severity: ErrorSeverity::High,
+            source: ErrorSource::Integration,
+            status: ErrorStatus::Resolved,
+            user_id: None,
+            session_id: None,
+            page_url: Some("/tickets".to_string()),
+            action: Some("Loading tickets".to_string()),
+            browser_info: None,
+            device_info: None,
+            context: serde_json::json!({"integration": "Jira"}),
+            occurrence_count: 4,
+            first_seen_at: now,
+            last_seen_at: now,
+            resolution_notes: notes.map(str::to_string),
+            kb_entry_id: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    #[test]
+    fn test_plain_draft_uses_error_and_notes() {
+        let error = resolved(Some("Root cause: the OAuth token expired.\nReconnect Jira in Settings."));
+        let draft = plain_draft(&error).unwrap();
+
+        assert_eq!(draft.title, "Jira request failed: 401 Unauthorized");
+        assert!(draft.problem.starts_with("Integration error: Jira request failed"));
+        assert!(draft.problem.contains("While: Loading tickets"));
+        assert_eq!(draft.cause, "the OAuth token expired.");
+        assert!(draft.solution.contains("Reconnect Jira"));
+        assert_eq!(draft.related_errors, vec!["Jira request failed: 401 Unauthorized"]);
+        assert_eq!(draft.tags, vec!["integration", "jira"]);
+    }
+
+    #[test]
+    fn test_only_resolved_errors_with_notes_are_drafted() {
+        assert!(plain_draft(&resolved(None)).is_err());
+        assert!(plain_draft(&resolved(Some("  "))).is_err());
+
+        let mut open = resolved(Some("Fixed"));
+        open.status = ErrorStatus::Investigating;
+        assert!(plain_draft(&open).is_err());
+    }
+
+    #[test]
+    fn test_ai_response_fills_blanks_from_plain_draft() {
+        let plain = plain_draft(&resolved(Some("Reconnect Jira."))).unwrap();
+        let content = r#"Here you go:
+        {"title": "Jira token expired", "problem": "Tickets fail to load with 401", "cause": "",
+         "solution": "1. Reconnect Jira", "tags": ["OAuth", "jira"]}"#;
+
+        let entry = parse_response(content, plain.clone()).unwrap();
+        assert_eq!(entry.title, "Jira token expired");
+        assert_eq!(entry.cause, plain.cause);
+        assert_eq!(entry.related_errors, plain.related_errors);
+        assert_eq!(entry.tags, vec!["integration", "jira", "oauth"]);
+
+        assert!(parse_response("No JSON here", plain).is_none());
+    }
+}
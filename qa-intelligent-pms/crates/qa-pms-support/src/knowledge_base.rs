@@ -49,7 +49,7 @@ impl KnowledgeBaseService {
         suggestions.extend(diagnostic_suggestions);
 
         // 3. Sort by relevance
-        suggestions.sort_by(|a, b| b.relevance_score.cmp(&a.relevance_score));
+        suggestions.sort_by_key(|s| std::cmp::Reverse(s.relevance_score));
 
         // Limit to top 10 suggestions
         suggestions.truncate(10);
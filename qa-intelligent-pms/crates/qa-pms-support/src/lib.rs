@@ -3,8 +3,9 @@
 //! This crate provides:
 //! - Error log capture and storage
 //! - Support dashboard functionality
-//! - Integration diagnostics
+//! - Integration and system diagnostics
 //! - Knowledge base for common issues
+//! - Knowledge base drafts from resolved errors
 //! - Troubleshooting suggestions
 
 pub mod types;
@@ -12,9 +13,11 @@ pub mod error;
 pub mod repository;
 pub mod diagnostics;
 pub mod knowledge_base;
+pub mod kb_draft;
 
 pub use types::*;
 pub use error::SupportError;
 pub use repository::SupportRepository;
-pub use diagnostics::DiagnosticsService;
+pub use diagnostics::{DiagnosticsService, SystemTargets};
 pub use knowledge_base::KnowledgeBaseService;
+pub use kb_draft::{KbDraft, KbDrafter};
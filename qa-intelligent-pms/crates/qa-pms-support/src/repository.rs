@@ -1,16 +1,31 @@
 //! Database repository for support-related operations.
 
 use chrono::Utc;
+use qa_pms_core::types::PageRequest;
 use sqlx::PgPool;
 use uuid::Uuid;
 
 use crate::error::SupportError;
 use crate::types::{
-    CreateErrorLogInput, CreateKbEntryInput, ErrorLog, ErrorLogFilter, ErrorLogSort, ErrorSource, KnowledgeBaseEntry, Pagination,
+    CreateErrorLogInput, CreateKbEntryInput, ErrorLog, ErrorLogFilter, ErrorSource, KbSearchResult,
+    KnowledgeBaseEntry, Pagination,
     PaginatedResponse, SourceCount, SupportDashboardSummary, TopError, UpdateErrorStatusInput,
     UpdateKbEntryInput,
 };
 
+/// Full-text search document of a knowledge base entry, stored in its
+/// `search_vector` column (GIN indexed). The title weighs most, then the
+/// related errors and tags, then the problem and cause, then the solution.
+const KB_SEARCH_VECTOR: &str = "
+    setweight(to_tsvector('english', title), 'A')
+    || setweight(jsonb_to_tsvector('english', related_errors || tags, '[\"string\"]'), 'B')
+    || setweight(to_tsvector('english', problem || ' ' || cause), 'C')
+    || setweight(to_tsvector('english', solution), 'D')";
+
+/// Options for `ts_headline` when highlighting matches in search results.
+const KB_HEADLINE_OPTIONS: &str =
+    "StartSel=<mark>, StopSel=</mark>, MaxFragments=2, MaxWords=30, MinWords=10, FragmentDelimiter=\" … \"";
+
 /// Repository for support database operations.
 pub struct SupportRepository {
     pool: PgPool,
@@ -138,101 +153,104 @@ impl SupportRepository {
         error.ok_or(SupportError::ErrorLogNotFound(id))
     }
 
-    /// List error logs with filtering, sorting, and pagination.
+    /// List error logs by last occurrence, one keyset page at a time.
+    ///
+    /// Returns up to `page.fetch_limit()` rows; pass them to `PageRequest::finish`
+    /// keyed on `(last_seen_at, id)`.
     pub async fn list_error_logs(
         &self,
-        filter: ErrorLogFilter,
-        sort: ErrorLogSort,
-        pagination: Pagination,
-    ) -> Result<PaginatedResponse<ErrorLog>, SupportError> {
-        let offset = (pagination.page - 1) * pagination.per_page;
-
-        // Build dynamic query
-        let mut conditions = vec!["1=1".to_string()];
-        let mut params_count = 0;
+        filter: &ErrorLogFilter,
+        page: &PageRequest,
+    ) -> Result<Vec<ErrorLog>, SupportError> {
+        let mut conditions = Vec::new();
+        let mut params = 0;
+        let mut next_param = || {
+            params += 1;
+            params
+        };
 
         if filter.status.is_some() {
-            params_count += 1;
-            conditions.push(format!("status = ${params_count}::VARCHAR::error_status"));
+            conditions.push(format!("status = ${}::VARCHAR::error_status", next_param()));
         }
         if filter.severity.is_some() {
-            params_count += 1;
-            conditions.push(format!("severity = ${params_count}::VARCHAR::error_severity"));
+            conditions.push(format!("severity = ${}::VARCHAR::error_severity", next_param()));
         }
         if filter.source.is_some() {
-            params_count += 1;
-            conditions.push(format!("source = ${params_count}::VARCHAR::error_source"));
+            conditions.push(format!("source = ${}::VARCHAR::error_source", next_param()));
         }
         if filter.user_id.is_some() {
-            params_count += 1;
-            conditions.push(format!("user_id = ${params_count}"));
+            conditions.push(format!("user_id = ${}", next_param()));
         }
         if filter.search.is_some() {
-            params_count += 1;
-            conditions.push(format!("message ILIKE '%' || ${params_count} || '%'"));
+            conditions.push(format!("message ILIKE '%' || ${} || '%'", next_param()));
         }
         if filter.from_date.is_some() {
-            params_count += 1;
-            conditions.push(format!("last_seen_at >= ${params_count}"));
+            conditions.push(format!("last_seen_at >= ${}", next_param()));
         }
         if filter.to_date.is_some() {
-            params_count += 1;
-            conditions.push(format!("last_seen_at <= ${params_count}"));
+            conditions.push(format!("last_seen_at <= ${}", next_param()));
+        }
+        if let Some(keyset) = page.keyset_sql("last_seen_at", "id", next_param()) {
+            conditions.push(keyset);
         }
 
-        let where_clause = conditions.join(" AND ");
-        let order_clause = match sort {
-            ErrorLogSort::LastSeenDesc => "last_seen_at DESC",
-            ErrorLogSort::LastSeenAsc => "last_seen_at ASC",
-            ErrorLogSort::SeverityDesc => "CASE severity WHEN 'critical' THEN 1 WHEN 'high' THEN 2 WHEN 'medium' THEN 3 ELSE 4 END",
-            ErrorLogSort::OccurrenceDesc => "occurrence_count DESC",
+        let where_clause = if conditions.is_empty() {
+            String::new()
+        } else {
+            format!("WHERE {}", conditions.join(" AND "))
         };
-
-        // For simplicity, we'll use a simpler approach with optional bindings
-        // In production, consider using a query builder like sea-query
-        let _query = format!(
-            r#"
-            SELECT id, message, stack_trace, severity as "severity: ErrorSeverity",
-                   source as "source: ErrorSource", status as "status: ErrorStatus",
+        let sql = format!(
+            r"
+            SELECT id, message, stack_trace, severity::VARCHAR AS severity,
+                   source::VARCHAR AS source, status::VARCHAR AS status,
                    user_id, session_id, page_url, action, browser_info, device_info,
                    context, occurrence_count, first_seen_at, last_seen_at,
                    resolution_notes, kb_entry_id, created_at, updated_at
             FROM error_logs
-            WHERE {}
-            ORDER BY {}
-            LIMIT {} OFFSET {}
-            "#,
-            where_clause, order_clause, pagination.per_page, offset
+            {where_clause}
+            ORDER BY {order}
+            LIMIT {limit}
+            ",
+            order = page.order_sql("last_seen_at", "id"),
+            limit = page.fetch_limit(),
         );
 
-        // For now, use a simpler query without dynamic filtering
-        let errors: Vec<ErrorLog> = sqlx::query_as(
-            r#"
-            SELECT id, message, stack_trace, severity as "severity: ErrorSeverity",
-                   source as "source: ErrorSource", status as "status: ErrorStatus",
-                   user_id, session_id, page_url, action, browser_info, device_info,
-                   context, occurrence_count, first_seen_at, last_seen_at,
-                   resolution_notes, kb_entry_id, created_at, updated_at
-            FROM error_logs
-            ORDER BY last_seen_at DESC
-            LIMIT $1 OFFSET $2
-            "#,
-        )
-        .bind(pagination.per_page)
-        .bind(offset)
-        .fetch_all(&self.pool)
-        .await?;
+        // Unset filters have no placeholder, so only bind the ones in use
+        let mut query = sqlx::query_as::<_, ErrorLog>(&sql);
+        if let Some(status) = filter.status {
+            query = query.bind(status.to_string());
+        }
+        if let Some(severity) = filter.severity {
+            query = query.bind(severity.to_string());
+        }
+        if let Some(source) = filter.source {
+            query = query.bind(source.to_string());
+        }
+        if let Some(user_id) = filter.user_id {
+            query = query.bind(user_id);
+        }
+        if let Some(search) = &filter.search {
+            query = query.bind(search);
+        }
+        if let Some(from) = filter.from_date {
+            query = query.bind(from);
+        }
+        if let Some(to) = filter.to_date {
+            query = query.bind(to);
+        }
+        if let Some((at, id)) = page.keyset() {
+            query = query.bind(at).bind(id);
+        }
+
+        Ok(query.fetch_all(&self.pool).await?)
+    }
 
+    /// Count error logs.
+    pub async fn count_error_logs(&self) -> Result<i64, SupportError> {
         let total: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM error_logs")
             .fetch_one(&self.pool)
             .await?;
-
-        Ok(PaginatedResponse::new(
-            errors,
-            total.0,
-            pagination.page,
-            pagination.per_page,
-        ))
+        Ok(total.0)
     }
 
     /// Update an error log status.
@@ -422,6 +440,7 @@ impl SupportRepository {
         .bind(now)
         .fetch_one(&self.pool)
         .await?;
+        self.reindex_kb_entry(id).await?;
 
         Ok(entry)
     }
@@ -534,10 +553,86 @@ impl SupportRepository {
         .bind(input.tags.map(|v| serde_json::to_value(v).unwrap_or_default()))
         .fetch_one(&self.pool)
         .await?;
+        self.reindex_kb_entry(id).await?;
 
         Ok(entry)
     }
 
+    /// Search knowledge base entries by full text, most relevant first.
+    ///
+    /// The query takes web search syntax: quoted phrases, `or` and `-word`.
+    /// Results carry highlighted passages rather than the whole entry.
+    pub async fn search_kb_entries(
+        &self,
+        query: &str,
+        pagination: Pagination,
+    ) -> Result<PaginatedResponse<KbSearchResult>, SupportError> {
+        let offset = (pagination.page - 1) * pagination.per_page;
+
+        let results: Vec<KbSearchResult> = sqlx::query_as(
+            r"
+            SELECT id, title,
+                   ts_headline('english', title, query, 'HighlightAll=true, StartSel=<mark>, StopSel=</mark>')
+                       AS title_highlight,
+                   ts_headline('english', problem || E'\n' || cause || E'\n' || solution, query, $4)
+                       AS snippet,
+                   tags,
+                   ts_rank_cd(search_vector, query) AS rank,
+                   helpful_count, updated_at
+            FROM knowledge_base_entries, websearch_to_tsquery('english', $1) AS query
+            WHERE search_vector @@ query
+            ORDER BY rank DESC, helpful_count DESC, id
+            LIMIT $2 OFFSET $3
+            ",
+        )
+        .bind(query)
+        .bind(pagination.per_page)
+        .bind(offset)
+        .bind(KB_HEADLINE_OPTIONS)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let total: (i64,) = sqlx::query_as(
+            r"
+            SELECT COUNT(*)
+            FROM knowledge_base_entries
+            WHERE search_vector @@ websearch_to_tsquery('english', $1)
+            ",
+        )
+        .bind(query)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(PaginatedResponse::new(
+            results,
+            total.0,
+            pagination.page,
+            pagination.per_page,
+        ))
+    }
+
+    /// Index knowledge base entries that have no search document yet, e.g.
+    /// ones created before full-text search. Returns how many were indexed.
+    pub async fn reindex_kb_entries(&self) -> Result<u64, SupportError> {
+        let result = sqlx::query(&format!(
+            "UPDATE knowledge_base_entries SET search_vector = {KB_SEARCH_VECTOR} WHERE search_vector IS NULL"
+        ))
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Rebuild the search document of an entry from its stored fields.
+    async fn reindex_kb_entry(&self, id: Uuid) -> Result<(), SupportError> {
+        sqlx::query(&format!(
+            "UPDATE knowledge_base_entries SET search_vector = {KB_SEARCH_VECTOR} WHERE id = $1"
+        ))
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
     /// Delete a knowledge base entry.
     pub async fn delete_kb_entry(&self, id: Uuid) -> Result<(), SupportError> {
         let result = sqlx::query("DELETE FROM knowledge_base_entries WHERE id = $1")
@@ -280,6 +280,30 @@ pub struct UpdateKbEntryInput {
     pub tags: Option<Vec<String>>,
 }
 
+/// A knowledge base entry found by full-text search.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct KbSearchResult {
+    /// Entry ID
+    pub id: Uuid,
+    /// Title of the entry
+    pub title: String,
+    /// Title with the matched words wrapped in `<mark>` tags
+    pub title_highlight: String,
+    /// Passages of the problem, cause and solution around the matched words,
+    /// highlighted the same way
+    pub snippet: String,
+    /// Tags for categorization
+    #[sqlx(json)]
+    pub tags: Vec<String>,
+    /// Relevance to the query, higher is better
+    pub rank: f32,
+    /// Number of times marked as helpful
+    pub helpful_count: i32,
+    /// Updated timestamp
+    pub updated_at: DateTime<Utc>,
+}
+
 /// Result of an integration diagnostic check.
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
@@ -4,36 +4,65 @@
 
 use crate::error::TestmoError;
 use crate::types::{
-    CreateTestRunRequest, Project, ProjectsResponse, SearchResult, TestCase, TestCaseResponse,
-    TestCasesResponse, TestRun, TestRunResponse, TestSuite, TestSuitesResponse,
+    AddResultsRequest, AppendAutomationRequest, AutomationRun, AutomationRunResponse,
+    AutomationTest, BulkCreateFailure, BulkCreateReport, CreatedTestCase, CreateTestCaseRequest, CreateTestCasesRequest, CreateAutomationRunRequest, CreateMilestoneRequest, CreateTestRunRequest, Milestone, MilestoneResponse,
+    Page, Project, SearchResult, TestCase, TestCaseResponse,
+    TestCasesResponse, TestResult, TestResultInput, TestResultsResponse, TestRun, TestRunResponse,
+    TestSuite, UpdateTestCaseRequest,
 };
-use reqwest::Client;
+use futures::stream::{self, Stream, TryStreamExt};
+use qa_pms_core::{CircuitBreaker, RetryPolicy};
+use reqwest::{Client, Method, Response};
+use std::sync::Arc;
 use std::time::Duration;
-use tokio::time::sleep;
 use tracing::{debug, warn};
 
 /// Default request timeout in seconds.
 const DEFAULT_TIMEOUT_SECS: u64 = 10;
 
-/// Maximum retry attempts.
-const MAX_RETRIES: u32 = 3;
+/// Default page size for list endpoints.
+const DEFAULT_PAGE_SIZE: u32 = 100;
+
+/// Maximum number of cases sent in one bulk create request.
+pub const BULK_CREATE_BATCH_SIZE: usize = 100;
+
+/// Tunables for [`TestmoClient`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClientOptions {
+    /// Per-request timeout.
+    pub timeout: Duration,
+    /// Retry attempts and backoff for failed requests.
+    pub retry: RetryPolicy,
+    /// Items requested per page when listing.
+    pub page_size: u32,
+}
 
-/// Base delay for exponential backoff (1 second).
-const BASE_DELAY_SECS: u64 = 1;
+impl Default for ClientOptions {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(DEFAULT_TIMEOUT_SECS),
+            retry: RetryPolicy::default(),
+            page_size: DEFAULT_PAGE_SIZE,
+        }
+    }
+}
 
 /// Testmo API client.
 ///
 /// Provides methods for interacting with the Testmo API including
 /// listing projects, test suites, test cases, and creating test runs.
+/// List methods follow pagination until the last page.
 #[derive(Clone)]
 pub struct TestmoClient {
     http_client: Client,
     api_key: String,
     base_url: String,
+    options: ClientOptions,
+    circuit_breaker: Option<Arc<CircuitBreaker>>,
 }
 
 impl TestmoClient {
-    /// Create a new Testmo client.
+    /// Create a new Testmo client with default options.
     ///
     /// # Arguments
     /// * `base_url` - Testmo instance URL (e.g., "<https://company.testmo.net>")
@@ -43,8 +72,18 @@ impl TestmoClient {
     /// Panics if the HTTP client cannot be created.
     #[must_use]
     pub fn new(base_url: String, api_key: String) -> Self {
+        Self::with_options(base_url, api_key, ClientOptions::default())
+    }
+
+    /// Create a new Testmo client with custom timeouts, retries and page size.
+    ///
+    /// # Panics
+    /// Panics if the HTTP client cannot be created.
+    #[must_use]
+    #[allow(clippy::expect_used)]
+    pub fn with_options(base_url: String, api_key: String, options: ClientOptions) -> Self {
         let http_client = Client::builder()
-            .timeout(Duration::from_secs(DEFAULT_TIMEOUT_SECS))
+            .timeout(options.timeout)
             .build()
             .expect("Failed to create HTTP client");
 
@@ -55,15 +94,30 @@ impl TestmoClient {
             http_client,
             api_key,
             base_url,
+            options,
+            circuit_breaker: None,
         }
     }
 
+    /// Guard requests with a circuit breaker shared across clients.
+    #[must_use]
+    pub fn with_circuit_breaker(mut self, breaker: Arc<CircuitBreaker>) -> Self {
+        self.circuit_breaker = Some(breaker);
+        self
+    }
+
     /// Get the base URL.
     #[must_use]
     pub fn base_url(&self) -> &str {
         &self.base_url
     }
 
+    /// Get the client options.
+    #[must_use]
+    pub const fn options(&self) -> &ClientOptions {
+        &self.options
+    }
+
     /// Make an authenticated GET request with retry logic.
     async fn request<T: serde::de::DeserializeOwned>(
         &self,
@@ -81,29 +135,31 @@ impl TestmoClient {
                 .send()
                 .await?;
 
-            let status = response.status();
-
-            if status.is_success() {
-                let body = response.text().await?;
-                serde_json::from_str(&body).map_err(|e| {
-                    TestmoError::Parse(format!("{}: {}", e, &body[..200.min(body.len())]))
-                })
-            } else if status == reqwest::StatusCode::UNAUTHORIZED {
-                Err(TestmoError::Unauthorized)
-            } else if status == reqwest::StatusCode::NOT_FOUND {
-                Err(TestmoError::NotFound(endpoint.to_string()))
-            } else if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
-                Err(TestmoError::RateLimited)
-            } else {
-                let body = response.text().await.unwrap_or_default();
-                Err(TestmoError::ApiError { status, body })
-            }
+            parse_response(response, endpoint).await
         })
         .await
     }
 
     /// Make an authenticated POST request with retry logic.
     async fn post<T, B>(&self, endpoint: &str, body: &B) -> Result<T, TestmoError>
+    where
+        T: serde::de::DeserializeOwned,
+        B: serde::Serialize,
+    {
+        self.send_json(Method::POST, endpoint, body).await
+    }
+
+    /// Make an authenticated PUT request with retry logic.
+    async fn put<T, B>(&self, endpoint: &str, body: &B) -> Result<T, TestmoError>
+    where
+        T: serde::de::DeserializeOwned,
+        B: serde::Serialize,
+    {
+        self.send_json(Method::PUT, endpoint, body).await
+    }
+
+    /// Send a JSON body with the given method.
+    async fn send_json<T, B>(&self, method: Method, endpoint: &str, body: &B) -> Result<T, TestmoError>
     where
         T: serde::de::DeserializeOwned,
         B: serde::Serialize,
@@ -111,61 +167,74 @@ impl TestmoClient {
         let url = format!("{}/api/v1{}", self.base_url, endpoint);
 
         self.with_retry(|| async {
-            debug!(endpoint = %endpoint, "Making Testmo API POST request");
+            debug!(endpoint = %endpoint, method = %method, "Making Testmo API request");
 
             let response = self
                 .http_client
-                .post(&url)
+                .request(method.clone(), &url)
                 .bearer_auth(&self.api_key)
                 .json(body)
                 .send()
                 .await?;
 
-            let status = response.status();
-
-            if status.is_success() {
-                let body = response.text().await?;
-                serde_json::from_str(&body).map_err(|e| {
-                    TestmoError::Parse(format!("{}: {}", e, &body[..200.min(body.len())]))
-                })
-            } else if status == reqwest::StatusCode::UNAUTHORIZED {
-                Err(TestmoError::Unauthorized)
-            } else {
-                let body = response.text().await.unwrap_or_default();
-                Err(TestmoError::ApiError { status, body })
-            }
+            parse_response(response, endpoint).await
         })
         .await
     }
 
-    /// Execute a function with exponential backoff retry.
+    /// Execute a function with the configured retry policy.
+    ///
+    /// Rate-limited responses wait for the server's `Retry-After` when given.
+    ///
+    /// While the circuit breaker is open the request is not sent; a request
+    /// that still fails after its retries counts as one breaker failure.
     async fn with_retry<T, F, Fut>(&self, f: F) -> Result<T, TestmoError>
     where
         F: Fn() -> Fut,
         Fut: std::future::Future<Output = Result<T, TestmoError>>,
     {
-        let mut attempt = 0;
-
-        loop {
-            attempt += 1;
-            match f().await {
-                Ok(result) => return Ok(result),
-                Err(e) if e.is_retryable() && attempt < MAX_RETRIES => {
-                    let delay = Duration::from_secs(BASE_DELAY_SECS * 2u64.pow(attempt - 1));
-                    warn!(
-                        attempt = attempt,
-                        max_attempts = MAX_RETRIES,
-                        delay_ms = delay.as_millis(),
-                        error = %e,
-                        "Testmo API error, retrying"
-                    );
-                    sleep(delay).await;
-                }
-                Err(e) => return Err(e),
-            }
+        match &self.circuit_breaker {
+            Some(breaker) => breaker.call(self.retry(f), TestmoError::is_outage).await?,
+            None => self.retry(f).await,
         }
     }
 
+    /// Retry loop behind [`Self::with_retry`].
+    async fn retry<T, F, Fut>(&self, f: F) -> Result<T, TestmoError>
+    where
+        F: Fn() -> Fut,
+        Fut: std::future::Future<Output = Result<T, TestmoError>>,
+    {
+        self.options.retry.run("Testmo", f, TestmoError::retry_decision).await
+    }
+
+    /// Stream every item of a paginated list endpoint.
+    ///
+    /// Pages are fetched lazily as the stream is consumed.
+    fn paginate<'a, T>(&'a self, endpoint: String) -> impl Stream<Item = Result<T, TestmoError>> + 'a
+    where
+        T: serde::de::DeserializeOwned + 'a,
+    {
+        stream::try_unfold(Some(1u32), move |page| {
+            let endpoint = endpoint.clone();
+            async move {
+                let Some(page) = page else {
+                    return Ok::<_, TestmoError>(None);
+                };
+                let url = page_endpoint(&endpoint, page, self.options.page_size);
+                let response: Page<T> = self.request(&url).await?;
+                let next = response.next_page.filter(|next| *next > page && !response.data.is_empty());
+                Ok(Some((stream::iter(response.data.into_iter().map(Ok::<T, TestmoError>)), next)))
+            }
+        })
+        .try_flatten()
+    }
+
+    /// Collect every item of a paginated list endpoint.
+    async fn fetch_all<T: serde::de::DeserializeOwned>(&self, endpoint: String) -> Result<Vec<T>, TestmoError> {
+        self.paginate(endpoint).try_collect().await
+    }
+
     // ========================================================================
     // Project Operations
     // ========================================================================
@@ -178,9 +247,9 @@ impl TestmoClient {
     /// Returns error if the API call fails or response cannot be parsed.
     pub async fn list_projects(&self) -> Result<Vec<Project>, TestmoError> {
         debug!("Listing Testmo projects");
-        let response: ProjectsResponse = self.request("/projects").await?;
-        debug!(count = response.data.len(), "Retrieved projects");
-        Ok(response.data)
+        let projects: Vec<Project> = self.fetch_all("/projects".to_string()).await?;
+        debug!(count = projects.len(), "Retrieved projects");
+        Ok(projects)
     }
 
     // ========================================================================
@@ -197,9 +266,9 @@ impl TestmoClient {
     pub async fn list_test_suites(&self, project_id: i64) -> Result<Vec<TestSuite>, TestmoError> {
         let endpoint = format!("/projects/{project_id}/suites");
         debug!(project_id = project_id, "Listing Testmo test suites");
-        let response: TestSuitesResponse = self.request(&endpoint).await?;
-        debug!(count = response.data.len(), "Retrieved test suites");
-        Ok(response.data)
+        let suites: Vec<TestSuite> = self.fetch_all(endpoint).await?;
+        debug!(count = suites.len(), "Retrieved test suites");
+        Ok(suites)
     }
 
     // ========================================================================
@@ -221,20 +290,31 @@ impl TestmoClient {
         project_id: i64,
         suite_id: Option<i64>,
     ) -> Result<Vec<TestCase>, TestmoError> {
-        let endpoint = match suite_id {
-            Some(id) => format!("/projects/{project_id}/cases?suite_id={id}"),
-            None => format!("/projects/{project_id}/cases"),
-        };
-
         debug!(
             project_id = project_id,
             suite_id = suite_id,
             "Listing Testmo test cases"
         );
 
-        let response: TestCasesResponse = self.request(&endpoint).await?;
-        debug!(count = response.data.len(), "Retrieved test cases");
-        Ok(response.data)
+        let cases: Vec<TestCase> = self.stream_test_cases(project_id, suite_id).try_collect().await?;
+        debug!(count = cases.len(), "Retrieved test cases");
+        Ok(cases)
+    }
+
+    /// Stream test cases in a project page by page.
+    ///
+    /// Prefer this over [`Self::list_test_cases`] for large projects; pages
+    /// are only requested as the stream is consumed.
+    pub fn stream_test_cases(
+        &self,
+        project_id: i64,
+        suite_id: Option<i64>,
+    ) -> impl Stream<Item = Result<TestCase, TestmoError>> + '_ {
+        let endpoint = match suite_id {
+            Some(id) => format!("/projects/{project_id}/cases?suite_id={id}"),
+            None => format!("/projects/{project_id}/cases"),
+        };
+        self.paginate(endpoint)
     }
 
     /// Get test case details.
@@ -262,6 +342,102 @@ impl TestmoClient {
         Ok(response.data)
     }
 
+    /// Create many test cases.
+    ///
+    /// Cases are sent in batches of [`BULK_CREATE_BATCH_SIZE`]; rate-limited
+    /// batches are retried with backoff. When Testmo rejects a batch, its cases
+    /// are retried one by one so a single invalid case does not fail the rest.
+    /// Every failure is reported rather than returned as an error.
+    pub async fn create_test_cases(
+        &self,
+        project_id: i64,
+        cases: &[CreateTestCaseRequest],
+    ) -> BulkCreateReport {
+        let endpoint = format!("/projects/{project_id}/cases");
+        let mut report = BulkCreateReport::default();
+
+        debug!(project_id = project_id, count = cases.len(), "Bulk creating Testmo test cases");
+
+        for (batch_index, batch) in cases.chunks(BULK_CREATE_BATCH_SIZE).enumerate() {
+            let offset = batch_index * BULK_CREATE_BATCH_SIZE;
+            let result: Result<TestCasesResponse, TestmoError> =
+                self.post(&endpoint, &CreateTestCasesRequest { cases: batch }).await;
+
+            match result {
+                Ok(response) if response.data.len() == batch.len() => {
+                    report.created.extend(
+                        response
+                            .data
+                            .into_iter()
+                            .enumerate()
+                            .map(|(i, case)| CreatedTestCase { index: offset + i, case }),
+                    );
+                }
+                Ok(response) => {
+                    // Cannot tell which cases were dropped without positions
+                    let error = format!("Testmo created {} of {} cases", response.data.len(), batch.len());
+                    warn!(project_id = project_id, batch = batch_index, "{error}");
+                    report.failed.extend(batch_failures(batch, offset, &error));
+                }
+                Err(e) if batch.len() > 1 && is_rejected_batch(&e) => {
+                    warn!(error = %e, batch = batch_index, "Testmo rejected batch, creating cases individually");
+                    for (i, case) in batch.iter().enumerate() {
+                        self.create_single(&endpoint, case, offset + i, &mut report).await;
+                    }
+                }
+                Err(e) => report.failed.extend(batch_failures(batch, offset, &e.to_string())),
+            }
+        }
+
+        debug!(
+            created = report.created.len(),
+            failed = report.failed.len(),
+            "Bulk create finished"
+        );
+        report
+    }
+
+    async fn create_single(
+        &self,
+        endpoint: &str,
+        case: &CreateTestCaseRequest,
+        index: usize,
+        report: &mut BulkCreateReport,
+    ) {
+        let result: Result<TestCasesResponse, TestmoError> = self
+            .post(endpoint, &CreateTestCasesRequest { cases: std::slice::from_ref(case) })
+            .await;
+        match result.map(|r| r.data.into_iter().next()) {
+            Ok(Some(created)) => report.created.push(CreatedTestCase { index, case: created }),
+            Ok(None) => report.failed.extend(batch_failures(
+                std::slice::from_ref(case),
+                index,
+                "Testmo returned no case",
+            )),
+            Err(e) => report
+                .failed
+                .extend(batch_failures(std::slice::from_ref(case), index, &e.to_string())),
+        }
+    }
+
+    /// Update a test case.
+    ///
+    /// Only the fields set in `request` are changed.
+    ///
+    /// # Errors
+    /// Returns error if the test case is not found or API call fails.
+    pub async fn update_test_case(
+        &self,
+        project_id: i64,
+        case_id: i64,
+        request: &UpdateTestCaseRequest,
+    ) -> Result<TestCase, TestmoError> {
+        let endpoint = format!("/projects/{project_id}/cases/{case_id}");
+        debug!(project_id = project_id, case_id = case_id, "Updating Testmo test case");
+        let response: TestCaseResponse = self.put(&endpoint, request).await?;
+        Ok(response.data)
+    }
+
     // ========================================================================
     // Search Operations
     // ========================================================================
@@ -338,6 +514,38 @@ impl TestmoClient {
         Ok(results)
     }
 
+    // ========================================================================
+    // Milestone Operations
+    // ========================================================================
+
+    /// List milestones in a project.
+    ///
+    /// # Errors
+    /// Returns error if the API call fails or response cannot be parsed.
+    pub async fn list_milestones(&self, project_id: i64) -> Result<Vec<Milestone>, TestmoError> {
+        let endpoint = format!("/projects/{project_id}/milestones");
+        debug!(project_id = project_id, "Listing Testmo milestones");
+        let milestones: Vec<Milestone> = self.fetch_all(endpoint).await?;
+        debug!(count = milestones.len(), "Retrieved milestones");
+        Ok(milestones)
+    }
+
+    /// Create a milestone.
+    ///
+    /// # Errors
+    /// Returns error if the API call fails.
+    pub async fn create_milestone(
+        &self,
+        project_id: i64,
+        request: &CreateMilestoneRequest,
+    ) -> Result<Milestone, TestmoError> {
+        let endpoint = format!("/projects/{project_id}/milestones");
+        debug!(project_id = project_id, name = %request.name, "Creating Testmo milestone");
+        let response: MilestoneResponse = self.post(&endpoint, request).await?;
+        debug!(milestone_id = response.data.id, "Milestone created");
+        Ok(response.data)
+    }
+
     // ========================================================================
     // Test Run Operations
     // ========================================================================
@@ -350,6 +558,7 @@ impl TestmoClient {
     /// * `project_id` - Project ID to create the run in
     /// * `name` - Name for the test run
     /// * `case_ids` - Test case IDs to include in the run
+    /// * `milestone_id` - Optional milestone to group the run under
     ///
     /// # Errors
     /// Returns error if the API call fails.
@@ -358,6 +567,7 @@ impl TestmoClient {
         project_id: i64,
         name: &str,
         case_ids: &[i64],
+        milestone_id: Option<i64>,
     ) -> Result<TestRun, TestmoError> {
         let endpoint = format!("/projects/{project_id}/runs");
 
@@ -371,35 +581,167 @@ impl TestmoClient {
         let body = CreateTestRunRequest {
             name: name.to_string(),
             case_ids: case_ids.to_vec(),
+            milestone_id,
         };
 
         let response: TestRunResponse = self.post(&endpoint, &body).await?;
         debug!(run_id = response.data.id, "Test run created");
         Ok(response.data)
     }
-}
 
-/// Calculate match score for text against keywords.
-fn calculate_match_score(text: &str, keywords: &[String]) -> f32 {
-    if keywords.is_empty() {
-        return 0.0;
+    /// Get a test run.
+    ///
+    /// # Errors
+    /// Returns error if the run is not found or the API call fails.
+    pub async fn get_test_run(&self, run_id: i64) -> Result<TestRun, TestmoError> {
+        let endpoint = format!("/runs/{run_id}");
+        let response: TestRunResponse = self.request(&endpoint).await?;
+        Ok(response.data)
+    }
+
+    /// Get every result recorded in a test run.
+    ///
+    /// Includes earlier results of re-run cases; see
+    /// [`crate::RunResultCounts::from_results`] for per-case outcomes.
+    ///
+    /// # Errors
+    /// Returns error if the run is not found or the API call fails.
+    pub async fn get_test_run_results(&self, run_id: i64) -> Result<Vec<TestResult>, TestmoError> {
+        debug!(run_id = run_id, "Fetching Testmo test run results");
+        let results: Vec<TestResult> = self.fetch_all(format!("/runs/{run_id}/results")).await?;
+        debug!(count = results.len(), "Retrieved test run results");
+        Ok(results)
     }
 
-    let text_lower = text.to_lowercase();
-    let mut score = 0.0;
+    /// Submit results for test cases in a run.
+    ///
+    /// # Arguments
+    /// * `run_id` - Test run to record results in
+    /// * `results` - One result per test case
+    ///
+    /// # Errors
+    /// Returns error if the API call fails.
+    pub async fn add_results(
+        &self,
+        run_id: i64,
+        results: &[TestResultInput],
+    ) -> Result<Vec<TestResult>, TestmoError> {
+        let endpoint = format!("/runs/{run_id}/results");
 
-    for keyword in keywords {
-        let keyword_lower = keyword.to_lowercase();
-        if text_lower.contains(&keyword_lower) {
-            score += 1.0;
-            // Bonus for exact word match
-            if text_lower.split_whitespace().any(|w| w == keyword_lower) {
-                score += 0.5;
-            }
-        }
+        debug!(
+            run_id = run_id,
+            result_count = results.len(),
+            "Submitting Testmo test results"
+        );
+
+        let response: TestResultsResponse =
+            self.post(&endpoint, &AddResultsRequest { results }).await?;
+        debug!(stored = response.data.len(), "Test results submitted");
+        Ok(response.data)
+    }
+
+    // ========================================================================
+    // Automation Run Operations
+    // ========================================================================
+
+    /// Create an automation run.
+    ///
+    /// # Errors
+    /// Returns error if the API call fails.
+    pub async fn create_automation_run(
+        &self,
+        project_id: i64,
+        request: &CreateAutomationRunRequest,
+    ) -> Result<AutomationRun, TestmoError> {
+        let endpoint = format!("/projects/{project_id}/automation/runs");
+        debug!(project_id = project_id, name = %request.name, "Creating Testmo automation run");
+        let response: AutomationRunResponse = self.post(&endpoint, request).await?;
+        debug!(run_id = response.data.id, "Automation run created");
+        Ok(response.data)
     }
 
-    score
+    /// Append test results to an automation run.
+    ///
+    /// # Errors
+    /// Returns error if the API call fails.
+    pub async fn append_automation_results(
+        &self,
+        run_id: i64,
+        tests: &[AutomationTest],
+    ) -> Result<AutomationRun, TestmoError> {
+        let endpoint = format!("/automation/runs/{run_id}/append");
+        debug!(run_id = run_id, test_count = tests.len(), "Appending Testmo automation results");
+        let response: AutomationRunResponse =
+            self.post(&endpoint, &AppendAutomationRequest { tests }).await?;
+        Ok(response.data)
+    }
+
+    /// Mark an automation run as completed.
+    ///
+    /// # Errors
+    /// Returns error if the API call fails.
+    pub async fn complete_automation_run(&self, run_id: i64) -> Result<AutomationRun, TestmoError> {
+        let endpoint = format!("/automation/runs/{run_id}/complete");
+        debug!(run_id = run_id, "Completing Testmo automation run");
+        let response: AutomationRunResponse = self.post(&endpoint, &serde_json::json!({})).await?;
+        Ok(response.data)
+    }
+}
+
+/// Map a response to its JSON body or a typed error.
+async fn parse_response<T: serde::de::DeserializeOwned>(
+    response: Response,
+    endpoint: &str,
+) -> Result<T, TestmoError> {
+    let status = response.status();
+
+    if status.is_success() {
+        let body = response.text().await?;
+        serde_json::from_str(&body)
+            .map_err(|e| TestmoError::Parse(format!("{}: {}", e, &body[..200.min(body.len())])))
+    } else if status == reqwest::StatusCode::UNAUTHORIZED {
+        Err(TestmoError::Unauthorized)
+    } else if status == reqwest::StatusCode::NOT_FOUND {
+        Err(TestmoError::NotFound(endpoint.to_string()))
+    } else if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+        let retry_after = response
+            .headers()
+            .get(reqwest::header::RETRY_AFTER)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.trim().parse::<u64>().ok());
+        Err(TestmoError::RateLimited(retry_after))
+    } else {
+        let body = response.text().await.unwrap_or_default();
+        Err(TestmoError::ApiError { status, body })
+    }
+}
+
+/// Append page parameters to an endpoint that may already have a query.
+fn page_endpoint(endpoint: &str, page: u32, per_page: u32) -> String {
+    let separator = if endpoint.contains('?') { '&' } else { '?' };
+    format!("{endpoint}{separator}page={page}&per_page={per_page}")
+}
+
+/// Whether a batch failed because of its content rather than the connection.
+fn is_rejected_batch(error: &TestmoError) -> bool {
+    matches!(error, TestmoError::ApiError { status, .. } if status.is_client_error())
+}
+
+fn batch_failures(batch: &[CreateTestCaseRequest], offset: usize, error: &str) -> Vec<BulkCreateFailure> {
+    batch
+        .iter()
+        .enumerate()
+        .map(|(i, case)| BulkCreateFailure {
+            index: offset + i,
+            title: case.title.clone(),
+            error: error.to_string(),
+        })
+        .collect()
+}
+
+/// Calculate match score for text against keywords, tolerating typos.
+fn calculate_match_score(text: &str, keywords: &[String]) -> f32 {
+    qa_pms_core::match_score(text, keywords)
 }
 
 #[cfg(test)]
@@ -407,6 +749,76 @@ mod tests {
     use super::*;
     use crate::types::TestStep;
 
+    #[test]
+    fn test_page_endpoint_appends_to_existing_query() {
+        assert_eq!(page_endpoint("/projects", 1, 100), "/projects?page=1&per_page=100");
+        assert_eq!(
+            page_endpoint("/projects/1/cases?suite_id=4", 3, 50),
+            "/projects/1/cases?suite_id=4&page=3&per_page=50"
+        );
+    }
+
+    #[test]
+    fn test_page_next_page_defaults_to_last() {
+        let page: Page<Project> = serde_json::from_str(r#"{"data": []}"#).unwrap();
+        assert!(page.next_page.is_none());
+    }
+
+    #[test]
+    fn test_with_options_keeps_options() {
+        let options = ClientOptions {
+            timeout: Duration::from_secs(30),
+            page_size: 25,
+            ..ClientOptions::default()
+        };
+        let client = TestmoClient::with_options("https://x.testmo.net".into(), "key".into(), options);
+        assert_eq!(client.options().page_size, 25);
+        assert_eq!(client.options().retry, RetryPolicy::default());
+    }
+
+    #[tokio::test]
+    async fn test_open_circuit_skips_request() {
+        let breaker = Arc::new(CircuitBreaker::new("testmo", 1, Duration::from_secs(60)));
+        breaker.record_failure();
+        let client = TestmoClient::new("http://127.0.0.1:9".into(), "key".into())
+            .with_circuit_breaker(breaker);
+
+        let err = client.list_projects().await.unwrap_err();
+        assert!(matches!(err, TestmoError::CircuitOpen(_)));
+    }
+
+    #[test]
+    fn test_only_client_errors_reject_a_batch() {
+        let rejected = TestmoError::ApiError {
+            status: reqwest::StatusCode::UNPROCESSABLE_ENTITY,
+            body: "title is required".to_string(),
+        };
+        let server = TestmoError::ApiError {
+            status: reqwest::StatusCode::BAD_GATEWAY,
+            body: String::new(),
+        };
+        assert!(is_rejected_batch(&rejected));
+        assert!(!is_rejected_batch(&server));
+        assert!(!is_rejected_batch(&TestmoError::RateLimited(None)));
+    }
+
+    #[test]
+    fn test_batch_failures_keep_input_positions() {
+        let case = |title: &str| CreateTestCaseRequest {
+            title: title.to_string(),
+            folder_id: None,
+            preconditions: None,
+            priority_id: None,
+            steps: Vec::new(),
+            custom_fields: serde_json::Map::new(),
+        };
+        let failures = batch_failures(&[case("a"), case("b")], 100, "boom");
+        assert_eq!(failures.len(), 2);
+        assert_eq!(failures[1].index, 101);
+        assert_eq!(failures[1].title, "b");
+        assert_eq!(failures[1].error, "boom");
+    }
+
     #[test]
     fn test_new_strips_trailing_slash() {
         let client = TestmoClient::new(
@@ -471,6 +883,7 @@ mod tests {
             ]),
             created_at: "2024-01-01".to_string(),
             updated_at: "2024-01-01".to_string(),
+            custom_fields: serde_json::Map::new(),
         };
 
         // Title match should have highest weight
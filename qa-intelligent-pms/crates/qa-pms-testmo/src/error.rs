@@ -2,6 +2,9 @@
 //!
 //! Domain-specific error types for Testmo API operations.
 
+use std::time::Duration;
+
+use qa_pms_core::{is_retryable_status, CircuitOpenError, RetryDecision};
 use reqwest::StatusCode;
 use thiserror::Error;
 
@@ -16,9 +19,9 @@ pub enum TestmoError {
     #[error("Resource not found: {0}")]
     NotFound(String),
 
-    /// Rate limit exceeded.
+    /// Rate limit exceeded, with the `Retry-After` delay in seconds if sent.
     #[error("Rate limited - too many requests")]
-    RateLimited,
+    RateLimited(Option<u64>),
 
     /// Generic API error with status code.
     #[error("API error (HTTP {status}): {body}")]
@@ -33,19 +36,50 @@ pub enum TestmoError {
     #[error("Network error: {0}")]
     Network(#[from] reqwest::Error),
 
+    /// Request skipped because the circuit breaker is open.
+    #[error(transparent)]
+    CircuitOpen(#[from] CircuitOpenError),
+
     /// JSON parsing error.
     #[error("Failed to parse response: {0}")]
     Parse(String),
+
+    /// Local test case storage error during sync.
+    #[error("Local storage error: {0}")]
+    Storage(String),
 }
 
 impl TestmoError {
     /// Check if the error is retryable.
     ///
-    /// Returns `true` for rate limiting, network errors, and server errors (5xx).
+    /// Returns `true` for rate limiting, network errors, and transient
+    /// statuses (timeouts and 5xx).
     #[must_use]
     pub fn is_retryable(&self) -> bool {
         match self {
-            Self::RateLimited | Self::Network(_) => true,
+            Self::RateLimited(_) | Self::Network(_) => true,
+            Self::ApiError { status, .. } => is_retryable_status(status.as_u16()),
+            _ => false,
+        }
+    }
+
+    /// How the retry policy should handle this error.
+    #[must_use]
+    pub fn retry_decision(&self) -> RetryDecision {
+        match self {
+            Self::RateLimited(Some(seconds)) => RetryDecision::RetryAfter(Duration::from_secs(*seconds)),
+            _ if self.is_retryable() => RetryDecision::Retry,
+            _ => RetryDecision::Stop,
+        }
+    }
+
+    /// Check if the error means the service is down.
+    ///
+    /// Only these count towards opening the circuit breaker.
+    #[must_use]
+    pub fn is_outage(&self) -> bool {
+        match self {
+            Self::Network(_) => true,
             Self::ApiError { status, .. } => status.is_server_error(),
             _ => false,
         }
@@ -70,7 +104,7 @@ mod tests {
 
     #[test]
     fn test_rate_limited_is_retryable() {
-        let err = TestmoError::RateLimited;
+        let err = TestmoError::RateLimited(Some(5));
         assert!(err.is_retryable());
     }
 
@@ -83,6 +117,27 @@ mod tests {
         assert!(err.is_retryable());
     }
 
+    #[test]
+    fn test_only_network_and_server_errors_are_outages() {
+        let server = TestmoError::ApiError {
+            status: StatusCode::BAD_GATEWAY,
+            body: String::new(),
+        };
+        assert!(server.is_outage());
+        assert!(!TestmoError::NotFound("/x".to_string()).is_outage());
+        assert!(!TestmoError::RateLimited(None).is_outage());
+    }
+
+    #[test]
+    fn test_retry_decision_uses_retry_after() {
+        assert_eq!(
+            TestmoError::RateLimited(Some(7)).retry_decision(),
+            RetryDecision::RetryAfter(Duration::from_secs(7))
+        );
+        assert_eq!(TestmoError::RateLimited(None).retry_decision(), RetryDecision::Retry);
+        assert_eq!(TestmoError::Unauthorized.retry_decision(), RetryDecision::Stop);
+    }
+
     #[test]
     fn test_client_error_not_retryable() {
         let err = TestmoError::ApiError {
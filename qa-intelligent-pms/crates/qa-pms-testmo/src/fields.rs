@@ -0,0 +1,250 @@
+//! Custom field mapping.
+//!
+//! Testmo projects model priority, category, tags and preconditions with
+//! instance-specific custom fields (`custom_*`). A `FieldMapping` says which
+//! field each local attribute goes to and how its values translate, e.g.
+//! dropdown option IDs. Unmapped attributes use Testmo's built-in fields.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use qa_pms_core::test_cases::TestCase as LocalTestCase;
+
+use crate::types::{CreateTestCaseRequest, TestCase, TestStep, UpdateTestCaseRequest};
+
+/// Target custom field for one local attribute.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FieldTarget {
+    /// Testmo field key (e.g., "`custom_severity`")
+    pub field: String,
+    /// Local value to Testmo value; values not listed are sent as strings
+    #[serde(default)]
+    pub values: HashMap<String, Value>,
+}
+
+impl FieldTarget {
+    fn to_remote(&self, local: &str) -> Value {
+        self.values
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(local))
+            .map_or_else(|| Value::String(local.to_string()), |(_, value)| value.clone())
+    }
+
+    fn to_local(&self, remote: &Value) -> Option<String> {
+        if let Some((key, _)) = self.values.iter().find(|(_, value)| *value == remote) {
+            return Some(key.clone());
+        }
+        match remote {
+            Value::String(s) if !s.is_empty() => Some(s.clone()),
+            Value::Number(n) => Some(n.to_string()),
+            _ => None,
+        }
+    }
+}
+
+/// Built-in and custom field values derived from a local case.
+struct RemoteFields {
+    priority_id: Option<i32>,
+    preconditions: Option<String>,
+    custom_fields: Map<String, Value>,
+}
+
+/// Mapping from local test case attributes to a project's custom fields.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FieldMapping {
+    /// Priority field (default: built-in `priority_id`)
+    pub priority: Option<FieldTarget>,
+    /// Category field (not synced by default)
+    pub category: Option<FieldTarget>,
+    /// Tags field, sent as an array (not synced by default)
+    pub tags: Option<FieldTarget>,
+    /// Preconditions field (default: built-in `preconditions`)
+    pub preconditions: Option<FieldTarget>,
+}
+
+impl FieldMapping {
+    /// Parse a mapping from JSON.
+    ///
+    /// # Errors
+    /// Returns error if the JSON does not describe a mapping.
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    /// Build an update request from a local case.
+    #[must_use]
+    pub fn to_update_request(&self, case: &LocalTestCase) -> UpdateTestCaseRequest {
+        let fields = self.remote_fields(case);
+        UpdateTestCaseRequest {
+            title: Some(case.title.clone()),
+            preconditions: fields.preconditions,
+            priority_id: fields.priority_id,
+            steps: None,
+            custom_fields: fields.custom_fields,
+        }
+    }
+
+    /// Build a create request from a local case, including its steps.
+    #[must_use]
+    pub fn to_create_request(&self, case: &LocalTestCase, folder_id: Option<i64>) -> CreateTestCaseRequest {
+        let fields = self.remote_fields(case);
+        CreateTestCaseRequest {
+            title: case.title.clone(),
+            folder_id,
+            preconditions: fields.preconditions,
+            priority_id: fields.priority_id,
+            steps: case
+                .steps
+                .iter()
+                .map(|s| TestStep {
+                    content: s.action.clone(),
+                    expected: s.expected.clone(),
+                })
+                .collect(),
+            custom_fields: fields.custom_fields,
+        }
+    }
+
+    fn remote_fields(&self, case: &LocalTestCase) -> RemoteFields {
+        let mut custom_fields = Map::new();
+
+        let priority_id = match &self.priority {
+            Some(target) => {
+                if let Some(priority) = &case.priority {
+                    custom_fields.insert(target.field.clone(), target.to_remote(priority));
+                }
+                None
+            }
+            None => case.priority.as_deref().and_then(|p| p.parse().ok()),
+        };
+        let preconditions = match &self.preconditions {
+            Some(target) => {
+                if let Some(preconditions) = &case.preconditions {
+                    custom_fields.insert(target.field.clone(), Value::String(preconditions.clone()));
+                }
+                None
+            }
+            None => case.preconditions.clone(),
+        };
+        if let (Some(target), Some(category)) = (&self.category, &case.category) {
+            custom_fields.insert(target.field.clone(), target.to_remote(category));
+        }
+        if let Some(target) = &self.tags {
+            let tags = case.tags.iter().map(|t| target.to_remote(t)).collect();
+            custom_fields.insert(target.field.clone(), Value::Array(tags));
+        }
+
+        RemoteFields {
+            priority_id,
+            preconditions,
+            custom_fields,
+        }
+    }
+
+    /// Copy mapped attributes from a remote case onto a local one.
+    pub fn apply_from_remote(&self, remote: &TestCase, case: &mut LocalTestCase) {
+        let field = |target: &FieldTarget| remote.custom_fields.get(&target.field);
+
+        case.priority = match &self.priority {
+            Some(target) => field(target).and_then(|v| target.to_local(v)),
+            None => remote.priority_id.map(|p| p.to_string()),
+        };
+        case.preconditions = match &self.preconditions {
+            Some(target) => field(target).and_then(|v| target.to_local(v)),
+            None => remote.preconditions.clone(),
+        };
+        if let Some(target) = &self.category {
+            case.category = field(target).and_then(|v| target.to_local(v));
+        }
+        if let Some(target) = &self.tags {
+            case.tags = match field(target) {
+                Some(Value::Array(values)) => values.iter().filter_map(|v| target.to_local(v)).collect(),
+                Some(value) => target.to_local(value).into_iter().collect(),
+                None => Vec::new(),
+            };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn mapping() -> FieldMapping {
+        FieldMapping::from_json(
+            r#"{
+                "priority": { "field": "custom_severity", "values": { "high": 3, "low": 1 } },
+                "category": { "field": "custom_area" },
+                "tags": { "field": "custom_labels" }
+            }"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_to_update_request_uses_custom_fields() {
+        let mut case = LocalTestCase::new("Refund");
+        case.priority = Some("High".to_string());
+        case.category = Some("payments".to_string());
+        case.tags = vec!["smoke".to_string(), "api".to_string()];
+        case.preconditions = Some("Order paid".to_string());
+
+        let request = mapping().to_update_request(&case);
+        assert_eq!(request.priority_id, None);
+        assert_eq!(request.preconditions.as_deref(), Some("Order paid"));
+        assert_eq!(request.custom_fields["custom_severity"], json!(3));
+        assert_eq!(request.custom_fields["custom_area"], json!("payments"));
+        assert_eq!(request.custom_fields["custom_labels"], json!(["smoke", "api"]));
+    }
+
+    #[test]
+    fn test_to_create_request_includes_steps() {
+        let mut case = LocalTestCase::new("Refund");
+        case.priority = Some("low".to_string());
+        case.steps = vec![qa_pms_core::TestCaseStep {
+            action: "Request refund".to_string(),
+            expected: Some("Refund issued".to_string()),
+        }];
+
+        let request = mapping().to_create_request(&case, Some(9));
+        assert_eq!(request.folder_id, Some(9));
+        assert_eq!(request.steps[0].content, "Request refund");
+        assert_eq!(request.custom_fields["custom_severity"], json!(1));
+    }
+
+    #[test]
+    fn test_default_mapping_uses_builtin_fields() {
+        let mut case = LocalTestCase::new("Refund");
+        case.priority = Some("2".to_string());
+        case.category = Some("payments".to_string());
+
+        let request = FieldMapping::default().to_update_request(&case);
+        assert_eq!(request.priority_id, Some(2));
+        assert!(request.custom_fields.is_empty());
+    }
+
+    #[test]
+    fn test_apply_from_remote_reverses_values() {
+        let remote: TestCase = serde_json::from_value(json!({
+            "id": 1, "project_id": 1, "suite_id": null, "title": "Refund",
+            "preconditions": null, "priority_id": null, "type_id": null,
+            "template_id": null, "steps": null,
+            "created_at": "2024-01-01T00:00:00Z", "updated_at": "2024-01-01T00:00:00Z",
+            "custom_severity": 1,
+            "custom_area": "payments",
+            "custom_labels": ["smoke"]
+        }))
+        .unwrap();
+
+        let mut case = LocalTestCase::new("");
+        mapping().apply_from_remote(&remote, &mut case);
+        assert_eq!(case.priority.as_deref(), Some("low"));
+        assert_eq!(case.category.as_deref(), Some("payments"));
+        assert_eq!(case.tags, vec!["smoke".to_string()]);
+    }
+}
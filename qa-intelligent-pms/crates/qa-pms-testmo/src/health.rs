@@ -27,6 +27,7 @@ impl TestmoHealthCheck {
     /// * `base_url` - Testmo instance URL (e.g., "<https://company.testmo.net>")
     /// * `api_key` - Testmo API key
     #[must_use]
+    #[allow(clippy::expect_used)]
     pub fn new(base_url: String, api_key: String) -> Self {
         let http_client = Client::builder()
             .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS))
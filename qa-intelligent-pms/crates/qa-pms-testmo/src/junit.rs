@@ -0,0 +1,165 @@
+//! JUnit XML report parsing.
+//!
+//! Converts `<testsuite>`/`<testcase>` reports produced by most test runners
+//! into automation results for Testmo.
+
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+
+use crate::error::TestmoError;
+use crate::types::{AutomationTest, ResultStatus};
+
+/// Parse a JUnit XML report into automation test results.
+///
+/// Nested suites are supported; a test's folder is its `classname`, falling
+/// back to the innermost suite name.
+///
+/// # Errors
+/// Returns `TestmoError::Parse` if the document is not well-formed XML.
+pub fn parse_junit(xml: &str) -> Result<Vec<AutomationTest>, TestmoError> {
+    let mut reader = Reader::from_str(xml);
+    reader.config_mut().trim_text(true);
+
+    let mut tests = Vec::new();
+    let mut suites: Vec<String> = Vec::new();
+    let mut current: Option<AutomationTest> = None;
+    // Set while inside <failure>/<error> without a message attribute
+    let mut capture_message = false;
+
+    loop {
+        match reader.read_event().map_err(parse_error)? {
+            Event::Start(e) => match e.name().as_ref() {
+                b"testsuite" => suites.push(attribute(&e, b"name")?.unwrap_or_default()),
+                b"testcase" => current = Some(test_case(&e, suites.last())?),
+                b"failure" | b"error" => {
+                    if let Some(test) = current.as_mut() {
+                        mark_failed(test, &e)?;
+                        capture_message = test.message.is_none();
+                    }
+                }
+                b"skipped" => mark_skipped(current.as_mut()),
+                _ => {}
+            },
+            Event::Empty(e) => match e.name().as_ref() {
+                b"testcase" => tests.push(test_case(&e, suites.last())?),
+                b"failure" | b"error" => {
+                    if let Some(test) = current.as_mut() {
+                        mark_failed(test, &e)?;
+                    }
+                }
+                b"skipped" => mark_skipped(current.as_mut()),
+                _ => {}
+            },
+            Event::Text(text) if capture_message => {
+                if let Some(test) = current.as_mut() {
+                    test.message = Some(text.unescape().map_err(parse_error)?.into_owned());
+                }
+                capture_message = false;
+            }
+            Event::CData(data) if capture_message => {
+                if let Some(test) = current.as_mut() {
+                    test.message = Some(String::from_utf8_lossy(&data).into_owned());
+                }
+                capture_message = false;
+            }
+            Event::End(e) => match e.name().as_ref() {
+                b"testsuite" => {
+                    suites.pop();
+                }
+                b"testcase" => tests.extend(current.take()),
+                b"failure" | b"error" => capture_message = false,
+                _ => {}
+            },
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    Ok(tests)
+}
+
+fn parse_error(e: impl std::fmt::Display) -> TestmoError {
+    TestmoError::Parse(format!("Invalid JUnit XML: {e}"))
+}
+
+fn attribute(element: &BytesStart<'_>, name: &[u8]) -> Result<Option<String>, TestmoError> {
+    element
+        .try_get_attribute(name)
+        .map_err(parse_error)?
+        .map(|a| a.unescape_value().map(|v| v.into_owned()).map_err(parse_error))
+        .transpose()
+}
+
+fn test_case(element: &BytesStart<'_>, suite: Option<&String>) -> Result<AutomationTest, TestmoError> {
+    let name = attribute(element, b"name")?.unwrap_or_else(|| "unnamed".to_string());
+    let folder = attribute(element, b"classname")?
+        .or_else(|| suite.cloned())
+        .filter(|f| !f.is_empty());
+    #[allow(clippy::cast_possible_truncation)]
+    let elapsed_ms = attribute(element, b"time")?
+        .and_then(|t| t.parse::<f64>().ok())
+        .map(|seconds| (seconds * 1000.0).round() as i64);
+
+    Ok(AutomationTest {
+        name,
+        folder,
+        status: ResultStatus::Passed,
+        elapsed_ms,
+        message: None,
+    })
+}
+
+fn mark_failed(test: &mut AutomationTest, element: &BytesStart<'_>) -> Result<(), TestmoError> {
+    test.status = ResultStatus::Failed;
+    test.message = attribute(element, b"message")?;
+    Ok(())
+}
+
+fn mark_skipped(test: Option<&mut AutomationTest>) {
+    if let Some(test) = test {
+        test.status = ResultStatus::Skipped;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_junit_statuses_and_timing() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+            <testsuites>
+              <testsuite name="checkout">
+                <testcase name="adds item" classname="cart.spec" time="0.25"/>
+                <testcase name="applies coupon" time="1.5">
+                  <failure message="expected 10 got 12">stack...</failure>
+                </testcase>
+                <testcase name="pays with card">
+                  <error><![CDATA[timeout &amp; retry]]></error>
+                </testcase>
+                <testcase name="pays with paypal"><skipped/></testcase>
+              </testsuite>
+            </testsuites>"#;
+
+        let tests = parse_junit(xml).unwrap();
+        assert_eq!(tests.len(), 4);
+
+        assert_eq!(tests[0].status, ResultStatus::Passed);
+        assert_eq!(tests[0].folder.as_deref(), Some("cart.spec"));
+        assert_eq!(tests[0].elapsed_ms, Some(250));
+
+        assert_eq!(tests[1].status, ResultStatus::Failed);
+        assert_eq!(tests[1].folder.as_deref(), Some("checkout"));
+        assert_eq!(tests[1].message.as_deref(), Some("expected 10 got 12"));
+
+        assert_eq!(tests[2].status, ResultStatus::Failed);
+        assert_eq!(tests[2].message.as_deref(), Some("timeout &amp; retry"));
+
+        assert_eq!(tests[3].status, ResultStatus::Skipped);
+    }
+
+    #[test]
+    fn test_parse_junit_rejects_malformed_xml() {
+        assert!(parse_junit("<testsuite><testcase></testsuite>").is_err());
+    }
+}
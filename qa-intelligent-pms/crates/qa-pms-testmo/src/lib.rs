@@ -4,20 +4,33 @@
 //!
 //! This crate provides:
 //! - API key authentication with Bearer token
-//! - Project and test suite listing
+//! - Project and test suite listing with transparent pagination
+//! - Rate-limit aware retries and configurable timeouts
 //! - Test case search by keywords
 //! - Test case details retrieval
-//! - Test run creation
+//! - Bulk test case creation with partial-failure reporting
+//! - Milestone listing and creation
+//! - Test run creation, result submission and result retrieval
+//! - Automation runs with JUnit report ingestion
+//! - Custom field mapping for test case attributes
+//! - Bidirectional sync with the local test case repository
 //! - Health check for integration monitoring
 
 mod client;
 mod error;
 mod types;
+pub mod fields;
 pub mod health;
+pub mod junit;
+pub mod sync;
 
-pub use client::TestmoClient;
+pub use client::{ClientOptions, TestmoClient, BULK_CREATE_BATCH_SIZE};
 pub use error::TestmoError;
+pub use fields::{FieldMapping, FieldTarget};
 pub use health::TestmoHealthCheck;
+pub use junit::parse_junit;
+pub use sync::{SyncConflict, SyncDirection, SyncReport, TestmoSync};
 pub use types::{
-    CreateTestRunRequest, Project, SearchResult, TestCase, TestRun, TestStep, TestSuite,
+    AutomationRun, AutomationTest, BulkCreateFailure, BulkCreateReport, CreateAutomationRunRequest, CreateMilestoneRequest, CreateTestCaseRequest, CreateTestRunRequest, CreatedTestCase, Milestone, Project, ResultAttachment, ResultStatus, RunResultCounts, SearchResult, TestCase,
+    TestResult, TestResultInput, TestRun, TestStep, TestSuite, UpdateTestCaseRequest,
 };
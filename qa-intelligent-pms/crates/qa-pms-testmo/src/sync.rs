@@ -0,0 +1,343 @@
+//! Bidirectional test case sync.
+//!
+//! Imports Testmo cases into the local `TestCaseRepository` and pushes local
+//! edits back. Each local copy remembers the remote `updated_at` it was last
+//! synced against; a case edited on both sides since then is reported as a
+//! conflict and left untouched on either side.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info};
+use uuid::Uuid;
+
+use qa_pms_core::test_cases::{RemoteLink, TestCase as LocalTestCase, TestCaseRepository, TestCaseStep};
+
+use crate::client::TestmoClient;
+use crate::error::TestmoError;
+use crate::fields::FieldMapping;
+use crate::types::{TestCase, TestStep, UpdateTestCaseRequest};
+
+/// System name recorded in `RemoteLink::system`.
+pub const SYSTEM: &str = "testmo";
+
+/// Which way to sync.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SyncDirection {
+    /// Import remote changes only.
+    Pull,
+    /// Push local edits only.
+    Push,
+    /// Pull, then push.
+    #[default]
+    Both,
+}
+
+impl SyncDirection {
+    const fn pulls(self) -> bool {
+        matches!(self, Self::Pull | Self::Both)
+    }
+
+    const fn pushes(self) -> bool {
+        matches!(self, Self::Push | Self::Both)
+    }
+}
+
+/// A case changed on both sides since the last sync.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncConflict {
+    /// Local case ID.
+    pub local_id: Uuid,
+    /// Testmo case ID.
+    pub remote_id: i64,
+    /// Local title.
+    pub title: String,
+    /// Last local edit.
+    pub local_updated_at: DateTime<Utc>,
+    /// Current remote `updated_at`.
+    pub remote_updated_at: String,
+}
+
+/// Outcome of a sync.
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncReport {
+    /// Remote cases imported for the first time.
+    pub imported: usize,
+    /// Local copies refreshed from remote changes.
+    pub updated_local: usize,
+    /// Local edits written back to Testmo.
+    pub pushed: usize,
+    /// Cases unchanged on both sides.
+    pub unchanged: usize,
+    /// Cases edited on both sides; neither side was modified.
+    pub conflicts: Vec<SyncConflict>,
+}
+
+/// What pulling a remote case should do to its local copy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PullAction {
+    Import,
+    UpdateLocal,
+    /// Only the local copy changed; left for the push phase.
+    LocalAhead,
+    Conflict,
+    Unchanged,
+}
+
+fn plan_pull(local: Option<&LocalTestCase>, remote: &TestCase) -> PullAction {
+    let Some(local) = local else {
+        return PullAction::Import;
+    };
+    let remote_changed = local
+        .remote
+        .as_ref()
+        .map_or(true, |link| link.remote_updated_at != remote.updated_at);
+
+    match (remote_changed, local.has_local_changes()) {
+        (true, true) => PullAction::Conflict,
+        (true, false) => PullAction::UpdateLocal,
+        (false, true) => PullAction::LocalAhead,
+        (false, false) => PullAction::Unchanged,
+    }
+}
+
+/// Sync service between a Testmo project and a local repository.
+pub struct TestmoSync<'a> {
+    client: &'a TestmoClient,
+    repository: &'a dyn TestCaseRepository,
+    mapping: FieldMapping,
+}
+
+impl<'a> TestmoSync<'a> {
+    /// Create a sync service.
+    #[must_use]
+    pub fn new(client: &'a TestmoClient, repository: &'a dyn TestCaseRepository) -> Self {
+        Self {
+            client,
+            repository,
+            mapping: FieldMapping::default(),
+        }
+    }
+
+    /// Use a project-specific custom field mapping.
+    #[must_use]
+    pub fn with_field_mapping(mut self, mapping: FieldMapping) -> Self {
+        self.mapping = mapping;
+        self
+    }
+
+    /// Sync the cases of one project.
+    ///
+    /// # Errors
+    /// Returns error if a Testmo call or repository operation fails. Changes
+    /// applied before the failure are kept.
+    pub async fn sync(&self, project_id: i64, direction: SyncDirection) -> Result<SyncReport, TestmoError> {
+        let mut report = SyncReport::default();
+        if direction.pulls() {
+            self.pull(project_id, &mut report).await?;
+        }
+        if direction.pushes() {
+            self.push(project_id, &mut report).await?;
+        }
+        info!(
+            project_id = project_id,
+            imported = report.imported,
+            updated_local = report.updated_local,
+            pushed = report.pushed,
+            conflicts = report.conflicts.len(),
+            "Testmo sync finished"
+        );
+        Ok(report)
+    }
+
+    async fn pull(&self, project_id: i64, report: &mut SyncReport) -> Result<(), TestmoError> {
+        let remote_cases = self.client.list_test_cases(project_id, None).await?;
+        debug!(project_id = project_id, count = remote_cases.len(), "Pulling Testmo cases");
+
+        for remote in &remote_cases {
+            let local = self
+                .repository
+                .find_by_remote(SYSTEM, &remote.id.to_string())
+                .await
+                .map_err(storage_error)?;
+
+            match plan_pull(local.as_ref(), remote) {
+                PullAction::Import => {
+                    let mut case = LocalTestCase::new(remote.title.clone());
+                    apply_remote(&mut case, remote, &self.mapping);
+                    self.repository.save(case).await.map_err(storage_error)?;
+                    report.imported += 1;
+                }
+                PullAction::UpdateLocal => {
+                    if let Some(mut case) = local {
+                        apply_remote(&mut case, remote, &self.mapping);
+                        self.repository.save(case).await.map_err(storage_error)?;
+                        report.updated_local += 1;
+                    }
+                }
+                PullAction::Conflict => {
+                    if let Some(case) = local {
+                        report.conflicts.push(conflict(&case, remote));
+                    }
+                }
+                PullAction::Unchanged => report.unchanged += 1,
+                PullAction::LocalAhead => {}
+            }
+        }
+        Ok(())
+    }
+
+    async fn push(&self, project_id: i64, report: &mut SyncReport) -> Result<(), TestmoError> {
+        let project = project_id.to_string();
+        let cases = self.repository.list().await.map_err(storage_error)?;
+
+        for mut case in cases {
+            let Some(remote_id) = case
+                .remote
+                .as_ref()
+                .filter(|link| link.system == SYSTEM && link.project_id == project)
+                .and_then(|link| link.remote_id.parse::<i64>().ok())
+            else {
+                continue;
+            };
+            if !case.has_local_changes() || report.conflicts.iter().any(|c| c.local_id == case.id) {
+                continue;
+            }
+
+            // Re-check the remote so edits made since the pull are not overwritten
+            let current = self.client.get_test_case(project_id, remote_id).await?;
+            if plan_pull(Some(&case), &current) == PullAction::Conflict {
+                report.conflicts.push(conflict(&case, &current));
+                continue;
+            }
+
+            let updated = self
+                .client
+                .update_test_case(project_id, remote_id, &update_request(&case, &self.mapping))
+                .await?;
+            if let Some(link) = case.remote.as_mut() {
+                link.remote_updated_at = updated.updated_at;
+                link.synced_at = Utc::now();
+            }
+            self.repository.save(case).await.map_err(storage_error)?;
+            report.pushed += 1;
+        }
+        Ok(())
+    }
+}
+
+fn storage_error(e: anyhow::Error) -> TestmoError {
+    TestmoError::Storage(e.to_string())
+}
+
+/// Overwrite the local copy with remote content and mark it synced.
+fn apply_remote(case: &mut LocalTestCase, remote: &TestCase, mapping: &FieldMapping) {
+    let now = Utc::now();
+    case.title.clone_from(&remote.title);
+    mapping.apply_from_remote(remote, case);
+    case.steps = remote
+        .steps
+        .iter()
+        .flatten()
+        .map(|s| TestCaseStep {
+            action: s.content.clone(),
+            expected: s.expected.clone(),
+        })
+        .collect();
+    case.updated_at = now;
+    case.remote = Some(RemoteLink {
+        system: SYSTEM.to_string(),
+        project_id: remote.project_id.to_string(),
+        remote_id: remote.id.to_string(),
+        remote_updated_at: remote.updated_at.clone(),
+        synced_at: now,
+    });
+}
+
+fn update_request(case: &LocalTestCase, mapping: &FieldMapping) -> UpdateTestCaseRequest {
+    let mut request = mapping.to_update_request(case);
+    request.steps = Some(
+        case.steps
+            .iter()
+            .map(|s| TestStep {
+                content: s.action.clone(),
+                expected: s.expected.clone(),
+            })
+            .collect(),
+    );
+    request
+}
+
+fn conflict(case: &LocalTestCase, remote: &TestCase) -> SyncConflict {
+    SyncConflict {
+        local_id: case.id,
+        remote_id: remote.id,
+        title: case.title.clone(),
+        local_updated_at: case.updated_at,
+        remote_updated_at: remote.updated_at.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn remote(updated_at: &str) -> TestCase {
+        TestCase {
+            id: 7,
+            project_id: 1,
+            suite_id: None,
+            title: "Checkout with coupon".to_string(),
+            preconditions: Some("Cart has items".to_string()),
+            priority_id: Some(2),
+            type_id: None,
+            template_id: None,
+            steps: Some(vec![TestStep {
+                content: "Apply coupon".to_string(),
+                expected: Some("Total reduced".to_string()),
+            }]),
+            created_at: "2024-01-01T00:00:00Z".to_string(),
+            updated_at: updated_at.to_string(),
+            custom_fields: serde_json::Map::new(),
+        }
+    }
+
+    fn synced(remote_case: &TestCase) -> LocalTestCase {
+        let mut case = LocalTestCase::new("");
+        apply_remote(&mut case, remote_case, &FieldMapping::default());
+        case
+    }
+
+    #[test]
+    fn test_plan_pull_decisions() {
+        let v1 = remote("2024-01-01T00:00:00Z");
+        let v2 = remote("2024-02-01T00:00:00Z");
+        let local = synced(&v1);
+
+        assert_eq!(plan_pull(None, &v1), PullAction::Import);
+        assert_eq!(plan_pull(Some(&local), &v1), PullAction::Unchanged);
+        assert_eq!(plan_pull(Some(&local), &v2), PullAction::UpdateLocal);
+
+        let mut edited = local;
+        edited.updated_at += chrono::Duration::seconds(5);
+        assert_eq!(plan_pull(Some(&edited), &v1), PullAction::LocalAhead);
+        assert_eq!(plan_pull(Some(&edited), &v2), PullAction::Conflict);
+    }
+
+    #[test]
+    fn test_apply_remote_links_and_round_trips() {
+        let case = synced(&remote("2024-01-01T00:00:00Z"));
+
+        assert_eq!(case.title, "Checkout with coupon");
+        assert_eq!(case.priority.as_deref(), Some("2"));
+        assert_eq!(case.steps[0].action, "Apply coupon");
+        assert!(case.is_linked_to(SYSTEM, "7"));
+        assert!(!case.has_local_changes());
+
+        let request = update_request(&case, &FieldMapping::default());
+        assert_eq!(request.priority_id, Some(2));
+        assert_eq!(request.steps.unwrap()[0].expected.as_deref(), Some("Total reduced"));
+    }
+}
@@ -2,27 +2,25 @@
 //!
 //! Typed structs for Testmo API responses.
 
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize};
 
 // ============================================================================
 // Response Wrappers
 // ============================================================================
 
-/// Response wrapper for projects list.
-#[derive(Debug, Deserialize)]
-pub struct ProjectsResponse {
-    /// List of projects.
-    pub data: Vec<Project>,
-}
-
-/// Response wrapper for test suites list.
+/// One page of a paginated list.
 #[derive(Debug, Deserialize)]
-pub struct TestSuitesResponse {
-    /// List of test suites.
-    pub data: Vec<TestSuite>,
+pub struct Page<T> {
+    /// Items on this page.
+    pub data: Vec<T>,
+    /// Next page number, absent on the last page.
+    #[serde(default)]
+    pub next_page: Option<u32>,
 }
 
-/// Response wrapper for test cases list.
+/// Response wrapper for created test cases.
 #[derive(Debug, Deserialize)]
 pub struct TestCasesResponse {
     /// List of test cases.
@@ -43,6 +41,27 @@ pub struct TestRunResponse {
     pub data: TestRun,
 }
 
+/// Response wrapper for single milestone.
+#[derive(Debug, Deserialize)]
+pub struct MilestoneResponse {
+    /// Milestone data.
+    pub data: Milestone,
+}
+
+/// Response wrapper for automation run.
+#[derive(Debug, Deserialize)]
+pub struct AutomationRunResponse {
+    /// Automation run data.
+    pub data: AutomationRun,
+}
+
+/// Response wrapper for submitted results.
+#[derive(Debug, Deserialize)]
+pub struct TestResultsResponse {
+    /// Stored results.
+    pub data: Vec<TestResult>,
+}
+
 // ============================================================================
 // Core Types
 // ============================================================================
@@ -108,6 +127,9 @@ pub struct TestCase {
     pub created_at: String,
     /// Last update timestamp.
     pub updated_at: String,
+    /// Remaining fields, including project custom fields (`custom_*`).
+    #[serde(flatten)]
+    pub custom_fields: serde_json::Map<String, serde_json::Value>,
 }
 
 /// Test step within a test case.
@@ -132,12 +154,192 @@ pub struct TestRun {
     pub description: Option<String>,
     /// Status ID.
     pub status_id: i32,
+    /// Milestone the run is grouped under.
+    #[serde(default)]
+    pub milestone_id: Option<i64>,
+    /// Whether the run has been closed.
+    #[serde(default)]
+    pub is_completed: bool,
     /// Creation timestamp.
     pub created_at: String,
     /// Last update timestamp.
     pub updated_at: String,
 }
 
+/// Milestone (sprint or release) grouping test runs.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Milestone {
+    /// Milestone unique ID.
+    pub id: i64,
+    /// Parent project ID.
+    pub project_id: i64,
+    /// Milestone name.
+    pub name: String,
+    /// Milestone description.
+    pub description: Option<String>,
+    /// Whether the milestone is closed.
+    #[serde(default)]
+    pub is_completed: bool,
+    /// Due date.
+    pub due_at: Option<String>,
+    /// Creation timestamp.
+    pub created_at: String,
+    /// Last update timestamp.
+    pub updated_at: String,
+}
+
+/// Outcome of a test case within a run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ResultStatus {
+    /// Test passed.
+    Passed,
+    /// Test failed.
+    Failed,
+    /// Test could not be executed.
+    Blocked,
+    /// Test was intentionally not executed.
+    Skipped,
+    /// Test needs to be run again.
+    Retest,
+}
+
+impl ResultStatus {
+    /// Parse a status from its wire name (case-insensitive).
+    #[must_use]
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "passed" => Some(Self::Passed),
+            "failed" => Some(Self::Failed),
+            "blocked" => Some(Self::Blocked),
+            "skipped" => Some(Self::Skipped),
+            "retest" => Some(Self::Retest),
+            _ => None,
+        }
+    }
+}
+
+/// Automation run collecting results from an automated suite.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AutomationRun {
+    /// Automation run unique ID.
+    pub id: i64,
+    /// Parent project ID.
+    pub project_id: i64,
+    /// Run name.
+    pub name: String,
+    /// Source label (e.g., CI job name).
+    pub source: Option<String>,
+    /// Milestone the run is grouped under.
+    #[serde(default)]
+    pub milestone_id: Option<i64>,
+    /// Whether the run has been completed.
+    #[serde(default)]
+    pub is_completed: bool,
+    /// Creation timestamp.
+    pub created_at: String,
+}
+
+/// A single automated test outcome.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AutomationTest {
+    /// Test name.
+    pub name: String,
+    /// Folder (suite or class) the test belongs to.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub folder: Option<String>,
+    /// Outcome.
+    pub status: ResultStatus,
+    /// Execution time in milliseconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub elapsed_ms: Option<i64>,
+    /// Failure message.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+/// Link to evidence attached to a result.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResultAttachment {
+    /// Display name.
+    pub name: String,
+    /// Location of the file.
+    pub url: String,
+}
+
+/// Result recorded for a test case in a run.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TestResult {
+    /// Result unique ID.
+    pub id: i64,
+    /// Run the result belongs to.
+    pub run_id: i64,
+    /// Test case the result is for.
+    pub case_id: i64,
+    /// Outcome.
+    pub status: ResultStatus,
+    /// Execution time in seconds.
+    pub elapsed: Option<i64>,
+    /// Tester notes.
+    pub note: Option<String>,
+    /// Creation timestamp.
+    pub created_at: String,
+}
+
+/// Outcome counts of a run, using the latest result of each test case.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RunResultCounts {
+    /// Cases whose latest result passed.
+    pub passed: i64,
+    /// Cases whose latest result failed.
+    pub failed: i64,
+    /// Cases whose latest result is blocked.
+    pub blocked: i64,
+    /// Cases whose latest result is skipped.
+    pub skipped: i64,
+    /// Cases marked for retest.
+    pub retest: i64,
+}
+
+impl RunResultCounts {
+    /// Count the latest result per test case.
+    ///
+    /// A case can be re-run within the same run; only its newest result
+    /// (highest ID) counts.
+    #[must_use]
+    pub fn from_results(results: &[TestResult]) -> Self {
+        let mut latest: HashMap<i64, &TestResult> = HashMap::new();
+        for result in results {
+            latest
+                .entry(result.case_id)
+                .and_modify(|current| {
+                    if result.id > current.id {
+                        *current = result;
+                    }
+                })
+                .or_insert(result);
+        }
+
+        let mut counts = Self::default();
+        for result in latest.values() {
+            match result.status {
+                ResultStatus::Passed => counts.passed += 1,
+                ResultStatus::Failed => counts.failed += 1,
+                ResultStatus::Blocked => counts.blocked += 1,
+                ResultStatus::Skipped => counts.skipped += 1,
+                ResultStatus::Retest => counts.retest += 1,
+            }
+        }
+        counts
+    }
+
+    /// Cases with a result.
+    #[must_use]
+    pub const fn total(&self) -> i64 {
+        self.passed + self.failed + self.blocked + self.skipped + self.retest
+    }
+}
+
 // ============================================================================
 // Request Types
 // ============================================================================
@@ -149,6 +351,144 @@ pub struct CreateTestRunRequest {
     pub name: String,
     /// Test case IDs to include in the run.
     pub case_ids: Vec<i64>,
+    /// Milestone to group the run under.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub milestone_id: Option<i64>,
+}
+
+/// A test case to create.
+#[derive(Debug, Clone, Serialize)]
+pub struct CreateTestCaseRequest {
+    /// Test case title.
+    pub title: String,
+    /// Folder to create the case in.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub folder_id: Option<i64>,
+    /// Preconditions for the test.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub preconditions: Option<String>,
+    /// Priority level ID.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub priority_id: Option<i32>,
+    /// Test steps.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub steps: Vec<TestStep>,
+    /// Custom field values keyed by field name.
+    #[serde(flatten)]
+    pub custom_fields: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Request body for creating several test cases at once.
+#[derive(Debug, Serialize)]
+pub struct CreateTestCasesRequest<'a> {
+    /// Cases to create.
+    pub cases: &'a [CreateTestCaseRequest],
+}
+
+/// A case created by a bulk create, with its position in the input.
+#[derive(Debug, Clone)]
+pub struct CreatedTestCase {
+    /// Index of the case in the submitted list.
+    pub index: usize,
+    /// The created case.
+    pub case: TestCase,
+}
+
+/// A case a bulk create could not store.
+#[derive(Debug, Clone)]
+pub struct BulkCreateFailure {
+    /// Index of the case in the submitted list.
+    pub index: usize,
+    /// Case title.
+    pub title: String,
+    /// Error reported for the case or its batch.
+    pub error: String,
+}
+
+/// Outcome of a bulk create; failures do not stop the remaining batches.
+#[derive(Debug, Clone, Default)]
+pub struct BulkCreateReport {
+    /// Created cases in input order.
+    pub created: Vec<CreatedTestCase>,
+    /// Cases that failed.
+    pub failed: Vec<BulkCreateFailure>,
+}
+
+/// Request body for updating a test case.
+#[derive(Debug, Default, Serialize)]
+pub struct UpdateTestCaseRequest {
+    /// New title.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// New preconditions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub preconditions: Option<String>,
+    /// New priority level ID.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub priority_id: Option<i32>,
+    /// Replacement steps.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub steps: Option<Vec<TestStep>>,
+    /// Custom field values keyed by field name.
+    #[serde(flatten)]
+    pub custom_fields: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Request body for creating a milestone.
+#[derive(Debug, Serialize)]
+pub struct CreateMilestoneRequest {
+    /// Milestone name.
+    pub name: String,
+    /// Milestone description.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Due date (RFC 3339).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub due_at: Option<String>,
+}
+
+/// A single result to submit to a run.
+#[derive(Debug, Clone, Serialize)]
+pub struct TestResultInput {
+    /// Test case the result is for.
+    pub case_id: i64,
+    /// Outcome.
+    pub status: ResultStatus,
+    /// Execution time in seconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub elapsed: Option<i64>,
+    /// Tester notes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    /// Evidence links.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub attachments: Vec<ResultAttachment>,
+}
+
+/// Request body for creating an automation run.
+#[derive(Debug, Serialize)]
+pub struct CreateAutomationRunRequest {
+    /// Run name.
+    pub name: String,
+    /// Source label (e.g., CI job name).
+    pub source: String,
+    /// Milestone to group the run under.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub milestone_id: Option<i64>,
+}
+
+/// Request body for appending automation results.
+#[derive(Debug, Serialize)]
+pub struct AppendAutomationRequest<'a> {
+    /// Tests to append.
+    pub tests: &'a [AutomationTest],
+}
+
+/// Request body for submitting results to a run.
+#[derive(Debug, Serialize)]
+pub struct AddResultsRequest<'a> {
+    /// Results to store.
+    pub results: &'a [TestResultInput],
 }
 
 // ============================================================================
@@ -262,9 +602,90 @@ mod tests {
         let request = CreateTestRunRequest {
             name: "Sprint 1 Regression".to_string(),
             case_ids: vec![100, 101, 102],
+            milestone_id: None,
         };
         let json = serde_json::to_string(&request).unwrap();
         assert!(json.contains("Sprint 1 Regression"));
         assert!(json.contains("[100,101,102]"));
+        assert!(!json.contains("milestone_id"));
+    }
+
+    #[test]
+    fn test_deserialize_milestone() {
+        let json = r#"{
+            "id": 5,
+            "project_id": 1,
+            "name": "Sprint 42",
+            "description": null,
+            "due_at": "2024-02-01T00:00:00Z",
+            "created_at": "2024-01-01T00:00:00Z",
+            "updated_at": "2024-01-02T00:00:00Z"
+        }"#;
+        let milestone: Milestone = serde_json::from_str(json).unwrap();
+        assert_eq!(milestone.name, "Sprint 42");
+        assert!(!milestone.is_completed);
+    }
+
+    #[test]
+    fn test_serialize_add_results_request() {
+        let results = vec![
+            TestResultInput {
+                case_id: 100,
+                status: ResultStatus::Failed,
+                elapsed: Some(90),
+                note: Some("Button disabled".to_string()),
+                attachments: vec![ResultAttachment {
+                    name: "screenshot.png".to_string(),
+                    url: "https://files.example.com/1.png".to_string(),
+                }],
+            },
+            TestResultInput {
+                case_id: 101,
+                status: ResultStatus::Passed,
+                elapsed: None,
+                note: None,
+                attachments: vec![],
+            },
+        ];
+        let json = serde_json::to_value(AddResultsRequest { results: &results }).unwrap();
+        assert_eq!(json["results"][0]["status"], "failed");
+        assert_eq!(json["results"][0]["attachments"][0]["name"], "screenshot.png");
+        assert!(json["results"][1].get("elapsed").is_none());
+        assert!(json["results"][1].get("attachments").is_none());
+    }
+
+    #[test]
+    fn test_result_status_parse() {
+        assert_eq!(ResultStatus::parse("Blocked"), Some(ResultStatus::Blocked));
+        assert_eq!(ResultStatus::parse("unknown"), None);
+    }
+
+    #[test]
+    fn test_run_counts_use_latest_result_per_case() {
+        let result = |id, case_id, status| TestResult {
+            id,
+            run_id: 9,
+            case_id,
+            status,
+            elapsed: None,
+            note: None,
+            created_at: "2024-01-01T00:00:00Z".to_string(),
+        };
+        let counts = RunResultCounts::from_results(&[
+            result(1, 100, ResultStatus::Failed),
+            result(3, 100, ResultStatus::Passed),
+            result(2, 101, ResultStatus::Blocked),
+            result(4, 102, ResultStatus::Failed),
+        ]);
+        assert_eq!(
+            counts,
+            RunResultCounts {
+                passed: 1,
+                failed: 1,
+                blocked: 1,
+                ..RunResultCounts::default()
+            }
+        );
+        assert_eq!(counts.total(), 3);
     }
 }
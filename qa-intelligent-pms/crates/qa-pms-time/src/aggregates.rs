@@ -130,6 +130,7 @@ pub struct TrendPoint {
 /// * `actual_seconds` - Total actual time spent
 /// * `estimated_seconds` - Total estimated time
 /// * `step_times` - Vector of (`step_index`, `actual_seconds`) for each step
+#[allow(clippy::too_many_arguments)]
 pub async fn record_workflow_completion(
     pool: &PgPool,
     workflow_instance_id: Uuid,
@@ -399,19 +400,23 @@ pub async fn get_undismissed_alerts(
     .await
 }
 
-/// Dismiss a gap alert.
-pub async fn dismiss_alert(pool: &PgPool, alert_id: Uuid) -> Result<(), sqlx::Error> {
-    sqlx::query(
+/// Dismiss a gap alert on a workflow of the tenant.
+///
+/// Returns whether the alert was found.
+pub async fn dismiss_alert(pool: &PgPool, alert_id: Uuid, tenant_id: &str) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query(
         r"
         UPDATE time_gap_alerts
         SET dismissed = true, dismissed_at = NOW()
         WHERE id = $1
+          AND workflow_instance_id IN (SELECT id FROM workflow_instances WHERE tenant_id = $2)
         ",
     )
     .bind(alert_id)
+    .bind(tenant_id)
     .execute(pool)
     .await?;
-    Ok(())
+    Ok(result.rows_affected() > 0)
 }
 
 /// Get daily aggregates for a date range.
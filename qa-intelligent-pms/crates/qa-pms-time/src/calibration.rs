@@ -0,0 +1,158 @@
+//! Estimate calibration.
+//!
+//! Step averages recorded when workflows complete are compared with the
+//! template's estimates to give each step a correction factor. Steps with
+//! few completions are pulled towards a factor of 1, so a couple of unusual
+//! runs do not swing the estimate, and factors are kept within
+//! [`MIN_FACTOR`, `MAX_FACTOR`].
+
+use serde::Serialize;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::aggregates::{get_step_averages, StepAverage};
+
+/// Completions that count as much as the original estimate.
+pub const PRIOR_WEIGHT: f64 = 5.0;
+
+/// Smallest correction factor applied.
+pub const MIN_FACTOR: f64 = 0.25;
+
+/// Largest correction factor applied.
+pub const MAX_FACTOR: f64 = 4.0;
+
+/// Calibrated estimate of one template step.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StepCalibration {
+    pub step_index: i32,
+    /// Estimate from the template
+    pub estimated_minutes: i32,
+    /// Completions the calibration is based on
+    pub sample_count: i32,
+    /// Average actual time, when the step has completions
+    pub average_minutes: Option<f64>,
+    /// Multiplier applied to the estimate
+    pub correction_factor: f64,
+    /// Estimate after correction
+    pub calibrated_minutes: i32,
+}
+
+/// Calibrate step estimates against recorded averages.
+///
+/// `estimated_minutes` holds the template's estimate for each step, in step
+/// order. Averages for steps the template no longer has are ignored.
+#[must_use]
+pub fn calibrate(estimated_minutes: &[i32], averages: &[StepAverage]) -> Vec<StepCalibration> {
+    (0i32..)
+        .zip(estimated_minutes)
+        .map(|(step_index, &estimated)| {
+            let average = averages
+                .iter()
+                .find(|a| a.step_index == step_index && a.sample_count > 0);
+            let sample_count = average.map_or(0, |a| a.sample_count);
+            let average_minutes = average.map(|a| f64::from(a.avg_seconds) / 60.0);
+            let correction_factor = match average_minutes {
+                Some(actual) if estimated > 0 => {
+                    let observed = actual / f64::from(estimated);
+                    let samples = f64::from(sample_count);
+                    ((samples * observed + PRIOR_WEIGHT) / (samples + PRIOR_WEIGHT))
+                        .clamp(MIN_FACTOR, MAX_FACTOR)
+                }
+                _ => 1.0,
+            };
+            StepCalibration {
+                step_index,
+                estimated_minutes: estimated,
+                sample_count,
+                average_minutes,
+                correction_factor,
+                calibrated_minutes: (f64::from(estimated) * correction_factor).round() as i32,
+            }
+        })
+        .collect()
+}
+
+/// Calibrates template estimates from completion history.
+#[derive(Debug, Clone)]
+pub struct CalibrationService {
+    pool: PgPool,
+}
+
+impl CalibrationService {
+    /// Create a calibration service on the given pool.
+    #[must_use]
+    pub const fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Calibrate the step estimates of a template.
+    ///
+    /// # Errors
+    /// Returns error if the step averages cannot be loaded.
+    pub async fn calibrated_estimates(
+        &self,
+        template_id: Uuid,
+        estimated_minutes: &[i32],
+    ) -> Result<Vec<StepCalibration>, sqlx::Error> {
+        let averages = get_step_averages(&self.pool, template_id).await?;
+        Ok(calibrate(estimated_minutes, &averages))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use rust_decimal::Decimal;
+
+    use super::*;
+
+    fn average(step_index: i32, sample_count: i32, avg_minutes: i32) -> StepAverage {
+        StepAverage {
+            id: Uuid::new_v4(),
+            template_id: Uuid::nil(),
+            step_index,
+            sample_count,
+            total_seconds: sample_count * avg_minutes * 60,
+            avg_seconds: avg_minutes * 60,
+            min_seconds: 0,
+            max_seconds: 0,
+            std_dev_seconds: Decimal::ZERO,
+            last_sample_at: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_factor_moves_towards_observed_ratio_with_samples() {
+        let few = calibrate(&[10], &[average(0, 1, 20)]);
+        let many = calibrate(&[10], &[average(0, 95, 20)]);
+
+        assert!((few[0].correction_factor - 7.0 / 6.0).abs() < 1e-9);
+        assert!((many[0].correction_factor - 1.95).abs() < 1e-9);
+        assert_eq!(many[0].calibrated_minutes, 20);
+        assert_eq!(many[0].average_minutes, Some(20.0));
+    }
+
+    #[test]
+    fn test_steps_without_history_keep_their_estimate() {
+        let steps = calibrate(&[15, 0, 30], &[average(1, 10, 5), average(7, 10, 5)]);
+
+        assert_eq!(steps.len(), 3);
+        assert_eq!(steps[0].sample_count, 0);
+        assert!((steps[0].correction_factor - 1.0).abs() < f64::EPSILON);
+        assert_eq!(steps[0].calibrated_minutes, 15);
+        assert_eq!(steps[1].calibrated_minutes, 0);
+        assert_eq!(steps[2].calibrated_minutes, 30);
+    }
+
+    #[test]
+    fn test_factor_is_clamped() {
+        let slow = calibrate(&[1], &[average(0, 1000, 600)]);
+        let fast = calibrate(&[600], &[average(0, 1000, 1)]);
+
+        assert!((slow[0].correction_factor - MAX_FACTOR).abs() < f64::EPSILON);
+        assert!((fast[0].correction_factor - MIN_FACTOR).abs() < f64::EPSILON);
+    }
+}
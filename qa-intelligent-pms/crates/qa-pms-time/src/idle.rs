@@ -0,0 +1,128 @@
+//! Idle detection for time sessions.
+//!
+//! Clients send a heartbeat while the user is active on a step. The
+//! [`IdleSweeper`] pauses running sessions that have had no heartbeat for the
+//! idle period, backdating the pause to the last heartbeat so the idle time is
+//! not counted, and marks them [`AUTO_PAUSED`]. The next heartbeat resumes an
+//! auto-paused session; sessions the user paused stay paused.
+
+use std::time::Duration;
+
+use sqlx::PgPool;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::repository::{get_session, resume_session};
+use crate::types::TimeSession;
+
+/// Pause reason recorded on sessions paused for inactivity.
+pub const AUTO_PAUSED: &str = "auto-paused";
+
+/// Time between sweeps for idle sessions.
+const SWEEP_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Record activity on a running session.
+///
+/// An auto-paused session is resumed. Returns the updated session, or `None`
+/// if the session does not exist or has ended.
+///
+/// # Errors
+/// Returns error if a database query fails.
+pub async fn heartbeat(
+    pool: &PgPool,
+    session_id: Uuid,
+) -> Result<Option<TimeSession>, sqlx::Error> {
+    let session = match get_session(pool, session_id).await {
+        Ok(session) => session,
+        Err(sqlx::Error::RowNotFound) => return Ok(None),
+        Err(e) => return Err(e),
+    };
+    if !session.is_active {
+        return Ok(None);
+    }
+    if session.paused_at.is_some() && session.pause_reason.as_deref() == Some(AUTO_PAUSED) {
+        resume_session(pool, session_id).await?;
+        info!(session_id = %session_id, "Resumed auto-paused time session");
+    }
+
+    sqlx::query_as::<_, TimeSession>(
+        r"
+        UPDATE time_sessions SET last_heartbeat_at = NOW(), updated_at = NOW()
+        WHERE id = $1 AND is_active = true
+        RETURNING *
+        ",
+    )
+    .bind(session_id)
+    .fetch_optional(pool)
+    .await
+}
+
+/// Pauses running sessions that have gone quiet.
+#[derive(Debug, Clone)]
+pub struct IdleSweeper {
+    pool: PgPool,
+    idle_after: Duration,
+}
+
+impl IdleSweeper {
+    /// Create a sweeper that pauses sessions idle for longer than `idle_after`.
+    #[must_use]
+    pub const fn new(pool: PgPool, idle_after: Duration) -> Self {
+        Self { pool, idle_after }
+    }
+
+    /// Pause every running session idle for longer than the idle period.
+    ///
+    /// A session's last activity is its latest heartbeat, start or resume.
+    /// Returns the sessions paused.
+    ///
+    /// # Errors
+    /// Returns error if the database query fails.
+    pub async fn sweep(&self) -> Result<Vec<TimeSession>, sqlx::Error> {
+        sqlx::query_as::<_, TimeSession>(
+            r"
+            WITH idle AS (
+                UPDATE time_sessions
+                SET paused_at = GREATEST(last_heartbeat_at, resumed_at, started_at),
+                    pause_reason = $2,
+                    updated_at = NOW()
+                WHERE is_active = true
+                  AND paused_at IS NULL
+                  AND GREATEST(last_heartbeat_at, resumed_at, started_at)
+                      < NOW() - make_interval(secs => $1)
+                RETURNING *
+            ),
+            events AS (
+                INSERT INTO time_pause_events (session_id, paused_at)
+                SELECT id, paused_at FROM idle
+            )
+            SELECT * FROM idle
+            ",
+        )
+        .bind(self.idle_after.as_secs_f64())
+        .bind(AUTO_PAUSED)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// Sweep for idle sessions every minute, in the background.
+    pub fn start(self) {
+        tokio::spawn(async move {
+            info!(
+                idle_secs = self.idle_after.as_secs(),
+                "Idle time session sweeper started"
+            );
+            let mut ticker = tokio::time::interval(SWEEP_INTERVAL);
+            loop {
+                ticker.tick().await;
+                match self.sweep().await {
+                    Ok(paused) if !paused.is_empty() => {
+                        info!(count = paused.len(), "Auto-paused idle time sessions");
+                    }
+                    Ok(_) => {}
+                    Err(e) => warn!(error = %e, "Idle time session sweep failed"),
+                }
+            }
+        });
+    }
+}
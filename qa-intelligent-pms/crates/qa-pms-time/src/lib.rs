@@ -7,11 +7,22 @@
 //! - `repository`: Core time session CRUD operations
 //! - `types`: Time tracking type definitions
 //! - `aggregates`: Historical time data aggregation (Story 6.7)
+//! - `tracking`: Automatic sessions that follow workflow step progress
+//! - `idle`: Client heartbeats and auto-pausing of idle sessions
+//! - `export`: Cross-workflow time reports as CSV or XLSX
+//! - `calibration`: Step estimates corrected by completion history
 
 pub mod aggregates;
+pub mod calibration;
+pub mod export;
+pub mod idle;
 pub mod repository;
+pub mod tracking;
 pub mod types;
 
 pub use aggregates::*;
+pub use calibration::{CalibrationService, StepCalibration};
+pub use idle::{heartbeat, IdleSweeper, AUTO_PAUSED};
 pub use repository::*;
+pub use tracking::TrackingService;
 pub use types::*;
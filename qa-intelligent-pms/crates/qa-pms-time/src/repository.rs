@@ -6,7 +6,7 @@ use uuid::Uuid;
 
 use crate::types::{TimeEstimate, TimePauseEvent, TimeSession};
 
-/// Start a new time session for a workflow step.
+/// Start a new time session for a workflow step, in the workflow's tenant.
 pub async fn start_session(
     pool: &PgPool,
     workflow_instance_id: Uuid,
@@ -14,8 +14,10 @@ pub async fn start_session(
 ) -> Result<TimeSession, sqlx::Error> {
     sqlx::query_as::<_, TimeSession>(
         r"
-        INSERT INTO time_sessions (workflow_instance_id, step_index, started_at, is_active)
-        VALUES ($1, $2, NOW(), true)
+        INSERT INTO time_sessions (workflow_instance_id, step_index, started_at, is_active, tenant_id)
+        SELECT $1, $2, NOW(), true, tenant_id
+        FROM workflow_instances
+        WHERE id = $1
         ON CONFLICT (workflow_instance_id, step_index) 
         DO UPDATE SET started_at = NOW(), is_active = true, updated_at = NOW()
         RETURNING *
@@ -58,7 +60,7 @@ pub async fn pause_session(pool: &PgPool, session_id: Uuid) -> Result<TimePauseE
     // Update session paused_at
     sqlx::query(
         r"
-        UPDATE time_sessions SET paused_at = NOW(), updated_at = NOW()
+        UPDATE time_sessions SET paused_at = NOW(), pause_reason = NULL, updated_at = NOW()
         WHERE id = $1
         ",
     )
@@ -84,7 +86,8 @@ pub async fn resume_session(pool: &PgPool, session_id: Uuid) -> Result<(), sqlx:
     // Update session resumed_at
     sqlx::query(
         r"
-        UPDATE time_sessions SET resumed_at = NOW(), paused_at = NULL, updated_at = NOW()
+        UPDATE time_sessions
+        SET resumed_at = NOW(), paused_at = NULL, pause_reason = NULL, updated_at = NOW()
         WHERE id = $1
         ",
     )
@@ -118,6 +121,21 @@ pub async fn get_session(pool: &PgPool, session_id: Uuid) -> Result<TimeSession,
     .await
 }
 
+/// Get a time session of a tenant by ID.
+pub async fn get_tenant_session(
+    pool: &PgPool,
+    session_id: Uuid,
+    tenant_id: &str,
+) -> Result<Option<TimeSession>, sqlx::Error> {
+    sqlx::query_as::<_, TimeSession>(
+        r"SELECT * FROM time_sessions WHERE id = $1 AND tenant_id = $2",
+    )
+    .bind(session_id)
+    .bind(tenant_id)
+    .fetch_optional(pool)
+    .await
+}
+
 /// Get active session for a workflow.
 pub async fn get_active_session(
     pool: &PgPool,
@@ -171,13 +189,36 @@ pub async fn get_workflow_sessions(
     .await
 }
 
+/// Get all sessions for a set of workflows.
+pub async fn get_sessions_for_workflows(
+    pool: &PgPool,
+    workflow_instance_ids: &[Uuid],
+) -> Result<Vec<TimeSession>, sqlx::Error> {
+    sqlx::query_as::<_, TimeSession>(
+        r"
+        SELECT * FROM time_sessions
+        WHERE workflow_instance_id = ANY($1)
+        ORDER BY workflow_instance_id, step_index
+        ",
+    )
+    .bind(workflow_instance_ids)
+    .fetch_all(pool)
+    .await
+}
+
 /// Get total paused time for a session.
+///
+/// A pause that has not been resumed counts up to now, so a session ended
+/// while paused is not charged for the pause.
 pub async fn get_total_paused_time(pool: &PgPool, session_id: Uuid) -> Result<i32, sqlx::Error> {
     let result: (Option<i64>,) = sqlx::query_as(
         r"
-        SELECT COALESCE(SUM(duration_seconds), 0) as total
+        SELECT COALESCE(SUM(COALESCE(
+            duration_seconds,
+            EXTRACT(EPOCH FROM (NOW() - paused_at))::INT
+        )), 0) as total
         FROM time_pause_events
-        WHERE session_id = $1 AND duration_seconds IS NOT NULL
+        WHERE session_id = $1
         ",
     )
     .bind(session_id)
@@ -0,0 +1,85 @@
+//! Automatic time tracking for workflow steps.
+//!
+//! Workflows created with auto-tracking are timed without the user starting
+//! sessions: a session starts when a step becomes current and ends when the
+//! step is completed or skipped. Steps a branch jumps over are never timed.
+
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::repository::{end_session, get_active_session, get_session_for_step, start_session};
+use crate::types::TimeSession;
+
+/// Keeps time sessions in step with workflow progress.
+#[derive(Debug, Clone)]
+pub struct TrackingService {
+    pool: PgPool,
+}
+
+impl TrackingService {
+    /// Create a tracking service on the given pool.
+    #[must_use]
+    pub const fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Start timing a step that became current.
+    ///
+    /// A session still running for another step of the workflow is ended
+    /// first, so only one step is timed at a time. A step already being
+    /// timed keeps its session.
+    ///
+    /// # Errors
+    /// Returns error if a database query fails.
+    pub async fn step_started(
+        &self,
+        workflow_id: Uuid,
+        step_index: i32,
+    ) -> Result<TimeSession, sqlx::Error> {
+        if let Some(active) = get_active_session(&self.pool, workflow_id).await? {
+            if active.step_index == step_index {
+                return Ok(active);
+            }
+            end_session(&self.pool, active.id).await?;
+        }
+        start_session(&self.pool, workflow_id, step_index).await
+    }
+
+    /// Stop timing a step that was completed or skipped.
+    ///
+    /// Returns the ended session, or `None` if the step was not being timed.
+    ///
+    /// # Errors
+    /// Returns error if a database query fails.
+    pub async fn step_finished(
+        &self,
+        workflow_id: Uuid,
+        step_index: i32,
+    ) -> Result<Option<TimeSession>, sqlx::Error> {
+        match get_session_for_step(&self.pool, workflow_id, step_index).await? {
+            Some(session) if session.is_active => {
+                end_session(&self.pool, session.id).await.map(Some)
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Move timing from a finished step to the next current one, if any.
+    ///
+    /// Returns the session of the finished step when it was being timed.
+    ///
+    /// # Errors
+    /// Returns error if a database query fails.
+    pub async fn advance(
+        &self,
+        workflow_id: Uuid,
+        finished_step: i32,
+        next_step: Option<i32>,
+    ) -> Result<Option<TimeSession>, sqlx::Error> {
+        let ended = self.step_finished(workflow_id, finished_step).await?;
+        if let Some(next) = next_step {
+            self.step_started(workflow_id, next).await?;
+        }
+        Ok(ended)
+    }
+}
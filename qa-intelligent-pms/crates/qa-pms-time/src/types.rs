@@ -17,6 +17,10 @@ pub struct TimeSession {
     pub ended_at: Option<DateTime<Utc>>,
     pub total_seconds: i32,
     pub is_active: bool,
+    /// Last sign of activity from the client, see [`crate::idle`]
+    pub last_heartbeat_at: Option<DateTime<Utc>>,
+    /// Why the session is paused, if it was not paused by the user
+    pub pause_reason: Option<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -44,10 +48,15 @@ pub struct TimeEstimate {
 }
 
 /// Summary of time spent on a workflow.
+///
+/// `total_seconds` includes time spent in sub-workflows spawned from its
+/// steps, which is also reported per step and in `sub_workflow_seconds`.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TimeSummary {
     pub workflow_instance_id: Uuid,
     pub total_seconds: i32,
+    #[serde(default)]
+    pub sub_workflow_seconds: i32,
     pub step_times: Vec<StepTime>,
 }
 
@@ -55,7 +64,109 @@ pub struct TimeSummary {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StepTime {
     pub step_index: i32,
+    /// Time tracked on the step itself plus its sub-workflows
     pub actual_seconds: i32,
+    /// Part of `actual_seconds` spent in sub-workflows
+    #[serde(default)]
+    pub sub_workflow_seconds: i32,
     pub estimated_seconds: Option<i32>,
     pub gap_percentage: Option<f32>,
 }
+
+impl TimeSummary {
+    /// Summarize a workflow's own sessions, one entry per tracked step.
+    #[must_use]
+    pub fn from_sessions(workflow_instance_id: Uuid, sessions: &[TimeSession]) -> Self {
+        let mut summary = Self {
+            workflow_instance_id,
+            total_seconds: 0,
+            sub_workflow_seconds: 0,
+            step_times: Vec::new(),
+        };
+        for session in sessions.iter().filter(|s| s.workflow_instance_id == workflow_instance_id) {
+            summary.step_mut(session.step_index).actual_seconds += session.total_seconds;
+            summary.total_seconds += session.total_seconds;
+        }
+        summary
+    }
+
+    /// Add time spent in a sub-workflow spawned from `step_index`.
+    pub fn add_sub_workflow_time(&mut self, step_index: i32, seconds: i32) {
+        let step = self.step_mut(step_index);
+        step.actual_seconds += seconds;
+        step.sub_workflow_seconds += seconds;
+        self.sub_workflow_seconds += seconds;
+        self.total_seconds += seconds;
+    }
+
+    /// Fill in a step's estimate and the gap between actual and estimated time.
+    pub fn set_estimate(&mut self, step_index: i32, estimated_seconds: i32) {
+        let step = self.step_mut(step_index);
+        step.estimated_seconds = Some(estimated_seconds);
+        step.gap_percentage = (estimated_seconds > 0).then(|| {
+            (step.actual_seconds - estimated_seconds) as f32 / estimated_seconds as f32 * 100.0
+        });
+    }
+
+    fn step_mut(&mut self, step_index: i32) -> &mut StepTime {
+        let position = match self.step_times.binary_search_by_key(&step_index, |s| s.step_index) {
+            Ok(position) => position,
+            Err(position) => {
+                self.step_times.insert(
+                    position,
+                    StepTime {
+                        step_index,
+                        actual_seconds: 0,
+                        sub_workflow_seconds: 0,
+                        estimated_seconds: None,
+                        gap_percentage: None,
+                    },
+                );
+                position
+            }
+        };
+        &mut self.step_times[position]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session(workflow_instance_id: Uuid, step_index: i32, total_seconds: i32) -> TimeSession {
+        TimeSession {
+            id: Uuid::new_v4(),
+            workflow_instance_id,
+            step_index,
+            started_at: Utc::now(),
+            paused_at: None,
+            resumed_at: None,
+            ended_at: None,
+            total_seconds,
+            is_active: false,
+            last_heartbeat_at: None,
+            pause_reason: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_time_summary_rolls_up_sub_workflows() {
+        let id = Uuid::new_v4();
+        let sessions = vec![session(id, 2, 300), session(id, 0, 120), session(id, 2, 60)];
+
+        let mut summary = TimeSummary::from_sessions(id, &sessions);
+        summary.add_sub_workflow_time(2, 900);
+        summary.add_sub_workflow_time(4, 30);
+        summary.set_estimate(2, 1000);
+
+        let indices: Vec<i32> = summary.step_times.iter().map(|s| s.step_index).collect();
+        assert_eq!(indices, vec![0, 2, 4]);
+        assert_eq!(summary.total_seconds, 1410);
+        assert_eq!(summary.sub_workflow_seconds, 930);
+        let step = &summary.step_times[1];
+        assert_eq!((step.actual_seconds, step.sub_workflow_seconds), (1260, 900));
+        assert_eq!(step.gap_percentage, Some(26.0));
+    }
+}
@@ -0,0 +1,86 @@
+//! Automatic archival of finished workflows.
+//!
+//! The [`WorkflowArchiver`] archives completed and cancelled workflows once
+//! they have been finished for the retention period, keeping the default
+//! listings focused on recent work. Archived workflows stay readable and can
+//! be restored.
+
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use tracing::{info, warn};
+
+use crate::repository::archive_finished_instances;
+
+/// Time between archival runs.
+const RUN_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Archives workflows finished longer ago than the retention period.
+#[derive(Debug, Clone)]
+pub struct WorkflowArchiver {
+    pool: PgPool,
+    archive_after_days: u32,
+}
+
+impl WorkflowArchiver {
+    /// Create an archiver for workflows finished more than
+    /// `archive_after_days` days ago.
+    #[must_use]
+    pub const fn new(pool: PgPool, archive_after_days: u32) -> Self {
+        Self {
+            pool,
+            archive_after_days,
+        }
+    }
+
+    /// Archive every workflow finished before the retention period.
+    ///
+    /// Returns the number archived.
+    ///
+    /// # Errors
+    /// Returns error if the database update fails.
+    pub async fn run_once(&self) -> Result<u64, sqlx::Error> {
+        archive_finished_instances(&self.pool, cutoff(Utc::now(), self.archive_after_days)).await
+    }
+
+    /// Archive finished workflows every hour, in the background.
+    pub fn start(self) {
+        tokio::spawn(async move {
+            info!(
+                archive_after_days = self.archive_after_days,
+                "Workflow archiver started"
+            );
+            let mut ticker = tokio::time::interval(RUN_INTERVAL);
+            loop {
+                ticker.tick().await;
+                match self.run_once().await {
+                    Ok(0) => {}
+                    Ok(count) => info!(count, "Archived finished workflows"),
+                    Err(e) => warn!(error = %e, "Workflow archival failed"),
+                }
+            }
+        });
+    }
+}
+
+/// Workflows finished before this time are archived.
+fn cutoff(now: DateTime<Utc>, days: u32) -> DateTime<Utc> {
+    now - chrono::Duration::days(i64::from(days))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_cutoff_is_retention_days_ago() {
+        let now = Utc.with_ymd_and_hms(2024, 3, 31, 12, 0, 0).unwrap();
+        assert_eq!(
+            cutoff(now, 90),
+            Utc.with_ymd_and_hms(2024, 1, 1, 12, 0, 0).unwrap()
+        );
+        assert_eq!(cutoff(now, 0), now);
+    }
+}
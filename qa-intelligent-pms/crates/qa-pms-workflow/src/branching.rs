@@ -0,0 +1,154 @@
+//! Conditional next-step resolution.
+//!
+//! A step may declare branches that pick the step to continue with from the
+//! metadata submitted when it is completed (e.g. `{"reproduced": false}`).
+//! Branches only jump forward, so every workflow still terminates; a jump
+//! to the step count ends the workflow.
+
+use serde_json::{Map, Value};
+
+use crate::types::{BranchCondition, WorkflowStep};
+
+/// Metadata submitted with a completed step.
+pub type StepMetadata = Map<String, Value>;
+
+impl BranchCondition {
+    /// Whether the metadata holds the expected value for this key.
+    #[must_use]
+    pub fn matches(&self, metadata: &StepMetadata) -> bool {
+        metadata.get(&self.key) == Some(&self.equals)
+    }
+}
+
+/// Index of the step that follows `current`, or `None` when the workflow is
+/// finished.
+///
+/// The first branch whose condition matches wins; without one the workflow
+/// continues with the next step in order.
+#[must_use]
+pub fn resolve_next_step(
+    steps: &[WorkflowStep],
+    current: usize,
+    metadata: &StepMetadata,
+) -> Option<usize> {
+    let next = steps
+        .get(current)
+        .and_then(|step| {
+            step.branches
+                .iter()
+                .find(|branch| branch.when.as_ref().map_or(true, |c| c.matches(metadata)))
+        })
+        .map_or(current + 1, |branch| branch.next_step);
+    (next < steps.len()).then_some(next)
+}
+
+/// Check that every branch jumps forward and stays within the template.
+///
+/// # Errors
+/// Returns a description of the first invalid branch.
+pub fn validate_branches(steps: &[WorkflowStep]) -> Result<(), String> {
+    for (index, step) in steps.iter().enumerate() {
+        for branch in &step.branches {
+            if branch.next_step <= index || branch.next_step > steps.len() {
+                return Err(format!(
+                    "Step {} ({}) branches to step {}; branches must point to a later step or to {} to finish",
+                    index, step.name, branch.next_step, steps.len()
+                ));
+            }
+            if branch.when.as_ref().is_some_and(|c| c.key.trim().is_empty()) {
+                return Err(format!("Step {index} ({}) has a branch condition without a key", step.name));
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::StepBranch;
+    use serde_json::json;
+
+    fn step(name: &str, branches: Vec<StepBranch>) -> WorkflowStep {
+        WorkflowStep {
+            name: name.to_string(),
+            description: String::new(),
+            estimated_minutes: 10,
+            branches,
+        }
+    }
+
+    fn branch(when: Option<(&str, Value)>, next_step: usize) -> StepBranch {
+        StepBranch {
+            when: when.map(|(key, equals)| BranchCondition {
+                key: key.to_string(),
+                equals,
+            }),
+            next_step,
+        }
+    }
+
+    fn metadata(value: Value) -> StepMetadata {
+        value.as_object().cloned().unwrap()
+    }
+
+    /// Reproduce → (A, B) when reproduced, otherwise C; then Close.
+    fn bug_verification() -> Vec<WorkflowStep> {
+        vec![
+            step(
+                "Reproduce",
+                vec![branch(Some(("reproduced", json!(true))), 1), branch(None, 3)],
+            ),
+            step("A", vec![]),
+            step("B", vec![branch(None, 4)]),
+            step("C", vec![]),
+            step("Close", vec![]),
+        ]
+    }
+
+    #[test]
+    fn test_resolve_next_step_follows_matching_branch() {
+        let steps = bug_verification();
+        let reproduced = metadata(json!({ "reproduced": true }));
+        let not_reproduced = metadata(json!({ "reproduced": false }));
+
+        assert_eq!(resolve_next_step(&steps, 0, &reproduced), Some(1));
+        assert_eq!(resolve_next_step(&steps, 0, &not_reproduced), Some(3));
+        assert_eq!(resolve_next_step(&steps, 0, &StepMetadata::new()), Some(3));
+        assert_eq!(resolve_next_step(&steps, 1, &reproduced), Some(2));
+        assert_eq!(resolve_next_step(&steps, 2, &reproduced), Some(4));
+        assert_eq!(resolve_next_step(&steps, 4, &reproduced), None);
+    }
+
+    #[test]
+    fn test_resolve_next_step_without_default_falls_through() {
+        let mut steps = bug_verification();
+        steps[0].branches = vec![branch(Some(("reproduced", json!(false))), 5)];
+
+        assert_eq!(
+            resolve_next_step(&steps, 0, &metadata(json!({ "reproduced": false }))),
+            None
+        );
+        assert_eq!(
+            resolve_next_step(&steps, 0, &metadata(json!({ "reproduced": "no" }))),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn test_validate_branches() {
+        assert!(validate_branches(&bug_verification()).is_ok());
+
+        let mut backwards = bug_verification();
+        backwards[3].branches = vec![branch(None, 1)];
+        assert!(validate_branches(&backwards).unwrap_err().contains("Step 3 (C)"));
+
+        let mut past_end = bug_verification();
+        past_end[1].branches = vec![branch(None, 6)];
+        assert!(validate_branches(&past_end).is_err());
+
+        let mut blank_key = bug_verification();
+        blank_key[1].branches = vec![branch(Some((" ", json!(1))), 2)];
+        assert!(validate_branches(&blank_key).is_err());
+    }
+}
@@ -0,0 +1,160 @@
+//! Step-level diff between two template versions.
+//!
+//! Steps are matched by name, keeping their relative order, so a renamed
+//! step shows up as one removal and one addition. Matched steps whose
+//! description, estimate or branches changed are reported as modified.
+
+use crate::types::WorkflowStep;
+
+/// How a step differs between two versions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StepChangeKind {
+    /// Only in the newer version
+    Added,
+    /// Only in the older version
+    Removed,
+    /// In both, with a different description, estimate or branches
+    Modified,
+    /// In both and identical
+    Unchanged,
+}
+
+/// One entry of a step diff.
+#[derive(Debug, Clone)]
+pub struct StepChange {
+    /// Kind of change
+    pub kind: StepChangeKind,
+    /// Position in the older version
+    pub from_index: Option<usize>,
+    /// Position in the newer version
+    pub to_index: Option<usize>,
+    /// Step in the older version
+    pub before: Option<WorkflowStep>,
+    /// Step in the newer version
+    pub after: Option<WorkflowStep>,
+}
+
+/// Diff two step lists, in newer-version order with removals placed where
+/// they used to be.
+#[must_use]
+pub fn diff_steps(old: &[WorkflowStep], new: &[WorkflowStep]) -> Vec<StepChange> {
+    // Longest common subsequence of step names
+    let mut lcs = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i].name == new[j].name {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut changes = Vec::with_capacity(old.len().max(new.len()));
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i].name == new[j].name {
+            let unchanged = old[i].description == new[j].description
+                && old[i].estimated_minutes == new[j].estimated_minutes
+                && old[i].branches == new[j].branches;
+            changes.push(StepChange {
+                kind: if unchanged {
+                    StepChangeKind::Unchanged
+                } else {
+                    StepChangeKind::Modified
+                },
+                from_index: Some(i),
+                to_index: Some(j),
+                before: Some(old[i].clone()),
+                after: Some(new[j].clone()),
+            });
+            i += 1;
+            j += 1;
+        } else if j < new.len() && (i == old.len() || lcs[i][j + 1] >= lcs[i + 1][j]) {
+            changes.push(StepChange {
+                kind: StepChangeKind::Added,
+                from_index: None,
+                to_index: Some(j),
+                before: None,
+                after: Some(new[j].clone()),
+            });
+            j += 1;
+        } else {
+            changes.push(StepChange {
+                kind: StepChangeKind::Removed,
+                from_index: Some(i),
+                to_index: None,
+                before: Some(old[i].clone()),
+                after: None,
+            });
+            i += 1;
+        }
+    }
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn step(name: &str, minutes: i32) -> WorkflowStep {
+        WorkflowStep {
+            name: name.to_string(),
+            description: String::new(),
+            estimated_minutes: minutes,
+            branches: Vec::new(),
+        }
+    }
+
+    fn kinds(changes: &[StepChange]) -> Vec<(StepChangeKind, &str)> {
+        changes
+            .iter()
+            .map(|c| {
+                let step = c.after.as_ref().or(c.before.as_ref()).unwrap();
+                (c.kind, step.name.as_str())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_diff_steps_reports_each_kind() {
+        let old = vec![
+            step("Reproduce", 15),
+            step("Fix check", 10),
+            step("Close", 5),
+        ];
+        let new = vec![
+            step("Reproduce", 20),
+            step("Regression", 30),
+            step("Close", 5),
+        ];
+
+        let changes = diff_steps(&old, &new);
+        assert_eq!(
+            kinds(&changes),
+            vec![
+                (StepChangeKind::Modified, "Reproduce"),
+                (StepChangeKind::Added, "Regression"),
+                (StepChangeKind::Removed, "Fix check"),
+                (StepChangeKind::Unchanged, "Close"),
+            ]
+        );
+        assert_eq!(changes[0].before.as_ref().unwrap().estimated_minutes, 15);
+        assert_eq!(changes[0].after.as_ref().unwrap().estimated_minutes, 20);
+        assert_eq!(changes[2].from_index, Some(1));
+        assert_eq!(changes[3].to_index, Some(2));
+    }
+
+    #[test]
+    fn test_diff_steps_identical_and_empty() {
+        let steps = vec![step("A", 1), step("B", 2)];
+        assert!(diff_steps(&steps, &steps)
+            .iter()
+            .all(|c| c.kind == StepChangeKind::Unchanged));
+        assert_eq!(
+            kinds(&diff_steps(&[], &steps)),
+            vec![(StepChangeKind::Added, "A"), (StepChangeKind::Added, "B")]
+        );
+        assert!(diff_steps(&[], &[]).is_empty());
+    }
+}
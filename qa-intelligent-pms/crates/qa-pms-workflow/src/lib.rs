@@ -4,14 +4,23 @@
 //!
 //! This crate provides:
 //! - Workflow templates for different ticket types
-//! - Step-by-step workflow execution
+//! - Step-by-step workflow execution with conditional branching
+//! - Sub-workflows spawned from a step of a parent workflow
 //! - Workflow state persistence
+//! - Template versioning with per-instance pinning
+//! - Archival and soft deletion of finished workflows
 //! - Report generation
 
+pub mod archival;
+pub mod branching;
+pub mod diff;
 pub mod repository;
 pub mod seeding;
 pub mod types;
 
+pub use archival::*;
+pub use branching::*;
+pub use diff::*;
 pub use repository::*;
 pub use seeding::*;
 pub use types::*;
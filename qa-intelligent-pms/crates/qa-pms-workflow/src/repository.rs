@@ -1,12 +1,21 @@
 //! Workflow repository functions.
 //!
-//! Database operations for workflow templates, instances, and step results.
+//! Database operations for workflow templates, instances, sub-workflows,
+//! and step results.
+//!
+//! Finished workflow instances can be archived or soft-deleted. Both only set
+//! a timestamp: the instance lookups and listings here hide them, while the
+//! analytics and time queries elsewhere keep counting them so history stays
+//! intact. A restore clears both.
 
+use chrono::{DateTime, Utc};
+use qa_pms_core::types::PageRequest;
 use sqlx::PgPool;
 use uuid::Uuid;
 
 use crate::types::{
-    StepLink, WorkflowInstance, WorkflowStep, WorkflowStepResult, WorkflowTemplate,
+    StepLink, SubWorkflowLink, TemplateChanges, TemplateDeletion, TemplateUpdate, TemplateVersion,
+    WorkflowInstance, WorkflowStep, WorkflowStepResult, WorkflowTemplate,
 };
 
 // ============================================================================
@@ -21,9 +30,9 @@ pub async fn get_default_templates(pool: &PgPool) -> Result<Vec<WorkflowTemplate
     sqlx::query_as::<_, WorkflowTemplate>(
         r"
         SELECT id, name, description, ticket_type, 
-               steps_json, is_default, created_at, updated_at
+               steps_json, is_default, version, archived_at, created_at, updated_at
         FROM workflow_templates
-        WHERE is_default = true
+        WHERE is_default = true AND archived_at IS NULL
         ORDER BY name
         ",
     )
@@ -33,6 +42,9 @@ pub async fn get_default_templates(pool: &PgPool) -> Result<Vec<WorkflowTemplate
 
 /// Get template by ID.
 ///
+/// Deleted templates are still returned so workflows started from them
+/// keep resolving.
+///
 /// # Errors
 /// Returns error if database query fails.
 pub async fn get_template(
@@ -42,7 +54,7 @@ pub async fn get_template(
     sqlx::query_as::<_, WorkflowTemplate>(
         r"
         SELECT id, name, description, ticket_type,
-               steps_json, is_default, created_at, updated_at
+               steps_json, is_default, version, archived_at, created_at, updated_at
         FROM workflow_templates
         WHERE id = $1
         ",
@@ -63,9 +75,9 @@ pub async fn get_templates_by_type(
     sqlx::query_as::<_, WorkflowTemplate>(
         r"
         SELECT id, name, description, ticket_type,
-               steps_json, is_default, created_at, updated_at
+               steps_json, is_default, version, archived_at, created_at, updated_at
         FROM workflow_templates
-        WHERE ticket_type = $1
+        WHERE ticket_type = $1 AND archived_at IS NULL
         ORDER BY is_default DESC, name
         ",
     )
@@ -82,8 +94,9 @@ pub async fn get_all_templates(pool: &PgPool) -> Result<Vec<WorkflowTemplate>, s
     sqlx::query_as::<_, WorkflowTemplate>(
         r"
         SELECT id, name, description, ticket_type,
-               steps_json, is_default, created_at, updated_at
+               steps_json, is_default, version, archived_at, created_at, updated_at
         FROM workflow_templates
+        WHERE archived_at IS NULL
         ORDER BY is_default DESC, ticket_type, name
         ",
     )
@@ -103,13 +116,14 @@ pub async fn create_template(
     steps: &[WorkflowStep],
     is_default: bool,
 ) -> Result<WorkflowTemplate, sqlx::Error> {
-    let steps_json = serde_json::to_value(steps).expect("Failed to serialize steps");
+    let steps_json =
+        serde_json::to_value(steps).map_err(|e| sqlx::Error::Protocol(format!("Failed to serialize steps: {e}")))?;
 
     sqlx::query_as::<_, WorkflowTemplate>(
         r"
         INSERT INTO workflow_templates (name, description, ticket_type, steps_json, is_default)
         VALUES ($1, $2, $3, $4, $5)
-        RETURNING id, name, description, ticket_type, steps_json, is_default, created_at, updated_at
+        RETURNING id, name, description, ticket_type, steps_json, is_default, version, archived_at, created_at, updated_at
         ",
     )
     .bind(name)
@@ -121,47 +135,261 @@ pub async fn create_template(
     .await
 }
 
+/// Replace a template's name, type and steps, archiving the previous version.
+///
+/// With `expected_version` set the update only applies if the template is
+/// still at that version, so concurrent editors cannot overwrite each other.
+///
+/// # Errors
+/// Returns error if database operations fail.
+pub async fn update_template(
+    pool: &PgPool,
+    id: Uuid,
+    changes: &TemplateChanges<'_>,
+    expected_version: Option<i32>,
+) -> Result<TemplateUpdate, sqlx::Error> {
+    let steps_json = serde_json::to_value(changes.steps)
+        .map_err(|e| sqlx::Error::Protocol(format!("Failed to serialize steps: {e}")))?;
+
+    let mut tx = pool.begin().await?;
+    let current = sqlx::query_as::<_, WorkflowTemplate>(
+        r"
+        SELECT id, name, description, ticket_type,
+               steps_json, is_default, version, archived_at, created_at, updated_at
+        FROM workflow_templates
+        WHERE id = $1 AND archived_at IS NULL
+        FOR UPDATE
+        ",
+    )
+    .bind(id)
+    .fetch_optional(&mut *tx)
+    .await?;
+    let Some(current) = current else {
+        return Ok(TemplateUpdate::NotFound);
+    };
+    if expected_version.is_some_and(|expected| expected != current.version) {
+        return Ok(TemplateUpdate::VersionConflict {
+            current: current.version,
+        });
+    }
+
+    sqlx::query(
+        r"
+        INSERT INTO workflow_template_versions
+            (template_id, version, name, description, ticket_type, steps_json, created_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        ",
+    )
+    .bind(current.id)
+    .bind(current.version)
+    .bind(&current.name)
+    .bind(&current.description)
+    .bind(&current.ticket_type)
+    .bind(&current.steps_json)
+    .bind(current.updated_at)
+    .execute(&mut *tx)
+    .await?;
+
+    let updated = sqlx::query_as::<_, WorkflowTemplate>(
+        r"
+        UPDATE workflow_templates
+        SET name = $2, description = $3, ticket_type = $4, steps_json = $5,
+            version = version + 1, updated_at = NOW()
+        WHERE id = $1
+        RETURNING id, name, description, ticket_type, steps_json, is_default, version, archived_at, created_at, updated_at
+        ",
+    )
+    .bind(id)
+    .bind(changes.name)
+    .bind(changes.description)
+    .bind(changes.ticket_type)
+    .bind(steps_json)
+    .fetch_one(&mut *tx)
+    .await?;
+    tx.commit().await?;
+
+    Ok(TemplateUpdate::Updated(updated))
+}
+
+/// Delete a template unless it is a default or workflows are still running
+/// on it.
+///
+/// Templates are archived rather than removed: they disappear from listings
+/// and cannot start new workflows, but finished workflows keep their steps.
+///
+/// # Errors
+/// Returns error if database operations fail.
+pub async fn delete_template(pool: &PgPool, id: Uuid) -> Result<TemplateDeletion, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    let is_default: Option<bool> = sqlx::query_scalar(
+        "SELECT is_default FROM workflow_templates WHERE id = $1 AND archived_at IS NULL FOR UPDATE",
+    )
+    .bind(id)
+    .fetch_optional(&mut *tx)
+    .await?;
+    match is_default {
+        None => return Ok(TemplateDeletion::NotFound),
+        Some(true) => return Ok(TemplateDeletion::Default),
+        Some(false) => {}
+    }
+
+    let active_instances: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM workflow_instances WHERE template_id = $1 AND status IN ('active', 'paused')",
+    )
+    .bind(id)
+    .fetch_one(&mut *tx)
+    .await?;
+    if active_instances > 0 {
+        return Ok(TemplateDeletion::InUse { active_instances });
+    }
+
+    sqlx::query("UPDATE workflow_templates SET archived_at = NOW(), updated_at = NOW() WHERE id = $1")
+        .bind(id)
+        .execute(&mut *tx)
+        .await?;
+    tx.commit().await?;
+
+    Ok(TemplateDeletion::Deleted)
+}
+
+/// Earlier versions of a template, newest first.
+///
+/// # Errors
+/// Returns error if database query fails.
+pub async fn get_template_versions(
+    pool: &PgPool,
+    template_id: Uuid,
+) -> Result<Vec<TemplateVersion>, sqlx::Error> {
+    sqlx::query_as::<_, TemplateVersion>(
+        r"
+        SELECT template_id, version, name, description, ticket_type, steps_json, created_at
+        FROM workflow_template_versions
+        WHERE template_id = $1
+        ORDER BY version DESC
+        ",
+    )
+    .bind(template_id)
+    .fetch_all(pool)
+    .await
+}
+
+/// Get one version of a template, current or archived.
+///
+/// # Errors
+/// Returns error if database query fails.
+pub async fn get_template_version(
+    pool: &PgPool,
+    template_id: Uuid,
+    version: i32,
+) -> Result<Option<TemplateVersion>, sqlx::Error> {
+    let archived = sqlx::query_as::<_, TemplateVersion>(
+        r"
+        SELECT template_id, version, name, description, ticket_type, steps_json, created_at
+        FROM workflow_template_versions
+        WHERE template_id = $1 AND version = $2
+        ",
+    )
+    .bind(template_id)
+    .bind(version)
+    .fetch_optional(pool)
+    .await?;
+    if archived.is_some() {
+        return Ok(archived);
+    }
+
+    Ok(get_template(pool, template_id)
+        .await?
+        .filter(|template| template.version == version)
+        .map(|template| TemplateVersion::from(&template)))
+}
+
+/// Get the template as it was when `instance` started.
+///
+/// Workflows keep the steps they started with even if the template is
+/// edited later.
+///
+/// # Errors
+/// Returns error if database query fails.
+pub async fn get_instance_template(
+    pool: &PgPool,
+    instance: &WorkflowInstance,
+) -> Result<Option<WorkflowTemplate>, sqlx::Error> {
+    let Some(template) = get_template(pool, instance.template_id).await? else {
+        return Ok(None);
+    };
+    if template.version == instance.template_version {
+        return Ok(Some(template));
+    }
+
+    let archived = get_template_version(pool, instance.template_id, instance.template_version).await?;
+
+    Ok(Some(match archived {
+        Some(version) => version.apply_to(template),
+        None => template,
+    }))
+}
+
 // ============================================================================
 // Instance Operations
 // ============================================================================
 
-/// Get active workflow for a ticket.
+/// Get active workflow for a ticket, in `tenant_id` when given.
 ///
 /// # Errors
 /// Returns error if database query fails.
 pub async fn get_active_workflow(
     pool: &PgPool,
     ticket_id: &str,
+    tenant_id: Option<&str>,
 ) -> Result<Option<WorkflowInstance>, sqlx::Error> {
     sqlx::query_as::<_, WorkflowInstance>(
         r"
-        SELECT id, template_id, ticket_id, user_id, status,
-               current_step, started_at, paused_at, resumed_at, completed_at,
-               created_at, updated_at
+        SELECT id, template_id, template_version, ticket_id, user_id, tenant_id, status,
+               current_step, started_at, paused_at, resumed_at, completed_at, auto_track,
+               archived_at, deleted_at, created_at, updated_at
         FROM workflow_instances
         WHERE ticket_id = $1 AND status IN ('active', 'paused')
+          AND ($2::TEXT IS NULL OR tenant_id = $2)
+          AND NOT EXISTS (
+              SELECT 1 FROM workflow_sub_workflows
+              WHERE child_instance_id = workflow_instances.id
+          )
         ORDER BY created_at DESC
         LIMIT 1
         ",
     )
     .bind(ticket_id)
+    .bind(tenant_id)
     .fetch_optional(pool)
     .await
 }
 
-/// Get workflow instance by ID.
+/// Get workflow instance by ID, archived included but not soft-deleted.
 ///
 /// # Errors
 /// Returns error if database query fails.
 pub async fn get_instance(
     pool: &PgPool,
     id: Uuid,
+) -> Result<Option<WorkflowInstance>, sqlx::Error> {
+    Ok(get_instance_with_deleted(pool, id)
+        .await?
+        .filter(|instance| instance.deleted_at.is_none()))
+}
+
+/// Get workflow instance by ID, soft-deleted included.
+///
+/// # Errors
+/// Returns error if database query fails.
+pub async fn get_instance_with_deleted(
+    pool: &PgPool,
+    id: Uuid,
 ) -> Result<Option<WorkflowInstance>, sqlx::Error> {
     sqlx::query_as::<_, WorkflowInstance>(
         r"
-        SELECT id, template_id, ticket_id, user_id, status,
-               current_step, started_at, paused_at, resumed_at, completed_at,
-               created_at, updated_at
+        SELECT id, template_id, template_version, ticket_id, user_id, tenant_id, status,
+               current_step, started_at, paused_at, resumed_at, completed_at, auto_track,
+               archived_at, deleted_at, created_at, updated_at
         FROM workflow_instances
         WHERE id = $1
         ",
@@ -171,7 +399,7 @@ pub async fn get_instance(
     .await
 }
 
-/// Get all workflows for a user.
+/// Get all workflows for a user, except archived and deleted ones.
 ///
 /// # Errors
 /// Returns error if database query fails.
@@ -181,11 +409,11 @@ pub async fn get_user_workflows(
 ) -> Result<Vec<WorkflowInstance>, sqlx::Error> {
     sqlx::query_as::<_, WorkflowInstance>(
         r"
-        SELECT id, template_id, ticket_id, user_id, status,
-               current_step, started_at, paused_at, resumed_at, completed_at,
-               created_at, updated_at
+        SELECT id, template_id, template_version, ticket_id, user_id, tenant_id, status,
+               current_step, started_at, paused_at, resumed_at, completed_at, auto_track,
+               archived_at, deleted_at, created_at, updated_at
         FROM workflow_instances
-        WHERE user_id = $1
+        WHERE user_id = $1 AND archived_at IS NULL AND deleted_at IS NULL
         ORDER BY created_at DESC
         ",
     )
@@ -194,28 +422,36 @@ pub async fn get_user_workflows(
     .await
 }
 
-/// Create a new workflow instance.
+/// Create a new workflow instance pinned to the template's current version.
 ///
 /// # Errors
-/// Returns error if database insert fails.
+/// Returns error if database insert fails, or `RowNotFound` if the template
+/// does not exist or has been deleted.
 pub async fn create_instance(
     pool: &PgPool,
     template_id: Uuid,
     ticket_id: &str,
     user_id: &str,
+    tenant_id: &str,
+    auto_track: bool,
 ) -> Result<WorkflowInstance, sqlx::Error> {
     sqlx::query_as::<_, WorkflowInstance>(
         r"
-        INSERT INTO workflow_instances (template_id, ticket_id, user_id)
-        VALUES ($1, $2, $3)
-        RETURNING id, template_id, ticket_id, user_id, status,
-                  current_step, started_at, paused_at, resumed_at, completed_at,
-                  created_at, updated_at
+        INSERT INTO workflow_instances
+            (template_id, template_version, ticket_id, user_id, tenant_id, auto_track)
+        SELECT id, version, $2, $3, $4, $5
+        FROM workflow_templates
+        WHERE id = $1 AND archived_at IS NULL
+        RETURNING id, template_id, template_version, ticket_id, user_id, tenant_id, status,
+                  current_step, started_at, paused_at, resumed_at, completed_at, auto_track,
+                  archived_at, deleted_at, created_at, updated_at
         ",
     )
     .bind(template_id)
     .bind(ticket_id)
     .bind(user_id)
+    .bind(tenant_id)
+    .bind(auto_track)
     .fetch_one(pool)
     .await
 }
@@ -246,9 +482,9 @@ pub async fn update_instance_status(
         SET status = $2, paused_at = COALESCE($3, paused_at), 
             completed_at = COALESCE($4, completed_at)
         WHERE id = $1
-        RETURNING id, template_id, ticket_id, user_id, status,
-                  current_step, started_at, paused_at, resumed_at, completed_at,
-                  created_at, updated_at
+        RETURNING id, template_id, template_version, ticket_id, user_id, tenant_id, status,
+                  current_step, started_at, paused_at, resumed_at, completed_at, auto_track,
+                  archived_at, deleted_at, created_at, updated_at
         ",
     )
     .bind(id)
@@ -273,9 +509,9 @@ pub async fn update_instance_step(
         UPDATE workflow_instances
         SET current_step = $2
         WHERE id = $1
-        RETURNING id, template_id, ticket_id, user_id, status,
-                  current_step, started_at, paused_at, resumed_at, completed_at,
-                  created_at, updated_at
+        RETURNING id, template_id, template_version, ticket_id, user_id, tenant_id, status,
+                  current_step, started_at, paused_at, resumed_at, completed_at, auto_track,
+                  archived_at, deleted_at, created_at, updated_at
         ",
     )
     .bind(id)
@@ -284,6 +520,153 @@ pub async fn update_instance_step(
     .await
 }
 
+// ============================================================================
+// Sub-workflow Operations
+// ============================================================================
+
+/// Spawn a child workflow from a step of `parent_id`.
+///
+/// The child runs on the current version of `template_id` in the parent's
+/// tenant and is recorded against the parent step so its time rolls up into
+/// the parent.
+///
+/// # Errors
+/// Returns `RowNotFound` if the template is missing or archived, or another
+/// error if the insert fails.
+pub async fn create_sub_workflow(
+    pool: &PgPool,
+    parent_id: Uuid,
+    parent_step_index: i32,
+    template_id: Uuid,
+    ticket_id: &str,
+    user_id: &str,
+) -> Result<WorkflowInstance, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    let child = sqlx::query_as::<_, WorkflowInstance>(
+        r"
+        INSERT INTO workflow_instances (template_id, template_version, ticket_id, user_id, tenant_id)
+        SELECT t.id, t.version, $2, $3, p.tenant_id
+        FROM workflow_templates t, workflow_instances p
+        WHERE t.id = $1 AND t.archived_at IS NULL AND p.id = $4
+        RETURNING id, template_id, template_version, ticket_id, user_id, tenant_id, status,
+                  current_step, started_at, paused_at, resumed_at, completed_at, auto_track,
+                  archived_at, deleted_at, created_at, updated_at
+        ",
+    )
+    .bind(template_id)
+    .bind(ticket_id)
+    .bind(user_id)
+    .bind(parent_id)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    sqlx::query(
+        r"
+        INSERT INTO workflow_sub_workflows (parent_instance_id, parent_step_index, child_instance_id)
+        VALUES ($1, $2, $3)
+        ",
+    )
+    .bind(parent_id)
+    .bind(parent_step_index)
+    .bind(child.id)
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok(child)
+}
+
+/// Get the workflows spawned directly from a workflow's steps.
+///
+/// # Errors
+/// Returns error if database query fails.
+pub async fn get_sub_workflows(
+    pool: &PgPool,
+    parent_id: Uuid,
+) -> Result<Vec<SubWorkflowLink>, sqlx::Error> {
+    sqlx::query_as::<_, SubWorkflowLink>(
+        r"
+        SELECT parent_instance_id, parent_step_index, child_instance_id, created_at
+        FROM workflow_sub_workflows
+        WHERE parent_instance_id = $1
+        ORDER BY parent_step_index, created_at
+        ",
+    )
+    .bind(parent_id)
+    .fetch_all(pool)
+    .await
+}
+
+/// Get every link below a workflow, including nested sub-workflows.
+///
+/// # Errors
+/// Returns error if database query fails.
+pub async fn get_sub_workflow_tree(
+    pool: &PgPool,
+    root_id: Uuid,
+) -> Result<Vec<SubWorkflowLink>, sqlx::Error> {
+    sqlx::query_as::<_, SubWorkflowLink>(
+        r"
+        WITH RECURSIVE tree AS (
+            SELECT parent_instance_id, parent_step_index, child_instance_id, created_at
+            FROM workflow_sub_workflows
+            WHERE parent_instance_id = $1
+            UNION
+            SELECT l.parent_instance_id, l.parent_step_index, l.child_instance_id, l.created_at
+            FROM workflow_sub_workflows l
+            JOIN tree t ON l.parent_instance_id = t.child_instance_id
+        )
+        SELECT parent_instance_id, parent_step_index, child_instance_id, created_at
+        FROM tree
+        ",
+    )
+    .bind(root_id)
+    .fetch_all(pool)
+    .await
+}
+
+/// Get the link to the workflow that spawned `child_id`, if any.
+///
+/// # Errors
+/// Returns error if database query fails.
+pub async fn get_parent_link(
+    pool: &PgPool,
+    child_id: Uuid,
+) -> Result<Option<SubWorkflowLink>, sqlx::Error> {
+    sqlx::query_as::<_, SubWorkflowLink>(
+        r"
+        SELECT parent_instance_id, parent_step_index, child_instance_id, created_at
+        FROM workflow_sub_workflows
+        WHERE child_instance_id = $1
+        ",
+    )
+    .bind(child_id)
+    .fetch_optional(pool)
+    .await
+}
+
+/// Get workflow instances by ID, in no particular order.
+///
+/// # Errors
+/// Returns error if database query fails.
+pub async fn get_instances(
+    pool: &PgPool,
+    ids: &[Uuid],
+) -> Result<Vec<WorkflowInstance>, sqlx::Error> {
+    sqlx::query_as::<_, WorkflowInstance>(
+        r"
+        SELECT id, template_id, template_version, ticket_id, user_id, tenant_id, status,
+               current_step, started_at, paused_at, resumed_at, completed_at, auto_track,
+               archived_at, deleted_at, created_at, updated_at
+        FROM workflow_instances
+        WHERE id = ANY($1)
+        ",
+    )
+    .bind(ids)
+    .fetch_all(pool)
+    .await
+}
+
 // ============================================================================
 // Step Result Operations
 // ============================================================================
@@ -345,7 +728,10 @@ pub async fn upsert_step_result(
     notes: Option<&str>,
     links: Option<&[StepLink]>,
 ) -> Result<WorkflowStepResult, sqlx::Error> {
-    let links_json = links.map(|l| serde_json::to_value(l).expect("Failed to serialize links"));
+    let links_json = links
+        .map(serde_json::to_value)
+        .transpose()
+        .map_err(|e| sqlx::Error::Protocol(format!("Failed to serialize links: {e}")))?;
 
     let started_at = if status == "in_progress" {
         Some(chrono::Utc::now())
@@ -384,6 +770,39 @@ pub async fn upsert_step_result(
     .await
 }
 
+/// Append a link to a step without changing its status.
+///
+/// Creates a pending step result if the step has not been started yet.
+///
+/// # Errors
+/// Returns error if database upsert fails.
+pub async fn add_step_link(
+    pool: &PgPool,
+    instance_id: Uuid,
+    step_index: i32,
+    link: &StepLink,
+) -> Result<WorkflowStepResult, sqlx::Error> {
+    let links_json = serde_json::to_value([link])
+        .map_err(|e| sqlx::Error::Protocol(format!("Failed to serialize links: {e}")))?;
+
+    sqlx::query_as::<_, WorkflowStepResult>(
+        r"
+        INSERT INTO workflow_step_results (instance_id, step_index, status, links)
+        VALUES ($1, $2, 'pending', $3)
+        ON CONFLICT (instance_id, step_index)
+        DO UPDATE SET
+            links = COALESCE(workflow_step_results.links, '[]'::jsonb) || EXCLUDED.links
+        RETURNING id, instance_id, step_index, status, notes,
+                  links, started_at, completed_at, created_at, updated_at
+        ",
+    )
+    .bind(instance_id)
+    .bind(step_index)
+    .bind(links_json)
+    .fetch_one(pool)
+    .await
+}
+
 /// Complete a step with notes and links.
 ///
 /// # Errors
@@ -422,6 +841,40 @@ pub async fn skip_step(
     upsert_step_result(pool, instance_id, step_index, "skipped", None, None).await
 }
 
+/// Mark the steps a branch jumped over as skipped.
+///
+/// Covers the steps strictly between `from_step` and `to_step`; steps that
+/// were already completed or skipped are left alone.
+///
+/// # Errors
+/// Returns error if database upsert fails.
+pub async fn skip_bypassed_steps(
+    pool: &PgPool,
+    instance_id: Uuid,
+    from_step: i32,
+    to_step: i32,
+) -> Result<(), sqlx::Error> {
+    if to_step <= from_step + 1 {
+        return Ok(());
+    }
+    sqlx::query(
+        r"
+        INSERT INTO workflow_step_results (instance_id, step_index, status, completed_at)
+        SELECT $1, step_index, 'skipped', NOW()
+        FROM generate_series($2 + 1, $3 - 1) AS step_index
+        ON CONFLICT (instance_id, step_index)
+        DO UPDATE SET status = 'skipped', completed_at = NOW()
+        WHERE workflow_step_results.status NOT IN ('completed', 'skipped')
+        ",
+    )
+    .bind(instance_id)
+    .bind(from_step)
+    .bind(to_step)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
 /// Pause a workflow.
 ///
 /// # Errors
@@ -504,9 +957,9 @@ pub async fn get_all_user_active_workflows(
 ) -> Result<Vec<WorkflowInstance>, sqlx::Error> {
     sqlx::query_as::<_, WorkflowInstance>(
         r"
-        SELECT id, template_id, ticket_id, user_id, status,
-               current_step, started_at, completed_at, paused_at, resumed_at,
-               created_at, updated_at
+        SELECT id, template_id, template_version, ticket_id, user_id, tenant_id, status,
+               current_step, started_at, paused_at, resumed_at, completed_at, auto_track,
+               archived_at, deleted_at, created_at, updated_at
         FROM workflow_instances
         WHERE user_id = $1 AND status IN ('active', 'paused')
         ORDER BY updated_at DESC
@@ -516,3 +969,209 @@ pub async fn get_all_user_active_workflows(
     .fetch_all(pool)
     .await
 }
+
+// ============================================================================
+// Archival Operations
+// ============================================================================
+
+/// Archive a finished workflow.
+///
+/// Returns `None` if the workflow does not exist, is still running, or is
+/// already archived or deleted.
+///
+/// # Errors
+/// Returns error if database update fails.
+pub async fn archive_instance(
+    pool: &PgPool,
+    id: Uuid,
+) -> Result<Option<WorkflowInstance>, sqlx::Error> {
+    set_removed_at(pool, id, "archived_at").await
+}
+
+/// Soft-delete a finished workflow.
+///
+/// Returns `None` if the workflow does not exist, is still running, or is
+/// already deleted.
+///
+/// # Errors
+/// Returns error if database update fails.
+pub async fn delete_instance(
+    pool: &PgPool,
+    id: Uuid,
+) -> Result<Option<WorkflowInstance>, sqlx::Error> {
+    set_removed_at(pool, id, "deleted_at").await
+}
+
+async fn set_removed_at(
+    pool: &PgPool,
+    id: Uuid,
+    column: &str,
+) -> Result<Option<WorkflowInstance>, sqlx::Error> {
+    sqlx::query_as::<_, WorkflowInstance>(&format!(
+        r"
+        UPDATE workflow_instances
+        SET {column} = NOW(), updated_at = NOW()
+        WHERE id = $1 AND {column} IS NULL AND deleted_at IS NULL
+          AND status IN ('completed', 'cancelled')
+        RETURNING id, template_id, template_version, ticket_id, user_id, tenant_id, status,
+                  current_step, started_at, paused_at, resumed_at, completed_at, auto_track,
+                  archived_at, deleted_at, created_at, updated_at
+        "
+    ))
+    .bind(id)
+    .fetch_optional(pool)
+    .await
+}
+
+/// Bring an archived or deleted workflow back into the listings.
+///
+/// Returns `None` if the workflow does not exist or is neither archived nor
+/// deleted.
+///
+/// # Errors
+/// Returns error if database update fails.
+pub async fn restore_instance(
+    pool: &PgPool,
+    id: Uuid,
+) -> Result<Option<WorkflowInstance>, sqlx::Error> {
+    sqlx::query_as::<_, WorkflowInstance>(
+        r"
+        UPDATE workflow_instances
+        SET archived_at = NULL, deleted_at = NULL, updated_at = NOW()
+        WHERE id = $1 AND (archived_at IS NOT NULL OR deleted_at IS NOT NULL)
+        RETURNING id, template_id, template_version, ticket_id, user_id, tenant_id, status,
+                  current_step, started_at, paused_at, resumed_at, completed_at, auto_track,
+                  archived_at, deleted_at, created_at, updated_at
+        ",
+    )
+    .bind(id)
+    .fetch_optional(pool)
+    .await
+}
+
+/// Archive every workflow that finished before `cutoff`.
+///
+/// Cancelled workflows have no completion time, so their last update counts
+/// as the time they finished. Returns the number archived.
+///
+/// # Errors
+/// Returns error if database update fails.
+pub async fn archive_finished_instances(
+    pool: &PgPool,
+    cutoff: DateTime<Utc>,
+) -> Result<u64, sqlx::Error> {
+    let result = sqlx::query(
+        r"
+        UPDATE workflow_instances
+        SET archived_at = NOW(), updated_at = NOW()
+        WHERE status IN ('completed', 'cancelled')
+          AND archived_at IS NULL AND deleted_at IS NULL
+          AND COALESCE(completed_at, updated_at) < $1
+        ",
+    )
+    .bind(cutoff)
+    .execute(pool)
+    .await?;
+    Ok(result.rows_affected())
+}
+
+/// Which workflows a listing includes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum InstanceVisibility {
+    /// Neither archived nor deleted
+    #[default]
+    Current,
+    /// Archived but not deleted
+    Archived,
+    /// Soft-deleted
+    Deleted,
+}
+
+impl InstanceVisibility {
+    const fn condition(self) -> &'static str {
+        match self {
+            Self::Current => "archived_at IS NULL AND deleted_at IS NULL",
+            Self::Archived => "archived_at IS NOT NULL AND deleted_at IS NULL",
+            Self::Deleted => "deleted_at IS NOT NULL",
+        }
+    }
+}
+
+/// Filters for listing workflow instances.
+#[derive(Debug, Clone, Default)]
+pub struct InstanceFilter {
+    /// Only instances started by this user
+    pub user_id: Option<String>,
+    /// Only instances in this status
+    pub status: Option<String>,
+    /// Only instances for this ticket
+    pub ticket_id: Option<String>,
+    /// Only instances for tickets of this project key (`PAY` for `PAY-123`)
+    pub project_key: Option<String>,
+    /// Only instances of this tenant
+    pub tenant_id: Option<String>,
+    /// Current, archived or deleted instances
+    pub visibility: InstanceVisibility,
+}
+
+/// List workflow instances ordered by start time, one keyset page at a time.
+///
+/// Returns up to `page.fetch_limit()` rows; pass them to `PageRequest::finish`.
+///
+/// # Errors
+/// Returns error if database query fails.
+pub async fn list_instances(
+    pool: &PgPool,
+    filter: &InstanceFilter,
+    page: &PageRequest,
+) -> Result<Vec<WorkflowInstance>, sqlx::Error> {
+    let mut conditions = vec![filter.visibility.condition().to_string()];
+    let mut params = 0;
+    for (value, column) in [
+        (&filter.user_id, "user_id"),
+        (&filter.status, "status"),
+        (&filter.ticket_id, "ticket_id"),
+        (&filter.project_key, "SPLIT_PART(ticket_id, '-', 1)"),
+        (&filter.tenant_id, "tenant_id"),
+    ] {
+        if value.is_some() {
+            params += 1;
+            conditions.push(format!("{column} = ${params}"));
+        }
+    }
+    if let Some(keyset) = page.keyset_sql("started_at", "id", params + 1) {
+        conditions.push(keyset);
+    }
+    let sql = format!(
+        r"
+        SELECT id, template_id, template_version, ticket_id, user_id, tenant_id, status,
+               current_step, started_at, paused_at, resumed_at, completed_at, auto_track,
+               archived_at, deleted_at, created_at, updated_at
+        FROM workflow_instances
+        WHERE {conditions}
+        ORDER BY {order}
+        LIMIT {limit}
+        ",
+        conditions = conditions.join(" AND "),
+        order = page.order_sql("started_at", "id"),
+        limit = page.fetch_limit(),
+    );
+
+    let mut query = sqlx::query_as::<_, WorkflowInstance>(&sql);
+    for value in [
+        &filter.user_id,
+        &filter.status,
+        &filter.ticket_id,
+        &filter.project_key,
+        &filter.tenant_id,
+    ]
+    .into_iter()
+    .flatten()
+    {
+        query = query.bind(value);
+    }
+    if let Some((at, id)) = page.keyset() {
+        query = query.bind(at).bind(id);
+    }
+    query.fetch_all(pool).await
+}
@@ -19,26 +19,31 @@ pub fn bug_fix_template_steps() -> Vec<WorkflowStep> {
             name: "Reproduce Bug".to_string(),
             description: "Follow the steps in the ticket to reproduce the bug. Document exact steps, environment, and any variations observed.".to_string(),
             estimated_minutes: 15,
+            branches: Vec::new(),
         },
         WorkflowStep {
             name: "Investigate Root Cause".to_string(),
             description: "Analyze logs, code, and related components to identify the root cause. Note any related issues or dependencies.".to_string(),
             estimated_minutes: 20,
+            branches: Vec::new(),
         },
         WorkflowStep {
             name: "Test Fix".to_string(),
             description: "Verify the fix resolves the original issue. Test with the same steps used to reproduce, plus variations.".to_string(),
             estimated_minutes: 30,
+            branches: Vec::new(),
         },
         WorkflowStep {
             name: "Regression Check".to_string(),
             description: "Ensure the fix doesn't break existing functionality. Run related test cases and check impacted areas.".to_string(),
             estimated_minutes: 20,
+            branches: Vec::new(),
         },
         WorkflowStep {
             name: "Document Findings".to_string(),
             description: "Update the ticket with test results, any issues found, and recommendations. Link related test cases.".to_string(),
             estimated_minutes: 10,
+            branches: Vec::new(),
         },
     ]
 }
@@ -51,26 +56,31 @@ pub fn feature_test_template_steps() -> Vec<WorkflowStep> {
             name: "Review Requirements".to_string(),
             description: "Read the feature requirements, acceptance criteria, and design documents. Identify testable scenarios.".to_string(),
             estimated_minutes: 15,
+            branches: Vec::new(),
         },
         WorkflowStep {
             name: "Exploratory Testing".to_string(),
             description: "Explore the feature freely to understand its behavior. Note unexpected behaviors and potential edge cases.".to_string(),
             estimated_minutes: 45,
+            branches: Vec::new(),
         },
         WorkflowStep {
             name: "Happy Path Testing".to_string(),
             description: "Test the main user flows with valid inputs. Verify all acceptance criteria are met.".to_string(),
             estimated_minutes: 30,
+            branches: Vec::new(),
         },
         WorkflowStep {
             name: "Edge Case Testing".to_string(),
             description: "Test boundary conditions, invalid inputs, error handling, and unusual scenarios.".to_string(),
             estimated_minutes: 30,
+            branches: Vec::new(),
         },
         WorkflowStep {
             name: "Document Test Cases".to_string(),
             description: "Record test cases executed, results, and any bugs found. Update test documentation.".to_string(),
             estimated_minutes: 15,
+            branches: Vec::new(),
         },
     ]
 }
@@ -83,21 +93,25 @@ pub fn regression_template_steps() -> Vec<WorkflowStep> {
             name: "Setup Test Environment".to_string(),
             description: "Prepare the test environment with correct version, data, and configurations. Verify environment health.".to_string(),
             estimated_minutes: 20,
+            branches: Vec::new(),
         },
         WorkflowStep {
             name: "Run Test Suite".to_string(),
             description: "Execute the regression test suite. Monitor for failures and performance issues.".to_string(),
             estimated_minutes: 60,
+            branches: Vec::new(),
         },
         WorkflowStep {
             name: "Analyze Failures".to_string(),
             description: "Investigate any test failures. Determine if failures are bugs, test issues, or environment problems.".to_string(),
             estimated_minutes: 30,
+            branches: Vec::new(),
         },
         WorkflowStep {
             name: "Generate Report".to_string(),
             description: "Create a summary report with pass/fail rates, identified issues, and recommendations.".to_string(),
             estimated_minutes: 15,
+            branches: Vec::new(),
         },
     ]
 }
@@ -161,7 +175,8 @@ pub async fn seed_default_templates(pool: &PgPool) -> Result<SeedingResult, sqlx
 
         // Create the template
         let steps = (template.steps_fn)();
-        let steps_json = serde_json::to_value(&steps).expect("Failed to serialize steps");
+        let steps_json =
+            serde_json::to_value(&steps).map_err(|e| sqlx::Error::Protocol(format!("Failed to serialize steps: {e}")))?;
 
         sqlx::query(
             r"
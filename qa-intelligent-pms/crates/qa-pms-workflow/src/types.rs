@@ -2,6 +2,8 @@
 //!
 //! Database models and domain types for the workflow engine.
 
+use std::collections::HashMap;
+
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
@@ -28,6 +30,7 @@ pub enum WorkflowStatus {
 impl WorkflowStatus {
     /// Convert from database string.
     #[must_use]
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(s: &str) -> Self {
         match s {
             "active" => Self::Active,
@@ -67,6 +70,7 @@ pub enum StepStatus {
 impl StepStatus {
     /// Convert from database string.
     #[must_use]
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(s: &str) -> Self {
         match s {
             "pending" => Self::Pending,
@@ -103,6 +107,31 @@ pub struct WorkflowStep {
     pub description: String,
     /// Estimated time in minutes
     pub estimated_minutes: i32,
+    /// Rules picking the step that follows this one, tried in order.
+    /// Without a matching rule the workflow moves to the next step in order.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub branches: Vec<StepBranch>,
+}
+
+/// Conditional jump taken when a step is completed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StepBranch {
+    /// Condition on the completion metadata; a rule without one always matches
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub when: Option<BranchCondition>,
+    /// Index of the step to continue with; the step count ends the workflow
+    pub next_step: usize,
+}
+
+/// Test against one key of the metadata submitted with a completed step.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BranchCondition {
+    /// Metadata key (e.g., "reproduced")
+    pub key: String,
+    /// Value the key must hold for the branch to be taken
+    pub equals: serde_json::Value,
 }
 
 /// Link attached to a step result.
@@ -134,6 +163,10 @@ pub struct WorkflowTemplate {
     pub steps_json: sqlx::types::Json<Vec<WorkflowStep>>,
     /// Whether this is a default template
     pub is_default: bool,
+    /// Definition version, bumped on every update
+    pub version: i32,
+    /// When the template was deleted; deleted templates start no new workflows
+    pub archived_at: Option<DateTime<Utc>>,
     /// Creation timestamp
     pub created_at: DateTime<Utc>,
     /// Last update timestamp
@@ -154,6 +187,103 @@ impl WorkflowTemplate {
     }
 }
 
+/// Earlier definition of a template, kept when the template is updated.
+#[derive(Debug, Clone, FromRow)]
+pub struct TemplateVersion {
+    /// Template this version belongs to
+    pub template_id: Uuid,
+    /// Version number
+    pub version: i32,
+    /// Template name at this version
+    pub name: String,
+    /// Template description at this version
+    pub description: Option<String>,
+    /// Ticket type at this version
+    pub ticket_type: String,
+    /// Steps at this version
+    pub steps_json: sqlx::types::Json<Vec<WorkflowStep>>,
+    /// When this version was created
+    pub created_at: DateTime<Utc>,
+}
+
+impl TemplateVersion {
+    /// Get the steps of this version.
+    #[must_use]
+    pub fn steps(&self) -> &[WorkflowStep] {
+        &self.steps_json.0
+    }
+
+    /// Overlay this version's definition on the current template.
+    #[must_use]
+    pub fn apply_to(self, template: WorkflowTemplate) -> WorkflowTemplate {
+        WorkflowTemplate {
+            name: self.name,
+            description: self.description,
+            ticket_type: self.ticket_type,
+            steps_json: self.steps_json,
+            version: self.version,
+            ..template
+        }
+    }
+}
+
+impl From<&WorkflowTemplate> for TemplateVersion {
+    fn from(template: &WorkflowTemplate) -> Self {
+        Self {
+            template_id: template.id,
+            version: template.version,
+            name: template.name.clone(),
+            description: template.description.clone(),
+            ticket_type: template.ticket_type.clone(),
+            steps_json: template.steps_json.clone(),
+            created_at: template.updated_at,
+        }
+    }
+}
+
+/// New definition for an existing template.
+#[derive(Debug, Clone, Copy)]
+pub struct TemplateChanges<'a> {
+    /// Template name
+    pub name: &'a str,
+    /// Template description
+    pub description: Option<&'a str>,
+    /// Ticket type the template is for
+    pub ticket_type: &'a str,
+    /// Steps in order
+    pub steps: &'a [WorkflowStep],
+}
+
+/// Outcome of a template update.
+#[derive(Debug, Clone)]
+pub enum TemplateUpdate {
+    /// The template was updated
+    Updated(WorkflowTemplate),
+    /// No template with that ID
+    NotFound,
+    /// The template changed since the caller read it
+    VersionConflict {
+        /// Version currently stored
+        current: i32,
+    },
+}
+
+/// Outcome of a template deletion.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TemplateDeletion {
+    /// The template was deleted
+    Deleted,
+    /// No template with that ID
+    NotFound,
+    /// Default templates cannot be deleted
+    Default,
+    /// Workflows are still running on the template
+    InUse {
+        /// Active or paused workflows using it
+        active_instances: i64,
+    },
+}
+
 /// Workflow instance for a specific ticket.
 #[derive(Debug, Clone, FromRow)]
 pub struct WorkflowInstance {
@@ -161,10 +291,14 @@ pub struct WorkflowInstance {
     pub id: Uuid,
     /// Template this instance is based on
     pub template_id: Uuid,
+    /// Template version the instance started on
+    pub template_version: i32,
     /// Jira ticket key (e.g., "PROJ-123")
     pub ticket_id: String,
     /// User who started the workflow
     pub user_id: String,
+    /// Tenant the workflow belongs to
+    pub tenant_id: String,
     /// Current status (stored as string in DB)
     pub status: String,
     /// Current step index (0-based)
@@ -177,6 +311,12 @@ pub struct WorkflowInstance {
     pub resumed_at: Option<DateTime<Utc>>,
     /// When the workflow was completed (if completed)
     pub completed_at: Option<DateTime<Utc>>,
+    /// Whether time sessions follow the current step automatically
+    pub auto_track: bool,
+    /// When the workflow was archived (if archived)
+    pub archived_at: Option<DateTime<Utc>>,
+    /// When the workflow was deleted (if soft-deleted)
+    pub deleted_at: Option<DateTime<Utc>>,
     /// Creation timestamp
     pub created_at: DateTime<Utc>,
     /// Last update timestamp
@@ -204,6 +344,50 @@ impl WorkflowInstance {
             WorkflowStatus::Active | WorkflowStatus::Paused
         )
     }
+
+    /// Check if workflow has ended, so it may be archived or deleted.
+    #[must_use]
+    pub fn is_finished(&self) -> bool {
+        matches!(
+            self.status_enum(),
+            WorkflowStatus::Completed | WorkflowStatus::Cancelled
+        )
+    }
+}
+
+/// Child workflow spawned from a step of another workflow.
+#[derive(Debug, Clone, FromRow)]
+pub struct SubWorkflowLink {
+    /// Workflow that spawned the child
+    pub parent_instance_id: Uuid,
+    /// Parent step the child was spawned from
+    pub parent_step_index: i32,
+    /// The spawned workflow
+    pub child_instance_id: Uuid,
+    /// When the child was spawned
+    pub created_at: DateTime<Utc>,
+}
+
+impl SubWorkflowLink {
+    /// Map every descendant of `root` to the root step it was spawned under,
+    /// following nested sub-workflows down to any depth.
+    #[must_use]
+    pub fn root_steps(root: Uuid, links: &[Self]) -> HashMap<Uuid, i32> {
+        let mut root_steps = HashMap::new();
+        let mut frontier: Vec<(Uuid, Option<i32>)> = vec![(root, None)];
+        while let Some((parent, root_step)) = frontier.pop() {
+            for link in links.iter().filter(|l| l.parent_instance_id == parent) {
+                let step = root_step.unwrap_or(link.parent_step_index);
+                // Guards against cycles in malformed data
+                if link.child_instance_id != root
+                    && root_steps.insert(link.child_instance_id, step).is_none()
+                {
+                    frontier.push((link.child_instance_id, Some(step)));
+                }
+            }
+        }
+        root_steps
+    }
 }
 
 /// Result of a completed workflow step.
@@ -269,6 +453,7 @@ pub struct TemplateSummary {
     pub step_count: usize,
     pub estimated_minutes: i32,
     pub is_default: bool,
+    pub version: i32,
 }
 
 impl From<&WorkflowTemplate> for TemplateSummary {
@@ -281,6 +466,7 @@ impl From<&WorkflowTemplate> for TemplateSummary {
             step_count: t.steps().len(),
             estimated_minutes: t.total_estimated_minutes(),
             is_default: t.is_default,
+            version: t.version,
         }
     }
 }
@@ -319,11 +505,51 @@ mod tests {
             name: "Test Step".to_string(),
             description: "Do something".to_string(),
             estimated_minutes: 15,
+            branches: Vec::new(),
         };
 
         let json = serde_json::to_string(&step).unwrap();
         assert!(json.contains("\"name\":\"Test Step\""));
         assert!(json.contains("\"estimatedMinutes\":15"));
+        assert!(!json.contains("branches"));
+    }
+
+    #[test]
+    fn test_template_version_overlays_definition() {
+        let step = |name: &str, minutes| WorkflowStep {
+            name: name.to_string(),
+            description: String::new(),
+            estimated_minutes: minutes,
+            branches: Vec::new(),
+        };
+        let current = WorkflowTemplate {
+            id: Uuid::new_v4(),
+            name: "Bug Fix v2".to_string(),
+            description: None,
+            ticket_type: "bug".to_string(),
+            steps_json: sqlx::types::Json(vec![step("Reproduce", 10), step("Verify", 20)]),
+            is_default: false,
+            version: 2,
+            archived_at: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+        let v1 = TemplateVersion {
+            template_id: current.id,
+            version: 1,
+            name: "Bug Fix".to_string(),
+            description: Some("First draft".to_string()),
+            ticket_type: "bug".to_string(),
+            steps_json: sqlx::types::Json(vec![step("Reproduce", 15)]),
+            created_at: Utc::now(),
+        };
+
+        let pinned = v1.apply_to(current.clone());
+        assert_eq!(pinned.id, current.id);
+        assert_eq!(pinned.version, 1);
+        assert_eq!(pinned.name, "Bug Fix");
+        assert_eq!(pinned.steps().len(), 1);
+        assert_eq!(pinned.total_estimated_minutes(), 15);
     }
 
     #[test]
@@ -336,4 +562,28 @@ mod tests {
         let json = serde_json::to_string(&link).unwrap();
         assert!(json.contains("\"title\":\"Bug Report\""));
     }
+
+    #[test]
+    fn test_sub_workflow_root_steps_follow_nesting() {
+        let link = |parent, step, child| SubWorkflowLink {
+            parent_instance_id: parent,
+            parent_step_index: step,
+            child_instance_id: child,
+            created_at: Utc::now(),
+        };
+        let (root, regression, smoke, other, unrelated) =
+            (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let links = vec![
+            link(root, 2, regression),
+            link(regression, 0, smoke),
+            link(root, 3, other),
+            link(unrelated, 1, Uuid::new_v4()),
+        ];
+
+        let steps = SubWorkflowLink::root_steps(root, &links);
+        assert_eq!(steps.len(), 3);
+        assert_eq!(steps[&regression], 2);
+        assert_eq!(steps[&smoke], 2);
+        assert_eq!(steps[&other], 3);
+    }
 }
@@ -0,0 +1,354 @@
+-- Schema the server had before versioned migrations were embedded.
+-- Every statement is idempotent, so databases created before this
+-- migration existed pick it up without changes.
+
+-- Workflows
+
+CREATE TABLE IF NOT EXISTS workflow_templates (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    name VARCHAR(255) NOT NULL,
+    description TEXT,
+    ticket_type VARCHAR(50) NOT NULL,
+    steps_json JSONB NOT NULL DEFAULT '[]',
+    is_default BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_workflow_templates_ticket_type
+    ON workflow_templates (ticket_type);
+
+CREATE TABLE IF NOT EXISTS workflow_instances (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    template_id UUID NOT NULL REFERENCES workflow_templates (id),
+    ticket_id VARCHAR(50) NOT NULL,
+    user_id VARCHAR(255) NOT NULL,
+    status VARCHAR(20) NOT NULL DEFAULT 'active',
+    current_step INTEGER NOT NULL DEFAULT 0,
+    started_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    paused_at TIMESTAMPTZ,
+    resumed_at TIMESTAMPTZ,
+    completed_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_workflow_instances_ticket_id ON workflow_instances (ticket_id);
+CREATE INDEX IF NOT EXISTS idx_workflow_instances_user_id ON workflow_instances (user_id);
+CREATE INDEX IF NOT EXISTS idx_workflow_instances_status ON workflow_instances (status);
+CREATE INDEX IF NOT EXISTS idx_workflow_instances_completed_at
+    ON workflow_instances (completed_at);
+
+CREATE TABLE IF NOT EXISTS workflow_step_results (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    instance_id UUID NOT NULL REFERENCES workflow_instances (id) ON DELETE CASCADE,
+    step_index INTEGER NOT NULL,
+    status VARCHAR(20) NOT NULL DEFAULT 'pending',
+    notes TEXT,
+    links JSONB,
+    started_at TIMESTAMPTZ,
+    completed_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (instance_id, step_index)
+);
+
+CREATE TABLE IF NOT EXISTS workflow_reports (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    workflow_instance_id UUID NOT NULL REFERENCES workflow_instances (id) ON DELETE CASCADE,
+    ticket_id VARCHAR(50) NOT NULL,
+    ticket_title TEXT,
+    template_name VARCHAR(255) NOT NULL,
+    content JSONB NOT NULL,
+    total_time_seconds INTEGER NOT NULL DEFAULT 0,
+    generated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_workflow_reports_instance
+    ON workflow_reports (workflow_instance_id, generated_at DESC);
+
+-- Time tracking
+
+CREATE TABLE IF NOT EXISTS time_sessions (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    workflow_instance_id UUID NOT NULL REFERENCES workflow_instances (id) ON DELETE CASCADE,
+    step_index INTEGER NOT NULL,
+    started_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    paused_at TIMESTAMPTZ,
+    resumed_at TIMESTAMPTZ,
+    ended_at TIMESTAMPTZ,
+    total_seconds INTEGER NOT NULL DEFAULT 0,
+    is_active BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (workflow_instance_id, step_index)
+);
+
+CREATE INDEX IF NOT EXISTS idx_time_sessions_ended_at ON time_sessions (ended_at);
+
+CREATE TABLE IF NOT EXISTS time_pause_events (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    session_id UUID NOT NULL REFERENCES time_sessions (id) ON DELETE CASCADE,
+    paused_at TIMESTAMPTZ NOT NULL,
+    resumed_at TIMESTAMPTZ,
+    duration_seconds INTEGER,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_time_pause_events_session ON time_pause_events (session_id);
+
+CREATE TABLE IF NOT EXISTS time_estimates (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    template_id UUID NOT NULL REFERENCES workflow_templates (id) ON DELETE CASCADE,
+    step_index INTEGER NOT NULL,
+    estimated_seconds INTEGER NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (template_id, step_index)
+);
+
+CREATE TABLE IF NOT EXISTS time_daily_aggregates (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL,
+    aggregate_date DATE NOT NULL,
+    tickets_completed INTEGER NOT NULL DEFAULT 0,
+    total_time_seconds INTEGER NOT NULL DEFAULT 0,
+    total_estimated_seconds INTEGER NOT NULL DEFAULT 0,
+    avg_time_per_ticket_seconds INTEGER NOT NULL DEFAULT 0,
+    efficiency_ratio NUMERIC(8, 4) NOT NULL DEFAULT 0,
+    bug_tickets INTEGER NOT NULL DEFAULT 0,
+    bug_time_seconds INTEGER NOT NULL DEFAULT 0,
+    feature_tickets INTEGER NOT NULL DEFAULT 0,
+    feature_time_seconds INTEGER NOT NULL DEFAULT 0,
+    regression_tickets INTEGER NOT NULL DEFAULT 0,
+    regression_time_seconds INTEGER NOT NULL DEFAULT 0,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (user_id, aggregate_date)
+);
+
+CREATE INDEX IF NOT EXISTS idx_time_daily_aggregates_date
+    ON time_daily_aggregates (aggregate_date);
+
+CREATE TABLE IF NOT EXISTS time_step_averages (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    template_id UUID NOT NULL REFERENCES workflow_templates (id) ON DELETE CASCADE,
+    step_index INTEGER NOT NULL,
+    sample_count INTEGER NOT NULL DEFAULT 0,
+    total_seconds INTEGER NOT NULL DEFAULT 0,
+    avg_seconds INTEGER NOT NULL DEFAULT 0,
+    min_seconds INTEGER NOT NULL DEFAULT 0,
+    max_seconds INTEGER NOT NULL DEFAULT 0,
+    std_dev_seconds NUMERIC(12, 2) NOT NULL DEFAULT 0,
+    last_sample_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (template_id, step_index)
+);
+
+CREATE TABLE IF NOT EXISTS time_user_averages (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL,
+    ticket_type VARCHAR(50) NOT NULL,
+    sample_count INTEGER NOT NULL DEFAULT 0,
+    total_seconds INTEGER NOT NULL DEFAULT 0,
+    avg_seconds INTEGER NOT NULL DEFAULT 0,
+    min_seconds INTEGER NOT NULL DEFAULT 0,
+    max_seconds INTEGER NOT NULL DEFAULT 0,
+    rolling_avg_seconds INTEGER NOT NULL DEFAULT 0,
+    rolling_sample_count INTEGER NOT NULL DEFAULT 0,
+    last_sample_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (user_id, ticket_type)
+);
+
+CREATE TABLE IF NOT EXISTS time_gap_alerts (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    workflow_instance_id UUID NOT NULL REFERENCES workflow_instances (id) ON DELETE CASCADE,
+    step_index INTEGER,
+    user_id UUID NOT NULL,
+    actual_seconds INTEGER NOT NULL,
+    estimated_seconds INTEGER NOT NULL,
+    gap_percentage NUMERIC(8, 2) NOT NULL,
+    alert_type VARCHAR(50) NOT NULL,
+    dismissed BOOLEAN NOT NULL DEFAULT FALSE,
+    dismissed_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_time_gap_alerts_user
+    ON time_gap_alerts (user_id, created_at DESC) WHERE NOT dismissed;
+
+-- Patterns and alerts
+
+CREATE TABLE IF NOT EXISTS detected_patterns (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    pattern_type VARCHAR(50) NOT NULL,
+    severity VARCHAR(20) NOT NULL,
+    title TEXT NOT NULL,
+    description TEXT,
+    affected_tickets TEXT[] NOT NULL DEFAULT '{}',
+    common_factor TEXT,
+    average_excess_percent DOUBLE PRECISION,
+    confidence_score DOUBLE PRECISION NOT NULL DEFAULT 0,
+    suggested_actions TEXT[] NOT NULL DEFAULT '{}',
+    metadata JSONB NOT NULL DEFAULT '{}',
+    detected_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_detected_patterns_type
+    ON detected_patterns (pattern_type, detected_at DESC);
+CREATE INDEX IF NOT EXISTS idx_detected_patterns_detected_at
+    ON detected_patterns (detected_at DESC);
+
+CREATE TABLE IF NOT EXISTS alerts (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    pattern_id UUID REFERENCES detected_patterns (id) ON DELETE SET NULL,
+    alert_type VARCHAR(50) NOT NULL,
+    severity VARCHAR(20) NOT NULL,
+    title TEXT NOT NULL,
+    message TEXT,
+    affected_tickets TEXT[] NOT NULL DEFAULT '{}',
+    suggested_actions TEXT[] NOT NULL DEFAULT '{}',
+    is_read BOOLEAN NOT NULL DEFAULT FALSE,
+    is_dismissed BOOLEAN NOT NULL DEFAULT FALSE,
+    dismissed_at TIMESTAMPTZ,
+    dismissed_by VARCHAR(255),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_alerts_created_at ON alerts (created_at DESC);
+CREATE INDEX IF NOT EXISTS idx_alerts_unread
+    ON alerts (created_at DESC) WHERE NOT is_read AND NOT is_dismissed;
+
+-- Support
+
+DO $$
+BEGIN
+    CREATE TYPE error_severity AS ENUM ('low', 'medium', 'high', 'critical');
+EXCEPTION WHEN duplicate_object THEN NULL;
+END $$;
+
+DO $$
+BEGIN
+    CREATE TYPE error_source AS ENUM ('frontend', 'backend', 'integration', 'database', 'unknown');
+EXCEPTION WHEN duplicate_object THEN NULL;
+END $$;
+
+DO $$
+BEGIN
+    CREATE TYPE error_status AS ENUM ('new', 'investigating', 'resolved', 'dismissed');
+EXCEPTION WHEN duplicate_object THEN NULL;
+END $$;
+
+CREATE TABLE IF NOT EXISTS knowledge_base_entries (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    title TEXT NOT NULL,
+    problem TEXT NOT NULL,
+    cause TEXT NOT NULL,
+    solution TEXT NOT NULL,
+    related_errors JSONB NOT NULL DEFAULT '[]',
+    tags JSONB NOT NULL DEFAULT '[]',
+    view_count INTEGER NOT NULL DEFAULT 0,
+    helpful_count INTEGER NOT NULL DEFAULT 0,
+    not_helpful_count INTEGER NOT NULL DEFAULT 0,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE IF NOT EXISTS error_logs (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    message TEXT NOT NULL,
+    stack_trace TEXT,
+    severity error_severity NOT NULL DEFAULT 'medium',
+    source error_source NOT NULL DEFAULT 'unknown',
+    status error_status NOT NULL DEFAULT 'new',
+    user_id UUID,
+    session_id VARCHAR(255),
+    page_url TEXT,
+    action TEXT,
+    browser_info TEXT,
+    device_info TEXT,
+    context JSONB NOT NULL DEFAULT '{}',
+    occurrence_count INTEGER NOT NULL DEFAULT 1,
+    first_seen_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_seen_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    resolution_notes TEXT,
+    kb_entry_id UUID REFERENCES knowledge_base_entries (id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_error_logs_status ON error_logs (status);
+CREATE INDEX IF NOT EXISTS idx_error_logs_source ON error_logs (source);
+CREATE INDEX IF NOT EXISTS idx_error_logs_last_seen_at ON error_logs (last_seen_at DESC);
+
+-- Integrations and settings
+
+CREATE TABLE IF NOT EXISTS ai_configs (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID UNIQUE,
+    enabled BOOLEAN NOT NULL DEFAULT FALSE,
+    provider VARCHAR(50) NOT NULL,
+    model_id VARCHAR(255) NOT NULL,
+    api_key_encrypted TEXT,
+    custom_base_url TEXT,
+    validated_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE IF NOT EXISTS integration_credentials (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    integration_type VARCHAR(50) NOT NULL UNIQUE,
+    access_token_encrypted TEXT,
+    refresh_token_encrypted TEXT,
+    expires_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE IF NOT EXISTS user_configs (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID UNIQUE,
+    postman_api_key TEXT,
+    testmo_url TEXT,
+    testmo_api_key TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Splunk
+
+CREATE TABLE IF NOT EXISTS splunk_query_templates (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    name VARCHAR(255) NOT NULL,
+    description TEXT,
+    query TEXT NOT NULL,
+    category VARCHAR(50) NOT NULL,
+    is_system BOOLEAN NOT NULL DEFAULT FALSE,
+    created_by UUID,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_splunk_query_templates_category
+    ON splunk_query_templates (category);
+
+CREATE TABLE IF NOT EXISTS splunk_query_history (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL,
+    query TEXT NOT NULL,
+    time_start TIMESTAMPTZ NOT NULL,
+    time_end TIMESTAMPTZ NOT NULL,
+    index_name VARCHAR(255),
+    execution_time_ms INTEGER,
+    result_count INTEGER,
+    template_id UUID REFERENCES splunk_query_templates (id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_splunk_query_history_user
+    ON splunk_query_history (user_id, created_at DESC);
@@ -0,0 +1,16 @@
+-- Newman runs of Postman collections, optionally attached to a workflow step.
+
+CREATE TABLE IF NOT EXISTS postman_collection_runs (
+    id UUID PRIMARY KEY,
+    collection_id VARCHAR(255) NOT NULL,
+    collection_name TEXT NOT NULL,
+    passed BOOLEAN NOT NULL,
+    report JSONB NOT NULL,
+    workflow_instance_id UUID REFERENCES workflow_instances (id) ON DELETE SET NULL,
+    step_index INTEGER,
+    started_at TIMESTAMPTZ,
+    finished_at TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS idx_postman_collection_runs_workflow
+    ON postman_collection_runs (workflow_instance_id, step_index);
@@ -0,0 +1,13 @@
+-- Postman collections cached for search.
+
+CREATE TABLE IF NOT EXISTS postman_collection_cache (
+    collection_id VARCHAR(255) PRIMARY KEY,
+    uid VARCHAR(255) NOT NULL,
+    name TEXT NOT NULL,
+    description TEXT,
+    requests JSONB NOT NULL DEFAULT '[]',
+    postman_updated_at TEXT,
+    refreshed_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_postman_collection_cache_name ON postman_collection_cache (name);
@@ -0,0 +1,13 @@
+-- Customizable report layouts. At most one is the default.
+
+CREATE TABLE IF NOT EXISTS report_templates (
+    id UUID PRIMARY KEY,
+    name VARCHAR(255) NOT NULL,
+    layout JSONB NOT NULL,
+    is_default BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_report_templates_default
+    ON report_templates (is_default) WHERE is_default;
@@ -0,0 +1,4 @@
+-- SHA-256 checksum and optional signature of each stored report.
+
+ALTER TABLE workflow_reports ADD COLUMN IF NOT EXISTS checksum TEXT;
+ALTER TABLE workflow_reports ADD COLUMN IF NOT EXISTS signature TEXT;
@@ -0,0 +1,24 @@
+-- Recent searches per user, and searches saved under a name.
+
+CREATE TABLE IF NOT EXISTS search_history (
+    id UUID PRIMARY KEY,
+    user_id VARCHAR(255) NOT NULL,
+    query JSONB NOT NULL,
+    result_count INTEGER NOT NULL DEFAULT 0,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_search_history_user
+    ON search_history (user_id, created_at DESC);
+
+CREATE TABLE IF NOT EXISTS saved_searches (
+    id UUID PRIMARY KEY,
+    user_id VARCHAR(255) NOT NULL,
+    name VARCHAR(255) NOT NULL,
+    query JSONB NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_run_at TIMESTAMPTZ
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_saved_searches_user_name
+    ON saved_searches (user_id, lower(name));
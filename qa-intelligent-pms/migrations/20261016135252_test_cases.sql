@@ -0,0 +1,23 @@
+-- Local test cases, optionally linked to a case in Testmo.
+
+CREATE TABLE IF NOT EXISTS test_cases (
+    id UUID PRIMARY KEY,
+    title TEXT NOT NULL,
+    preconditions TEXT,
+    steps JSONB NOT NULL DEFAULT '[]',
+    priority VARCHAR(50),
+    category VARCHAR(255),
+    tags TEXT[] NOT NULL DEFAULT '{}',
+    remote_system VARCHAR(50),
+    remote_project_id VARCHAR(255),
+    remote_id VARCHAR(255),
+    remote_updated_at TEXT,
+    synced_at TIMESTAMPTZ,
+    merged_from JSONB NOT NULL DEFAULT '[]',
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_test_cases_remote
+    ON test_cases (remote_system, remote_id) WHERE remote_id IS NOT NULL;
+CREATE INDEX IF NOT EXISTS idx_test_cases_created_at ON test_cases (created_at, id);
@@ -0,0 +1,11 @@
+-- Running mean and variance (Welford) of each template step's duration.
+
+CREATE TABLE IF NOT EXISTS workflow_step_baselines (
+    template_id UUID NOT NULL REFERENCES workflow_templates (id) ON DELETE CASCADE,
+    step_index INTEGER NOT NULL,
+    sample_count BIGINT NOT NULL DEFAULT 0,
+    mean_seconds DOUBLE PRECISION NOT NULL DEFAULT 0,
+    m2 DOUBLE PRECISION NOT NULL DEFAULT 0,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (template_id, step_index)
+);
@@ -0,0 +1,21 @@
+-- Testmo runs created from workflows, with their latest synced results.
+
+CREATE TABLE IF NOT EXISTS test_run_results (
+    run_id BIGINT PRIMARY KEY,
+    workflow_id UUID REFERENCES workflow_instances (id) ON DELETE SET NULL,
+    ticket_key VARCHAR(50) NOT NULL,
+    name TEXT NOT NULL,
+    passed BIGINT NOT NULL DEFAULT 0,
+    failed BIGINT NOT NULL DEFAULT 0,
+    blocked BIGINT NOT NULL DEFAULT 0,
+    skipped BIGINT NOT NULL DEFAULT 0,
+    retest BIGINT NOT NULL DEFAULT 0,
+    is_completed BOOLEAN NOT NULL DEFAULT FALSE,
+    synced_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_test_run_results_workflow ON test_run_results (workflow_id);
+CREATE INDEX IF NOT EXISTS idx_test_run_results_ticket ON test_run_results (ticket_key);
+CREATE INDEX IF NOT EXISTS idx_test_run_results_open
+    ON test_run_results (created_at) WHERE NOT is_completed;
@@ -0,0 +1,18 @@
+-- Template versions and archival. Each update snapshots the previous
+-- version; instances record the version they were started from.
+
+ALTER TABLE workflow_templates ADD COLUMN IF NOT EXISTS version INTEGER NOT NULL DEFAULT 1;
+ALTER TABLE workflow_templates ADD COLUMN IF NOT EXISTS archived_at TIMESTAMPTZ;
+
+ALTER TABLE workflow_instances ADD COLUMN IF NOT EXISTS template_version INTEGER NOT NULL DEFAULT 1;
+
+CREATE TABLE IF NOT EXISTS workflow_template_versions (
+    template_id UUID NOT NULL REFERENCES workflow_templates (id) ON DELETE CASCADE,
+    version INTEGER NOT NULL,
+    name VARCHAR(255) NOT NULL,
+    description TEXT,
+    ticket_type VARCHAR(50) NOT NULL,
+    steps_json JSONB NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (template_id, version)
+);
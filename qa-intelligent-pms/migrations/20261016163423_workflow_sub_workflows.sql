@@ -0,0 +1,9 @@
+-- Sub-workflows spawned from a step of a parent workflow.
+
+CREATE TABLE IF NOT EXISTS workflow_sub_workflows (
+    parent_instance_id UUID NOT NULL REFERENCES workflow_instances (id) ON DELETE CASCADE,
+    parent_step_index INTEGER NOT NULL,
+    child_instance_id UUID NOT NULL UNIQUE REFERENCES workflow_instances (id) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (parent_instance_id, child_instance_id)
+);
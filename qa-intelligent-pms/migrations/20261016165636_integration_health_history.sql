@@ -0,0 +1,15 @@
+-- Health check samples per integration, for uptime over time.
+
+CREATE TABLE IF NOT EXISTS integration_health_history (
+    id BIGSERIAL PRIMARY KEY,
+    integration VARCHAR(50) NOT NULL,
+    status VARCHAR(20) NOT NULL,
+    response_time_ms BIGINT,
+    error_message TEXT,
+    checked_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_integration_health_history_lookup
+    ON integration_health_history (integration, checked_at);
+CREATE INDEX IF NOT EXISTS idx_integration_health_history_checked_at
+    ON integration_health_history (checked_at);
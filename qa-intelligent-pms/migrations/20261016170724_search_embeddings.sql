@@ -0,0 +1,18 @@
+-- Embeddings of searchable items for semantic search (pgvector).
+
+CREATE EXTENSION IF NOT EXISTS vector;
+
+CREATE TABLE IF NOT EXISTS search_embeddings (
+    source VARCHAR(50) NOT NULL,
+    item_id VARCHAR(255) NOT NULL,
+    model VARCHAR(255) NOT NULL,
+    content_hash VARCHAR(64) NOT NULL,
+    title TEXT NOT NULL,
+    description TEXT,
+    url TEXT NOT NULL,
+    embedding vector NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (source, item_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_search_embeddings_model ON search_embeddings (model, source);
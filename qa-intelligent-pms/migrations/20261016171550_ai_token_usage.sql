@@ -0,0 +1,15 @@
+-- AI tokens used per user, provider, feature and day.
+
+CREATE TABLE IF NOT EXISTS ai_token_usage (
+    user_id VARCHAR(255) NOT NULL,
+    provider VARCHAR(50) NOT NULL,
+    feature VARCHAR(50) NOT NULL,
+    usage_date DATE NOT NULL,
+    prompt_tokens BIGINT NOT NULL DEFAULT 0,
+    completion_tokens BIGINT NOT NULL DEFAULT 0,
+    total_tokens BIGINT NOT NULL DEFAULT 0,
+    requests BIGINT NOT NULL DEFAULT 0,
+    UNIQUE (user_id, provider, feature, usage_date)
+);
+
+CREATE INDEX IF NOT EXISTS idx_ai_token_usage_user_date ON ai_token_usage (user_id, usage_date);
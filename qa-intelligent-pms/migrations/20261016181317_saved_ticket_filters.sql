@@ -0,0 +1,18 @@
+-- Ticket filters saved per user. At most one per user is the default.
+
+CREATE TABLE IF NOT EXISTS saved_ticket_filters (
+    id UUID PRIMARY KEY,
+    owner VARCHAR(255) NOT NULL,
+    name VARCHAR(255) NOT NULL,
+    statuses TEXT[] NOT NULL DEFAULT '{}',
+    project VARCHAR(50),
+    assignee VARCHAR(255),
+    jql TEXT,
+    is_default BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (owner, name)
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_saved_ticket_filters_default
+    ON saved_ticket_filters (owner) WHERE is_default;
@@ -0,0 +1,14 @@
+-- Tickets users watch, with the last state seen by the poller.
+
+CREATE TABLE IF NOT EXISTS ticket_watches (
+    id UUID PRIMARY KEY,
+    owner VARCHAR(255) NOT NULL,
+    ticket_key VARCHAR(50) NOT NULL,
+    last_status TEXT,
+    last_updated TEXT,
+    last_comment_count INTEGER,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (owner, ticket_key)
+);
+
+CREATE INDEX IF NOT EXISTS idx_ticket_watches_ticket ON ticket_watches (ticket_key);
@@ -0,0 +1,3 @@
+-- Whether step time is tracked automatically as the workflow advances.
+
+ALTER TABLE workflow_instances ADD COLUMN IF NOT EXISTS auto_track BOOLEAN NOT NULL DEFAULT FALSE;
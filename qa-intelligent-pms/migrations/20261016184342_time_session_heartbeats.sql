@@ -0,0 +1,7 @@
+-- Heartbeats of active sessions, and why a session was paused.
+
+ALTER TABLE time_sessions ADD COLUMN IF NOT EXISTS last_heartbeat_at TIMESTAMPTZ;
+ALTER TABLE time_sessions ADD COLUMN IF NOT EXISTS pause_reason VARCHAR(20);
+
+CREATE INDEX IF NOT EXISTS idx_time_sessions_active
+    ON time_sessions (workflow_instance_id) WHERE is_active;
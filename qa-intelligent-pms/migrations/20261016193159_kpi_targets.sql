@@ -0,0 +1,14 @@
+-- Dashboard KPI targets per team.
+
+CREATE TABLE IF NOT EXISTS kpi_targets (
+    id UUID PRIMARY KEY,
+    team VARCHAR(255) NOT NULL,
+    kpi VARCHAR(50) NOT NULL,
+    comparison VARCHAR(20) NOT NULL,
+    target_value DOUBLE PRECISION NOT NULL,
+    breach_days INTEGER NOT NULL,
+    last_alerted_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (team, kpi)
+);
@@ -0,0 +1,17 @@
+-- Scheduled PM dashboard exports and where they were delivered.
+
+CREATE TABLE IF NOT EXISTS pm_dashboard_exports (
+    id UUID PRIMARY KEY,
+    format VARCHAR(10) NOT NULL,
+    period VARCHAR(50) NOT NULL,
+    period_from DATE NOT NULL,
+    period_to DATE NOT NULL,
+    filename TEXT NOT NULL,
+    size_bytes BIGINT NOT NULL,
+    delivered_to TEXT[] NOT NULL DEFAULT '{}',
+    error TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_pm_dashboard_exports_created_at
+    ON pm_dashboard_exports (created_at DESC);
@@ -0,0 +1,23 @@
+-- Startup validation runs and the result of each integration check.
+
+CREATE TABLE IF NOT EXISTS startup_validation_runs (
+    id UUID PRIMARY KEY,
+    trigger VARCHAR(20) NOT NULL,
+    valid BOOLEAN NOT NULL,
+    has_critical_failure BOOLEAN NOT NULL,
+    total_time_ms BIGINT NOT NULL,
+    ran_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_startup_validation_runs_ran_at
+    ON startup_validation_runs (ran_at DESC, id DESC);
+
+CREATE TABLE IF NOT EXISTS startup_validation_results (
+    run_id UUID NOT NULL REFERENCES startup_validation_runs (id) ON DELETE CASCADE,
+    integration VARCHAR(50) NOT NULL,
+    success BOOLEAN NOT NULL,
+    is_critical BOOLEAN NOT NULL,
+    response_time_ms BIGINT,
+    error_message TEXT,
+    PRIMARY KEY (run_id, integration)
+);
@@ -0,0 +1,10 @@
+-- Archival and soft delete of finished workflows.
+
+ALTER TABLE workflow_instances ADD COLUMN IF NOT EXISTS archived_at TIMESTAMPTZ;
+ALTER TABLE workflow_instances ADD COLUMN IF NOT EXISTS deleted_at TIMESTAMPTZ;
+
+CREATE INDEX IF NOT EXISTS idx_workflow_instances_visible
+    ON workflow_instances (user_id, created_at DESC)
+    WHERE archived_at IS NULL AND deleted_at IS NULL;
+CREATE INDEX IF NOT EXISTS idx_workflow_instances_deleted_at
+    ON workflow_instances (deleted_at) WHERE deleted_at IS NOT NULL;
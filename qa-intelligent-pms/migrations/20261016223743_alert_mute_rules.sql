@@ -0,0 +1,14 @@
+-- Rules that mute alerts of a type, optionally for one component, until a time.
+
+CREATE TABLE IF NOT EXISTS alert_mute_rules (
+    id UUID PRIMARY KEY,
+    tenant_id VARCHAR(64) NOT NULL,
+    alert_type VARCHAR(50) NOT NULL,
+    component VARCHAR(255),
+    until TIMESTAMPTZ,
+    reason TEXT,
+    created_by VARCHAR(255),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_alert_mute_rules_tenant ON alert_mute_rules (tenant_id, created_at DESC);
@@ -0,0 +1,13 @@
+-- Pattern detection thresholds per team.
+
+CREATE TABLE IF NOT EXISTS pattern_thresholds (
+    team VARCHAR(255) PRIMARY KEY,
+    time_excess_percent DOUBLE PRECISION NOT NULL,
+    consecutive_count INTEGER NOT NULL,
+    consecutive_window INTEGER NOT NULL,
+    spike_window INTEGER NOT NULL,
+    spike_multiplier DOUBLE PRECISION NOT NULL,
+    flaky_round_trips INTEGER NOT NULL,
+    flaky_window_days INTEGER NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,15 @@
+-- Jira status transitions seen for each ticket.
+
+CREATE TABLE IF NOT EXISTS ticket_status_changes (
+    id UUID PRIMARY KEY,
+    ticket_key VARCHAR(50) NOT NULL,
+    from_status TEXT,
+    to_status TEXT NOT NULL,
+    component VARCHAR(255),
+    changed_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_ticket_status_changes_ticket
+    ON ticket_status_changes (ticket_key, changed_at DESC);
+CREATE INDEX IF NOT EXISTS idx_ticket_status_changes_changed_at
+    ON ticket_status_changes (changed_at);
@@ -0,0 +1,7 @@
+-- Full-text search document of each knowledge base entry. The server fills
+-- it on write and backfills entries where it is still NULL.
+
+ALTER TABLE knowledge_base_entries ADD COLUMN IF NOT EXISTS search_vector TSVECTOR;
+
+CREATE INDEX IF NOT EXISTS idx_knowledge_base_entries_search
+    ON knowledge_base_entries USING GIN (search_vector);